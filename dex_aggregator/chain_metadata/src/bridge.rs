@@ -21,15 +21,20 @@ use xcm::latest::{Junction, Junctions, MultiLocation, NetworkId};
 
 use crate::chain_info::{AddressType, ChainInfo};
 use crate::common::{
-    Amount, PublicError, Result, UniversalAddress, UniversalChainId, UniversalTokenId,
+    Amount, EthAddress, PublicError, Result, UniversalAddress, UniversalChainId, UniversalTokenId,
 };
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum Bridge {
     Xcm(XCMBridge),
+    Wormhole(WormholeBridge),
 }
 
+// The destination weight limit (refTime) we historically hard-coded for every lane. It works
+// for the current lanes but is far more than a simple reserve transfer needs
+pub const DEFAULT_XCM_DEST_WEIGHT_LIMIT: u64 = 10_000_000_000;
+
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub struct XCMBridge {
@@ -40,6 +45,39 @@ pub struct XCMBridge {
     // Generates the MultiLocation for the destination wallet address
     pub dest_multilocation_template: WalletMultiLocationTemplate,
     pub estimated_bridge_fee_in_dest_chain_native_token: Amount,
+    // Upper bound on the weight bought on the destination chain. The executor may lower it
+    // based on the weight that recent transfers on this lane actually consumed
+    pub dest_weight_limit: u64,
+}
+
+// Wormhole's own chain numbering (https://docs.wormhole.com/wormhole/reference/constants),
+// which is distinct from both the EVM chain ID and the parachain ID
+pub type WormholeChainId = u16;
+
+// The token bridge normalizes every transfer amount to (at most) this many decimals, so
+// anything finer is lost in transit
+pub const WORMHOLE_MAX_DECIMALS: u8 = 8;
+
+// A Wormhole token bridge lane between two EVM chains. Transfers go through three phases:
+// 1. deposit (transferTokens) on the source chain's token bridge, which emits a message,
+// 2. wait for the guardians to sign the message (the VAA), and
+// 3. claim (completeTransfer) on the destination chain's token bridge with the signed VAA.
+// We claim the transfer ourselves instead of paying a relayer, so the bridge fee is
+// the destination chain gas fee for the claim transaction
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WormholeBridge {
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub src_token_bridge_addr: EthAddress,
+    pub dest_token_bridge_addr: EthAddress,
+    pub src_wormhole_chain_id: WormholeChainId,
+    pub dest_wormhole_chain_id: WormholeChainId,
+    // The lanes carry tokens whose remote chain usually has no DEX in our graph (so we
+    // cannot price its native token). Hence fees are estimated in units of the bridged token
+    pub estimated_deposit_fee_in_token: Amount,
+    pub estimated_claim_fee_in_token: Amount,
+    pub estimated_claim_fee_in_dest_chain_native_token: Amount,
 }
 
 trait DestMultiLocationGenerator<T> {
@@ -163,6 +201,13 @@ pub(crate) const fn get_dest_multilocation_template(
     src_chain_info: &ChainInfo,
     dest_chain_info: &ChainInfo,
 ) -> WalletMultiLocationTemplate {
+    // The XCM bridge registry is a static, so this check happens at compile time
+    if !src_chain_info
+        .chain_id
+        .shares_relay_with(&dest_chain_info.chain_id)
+    {
+        panic!("Hard fail. XCM bridges must stay within a single relay chain")
+    }
     let address_tail_junction_template = match dest_chain_info.xcm_address_type {
         AddressType::Ethereum => {
             let zero_addr: [u8; 20] = [0; 20];
@@ -209,6 +254,9 @@ pub(crate) const fn get_dest_multilocation_template(
         (UniversalChainId::SubstrateRelayChain(_), UniversalChainId::SubstrateRelayChain(_)) => {
             panic!("Hard fail. We should not be bridging across two relay chains")
         }
+        (UniversalChainId::EVM(_), _) | (_, UniversalChainId::EVM(_)) => {
+            panic!("Hard fail. Standalone EVM chains do not support XCM")
+        }
     };
     WalletMultiLocationTemplate {
         template: raw_multilocation,
@@ -19,13 +19,19 @@
 use scale::{Decode, Encode};
 use ss58_registry::Ss58AddressFormat;
 
-use privadex_common::signature_scheme::SignatureScheme;
+use privadex_common::{
+    signature_scheme::SignatureScheme, utils::ss58_utils::h160_to_mapped_account,
+};
 
-use crate::common::{Amount, EthAddress, UniversalChainId};
+use crate::common::{
+    Amount, BlockNum, EthAddress, EvmChainId, MillisSinceEpoch, SubstratePublicKey,
+    UniversalAddress, UniversalChainId,
+};
+use crate::xcm_location::XcmVersion;
 
 // From what I have seen,
 // AddressType.Ethereum corresponds to SignatureScheme.Ethereum (e.g. Moonbeam) and
-// AddressType.SS58 corresponds to SignatureScheme.Sr25519 (e.g. Polkadot, Astar)
+// AddressType.SS58 corresponds to SignatureScheme.Sr25519 or Ed25519 (e.g. Polkadot, Astar)
 // but I don't enforce that link
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -34,6 +40,84 @@ pub enum AddressType {
     SS58,
 }
 
+// How we look up past extrinsics and events on a chain
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum IndexerKind {
+    Subsquid(&'static str /* graphql archive url */),
+    // e.g. standalone EVM chains, where we only ever look at EVM txn receipts
+    Unavailable,
+}
+
+// The extrinsic (or EVM txn) used to send an outgoing XCM transfer from a chain
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmTransferMethod {
+    EvmPrecompile,
+    XTokens(u8 /* pallet index */),
+    XcmPallet(u8 /* pallet index */),
+}
+
+// Where a chain maps its XC20 asset IDs to MultiLocations
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XC20RegistryKind {
+    MoonbeamAssetManager, // assetManager.assetIdType
+    AstarXcAssetConfig,   // xcAssetConfig.assetIdToLocation
+}
+
+// Runtimes with pallet-asset-tx-payment sign ChargeAssetTxPayment (the tip plus an optional
+// pallet-assets id to pay the fee in) instead of ChargeTransactionPayment. The id's width is
+// part of the extrinsic encoding, and it differs between runtimes
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AssetTxPaymentKind {
+    U32AssetIds,  // e.g. Asset Hub
+    U128AssetIds, // e.g. Astar's pallet-assets
+}
+
+// Factory that deploys a minimal forwarder contract per ExecutionPlan at a CREATE2 address.
+// Users deposit into that address so every deposit maps to exactly one plan, and
+// factory.sweep(salt, token, to) deploys the forwarder (if needed) and drains it into the escrow
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DepositForwarderFactory {
+    pub addr: EthAddress,
+    pub forwarder_init_code_hash: [u8; 32],
+}
+
+// Inclusive range of EVM addresses, e.g. a block of precompiles
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthAddressRange {
+    pub first: EthAddress,
+    pub last: EthAddress,
+}
+
+impl EthAddressRange {
+    // Precompiles mostly sit at low addresses, e.g. 0x00..01 through 0x00..0a on Ethereum
+    pub const fn low(first: u32, last: u32) -> Self {
+        Self {
+            first: low_eth_address(first),
+            last: low_eth_address(last),
+        }
+    }
+
+    pub fn contains(&self, addr: &EthAddress) -> bool {
+        self.first <= *addr && *addr <= self.last
+    }
+}
+
+const fn low_eth_address(n: u32) -> EthAddress {
+    let n_bytes = n.to_be_bytes();
+    let mut addr = [0u8; 20];
+    addr[16] = n_bytes[0];
+    addr[17] = n_bytes[1];
+    addr[18] = n_bytes[2];
+    addr[19] = n_bytes[3];
+    EthAddress { 0: addr }
+}
+
 // Not deriving Encode or Decode because
 // "the trait `WrapperTypeDecode` is not implemented for `&'static str"
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -49,15 +133,56 @@ pub struct ChainInfo {
     pub sig_scheme: SignatureScheme,
 
     // Used in sending EVM txns, can look up at chainlist.org
-    pub evm_chain_id: Option<u64>,
+    pub evm_chain_id: Option<EvmChainId>,
     pub weth_addr: Option<EthAddress>,
+    // Precompiled contracts (including XC20s, which are precompiles too). Tokens sent to them
+    // can never be recovered, so we refuse them as destinations
+    pub evm_precompile_ranges: &'static [EthAddressRange],
+    // None means deposits go straight to the shared escrow address
+    pub deposit_forwarder_factory: Option<DepositForwarderFactory>,
     // I look at swap txns for reference
     pub avg_gas_fee_in_native_token: Amount, // hard-coded estimate
     // Cost of bridging TO this chain
     pub avg_bridge_fee_in_native_token: Amount, // hard-coded estimate
+    // Used to pace how often workers poll a step on this chain for confirmation
+    pub avg_block_time_millis: MillisSinceEpoch, // hard-coded estimate
+    // How many blocks deep (counting its own block) a user's prestart deposit must be before
+    // the escrow acts on it. Guards against deposits seen in a block that later gets reorged out
+    pub prestart_confirmation_depth: BlockNum,
+
+    // Capability flags. Execution dispatches on these rather than on chain IDs, so adding
+    // a chain should only require a new ChainInfo
+    // Outgoing XCM transfers are EVM txns to an XCM precompile (e.g. Astar)
+    pub has_xcm_precompile: bool,
+    // Outgoing XCM transfers are xTokens.transferMultiasset extrinsics (e.g. Moonbeam)
+    pub xtokens_pallet_index: Option<u8>,
+    // Outgoing XCM transfers are xcmPallet.limitedReserveTransferAssets extrinsics (relay chains)
+    pub xcm_pallet_index: Option<u8>,
+    // utility.batch_all merges several calls into one extrinsic, None if the chain has no
+    // utility pallet (see extrinsic_call_factory::utility_batch_all)
+    pub utility_pallet_index: Option<u8>,
+    // proxy.proxy lets the escrow's key sign for another account (see
+    // extrinsic_call_factory::proxy_proxy), None if the chain has no proxy pallet or its accounts
+    // are not 32-byte public keys (e.g. Moonbeam's AccountId20)
+    pub proxy_pallet_index: Option<u8>,
+    // EVM accounts are backed by a hashed Substrate account (Frontier's HashedAddressMapping,
+    // e.g. Astar) instead of accounts being 20-byte keys throughout (e.g. Moonbeam)
+    pub has_hashed_evm_account_mapping: bool,
+    // Users can deposit the native token with a utility.batch_all of a balances transfer to the
+    // escrow's EVM backing account and a system.remarkWithEvent tagging the plan, which we
+    // match via the indexer (see get_deposit_instructions). For wallets and exchanges that
+    // can't send EVM txns
+    pub accepts_remark_deposits: bool,
+    // None means extrinsic fees can only be paid in the native token
+    pub asset_tx_payment: Option<AssetTxPaymentKind>,
+    pub supports_eip1559: bool,
+    // Highest XCM version the chain accepts (polkadotXcm.safeXcmVersion), None if no XCM
+    pub max_xcm_version: Option<XcmVersion>,
+    // Used to discover XC20s at runtime (see xc20_registry_overlay)
+    pub xc20_registry: Option<XC20RegistryKind>,
 
     pub rpc_url: &'static str,
-    pub subsquid_graphql_archive_url: &'static str,
+    pub indexer: IndexerKind,
 }
 
 impl ChainInfo {
@@ -66,4 +191,53 @@ impl ChainInfo {
     pub fn get_ss58_prefix(&self) -> Option<Ss58AddressFormat> {
         Some(Ss58AddressFormat::custom(self.ss58_prefix_raw?))
     }
+
+    // The precompile takes priority because it lets the escrow send from its EVM account
+    pub fn get_xcm_transfer_method(&self) -> Option<XcmTransferMethod> {
+        if self.has_xcm_precompile {
+            Some(XcmTransferMethod::EvmPrecompile)
+        } else if let Some(pallet_index) = self.xtokens_pallet_index {
+            Some(XcmTransferMethod::XTokens(pallet_index))
+        } else if let Some(pallet_index) = self.xcm_pallet_index {
+            Some(XcmTransferMethod::XcmPallet(pallet_index))
+        } else {
+            None
+        }
+    }
+
+    // Address type of the account that signs outgoing XCM transfers. This differs from
+    // xcm_address_type when we go through an EVM precompile on an SS58 chain
+    pub fn get_xcm_sender_address_type(&self) -> AddressType {
+        if self.has_xcm_precompile {
+            AddressType::Ethereum
+        } else {
+            self.xcm_address_type
+        }
+    }
+
+    // The Substrate account that holds the native balance of an EVM address, i.e. where a
+    // balances transfer must go for the EVM address to be credited
+    pub fn get_evm_backing_account(&self, eth_addr: &EthAddress) -> Option<UniversalAddress> {
+        self.evm_chain_id?;
+        if self.has_hashed_evm_account_mapping {
+            Some(UniversalAddress::Substrate(SubstratePublicKey {
+                0: h160_to_mapped_account(&eth_addr.0),
+            }))
+        } else {
+            Some(UniversalAddress::Ethereum(eth_addr.clone()))
+        }
+    }
+
+    pub fn is_evm_precompile(&self, addr: &EthAddress) -> bool {
+        self.evm_precompile_ranges
+            .iter()
+            .any(|range| range.contains(addr))
+    }
+
+    pub fn get_subsquid_graphql_archive_url(&self) -> Option<&'static str> {
+        match self.indexer {
+            IndexerKind::Subsquid(url) => Some(url),
+            IndexerKind::Unavailable => None,
+        }
+    }
 }
@@ -18,12 +18,12 @@
 
 use core::{fmt, hash::Hash};
 use hex_literal::hex;
-use ink_prelude::vec::Vec;
+use ink::prelude::vec::Vec;
 use scale::{Decode, Encode};
 
 use privadex_common::utils::general_utils::slice_to_hex_string;
 
-use crate::registry::{chain::RelayChain, dex::DexId};
+use crate::registry::{aggregator::AggregatorId, chain::RelayChain, dex::DexId};
 
 // We should allow only checked arithmetic. Can later wrap u128 into a struct
 // that exposes just checked_* operations
@@ -35,12 +35,14 @@ pub type Nonce = u32;
 // PinkEnvironment defines a type 'Timestamp' so we call this MillisSinceEpoch to avoid name clashing
 pub type MillisSinceEpoch = u64;
 pub type ParachainId = u32;
+pub type EvmChainId = u64;
 pub type SecretKey = [u8; 32];
 
 pub use pink_web3::types::Address as EthAddress;
 pub use pink_web3::types::H256 as EthTxnHash;
 pub use pink_web3::types::H256 as SubstrateExtrinsicHash;
 pub use pink_web3::types::H256 as BlockHash;
+pub use pink_web3::types::H256 as XcmMessageHash;
 pub use pink_web3::types::H256 as SubstratePublicKey;
 pub use pink_web3::types::H256 as SecretKeyContainer;
 // Currently we hard-code 18 as the # decimals for every native token. Can
@@ -56,6 +58,9 @@ pub enum PublicError {
     InvalidMultiLocationLength,
     NoSovereignAccount,
     FailedToParseKey,
+    NoTokenMultiLocation,
+    UnsupportedXcmChain,
+    UnsupportedXcmVersion,
 }
 pub(crate) type Result<T> = core::result::Result<T, PublicError>;
 
@@ -76,26 +81,38 @@ pub enum UniversalChainId {
     // polkadot.js.org/apps -> ChainState -> Storage -> parachainInfo.parachainId
     SubstrateParachain(RelayChain, ParachainId),
     // SubstrateStandalone(StandaloneChain),
-    // EVM(ChainId),
+    // Pure EVM L1/L2 (e.g. Ethereum, Arbitrum) keyed by its EVM chain ID. These have no relay
+    // and no Substrate RPC, so XCM and extrinsic-based logic does not apply to them
+    EVM(EvmChainId),
 }
 
 impl fmt::Display for UniversalChainId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Parachain IDs are only unique within a relay (e.g. Moonbeam and Khala are both 2004)
+        // so we always include the relay
         match self {
-            Self::SubstrateRelayChain(_) => write!(f, "Relay"),
-            Self::SubstrateParachain(_, parachain) => write!(f, "Para_{}", parachain),
+            Self::SubstrateRelayChain(relay) => write!(f, "{:?}_Relay", relay),
+            Self::SubstrateParachain(relay, parachain) => {
+                write!(f, "{:?}_Para_{}", relay, parachain)
+            }
+            Self::EVM(evm_chain_id) => write!(f, "EVM_{}", evm_chain_id),
         }
     }
 }
 
 impl UniversalChainId {
-    pub const fn get_relay(&self) -> RelayChain {
+    pub const fn get_relay(&self) -> Option<RelayChain> {
         match self {
-            Self::SubstrateRelayChain(relay) => *relay,
-            Self::SubstrateParachain(relay, _) => *relay,
+            Self::SubstrateRelayChain(relay) => Some(*relay),
+            Self::SubstrateParachain(relay, _) => Some(*relay),
+            Self::EVM(_) => None,
         }
     }
 
+    pub const fn is_substrate(&self) -> bool {
+        !matches!(self, Self::EVM(_))
+    }
+
     pub const fn get_parachain_id(&self) -> Option<ParachainId> {
         if let UniversalChainId::SubstrateParachain(_, parachain_id) = self {
             Some(*parachain_id)
@@ -104,6 +121,15 @@ impl UniversalChainId {
         }
     }
 
+    // Two chains can only exchange XCM messages (and have sovereign accounts on each other)
+    // if they are secured by the same relay chain
+    pub const fn shares_relay_with(&self, other: &Self) -> bool {
+        match (self.get_relay(), other.get_relay()) {
+            (Some(relay), Some(other_relay)) => relay as u8 == other_relay as u8,
+            _ => false,
+        }
+    }
+
     pub const fn get_parachain_id_unsafe(&self) -> ParachainId {
         if let UniversalChainId::SubstrateParachain(_, parachain_id) = self {
             *parachain_id
@@ -202,10 +228,23 @@ pub struct Dex {
     // DEX fee in basis points e.g. bps = 100 -> 1%. Will need to see if it
     // applies to non constant-product AMM
     pub fee_bps: u16,
+    // For V2 forks that run several pools per pair, each with its own fee. The subgraph then
+    // has each pair's fee (in bps) as `fee`. fee_bps must be the router's tier, since we only
+    // quote the pools the router swaps through. Empty means every pool charges fee_bps
+    pub fee_tiers_bps: &'static [u16],
     pub graphql_url: &'static str,
     // We enforce that there is an eth_dex_router for now. If this changes later, we
     // will refactor to an Option or 'subclass' this
     pub eth_dex_router: EthAddress,
+    // What the router's factory() returns. New plans check it on-chain before swapping
+    // through the router
+    pub eth_dex_factory: EthAddress,
+}
+
+impl Dex {
+    pub fn has_fee_tiers(&self) -> bool {
+        !self.fee_tiers_bps.is_empty()
+    }
 }
 
 impl Encode for Dex {
@@ -213,3 +252,25 @@ impl Encode for Dex {
         self.id.encode()
     }
 }
+
+// Another DEX aggregator's on-chain exchange, which we can route a swap through when its quote
+// beats our pools. Quotes and swap calldata come from its HTTP API
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Aggregator {
+    pub id: AggregatorId,
+    pub chain_id: UniversalChainId,
+    // e.g. https://open-api.openocean.finance/v4/moonbeam. The quote and swap endpoints are
+    // under it
+    pub api_url: &'static str,
+    // The only contract the API's swap calldata may call, and the spender of the src token
+    pub eth_exchange: EthAddress,
+    // What the API calls the chain's native token
+    pub native_token_addr: EthAddress,
+}
+
+impl Encode for Aggregator {
+    fn encode(&self) -> Vec<u8> {
+        self.id.encode()
+    }
+}
@@ -23,15 +23,18 @@ pub mod bridge;
 pub mod chain_info;
 pub mod common;
 pub mod registry;
+pub mod xcm_location;
 
 use chain_info::{AddressType, ChainInfo};
 use common::{
-    Dex, EthAddress, PublicError, Result, SubstratePublicKey, UniversalAddress, UniversalChainId,
+    Aggregator, Dex, EthAddress, EvmChainId, PublicError, Result, SubstratePublicKey,
+    UniversalAddress, UniversalChainId,
 };
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 use registry::{
+    aggregator::{aggregator_registry, AggregatorId},
     chain::{chain_info_registry, universal_chain_id_registry},
-    dex::dex_registry,
+    dex::{dex_registry, DexId},
 };
 use scale::Encode;
 
@@ -41,6 +44,12 @@ pub fn get_chain_id_from_network_name(network_name: &str) -> Option<UniversalCha
         "moonbeam" => Some(universal_chain_id_registry::MOONBEAM),
         "polkadot" => Some(universal_chain_id_registry::POLKADOT),
 
+        "khala" => Some(universal_chain_id_registry::KHALA),
+        "kusama" => Some(universal_chain_id_registry::KUSAMA),
+
+        "ethereum" => Some(universal_chain_id_registry::ETHEREUM),
+        "arbitrum" => Some(universal_chain_id_registry::ARBITRUM),
+
         "moonbase-alpha" => Some(universal_chain_id_registry::MOONBASE_ALPHA),
         "moonbase-beta" => Some(universal_chain_id_registry::MOONBASE_BETA),
         _ => None,
@@ -53,6 +62,12 @@ pub fn get_chain_info_from_chain_id(chain_id: &UniversalChainId) -> Option<&'sta
         &universal_chain_id_registry::MOONBEAM => Some(&chain_info_registry::MOONBEAM_INFO),
         &universal_chain_id_registry::POLKADOT => Some(&chain_info_registry::POLKADOT_INFO),
 
+        &universal_chain_id_registry::KHALA => Some(&chain_info_registry::KHALA_INFO),
+        &universal_chain_id_registry::KUSAMA => Some(&chain_info_registry::KUSAMA_INFO),
+
+        &universal_chain_id_registry::ETHEREUM => Some(&chain_info_registry::ETHEREUM_INFO),
+        &universal_chain_id_registry::ARBITRUM => Some(&chain_info_registry::ARBITRUM_INFO),
+
         &universal_chain_id_registry::MOONBASE_ALPHA => {
             Some(&chain_info_registry::MOONBASEALPHA_INFO)
         }
@@ -63,6 +78,20 @@ pub fn get_chain_info_from_chain_id(chain_id: &UniversalChainId) -> Option<&'sta
     }
 }
 
+// Token lists and wallets identify chains by their EIP-155 chain ID
+pub fn get_chain_id_from_evm_chain_id(evm_chain_id: EvmChainId) -> Option<UniversalChainId> {
+    [
+        &chain_info_registry::ASTAR_INFO,
+        &chain_info_registry::MOONBEAM_INFO,
+        &chain_info_registry::ETHEREUM_INFO,
+        &chain_info_registry::ARBITRUM_INFO,
+        &chain_info_registry::MOONBASEALPHA_INFO,
+    ]
+    .into_iter()
+    .find(|chain_info| chain_info.evm_chain_id == Some(evm_chain_id))
+    .map(|chain_info| chain_info.chain_id)
+}
+
 pub fn get_dexes_from_chain_id(chain_id: &UniversalChainId) -> Vec<&'static Dex> {
     match chain_id {
         &universal_chain_id_registry::ASTAR => vec![&dex_registry::ARTHSWAP],
@@ -71,12 +100,40 @@ pub fn get_dexes_from_chain_id(chain_id: &UniversalChainId) -> Vec<&'static Dex>
         }
         &universal_chain_id_registry::POLKADOT => vec![],
 
+        &universal_chain_id_registry::KHALA => vec![],
+        &universal_chain_id_registry::KUSAMA => vec![],
+
+        &universal_chain_id_registry::ETHEREUM => vec![],
+        &universal_chain_id_registry::ARBITRUM => vec![],
+
         &universal_chain_id_registry::MOONBASE_ALPHA => vec![&dex_registry::MOONBASE_UNISWAP],
         &universal_chain_id_registry::MOONBASE_BETA => vec![],
         _ => vec![],
     }
 }
 
+pub fn get_dex_from_dex_id(dex_id: &DexId) -> &'static Dex {
+    match dex_id {
+        DexId::Arthswap => &dex_registry::ARTHSWAP,
+        DexId::Beamswap => &dex_registry::BEAMSWAP,
+        DexId::Stellaswap => &dex_registry::STELLASWAP,
+        DexId::MoonbaseUniswap => &dex_registry::MOONBASE_UNISWAP,
+    }
+}
+
+pub fn get_aggregators_from_chain_id(chain_id: &UniversalChainId) -> Vec<&'static Aggregator> {
+    match chain_id {
+        &universal_chain_id_registry::MOONBEAM => vec![&aggregator_registry::OPENOCEAN_MOONBEAM],
+        _ => vec![],
+    }
+}
+
+pub fn get_aggregator_from_aggregator_id(aggregator_id: &AggregatorId) -> &'static Aggregator {
+    match aggregator_id {
+        AggregatorId::OpenOceanMoonbeam => &aggregator_registry::OPENOCEAN_MOONBEAM,
+    }
+}
+
 // Defined in https://docs.moonbeam.network/builders/xcm/overview/#general-xcm-definitions
 // ^This specifies that a blake2 hash is involved, but it actually isn't
 // Logic based on https://github.com/albertov19/xcmTools/blob/main/calculateSovereignAddress.ts
@@ -87,7 +144,11 @@ pub fn get_sovereign_account(
     account_chain: UniversalChainId,
     dest_chain_info: &ChainInfo,
 ) -> Result<UniversalAddress> {
-    if account_chain.get_relay() != dest_chain_info.chain_id.get_relay() {
+    // Sovereign accounts only exist within a single relay's consensus system, and a chain
+    // has no sovereign account on itself
+    if !account_chain.shares_relay_with(&dest_chain_info.chain_id)
+        || account_chain == dest_chain_info.chain_id
+    {
         return Err(PublicError::NoSovereignAccount);
     }
     let para_id = account_chain
@@ -176,4 +237,199 @@ mod tests {
         });
         assert_eq!(addr, expected);
     }
+
+    #[test]
+    fn test_sovereign_account_khala_on_kusama() {
+        // Same parachain ID as Moonbeam on Polkadot, so the sovereign account bytes are identical
+        let addr = get_sovereign_account(
+            universal_chain_id_registry::KHALA,
+            &chain_info_registry::KUSAMA_INFO,
+        )
+        .expect("Should output a valid address");
+        let expected = UniversalAddress::Substrate(SubstratePublicKey {
+            0: hex!("70617261d4070000000000000000000000000000000000000000000000000000"),
+        });
+        assert_eq!(addr, expected);
+    }
+
+    #[test]
+    fn test_sovereign_account_cross_relay_rejected() {
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::KHALA,
+                &chain_info_registry::POLKADOT_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::KHALA,
+                &chain_info_registry::MOONBEAM_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::MOONBEAM,
+                &chain_info_registry::KUSAMA_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::MOONBEAM,
+                &chain_info_registry::MOONBEAM_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+    }
+
+    #[test]
+    fn test_sovereign_account_evm_chain_rejected() {
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::ETHEREUM,
+                &chain_info_registry::MOONBEAM_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+        assert_eq!(
+            get_sovereign_account(
+                universal_chain_id_registry::MOONBEAM,
+                &chain_info_registry::ARBITRUM_INFO,
+            ),
+            Err(PublicError::NoSovereignAccount)
+        );
+        // Two EVM chains have no relay, so they do not share one
+        assert!(!universal_chain_id_registry::ETHEREUM
+            .shares_relay_with(&universal_chain_id_registry::ARBITRUM));
+    }
+
+    #[test]
+    fn test_chain_id_display_includes_relay() {
+        assert_ne!(
+            universal_chain_id_registry::KHALA.to_string(),
+            universal_chain_id_registry::MOONBEAM.to_string()
+        );
+        assert_eq!(
+            universal_chain_id_registry::MOONBEAM.to_string(),
+            "Polkadot_Para_2004"
+        );
+    }
+
+    #[test]
+    fn test_chain_id_from_evm_chain_id() {
+        assert_eq!(
+            get_chain_id_from_evm_chain_id(1284),
+            Some(universal_chain_id_registry::MOONBEAM)
+        );
+        assert_eq!(
+            get_chain_id_from_evm_chain_id(592),
+            Some(universal_chain_id_registry::ASTAR)
+        );
+        assert_eq!(
+            get_chain_id_from_evm_chain_id(1),
+            Some(universal_chain_id_registry::ETHEREUM)
+        );
+        assert_eq!(get_chain_id_from_evm_chain_id(56), None);
+    }
+
+    #[test]
+    fn test_chain_capability_flags_are_consistent() {
+        use chain_info::{AddressType, AssetTxPaymentKind, XcmTransferMethod};
+        use registry::chain::chain_info_registry;
+        for chain_info in [
+            &chain_info_registry::ASTAR_INFO,
+            &chain_info_registry::MOONBEAM_INFO,
+            &chain_info_registry::POLKADOT_INFO,
+            &chain_info_registry::KHALA_INFO,
+            &chain_info_registry::KUSAMA_INFO,
+            &chain_info_registry::ETHEREUM_INFO,
+            &chain_info_registry::ARBITRUM_INFO,
+            &chain_info_registry::MOONBASEALPHA_INFO,
+            &chain_info_registry::MOONBASEBETA_INFO,
+        ] {
+            // EVM-only capabilities need an EVM
+            if chain_info.has_xcm_precompile
+                || chain_info.has_hashed_evm_account_mapping
+                || chain_info.supports_eip1559
+            {
+                assert!(chain_info.evm_chain_id.is_some());
+            }
+            // Remark deposits credit the escrow's EVM account, and we find them via Subsquid
+            if chain_info.accepts_remark_deposits {
+                assert!(chain_info.evm_chain_id.is_some());
+                assert!(chain_info.get_subsquid_graphql_archive_url().is_some());
+            }
+            // Standalone EVM chains can't send XCM, have no Substrate indexer and never sign
+            // extrinsics
+            if !chain_info.chain_id.is_substrate() {
+                assert_eq!(chain_info.get_xcm_transfer_method(), None);
+                assert_eq!(chain_info.get_subsquid_graphql_archive_url(), None);
+                assert_eq!(chain_info.asset_tx_payment, None);
+            }
+        }
+        assert_eq!(
+            chain_info_registry::ASTAR_INFO.get_xcm_transfer_method(),
+            Some(XcmTransferMethod::EvmPrecompile)
+        );
+        assert_eq!(
+            chain_info_registry::ASTAR_INFO.get_xcm_sender_address_type(),
+            AddressType::Ethereum
+        );
+        assert_eq!(
+            chain_info_registry::MOONBEAM_INFO.get_xcm_transfer_method(),
+            Some(XcmTransferMethod::XTokens(0x6a))
+        );
+        assert_eq!(
+            chain_info_registry::POLKADOT_INFO.get_xcm_transfer_method(),
+            Some(XcmTransferMethod::XcmPallet(0x63))
+        );
+        // The signed extension is part of every extrinsic's encoding, so this must match the
+        // runtime
+        assert_eq!(
+            chain_info_registry::ASTAR_INFO.asset_tx_payment,
+            Some(AssetTxPaymentKind::U128AssetIds)
+        );
+        assert_eq!(chain_info_registry::POLKADOT_INFO.asset_tx_payment, None);
+    }
+
+    #[test]
+    fn test_wormhole_bridges_are_registered_and_symmetric() {
+        use registry::bridge::wormhole_bridge_registry::WORMHOLE_BRIDGES;
+        for bridge in WORMHOLE_BRIDGES.iter() {
+            // Wormhole lanes run between EVM accounts on both ends
+            for chain_id in [bridge.src_token.chain, bridge.dest_token.chain] {
+                let chain_info =
+                    get_chain_info_from_chain_id(&chain_id).expect("Chain must be registered");
+                assert!(chain_info.evm_chain_id.is_some());
+            }
+            assert!(WORMHOLE_BRIDGES
+                .iter()
+                .any(|other| other.src_token == bridge.dest_token
+                    && other.dest_token == bridge.src_token
+                    && other.src_token_bridge_addr == bridge.dest_token_bridge_addr));
+        }
+    }
+
+    #[test]
+    fn test_aggregators_are_registered() {
+        for chain_id in [
+            universal_chain_id_registry::ASTAR,
+            universal_chain_id_registry::MOONBEAM,
+            universal_chain_id_registry::MOONBASE_ALPHA,
+        ] {
+            for aggregator in get_aggregators_from_chain_id(&chain_id) {
+                // The swap is an EVM txn to the aggregator's exchange
+                assert_eq!(aggregator.chain_id, chain_id);
+                let chain_info =
+                    get_chain_info_from_chain_id(&chain_id).expect("Chain must be registered");
+                assert!(chain_info.evm_chain_id.is_some());
+                assert_eq!(
+                    get_aggregator_from_aggregator_id(&aggregator.id),
+                    aggregator
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,55 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use core::fmt;
+use scale::{Decode, Encode};
+
+// One per aggregator deployment, i.e. per chain (like DexId)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AggregatorId {
+    OpenOceanMoonbeam,
+}
+
+impl fmt::Display for AggregatorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OpenOceanMoonbeam => write!(f, "OpenOcean"),
+        }
+    }
+}
+
+pub mod aggregator_registry {
+    use hex_literal::hex;
+
+    use super::AggregatorId;
+    use crate::common::{Aggregator, EthAddress};
+    use crate::registry::chain::universal_chain_id_registry::MOONBEAM;
+
+    pub const OPENOCEAN_MOONBEAM: Aggregator = Aggregator {
+        id: AggregatorId::OpenOceanMoonbeam,
+        chain_id: MOONBEAM,
+        api_url: "https://open-api.openocean.finance/v4/moonbeam",
+        eth_exchange: EthAddress {
+            0: hex!("6352a56caadC4F1E25CD6c75970Fa768A3304e64"),
+        }, // OpenOceanExchange proxy
+        native_token_addr: EthAddress {
+            0: hex!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"),
+        },
+    };
+}
@@ -17,7 +17,9 @@
  */
 
 pub mod xcm_bridge_registry {
-    use crate::bridge::{get_dest_multilocation_template, XCMBridge};
+    use crate::bridge::{
+        get_dest_multilocation_template, XCMBridge, DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+    };
     use crate::registry::{
         chain::chain_info_registry, token::token_multilocation_spec_registry as token_spec_reg,
     };
@@ -40,6 +42,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::MOONBEAM_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::ASTR_MOONBEAM.token,
@@ -51,6 +54,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::ASTAR_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::GLMR_NATIVE.token,
@@ -62,6 +66,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::ASTAR_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::GLMR_ASTAR.token,
@@ -73,6 +78,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::MOONBEAM_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::DOT_NATIVE.token,
@@ -84,6 +90,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::ASTAR_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::DOT_ASTAR.token,
@@ -95,6 +102,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::POLKADOT_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::DOT_NATIVE.token,
@@ -106,6 +114,7 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::MOONBEAM_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         },
         XCMBridge {
             src_token: token_spec_reg::DOT_MOONBEAM.token,
@@ -117,6 +126,56 @@ pub mod xcm_bridge_registry {
             ),
             estimated_bridge_fee_in_dest_chain_native_token: chain_info_registry::POLKADOT_INFO
                 .avg_bridge_fee_in_native_token,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        },
+    ];
+}
+
+pub mod wormhole_bridge_registry {
+    use hex_literal::hex;
+
+    use crate::bridge::{WormholeBridge, WormholeChainId};
+    use crate::common::EthAddress;
+    use crate::registry::{chain::chain_info_registry, token::universal_token_id_registry};
+
+    pub const GUARDIAN_RPC_URL: &str = "https://wormhole-v2-mainnet-api.certus.one";
+
+    pub const ETHEREUM_WORMHOLE_CHAIN_ID: WormholeChainId = 2;
+    pub const MOONBEAM_WORMHOLE_CHAIN_ID: WormholeChainId = 16;
+
+    // https://docs.wormhole.com/wormhole/reference/constants#token-bridge
+    pub const ETHEREUM_TOKEN_BRIDGE: EthAddress = EthAddress {
+        0: hex!("3ee18B2214AFF97000D974cf647E7C347E8fa585"),
+    };
+    pub const MOONBEAM_TOKEN_BRIDGE: EthAddress = EthAddress {
+        0: hex!("B1731c586ca89a23809861c6103F0b96B3F57D92"),
+    };
+
+    // DO NOT REORDER the bridges below because unit tests depend on the ordering
+    pub static WORMHOLE_BRIDGES: [WormholeBridge; 2] = [
+        WormholeBridge {
+            src_token: universal_token_id_registry::USDC_ETHEREUM,
+            dest_token: universal_token_id_registry::USDC_WH_MOONBEAM,
+            src_token_bridge_addr: ETHEREUM_TOKEN_BRIDGE,
+            dest_token_bridge_addr: MOONBEAM_TOKEN_BRIDGE,
+            src_wormhole_chain_id: ETHEREUM_WORMHOLE_CHAIN_ID,
+            dest_wormhole_chain_id: MOONBEAM_WORMHOLE_CHAIN_ID,
+            estimated_deposit_fee_in_token: 10_000_000, // USDC (6 decimals) -> $10 on Ethereum
+            estimated_claim_fee_in_token: 50_000,       // ~$0.05 on Moonbeam
+            estimated_claim_fee_in_dest_chain_native_token: chain_info_registry::MOONBEAM_INFO
+                .avg_gas_fee_in_native_token,
+        },
+        WormholeBridge {
+            src_token: universal_token_id_registry::USDC_WH_MOONBEAM,
+            dest_token: universal_token_id_registry::USDC_ETHEREUM,
+            src_token_bridge_addr: MOONBEAM_TOKEN_BRIDGE,
+            dest_token_bridge_addr: ETHEREUM_TOKEN_BRIDGE,
+            src_wormhole_chain_id: MOONBEAM_WORMHOLE_CHAIN_ID,
+            dest_wormhole_chain_id: ETHEREUM_WORMHOLE_CHAIN_ID,
+            estimated_deposit_fee_in_token: 50_000, // USDC (6 decimals) -> $0.05 on Moonbeam
+            estimated_claim_fee_in_token: 15_000_000, // ~$15 on Ethereum (completeTransfer is gas-heavy)
+            estimated_claim_fee_in_dest_chain_native_token: 3 * chain_info_registry::ETHEREUM_INFO
+                .avg_gas_fee_in_native_token,
         },
     ];
 }
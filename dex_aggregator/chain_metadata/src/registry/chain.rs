@@ -46,6 +46,10 @@ pub mod universal_chain_id_registry {
         UniversalChainId::SubstrateParachain(RelayChain::MoonbaseRelay, 888);
     pub const KHALA: UniversalChainId =
         UniversalChainId::SubstrateParachain(RelayChain::Kusama, 2004);
+    pub const KUSAMA: UniversalChainId = UniversalChainId::SubstrateRelayChain(RelayChain::Kusama);
+
+    pub const ETHEREUM: UniversalChainId = UniversalChainId::EVM(1);
+    pub const ARBITRUM: UniversalChainId = UniversalChainId::EVM(42161);
 }
 
 pub mod chain_info_registry {
@@ -53,11 +57,43 @@ pub mod chain_info_registry {
     use privadex_common::signature_scheme::SignatureScheme;
 
     use super::universal_chain_id_registry;
-    use crate::chain_info::{AddressType, ChainInfo};
+    use crate::chain_info::{
+        AddressType, AssetTxPaymentKind, ChainInfo, EthAddressRange, IndexerKind, XC20RegistryKind,
+    };
     use crate::common::EthAddress;
+    use crate::xcm_location::XcmVersion;
     // Note that Ss58AddressFormat::try_from("astar").ok() uses https://github.com/paritytech/ss58-registry
     // but to keep these const I have manually pulled the values
 
+    // ecrecover through the KZG point evaluation precompile
+    const ETHEREUM_STANDARD_PRECOMPILES: EthAddressRange = EthAddressRange::low(0x01, 0x0a);
+    // XC20s are 0xffffffff followed by the asset ID (Moonbeam's local assets use 0xfffffffe)
+    const XC20_PRECOMPILES: EthAddressRange = EthAddressRange {
+        first: EthAddress {
+            0: hex!("fffffffe00000000000000000000000000000000"),
+        },
+        last: EthAddress {
+            0: hex!("ffffffffffffffffffffffffffffffffffffffff"),
+        },
+    };
+    const ETHEREUM_PRECOMPILES: [EthAddressRange; 1] = [ETHEREUM_STANDARD_PRECOMPILES];
+    const ARBITRUM_PRECOMPILES: [EthAddressRange; 2] = [
+        ETHEREUM_STANDARD_PRECOMPILES,
+        EthAddressRange::low(0x64, 0xff), // ArbSys, ArbGasInfo, NodeInterface, etc.
+    ];
+    const MOONBEAM_PRECOMPILES: [EthAddressRange; 4] = [
+        ETHEREUM_STANDARD_PRECOMPILES,
+        EthAddressRange::low(0x400, 0x4ff), // e.g. Sha3FIPS256
+        EthAddressRange::low(0x800, 0x8ff), // e.g. staking, xTokens, batch
+        XC20_PRECOMPILES,
+    ];
+    const ASTAR_PRECOMPILES: [EthAddressRange; 4] = [
+        ETHEREUM_STANDARD_PRECOMPILES,
+        EthAddressRange::low(0x400, 0x4ff),
+        EthAddressRange::low(0x5001, 0x50ff), // e.g. dApps staking, XCM, batch
+        XC20_PRECOMPILES,
+    ];
+
     pub const ASTAR_INFO: ChainInfo = ChainInfo {
         chain_id: universal_chain_id_registry::ASTAR,
         ss58_prefix_raw: Some(5),
@@ -67,11 +103,26 @@ pub mod chain_info_registry {
         weth_addr: Some(EthAddress {
             0: hex!("Aeaaf0e2c81Af264101B9129C00F4440cCF0F720"),
         }), // WASTR
+        evm_precompile_ranges: &ASTAR_PRECOMPILES,
+        deposit_forwarder_factory: None,
         avg_gas_fee_in_native_token: 300_000 * u128::pow(10, 9), // ASTR (18 decimals) -> basically free
         avg_bridge_fee_in_native_token: 200_000 * u128::pow(10, 9), // basically free
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 2, // parachain blocks are rarely reorged before finality
+        has_xcm_precompile: true,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: None,
+        utility_pallet_index: Some(0x0b),
+        proxy_pallet_index: Some(0x0f),
+        has_hashed_evm_account_mapping: true,
+        accepts_remark_deposits: true,
+        asset_tx_payment: Some(AssetTxPaymentKind::U128AssetIds), // pallet-assets ids are u128
+        supports_eip1559: false,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: Some(XC20RegistryKind::AstarXcAssetConfig),
         rpc_url: "https://astar.public.blastapi.io", // author_submitExtrinsic fails, use private endpoint for live action
         // rpc_url: "https://astar.api.onfinality.io/rpc?apikey=[INSERT API KEY HERE]",
-        subsquid_graphql_archive_url: "https://astar.explorer.subsquid.io/graphql",
+        indexer: IndexerKind::Subsquid("https://astar.explorer.subsquid.io/graphql"),
     };
     pub const MOONBEAM_INFO: ChainInfo = ChainInfo {
         chain_id: universal_chain_id_registry::MOONBEAM,
@@ -82,11 +133,26 @@ pub mod chain_info_registry {
         weth_addr: Some(EthAddress {
             0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
         }), // WGLMR
+        evm_precompile_ranges: &MOONBEAM_PRECOMPILES,
+        deposit_forwarder_factory: None,
         avg_gas_fee_in_native_token: 12_000_000 * u128::pow(10, 9), // GLMR (18 decimals) -> 0.01 GLMR = ~$0.003
         avg_bridge_fee_in_native_token: 10_000_000 * u128::pow(10, 9), // ~$0.003
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 2,
+        has_xcm_precompile: false,
+        xtokens_pallet_index: Some(0x6a),
+        xcm_pallet_index: None,
+        utility_pallet_index: Some(0x1e),
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: true,
+        asset_tx_payment: None,
+        supports_eip1559: true,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: Some(XC20RegistryKind::MoonbeamAssetManager),
         rpc_url: "https://moonbeam.public.blastapi.io", // author_submitExtrinsic fails
         // rpc_url: "https://moonbeam.api.onfinality.io/rpc?apikey=[INSERT API KEY HERE]",
-        subsquid_graphql_archive_url: "https://moonbeam.explorer.subsquid.io/graphql",
+        indexer: IndexerKind::Subsquid("https://moonbeam.explorer.subsquid.io/graphql"),
     };
     pub const POLKADOT_INFO: ChainInfo = ChainInfo {
         chain_id: universal_chain_id_registry::POLKADOT,
@@ -95,11 +161,145 @@ pub mod chain_info_registry {
         sig_scheme: SignatureScheme::Sr25519,
         evm_chain_id: None,
         weth_addr: None,
+        evm_precompile_ranges: &[],
+        deposit_forwarder_factory: None,
         // Gas estimate is from an xcmPallet transfer originating from Polkadot
         avg_gas_fee_in_native_token: 190_000_000, // DOT (10 decimals) -> 0.02 DOT = ~$0.10
         avg_bridge_fee_in_native_token: 500_000_000, // ~$0.24
+        avg_block_time_millis: 6_000,
+        prestart_confirmation_depth: 2,
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: Some(0x63),
+        utility_pallet_index: Some(0x1a),
+        proxy_pallet_index: Some(0x1d),
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: false,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: None,
         rpc_url: "https://polkadot.api.onfinality.io/rpc?apikey=[INSERT API KEY HERE]",
-        subsquid_graphql_archive_url: "https://polkadot.explorer.subsquid.io/graphql",
+        indexer: IndexerKind::Subsquid("https://polkadot.explorer.subsquid.io/graphql"),
+    };
+
+    // Kusama chains are registered but have no DEXes or XCM bridges yet. They mostly exist so
+    // that we exercise the multi-relay logic (a Kusama parachain can share a parachain ID with a
+    // Polkadot parachain)
+    pub const KHALA_INFO: ChainInfo = ChainInfo {
+        chain_id: universal_chain_id_registry::KHALA,
+        ss58_prefix_raw: Some(30),
+        xcm_address_type: AddressType::SS58,
+        sig_scheme: SignatureScheme::Sr25519,
+        evm_chain_id: None,
+        weth_addr: None,
+        evm_precompile_ranges: &[],
+        deposit_forwarder_factory: None,
+        avg_gas_fee_in_native_token: 10_000_000_000, // PHA (12 decimals) -> 0.01 PHA = ~$0.001
+        avg_bridge_fee_in_native_token: 64_000_000_000, // ~$0.006
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 2,
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: None,
+        utility_pallet_index: None,
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: false,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: None,
+        rpc_url: "https://khala.api.onfinality.io/public",
+        indexer: IndexerKind::Subsquid("https://khala.explorer.subsquid.io/graphql"),
+    };
+    pub const KUSAMA_INFO: ChainInfo = ChainInfo {
+        chain_id: universal_chain_id_registry::KUSAMA,
+        ss58_prefix_raw: Some(2),
+        xcm_address_type: AddressType::SS58,
+        sig_scheme: SignatureScheme::Sr25519,
+        evm_chain_id: None,
+        weth_addr: None,
+        evm_precompile_ranges: &[],
+        deposit_forwarder_factory: None,
+        avg_gas_fee_in_native_token: 200_000_000, // KSM (12 decimals) -> 0.0002 KSM = ~$0.006
+        avg_bridge_fee_in_native_token: 100_000_000, // ~$0.003
+        avg_block_time_millis: 6_000,
+        prestart_confirmation_depth: 2,
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: Some(0x63),
+        utility_pallet_index: Some(0x18),
+        proxy_pallet_index: Some(0x1e),
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: false,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: None,
+        rpc_url: "https://kusama.api.onfinality.io/public",
+        indexer: IndexerKind::Subsquid("https://kusama.explorer.subsquid.io/graphql"),
+    };
+
+    // Standalone EVM chains: rpc_url is an Ethereum JSON-RPC endpoint (no Substrate RPC) and
+    // there is no Subsquid archive, so anything that needs either must check the chain type first
+    pub const ETHEREUM_INFO: ChainInfo = ChainInfo {
+        chain_id: universal_chain_id_registry::ETHEREUM,
+        ss58_prefix_raw: None,
+        xcm_address_type: AddressType::Ethereum,
+        sig_scheme: SignatureScheme::Ethereum,
+        evm_chain_id: Some(1),
+        weth_addr: Some(EthAddress {
+            0: hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        }), // WETH
+        evm_precompile_ranges: &ETHEREUM_PRECOMPILES,
+        deposit_forwarder_factory: None,
+        avg_gas_fee_in_native_token: 3_000_000 * u128::pow(10, 9), // ETH (18 decimals) -> 0.003 ETH = ~$5
+        avg_bridge_fee_in_native_token: 0,                         // no XCM bridges
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 12, // ~2.5 min, well short of finality (2 epochs) but past any reorg seen post-merge
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: None,
+        utility_pallet_index: None,
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: true,
+        max_xcm_version: None,
+        xc20_registry: None,
+        rpc_url: "https://eth.llamarpc.com",
+        indexer: IndexerKind::Unavailable,
+    };
+    pub const ARBITRUM_INFO: ChainInfo = ChainInfo {
+        chain_id: universal_chain_id_registry::ARBITRUM,
+        ss58_prefix_raw: None,
+        xcm_address_type: AddressType::Ethereum,
+        sig_scheme: SignatureScheme::Ethereum,
+        evm_chain_id: Some(42161),
+        weth_addr: Some(EthAddress {
+            0: hex!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+        }), // WETH
+        evm_precompile_ranges: &ARBITRUM_PRECOMPILES,
+        deposit_forwarder_factory: None,
+        avg_gas_fee_in_native_token: 100_000 * u128::pow(10, 9), // ETH (18 decimals) -> 0.0001 ETH = ~$0.20
+        avg_bridge_fee_in_native_token: 0,                       // no XCM bridges
+        avg_block_time_millis: 250,
+        prestart_confirmation_depth: 40, // ~10 sec, the sequencer rarely reorgs
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: None,
+        utility_pallet_index: None,
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: true,
+        max_xcm_version: None,
+        xc20_registry: None,
+        rpc_url: "https://arb1.arbitrum.io/rpc",
+        indexer: IndexerKind::Unavailable,
     };
 
     pub const MOONBASEALPHA_INFO: ChainInfo = ChainInfo {
@@ -111,11 +311,26 @@ pub mod chain_info_registry {
         weth_addr: Some(EthAddress {
             0: hex!("d909178cc99d318e4d46e7e66a972955859670e1"),
         }), // WDEV
+        evm_precompile_ranges: &MOONBEAM_PRECOMPILES,
+        deposit_forwarder_factory: None,
         avg_gas_fee_in_native_token: 12_000_000 * u128::pow(10, 9), // GLMR (18 decimals) -> 0.01 GLMR = ~$0.003
         avg_bridge_fee_in_native_token: 10_000_000 * u128::pow(10, 9), // ~$0.003
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 2,
         // Don't use: "https://rpc.api.moonbase.moonbeam.network", // doesn't support author_submitExtrinsic on HTTP (only WS)
+        has_xcm_precompile: false,
+        xtokens_pallet_index: Some(0x1e),
+        xcm_pallet_index: None,
+        utility_pallet_index: Some(0x01),
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: true,
+        asset_tx_payment: None,
+        supports_eip1559: true,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: Some(XC20RegistryKind::MoonbeamAssetManager),
         rpc_url: "https://moonbeam-alpha.api.onfinality.io/public",
-        subsquid_graphql_archive_url: "https://moonbase.explorer.subsquid.io/graphql",
+        indexer: IndexerKind::Subsquid("https://moonbase.explorer.subsquid.io/graphql"),
     };
     pub const MOONBASEBETA_INFO: ChainInfo = ChainInfo {
         chain_id: universal_chain_id_registry::MOONBASE_BETA,
@@ -124,9 +339,24 @@ pub mod chain_info_registry {
         sig_scheme: SignatureScheme::Ethereum,
         evm_chain_id: None, // definitely has an EVM chain ID, I just don't know what it is
         weth_addr: None,
+        evm_precompile_ranges: &MOONBEAM_PRECOMPILES,
+        deposit_forwarder_factory: None,
         avg_gas_fee_in_native_token: 12_000_000 * u128::pow(10, 9), // GLMR (18 decimals) -> 0.01 GLMR = ~$0.003
         avg_bridge_fee_in_native_token: 10_000_000 * u128::pow(10, 9), // ~$0.003
+        avg_block_time_millis: 12_000,
+        prestart_confirmation_depth: 2,
+        has_xcm_precompile: false,
+        xtokens_pallet_index: None,
+        xcm_pallet_index: None,
+        utility_pallet_index: None,
+        proxy_pallet_index: None,
+        has_hashed_evm_account_mapping: false,
+        accepts_remark_deposits: false,
+        asset_tx_payment: None,
+        supports_eip1559: true,
+        max_xcm_version: Some(XcmVersion::V1),
+        xc20_registry: None,
         rpc_url: "https://frag-moonbase-beta-rpc.g.moonbase.moonbeam.network",
-        subsquid_graphql_archive_url: "",
+        indexer: IndexerKind::Unavailable,
     };
 }
@@ -50,37 +50,141 @@ pub mod dex_registry {
         id: DexId::Arthswap,
         chain_id: ASTAR,
         fee_bps: 30,
+        fee_tiers_bps: &[],
         graphql_url: "https://squid.subsquid.io/privadex-arthswap/v/v0/graphql",
         eth_dex_router: EthAddress {
             0: hex!("E915D2393a08a00c5A463053edD31bAe2199b9e7"),
         }, // PancakeRouter
+        eth_dex_factory: EthAddress {
+            0: hex!("A9473608514457b4bF083f9045fA63ae5810A03E"),
+        }, // PancakeFactory
     };
     pub const BEAMSWAP: Dex = Dex {
         id: DexId::Beamswap,
         chain_id: MOONBEAM,
         fee_bps: 30,
+        fee_tiers_bps: &[],
         graphql_url: "https://squid.subsquid.io/privadex-beamswap/v/v0/graphql",
         eth_dex_router: EthAddress {
             0: hex!("96b244391D98B62D19aE89b1A4dCcf0fc56970C7"),
         }, // Router02
+        eth_dex_factory: EthAddress {
+            0: hex!("985BcA32293A7A496300a48081947321177a86FD"),
+        },
     };
     pub const STELLASWAP: Dex = Dex {
         id: DexId::Stellaswap,
         chain_id: MOONBEAM,
         fee_bps: 25,
+        fee_tiers_bps: &[],
         graphql_url: "https://squid.subsquid.io/privadex-stellaswap/v/v0/graphql",
         eth_dex_router: EthAddress {
             0: hex!("70085a09d30d6f8c4ecf6ee10120d1847383bb57"),
         }, // StellaSwap: Router v2.1
+        eth_dex_factory: EthAddress {
+            0: hex!("68a384d826d3678f78bb9fb1533c7e9577dacc0e"),
+        },
     };
 
     pub const MOONBASE_UNISWAP: Dex = Dex {
         id: DexId::MoonbaseUniswap,
         chain_id: MOONBASE_ALPHA,
         fee_bps: 30,
+        fee_tiers_bps: &[],
         graphql_url: "",
         eth_dex_router: EthAddress {
             0: hex!("8a1932d6e26433f3037bd6c3a40c816222a6ccd4"),
         }, // Uniswap v2
+        eth_dex_factory: EthAddress {
+            0: hex!("049581aEB6Fe262727f290165C29BDAB065a1B68"),
+        },
     };
 }
+
+// Pools (i.e. pair contracts) the operator blocked, e.g. rug pools or pairs whose router swaps
+// keep reverting. Unlike the token overlays this removes from the registry's DEXes rather than
+// adding to them: a blocked pool is never routed through, even if both of its tokens are fine.
+// Each update replaces the previous blocklist wholesale
+pub mod pool_blocklist_overlay {
+    use ink::prelude::vec::Vec;
+    use scale::{Decode, Encode};
+
+    use crate::common::{EthAddress, UniversalChainId};
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BlockedPool {
+        pub chain_id: UniversalChainId,
+        pub pair_address: EthAddress,
+    }
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PoolBlocklistOverlay {
+        blocked: Vec<BlockedPool>,
+    }
+
+    impl PoolBlocklistOverlay {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn replace(&mut self, pools: Vec<BlockedPool>) {
+            self.blocked.clear();
+            for pool in pools {
+                if !self.blocked.contains(&pool) {
+                    self.blocked.push(pool);
+                }
+            }
+        }
+
+        pub fn is_blocked(&self, chain_id: UniversalChainId, pair_address: &EthAddress) -> bool {
+            self.blocked
+                .iter()
+                .any(|pool| pool.chain_id == chain_id && &pool.pair_address == pair_address)
+        }
+
+        pub fn pools(&self) -> &[BlockedPool] {
+            &self.blocked
+        }
+
+        pub fn len(&self) -> usize {
+            self.blocked.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.blocked.is_empty()
+        }
+    }
+}
+
+#[cfg(test)]
+mod pool_blocklist_overlay_tests {
+    use hex_literal::hex;
+    use ink::prelude::{vec, vec::Vec};
+
+    use super::pool_blocklist_overlay::{BlockedPool, PoolBlocklistOverlay};
+    use crate::common::EthAddress;
+    use crate::registry::chain::universal_chain_id_registry::{ASTAR, MOONBEAM};
+
+    const PAIR_ADDRESS: EthAddress = EthAddress {
+        0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2"),
+    };
+
+    #[test]
+    fn test_blocked_pool_is_per_chain() {
+        let mut overlay = PoolBlocklistOverlay::new();
+        assert!(!overlay.is_blocked(MOONBEAM, &PAIR_ADDRESS));
+        let pool = BlockedPool {
+            chain_id: MOONBEAM,
+            pair_address: PAIR_ADDRESS,
+        };
+        overlay.replace(vec![pool.clone(), pool]);
+        assert_eq!(overlay.len(), 1);
+        assert!(overlay.is_blocked(MOONBEAM, &PAIR_ADDRESS));
+        assert!(!overlay.is_blocked(ASTAR, &PAIR_ADDRESS));
+
+        overlay.replace(Vec::new());
+        assert!(!overlay.is_blocked(MOONBEAM, &PAIR_ADDRESS));
+    }
+}
@@ -16,6 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod aggregator;
 pub mod bridge;
 pub mod chain;
 pub mod dex;
@@ -17,6 +17,8 @@
  */
 
 pub mod universal_token_id_registry {
+    use hex_literal::hex;
+
     use crate::common::{
         ChainTokenId, ERC20Token, EthAddress, UniversalChainId, UniversalTokenId, XC20Token,
     };
@@ -71,6 +73,25 @@ pub mod universal_token_id_registry {
         id: ChainTokenId::XC20(XC20Token::from_asset_id(4_294_969_280)),
     };
 
+    // Wormhole token bridge lane (Ethereum <-> Moonbeam)
+    pub const USDC_ETHEREUM: UniversalTokenId = UniversalTokenId {
+        chain: universal_chain_id_registry::ETHEREUM,
+        id: ChainTokenId::ERC20(ERC20Token {
+            addr: EthAddress {
+                0: hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            },
+        }),
+    };
+    // USDC.wh i.e. Wormhole-wrapped USDC
+    pub const USDC_WH_MOONBEAM: UniversalTokenId = UniversalTokenId {
+        chain: universal_chain_id_registry::MOONBEAM,
+        id: ChainTokenId::ERC20(ERC20Token {
+            addr: EthAddress {
+                0: hex!("931715FEE2d06333043d11F658C8CE934aC61D0c"),
+            },
+        }),
+    };
+
     pub static REGISTERED_XC20_TOKENS: [UniversalTokenId; 6] = [
         GLMR_ASTAR,
         DOT_ASTAR,
@@ -99,6 +120,335 @@ pub mod universal_token_id_registry {
     }
 }
 
+// XC20s discovered at runtime from on-chain asset registries (see the executor's
+// asset_registry_utils). We keep them in an overlay on top of the hand-maintained registry
+// rather than mutating it, so a bad discovery can never clobber a listed token
+pub mod xc20_registry_overlay {
+    use ink::prelude::{string::String, vec::Vec};
+    use scale::{Decode, Encode};
+    use xcm::latest::MultiLocation;
+
+    use crate::common::{ChainTokenId, UniversalChainId, UniversalTokenId};
+
+    use super::{token_list_overlay::ListedToken, universal_token_id_registry};
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DiscoveredXC20 {
+        pub token: UniversalTokenId,
+        pub symbol: String,
+        pub decimals: u8,
+        // Token's MultiLocation from this chain's perspective
+        pub token_asset_multilocation: MultiLocation,
+    }
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct XC20RegistryOverlay {
+        discovered: Vec<DiscoveredXC20>,
+    }
+
+    impl XC20RegistryOverlay {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        // Replaces everything previously discovered on this chain, so a token removed
+        // from the on-chain registry also drops out of the overlay
+        pub fn replace_chain(&mut self, chain_id: UniversalChainId, tokens: Vec<DiscoveredXC20>) {
+            self.discovered.retain(|xc20| xc20.token.chain != chain_id);
+            self.discovered.extend(
+                tokens
+                    .into_iter()
+                    .filter(|xc20| xc20.token.chain == chain_id),
+            );
+        }
+
+        pub fn get(&self, token: &UniversalTokenId) -> Option<&DiscoveredXC20> {
+            self.discovered.iter().find(|xc20| &xc20.token == token)
+        }
+
+        pub fn get_by_multilocation(
+            &self,
+            chain_id: UniversalChainId,
+            multilocation: &MultiLocation,
+        ) -> Option<&DiscoveredXC20> {
+            self.discovered.iter().find(|xc20| {
+                xc20.token.chain == chain_id && &xc20.token_asset_multilocation == multilocation
+            })
+        }
+
+        pub fn len(&self) -> usize {
+            self.discovered.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.discovered.is_empty()
+        }
+
+        // The discovered XC20s as token list entries, so symbol lookups and resolve_token know
+        // them. The graph only knows the hand-maintained XC20s as XC20s (see
+        // universal_token_id_registry::chain_and_eth_addr_to_token), so each one is listed
+        // under the ID the graph gives its ERC20 precompile
+        pub fn to_listed_tokens(&self) -> Vec<ListedToken> {
+            self.discovered
+                .iter()
+                .filter_map(|xc20| match &xc20.token.id {
+                    ChainTokenId::XC20(xc20_token) => Some(ListedToken {
+                        token: universal_token_id_registry::chain_and_eth_addr_to_token(
+                            xc20.token.chain,
+                            xc20_token.get_eth_address(),
+                        ),
+                        symbol: xc20.symbol.clone(),
+                        name: xc20.symbol.clone(),
+                        decimals: xc20.decimals,
+                        logo_uri: None,
+                    }),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+}
+
+// Tokens that the operator allow-listed by importing a standard (Uniswap format) token list.
+// Like the XC20 overlay it sits on top of the hand-maintained registry, whose symbols take
+// priority, and each import replaces the previous list wholesale
+pub mod token_list_overlay {
+    use ink::prelude::{string::String, vec::Vec};
+    use scale::{Decode, Encode};
+
+    use crate::common::{UniversalChainId, UniversalTokenId};
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ListedToken {
+        pub token: UniversalTokenId,
+        pub symbol: String,
+        pub name: String,
+        pub decimals: u8,
+        pub logo_uri: Option<String>,
+    }
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy, Default, PartialOrd, Ord)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TokenListVersion {
+        pub major: u32,
+        pub minor: u32,
+        pub patch: u32,
+    }
+
+    #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TokenListOverlay {
+        pub list_name: String,
+        pub version: TokenListVersion,
+        listed: Vec<ListedToken>,
+    }
+
+    impl TokenListOverlay {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        // Adds the tokens the list doesn't have yet, e.g. discovered XC20s. The list's own
+        // entries win
+        pub fn extend(&mut self, tokens: Vec<ListedToken>) {
+            for listed_token in tokens {
+                if self.get(&listed_token.token).is_none() {
+                    self.listed.push(listed_token);
+                }
+            }
+        }
+
+        // If the list has the same token more than once, the first entry wins
+        pub fn replace(
+            &mut self,
+            list_name: String,
+            version: TokenListVersion,
+            tokens: Vec<ListedToken>,
+        ) {
+            self.list_name = list_name;
+            self.version = version;
+            self.listed.clear();
+            self.extend(tokens);
+        }
+
+        pub fn get(&self, token: &UniversalTokenId) -> Option<&ListedToken> {
+            self.listed
+                .iter()
+                .find(|listed_token| &listed_token.token == token)
+        }
+
+        pub fn tokens(&self) -> &[ListedToken] {
+            &self.listed
+        }
+
+        pub fn len(&self) -> usize {
+            self.listed.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.listed.is_empty()
+        }
+
+        // Case-insensitive like token_symbol_registry::symbol_to_token
+        pub fn symbol_to_token(
+            &self,
+            chain_id: UniversalChainId,
+            symbol: &str,
+        ) -> Option<UniversalTokenId> {
+            self.listed
+                .iter()
+                .find(|listed_token| {
+                    listed_token.token.chain == chain_id
+                        && listed_token.symbol.eq_ignore_ascii_case(symbol)
+                })
+                .map(|listed_token| listed_token.token.clone())
+        }
+    }
+}
+
+// Human-readable symbols for the registered tokens, so users can ask for e.g. "USDC@moonbeam"
+// instead of spelling out the token address. The same symbol maps to a different token on
+// each chain
+pub mod token_symbol_registry {
+    use crate::common::{UniversalChainId, UniversalTokenId};
+
+    use super::universal_token_id_registry as token_reg;
+
+    static TOKEN_SYMBOLS: [(&str, UniversalTokenId); 12] = [
+        ("DOT", token_reg::DOT_NATIVE),
+        ("DOT", token_reg::DOT_MOONBEAM),
+        ("DOT", token_reg::DOT_ASTAR),
+        ("ASTR", token_reg::ASTR_NATIVE),
+        ("ASTR", token_reg::ASTR_MOONBEAM),
+        ("GLMR", token_reg::GLMR_NATIVE),
+        ("GLMR", token_reg::GLMR_ASTAR),
+        ("USDT", token_reg::USDT_MOONBEAM),
+        ("USDT", token_reg::USDT_ASTAR),
+        ("USDC", token_reg::USDC_ETHEREUM),
+        ("USDC", token_reg::USDC_WH_MOONBEAM),
+        ("USDC.wh", token_reg::USDC_WH_MOONBEAM),
+    ];
+
+    // Case-insensitive. Moonbeam's XC20 symbols have an "xc" prefix (e.g. xcDOT) which we
+    // also accept
+    pub fn symbol_to_token(chain_id: UniversalChainId, symbol: &str) -> Option<UniversalTokenId> {
+        let find_symbol = |symbol: &str| {
+            TOKEN_SYMBOLS
+                .iter()
+                .find(|(s, token)| token.chain == chain_id && s.eq_ignore_ascii_case(symbol))
+                .map(|(_, token)| token.clone())
+        };
+        find_symbol(symbol).or_else(|| {
+            let lowercase_symbol = symbol.to_ascii_lowercase();
+            lowercase_symbol.strip_prefix("xc").and_then(find_symbol)
+        })
+    }
+
+    // The first symbol listed for the token, e.g. "USDC" rather than "USDC.wh"
+    pub fn token_to_symbol(token: &UniversalTokenId) -> Option<&'static str> {
+        TOKEN_SYMBOLS
+            .iter()
+            .find(|(_, t)| t == token)
+            .map(|(s, _)| *s)
+    }
+}
+
+// Stablecoins that the "best-stable" destination selector picks from (see the executor's
+// quote_engine). Only tokens we trust to hold their peg belong here
+pub mod stablecoin_registry {
+    use crate::common::{UniversalChainId, UniversalTokenId};
+
+    use super::universal_token_id_registry as token_reg;
+
+    static STABLECOINS: [UniversalTokenId; 4] = [
+        token_reg::USDT_MOONBEAM,
+        token_reg::USDT_ASTAR,
+        token_reg::USDC_ETHEREUM,
+        token_reg::USDC_WH_MOONBEAM,
+    ];
+
+    pub fn get_stablecoins(chain_id: UniversalChainId) -> impl Iterator<Item = UniversalTokenId> {
+        STABLECOINS
+            .iter()
+            .filter(move |token| token.chain == chain_id)
+            .cloned()
+    }
+}
+
+// Decimals of each registered token on its own chain. The same asset need not have the same
+// decimals everywhere (its XC20 or wrapped form is a separate token), so amounts must be
+// converted whenever they cross a bridge rather than carried over as-is
+pub mod token_decimals_registry {
+    use crate::common::{Amount, UniversalTokenId};
+
+    use super::universal_token_id_registry as token_reg;
+
+    static TOKEN_DECIMALS: [(UniversalTokenId, u8); 11] = [
+        (token_reg::DOT_NATIVE, 10),
+        (token_reg::DOT_MOONBEAM, 10),
+        (token_reg::DOT_ASTAR, 10),
+        (token_reg::ASTR_NATIVE, 18),
+        (token_reg::ASTR_MOONBEAM, 18),
+        (token_reg::GLMR_NATIVE, 18),
+        (token_reg::GLMR_ASTAR, 18),
+        (token_reg::USDT_MOONBEAM, 6),
+        (token_reg::USDT_ASTAR, 6),
+        (token_reg::USDC_ETHEREUM, 6),
+        (token_reg::USDC_WH_MOONBEAM, 6),
+    ];
+
+    pub fn get_decimals(token: &UniversalTokenId) -> Option<u8> {
+        TOKEN_DECIMALS
+            .iter()
+            .find(|(t, _)| t == token)
+            .map(|(_, decimals)| *decimals)
+    }
+
+    // Rounds down when dropping decimals and saturates when adding them
+    pub fn convert_decimals(amount: Amount, src_decimals: u8, dest_decimals: u8) -> Amount {
+        if src_decimals >= dest_decimals {
+            amount / Amount::pow(10, (src_decimals - dest_decimals) as u32)
+        } else {
+            amount.saturating_mul(Amount::pow(10, (dest_decimals - src_decimals) as u32))
+        }
+    }
+
+    // Converts an amount of src_token into the same quantity of dest_token (e.g. DOT into
+    // xcDOT). If either token is missing from the registry we assume the bridge preserves
+    // decimals and return the amount unchanged
+    pub fn normalize_amount(
+        amount: Amount,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+    ) -> Amount {
+        normalize_amount_with_max_decimals(amount, src_token, dest_token, u8::MAX)
+    }
+
+    // Same as normalize_amount, for bridges that carry the amount at a lower precision than
+    // the token itself (e.g. Wormhole truncates to 8 decimals)
+    pub fn normalize_amount_with_max_decimals(
+        amount: Amount,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        max_decimals: u8,
+    ) -> Amount {
+        match (get_decimals(src_token), get_decimals(dest_token)) {
+            (Some(src_decimals), Some(dest_decimals)) => {
+                let bridged_decimals = src_decimals.min(max_decimals);
+                convert_decimals(
+                    convert_decimals(amount, src_decimals, bridged_decimals),
+                    bridged_decimals,
+                    dest_decimals,
+                )
+            }
+            _ => amount,
+        }
+    }
+}
+
 pub(crate) mod token_multilocation_spec_registry {
     use xcm::latest::{Junction, Junctions, MultiLocation};
 
@@ -168,4 +518,243 @@ pub(crate) mod token_multilocation_spec_registry {
             ),
         },
     };
+
+    pub(crate) static ALL_TOKEN_MULTILOCATION_SPECS: [TokenMultiLocationSpec; 7] = [
+        DOT_NATIVE,
+        DOT_MOONBEAM,
+        DOT_ASTAR,
+        ASTR_NATIVE,
+        ASTR_MOONBEAM,
+        GLMR_NATIVE,
+        GLMR_ASTAR,
+    ];
+}
+
+#[cfg(test)]
+mod xc20_registry_overlay_tests {
+    use ink::prelude::{string::ToString, vec};
+    use xcm::latest::{Junction, Junctions, MultiLocation};
+
+    use crate::common::{ChainTokenId, ERC20Token, UniversalTokenId, XC20Token};
+    use crate::registry::chain::universal_chain_id_registry;
+
+    use super::token_list_overlay::{TokenListOverlay, TokenListVersion};
+    use super::universal_token_id_registry as token_reg;
+    use super::xc20_registry_overlay::{DiscoveredXC20, XC20RegistryOverlay};
+
+    fn discovered_xc20(asset_id: u128) -> DiscoveredXC20 {
+        DiscoveredXC20 {
+            token: crate::common::UniversalTokenId {
+                chain: universal_chain_id_registry::MOONBEAM,
+                id: ChainTokenId::XC20(XC20Token::from_asset_id(asset_id)),
+            },
+            symbol: "xcFOO".to_string(),
+            decimals: 12,
+            token_asset_multilocation: MultiLocation {
+                parents: 1,
+                interior: Junctions::X1(Junction::Parachain(9999)),
+            },
+        }
+    }
+
+    #[test]
+    fn test_overlay_lists_discovered_xc20_under_graph_id() {
+        let xc20 = discovered_xc20(123_456);
+        let eth_addr = match &xc20.token.id {
+            ChainTokenId::XC20(token) => token.get_eth_address(),
+            _ => unreachable!(),
+        };
+        let mut overlay = XC20RegistryOverlay::new();
+        assert!(overlay.to_listed_tokens().is_empty());
+        overlay.replace_chain(universal_chain_id_registry::MOONBEAM, vec![xc20.clone()]);
+        assert_eq!(
+            overlay.get_by_multilocation(
+                universal_chain_id_registry::MOONBEAM,
+                &xc20.token_asset_multilocation
+            ),
+            Some(&xc20)
+        );
+        let listed = overlay.to_listed_tokens();
+        assert_eq!(listed.len(), 1);
+        // The graph knows the unregistered XC20 by its ERC20 precompile
+        assert_eq!(
+            listed[0].token,
+            UniversalTokenId {
+                chain: universal_chain_id_registry::MOONBEAM,
+                id: ChainTokenId::ERC20(ERC20Token { addr: eth_addr }),
+            }
+        );
+        assert_eq!(listed[0].symbol, "xcFOO");
+        assert_eq!(listed[0].decimals, 12);
+    }
+
+    #[test]
+    fn test_token_list_keeps_its_entries_over_discovered_xc20() {
+        let mut overlay = XC20RegistryOverlay::new();
+        overlay.replace_chain(
+            universal_chain_id_registry::MOONBEAM,
+            vec![discovered_xc20(123_456)],
+        );
+        let discovered = overlay.to_listed_tokens();
+        let mut listed_first = discovered[0].clone();
+        listed_first.symbol = "FOO".to_string();
+
+        let mut token_list = TokenListOverlay::new();
+        token_list.replace(
+            "list".to_string(),
+            TokenListVersion::default(),
+            vec![listed_first],
+        );
+        token_list.extend(discovered);
+        assert_eq!(token_list.len(), 1);
+        assert!(token_list
+            .symbol_to_token(universal_chain_id_registry::MOONBEAM, "xcFOO")
+            .is_none());
+        assert!(token_list
+            .symbol_to_token(universal_chain_id_registry::MOONBEAM, "FOO")
+            .is_some());
+        // Hand-maintained XC20s keep their registry entry
+        assert!(token_reg::REGISTERED_XC20_TOKENS.contains(&token_reg::DOT_MOONBEAM));
+    }
+
+    #[test]
+    fn test_overlay_replace_chain_drops_stale_tokens() {
+        let mut overlay = XC20RegistryOverlay::new();
+        overlay.replace_chain(
+            universal_chain_id_registry::MOONBEAM,
+            vec![discovered_xc20(1), discovered_xc20(2)],
+        );
+        overlay.replace_chain(
+            universal_chain_id_registry::MOONBEAM,
+            vec![discovered_xc20(2)],
+        );
+        assert_eq!(overlay.len(), 1);
+        assert!(overlay.get(&discovered_xc20(1).token).is_none());
+        // Tokens tagged with a different chain are ignored
+        overlay.replace_chain(universal_chain_id_registry::ASTAR, vec![discovered_xc20(3)]);
+        assert_eq!(overlay.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod token_list_overlay_tests {
+    use ink::prelude::{string::ToString, vec};
+
+    use crate::registry::chain::universal_chain_id_registry;
+
+    use super::token_list_overlay::{ListedToken, TokenListOverlay, TokenListVersion};
+    use super::universal_token_id_registry as token_reg;
+
+    fn listed_token(token: crate::common::UniversalTokenId, symbol: &str) -> ListedToken {
+        ListedToken {
+            token,
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            decimals: 6,
+            logo_uri: None,
+        }
+    }
+
+    #[test]
+    fn test_token_list_replace_and_symbol_lookup() {
+        let mut overlay = TokenListOverlay::new();
+        overlay.replace(
+            "Test List".to_string(),
+            TokenListVersion {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            },
+            vec![
+                listed_token(token_reg::USDC_WH_MOONBEAM, "USDC.wh"),
+                listed_token(token_reg::USDC_WH_MOONBEAM, "DUP"),
+                listed_token(token_reg::USDT_MOONBEAM, "xcUSDT"),
+            ],
+        );
+        assert_eq!(overlay.len(), 2);
+        assert_eq!(
+            overlay.symbol_to_token(universal_chain_id_registry::MOONBEAM, "usdc.WH"),
+            Some(token_reg::USDC_WH_MOONBEAM)
+        );
+        assert_eq!(
+            overlay.symbol_to_token(universal_chain_id_registry::MOONBEAM, "DUP"),
+            None
+        );
+        assert_eq!(
+            overlay.symbol_to_token(universal_chain_id_registry::ASTAR, "xcUSDT"),
+            None
+        );
+
+        // A new import drops everything from the previous one
+        overlay.replace(
+            "Test List".to_string(),
+            TokenListVersion {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            },
+            vec![listed_token(token_reg::USDT_MOONBEAM, "xcUSDT")],
+        );
+        assert_eq!(overlay.len(), 1);
+        assert!(overlay.get(&token_reg::USDC_WH_MOONBEAM).is_none());
+    }
+}
+
+#[cfg(test)]
+mod token_decimals_registry_tests {
+    use crate::common::{ChainTokenId, UniversalTokenId, XC20Token};
+    use crate::registry::chain::universal_chain_id_registry;
+
+    use super::token_decimals_registry::{
+        convert_decimals, get_decimals, normalize_amount, normalize_amount_with_max_decimals,
+    };
+    use super::universal_token_id_registry as token_reg;
+
+    #[test]
+    fn test_convert_decimals() {
+        assert_eq!(convert_decimals(12_345_678_901, 10, 10), 12_345_678_901);
+        assert_eq!(
+            convert_decimals(12_345_678_901, 10, 18),
+            1_234_567_890_100_000_000
+        );
+        // Dust below the destination's precision is dropped
+        assert_eq!(convert_decimals(12_345_678_901, 10, 8), 123_456_789);
+        assert_eq!(convert_decimals(u128::MAX, 0, 18), u128::MAX);
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        assert_eq!(get_decimals(&token_reg::DOT_MOONBEAM), Some(10));
+        assert_eq!(
+            normalize_amount(
+                5_000_000,
+                &token_reg::USDC_ETHEREUM,
+                &token_reg::USDC_WH_MOONBEAM
+            ),
+            5_000_000
+        );
+        assert_eq!(
+            normalize_amount(1_000_000, &token_reg::USDT_ASTAR, &token_reg::GLMR_ASTAR),
+            1_000_000_000_000_000_000
+        );
+        assert_eq!(
+            normalize_amount_with_max_decimals(
+                1_234_567_890_123_456_789,
+                &token_reg::GLMR_NATIVE,
+                &token_reg::GLMR_ASTAR,
+                8
+            ),
+            1_234_567_890_000_000_000
+        );
+        // Unregistered tokens are passed through unchanged
+        let unregistered = UniversalTokenId {
+            chain: universal_chain_id_registry::MOONBEAM,
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(123_456)),
+        };
+        assert_eq!(get_decimals(&unregistered), None);
+        assert_eq!(
+            normalize_amount(1_000_000, &token_reg::DOT_NATIVE, &unregistered),
+            1_000_000
+        );
+    }
 }
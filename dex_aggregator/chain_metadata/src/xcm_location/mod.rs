@@ -0,0 +1,417 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Builds XCM MultiLocations from our own types (instead of storing them pre-built) and
+// encodes them for whichever XCM version the chains on both ends of a transfer accept, V1
+// (whose MultiLocations V2 reuses) or V3. The xcm crate we are pinned to predates V3, so the
+// V3 encoding comes from the types in v3.rs. V0 is not emitted since every chain with XCM
+// accepts V1
+
+pub mod v3;
+
+use scale::{Decode, Encode};
+use xcm::latest::{Junction, Junctions, MultiAsset, MultiAssets, MultiLocation, NetworkId};
+
+use crate::chain_info::{AddressType, ChainInfo};
+use crate::common::{PublicError, Result, UniversalAddress, UniversalChainId, UniversalTokenId};
+use crate::registry::token::{
+    token_multilocation_spec_registry::ALL_TOKEN_MULTILOCATION_SPECS,
+    xc20_registry_overlay::XC20RegistryOverlay,
+};
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmVersion {
+    V1,
+    V3,
+}
+
+// What the XCM pallets take, i.e. xcm::VersionedMultiLocation and co. as of XCM v3. The variant
+// index is the version, so they decode on chains that still only know V1 (and V2)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum VersionedMultiLocation {
+    #[codec(index = 1)]
+    V1(MultiLocation),
+    #[codec(index = 3)]
+    V3(v3::MultiLocation),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum VersionedMultiAsset {
+    #[codec(index = 1)]
+    V1(MultiAsset),
+    #[codec(index = 3)]
+    V3(v3::MultiAsset),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum VersionedMultiAssets {
+    #[codec(index = 1)]
+    V1(MultiAssets),
+    #[codec(index = 3)]
+    V3(v3::MultiAssets),
+}
+
+// Both chains need to understand the encoded MultiLocations: the source chain decodes the
+// extrinsic and the destination chain decodes the resulting XCM message
+pub fn negotiate_xcm_version(
+    src_chain_info: &ChainInfo,
+    dest_chain_info: &ChainInfo,
+) -> Result<XcmVersion> {
+    match (
+        src_chain_info.max_xcm_version,
+        dest_chain_info.max_xcm_version,
+    ) {
+        (Some(src_version), Some(dest_version)) => Ok(src_version.min(dest_version)),
+        _ => Err(PublicError::UnsupportedXcmChain),
+    }
+}
+
+pub fn to_versioned_multilocation(
+    multilocation: MultiLocation,
+    version: XcmVersion,
+) -> Result<VersionedMultiLocation> {
+    match version {
+        XcmVersion::V1 => Ok(VersionedMultiLocation::V1(multilocation)),
+        XcmVersion::V3 => {
+            v3::MultiLocation::try_from(&multilocation).map(VersionedMultiLocation::V3)
+        }
+    }
+}
+
+pub fn to_versioned_multiasset(
+    asset: MultiAsset,
+    version: XcmVersion,
+) -> Result<VersionedMultiAsset> {
+    match version {
+        XcmVersion::V1 => Ok(VersionedMultiAsset::V1(asset)),
+        XcmVersion::V3 => v3::MultiAsset::try_from(&asset).map(VersionedMultiAsset::V3),
+    }
+}
+
+pub fn to_versioned_multiassets(
+    assets: MultiAssets,
+    version: XcmVersion,
+) -> Result<VersionedMultiAssets> {
+    match version {
+        XcmVersion::V1 => Ok(VersionedMultiAssets::V1(assets)),
+        XcmVersion::V3 => v3::MultiAssets::try_from(&assets).map(VersionedMultiAssets::V3),
+    }
+}
+
+// Re-expresses a MultiLocation given from `from_chain`'s perspective as seen from `to_chain`.
+// We only handle chains within a single relay, which covers every transfer we make
+pub fn reanchor(
+    multilocation: &MultiLocation,
+    from_chain: UniversalChainId,
+    to_chain: UniversalChainId,
+) -> Result<MultiLocation> {
+    if !from_chain.shares_relay_with(&to_chain) {
+        return Err(PublicError::UnsupportedXcmChain);
+    }
+    let relay_relative_interior = match (from_chain, multilocation.parents) {
+        (UniversalChainId::SubstrateRelayChain(_), 0) => multilocation.interior.clone(),
+        (UniversalChainId::SubstrateParachain(_, para_id), 0) => {
+            let mut interior = multilocation.interior.clone();
+            interior
+                .push_front(Junction::Parachain(para_id))
+                .map_err(|_| PublicError::InvalidMultiLocationLength)?;
+            interior
+        }
+        (UniversalChainId::SubstrateParachain(_, _), 1) => multilocation.interior.clone(),
+        _ => return Err(PublicError::InvalidMultiLocationLength),
+    };
+    match to_chain {
+        UniversalChainId::SubstrateRelayChain(_) => Ok(MultiLocation {
+            parents: 0,
+            interior: relay_relative_interior,
+        }),
+        UniversalChainId::SubstrateParachain(_, para_id) => {
+            if relay_relative_interior.first() == Some(&Junction::Parachain(para_id)) {
+                let (interior, _) = relay_relative_interior.split_first();
+                Ok(MultiLocation {
+                    parents: 0,
+                    interior,
+                })
+            } else {
+                Ok(MultiLocation {
+                    parents: 1,
+                    interior: relay_relative_interior,
+                })
+            }
+        }
+        UniversalChainId::EVM(_) => Err(PublicError::UnsupportedXcmChain),
+    }
+}
+
+// The token's MultiLocation as seen from `perspective_chain`. Hand-maintained specs take
+// priority over discovered XC20s
+pub fn token_asset_multilocation(
+    token: &UniversalTokenId,
+    perspective_chain: UniversalChainId,
+    overlay: Option<&XC20RegistryOverlay>,
+) -> Result<MultiLocation> {
+    let home_multilocation = ALL_TOKEN_MULTILOCATION_SPECS
+        .iter()
+        .find(|spec| &spec.token == token)
+        .map(|spec| spec.token_asset_multilocation.clone())
+        .or_else(|| {
+            overlay?
+                .get(token)
+                .map(|xc20| xc20.token_asset_multilocation.clone())
+        })
+        .ok_or(PublicError::NoTokenMultiLocation)?;
+    reanchor(&home_multilocation, token.chain, perspective_chain)
+}
+
+// The full destination MultiLocation (chain + account) as seen from `src_chain`,
+// i.e. what xTokens.transferMultiasset expects
+pub fn beneficiary_multilocation(
+    src_chain: UniversalChainId,
+    dest_chain_info: &ChainInfo,
+    address: &UniversalAddress,
+) -> Result<MultiLocation> {
+    let account_junction = match (dest_chain_info.xcm_address_type, address) {
+        (AddressType::Ethereum, UniversalAddress::Ethereum(eth_addr)) => Junction::AccountKey20 {
+            network: NetworkId::Any,
+            key: eth_addr.0,
+        },
+        (AddressType::SS58, UniversalAddress::Substrate(substrate_addr)) => Junction::AccountId32 {
+            network: NetworkId::Any,
+            id: substrate_addr.0,
+        },
+        _ => return Err(PublicError::InvalidMultiLocationAddress),
+    };
+    let mut dest_chain_multilocation = reanchor(
+        &MultiLocation {
+            parents: 0,
+            interior: Junctions::Here,
+        },
+        dest_chain_info.chain_id,
+        src_chain,
+    )?;
+    dest_chain_multilocation
+        .push_interior(account_junction)
+        .map_err(|_| PublicError::InvalidMultiLocationLength)?;
+    Ok(dest_chain_multilocation)
+}
+
+#[cfg(test)]
+mod xcm_location_tests {
+    use hex_literal::hex;
+    use xcm::latest::{AssetId, BodyId, BodyPart, Fungibility};
+
+    use super::*;
+    use crate::common::{EthAddress, SubstratePublicKey};
+    use crate::registry::{
+        bridge::xcm_bridge_registry::XCM_BRIDGES,
+        chain::{chain_info_registry, universal_chain_id_registry},
+        token::universal_token_id_registry,
+    };
+
+    #[test]
+    fn test_token_multilocations_match_bridge_registry() {
+        for bridge in XCM_BRIDGES.iter() {
+            assert_eq!(
+                token_asset_multilocation(&bridge.src_token, bridge.src_token.chain, None),
+                Ok(bridge.token_asset_multilocation.clone())
+            );
+        }
+        // GLMR as seen from Astar is built from GLMR as seen from Moonbeam
+        assert_eq!(
+            token_asset_multilocation(
+                &universal_token_id_registry::GLMR_NATIVE,
+                universal_chain_id_registry::ASTAR,
+                None
+            ),
+            Ok(MultiLocation {
+                parents: 1,
+                interior: Junctions::X2(Junction::Parachain(2004), Junction::PalletInstance(10)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_beneficiary_multilocation_matches_templates() {
+        let eth_addr = UniversalAddress::Ethereum(EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        });
+        let substrate_addr = UniversalAddress::Substrate(SubstratePublicKey {
+            0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
+        });
+        for bridge in XCM_BRIDGES.iter() {
+            let dest_chain_info = crate::get_chain_info_from_chain_id(&bridge.dest_token.chain)
+                .expect("Chain must be registered");
+            let address = match dest_chain_info.xcm_address_type {
+                AddressType::Ethereum => &eth_addr,
+                AddressType::SS58 => &substrate_addr,
+            };
+            assert_eq!(
+                beneficiary_multilocation(bridge.src_token.chain, dest_chain_info, address),
+                bridge
+                    .dest_multilocation_template
+                    .get_full_dest_multilocation(address.clone())
+            );
+        }
+        assert_eq!(
+            beneficiary_multilocation(
+                universal_chain_id_registry::MOONBEAM,
+                &chain_info_registry::ASTAR_INFO,
+                &eth_addr
+            ),
+            Err(PublicError::InvalidMultiLocationAddress)
+        );
+    }
+
+    #[test]
+    fn test_reanchor_rejects_cross_relay_and_evm() {
+        let here = MultiLocation {
+            parents: 0,
+            interior: Junctions::Here,
+        };
+        assert_eq!(
+            reanchor(
+                &here,
+                universal_chain_id_registry::KHALA,
+                universal_chain_id_registry::MOONBEAM
+            ),
+            Err(PublicError::UnsupportedXcmChain)
+        );
+        assert_eq!(
+            reanchor(
+                &here,
+                universal_chain_id_registry::ETHEREUM,
+                universal_chain_id_registry::MOONBEAM
+            ),
+            Err(PublicError::UnsupportedXcmChain)
+        );
+    }
+
+    #[test]
+    fn test_versioned_multilocation() {
+        let dest = MultiLocation {
+            parents: 1,
+            interior: Junctions::X1(Junction::Parachain(2006)),
+        };
+        assert_eq!(
+            to_versioned_multilocation(dest.clone(), XcmVersion::V1),
+            Ok(VersionedMultiLocation::V1(dest.clone()))
+        );
+        // Only the version byte differs, since a Parachain junction encodes the same in V3
+        assert_eq!(
+            to_versioned_multilocation(dest.clone(), XcmVersion::V1)
+                .unwrap()
+                .encode(),
+            hex!("01010100591f")
+        );
+        assert_eq!(
+            to_versioned_multilocation(dest, XcmVersion::V3)
+                .unwrap()
+                .encode(),
+            hex!("03010100591f")
+        );
+        assert_eq!(
+            negotiate_xcm_version(
+                &chain_info_registry::MOONBEAM_INFO,
+                &chain_info_registry::ASTAR_INFO
+            ),
+            Ok(XcmVersion::V1)
+        );
+        assert_eq!(
+            negotiate_xcm_version(
+                &chain_info_registry::MOONBEAM_INFO,
+                &chain_info_registry::ETHEREUM_INFO
+            ),
+            Err(PublicError::UnsupportedXcmChain)
+        );
+    }
+
+    #[test]
+    fn test_v3_encoding() {
+        // NetworkId::Any is no network at all in V3, so the account encodes as in V1
+        let any_account = MultiLocation {
+            parents: 0,
+            interior: Junctions::X1(Junction::AccountKey20 {
+                network: NetworkId::Any,
+                key: [0xaa; 20],
+            }),
+        };
+        assert_eq!(
+            to_versioned_multilocation(any_account, XcmVersion::V3)
+                .unwrap()
+                .encode(),
+            [&hex!("0300010300")[..], &[0xaa; 20]].concat()
+        );
+        // whereas a given network is wrapped in an Option
+        let polkadot_account = MultiLocation {
+            parents: 0,
+            interior: Junctions::X1(Junction::AccountId32 {
+                network: NetworkId::Polkadot,
+                id: [0xbb; 32],
+            }),
+        };
+        assert_eq!(
+            to_versioned_multilocation(polkadot_account.clone(), XcmVersion::V1)
+                .unwrap()
+                .encode(),
+            [&hex!("0100010102")[..], &[0xbb; 32]].concat()
+        );
+        assert_eq!(
+            to_versioned_multilocation(polkadot_account, XcmVersion::V3)
+                .unwrap()
+                .encode(),
+            [&hex!("030001010102")[..], &[0xbb; 32]].concat()
+        );
+
+        let asset = MultiAsset {
+            id: AssetId::Concrete(MultiLocation {
+                parents: 0,
+                interior: Junctions::X1(Junction::PalletInstance(10)),
+            }),
+            fun: Fungibility::Fungible(1_000),
+        };
+        assert_eq!(
+            to_versioned_multiasset(asset.clone(), XcmVersion::V3)
+                .unwrap()
+                .encode(),
+            hex!("03000001040a00a10f")
+        );
+        assert_eq!(
+            to_versioned_multiassets(MultiAssets::from(vec![asset]), XcmVersion::V3)
+                .unwrap()
+                .encode(),
+            hex!("0304000001040a00a10f")
+        );
+
+        let plurality = MultiLocation {
+            parents: 1,
+            interior: Junctions::X1(Junction::Plurality {
+                id: BodyId::Unit,
+                part: BodyPart::Voice,
+            }),
+        };
+        assert_eq!(
+            to_versioned_multilocation(plurality.clone(), XcmVersion::V3),
+            Err(PublicError::UnsupportedXcmVersion)
+        );
+        assert!(to_versioned_multilocation(plurality, XcmVersion::V1).is_ok());
+    }
+}
@@ -0,0 +1,240 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// XCM v3's MultiLocation and MultiAsset, which the xcm crate we are pinned to predates. They
+// only need to encode exactly like upstream's, so just the parts we put in extrinsics are here,
+// converted from their V1 counterparts. Anything without a V3 equivalent (a named NetworkId,
+// an abstract asset, ...) fails with UnsupportedXcmVersion
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+use xcm::v1;
+
+use crate::common::{PublicError, Result};
+
+// Variant indices as upstream, which starts at ByGenesis and ByFork
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NetworkId {
+    #[codec(index = 2)]
+    Polkadot,
+    #[codec(index = 3)]
+    Kusama,
+}
+
+// V1's NetworkId::Any is no network at all in V3
+fn network_from_v1(network: &v1::NetworkId) -> Result<Option<NetworkId>> {
+    match network {
+        v1::NetworkId::Any => Ok(None),
+        v1::NetworkId::Polkadot => Ok(Some(NetworkId::Polkadot)),
+        v1::NetworkId::Kusama => Ok(Some(NetworkId::Kusama)),
+        v1::NetworkId::Named(_) => Err(PublicError::UnsupportedXcmVersion),
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Junction {
+    Parachain(#[codec(compact)] u32),
+    AccountId32 {
+        network: Option<NetworkId>,
+        id: [u8; 32],
+    },
+    AccountIndex64 {
+        network: Option<NetworkId>,
+        #[codec(compact)]
+        index: u64,
+    },
+    AccountKey20 {
+        network: Option<NetworkId>,
+        key: [u8; 20],
+    },
+    PalletInstance(u8),
+    GeneralIndex(#[codec(compact)] u128),
+    // Zero-padded to 32 bytes, with the length of the actual key
+    GeneralKey {
+        length: u8,
+        data: [u8; 32],
+    },
+    OnlyChild,
+}
+
+impl TryFrom<&v1::Junction> for Junction {
+    type Error = PublicError;
+
+    fn try_from(junction: &v1::Junction) -> Result<Self> {
+        Ok(match junction {
+            v1::Junction::Parachain(para_id) => Self::Parachain(*para_id),
+            v1::Junction::AccountId32 { network, id } => Self::AccountId32 {
+                network: network_from_v1(network)?,
+                id: *id,
+            },
+            v1::Junction::AccountIndex64 { network, index } => Self::AccountIndex64 {
+                network: network_from_v1(network)?,
+                index: *index,
+            },
+            v1::Junction::AccountKey20 { network, key } => Self::AccountKey20 {
+                network: network_from_v1(network)?,
+                key: *key,
+            },
+            v1::Junction::PalletInstance(instance) => Self::PalletInstance(*instance),
+            v1::Junction::GeneralIndex(index) => Self::GeneralIndex(*index),
+            v1::Junction::GeneralKey(key) => {
+                let mut data = [0u8; 32];
+                data.get_mut(..key.len())
+                    .ok_or(PublicError::UnsupportedXcmVersion)?
+                    .copy_from_slice(&key[..]);
+                Self::GeneralKey {
+                    length: key.len() as u8,
+                    data,
+                }
+            }
+            v1::Junction::OnlyChild => Self::OnlyChild,
+            v1::Junction::Plurality { .. } => return Err(PublicError::UnsupportedXcmVersion),
+        })
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Junctions {
+    Here,
+    X1(Junction),
+    X2(Junction, Junction),
+    X3(Junction, Junction, Junction),
+    X4(Junction, Junction, Junction, Junction),
+    X5(Junction, Junction, Junction, Junction, Junction),
+    X6(Junction, Junction, Junction, Junction, Junction, Junction),
+    X7(
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+    ),
+    X8(
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+        Junction,
+    ),
+}
+
+impl TryFrom<&v1::Junctions> for Junctions {
+    type Error = PublicError;
+
+    fn try_from(junctions: &v1::Junctions) -> Result<Self> {
+        let j = |junction: &v1::Junction| Junction::try_from(junction);
+        Ok(match junctions {
+            v1::Junctions::Here => Self::Here,
+            v1::Junctions::X1(a) => Self::X1(j(a)?),
+            v1::Junctions::X2(a, b) => Self::X2(j(a)?, j(b)?),
+            v1::Junctions::X3(a, b, c) => Self::X3(j(a)?, j(b)?, j(c)?),
+            v1::Junctions::X4(a, b, c, d) => Self::X4(j(a)?, j(b)?, j(c)?, j(d)?),
+            v1::Junctions::X5(a, b, c, d, e) => Self::X5(j(a)?, j(b)?, j(c)?, j(d)?, j(e)?),
+            v1::Junctions::X6(a, b, c, d, e, f) => {
+                Self::X6(j(a)?, j(b)?, j(c)?, j(d)?, j(e)?, j(f)?)
+            }
+            v1::Junctions::X7(a, b, c, d, e, f, g) => {
+                Self::X7(j(a)?, j(b)?, j(c)?, j(d)?, j(e)?, j(f)?, j(g)?)
+            }
+            v1::Junctions::X8(a, b, c, d, e, f, g, h) => {
+                Self::X8(j(a)?, j(b)?, j(c)?, j(d)?, j(e)?, j(f)?, j(g)?, j(h)?)
+            }
+        })
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MultiLocation {
+    pub parents: u8,
+    pub interior: Junctions,
+}
+
+impl TryFrom<&v1::MultiLocation> for MultiLocation {
+    type Error = PublicError;
+
+    fn try_from(multilocation: &v1::MultiLocation) -> Result<Self> {
+        Ok(Self {
+            parents: multilocation.parents,
+            interior: Junctions::try_from(&multilocation.interior)?,
+        })
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AssetId {
+    Concrete(MultiLocation),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Fungibility {
+    Fungible(#[codec(compact)] u128),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MultiAsset {
+    pub id: AssetId,
+    pub fun: Fungibility,
+}
+
+impl TryFrom<&v1::MultiAsset> for MultiAsset {
+    type Error = PublicError;
+
+    fn try_from(asset: &v1::MultiAsset) -> Result<Self> {
+        let id = match &asset.id {
+            v1::AssetId::Concrete(multilocation) => {
+                AssetId::Concrete(MultiLocation::try_from(multilocation)?)
+            }
+            v1::AssetId::Abstract(_) => return Err(PublicError::UnsupportedXcmVersion),
+        };
+        let fun = match &asset.fun {
+            v1::Fungibility::Fungible(amount) => Fungibility::Fungible(*amount),
+            v1::Fungibility::NonFungible(_) => return Err(PublicError::UnsupportedXcmVersion),
+        };
+        Ok(Self { id, fun })
+    }
+}
+
+// Kept in the order of the V1 MultiAssets it came from, which are already sorted and deduplicated
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MultiAssets(pub Vec<MultiAsset>);
+
+impl TryFrom<&v1::MultiAssets> for MultiAssets {
+    type Error = PublicError;
+
+    fn try_from(assets: &v1::MultiAssets) -> Result<Self> {
+        assets
+            .inner()
+            .iter()
+            .map(MultiAsset::try_from)
+            .collect::<Result<Vec<MultiAsset>>>()
+            .map(Self)
+    }
+}
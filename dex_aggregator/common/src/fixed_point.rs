@@ -17,7 +17,7 @@
  */
 
 use core::cmp::min;
-use ink_prelude::string::String;
+use ink::prelude::string::String;
 use primitive_types::{U128, U256};
 
 // val = coef * 10^exp
@@ -35,6 +35,7 @@ pub enum PublicError {
     InvalidHex,
     InvalidPrefix,
     RequestFailed,
+    Ss58PrefixMismatch { expected: u16, found: u16 },
     UnknownSs58AddressFormat(Ss58AddressFormat),
 }
 pub(crate) type Result<T> = core::result::Result<T, PublicError>;
@@ -16,9 +16,11 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{format, vec::Vec};
+use ink::prelude::{format, vec::Vec};
+#[cfg(feature = "pink")]
 use pink_extension::chain_extension::{signing, SigType};
 use scale::{Decode, Encode};
+#[cfg(feature = "pink")]
 use sp_core_hashing;
 
 // Defines what algorithm is used to sign extrinsics
@@ -27,27 +29,34 @@ use sp_core_hashing;
 pub enum SignatureScheme {
     Ethereum,
     Sr25519,
+    Ed25519,
 }
 
 impl SignatureScheme {
+    // Signing and verification go through the pink chain extension
+    #[cfg(feature = "pink")]
     pub fn prefix_then_sign_msg(&self, msg: &[u8], secret_key: &[u8]) -> Vec<u8> {
         self.sign(&self.prefix_msg(msg), secret_key)
     }
 
+    #[cfg(feature = "pink")]
     pub fn sign(&self, msg: &[u8], secret_key: &[u8]) -> Vec<u8> {
         match self {
             SignatureScheme::Ethereum => {
                 signing::ecdsa_sign_prehashed(secret_key, sp_core_hashing::keccak_256(msg)).to_vec()
             }
             SignatureScheme::Sr25519 => signing::sign(msg, secret_key, SigType::Sr25519),
+            SignatureScheme::Ed25519 => signing::sign(msg, secret_key, SigType::Ed25519),
         }
     }
 
+    #[cfg(feature = "pink")]
     pub fn verify_unprefixed_msg(&self, pubkey: &[u8], msg: &[u8], signature: &[u8]) -> bool {
         let prefixed_msg = self.prefix_msg(msg);
         self.verify(pubkey, &prefixed_msg, signature)
     }
 
+    #[cfg(feature = "pink")]
     pub fn verify(&self, pubkey: &[u8], msg: &[u8], signature: &[u8]) -> bool {
         match self {
             SignatureScheme::Ethereum => {
@@ -58,6 +67,7 @@ impl SignatureScheme {
                 }
             }
             SignatureScheme::Sr25519 => signing::verify(msg, pubkey, signature, SigType::Sr25519),
+            SignatureScheme::Ed25519 => signing::verify(msg, pubkey, signature, SigType::Ed25519),
         }
     }
 
@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     format,
     string::{String, ToString},
     vec,
@@ -85,7 +85,7 @@ impl DynamoDbApi {
         let datestamp = datetime.format("%Y%m%d").to_string();
         let datetimestamp = datetime.format("%Y%m%dT%H%M%SZ").to_string();
 
-        // ink_env::debug_println!("Time: {}, {}", datestamp, datetimestamp);
+        // ink::env::debug_println!("Time: {}, {}", datestamp, datetimestamp);
 
         (datestamp, datetimestamp)
     }
@@ -133,7 +133,7 @@ impl DynamoDbApi {
             payload_hash
         );
 
-        // ink_env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
+        // ink::env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
         //  ----- Canonical request -----
         // PUT
         // /test/api-upload
@@ -154,7 +154,7 @@ impl DynamoDbApi {
             algorithm, amz_date, credential_scope, canonical_request_hash
         );
 
-        // ink_env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
+        // ink::env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
         //  ----- String to sign -----
         // AWS4-HMAC-SHA256
         // 19700101T000000Z
@@ -171,7 +171,7 @@ impl DynamoDbApi {
         let signature_bytes = hmac_sign(&signature_key, &string_to_sign.as_bytes());
         let signature = format!("{}", base16::encode_lower(&signature_bytes));
 
-        // ink_env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
+        // ink::env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
         //  ----- Signature -----
         // 84bf2db9f7a0007f5124cf2e9c0e1b7e1cec2b1b1b209ab9458387caa3b8da52
 
@@ -181,7 +181,7 @@ impl DynamoDbApi {
             algorithm, self.access_key, credential_scope, signed_headers, signature
         );
 
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     " ----- Authorization header ----- \nAuthorization: {}\n",
         //     &authorization_header
         // );
@@ -200,7 +200,7 @@ impl DynamoDbApi {
         ];
 
         let response = http_post!(request_url, payload, headers);
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     "Status = {}, Reason = {}, Json string response: {:?}",
         //     response.status_code,
         //     response.reason_phrase,
@@ -285,7 +285,7 @@ mod dynamodb_tests {
                 DynamoDbAction::GetItem,
             )
             .expect("Response expected");
-        ink_env::debug_println!("get_object post - {:?}", String::from_utf8(post_response));
+        ink::env::debug_println!("get_object post - {:?}", String::from_utf8(post_response));
     }
 
     #[test]
@@ -311,7 +311,7 @@ mod dynamodb_tests {
                 DynamoDbAction::UpdateItem,
             )
             .expect("Response expected");
-        ink_env::debug_println!(
+        ink::env::debug_println!(
             "update_object post - {:?}",
             String::from_utf8(post_response)
         );
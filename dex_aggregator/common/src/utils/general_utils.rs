@@ -17,7 +17,7 @@
  */
 
 use core::fmt::Write;
-use ink_prelude::{
+use ink::prelude::{
     string::{String, ToString},
     vec::Vec,
 };
@@ -16,13 +16,18 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{format, string::String, vec, vec::Vec};
-use pink_extension::http_post;
+use ink::prelude::{format, string::String, vec, vec::Vec};
+use pink_extension::{chain_extension::HttpRequest, http_get, http_post};
 #[allow(unused_imports)]
 use scale::Encode;
 
 use crate::{PublicError, Result};
 
+// The runtime caps how many requests go out in one batch, so larger ones are split up
+const MAX_BATCH_HTTP_REQUESTS: usize = 5;
+// For a whole batch, i.e. the slowest request in it
+const BATCH_HTTP_TIMEOUT_MILLIS: u64 = 10_000;
+
 pub fn http_post_wrapper(url: &str, data: Vec<u8>) -> Result<Vec<u8>> {
     let content_length = format!("{}", data.len());
     let headers: Vec<(String, String)> = vec![
@@ -32,7 +37,7 @@ pub fn http_post_wrapper(url: &str, data: Vec<u8>) -> Result<Vec<u8>> {
 
     let response = http_post!(url, data, headers);
     if response.body.len() > 4_000 {
-        ink_env::debug_println!(
+        ink::env::debug_println!(
             "{}: total = {} bytes, body = {} bytes",
             url,
             response.encoded_size(),
@@ -46,3 +51,41 @@ pub fn http_post_wrapper(url: &str, data: Vec<u8>) -> Result<Vec<u8>> {
     let body = response.body;
     Ok(body)
 }
+
+pub fn http_get_wrapper(url: &str) -> Result<Vec<u8>> {
+    let headers: Vec<(String, String)> = vec![("Accept".into(), "application/json".into())];
+    let response = http_get!(url, headers);
+    if response.status_code != 200 {
+        return Err(PublicError::RequestFailed);
+    }
+    Ok(response.body)
+}
+
+// Like http_post_wrapper, but the requests are sent concurrently rather than one after the
+// other. Each request (url, data) gets its own response, in order
+pub fn http_post_batch_wrapper(requests: Vec<(String, Vec<u8>)>) -> Vec<Result<Vec<u8>>> {
+    let mut responses = Vec::new();
+    for batch in requests.chunks(MAX_BATCH_HTTP_REQUESTS) {
+        let http_requests: Vec<HttpRequest> = batch
+            .iter()
+            .map(|(url, data)| {
+                let headers: Vec<(String, String)> = vec![
+                    ("Content-Type".into(), "application/json".into()),
+                    ("Content-Length".into(), format!("{}", data.len())),
+                ];
+                HttpRequest::new(url, "POST", headers, data.clone())
+            })
+            .collect();
+        match pink_extension::ext().batch_http_request(http_requests, BATCH_HTTP_TIMEOUT_MILLIS) {
+            Ok(batch_responses) => {
+                responses.extend(batch_responses.into_iter().map(|response| match response {
+                    Ok(response) if response.status_code == 200 => Ok(response.body),
+                    _ => Err(PublicError::RequestFailed),
+                }))
+            }
+            // e.g. the batch timed out as a whole
+            Err(_) => responses.extend(batch.iter().map(|_| Err(PublicError::RequestFailed))),
+        }
+    }
+    responses
+}
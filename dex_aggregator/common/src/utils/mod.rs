@@ -16,8 +16,15 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+#[cfg(feature = "pink")]
 pub mod dynamodb_api;
 pub mod general_utils;
+#[cfg(feature = "pink")]
 pub mod http_request;
+#[cfg(feature = "pink")]
+pub mod remote_signer_api;
+#[cfg(feature = "pink")]
 pub mod s3_api;
 pub mod ss58_utils;
+#[cfg(feature = "pink")]
+pub mod threshold_aggregator_api;
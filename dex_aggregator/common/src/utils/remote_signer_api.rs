@@ -0,0 +1,153 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use ink::prelude::{format, string::String, vec, vec::Vec};
+use scale::{Decode, Encode};
+
+// To make HTTP requests
+use pink_extension::http_post;
+
+// To authenticate requests
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{signature_scheme::SignatureScheme, utils::general_utils::slice_to_hex_string};
+
+/// Client for an external (e.g. HSM-backed) signing service, for operators that do not want
+/// raw escrow keys in contract storage.
+///
+/// Requests are POSTed as {"key": "0x<address>", "scheme": "...", "payload": "0x..."} and the
+/// response body is the hex-encoded signature. For SignatureScheme::Ethereum the payload is
+/// already the 32-byte Keccak-256 digest (scheme "ecdsa_prehashed") and the signature is
+/// r || s || v with v in {0, 1}.
+/// Every request carries x-privadex-timestamp and
+/// x-privadex-signature = hex(HMAC-SHA256(auth_secret, "<timestamp>\n<body>")) so that the
+/// service can reject forged and replayed requests
+#[derive(Encode, Decode, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RemoteSignerApi {
+    signer_url: String,
+    auth_secret: String,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RemoteSignerError {
+    InvalidSignature,
+    RequestFailed,
+}
+
+// Do not print the auth secret
+impl core::fmt::Debug for RemoteSignerApi {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "RemoteSignerApi({})", self.signer_url)
+    }
+}
+
+impl RemoteSignerApi {
+    pub fn new(signer_url: String, auth_secret: String) -> Self {
+        Self {
+            signer_url,
+            auth_secret,
+        }
+    }
+
+    pub fn sign(
+        &self,
+        timestamp_millis: u64,
+        key: &[u8], // address or public key of the signing account
+        sig_scheme: SignatureScheme,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, RemoteSignerError> {
+        let body = get_request_body(key, sig_scheme, payload);
+        let headers: Vec<(String, String)> = vec![
+            ("Content-Type".into(), "application/json".into()),
+            ("Content-Length".into(), format!("{}", body.len())),
+            (
+                "x-privadex-timestamp".into(),
+                format!("{}", timestamp_millis),
+            ),
+            (
+                "x-privadex-signature".into(),
+                self.get_request_signature(timestamp_millis, &body),
+            ),
+        ];
+
+        let response = http_post!(self.signer_url.clone(), body.into_bytes(), headers);
+        if response.status_code != 200 {
+            return Err(RemoteSignerError::RequestFailed);
+        }
+        let signature_hex =
+            String::from_utf8(response.body).map_err(|_| RemoteSignerError::InvalidSignature)?;
+        let signature_hex = signature_hex.trim();
+        hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex))
+            .map_err(|_| RemoteSignerError::InvalidSignature)
+    }
+
+    fn get_request_signature(&self, timestamp_millis: u64, body: &str) -> String {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(self.auth_secret.as_bytes())
+            .expect("Could not instantiate HMAC instance");
+        mac.update(format!("{}\n{}", timestamp_millis, body).as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+// Create alias for HMAC-SHA256
+type HmacSha256 = Hmac<Sha256>;
+
+fn get_request_body(key: &[u8], sig_scheme: SignatureScheme, payload: &[u8]) -> String {
+    let scheme = match sig_scheme {
+        SignatureScheme::Ethereum => "ecdsa_prehashed",
+        SignatureScheme::Sr25519 => "sr25519",
+        SignatureScheme::Ed25519 => "ed25519",
+    };
+    format!(
+        "{{\"key\":\"{}\",\"scheme\":\"{}\",\"payload\":\"{}\"}}",
+        slice_to_hex_string(key),
+        scheme,
+        slice_to_hex_string(payload)
+    )
+}
+
+#[cfg(test)]
+mod remote_signer_api_tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_request_body() {
+        assert_eq!(
+            get_request_body(
+                &hex!("05a81d8564a3ea298660e34e03e5eff9a29d7a2a"),
+                SignatureScheme::Ethereum,
+                &[0xab; 4],
+            ),
+            r#"{"key":"0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a","scheme":"ecdsa_prehashed","payload":"0xabababab"}"#
+        );
+    }
+
+    #[test]
+    fn test_request_signature() {
+        // Matches `echo -n $'1680307200000\n{}' | openssl dgst -sha256 -hmac secret`
+        let api = RemoteSignerApi::new("https://signer.example".into(), "secret".into());
+        assert_eq!(
+            api.get_request_signature(1_680_307_200_000, "{}"),
+            "420382807d14882a4a2412954550948473f71969501f8231cf55ebb6cc87f63b"
+        );
+    }
+}
@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     format,
     string::{String, ToString},
     vec,
@@ -58,6 +58,10 @@ pub enum Error {
     DecryptionFailed,
     PlatformNotFound,
     CredentialsNotSealed,
+    // A conditional put found the object changed since the ETag it was given
+    PreconditionFailed,
+    // The GET succeeded, but there is no object under the key
+    NotFound,
 }
 
 impl S3Api {
@@ -78,7 +82,7 @@ impl S3Api {
         let datestamp = datetime.format("%Y%m%d").to_string();
         let datetimestamp = datetime.format("%Y%m%dT%H%M%SZ").to_string();
 
-        // ink_env::debug_println!("Time: {}, {}", datestamp, datetimestamp);
+        // ink::env::debug_println!("Time: {}, {}", datestamp, datetimestamp);
 
         (datestamp, datetimestamp)
     }
@@ -93,6 +97,20 @@ impl S3Api {
         bucket_name: String,
         region: String,
     ) -> Result<Vec<u8>, Error> {
+        self.get_object_raw_with_etag(timestamp_millis, platform, object_key, bucket_name, region)
+            .map(|(bytes, _)| bytes)
+    }
+
+    /// Like get_object_raw, but also returns the object's ETag (if the platform sent one) so
+    /// that it can be passed to put_object_raw_if_match
+    pub fn get_object_raw_with_etag(
+        &self,
+        timestamp_millis: u64,
+        platform: String,
+        object_key: String,
+        bucket_name: String,
+        region: String,
+    ) -> Result<(Vec<u8>, Option<String>), Error> {
         // Set request values
         let method = "GET";
         let service = "s3";
@@ -136,7 +154,7 @@ impl S3Api {
             payload_hash
         );
 
-        // ink_env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
+        // ink::env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
         //  ----- Canonical request -----
         // GET
         // /test/api-upload
@@ -157,7 +175,7 @@ impl S3Api {
             algorithm, amz_date, credential_scope, canonical_request_hash
         );
 
-        // ink_env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
+        // ink::env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
         //  ----- String to sign -----
         // AWS4-HMAC-SHA256
         // 19700101T000000Z
@@ -174,7 +192,7 @@ impl S3Api {
         let signature_bytes = hmac_sign(&signature_key, &string_to_sign.as_bytes());
         let signature = format!("{}", base16::encode_lower(&signature_bytes));
 
-        // ink_env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
+        // ink::env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
         //  ----- Signature -----
         // 485e174a7fed1691de34f116a968981709ed5a00f4975470bd3d0dd06ccd3e1d
 
@@ -184,7 +202,7 @@ impl S3Api {
             algorithm, self.access_key, credential_scope, signed_headers, signature
         );
 
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     " ----- Authorization header ----- \nAuthorization: {}\n",
         //     &authorization_header
         // );
@@ -209,11 +227,15 @@ impl S3Api {
         };
         let response = http_get!(request_url, headers);
 
-        // ink_env::debug_println!("Get response: {}", response.reason_phrase);
+        // ink::env::debug_println!("Get response: {}", response.reason_phrase);
 
+        if response.status_code == 404 {
+            return Err(Error::NotFound);
+        }
         if response.status_code != 200 {
             return Err(Error::RequestFailed);
         }
+        let etag = get_etag(&response.headers);
 
         // Generate key and nonce
         let key_bytes: Vec<u8> = signing::derive_sr25519_key(object_key.as_bytes())[..32].to_vec();
@@ -224,8 +246,10 @@ impl S3Api {
         // Decrypt payload
         let cipher = Aes256GcmSiv::new(key.into());
         let decrypted_byte = cipher.decrypt(&nonce, response.body.as_ref());
-        ink_env::debug_println!("Decrypted result: {:?}", decrypted_byte);
-        decrypted_byte.or(Err(Error::DecryptionFailed))
+        ink::env::debug_println!("Decrypted result: {:?}", decrypted_byte);
+        decrypted_byte
+            .map(|bytes| (bytes, etag))
+            .or(Err(Error::DecryptionFailed))
     }
 
     pub fn get_object_str(
@@ -252,8 +276,33 @@ impl S3Api {
         region: String,
         payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
+        self.put_object_raw_if_match(
+            timestamp_millis,
+            platform,
+            object_key,
+            bucket_name,
+            region,
+            payload,
+            None,
+        )
+        .map(|(body, _)| body)
+    }
+
+    /// Like put_object_raw, but if if_match is set the put only goes through if the object's
+    /// ETag still is if_match (else PreconditionFailed). Returns the new ETag along with the
+    /// response body
+    pub fn put_object_raw_if_match(
+        &self,
+        timestamp_millis: u64,
+        platform: String,
+        object_key: String,
+        bucket_name: String,
+        region: String,
+        payload: &[u8],
+        if_match: Option<&str>,
+    ) -> Result<(Vec<u8>, Option<String>), Error> {
         // Generate key and nonce
-        // ink_env::debug_println!("Key: {:?}", object_key.as_bytes());
+        // ink::env::debug_println!("Key: {:?}", object_key.as_bytes());
         let key_bytes: Vec<u8> = signing::derive_sr25519_key(object_key.as_bytes())[..32].to_vec();
         let key: &GenericArray<u8, U32> = GenericArray::from_slice(&key_bytes);
         let nonce_bytes: Vec<u8> = self.access_key.as_bytes()[..12].to_vec();
@@ -308,7 +357,7 @@ impl S3Api {
             payload_hash
         );
 
-        // ink_env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
+        // ink::env::debug_println!(" ----- Canonical request -----  \n{}\n", canonical_request);
         //  ----- Canonical request -----
         // PUT
         // /test/api-upload
@@ -329,7 +378,7 @@ impl S3Api {
             algorithm, amz_date, credential_scope, canonical_request_hash
         );
 
-        // ink_env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
+        // ink::env::debug_println!(" ----- String to sign ----- \n{}\n", string_to_sign);
         //  ----- String to sign -----
         // AWS4-HMAC-SHA256
         // 19700101T000000Z
@@ -346,7 +395,7 @@ impl S3Api {
         let signature_bytes = hmac_sign(&signature_key, &string_to_sign.as_bytes());
         let signature = format!("{}", base16::encode_lower(&signature_bytes));
 
-        // ink_env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
+        // ink::env::debug_println!(" ----- Signature ----- \n{}\n", &signature);
         //  ----- Signature -----
         // 84bf2db9f7a0007f5124cf2e9c0e1b7e1cec2b1b1b209ab9458387caa3b8da52
 
@@ -356,7 +405,7 @@ impl S3Api {
             algorithm, self.access_key, credential_scope, signed_headers, signature
         );
 
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     " ----- Authorization header ----- \nAuthorization: {}\n",
         //     &authorization_header
         // );
@@ -372,7 +421,7 @@ impl S3Api {
             format!("https://{}/{}/{}", host, bucket_name, object_key)
         };
 
-        let headers: Vec<(String, String)> = vec![
+        let mut headers: Vec<(String, String)> = vec![
             ("Host".into(), host),
             ("Authorization".into(), authorization_header),
             ("Content-Length".into(), content_length),
@@ -380,14 +429,22 @@ impl S3Api {
             ("x-amz-content-sha256".into(), payload_hash),
             ("x-amz-date".into(), amz_date),
         ];
+        // Doesn't need to be signed
+        if let Some(etag) = if_match {
+            headers.push(("If-Match".into(), etag.to_string()));
+        }
 
         let response = http_put!(request_url, encrypted_bytes, headers);
 
+        // S3 answers 409 if another conditional put on the object is in flight
+        if response.status_code == 412 || response.status_code == 409 {
+            return Err(Error::PreconditionFailed);
+        }
         if response.status_code != 200 {
             return Err(Error::RequestFailed);
         }
 
-        Ok(response.body)
+        Ok((response.body, get_etag(&response.headers)))
     }
 
     pub fn put_object_str(
@@ -410,6 +467,14 @@ impl S3Api {
     }
 }
 
+// Header names are case-insensitive, and gateways differ in how they send this one
+fn get_etag(headers: &[(String, String)]) -> Option<String> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("etag"))
+        .map(|(_, value)| value.clone())
+}
+
 // Create alias for HMAC-SHA256
 type HmacSha256 = Hmac<Sha256>;
 
@@ -485,7 +550,7 @@ mod tests {
             "us-east-1".to_string(),
             "This is a test comment234".to_string(),
         );
-        ink_env::debug_println!("put_object put - {:?}", put_response);
+        ink::env::debug_println!("put_object put - {:?}", put_response);
 
         let get_response = api.get_object_str(
             timestamp_millis,
@@ -494,7 +559,7 @@ mod tests {
             "transfer-txn".to_string(),
             "us-east-1".to_string(),
         );
-        ink_env::debug_println!("put_object get - {:?}", get_response);
+        ink::env::debug_println!("put_object get - {:?}", get_response);
     }
 
     #[cfg(feature = "s3-live-test")]
@@ -518,7 +583,7 @@ mod tests {
             "transfer-txn".to_string(),
             "us-east-1".to_string(),
         );
-        ink_env::debug_println!("get_object get - {:?}", get_response);
+        ink::env::debug_println!("get_object get - {:?}", get_response);
     }
 
     #[test]
@@ -548,4 +613,14 @@ mod tests {
         assert_eq!(payload.as_bytes(), decrypted_text);
         assert_eq!(payload, String::from_utf8_lossy(&decrypted_text));
     }
+
+    #[test]
+    fn test_get_etag() {
+        let headers = vec![
+            ("Content-Length".to_string(), "10".to_string()),
+            ("etag".to_string(), "\"abc123\"".to_string()),
+        ];
+        assert_eq!(get_etag(&headers), Some("\"abc123\"".to_string()));
+        assert_eq!(get_etag(&headers[..1]), None);
+    }
 }
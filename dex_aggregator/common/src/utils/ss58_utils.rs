@@ -23,7 +23,6 @@
  */
 pub use ss58_registry::{from_known_address_format, Ss58AddressFormat, Ss58AddressFormatRegistry};
 
-#[cfg(not(feature = "std"))]
 use crate::{PublicError, Result};
 
 const PREFIX: &[u8] = b"SS58PRE";
@@ -40,8 +39,10 @@ pub use sp_core::crypto::Ss58Codec;
 
 #[cfg(not(feature = "std"))]
 use base58::{FromBase58, ToBase58};
+use ink::prelude::string::String;
 #[cfg(not(feature = "std"))]
-use ink_prelude::{string::String, vec};
+use ink::prelude::vec;
+use sp_core::crypto::AccountId32;
 
 /// Key that can be encoded to/from SS58.
 ///
@@ -160,3 +161,150 @@ pub trait Ss58Codec: Sized + AsMut<[u8]> + AsRef<[u8]> + sp_core::crypto::ByteAr
 
 #[cfg(not(feature = "std"))]
 impl Ss58Codec for sp_core::crypto::AccountId32 {}
+
+// Frontier's HashedAddressMapping (used by Astar) prefixes the H160 with this before hashing
+const EVM_ACCOUNT_MAPPING_PREFIX: &[u8] = b"evm:";
+
+/// Encodes a 32-byte public key as an SS58 address with the given prefix
+pub fn encode_ss58(pubkey: &[u8; 32], prefix: u16) -> String {
+    AccountId32::new(*pubkey).to_ss58check_with_version(Ss58AddressFormat::custom(prefix))
+}
+
+/// Decodes an SS58 address into its public key and prefix, whatever the prefix is
+pub fn decode_ss58(address: &str) -> Result<([u8; 32], u16)> {
+    let (account, format) =
+        AccountId32::from_ss58check_with_version(address).map_err(to_public_error)?;
+    Ok((account.into(), u16::from(format)))
+}
+
+/// Same as decode_ss58 but rejects an address encoded for a different chain, since users
+/// frequently paste e.g. their Polkadot address where a Moonbeam one is expected
+pub fn decode_ss58_for_prefix(address: &str, expected_prefix: u16) -> Result<[u8; 32]> {
+    let (pubkey, prefix) = decode_ss58(address)?;
+    if prefix != expected_prefix {
+        return Err(PublicError::Ss58PrefixMismatch {
+            expected: expected_prefix,
+            found: prefix,
+        });
+    }
+    Ok(pubkey)
+}
+
+/// Re-encodes an SS58 address (any prefix) for another chain's prefix
+pub fn reencode_ss58(address: &str, prefix: u16) -> Result<String> {
+    let (pubkey, _) = decode_ss58(address)?;
+    Ok(encode_ss58(&pubkey, prefix))
+}
+
+/// The substrate account that an H160 maps to on chains with a hashed EVM account mapping.
+/// This is one way, so there is no H160 for an arbitrary substrate account
+pub fn h160_to_mapped_account(h160: &[u8; 20]) -> [u8; 32] {
+    let mut data = EVM_ACCOUNT_MAPPING_PREFIX.to_vec();
+    data.extend_from_slice(h160);
+    sp_core_hashing::blake2_256(&data)
+}
+
+pub fn h160_to_mapped_ss58(h160: &[u8; 20], prefix: u16) -> String {
+    encode_ss58(&h160_to_mapped_account(h160), prefix)
+}
+
+#[cfg(feature = "std")]
+fn to_public_error(error: sp_core::crypto::PublicError) -> PublicError {
+    use sp_core::crypto::PublicError as CodecError;
+    match error {
+        CodecError::BadBase58 => PublicError::BadBase58,
+        CodecError::BadLength => PublicError::BadLength,
+        CodecError::FormatNotAllowed => PublicError::FormatNotAllowed,
+        CodecError::InvalidChecksum => PublicError::InvalidChecksum,
+        CodecError::InvalidPrefix => PublicError::InvalidPrefix,
+        CodecError::UnknownSs58AddressFormat(format) => {
+            PublicError::UnknownSs58AddressFormat(format)
+        }
+        // The remaining errors are about derivation paths, which from_ss58check never parses
+        _ => PublicError::BadBase58,
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn to_public_error(error: PublicError) -> PublicError {
+    error
+}
+
+#[cfg(test)]
+mod ss58_utils_tests {
+    use super::*;
+    use hex_literal::hex;
+
+    const ALICE: [u8; 32] =
+        hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+
+    #[test]
+    fn test_encode_ss58() {
+        assert_eq!(
+            encode_ss58(&ALICE, 42),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(
+            encode_ss58(&ALICE, 0),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        // Two-byte prefix (Moonbeam)
+        assert_eq!(
+            encode_ss58(&ALICE, 1284),
+            "VdvKmYJfD4VXA9fzz1SbmCo2eYHSzUFbaDCZSuaNKJAe8YNg6"
+        );
+    }
+
+    #[test]
+    fn test_decode_ss58() {
+        assert_eq!(
+            decode_ss58("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F").unwrap(),
+            (ALICE, 2)
+        );
+        assert_eq!(
+            decode_ss58("VdvKmYJfD4VXA9fzz1SbmCo2eYHSzUFbaDCZSuaNKJAe8YNg6").unwrap(),
+            (ALICE, 1284)
+        );
+        assert_eq!(
+            decode_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"),
+            Err(PublicError::InvalidChecksum)
+        );
+        assert_eq!(decode_ss58("0x1234"), Err(PublicError::BadBase58));
+    }
+
+    #[test]
+    fn test_decode_ss58_for_prefix() {
+        assert_eq!(
+            decode_ss58_for_prefix("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 0),
+            Ok(ALICE)
+        );
+        assert_eq!(
+            decode_ss58_for_prefix("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 5),
+            Err(PublicError::Ss58PrefixMismatch {
+                expected: 5,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_reencode_ss58() {
+        assert_eq!(
+            reencode_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 5).unwrap(),
+            "ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8"
+        );
+    }
+
+    #[test]
+    fn test_h160_to_mapped_account() {
+        let h160 = hex!("05a81d8564a3ea298660e34e03e5eff9a29d7a2a");
+        assert_eq!(
+            h160_to_mapped_account(&h160),
+            hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be")
+        );
+        assert_eq!(
+            h160_to_mapped_ss58(&h160, 5),
+            "XmmrKUnJjEsupddUPCKQuBkUqEFiCm6jJPZaaYK5T25f9w7"
+        );
+    }
+}
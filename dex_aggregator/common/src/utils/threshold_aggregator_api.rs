@@ -0,0 +1,107 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String, vec, vec::Vec};
+use scale::{Decode, Encode};
+
+// To make HTTP requests
+use pink_extension::http_post;
+
+use crate::utils::general_utils::slice_to_hex_string;
+
+/// Client for the service that combines the escrow's partial ECDSA signatures (one per
+/// threshold party) into a regular Ethereum signature.
+///
+/// Requests are POSTed as
+/// {"msg_hash": "0x...", "partials": [{"party": 0, "sig": "0x..."}, ...]}
+/// and the response body is the hex-encoded r || s || v with v in {0, 1}.
+/// The service is expected to check each partial against the party's registered public
+/// share, so it does not need to be trusted with any key material
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ThresholdAggregatorApi {
+    aggregator_url: String,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ThresholdAggregatorError {
+    InvalidSignature,
+    RequestFailed,
+}
+
+impl ThresholdAggregatorApi {
+    pub fn new(aggregator_url: String) -> Self {
+        Self { aggregator_url }
+    }
+
+    pub fn aggregate_ecdsa(
+        &self,
+        msg_hash: &[u8; 32],
+        partials: &[(u8 /* party index */, Vec<u8>)],
+    ) -> Result<[u8; 65], ThresholdAggregatorError> {
+        let body = get_request_body(msg_hash, partials);
+        let headers: Vec<(String, String)> = vec![
+            ("Content-Type".into(), "application/json".into()),
+            ("Content-Length".into(), format!("{}", body.len())),
+        ];
+
+        let response = http_post!(self.aggregator_url.clone(), body.into_bytes(), headers);
+        if response.status_code != 200 {
+            return Err(ThresholdAggregatorError::RequestFailed);
+        }
+        let signature_hex = String::from_utf8(response.body)
+            .map_err(|_| ThresholdAggregatorError::InvalidSignature)?;
+        let signature_hex = signature_hex.trim();
+        hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex))
+            .map_err(|_| ThresholdAggregatorError::InvalidSignature)?
+            .try_into()
+            .map_err(|_| ThresholdAggregatorError::InvalidSignature)
+    }
+}
+
+fn get_request_body(msg_hash: &[u8; 32], partials: &[(u8, Vec<u8>)]) -> String {
+    let partials_json: Vec<String> = partials
+        .iter()
+        .map(|(party_index, sig)| {
+            format!(
+                "{{\"party\":{},\"sig\":\"{}\"}}",
+                party_index,
+                slice_to_hex_string(sig)
+            )
+        })
+        .collect();
+    format!(
+        "{{\"msg_hash\":\"{}\",\"partials\":[{}]}}",
+        slice_to_hex_string(msg_hash),
+        partials_json.join(",")
+    )
+}
+
+#[cfg(test)]
+mod threshold_aggregator_api_tests {
+    use super::*;
+
+    #[test]
+    fn test_request_body() {
+        assert_eq!(
+            get_request_body(&[0x11; 32], &[(0, vec![0xab, 0xcd]), (2, vec![0xef])]),
+            r#"{"msg_hash":"0x1111111111111111111111111111111111111111111111111111111111111111","partials":[{"party":0,"sig":"0xabcd"},{"party":2,"sig":"0xef"}]}"#
+        );
+    }
+}
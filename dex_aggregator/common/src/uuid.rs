@@ -17,7 +17,7 @@
  */
 
 use core::{fmt, str::FromStr};
-use ink_prelude::string::String;
+use ink::prelude::string::String;
 use scale::{Decode, Encode};
 use uuid;
 
@@ -0,0 +1,47 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use std::{env, fs, process};
+
+use privadex_execution_plan::schema_check::check_exec_plan_blob;
+
+// Checks ExecutionPlan objects pulled from the execution-plan bucket against the ExecutionPlan
+// of this build, e.g. before deploying a change to it. Exits with 1 if any of them don't match
+// cargo run --example privadex_check_stored_plan -- plan1.scale plan2.scale ...
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <stored plan>...", args[0]);
+        process::exit(2);
+    }
+
+    let mut num_failed = 0;
+    for path in &args[1..] {
+        let plan_bytes = fs::read(path).expect("Could not read stored plan");
+        match check_exec_plan_blob(&plan_bytes) {
+            Ok(exec_plan) => println!("OK: {} (plan {})", path, exec_plan.uuid.to_hex_string()),
+            Err(err) => {
+                println!("INCOMPATIBLE: {}: {:?}", path, err);
+                num_failed += 1;
+            }
+        }
+    }
+    if num_failed > 0 {
+        process::exit(1);
+    }
+}
@@ -17,7 +17,7 @@
  */
 
 use hex_literal::hex;
-use ink_env::debug_println;
+use ink::env::debug_println;
 
 use privadex_chain_metadata::{
     common::{
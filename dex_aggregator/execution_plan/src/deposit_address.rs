@@ -0,0 +1,151 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::Encode;
+
+use privadex_chain_metadata::{
+    chain_info::DepositForwarderFactory,
+    common::{Amount, EthAddress, UniversalTokenId},
+};
+
+// Domain separator so that salts cannot collide with other hashes of the same fields
+const DEPOSIT_SALT_PREFIX: &[u8] = b"privadex/deposit";
+const DEPOSIT_REMARK_PREFIX: &[u8] = b"privadex/remark";
+
+pub type DepositSalt = [u8; 32];
+// Short enough to paste into an exchange's memo field as hex
+pub type DepositRemark = [u8; 16];
+
+// The salt is derived from the swap request rather than the ExecutionPlan UUID: the UUID
+// depends on the latest pool state, and the user needs to know the deposit address before
+// sending funds (start_swap recomputes the plan afterwards).
+// Identical requests share a deposit address, but the prestart txn hash can only be
+// registered once so a deposit is still never matched to two plans
+pub fn get_deposit_salt(
+    src_addr: &EthAddress,
+    dest_addr: &EthAddress,
+    src_token: &UniversalTokenId,
+    dest_token: &UniversalTokenId,
+    amount_in: Amount,
+) -> DepositSalt {
+    let mut preimage: Vec<u8> = DEPOSIT_SALT_PREFIX.into();
+    preimage.extend_from_slice(&(src_addr, dest_addr, src_token, dest_token, amount_in).encode());
+    sp_core_hashing::keccak_256(&preimage)
+}
+
+// On chains without a deposit forwarder factory, users who can't send EVM txns deposit into the
+// shared escrow and tag the deposit with this remark instead. Unlike the salt, identical
+// requests must not share a remark (the escrow address is shared too), so the caller mixes in
+// fresh entropy. The request fields only keep remarks apart if the entropy ever repeats
+pub fn get_deposit_remark(
+    src_addr: &EthAddress,
+    amount_in: Amount,
+    entropy: &[u8],
+) -> DepositRemark {
+    let mut preimage: Vec<u8> = DEPOSIT_REMARK_PREFIX.into();
+    preimage.extend_from_slice(&(src_addr, amount_in).encode());
+    preimage.extend_from_slice(entropy);
+    let hash = sp_core_hashing::keccak_256(&preimage);
+    hash[..16].try_into().expect("Hash is 32 bytes")
+}
+
+// Standard CREATE2 derivation (EIP-1014):
+// keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12..]
+pub fn get_create2_address(
+    deployer: &EthAddress,
+    salt: &DepositSalt,
+    init_code_hash: &[u8; 32],
+) -> EthAddress {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(deployer.as_bytes());
+    preimage[21..53].copy_from_slice(salt);
+    preimage[53..85].copy_from_slice(init_code_hash);
+    let hash = sp_core_hashing::keccak_256(&preimage);
+    EthAddress::from_slice(&hash[12..])
+}
+
+// The address the user deposits into. It is known before the forwarder is deployed
+pub fn get_deposit_address(factory: &DepositForwarderFactory, salt: &DepositSalt) -> EthAddress {
+    get_create2_address(&factory.addr, salt, &factory.forwarder_init_code_hash)
+}
+
+#[cfg(test)]
+mod deposit_address_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::registry::token::universal_token_id_registry;
+
+    use super::*;
+
+    // Example 5 from EIP-1014
+    #[test]
+    fn test_create2_address() {
+        let deployer = EthAddress {
+            0: hex!("00000000000000000000000000000000deadbeef"),
+        };
+        let salt = hex!("00000000000000000000000000000000000000000000000000000000cafebabe");
+        let init_code_hash = sp_core_hashing::keccak_256(&hex!("deadbeef"));
+        assert_eq!(
+            get_create2_address(&deployer, &salt, &init_code_hash),
+            EthAddress {
+                0: hex!("60f3f640a8508fC6a86d45DF051962668E1e8AC7")
+            }
+        );
+    }
+
+    #[test]
+    fn test_deposit_address_is_unique_per_request() {
+        let factory = DepositForwarderFactory {
+            addr: EthAddress {
+                0: hex!("00000000000000000000000000000000deadbeef"),
+            },
+            forwarder_init_code_hash: [7u8; 32],
+        };
+        let user = EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        };
+        let salt = |amount_in| {
+            get_deposit_salt(
+                &user,
+                &user,
+                &universal_token_id_registry::GLMR_NATIVE,
+                &universal_token_id_registry::DOT_NATIVE,
+                amount_in,
+            )
+        };
+        assert_eq!(
+            get_deposit_address(&factory, &salt(100)),
+            get_deposit_address(&factory, &salt(100))
+        );
+        assert_ne!(
+            get_deposit_address(&factory, &salt(100)),
+            get_deposit_address(&factory, &salt(101))
+        );
+    }
+
+    #[test]
+    fn test_deposit_remark_is_unique_per_entropy() {
+        let user = EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        };
+        let remark = |entropy: &[u8]| get_deposit_remark(&user, 100, entropy);
+        assert_eq!(remark(&[1, 2, 3]), remark(&[1, 2, 3]));
+        assert_ne!(remark(&[1, 2, 3]), remark(&[1, 2, 4]));
+    }
+}
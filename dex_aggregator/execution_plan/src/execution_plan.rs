@@ -17,15 +17,23 @@
  */
 
 use core::fmt::{self, Debug};
-use ink_prelude::vec::Vec;
+use ink::prelude::{string::String, vec::Vec};
 use scale::{Decode, Encode};
 use xcm::latest::MultiLocation;
 
-use privadex_common::uuid::Uuid;
+use privadex_common::{utils::general_utils::mul_ratio_u128, uuid::Uuid};
+use privadex_routing::graph::fee_breakdown::FeeBreakdown;
 
-use privadex_chain_metadata::common::{
-    Amount, BlockNum, EthAddress, EthTxnHash, Nonce, SubstrateExtrinsicHash, UniversalAddress,
-    UniversalChainId, UniversalTokenId,
+use crate::deposit_address::{DepositRemark, DepositSalt};
+
+use privadex_chain_metadata::{
+    bridge::WormholeChainId,
+    common::{
+        Amount, BlockNum, ChainTokenId, EthAddress, EthTxnHash, MillisSinceEpoch, Nonce,
+        SubstrateExtrinsicHash, UniversalAddress, UniversalChainId, UniversalTokenId,
+        XcmMessageHash,
+    },
+    registry::aggregator::AggregatorId,
 };
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
@@ -33,17 +41,207 @@ use privadex_chain_metadata::common::{
 pub struct ExecutionPlan {
     pub uuid: Uuid,
     pub paths: Vec<ExecutionPath>,
-    pub prestart_user_to_escrow_transfer: ExecutionStep, // EthSend/ERC20Transfer from user to escrow
+    // EthSend/ERC20Transfer from user to escrow, or SubstrateRemarkDeposit if the user
+    // deposited with a remark-tagged batch (see deposit_address::get_deposit_remark)
+    pub prestart_user_to_escrow_transfer: ExecutionStep,
+    // EthDepositSweep from the plan's deposit address into the escrow. Only set if the src
+    // chain has a deposit forwarder factory, in which case the prestart step sends to the
+    // deposit address instead of the escrow
+    pub deposit_sweep: Option<ExecutionStep>,
     pub postend_escrow_to_user_transfer: ExecutionStep, // EthSend/ERC20Transfer from escrow to user
+    // EthSend/ERC20Transfer of the src token from escrow back to the user. Only used if the
+    // plan misses deadline_millis, to return the funds of the paths that never started
+    pub refund_escrow_to_user_transfer: ExecutionStep,
+    pub path_execution_mode: PathExecutionMode,
+    pub execution_policy: ExecutionPolicy,
+    // Past this timestamp the executor stops advancing the paths and refunds the user
+    pub deadline_millis: MillisSinceEpoch,
+    // Last time a step changed status (initially when the plan was started). The stuck plan
+    // watchdog compares it against the worst case duration of the steps in flight
+    pub last_progress_millis: MillisSinceEpoch,
+    // Staging-only: every step is signed but never broadcast, and the executor records
+    // what it would have sent instead (see the executor's paper_trade module)
+    pub paper_trade: bool,
+    // Set if the user gave a name instead of an address for the destination
+    pub dest_name: Option<ResolvedDestinationName>,
+    // Opaque bytes from the integrator (e.g. their order ID), at most MAX_PLAN_METADATA_LEN.
+    // Also the tag the plan can be looked up by
+    pub metadata: Option<Vec<u8>>,
+    // What the user was quoted when the plan was computed
+    pub quote: Option<PlanQuote>,
+    // Block the prestart deposit was included in, set once it is the src chain's
+    // prestart_confirmation_depth deep and still on the canonical chain. Nothing escrow-side
+    // runs before then
+    pub prestart_confirmed_block: Option<BlockNum>,
+    // The executor stops advancing a plan that needs review, until the admin resolves it
+    pub review_status: PlanReviewStatus,
+    // get_route_hash of the GraphSolution the plan was created from, i.e. what the quote returned
+    pub route_hash: Option<RouteHash>,
+    // The terms as quoted when the plan was created. Unlike quote, it is never updated (e.g. when
+    // a deposit is rescaled), so it is the record to go by if the user disputes their quote
+    pub quoted_terms: Option<QuotedTerms>,
+    // EthRemoveLiquidity of the src LP token into the pair's two tokens, which the paths then
+    // route onward (see converter::set_lp_source). Only set if the user deposited LP tokens.
+    // Runs after the deposit sweep
+    pub remove_liquidity: Option<ExecutionStep>,
+    // ID of the sponsorship campaign that covers the plan, in which case the escrow takes no
+    // protocol fee. The campaign's budget was drawn down when the plan started
+    pub sponsorship_campaign_id: Option<String>,
+}
+
+pub const MAX_PLAN_METADATA_LEN: usize = 128;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PlanReviewStatus {
+    NotNeeded,
+    // The escrow has spent more gas on the plan than the executor's gas ceiling allows
+    NeedsReview { realized_gas_fee_usd: Amount },
+    // The admin let the plan continue, so the gas ceiling no longer applies to it
+    Resolved,
+    // The user deposited more or less than they were quoted, by more than the executor's deposit
+    // tolerance. The plan is already rescaled to the deposited amount
+    DepositMismatch { deposited_amount: Amount },
+}
+
+impl PlanReviewStatus {
+    // i.e. the plan is parked until the admin resolves it
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            PlanReviewStatus::NeedsReview { .. } | PlanReviewStatus::DepositMismatch { .. }
+        )
+    }
+}
+
+// See graph_solution_to_execution_plan::route_hash
+pub type RouteHash = [u8; 32];
+
+// USD amounts are in $ x 10^USD_AMOUNT_EXPONENT, at the token prices the plan was computed with
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PlanQuote {
+    pub amount_out: Amount, // dest token
+    pub amount_in_usd: Amount,
+    pub amount_out_usd: Amount,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QuotedTerms {
+    pub quoted_at_millis: MillisSinceEpoch,
+    pub amount_in: Amount,  // src token
+    pub amount_out: Amount, // dest token, net of the estimated gas and bridge fees
+    pub amount_in_usd: Amount,
+    pub amount_out_usd: Amount,
+    // Includes the integrator's markup if the plan was started with one
+    pub fee_breakdown: FeeBreakdown,
+    // How far the deposit could be off amount_in and still go ahead without review. DEX swaps
+    // don't set a minimum amount out, so this is the only tolerance the plan was quoted with
+    pub deposit_tolerance_bps: u32,
+    // The worst-case completion deadline, past which the plan is refunded
+    pub expiry_millis: MillisSinceEpoch,
+    pub route_hash: RouteHash,
+}
+
+// The resolved address is what the postend step delivers to. The name is kept for support
+// and for the user's own records
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ResolvedDestinationName {
+    pub name: String,
+    pub resolved_address: EthAddress,
+}
+
+// Pessimistic wall-clock bounds on each kind of step, including the time it takes a worker
+// to pick the plan back up. Used to compute (and quote) the deadline of an ExecutionPlan
+const ETH_STEP_WORST_CASE_MILLIS: MillisSinceEpoch = 180_000;
+const XCM_TRANSFER_WORST_CASE_MILLIS: MillisSinceEpoch = 600_000;
+const WORMHOLE_TRANSFER_WORST_CASE_MILLIS: MillisSinceEpoch = 1_800_000;
+
+impl ExecutionPlan {
+    pub fn get_worst_case_duration_millis(&self) -> MillisSinceEpoch {
+        let paths_duration = match self.path_execution_mode {
+            PathExecutionMode::Concurrent => self
+                .paths
+                .iter()
+                .map(|path| path.get_worst_case_duration_millis())
+                .max()
+                .unwrap_or(0),
+            PathExecutionMode::Sequential => self
+                .paths
+                .iter()
+                .map(|path| path.get_worst_case_duration_millis())
+                .sum(),
+        };
+        self.prestart_user_to_escrow_transfer
+            .get_worst_case_duration_millis()
+            + self
+                .deposit_sweep
+                .as_ref()
+                .map_or(0, |step| step.get_worst_case_duration_millis())
+            + self
+                .remove_liquidity
+                .as_ref()
+                .map_or(0, |step| step.get_worst_case_duration_millis())
+            + paths_duration
+            + self
+                .postend_escrow_to_user_transfer
+                .get_worst_case_duration_millis()
+    }
+}
+
+// Concurrent steps forward all the ExecutionPaths together. Sequential only starts an
+// ExecutionPath once the preceding one has delivered its amount_out to the escrow, which
+// bounds the funds at risk to a single path (used with GraphSolution::split_into_tranches)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PathExecutionMode {
+    Concurrent,
+    Sequential,
+}
+
+// Default keeps retrying the paths until deadline_millis. NowOrNever is for arbitrage-style
+// users who would rather get their deposit back than be filled late: if the first hop of the
+// paths is not confirmed within max_blocks src chain blocks of the deposit reaching the escrow,
+// the plan is refunded. The executor sets first_hop_deadline_block once the deposit lands
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ExecutionPolicy {
+    Default,
+    NowOrNever {
+        max_blocks: BlockNum,
+        first_hop_deadline_block: Option<BlockNum>,
+    },
+}
+
+impl ExecutionPolicy {
+    pub fn from_now_or_never_max_blocks(now_or_never_max_blocks: Option<BlockNum>) -> Self {
+        match now_or_never_max_blocks {
+            Some(max_blocks) => Self::NowOrNever {
+                max_blocks,
+                first_hop_deadline_block: None,
+            },
+            None => Self::Default,
+        }
+    }
 }
 
 impl fmt::Display for ExecutionPlan {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let _ = write!(
             f,
-            "ExecutionPlan [{:?}]: \nprestart_user_to_escrow_transfer = {:?}, \
-			 \npostend_escrow_to_user_transfer = {:?}",
-            self.uuid, self.prestart_user_to_escrow_transfer, self.postend_escrow_to_user_transfer
+            "ExecutionPlan [{:?}, {:?}, {:?}, deadline {}]: \nprestart_user_to_escrow_transfer = {:?}, \
+			 \ndeposit_sweep = {:?}, \nremove_liquidity = {:?}, \npostend_escrow_to_user_transfer = {:?}, \nrefund_escrow_to_user_transfer = {:?}",
+            self.uuid,
+            self.path_execution_mode,
+            self.execution_policy,
+            self.deadline_millis,
+            self.prestart_user_to_escrow_transfer,
+            self.deposit_sweep,
+            self.remove_liquidity,
+            self.postend_escrow_to_user_transfer,
+            self.refund_escrow_to_user_transfer
         );
         for (i, p) in self.paths.iter().enumerate() {
             let _ = write!(f, "\nExecutionPath {}: {}", i + 1, p);
@@ -57,10 +255,28 @@ impl fmt::Display for ExecutionPlan {
 pub struct ExecutionPath {
     pub steps: Vec<ExecutionStep>,
     pub amount_out: Option<Amount>,
+    // Realized amount out of each step that succeeded, in step order. Bounded by steps.len()
+    pub step_amounts_out: Vec<Amount>,
     // TODO: Should also add amount_out_usd so that we can properly
     // compute the fee when all ExecutionPaths finish
 }
 
+impl ExecutionPath {
+    pub fn get_worst_case_duration_millis(&self) -> MillisSinceEpoch {
+        self.steps
+            .iter()
+            .map(|step| step.get_worst_case_duration_millis())
+            .sum()
+    }
+
+    // Steps succeed in order, so anything but the next step's amount is a duplicate
+    pub fn record_step_amount_out(&mut self, step_idx: usize, amount_out: Amount) {
+        if step_idx == self.step_amounts_out.len() && step_idx < self.steps.len() {
+            self.step_amounts_out.push(amount_out);
+        }
+    }
+}
+
 impl fmt::Display for ExecutionPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -83,6 +299,37 @@ pub struct ExecutionStep {
     // singleton struct instead of collapsing it down for ease of adding items in
     // the future
     pub inner: ExecutionStepEnum,
+    pub timing: StepTiming,
+    // Set for the EVM steps that were signed when the plan was created. The step broadcasts it
+    // as is instead of building and signing its txn
+    pub presigned_txn: Option<PresignedTxn>,
+}
+
+// When the executor saw the step go in flight and complete, for progress bars and ETAs. The
+// block is the step's src chain's current block at the time, and may be missing if the
+// lookup failed
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StepTiming {
+    pub submitted: Option<StepMilestone>,
+    // Set once the step succeeds or fails
+    pub confirmed: Option<StepMilestone>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StepMilestone {
+    pub block_num: Option<BlockNum>,
+    pub timestamp_millis: MillisSinceEpoch,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PresignedTxn {
+    // Assigned to the step in the NonceManager, so it must be dropped if the txn is never sent
+    pub nonce: Nonce,
+    pub txn_hash: EthTxnHash,
+    pub raw_txn: Vec<u8>,
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
@@ -112,6 +359,32 @@ pub enum ExecutionStepEnum {
     // xTokens.transferMultiasset from Moonbeam
     // xTransfer.transfer from Phala
     XCMTransfer(XCMTransferStep),
+    // Wormhole token bridge transfer between EVM chains (e.g. Moonbeam <-> Ethereum):
+    // tokenBridge.transferTokens on the source chain, then tokenBridge.completeTransfer
+    // with the guardian-signed VAA on the destination chain
+    WormholeTransfer(WormholeTransferStep),
+    // DepositForwarderFactory.sweep, which moves the full balance of the plan's deposit
+    // address into the escrow
+    EthDepositSweep(EthDepositSweepStep),
+    // The user's utility.batch_all of a balances transfer to the escrow and a remark tagging
+    // the plan. Only used as the prestart step, and we never send anything for it
+    SubstrateRemarkDeposit(SubstrateRemarkDepositStep),
+    // Delivers the dest token by calling a contract with it (swap-and-call), instead of
+    // transferring it to the user. Only used as the postend step
+    EthContractCall(EthContractCallStep),
+    // Sends the integrator's markup out of what the path delivered to the escrow (see
+    // quote_with_markup). Only used as the last step of a path
+    EthFeeSkim(EthFeeSkimStep),
+    // Another aggregator's exchange, called with the calldata its API returns for the actual
+    // amount in (see EthAggregatorSwapStep)
+    EthAggregatorSwap(EthAggregatorSwapStep),
+    // Delivers the dest token as liquidity (zap-in): swaps half of it into the pair's other
+    // token and adds both to the pair, which mints the LP tokens to the user. Only used as the
+    // postend step
+    EthZapIn(EthZapInStep),
+    // Redeems the LP tokens the user deposited into the pair's two tokens (zap-out) with the
+    // router's removeLiquidity. Only used as the plan's remove_liquidity step
+    EthRemoveLiquidity(EthRemoveLiquidityStep),
     // FYI Batch will be inelegant since I insert status into the ExecutionStep
     // struct MoonbeamBatchStep { substeps: Vec<ExecutionStep>, ... }
     // MoonbeamBatch(MoonbeamBatchStep),
@@ -119,7 +392,11 @@ pub enum ExecutionStepEnum {
 
 impl ExecutionStep {
     pub fn new(inner: ExecutionStepEnum) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            timing: StepTiming::default(),
+            presigned_txn: None,
+        }
     }
 
     pub fn get_amount_in(&self) -> Option<Amount> {
@@ -130,6 +407,14 @@ impl ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.amount,
             ExecutionStepEnum::EthDexSwap(step) => step.amount_in,
             ExecutionStepEnum::XCMTransfer(step) => step.amount_in,
+            ExecutionStepEnum::WormholeTransfer(step) => step.amount_in,
+            ExecutionStepEnum::EthDepositSweep(step) => step.amount,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => step.amount,
+            ExecutionStepEnum::EthContractCall(step) => step.amount,
+            ExecutionStepEnum::EthFeeSkim(step) => step.amount,
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.amount_in,
+            ExecutionStepEnum::EthZapIn(step) => step.amount,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.amount,
         }
     }
 
@@ -141,6 +426,14 @@ impl ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.amount = Some(amount_in),
             ExecutionStepEnum::EthDexSwap(step) => step.amount_in = Some(amount_in),
             ExecutionStepEnum::XCMTransfer(step) => step.amount_in = Some(amount_in),
+            ExecutionStepEnum::WormholeTransfer(step) => step.amount_in = Some(amount_in),
+            ExecutionStepEnum::EthDepositSweep(step) => step.amount = Some(amount_in),
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => step.amount = Some(amount_in),
+            ExecutionStepEnum::EthContractCall(step) => step.amount = Some(amount_in),
+            ExecutionStepEnum::EthFeeSkim(step) => step.amount = Some(amount_in),
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.amount_in = Some(amount_in),
+            ExecutionStepEnum::EthZapIn(step) => step.amount = Some(amount_in),
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.amount = Some(amount_in),
         }
     }
 
@@ -152,6 +445,18 @@ impl ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.status = EthStepStatus::Dropped,
             ExecutionStepEnum::EthDexSwap(step) => step.status = EthStepStatus::Dropped,
             ExecutionStepEnum::XCMTransfer(step) => step.status = CrossChainStepStatus::Dropped,
+            ExecutionStepEnum::WormholeTransfer(step) => {
+                step.status = MultiPhaseBridgeStepStatus::Dropped
+            }
+            ExecutionStepEnum::EthDepositSweep(step) => step.status = EthStepStatus::Dropped,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => {
+                step.status = RemarkDepositStatus::Dropped
+            }
+            ExecutionStepEnum::EthContractCall(step) => step.status = EthStepStatus::Dropped,
+            ExecutionStepEnum::EthFeeSkim(step) => step.status = EthStepStatus::Dropped,
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.status = EthStepStatus::Dropped,
+            ExecutionStepEnum::EthZapIn(step) => step.status = EthStepStatus::Dropped,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.status = EthStepStatus::Dropped,
         }
     }
 
@@ -163,6 +468,71 @@ impl ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.chain,
             ExecutionStepEnum::EthDexSwap(step) => step.token_path[0].chain,
             ExecutionStepEnum::XCMTransfer(step) => step.src_token.chain,
+            ExecutionStepEnum::WormholeTransfer(step) => step.src_token.chain,
+            ExecutionStepEnum::EthDepositSweep(step) => step.token.chain,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => step.chain,
+            ExecutionStepEnum::EthContractCall(step) => step.token.chain,
+            ExecutionStepEnum::EthFeeSkim(step) => step.token.chain,
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.src_token.chain,
+            ExecutionStepEnum::EthZapIn(step) => step.token.chain,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.lp_token.chain,
+        }
+    }
+
+    pub fn get_worst_case_duration_millis(&self) -> MillisSinceEpoch {
+        match &self.inner {
+            ExecutionStepEnum::XCMTransfer(_) => XCM_TRANSFER_WORST_CASE_MILLIS,
+            ExecutionStepEnum::WormholeTransfer(_) => WORMHOLE_TRANSFER_WORST_CASE_MILLIS,
+            // ERC20s are approved in a txn of their own before the call
+            ExecutionStepEnum::EthContractCall(step) if step.needs_approval() => {
+                2 * ETH_STEP_WORST_CASE_MILLIS
+            }
+            ExecutionStepEnum::EthAggregatorSwap(step) if step.needs_approval() => {
+                2 * ETH_STEP_WORST_CASE_MILLIS
+            }
+            // The swap, then addLiquidity
+            ExecutionStepEnum::EthZapIn(_) => 2 * ETH_STEP_WORST_CASE_MILLIS,
+            // The LP tokens' approval, then removeLiquidity
+            ExecutionStepEnum::EthRemoveLiquidity(_) => 2 * ETH_STEP_WORST_CASE_MILLIS,
+            _ => ETH_STEP_WORST_CASE_MILLIS,
+        }
+    }
+
+    pub fn get_common(&self) -> &CommonExecutionMeta {
+        match &self.inner {
+            ExecutionStepEnum::EthSend(step) => &step.common,
+            ExecutionStepEnum::ERC20Transfer(step) => &step.common,
+            ExecutionStepEnum::EthWrap(step) => &step.common,
+            ExecutionStepEnum::EthUnwrap(step) => &step.common,
+            ExecutionStepEnum::EthDexSwap(step) => &step.common,
+            ExecutionStepEnum::XCMTransfer(step) => &step.common,
+            ExecutionStepEnum::WormholeTransfer(step) => &step.common,
+            ExecutionStepEnum::EthDepositSweep(step) => &step.common,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => &step.common,
+            ExecutionStepEnum::EthContractCall(step) => &step.common,
+            ExecutionStepEnum::EthFeeSkim(step) => &step.common,
+            ExecutionStepEnum::EthAggregatorSwap(step) => &step.common,
+            ExecutionStepEnum::EthZapIn(step) => &step.common,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => &step.common,
+        }
+    }
+
+    pub fn get_common_mut(&mut self) -> &mut CommonExecutionMeta {
+        match &mut self.inner {
+            ExecutionStepEnum::EthSend(step) => &mut step.common,
+            ExecutionStepEnum::ERC20Transfer(step) => &mut step.common,
+            ExecutionStepEnum::EthWrap(step) => &mut step.common,
+            ExecutionStepEnum::EthUnwrap(step) => &mut step.common,
+            ExecutionStepEnum::EthDexSwap(step) => &mut step.common,
+            ExecutionStepEnum::XCMTransfer(step) => &mut step.common,
+            ExecutionStepEnum::WormholeTransfer(step) => &mut step.common,
+            ExecutionStepEnum::EthDepositSweep(step) => &mut step.common,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => &mut step.common,
+            ExecutionStepEnum::EthContractCall(step) => &mut step.common,
+            ExecutionStepEnum::EthFeeSkim(step) => &mut step.common,
+            ExecutionStepEnum::EthAggregatorSwap(step) => &mut step.common,
+            ExecutionStepEnum::EthZapIn(step) => &mut step.common,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => &mut step.common,
         }
     }
 
@@ -174,6 +544,14 @@ impl ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => &step.uuid,
             ExecutionStepEnum::EthDexSwap(step) => &step.uuid,
             ExecutionStepEnum::XCMTransfer(step) => &step.uuid,
+            ExecutionStepEnum::WormholeTransfer(step) => &step.uuid,
+            ExecutionStepEnum::EthDepositSweep(step) => &step.uuid,
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => &step.uuid,
+            ExecutionStepEnum::EthContractCall(step) => &step.uuid,
+            ExecutionStepEnum::EthFeeSkim(step) => &step.uuid,
+            ExecutionStepEnum::EthAggregatorSwap(step) => &step.uuid,
+            ExecutionStepEnum::EthZapIn(step) => &step.uuid,
+            ExecutionStepEnum::EthRemoveLiquidity(step) => &step.uuid,
         }
     }
 }
@@ -260,6 +638,8 @@ pub struct XCMTransferStep {
     pub dest_token: UniversalTokenId,
     pub token_asset_multilocation: MultiLocation,
     pub full_dest_multilocation: MultiLocation,
+    // Lane's configured ceiling on the weight bought on the destination chain
+    pub dest_weight_limit: u64,
     pub amount_in: Option<Amount>,
     pub bridge_fee_native: Amount,
     pub bridge_fee_usd: Amount,
@@ -267,6 +647,299 @@ pub struct XCMTransferStep {
     pub status: CrossChainStepStatus,
 }
 
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WormholeTransferStep {
+    pub uuid: Uuid,
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub src_token_bridge_addr: EthAddress,
+    pub dest_token_bridge_addr: EthAddress,
+    pub src_wormhole_chain_id: WormholeChainId,
+    pub dest_wormhole_chain_id: WormholeChainId,
+    pub amount_in: Option<Amount>,
+    // Gas fee of the claim txn on the destination chain
+    pub bridge_fee_native: Amount,
+    pub bridge_fee_usd: Amount,
+    pub common: CommonExecutionMeta,
+    pub status: MultiPhaseBridgeStepStatus,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthDepositSweepStep {
+    pub uuid: Uuid,
+    // Native or ERC20 token that the user deposited
+    pub token: UniversalTokenId,
+    pub factory_addr: EthAddress,
+    // CREATE2 salt of the forwarder, see deposit_address::get_deposit_salt
+    pub salt: DepositSalt,
+    pub amount: Option<Amount>,
+    // src_addr is the deposit address and dest_addr is the escrow. The txn itself is sent
+    // from the escrow
+    pub common: CommonExecutionMeta,
+    pub status: EthStepStatus,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstrateRemarkDepositStep {
+    pub uuid: Uuid,
+    // The deposit is always the chain's native token
+    pub chain: UniversalChainId,
+    // What the user attaches with system.remarkWithEvent, see deposit_address::get_deposit_remark
+    pub remark: DepositRemark,
+    pub amount: Option<Amount>,
+    // src_addr is the user's Eth address (refunds go there, since the batch can be sent from
+    // any account) and dest_addr is the escrow's EVM backing account
+    pub common: CommonExecutionMeta,
+    pub status: RemarkDepositStatus,
+}
+
+// The 32-byte word in a ContractCall's calldata_template that the executor overwrites with the
+// amount the escrow received, as a big-endian uint256. Frontends encode the call with this
+// uint256 in the amount argument's place
+pub const CONTRACT_CALL_AMOUNT_PLACEHOLDER: [u8; 32] = *b"PRIVADEX_AMOUNT_PLACEHOLDER_WORD";
+pub const MAX_CONTRACT_CALLDATA_LEN: usize = 1024;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ContractCall {
+    pub target_addr: EthAddress,
+    // ABI-encoded call (selector included) with exactly one CONTRACT_CALL_AMOUNT_PLACEHOLDER
+    // argument
+    pub calldata_template: Vec<u8>,
+}
+
+impl ContractCall {
+    // Byte offset of the placeholder. None if the template doesn't have exactly one of them
+    // as an argument (i.e. 32-byte aligned after the selector)
+    pub fn get_amount_offset(&self) -> Option<usize> {
+        let len = self.calldata_template.len();
+        if len > MAX_CONTRACT_CALLDATA_LEN || len < 4 || (len - 4) % 32 != 0 {
+            return None;
+        }
+        let mut offsets = (4..len).step_by(32).filter(|&offset| {
+            self.calldata_template[offset..offset + 32] == CONTRACT_CALL_AMOUNT_PLACEHOLDER
+        });
+        match (offsets.next(), offsets.next()) {
+            (Some(offset), None) => Some(offset),
+            _ => None,
+        }
+    }
+
+    pub fn get_calldata(&self, amount: Amount) -> Option<Vec<u8>> {
+        let offset = self.get_amount_offset()?;
+        let mut calldata = self.calldata_template.clone();
+        calldata[offset..offset + 16].fill(0);
+        calldata[offset + 16..offset + 32].copy_from_slice(&amount.to_be_bytes());
+        Some(calldata)
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthContractCallStep {
+    pub uuid: Uuid,
+    // Native tokens are sent as the call's value. ERC20s are approved to the target for exactly
+    // amount right before the call, so the call can't pull more than what the user swapped into
+    pub token: UniversalTokenId,
+    pub call: ContractCall,
+    pub amount: Option<Amount>,
+    // src_addr is the escrow and dest_addr is call.target_addr
+    pub common: CommonExecutionMeta,
+    // The approval has a nonce of its own, so it is assigned under a UUID of its own
+    pub approval_uuid: Uuid,
+    // Set once the approval is confirmed, after which status tracks the call itself
+    pub approval_txn_hash: Option<EthTxnHash>,
+    pub status: EthStepStatus,
+}
+
+impl EthContractCallStep {
+    pub fn needs_approval(&self) -> bool {
+        self.token.id != ChainTokenId::Native
+    }
+
+    pub fn is_approving(&self) -> bool {
+        self.needs_approval() && self.approval_txn_hash.is_none()
+    }
+}
+
+// Integrators can charge at most 5% on top of the protocol fee
+pub const MAX_INTEGRATOR_MARKUP_BPS: u16 = 500;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthFeeSkimStep {
+    pub uuid: Uuid,
+    // Native or ERC20 token that the path delivered to the escrow
+    pub token: UniversalTokenId,
+    pub markup_bps: u16,
+    // What the path delivered. The step sends get_markup_amount() of it to the integrator and
+    // its amount out is the rest
+    pub amount: Option<Amount>,
+    // src_addr is the escrow and dest_addr is the integrator's markup recipient
+    pub common: CommonExecutionMeta,
+    pub status: EthStepStatus,
+}
+
+impl EthFeeSkimStep {
+    pub fn get_markup_amount(&self) -> Option<Amount> {
+        self.amount
+            .map(|amount| mul_ratio_u128(amount, Amount::from(self.markup_bps), 10_000))
+    }
+}
+
+// The most the amount out may fall short of the aggregator's quote (pro rata to the amount in)
+// before its exchange reverts the swap
+pub const AGGREGATOR_SWAP_SLIPPAGE_BPS: u16 = 50;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthAggregatorSwapStep {
+    pub uuid: Uuid,
+    pub aggregator_id: AggregatorId,
+    // The swap txn only ever goes to this contract, whatever the API's calldata says. ERC20s
+    // are approved to it for exactly amount_in right before the swap
+    pub exchange_addr: EthAddress,
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub amount_in: Option<Amount>,
+    // The aggregator's quote when the plan was created, which get_amount_out_min scales to the
+    // amount actually swapped
+    pub quoted_amount_in: Amount,
+    pub quoted_amount_out: Amount,
+    // src_addr and dest_addr are both the escrow
+    pub common: CommonExecutionMeta,
+    // The approval has a nonce of its own, so it is assigned under a UUID of its own
+    pub approval_uuid: Uuid,
+    // Set once the approval is confirmed, after which status tracks the swap itself
+    pub approval_txn_hash: Option<EthTxnHash>,
+    pub status: EthStepStatus,
+}
+
+impl EthAggregatorSwapStep {
+    pub fn needs_approval(&self) -> bool {
+        self.src_token.id != ChainTokenId::Native
+    }
+
+    pub fn is_approving(&self) -> bool {
+        self.needs_approval() && self.approval_txn_hash.is_none()
+    }
+
+    pub fn get_amount_out_min(&self) -> Option<Amount> {
+        if self.quoted_amount_in == 0 {
+            return None;
+        }
+        let amount_out = mul_ratio_u128(
+            self.quoted_amount_out,
+            self.amount_in?,
+            self.quoted_amount_in,
+        );
+        Some(mul_ratio_u128(
+            amount_out,
+            Amount::from(10_000 - AGGREGATOR_SWAP_SLIPPAGE_BPS),
+            10_000,
+        ))
+    }
+}
+
+// The most either side of a zap-in's addLiquidity may fall short of what the pair's reserves
+// called for when the txn was signed, before the router reverts it
+pub const ZAP_IN_SLIPPAGE_BPS: u16 = 100;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthZapInStep {
+    pub uuid: Uuid,
+    // The router of the pair's DEX. Like an EthDexSwapStep's, it spends the escrow's tokens
+    // through the escrow's standing approvals
+    pub dex_router_addr: EthAddress,
+    // The ERC20 the paths delivered to the escrow, i.e. the plan's (wrapped) dest token
+    pub token: UniversalTokenId,
+    // The pair's other token
+    pub pair_token: UniversalTokenId,
+    // The pair contract, which is also the LP token
+    pub lp_token: UniversalTokenId,
+    pub amount: Option<Amount>,
+    // src_addr is the escrow and dest_addr is the user, who gets the LP tokens
+    pub common: CommonExecutionMeta,
+    // The swap has a nonce of its own, so it is assigned under a UUID of its own
+    pub swap_uuid: Uuid,
+    // Set once the swap is confirmed, after which status tracks addLiquidity
+    pub swap_txn_hash: Option<EthTxnHash>,
+    // What the swap paid out into the escrow
+    pub pair_token_amount: Option<Amount>,
+    pub status: EthStepStatus,
+}
+
+impl EthZapInStep {
+    pub fn is_swapping(&self) -> bool {
+        self.swap_txn_hash.is_none()
+    }
+
+    // Half of amount is swapped into pair_token. The other half is added as liquidity
+    pub fn get_swap_amount_in(&self) -> Option<Amount> {
+        self.amount.map(|amount| amount / 2)
+    }
+
+    pub fn get_liquidity_amount(&self) -> Option<Amount> {
+        self.amount.map(|amount| amount - amount / 2)
+    }
+}
+
+// The most either of removeLiquidity's payouts may fall short of the LP tokens' share of the
+// pair's reserves when the txn was signed, before the router reverts it
+pub const REMOVE_LIQUIDITY_SLIPPAGE_BPS: u16 = 100;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthRemoveLiquidityStep {
+    pub uuid: Uuid,
+    // The router of the pair's DEX
+    pub dex_router_addr: EthAddress,
+    // The pair contract, which is also the LP token the user deposited
+    pub lp_token: UniversalTokenId,
+    // The pair's two tokens. The plan's first num_token_a_paths paths route token_a onward and
+    // the rest route token_b
+    pub token_a: UniversalTokenId,
+    pub token_b: UniversalTokenId,
+    pub num_token_a_paths: u32,
+    pub amount: Option<Amount>,
+    // src_addr and dest_addr are both the escrow, which gets both tokens
+    pub common: CommonExecutionMeta,
+    // The router pulls the LP tokens with transferFrom, so they are approved first. The approval
+    // has a nonce of its own, so it is assigned under a UUID of its own
+    pub approval_uuid: Uuid,
+    // Set once the approval is confirmed, after which status tracks removeLiquidity itself
+    pub approval_txn_hash: Option<EthTxnHash>,
+    // What removeLiquidity paid out into the escrow, set once it is confirmed
+    pub token_a_amount_out: Option<Amount>,
+    pub token_b_amount_out: Option<Amount>,
+    pub status: EthStepStatus,
+}
+
+impl EthRemoveLiquidityStep {
+    pub fn is_approving(&self) -> bool {
+        self.approval_txn_hash.is_none()
+    }
+}
+
+// Not part of any plan. The executor uses it to set one of the escrow's standing ERC20
+// approvals, e.g. to zero to revoke a DEX router's allowance
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EthApproveStep {
+    pub uuid: Uuid,
+    // ERC20 token
+    pub token: UniversalTokenId,
+    pub spender: EthAddress,
+    pub amount: Amount,
+    // src_addr and dest_addr are both the escrow
+    pub common: CommonExecutionMeta,
+    pub status: EthStepStatus,
+}
+
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum EthStepStatus {
@@ -330,7 +1003,11 @@ pub struct SubstrateFinalizedExtrinsicId {
 pub struct SubstratePendingEventId {
     // To be used to find the event on a remote chain
     pub start_block_num: BlockNum,
-    // Use fields on the ExecutionStep (e.g. XCMTransferStep) as the identifier
+    // Hash of the XCM message emitted by the local chain, which the remote chain reports
+    // when it executes (or fails to execute) the message. It is only known once the local
+    // txn is finalized, and not all source chains emit it.
+    // Otherwise use fields on the ExecutionStep (e.g. XCMTransferStep) as the identifier
+    pub message_hash: Option<XcmMessageHash>,
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
@@ -340,6 +1017,27 @@ pub struct SubstrateEventId {
     pub event_index: Nonce,
 }
 
+// The user sends the batch whenever they like, so unlike our own txns there is nothing to
+// submit. We just look for it until end_block_num
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RemarkDepositStatus {
+    NotStarted,
+    Pending(SubstratePendingRemarkId),
+    // Nothing tagged with the remark landed by end_block_num
+    Dropped,
+    // The tagged batch did not credit the escrow with the expected amount
+    Failed(SubstrateFinalizedExtrinsicId),
+    Confirmed(SubstrateFinalizedExtrinsicId),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstratePendingRemarkId {
+    pub start_block_num: BlockNum,
+    pub end_block_num: BlockNum,
+}
+
 // Will need to implement this when we handle intra-chain extrinsics
 // e.g. SubstrateNativeTokenTransfer
 // enum SubstrateStepStatus { ... }
@@ -362,4 +1060,70 @@ pub enum CrossChainStepStatus {
     // Transaction has been included in a block on the local chain and
     // produced an event on the remote chain
     Confirmed(FinalizedTxnId, SubstrateEventId),
+    // Transaction succeeded on the local chain but the XCM message failed to
+    // execute on the remote chain (funds are typically trapped there)
+    RemoteFailed(FinalizedTxnId, SubstrateEventId, XcmFailureKind),
+}
+
+// Why the remote chain refused to execute an XCM message. The weight-related kinds
+// usually mean the lane's dest_weight_limit needs to be re-tuned
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum XcmFailureKind {
+    // Message did not pass the Barrier, e.g. it bought less weight than it needs
+    Barrier,
+    // Fee asset is not enough to pay for the bought weight
+    TooExpensive,
+    WeightLimitReached,
+    WeightNotComputable,
+    Other,
+}
+
+impl XcmFailureKind {
+    // Maps the XcmError variant name (as reported in the remote chain's event)
+    pub fn from_xcm_error_name(name: &str) -> Self {
+        match name {
+            "Barrier" => Self::Barrier,
+            "TooExpensive" => Self::TooExpensive,
+            "WeightLimitReached" => Self::WeightLimitReached,
+            "WeightNotComputable" => Self::WeightNotComputable,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn is_weight_related(&self) -> bool {
+        *self != Self::Other
+    }
+}
+
+// Identifies the message emitted by the source chain's token bridge
+// (the emitter is the token bridge itself and is stored on the ExecutionStep)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WormholeMessageId {
+    pub deposit_txn_hash: EthTxnHash,
+    pub sequence: u64,
+}
+
+// Used by bridges that need several transactions (on different chains) to complete
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum MultiPhaseBridgeStepStatus {
+    // Haven't started executing this step yet, which is the default status.
+    NotStarted,
+    // Deposit transaction has been sent to the source chain
+    DepositSubmitted(EthPendingTxnId),
+    // Deposit transaction has been sent but was dropped. Once the deposit is
+    // confirmed, we never enter this state (funds are locked in the bridge)
+    Dropped,
+    // Deposit or claim transaction has been included in a block but failed
+    Failed(EthTxnHash),
+    // Deposit is confirmed on the source chain and we are waiting for the relayers
+    // (e.g. Wormhole guardians) to attest to it
+    AwaitingRelay(WormholeMessageId),
+    // Claim transaction has been sent to the destination chain. If it is dropped,
+    // we go back to AwaitingRelay and claim again
+    ClaimSubmitted(WormholeMessageId, EthPendingTxnId),
+    // Claim transaction has been included in a block on the destination chain
+    Confirmed(WormholeMessageId, EthTxnHash),
 }
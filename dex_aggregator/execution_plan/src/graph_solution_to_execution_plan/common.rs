@@ -25,9 +25,10 @@ pub(crate) const ESCROW_ETH_ADDRESS: EthAddress = EthAddress {
 };
 
 // This is the Substrate-mapped address of 0x05a81d8564a3eA298660e34e03E5Eff9a29d7a2A
+// under Frontier's HashedAddressMapping (used by Astar)
 // Converted using https://hoonsubin.github.io/evm-substrate-address-converter/
 // (original article at https://medium.com/astar-network/using-astar-network-account-between-substrate-and-evm-656643df22a0)
-pub(crate) const ESCROW_ASTAR_NATIVE_ADDRESS: SubstratePublicKey = SubstratePublicKey {
+pub(crate) const ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS: SubstratePublicKey = SubstratePublicKey {
     0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
 };
 
@@ -44,4 +45,24 @@ pub enum GraphToExecConversionError {
     StartedWrapEndedUnwrap, // Should not start with a wrap and end with unwrap (we do not expect cycles)
     UnexpectedStillProcessingSwap, // Should not be processing a swap (when we encounter some edge)
     UnexpectedSwapAfterUnwrap, // Should not encounter a CPMM after unwrap
+    // Calldata needs exactly one amount placeholder, or the postend step is not a transfer
+    InvalidContractCall,
+    DestTokenNotNative,   // Only a native dest token can be delivered wrapped
+    NoWrappedNativeToken, // The dest chain has no WETH (or its equivalent) registered
+    WrappedDeliveryOfWrappedToken, // A path is only an unwrap i.e. the swap would be a no-op
+    SrcTokenNotNativeOrWrapped, // Only a native or wrapped src token has another deposit form
+    DepositFormSwitchIsNoOp, // A path is only the wrap or unwrap of the deposit
+    // Markup is zero or above MAX_INTEGRATOR_MARKUP_BPS, or the plan already has one
+    InvalidIntegratorMarkup,
+    BlockedPool,     // A path swaps through a pool on the operator's pool blocklist
+    NoDepositAmount, // The prestart step has no (or a zero) amount to rescale the plan from
+    // Only the shared escrow owns the deposit forwarder factory, so it must sweep the deposit
+    DepositForwarderNeedsSharedEscrow,
+    InvalidDepositForwarder, // The prestart step is not an EthSend or ERC20Transfer
+    // The postend step is not an ERC20 transfer, or the pair is not on its chain or is the token
+    // with itself
+    InvalidZapIn,
+    // The two plans don't deposit different ERC20s of the LP token's chain, don't deliver the same
+    // dest token to the same address, or the plan already has an LP source
+    InvalidLpSource,
 }
@@ -16,29 +16,45 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 use scale::Encode;
 
 use privadex_chain_metadata::{
-    common::{ChainTokenId, Dex, UniversalAddress},
+    chain_info::DepositForwarderFactory,
+    common::{
+        Amount, ChainTokenId, Dex, ERC20Token, EthAddress, MillisSinceEpoch, SubstratePublicKey,
+        UniversalAddress, UniversalTokenId,
+    },
     get_chain_info_from_chain_id,
+    registry::dex::pool_blocklist_overlay::PoolBlocklistOverlay,
+};
+use privadex_common::{
+    utils::{general_utils::mul_ratio_u128, ss58_utils::h160_to_mapped_account},
+    uuid::Uuid,
 };
-use privadex_common::uuid::Uuid;
 use privadex_routing::graph::{
     edge::{BridgeEdge, ConstantProductAMMSwapEdge, Edge, SwapEdge},
     graph::{GraphSolution, SplitGraphPath},
     traits::QuoteGetter,
 };
 
+use crate::deposit_address::{get_deposit_address, get_deposit_salt, DepositSalt};
 use crate::execution_plan::{
-    CommonExecutionMeta, ERC20TransferStep, EthSendStep, EthStepStatus, ExecutionPath,
-    ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+    CommonExecutionMeta, ContractCall, DexRouterFunction, ERC20TransferStep, EthContractCallStep,
+    EthDepositSweepStep, EthDexSwapStep, EthFeeSkimStep, EthRemoveLiquidityStep, EthSendStep,
+    EthStepStatus, EthUnwrapStep, EthWrapStep, EthZapInStep, ExecutionPath, ExecutionPlan,
+    ExecutionPolicy, ExecutionStep, ExecutionStepEnum, PathExecutionMode, PlanReviewStatus,
+    MAX_INTEGRATOR_MARKUP_BPS,
 };
 
-use super::common::{GraphToExecConversionError, ESCROW_ETH_ADDRESS};
+use super::common::{
+    GraphToExecConversionError, ESCROW_ETH_ADDRESS, ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS,
+    ESCROW_SUBSTRATE_PUBLIC_KEY,
+};
 use super::helper_process_graph_edge::{
     self as process_graph_edge_helper, ParseSwapState, ProcessHelperResult,
 };
+use super::route_hash::get_route_hash;
 
 impl TryFrom<GraphSolution> for ExecutionPlan {
     type Error = GraphToExecConversionError;
@@ -57,6 +73,8 @@ impl TryFrom<GraphSolution> for ExecutionPlan {
         let mut uuid_seed =
             u128::from_le_bytes(sp_core_hashing::blake2_128(&graph_solution.encode()));
         let exec_plan_uuid = get_uuid_and_increment_seed(&mut uuid_seed);
+        let route_hash = get_route_hash(&graph_solution);
+        let deposit_salt = get_graph_solution_deposit_salt(&graph_solution)?;
 
         let prestart_user_to_escrow_transfer = {
             let start_edge = graph_solution.paths[0]
@@ -148,12 +166,768 @@ impl TryFrom<GraphSolution> for ExecutionPlan {
             exec_paths?
         };
 
-        Ok(Self {
+        // Mirror image of the prestart transfer. Its UUID is generated last so that the
+        // other steps' UUIDs are unchanged
+        let refund_escrow_to_user_transfer = {
+            let mut refund_step = prestart_user_to_escrow_transfer.clone();
+            let refund_uuid = get_uuid_and_increment_seed(&mut uuid_seed);
+            let refund_common = CommonExecutionMeta {
+                src_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+                dest_addr: UniversalAddress::Ethereum(graph_solution.src_addr.clone()),
+                ..common_execution_meta(&prestart_user_to_escrow_transfer)
+            };
+            match &mut refund_step.inner {
+                ExecutionStepEnum::EthSend(step) => {
+                    step.uuid = refund_uuid;
+                    step.amount = None;
+                    step.common = refund_common;
+                }
+                ExecutionStepEnum::ERC20Transfer(step) => {
+                    step.uuid = refund_uuid;
+                    step.amount = None;
+                    step.common = refund_common;
+                }
+                _ => unreachable!("Prestart step is always an EthSend or ERC20Transfer"),
+            }
+            refund_step
+        };
+
+        let deposit_forwarder_factory =
+            get_chain_info_from_chain_id(&prestart_user_to_escrow_transfer.get_src_chain())
+                .ok_or(GraphToExecConversionError::NoChainInfo)?
+                .deposit_forwarder_factory;
+
+        let mut exec_plan = Self {
             uuid: exec_plan_uuid,
             paths,
             prestart_user_to_escrow_transfer,
+            deposit_sweep: None,
             postend_escrow_to_user_transfer,
+            refund_escrow_to_user_transfer,
+            path_execution_mode: PathExecutionMode::Concurrent,
+            execution_policy: ExecutionPolicy::Default,
+            // The caller sets the actual deadline since it depends on the current time
+            deadline_millis: MillisSinceEpoch::MAX,
+            last_progress_millis: 0,
+            paper_trade: false,
+            dest_name: None,
+            metadata: None,
+            quote: None,
+            prestart_confirmed_block: None,
+            review_status: PlanReviewStatus::NotNeeded,
+            route_hash: Some(route_hash),
+            // The quote engine sets it along with quote
+            quoted_terms: None,
+            remove_liquidity: None,
+            sponsorship_campaign_id: None,
+        };
+        if let Some(factory) = deposit_forwarder_factory {
+            set_deposit_forwarder(&mut exec_plan, &factory, deposit_salt)?;
+        }
+        Ok(exec_plan)
+    }
+}
+
+// The salt of the plan's deposit address (see deposit_address). The caller computes it before
+// the GraphSolution is moved into the plan, in case it sets a deposit forwarder afterwards
+pub fn get_graph_solution_deposit_salt(
+    graph_solution: &GraphSolution,
+) -> Result<DepositSalt, GraphToExecConversionError> {
+    let graph_path = &graph_solution
+        .paths
+        .first()
+        .ok_or(GraphToExecConversionError::GraphSolutionPathsLengthZero)?
+        .path
+        .0;
+    let (src_token, _) = graph_path
+        .first()
+        .ok_or(GraphToExecConversionError::GraphPathLengthZero)?
+        .get_src_dest_token();
+    let (_, dest_token) = graph_path
+        .last()
+        .ok_or(GraphToExecConversionError::GraphPathLengthZero)?
+        .get_src_dest_token();
+    Ok(get_deposit_salt(
+        &graph_solution.src_addr,
+        &graph_solution.dest_addr,
+        src_token,
+        dest_token,
+        graph_solution.amount_in,
+    ))
+}
+
+// The user deposits into an address unique to this plan (factory's CREATE2 address for salt)
+// instead of the escrow, and a deposit sweep step then moves the deposit into the escrow. A
+// sweep from another factory is replaced. Call it before set_plan_escrow, which only the shared
+// escrow's plans can have a sweep for
+pub fn set_deposit_forwarder(
+    exec_plan: &mut ExecutionPlan,
+    factory: &DepositForwarderFactory,
+    salt: DepositSalt,
+) -> Result<(), GraphToExecConversionError> {
+    let prestart = &mut exec_plan.prestart_user_to_escrow_transfer;
+    let token =
+        get_transfer_token(prestart).ok_or(GraphToExecConversionError::InvalidDepositForwarder)?;
+    let prestart_common = prestart.get_common_mut();
+    let escrow_addr = UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS);
+    let is_into_escrow = match &exec_plan.deposit_sweep {
+        Some(sweep) => sweep.get_common().dest_addr == escrow_addr,
+        None => prestart_common.dest_addr == escrow_addr,
+    };
+    if !is_into_escrow {
+        return Err(GraphToExecConversionError::DepositForwarderNeedsSharedEscrow);
+    }
+    prestart_common.dest_addr = UniversalAddress::Ethereum(get_deposit_address(factory, &salt));
+    let prestart_common = prestart_common.clone();
+    let sweep_step = EthDepositSweepStep {
+        uuid: Uuid::new(sp_core_hashing::blake2_128(
+            &(prestart.get_uuid(), b"sweep").encode(),
+        )),
+        token,
+        factory_addr: factory.addr,
+        salt,
+        amount: prestart.get_amount_in(),
+        common: CommonExecutionMeta {
+            src_addr: prestart_common.dest_addr,
+            dest_addr: escrow_addr,
+            gas_fee_native: prestart_common.gas_fee_native,
+            gas_fee_usd: prestart_common.gas_fee_usd,
+        },
+        status: EthStepStatus::NotStarted,
+    };
+    exec_plan.deposit_sweep = Some(ExecutionStep::new(ExecutionStepEnum::EthDepositSweep(
+        sweep_step,
+    )));
+    Ok(())
+}
+
+// Delivers the wrapped native token (e.g. WGLMR) instead of the native token that the plan
+// swaps into. A path that ends in an unwrap (or a swap for ETH) keeps the wrapped token instead,
+// and any other path gets a wrap step before the postend transfer, which becomes an ERC20Transfer
+pub fn set_wrapped_delivery(
+    exec_plan: &mut ExecutionPlan,
+) -> Result<(), GraphToExecConversionError> {
+    let (uuid, chain, common) = match &exec_plan.postend_escrow_to_user_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => (step.uuid.clone(), step.chain, step.common.clone()),
+        _ => return Err(GraphToExecConversionError::DestTokenNotNative),
+    };
+    let weth_addr = get_chain_info_from_chain_id(&chain)
+        .ok_or(GraphToExecConversionError::NoChainInfo)?
+        .weth_addr
+        .ok_or(GraphToExecConversionError::NoWrappedNativeToken)?;
+
+    for exec_path in exec_plan.paths.iter_mut() {
+        match exec_path.steps.last() {
+            None => return Err(GraphToExecConversionError::GraphPathLengthZero),
+            Some(ExecutionStep {
+                inner: ExecutionStepEnum::EthUnwrap(_),
+                ..
+            }) => {
+                // i.e. the user swaps the wrapped token for the native token
+                if exec_path.steps.len() == 1 {
+                    return Err(GraphToExecConversionError::WrappedDeliveryOfWrappedToken);
+                }
+                exec_path.steps.pop();
+            }
+            Some(ExecutionStep {
+                inner:
+                    ExecutionStepEnum::EthDexSwap(EthDexSwapStep {
+                        dex_router_func: DexRouterFunction::SwapExactTokensForETH,
+                        ..
+                    }),
+                ..
+            }) => {
+                // The token path already ends in the wrapped token
+                if let Some(ExecutionStepEnum::EthDexSwap(step)) =
+                    exec_path.steps.last_mut().map(|step| &mut step.inner)
+                {
+                    step.dex_router_func = DexRouterFunction::SwapExactTokensForTokens;
+                }
+            }
+            Some(last_step) => {
+                let wrap_step = EthWrapStep {
+                    uuid: Uuid::new(sp_core_hashing::blake2_128(
+                        &(last_step.get_uuid(), b"wrap").encode(),
+                    )),
+                    chain,
+                    amount: None,
+                    common: CommonExecutionMeta {
+                        dest_addr: common.src_addr.clone(),
+                        ..common.clone()
+                    },
+                    status: EthStepStatus::NotStarted,
+                };
+                exec_path
+                    .steps
+                    .push(ExecutionStep::new(ExecutionStepEnum::EthWrap(wrap_step)));
+            }
+        }
+    }
+
+    exec_plan.postend_escrow_to_user_transfer =
+        ExecutionStep::new(ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+            uuid,
+            token: UniversalTokenId {
+                chain,
+                id: ChainTokenId::ERC20(ERC20Token { addr: weth_addr }),
+            },
+            amount: None,
+            common,
+            status: EthStepStatus::NotStarted,
+        }));
+    Ok(())
+}
+
+// Switches the plan to the other form of its deposit, for a user who was quoted the native token
+// but deposited the wrapped one (e.g. WGLMR) or vice versa. The prestart step (and deposit sweep)
+// keep their UUIDs and statuses. Each path then drops the wrap or unwrap it started with, switches
+// its first DEX swap between SwapExactETHForTokens and SwapExactTokensForTokens, or gets an
+// unwrap or wrap in front
+pub fn switch_deposit_form(
+    exec_plan: &mut ExecutionPlan,
+) -> Result<(), GraphToExecConversionError> {
+    let chain = exec_plan.prestart_user_to_escrow_transfer.get_src_chain();
+    let weth = UniversalTokenId {
+        chain,
+        id: ChainTokenId::ERC20(ERC20Token {
+            addr: get_chain_info_from_chain_id(&chain)
+                .ok_or(GraphToExecConversionError::NoChainInfo)?
+                .weth_addr
+                .ok_or(GraphToExecConversionError::NoWrappedNativeToken)?,
+        }),
+    };
+    let native = UniversalTokenId {
+        chain,
+        id: ChainTokenId::Native,
+    };
+    let new_prestart = match &exec_plan.prestart_user_to_escrow_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+            uuid: step.uuid.clone(),
+            token: weth.clone(),
+            amount: step.amount,
+            common: step.common.clone(),
+            status: step.status.clone(),
+        }),
+        ExecutionStepEnum::ERC20Transfer(step) if step.token == weth => {
+            ExecutionStepEnum::EthSend(EthSendStep {
+                uuid: step.uuid.clone(),
+                chain,
+                amount: step.amount,
+                common: step.common.clone(),
+                status: step.status.clone(),
+            })
+        }
+        _ => return Err(GraphToExecConversionError::SrcTokenNotNativeOrWrapped),
+    };
+    let deposited_wrapped = matches!(new_prestart, ExecutionStepEnum::ERC20Transfer(_));
+
+    for exec_path in exec_plan.paths.iter_mut() {
+        let first_step = exec_path
+            .steps
+            .first_mut()
+            .ok_or(GraphToExecConversionError::GraphPathLengthZero)?;
+        let amount_in = first_step.get_amount_in();
+        match &mut first_step.inner {
+            ExecutionStepEnum::EthWrap(_) if deposited_wrapped => {}
+            ExecutionStepEnum::EthUnwrap(_) if !deposited_wrapped => {}
+            ExecutionStepEnum::EthDexSwap(step)
+                if deposited_wrapped
+                    && step.dex_router_func == DexRouterFunction::SwapExactETHForTokens =>
+            {
+                // The token path already starts with the wrapped token
+                step.dex_router_func = DexRouterFunction::SwapExactTokensForTokens;
+                continue;
+            }
+            ExecutionStepEnum::EthDexSwap(step)
+                if !deposited_wrapped
+                    && step.dex_router_func == DexRouterFunction::SwapExactTokensForTokens =>
+            {
+                step.dex_router_func = DexRouterFunction::SwapExactETHForTokens;
+                continue;
+            }
+            _ => {
+                let common = first_step.get_common();
+                let uuid = Uuid::new(sp_core_hashing::blake2_128(
+                    &(first_step.get_uuid(), b"deposit-form").encode(),
+                ));
+                let common = CommonExecutionMeta {
+                    dest_addr: common.src_addr.clone(),
+                    ..common.clone()
+                };
+                let status = EthStepStatus::NotStarted;
+                let new_first_step = if deposited_wrapped {
+                    ExecutionStepEnum::EthUnwrap(EthUnwrapStep {
+                        uuid,
+                        chain,
+                        amount: amount_in,
+                        common,
+                        status,
+                    })
+                } else {
+                    ExecutionStepEnum::EthWrap(EthWrapStep {
+                        uuid,
+                        chain,
+                        amount: amount_in,
+                        common,
+                        status,
+                    })
+                };
+                exec_path
+                    .steps
+                    .insert(0, ExecutionStep::new(new_first_step));
+                continue;
+            }
+        }
+        // The path starts by turning the deposit into the form the user deposited
+        if exec_path.steps.len() == 1 {
+            return Err(GraphToExecConversionError::DepositFormSwitchIsNoOp);
+        }
+        exec_path.steps.remove(0);
+        if let Some(amount_in) = amount_in {
+            exec_path.steps[0].set_amount_in(amount_in);
+        }
+    }
+
+    if let Some(ExecutionStep {
+        inner: ExecutionStepEnum::EthDepositSweep(step),
+        ..
+    }) = exec_plan.deposit_sweep.as_mut()
+    {
+        step.token = if deposited_wrapped { weth } else { native };
+    }
+    exec_plan.prestart_user_to_escrow_transfer.inner = new_prestart;
+    Ok(())
+}
+
+// For a user who deposited a slightly different amount than they were quoted. Every amount the
+// plan fixes upfront (the prestart step, deposit sweep, the paths' first steps and the quote) is
+// scaled by deposited_amount / quoted amount. Rounding leftovers go to the last path, so that the
+// paths still add up to exactly what was deposited
+pub fn rescale_deposit(
+    exec_plan: &mut ExecutionPlan,
+    deposited_amount: Amount,
+) -> Result<(), GraphToExecConversionError> {
+    let quoted_amount = match exec_plan.prestart_user_to_escrow_transfer.get_amount_in() {
+        Some(amount) if amount > 0 => amount,
+        _ => return Err(GraphToExecConversionError::NoDepositAmount),
+    };
+    let rescale = |amount: Amount| mul_ratio_u128(amount, deposited_amount, quoted_amount);
+
+    // An LP source's paths are in the pair's tokens. set_remove_liquidity_amounts_out sets them
+    // once the deposit is redeemed
+    if exec_plan.remove_liquidity.is_none() {
+        let mut total_path_amount_in: Amount = 0;
+        for exec_path in exec_plan.paths.iter_mut() {
+            let first_step = exec_path
+                .steps
+                .first_mut()
+                .ok_or(GraphToExecConversionError::GraphPathLengthZero)?;
+            let amount_in = first_step
+                .get_amount_in()
+                .ok_or(GraphToExecConversionError::NoDepositAmount)?;
+            first_step.set_amount_in(rescale(amount_in));
+            total_path_amount_in += rescale(amount_in);
+        }
+        if let Some(last_step) = exec_plan
+            .paths
+            .last_mut()
+            .and_then(|exec_path| exec_path.steps.first_mut())
+        {
+            let amount_in = last_step.get_amount_in().unwrap_or(0);
+            last_step
+                .set_amount_in((amount_in + deposited_amount).saturating_sub(total_path_amount_in));
+        }
+    }
+
+    exec_plan
+        .prestart_user_to_escrow_transfer
+        .set_amount_in(deposited_amount);
+    if let Some(deposit_sweep) = exec_plan.deposit_sweep.as_mut() {
+        deposit_sweep.set_amount_in(deposited_amount);
+    }
+    if let Some(remove_liquidity) = exec_plan.remove_liquidity.as_mut() {
+        remove_liquidity.set_amount_in(deposited_amount);
+    }
+    if let Some(quote) = exec_plan.quote.as_mut() {
+        quote.amount_out = rescale(quote.amount_out);
+        quote.amount_in_usd = rescale(quote.amount_in_usd);
+        quote.amount_out_usd = rescale(quote.amount_out_usd);
+    }
+    Ok(())
+}
+
+// Swap-and-call: replaces the postend transfer with a call to call.target_addr that delivers
+// the dest token. The step keeps the transfer's UUID so the rest of the plan is unchanged
+pub fn set_contract_call_delivery(
+    exec_plan: &mut ExecutionPlan,
+    call: ContractCall,
+) -> Result<(), GraphToExecConversionError> {
+    if call.get_amount_offset().is_none() {
+        return Err(GraphToExecConversionError::InvalidContractCall);
+    }
+    let (uuid, token, common) = match &exec_plan.postend_escrow_to_user_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => (
+            step.uuid.clone(),
+            UniversalTokenId {
+                chain: step.chain,
+                id: ChainTokenId::Native,
+            },
+            step.common.clone(),
+        ),
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            (step.uuid.clone(), step.token.clone(), step.common.clone())
+        }
+        _ => return Err(GraphToExecConversionError::InvalidContractCall),
+    };
+    let approval_uuid = Uuid::new(sp_core_hashing::blake2_128(&(&uuid, b"approve").encode()));
+    exec_plan.postend_escrow_to_user_transfer =
+        ExecutionStep::new(ExecutionStepEnum::EthContractCall(EthContractCallStep {
+            uuid,
+            token,
+            common: CommonExecutionMeta {
+                dest_addr: UniversalAddress::Ethereum(call.target_addr),
+                ..common
+            },
+            call,
+            amount: None,
+            approval_uuid,
+            approval_txn_hash: None,
+            status: EthStepStatus::NotStarted,
+        }));
+    Ok(())
+}
+
+// Zap-in: rather than transfer the dest token to the user, the escrow swaps half of it into the
+// pair's other token and adds both as liquidity, so the user gets the pair's LP tokens. The dest
+// token must be an ERC20, so call set_wrapped_delivery first for a native one
+pub fn set_zap_in_delivery(
+    exec_plan: &mut ExecutionPlan,
+    dex_router_addr: EthAddress,
+    pair_token: UniversalTokenId,
+    lp_token: UniversalTokenId,
+) -> Result<(), GraphToExecConversionError> {
+    let (uuid, token, common) = match &exec_plan.postend_escrow_to_user_transfer.inner {
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            (step.uuid.clone(), step.token.clone(), step.common.clone())
+        }
+        _ => return Err(GraphToExecConversionError::InvalidZapIn),
+    };
+    if pair_token == token || pair_token.chain != token.chain || lp_token.chain != token.chain {
+        return Err(GraphToExecConversionError::InvalidZapIn);
+    }
+    let swap_uuid = Uuid::new(sp_core_hashing::blake2_128(&(&uuid, b"swap").encode()));
+    exec_plan.postend_escrow_to_user_transfer =
+        ExecutionStep::new(ExecutionStepEnum::EthZapIn(EthZapInStep {
+            uuid,
+            dex_router_addr,
+            token,
+            pair_token,
+            lp_token,
+            amount: None,
+            common,
+            swap_uuid,
+            swap_txn_hash: None,
+            pair_token_amount: None,
+            status: EthStepStatus::NotStarted,
+        }));
+    Ok(())
+}
+
+// LP source (zap-out): the user deposits LP tokens of the pair at lp_token, which the escrow
+// redeems for the pair's two tokens before the paths start. exec_plan routes one of them and
+// token_b_plan the other, each from what lp_amount redeems for at quote time, to the same dest
+// token. token_b_plan's paths are moved into exec_plan, whose prestart and refund steps become
+// LP token transfers. Call it before any of the other set_* functions
+pub fn set_lp_source(
+    exec_plan: &mut ExecutionPlan,
+    token_b_plan: ExecutionPlan,
+    dex_router_addr: EthAddress,
+    lp_token: UniversalTokenId,
+    lp_amount: Amount,
+) -> Result<(), GraphToExecConversionError> {
+    let get_deposited_token =
+        |plan: &ExecutionPlan| match &plan.prestart_user_to_escrow_transfer.inner {
+            ExecutionStepEnum::ERC20Transfer(step) => Some(step.token.clone()),
+            _ => None,
+        };
+    let (token_a, token_b) = match (
+        get_deposited_token(exec_plan),
+        get_deposited_token(&token_b_plan),
+    ) {
+        (Some(token_a), Some(token_b)) => (token_a, token_b),
+        _ => return Err(GraphToExecConversionError::InvalidLpSource),
+    };
+    let postend_a = &exec_plan.postend_escrow_to_user_transfer;
+    let postend_b = &token_b_plan.postend_escrow_to_user_transfer;
+    if token_a == token_b
+        || token_a.chain != lp_token.chain
+        || token_b.chain != lp_token.chain
+        || lp_token == token_a
+        || lp_token == token_b
+        || exec_plan.remove_liquidity.is_some()
+        || core::mem::discriminant(&postend_a.inner) != core::mem::discriminant(&postend_b.inner)
+        || postend_a.get_src_chain() != postend_b.get_src_chain()
+        || postend_a.get_common().dest_addr != postend_b.get_common().dest_addr
+        || get_transfer_token(postend_a) != get_transfer_token(postend_b)
+    {
+        return Err(GraphToExecConversionError::InvalidLpSource);
+    }
+
+    let num_token_a_paths = exec_plan.paths.len() as u32;
+    exec_plan.paths.extend(token_b_plan.paths);
+    for transfer_step in [
+        &mut exec_plan.prestart_user_to_escrow_transfer,
+        &mut exec_plan.refund_escrow_to_user_transfer,
+    ] {
+        if let ExecutionStepEnum::ERC20Transfer(step) = &mut transfer_step.inner {
+            step.token = lp_token.clone();
+        }
+    }
+    exec_plan
+        .prestart_user_to_escrow_transfer
+        .set_amount_in(lp_amount);
+    if let Some(ExecutionStep {
+        inner: ExecutionStepEnum::EthDepositSweep(step),
+        ..
+    }) = exec_plan.deposit_sweep.as_mut()
+    {
+        step.token = lp_token.clone();
+        step.amount = Some(lp_amount);
+    }
+
+    let prestart_common = common_execution_meta(&exec_plan.prestart_user_to_escrow_transfer);
+    let uuid = Uuid::new(sp_core_hashing::blake2_128(
+        &(&exec_plan.uuid, b"remove_liquidity").encode(),
+    ));
+    let approval_uuid = Uuid::new(sp_core_hashing::blake2_128(&(&uuid, b"approve").encode()));
+    exec_plan.remove_liquidity = Some(ExecutionStep::new(ExecutionStepEnum::EthRemoveLiquidity(
+        EthRemoveLiquidityStep {
+            uuid,
+            dex_router_addr,
+            lp_token,
+            token_a,
+            token_b,
+            num_token_a_paths,
+            amount: Some(lp_amount),
+            common: CommonExecutionMeta {
+                src_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+                dest_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+                gas_fee_native: prestart_common.gas_fee_native,
+                gas_fee_usd: prestart_common.gas_fee_usd,
+            },
+            approval_uuid,
+            approval_txn_hash: None,
+            token_a_amount_out: None,
+            token_b_amount_out: None,
+            status: EthStepStatus::NotStarted,
+        },
+    )));
+    Ok(())
+}
+
+// Once the LP tokens are redeemed, each token's paths are scaled to what removeLiquidity
+// actually paid out rather than what the LP tokens redeemed for at quote time
+pub fn set_remove_liquidity_amounts_out(
+    exec_plan: &mut ExecutionPlan,
+) -> Result<(), GraphToExecConversionError> {
+    let (num_token_a_paths, token_a_amount_out, token_b_amount_out) =
+        match exec_plan.remove_liquidity.as_ref().map(|step| &step.inner) {
+            Some(ExecutionStepEnum::EthRemoveLiquidity(EthRemoveLiquidityStep {
+                num_token_a_paths,
+                token_a_amount_out: Some(token_a_amount_out),
+                token_b_amount_out: Some(token_b_amount_out),
+                ..
+            })) => (
+                *num_token_a_paths as usize,
+                *token_a_amount_out,
+                *token_b_amount_out,
+            ),
+            _ => return Err(GraphToExecConversionError::InvalidLpSource),
+        };
+    if num_token_a_paths == 0 || num_token_a_paths >= exec_plan.paths.len() {
+        return Err(GraphToExecConversionError::InvalidLpSource);
+    }
+    let (token_a_paths, token_b_paths) = exec_plan.paths.split_at_mut(num_token_a_paths);
+    rescale_paths_amount_in(token_a_paths, token_a_amount_out)?;
+    rescale_paths_amount_in(token_b_paths, token_b_amount_out)
+}
+
+// Integrator markup: every path ends with a fee skim that sends markup_bps of what the path
+// delivered to recipient, so the postend step only gets the rest. Call it after
+// set_wrapped_delivery, since the skim is in whichever token the postend step delivers
+pub fn set_integrator_markup(
+    exec_plan: &mut ExecutionPlan,
+    markup_bps: u16,
+    recipient: EthAddress,
+) -> Result<(), GraphToExecConversionError> {
+    if markup_bps == 0 || markup_bps > MAX_INTEGRATOR_MARKUP_BPS {
+        return Err(GraphToExecConversionError::InvalidIntegratorMarkup);
+    }
+    let (token, common) = match &exec_plan.postend_escrow_to_user_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => (
+            UniversalTokenId {
+                chain: step.chain,
+                id: ChainTokenId::Native,
+            },
+            step.common.clone(),
+        ),
+        ExecutionStepEnum::ERC20Transfer(step) => (step.token.clone(), step.common.clone()),
+        ExecutionStepEnum::EthContractCall(step) => (step.token.clone(), step.common.clone()),
+        ExecutionStepEnum::EthZapIn(step) => (step.token.clone(), step.common.clone()),
+        _ => return Err(GraphToExecConversionError::InvalidIntegratorMarkup),
+    };
+    for exec_path in exec_plan.paths.iter_mut() {
+        let last_step = exec_path
+            .steps
+            .last()
+            .ok_or(GraphToExecConversionError::GraphPathLengthZero)?;
+        if let ExecutionStepEnum::EthFeeSkim(_) = last_step.inner {
+            return Err(GraphToExecConversionError::InvalidIntegratorMarkup);
+        }
+        let skim_step = EthFeeSkimStep {
+            uuid: Uuid::new(sp_core_hashing::blake2_128(
+                &(last_step.get_uuid(), b"markup").encode(),
+            )),
+            token: token.clone(),
+            markup_bps,
+            amount: None,
+            common: CommonExecutionMeta {
+                dest_addr: UniversalAddress::Ethereum(recipient),
+                ..common.clone()
+            },
+            status: EthStepStatus::NotStarted,
+        };
+        exec_path
+            .steps
+            .push(ExecutionStep::new(ExecutionStepEnum::EthFeeSkim(skim_step)));
+    }
+    Ok(())
+}
+
+// Moves the plan from the shared escrow to another escrow account (e.g. one dedicated to an
+// integrator), i.e. every step that sends from or to the shared escrow uses escrow_eth_addr or
+// escrow_substrate_pubkey instead. On chains with a hashed EVM account mapping, transfers into
+// the escrow go to the account that backs escrow_eth_addr
+pub fn set_plan_escrow(
+    exec_plan: &mut ExecutionPlan,
+    escrow_eth_addr: EthAddress,
+    escrow_substrate_pubkey: SubstratePublicKey,
+) -> Result<(), GraphToExecConversionError> {
+    if exec_plan.deposit_sweep.is_some() {
+        return Err(GraphToExecConversionError::DepositForwarderNeedsSharedEscrow);
+    }
+    let escrow_evm_mapped_addr = SubstratePublicKey {
+        0: h160_to_mapped_account(&escrow_eth_addr.0),
+    };
+    let rebind = |addr: &mut UniversalAddress| match addr {
+        UniversalAddress::Ethereum(eth_addr) if *eth_addr == ESCROW_ETH_ADDRESS => {
+            *eth_addr = escrow_eth_addr
+        }
+        UniversalAddress::Substrate(pubkey) if *pubkey == ESCROW_SUBSTRATE_PUBLIC_KEY => {
+            *pubkey = escrow_substrate_pubkey.clone()
+        }
+        UniversalAddress::Substrate(pubkey) if *pubkey == ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS => {
+            *pubkey = escrow_evm_mapped_addr.clone()
+        }
+        _ => {}
+    };
+    let steps = exec_plan
+        .paths
+        .iter_mut()
+        .flat_map(|path| path.steps.iter_mut())
+        .chain([
+            &mut exec_plan.prestart_user_to_escrow_transfer,
+            &mut exec_plan.postend_escrow_to_user_transfer,
+            &mut exec_plan.refund_escrow_to_user_transfer,
+        ])
+        .chain(exec_plan.remove_liquidity.iter_mut());
+    for step in steps {
+        let common = step.get_common_mut();
+        rebind(&mut common.src_addr);
+        rebind(&mut common.dest_addr);
+    }
+    Ok(())
+}
+
+// The graph we route on already leaves the blocked pools out, but a GraphSolution can outlive
+// the graph it was found on (e.g. a cached quote that is planned after the pool got blocked)
+pub fn check_no_blocked_pools(
+    graph_solution: &GraphSolution,
+    pool_blocklist: &PoolBlocklistOverlay,
+) -> Result<(), GraphToExecConversionError> {
+    let is_blocked = graph_solution
+        .paths
+        .iter()
+        .flat_map(|split_path| split_path.path.0.iter())
+        .any(|edge| match edge {
+            Edge::Swap(SwapEdge::CPMM(cpmm_edge)) => {
+                pool_blocklist.is_blocked(cpmm_edge.dex.chain_id, &cpmm_edge.pair_address)
+            }
+            _ => false,
+        });
+    if is_blocked {
+        Err(GraphToExecConversionError::BlockedPool)
+    } else {
+        Ok(())
+    }
+}
+
+// Scales the paths' first steps so that they add up to exactly amount_in. Rounding leftovers go
+// to the last path
+fn rescale_paths_amount_in(
+    exec_paths: &mut [ExecutionPath],
+    amount_in: Amount,
+) -> Result<(), GraphToExecConversionError> {
+    let mut first_steps = exec_paths
+        .iter_mut()
+        .map(|exec_path| {
+            exec_path
+                .steps
+                .first_mut()
+                .ok_or(GraphToExecConversionError::GraphPathLengthZero)
         })
+        .collect::<Result<Vec<&mut ExecutionStep>, GraphToExecConversionError>>()?;
+    let total_amount_in: Amount = first_steps
+        .iter()
+        .map(|step| step.get_amount_in().unwrap_or(0))
+        .sum();
+    if total_amount_in == 0 {
+        return Err(GraphToExecConversionError::NoDepositAmount);
+    }
+    let num_steps = first_steps.len();
+    let mut amount_left = amount_in;
+    for (i, step) in first_steps.iter_mut().enumerate() {
+        let new_amount_in = if i + 1 == num_steps {
+            amount_left
+        } else {
+            mul_ratio_u128(
+                step.get_amount_in().unwrap_or(0),
+                amount_in,
+                total_amount_in,
+            )
+        };
+        amount_left = amount_left.saturating_sub(new_amount_in);
+        step.set_amount_in(new_amount_in);
+    }
+    Ok(())
+}
+
+fn get_transfer_token(transfer_step: &ExecutionStep) -> Option<UniversalTokenId> {
+    match &transfer_step.inner {
+        ExecutionStepEnum::EthSend(step) => Some(UniversalTokenId {
+            chain: step.chain,
+            id: ChainTokenId::Native,
+        }),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(step.token.clone()),
+        _ => None,
+    }
+}
+
+fn common_execution_meta(transfer_step: &ExecutionStep) -> CommonExecutionMeta {
+    match &transfer_step.inner {
+        ExecutionStepEnum::EthSend(step) => step.common.clone(),
+        ExecutionStepEnum::ERC20Transfer(step) => step.common.clone(),
+        _ => unreachable!("Prestart step is always an EthSend or ERC20Transfer"),
     }
 }
 
@@ -201,6 +975,14 @@ fn split_graph_path_to_exec_path(
                     &parse_swap_state,
                 )
             }
+            Edge::Bridge(BridgeEdge::Wormhole(edge)) => {
+                process_graph_edge_helper::process_wormhole_bridge_edge(
+                    uuid_seed,
+                    edge,
+                    &amount_in,
+                    &parse_swap_state,
+                )
+            }
             Edge::Swap(SwapEdge::Wrap(edge)) => process_graph_edge_helper::process_wrap_edge(
                 uuid_seed,
                 edge,
@@ -228,10 +1010,20 @@ fn split_graph_path_to_exec_path(
                 next_dex_id,
                 is_next_step_unwrap,
             ),
+            Edge::Swap(SwapEdge::Aggregator(edge)) => {
+                process_graph_edge_helper::process_aggregator_edge(
+                    uuid_seed,
+                    edge,
+                    &amount_in,
+                    &parse_swap_state,
+                )
+            }
         }?;
         match process_helper_result {
             ProcessHelperResult::NoChange => {}
             ProcessHelperResult::NewExecStep(new_exec_step) => {
+                // Only the path's first step has a fixed amount in. The rest take the previous
+                // step's realized amount out whole at execution time, so no hop rounds
                 let _ = amount_in.take();
                 let _ = parse_swap_state.take();
                 exec_steps.push(new_exec_step);
@@ -245,6 +1037,7 @@ fn split_graph_path_to_exec_path(
     Ok(ExecutionPath {
         steps: exec_steps,
         amount_out: None,
+        step_amounts_out: Vec::new(),
     })
 }
 
@@ -254,7 +1047,7 @@ fn split_graph_path_to_exec_path(
 #[cfg(test)]
 mod graph_solution_converter_tests {
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
     use scale::Encode;
 
     use super::*;
@@ -381,6 +1174,717 @@ mod graph_solution_converter_tests {
         (graph_solution, exec_plan)
     }
 
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_set_wrapped_delivery() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let amount_in = 100_000_000_000_000_000_000;
+        let src_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2006),
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(18_446_744_073_709_551_619)),
+        };
+        let dest_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ChainTokenId::Native,
+        };
+        let (_, mut exec_plan) = get_validated_graph_solution_and_exec_plan(
+            src_token_id,
+            dest_token_id.clone(),
+            amount_in,
+        );
+        let postend_uuid = exec_plan.postend_escrow_to_user_transfer.get_uuid().clone();
+
+        set_wrapped_delivery(&mut exec_plan).expect("Native dest token");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        let weth_addr = get_chain_info_from_chain_id(&dest_token_id.chain)
+            .unwrap()
+            .weth_addr
+            .unwrap();
+        if let ExecutionStepEnum::ERC20Transfer(step) =
+            &exec_plan.postend_escrow_to_user_transfer.inner
+        {
+            assert_eq!(step.uuid, postend_uuid);
+            assert_eq!(
+                step.token,
+                UniversalTokenId {
+                    chain: dest_token_id.chain,
+                    id: ChainTokenId::ERC20(ERC20Token { addr: weth_addr }),
+                }
+            );
+        } else {
+            assert!(false)
+        }
+        for exec_path in exec_plan.paths.iter() {
+            match &exec_path.steps.last().unwrap().inner {
+                ExecutionStepEnum::EthUnwrap(_) => assert!(false),
+                ExecutionStepEnum::EthDexSwap(step) => {
+                    assert_eq!(
+                        step.token_path.last().unwrap().id,
+                        ChainTokenId::ERC20(ERC20Token { addr: weth_addr })
+                    );
+                    assert!(step.dex_router_func != DexRouterFunction::SwapExactTokensForETH);
+                }
+                _ => {}
+            }
+        }
+
+        // Already delivered as an ERC20
+        assert_eq!(
+            set_wrapped_delivery(&mut exec_plan),
+            Err(GraphToExecConversionError::DestTokenNotNative)
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_switch_deposit_form() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let amount_in = 100_000_000_000_000_000_000;
+        let src_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ChainTokenId::Native,
+        };
+        let dest_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2006),
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(18_446_744_073_709_551_619)),
+        };
+        let (_, mut exec_plan) = get_validated_graph_solution_and_exec_plan(
+            src_token_id.clone(),
+            dest_token_id,
+            amount_in,
+        );
+        let original_plan = exec_plan.clone();
+        let prestart_uuid = exec_plan
+            .prestart_user_to_escrow_transfer
+            .get_uuid()
+            .clone();
+
+        switch_deposit_form(&mut exec_plan).expect("Native src token");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        let weth_addr = get_chain_info_from_chain_id(&src_token_id.chain)
+            .unwrap()
+            .weth_addr
+            .unwrap();
+        if let ExecutionStepEnum::ERC20Transfer(step) =
+            &exec_plan.prestart_user_to_escrow_transfer.inner
+        {
+            assert_eq!(step.uuid, prestart_uuid);
+            assert_eq!(step.amount, Some(amount_in));
+            assert_eq!(
+                step.token,
+                UniversalTokenId {
+                    chain: src_token_id.chain,
+                    id: ChainTokenId::ERC20(ERC20Token { addr: weth_addr }),
+                }
+            );
+        } else {
+            assert!(false)
+        }
+        for exec_path in exec_plan.paths.iter() {
+            match &exec_path.steps.first().unwrap().inner {
+                ExecutionStepEnum::EthWrap(_) => assert!(false),
+                ExecutionStepEnum::EthDexSwap(step) => {
+                    assert!(step.dex_router_func != DexRouterFunction::SwapExactETHForTokens);
+                }
+                _ => {}
+            }
+        }
+
+        // Switching back gives the original plan
+        switch_deposit_form(&mut exec_plan).expect("Wrapped src token");
+        assert_eq!(
+            exec_plan.prestart_user_to_escrow_transfer,
+            original_plan.prestart_user_to_escrow_transfer
+        );
+        assert_eq!(exec_plan.paths.len(), original_plan.paths.len());
+        for (exec_path, original_path) in exec_plan.paths.iter().zip(original_plan.paths.iter()) {
+            let original_first_step = &original_path.steps.first().unwrap().inner;
+            // A removed wrap is added back with a new UUID
+            if !matches!(original_first_step, ExecutionStepEnum::EthWrap(_)) {
+                assert_eq!(&exec_path.steps.first().unwrap().inner, original_first_step);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_contract_call_delivery() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        let postend_uuid = exec_plan.postend_escrow_to_user_transfer.get_uuid().clone();
+        let target_addr = EthAddress {
+            0: hex!("00000000000000000000000000000000000000aa"),
+        };
+
+        // deposit(address,uint256) with the amount placeholder as the second argument
+        let mut calldata_template = hex!("47e7ef24").to_vec();
+        calldata_template.extend_from_slice(&[0u8; 12]);
+        calldata_template.extend_from_slice(&hex!("00000000000000000000000000000000000000bb"));
+        calldata_template
+            .extend_from_slice(&crate::execution_plan::CONTRACT_CALL_AMOUNT_PLACEHOLDER);
+        let call = ContractCall {
+            target_addr,
+            calldata_template: calldata_template.clone(),
+        };
+
+        // No placeholder
+        let bad_call = ContractCall {
+            target_addr,
+            calldata_template: calldata_template[..36].to_vec(),
+        };
+        assert_eq!(
+            set_contract_call_delivery(&mut exec_plan.clone(), bad_call),
+            Err(GraphToExecConversionError::InvalidContractCall)
+        );
+
+        set_contract_call_delivery(&mut exec_plan, call.clone()).expect("Valid contract call");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        if let ExecutionStepEnum::EthContractCall(step) =
+            &exec_plan.postend_escrow_to_user_transfer.inner
+        {
+            assert_eq!(step.uuid, postend_uuid);
+            assert_ne!(step.approval_uuid, postend_uuid);
+            assert_eq!(
+                step.common.dest_addr,
+                UniversalAddress::Ethereum(target_addr)
+            );
+            assert!(step.amount.is_none());
+        } else {
+            assert!(false)
+        }
+
+        let calldata = call.get_calldata(12_345).expect("Valid contract call");
+        assert_eq!(calldata[..36], calldata_template[..36]);
+        assert_eq!(calldata[36..66], [0u8; 30]);
+        assert_eq!(calldata[66..], hex!("3039"));
+    }
+
+    #[test]
+    fn test_set_zap_in_delivery() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let src_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2006),
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(18_446_744_073_709_551_619)),
+        };
+        let dest_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ChainTokenId::Native,
+        };
+        let (_, mut exec_plan) = get_validated_graph_solution_and_exec_plan(
+            src_token_id,
+            dest_token_id.clone(),
+            100_000_000_000_000_000_000,
+        );
+        let postend_uuid = exec_plan.postend_escrow_to_user_transfer.get_uuid().clone();
+        let router_addr = EthAddress {
+            0: hex!("00000000000000000000000000000000000000aa"),
+        };
+        let pair_token = UniversalTokenId {
+            chain: dest_token_id.chain,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("00000000000000000000000000000000000000bb"),
+                },
+            }),
+        };
+        let lp_token = UniversalTokenId {
+            chain: dest_token_id.chain,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("00000000000000000000000000000000000000cc"),
+                },
+            }),
+        };
+
+        // A native dest token must be wrapped first
+        assert_eq!(
+            set_zap_in_delivery(
+                &mut exec_plan.clone(),
+                router_addr,
+                pair_token.clone(),
+                lp_token.clone()
+            ),
+            Err(GraphToExecConversionError::InvalidZapIn)
+        );
+        set_wrapped_delivery(&mut exec_plan).expect("Native dest token");
+        let weth_token = match &exec_plan.postend_escrow_to_user_transfer.inner {
+            ExecutionStepEnum::ERC20Transfer(step) => step.token.clone(),
+            _ => panic!("Expect a wrapped delivery"),
+        };
+
+        // The token with itself
+        assert_eq!(
+            set_zap_in_delivery(
+                &mut exec_plan.clone(),
+                router_addr,
+                weth_token.clone(),
+                lp_token.clone()
+            ),
+            Err(GraphToExecConversionError::InvalidZapIn)
+        );
+
+        set_zap_in_delivery(
+            &mut exec_plan,
+            router_addr,
+            pair_token.clone(),
+            lp_token.clone(),
+        )
+        .expect("Valid zap-in");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        if let ExecutionStepEnum::EthZapIn(step) = &exec_plan.postend_escrow_to_user_transfer.inner
+        {
+            assert_eq!(step.uuid, postend_uuid);
+            assert_ne!(step.swap_uuid, postend_uuid);
+            assert_eq!(step.token, weth_token);
+            assert_eq!(step.pair_token, pair_token);
+            assert_eq!(step.lp_token, lp_token);
+            assert!(step.is_swapping());
+            assert!(step.amount.is_none());
+        } else {
+            assert!(false)
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_set_lp_source() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let token_a = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
+                },
+            }),
+        };
+        let token_b = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055"),
+                },
+            }),
+        };
+        let dest_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2006),
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(18_446_744_073_709_551_619)),
+        };
+        let (_, mut exec_plan) = get_validated_graph_solution_and_exec_plan(
+            token_a.clone(),
+            dest_token_id.clone(),
+            100_000_000_000_000_000_000,
+        );
+        let (_, token_b_plan) =
+            get_validated_graph_solution_and_exec_plan(token_b.clone(), dest_token_id, 100_000_000);
+        let router_addr = EthAddress {
+            0: hex!("00000000000000000000000000000000000000aa"),
+        };
+        let lp_token = UniversalTokenId {
+            chain: token_a.chain,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("00000000000000000000000000000000000000cc"),
+                },
+            }),
+        };
+        let lp_amount = 5_000_000_000_000_000_000;
+
+        // Both halves can't be the same token
+        assert_eq!(
+            set_lp_source(
+                &mut exec_plan.clone(),
+                exec_plan.clone(),
+                router_addr,
+                lp_token.clone(),
+                lp_amount
+            ),
+            Err(GraphToExecConversionError::InvalidLpSource)
+        );
+
+        let num_token_a_paths = exec_plan.paths.len();
+        let num_paths = num_token_a_paths + token_b_plan.paths.len();
+        set_lp_source(
+            &mut exec_plan,
+            token_b_plan,
+            router_addr,
+            lp_token.clone(),
+            lp_amount,
+        )
+        .expect("Valid LP source");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        assert_eq!(exec_plan.paths.len(), num_paths);
+        assert_eq!(
+            exec_plan.prestart_user_to_escrow_transfer.get_amount_in(),
+            Some(lp_amount)
+        );
+        if let ExecutionStepEnum::ERC20Transfer(step) =
+            &exec_plan.prestart_user_to_escrow_transfer.inner
+        {
+            assert_eq!(step.token, lp_token);
+        } else {
+            assert!(false)
+        }
+
+        // The paths only take on the redeemed amounts once both are known
+        assert_eq!(
+            set_remove_liquidity_amounts_out(&mut exec_plan),
+            Err(GraphToExecConversionError::InvalidLpSource)
+        );
+        if let Some(ExecutionStep {
+            inner: ExecutionStepEnum::EthRemoveLiquidity(step),
+            ..
+        }) = exec_plan.remove_liquidity.as_mut()
+        {
+            assert_eq!(step.token_a, token_a);
+            assert_eq!(step.token_b, token_b);
+            assert_eq!(step.num_token_a_paths as usize, num_token_a_paths);
+            assert!(step.is_approving());
+            step.token_a_amount_out = Some(3_000_000_000_000_000_000);
+            step.token_b_amount_out = Some(2_000_000);
+        } else {
+            assert!(false)
+        }
+        set_remove_liquidity_amounts_out(&mut exec_plan).expect("Both amounts out are set");
+        let sum_amount_in = |paths: &[ExecutionPath]| -> Amount {
+            paths
+                .iter()
+                .map(|path| path.steps[0].get_amount_in().unwrap_or(0))
+                .sum()
+        };
+        assert_eq!(
+            sum_amount_in(&exec_plan.paths[..num_token_a_paths]),
+            3_000_000_000_000_000_000
+        );
+        assert_eq!(
+            sum_amount_in(&exec_plan.paths[num_token_a_paths..]),
+            2_000_000
+        );
+    }
+
+    #[test]
+    fn test_set_integrator_markup() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        let original_plan = exec_plan.clone();
+        let recipient = EthAddress {
+            0: hex!("00000000000000000000000000000000000000cc"),
+        };
+
+        assert_eq!(
+            set_integrator_markup(&mut exec_plan, 0, recipient),
+            Err(GraphToExecConversionError::InvalidIntegratorMarkup)
+        );
+        assert_eq!(
+            set_integrator_markup(&mut exec_plan, MAX_INTEGRATOR_MARKUP_BPS + 1, recipient),
+            Err(GraphToExecConversionError::InvalidIntegratorMarkup)
+        );
+        assert_eq!(exec_plan, original_plan);
+
+        set_integrator_markup(&mut exec_plan, 30, recipient).expect("Valid markup");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        let postend_common = exec_plan.postend_escrow_to_user_transfer.get_common();
+        for (exec_path, original_path) in exec_plan.paths.iter().zip(original_plan.paths.iter()) {
+            assert_eq!(exec_path.steps.len(), original_path.steps.len() + 1);
+            if let ExecutionStepEnum::EthFeeSkim(step) = &exec_path.steps.last().unwrap().inner {
+                assert_eq!(step.markup_bps, 30);
+                assert_eq!(step.common.src_addr, postend_common.src_addr);
+                assert_eq!(step.common.dest_addr, UniversalAddress::Ethereum(recipient));
+                assert!(step.amount.is_none());
+                assert_eq!(
+                    step.token.chain,
+                    exec_plan.postend_escrow_to_user_transfer.get_src_chain()
+                );
+            } else {
+                assert!(false)
+            }
+        }
+
+        // Only one markup per plan
+        assert_eq!(
+            set_integrator_markup(&mut exec_plan, 30, recipient),
+            Err(GraphToExecConversionError::InvalidIntegratorMarkup)
+        );
+    }
+
+    #[test]
+    fn test_set_plan_escrow() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        // Goes through the relay chain, so the plan also has substrate escrow addresses
+        let graph_solution = graph_solution_factory::graph_solution_full_static();
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        let original_plan = exec_plan.clone();
+        let escrow_eth_addr = EthAddress {
+            0: hex!("00000000000000000000000000000000000000ee"),
+        };
+        let escrow_substrate_pubkey = SubstratePublicKey { 0: [0xee; 32] };
+
+        set_plan_escrow(
+            &mut exec_plan,
+            escrow_eth_addr,
+            escrow_substrate_pubkey.clone(),
+        )
+        .expect("Plan has no deposit sweep");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        assert_eq!(
+            exec_plan
+                .prestart_user_to_escrow_transfer
+                .get_common()
+                .dest_addr,
+            UniversalAddress::Ethereum(escrow_eth_addr)
+        );
+        assert_eq!(
+            exec_plan
+                .refund_escrow_to_user_transfer
+                .get_common()
+                .src_addr,
+            UniversalAddress::Ethereum(escrow_eth_addr)
+        );
+        let shared_escrow_addrs = [
+            UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+            UniversalAddress::Substrate(ESCROW_SUBSTRATE_PUBLIC_KEY),
+            UniversalAddress::Substrate(ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS),
+        ];
+        let mut saw_substrate_escrow = false;
+        for (step, original_step) in exec_plan.paths[0]
+            .steps
+            .iter()
+            .zip(original_plan.paths[0].steps.iter())
+        {
+            for addr in [&step.get_common().src_addr, &step.get_common().dest_addr] {
+                assert!(!shared_escrow_addrs.contains(addr));
+                saw_substrate_escrow |=
+                    *addr == UniversalAddress::Substrate(escrow_substrate_pubkey.clone());
+            }
+            assert_eq!(step.get_uuid(), original_step.get_uuid());
+        }
+        assert!(saw_substrate_escrow);
+    }
+
+    #[test]
+    fn test_set_deposit_forwarder() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let salt = get_graph_solution_deposit_salt(&graph_solution).expect("Has a path");
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        assert!(exec_plan.deposit_sweep.is_none());
+        let original_plan = exec_plan.clone();
+        let factory = DepositForwarderFactory {
+            addr: EthAddress {
+                0: hex!("00000000000000000000000000000000deadbeef"),
+            },
+            forwarder_init_code_hash: [7u8; 32],
+        };
+        let deposit_addr = UniversalAddress::Ethereum(get_deposit_address(&factory, &salt));
+
+        set_deposit_forwarder(&mut exec_plan, &factory, salt).expect("Plan uses the shared escrow");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        let prestart = &exec_plan.prestart_user_to_escrow_transfer;
+        assert_eq!(prestart.get_common().dest_addr, deposit_addr);
+        assert_eq!(
+            prestart.get_uuid(),
+            original_plan.prestart_user_to_escrow_transfer.get_uuid()
+        );
+        if let ExecutionStepEnum::EthDepositSweep(step) =
+            &exec_plan.deposit_sweep.as_ref().unwrap().inner
+        {
+            assert_eq!(step.factory_addr, factory.addr);
+            assert_eq!(step.salt, salt);
+            assert_eq!(
+                Some(step.token.clone()),
+                get_transfer_token(&original_plan.prestart_user_to_escrow_transfer)
+            );
+            assert_eq!(step.amount, Some(graph_solution.amount_in));
+            assert_eq!(step.common.src_addr, deposit_addr);
+            assert_eq!(
+                step.common.dest_addr,
+                UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS)
+            );
+            assert_eq!(step.status, EthStepStatus::NotStarted);
+        } else {
+            assert!(false)
+        }
+        // Nothing else moves
+        assert_eq!(exec_plan.paths, original_plan.paths);
+        assert_eq!(
+            exec_plan.refund_escrow_to_user_transfer,
+            original_plan.refund_escrow_to_user_transfer
+        );
+
+        // Another factory replaces the sweep instead of adding a second one
+        let other_factory = DepositForwarderFactory {
+            forwarder_init_code_hash: [8u8; 32],
+            ..factory
+        };
+        set_deposit_forwarder(&mut exec_plan, &other_factory, salt).expect("Replaces the sweep");
+        assert_eq!(
+            exec_plan
+                .prestart_user_to_escrow_transfer
+                .get_common()
+                .dest_addr,
+            UniversalAddress::Ethereum(get_deposit_address(&other_factory, &salt))
+        );
+        assert_eq!(
+            exec_plan
+                .deposit_sweep
+                .as_ref()
+                .unwrap()
+                .get_common()
+                .src_addr,
+            UniversalAddress::Ethereum(get_deposit_address(&other_factory, &salt))
+        );
+
+        // The forwarder factory only sweeps into the shared escrow
+        let mut escrow_plan = original_plan.clone();
+        set_plan_escrow(
+            &mut escrow_plan,
+            EthAddress {
+                0: hex!("00000000000000000000000000000000000000ee"),
+            },
+            SubstratePublicKey { 0: [0xee; 32] },
+        )
+        .expect("Plan has no deposit sweep");
+        assert_eq!(
+            set_deposit_forwarder(&mut escrow_plan, &factory, salt),
+            Err(GraphToExecConversionError::DepositForwarderNeedsSharedEscrow)
+        );
+        assert_eq!(
+            set_plan_escrow(
+                &mut exec_plan,
+                EthAddress {
+                    0: hex!("00000000000000000000000000000000000000ee"),
+                },
+                SubstratePublicKey { 0: [0xee; 32] },
+            ),
+            Err(GraphToExecConversionError::DepositForwarderNeedsSharedEscrow)
+        );
+    }
+
+    #[test]
+    fn test_rescale_deposit() {
+        use crate::execution_plan::PlanQuote;
+
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        exec_plan.quote = Some(PlanQuote {
+            amount_out: 2_000,
+            amount_in_usd: 1_000,
+            amount_out_usd: 990,
+        });
+        let quoted_amount = graph_solution.amount_in;
+        // A 0.3% overpayment, plus a wei so that the amounts do not scale evenly
+        let deposited_amount = quoted_amount + quoted_amount * 3 / 1_000 + 1;
+
+        rescale_deposit(&mut exec_plan, deposited_amount).expect("Plan has a deposit amount");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+        assert_eq!(
+            exec_plan.prestart_user_to_escrow_transfer.get_amount_in(),
+            Some(deposited_amount)
+        );
+        let total_path_amount_in: Amount = exec_plan
+            .paths
+            .iter()
+            .map(|exec_path| exec_path.steps[0].get_amount_in().unwrap())
+            .sum();
+        assert_eq!(total_path_amount_in, deposited_amount);
+        assert_eq!(
+            exec_plan.quote,
+            Some(PlanQuote {
+                amount_out: 2_006,
+                amount_in_usd: 1_003,
+                amount_out_usd: 992,
+            })
+        );
+
+        exec_plan.prestart_user_to_escrow_transfer.set_amount_in(0);
+        assert_eq!(
+            rescale_deposit(&mut exec_plan, deposited_amount),
+            Err(GraphToExecConversionError::NoDepositAmount)
+        );
+    }
+
+    #[test]
+    fn test_check_no_blocked_pools() {
+        use privadex_chain_metadata::registry::{
+            chain::universal_chain_id_registry::MOONBEAM, dex::pool_blocklist_overlay::BlockedPool,
+        };
+
+        let graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let mut pool_blocklist = PoolBlocklistOverlay::new();
+        assert_eq!(
+            check_no_blocked_pools(&graph_solution, &pool_blocklist),
+            Ok(())
+        );
+        pool_blocklist.replace(vec![BlockedPool {
+            chain_id: MOONBEAM,
+            pair_address: EthAddress {
+                0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af"),
+            },
+        }]);
+        assert_eq!(
+            check_no_blocked_pools(&graph_solution, &pool_blocklist),
+            Err(GraphToExecConversionError::BlockedPool)
+        );
+    }
+
+    #[test]
+    fn test_convert_aggregator_edge() {
+        use privadex_chain_metadata::registry::aggregator::aggregator_registry::OPENOCEAN_MOONBEAM;
+        use privadex_routing::graph::edge::AggregatorSwapEdge;
+
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        // Swap the medium solution's DEX hop for an aggregator quote over the same tokens
+        let mut graph_solution = graph_solution_factory::graph_solution_medium_static();
+        let cpmm_edge = match &graph_solution.paths[0].path.0[1] {
+            Edge::Swap(SwapEdge::CPMM(edge)) => edge.clone(),
+            _ => panic!("Expected the second edge to be a DEX swap"),
+        };
+        graph_solution.paths[0].path.0[1] = Edge::Swap(SwapEdge::Aggregator(AggregatorSwapEdge {
+            src_token: cpmm_edge.src_token.clone(),
+            dest_token: cpmm_edge.dest_token.clone(),
+            quoted_amount_in: 100_000_000_000_000_000_000,
+            quoted_amount_out: 80_000_000_000,
+            estimated_gas_fee_in_dest_token: cpmm_edge.estimated_gas_fee_in_dest_token,
+            estimated_gas_fee_usd: cpmm_edge.estimated_gas_fee_usd,
+            aggregator: &OPENOCEAN_MOONBEAM,
+        }));
+        let exec_plan = ExecutionPlan::try_from(graph_solution.clone())
+            .expect("Expect exec plan from graph solution");
+        let _ = validate_execution_plan(&exec_plan).expect("Expect no errors in ExecutionPlan");
+
+        let steps = &exec_plan.paths[0].steps;
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(steps[0].inner, ExecutionStepEnum::EthWrap(_)));
+        assert!(matches!(steps[2].inner, ExecutionStepEnum::XCMTransfer(_)));
+        if let ExecutionStepEnum::EthAggregatorSwap(step) = &steps[1].inner {
+            assert_eq!(step.exchange_addr, OPENOCEAN_MOONBEAM.eth_exchange);
+            assert_eq!(step.src_token, cpmm_edge.src_token);
+            assert_eq!(step.dest_token, cpmm_edge.dest_token);
+            assert_eq!(step.amount_in, None);
+            assert_eq!(step.quoted_amount_out, 80_000_000_000);
+            assert_ne!(step.approval_uuid, step.uuid);
+            assert!(step.needs_approval());
+        } else {
+            panic!("Expected the aggregator edge to become an EthAggregatorSwap step");
+        }
+    }
+
     #[test]
     fn test_convert_graph_solution_medium_static() {
         pink_extension_runtime::mock_ext::mock_all_ext();
@@ -16,14 +16,15 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::vec::Vec;
+use ink::prelude::vec::Vec;
 
 use privadex_chain_metadata::{
     common::{Amount, Dex},
     registry::dex::DexId,
 };
 use privadex_routing::graph::edge::{
-    ConstantProductAMMSwapEdge, Edge, SwapEdge, UnwrapEdge, WrapEdge, XCMBridgeEdge,
+    AggregatorSwapEdge, ConstantProductAMMSwapEdge, Edge, SwapEdge, UnwrapEdge, WormholeBridgeEdge,
+    WrapEdge, XCMBridgeEdge,
 };
 
 use crate::execution_plan::{DexRouterFunction, ExecutionStep, ExecutionStepEnum};
@@ -65,6 +66,49 @@ pub(crate) fn process_xcm_bridge_edge(
     }
 }
 
+pub(crate) fn process_wormhole_bridge_edge(
+    uuid_seed: &mut u128,
+    edge: &WormholeBridgeEdge,
+    amount_in: &Option<Amount>,
+    parse_swap_state: &Option<ParseSwapState>,
+) -> Result<ProcessHelperResult, GraphToExecConversionError> {
+    match parse_swap_state {
+        None => {
+            let wormhole_transfer_step = exec_step_helper::convert_wormhole_bridge_to_exec_step(
+                &edge,
+                get_uuid_and_increment_seed(uuid_seed),
+                amount_in.clone(),
+            );
+            Ok(ProcessHelperResult::NewExecStep(ExecutionStep::new(
+                ExecutionStepEnum::WormholeTransfer(wormhole_transfer_step),
+            )))
+        }
+        Some(_) => Err(GraphToExecConversionError::UnexpectedStillProcessingSwap),
+    }
+}
+
+pub(crate) fn process_aggregator_edge(
+    uuid_seed: &mut u128,
+    edge: &AggregatorSwapEdge,
+    amount_in: &Option<Amount>,
+    parse_swap_state: &Option<ParseSwapState>,
+) -> Result<ProcessHelperResult, GraphToExecConversionError> {
+    match parse_swap_state {
+        None => {
+            let aggregator_swap_step = exec_step_helper::convert_aggregator_swap_to_exec_step(
+                &edge,
+                get_uuid_and_increment_seed(uuid_seed),
+                get_uuid_and_increment_seed(uuid_seed),
+                amount_in.clone(),
+            );
+            Ok(ProcessHelperResult::NewExecStep(ExecutionStep::new(
+                ExecutionStepEnum::EthAggregatorSwap(aggregator_swap_step),
+            )))
+        }
+        Some(_) => Err(GraphToExecConversionError::UnexpectedStillProcessingSwap),
+    }
+}
+
 pub(crate) fn process_wrap_edge(
     uuid_seed: &mut u128,
     edge: &WrapEdge,
@@ -17,25 +17,29 @@
  */
 
 use duplicate::duplicate_item;
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 
 use privadex_chain_metadata::{
     chain_info::{AddressType, ChainInfo},
     common::{Amount, UniversalAddress, UniversalTokenId},
     get_chain_info_from_chain_id,
-    registry::chain::universal_chain_id_registry,
+    xcm_location::beneficiary_multilocation,
 };
 use privadex_common::uuid::Uuid;
 use privadex_routing::graph::edge::{
-    ConstantProductAMMSwapEdge, UnwrapEdge, WrapEdge, XCMBridgeEdge,
+    AggregatorSwapEdge, ConstantProductAMMSwapEdge, UnwrapEdge, WormholeBridgeEdge, WrapEdge,
+    XCMBridgeEdge,
 };
 
 use crate::execution_plan::{
-    CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, EthDexSwapStep, EthStepStatus,
-    EthUnwrapStep, EthWrapStep, XCMTransferStep,
+    CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, EthAggregatorSwapStep,
+    EthDexSwapStep, EthStepStatus, EthUnwrapStep, EthWrapStep, MultiPhaseBridgeStepStatus,
+    WormholeTransferStep, XCMTransferStep,
 };
 
-use super::common::{ESCROW_ASTAR_NATIVE_ADDRESS, ESCROW_ETH_ADDRESS, ESCROW_SUBSTRATE_PUBLIC_KEY};
+use super::common::{
+    ESCROW_ETH_ADDRESS, ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS, ESCROW_SUBSTRATE_PUBLIC_KEY,
+};
 
 // Converts a single wrap/unwrap edge into unwrap/wrap step. Note that generally,
 // wraps/unwraps will be preceded or followed by DEX swaps, in which case we generate
@@ -127,6 +131,40 @@ pub(crate) fn convert_same_dex_swaps_to_exec_step(
     }
 }
 
+// Unlike DEX swaps, an aggregator edge is its own step. The aggregator already routes across
+// pools internally, so there is nothing to merge with the neighbouring edges
+pub(crate) fn convert_aggregator_swap_to_exec_step(
+    aggregator_edge: &AggregatorSwapEdge,
+    uuid: Uuid,
+    approval_uuid: Uuid,
+    amount_in: Option<Amount>,
+) -> EthAggregatorSwapStep {
+    let chain_info = get_chain_info_from_chain_id(&aggregator_edge.aggregator.chain_id)
+        .expect("Aggregator must have an associated ChainInfo");
+
+    let common = CommonExecutionMeta {
+        src_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+        dest_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+        gas_fee_native: chain_info.avg_gas_fee_in_native_token,
+        gas_fee_usd: aggregator_edge.estimated_gas_fee_usd,
+    };
+
+    EthAggregatorSwapStep {
+        uuid,
+        aggregator_id: aggregator_edge.aggregator.id,
+        exchange_addr: aggregator_edge.aggregator.eth_exchange,
+        src_token: aggregator_edge.src_token.clone(),
+        dest_token: aggregator_edge.dest_token.clone(),
+        amount_in,
+        quoted_amount_in: aggregator_edge.quoted_amount_in,
+        quoted_amount_out: aggregator_edge.quoted_amount_out,
+        common,
+        approval_uuid,
+        approval_txn_hash: None,
+        status: EthStepStatus::NotStarted,
+    }
+}
+
 pub(crate) fn convert_xcm_bridge_to_exec_step(
     bridge_edge: &XCMBridgeEdge,
     uuid: Uuid,
@@ -139,10 +177,9 @@ pub(crate) fn convert_xcm_bridge_to_exec_step(
 
     let src_addr = get_escrow_send_xcm_address(&src_chain_info);
     let dest_addr = get_escrow_receive_xcm_address(&dest_chain_info);
-    let full_dest_multilocation = bridge_edge
-        .dest_multilocation_template
-        .get_full_dest_multilocation(dest_addr.clone())
-        .expect("const MultiLocation template was formatted incorrectly");
+    let full_dest_multilocation =
+        beneficiary_multilocation(src_chain_info.chain_id, dest_chain_info, &dest_addr)
+            .expect("Escrow address must match the destination chain's XCM address type");
 
     let common = CommonExecutionMeta {
         src_addr,
@@ -161,6 +198,7 @@ pub(crate) fn convert_xcm_bridge_to_exec_step(
         dest_token: bridge_edge.dest_token.clone(),
         token_asset_multilocation: bridge_edge.token_asset_multilocation.clone(),
         full_dest_multilocation,
+        dest_weight_limit: bridge_edge.dest_weight_limit,
         amount_in,
         bridge_fee_native: dest_chain_info.avg_bridge_fee_in_native_token,
         bridge_fee_usd: bridge_edge.estimated_bridge_fee_usd,
@@ -169,22 +207,51 @@ pub(crate) fn convert_xcm_bridge_to_exec_step(
     }
 }
 
-fn get_escrow_send_xcm_address(chain_info: &ChainInfo) -> UniversalAddress {
-    if chain_info.chain_id == universal_chain_id_registry::ASTAR {
-        // Use ETH address because Astar EVM uses an EVM precompile for
-        // XCM transfers
-        return UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS);
+// Wormhole lanes are between EVM chains, so the escrow ETH address is used on both ends
+pub(crate) fn convert_wormhole_bridge_to_exec_step(
+    bridge_edge: &WormholeBridgeEdge,
+    uuid: Uuid,
+    amount_in: Option<Amount>,
+) -> WormholeTransferStep {
+    let src_chain_info = get_chain_info_from_chain_id(&bridge_edge.src_token.chain)
+        .expect("Bridge must have an associated source ChainInfo");
+
+    let common = CommonExecutionMeta {
+        src_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+        dest_addr: UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
+        gas_fee_native: src_chain_info.avg_gas_fee_in_native_token,
+        gas_fee_usd: bridge_edge.estimated_gas_fee_usd,
+    };
+
+    WormholeTransferStep {
+        uuid,
+        src_token: bridge_edge.src_token.clone(),
+        dest_token: bridge_edge.dest_token.clone(),
+        src_token_bridge_addr: bridge_edge.src_token_bridge_addr,
+        dest_token_bridge_addr: bridge_edge.dest_token_bridge_addr,
+        src_wormhole_chain_id: bridge_edge.src_wormhole_chain_id,
+        dest_wormhole_chain_id: bridge_edge.dest_wormhole_chain_id,
+        amount_in,
+        bridge_fee_native: bridge_edge.estimated_bridge_fee_in_dest_chain_native_token,
+        bridge_fee_usd: bridge_edge.estimated_bridge_fee_usd,
+        common,
+        status: MultiPhaseBridgeStepStatus::NotStarted,
     }
+}
 
-    match chain_info.xcm_address_type {
+fn get_escrow_send_xcm_address(chain_info: &ChainInfo) -> UniversalAddress {
+    // Chains with an XCM precompile (e.g. Astar) send from the escrow's ETH address
+    match chain_info.get_xcm_sender_address_type() {
         AddressType::Ethereum => UniversalAddress::Ethereum(ESCROW_ETH_ADDRESS),
         AddressType::SS58 => UniversalAddress::Substrate(ESCROW_SUBSTRATE_PUBLIC_KEY),
     }
 }
 
 fn get_escrow_receive_xcm_address(chain_info: &ChainInfo) -> UniversalAddress {
-    if chain_info.chain_id == universal_chain_id_registry::ASTAR {
-        return UniversalAddress::Substrate(ESCROW_ASTAR_NATIVE_ADDRESS);
+    // Receive into the account that backs the escrow's ETH address so that funds are
+    // immediately usable from the EVM
+    if chain_info.has_hashed_evm_account_mapping {
+        return UniversalAddress::Substrate(ESCROW_EVM_MAPPED_SUBSTRATE_ADDRESS);
     }
 
     match chain_info.xcm_address_type {
@@ -20,3 +20,4 @@ pub mod common;
 pub mod converter;
 mod helper_process_graph_edge;
 mod helper_to_single_exec_step;
+pub mod route_hash;
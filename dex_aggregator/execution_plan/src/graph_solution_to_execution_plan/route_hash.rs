@@ -0,0 +1,197 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// A route's fingerprint, so integrators can check that a plan executes the route they quoted (and
+// showed the user). It only covers the pools, wraps and bridges each path goes through, not the
+// amounts, so rescaling a deposit or splitting into tranches keeps it. Neither does path order
+use ink::prelude::vec::Vec;
+use scale::Encode;
+
+use privadex_chain_metadata::{
+    common::{EthAddress, UniversalTokenId},
+    registry::{aggregator::AggregatorId, dex::DexId},
+};
+use privadex_routing::graph::{
+    edge::{BridgeEdge, Edge, SwapEdge},
+    graph::GraphSolution,
+};
+
+use crate::execution_plan::RouteHash;
+
+#[derive(Encode)]
+enum CanonicalEdge<'a> {
+    DexSwap {
+        dex_id: DexId,
+        pair_address: &'a EthAddress,
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+    Wrap {
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+    Unwrap {
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+    XcmBridge {
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+    WormholeBridge {
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+    AggregatorSwap {
+        aggregator_id: AggregatorId,
+        src_token: &'a UniversalTokenId,
+        dest_token: &'a UniversalTokenId,
+    },
+}
+
+impl<'a> From<&'a Edge> for CanonicalEdge<'a> {
+    fn from(edge: &'a Edge) -> Self {
+        match edge {
+            Edge::Swap(SwapEdge::CPMM(edge)) => Self::DexSwap {
+                dex_id: edge.dex.id,
+                pair_address: &edge.pair_address,
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+            Edge::Swap(SwapEdge::Wrap(edge)) => Self::Wrap {
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+            Edge::Swap(SwapEdge::Unwrap(edge)) => Self::Unwrap {
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+            Edge::Bridge(BridgeEdge::Xcm(edge)) => Self::XcmBridge {
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+            Edge::Bridge(BridgeEdge::Wormhole(edge)) => Self::WormholeBridge {
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+            Edge::Swap(SwapEdge::Aggregator(edge)) => Self::AggregatorSwap {
+                aggregator_id: edge.aggregator.id,
+                src_token: &edge.src_token,
+                dest_token: &edge.dest_token,
+            },
+        }
+    }
+}
+
+// blake2_256 of the distinct paths' canonical edges, sorted by their encoding
+pub fn get_route_hash(graph_solution: &GraphSolution) -> RouteHash {
+    let mut paths: Vec<Vec<u8>> = graph_solution
+        .paths
+        .iter()
+        .map(|split_path| {
+            split_path
+                .path
+                .0
+                .iter()
+                .map(CanonicalEdge::from)
+                .collect::<Vec<CanonicalEdge>>()
+                .encode()
+        })
+        .collect();
+    paths.sort();
+    paths.dedup();
+    sp_core_hashing::blake2_256(&paths.encode())
+}
+
+#[cfg(test)]
+mod route_hash_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::{Amount, EthAddress},
+        registry::bridge::xcm_bridge_registry,
+    };
+    use privadex_common::fixed_point::DecimalFixedPoint;
+    use privadex_routing::graph::{
+        edge::XCMBridgeEdge,
+        graph::{GraphPath, SplitGraphPath},
+    };
+
+    use super::*;
+
+    fn xcm_path(bridge_index: usize) -> GraphPath {
+        let derived = DecimalFixedPoint::from_str_and_exp("10", 3);
+        GraphPath(vec![Edge::Bridge(BridgeEdge::Xcm(
+            XCMBridgeEdge::from_bridge_and_derived_quantities(
+                xcm_bridge_registry::XCM_BRIDGES[bridge_index].clone(),
+                &derived,
+                &derived,
+                &derived,
+            ),
+        ))])
+    }
+
+    fn graph_solution(paths: Vec<(GraphPath, Amount)>) -> GraphSolution {
+        let amount_in = paths.iter().map(|(_, amount)| amount).sum();
+        GraphSolution {
+            paths: paths
+                .into_iter()
+                .map(|(path, fraction_amount_in)| SplitGraphPath {
+                    path,
+                    fraction_amount_in,
+                    fraction_bps: 0,
+                })
+                .collect(),
+            amount_in,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        }
+    }
+
+    #[test]
+    fn test_get_route_hash() {
+        let route_hash = get_route_hash(&graph_solution(vec![(xcm_path(0), 1_000)]));
+        // Amounts and tranches don't change the route
+        assert_eq!(
+            get_route_hash(&graph_solution(vec![(xcm_path(0), 5_000)])),
+            route_hash
+        );
+        assert_eq!(
+            get_route_hash(
+                &graph_solution(vec![(xcm_path(0), 1_000)])
+                    .split_into_tranches(300, 10)
+                    .unwrap()
+            ),
+            route_hash
+        );
+        // Neither does path order, but a different edge does
+        assert_eq!(
+            get_route_hash(&graph_solution(vec![
+                (xcm_path(0), 500),
+                (xcm_path(1), 500)
+            ])),
+            get_route_hash(&graph_solution(vec![
+                (xcm_path(1), 500),
+                (xcm_path(0), 500)
+            ])),
+        );
+        assert_ne!(
+            get_route_hash(&graph_solution(vec![(xcm_path(1), 1_000)])),
+            route_hash
+        );
+    }
+}
@@ -19,8 +19,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
+pub mod deposit_address;
 pub mod execution_plan;
 pub mod graph_solution_to_execution_plan;
+pub mod schema_check;
+pub mod stored_plan;
 pub mod validator;
 
 #[cfg(any(test, feature = "test-utils"))]
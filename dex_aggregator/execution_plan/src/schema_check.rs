@@ -0,0 +1,687 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+// S3 holds ExecutionPlans (and the steps and statuses inside them) as their SCALE encoding,
+// behind the version tag of the stored_plan envelope. SCALE has no field names, so reordering
+// fields, inserting a field or enum variant anywhere but the end, or changing a field's type
+// silently changes what the stored bytes mean. golden/v<n> pins the encodings of layout version n
+// of stored_plan, v0 being the bare layout from before the envelope. Plans were stored in each
+// of them, so they are frozen: a layout change bumps STORED_PLAN_VERSION, adds a golden/v<n+1>
+// for the new layout and points the tests of the old one at its ExecutionPlanV<n> mirror
+use scale::{Decode, Encode};
+
+use crate::{execution_plan::ExecutionPlan, stored_plan::decode_stored_plan};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SchemaCheckError {
+    // The blob isn't a valid encoding of the type, e.g. it ends early or has an unknown variant
+    DecodeFailed,
+    // The type decoded from a prefix of the blob, so the stored type had more data in it
+    TrailingBytes(usize /* numBytes */),
+    // The blob decodes, but re-encoding the result gives different bytes. The derived codecs
+    // reject non-canonical input themselves, so this catches hand-written Encode/Decode impls
+    NonCanonical,
+    // The blob is a stored plan envelope (see stored_plan) with a layout version this build
+    // doesn't know, i.e. it was written by a newer build
+    UnknownVersion(u8),
+}
+
+// Strict decode of a stored blob: unlike T::decode, all of it must be used up, and it must be
+// exactly what the current schema would have written
+pub fn check_blob<T: Encode + Decode>(blob: &[u8]) -> Result<T, SchemaCheckError> {
+    let mut input = blob;
+    let value = T::decode(&mut input).map_err(|_| SchemaCheckError::DecodeFailed)?;
+    if !input.is_empty() {
+        return Err(SchemaCheckError::TrailingBytes(input.len()));
+    }
+    if value.encode() != blob {
+        return Err(SchemaCheckError::NonCanonical);
+    }
+    Ok(value)
+}
+
+// A stored plan, in the envelope or in the bare V0 layout (see stored_plan)
+pub fn check_exec_plan_blob(blob: &[u8]) -> Result<ExecutionPlan, SchemaCheckError> {
+    decode_stored_plan(blob)
+}
+
+#[cfg(test)]
+mod schema_check_tests {
+    use hex_literal::hex;
+    use ink::prelude::{string::String, vec, vec::Vec};
+    use xcm::latest::{Junction, Junctions, MultiLocation, NetworkId};
+
+    use privadex_chain_metadata::{
+        bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        common::{
+            ChainTokenId, ERC20Token, EthAddress, EthTxnHash, UniversalAddress, UniversalChainId,
+            UniversalTokenId, XC20Token,
+        },
+        registry::{
+            aggregator::AggregatorId,
+            chain::universal_chain_id_registry::{MOONBEAM, POLKADOT},
+        },
+    };
+    use privadex_common::uuid::Uuid;
+    use privadex_routing::graph::fee_breakdown::FeeBreakdown;
+
+    use super::*;
+    use crate::execution_plan::{
+        CommonExecutionMeta, ContractCall, CrossChainStepStatus, DexRouterFunction,
+        ERC20TransferStep, EthAggregatorSwapStep, EthContractCallStep, EthDepositSweepStep,
+        EthDexSwapStep, EthFeeSkimStep, EthPendingTxnId, EthRemoveLiquidityStep, EthSendStep,
+        EthStepStatus, EthUnwrapStep, EthWrapStep, EthZapInStep, ExecutionPath, ExecutionPolicy,
+        ExecutionStep, ExecutionStepEnum, FinalizedTxnId, MultiPhaseBridgeStepStatus,
+        PathExecutionMode, PendingTxnId, PlanQuote, PlanReviewStatus, PresignedTxn, QuotedTerms,
+        RemarkDepositStatus, ResolvedDestinationName, StepMilestone, StepTiming, SubstrateEventId,
+        SubstrateFinalizedExtrinsicId, SubstratePendingEventId, SubstratePendingExtrinsicId,
+        SubstratePendingRemarkId, SubstrateRemarkDepositStep, WormholeMessageId,
+        WormholeTransferStep, XCMTransferStep, XcmFailureKind,
+    };
+    use crate::stored_plan::{CrossChainStepStatusV0, ExecutionStepEnumV0};
+
+    const EXECUTION_PLAN_GOLDEN: &str = include_str!("./golden/v1/execution_plan.hex");
+    const EXECUTION_STEPS_GOLDEN: &str = include_str!("./golden/v1/execution_steps.hex");
+    const STEP_STATUSES_GOLDEN: &str = include_str!("./golden/v1/step_statuses.hex");
+    // Hand-encoded from the types at c4272ff, like golden/v0/execution_plan.hex
+    const EXECUTION_STEPS_V0_GOLDEN: &str = include_str!("./golden/v0/execution_steps.hex");
+    const STEP_STATUSES_V0_GOLDEN: &str = include_str!("./golden/v0/step_statuses.hex");
+
+    const ESCROW: EthAddress = EthAddress {
+        0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+    };
+    const USER: EthAddress = EthAddress { 0: [0x11; 20] };
+    const HASH_A: EthTxnHash = EthTxnHash { 0: [0xaa; 32] };
+    const HASH_B: EthTxnHash = EthTxnHash { 0: [0xbb; 32] };
+    const ETHEREUM: UniversalChainId = UniversalChainId::EVM(1);
+
+    // The golden files hold one hex encoding per line (execution_plan.hex is a single one,
+    // wrapped)
+    fn parse_golden_lines(golden: &str) -> Vec<Vec<u8>> {
+        golden
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| hex::decode(line.trim()).expect("Golden files must be hex"))
+            .collect()
+    }
+
+    fn parse_golden_blob(golden: &str) -> Vec<u8> {
+        hex::decode(golden.split_whitespace().collect::<String>())
+            .expect("Golden files must be hex")
+    }
+
+    fn uuid(byte: u8) -> Uuid {
+        Uuid::new([byte; 16])
+    }
+
+    fn token(chain: UniversalChainId, id: ChainTokenId) -> UniversalTokenId {
+        UniversalTokenId { chain, id }
+    }
+
+    fn glmr() -> UniversalTokenId {
+        token(MOONBEAM, ChainTokenId::Native)
+    }
+
+    fn wglmr() -> UniversalTokenId {
+        token(
+            MOONBEAM,
+            ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress { 0: [0x33; 20] },
+            }),
+        )
+    }
+
+    fn xcdot() -> UniversalTokenId {
+        token(MOONBEAM, ChainTokenId::XC20(XC20Token::from_asset_id(1)))
+    }
+
+    fn common() -> CommonExecutionMeta {
+        CommonExecutionMeta {
+            src_addr: UniversalAddress::Ethereum(ESCROW),
+            dest_addr: UniversalAddress::Ethereum(USER),
+            gas_fee_native: 1_000,
+            gas_fee_usd: 2_000,
+        }
+    }
+
+    fn eth_pending(txn_hash: EthTxnHash, end_block_num: u32) -> EthPendingTxnId {
+        EthPendingTxnId {
+            txn_hash,
+            end_block_num,
+        }
+    }
+
+    fn eth_statuses() -> Vec<EthStepStatus> {
+        vec![
+            EthStepStatus::NotStarted,
+            EthStepStatus::Submitted(eth_pending(HASH_A, 100)),
+            EthStepStatus::Dropped,
+            EthStepStatus::Failed(HASH_A),
+            EthStepStatus::Confirmed(HASH_B),
+        ]
+    }
+
+    fn cross_chain_statuses() -> Vec<CrossChainStepStatus> {
+        vec![
+            CrossChainStepStatus::NotStarted,
+            CrossChainStepStatus::Submitted(
+                PendingTxnId::Substrate(SubstratePendingExtrinsicId {
+                    start_block_num: 1,
+                    end_block_num: 65,
+                    extrinsic_hash: HASH_A,
+                }),
+                SubstratePendingEventId {
+                    start_block_num: 2,
+                    message_hash: Some(HASH_B),
+                },
+            ),
+            CrossChainStepStatus::Dropped,
+            CrossChainStepStatus::Failed(FinalizedTxnId::Substrate(
+                SubstrateFinalizedExtrinsicId {
+                    block_num: 3,
+                    extrinsic_index: 4,
+                },
+            )),
+            CrossChainStepStatus::LocalConfirmed(
+                FinalizedTxnId::Ethereum(HASH_A),
+                SubstratePendingEventId {
+                    start_block_num: 5,
+                    message_hash: None,
+                },
+            ),
+            CrossChainStepStatus::Confirmed(
+                FinalizedTxnId::Substrate(SubstrateFinalizedExtrinsicId {
+                    block_num: 6,
+                    extrinsic_index: 7,
+                }),
+                SubstrateEventId {
+                    block_num: 8,
+                    event_index: 9,
+                },
+            ),
+            CrossChainStepStatus::RemoteFailed(
+                FinalizedTxnId::Ethereum(HASH_B),
+                SubstrateEventId {
+                    block_num: 10,
+                    event_index: 11,
+                },
+                XcmFailureKind::TooExpensive,
+            ),
+        ]
+    }
+
+    fn wormhole_message(sequence: u64) -> WormholeMessageId {
+        WormholeMessageId {
+            deposit_txn_hash: HASH_A,
+            sequence,
+        }
+    }
+
+    fn multi_phase_bridge_statuses() -> Vec<MultiPhaseBridgeStepStatus> {
+        vec![
+            MultiPhaseBridgeStepStatus::NotStarted,
+            MultiPhaseBridgeStepStatus::DepositSubmitted(eth_pending(HASH_A, 12)),
+            MultiPhaseBridgeStepStatus::Dropped,
+            MultiPhaseBridgeStepStatus::Failed(HASH_B),
+            MultiPhaseBridgeStepStatus::AwaitingRelay(wormhole_message(13)),
+            MultiPhaseBridgeStepStatus::ClaimSubmitted(
+                wormhole_message(14),
+                eth_pending(HASH_B, 15),
+            ),
+            MultiPhaseBridgeStepStatus::Confirmed(wormhole_message(16), HASH_B),
+        ]
+    }
+
+    fn remark_deposit_statuses() -> Vec<RemarkDepositStatus> {
+        vec![
+            RemarkDepositStatus::NotStarted,
+            RemarkDepositStatus::Pending(SubstratePendingRemarkId {
+                start_block_num: 22,
+                end_block_num: 23,
+            }),
+            RemarkDepositStatus::Dropped,
+            RemarkDepositStatus::Failed(SubstrateFinalizedExtrinsicId {
+                block_num: 24,
+                extrinsic_index: 25,
+            }),
+            RemarkDepositStatus::Confirmed(SubstrateFinalizedExtrinsicId {
+                block_num: 26,
+                extrinsic_index: 27,
+            }),
+        ]
+    }
+
+    fn review_statuses() -> Vec<PlanReviewStatus> {
+        vec![
+            PlanReviewStatus::NotNeeded,
+            PlanReviewStatus::NeedsReview {
+                realized_gas_fee_usd: 6,
+            },
+            PlanReviewStatus::Resolved,
+            PlanReviewStatus::DepositMismatch {
+                deposited_amount: 10,
+            },
+        ]
+    }
+
+    // One of each step type, in ExecutionStepEnum order
+    fn all_step_types() -> Vec<ExecutionStepEnum> {
+        vec![
+            ExecutionStepEnum::EthSend(EthSendStep {
+                uuid: uuid(1),
+                chain: MOONBEAM,
+                amount: Some(1_000_000),
+                common: common(),
+                status: eth_statuses()[0].clone(),
+            }),
+            ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+                uuid: uuid(2),
+                token: wglmr(),
+                amount: Some(5),
+                common: common(),
+                status: eth_statuses()[1].clone(),
+            }),
+            ExecutionStepEnum::EthWrap(EthWrapStep {
+                uuid: uuid(3),
+                chain: MOONBEAM,
+                amount: None,
+                common: common(),
+                status: eth_statuses()[2].clone(),
+            }),
+            ExecutionStepEnum::EthUnwrap(EthUnwrapStep {
+                uuid: uuid(4),
+                chain: MOONBEAM,
+                amount: Some(7),
+                common: common(),
+                status: eth_statuses()[3].clone(),
+            }),
+            ExecutionStepEnum::EthDexSwap(EthDexSwapStep {
+                uuid: uuid(5),
+                dex_router_addr: EthAddress { 0: [0x44; 20] },
+                dex_router_func: DexRouterFunction::SwapExactTokensForTokens,
+                token_path: vec![wglmr(), xcdot()],
+                amount_in: Some(9),
+                common: common(),
+                status: eth_statuses()[4].clone(),
+            }),
+            ExecutionStepEnum::XCMTransfer(XCMTransferStep {
+                uuid: uuid(6),
+                src_token: xcdot(),
+                dest_token: token(POLKADOT, ChainTokenId::Native),
+                token_asset_multilocation: MultiLocation::parent(),
+                full_dest_multilocation: MultiLocation::here(),
+                dest_weight_limit: 6_000_000_000,
+                amount_in: Some(11),
+                bridge_fee_native: 12,
+                bridge_fee_usd: 13,
+                common: common(),
+                status: cross_chain_statuses()[1].clone(),
+            }),
+            ExecutionStepEnum::WormholeTransfer(WormholeTransferStep {
+                uuid: uuid(7),
+                src_token: wglmr(),
+                dest_token: token(ETHEREUM, ChainTokenId::Native),
+                src_token_bridge_addr: EthAddress { 0: [0x55; 20] },
+                dest_token_bridge_addr: EthAddress { 0: [0x66; 20] },
+                src_wormhole_chain_id: 16,
+                dest_wormhole_chain_id: 2,
+                amount_in: Some(15),
+                bridge_fee_native: 16,
+                bridge_fee_usd: 17,
+                common: common(),
+                status: multi_phase_bridge_statuses()[5].clone(),
+            }),
+            ExecutionStepEnum::EthDepositSweep(EthDepositSweepStep {
+                uuid: uuid(8),
+                token: glmr(),
+                factory_addr: EthAddress { 0: [0x77; 20] },
+                salt: [0x88; 32],
+                amount: Some(20),
+                common: common(),
+                status: eth_statuses()[0].clone(),
+            }),
+            ExecutionStepEnum::SubstrateRemarkDeposit(SubstrateRemarkDepositStep {
+                uuid: uuid(9),
+                chain: POLKADOT,
+                remark: [0x99; 16],
+                amount: Some(21),
+                common: common(),
+                status: remark_deposit_statuses()[1].clone(),
+            }),
+            ExecutionStepEnum::EthContractCall(EthContractCallStep {
+                uuid: uuid(10),
+                token: wglmr(),
+                call: ContractCall {
+                    target_addr: EthAddress { 0: [0xcc; 20] },
+                    calldata_template: vec![1, 2, 3, 4],
+                },
+                amount: Some(24),
+                common: common(),
+                approval_uuid: uuid(11),
+                approval_txn_hash: Some(HASH_A),
+                status: eth_statuses()[4].clone(),
+            }),
+            ExecutionStepEnum::EthFeeSkim(EthFeeSkimStep {
+                uuid: uuid(13),
+                token: wglmr(),
+                markup_bps: 30,
+                amount: Some(25),
+                common: common(),
+                status: eth_statuses()[1].clone(),
+            }),
+            ExecutionStepEnum::EthAggregatorSwap(EthAggregatorSwapStep {
+                uuid: uuid(14),
+                aggregator_id: AggregatorId::OpenOceanMoonbeam,
+                exchange_addr: EthAddress { 0: [0xdd; 20] },
+                src_token: wglmr(),
+                dest_token: glmr(),
+                amount_in: Some(26),
+                quoted_amount_in: 1_000,
+                quoted_amount_out: 990,
+                common: common(),
+                approval_uuid: uuid(15),
+                approval_txn_hash: None,
+                status: eth_statuses()[3].clone(),
+            }),
+            ExecutionStepEnum::EthZapIn(EthZapInStep {
+                uuid: uuid(16),
+                dex_router_addr: EthAddress { 0: [0x44; 20] },
+                token: wglmr(),
+                pair_token: token(
+                    MOONBEAM,
+                    ChainTokenId::ERC20(ERC20Token {
+                        addr: EthAddress { 0: [0x55; 20] },
+                    }),
+                ),
+                lp_token: token(
+                    MOONBEAM,
+                    ChainTokenId::ERC20(ERC20Token {
+                        addr: EthAddress { 0: [0x66; 20] },
+                    }),
+                ),
+                amount: Some(27),
+                common: common(),
+                swap_uuid: uuid(17),
+                swap_txn_hash: Some(HASH_A),
+                pair_token_amount: Some(13),
+                status: eth_statuses()[1].clone(),
+            }),
+            ExecutionStepEnum::EthRemoveLiquidity(EthRemoveLiquidityStep {
+                uuid: uuid(18),
+                dex_router_addr: EthAddress { 0: [0x45; 20] },
+                lp_token: token(
+                    MOONBEAM,
+                    ChainTokenId::ERC20(ERC20Token {
+                        addr: EthAddress { 0: [0x66; 20] },
+                    }),
+                ),
+                token_a: wglmr(),
+                token_b: token(
+                    MOONBEAM,
+                    ChainTokenId::ERC20(ERC20Token {
+                        addr: EthAddress { 0: [0x55; 20] },
+                    }),
+                ),
+                num_token_a_paths: 1,
+                amount: Some(28),
+                common: common(),
+                approval_uuid: uuid(19),
+                approval_txn_hash: Some(HASH_B),
+                token_a_amount_out: Some(14),
+                token_b_amount_out: None,
+                status: eth_statuses()[2].clone(),
+            }),
+        ]
+    }
+
+    // Every step type, optional field and enum with data in it shows up at least once
+    fn golden_exec_plan() -> ExecutionPlan {
+        let mut steps: Vec<ExecutionStep> = all_step_types()
+            .into_iter()
+            .map(ExecutionStep::new)
+            .collect();
+        let remove_liquidity = steps.pop().unwrap();
+        let zap_in = steps.pop().unwrap();
+        let aggregator_swap = steps.pop().unwrap();
+        let fee_skim = steps.pop().unwrap();
+        let postend = steps.pop().unwrap();
+        let prestart = steps.pop().unwrap();
+        let deposit_sweep = steps.pop().unwrap();
+        let mut second_path_steps = steps.split_off(5);
+        second_path_steps.push(fee_skim);
+        second_path_steps.push(aggregator_swap);
+        second_path_steps.push(zap_in);
+        steps[0].timing.submitted = Some(StepMilestone {
+            block_num: Some(7),
+            timestamp_millis: 8,
+        });
+        steps[0].presigned_txn = Some(PresignedTxn {
+            nonce: 9,
+            txn_hash: HASH_A,
+            raw_txn: vec![0xf8, 0x6b],
+        });
+        second_path_steps[0].timing = StepTiming {
+            submitted: Some(StepMilestone {
+                block_num: None,
+                timestamp_millis: 30,
+            }),
+            confirmed: Some(StepMilestone {
+                block_num: Some(31),
+                timestamp_millis: 32,
+            }),
+        };
+        ExecutionPlan {
+            uuid: uuid(0xee),
+            paths: vec![
+                ExecutionPath {
+                    steps,
+                    amount_out: Some(100),
+                    step_amounts_out: vec![1, 2],
+                },
+                ExecutionPath {
+                    steps: second_path_steps,
+                    amount_out: None,
+                    step_amounts_out: Vec::new(),
+                },
+            ],
+            prestart_user_to_escrow_transfer: prestart,
+            deposit_sweep: Some(deposit_sweep),
+            postend_escrow_to_user_transfer: postend,
+            refund_escrow_to_user_transfer: ExecutionStep::new(ExecutionStepEnum::EthSend(
+                EthSendStep {
+                    uuid: uuid(12),
+                    chain: MOONBEAM,
+                    amount: None,
+                    common: common(),
+                    status: EthStepStatus::NotStarted,
+                },
+            )),
+            path_execution_mode: PathExecutionMode::Sequential,
+            execution_policy: ExecutionPolicy::NowOrNever {
+                max_blocks: 3,
+                first_hop_deadline_block: Some(4),
+            },
+            deadline_millis: 1_700_000_000_000,
+            last_progress_millis: 1_700_000_000_001,
+            paper_trade: false,
+            dest_name: Some(ResolvedDestinationName {
+                name: String::from("kap.eth"),
+                resolved_address: USER,
+            }),
+            metadata: Some(vec![0xde, 0xad]),
+            quote: Some(PlanQuote {
+                amount_out: 1,
+                amount_in_usd: 2,
+                amount_out_usd: 3,
+            }),
+            prestart_confirmed_block: Some(5),
+            review_status: review_statuses()[1].clone(),
+            route_hash: Some([0x77; 32]),
+            quoted_terms: Some(QuotedTerms {
+                quoted_at_millis: 1_700_000_000_000,
+                amount_in: 1_000,
+                amount_out: 990,
+                amount_in_usd: 4,
+                amount_out_usd: 3,
+                fee_breakdown: FeeBreakdown {
+                    hops_per_path: vec![],
+                    protocol_fee_in_dest_token: 6,
+                    protocol_fee_usd: 7,
+                    integrator_fee_in_dest_token: 8,
+                    integrator_fee_usd: 9,
+                    total_fee_usd: 10,
+                },
+                deposit_tolerance_bps: 50,
+                expiry_millis: 1_700_000_600_000,
+                route_hash: [0x77; 32],
+            }),
+            remove_liquidity: Some(remove_liquidity),
+            sponsorship_campaign_id: Some(String::from("spring")),
+        }
+    }
+
+    #[test]
+    fn test_exec_plan_golden_encoding() {
+        let golden = parse_golden_blob(EXECUTION_PLAN_GOLDEN);
+        assert_eq!(golden_exec_plan().encode(), golden);
+        assert_eq!(check_exec_plan_blob(&golden), Ok(golden_exec_plan()));
+    }
+
+    #[test]
+    fn test_step_golden_encodings() {
+        let golden = parse_golden_lines(EXECUTION_STEPS_GOLDEN);
+        let steps = all_step_types();
+        assert_eq!(steps.len(), golden.len());
+        for (step, golden_step) in steps.iter().zip(golden.iter()) {
+            assert_eq!(&step.encode(), golden_step, "{:?}", step);
+            assert_eq!(
+                check_blob::<ExecutionStepEnum>(golden_step).as_ref(),
+                Ok(step)
+            );
+        }
+    }
+
+    fn encode_all<T: Encode>(statuses: Vec<T>) -> Vec<Vec<u8>> {
+        statuses.iter().map(|status| status.encode()).collect()
+    }
+
+    #[test]
+    fn test_status_golden_encodings() {
+        // In the file's order, one line per variant
+        let encodings = [
+            encode_all(eth_statuses()),
+            encode_all(cross_chain_statuses()),
+            encode_all(multi_phase_bridge_statuses()),
+            encode_all(remark_deposit_statuses()),
+            encode_all(review_statuses()),
+        ]
+        .concat();
+        assert_eq!(encodings, parse_golden_lines(STEP_STATUSES_GOLDEN));
+    }
+
+    // The step types that haven't changed since V0 still encode the same, and the XCM transfer
+    // (the last one) is migrated
+    #[test]
+    fn test_v0_step_golden_encodings() {
+        let golden = parse_golden_lines(EXECUTION_STEPS_V0_GOLDEN);
+        let (xcm_transfer, unchanged) = golden.split_last().unwrap();
+        assert_eq!(unchanged.len(), 5);
+        for (step, golden_step) in all_step_types().iter().zip(unchanged.iter()) {
+            assert_eq!(&step.encode(), golden_step, "{:?}", step);
+            assert_eq!(
+                check_blob::<ExecutionStepEnumV0>(golden_step).map(ExecutionStepEnum::from),
+                Ok(step.clone())
+            );
+        }
+        assert_eq!(
+            check_blob::<ExecutionStepEnumV0>(xcm_transfer).map(ExecutionStepEnum::from),
+            Ok(ExecutionStepEnum::XCMTransfer(XCMTransferStep {
+                uuid: uuid(6),
+                src_token: xcdot(),
+                dest_token: token(POLKADOT, ChainTokenId::Native),
+                token_asset_multilocation: MultiLocation::parent(),
+                full_dest_multilocation: MultiLocation {
+                    parents: 1,
+                    interior: Junctions::X1(Junction::AccountId32 {
+                        network: NetworkId::Any,
+                        id: [0x22; 32],
+                    }),
+                },
+                dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+                amount_in: Some(11),
+                bridge_fee_native: 12,
+                bridge_fee_usd: 13,
+                common: common(),
+                status: CrossChainStepStatus::Submitted(
+                    PendingTxnId::Substrate(SubstratePendingExtrinsicId {
+                        start_block_num: 1,
+                        end_block_num: 65,
+                        extrinsic_hash: HASH_A,
+                    }),
+                    SubstratePendingEventId {
+                        start_block_num: 2,
+                        message_hash: None,
+                    },
+                ),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_v0_status_golden_encodings() {
+        let golden = parse_golden_lines(STEP_STATUSES_V0_GOLDEN);
+        let (eth, cross_chain) = golden.split_at(eth_statuses().len());
+        assert_eq!(encode_all(eth_statuses()), eth);
+
+        let mut expected = cross_chain_statuses();
+        expected.pop(); // RemoteFailed
+        if let CrossChainStepStatus::Submitted(_, event_id) = &mut expected[1] {
+            event_id.message_hash = None;
+        }
+        let migrated: Result<Vec<CrossChainStepStatus>, SchemaCheckError> = cross_chain
+            .iter()
+            .map(|status| {
+                check_blob::<CrossChainStepStatusV0>(status).map(CrossChainStepStatus::from)
+            })
+            .collect();
+        assert_eq!(migrated, Ok(expected));
+    }
+
+    #[test]
+    fn test_check_blob_is_strict() {
+        let golden = parse_golden_blob(EXECUTION_PLAN_GOLDEN);
+
+        let mut extended = golden.clone();
+        extended.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            check_exec_plan_blob(&extended),
+            Err(SchemaCheckError::TrailingBytes(2))
+        );
+        assert!(ExecutionPlan::decode(&mut extended.as_slice()).is_ok());
+
+        assert_eq!(
+            check_exec_plan_blob(&golden[..golden.len() - 1]),
+            Err(SchemaCheckError::DecodeFailed)
+        );
+
+        // e.g. a variant that was appended in a newer build
+        assert_eq!(
+            check_blob::<PlanReviewStatus>(&[4u8]),
+            Err(SchemaCheckError::DecodeFailed)
+        );
+    }
+}
@@ -0,0 +1,584 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+// How ExecutionPlans are stored in S3: STORED_PLAN_MAGIC, then the version of the ExecutionPlan
+// layout, then the plan's SCALE encoding. Plans written before the envelope are bare SCALE in
+// the V0 layout below (ExecutionPlan as deployed before the envelope), which decode_stored_plan
+// migrates to the current one.
+// To change ExecutionPlan's layout: copy the current one into ExecutionPlanV<STORED_PLAN_VERSION>
+// with a From, bump STORED_PLAN_VERSION and decode the old version with it in decode_stored_plan
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+use xcm::latest::MultiLocation;
+
+use privadex_chain_metadata::{
+    bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+    common::{Amount, BlockNum, MillisSinceEpoch, UniversalTokenId},
+};
+use privadex_common::uuid::Uuid;
+
+use crate::{
+    execution_plan::{
+        CommonExecutionMeta, CrossChainStepStatus, ERC20TransferStep, EthDexSwapStep, EthSendStep,
+        EthStepStatus, EthUnwrapStep, EthWrapStep, ExecutionPath, ExecutionPlan, ExecutionPolicy,
+        ExecutionStep, ExecutionStepEnum, FinalizedTxnId, PathExecutionMode, PendingTxnId,
+        PlanReviewStatus, SubstrateEventId, SubstratePendingEventId, XCMTransferStep,
+    },
+    schema_check::{check_blob, SchemaCheckError},
+};
+
+pub const STORED_PLAN_MAGIC: [u8; 4] = *b"PDXP";
+pub const STORED_PLAN_VERSION: u8 = 1;
+
+pub fn encode_stored_plan(exec_plan: &ExecutionPlan) -> Vec<u8> {
+    let mut blob = STORED_PLAN_MAGIC.to_vec();
+    blob.push(STORED_PLAN_VERSION);
+    exec_plan.encode_to(&mut blob);
+    blob
+}
+
+// Strict (see check_blob), so a plan written by a newer build fails instead of decoding from a
+// prefix of its bytes, which would then be saved back without the rest
+pub fn decode_stored_plan(blob: &[u8]) -> Result<ExecutionPlan, SchemaCheckError> {
+    match blob.split_at(blob.len().min(STORED_PLAN_MAGIC.len())) {
+        (magic, [version, plan_bytes @ ..]) if magic == STORED_PLAN_MAGIC => {
+            let res = match *version {
+                STORED_PLAN_VERSION => check_blob::<ExecutionPlan>(plan_bytes),
+                _ => Err(SchemaCheckError::UnknownVersion(*version)),
+            };
+            // A bare plan's UUID could start with the magic bytes (1 in 2^32), so fall back to
+            // the V0 layout rather than trust the prefix
+            res.or_else(|err| {
+                check_blob::<ExecutionPlanV0>(blob)
+                    .map(ExecutionPlan::from)
+                    .map_err(|_| err)
+            })
+        }
+        _ => check_blob::<ExecutionPlanV0>(blob).map(ExecutionPlan::from),
+    }
+}
+
+// ExecutionPlan and the types in it whose layout has changed since, as deployed before the
+// envelope (i.e. at c4272ff). Every bare plan in S3 is in this layout. The step types that are
+// not mirrored here have not changed
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) struct ExecutionPlanV0 {
+    pub uuid: Uuid,
+    pub paths: Vec<ExecutionPathV0>,
+    pub prestart_user_to_escrow_transfer: ExecutionStepV0,
+    pub postend_escrow_to_user_transfer: ExecutionStepV0,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) struct ExecutionPathV0 {
+    pub steps: Vec<ExecutionStepV0>,
+    pub amount_out: Option<Amount>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) struct ExecutionStepV0 {
+    pub inner: ExecutionStepEnumV0,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) enum ExecutionStepEnumV0 {
+    EthSend(EthSendStep),
+    ERC20Transfer(ERC20TransferStep),
+    EthWrap(EthWrapStep),
+    EthUnwrap(EthUnwrapStep),
+    EthDexSwap(EthDexSwapStep),
+    XCMTransfer(XCMTransferStepV0),
+}
+
+// Before dest_weight_limit
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) struct XCMTransferStepV0 {
+    pub uuid: Uuid,
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub token_asset_multilocation: MultiLocation,
+    pub full_dest_multilocation: MultiLocation,
+    pub amount_in: Option<Amount>,
+    pub bridge_fee_native: Amount,
+    pub bridge_fee_usd: Amount,
+    pub common: CommonExecutionMeta,
+    pub status: CrossChainStepStatusV0,
+}
+
+// Before RemoteFailed
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) enum CrossChainStepStatusV0 {
+    NotStarted,
+    Submitted(PendingTxnId, SubstratePendingEventIdV0),
+    Dropped,
+    Failed(FinalizedTxnId),
+    LocalConfirmed(FinalizedTxnId, SubstratePendingEventIdV0),
+    Confirmed(FinalizedTxnId, SubstrateEventId),
+}
+
+// Before message_hash
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub(crate) struct SubstratePendingEventIdV0 {
+    pub start_block_num: BlockNum,
+}
+
+// V0 plans have no refund step, so it is derived from the prestart step like the converter
+// does. Its UUID can't come from the converter's seed, which was never stored
+pub(crate) fn get_v0_refund_uuid(exec_plan_uuid: &Uuid) -> Uuid {
+    Uuid::new(sp_core_hashing::blake2_128(
+        &(b"refund", exec_plan_uuid).encode(),
+    ))
+}
+
+fn get_v0_refund_step(exec_plan_uuid: &Uuid, prestart: &ExecutionStep) -> ExecutionStep {
+    let mut refund_step = prestart.clone();
+    let refund_uuid = get_v0_refund_uuid(exec_plan_uuid);
+    let refund_common = |common: &CommonExecutionMeta| CommonExecutionMeta {
+        src_addr: common.dest_addr.clone(),
+        dest_addr: common.src_addr.clone(),
+        ..common.clone()
+    };
+    match &mut refund_step.inner {
+        ExecutionStepEnum::EthSend(step) => {
+            step.uuid = refund_uuid;
+            step.amount = None;
+            step.common = refund_common(&step.common);
+            step.status = EthStepStatus::NotStarted;
+        }
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            step.uuid = refund_uuid;
+            step.amount = None;
+            step.common = refund_common(&step.common);
+            step.status = EthStepStatus::NotStarted;
+        }
+        // The V0 converter only ever created the two above. The deadline is never set for a
+        // V0 plan, so the refund step is never run either way
+        _ => {}
+    }
+    refund_step
+}
+
+// The fields added since are set to what a V0 plan did implicitly: its paths ran concurrently
+// with no deadline, no deposit address and no prestart confirmation depth (so the deposit is
+// re-confirmed once, which it long since is). When it last progressed is unknown, so the stuck
+// plan watchdog reports an in-flight V0 plan until its next step changes status
+impl From<ExecutionPlanV0> for ExecutionPlan {
+    fn from(v0: ExecutionPlanV0) -> Self {
+        let prestart_user_to_escrow_transfer: ExecutionStep =
+            v0.prestart_user_to_escrow_transfer.into();
+        let refund_escrow_to_user_transfer =
+            get_v0_refund_step(&v0.uuid, &prestart_user_to_escrow_transfer);
+        Self {
+            uuid: v0.uuid,
+            paths: v0.paths.into_iter().map(ExecutionPath::from).collect(),
+            prestart_user_to_escrow_transfer,
+            deposit_sweep: None,
+            postend_escrow_to_user_transfer: v0.postend_escrow_to_user_transfer.into(),
+            refund_escrow_to_user_transfer,
+            path_execution_mode: PathExecutionMode::Concurrent,
+            execution_policy: ExecutionPolicy::Default,
+            deadline_millis: MillisSinceEpoch::MAX,
+            last_progress_millis: 0,
+            paper_trade: false,
+            dest_name: None,
+            metadata: None,
+            quote: None,
+            prestart_confirmed_block: None,
+            review_status: PlanReviewStatus::NotNeeded,
+            route_hash: None,
+            quoted_terms: None,
+            remove_liquidity: None,
+            sponsorship_campaign_id: None,
+        }
+    }
+}
+
+// step_amounts_out is only appended to in step order, so steps that already succeeded never
+// get theirs
+impl From<ExecutionPathV0> for ExecutionPath {
+    fn from(v0: ExecutionPathV0) -> Self {
+        Self {
+            steps: v0.steps.into_iter().map(ExecutionStep::from).collect(),
+            amount_out: v0.amount_out,
+            step_amounts_out: vec![],
+        }
+    }
+}
+
+impl From<ExecutionStepV0> for ExecutionStep {
+    fn from(v0: ExecutionStepV0) -> Self {
+        Self::new(v0.inner.into())
+    }
+}
+
+impl From<ExecutionStepEnumV0> for ExecutionStepEnum {
+    fn from(v0: ExecutionStepEnumV0) -> Self {
+        match v0 {
+            ExecutionStepEnumV0::EthSend(step) => Self::EthSend(step),
+            ExecutionStepEnumV0::ERC20Transfer(step) => Self::ERC20Transfer(step),
+            ExecutionStepEnumV0::EthWrap(step) => Self::EthWrap(step),
+            ExecutionStepEnumV0::EthUnwrap(step) => Self::EthUnwrap(step),
+            ExecutionStepEnumV0::EthDexSwap(step) => Self::EthDexSwap(step),
+            ExecutionStepEnumV0::XCMTransfer(step) => Self::XCMTransfer(step.into()),
+        }
+    }
+}
+
+// V0 executors bought DEFAULT_XCM_DEST_WEIGHT_LIMIT on every lane
+impl From<XCMTransferStepV0> for XCMTransferStep {
+    fn from(v0: XCMTransferStepV0) -> Self {
+        Self {
+            uuid: v0.uuid,
+            src_token: v0.src_token,
+            dest_token: v0.dest_token,
+            token_asset_multilocation: v0.token_asset_multilocation,
+            full_dest_multilocation: v0.full_dest_multilocation,
+            dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+            amount_in: v0.amount_in,
+            bridge_fee_native: v0.bridge_fee_native,
+            bridge_fee_usd: v0.bridge_fee_usd,
+            common: v0.common,
+            status: v0.status.into(),
+        }
+    }
+}
+
+impl From<CrossChainStepStatusV0> for CrossChainStepStatus {
+    fn from(v0: CrossChainStepStatusV0) -> Self {
+        match v0 {
+            CrossChainStepStatusV0::NotStarted => Self::NotStarted,
+            CrossChainStepStatusV0::Submitted(txn_id, event_id) => {
+                Self::Submitted(txn_id, event_id.into())
+            }
+            CrossChainStepStatusV0::Dropped => Self::Dropped,
+            CrossChainStepStatusV0::Failed(txn_id) => Self::Failed(txn_id),
+            CrossChainStepStatusV0::LocalConfirmed(txn_id, event_id) => {
+                Self::LocalConfirmed(txn_id, event_id.into())
+            }
+            CrossChainStepStatusV0::Confirmed(txn_id, event_id) => {
+                Self::Confirmed(txn_id, event_id)
+            }
+        }
+    }
+}
+
+impl From<SubstratePendingEventIdV0> for SubstratePendingEventId {
+    fn from(v0: SubstratePendingEventIdV0) -> Self {
+        Self {
+            start_block_num: v0.start_block_num,
+            message_hash: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod stored_plan_tests {
+    use hex_literal::hex;
+    use ink::prelude::string::String;
+    use xcm::latest::{Junction, Junctions, NetworkId};
+
+    use privadex_chain_metadata::{
+        common::{ChainTokenId, ERC20Token, EthAddress, EthTxnHash, UniversalAddress, XC20Token},
+        registry::chain::universal_chain_id_registry::{MOONBEAM, POLKADOT},
+    };
+
+    use super::*;
+    use crate::execution_plan::{DexRouterFunction, EthPendingTxnId, SubstratePendingExtrinsicId};
+
+    const EXECUTION_PLAN_GOLDEN: &str = include_str!("./golden/v1/execution_plan.hex");
+    const STORED_EXECUTION_PLAN_GOLDEN: &str =
+        include_str!("./golden/v1/stored_execution_plan.hex");
+    // A plan in the V0 layout, hand-encoded from the types at c4272ff since that build's
+    // dependencies are no longer fetchable. Never regenerate it: it stands in for the plans that
+    // are still in S3
+    const EXECUTION_PLAN_V0_GOLDEN: &str = include_str!("./golden/v0/execution_plan.hex");
+
+    const ESCROW: EthAddress = EthAddress {
+        0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+    };
+    const USER: EthAddress = EthAddress { 0: [0x11; 20] };
+    const HASH_A: EthTxnHash = EthTxnHash { 0: [0xaa; 32] };
+    const HASH_B: EthTxnHash = EthTxnHash { 0: [0xbb; 32] };
+
+    fn parse_golden_blob(golden: &str) -> Vec<u8> {
+        hex::decode(golden.split_whitespace().collect::<String>())
+            .expect("Golden files must be hex")
+    }
+
+    fn golden_exec_plan() -> ExecutionPlan {
+        decode_stored_plan(&parse_golden_blob(STORED_EXECUTION_PLAN_GOLDEN))
+            .expect("The stored golden plan must decode")
+    }
+
+    fn uuid(byte: u8) -> Uuid {
+        Uuid::new([byte; 16])
+    }
+
+    fn wglmr() -> UniversalTokenId {
+        UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress { 0: [0x33; 20] },
+            }),
+        }
+    }
+
+    fn xcdot() -> UniversalTokenId {
+        UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::XC20(XC20Token::from_asset_id(1)),
+        }
+    }
+
+    fn common(src: EthAddress, dest: EthAddress) -> CommonExecutionMeta {
+        CommonExecutionMeta {
+            src_addr: UniversalAddress::Ethereum(src),
+            dest_addr: UniversalAddress::Ethereum(dest),
+            gas_fee_native: 1_000,
+            gas_fee_usd: 2_000,
+        }
+    }
+
+    fn xcm_transfer_v0(
+        uuid: Uuid,
+        amount_in: Option<Amount>,
+        status: CrossChainStepStatusV0,
+    ) -> ExecutionStepV0 {
+        ExecutionStepV0 {
+            inner: ExecutionStepEnumV0::XCMTransfer(XCMTransferStepV0 {
+                uuid,
+                src_token: xcdot(),
+                dest_token: UniversalTokenId {
+                    chain: POLKADOT,
+                    id: ChainTokenId::Native,
+                },
+                token_asset_multilocation: MultiLocation::parent(),
+                full_dest_multilocation: MultiLocation {
+                    parents: 1,
+                    interior: Junctions::X1(Junction::AccountId32 {
+                        network: NetworkId::Any,
+                        id: [0x22; 32],
+                    }),
+                },
+                amount_in,
+                bridge_fee_native: 12,
+                bridge_fee_usd: 13,
+                common: common(ESCROW, USER),
+                status,
+            }),
+        }
+    }
+
+    // What EXECUTION_PLAN_V0_GOLDEN holds: an in-flight plan with one path midway and the other
+    // waiting on the remote chain
+    fn golden_exec_plan_v0() -> ExecutionPlanV0 {
+        ExecutionPlanV0 {
+            uuid: uuid(0xee),
+            paths: vec![
+                ExecutionPathV0 {
+                    steps: vec![
+                        ExecutionStepV0 {
+                            inner: ExecutionStepEnumV0::EthWrap(EthWrapStep {
+                                uuid: uuid(3),
+                                chain: MOONBEAM,
+                                amount: None,
+                                common: common(ESCROW, USER),
+                                status: EthStepStatus::Confirmed(HASH_B),
+                            }),
+                        },
+                        ExecutionStepV0 {
+                            inner: ExecutionStepEnumV0::EthDexSwap(EthDexSwapStep {
+                                uuid: uuid(5),
+                                dex_router_addr: EthAddress { 0: [0x44; 20] },
+                                dex_router_func: DexRouterFunction::SwapExactTokensForTokens,
+                                token_path: vec![wglmr(), xcdot()],
+                                amount_in: None,
+                                common: common(ESCROW, USER),
+                                status: EthStepStatus::Submitted(EthPendingTxnId {
+                                    txn_hash: HASH_A,
+                                    end_block_num: 100,
+                                }),
+                            }),
+                        },
+                        xcm_transfer_v0(
+                            uuid(6),
+                            None,
+                            CrossChainStepStatusV0::Submitted(
+                                PendingTxnId::Substrate(SubstratePendingExtrinsicId {
+                                    start_block_num: 1,
+                                    end_block_num: 65,
+                                    extrinsic_hash: HASH_A,
+                                }),
+                                SubstratePendingEventIdV0 { start_block_num: 2 },
+                            ),
+                        ),
+                    ],
+                    amount_out: None,
+                },
+                ExecutionPathV0 {
+                    steps: vec![xcm_transfer_v0(
+                        uuid(7),
+                        Some(11),
+                        CrossChainStepStatusV0::LocalConfirmed(
+                            FinalizedTxnId::Ethereum(HASH_A),
+                            SubstratePendingEventIdV0 { start_block_num: 5 },
+                        ),
+                    )],
+                    amount_out: Some(100),
+                },
+            ],
+            prestart_user_to_escrow_transfer: ExecutionStepV0 {
+                inner: ExecutionStepEnumV0::EthSend(EthSendStep {
+                    uuid: uuid(1),
+                    chain: MOONBEAM,
+                    amount: Some(1_000_000),
+                    common: common(USER, ESCROW),
+                    status: EthStepStatus::Confirmed(HASH_B),
+                }),
+            },
+            postend_escrow_to_user_transfer: ExecutionStepV0 {
+                inner: ExecutionStepEnumV0::ERC20Transfer(ERC20TransferStep {
+                    uuid: uuid(2),
+                    token: wglmr(),
+                    amount: None,
+                    common: common(ESCROW, USER),
+                    status: EthStepStatus::NotStarted,
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn test_stored_plan_golden_encoding() {
+        let golden = parse_golden_blob(STORED_EXECUTION_PLAN_GOLDEN);
+        let exec_plan = golden_exec_plan();
+        assert_eq!(encode_stored_plan(&exec_plan), golden);
+        assert_eq!(
+            golden[STORED_PLAN_MAGIC.len() + 1..],
+            parse_golden_blob(EXECUTION_PLAN_GOLDEN)
+        );
+    }
+
+    #[test]
+    fn test_v0_plan_golden_encoding() {
+        let golden = parse_golden_blob(EXECUTION_PLAN_V0_GOLDEN);
+        assert_eq!(golden_exec_plan_v0().encode(), golden);
+        assert_eq!(
+            decode_stored_plan(&golden),
+            Ok(ExecutionPlan::from(golden_exec_plan_v0()))
+        );
+    }
+
+    #[test]
+    fn test_v0_plan_migrates_with_new_fields_unset() {
+        let exec_plan = decode_stored_plan(&parse_golden_blob(EXECUTION_PLAN_V0_GOLDEN))
+            .expect("The V0 golden plan must decode");
+        assert_eq!(exec_plan.uuid, uuid(0xee));
+        assert_eq!(exec_plan.path_execution_mode, PathExecutionMode::Concurrent);
+        assert_eq!(exec_plan.deadline_millis, MillisSinceEpoch::MAX);
+        assert_eq!(exec_plan.deposit_sweep, None);
+        assert_eq!(exec_plan.route_hash, None);
+        assert_eq!(exec_plan.sponsorship_campaign_id, None);
+
+        let first_path = &exec_plan.paths[0];
+        assert_eq!(first_path.steps.len(), 3);
+        assert!(first_path.step_amounts_out.is_empty());
+        assert!(first_path
+            .steps
+            .iter()
+            .all(|step| step.timing == Default::default() && step.presigned_txn.is_none()));
+        match &first_path.steps[2].inner {
+            ExecutionStepEnum::XCMTransfer(step) => {
+                assert_eq!(step.dest_weight_limit, DEFAULT_XCM_DEST_WEIGHT_LIMIT);
+                assert_eq!(
+                    step.status,
+                    CrossChainStepStatus::Submitted(
+                        PendingTxnId::Substrate(SubstratePendingExtrinsicId {
+                            start_block_num: 1,
+                            end_block_num: 65,
+                            extrinsic_hash: HASH_A,
+                        }),
+                        SubstratePendingEventId {
+                            start_block_num: 2,
+                            message_hash: None,
+                        },
+                    )
+                );
+            }
+            step => panic!("Expected an XCMTransfer, got {:?}", step),
+        }
+        assert_eq!(exec_plan.paths[1].amount_out, Some(100));
+
+        // The prestart step turned around, with a UUID no other step has
+        assert_eq!(
+            exec_plan.refund_escrow_to_user_transfer,
+            ExecutionStep::new(ExecutionStepEnum::EthSend(EthSendStep {
+                uuid: get_v0_refund_uuid(&uuid(0xee)),
+                chain: MOONBEAM,
+                amount: None,
+                common: common(ESCROW, USER),
+                status: EthStepStatus::NotStarted,
+            }))
+        );
+        assert!(![0xee, 1, 2, 3, 5, 6, 7]
+            .iter()
+            .any(|byte| get_v0_refund_uuid(&uuid(0xee)) == uuid(*byte)));
+
+        // Saved back in the envelope, the plan keeps everything the V0 layout had
+        assert_eq!(
+            decode_stored_plan(&encode_stored_plan(&exec_plan)),
+            Ok(exec_plan)
+        );
+    }
+
+    #[test]
+    fn test_stored_plan_is_strict() {
+        let golden = parse_golden_blob(STORED_EXECUTION_PLAN_GOLDEN);
+
+        let mut newer = golden.clone();
+        newer[STORED_PLAN_MAGIC.len()] = STORED_PLAN_VERSION + 1;
+        assert_eq!(
+            decode_stored_plan(&newer),
+            Err(SchemaCheckError::UnknownVersion(STORED_PLAN_VERSION + 1))
+        );
+
+        let mut extended = golden.clone();
+        extended.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            decode_stored_plan(&extended),
+            Err(SchemaCheckError::TrailingBytes(2))
+        );
+
+        assert_eq!(
+            decode_stored_plan(&golden[..golden.len() - 1]),
+            Err(SchemaCheckError::DecodeFailed)
+        );
+        assert_eq!(
+            decode_stored_plan(&STORED_PLAN_MAGIC),
+            Err(SchemaCheckError::DecodeFailed)
+        );
+
+        // Nothing wrote the current layout without the envelope
+        assert!(decode_stored_plan(&golden[STORED_PLAN_MAGIC.len() + 1..]).is_err());
+        let mut v0_extended = parse_golden_blob(EXECUTION_PLAN_V0_GOLDEN);
+        v0_extended.push(0);
+        assert_eq!(
+            decode_stored_plan(&v0_extended),
+            Err(SchemaCheckError::TrailingBytes(1))
+        );
+    }
+}
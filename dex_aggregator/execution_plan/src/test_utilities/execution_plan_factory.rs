@@ -0,0 +1,150 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Plans and steps for unit tests. A test only spells out what it is about and takes the rest
+// from here, e.g. ExecutionPlan { quote: Some(..), ..plan(paths) }, so that a new plan field
+// is only added in one place
+
+use hex_literal::hex;
+use ink::prelude::{vec, vec::Vec};
+
+use privadex_chain_metadata::{
+    common::{Amount, EthAddress, EthTxnHash, MillisSinceEpoch, UniversalAddress},
+    registry::chain::universal_chain_id_registry::MOONBEAM,
+};
+use privadex_common::uuid::Uuid;
+
+use crate::execution_plan::{
+    CommonExecutionMeta, EthPendingTxnId, EthSendStep, EthStepStatus, EthUnwrapStep, EthWrapStep,
+    ExecutionPath, ExecutionPlan, ExecutionPolicy, ExecutionStep, ExecutionStepEnum,
+    PathExecutionMode, PlanReviewStatus,
+};
+
+pub const ESCROW: UniversalAddress = UniversalAddress::Ethereum(EthAddress {
+    0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+});
+pub const USER: UniversalAddress = UniversalAddress::Ethereum(EthAddress {
+    0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
+});
+
+// Gas is free, so that the amounts a test checks are not offset by it
+pub fn common(src_addr: UniversalAddress, dest_addr: UniversalAddress) -> CommonExecutionMeta {
+    CommonExecutionMeta {
+        src_addr,
+        dest_addr,
+        gas_fee_native: 0,
+        gas_fee_usd: 0,
+    }
+}
+
+pub fn eth_send(
+    uuid_byte: u8,
+    src_addr: UniversalAddress,
+    dest_addr: UniversalAddress,
+    amount: Option<Amount>,
+    status: EthStepStatus,
+) -> ExecutionStep {
+    ExecutionStep::new(ExecutionStepEnum::EthSend(EthSendStep {
+        uuid: Uuid::new([uuid_byte; 16]),
+        chain: MOONBEAM,
+        amount,
+        common: common(src_addr, dest_addr),
+        status,
+    }))
+}
+
+pub fn eth_wrap(uuid_byte: u8, amount: Option<Amount>, status: EthStepStatus) -> ExecutionStep {
+    ExecutionStep::new(ExecutionStepEnum::EthWrap(EthWrapStep {
+        uuid: Uuid::new([uuid_byte; 16]),
+        chain: MOONBEAM,
+        amount,
+        common: common(ESCROW, ESCROW),
+        status,
+    }))
+}
+
+pub fn eth_unwrap(uuid_byte: u8, amount: Option<Amount>, status: EthStepStatus) -> ExecutionStep {
+    ExecutionStep::new(ExecutionStepEnum::EthUnwrap(EthUnwrapStep {
+        uuid: Uuid::new([uuid_byte; 16]),
+        chain: MOONBEAM,
+        amount,
+        common: common(ESCROW, ESCROW),
+        status,
+    }))
+}
+
+pub fn submitted(txn_hash: EthTxnHash) -> EthStepStatus {
+    EthStepStatus::Submitted(EthPendingTxnId {
+        txn_hash,
+        end_block_num: 100,
+    })
+}
+
+pub fn path(steps: Vec<ExecutionStep>) -> ExecutionPath {
+    ExecutionPath {
+        steps,
+        amount_out: None,
+        step_amounts_out: Vec::new(),
+    }
+}
+
+// Plan (0x01..) whose 1_000 prestart deposit (0xa1..) landed, with the postend (0xa2..) and
+// refund (0xa3..) not started. The step UUIDs stay clear of the low bytes the tests give
+// their path steps
+pub fn plan(paths: Vec<ExecutionPath>) -> ExecutionPlan {
+    ExecutionPlan {
+        uuid: Uuid::new([1u8; 16]),
+        paths,
+        prestart_user_to_escrow_transfer: eth_send(
+            0xa1,
+            USER,
+            ESCROW,
+            Some(1_000),
+            EthStepStatus::Confirmed(EthTxnHash::zero()),
+        ),
+        deposit_sweep: None,
+        postend_escrow_to_user_transfer: eth_send(
+            0xa2,
+            ESCROW,
+            USER,
+            None,
+            EthStepStatus::NotStarted,
+        ),
+        refund_escrow_to_user_transfer: eth_send(
+            0xa3,
+            ESCROW,
+            USER,
+            None,
+            EthStepStatus::NotStarted,
+        ),
+        path_execution_mode: PathExecutionMode::Concurrent,
+        execution_policy: ExecutionPolicy::Default,
+        deadline_millis: MillisSinceEpoch::MAX,
+        last_progress_millis: 0,
+        paper_trade: false,
+        dest_name: None,
+        metadata: None,
+        quote: None,
+        prestart_confirmed_block: Some(0),
+        review_status: PlanReviewStatus::NotNeeded,
+        route_hash: None,
+        quoted_terms: None,
+        remove_liquidity: None,
+        sponsorship_campaign_id: None,
+    }
+}
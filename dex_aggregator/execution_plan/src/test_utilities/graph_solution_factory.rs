@@ -17,7 +17,7 @@
  */
 
 use hex_literal::hex;
-use ink_prelude::vec;
+use ink::prelude::vec;
 
 use privadex_chain_metadata::common::{
     ChainTokenId::{Native, ERC20, XC20},
@@ -129,6 +129,7 @@ pub fn graph_solution_full_static() -> GraphSolution {
                     }),
                     reserve0: 17410180344594059755520,
                     reserve1: 16813114969,
+                    fee_bps: 30,
                     estimated_gas_fee_in_dest_token: 11,
                     estimated_gas_fee_usd: 11270644754894,
                     dex: &ARTHSWAP,
@@ -161,6 +162,7 @@ pub fn graph_solution_full_static() -> GraphSolution {
                     )),
                     reserve0: 25326566566,
                     reserve1: 58965001158180,
+                    fee_bps: 30,
                     estimated_gas_fee_in_dest_token: 26232,
                     estimated_gas_fee_usd: 11270644754894,
                     dex: &ARTHSWAP,
@@ -195,6 +197,7 @@ pub fn graph_solution_full_static() -> GraphSolution {
                     )),
                     reserve0: 87625774395904957087744,
                     reserve1: 70243543517614,
+                    fee_bps: 30,
                     estimated_gas_fee_in_dest_token: 12000000000000000,
                     estimated_gas_fee_usd: 4142787920734278,
                     dex: &BEAMSWAP,
@@ -231,6 +234,7 @@ pub fn graph_solution_full_static() -> GraphSolution {
                     }),
                     reserve0: 122125802565,
                     reserve1: 353749614708794790510592,
+                    fee_bps: 30,
                     estimated_gas_fee_in_dest_token: 4136,
                     estimated_gas_fee_usd: 4142787920734278,
                     dex: &BEAMSWAP,
@@ -267,6 +271,7 @@ pub fn graph_solution_full_static() -> GraphSolution {
                     }),
                     reserve0: 18664652931,
                     reserve1: 76692893618149933056,
+                    fee_bps: 25,
                     estimated_gas_fee_in_dest_token: 16986992011195,
                     estimated_gas_fee_usd: 4125948315331289,
                     dex: &STELLASWAP,
@@ -340,6 +345,7 @@ pub fn graph_solution_medium_static() -> GraphSolution {
                     )),
                     reserve0: 6310610688295264816463872,
                     reserve1: 5084579044703826,
+                    fee_bps: 25,
                     estimated_gas_fee_in_dest_token: 9565115,
                     estimated_gas_fee_usd: 4125948315331289,
                     dex: &STELLASWAP,
@@ -387,7 +393,7 @@ mod graph_solution_factory_tests {
     #[cfg(feature = "test-utils")]
     use super::*;
     use crate::test_utilities::graph_solution_factory;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     #[test]
     fn test_full_solution_static() {
@@ -16,5 +16,6 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod execution_plan_factory;
 #[rustfmt::skip]
 pub mod graph_solution_factory;
@@ -16,7 +16,9 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use crate::execution_plan::{EthDexSwapStep, ExecutionPlan, ExecutionStepEnum};
+use privadex_chain_metadata::common::UniversalAddress;
+
+use crate::execution_plan::{EthDexSwapStep, ExecutionPlan, ExecutionStep, ExecutionStepEnum};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -31,12 +33,31 @@ pub enum ExecutionPlanValidationError {
     ConsecutiveUnwrapWrap,
     InvalidPrestartStep,
     InvalidPostendStep,
+    // Deposit sweep must be an EthDepositSweep out of the prestart step's dest address
+    InvalidDepositSweepStep,
+    // We only expect this as the plan's deposit_sweep
+    UnexpectedEthDepositSweep,
+    // Remove liquidity must be an EthRemoveLiquidity of the token the prestart step deposits,
+    // into two other tokens on its chain, with paths for both
+    InvalidRemoveLiquidityStep,
+    // We only expect this as the plan's remove_liquidity
+    UnexpectedEthRemoveLiquidity,
+    // We only expect this as the plan's prestart step
+    UnexpectedSubstrateRemarkDeposit,
+    // We only expect this as the plan's postend step
+    UnexpectedEthContractCall,
+    // We only expect this as the plan's postend step
+    UnexpectedEthZapIn,
+    // We only expect this as the last step of a path
+    UnexpectedEthFeeSkim,
     SwapAfterWrap, // Wrap + Swap should be merged into a SwapETHForTokens swap
     WrapSrcDestAddressMismatch, // Wrap step's src and dest address must match
     UnexpectedEthSend, // We currently only expect this in the prestart and postend steps
     UnexpectedERC20Transfer, // We currently only expect this in the prestart and postend steps
     UnwrapAfterSwap, // Swap + Unwrap should be merged into a SwapTokensForETH swap
     UnwrapSrcDestAddressMismatch, // Unwrap step's src and dest address must match
+    // The aggregator swap's proceeds must land back in the address it swapped out of
+    AggregatorSwapSrcDestAddressMismatch,
 }
 
 // Used in the unit tests in graph_solution_to_execution_plan
@@ -56,11 +77,54 @@ pub fn validate_execution_plan(
     let _ = match execution_plan.prestart_user_to_escrow_transfer.inner {
         ExecutionStepEnum::EthSend(_) => Ok(()),
         ExecutionStepEnum::ERC20Transfer(_) => Ok(()),
+        ExecutionStepEnum::SubstrateRemarkDeposit(_) => Ok(()),
         _ => Err(ExecutionPlanValidationError::InvalidPrestartStep),
     }?;
+    if let Some(deposit_sweep) = &execution_plan.deposit_sweep {
+        let _ = match &deposit_sweep.inner {
+            ExecutionStepEnum::EthDepositSweep(step)
+                if Some(&step.common.src_addr)
+                    == get_transfer_dest_addr(&execution_plan.prestart_user_to_escrow_transfer) =>
+            {
+                Ok(())
+            }
+            _ => Err(ExecutionPlanValidationError::InvalidDepositSweepStep),
+        }?;
+    }
+    if let Some(remove_liquidity) = &execution_plan.remove_liquidity {
+        let _ = match (
+            &remove_liquidity.inner,
+            &execution_plan.prestart_user_to_escrow_transfer.inner,
+        ) {
+            (
+                ExecutionStepEnum::EthRemoveLiquidity(step),
+                ExecutionStepEnum::ERC20Transfer(prestart_step),
+            ) if step.lp_token == prestart_step.token
+                && step.token_a.chain == step.lp_token.chain
+                && step.token_b.chain == step.lp_token.chain
+                && step.token_a != step.token_b
+                && step.num_token_a_paths > 0
+                && (step.num_token_a_paths as usize) < execution_plan.paths.len() =>
+            {
+                Ok(())
+            }
+            _ => Err(ExecutionPlanValidationError::InvalidRemoveLiquidityStep),
+        }?;
+    }
     let _ = match execution_plan.postend_escrow_to_user_transfer.inner {
         ExecutionStepEnum::EthSend(_) => Ok(()),
         ExecutionStepEnum::ERC20Transfer(_) => Ok(()),
+        ExecutionStepEnum::EthContractCall(ref step) if step.call.get_amount_offset().is_some() => {
+            Ok(())
+        }
+        // The pair is on the chain the token was delivered to, and is not the token with itself
+        ExecutionStepEnum::EthZapIn(ref step)
+            if step.token.chain == step.pair_token.chain
+                && step.token.chain == step.lp_token.chain
+                && step.token != step.pair_token =>
+        {
+            Ok(())
+        }
         _ => Err(ExecutionPlanValidationError::InvalidPostendStep),
     }?;
 
@@ -69,7 +133,8 @@ pub fn validate_execution_plan(
             // The first step's amount_in must be non-null
             return Err(ExecutionPlanValidationError::FirstStepHasNullAmount);
         }
-        for step in exec_path.steps.iter() {
+        let num_steps = exec_path.steps.len();
+        for (i, step) in exec_path.steps.iter().enumerate() {
             let _ = match &step.inner {
                 ExecutionStepEnum::EthWrap(step) => {
                     if step.common.src_addr != step.common.dest_addr {
@@ -85,12 +150,30 @@ pub fn validate_execution_plan(
                         Ok(())
                     }
                 }
+                ExecutionStepEnum::EthAggregatorSwap(step) => {
+                    if step.common.src_addr != step.common.dest_addr {
+                        Err(ExecutionPlanValidationError::AggregatorSwapSrcDestAddressMismatch)
+                    } else {
+                        Ok(())
+                    }
+                }
+                ExecutionStepEnum::EthContractCall(_) => {
+                    Err(ExecutionPlanValidationError::UnexpectedEthContractCall)
+                }
+                ExecutionStepEnum::EthZapIn(_) => {
+                    Err(ExecutionPlanValidationError::UnexpectedEthZapIn)
+                }
+                ExecutionStepEnum::EthRemoveLiquidity(_) => {
+                    Err(ExecutionPlanValidationError::UnexpectedEthRemoveLiquidity)
+                }
+                ExecutionStepEnum::EthFeeSkim(_) if i != num_steps - 1 => {
+                    Err(ExecutionPlanValidationError::UnexpectedEthFeeSkim)
+                }
                 _ => Ok(()),
             }?;
         }
 
         // Iterator::array_chunks is elegant but only has nightly support, so we do a raw loop
-        let num_steps = exec_path.steps.len();
         for i in 0..(num_steps - 1) {
             let cur_step = &exec_path.steps[i];
             let next_step = &exec_path.steps[i + 1];
@@ -102,6 +185,14 @@ pub fn validate_execution_plan(
                 | (_, ExecutionStepEnum::ERC20Transfer(_)) => {
                     Err(ExecutionPlanValidationError::UnexpectedERC20Transfer)
                 }
+                (ExecutionStepEnum::EthDepositSweep(_), _)
+                | (_, ExecutionStepEnum::EthDepositSweep(_)) => {
+                    Err(ExecutionPlanValidationError::UnexpectedEthDepositSweep)
+                }
+                (ExecutionStepEnum::SubstrateRemarkDeposit(_), _)
+                | (_, ExecutionStepEnum::SubstrateRemarkDeposit(_)) => {
+                    Err(ExecutionPlanValidationError::UnexpectedSubstrateRemarkDeposit)
+                }
 
                 (ExecutionStepEnum::EthWrap(_), ExecutionStepEnum::EthWrap(_)) => {
                     Err(ExecutionPlanValidationError::ConsecutiveWraps)
@@ -143,3 +234,11 @@ pub fn validate_execution_plan(
     }
     Ok(())
 }
+
+fn get_transfer_dest_addr(transfer_step: &ExecutionStep) -> Option<&UniversalAddress> {
+    match &transfer_step.inner {
+        ExecutionStepEnum::EthSend(step) => Some(&step.common.dest_addr),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(&step.common.dest_addr),
+        _ => None,
+    }
+}
@@ -18,8 +18,8 @@
 
 use core::str::FromStr;
 use hex_literal::hex;
-use ink_env::debug_println;
-use ink_prelude::vec;
+use ink::env::debug_println;
+use ink::prelude::vec;
 use std::{thread, time::Duration};
 
 use privadex_chain_metadata::{
@@ -32,6 +32,7 @@ use privadex_chain_metadata::{
         token::universal_token_id_registry,
     },
 };
+use privadex_common::signature_scheme::SignatureScheme;
 use privadex_execution_plan::execution_plan::{
     EthPendingTxnId, EthStepStatus, ExecutionPlan, ExecutionStepEnum,
 };
@@ -44,7 +45,7 @@ use privadex_executor::{
         execute_step_meta::ExecuteStepMeta,
         traits::{Executable, ExecutableSimpleStatus, StepForwardResult},
     },
-    key_container::{AddressKeyPair, KeyContainer},
+    key_container::{AddressKeyPair, KeyContainer, SigningKey},
 };
 use privadex_routing;
 
@@ -140,6 +141,7 @@ fn main() {
                 initial_amount,
                 keys.get_key(&UniversalAddress::Ethereum(user_eth_addr))
                     .expect("Key must exist"),
+                astar_chain_info.supports_eip1559,
                 nonce,
             )
             .expect("Expected signed txn");
@@ -193,35 +195,42 @@ fn get_state() -> (ExecuteStepMeta, KeyContainer) {
     let escrow_substrate_addr = UniversalAddress::Substrate(SubstratePublicKey {
         0: hex!("7011b670bb662eedbd60a1c4c11b7c197ec22e7cfe87df00013ca2c494f3b01a"),
     });
-    let keys = KeyContainer {
-        0: vec![
-            AddressKeyPair {
-                address: escrow_eth_addr.clone(),
-                key: SecretKeyContainer::from_str(
+    let keys = KeyContainer::new(vec![
+        AddressKeyPair {
+            address: escrow_eth_addr.clone(),
+            key: SigningKey::Local(
+                SecretKeyContainer::from_str(
                     &std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set"),
                 )
                 .expect("ETH_PRIVATE_KEY to_hex failed")
                 .0,
-            },
-            AddressKeyPair {
-                address: astar_native_addr.clone(),
-                key: SecretKeyContainer::from_str(
+            ),
+            sig_scheme: SignatureScheme::Ethereum,
+        },
+        AddressKeyPair {
+            address: astar_native_addr.clone(),
+            key: SigningKey::Local(
+                SecretKeyContainer::from_str(
                     &std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set"),
                 )
                 .expect("ETH_PRIVATE_KEY to_hex failed")
                 .0,
-            },
-            AddressKeyPair {
-                address: escrow_substrate_addr.clone(),
-                key: SecretKeyContainer::from_str(
+            ),
+            sig_scheme: SignatureScheme::Sr25519,
+        },
+        AddressKeyPair {
+            address: escrow_substrate_addr.clone(),
+            key: SigningKey::Local(
+                SecretKeyContainer::from_str(
                     &std::env::var("SUBSTRATE_PRIVATE_KEY")
                         .expect("Env var SUBSTRATE_PRIVATE_KEY is not set"),
                 )
                 .expect("SUBSTRATE_PRIVATE_KEY to_hex failed")
                 .0,
-            },
-        ],
-    };
+            ),
+            sig_scheme: SignatureScheme::Sr25519,
+        },
+    ]);
     let execute_step_meta = ExecuteStepMeta::dummy(now_millis());
     (execute_step_meta, keys)
 }
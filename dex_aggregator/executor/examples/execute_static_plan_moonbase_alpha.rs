@@ -18,26 +18,28 @@
 
 use core::str::FromStr;
 use hex_literal::hex;
-use ink_env::debug_println;
-use ink_prelude::vec;
+use ink::env::debug_println;
+use ink::prelude::vec;
 use std::{thread, time::Duration};
 use xcm::prelude::{Junction, Junctions, MultiLocation, NetworkId};
 
 use privadex_chain_metadata::{
+    bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT,
     common::{
-        ChainTokenId, ERC20Token, EthAddress, SecretKeyContainer, UniversalAddress,
-        UniversalTokenId, XC20Token,
+        ChainTokenId, ERC20Token, EthAddress, MillisSinceEpoch, SecretKeyContainer,
+        UniversalAddress, UniversalTokenId, XC20Token,
     },
     registry::{
         chain::{chain_info_registry, universal_chain_id_registry},
         dex::dex_registry,
     },
 };
-use privadex_common::uuid::Uuid;
+use privadex_common::{signature_scheme::SignatureScheme, uuid::Uuid};
 use privadex_execution_plan::execution_plan::{
     CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, ERC20TransferStep,
     EthDexSwapStep, EthPendingTxnId, EthStepStatus, EthUnwrapStep, EthWrapStep, ExecutionPath,
-    ExecutionPlan, ExecutionStep, ExecutionStepEnum, XCMTransferStep,
+    ExecutionPlan, ExecutionPolicy, ExecutionStep, ExecutionStepEnum, PathExecutionMode,
+    PlanReviewStatus, XCMTransferStep,
 };
 use privadex_executor::{
     eth_utils::{
@@ -48,7 +50,7 @@ use privadex_executor::{
         execute_step_meta::ExecuteStepMeta,
         traits::{Executable, ExecutableSimpleStatus, StepForwardResult},
     },
-    key_container::{AddressKeyPair, KeyContainer},
+    key_container::{AddressKeyPair, KeyContainer, SigningKey},
 };
 
 // This sends out real transactions on Moonbase Alpha, so you need to use an
@@ -179,6 +181,7 @@ fn main() {
                             },
                         ),
                     },
+                    dest_weight_limit: DEFAULT_XCM_DEST_WEIGHT_LIMIT,
                     amount_in: None,
                     bridge_fee_native: 100_000_000,
                     bridge_fee_usd: 3_000_000_000_000,
@@ -192,7 +195,9 @@ fn main() {
                 })),
             ],
             amount_out: None,
+            step_amounts_out: Vec::new(),
         }],
+        deposit_sweep: None,
         postend_escrow_to_user_transfer: ExecutionStep::new(ExecutionStepEnum::ERC20Transfer(
             ERC20TransferStep {
                 uuid: Uuid::new([0u8; 16]),
@@ -213,6 +218,41 @@ fn main() {
                 status: EthStepStatus::NotStarted,
             },
         )),
+        refund_escrow_to_user_transfer: ExecutionStep::new(ExecutionStepEnum::ERC20Transfer(
+            ERC20TransferStep {
+                uuid: Uuid::new([0u8; 16]),
+                token: UniversalTokenId {
+                    chain: universal_chain_id_registry::MOONBASE_ALPHA,
+                    id: ChainTokenId::ERC20(ERC20Token {
+                        addr: EthAddress {
+                            0: hex!("CdF746C5C86Df2c2772d2D36E227B4c0203CbA25"),
+                        },
+                    }),
+                },
+                amount: None,
+                common: CommonExecutionMeta {
+                    src_addr: addr.clone(),
+                    dest_addr: addr.clone(),
+                    gas_fee_native: 1_000_000_000,
+                    gas_fee_usd: 2_000_0000_000,
+                },
+                status: EthStepStatus::NotStarted,
+            },
+        )),
+        path_execution_mode: PathExecutionMode::Concurrent,
+        execution_policy: ExecutionPolicy::Default,
+        deadline_millis: MillisSinceEpoch::MAX,
+        last_progress_millis: 0,
+        paper_trade: false,
+        dest_name: None,
+        metadata: None,
+        quote: None,
+        prestart_confirmed_block: None,
+        review_status: PlanReviewStatus::NotNeeded,
+        route_hash: None,
+        quoted_terms: None,
+        remove_liquidity: None,
+        sponsorship_campaign_id: None,
     };
     assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::NotStarted);
     assert_eq!(exec_plan.get_total_fee_usd(), None);
@@ -235,6 +275,7 @@ fn main() {
                 eth_addr,
                 initial_amount,
                 keys.get_key(&addr).expect("Key must exist"),
+                chain_info.supports_eip1559,
                 nonce,
             )
             .expect("Expected signed txn");
@@ -282,16 +323,17 @@ fn get_state() -> (UniversalAddress, ExecuteStepMeta, KeyContainer) {
     let escrow_addr = UniversalAddress::Ethereum(EthAddress {
         0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
     });
-    let keys = KeyContainer {
-        0: vec![AddressKeyPair {
-            address: escrow_addr.clone(),
-            key: SecretKeyContainer::from_str(
+    let keys = KeyContainer::new(vec![AddressKeyPair {
+        address: escrow_addr.clone(),
+        key: SigningKey::Local(
+            SecretKeyContainer::from_str(
                 &std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set"),
             )
             .expect("ETH_PRIVATE_KEY to_hex failed")
             .0,
-        }],
-    };
+        ),
+        sig_scheme: SignatureScheme::Ethereum,
+    }]);
     let execute_step_meta = ExecuteStepMeta::dummy(now_millis());
     (escrow_addr, execute_step_meta, keys)
 }
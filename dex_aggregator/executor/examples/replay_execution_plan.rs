@@ -0,0 +1,70 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use std::{env, fs, process};
+
+use privadex_execution_plan::stored_plan::decode_stored_plan;
+use privadex_executor::executable::{
+    plan_events::PlanEvent,
+    plan_replay::{replay_execution_plan, ReplayFixtures},
+};
+
+// Read-only audit of an ExecutionPlan's history. Nothing here touches a chain or S3:
+//   <archived plan>: the plan object from the execution-plan bucket (see stored_plan)
+//   <event log>: the plan's plan-events/*.ndjson objects, concatenated in order
+//   <rpc fixtures>: {"eth_receipts": [{"txn_hash": "0x...", "status": "0x1" | "0x0" | null}]}
+//     i.e. the recorded eth_getTransactionReceipt result for each of the plan's txns
+// cargo run --example privadex_replay_execution_plan -- plan.scale events.ndjson fixtures.json
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        eprintln!(
+            "Usage: {} <archived plan> <event log> <rpc fixtures>",
+            args[0]
+        );
+        process::exit(2);
+    }
+
+    let plan_bytes = fs::read(&args[1]).expect("Could not read archived plan");
+    let exec_plan = decode_stored_plan(&plan_bytes).expect("Invalid archived plan");
+    let events: Vec<PlanEvent> = fs::read_to_string(&args[2])
+        .expect("Could not read event log")
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| PlanEvent::from_json(line).unwrap_or_else(|| panic!("Invalid event: {}", line)))
+        .collect();
+    let fixtures =
+        ReplayFixtures::from_json(&fs::read(&args[3]).expect("Could not read rpc fixtures"))
+            .expect("Invalid rpc fixtures");
+
+    match replay_execution_plan(&exec_plan, &events, &fixtures) {
+        Ok(num_transitions) => println!(
+            "OK: replayed {} step transitions of plan {}",
+            num_transitions,
+            exec_plan.uuid.to_hex_string()
+        ),
+        Err(err) => {
+            println!(
+                "INCONSISTENT: plan {}: {:?}",
+                exec_plan.uuid.to_hex_string(),
+                err
+            );
+            process::exit(1);
+        }
+    }
+}
@@ -18,18 +18,23 @@
 
 use core::str::FromStr;
 use hex_literal::hex;
-use ink_env::debug_println;
-use ink_prelude::{string::ToString, vec::Vec};
+use ink::env::debug_println;
+use ink::prelude::{string::ToString, vec::Vec};
 use sp_runtime::generic::Era;
 
-use privadex_chain_metadata::common::SecretKeyContainer;
+use privadex_chain_metadata::{
+    bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT, common::SecretKeyContainer,
+    registry::chain::chain_info_registry::MOONBASEALPHA_INFO, xcm_location::XcmVersion,
+};
 use privadex_common::{
     signature_scheme::SignatureScheme, utils::general_utils::slice_to_hex_string,
 };
 use privadex_executor::{
     extrinsic_call_factory,
+    key_container::SigningKey,
     substrate_utils::{
-        extrinsic_sig_config::ExtrinsicSigConfig, node_rpc_utils::SubstrateNodeRpcUtils,
+        extrinsic_sig_config::ExtrinsicSigConfig,
+        node_rpc_utils::{SubstrateNodeRpcUtils, TxPayment},
     },
 };
 
@@ -51,7 +56,7 @@ fn main() {
     let sigconfig = ExtrinsicSigConfig::<[u8; 20]> {
         sig_scheme: SignatureScheme::Ethereum,
         signer: sender,
-        privkey: kap_privkey.to_vec(),
+        key: SigningKey::Local(kap_privkey),
     };
 
     let nonce = chain_utils
@@ -80,16 +85,18 @@ fn main() {
         sender,                    /* dest */
         1_000_000_000_000_000u128, /* amount */
     );
-    let tx_raw = chain_utils.create_extrinsic::<[u8; 20]>(
-        sigconfig,
-        &encoded_call_data,
-        nonce,
-        runtime_version,
-        genesis_hash,
-        finalized_head, // checkpoint block hash
-        era,
-        0, // tip
-    );
+    let tx_raw = chain_utils
+        .create_extrinsic::<[u8; 20]>(
+            sigconfig,
+            &encoded_call_data,
+            nonce,
+            runtime_version,
+            genesis_hash,
+            finalized_head, // checkpoint block hash
+            era,
+            TxPayment::from_chain_info(&MOONBASEALPHA_INFO, 0 /* tip */, None),
+        )
+        .expect("Expected signed extrinsic");
     debug_println!("Raw txn: {:?}", slice_to_hex_string(&tx_raw));
 
     // Commented out to avoid actually sending out transactions
@@ -122,9 +129,14 @@ fn moonbase_alpha_xtokens_transfer_multiasset_demo(dest: [u8; 20], amount: u128)
         ),
     };
 
-    extrinsic_call_factory::moonbase_alpha_xtokens_transfer_multiasset(
+    extrinsic_call_factory::xtokens_transfer_multiasset(
+        MOONBASEALPHA_INFO
+            .xtokens_pallet_index
+            .expect("xTokens pallet"),
+        XcmVersion::V1,
         alpha_dev_asset,
         dest_location,
+        DEFAULT_XCM_DEST_WEIGHT_LIMIT,
     )
     .expect("Valid extrinsic")
 }
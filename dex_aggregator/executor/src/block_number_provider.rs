@@ -0,0 +1,295 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    chain_info::ChainInfo,
+    common::{Amount, BlockHash, BlockNum, UniversalChainId},
+};
+
+use crate::eth_utils::common::{
+    block_number as eth_block_number, block_number_call as eth_block_number_call,
+    parse_block_number_response as parse_eth_block_number_response,
+};
+use crate::json_rpc_batch::{send_concurrently, JsonRpcCall};
+use crate::rpc_endpoints::{get_rpc_url, RpcEndpoint};
+use crate::substrate_utils::node_rpc_utils::SubstrateNodeRpcUtils;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BlockNumberProviderError {
+    EthRequestFailed,
+    SubstrateRequestFailed,
+}
+
+/// Which block counts as the chain's current one when the executor looks for a step's txn or
+/// events, computes expiry windows, etc.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum FinalityPolicy {
+    Finalized,
+    // Saves the finality lag (a few blocks on Polkadot) per step, but a step can then be
+    // confirmed in a block that gets reorged out. Meant for low-value plans
+    BestBlock,
+}
+
+/// Plans whose quoted value is at most max_plan_usd (in $ x 10^USD_AMOUNT_EXPONENT) use
+/// BestBlock on chain_id
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BestBlockTier {
+    pub chain_id: UniversalChainId,
+    pub max_plan_usd: Amount,
+}
+
+// Finalized unless the chain has a tier and the plan is small enough. Plans without a quote
+// have an unknown value, so they stay on Finalized
+pub fn get_finality_policy(
+    best_block_tiers: &[BestBlockTier],
+    chain_id: &UniversalChainId,
+    plan_usd: Option<Amount>,
+) -> FinalityPolicy {
+    match (
+        best_block_tiers
+            .iter()
+            .find(|tier| tier.chain_id == *chain_id),
+        plan_usd,
+    ) {
+        (Some(tier), Some(plan_usd)) if plan_usd <= tier.max_plan_usd => FinalityPolicy::BestBlock,
+        _ => FinalityPolicy::Finalized,
+    }
+}
+
+// Chooses how to look up the current block number based on the chain type. Substrate chains
+// (including Frontier-based EVM parachains like Moonbeam and Astar) are queried via the Substrate
+// node RPC, while standalone EVM chains only expose eth_blockNumber
+pub enum BlockNumberProvider {
+    SubstrateNode(SubstrateNodeRpcUtils),
+    EthRpc(String /* rpc_url */),
+}
+
+impl BlockNumberProvider {
+    // Uses the admin's RPC endpoint for the chain if there is one (see rpc_endpoints)
+    pub fn from_chain_info(chain_info: &ChainInfo, rpc_endpoints: &[RpcEndpoint]) -> Self {
+        Self::from_chain_info_and_rpc_url(chain_info, get_rpc_url(rpc_endpoints, chain_info))
+    }
+
+    pub fn from_chain_info_and_rpc_url(chain_info: &ChainInfo, rpc_url: String) -> Self {
+        if chain_info.chain_id.is_substrate() {
+            Self::SubstrateNode(SubstrateNodeRpcUtils { rpc_url })
+        } else {
+            Self::EthRpc(rpc_url)
+        }
+    }
+
+    pub fn get_finalized_block_number(&self) -> Result<BlockNum, BlockNumberProviderError> {
+        self.get_block_number(FinalityPolicy::Finalized)
+    }
+
+    // Note that for standalone EVM chains this is always the latest (not finalized) block. We
+    // only use it to compute txn expiry windows, so being a few blocks ahead is fine
+    pub fn get_block_number(
+        &self,
+        finality: FinalityPolicy,
+    ) -> Result<BlockNum, BlockNumberProviderError> {
+        match (self, finality) {
+            (Self::SubstrateNode(subutils), FinalityPolicy::Finalized) => subutils
+                .get_finalized_block_number()
+                .map_err(|_| BlockNumberProviderError::SubstrateRequestFailed),
+            (Self::SubstrateNode(subutils), FinalityPolicy::BestBlock) => subutils
+                .get_best_block_number()
+                .map_err(|_| BlockNumberProviderError::SubstrateRequestFailed),
+            (Self::EthRpc(rpc_url), _) => {
+                eth_block_number(rpc_url).map_err(|_| BlockNumberProviderError::EthRequestFailed)
+            }
+        }
+    }
+
+    // Same as get_block_number for each provider, but the chains are queried concurrently
+    // rather than one after the other. A Substrate chain's finalized block still takes two
+    // rounds (its finalized head, then the number at it), but each round covers all the chains
+    pub fn get_block_numbers(
+        providers: &[(Self, FinalityPolicy)],
+    ) -> Vec<Result<BlockNum, BlockNumberProviderError>> {
+        let first_calls: Vec<(&str, JsonRpcCall)> = providers
+            .iter()
+            .map(|(provider, finality)| match (provider, finality) {
+                (Self::SubstrateNode(subutils), FinalityPolicy::Finalized) => (
+                    subutils.rpc_url.as_str(),
+                    SubstrateNodeRpcUtils::finalized_head_call(),
+                ),
+                (Self::SubstrateNode(subutils), FinalityPolicy::BestBlock) => (
+                    subutils.rpc_url.as_str(),
+                    SubstrateNodeRpcUtils::block_number_call(None),
+                ),
+                (Self::EthRpc(rpc_url), _) => (rpc_url.as_str(), eth_block_number_call()),
+            })
+            .collect();
+        let mut block_numbers = Vec::new();
+        let mut finalized_heads: Vec<(usize, &str, BlockHash)> = Vec::new();
+        for (index, ((provider, finality), resp_body)) in providers
+            .iter()
+            .zip(send_concurrently(&first_calls))
+            .enumerate()
+        {
+            let resp_body = resp_body.ok();
+            block_numbers.push(match (provider, finality) {
+                (Self::SubstrateNode(subutils), FinalityPolicy::Finalized) => {
+                    match resp_body.and_then(|resp_body| {
+                        SubstrateNodeRpcUtils::parse_block_hash_response(&resp_body).ok()
+                    }) {
+                        Some(finalized_head) => {
+                            finalized_heads.push((
+                                index,
+                                subutils.rpc_url.as_str(),
+                                finalized_head,
+                            ));
+                            // Replaced once the second round is back
+                            Err(BlockNumberProviderError::SubstrateRequestFailed)
+                        }
+                        None => Err(BlockNumberProviderError::SubstrateRequestFailed),
+                    }
+                }
+                (Self::SubstrateNode(_), FinalityPolicy::BestBlock) => resp_body
+                    .and_then(|resp_body| {
+                        SubstrateNodeRpcUtils::parse_block_number_response(&resp_body).ok()
+                    })
+                    .ok_or(BlockNumberProviderError::SubstrateRequestFailed),
+                (Self::EthRpc(_), _) => resp_body
+                    .and_then(|resp_body| parse_eth_block_number_response(&resp_body).ok())
+                    .ok_or(BlockNumberProviderError::EthRequestFailed),
+            });
+        }
+
+        let second_calls: Vec<(&str, JsonRpcCall)> = finalized_heads
+            .iter()
+            .map(|(_, rpc_url, finalized_head)| {
+                (
+                    *rpc_url,
+                    SubstrateNodeRpcUtils::block_number_call(Some(finalized_head)),
+                )
+            })
+            .collect();
+        for ((index, _, _), resp_body) in
+            finalized_heads.iter().zip(send_concurrently(&second_calls))
+        {
+            block_numbers[*index] = resp_body
+                .ok()
+                .and_then(|resp_body| {
+                    SubstrateNodeRpcUtils::parse_block_number_response(&resp_body).ok()
+                })
+                .ok_or(BlockNumberProviderError::SubstrateRequestFailed);
+        }
+        block_numbers
+    }
+}
+
+#[cfg(test)]
+mod block_number_provider_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::{
+        chain_info_registry,
+        universal_chain_id_registry::{MOONBEAM, POLKADOT},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finality_policy_by_chain_and_value() {
+        let tiers = vec![BestBlockTier {
+            chain_id: POLKADOT,
+            max_plan_usd: 100,
+        }];
+        assert_eq!(
+            get_finality_policy(&tiers, &POLKADOT, Some(100)),
+            FinalityPolicy::BestBlock
+        );
+        assert_eq!(
+            get_finality_policy(&tiers, &POLKADOT, Some(101)),
+            FinalityPolicy::Finalized
+        );
+        assert_eq!(
+            get_finality_policy(&tiers, &POLKADOT, None),
+            FinalityPolicy::Finalized
+        );
+        assert_eq!(
+            get_finality_policy(&tiers, &MOONBEAM, Some(1)),
+            FinalityPolicy::Finalized
+        );
+    }
+
+    #[test]
+    fn test_provider_keyed_by_chain_type() {
+        assert!(matches!(
+            BlockNumberProvider::from_chain_info(&chain_info_registry::MOONBEAM_INFO, &[]),
+            BlockNumberProvider::SubstrateNode(_)
+        ));
+        assert!(matches!(
+            BlockNumberProvider::from_chain_info(&chain_info_registry::POLKADOT_INFO, &[]),
+            BlockNumberProvider::SubstrateNode(_)
+        ));
+        assert!(matches!(
+            BlockNumberProvider::from_chain_info(&chain_info_registry::ETHEREUM_INFO, &[]),
+            BlockNumberProvider::EthRpc(_)
+        ));
+    }
+
+    #[test]
+    fn test_get_block_numbers() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let block_numbers = BlockNumberProvider::get_block_numbers(&[
+            (
+                BlockNumberProvider::from_chain_info(&chain_info_registry::MOONBEAM_INFO, &[]),
+                FinalityPolicy::Finalized,
+            ),
+            (
+                BlockNumberProvider::from_chain_info(&chain_info_registry::POLKADOT_INFO, &[]),
+                FinalityPolicy::BestBlock,
+            ),
+            (
+                BlockNumberProvider::from_chain_info(&chain_info_registry::ARBITRUM_INFO, &[]),
+                FinalityPolicy::Finalized,
+            ),
+        ]);
+        assert_eq!(block_numbers.len(), 3);
+        ink::env::debug_println!("Moonbeam, Polkadot, Arbitrum: {:?}", block_numbers);
+        assert!(block_numbers
+            .iter()
+            .all(|block_number| block_number.is_ok()));
+    }
+
+    #[test]
+    fn test_get_block_number() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let moonbeam_block =
+            BlockNumberProvider::from_chain_info(&chain_info_registry::MOONBEAM_INFO, &[])
+                .get_finalized_block_number()
+                .expect("Moonbeam block number lookup should succeed");
+        let arbitrum_block =
+            BlockNumberProvider::from_chain_info(&chain_info_registry::ARBITRUM_INFO, &[])
+                .get_finalized_block_number()
+                .expect("Arbitrum block number lookup should succeed");
+        ink::env::debug_println!("Moonbeam: {}, Arbitrum: {}", moonbeam_block, arbitrum_block);
+    }
+}
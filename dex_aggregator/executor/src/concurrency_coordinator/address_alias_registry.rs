@@ -0,0 +1,164 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+
+use privadex_chain_metadata::common::{EthAddress, MillisSinceEpoch};
+use privadex_common::utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError};
+
+use super::{
+    deserialize_helper::{AddressAliasResponse, OptionalItemWrapper},
+    dynamodb_request_factory::DynamoDbAddressAliasRequestFactory,
+};
+
+const DYNAMODB_TABLE_ADDRESS_ALIAS: &'static str = "privadex_phat_contract";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AddressAliasRegistryError {
+    ConditionalCheckFailed,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for AddressAliasRegistryError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, AddressAliasRegistryError>;
+
+/// Address book of human-readable aliases (e.g. an exchange's deposit address) that
+/// start_swap accepts in place of a destination address. Aliases are expected to be
+/// normalized by the caller (see name_resolver::normalize_name)
+pub struct AddressAliasRegistry {
+    api: DynamoDbApi,
+    request_factory: DynamoDbAddressAliasRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl AddressAliasRegistry {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbAddressAliasRequestFactory {
+                table_name: DYNAMODB_TABLE_ADDRESS_ALIAS,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    pub fn set_alias(&self, alias: &str, address: &EthAddress) -> Result<()> {
+        let request_payload =
+            self.request_factory
+                .set_alias_request(alias, address, self.millis_since_epoch);
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| AddressAliasRegistryError::from(dynamodb_err))?;
+        Ok(())
+    }
+
+    pub fn get_alias(&self, alias: &str) -> Result<Option<EthAddress>> {
+        let request_payload = self.request_factory.get_alias_request(alias);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| AddressAliasRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<AddressAliasResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| AddressAliasRegistryError::UnexpectedDeserializationError)?;
+        decoded
+            .Item
+            .map(|item| {
+                let address: [u8; 20] = item
+                    .ResolvedAddress
+                    .S
+                    .try_into()
+                    .map_err(|_| AddressAliasRegistryError::UnexpectedDeserializationError)?;
+                Ok(EthAddress::from(address))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(feature = "dynamodb-live-test")]
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod address_alias_registry_tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    fn now_millis() -> u64 {
+        use std::time::SystemTime;
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+            .try_into()
+            .unwrap()
+    }
+
+    fn address_alias_registry() -> AddressAliasRegistry {
+        let dynamodb_access_key =
+            std::env::var("DYNAMODB_ACCESS_KEY").expect("Env var DYNAMODB_ACCESS_KEY is not set");
+        let dynamodb_secret_key =
+            std::env::var("DYNAMODB_SECRET_KEY").expect("Env var DYNAMODB_SECRET_KEY is not set");
+        AddressAliasRegistry::new(dynamodb_access_key, dynamodb_secret_key, now_millis())
+    }
+
+    #[test]
+    fn test_set_and_get_alias() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let address = EthAddress {
+            0: hex!("42B7D766824422F499F84703eC4E2abb273171cF"),
+        };
+        let registry = address_alias_registry();
+        registry
+            .set_alias("privadex-test", &address)
+            .expect("Database update failed");
+        assert_eq!(
+            registry
+                .get_alias("privadex-test")
+                .expect("Database request failed"),
+            Some(address)
+        );
+        assert_eq!(
+            registry
+                .get_alias("privadex-test-missing")
+                .expect("Database request failed"),
+            None
+        );
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+
+use privadex_common::utils::general_utils::hex_string_to_vec;
+
+// Binary values (signatures and partial signatures) are stored as base64 of a version byte
+// followed by the payload, instead of 0x-prefixed hex. That is a third smaller, which adds up on
+// the execplans item since it holds two signatures per plan.
+// Bump the version if the payload's encoding ever changes, and keep decoding the old ones until
+// no item holds them anymore
+pub(super) const COMPACT_VALUE_VERSION: u8 = 1;
+// DynamoDB rejects writes that grow an item past 400 KB. The execplans item is the one that
+// grows with load, so we warn well before it gets there
+pub(super) const ITEM_SIZE_WARNING_BYTES: usize = 300_000;
+
+// Version 1 always encodes to a string that starts with 'A', so it can't be mistaken for hex
+pub(super) fn encode_compact_value(payload: &[u8]) -> String {
+    let mut bytes = Vec::with_capacity(payload.len() + 1);
+    bytes.push(COMPACT_VALUE_VERSION);
+    bytes.extend_from_slice(payload);
+    base64::encode(bytes)
+}
+
+// Also reads the 0x-prefixed hex that values were stored as before, so items written by an
+// older deployment stay readable. None for unknown versions
+pub(super) fn decode_compact_value(value_str: &str) -> Option<Vec<u8>> {
+    if value_str.starts_with("0x") {
+        return hex_string_to_vec(value_str).ok();
+    }
+    let bytes = base64::decode(value_str).ok()?;
+    match bytes.split_first() {
+        Some((&COMPACT_VALUE_VERSION, payload)) => Some(payload.to_vec()),
+        _ => None,
+    }
+}
+
+// The GetItem response is a bit larger than the item itself, which is fine for a warning
+pub(super) fn check_item_size(item_key: &str, response: &[u8]) {
+    if response.len() >= ITEM_SIZE_WARNING_BYTES {
+        ink::env::debug_println!(
+            "DynamoDB item {} is {} bytes, close to the 400 KB item limit",
+            item_key,
+            response.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod compact_value_tests {
+    use ink::prelude::vec;
+
+    use privadex_common::utils::general_utils::slice_to_hex_string;
+
+    use super::*;
+
+    #[test]
+    fn test_compact_value_roundtrip() {
+        let payload = vec![0xab; 96];
+        let value_str = encode_compact_value(&payload);
+        assert!(value_str.starts_with('A'));
+        assert!(value_str.len() < slice_to_hex_string(&payload).len());
+        assert_eq!(decode_compact_value(&value_str), Some(payload));
+        assert_eq!(
+            decode_compact_value(&encode_compact_value(&[])),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_decode_legacy_hex() {
+        assert_eq!(decode_compact_value("0xabcd"), Some(vec![0xab, 0xcd]));
+        assert_eq!(decode_compact_value("0xzz"), None);
+    }
+
+    #[test]
+    fn test_decode_unknown_version() {
+        assert_eq!(decode_compact_value(&base64::encode([2u8, 0xab])), None);
+        assert_eq!(decode_compact_value(""), None);
+        assert_eq!(decode_compact_value("not base64!"), None);
+    }
+}
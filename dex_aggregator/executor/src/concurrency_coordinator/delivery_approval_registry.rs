@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::{
+    utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+    uuid::Uuid,
+};
+
+use super::{
+    deserialize_helper::{DeliveryApprovalResponse, OptionalItemWrapper},
+    dynamodb_request_factory::DynamoDbDeliveryApprovalRequestFactory,
+};
+
+const DYNAMODB_TABLE_DELIVERY_APPROVAL: &'static str = "privadex_phat_contract";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DeliveryApprovalRegistryError {
+    AlreadyApproved,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for DeliveryApprovalRegistryError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::AlreadyApproved,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, DeliveryApprovalRegistryError>;
+
+/// Co-signatures on the final escrow to user transfer of high-value ExecutionPlans. The
+/// worker stepping a plan forward only broadcasts that transfer once an approver (other than
+/// the worker itself) recorded its approval here. An approval is never overwritten, so the
+/// first approver stays on record
+pub struct DeliveryApprovalRegistry {
+    api: DynamoDbApi,
+    request_factory: DynamoDbDeliveryApprovalRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl DeliveryApprovalRegistry {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbDeliveryApprovalRequestFactory {
+                table_name: DYNAMODB_TABLE_DELIVERY_APPROVAL,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    pub fn approve(&self, exec_plan_uuid: &Uuid, approver: &[u8; 32]) -> Result<()> {
+        let request_payload = self.request_factory.approve_delivery_request(
+            exec_plan_uuid,
+            approver,
+            self.millis_since_epoch,
+        );
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| DeliveryApprovalRegistryError::from(dynamodb_err))?;
+        Ok(())
+    }
+
+    // Returns the approver, if the plan's delivery was approved
+    pub fn get_approver(&self, exec_plan_uuid: &Uuid) -> Result<Option<[u8; 32]>> {
+        let request_payload = self
+            .request_factory
+            .get_delivery_approval_request(exec_plan_uuid);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| DeliveryApprovalRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<DeliveryApprovalResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| DeliveryApprovalRegistryError::UnexpectedDeserializationError)?;
+        match decoded.Item {
+            Some(item) => {
+                let approver: [u8; 32] =
+                    item.Approver.S.try_into().map_err(|_| {
+                        DeliveryApprovalRegistryError::UnexpectedDeserializationError
+                    })?;
+                Ok(Some(approver))
+            }
+            None => Ok(None),
+        }
+    }
+}
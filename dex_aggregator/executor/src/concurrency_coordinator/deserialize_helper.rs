@@ -17,11 +17,17 @@
  */
 
 use core::fmt;
-use ink_prelude::vec::Vec;
+use ink::prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use serde::{de, Deserialize, Deserializer};
 
+use privadex_chain_metadata::common::{Amount, MillisSinceEpoch};
 use privadex_common::{utils::general_utils::hex_string_to_vec, uuid::Uuid};
 
+use super::{compact_value::decode_compact_value, worker_identity::WorkerSignature};
+
 #[derive(Deserialize, Debug, PartialEq)]
 #[allow(non_snake_case)]
 pub(super) struct Empty {}
@@ -32,6 +38,13 @@ pub(super) struct ItemWrapper<T> {
     pub Item: T,
 }
 
+// GetItem responds with {} if the item does not exist
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct OptionalItemWrapper<T> {
+    pub Item: Option<T>,
+}
+
 #[derive(Deserialize, Debug, PartialEq)]
 #[allow(non_snake_case)]
 pub(super) struct AttributesWrapper<T> {
@@ -57,8 +70,47 @@ pub(super) struct ExecPlanIdsWrapper {
     pub Plans: StringSet,
 }
 
+// NextPollAfterEpochMillis and LastWorker are missing until the first plan is unclaimed (see
+// the README)
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct ExecPlanIdsWithPollScheduleWrapper {
+    pub Plans: StringSet,
+    pub NextPollAfterEpochMillis: Option<MapWrapper<ExecPlanMillis>>,
+    pub LastWorker: Option<MapWrapper<ExecPlanWorkers>>,
+    pub ReleaseSig: Option<MapWrapper<ExecPlanSignatures>>,
+}
+
+// The claim state of every active plan. ClaimedBy is missing until a worker first claims a plan
+// (see the README), and admin claims leave it unset
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct ExecPlanClaimsWrapper {
+    pub Plans: StringSet,
+    pub WorkerIsAllocated: Option<MapWrapper<ExecPlanAllocations>>,
+    pub WorkerAssignmentUpdateEpochMillis: Option<MapWrapper<ExecPlanMillis>>,
+    pub ClaimedBy: Option<MapWrapper<ExecPlanWorkers>>,
+    pub LastWorker: Option<MapWrapper<ExecPlanWorkers>>,
+    pub NextPollAfterEpochMillis: Option<MapWrapper<ExecPlanMillis>>,
+    pub ClaimSig: Option<MapWrapper<ExecPlanSignatures>>,
+    pub ReleaseSig: Option<MapWrapper<ExecPlanSignatures>>,
+}
+
+// Draining is missing until drain mode is first set
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct DrainingResponse {
+    pub Draining: Option<BoolWrapper>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct BoolWrapper {
+    pub BOOL: bool,
+}
+
 #[derive(Deserialize, Debug, PartialEq)]
-#[serde(bound(deserialize = "ink_prelude::vec::Vec<UuidContainer>: Deserialize<'de>"))]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<UuidContainer>: Deserialize<'de>"))]
 #[allow(non_snake_case)]
 pub(super) struct StringSet {
     pub SS: Vec<UuidContainer>,
@@ -91,6 +143,138 @@ pub(super) struct PendingNonceBlockResponse {
     pub ExecStepPendingNonce: MapWrapper<UnknownSingleKeyToNumWrapper>,
 }
 
+// An ExecutionStep's existing assignment with its signature, which is missing if the
+// reservation was not signed (see the README)
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct SignedPendingNonceResponse {
+    pub ExecStepPendingBlockAdded: MapWrapper<UnknownSingleKeyToNumWrapper>,
+    pub ExecStepPendingNonce: MapWrapper<UnknownSingleKeyToNumWrapper>,
+    pub ExecStepNonceSig: Option<MapWrapper<UnknownKeysToSignatureWrapper>>,
+}
+
+// A chain's whole nonce state, as returned by the seal request (see migrate_reservations)
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct NonceStateResponse {
+    pub DroppedNonces: NumListWrapper,
+    pub ExecStepPendingBlockAdded: MapWrapper<UnknownKeysToNumWrapper>,
+    pub ExecStepPendingNonce: MapWrapper<UnknownKeysToNumWrapper>,
+    pub ExecStepNonceSig: Option<MapWrapper<UnknownKeysToSignatureWrapper>>,
+    pub NextNonce: NumWrapper,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<NumWrapper>: Deserialize<'de>"))]
+#[allow(non_snake_case)]
+pub(super) struct NumListWrapper {
+    pub L: Vec<NumWrapper>,
+}
+
+// Partial signatures of the (up to 3) threshold parties. Parties that have not signed yet
+// are missing from the item
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct ThresholdPartialsResponse {
+    pub Partial0: Option<CompactBytesWrapper>,
+    pub Partial1: Option<CompactBytesWrapper>,
+    pub Partial2: Option<CompactBytesWrapper>,
+}
+
+// The txn that the parties sign in a threshold round (see ThresholdRoundTxn)
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct ThresholdRoundResponse {
+    pub RoundTxn: CompactBytesWrapper,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct AddressAliasResponse {
+    pub ResolvedAddress: HexBytesWrapper,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct PlanTagResponse {
+    pub ExecPlanUuid: UuidWrapper,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct DeliveryApprovalResponse {
+    pub Approver: HexBytesWrapper,
+}
+
+// A worker's counters are missing until it first advances a step or claims
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct RelayerContributionResponse {
+    pub NumStepsAdvanced: Option<NumWrapper>,
+    pub GasFeeUsd: Option<AmountWrapper>,
+    pub ClaimedUsd: Option<AmountWrapper>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct RelayerFeePoolResponse {
+    pub FeesAvailable: Option<AmountWrapper>,
+}
+
+// Missing until the campaign's first plan
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct SponsorshipResponse {
+    pub SpentUsd: Option<AmountWrapper>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct SponsoredPlanResponse {
+    pub DrawnUsd: AmountWrapper,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct UuidWrapper {
+    pub S: UuidContainer,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct HexBytesWrapper {
+    #[serde(deserialize_with = "str_to_vec")]
+    pub S: Vec<u8>,
+}
+
+// A compact value (see compact_value), or a hex string written before those
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct CompactBytesWrapper {
+    #[serde(deserialize_with = "compact_str_to_vec")]
+    pub S: Vec<u8>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+struct StrWrapper<'a> {
+    pub S: &'a str,
+}
+
+fn str_to_vec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<Vec<u8>, D::Error> {
+    let raw_string = <&str>::deserialize(deserializer)?;
+    hex_string_to_vec(raw_string).map_err(|_| de::Error::custom("Invalid hex string"))
+}
+
+fn compact_str_to_vec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<Vec<u8>, D::Error> {
+    let raw_string = <&str>::deserialize(deserializer)?;
+    decode_compact_value(raw_string).ok_or_else(|| de::Error::custom("Invalid compact value"))
+}
+
 fn quoted_str_to_u32<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> core::result::Result<u32, D::Error> {
@@ -101,6 +285,319 @@ fn quoted_str_to_u32<'de, D: Deserializer<'de>>(
     Ok(num)
 }
 
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct MillisWrapper {
+    #[serde(deserialize_with = "quoted_str_to_u64")]
+    pub N: MillisSinceEpoch,
+}
+
+fn quoted_str_to_u64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<u64, D::Error> {
+    let string = <&str>::deserialize(deserializer)?;
+    let num: u64 = string
+        .parse()
+        .map_err(|_| de::Error::custom("String to u64 failed"))?;
+    Ok(num)
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub(super) struct AmountWrapper {
+    #[serde(deserialize_with = "quoted_str_to_u128")]
+    pub N: Amount,
+}
+
+fn quoted_str_to_u128<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<u128, D::Error> {
+    let string = <&str>::deserialize(deserializer)?;
+    let num: u128 = string
+        .parse()
+        .map_err(|_| de::Error::custom("String to u128 failed"))?;
+    Ok(num)
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execplan_0x0101...\":{\"N\":\"1000\"},\"execplan_0x0202...\":{\"N\":\"2000\"}}"
+// i.e. a timestamp (e.g. the next poll or the last claim) keyed by the ExecutionPlan attribute.
+// Like UnknownSingleKeyToNumWrapper, this needs a custom deserializer since the keys are only
+// known at runtime
+pub(super) struct ExecPlanMillis {
+    pub millis: Vec<(Uuid, MillisSinceEpoch)>,
+}
+
+impl<'de> Deserialize<'de> for ExecPlanMillis {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExecPlanMillisVisitor;
+
+        impl<'de> de::Visitor<'de> for ExecPlanMillisVisitor {
+            type Value = ExecPlanMillis;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct ExecPlanMillis")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ExecPlanMillis, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut millis = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: MillisWrapper = map.next_value()?;
+                    millis.push((exec_plan_attr_to_uuid(key)?, val.N));
+                }
+                Ok(ExecPlanMillis { millis })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["millis"];
+        deserializer.deserialize_struct("ExecPlanMillis", FIELDS, ExecPlanMillisVisitor)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execplan_0x0101...\":{\"S\":\"0x0303...\"}}"
+// i.e. a worker (e.g. the last one to step it forward) keyed like ExecPlanMillis
+pub(super) struct ExecPlanWorkers {
+    pub workers: Vec<(Uuid, [u8; 32])>,
+}
+
+impl<'de> Deserialize<'de> for ExecPlanWorkers {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExecPlanWorkersVisitor;
+
+        impl<'de> de::Visitor<'de> for ExecPlanWorkersVisitor {
+            type Value = ExecPlanWorkers;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct ExecPlanWorkers")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ExecPlanWorkers, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut workers = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: HexBytesWrapper = map.next_value()?;
+                    let worker: [u8; 32] = val
+                        .S
+                        .try_into()
+                        .map_err(|_| de::Error::custom("Worker hex str length is incorrect"))?;
+                    workers.push((exec_plan_attr_to_uuid(key)?, worker));
+                }
+                Ok(ExecPlanWorkers { workers })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["workers"];
+        deserializer.deserialize_struct("ExecPlanWorkers", FIELDS, ExecPlanWorkersVisitor)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execplan_0x0101...\":{\"S\":\"0x0303...\"}}"
+// i.e. the signature over a claim or release (see worker_identity), keyed like ExecPlanMillis.
+// Signatures that don't decode are skipped rather than failing the whole map, since anyone
+// with write access can put anything there
+pub(super) struct ExecPlanSignatures {
+    pub signatures: Vec<(Uuid, WorkerSignature)>,
+}
+
+impl<'de> Deserialize<'de> for ExecPlanSignatures {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExecPlanSignaturesVisitor;
+
+        impl<'de> de::Visitor<'de> for ExecPlanSignaturesVisitor {
+            type Value = ExecPlanSignatures;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct ExecPlanSignatures")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ExecPlanSignatures, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut signatures = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: StrWrapper = map.next_value()?;
+                    if let Some(signature) = WorkerSignature::from_compact_string(val.S) {
+                        signatures.push((exec_plan_attr_to_uuid(key)?, signature));
+                    }
+                }
+                Ok(ExecPlanSignatures { signatures })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["signatures"];
+        deserializer.deserialize_struct("ExecPlanSignatures", FIELDS, ExecPlanSignaturesVisitor)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execplan_0x0101...\":{\"BOOL\":true}}"
+// i.e. whether each ExecutionPlan is claimed, keyed like ExecPlanMillis
+pub(super) struct ExecPlanAllocations {
+    pub is_allocated: Vec<(Uuid, bool)>,
+}
+
+impl<'de> Deserialize<'de> for ExecPlanAllocations {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExecPlanAllocationsVisitor;
+
+        impl<'de> de::Visitor<'de> for ExecPlanAllocationsVisitor {
+            type Value = ExecPlanAllocations;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct ExecPlanAllocations")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ExecPlanAllocations, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut is_allocated = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: BoolWrapper = map.next_value()?;
+                    is_allocated.push((exec_plan_attr_to_uuid(key)?, val.BOOL));
+                }
+                Ok(ExecPlanAllocations { is_allocated })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["is_allocated"];
+        deserializer.deserialize_struct("ExecPlanAllocations", FIELDS, ExecPlanAllocationsVisitor)
+    }
+}
+
+fn exec_plan_attr_to_uuid<E: de::Error>(key: &str) -> core::result::Result<Uuid, E> {
+    let hex_uuid = key
+        .strip_prefix("execplan_")
+        .ok_or_else(|| E::custom("Unexpected ExecutionPlan attribute"))?;
+    let uuid: [u8; 16] = hex_string_to_vec(hex_uuid)
+        .map_err(|_| E::custom("Invalid hex string for UUID"))?
+        .try_into()
+        .map_err(|_| E::custom("UUID hex str length is incorrect"))?;
+    Ok(Uuid::new(uuid))
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execstep_0x0101...\":{\"N\":\"51\"},\"noncepool_0x0202...\":{\"N\":\"52\"}}"
+// i.e. UnknownSingleKeyToNumWrapper but with any number of keys, which we keep so that the
+// map can be written back as is
+pub(super) struct UnknownKeysToNumWrapper {
+    pub entries: Vec<(String, u32)>,
+}
+
+impl<'de> Deserialize<'de> for UnknownKeysToNumWrapper {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct UnknownKeysToNumWrapperVisitor;
+
+        impl<'de> de::Visitor<'de> for UnknownKeysToNumWrapperVisitor {
+            type Value = UnknownKeysToNumWrapper;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct UnknownKeysToNumWrapper")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> core::result::Result<UnknownKeysToNumWrapper, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: NumWrapper = map.next_value()?;
+                    entries.push((key.to_string(), val.N));
+                }
+                Ok(UnknownKeysToNumWrapper { entries })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["entries"];
+        deserializer.deserialize_struct(
+            "UnknownKeysToNumWrapper",
+            FIELDS,
+            UnknownKeysToNumWrapperVisitor,
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+// Used to parse a json of the form
+// "{\"execstep_0x0101...\":{\"S\":\"0x0303...\"}}"
+// i.e. UnknownKeysToNumWrapper for nonce reservation signatures. Like ExecPlanSignatures,
+// signatures that don't decode are skipped
+pub(super) struct UnknownKeysToSignatureWrapper {
+    pub entries: Vec<(String, WorkerSignature)>,
+}
+
+impl<'de> Deserialize<'de> for UnknownKeysToSignatureWrapper {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct UnknownKeysToSignatureWrapperVisitor;
+
+        impl<'de> de::Visitor<'de> for UnknownKeysToSignatureWrapperVisitor {
+            type Value = UnknownKeysToSignatureWrapper;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct UnknownKeysToSignatureWrapper")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> core::result::Result<UnknownKeysToSignatureWrapper, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(key) = map.next_key::<&str>()? {
+                    let val: StrWrapper = map.next_value()?;
+                    if let Some(signature) = WorkerSignature::from_compact_string(val.S) {
+                        entries.push((key.to_string(), signature));
+                    }
+                }
+                Ok(UnknownKeysToSignatureWrapper { entries })
+            }
+        }
+
+        const FIELDS: &'static [&'static str] = &["entries"];
+        deserializer.deserialize_struct(
+            "UnknownKeysToSignatureWrapper",
+            FIELDS,
+            UnknownKeysToSignatureWrapperVisitor,
+        )
+    }
+}
+
 #[derive(Debug, PartialEq)]
 // Used to parse a json of the form "{\"unknown-key\":{\"N\":\"51\"}}"
 // This requires custom deserialization because we cannot use HashMap in no_std
@@ -152,9 +649,13 @@ impl<'de> Deserialize<'de> for UnknownSingleKeyToNumWrapper {
 
 #[cfg(test)]
 mod deserialize_helper_tests {
-    use ink_prelude::vec;
+    use ink::prelude::{format, vec};
+    use scale::Encode;
+
+    use privadex_common::utils::general_utils::slice_to_hex_string;
 
     use super::*;
+    use crate::concurrency_coordinator::compact_value::encode_compact_value;
 
     #[test]
     fn test_execution_plan_deserialization() {
@@ -178,6 +679,133 @@ mod deserialize_helper_tests {
         );
     }
 
+    #[test]
+    fn test_execution_plan_poll_schedule_deserialization() {
+        let get_exec_plan_ids_response = "{\"Item\":{\"Plans\":{\"SS\":[\"0x01010101010101010101010101010101\",\"0x02020202020202020202020202020202\"]},\"NextPollAfterEpochMillis\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"N\":\"1690000012000\"}}}}}";
+        let (decoded, _): (ItemWrapper<ExecPlanIdsWithPollScheduleWrapper>, usize) =
+            serde_json_core::from_slice(get_exec_plan_ids_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(decoded.Item.Plans.SS.len(), 2);
+        assert_eq!(
+            decoded.Item.NextPollAfterEpochMillis,
+            Some(MapWrapper {
+                M: ExecPlanMillis {
+                    millis: vec![(Uuid::new([1u8; 16]), 1_690_000_012_000)]
+                }
+            })
+        );
+
+        // Before any plan has been unclaimed since the upgrade
+        let get_exec_plan_ids_response_no_schedule =
+            "{\"Item\":{\"Plans\":{\"SS\":[\"0x01010101010101010101010101010101\"]}}}";
+        let (decoded_no_schedule, _): (ItemWrapper<ExecPlanIdsWithPollScheduleWrapper>, usize) =
+            serde_json_core::from_slice(get_exec_plan_ids_response_no_schedule.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(decoded_no_schedule.Item.NextPollAfterEpochMillis, None);
+        assert_eq!(decoded_no_schedule.Item.LastWorker, None);
+    }
+
+    #[test]
+    fn test_execution_plan_last_worker_deserialization() {
+        let get_exec_plan_ids_response = "{\"Item\":{\"Plans\":{\"SS\":[\"0x01010101010101010101010101010101\"]},\"NextPollAfterEpochMillis\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"N\":\"1690000012000\"}}},\"LastWorker\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"S\":\"0x0303030303030303030303030303030303030303030303030303030303030303\"}}}}}";
+        let (decoded, _): (ItemWrapper<ExecPlanIdsWithPollScheduleWrapper>, usize) =
+            serde_json_core::from_slice(get_exec_plan_ids_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.LastWorker,
+            Some(MapWrapper {
+                M: ExecPlanWorkers {
+                    workers: vec![(Uuid::new([1u8; 16]), [3u8; 32])]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_execution_plan_claims_deserialization() {
+        let get_claims_response = "{\"Item\":{\"Plans\":{\"SS\":[\"0x01010101010101010101010101010101\",\"0x02020202020202020202020202020202\"]},\"WorkerIsAllocated\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"BOOL\":true},\"execplan_0x02020202020202020202020202020202\":{\"BOOL\":false}}},\"WorkerAssignmentUpdateEpochMillis\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"N\":\"1690000012000\"},\"execplan_0x02020202020202020202020202020202\":{\"N\":\"1690000000000\"}}},\"ClaimedBy\":{\"M\":{\"execplan_0x01010101010101010101010101010101\":{\"S\":\"0x0303030303030303030303030303030303030303030303030303030303030303\"}}}}}";
+        let (decoded, _): (ItemWrapper<ExecPlanClaimsWrapper>, usize) =
+            serde_json_core::from_slice(get_claims_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.WorkerIsAllocated,
+            Some(MapWrapper {
+                M: ExecPlanAllocations {
+                    is_allocated: vec![(Uuid::new([1u8; 16]), true), (Uuid::new([2u8; 16]), false)]
+                }
+            })
+        );
+        assert_eq!(
+            decoded.Item.ClaimedBy,
+            Some(MapWrapper {
+                M: ExecPlanWorkers {
+                    workers: vec![(Uuid::new([1u8; 16]), [3u8; 32])]
+                }
+            })
+        );
+        assert_eq!(decoded.Item.LastWorker, None);
+    }
+
+    #[test]
+    fn test_signature_deserialization() {
+        let signature = WorkerSignature {
+            signer: [3u8; 32],
+            signature: vec![0xab; 64],
+        };
+        // The second plan's signature is malformed, so it is as good as missing
+        let get_claims_response = format!("{{\"Item\":{{\"Plans\":{{\"SS\":[\"0x01010101010101010101010101010101\",\"0x02020202020202020202020202020202\"]}},\"ClaimSig\":{{\"M\":{{\"execplan_0x01010101010101010101010101010101\":{{\"S\":\"{}\"}},\"execplan_0x02020202020202020202020202020202\":{{\"S\":\"0x1234\"}}}}}}}}}}", signature.to_compact_string());
+        let (decoded, _): (ItemWrapper<ExecPlanClaimsWrapper>, usize) =
+            serde_json_core::from_slice(get_claims_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.ClaimSig,
+            Some(MapWrapper {
+                M: ExecPlanSignatures {
+                    signatures: vec![(Uuid::new([1u8; 16]), signature.clone())]
+                }
+            })
+        );
+        assert_eq!(decoded.Item.ReleaseSig, None);
+
+        // Written before signatures were stored as compact values
+        let get_block_nonce_response = format!("{{\"Item\":{{\"ExecStepPendingNonce\":{{\"M\":{{\"execstep_0xcase3\":{{\"N\":\"51\"}}}}}},\"ExecStepPendingBlockAdded\":{{\"M\":{{\"execstep_0xcase3\":{{\"N\":\"1001\"}}}}}},\"ExecStepNonceSig\":{{\"M\":{{\"execstep_0xcase3\":{{\"S\":\"{}\"}}}}}}}}}}", slice_to_hex_string(&signature.encode()));
+        let (decoded, _): (ItemWrapper<SignedPendingNonceResponse>, usize) =
+            serde_json_core::from_slice(get_block_nonce_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(decoded.Item.ExecStepPendingNonce.M.num.N, 51);
+        assert_eq!(
+            decoded.Item.ExecStepNonceSig,
+            Some(MapWrapper {
+                M: UnknownKeysToSignatureWrapper {
+                    entries: vec![("execstep_0xcase3".to_string(), signature)]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_draining_deserialization() {
+        let get_draining_response = "{\"Item\":{\"Draining\":{\"BOOL\":true}}}";
+        let (decoded, _): (OptionalItemWrapper<DrainingResponse>, usize) =
+            serde_json_core::from_slice(get_draining_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item,
+            Some(DrainingResponse {
+                Draining: Some(BoolWrapper { BOOL: true })
+            })
+        );
+
+        let get_draining_response_unset = "{\"Item\":{}}";
+        let (decoded_unset, _): (OptionalItemWrapper<DrainingResponse>, usize) =
+            serde_json_core::from_slice(get_draining_response_unset.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded_unset.Item,
+            Some(DrainingResponse { Draining: None })
+        );
+    }
+
     #[test]
     fn test_nonce_deserialization() {
         pink_extension_runtime::mock_ext::mock_all_ext();
@@ -265,4 +893,124 @@ mod deserialize_helper_tests {
             );
         }
     }
+
+    #[test]
+    fn test_threshold_partials_deserialization() {
+        // Partial0 was written before partials were stored as compact values
+        let add_partial_response = format!("{{\"Attributes\":{{\"id\":{{\"S\":\"thresholdsig_0x11\"}},\"Partial2\":{{\"S\":\"{}\"}},\"LastUpdateEpochMillis\":{{\"N\":\"1680307200000\"}},\"Partial0\":{{\"S\":\"0xabcd\"}}}}}}", encode_compact_value(&[0xef]));
+        let (decoded, _): (AttributesWrapper<ThresholdPartialsResponse>, usize) =
+            serde_json_core::from_slice(add_partial_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded,
+            AttributesWrapper {
+                Attributes: ThresholdPartialsResponse {
+                    Partial0: Some(CompactBytesWrapper {
+                        S: vec![0xab, 0xcd]
+                    }),
+                    Partial1: None,
+                    Partial2: Some(CompactBytesWrapper { S: vec![0xef] }),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_threshold_round_deserialization() {
+        let pin_round_response = format!("{{\"Attributes\":{{\"id\":{{\"S\":\"thresholdround_0x11_592_7_0\"}},\"ExpiryEpochSecs\":{{\"N\":\"1680393600\"}},\"RoundTxn\":{{\"S\":\"{}\"}}}}}}", encode_compact_value(&[0xab, 0xcd]));
+        let (decoded, _): (AttributesWrapper<ThresholdRoundResponse>, usize) =
+            serde_json_core::from_slice(pin_round_response.as_bytes()).expect("deserialize failed");
+        assert_eq!(
+            decoded,
+            AttributesWrapper {
+                Attributes: ThresholdRoundResponse {
+                    RoundTxn: CompactBytesWrapper {
+                        S: vec![0xab, 0xcd]
+                    },
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_address_alias_deserialization() {
+        let get_alias_response = "{\"Item\":{\"ResolvedAddress\":{\"S\":\"0x42b7d766824422f499f84703ec4e2abb273171cf\"}}}";
+        let (decoded, _): (OptionalItemWrapper<AddressAliasResponse>, usize) =
+            serde_json_core::from_slice(get_alias_response.as_bytes()).expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.expect("Alias should exist").ResolvedAddress.S,
+            hex_string_to_vec("0x42b7d766824422f499f84703ec4e2abb273171cf").unwrap()
+        );
+
+        let (decoded, _): (OptionalItemWrapper<AddressAliasResponse>, usize) =
+            serde_json_core::from_slice("{}".as_bytes()).expect("deserialize failed");
+        assert_eq!(decoded, OptionalItemWrapper { Item: None });
+    }
+
+    #[test]
+    fn test_plan_tag_deserialization() {
+        let get_plan_tag_response =
+            "{\"Item\":{\"ExecPlanUuid\":{\"S\":\"0x01010101010101010101010101010101\"}}}";
+        let (decoded, _): (OptionalItemWrapper<PlanTagResponse>, usize) =
+            serde_json_core::from_slice(get_plan_tag_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.expect("Tag should exist").ExecPlanUuid.S.0,
+            Uuid::new([1u8; 16])
+        );
+    }
+
+    #[test]
+    fn test_delivery_approval_deserialization() {
+        let get_approval_response = "{\"Item\":{\"Approver\":{\"S\":\"0x0303030303030303030303030303030303030303030303030303030303030303\"}}}";
+        let (decoded, _): (OptionalItemWrapper<DeliveryApprovalResponse>, usize) =
+            serde_json_core::from_slice(get_approval_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item.expect("Approval should exist").Approver.S,
+            vec![3u8; 32]
+        );
+    }
+
+    #[test]
+    fn test_relayer_contribution_deserialization() {
+        let get_contribution_response = "{\"Item\":{\"NumStepsAdvanced\":{\"N\":\"12\"},\"GasFeeUsd\":{\"N\":\"350000000000000000000\"}}}";
+        let (decoded, _): (OptionalItemWrapper<RelayerContributionResponse>, usize) =
+            serde_json_core::from_slice(get_contribution_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Item,
+            Some(RelayerContributionResponse {
+                NumStepsAdvanced: Some(NumWrapper { N: 12 }),
+                GasFeeUsd: Some(AmountWrapper {
+                    N: 350_000_000_000_000_000_000
+                }),
+                ClaimedUsd: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_nonce_state_deserialization() {
+        let seal_nonce_state_response = "{\"Attributes\":{\"id\":{\"S\":\"chainstate_astar\"},\"BlockAtLastConfirmedNonce\":{\"N\":\"1001\"},\"DroppedNonces\":{\"L\":[{\"N\":\"49\"}]},\"ExecStepPendingBlockAdded\":{\"M\":{\"execstep_0xcase1\":{\"N\":\"1000\"},\"noncepool_0xpool2\":{\"N\":\"1001\"}}},\"ExecStepPendingNonce\":{\"M\":{\"execstep_0xcase1\":{\"N\":\"50\"},\"noncepool_0xpool2\":{\"N\":\"51\"}}},\"NextNonce\":{\"N\":\"55\"}}}";
+        let (decoded, _): (AttributesWrapper<NonceStateResponse>, usize) =
+            serde_json_core::from_slice(seal_nonce_state_response.as_bytes())
+                .expect("deserialize failed");
+        assert_eq!(
+            decoded.Attributes.DroppedNonces.L,
+            vec![NumWrapper { N: 49 }]
+        );
+        assert_eq!(
+            decoded.Attributes.ExecStepPendingNonce.M.entries,
+            vec![
+                ("execstep_0xcase1".to_string(), 50),
+                ("noncepool_0xpool2".to_string(), 51)
+            ]
+        );
+        assert_eq!(
+            decoded.Attributes.ExecStepPendingBlockAdded.M.entries.len(),
+            2
+        );
+        assert_eq!(decoded.Attributes.NextNonce.N, 55);
+    }
 }
@@ -16,13 +16,28 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     format,
     string::{String, ToString},
+    vec::Vec,
+};
+use scale::Encode;
+
+use privadex_chain_metadata::common::{
+    Amount, BlockNum, EthAddress, EthTxnHash, MillisSinceEpoch, Nonce, UniversalTokenId,
 };
-use privadex_chain_metadata::common::{BlockNum, EthTxnHash, MillisSinceEpoch, Nonce};
 use privadex_common::{utils::general_utils::slice_to_hex_string, uuid::Uuid};
 
+use super::{
+    compact_value::encode_compact_value,
+    deserialize_helper::NonceStateResponse,
+    execution_plan_assigner::{UnverifiedWrite, CLAIM_LEASE_MILLIS, WORKER_AFFINITY_MILLIS},
+    job_lock::JOB_LOCK_LEASE_MILLIS,
+    nonce_manager::DeploymentEpoch,
+    threshold_signing_coordinator::{THRESHOLD_ITEM_TTL_SECS, THRESHOLD_ROUND_MILLIS},
+    worker_identity::WorkerSignature,
+};
+
 // One per chain
 pub(super) struct DynamoDbNonceRequestFactory {
     pub table_name: &'static str,
@@ -41,19 +56,58 @@ pub(super) struct DynamoDbPrestartTxnsRequestFactory {
     pub key: String,
 }
 
+// One overall (across all chains). Each message hash gets its own item
+pub(super) struct DynamoDbThresholdSigRequestFactory {
+    pub table_name: &'static str,
+}
+
+// One overall (across all chains). Each alias gets its own item
+pub(super) struct DynamoDbAddressAliasRequestFactory {
+    pub table_name: &'static str,
+}
+
+pub(super) struct DynamoDbPlanTagRequestFactory {
+    pub table_name: &'static str,
+}
+
+// One overall (across all chains). Each ExecutionPlan's approval gets its own item
+pub(super) struct DynamoDbDeliveryApprovalRequestFactory {
+    pub table_name: &'static str,
+}
+
+// One overall (across all chains). Each worker's contribution and each payout token's fee
+// pool get their own item
+pub(super) struct DynamoDbRelayerRequestFactory {
+    pub table_name: &'static str,
+}
+
+// One overall (across all chains). Each sponsorship campaign's drawdown gets its own item
+pub(super) struct DynamoDbSponsorshipRequestFactory {
+    pub table_name: &'static str,
+}
+
+// One overall (across all chains). Each locked job gets its own item
+pub(super) struct DynamoDbJobLockRequestFactory {
+    pub table_name: &'static str,
+}
+
 impl DynamoDbNonceRequestFactory {
     // Case 1: Cold start / cleanup
     // When: IsPendingTxnsEmpty (and thus !IsExecutionStepAssigned)
+    // nonce_signature is the reserving worker's signature over the reservation (see
+    // worker_identity). Like the other maps, cold start replaces ExecStepNonceSig as a whole
     pub fn cold_start_request(
         &self,
         exec_step_uuid: &Uuid,
         cur_block: BlockNum,
         system_nonce: Nonce,
+        nonce_signature: &WorkerSignature,
     ) -> String {
         let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
         let self_assigned_nonce = system_nonce;
         let next_nonce = system_nonce + 1;
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock, DroppedNonces = :emptylist, ExecStepPendingNonce = :pendingnonce, ExecStepPendingBlockAdded = :pendingblockadded, NextNonce = :nextnonce", "ConditionExpression": "size(ExecStepPendingNonce) = :zero", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":emptylist": {{"L": []}}, ":pendingnonce": {{"M": {{"{exec_step_attr}": {{"N": "{self_assigned_nonce}"}}}}}}, ":pendingblockadded": {{"M": {{"{exec_step_attr}": {{"N": "{cur_block}"}}}}}}, ":nextnonce": {{"N": "{next_nonce}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key).to_string()
+        let nonce_sig_str = nonce_signature.to_compact_string();
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock, DroppedNonces = :emptylist, ExecStepPendingNonce = :pendingnonce, ExecStepPendingBlockAdded = :pendingblockadded, ExecStepNonceSig = :noncesig, NextNonce = :nextnonce", "ConditionExpression": "size(ExecStepPendingNonce) = :zero", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":emptylist": {{"L": []}}, ":pendingnonce": {{"M": {{"{exec_step_attr}": {{"N": "{self_assigned_nonce}"}}}}}}, ":pendingblockadded": {{"M": {{"{exec_step_attr}": {{"N": "{cur_block}"}}}}}}, ":noncesig": {{"M": {{"{exec_step_attr}": {{"S": "{nonce_sig_str}"}}}}}}, ":nextnonce": {{"N": "{next_nonce}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key).to_string()
     }
 
     // Case 2: Assign the next nonce
@@ -67,7 +121,7 @@ impl DynamoDbNonceRequestFactory {
     // When: IsExecutionStepAssigned (and thus !IsPendingTxnsEmpty)
     pub fn existing_assignment_request(&self, exec_step_uuid: &Uuid) -> String {
         let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "ExecStepPendingNonce.{exec_step_attr}, ExecStepPendingBlockAdded.{exec_step_attr}"}}"#, self.table_name, self.key,).to_string()
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "ExecStepPendingNonce.{exec_step_attr}, ExecStepPendingBlockAdded.{exec_step_attr}, ExecStepNonceSig.{exec_step_attr}"}}"#, self.table_name, self.key,).to_string()
     }
 
     // Case 4: Reclaim a dropped transaction's nonce
@@ -81,6 +135,19 @@ impl DynamoDbNonceRequestFactory {
         format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "UPDATED_NEW", "UpdateExpression": "SET ExecStepPendingBlockAdded.{exec_step_attr} = :curblock, ExecStepPendingNonce.{exec_step_attr} = DroppedNonces[0] REMOVE DroppedNonces[0]", "ConditionExpression": "attribute_not_exists(ExecStepPendingNonce.{exec_step_attr}) AND size(DroppedNonces) > :zero AND size(ExecStepPendingNonce) > :zero", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key,).to_string()
     }
 
+    // After case 2 or 4: the nonce is only known once it is reserved, so its signature is
+    // recorded right after, for as long as the reservation is still the one we made
+    pub fn sign_nonce_reservation_request(
+        &self,
+        exec_step_uuid: &Uuid,
+        nonce: Nonce,
+        nonce_signature: &WorkerSignature,
+    ) -> String {
+        let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
+        let nonce_sig_str = nonce_signature.to_compact_string();
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ExecStepNonceSig.{exec_step_attr} = :noncesig", "ConditionExpression": "ExecStepPendingNonce.{exec_step_attr} = :nonce", "ExpressionAttributeValues": {{":noncesig": {{"S": "{nonce_sig_str}"}}, ":nonce": {{"N": "{nonce}"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
     // For every case: A transaction has been finalized
     pub fn process_finalized_step_request(
         &self,
@@ -88,7 +155,7 @@ impl DynamoDbNonceRequestFactory {
         cur_block: BlockNum,
     ) -> String {
         let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock REMOVE ExecStepPendingBlockAdded.{exec_step_attr}, ExecStepPendingNonce.{exec_step_attr}", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}}}}}"#, self.table_name, self.key,).to_string()
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock REMOVE ExecStepPendingBlockAdded.{exec_step_attr}, ExecStepPendingNonce.{exec_step_attr}, ExecStepNonceSig.{exec_step_attr}", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}}}}}"#, self.table_name, self.key,).to_string()
     }
 
     // For every case: A transaction has been dropped
@@ -98,49 +165,257 @@ impl DynamoDbNonceRequestFactory {
         dropped_nonce: Nonce,
     ) -> String {
         let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET DroppedNonces = list_append(DroppedNonces, :droppednonce) REMOVE ExecStepPendingBlockAdded.{exec_step_attr}, ExecStepPendingNonce.{exec_step_attr}", "ConditionExpression": "attribute_exists(ExecStepPendingBlockAdded.{exec_step_attr})", "ExpressionAttributeValues": {{":droppednonce": {{"L": [{{"N": "{dropped_nonce}"}}]}}}}}}"#,
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET DroppedNonces = list_append(DroppedNonces, :droppednonce) REMOVE ExecStepPendingBlockAdded.{exec_step_attr}, ExecStepPendingNonce.{exec_step_attr}, ExecStepNonceSig.{exec_step_attr}", "ConditionExpression": "attribute_exists(ExecStepPendingBlockAdded.{exec_step_attr})", "ExpressionAttributeValues": {{":droppednonce": {{"L": [{{"N": "{dropped_nonce}"}}]}}}}}}"#,
         self.table_name, self.key,).to_string()
     }
 
+    // Nonce pools: a block of nonces is recorded as a single ExecStepPendingNonce entry (its
+    // first nonce) until it is released, so that a cold start cannot reset NextNonce under it
+
+    // Pool case 1: Cold start. Same as case 1, but reserves pool_size nonces
+    // When: IsPendingTxnsEmpty
+    pub fn cold_start_nonce_block_request(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> String {
+        let pool_attr = self.get_nonce_pool_attribute(pool_id);
+        let next_nonce = system_nonce + pool_size;
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock, DroppedNonces = :emptylist, ExecStepPendingNonce = :pendingnonce, ExecStepPendingBlockAdded = :pendingblockadded, ExecStepNonceSig = :emptymap, NextNonce = :nextnonce", "ConditionExpression": "size(ExecStepPendingNonce) = :zero", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":emptylist": {{"L": []}}, ":pendingnonce": {{"M": {{"{pool_attr}": {{"N": "{system_nonce}"}}}}}}, ":pendingblockadded": {{"M": {{"{pool_attr}": {{"N": "{cur_block}"}}}}}}, ":emptymap": {{"M": {{}}}}, ":nextnonce": {{"N": "{next_nonce}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key).to_string()
+    }
+
+    // Pool case 2: Reserve the next pool_size nonces
+    // When: IsDroppedNoncesEmpty AND !IsPendingTxnsEmpty AND NextNonce >= system nonce
+    pub fn reserve_nonce_block_request(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> String {
+        let pool_attr = self.get_nonce_pool_attribute(pool_id);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "UPDATED_NEW", "UpdateExpression": "SET ExecStepPendingBlockAdded.{pool_attr} = :curblock, ExecStepPendingNonce.{pool_attr} = NextNonce, NextNonce = NextNonce + :poolsize", "ConditionExpression": "attribute_not_exists(ExecStepPendingNonce.{pool_attr}) AND size(DroppedNonces) = :zero AND size(ExecStepPendingNonce) > :zero AND NextNonce >= :systemnonce", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":poolsize": {{"N": "{pool_size}"}}, ":systemnonce": {{"N": "{system_nonce}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Pool case 3: Reconcile with the chain, which is ahead of NextNonce (i.e. nonces were used
+    // outside of the NonceManager), and reserve pool_size nonces from the system nonce
+    // When: IsDroppedNoncesEmpty AND !IsPendingTxnsEmpty AND NextNonce < system nonce
+    pub fn reconcile_nonce_block_request(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> String {
+        let pool_attr = self.get_nonce_pool_attribute(pool_id);
+        let next_nonce = system_nonce + pool_size;
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ExecStepPendingBlockAdded.{pool_attr} = :curblock, ExecStepPendingNonce.{pool_attr} = :systemnonce, NextNonce = :nextnonce", "ConditionExpression": "attribute_not_exists(ExecStepPendingNonce.{pool_attr}) AND size(DroppedNonces) = :zero AND size(ExecStepPendingNonce) > :zero AND NextNonce < :systemnonce", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":systemnonce": {{"N": "{system_nonce}"}}, ":nextnonce": {{"N": "{next_nonce}"}}, ":zero": {{"N": "0"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Hands a nonce from a reserved block to the ExecutionStep
+    // When: !IsExecutionStepAssigned AND the block is still reserved
+    pub fn assign_pooled_nonce_request(
+        &self,
+        exec_step_uuid: &Uuid,
+        pool_id: &Uuid,
+        nonce: Nonce,
+        cur_block: BlockNum,
+        nonce_signature: &WorkerSignature,
+    ) -> String {
+        let exec_step_attr = self.get_exec_step_attribute(exec_step_uuid);
+        let pool_attr = self.get_nonce_pool_attribute(pool_id);
+        let nonce_sig_str = nonce_signature.to_compact_string();
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ExecStepPendingBlockAdded.{exec_step_attr} = :curblock, ExecStepPendingNonce.{exec_step_attr} = :nonce, ExecStepNonceSig.{exec_step_attr} = :noncesig", "ConditionExpression": "attribute_not_exists(ExecStepPendingNonce.{exec_step_attr}) AND attribute_exists(ExecStepPendingNonce.{pool_attr})", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":nonce": {{"N": "{nonce}"}}, ":noncesig": {{"S": "{nonce_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Releases the block. The nonces that were not handed out are treated like dropped nonces
+    pub fn release_nonce_block_request(&self, pool_id: &Uuid, unused_nonces: &[Nonce]) -> String {
+        let pool_attr = self.get_nonce_pool_attribute(pool_id);
+        if unused_nonces.is_empty() {
+            return format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "REMOVE ExecStepPendingBlockAdded.{pool_attr}, ExecStepPendingNonce.{pool_attr}", "ConditionExpression": "attribute_exists(ExecStepPendingNonce.{pool_attr})"}}"#,
+            self.table_name, self.key,).to_string();
+        }
+        let unused_nonces_list = unused_nonces
+            .iter()
+            .map(|nonce| format!(r#"{{"N": "{nonce}"}}"#))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET DroppedNonces = list_append(DroppedNonces, :unusednonces) REMOVE ExecStepPendingBlockAdded.{pool_attr}, ExecStepPendingNonce.{pool_attr}", "ConditionExpression": "attribute_exists(ExecStepPendingNonce.{pool_attr})", "ExpressionAttributeValues": {{":unusednonces": {{"L": [{unused_nonces_list}]}}}}}}"#,
+        self.table_name, self.key,).to_string()
+    }
+
+    // Deployment epochs: when a chain's nonce state moves to a new epoch's key, the prior key is
+    // sealed so that no case above can hand out a nonce from it anymore (they all need NextNonce
+    // or ExecStepPendingNonce)
+
+    // Seal the prior epoch's state and return it
+    pub fn seal_nonce_state_request(&self, migrated_to_epoch: DeploymentEpoch) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "ALL_OLD", "UpdateExpression": "SET MigratedToEpoch = :epoch REMOVE NextNonce, DroppedNonces, ExecStepPendingNonce, ExecStepPendingBlockAdded, ExecStepNonceSig", "ConditionExpression": "attribute_exists(NextNonce)", "ExpressionAttributeValues": {{":epoch": {{"N": "{migrated_to_epoch}"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Undo the seal, e.g. if the new epoch's state could not be written
+    pub fn restore_nonce_state_request(&self, nonce_state: &NonceStateResponse) -> String {
+        let nonce_signatures = nonce_state
+            .ExecStepNonceSig
+            .as_ref()
+            .map_or(&[][..], |map_wrapper| &map_wrapper.M.entries);
+        let nonce_state_values = Self::get_nonce_state_values(nonce_state, nonce_signatures);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET NextNonce = :nextnonce, DroppedNonces = :droppednonces, ExecStepPendingNonce = :pendingnonce, ExecStepPendingBlockAdded = :pendingblockadded, ExecStepNonceSig = :noncesigs REMOVE MigratedToEpoch", "ConditionExpression": "attribute_exists(MigratedToEpoch)", "ExpressionAttributeValues": {{{nonce_state_values}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Adopt: the new epoch carries on with the prior epoch's state, reservations included.
+    // The reservations' signatures are made anew for the new epoch's key (see migrate_reservations)
+    // When: the new epoch's key does not exist yet
+    pub fn adopt_nonce_state_request(
+        &self,
+        nonce_state: &NonceStateResponse,
+        nonce_signatures: &[(String, WorkerSignature)],
+    ) -> String {
+        let nonce_state_values = Self::get_nonce_state_values(nonce_state, nonce_signatures);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET NextNonce = :nextnonce, DroppedNonces = :droppednonces, ExecStepPendingNonce = :pendingnonce, ExecStepPendingBlockAdded = :pendingblockadded, ExecStepNonceSig = :noncesigs", "ConditionExpression": "attribute_not_exists(id)", "ExpressionAttributeValues": {{{nonce_state_values}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Purge: the new epoch starts with no reservations, so its first step cold starts from the
+    // system nonce
+    // When: the new epoch's key does not exist yet
+    pub fn init_nonce_state_request(&self, cur_block: BlockNum, system_nonce: Nonce) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET BlockAtLastConfirmedNonce = :curblock, DroppedNonces = :emptylist, ExecStepPendingNonce = :emptymap, ExecStepPendingBlockAdded = :emptymap, ExecStepNonceSig = :emptymap, NextNonce = :nextnonce", "ConditionExpression": "attribute_not_exists(id)", "ExpressionAttributeValues": {{":curblock": {{"N": "{cur_block}"}}, ":emptylist": {{"L": []}}, ":emptymap": {{"M": {{}}}}, ":nextnonce": {{"N": "{system_nonce}"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    fn get_nonce_state_values(
+        nonce_state: &NonceStateResponse,
+        nonce_signatures: &[(String, WorkerSignature)],
+    ) -> String {
+        let dropped_nonces_list = nonce_state
+            .DroppedNonces
+            .L
+            .iter()
+            .map(|nonce| format!(r#"{{"N": "{}"}}"#, nonce.N))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let to_num_map = |entries: &[(String, u32)]| {
+            entries
+                .iter()
+                .map(|(attr, num)| format!(r#""{attr}": {{"N": "{num}"}}"#))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let pending_nonce_map = to_num_map(&nonce_state.ExecStepPendingNonce.M.entries);
+        let pending_block_added_map = to_num_map(&nonce_state.ExecStepPendingBlockAdded.M.entries);
+        let nonce_sig_map = nonce_signatures
+            .iter()
+            .map(|(attr, signature)| {
+                format!(r#""{attr}": {{"S": "{}"}}"#, signature.to_compact_string())
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!(
+            r#"":nextnonce": {{"N": "{}"}}, ":droppednonces": {{"L": [{dropped_nonces_list}]}}, ":pendingnonce": {{"M": {{{pending_nonce_map}}}}}, ":pendingblockadded": {{"M": {{{pending_block_added_map}}}}}, ":noncesigs": {{"M": {{{nonce_sig_map}}}}}"#,
+            nonce_state.NextNonce.N
+        )
+    }
+
     fn get_exec_step_attribute(&self, exec_step_uuid: &Uuid) -> String {
         format!("execstep_{}", exec_step_uuid.to_hex_string())
     }
+
+    fn get_nonce_pool_attribute(&self, pool_id: &Uuid) -> String {
+        format!("noncepool_{}", pool_id.to_hex_string())
+    }
 }
 
 impl DynamoDbExecPlanRequestFactory {
     // Allocate a plan to a worker
     // When: isallocated = false OR updateepochmillis is old (1 minute)
+    // A worker (as opposed to e.g. the admin) must also be the plan's last worker, unless the
+    // plan has been due for WORKER_AFFINITY_MILLIS (i.e. the last worker did not pick it up).
+    // We record who holds the claim for list_claims. Admin claims clear it instead.
+    // claim_signature is the claimer's signature over the claim (see worker_identity)
     pub fn allocate_execplan_request(
         &self,
         exec_plan_uuid: &Uuid,
         now_epoch_millis: MillisSinceEpoch,
+        worker: Option<&[u8; 32]>,
+        claim_signature: &WorkerSignature,
     ) -> String {
         let execplan_hex_str = exec_plan_uuid.to_hex_string();
         let exec_plan_attr = self.get_exec_plan_attribute(exec_plan_uuid);
+        let claim_sig_str = claim_signature.to_compact_string();
         // If the ExecutionPlan is still allocateed but its timestamp is over a minute ago, then we allocate to it
         // (we assume the worker that it was allocated to has died)
-        let min_epoch_millis = now_epoch_millis - 60_000;
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :true, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis ADD Plans :plan", "ConditionExpression": "WorkerIsAllocated.{exec_plan_attr} <> :true OR WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} < :minepochmillis", "ExpressionAttributeValues": {{":true": {{"BOOL": true}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":minepochmillis": {{"N": "{min_epoch_millis}"}}}}}}"#, self.table_name, self.key,).to_string()
+        let min_epoch_millis = now_epoch_millis - CLAIM_LEASE_MILLIS;
+        if let Some(worker) = worker {
+            let worker_hex_str = slice_to_hex_string(worker);
+            let affinity_epoch_millis = now_epoch_millis - WORKER_AFFINITY_MILLIS;
+            return format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :true, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, ClaimedBy.{exec_plan_attr} = :worker, ClaimSig.{exec_plan_attr} = :claimsig ADD Plans :plan", "ConditionExpression": "(WorkerIsAllocated.{exec_plan_attr} <> :true OR WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} < :minepochmillis) AND (attribute_not_exists(LastWorker.{exec_plan_attr}) OR LastWorker.{exec_plan_attr} = :worker OR NextPollAfterEpochMillis.{exec_plan_attr} < :affinityepochmillis)", "ExpressionAttributeValues": {{":true": {{"BOOL": true}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":minepochmillis": {{"N": "{min_epoch_millis}"}}, ":worker": {{"S": "{worker_hex_str}"}}, ":affinityepochmillis": {{"N": "{affinity_epoch_millis}"}}, ":claimsig": {{"S": "{claim_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string();
+        }
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :true, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, ClaimSig.{exec_plan_attr} = :claimsig REMOVE ClaimedBy.{exec_plan_attr} ADD Plans :plan", "ConditionExpression": "WorkerIsAllocated.{exec_plan_attr} <> :true OR WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} < :minepochmillis", "ExpressionAttributeValues": {{":true": {{"BOOL": true}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":minepochmillis": {{"N": "{min_epoch_millis}"}}, ":claimsig": {{"S": "{claim_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    // Take a plan over from a claim or release that is not signed by its worker (i.e. it was
+    // not written through the contract), as long as it is still the one we read. The write
+    // itself is the same as allocate_execplan_request's
+    pub fn take_over_unverified_execplan_request(
+        &self,
+        exec_plan_uuid: &Uuid,
+        now_epoch_millis: MillisSinceEpoch,
+        worker: Option<&[u8; 32]>,
+        claim_signature: &WorkerSignature,
+        unverified_write: &UnverifiedWrite,
+    ) -> String {
+        let execplan_hex_str = exec_plan_uuid.to_hex_string();
+        let exec_plan_attr = self.get_exec_plan_attribute(exec_plan_uuid);
+        let claim_sig_str = claim_signature.to_compact_string();
+        let (condition, unverified_millis) = match unverified_write {
+            UnverifiedWrite::Claim { claimed_millis } => (
+                format!("WorkerIsAllocated.{exec_plan_attr} = :true AND WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :unverifiedmillis"),
+                claimed_millis,
+            ),
+            UnverifiedWrite::Release {
+                next_poll_after_millis,
+            } => (
+                format!("WorkerIsAllocated.{exec_plan_attr} <> :true AND NextPollAfterEpochMillis.{exec_plan_attr} = :unverifiedmillis"),
+                next_poll_after_millis,
+            ),
+        };
+        if let Some(worker) = worker {
+            let worker_hex_str = slice_to_hex_string(worker);
+            return format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :true, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, ClaimedBy.{exec_plan_attr} = :worker, ClaimSig.{exec_plan_attr} = :claimsig ADD Plans :plan", "ConditionExpression": "{condition}", "ExpressionAttributeValues": {{":true": {{"BOOL": true}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":unverifiedmillis": {{"N": "{unverified_millis}"}}, ":worker": {{"S": "{worker_hex_str}"}}, ":claimsig": {{"S": "{claim_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string();
+        }
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :true, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, ClaimSig.{exec_plan_attr} = :claimsig REMOVE ClaimedBy.{exec_plan_attr} ADD Plans :plan", "ConditionExpression": "{condition}", "ExpressionAttributeValues": {{":true": {{"BOOL": true}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":unverifiedmillis": {{"N": "{unverified_millis}"}}, ":claimsig": {{"S": "{claim_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string()
     }
 
     // Unallocate a plan from a worker
     // When: Unconditional update. No other worker can allocate before this
     // (and only a worker that has allocated last should unallocate)
+    // We also record when the plan is next worth stepping forward, so the listing can skip it
+    // until then, and which worker stepped it forward (see allocate_execplan_request), along
+    // with the signature over both
     pub fn unallocate_execplan_request(
         &self,
         exec_plan_uuid: &Uuid,
         now_epoch_millis: MillisSinceEpoch,
+        next_poll_after_epoch_millis: MillisSinceEpoch,
+        worker: Option<&[u8; 32]>,
+        release_signature: &WorkerSignature,
     ) -> String {
         let execplan_hex_str = exec_plan_uuid.to_hex_string();
         let exec_plan_attr = self.get_exec_plan_attribute(exec_plan_uuid);
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :false, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis ADD Plans :plan", "ExpressionAttributeValues": {{":false": {{"BOOL": false}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}}}}}"#, self.table_name, self.key,).to_string()
+        let release_sig_str = release_signature.to_compact_string();
+        if let Some(worker) = worker {
+            let worker_hex_str = slice_to_hex_string(worker);
+            return format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :false, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, NextPollAfterEpochMillis.{exec_plan_attr} = :nextpollepochmillis, LastWorker.{exec_plan_attr} = :worker, ReleaseSig.{exec_plan_attr} = :releasesig ADD Plans :plan", "ExpressionAttributeValues": {{":false": {{"BOOL": false}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":nextpollepochmillis": {{"N": "{next_poll_after_epoch_millis}"}}, ":worker": {{"S": "{worker_hex_str}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":releasesig": {{"S": "{release_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string();
+        }
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET WorkerIsAllocated.{exec_plan_attr} = :false, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} = :epochmillis, NextPollAfterEpochMillis.{exec_plan_attr} = :nextpollepochmillis, ReleaseSig.{exec_plan_attr} = :releasesig ADD Plans :plan", "ExpressionAttributeValues": {{":false": {{"BOOL": false}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":nextpollepochmillis": {{"N": "{next_poll_after_epoch_millis}"}}, ":plan": {{"SS": ["{execplan_hex_str}"]}}, ":releasesig": {{"S": "{release_sig_str}"}}}}}}"#, self.table_name, self.key,).to_string()
     }
 
     // Remove exec plan from processing queue
     pub fn remove_completed_execplan_request(&self, exec_plan_uuid: &Uuid) -> String {
         let execplan_hex_str = exec_plan_uuid.to_hex_string();
         let exec_plan_attr = self.get_exec_plan_attribute(exec_plan_uuid);
-        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "REMOVE WorkerIsAllocated.{exec_plan_attr}, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr} DELETE Plans :plan", "ExpressionAttributeValues": {{":plan": {{"SS": ["{execplan_hex_str}"]}}}}}}"#, self.table_name, self.key,).to_string()
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "REMOVE WorkerIsAllocated.{exec_plan_attr}, WorkerAssignmentUpdateEpochMillis.{exec_plan_attr}, NextPollAfterEpochMillis.{exec_plan_attr}, LastWorker.{exec_plan_attr}, ClaimedBy.{exec_plan_attr}, ClaimSig.{exec_plan_attr}, ReleaseSig.{exec_plan_attr} DELETE Plans :plan", "ExpressionAttributeValues": {{":plan": {{"SS": ["{execplan_hex_str}"]}}}}}}"#, self.table_name, self.key,).to_string()
     }
 
     pub fn get_execplan_ids(&self) -> String {
@@ -148,6 +423,26 @@ impl DynamoDbExecPlanRequestFactory {
         self.table_name, self.key,).to_string()
     }
 
+    pub fn get_execplan_ids_with_poll_schedule(&self) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "Plans, NextPollAfterEpochMillis, LastWorker, ReleaseSig"}}"#,
+        self.table_name, self.key,).to_string()
+    }
+
+    pub fn get_execplan_claims(&self) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "Plans, WorkerIsAllocated, WorkerAssignmentUpdateEpochMillis, ClaimedBy, LastWorker, NextPollAfterEpochMillis, ClaimSig, ReleaseSig"}}"#,
+        self.table_name, self.key,).to_string()
+    }
+
+    // Drain mode lives on the same item as the plans, so that workers can read it alongside them
+    pub fn set_draining_request(&self, draining: bool) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET Draining = :draining", "ExpressionAttributeValues": {{":draining": {{"BOOL": {draining}}}}}}}"#, self.table_name, self.key,).to_string()
+    }
+
+    pub fn get_draining_request(&self) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "Draining"}}"#,
+        self.table_name, self.key,).to_string()
+    }
+
     fn get_exec_plan_attribute(&self, exec_plan_uuid: &Uuid) -> String {
         format!("execplan_{}", exec_plan_uuid.to_hex_string())
     }
@@ -166,11 +461,306 @@ impl DynamoDbPrestartTxnsRequestFactory {
     }
 }
 
+impl DynamoDbThresholdSigRequestFactory {
+    // Record this party's partial signature and return every partial collected so far
+    pub fn add_partial_signature_request(
+        &self,
+        msg_hash: &[u8; 32],
+        party_index: u8,
+        partial_sig: &[u8],
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let partial_sig_str = encode_compact_value(partial_sig);
+        let expiry_secs = get_threshold_item_expiry_secs(now_epoch_millis);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "ALL_NEW", "UpdateExpression": "SET Partial{party_index} = :partialsig, LastUpdateEpochMillis = :epochmillis, ExpiryEpochSecs = :expiry", "ExpressionAttributeValues": {{":partialsig": {{"S": "{partial_sig_str}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}, ":expiry": {{"N": "{expiry_secs}"}}}}}}"#, self.table_name, self.get_msg_hash_key(msg_hash)).to_string()
+    }
+
+    // Pins round_txn unless another party pinned its txn for this round first, and returns the
+    // pinned txn either way
+    pub fn pin_round_txn_request(
+        &self,
+        address: &EthAddress,
+        chain_id: u64,
+        nonce: Nonce,
+        round_txn: &[u8],
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let round_txn_str = encode_compact_value(round_txn);
+        let expiry_secs = get_threshold_item_expiry_secs(now_epoch_millis);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "ALL_NEW", "UpdateExpression": "SET RoundTxn = if_not_exists(RoundTxn, :roundtxn), ExpiryEpochSecs = if_not_exists(ExpiryEpochSecs, :expiry)", "ExpressionAttributeValues": {{":roundtxn": {{"S": "{round_txn_str}"}}, ":expiry": {{"N": "{expiry_secs}"}}}}}}"#, self.table_name, self.get_round_key(address, chain_id, nonce, now_epoch_millis)).to_string()
+    }
+
+    fn get_msg_hash_key(&self, msg_hash: &[u8; 32]) -> String {
+        format!("thresholdsig_{}", slice_to_hex_string(msg_hash))
+    }
+
+    // A round is one window of THRESHOLD_ROUND_MILLIS, so a pinned txn that went stale (e.g. its
+    // gas price is too low to be mined) is replaced in the next window
+    fn get_round_key(
+        &self,
+        address: &EthAddress,
+        chain_id: u64,
+        nonce: Nonce,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(
+            "thresholdround_{}_{chain_id}_{nonce}_{}",
+            slice_to_hex_string(&address.0),
+            now_epoch_millis / THRESHOLD_ROUND_MILLIS
+        )
+    }
+}
+
+// DynamoDB deletes threshold items some time after this (see the README on enabling TTL)
+fn get_threshold_item_expiry_secs(now_epoch_millis: MillisSinceEpoch) -> u64 {
+    now_epoch_millis / 1000 + THRESHOLD_ITEM_TTL_SECS
+}
+
+impl DynamoDbAddressAliasRequestFactory {
+    // Registers the alias, overwriting whatever address it pointed to before
+    pub fn set_alias_request(
+        &self,
+        alias: &str,
+        address: &EthAddress,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let address_str = slice_to_hex_string(&address.0);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ResolvedAddress = :address, LastUpdateEpochMillis = :epochmillis", "ExpressionAttributeValues": {{":address": {{"S": "{address_str}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_alias_key(alias)).to_string()
+    }
+
+    pub fn get_alias_request(&self, alias: &str) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "ResolvedAddress"}}"#,
+        self.table_name, self.get_alias_key(alias)).to_string()
+    }
+
+    // The caller is expected to have normalized the alias (see name_resolver::normalize_name),
+    // which also keeps it from breaking out of the JSON string
+    fn get_alias_key(&self, alias: &str) -> String {
+        format!("alias_{}", alias)
+    }
+}
+
+impl DynamoDbPlanTagRequestFactory {
+    // Points the tag at the plan, overwriting whatever plan it pointed to before
+    pub fn set_plan_tag_request(
+        &self,
+        tag_hash: &[u8; 16],
+        exec_plan_uuid: &Uuid,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let uuid_str = exec_plan_uuid.to_hex_string();
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ExecPlanUuid = :uuid, LastUpdateEpochMillis = :epochmillis", "ExpressionAttributeValues": {{":uuid": {{"S": "{uuid_str}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_plan_tag_key(tag_hash)).to_string()
+    }
+
+    pub fn get_plan_tag_request(&self, tag_hash: &[u8; 16]) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "ExecPlanUuid"}}"#,
+        self.table_name, self.get_plan_tag_key(tag_hash)).to_string()
+    }
+
+    fn get_plan_tag_key(&self, tag_hash: &[u8; 16]) -> String {
+        format!("plantag_{}", slice_to_hex_string(tag_hash))
+    }
+}
+
+impl DynamoDbDeliveryApprovalRequestFactory {
+    // Fails the condition check if the delivery was already approved
+    pub fn approve_delivery_request(
+        &self,
+        exec_plan_uuid: &Uuid,
+        approver: &[u8; 32],
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let approver_str = slice_to_hex_string(approver);
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET Approver = :approver, ApprovedEpochMillis = :epochmillis", "ConditionExpression": "attribute_not_exists(Approver)", "ExpressionAttributeValues": {{":approver": {{"S": "{approver_str}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_delivery_approval_key(exec_plan_uuid)).to_string()
+    }
+
+    pub fn get_delivery_approval_request(&self, exec_plan_uuid: &Uuid) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "Approver"}}"#,
+        self.table_name, self.get_delivery_approval_key(exec_plan_uuid)).to_string()
+    }
+
+    fn get_delivery_approval_key(&self, exec_plan_uuid: &Uuid) -> String {
+        format!("deliveryapproval_{}", exec_plan_uuid.to_hex_string())
+    }
+}
+
+impl DynamoDbRelayerRequestFactory {
+    // Unconditional. Counters start at 0 when the item or attribute does not exist yet
+    pub fn record_contribution_request(
+        &self,
+        worker: &[u8; 32],
+        num_steps_advanced: u32,
+        gas_fee_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET LastUpdateEpochMillis = :epochmillis ADD NumStepsAdvanced :numsteps, GasFeeUsd :gasfeeusd", "ExpressionAttributeValues": {{":epochmillis": {{"N": "{now_epoch_millis}"}}, ":numsteps": {{"N": "{num_steps_advanced}"}}, ":gasfeeusd": {{"N": "{gas_fee_usd}"}}}}}}"#, self.table_name, self.get_contribution_key(worker)).to_string()
+    }
+
+    pub fn get_contribution_request(&self, worker: &[u8; 32]) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "NumStepsAdvanced, GasFeeUsd, ClaimedUsd"}}"#,
+        self.table_name, self.get_contribution_key(worker)).to_string()
+    }
+
+    // Fails the condition check if another claim was recorded since we read prev_claimed_usd
+    pub fn record_claim_request(
+        &self,
+        worker: &[u8; 32],
+        prev_claimed_usd: Amount,
+        new_claimed_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET ClaimedUsd = :newclaimed, LastClaimEpochMillis = :epochmillis", "ConditionExpression": "(attribute_not_exists(ClaimedUsd) AND :prevclaimed = :zero) OR ClaimedUsd = :prevclaimed", "ExpressionAttributeValues": {{":newclaimed": {{"N": "{new_claimed_usd}"}}, ":prevclaimed": {{"N": "{prev_claimed_usd}"}}, ":zero": {{"N": "0"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_contribution_key(worker)).to_string()
+    }
+
+    // Fails the condition check if the plan's fee was already added, so recording a closed
+    // plan twice does not double count it
+    pub fn fund_fee_pool_request(
+        &self,
+        token: &UniversalTokenId,
+        exec_plan_uuid: &Uuid,
+        amount: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        let execplan_hex_str = exec_plan_uuid.to_hex_string();
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET LastUpdateEpochMillis = :epochmillis ADD FeesAvailable :amount, FundedPlans :planset", "ConditionExpression": "NOT contains(FundedPlans, :plan)", "ExpressionAttributeValues": {{":epochmillis": {{"N": "{now_epoch_millis}"}}, ":amount": {{"N": "{amount}"}}, ":planset": {{"SS": ["{execplan_hex_str}"]}}, ":plan": {{"S": "{execplan_hex_str}"}}}}}}"#, self.table_name, self.get_fee_pool_key(token)).to_string()
+    }
+
+    // Fails the condition check if the pool holds less than amount
+    pub fn debit_fee_pool_request(
+        &self,
+        token: &UniversalTokenId,
+        amount: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET FeesAvailable = FeesAvailable - :amount, LastUpdateEpochMillis = :epochmillis", "ConditionExpression": "FeesAvailable >= :amount", "ExpressionAttributeValues": {{":amount": {{"N": "{amount}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_fee_pool_key(token)).to_string()
+    }
+
+    // Hands back a debit whose claim could not be recorded
+    pub fn credit_fee_pool_request(
+        &self,
+        token: &UniversalTokenId,
+        amount: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET LastUpdateEpochMillis = :epochmillis ADD FeesAvailable :amount", "ExpressionAttributeValues": {{":epochmillis": {{"N": "{now_epoch_millis}"}}, ":amount": {{"N": "{amount}"}}}}}}"#, self.table_name, self.get_fee_pool_key(token)).to_string()
+    }
+
+    pub fn get_fee_pool_request(&self, token: &UniversalTokenId) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "FeesAvailable"}}"#,
+        self.table_name, self.get_fee_pool_key(token)).to_string()
+    }
+
+    fn get_contribution_key(&self, worker: &[u8; 32]) -> String {
+        format!("relayer_{}", slice_to_hex_string(worker))
+    }
+
+    fn get_fee_pool_key(&self, token: &UniversalTokenId) -> String {
+        format!("relayerfeepool_{}", slice_to_hex_string(&token.encode()))
+    }
+}
+
+impl DynamoDbSponsorshipRequestFactory {
+    // Fails the condition check if the plan was already paid for
+    pub fn record_plan_drawdown_request(
+        &self,
+        campaign_id: &str,
+        exec_plan_uuid: &Uuid,
+        amount_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET CampaignId = :campaign, DrawnUsd = :amount, LastUpdateEpochMillis = :epochmillis", "ConditionExpression": "attribute_not_exists(DrawnUsd)", "ExpressionAttributeValues": {{":campaign": {{"S": "{campaign_id}"}}, ":amount": {{"N": "{amount_usd}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_sponsored_plan_key(exec_plan_uuid)).to_string()
+    }
+
+    // Fails the condition check if the campaign has spent more than max_prev_spent_usd (its
+    // budget minus amount_usd). So concurrent plans can't overdraw the budget between them
+    pub fn draw_down_request(
+        &self,
+        campaign_id: &str,
+        amount_usd: Amount,
+        max_prev_spent_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET LastUpdateEpochMillis = :epochmillis ADD SpentUsd :amount", "ConditionExpression": "attribute_not_exists(SpentUsd) OR SpentUsd <= :maxprevspent", "ExpressionAttributeValues": {{":epochmillis": {{"N": "{now_epoch_millis}"}}, ":amount": {{"N": "{amount_usd}"}}, ":maxprevspent": {{"N": "{max_prev_spent_usd}"}}}}}}"#, self.table_name, self.get_sponsorship_key(campaign_id)).to_string()
+    }
+
+    pub fn get_plan_drawdown_request(&self, exec_plan_uuid: &Uuid) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "DrawnUsd"}}"#,
+        self.table_name, self.get_sponsored_plan_key(exec_plan_uuid)).to_string()
+    }
+
+    // Fails the condition check if the plan drew down less than amount_usd, or if something
+    // was already credited back for it. So a plan is credited back at most once
+    pub fn claim_plan_refund_request(
+        &self,
+        exec_plan_uuid: &Uuid,
+        amount_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET RefundedUsd = :amount, LastUpdateEpochMillis = :epochmillis", "ConditionExpression": "DrawnUsd >= :amount AND attribute_not_exists(RefundedUsd)", "ExpressionAttributeValues": {{":amount": {{"N": "{amount_usd}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_sponsored_plan_key(exec_plan_uuid)).to_string()
+    }
+
+    // Hands back (part of) a drawdown, once its plan has claimed the refund
+    pub fn refund_request(
+        &self,
+        campaign_id: &str,
+        amount_usd: Amount,
+        now_epoch_millis: MillisSinceEpoch,
+    ) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET SpentUsd = SpentUsd - :amount, LastUpdateEpochMillis = :epochmillis", "ConditionExpression": "SpentUsd >= :amount", "ExpressionAttributeValues": {{":amount": {{"N": "{amount_usd}"}}, ":epochmillis": {{"N": "{now_epoch_millis}"}}}}}}"#, self.table_name, self.get_sponsorship_key(campaign_id)).to_string()
+    }
+
+    pub fn get_spent_request(&self, campaign_id: &str) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ProjectionExpression": "SpentUsd"}}"#,
+        self.table_name, self.get_sponsorship_key(campaign_id)).to_string()
+    }
+
+    // Campaign IDs are limited to [A-Za-z0-9_-] (see sponsorship::is_valid_campaign_id), so they
+    // can go into the JSON as is
+    fn get_sponsorship_key(&self, campaign_id: &str) -> String {
+        format!("sponsorship_{}", campaign_id)
+    }
+
+    // Its own item, since a set of plans in the campaign's item would outgrow DynamoDB's 400 KB
+    // item limit
+    fn get_sponsored_plan_key(&self, exec_plan_uuid: &Uuid) -> String {
+        format!("sponsoredplan_{}", exec_plan_uuid.to_hex_string())
+    }
+}
+
+impl DynamoDbJobLockRequestFactory {
+    // Fails the condition check if the lock is held and its lease has not expired
+    pub fn acquire_request(&self, job: &str, now_epoch_millis: MillisSinceEpoch) -> String {
+        let expiry_millis = now_epoch_millis + JOB_LOCK_LEASE_MILLIS;
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "SET AcquiredEpochMillis = :epochmillis, LeaseExpiryEpochMillis = :expiry", "ConditionExpression": "attribute_not_exists(AcquiredEpochMillis) OR LeaseExpiryEpochMillis < :epochmillis", "ExpressionAttributeValues": {{":epochmillis": {{"N": "{now_epoch_millis}"}}, ":expiry": {{"N": "{expiry_millis}"}}}}}}"#, self.table_name, self.get_job_lock_key(job)).to_string()
+    }
+
+    // acquired_epoch_millis is what acquire_request was sent with, i.e. the holder's own lock
+    pub fn release_request(&self, job: &str, acquired_epoch_millis: MillisSinceEpoch) -> String {
+        format!(r#"{{"TableName": "{}", "Key": {{"id": {{"S": "{}"}}}}, "ReturnValues": "NONE", "UpdateExpression": "REMOVE AcquiredEpochMillis, LeaseExpiryEpochMillis", "ConditionExpression": "AcquiredEpochMillis = :acquired", "ExpressionAttributeValues": {{":acquired": {{"N": "{acquired_epoch_millis}"}}}}}}"#, self.table_name, self.get_job_lock_key(job)).to_string()
+    }
+
+    fn get_job_lock_key(&self, job: &str) -> String {
+        format!("joblock_{}", job)
+    }
+}
+
 #[cfg(test)]
 mod request_factory_tests {
-    use ink_env::debug_println;
+    use ink::{env::debug_println, prelude::vec};
+
+    use privadex_chain_metadata::registry::token::universal_token_id_registry;
 
     use super::*;
+    use crate::concurrency_coordinator::deserialize_helper::{
+        MapWrapper, NumListWrapper, NumWrapper, UnknownKeysToNumWrapper,
+    };
+
+    fn dummy_signature() -> WorkerSignature {
+        WorkerSignature {
+            signer: [3u8; 32],
+            signature: vec![0xab; 64],
+        }
+    }
 
     #[test]
     fn test_print_query() {
@@ -181,7 +771,317 @@ mod request_factory_tests {
         };
         let cur_block = 1_000_000;
         let system_nonce = 50;
-        let x = nonce_factory.cold_start_request(&Uuid::new([1u8; 16]), cur_block, system_nonce);
+        let x = nonce_factory.cold_start_request(
+            &Uuid::new([1u8; 16]),
+            cur_block,
+            system_nonce,
+            &dummy_signature(),
+        );
         debug_println!("{}", x);
     }
+
+    #[test]
+    fn test_release_nonce_block_request() {
+        let nonce_factory = DynamoDbNonceRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "chainstate_astar".into(),
+        };
+        let pool_id = Uuid::new([1u8; 16]);
+        let release_all = nonce_factory.release_nonce_block_request(&pool_id, &[]);
+        assert!(release_all.contains(r#""UpdateExpression": "REMOVE ExecStepPendingBlockAdded.noncepool_0x01010101010101010101010101010101, ExecStepPendingNonce.noncepool_0x01010101010101010101010101010101""#));
+        assert!(!release_all.contains("DroppedNonces"));
+        let release_unused = nonce_factory.release_nonce_block_request(&pool_id, &[52, 53]);
+        assert!(release_unused.contains(r#"":unusednonces": {"L": [{"N": "52"}, {"N": "53"}]}"#));
+    }
+
+    #[test]
+    fn test_nonce_state_requests() {
+        let nonce_factory = DynamoDbNonceRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "chainstate_astar_epoch1690000000000".into(),
+        };
+        let seal_request = nonce_factory.seal_nonce_state_request(1_690_000_000_000);
+        assert!(seal_request.contains(r#"":epoch": {"N": "1690000000000"}"#));
+        assert!(seal_request.contains(r#""ReturnValues": "ALL_OLD""#));
+
+        let nonce_state = NonceStateResponse {
+            DroppedNonces: NumListWrapper {
+                L: vec![NumWrapper { N: 49 }],
+            },
+            ExecStepPendingBlockAdded: MapWrapper {
+                M: UnknownKeysToNumWrapper {
+                    entries: vec![("execstep_0xcase1".into(), 1000)],
+                },
+            },
+            ExecStepPendingNonce: MapWrapper {
+                M: UnknownKeysToNumWrapper {
+                    entries: vec![("execstep_0xcase1".into(), 50)],
+                },
+            },
+            ExecStepNonceSig: None,
+            NextNonce: NumWrapper { N: 51 },
+        };
+        let adopt_request = nonce_factory.adopt_nonce_state_request(
+            &nonce_state,
+            &[("execstep_0xcase1".into(), dummy_signature())],
+        );
+        assert!(adopt_request.contains(r#""ExpressionAttributeValues": {":nextnonce": {"N": "51"}, ":droppednonces": {"L": [{"N": "49"}]}, ":pendingnonce": {"M": {"execstep_0xcase1": {"N": "50"}}}, ":pendingblockadded": {"M": {"execstep_0xcase1": {"N": "1000"}}}, ":noncesigs": {"M": {"execstep_0xcase1": {"S": "A"#));
+        assert!(adopt_request.contains(r#""ConditionExpression": "attribute_not_exists(id)""#));
+
+        // A prior state without signatures restores without them
+        let restore_request = nonce_factory.restore_nonce_state_request(&nonce_state);
+        assert!(restore_request.contains(r#"":noncesigs": {"M": {}}"#));
+
+        let init_request = nonce_factory.init_nonce_state_request(1001, 60);
+        assert!(init_request.contains(r#"":emptymap": {"M": {}}"#));
+        assert!(init_request.contains(r#"":nextnonce": {"N": "60"}"#));
+    }
+
+    #[test]
+    fn test_unallocate_execplan_request() {
+        let exec_plan_factory = DynamoDbExecPlanRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "execplans".into(),
+        };
+        let request = exec_plan_factory.unallocate_execplan_request(
+            &Uuid::new([1u8; 16]),
+            1_000,
+            13_000,
+            None,
+            &dummy_signature(),
+        );
+        assert!(request
+            .contains("ReleaseSig.execplan_0x01010101010101010101010101010101 = :releasesig"));
+        assert!(request.contains("NextPollAfterEpochMillis.execplan_0x01010101010101010101010101010101 = :nextpollepochmillis"));
+        assert!(request.contains(r#"":nextpollepochmillis": {"N": "13000"}"#));
+        assert!(!request.contains("LastWorker"));
+
+        let worker_request = exec_plan_factory.unallocate_execplan_request(
+            &Uuid::new([1u8; 16]),
+            1_000,
+            13_000,
+            Some(&[3u8; 32]),
+            &dummy_signature(),
+        );
+        assert!(worker_request
+            .contains("LastWorker.execplan_0x01010101010101010101010101010101 = :worker"));
+        assert!(worker_request.contains(
+            r#"":worker": {"S": "0x0303030303030303030303030303030303030303030303030303030303030303"}"#
+        ));
+    }
+
+    #[test]
+    fn test_allocate_execplan_request() {
+        let exec_plan_factory = DynamoDbExecPlanRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "execplans".into(),
+        };
+        let request = exec_plan_factory.allocate_execplan_request(
+            &Uuid::new([1u8; 16]),
+            100_000,
+            None,
+            &dummy_signature(),
+        );
+        assert!(!request.contains("LastWorker"));
+        assert!(request.contains(
+            ":claimsig REMOVE ClaimedBy.execplan_0x01010101010101010101010101010101 ADD Plans"
+        ));
+        assert!(request.contains(&format!(
+            r#"":claimsig": {{"S": "{}"}}"#,
+            dummy_signature().to_compact_string()
+        )));
+
+        let worker_request = exec_plan_factory.allocate_execplan_request(
+            &Uuid::new([1u8; 16]),
+            100_000,
+            Some(&[3u8; 32]),
+            &dummy_signature(),
+        );
+        assert!(worker_request
+            .contains("ClaimedBy.execplan_0x01010101010101010101010101010101 = :worker, ClaimSig.execplan_0x01010101010101010101010101010101 = :claimsig ADD Plans"));
+        assert!(worker_request.contains("LastWorker.execplan_0x01010101010101010101010101010101 = :worker OR NextPollAfterEpochMillis.execplan_0x01010101010101010101010101010101 < :affinityepochmillis"));
+        assert!(worker_request.contains(&format!(
+            r#"":affinityepochmillis": {{"N": "{}"}}"#,
+            100_000 - WORKER_AFFINITY_MILLIS
+        )));
+    }
+
+    #[test]
+    fn test_take_over_unverified_execplan_request() {
+        let exec_plan_factory = DynamoDbExecPlanRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "execplans".into(),
+        };
+        let claim_request = exec_plan_factory.take_over_unverified_execplan_request(
+            &Uuid::new([1u8; 16]),
+            100_000,
+            Some(&[3u8; 32]),
+            &dummy_signature(),
+            &UnverifiedWrite::Claim {
+                claimed_millis: 90_000,
+            },
+        );
+        assert!(claim_request.contains(r#""ConditionExpression": "WorkerIsAllocated.execplan_0x01010101010101010101010101010101 = :true AND WorkerAssignmentUpdateEpochMillis.execplan_0x01010101010101010101010101010101 = :unverifiedmillis""#));
+        assert!(claim_request.contains(r#"":unverifiedmillis": {"N": "90000"}"#));
+        // Affinity does not apply, since the last worker is not to be trusted either
+        assert!(!claim_request.contains("LastWorker"));
+
+        let release_request = exec_plan_factory.take_over_unverified_execplan_request(
+            &Uuid::new([1u8; 16]),
+            100_000,
+            None,
+            &dummy_signature(),
+            &UnverifiedWrite::Release {
+                next_poll_after_millis: 5_000_000,
+            },
+        );
+        assert!(release_request.contains(r#""ConditionExpression": "WorkerIsAllocated.execplan_0x01010101010101010101010101010101 <> :true AND NextPollAfterEpochMillis.execplan_0x01010101010101010101010101010101 = :unverifiedmillis""#));
+        assert!(release_request.contains("REMOVE ClaimedBy"));
+    }
+
+    #[test]
+    fn test_draining_requests() {
+        let exec_plan_factory = DynamoDbExecPlanRequestFactory {
+            table_name: "privadex_phat_contract",
+            key: "execplans".into(),
+        };
+        let set_request = exec_plan_factory.set_draining_request(true);
+        assert!(set_request.contains(r#""UpdateExpression": "SET Draining = :draining""#));
+        assert!(set_request.contains(r#"":draining": {"BOOL": true}"#));
+        assert!(exec_plan_factory
+            .get_draining_request()
+            .contains(r#""ProjectionExpression": "Draining""#));
+    }
+
+    #[test]
+    fn test_plan_tag_request() {
+        let plan_tag_factory = DynamoDbPlanTagRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let set_request =
+            plan_tag_factory.set_plan_tag_request(&[2u8; 16], &Uuid::new([1u8; 16]), 1_000);
+        assert!(set_request
+            .contains(r#""Key": {"id": {"S": "plantag_0x02020202020202020202020202020202"}}"#));
+        assert!(set_request.contains(r#"":uuid": {"S": "0x01010101010101010101010101010101"}"#));
+    }
+
+    #[test]
+    fn test_delivery_approval_request() {
+        let delivery_approval_factory = DynamoDbDeliveryApprovalRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let approve_request = delivery_approval_factory.approve_delivery_request(
+            &Uuid::new([1u8; 16]),
+            &[3u8; 32],
+            1_000,
+        );
+        assert!(approve_request.contains(
+            r#""Key": {"id": {"S": "deliveryapproval_0x01010101010101010101010101010101"}}"#
+        ));
+        assert!(
+            approve_request.contains(r#""ConditionExpression": "attribute_not_exists(Approver)""#)
+        );
+        assert!(approve_request.contains(r#"":epochmillis": {"N": "1000"}"#));
+    }
+
+    #[test]
+    fn test_relayer_requests() {
+        let relayer_factory = DynamoDbRelayerRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let record_request =
+            relayer_factory.record_contribution_request(&[3u8; 32], 2, 5_000, 1_000);
+        assert!(record_request.contains(
+            r#""Key": {"id": {"S": "relayer_0x0303030303030303030303030303030303030303030303030303030303030303"}}"#
+        ));
+        assert!(record_request.contains(r#"":numsteps": {"N": "2"}, ":gasfeeusd": {"N": "5000"}"#));
+
+        let claim_request = relayer_factory.record_claim_request(&[3u8; 32], 0, 7_000, 1_000);
+        assert!(claim_request.contains(r#"":prevclaimed": {"N": "0"}"#));
+        assert!(claim_request.contains(r#"":newclaimed": {"N": "7000"}"#));
+
+        let token = universal_token_id_registry::USDT_MOONBEAM;
+        let fund_request =
+            relayer_factory.fund_fee_pool_request(&token, &Uuid::new([1u8; 16]), 9_000, 1_000);
+        assert!(fund_request.contains(r#""Key": {"id": {"S": "relayerfeepool_0x"#));
+        assert!(fund_request.contains(r#"":plan": {"S": "0x01010101010101010101010101010101"}"#));
+        let debit_request = relayer_factory.debit_fee_pool_request(&token, 9_000, 1_000);
+        assert!(debit_request.contains(r#""ConditionExpression": "FeesAvailable >= :amount""#));
+    }
+
+    #[test]
+    fn test_sponsorship_requests() {
+        let sponsorship_factory = DynamoDbSponsorshipRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let exec_plan_uuid = Uuid::new([1u8; 16]);
+        let record_plan_request = sponsorship_factory.record_plan_drawdown_request(
+            "spring-2023",
+            &exec_plan_uuid,
+            4_000,
+            1_000,
+        );
+        assert!(record_plan_request.contains(
+            r#""Key": {"id": {"S": "sponsoredplan_0x01010101010101010101010101010101"}}"#
+        ));
+        assert!(record_plan_request
+            .contains(r#""ConditionExpression": "attribute_not_exists(DrawnUsd)""#));
+        let draw_down_request =
+            sponsorship_factory.draw_down_request("spring-2023", 4_000, 6_000, 1_000);
+        assert!(draw_down_request.contains(r#""Key": {"id": {"S": "sponsorship_spring-2023"}}"#));
+        assert!(draw_down_request
+            .contains(r#"":amount": {"N": "4000"}, ":maxprevspent": {"N": "6000"}"#));
+        let claim_refund_request =
+            sponsorship_factory.claim_plan_refund_request(&exec_plan_uuid, 3_000, 1_000);
+        assert!(claim_refund_request.contains(
+            r#""ConditionExpression": "DrawnUsd >= :amount AND attribute_not_exists(RefundedUsd)""#
+        ));
+        let refund_request = sponsorship_factory.refund_request("spring-2023", 4_000, 1_000);
+        assert!(refund_request.contains(r#""ConditionExpression": "SpentUsd >= :amount""#));
+    }
+
+    #[test]
+    fn test_job_lock_requests() {
+        let job_lock_factory = DynamoDbJobLockRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let acquire_request = job_lock_factory.acquire_request("strandedfunds", 1_000);
+        assert!(acquire_request.contains(r#""Key": {"id": {"S": "joblock_strandedfunds"}}"#));
+        assert!(acquire_request
+            .contains(r#"":epochmillis": {"N": "1000"}, ":expiry": {"N": "301000"}"#));
+        let release_request = job_lock_factory.release_request("strandedfunds", 1_000);
+        assert!(
+            release_request.contains(r#""ConditionExpression": "AcquiredEpochMillis = :acquired""#)
+        );
+        assert!(release_request.contains(r#"":acquired": {"N": "1000"}"#));
+    }
+
+    #[test]
+    fn test_threshold_sig_requests() {
+        let threshold_sig_factory = DynamoDbThresholdSigRequestFactory {
+            table_name: "privadex_phat_contract",
+        };
+        let address = EthAddress::from([0x11; 20]);
+        // Both parties land in the same round until the window rolls over
+        let pin_request =
+            threshold_sig_factory.pin_round_txn_request(&address, 592, 7, &[0xab], 1_000);
+        assert!(pin_request.contains(&format!(
+            r#""Key": {{"id": {{"S": "thresholdround_0x{}_592_7_0"}}}}"#,
+            "11".repeat(20)
+        )));
+        assert!(pin_request.contains("SET RoundTxn = if_not_exists(RoundTxn, :roundtxn)"));
+        assert!(pin_request.contains(r#"":expiry": {"N": "86401"}"#));
+        let next_round_request = threshold_sig_factory.pin_round_txn_request(
+            &address,
+            592,
+            7,
+            &[0xab],
+            1_000 + THRESHOLD_ROUND_MILLIS,
+        );
+        assert!(next_round_request.contains("_592_7_1\""));
+        let partial_request =
+            threshold_sig_factory.add_partial_signature_request(&[0x11; 32], 2, &[0xef], 1_000);
+        assert!(partial_request.contains("SET Partial2 = :partialsig"));
+        assert!(partial_request.contains(r#"":expiry": {"N": "86401"}"#));
+    }
 }
@@ -16,10 +16,11 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     string::{String, ToString},
     vec::Vec,
 };
+use scale::{Decode, Encode};
 
 use privadex_chain_metadata::common::MillisSinceEpoch;
 use privadex_common::{
@@ -28,12 +29,26 @@ use privadex_common::{
 };
 
 use super::{
-    deserialize_helper::{ExecPlanIdsWrapper, ItemWrapper},
+    compact_value::check_item_size,
+    deserialize_helper::{
+        DrainingResponse, ExecPlanClaimsWrapper, ExecPlanIdsWithPollScheduleWrapper,
+        ExecPlanIdsWrapper, ItemWrapper, OptionalItemWrapper,
+    },
     dynamodb_request_factory::DynamoDbExecPlanRequestFactory,
+    worker_identity::{
+        is_signed_by, CoordinationRecord, WorkerIdentity, WorkerSignature, CONTRACT_IDENTITY,
+    },
 };
 
 const DYNAMODB_TABLE_EXECPLAN: &'static str = "privadex_phat_contract";
 const DYNAMODB_TABLE_KEY: &'static str = "execplans";
+// A claim that has not been refreshed for this long is assumed to belong to a dead worker, and
+// anyone can claim the plan over it
+pub(super) const CLAIM_LEASE_MILLIS: MillisSinceEpoch = 60_000;
+// Once a plan is due, only the worker that last stepped it forward can claim it for this long.
+// That worker likely still has the plan's state warm, and the other workers don't all race
+// for it. If it does not come back in time, anyone can claim the plan
+pub(super) const WORKER_AFFINITY_MILLIS: MillisSinceEpoch = 20_000;
 
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -53,10 +68,98 @@ impl From<DynamoDbError> for ExecutionPlanAssignerError {
 
 type Result<T> = core::result::Result<T, ExecutionPlanAssignerError>;
 
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WorkerAffinity {
+    pub worker: [u8; 32],
+    // Other workers' claims fail until then
+    pub expiry_millis: MillisSinceEpoch,
+}
+
+/// A plan that is due to be stepped forward. Schedulers should send it to affinity's worker
+/// if there is one, and can spread the rest across their workers however they like
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DueExecPlan {
+    pub exec_plan_uuid: Uuid,
+    pub affinity: Option<WorkerAffinity>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ClaimLeaseState {
+    // Other claims fail until the lease expires or the plan is unclaimed
+    Active,
+    // The claimer did not unclaim the plan in time (e.g. it crashed mid step). The next claim
+    // takes the plan over
+    Stale,
+}
+
+impl ClaimLeaseState {
+    // Matches the claim condition in allocate_execplan_request
+    pub fn at(claimed_millis: MillisSinceEpoch, millis_since_epoch: MillisSinceEpoch) -> Self {
+        if claimed_millis + CLAIM_LEASE_MILLIS < millis_since_epoch {
+            Self::Stale
+        } else {
+            Self::Active
+        }
+    }
+}
+
+/// A plan that is currently claimed
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ExecPlanClaim {
+    pub exec_plan_uuid: Uuid,
+    // The worker stepping the plan forward. None for admin claims, and for claims made before
+    // claimers were recorded
+    pub claimed_by: Option<[u8; 32]>,
+    pub claimed_millis: MillisSinceEpoch,
+    pub lease_expiry_millis: MillisSinceEpoch,
+    pub lease_state: ClaimLeaseState,
+    pub last_worker: Option<[u8; 32]>,
+    // Whether the claim is signed by its claimer. An unverified claim was not made through
+    // the contract (or predates signing), and the next claim takes it over
+    pub verified: bool,
+}
+
+// A claim or release whose signature does not check out, with the value that the take over
+// is conditioned on (see take_over_unverified_execplan_request)
+pub(super) enum UnverifiedWrite {
+    Claim {
+        claimed_millis: MillisSinceEpoch,
+    },
+    Release {
+        next_poll_after_millis: MillisSinceEpoch,
+    },
+}
+
+struct ExecPlanAssignment {
+    exec_plan_uuid: Uuid,
+    next_poll_after_millis: Option<MillisSinceEpoch>,
+    last_worker: Option<[u8; 32]>,
+    // The signer of the release, if it checks out
+    release_signer: Option<[u8; 32]>,
+}
+
+struct ExecPlanClaimState {
+    exec_plan_uuid: Uuid,
+    is_claimed: bool,
+    claimed_by: Option<[u8; 32]>,
+    claimed_millis: MillisSinceEpoch,
+    last_worker: Option<[u8; 32]>,
+    next_poll_after_millis: Option<MillisSinceEpoch>,
+    claim_verified: bool,
+    release_signer: Option<[u8; 32]>,
+}
+
 pub struct ExecutionPlanAssigner {
     api: DynamoDbApi,
     request_factory: DynamoDbExecPlanRequestFactory,
     pub millis_since_epoch: MillisSinceEpoch,
+    // The caller of execution_plan_step_forward. Unset for claims that are not stepping a plan
+    // forward (e.g. the admin's), which ignore affinity and do not record a last worker
+    worker: Option<[u8; 32]>,
 }
 
 impl ExecutionPlanAssigner {
@@ -72,13 +175,79 @@ impl ExecutionPlanAssigner {
                 key: DYNAMODB_TABLE_KEY.to_string(),
             },
             millis_since_epoch,
+            worker: None,
         }
     }
 
+    pub fn set_worker(&mut self, worker: [u8; 32]) {
+        self.worker = Some(worker);
+    }
+
+    // If the claim fails because of a claim or release that is not signed by its worker, we
+    // take the plan over from it, so that a write made with the AWS credentials alone can't
+    // hold a plan back
     pub fn attempt_allocate_exec_plan(&self, exec_plan_uuid: &Uuid) -> Result<bool> {
-        let request_payload = self
-            .request_factory
-            .allocate_execplan_request(exec_plan_uuid, self.millis_since_epoch);
+        let claim_signature = self.sign(&CoordinationRecord::Claim {
+            exec_plan_uuid,
+            claimed_millis: self.millis_since_epoch,
+        });
+        let request_payload = self.request_factory.allocate_execplan_request(
+            exec_plan_uuid,
+            self.millis_since_epoch,
+            self.worker.as_ref(),
+            &claim_signature,
+        );
+        if self.send_claim_request(&request_payload)? {
+            return Ok(true);
+        }
+        self.attempt_take_over_unverified(exec_plan_uuid, &claim_signature)
+    }
+
+    fn attempt_take_over_unverified(
+        &self,
+        exec_plan_uuid: &Uuid,
+        claim_signature: &WorkerSignature,
+    ) -> Result<bool> {
+        let claim_state = match self
+            .get_execplan_claim_states()?
+            .into_iter()
+            .find(|claim_state| claim_state.exec_plan_uuid == *exec_plan_uuid)
+        {
+            Some(claim_state) => claim_state,
+            None => return Ok(false),
+        };
+        let unverified_write = if claim_state.is_claimed {
+            if claim_state.claim_verified {
+                return Ok(false);
+            }
+            UnverifiedWrite::Claim {
+                claimed_millis: claim_state.claimed_millis,
+            }
+        } else {
+            match claim_state.next_poll_after_millis {
+                Some(next_poll_after_millis) if claim_state.release_signer.is_none() => {
+                    UnverifiedWrite::Release {
+                        next_poll_after_millis,
+                    }
+                }
+                _ => return Ok(false),
+            }
+        };
+        ink::env::debug_println!(
+            "Taking over ExecutionPlan {:?} from an unverified write",
+            exec_plan_uuid
+        );
+        let request_payload = self.request_factory.take_over_unverified_execplan_request(
+            exec_plan_uuid,
+            self.millis_since_epoch,
+            self.worker.as_ref(),
+            claim_signature,
+            &unverified_write,
+        );
+        self.send_claim_request(&request_payload)
+    }
+
+    fn send_claim_request(&self, request_payload: &str) -> Result<bool> {
         self.api
             .dynamodb_request(
                 self.millis_since_epoch,
@@ -98,10 +267,22 @@ impl ExecutionPlanAssigner {
             )
     }
 
-    pub fn unallocate_exec_plan(&self, exec_plan_uuid: &Uuid) -> Result<()> {
-        let request_payload = self
-            .request_factory
-            .unallocate_execplan_request(exec_plan_uuid, self.millis_since_epoch);
+    pub fn unallocate_exec_plan(
+        &self,
+        exec_plan_uuid: &Uuid,
+        next_poll_after_millis: MillisSinceEpoch,
+    ) -> Result<()> {
+        let release_signature = self.sign(&CoordinationRecord::Release {
+            exec_plan_uuid,
+            next_poll_after_millis,
+        });
+        let request_payload = self.request_factory.unallocate_execplan_request(
+            exec_plan_uuid,
+            self.millis_since_epoch,
+            next_poll_after_millis,
+            self.worker.as_ref(),
+            &release_signature,
+        );
         self.api
             .dynamodb_request(
                 self.millis_since_epoch,
@@ -132,10 +313,47 @@ impl ExecutionPlanAssigner {
             )
     }
 
+    // While draining, workers only advance the plans that have something in flight
+    pub fn set_draining(&self, draining: bool) -> Result<()> {
+        let request_payload = self.request_factory.set_draining_request(draining);
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_or_else(
+                |dynamodb_err| Err(ExecutionPlanAssignerError::from(dynamodb_err)),
+                // We discard the response because we had set return_values to None
+                |_response| Ok(()),
+            )
+    }
+
+    pub fn is_draining(&self) -> Result<bool> {
+        let request_payload = self.request_factory.get_draining_request();
+        let get_draining_response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| ExecutionPlanAssignerError::from(dynamodb_err))?;
+
+        let (decoded, _): (OptionalItemWrapper<DrainingResponse>, usize) =
+            serde_json_core::from_slice(&get_draining_response)
+                .map_err(|_| ExecutionPlanAssignerError::UnexpectedDeserializationError)?;
+        Ok(decoded
+            .Item
+            .and_then(|item| item.Draining)
+            .map_or(false, |draining| draining.BOOL))
+    }
+
     // Below functions are more useful for the driver/scheduler
 
+    // A new plan is due right away
     pub fn register_exec_plan(&self, exec_plan_uuid: &Uuid) -> Result<()> {
-        self.unallocate_exec_plan(exec_plan_uuid)
+        self.unallocate_exec_plan(exec_plan_uuid, self.millis_since_epoch)
     }
 
     pub fn get_execplan_ids(&self) -> Result<Vec<Uuid>> {
@@ -161,13 +379,273 @@ impl ExecutionPlanAssigner {
             .map(|uuid_container| uuid_container.0)
             .collect())
     }
+
+    // Skips the plans whose next_poll_after_millis is still in the future. Plans without one
+    // (e.g. registered before it was recorded) are always due, and so are the ones whose
+    // release is not signed (see attempt_allocate_exec_plan)
+    pub fn get_due_execplans(&self) -> Result<Vec<DueExecPlan>> {
+        Ok(self
+            .get_execplan_assignments()?
+            .into_iter()
+            .filter_map(|assignment| {
+                let is_due = assignment.release_signer.is_none()
+                    || assignment
+                        .next_poll_after_millis
+                        .map_or(true, |next_poll_after_millis| {
+                            next_poll_after_millis <= self.millis_since_epoch
+                        });
+                if !is_due {
+                    return None;
+                }
+                // Matches the claim condition in allocate_execplan_request. Only the worker
+                // that signed the release gets affinity
+                let affinity = match (assignment.last_worker, assignment.next_poll_after_millis) {
+                    (Some(worker), Some(next_poll_after_millis))
+                        if assignment.release_signer == Some(worker)
+                            && next_poll_after_millis + WORKER_AFFINITY_MILLIS
+                                >= self.millis_since_epoch =>
+                    {
+                        Some(WorkerAffinity {
+                            worker,
+                            expiry_millis: next_poll_after_millis + WORKER_AFFINITY_MILLIS,
+                        })
+                    }
+                    _ => None,
+                };
+                Some(DueExecPlan {
+                    exec_plan_uuid: assignment.exec_plan_uuid,
+                    affinity,
+                })
+            })
+            .collect())
+    }
+
+    // Every active plan with its next_poll_after_millis, if one was recorded
+    pub fn get_execplan_poll_schedule(&self) -> Result<Vec<(Uuid, Option<MillisSinceEpoch>)>> {
+        Ok(self
+            .get_execplan_assignments()?
+            .into_iter()
+            .map(|assignment| (assignment.exec_plan_uuid, assignment.next_poll_after_millis))
+            .collect())
+    }
+
+    // The plans that are claimed right now, including the ones whose lease ran out but that no
+    // one has claimed over yet
+    pub fn get_execplan_claims(&self) -> Result<Vec<ExecPlanClaim>> {
+        Ok(self
+            .get_execplan_claim_states()?
+            .into_iter()
+            .filter(|claim_state| claim_state.is_claimed)
+            .map(|claim_state| ExecPlanClaim {
+                exec_plan_uuid: claim_state.exec_plan_uuid,
+                claimed_by: claim_state.claimed_by,
+                claimed_millis: claim_state.claimed_millis,
+                lease_expiry_millis: claim_state.claimed_millis + CLAIM_LEASE_MILLIS,
+                lease_state: ClaimLeaseState::at(
+                    claim_state.claimed_millis,
+                    self.millis_since_epoch,
+                ),
+                last_worker: claim_state.last_worker,
+                verified: claim_state.claim_verified,
+            })
+            .collect())
+    }
+
+    fn get_execplan_claim_states(&self) -> Result<Vec<ExecPlanClaimState>> {
+        let request_payload = self.request_factory.get_execplan_claims();
+        let get_claims_response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| ExecutionPlanAssignerError::from(dynamodb_err))?;
+        // This projection is nearly the whole item
+        check_item_size(DYNAMODB_TABLE_KEY, &get_claims_response);
+
+        let (decoded, _): (ItemWrapper<ExecPlanClaimsWrapper>, usize) =
+            serde_json_core::from_slice(&get_claims_response)
+                .map_err(|_| ExecutionPlanAssignerError::UnexpectedDeserializationError)?;
+
+        let is_allocated = decoded
+            .Item
+            .WorkerIsAllocated
+            .map(|map_wrapper| map_wrapper.M.is_allocated)
+            .unwrap_or_default();
+        let claim_times = decoded
+            .Item
+            .WorkerAssignmentUpdateEpochMillis
+            .map(|map_wrapper| map_wrapper.M.millis)
+            .unwrap_or_default();
+        let claimers = decoded
+            .Item
+            .ClaimedBy
+            .map(|map_wrapper| map_wrapper.M.workers)
+            .unwrap_or_default();
+        let last_workers = decoded
+            .Item
+            .LastWorker
+            .map(|map_wrapper| map_wrapper.M.workers)
+            .unwrap_or_default();
+        let poll_schedule = decoded
+            .Item
+            .NextPollAfterEpochMillis
+            .map(|map_wrapper| map_wrapper.M.millis)
+            .unwrap_or_default();
+        let claim_signatures = decoded
+            .Item
+            .ClaimSig
+            .map(|map_wrapper| map_wrapper.M.signatures)
+            .unwrap_or_default();
+        let release_signatures = decoded
+            .Item
+            .ReleaseSig
+            .map(|map_wrapper| map_wrapper.M.signatures)
+            .unwrap_or_default();
+        Ok(decoded
+            .Item
+            .Plans
+            .SS
+            .into_iter()
+            .map(|uuid_container| {
+                let exec_plan_uuid = uuid_container.0;
+                let is_claimed = find_for_plan(&is_allocated, &exec_plan_uuid).unwrap_or(false);
+                let claimed_millis = find_for_plan(&claim_times, &exec_plan_uuid).unwrap_or(0);
+                let claimed_by = find_for_plan(&claimers, &exec_plan_uuid);
+                let last_worker = find_for_plan(&last_workers, &exec_plan_uuid);
+                let next_poll_after_millis = find_for_plan(&poll_schedule, &exec_plan_uuid);
+                let claim_verified = is_signed_by(
+                    &CoordinationRecord::Claim {
+                        exec_plan_uuid: &exec_plan_uuid,
+                        claimed_millis,
+                    },
+                    find_signature_for_plan(&claim_signatures, &exec_plan_uuid),
+                    &claimed_by.unwrap_or(CONTRACT_IDENTITY),
+                );
+                let release_signer = get_release_signer(
+                    &exec_plan_uuid,
+                    next_poll_after_millis,
+                    last_worker,
+                    find_signature_for_plan(&release_signatures, &exec_plan_uuid),
+                );
+                ExecPlanClaimState {
+                    exec_plan_uuid,
+                    is_claimed,
+                    claimed_by,
+                    claimed_millis,
+                    last_worker,
+                    next_poll_after_millis,
+                    claim_verified,
+                    release_signer,
+                }
+            })
+            .collect())
+    }
+
+    fn get_execplan_assignments(&self) -> Result<Vec<ExecPlanAssignment>> {
+        let request_payload = self.request_factory.get_execplan_ids_with_poll_schedule();
+        let get_exec_plan_ids_response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| ExecutionPlanAssignerError::from(dynamodb_err))?;
+
+        let (decoded, _): (ItemWrapper<ExecPlanIdsWithPollScheduleWrapper>, usize) =
+            serde_json_core::from_slice(&get_exec_plan_ids_response)
+                .map_err(|_| ExecutionPlanAssignerError::UnexpectedDeserializationError)?;
+
+        let poll_schedule = decoded
+            .Item
+            .NextPollAfterEpochMillis
+            .map(|map_wrapper| map_wrapper.M.millis)
+            .unwrap_or_default();
+        let last_workers = decoded
+            .Item
+            .LastWorker
+            .map(|map_wrapper| map_wrapper.M.workers)
+            .unwrap_or_default();
+        let release_signatures = decoded
+            .Item
+            .ReleaseSig
+            .map(|map_wrapper| map_wrapper.M.signatures)
+            .unwrap_or_default();
+        Ok(decoded
+            .Item
+            .Plans
+            .SS
+            .into_iter()
+            .map(|uuid_container| {
+                let exec_plan_uuid = uuid_container.0;
+                let next_poll_after_millis = find_for_plan(&poll_schedule, &exec_plan_uuid);
+                let last_worker = find_for_plan(&last_workers, &exec_plan_uuid);
+                let release_signer = get_release_signer(
+                    &exec_plan_uuid,
+                    next_poll_after_millis,
+                    last_worker,
+                    find_signature_for_plan(&release_signatures, &exec_plan_uuid),
+                );
+                ExecPlanAssignment {
+                    exec_plan_uuid,
+                    next_poll_after_millis,
+                    last_worker,
+                    release_signer,
+                }
+            })
+            .collect())
+    }
+
+    // Signs as the worker, or as the contract for claims that are not stepping a plan forward
+    fn sign(&self, record: &CoordinationRecord) -> WorkerSignature {
+        WorkerIdentity::derive(self.worker.unwrap_or(CONTRACT_IDENTITY)).sign(record)
+    }
+}
+
+fn find_for_plan<T: Copy>(entries: &[(Uuid, T)], exec_plan_uuid: &Uuid) -> Option<T> {
+    entries
+        .iter()
+        .find(|(uuid, _)| uuid == exec_plan_uuid)
+        .map(|(_, val)| *val)
+}
+
+fn find_signature_for_plan<'a>(
+    signatures: &'a [(Uuid, WorkerSignature)],
+    exec_plan_uuid: &Uuid,
+) -> Option<&'a WorkerSignature> {
+    signatures
+        .iter()
+        .find(|(uuid, _)| uuid == exec_plan_uuid)
+        .map(|(_, signature)| signature)
+}
+
+// A release is signed by the last worker, or by the contract for releases that are not
+// stepping a plan forward (which leave the last worker as is). Plans without a poll schedule
+// have nothing to verify
+fn get_release_signer(
+    exec_plan_uuid: &Uuid,
+    next_poll_after_millis: Option<MillisSinceEpoch>,
+    last_worker: Option<[u8; 32]>,
+    release_signature: Option<&WorkerSignature>,
+) -> Option<[u8; 32]> {
+    let next_poll_after_millis = next_poll_after_millis?;
+    let record = CoordinationRecord::Release {
+        exec_plan_uuid,
+        next_poll_after_millis,
+    };
+    [last_worker, Some(CONTRACT_IDENTITY)]
+        .into_iter()
+        .flatten()
+        .find(|signer| is_signed_by(&record, release_signature, signer))
 }
 
 #[cfg(feature = "dynamodb-live-test")]
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod execution_plan_assigner_tests {
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     use super::*;
 
@@ -205,8 +683,12 @@ mod execution_plan_assigner_tests {
     fn test_unallocate_execplan() {
         pink_extension_runtime::mock_ext::mock_all_ext();
 
-        let _ = exec_plan_assigner()
-            .unallocate_exec_plan(&Uuid::new([1u8; 16]))
+        let exec_plan_assigner = exec_plan_assigner();
+        let _ = exec_plan_assigner
+            .unallocate_exec_plan(
+                &Uuid::new([1u8; 16]),
+                exec_plan_assigner.millis_since_epoch + 12_000,
+            )
             .expect("Database write error");
         debug_println!("Unallocated ExecutionPlan");
     }
@@ -230,4 +712,24 @@ mod execution_plan_assigner_tests {
             .expect("Database access/connection error");
         debug_println!("Active ExecutionPlans: {:?}", exec_plan_uuids);
     }
+
+    #[test]
+    fn test_get_due_exec_plan_ids() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let exec_plan_uuids = exec_plan_assigner()
+            .get_due_execplans()
+            .expect("Database access/connection error");
+        debug_println!("Due ExecutionPlans: {:?}", exec_plan_uuids);
+    }
+
+    #[test]
+    fn test_get_exec_plan_claims() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let claims = exec_plan_assigner()
+            .get_execplan_claims()
+            .expect("Database access/connection error");
+        debug_println!("Claimed ExecutionPlans: {:?}", claims);
+    }
 }
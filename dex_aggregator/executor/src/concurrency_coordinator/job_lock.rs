@@ -0,0 +1,123 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError};
+
+use super::dynamodb_request_factory::DynamoDbJobLockRequestFactory;
+
+const DYNAMODB_TABLE_JOB_LOCK: &'static str = "privadex_phat_contract";
+// A lock that was not released for this long is assumed to belong to a dead worker, and the
+// next acquire takes it over. Longer than any single contract call runs for
+pub(super) const JOB_LOCK_LEASE_MILLIS: MillisSinceEpoch = 300_000;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum JobLockError {
+    // Another worker holds the lock
+    ConditionalCheckFailed,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for JobLockError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, JobLockError>;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LockedJob {
+    StrandedFundsLedger,
+    // The pending plan analytics and everything else recorded when a plan closes
+    PlanAnalytics,
+    CanaryLedger,
+}
+
+impl LockedJob {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::StrandedFundsLedger => "strandedfunds",
+            Self::PlanAnalytics => "plananalytics",
+            Self::CanaryLedger => "canaryledger",
+        }
+    }
+}
+
+/// Mutual exclusion for the read-modify-write jobs on shared S3 objects (e.g. the stranded funds
+/// ledger), which S3 can't make atomic by itself. Each job has an item of its own, and a lock is
+/// held by the acquire time written into it. Only the acquirer releases it (by that time), and
+/// a lock that outlives JOB_LOCK_LEASE_MILLIS can be taken over
+pub struct JobLock {
+    api: DynamoDbApi,
+    request_factory: DynamoDbJobLockRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl JobLock {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbJobLockRequestFactory {
+                table_name: DYNAMODB_TABLE_JOB_LOCK,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    pub fn attempt_acquire(&self, job: LockedJob) -> Result<bool> /* didAcquire */ {
+        let request_payload = self
+            .request_factory
+            .acquire_request(job.name(), self.millis_since_epoch);
+        match self.update_item(request_payload) {
+            Ok(()) => Ok(true),
+            Err(JobLockError::ConditionalCheckFailed) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Must be called on the JobLock that acquired the lock, since its millis_since_epoch is what
+    // identifies the holder. Fails the condition check if the lease expired and another worker
+    // took the lock over
+    pub fn release(&self, job: LockedJob) -> Result<()> {
+        let request_payload = self
+            .request_factory
+            .release_request(job.name(), self.millis_since_epoch);
+        self.update_item(request_payload)
+    }
+
+    fn update_item(&self, request_payload: String) -> Result<()> {
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| JobLockError::from(dynamodb_err))?;
+        Ok(())
+    }
+}
@@ -16,8 +16,17 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod address_alias_registry;
+mod compact_value;
+pub mod delivery_approval_registry;
 mod deserialize_helper;
 mod dynamodb_request_factory;
 pub mod execution_plan_assigner;
+pub mod job_lock;
 pub mod nonce_manager;
+pub mod plan_tag_index;
 pub mod prestart_step_uniqueness_enforcer;
+pub mod relayer_registry;
+pub mod sponsorship_registry;
+pub mod threshold_signing_coordinator;
+pub mod worker_identity;
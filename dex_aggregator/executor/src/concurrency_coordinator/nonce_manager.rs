@@ -16,33 +16,47 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 
 use privadex_chain_metadata::common::{BlockNum, MillisSinceEpoch, Nonce};
 use privadex_common::{
-    utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+    utils::{
+        dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+        general_utils::hex_string_to_vec,
+    },
     uuid::Uuid,
 };
 
 use super::{
     deserialize_helper::{
-        AttributesWrapper, Empty, ItemWrapper, PendingNonceBlockNextResponse,
-        PendingNonceBlockResponse,
+        AttributesWrapper, Empty, ItemWrapper, NonceStateResponse, PendingNonceBlockNextResponse,
+        PendingNonceBlockResponse, SignedPendingNonceResponse,
     },
     dynamodb_request_factory::DynamoDbNonceRequestFactory,
+    worker_identity::{
+        is_signed_by, CoordinationRecord, WorkerIdentity, WorkerSignature, CONTRACT_IDENTITY,
+    },
 };
 
 const DYNAMODB_TABLE_NONCE: &'static str = "privadex_phat_contract";
 
+// Set when the contract is deployed (the block timestamp), so that a redeployment with the
+// same escrow keys does not share nonce state with the deployment before it
+pub type DeploymentEpoch = u64;
+
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum NonceManagerError {
     ConditionalCheckFailed,
+    ReservationsInFlight,
+    SameDeploymentEpoch,
     UnexpectedDeserializationError,
     UnlikelyAllNonceGettersFailed,
+    UnverifiedReservation,
     UpdateFailed,
 }
 impl From<DynamoDbError> for NonceManagerError {
@@ -59,6 +73,13 @@ type Result<T> = core::result::Result<T, NonceManagerError>;
 pub struct NonceManager {
     api: DynamoDbApi,
     request_factory: DynamoDbNonceRequestFactory,
+    chain_name: String,
+    deployment_epoch: DeploymentEpoch,
+    // None for the shared escrow. Each named escrow has nonce state of its own
+    escrow_name: Option<String>,
+    // Signs the reservations (see worker_identity). The contract's identity unless a worker is
+    // stepping a plan forward
+    worker: [u8; 32],
     pub millis_since_epoch: MillisSinceEpoch,
 }
 
@@ -68,18 +89,23 @@ impl NonceManager {
         dynamodb_secret_key: String,
         chain_name: &str,
         millis_since_epoch: MillisSinceEpoch,
+        deployment_epoch: DeploymentEpoch,
     ) -> Self {
-        let key = format!("chainstate_{chain_name}");
         Self {
             api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
-            request_factory: DynamoDbNonceRequestFactory {
-                table_name: DYNAMODB_TABLE_NONCE,
-                key: key.to_string(),
-            },
+            request_factory: Self::create_request_factory(chain_name, deployment_epoch, None),
+            chain_name: chain_name.to_string(),
+            deployment_epoch,
+            escrow_name: None,
+            worker: CONTRACT_IDENTITY,
             millis_since_epoch,
         }
     }
 
+    pub fn set_worker(&mut self, worker: [u8; 32]) {
+        self.worker = worker;
+    }
+
     // Tries each of our 'cases' in sequence (roughly from most to least likely).
     // It is theoretically possible for no nonce to be found (since different cases
     // are non-atomic though an individual case is an atomic transaction), but
@@ -91,16 +117,16 @@ impl NonceManager {
         system_nonce: Nonce,
     ) -> Result<Nonce> {
         if let Ok(nonce) = self.attempt_cold_start(exec_step_uuid, cur_block, system_nonce) {
-            ink_env::debug_println!("Nonce retrieved from cold start");
+            ink::env::debug_println!("Nonce retrieved from cold start");
             Ok(nonce)
         } else if let Ok(nonce) = self.attempt_next_nonce(exec_step_uuid, cur_block) {
-            ink_env::debug_println!("Nonce retrieved from NextNonce");
+            ink::env::debug_println!("Nonce retrieved from NextNonce");
             Ok(nonce)
         } else if let Ok(nonce) = self.attempt_existing_assignment(exec_step_uuid) {
-            ink_env::debug_println!("Nonce retrieved from existing assignment");
+            ink::env::debug_println!("Nonce retrieved from existing assignment");
             Ok(nonce)
         } else if let Ok(nonce) = self.attempt_reclaim_dropped_nonce(exec_step_uuid, cur_block) {
-            ink_env::debug_println!("Nonce retrieved from dropped nonce");
+            ink::env::debug_println!("Nonce retrieved from dropped nonce");
             Ok(nonce)
         } else {
             Err(NonceManagerError::UnlikelyAllNonceGettersFailed)
@@ -147,15 +173,276 @@ impl NonceManager {
         self.drop_execstep(exec_step_uuid, dropped_nonce)
     }
 
+    pub fn get_existing_assignment(&self, exec_step_uuid: &Uuid) -> Result<Nonce> {
+        self.attempt_existing_assignment(exec_step_uuid)
+    }
+
+    // Reserves pool_size consecutive nonces and returns the first one. Fails if there are
+    // dropped nonces, since those must be reused first (through get_nonce)
+    pub fn reserve_nonce_block(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> Result<Nonce> {
+        if let Ok(first_nonce) =
+            self.attempt_cold_start_nonce_block(pool_id, cur_block, system_nonce, pool_size)
+        {
+            ink::env::debug_println!("Nonce block reserved from cold start");
+            Ok(first_nonce)
+        } else if let Ok(first_nonce) =
+            self.attempt_reserve_nonce_block(pool_id, cur_block, system_nonce, pool_size)
+        {
+            ink::env::debug_println!("Nonce block reserved from NextNonce");
+            Ok(first_nonce)
+        } else if let Ok(first_nonce) =
+            self.attempt_reconcile_nonce_block(pool_id, cur_block, system_nonce, pool_size)
+        {
+            ink::env::debug_println!("Nonce block reserved from system nonce");
+            Ok(first_nonce)
+        } else {
+            Err(NonceManagerError::UnlikelyAllNonceGettersFailed)
+        }
+    }
+
+    // Fails with ConditionalCheckFailed if the step already has a nonce (or the block has been
+    // released), in which case the caller should fall back to get_nonce
+    pub fn assign_pooled_nonce(
+        &self,
+        exec_step_uuid: &Uuid,
+        pool_id: &Uuid,
+        nonce: Nonce,
+        cur_block: BlockNum,
+    ) -> Result<()> {
+        let request_payload = self.request_factory.assign_pooled_nonce_request(
+            exec_step_uuid,
+            pool_id,
+            nonce,
+            cur_block,
+            &self.sign_reservation(exec_step_uuid, nonce),
+        );
+        self.send_update_request(&request_payload)
+    }
+
+    pub fn release_nonce_block(&self, pool_id: &Uuid, unused_nonces: &[Nonce]) -> Result<()> {
+        let request_payload = self
+            .request_factory
+            .release_nonce_block_request(pool_id, unused_nonces);
+        self.send_update_request(&request_payload)
+    }
+
+    // Moves the chain's nonce state from prior_epoch's key to this deployment's. The prior key is
+    // sealed first, so a deployment still running on prior_epoch can't hand out any more nonces.
+    // adopt carries the prior reservations over as is (for when this deployment picks up the
+    // prior deployment's in-flight plans). Otherwise they are purged, which is only safe once
+    // none of them can still land on chain i.e. they are all below the system nonce.
+    // Adopted reservations are signed anew for this deployment's key: the prior deployment's
+    // identity keys are not ours, so we take the admin's word (adopt) for them.
+    // Returns the number of reservations that were adopted or purged
+    pub fn migrate_reservations(
+        &self,
+        prior_epoch: DeploymentEpoch,
+        adopt: bool,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+    ) -> Result<u32> {
+        if prior_epoch == self.deployment_epoch {
+            return Err(NonceManagerError::SameDeploymentEpoch);
+        }
+        let prior_request_factory = Self::create_request_factory(
+            &self.chain_name,
+            prior_epoch,
+            self.escrow_name.as_deref(),
+        );
+        let seal_response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                prior_request_factory
+                    .seal_nonce_state_request(self.deployment_epoch)
+                    .as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| NonceManagerError::from(dynamodb_err))?;
+        let (decoded, _): (AttributesWrapper<NonceStateResponse>, usize) =
+            serde_json_core::from_slice(&seal_response)
+                .map_err(|_| NonceManagerError::UnexpectedDeserializationError)?;
+        let prior_state = decoded.Attributes;
+        let reservations = &prior_state.ExecStepPendingNonce.M.entries;
+
+        let migrate_res = if adopt {
+            let nonce_signatures: Vec<(String, WorkerSignature)> = reservations
+                .iter()
+                .filter_map(|(attr, nonce)| {
+                    let exec_step_uuid = exec_step_attr_to_uuid(attr)?;
+                    Some((attr.clone(), self.sign_reservation(&exec_step_uuid, *nonce)))
+                })
+                .collect();
+            self.send_update_request(
+                &self
+                    .request_factory
+                    .adopt_nonce_state_request(&prior_state, &nonce_signatures),
+            )
+        } else if reservations.iter().any(|(_, nonce)| *nonce >= system_nonce) {
+            Err(NonceManagerError::ReservationsInFlight)
+        } else {
+            self.send_update_request(
+                &self
+                    .request_factory
+                    .init_nonce_state_request(cur_block, system_nonce),
+            )
+        };
+        if let Err(e) = migrate_res {
+            // Best effort. If this fails too, the prior key stays sealed until it is restored by
+            // hand (from the state in the debug log)
+            ink::env::debug_println!(
+                "Nonce state migration failed. Prior state: {:?}",
+                prior_state
+            );
+            let _ = self.send_update_request(
+                &prior_request_factory.restore_nonce_state_request(&prior_state),
+            );
+            return Err(e);
+        }
+        Ok(reservations.len() as u32)
+    }
+
+    // Switches to the nonce state of another escrow account on the same chain
+    pub fn set_escrow_name(&mut self, escrow_name: Option<&str>) {
+        self.request_factory =
+            Self::create_request_factory(&self.chain_name, self.deployment_epoch, escrow_name);
+        self.escrow_name = escrow_name.map(|escrow_name| escrow_name.to_string());
+    }
+
+    // Epoch 0 keeps the key from before deployment epochs, so existing deployments carry on
+    // with their nonce state. The same goes for the shared escrow, which has no escrow suffix
+    fn create_request_factory(
+        chain_name: &str,
+        deployment_epoch: DeploymentEpoch,
+        escrow_name: Option<&str>,
+    ) -> DynamoDbNonceRequestFactory {
+        let mut key = if deployment_epoch == 0 {
+            format!("chainstate_{chain_name}")
+        } else {
+            format!("chainstate_{chain_name}_epoch{deployment_epoch}")
+        };
+        if let Some(escrow_name) = escrow_name {
+            key = format!("{key}_escrow_{escrow_name}");
+        }
+        DynamoDbNonceRequestFactory {
+            table_name: DYNAMODB_TABLE_NONCE,
+            key,
+        }
+    }
+
+    fn attempt_cold_start_nonce_block(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> Result<Nonce> {
+        let request_payload = self.request_factory.cold_start_nonce_block_request(
+            pool_id,
+            cur_block,
+            system_nonce,
+            pool_size,
+        );
+        self.send_update_request(&request_payload)?;
+        Ok(system_nonce)
+    }
+
+    fn attempt_reserve_nonce_block(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> Result<Nonce> {
+        let request_payload = self.request_factory.reserve_nonce_block_request(
+            pool_id,
+            cur_block,
+            system_nonce,
+            pool_size,
+        );
+        let reserve_nonce_block_response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| NonceManagerError::from(dynamodb_err))?;
+
+        let (decoded, _): (AttributesWrapper<PendingNonceBlockNextResponse>, usize) =
+            serde_json_core::from_slice(&reserve_nonce_block_response)
+                .map_err(|_| NonceManagerError::UnexpectedDeserializationError)?;
+        Ok(decoded.Attributes.ExecStepPendingNonce.M.num.N)
+    }
+
+    fn attempt_reconcile_nonce_block(
+        &self,
+        pool_id: &Uuid,
+        cur_block: BlockNum,
+        system_nonce: Nonce,
+        pool_size: Nonce,
+    ) -> Result<Nonce> {
+        let request_payload = self.request_factory.reconcile_nonce_block_request(
+            pool_id,
+            cur_block,
+            system_nonce,
+            pool_size,
+        );
+        self.send_update_request(&request_payload)?;
+        Ok(system_nonce)
+    }
+
+    fn sign_reservation(&self, exec_step_uuid: &Uuid, nonce: Nonce) -> WorkerSignature {
+        WorkerIdentity::derive(self.worker).sign(&CoordinationRecord::NonceReservation {
+            nonce_state_key: &self.request_factory.key,
+            exec_step_uuid,
+            nonce,
+        })
+    }
+
+    // For the cases where the nonce is only known once it has been reserved. If this fails,
+    // the reservation stays unsigned and the step can't use it (see the README)
+    fn record_reservation_signature(&self, exec_step_uuid: &Uuid, nonce: Nonce) -> Result<()> {
+        let request_payload = self.request_factory.sign_nonce_reservation_request(
+            exec_step_uuid,
+            nonce,
+            &self.sign_reservation(exec_step_uuid, nonce),
+        );
+        self.send_update_request(&request_payload)
+    }
+
+    fn send_update_request(&self, request_payload: &str) -> Result<()> {
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_or_else(
+                |dynamodb_err| Err(NonceManagerError::from(dynamodb_err)),
+                // We discard the response because we had set return_values to None
+                |_response| Ok(()),
+            )
+    }
+
     fn attempt_cold_start(
         &self,
         exec_step_uuid: &Uuid,
         cur_block: BlockNum,
         system_nonce: Nonce,
     ) -> Result<Nonce> {
-        let request_payload =
-            self.request_factory
-                .cold_start_request(exec_step_uuid, cur_block, system_nonce);
+        let request_payload = self.request_factory.cold_start_request(
+            exec_step_uuid,
+            cur_block,
+            system_nonce,
+            &self.sign_reservation(exec_step_uuid, system_nonce),
+        );
         self.api
             .dynamodb_request(
                 self.millis_since_epoch,
@@ -185,7 +472,9 @@ impl NonceManager {
         let (decoded, _): (AttributesWrapper<PendingNonceBlockNextResponse>, usize) =
             serde_json_core::from_slice(&updated_block_nonce_next_response)
                 .map_err(|_| NonceManagerError::UnexpectedDeserializationError)?;
-        Ok(decoded.Attributes.ExecStepPendingNonce.M.num.N)
+        let nonce = decoded.Attributes.ExecStepPendingNonce.M.num.N;
+        self.record_reservation_signature(exec_step_uuid, nonce)?;
+        Ok(nonce)
     }
 
     fn attempt_existing_assignment(&self, exec_step_uuid: &Uuid) -> Result<Nonce> {
@@ -205,10 +494,33 @@ impl NonceManager {
         // Sort of hacky but we try the item deserialization and then the empty one. ORDER MATTERS
         // The empty deserialization works on a valid object also
         if let Ok((decoded, _)) = serde_json_core::from_slice::<
-            ItemWrapper<PendingNonceBlockResponse>,
+            ItemWrapper<SignedPendingNonceResponse>,
         >(&get_block_nonce_response)
         {
-            Ok(decoded.Item.ExecStepPendingNonce.M.num.N)
+            let nonce = decoded.Item.ExecStepPendingNonce.M.num.N;
+            // Any worker's identity will do, since only the contract can sign as one
+            let signature = decoded
+                .Item
+                .ExecStepNonceSig
+                .and_then(|map_wrapper| map_wrapper.M.entries.into_iter().next())
+                .map(|(_, signature)| signature);
+            let record = CoordinationRecord::NonceReservation {
+                nonce_state_key: &self.request_factory.key,
+                exec_step_uuid,
+                nonce,
+            };
+            let is_verified = signature.as_ref().map_or(false, |signature| {
+                is_signed_by(&record, Some(signature), &signature.signer)
+            });
+            if !is_verified {
+                ink::env::debug_println!(
+                    "Nonce {} reserved for {:?} is not signed by a worker",
+                    nonce,
+                    exec_step_uuid
+                );
+                return Err(NonceManagerError::UnverifiedReservation);
+            }
+            Ok(nonce)
         } else {
             let (_decoded, _): (ItemWrapper<Empty>, usize) =
                 serde_json_core::from_slice(&get_block_nonce_response)
@@ -237,15 +549,24 @@ impl NonceManager {
         let (decoded, _): (AttributesWrapper<PendingNonceBlockResponse>, usize) =
             serde_json_core::from_slice(&reclaim_dropped_nonce_response)
                 .map_err(|_| NonceManagerError::UnexpectedDeserializationError)?;
-        Ok(decoded.Attributes.ExecStepPendingNonce.M.num.N)
+        let nonce = decoded.Attributes.ExecStepPendingNonce.M.num.N;
+        self.record_reservation_signature(exec_step_uuid, nonce)?;
+        Ok(nonce)
     }
 }
 
+// The inverse of DynamoDbNonceRequestFactory's exec step attribute. None for nonce pools
+fn exec_step_attr_to_uuid(attr: &str) -> Option<Uuid> {
+    let hex_uuid = attr.strip_prefix("execstep_")?;
+    let uuid: [u8; 16] = hex_string_to_vec(hex_uuid).ok()?.try_into().ok()?;
+    Some(Uuid::new(uuid))
+}
+
 #[cfg(feature = "dynamodb-live-test")]
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod nonce_manager_tests {
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     use super::*;
 
@@ -272,9 +593,34 @@ mod nonce_manager_tests {
             dynamodb_secret_key,
             chain_name,
             millis_since_epoch,
+            0,
         )
     }
 
+    #[test]
+    fn test_request_factory_key() {
+        assert_eq!(
+            NonceManager::create_request_factory("astar", 0, None).key,
+            "chainstate_astar"
+        );
+        assert_eq!(
+            NonceManager::create_request_factory("astar", 1_680_307_200_000, Some("acme")).key,
+            "chainstate_astar_epoch1680307200000_escrow_acme"
+        );
+    }
+
+    #[test]
+    fn test_exec_step_attr_to_uuid() {
+        assert_eq!(
+            exec_step_attr_to_uuid("execstep_0x01010101010101010101010101010101"),
+            Some(Uuid::new([1u8; 16]))
+        );
+        assert_eq!(
+            exec_step_attr_to_uuid("noncepool_0x01010101010101010101010101010101"),
+            None
+        );
+    }
+
     #[test]
     fn test_cold_start() {
         pink_extension_runtime::mock_ext::mock_all_ext();
@@ -311,6 +657,24 @@ mod nonce_manager_tests {
         debug_println!("[Expected] Reclaim dropped nonce attempt: {:?}", res);
     }
 
+    #[test]
+    fn test_nonce_block() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let nonce_manager = nonce_manager();
+        let pool_id = Uuid::new([7u8; 16]);
+        let first_nonce = nonce_manager
+            .reserve_nonce_block(&pool_id, 10_000, 50, 3)
+            .expect("Reserve nonce block should succeed in a single-worker setting");
+        nonce_manager
+            .assign_pooled_nonce(&Uuid::new([8u8; 16]), &pool_id, first_nonce, 10_000)
+            .expect("Assign pooled nonce should succeed");
+        nonce_manager
+            .release_nonce_block(&pool_id, &[first_nonce + 1, first_nonce + 2])
+            .expect("Release nonce block should succeed");
+        debug_println!("Nonce block starting at {}", first_nonce);
+    }
+
     // This is a time-consuming test (takes 3-4 seconds) so we filter it out
     #[test]
     #[ignore]
@@ -0,0 +1,107 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::{
+    utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+    uuid::Uuid,
+};
+
+use super::{
+    deserialize_helper::{OptionalItemWrapper, PlanTagResponse},
+    dynamodb_request_factory::DynamoDbPlanTagRequestFactory,
+};
+
+const DYNAMODB_TABLE_PLAN_TAG: &'static str = "privadex_phat_contract";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PlanTagIndexError {
+    ConditionalCheckFailed,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for PlanTagIndexError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, PlanTagIndexError>;
+
+/// Secondary index from an integrator's tag (the plan's metadata) to the ExecutionPlan, so
+/// integrators can find a plan by their own order ID. Tags are hashed by the caller, which
+/// also scopes them per integrator
+pub struct PlanTagIndex {
+    api: DynamoDbApi,
+    request_factory: DynamoDbPlanTagRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl PlanTagIndex {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbPlanTagRequestFactory {
+                table_name: DYNAMODB_TABLE_PLAN_TAG,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    pub fn set_tag(&self, tag_hash: &[u8; 16], exec_plan_uuid: &Uuid) -> Result<()> {
+        let request_payload = self.request_factory.set_plan_tag_request(
+            tag_hash,
+            exec_plan_uuid,
+            self.millis_since_epoch,
+        );
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| PlanTagIndexError::from(dynamodb_err))?;
+        Ok(())
+    }
+
+    pub fn get_tag(&self, tag_hash: &[u8; 16]) -> Result<Option<Uuid>> {
+        let request_payload = self.request_factory.get_plan_tag_request(tag_hash);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| PlanTagIndexError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<PlanTagResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| PlanTagIndexError::UnexpectedDeserializationError)?;
+        Ok(decoded.Item.map(|item| item.ExecPlanUuid.S.0))
+    }
+}
@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::string::{String, ToString};
+use ink::prelude::string::{String, ToString};
 
 use privadex_chain_metadata::common::{EthTxnHash, MillisSinceEpoch};
 use privadex_common::utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError};
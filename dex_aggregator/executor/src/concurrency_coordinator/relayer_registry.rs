@@ -0,0 +1,204 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, MillisSinceEpoch, UniversalTokenId};
+use privadex_common::{
+    utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+    uuid::Uuid,
+};
+
+use super::{
+    deserialize_helper::{
+        OptionalItemWrapper, RelayerContributionResponse, RelayerFeePoolResponse,
+    },
+    dynamodb_request_factory::DynamoDbRelayerRequestFactory,
+};
+
+const DYNAMODB_TABLE_RELAYER: &'static str = "privadex_phat_contract";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RelayerRegistryError {
+    // The plan's fee was already added, the pool is short, or a concurrent claim won
+    ConditionalCheckFailed,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for RelayerRegistryError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, RelayerRegistryError>;
+
+// What a worker has done for the network so far. All zero for a worker we have never seen
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RelayerContribution {
+    pub num_steps_advanced: u32,
+    // Gas the escrow paid for the txns this worker broadcast ($ x 10^18)
+    pub gas_fee_usd: Amount,
+    // Rewards paid out (or being paid out) to this worker so far ($ x 10^18)
+    pub claimed_usd: Amount,
+}
+
+/// Per-worker contributions and the protocol fees set aside to reward them. Anyone can run a
+/// worker, so these are keyed by the caller of execution_plan_step_forward. The fee pool is
+/// kept per payout token, in that token's units
+pub struct RelayerRegistry {
+    api: DynamoDbApi,
+    request_factory: DynamoDbRelayerRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl RelayerRegistry {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbRelayerRequestFactory {
+                table_name: DYNAMODB_TABLE_RELAYER,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    pub fn record_contribution(
+        &self,
+        worker: &[u8; 32],
+        num_steps_advanced: u32,
+        gas_fee_usd: Amount,
+    ) -> Result<()> {
+        let request_payload = self.request_factory.record_contribution_request(
+            worker,
+            num_steps_advanced,
+            gas_fee_usd,
+            self.millis_since_epoch,
+        );
+        self.update_item(request_payload)
+    }
+
+    pub fn get_contribution(&self, worker: &[u8; 32]) -> Result<RelayerContribution> {
+        let request_payload = self.request_factory.get_contribution_request(worker);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| RelayerRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<RelayerContributionResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| RelayerRegistryError::UnexpectedDeserializationError)?;
+        Ok(match decoded.Item {
+            Some(item) => RelayerContribution {
+                num_steps_advanced: item.NumStepsAdvanced.map_or(0, |num| num.N),
+                gas_fee_usd: item.GasFeeUsd.map_or(0, |amount| amount.N),
+                claimed_usd: item.ClaimedUsd.map_or(0, |amount| amount.N),
+            },
+            None => RelayerContribution::default(),
+        })
+    }
+
+    // prev_claimed_usd must be what get_contribution returned, so that two concurrent claims
+    // cannot both go through
+    pub fn record_claim(
+        &self,
+        worker: &[u8; 32],
+        prev_claimed_usd: Amount,
+        new_claimed_usd: Amount,
+    ) -> Result<()> {
+        let request_payload = self.request_factory.record_claim_request(
+            worker,
+            prev_claimed_usd,
+            new_claimed_usd,
+            self.millis_since_epoch,
+        );
+        self.update_item(request_payload)
+    }
+
+    pub fn fund_fee_pool(
+        &self,
+        token: &UniversalTokenId,
+        exec_plan_uuid: &Uuid,
+        amount: Amount,
+    ) -> Result<()> {
+        let request_payload = self.request_factory.fund_fee_pool_request(
+            token,
+            exec_plan_uuid,
+            amount,
+            self.millis_since_epoch,
+        );
+        self.update_item(request_payload)
+    }
+
+    pub fn debit_fee_pool(&self, token: &UniversalTokenId, amount: Amount) -> Result<()> {
+        let request_payload =
+            self.request_factory
+                .debit_fee_pool_request(token, amount, self.millis_since_epoch);
+        self.update_item(request_payload)
+    }
+
+    pub fn credit_fee_pool(&self, token: &UniversalTokenId, amount: Amount) -> Result<()> {
+        let request_payload =
+            self.request_factory
+                .credit_fee_pool_request(token, amount, self.millis_since_epoch);
+        self.update_item(request_payload)
+    }
+
+    pub fn get_fee_pool(&self, token: &UniversalTokenId) -> Result<Amount> {
+        let request_payload = self.request_factory.get_fee_pool_request(token);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| RelayerRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<RelayerFeePoolResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| RelayerRegistryError::UnexpectedDeserializationError)?;
+        Ok(decoded
+            .Item
+            .and_then(|item| item.FeesAvailable)
+            .map_or(0, |amount| amount.N))
+    }
+
+    fn update_item(&self, request_payload: String) -> Result<()> {
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| RelayerRegistryError::from(dynamodb_err))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+
+use privadex_chain_metadata::common::{Amount, MillisSinceEpoch};
+use privadex_common::{
+    utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError},
+    uuid::Uuid,
+};
+
+use super::{
+    deserialize_helper::{OptionalItemWrapper, SponsoredPlanResponse, SponsorshipResponse},
+    dynamodb_request_factory::DynamoDbSponsorshipRequestFactory,
+};
+
+const DYNAMODB_TABLE_SPONSORSHIP: &'static str = "privadex_phat_contract";
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SponsorshipRegistryError {
+    // The amount is more than the campaign has left
+    BudgetExhausted,
+    // The plan was already paid for (or credited back), or a concurrent drawdown used up the
+    // budget
+    ConditionalCheckFailed,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for SponsorshipRegistryError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, SponsorshipRegistryError>;
+
+/// How much of each sponsorship campaign's USD budget its plans have used. The budget itself is
+/// contract config, so it is passed in on every drawdown. Each sponsored plan also has an item
+/// of its own with what it drew down, so that it is paid for, and credited back, at most once
+pub struct SponsorshipRegistry {
+    api: DynamoDbApi,
+    request_factory: DynamoDbSponsorshipRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl SponsorshipRegistry {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbSponsorshipRequestFactory {
+                table_name: DYNAMODB_TABLE_SPONSORSHIP,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    // Either the whole amount fits in what is left of budget_usd and is drawn down for the
+    // plan, or nothing is
+    pub fn draw_down(
+        &self,
+        campaign_id: &str,
+        exec_plan_uuid: &Uuid,
+        amount_usd: Amount,
+        budget_usd: Amount,
+    ) -> Result<()> {
+        let max_prev_spent_usd = budget_usd
+            .checked_sub(amount_usd)
+            .ok_or(SponsorshipRegistryError::BudgetExhausted)?;
+        self.update_item(self.request_factory.record_plan_drawdown_request(
+            campaign_id,
+            exec_plan_uuid,
+            amount_usd,
+            self.millis_since_epoch,
+        ))?;
+        let draw_down_res = self.update_item(self.request_factory.draw_down_request(
+            campaign_id,
+            amount_usd,
+            max_prev_spent_usd,
+            self.millis_since_epoch,
+        ));
+        if draw_down_res.is_err() {
+            // Nothing was drawn down, so nothing must be credited back for the plan either
+            let _ = self.update_item(self.request_factory.claim_plan_refund_request(
+                exec_plan_uuid,
+                amount_usd,
+                self.millis_since_epoch,
+            ));
+        }
+        draw_down_res
+    }
+
+    // Credits campaign_id back what the plan drew down, less spent_usd (e.g. the gas the plan
+    // used). Returns the amount credited back. Only the first call for a plan goes through, and
+    // if the second write fails, the credit is lost rather than made twice
+    pub fn credit_back(
+        &self,
+        campaign_id: &str,
+        exec_plan_uuid: &Uuid,
+        spent_usd: Amount,
+    ) -> Result<Amount> {
+        let drawn_usd = self.get_plan_drawn_usd(exec_plan_uuid)?;
+        let credit_usd = drawn_usd.saturating_sub(spent_usd);
+        self.update_item(self.request_factory.claim_plan_refund_request(
+            exec_plan_uuid,
+            credit_usd,
+            self.millis_since_epoch,
+        ))?;
+        if credit_usd > 0 {
+            self.update_item(self.request_factory.refund_request(
+                campaign_id,
+                credit_usd,
+                self.millis_since_epoch,
+            ))?;
+        }
+        Ok(credit_usd)
+    }
+
+    pub fn get_spent_usd(&self, campaign_id: &str) -> Result<Amount> {
+        let request_payload = self.request_factory.get_spent_request(campaign_id);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| SponsorshipRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<SponsorshipResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| SponsorshipRegistryError::UnexpectedDeserializationError)?;
+        Ok(decoded
+            .Item
+            .and_then(|item| item.SpentUsd)
+            .map_or(0, |amount| amount.N))
+    }
+
+    // 0 for a plan that was never paid for
+    fn get_plan_drawn_usd(&self, exec_plan_uuid: &Uuid) -> Result<Amount> {
+        let request_payload = self
+            .request_factory
+            .get_plan_drawdown_request(exec_plan_uuid);
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::GetItem,
+            )
+            .map_err(|dynamodb_err| SponsorshipRegistryError::from(dynamodb_err))?;
+        let (decoded, _): (OptionalItemWrapper<SponsoredPlanResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| SponsorshipRegistryError::UnexpectedDeserializationError)?;
+        Ok(decoded.Item.map_or(0, |item| item.DrawnUsd.N))
+    }
+
+    fn update_item(&self, request_payload: String) -> Result<()> {
+        self.api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| SponsorshipRegistryError::from(dynamodb_err))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+
+use privadex_chain_metadata::common::{EthAddress, MillisSinceEpoch, Nonce};
+use privadex_common::utils::dynamodb_api::{DynamoDbAction, DynamoDbApi, DynamoDbError};
+
+use super::{
+    deserialize_helper::{AttributesWrapper, ThresholdPartialsResponse, ThresholdRoundResponse},
+    dynamodb_request_factory::DynamoDbThresholdSigRequestFactory,
+};
+
+const DYNAMODB_TABLE_THRESHOLD_SIG: &'static str = "privadex_phat_contract";
+// The deserializer has a fixed number of partial signature slots
+pub const MAX_THRESHOLD_PARTIES: u8 = 3;
+// Parties that sign within the same window sign the same pinned txn
+pub const THRESHOLD_ROUND_MILLIS: MillisSinceEpoch = 5 * 60 * 1000;
+// Rounds and partial signatures are only useful for a few windows, but DynamoDB TTL may take a
+// while to delete them anyway
+pub const THRESHOLD_ITEM_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ThresholdSigningCoordinatorError {
+    ConditionalCheckFailed,
+    InvalidPartyIndex,
+    UnexpectedDeserializationError,
+    UpdateFailed,
+}
+impl From<DynamoDbError> for ThresholdSigningCoordinatorError {
+    fn from(e: DynamoDbError) -> Self {
+        match e {
+            DynamoDbError::GenericRequestFailed => Self::UpdateFailed,
+            DynamoDbError::ConditionalCheckFailed => Self::ConditionalCheckFailed,
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, ThresholdSigningCoordinatorError>;
+
+/// Collects the partial signatures that independent workers produce for the same message
+/// hash. Each worker adds its own partial when it gets to the step, so a signature is only
+/// complete once `threshold` different workers have attempted the step. The first party of a
+/// round pins its txn so that the others sign the same message hash
+pub struct ThresholdSigningCoordinator {
+    api: DynamoDbApi,
+    request_factory: DynamoDbThresholdSigRequestFactory,
+    pub millis_since_epoch: MillisSinceEpoch,
+}
+
+impl ThresholdSigningCoordinator {
+    pub fn new(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        millis_since_epoch: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            api: DynamoDbApi::new(dynamodb_access_key, dynamodb_secret_key),
+            request_factory: DynamoDbThresholdSigRequestFactory {
+                table_name: DYNAMODB_TABLE_THRESHOLD_SIG,
+            },
+            millis_since_epoch,
+        }
+    }
+
+    // Idempotent, so a worker can re-add its partial on every signing round.
+    // Returns (party index, partial signature) for every party that has signed msg_hash
+    pub fn add_partial_signature(
+        &self,
+        msg_hash: &[u8; 32],
+        party_index: u8,
+        partial_sig: &[u8],
+    ) -> Result<Vec<(u8, Vec<u8>)>> {
+        if party_index >= MAX_THRESHOLD_PARTIES {
+            return Err(ThresholdSigningCoordinatorError::InvalidPartyIndex);
+        }
+        let request_payload = self.request_factory.add_partial_signature_request(
+            msg_hash,
+            party_index,
+            partial_sig,
+            self.millis_since_epoch,
+        );
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| ThresholdSigningCoordinatorError::from(dynamodb_err))?;
+        let (decoded, _): (AttributesWrapper<ThresholdPartialsResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| ThresholdSigningCoordinatorError::UnexpectedDeserializationError)?;
+        let partials = decoded.Attributes;
+        Ok([partials.Partial0, partials.Partial1, partials.Partial2]
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, opt_partial)| opt_partial.map(|partial| (index as u8, partial.S)))
+            .collect())
+    }
+
+    // Returns the txn pinned for the current round of (address, chain_id, nonce), which is
+    // round_txn if no other party got there first. The caller must check a txn pinned by
+    // another party before signing it
+    pub fn pin_round_txn(
+        &self,
+        address: &EthAddress,
+        chain_id: u64,
+        nonce: Nonce,
+        round_txn: &[u8],
+    ) -> Result<Vec<u8>> {
+        let request_payload = self.request_factory.pin_round_txn_request(
+            address,
+            chain_id,
+            nonce,
+            round_txn,
+            self.millis_since_epoch,
+        );
+        let response = self
+            .api
+            .dynamodb_request(
+                self.millis_since_epoch,
+                request_payload.as_bytes(),
+                DynamoDbAction::UpdateItem,
+            )
+            .map_err(|dynamodb_err| ThresholdSigningCoordinatorError::from(dynamodb_err))?;
+        let (decoded, _): (AttributesWrapper<ThresholdRoundResponse>, usize) =
+            serde_json_core::from_slice(&response)
+                .map_err(|_| ThresholdSigningCoordinatorError::UnexpectedDeserializationError)?;
+        Ok(decoded.Attributes.RoundTxn.S)
+    }
+}
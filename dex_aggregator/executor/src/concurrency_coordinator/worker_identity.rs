@@ -0,0 +1,178 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+use pink_extension::chain_extension::{signing, SigType};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{MillisSinceEpoch, Nonce};
+use privadex_common::{signature_scheme::SignatureScheme, uuid::Uuid};
+
+use super::compact_value::{decode_compact_value, encode_compact_value};
+
+// Keeps the identity keys apart from any other key the contract derives
+const WORKER_IDENTITY_SALT: &[u8] = b"privadex_worker_identity";
+// Signs the writes that are not made on behalf of a worker, e.g. admin claims
+pub const CONTRACT_IDENTITY: [u8; 32] = [0u8; 32];
+
+// What a signature covers. Each kind of write gets its own variant so that a signature can't
+// be replayed as another kind of write
+#[derive(Encode, Debug, PartialEq, Eq, Clone)]
+pub(super) enum CoordinationRecord<'a> {
+    Claim {
+        exec_plan_uuid: &'a Uuid,
+        claimed_millis: MillisSinceEpoch,
+    },
+    Release {
+        exec_plan_uuid: &'a Uuid,
+        next_poll_after_millis: MillisSinceEpoch,
+    },
+    // Bound to the item (i.e. chain, deployment epoch and escrow) the nonce was reserved in
+    NonceReservation {
+        nonce_state_key: &'a str,
+        exec_step_uuid: &'a Uuid,
+        nonce: Nonce,
+    },
+}
+
+/// A signature over one coordination-store write, stored next to the write as a compact value
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct WorkerSignature {
+    pub signer: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl WorkerSignature {
+    pub fn to_compact_string(&self) -> String {
+        encode_compact_value(&self.encode())
+    }
+
+    // Also reads the hex strings that signatures were stored as before
+    pub fn from_compact_string(value_str: &str) -> Option<Self> {
+        let bytes = decode_compact_value(value_str)?;
+        Self::decode(&mut bytes.as_slice()).ok()
+    }
+
+    // Whether expected_signer's identity key signed the record. A leaked AWS credential is
+    // enough to write to the coordination store, but not to sign, since the identity keys
+    // never leave the contract
+    pub(super) fn verify(&self, record: &CoordinationRecord, expected_signer: &[u8; 32]) -> bool {
+        if self.signer != *expected_signer {
+            return false;
+        }
+        let pubkey = WorkerIdentity::derive(self.signer).public_key();
+        SignatureScheme::Sr25519.verify(&pubkey, &record.encode(), &self.signature)
+    }
+}
+
+/// A worker's signing key for its coordination-store writes. It is derived inside the contract
+/// from the worker's account, so only calls from that account can sign as it
+pub struct WorkerIdentity {
+    worker: [u8; 32],
+    secret_key: Vec<u8>,
+}
+
+impl WorkerIdentity {
+    pub fn derive(worker: [u8; 32]) -> Self {
+        let salt = [WORKER_IDENTITY_SALT, &worker].concat();
+        Self {
+            worker,
+            secret_key: pink_extension::ext().derive_sr25519_key(salt.into()),
+        }
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        signing::get_public_key(&self.secret_key, SigType::Sr25519)
+    }
+
+    pub(super) fn sign(&self, record: &CoordinationRecord) -> WorkerSignature {
+        WorkerSignature {
+            signer: self.worker,
+            signature: SignatureScheme::Sr25519.sign(&record.encode(), &self.secret_key),
+        }
+    }
+}
+
+// Missing signatures (e.g. written before signing, or malformed) do not verify
+pub(super) fn is_signed_by(
+    record: &CoordinationRecord,
+    signature: Option<&WorkerSignature>,
+    expected_signer: &[u8; 32],
+) -> bool {
+    signature.map_or(false, |signature| signature.verify(record, expected_signer))
+}
+
+#[cfg(test)]
+mod worker_identity_tests {
+    use privadex_common::utils::general_utils::slice_to_hex_string;
+
+    use super::*;
+
+    const WORKER: [u8; 32] = [3u8; 32];
+
+    fn claim_record(exec_plan_uuid: &Uuid) -> CoordinationRecord {
+        CoordinationRecord::Claim {
+            exec_plan_uuid,
+            claimed_millis: 1_690_000_012_000,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let exec_plan_uuid = Uuid::new([1u8; 16]);
+        let record = claim_record(&exec_plan_uuid);
+        let signature = WorkerIdentity::derive(WORKER).sign(&record);
+        assert_eq!(
+            WorkerSignature::from_compact_string(&signature.to_compact_string()),
+            Some(signature.clone())
+        );
+        assert_eq!(
+            WorkerSignature::from_compact_string(&slice_to_hex_string(&signature.encode())),
+            Some(signature.clone())
+        );
+        assert_eq!(WorkerSignature::from_compact_string("0x1234"), None);
+        assert!(is_signed_by(&record, Some(&signature), &WORKER));
+
+        // Another worker, another record, or no signature at all
+        assert!(!is_signed_by(&record, Some(&signature), &CONTRACT_IDENTITY));
+        let other_exec_plan_uuid = Uuid::new([2u8; 16]);
+        assert!(!is_signed_by(
+            &claim_record(&other_exec_plan_uuid),
+            Some(&signature),
+            &WORKER
+        ));
+        let release_record = CoordinationRecord::Release {
+            exec_plan_uuid: &exec_plan_uuid,
+            next_poll_after_millis: 1_690_000_012_000,
+        };
+        assert!(!is_signed_by(&release_record, Some(&signature), &WORKER));
+        assert!(!is_signed_by(&record, None, &WORKER));
+    }
+
+    #[test]
+    fn test_forged_signer() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let exec_plan_uuid = Uuid::new([1u8; 16]);
+        let record = claim_record(&exec_plan_uuid);
+        // A signature made with another key, relabelled as WORKER's
+        let mut forged = WorkerIdentity::derive([4u8; 32]).sign(&record);
+        forged.signer = WORKER;
+        assert!(!is_signed_by(&record, Some(&forged), &WORKER));
+    }
+}
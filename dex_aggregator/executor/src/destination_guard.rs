@@ -0,0 +1,118 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Catches destinations that would silently burn the delivered tokens: the zero address, token
+// contracts (users paste the token's address instead of their own more often than you'd
+// think) and precompiles. None of these can send the tokens back out
+use privadex_chain_metadata::{
+    chain_info::ChainInfo,
+    common::EthAddress,
+    registry::token::{token_decimals_registry, universal_token_id_registry},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum DestinationGuardError {
+    Precompile,
+    TokenContract,
+    ZeroAddress,
+}
+pub type Result<T> = core::result::Result<T, DestinationGuardError>;
+
+pub fn check_destination_address(
+    dest_chain_info: &ChainInfo,
+    dest_addr: &EthAddress,
+) -> Result<()> {
+    if dest_addr.is_zero() {
+        return Err(DestinationGuardError::ZeroAddress);
+    }
+    let potential_token = universal_token_id_registry::chain_and_eth_addr_to_token(
+        dest_chain_info.chain_id,
+        *dest_addr,
+    );
+    if dest_chain_info.weth_addr.as_ref() == Some(dest_addr)
+        || token_decimals_registry::get_decimals(&potential_token).is_some()
+    {
+        return Err(DestinationGuardError::TokenContract);
+    }
+    if dest_chain_info.is_evm_precompile(dest_addr) {
+        return Err(DestinationGuardError::Precompile);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod destination_guard_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::registry::chain::chain_info_registry::{
+        ASTAR_INFO, MOONBEAM_INFO, POLKADOT_INFO,
+    };
+
+    use super::*;
+
+    fn addr(raw: [u8; 20]) -> EthAddress {
+        EthAddress { 0: raw }
+    }
+
+    #[test]
+    fn test_check_destination_address() {
+        let user = addr(hex!("42b7d766824422f499f84703ec4e2abb273171cf"));
+        assert_eq!(check_destination_address(&MOONBEAM_INFO, &user), Ok(()));
+        assert_eq!(
+            check_destination_address(&MOONBEAM_INFO, &EthAddress::zero()),
+            Err(DestinationGuardError::ZeroAddress)
+        );
+        // WGLMR
+        assert_eq!(
+            check_destination_address(
+                &MOONBEAM_INFO,
+                &addr(hex!("acc15dc74880c9944775448304b263d191c6077f"))
+            ),
+            Err(DestinationGuardError::TokenContract)
+        );
+        // xTokens
+        assert_eq!(
+            check_destination_address(
+                &MOONBEAM_INFO,
+                &addr(hex!("0000000000000000000000000000000000000804"))
+            ),
+            Err(DestinationGuardError::Precompile)
+        );
+        // xcDOT is an XC20, i.e. a token and a precompile
+        let xcdot = addr(hex!("ffffffff1fcacbd218edc0eba20fc2308c778080"));
+        assert_eq!(
+            check_destination_address(&MOONBEAM_INFO, &xcdot),
+            Err(DestinationGuardError::TokenContract)
+        );
+        // An unregistered XC20
+        assert_eq!(
+            check_destination_address(
+                &ASTAR_INFO,
+                &addr(hex!("ffffffff00000000000000000000000000000123"))
+            ),
+            Err(DestinationGuardError::Precompile)
+        );
+        // Polkadot has no EVM and so no precompiles
+        assert_eq!(
+            check_destination_address(
+                &POLKADOT_INFO,
+                &addr(hex!("0000000000000000000000000000000000000804"))
+            ),
+            Ok(())
+        );
+    }
+}
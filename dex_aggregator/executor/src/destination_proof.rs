@@ -0,0 +1,180 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Proof that whoever starts a swap controls its destination, for deployments that would rather
+// reject a swap than deliver to a mistyped address (see set_require_destination_proof). The
+// destination signs get_destination_challenge once and the proof is kept in S3, so later swaps
+// to the same destination don't need a new signature
+use ink::prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::EthAddress;
+use privadex_common::{
+    signature_scheme::SignatureScheme, utils::general_utils::slice_to_hex_string,
+};
+
+const DESTINATION_PROOF_OBJECT_KEY_PREFIX: &str = "destination-proof";
+
+#[derive(Debug, PartialEq)]
+pub enum DestinationProofError {
+    InvalidSignature,
+    SignerIsNotDestination,
+}
+pub type Result<T> = core::result::Result<T, DestinationProofError>;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DestinationProof {
+    // personal_sign of the challenge (65 bytes, v may be 0/1 or 27/28). The signer is recovered
+    // from the signature
+    Ethereum(Vec<u8>),
+    // For a destination that is the EVM address of a substrate account, i.e. the first 20 bytes
+    // of its pubkey (polkadot.js' addressToEvm)
+    Sr25519 {
+        pubkey: [u8; 32],
+        signature: Vec<u8>,
+    },
+}
+
+impl DestinationProof {
+    pub fn verify(&self, dest_addr: &EthAddress) -> Result<()> {
+        let challenge = get_destination_challenge(dest_addr).into_bytes();
+        if &self.recover_signer(&challenge)? == dest_addr {
+            Ok(())
+        } else {
+            Err(DestinationProofError::SignerIsNotDestination)
+        }
+    }
+
+    // The EVM address that signed challenge. verify checks it against the destination, and
+    // plan_public_view against the plan's user
+    pub fn recover_signer(&self, challenge: &[u8]) -> Result<EthAddress> {
+        let signer = match self {
+            Self::Ethereum(signature) => {
+                let signature: [u8; 65] = signature
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| DestinationProofError::InvalidSignature)?;
+                let msg_hash =
+                    sp_core_hashing::keccak_256(&SignatureScheme::Ethereum.prefix_msg(challenge));
+                let mut pubkey = [0u8; 33];
+                ink::env::ecdsa_recover(&signature, &msg_hash, &mut pubkey)
+                    .map_err(|_| DestinationProofError::InvalidSignature)?;
+                let mut signer = EthAddress::zero();
+                ink::env::ecdsa_to_eth_address(&pubkey, &mut signer.0)
+                    .map_err(|_| DestinationProofError::InvalidSignature)?;
+                signer
+            }
+            Self::Sr25519 { pubkey, signature } => {
+                if !SignatureScheme::Sr25519.verify_unprefixed_msg(pubkey, challenge, signature) {
+                    return Err(DestinationProofError::InvalidSignature);
+                }
+                let mut signer = EthAddress::zero();
+                signer.0.copy_from_slice(&pubkey[..20]);
+                signer
+            }
+        };
+        Ok(signer)
+    }
+}
+
+// What the destination signs. It names the destination, so a proof can't be replayed for
+// another address
+pub fn get_destination_challenge(dest_addr: &EthAddress) -> String {
+    format!(
+        "PrivaDEX: I control {} and want my swaps delivered to it",
+        slice_to_hex_string(&dest_addr.0)
+    )
+}
+
+pub fn get_destination_proof_object_key(dest_addr: &EthAddress) -> String {
+    format!(
+        "{}-{}",
+        DESTINATION_PROOF_OBJECT_KEY_PREFIX,
+        &slice_to_hex_string(&dest_addr.0)[2..]
+    )
+}
+
+#[cfg(test)]
+mod destination_proof_tests {
+    use hex_literal::hex;
+    use pink_extension::chain_extension::{signing, SigType};
+
+    use super::*;
+
+    const SECRET_KEY: [u8; 32] =
+        hex!("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a"); // Alice
+
+    fn eth_address_of(secret_key: &[u8]) -> EthAddress {
+        let pubkey: [u8; 33] = signing::get_public_key(secret_key, SigType::Ecdsa)
+            .try_into()
+            .unwrap();
+        let mut address = EthAddress::zero();
+        ink::env::ecdsa_to_eth_address(&pubkey, &mut address.0).unwrap();
+        address
+    }
+
+    #[test]
+    fn test_ethereum_proof() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let dest_addr = eth_address_of(&SECRET_KEY);
+        let proof = DestinationProof::Ethereum(SignatureScheme::Ethereum.prefix_then_sign_msg(
+            get_destination_challenge(&dest_addr).as_bytes(),
+            &SECRET_KEY,
+        ));
+        assert_eq!(proof.verify(&dest_addr), Ok(()));
+        assert_eq!(
+            proof.verify(&EthAddress::zero()),
+            Err(DestinationProofError::SignerIsNotDestination)
+        );
+        assert_eq!(
+            DestinationProof::Ethereum(vec![0u8; 64]).verify(&dest_addr),
+            Err(DestinationProofError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_sr25519_proof() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let pubkey: [u8; 32] = signing::get_public_key(&SECRET_KEY, SigType::Sr25519)
+            .try_into()
+            .unwrap();
+        let mut dest_addr = EthAddress::zero();
+        dest_addr.0.copy_from_slice(&pubkey[..20]);
+        let proof = DestinationProof::Sr25519 {
+            pubkey,
+            signature: SignatureScheme::Sr25519.sign(
+                get_destination_challenge(&dest_addr).as_bytes(),
+                &SECRET_KEY,
+            ),
+        };
+        assert_eq!(proof.verify(&dest_addr), Ok(()));
+        // Signed for another destination
+        let other_proof = DestinationProof::Sr25519 {
+            pubkey,
+            signature: SignatureScheme::Sr25519.sign(
+                get_destination_challenge(&EthAddress::zero()).as_bytes(),
+                &SECRET_KEY,
+            ),
+        };
+        assert_eq!(
+            other_proof.verify(&dest_addr),
+            Err(DestinationProofError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,232 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use ink::prelude::{string::String, vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{
+    EthAddress, SecretKey, SubstratePublicKey, UniversalAddress,
+};
+use privadex_common::signature_scheme::SignatureScheme;
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use crate::key_container::{AddressKeyPair, KeyContainer, SigningKey};
+
+pub const MAX_ESCROW_KEY_SETS: usize = 8;
+// Names end up in DynamoDB keys, so keep them short and plain
+const MAX_ESCROW_NAME_LEN: usize = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum EscrowKeySetError {
+    AlreadyExists,
+    InvalidName,
+    TooMany,
+}
+pub type Result<T> = core::result::Result<T, EscrowKeySetError>;
+
+/// Escrow accounts of their own for integrators whose funds must not mix with the shared
+/// escrow's. A plan started with an escrow name sends from and to these accounts instead (see
+/// set_plan_escrow), and signs with these keys only. Only local keys are supported, i.e. not
+/// the remote or threshold signers of the shared escrow
+#[derive(Encode, Decode, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EscrowKeySet {
+    pub name: String,
+    pub eth_address: EthAddress,
+    pub substrate_pubkey: SubstratePublicKey,
+    pub substrate_sig_scheme: SignatureScheme,
+    eth_private_key: SecretKey,
+    substrate_private_key: SecretKey,
+}
+
+// Do not print the keys
+impl core::fmt::Debug for EscrowKeySet {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "EscrowKeySet({}, {:?})", self.name, self.eth_address)
+    }
+}
+
+/// What get_escrow_accounts returns about an EscrowKeySet
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EscrowAccount {
+    pub name: String,
+    pub eth_address: EthAddress,
+    pub substrate_pubkey: SubstratePublicKey,
+}
+
+impl EscrowKeySet {
+    // The caller derives the addresses from the keys
+    pub fn new(
+        name: String,
+        eth_address: EthAddress,
+        eth_private_key: SecretKey,
+        substrate_pubkey: SubstratePublicKey,
+        substrate_private_key: SecretKey,
+        substrate_sig_scheme: SignatureScheme,
+    ) -> Result<Self> {
+        if !is_valid_escrow_name(&name) {
+            return Err(EscrowKeySetError::InvalidName);
+        }
+        Ok(Self {
+            name,
+            eth_address,
+            substrate_pubkey,
+            substrate_sig_scheme,
+            eth_private_key,
+            substrate_private_key,
+        })
+    }
+
+    pub fn get_account(&self) -> EscrowAccount {
+        EscrowAccount {
+            name: self.name.clone(),
+            eth_address: self.eth_address,
+            substrate_pubkey: self.substrate_pubkey,
+        }
+    }
+
+    pub fn get_addresses(&self) -> Vec<UniversalAddress> {
+        vec![
+            UniversalAddress::Ethereum(self.eth_address),
+            UniversalAddress::Substrate(self.substrate_pubkey),
+        ]
+    }
+
+    pub fn to_key_container(&self) -> KeyContainer {
+        KeyContainer::new(vec![
+            AddressKeyPair {
+                address: UniversalAddress::Ethereum(self.eth_address),
+                key: SigningKey::Local(self.eth_private_key),
+                sig_scheme: SignatureScheme::Ethereum,
+            },
+            AddressKeyPair {
+                address: UniversalAddress::Substrate(self.substrate_pubkey),
+                key: SigningKey::Local(self.substrate_private_key),
+                sig_scheme: self.substrate_sig_scheme,
+            },
+        ])
+    }
+}
+
+// Lowercase letters, digits and dashes
+fn is_valid_escrow_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_ESCROW_NAME_LEN
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-')
+}
+
+pub fn add_escrow_key_set(
+    escrow_key_sets: &mut Vec<EscrowKeySet>,
+    escrow_key_set: EscrowKeySet,
+) -> Result<()> {
+    if escrow_key_sets.iter().any(|existing| {
+        existing.name == escrow_key_set.name
+            || existing.eth_address == escrow_key_set.eth_address
+            || existing.substrate_pubkey == escrow_key_set.substrate_pubkey
+    }) {
+        return Err(EscrowKeySetError::AlreadyExists);
+    }
+    if escrow_key_sets.len() >= MAX_ESCROW_KEY_SETS {
+        return Err(EscrowKeySetError::TooMany);
+    }
+    escrow_key_sets.push(escrow_key_set);
+    Ok(())
+}
+
+pub fn find_escrow_key_set<'a>(
+    escrow_key_sets: &'a [EscrowKeySet],
+    name: &str,
+) -> Option<&'a EscrowKeySet> {
+    escrow_key_sets
+        .iter()
+        .find(|escrow_key_set| escrow_key_set.name == name)
+}
+
+// None if the plan uses the shared escrow. The refund step always sends from the escrow's Eth
+// address, whatever the plan's route
+pub fn find_plan_escrow_key_set<'a>(
+    escrow_key_sets: &'a [EscrowKeySet],
+    exec_plan: &ExecutionPlan,
+) -> Option<&'a EscrowKeySet> {
+    let escrow_addr = &exec_plan
+        .refund_escrow_to_user_transfer
+        .get_common()
+        .src_addr;
+    escrow_key_sets.iter().find(|escrow_key_set| {
+        *escrow_addr == UniversalAddress::Ethereum(escrow_key_set.eth_address)
+    })
+}
+
+#[cfg(test)]
+mod escrow_key_sets_tests {
+    use super::*;
+
+    fn escrow_key_set(name: &str, seed: u8) -> EscrowKeySet {
+        EscrowKeySet::new(
+            name.into(),
+            EthAddress { 0: [seed; 20] },
+            [seed; 32],
+            SubstratePublicKey { 0: [seed; 32] },
+            [seed; 32],
+            SignatureScheme::Sr25519,
+        )
+        .expect("Valid name")
+    }
+
+    #[test]
+    fn test_escrow_names() {
+        assert!(is_valid_escrow_name("acme-2"));
+        assert!(!is_valid_escrow_name(""));
+        assert!(!is_valid_escrow_name("Acme"));
+        assert!(!is_valid_escrow_name("acme_2"));
+        assert!(!is_valid_escrow_name(&"a".repeat(MAX_ESCROW_NAME_LEN + 1)));
+    }
+
+    #[test]
+    fn test_add_escrow_key_set() {
+        let mut escrow_key_sets = Vec::new();
+        add_escrow_key_set(&mut escrow_key_sets, escrow_key_set("acme", 1)).expect("New escrow");
+        assert_eq!(
+            add_escrow_key_set(&mut escrow_key_sets, escrow_key_set("acme", 2)),
+            Err(EscrowKeySetError::AlreadyExists)
+        );
+        // Same keys under another name
+        assert_eq!(
+            add_escrow_key_set(&mut escrow_key_sets, escrow_key_set("acme-2", 1)),
+            Err(EscrowKeySetError::AlreadyExists)
+        );
+        for seed in 2..=MAX_ESCROW_KEY_SETS as u8 {
+            add_escrow_key_set(
+                &mut escrow_key_sets,
+                escrow_key_set(&format!("escrow-{}", seed), seed),
+            )
+            .expect("Below the limit");
+        }
+        assert_eq!(
+            add_escrow_key_set(&mut escrow_key_sets, escrow_key_set("one-too-many", 0xff)),
+            Err(EscrowKeySetError::TooMany)
+        );
+        assert_eq!(
+            find_escrow_key_set(&escrow_key_sets, "escrow-2").map(|set| set.eth_address),
+            Some(EthAddress { 0: [2; 20] })
+        );
+        assert!(find_escrow_key_set(&escrow_key_sets, "escrow-9").is_none());
+    }
+}
@@ -0,0 +1,427 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+#[allow(unused_imports)]
+use ink::prelude::{format, string::String, vec, vec::Vec};
+use pink_web3::ethabi::{self, ParamType, Token};
+use serde::Deserialize;
+
+use privadex_chain_metadata::common::{
+    Aggregator, Amount, ChainTokenId, EthAddress, UniversalTokenId,
+};
+#[allow(unused_imports)]
+use privadex_common::utils::{
+    general_utils::{hex_string_to_vec, slice_to_hex_string},
+    http_request::http_get_wrapper,
+};
+
+use super::common;
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct QuoteResponse<'a> {
+    #[serde(borrow)]
+    data: QuoteData<'a>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct QuoteData<'a> {
+    #[serde(rename = "outAmount")]
+    out_amount: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct SwapResponse<'a> {
+    #[serde(borrow)]
+    data: SwapData<'a>,
+}
+
+// The API also returns minOutAmount, which we ignore: only the calldata's minReturnAmount is
+// enforced on chain
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct SwapData<'a> {
+    to: &'a str,
+    data: &'a str,
+    value: &'a str,
+}
+
+// The exchange's swap(address caller, SwapDescription desc, CallDescription[] calls)
+const SWAP_SELECTOR: [u8; 4] = [0x90, 0x41, 0x1a, 0x32];
+
+// The swap txn as the API built it. The caller checks it before signing anything
+#[derive(Debug, PartialEq, Eq)]
+pub struct AggregatorSwapTxn {
+    pub to: EthAddress,
+    pub calldata: Vec<u8>,
+    pub value: Amount,
+    // Decoded from the calldata, so these are what the exchange will actually enforce
+    pub terms: AggregatorSwapTerms,
+}
+
+// The calldata's SwapDescription, less the fields that can't move funds elsewhere
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AggregatorSwapTerms {
+    pub src_token: EthAddress,
+    pub dest_token: EthAddress,
+    pub dest_receiver: EthAddress,
+    pub amount_in: Amount,
+    pub min_amount_out: Amount,
+}
+
+pub struct AggregatorApi {
+    pub aggregator: &'static Aggregator,
+}
+
+impl AggregatorApi {
+    pub fn new(aggregator: &'static Aggregator) -> Self {
+        Self { aggregator }
+    }
+
+    // The API takes the native token as a placeholder address
+    pub fn get_token_addr(&self, token: &UniversalTokenId) -> EthAddress {
+        match &token.id {
+            ChainTokenId::Native => self.aggregator.native_token_addr,
+            ChainTokenId::ERC20(erc20_token) => erc20_token.addr,
+            ChainTokenId::XC20(xc20_token) => xc20_token.get_eth_address(),
+        }
+    }
+
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn get_quote(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        amount_in: Amount,
+        gas_price: Amount,
+    ) -> common::Result<Amount> {
+        let resp_body =
+            http_get_wrapper(&self.get_url("quote", src_token, dest_token, amount_in, gas_price))
+                .map_err(|_| common::EthError::ContractCallFailed)?;
+        parse_quote_response(&resp_body)
+    }
+
+    #[cfg(feature = "mock-txn-send")]
+    pub fn get_quote(
+        &self,
+        _src_token: &UniversalTokenId,
+        _dest_token: &UniversalTokenId,
+        _amount_in: Amount,
+        _gas_price: Amount,
+    ) -> common::Result<Amount> {
+        ink::env::debug_println!("[Mock Aggregator get_quote]");
+        Err(common::EthError::ContractCallFailed)
+    }
+
+    // The calldata is for exactly amount_in and sends the dest token back to account
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn get_swap_txn(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        amount_in: Amount,
+        gas_price: Amount,
+        slippage_bps: u16,
+        account: EthAddress,
+    ) -> common::Result<AggregatorSwapTxn> {
+        let url = format!(
+            "{}&slippage={}&account={}",
+            self.get_url("swap", src_token, dest_token, amount_in, gas_price),
+            bps_to_percent_string(slippage_bps),
+            slice_to_hex_string(&account.0),
+        );
+        let resp_body = http_get_wrapper(&url).map_err(|_| common::EthError::ContractCallFailed)?;
+        parse_swap_response(&resp_body)
+    }
+
+    #[cfg(feature = "mock-txn-send")]
+    pub fn get_swap_txn(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        amount_in: Amount,
+        _gas_price: Amount,
+        _slippage_bps: u16,
+        account: EthAddress,
+    ) -> common::Result<AggregatorSwapTxn> {
+        ink::env::debug_println!("[Mock Aggregator get_swap_txn]");
+        let terms = AggregatorSwapTerms {
+            src_token: self.get_token_addr(src_token),
+            dest_token: self.get_token_addr(dest_token),
+            dest_receiver: account,
+            amount_in,
+            min_amount_out: Amount::MAX,
+        };
+        Ok(AggregatorSwapTxn {
+            to: self.aggregator.eth_exchange,
+            calldata: encode_swap_calldata(&terms),
+            value: if src_token.id == ChainTokenId::Native {
+                amount_in
+            } else {
+                0
+            },
+            terms,
+        })
+    }
+
+    // The quote and swap endpoints take the same query, the swap's with a few more params
+    #[allow(dead_code)]
+    fn get_url(
+        &self,
+        endpoint: &str,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        amount_in: Amount,
+        gas_price: Amount,
+    ) -> String {
+        format!(
+            "{}/{}?inTokenAddress={}&outTokenAddress={}&amountDecimals={}&gasPriceDecimals={}",
+            self.aggregator.api_url,
+            endpoint,
+            slice_to_hex_string(&self.get_token_addr(src_token).0),
+            slice_to_hex_string(&self.get_token_addr(dest_token).0),
+            amount_in,
+            gas_price,
+        )
+    }
+}
+
+// The API's slippage is a percentage, e.g. 50 bps is "0.50"
+#[allow(dead_code)]
+fn bps_to_percent_string(bps: u16) -> String {
+    format!("{}.{:02}", bps / 100, bps % 100)
+}
+
+#[allow(dead_code)]
+fn parse_quote_response(resp_body: &[u8]) -> common::Result<Amount> {
+    let (resp, _): (QuoteResponse, usize) =
+        serde_json_core::from_slice(resp_body).map_err(|_| common::EthError::ParseFailed)?;
+    resp.data
+        .out_amount
+        .parse::<Amount>()
+        .map_err(|_| common::EthError::ParseFailed)
+}
+
+#[allow(dead_code)]
+fn parse_swap_response(resp_body: &[u8]) -> common::Result<AggregatorSwapTxn> {
+    let (resp, _): (SwapResponse, usize) =
+        serde_json_core::from_slice(resp_body).map_err(|_| common::EthError::ParseFailed)?;
+    let to: [u8; 20] = hex_string_to_vec(resp.data.to)
+        .map_err(|_| common::EthError::ParseFailed)?
+        .try_into()
+        .map_err(|_| common::EthError::ParseFailed)?;
+    let calldata = hex_string_to_vec(resp.data.data).map_err(|_| common::EthError::ParseFailed)?;
+    Ok(AggregatorSwapTxn {
+        to: EthAddress { 0: to },
+        terms: decode_swap_calldata(&calldata)?,
+        calldata,
+        value: resp
+            .data
+            .value
+            .parse::<Amount>()
+            .map_err(|_| common::EthError::ParseFailed)?,
+    })
+}
+
+// SwapDescription is (srcToken, dstToken, srcReceiver, dstReceiver, amount, minReturnAmount,
+// guaranteedAmount, flags, referrer, permit)
+fn get_swap_description_param_type() -> ParamType {
+    ParamType::Tuple(vec![
+        ParamType::Address,
+        ParamType::Address,
+        ParamType::Address,
+        ParamType::Address,
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Uint(256),
+        ParamType::Address,
+        ParamType::Bytes,
+    ])
+}
+
+// Only caller and desc are decoded. The calls just route the swap, and the exchange checks
+// the payout against desc after making them
+pub fn decode_swap_calldata(calldata: &[u8]) -> common::Result<AggregatorSwapTerms> {
+    if calldata.len() < 4 || calldata[..4] != SWAP_SELECTOR {
+        return Err(common::EthError::FunctionNotFound);
+    }
+    let tokens = ethabi::decode(
+        &[ParamType::Address, get_swap_description_param_type()],
+        &calldata[4..],
+    )
+    .map_err(|_| common::EthError::ParseFailed)?;
+    let desc = match tokens.get(1) {
+        Some(Token::Tuple(desc)) => desc,
+        _ => return Err(common::EthError::ParseFailed),
+    };
+    let get_addr = |index: usize| match desc.get(index) {
+        Some(Token::Address(addr)) => Ok(EthAddress { 0: addr.0 }),
+        _ => Err(common::EthError::ParseFailed),
+    };
+    let get_amount = |index: usize| match desc.get(index) {
+        Some(Token::Uint(val)) => common::u256_to_u128(*val),
+        _ => Err(common::EthError::ParseFailed),
+    };
+    Ok(AggregatorSwapTerms {
+        src_token: get_addr(0)?,
+        dest_token: get_addr(1)?,
+        dest_receiver: get_addr(3)?,
+        amount_in: get_amount(4)?,
+        min_amount_out: get_amount(5)?,
+    })
+}
+
+// The calldata the exchange would get for terms, with no calls. For the mock API and tests
+#[cfg(any(test, feature = "mock-txn-send"))]
+fn encode_swap_calldata(terms: &AggregatorSwapTerms) -> Vec<u8> {
+    use pink_web3::types::U256;
+
+    let addr = |eth_addr: &EthAddress| Token::Address(eth_addr.0.into());
+    let desc = Token::Tuple(vec![
+        addr(&terms.src_token),
+        addr(&terms.dest_token),
+        addr(&terms.dest_receiver),
+        addr(&terms.dest_receiver),
+        Token::Uint(U256::from(terms.amount_in)),
+        Token::Uint(U256::from(terms.min_amount_out)),
+        Token::Uint(U256::from(terms.min_amount_out)),
+        Token::Uint(U256::zero()),
+        addr(&terms.dest_receiver),
+        Token::Bytes(Vec::new()),
+    ]);
+    let mut calldata = SWAP_SELECTOR.to_vec();
+    calldata.extend(ethabi::encode(&[
+        addr(&terms.dest_receiver),
+        desc,
+        Token::Array(Vec::new()),
+    ]));
+    calldata
+}
+
+#[cfg(test)]
+mod aggregator_api_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::{
+        common::ERC20Token,
+        registry::{
+            aggregator::aggregator_registry::OPENOCEAN_MOONBEAM,
+            chain::universal_chain_id_registry::MOONBEAM,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_quote_url() {
+        let api = AggregatorApi::new(&OPENOCEAN_MOONBEAM);
+        let glmr = UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::Native,
+        };
+        let wglmr = UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
+                },
+            }),
+        };
+        assert_eq!(
+            api.get_url("quote", &glmr, &wglmr, 1_000, 2),
+            "https://open-api.openocean.finance/v4/moonbeam/quote?\
+            inTokenAddress=0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee&\
+            outTokenAddress=0xacc15dc74880c9944775448304b263d191c6077f&\
+            amountDecimals=1000&gasPriceDecimals=2"
+        );
+    }
+
+    #[test]
+    fn test_bps_to_percent_string() {
+        assert_eq!(bps_to_percent_string(50), "0.50");
+        assert_eq!(bps_to_percent_string(5), "0.05");
+        assert_eq!(bps_to_percent_string(150), "1.50");
+    }
+
+    #[test]
+    fn test_parse_quote_response() {
+        let resp_body =
+            r#"{"code":200,"data":{"inAmount":"1000","outAmount":"990","estimatedGas":"180000"}}"#;
+        assert_eq!(parse_quote_response(resp_body.as_bytes()), Ok(990));
+        assert_eq!(
+            parse_quote_response(r#"{"code":500,"error":"no route"}"#.as_bytes()),
+            Err(common::EthError::ParseFailed)
+        );
+    }
+
+    fn get_swap_terms() -> AggregatorSwapTerms {
+        AggregatorSwapTerms {
+            src_token: EthAddress {
+                0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
+            },
+            dest_token: EthAddress {
+                0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c"),
+            },
+            dest_receiver: EthAddress {
+                0: hex!("05a81d8564a3ea298660e34e03e5eff9a29d7a2a"),
+            },
+            amount_in: 1_000,
+            min_amount_out: 985,
+        }
+    }
+
+    #[test]
+    fn test_parse_swap_response() {
+        let terms = get_swap_terms();
+        let calldata = encode_swap_calldata(&terms);
+        // The terms come from the calldata, whatever minOutAmount says
+        let resp_body = format!(
+            r#"{{"code":200,"data":{{"outAmount":"990","minOutAmount":"990",
+            "to":"0x6352a56caadc4f1e25cd6c75970fa768a3304e64","data":"{}","value":"0"}}}}"#,
+            slice_to_hex_string(&calldata)
+        );
+        assert_eq!(
+            parse_swap_response(resp_body.as_bytes()),
+            Ok(AggregatorSwapTxn {
+                to: OPENOCEAN_MOONBEAM.eth_exchange,
+                calldata,
+                value: 0,
+                terms,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_swap_calldata() {
+        let terms = get_swap_terms();
+        let calldata = encode_swap_calldata(&terms);
+        assert_eq!(decode_swap_calldata(&calldata), Ok(terms));
+        assert_eq!(
+            decode_swap_calldata(&calldata[..100]),
+            Err(common::EthError::ParseFailed)
+        );
+        let mut other_fn_calldata = calldata.clone();
+        other_fn_calldata[0] = 0x12;
+        assert_eq!(
+            decode_swap_calldata(&other_fn_calldata),
+            Err(common::EthError::FunctionNotFound)
+        );
+    }
+}
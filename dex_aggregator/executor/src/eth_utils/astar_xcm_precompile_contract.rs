@@ -17,7 +17,7 @@
  */
 
 use duplicate::duplicate_item;
-use ink_prelude::{
+use ink::prelude::{
     string::{String, ToString},
     vec,
 };
@@ -29,14 +29,18 @@ use pink_web3::{
 
 use privadex_chain_metadata::{
     common::{
-        Amount, ChainTokenId, EthAddress, Nonce, ParachainId, SecretKey, SubstratePublicKey,
-        UniversalAddress, UniversalChainId, UniversalTokenId,
+        Amount, ChainTokenId, EthAddress, Nonce, ParachainId, SubstratePublicKey, UniversalAddress,
+        UniversalChainId, UniversalTokenId,
     },
     registry::{chain::universal_chain_id_registry, token::universal_token_id_registry},
 };
 
 use super::common;
 
+pub const ASTAR_XCM_PRECOMPILE_ADDRESS: EthAddress = EthAddress {
+    0: hex_literal::hex!("0000000000000000000000000000000000005004"),
+};
+
 pub struct AstarXcmContract {
     contract: Contract<PinkHttp>,
     rpc_url: String,
@@ -44,9 +48,6 @@ pub struct AstarXcmContract {
 
 impl AstarXcmContract {
     pub fn new(rpc_url: &str) -> common::Result<Self> {
-        const ASTAR_XCM_PRECOMPILE_ADDRESS: EthAddress = EthAddress {
-            0: hex_literal::hex!("0000000000000000000000000000000000005004"),
-        };
         let contract = Contract::from_json(
             common::eth(rpc_url),
             ASTAR_XCM_PRECOMPILE_ADDRESS,
@@ -65,7 +66,8 @@ impl AstarXcmContract {
         amount: Amount,
         dest_chain: UniversalChainId,
         dest_addr: UniversalAddress,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         // I'm not sure of the difference between the functions other than
@@ -77,10 +79,24 @@ impl AstarXcmContract {
             UniversalTokenId {
                 chain: universal_chain_id_registry::ASTAR,
                 id: ChainTokenId::XC20(_),
-            } => self.assets_withdraw(src_token, amount, dest_chain, dest_addr, key, nonce),
-            &universal_token_id_registry::ASTR_NATIVE => {
-                self.assets_reserve_transfer(src_token, amount, dest_chain, dest_addr, key, nonce)
-            }
+            } => self.assets_withdraw(
+                src_token,
+                amount,
+                dest_chain,
+                dest_addr,
+                key,
+                supports_eip1559,
+                nonce,
+            ),
+            &universal_token_id_registry::ASTR_NATIVE => self.assets_reserve_transfer(
+                src_token,
+                amount,
+                dest_chain,
+                dest_addr,
+                key,
+                supports_eip1559,
+                nonce,
+            ),
             // Native token is NOT supported apparently for assets_withdraw
             _ => Err(common::EthError::InvalidArgument),
         }
@@ -92,7 +108,8 @@ impl AstarXcmContract {
         amount: Amount,
         dest_chain: UniversalChainId,
         dest_addr: UniversalAddress,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "assets_withdraw";
@@ -104,7 +121,16 @@ impl AstarXcmContract {
             // Native token is NOT supported apparently for assets_withdraw
             _ => Err(common::EthError::InvalidArgument),
         }?;
-        self.dispatch_helper(func, asset_id, amount, dest_chain, dest_addr, key, nonce)
+        self.dispatch_helper(
+            func,
+            asset_id,
+            amount,
+            dest_chain,
+            dest_addr,
+            key,
+            supports_eip1559,
+            nonce,
+        )
     }
 
     fn assets_reserve_transfer(
@@ -113,7 +139,8 @@ impl AstarXcmContract {
         amount: Amount,
         dest_chain: UniversalChainId,
         dest_addr: UniversalAddress,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "assets_reserve_transfer";
@@ -127,7 +154,16 @@ impl AstarXcmContract {
             &universal_token_id_registry::ASTR_NATIVE => Ok(EthAddress::zero()),
             _ => Err(common::EthError::InvalidArgument),
         }?;
-        self.dispatch_helper(func, asset_id, amount, dest_chain, dest_addr, key, nonce)
+        self.dispatch_helper(
+            func,
+            asset_id,
+            amount,
+            dest_chain,
+            dest_addr,
+            key,
+            supports_eip1559,
+            nonce,
+        )
     }
 
     fn dispatch_helper(
@@ -137,7 +173,8 @@ impl AstarXcmContract {
         amount: Amount,
         dest_chain: UniversalChainId,
         dest_addr: UniversalAddress,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let (is_relay, para_id) = {
@@ -160,6 +197,7 @@ impl AstarXcmContract {
                 // overload_index: the address type for recipient_account_id comes second in the ABI
                 0,
                 options_seed,
+                supports_eip1559,
                 nonce,
             ),
             UniversalAddress::Substrate(addr) => self.address32_helper(
@@ -173,6 +211,7 @@ impl AstarXcmContract {
                 // overload_index: the bytes32 type for recipient_account_id comes second in the ABI
                 1,
                 options_seed,
+                supports_eip1559,
                 nonce,
             ),
         }
@@ -190,10 +229,11 @@ impl AstarXcmContract {
         dest_addr: dest_addr_type,
         is_relay: bool,
         para_id: ParachainId,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
         func: &str,
         overload_index: u8,
         options_seed: Options,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let params = (
@@ -212,6 +252,7 @@ impl AstarXcmContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -232,9 +273,12 @@ impl common::ContractWrapper for AstarXcmContract {
 mod astar_xcm_precompile_tests {
     use core::str::FromStr;
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
-    use privadex_chain_metadata::common::{SecretKeyContainer, UniversalChainId};
+    use privadex_chain_metadata::{
+        common::{SecretKey, SecretKeyContainer, UniversalChainId},
+        registry::chain::chain_info_registry,
+    };
     use privadex_common::utils::general_utils::slice_to_hex_string;
 
     use super::*;
@@ -313,6 +357,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -344,6 +389,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -365,6 +411,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -389,6 +436,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -416,6 +464,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -437,6 +486,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -462,6 +512,7 @@ mod astar_xcm_precompile_tests {
                 dest_chain,
                 dest_addr,
                 &kap_privkey,
+                chain_info_registry::ASTAR_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Create txn failed");
@@ -16,19 +16,31 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::vec::Vec;
+use core::cell::Cell;
+use ink::prelude::{format, vec::Vec};
 use pink_web3::{
     api::{Accounts, Eth, Namespace},
     contract::{tokens::Tokenize, Contract, Options},
     ethabi::Function,
     keys::pink::KeyPair,
-    signing::Key,
+    signing::{Key, Signature, SigningError},
     transports::{resolve_ready, PinkHttp},
-    types::{Bytes, CallRequest, SignedTransaction, TransactionParameters, U256},
+    types::{
+        Address, BlockId, BlockNumber, Bytes, CallRequest, SignedTransaction,
+        TransactionParameters, H256, U256, U64,
+    },
 };
+use scale::{Decode, Encode};
 
-use privadex_chain_metadata::common::{Amount, BlockNum, EthAddress, EthTxnHash, Nonce, SecretKey};
-use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_chain_metadata::common::{
+    Amount, BlockNum, EthAddress, EthTxnHash, EvmChainId, MillisSinceEpoch, Nonce, SecretKey,
+    UniversalAddress,
+};
+use privadex_common::utils::general_utils::{mul_ratio_u128, slice_to_hex_string};
+
+use super::threshold_round_txn::ThresholdRoundTxn;
+use crate::json_rpc_batch::{parse_batch_response, send_batch, JsonRpcCall, JsonRpcResponse};
+use crate::key_container::{SigningKey, ThresholdSigner, ThresholdSigningError};
 
 #[derive(Debug, PartialEq)]
 pub enum EthError {
@@ -36,17 +48,25 @@ pub enum EthError {
     // so if any internal U256 is greater than u128::MAX, we return this error
     AmountTooHigh,
     BadSignature,
+    BalanceRequestFailed,
     BlockNumberRequestFailed,
+    BlockRequestFailed,
+    ChainIdRequestFailed,
+    CodeRequestFailed,
     CreateRawTransactionFailed,
     ContractCallFailed,
     FunctionNotFound,
     GasEstimateFailed,
+    GasPriceRequestFailed,
     InvalidABI,
     InvalidArgument,
     NonceRequestFailed,
     ParseFailed,
+    RpcBatchRequestFailed,
     SendTransactionFailed,
     SignTransactionFailed,
+    // Other threshold parties still have to sign the txn (see ThresholdSigner)
+    ThresholdSignaturePending,
     TransactionNotFound,
     // pink_web3 can technically accept nonce = None because it computes the
     // account nonce in that occasion. But we need to track nonce and pass it in
@@ -80,6 +100,162 @@ pub struct TxnSummary {
     pub gas_fee_native: Amount,
 }
 
+// Where a txn landed. is_canonical is false if the chain's block at block_num is no longer the
+// one the receipt points at i.e. the txn's block was reorged out
+#[derive(Debug)]
+pub struct TxnInclusion {
+    pub block_num: BlockNum,
+    pub is_canonical: bool,
+}
+
+#[derive(Debug)]
+pub struct WormholeDeposit {
+    pub is_txn_success: bool,
+    pub sequence: u64,
+    pub gas_fee_native: Amount,
+}
+
+/// Anything that can sign an Ethereum transaction: either a raw SecretKey or a SigningKey
+/// from the KeyContainer (which may be backed by a remote or threshold signer)
+pub trait TxnSigner {
+    fn get_eth_address(&self) -> Result<EthAddress>;
+    fn sign_txn(
+        &self,
+        rpc_url: &str,
+        txn_params: TransactionParameters,
+    ) -> Result<SignedTransaction>;
+}
+
+impl TxnSigner for SecretKey {
+    fn get_eth_address(&self) -> Result<EthAddress> {
+        Ok(KeyPair::from(self.clone()).address())
+    }
+
+    fn sign_txn(
+        &self,
+        rpc_url: &str,
+        txn_params: TransactionParameters,
+    ) -> Result<SignedTransaction> {
+        resolve_ready(accounts(rpc_url).sign_transaction(txn_params, KeyPair::from(self.clone())))
+            .map_err(|_| EthError::SignTransactionFailed)
+    }
+}
+
+impl TxnSigner for SigningKey {
+    fn get_eth_address(&self) -> Result<EthAddress> {
+        match self {
+            SigningKey::Local(secret_key) => secret_key.get_eth_address(),
+            SigningKey::Remote(remote_signer) => match remote_signer.address {
+                UniversalAddress::Ethereum(eth_addr) => Ok(eth_addr),
+                UniversalAddress::Substrate(_) => Err(EthError::InvalidArgument),
+            },
+            SigningKey::Threshold(threshold_signer) => Ok(threshold_signer.address),
+        }
+    }
+
+    fn sign_txn(
+        &self,
+        rpc_url: &str,
+        txn_params: TransactionParameters,
+    ) -> Result<SignedTransaction> {
+        let txn_params = match self {
+            SigningKey::Local(secret_key) => return secret_key.sign_txn(rpc_url, txn_params),
+            SigningKey::Remote(_) => txn_params,
+            SigningKey::Threshold(threshold_signer) => {
+                get_threshold_round_txn_params(rpc_url, threshold_signer, &txn_params)?
+            }
+        };
+        let is_pending = Cell::new(false);
+        let remote_key = RemoteEthKey {
+            key: self,
+            address: self.get_eth_address()?,
+            is_pending: &is_pending,
+        };
+        resolve_ready(accounts(rpc_url).sign_transaction(txn_params, remote_key)).map_err(|_| {
+            if is_pending.get() {
+                EthError::ThresholdSignaturePending
+            } else {
+                EthError::SignTransactionFailed
+            }
+        })
+    }
+}
+
+// The parties of a threshold escrow sign the txn pinned for the current round, as long as it
+// does the same thing as the txn we built
+fn get_threshold_round_txn_params(
+    rpc_url: &str,
+    threshold_signer: &ThresholdSigner,
+    txn_params: &TransactionParameters,
+) -> Result<TransactionParameters> {
+    let own_txn = ThresholdRoundTxn::from_txn_params(rpc_url, txn_params)?;
+    let pinned_txn = threshold_signer
+        .pin_round_txn(own_txn.chain_id, own_txn.nonce, &own_txn.encode())
+        .and_then(|round_txn| ThresholdRoundTxn::decode(&mut round_txn.as_slice()).ok())
+        .ok_or(EthError::SignTransactionFailed)?;
+    if !pinned_txn.is_compatible_with(&own_txn) {
+        ink::env::debug_println!(
+            "Refusing to sign the pinned threshold txn: {:?}",
+            pinned_txn
+        );
+        return Err(EthError::SignTransactionFailed);
+    }
+    Ok(pinned_txn.to_txn_params())
+}
+
+// Lets pink_web3 build and encode the transaction while the remote (or threshold) signer
+// signs its hash
+struct RemoteEthKey<'a> {
+    key: &'a SigningKey,
+    address: EthAddress,
+    // pink_web3 only passes on SigningError::InvalidMessage, so this tells a threshold round
+    // that is waiting on other parties apart from a failed one
+    is_pending: &'a Cell<bool>,
+}
+
+impl Key for RemoteEthKey<'_> {
+    fn sign(
+        &self,
+        message: &[u8],
+        chain_id: Option<u64>,
+    ) -> core::result::Result<Signature, SigningError> {
+        let mut signature = self.sign_message(message)?;
+        // EIP-155 replay protection for legacy transactions
+        signature.v += match chain_id {
+            Some(chain_id) => 35 + chain_id * 2,
+            None => 27,
+        };
+        Ok(signature)
+    }
+
+    fn sign_message(&self, message: &[u8]) -> core::result::Result<Signature, SigningError> {
+        let msg_hash: [u8; 32] = message
+            .try_into()
+            .map_err(|_| SigningError::InvalidMessage)?;
+        let raw_signature = match self.key {
+            SigningKey::Threshold(threshold_signer) => threshold_signer
+                .sign_prehashed_ecdsa(&msg_hash)
+                .map_err(|e| {
+                    self.is_pending.set(e == ThresholdSigningError::Pending);
+                    SigningError::InvalidMessage
+                })?,
+            _ => self
+                .key
+                .sign_prehashed_ecdsa(&msg_hash)
+                .ok_or(SigningError::InvalidMessage)?,
+        };
+        Ok(Signature {
+            r: H256::from_slice(&raw_signature[..32]),
+            s: H256::from_slice(&raw_signature[32..64]),
+            v: raw_signature[64] as u64,
+        })
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+}
+
 pub trait ContractWrapper {
     fn get_rpc_url(&self) -> &str;
 
@@ -98,19 +274,113 @@ pub fn send_raw_transaction(rpc_url: &str, signed: SignedTransaction) -> Result<
 
 #[cfg(feature = "mock-txn-send")]
 pub fn send_raw_transaction(_rpc_url: &str, signed: SignedTransaction) -> Result<EthTxnHash> {
-    ink_env::debug_println!("[Mock Eth send_raw_transaction]");
+    ink::env::debug_println!("[Mock Eth send_raw_transaction]");
     Ok(signed.transaction_hash)
 }
 
+// For a txn that was signed earlier and kept as its raw bytes (see create_fixed_gas_raw_txn)
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn send_raw_transaction_bytes(
+    rpc_url: &str,
+    raw_txn: Vec<u8>,
+    _txn_hash: EthTxnHash,
+) -> Result<EthTxnHash> {
+    eth(rpc_url)
+        .send_raw_transaction(Bytes(raw_txn))
+        .resolve()
+        .map_err(|_| EthError::SendTransactionFailed)
+}
+
+#[cfg(feature = "mock-txn-send")]
+pub fn send_raw_transaction_bytes(
+    _rpc_url: &str,
+    _raw_txn: Vec<u8>,
+    txn_hash: EthTxnHash,
+) -> Result<EthTxnHash> {
+    ink::env::debug_println!("[Mock Eth send_raw_transaction_bytes]");
+    Ok(txn_hash)
+}
+
 pub fn create_send_eth_raw_txn<'a, 'b>(
     rpc_url: &str,
     to: EthAddress,
     amount: Amount,
-    key: &SecretKey,
+    key: &impl TxnSigner,
     chain_id: u64,
+    supports_eip1559: bool,
     nonce: Nonce,
 ) -> Result<SignedTransaction> {
-    let txn_params = create_txn_params(to, amount, Bytes::from(Vec::new()), chain_id, nonce);
+    let txn_params = create_txn_params(
+        to,
+        amount,
+        Bytes::from(Vec::new()),
+        chain_id,
+        supports_eip1559,
+        nonce,
+    );
+    create_raw_txn_from_txn_params(rpc_url, key, txn_params)
+}
+
+// Like create_raw_txn but for calldata we did not encode from an ABI (e.g. swap-and-call
+// deliveries), so the gas is estimated on the raw bytes
+pub fn create_contract_call_raw_txn(
+    rpc_url: &str,
+    to: EthAddress,
+    calldata: Vec<u8>,
+    value: Amount,
+    key: &impl TxnSigner,
+    supports_eip1559: bool,
+    nonce: Nonce,
+) -> Result<SignedTransaction> {
+    let mut options_seed = Options::default();
+    options_seed.value = Some(U256::from(value));
+    set_txn_type(&mut options_seed, supports_eip1559);
+    let mut options = estimate_gas(
+        rpc_url,
+        to,
+        calldata.clone(),
+        key.get_eth_address()?,
+        options_seed,
+    )?;
+    options.nonce = Some(U256::from(nonce));
+
+    contract_sign_txn(rpc_url, calldata, to, options, key)
+}
+
+// Signs a txn with the given gas limit and gas_price_multiplier times the current gas price,
+// instead of estimating either. So it can be signed before the sender holds what the txn moves,
+// and still go through if it is broadcast once gas prices have gone up
+pub fn create_fixed_gas_raw_txn(
+    rpc_url: &str,
+    to: EthAddress,
+    value: Amount,
+    calldata: Vec<u8>,
+    gas_limit: u64,
+    gas_price_multiplier: u64,
+    key: &impl TxnSigner,
+    chain_id: u64,
+    supports_eip1559: bool,
+    nonce: Nonce,
+) -> Result<SignedTransaction> {
+    let gas_price = eth(rpc_url)
+        .gas_price()
+        .resolve()
+        .map_err(|_| EthError::GasPriceRequestFailed)?
+        .saturating_mul(U256::from(gas_price_multiplier));
+    let mut txn_params = create_txn_params(
+        to,
+        value,
+        Bytes::from(calldata),
+        chain_id,
+        supports_eip1559,
+        nonce,
+    );
+    txn_params.gas = U256::from(gas_limit);
+    if supports_eip1559 {
+        txn_params.max_fee_per_gas = Some(gas_price);
+    } else {
+        txn_params.gas_price = Some(gas_price);
+    }
     create_raw_txn_from_txn_params(rpc_url, key, txn_params)
 }
 
@@ -126,6 +396,44 @@ pub fn get_next_system_nonce(rpc_url: &str, address: EthAddress) -> Result<Nonce
     }
 }
 
+pub fn get_native_balance(rpc_url: &str, address: EthAddress) -> Result<Amount> {
+    let balance = eth(rpc_url)
+        .balance(address, None /* block number */)
+        .resolve()
+        .map_err(|_| EthError::BalanceRequestFailed)?;
+    u256_to_u128(balance)
+}
+
+pub fn get_gas_price(rpc_url: &str) -> Result<Amount> {
+    let gas_price = eth(rpc_url)
+        .gas_price()
+        .resolve()
+        .map_err(|_| EthError::GasPriceRequestFailed)?;
+    u256_to_u128(gas_price)
+}
+
+// Empty for an address with no contract deployed
+pub fn get_code(rpc_url: &str, address: EthAddress) -> Result<Vec<u8>> {
+    let code = eth(rpc_url)
+        .code(address, None /* block number */)
+        .resolve()
+        .map_err(|_| EthError::CodeRequestFailed)?;
+    Ok(code.0)
+}
+
+// The EIP-155 chain ID the node signs for, i.e. eth_chainId
+pub fn chain_id(rpc_url: &str) -> Result<EvmChainId> {
+    let chain_id = eth(rpc_url)
+        .chain_id()
+        .resolve()
+        .map_err(|_| EthError::ChainIdRequestFailed)?;
+    if chain_id > EvmChainId::MAX.into() {
+        Err(EthError::AmountTooHigh)
+    } else {
+        Ok(chain_id.low_u64())
+    }
+}
+
 pub fn block_number(rpc_url: &str) -> Result<BlockNum> {
     let block_num = eth(rpc_url)
         .block_number()
@@ -138,6 +446,53 @@ pub fn block_number(rpc_url: &str) -> Result<BlockNum> {
     }
 }
 
+// block_number as a bare call, for callers that send it alongside other chains' calls (see
+// BlockNumberProvider::get_block_numbers)
+pub fn block_number_call() -> JsonRpcCall<'static> {
+    JsonRpcCall::new("eth_blockNumber", "[]")
+}
+
+pub fn parse_block_number_response(resp_body: &[u8]) -> Result<BlockNum> {
+    let (block_num, _): (JsonRpcResponse<&str>, usize) =
+        serde_json_core::from_slice(resp_body).map_err(|_| EthError::ParseFailed)?;
+    hex_quantity_to_u32(block_num.result)
+}
+
+// Block timestamps are in seconds
+pub fn latest_block_timestamp_millis(rpc_url: &str) -> Result<MillisSinceEpoch> {
+    let block = eth(rpc_url)
+        .block(BlockId::Number(BlockNumber::Latest))
+        .resolve()
+        .map_err(|_| EthError::BlockRequestFailed)?
+        .ok_or(EthError::BlockRequestFailed)?;
+    if block.timestamp > U256::from(MillisSinceEpoch::MAX / 1000) {
+        Err(EthError::AmountTooHigh)
+    } else {
+        Ok(block.timestamp.low_u64() * 1000)
+    }
+}
+
+// Same as block_number and get_next_system_nonce, in one round trip
+pub fn block_number_and_system_nonce(
+    rpc_url: &str,
+    address: EthAddress,
+) -> Result<(BlockNum, Nonce)> {
+    let calls = [
+        block_number_call(),
+        JsonRpcCall::new(
+            "eth_getTransactionCount",
+            &format!(r#"["{}","latest"]"#, slice_to_hex_string(&address.0)),
+        ),
+    ];
+    let resp_body = send_batch(rpc_url, &calls).map_err(|_| EthError::RpcBatchRequestFailed)?;
+    let (block_num, nonce): (JsonRpcResponse<&str>, JsonRpcResponse<&str>) =
+        parse_batch_response(&resp_body).map_err(|_| EthError::ParseFailed)?;
+    Ok((
+        hex_quantity_to_u32(block_num.result)?,
+        hex_quantity_to_u32(nonce.result)?,
+    ))
+}
+
 /// Creates the SignedTransaction but does NOT send it!
 /// This is useful if we want to do something with the txn hash before submitting it
 pub(super) fn create_raw_txn<ParamsType: Clone + Tokenize>(
@@ -148,14 +503,13 @@ pub(super) fn create_raw_txn<ParamsType: Clone + Tokenize>(
     // the correct function
     overload_index: u8,
     params: ParamsType,
-    options_seed: Options,
-    key: &SecretKey,
+    mut options_seed: Options,
+    key: &impl TxnSigner,
+    supports_eip1559: bool,
     nonce: Nonce,
 ) -> Result<SignedTransaction> {
-    let fn_data = get_contract_func(contract, func, overload_index)?
-        .encode_input(&params.into_tokens())
-        .map_err(|_| EthError::CreateRawTransactionFailed)?;
-    let keypair = KeyPair::from(key.clone());
+    let fn_data = encode_call(contract, func, overload_index, params)?;
+    set_txn_type(&mut options_seed, supports_eip1559);
     let mut options = {
         if options_seed.gas.is_some() {
             options_seed
@@ -164,20 +518,41 @@ pub(super) fn create_raw_txn<ParamsType: Clone + Tokenize>(
                 rpc_url,
                 contract.address(),
                 fn_data.clone(),
-                keypair.address(),
+                key.get_eth_address()?,
                 options_seed,
             )?
         }
     };
     options.nonce = Some(U256::from(nonce));
 
-    contract_sign_txn(rpc_url, fn_data, contract.address(), options, keypair)
+    contract_sign_txn(rpc_url, fn_data, contract.address(), options, key)
+}
+
+// The calldata of a call to the contract's func
+pub(super) fn encode_call<ParamsType: Tokenize>(
+    contract: &Contract<PinkHttp>,
+    func: &str,
+    overload_index: u8,
+    params: ParamsType,
+) -> Result<Vec<u8>> {
+    get_contract_func(contract, func, overload_index)?
+        .encode_input(&params.into_tokens())
+        .map_err(|_| EthError::CreateRawTransactionFailed)
 }
 
 pub(super) fn eth(rpc_url: &str) -> Eth<PinkHttp> {
     Eth::new(PinkHttp::new(rpc_url.clone()))
 }
 
+// JSON-RPC quantities are 0x-prefixed hex without leading zeros e.g. "0x3b1a2c"
+fn hex_quantity_to_u32(quantity: &str) -> Result<u32> {
+    let hex_digits = quantity.strip_prefix("0x").ok_or(EthError::ParseFailed)?;
+    if hex_digits.len() > 8 {
+        return Err(EthError::AmountTooHigh);
+    }
+    u32::from_str_radix(hex_digits, 16).map_err(|_| EthError::ParseFailed)
+}
+
 pub(super) fn u256_to_u128(val: U256) -> Result<u128> {
     let low_u128 = val.low_u128();
     if val != U256::from(low_u128) {
@@ -194,7 +569,7 @@ fn contract_sign_txn(
     fn_data: Vec<u8>,
     contract_address: EthAddress,
     options: Options,
-    key: KeyPair,
+    key: &impl TxnSigner,
 ) -> Result<SignedTransaction> {
     let _ = validate_nonce(options.nonce)?;
     // The contract.abi().function() function takes just the first function of that name, which
@@ -217,19 +592,21 @@ fn contract_sign_txn(
     if let Some(value) = options.value {
         tx.value = value;
     }
-    resolve_ready(accounts(rpc_url).sign_transaction(tx, key))
-        .map_err(|_| EthError::SignTransactionFailed)
+    key.sign_txn(rpc_url, tx)
 }
 
 fn accounts(rpc_url: &str) -> Accounts<PinkHttp> {
     Accounts::new(PinkHttp::new(rpc_url.clone()))
 }
 
+pub(super) const EIP1559_TXN_TYPE: u64 = 2;
+
 /*
- * Creates the TransactionParameters for a legacy Ethereum transaction.
+ * Creates the TransactionParameters for a legacy or EIP-1559 Ethereum transaction.
  * Note that Accounts::sign_transaction will later override None for the following params:
  * - `nonce`: the signing account's transaction count
  * - `gas_price`: estimated recommended gas price
+ * - `max_fee_per_gas`: same as gas_price (EIP-1559 only)
  * https://docs.rs/pink-web3/latest/pink_web3/types/struct.TransactionParameters.html
  */
 fn create_txn_params(
@@ -237,6 +614,7 @@ fn create_txn_params(
     value: Amount,
     data: Bytes,
     chain_id: u64,
+    supports_eip1559: bool,
     nonce: Nonce,
 ) -> TransactionParameters {
     let mut tx = TransactionParameters::default();
@@ -245,18 +623,27 @@ fn create_txn_params(
     tx.data = data;
     tx.chain_id = Some(chain_id);
     tx.nonce = Some(U256::from(nonce));
+    if supports_eip1559 {
+        tx.transaction_type = Some(U64::from(EIP1559_TXN_TYPE));
+    }
     tx
 }
 
+// Contract calls get the same transaction type as create_txn_params. Accounts::sign_transaction
+// then fills in max_fee_per_gas the same way
+fn set_txn_type(options: &mut Options, supports_eip1559: bool) {
+    if supports_eip1559 {
+        options.transaction_type = Some(U64::from(EIP1559_TXN_TYPE));
+    }
+}
+
 fn create_raw_txn_from_txn_params(
     rpc_url: &str,
-    key: &SecretKey,
+    key: &impl TxnSigner,
     txn_params: TransactionParameters,
 ) -> Result<SignedTransaction> {
     let _ = validate_nonce(txn_params.nonce)?;
-    let keypair = KeyPair::from(key.clone());
-    resolve_ready(accounts(rpc_url).sign_transaction(txn_params, keypair))
-        .map_err(|_| EthError::BadSignature)
+    key.sign_txn(rpc_url, txn_params)
 }
 
 fn get_contract_func<'a, 'b>(
@@ -300,7 +687,7 @@ fn estimate_gas(
             None,
         )
     });
-    ink_env::debug_println!("Estimate gas: {:?}", opt_gas);
+    ink::env::debug_println!("Estimate gas: {:?}", opt_gas);
     let gas = opt_gas.map_err(|_| EthError::GasEstimateFailed)?;
     if let Ok(gas_u128) = u256_to_u128(gas) {
         // Add +100% to the gas limit since we sometimes (rarely) see run-out-of-gas errors e.g.
@@ -324,9 +711,9 @@ fn validate_nonce(nonce: Option<U256>) -> Result<()> {
 #[cfg(test)]
 #[allow(dead_code)]
 pub(super) fn print_and_send_txn(rpc_url: &str, signed_txn: SignedTransaction) {
-    ink_env::debug_println!("{:?}", signed_txn);
+    ink::env::debug_println!("{:?}", signed_txn);
     let txn_hash = send_raw_transaction(rpc_url, signed_txn).expect("returned txn hash");
-    ink_env::debug_println!("Txn hash: {:?}", txn_hash);
+    ink::env::debug_println!("Txn hash: {:?}", txn_hash);
 }
 
 #[cfg(test)]
@@ -347,7 +734,7 @@ mod eth_utils_common_tests {
         };
         let nonce = get_next_system_nonce(&chain_info_registry::ASTAR_INFO.rpc_url, address)
             .expect("Expect nonce value");
-        // ink_env::debug_println!("Nonce: {}", nonce);
+        // ink::env::debug_println!("Nonce: {}", nonce);
         assert!(nonce > 1);
     }
 
@@ -359,6 +746,141 @@ mod eth_utils_common_tests {
         assert!(block_num > 2_662_091);
     }
 
+    #[test]
+    fn test_astar_chain_id() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let chain_id = chain_id(&chain_info_registry::ASTAR_INFO.rpc_url).expect("Expect chain ID");
+        assert_eq!(Some(chain_id), chain_info_registry::ASTAR_INFO.evm_chain_id);
+    }
+
+    #[test]
+    fn test_astar_block_num_and_nonce() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let address = EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        };
+        let (block_num, nonce) =
+            block_number_and_system_nonce(&chain_info_registry::ASTAR_INFO.rpc_url, address)
+                .expect("Expect block num and nonce");
+        assert!(block_num > 2_662_091);
+        assert!(nonce > 1);
+    }
+
+    #[test]
+    fn test_hex_quantity_to_u32() {
+        assert_eq!(hex_quantity_to_u32("0x0"), Ok(0));
+        assert_eq!(hex_quantity_to_u32("0x3b1a2c"), Ok(3_873_324));
+        assert_eq!(hex_quantity_to_u32("3b1a2c"), Err(EthError::ParseFailed));
+        assert_eq!(
+            hex_quantity_to_u32("0x100000000"),
+            Err(EthError::AmountTooHigh)
+        );
+    }
+
+    #[test]
+    fn test_parse_block_number_response() {
+        assert_eq!(
+            parse_block_number_response(br#"{"jsonrpc":"2.0","id":0,"result":"0x3b1a2c"}"#),
+            Ok(3_873_324)
+        );
+        assert_eq!(
+            parse_block_number_response(
+                br#"{"jsonrpc":"2.0","id":0,"error":{"code":-32000,"message":"oops"}}"#
+            ),
+            Err(EthError::ParseFailed)
+        );
+    }
+
+    // Every param is filled in, so Accounts::sign_transaction makes no RPC calls
+    fn create_offline_txn_params(chain_id: u64, supports_eip1559: bool) -> TransactionParameters {
+        let mut tx = create_txn_params(
+            EthAddress {
+                0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+            },
+            1_000_000_000_000_000,
+            Bytes::default(),
+            chain_id,
+            supports_eip1559,
+            7,
+        );
+        tx.gas = U256::from(21_000);
+        tx.gas_price = Some(U256::from(1_000_000_000));
+        tx.max_fee_per_gas = Some(U256::from(1_000_000_000));
+        tx.max_priority_fee_per_gas = Some(U256::from(1_000_000_000));
+        tx
+    }
+
+    fn sign_with_remote_key(
+        rpc_url: &str,
+        key: &SigningKey,
+        txn_params: TransactionParameters,
+    ) -> SignedTransaction {
+        let is_pending = Cell::new(false);
+        let remote_key = RemoteEthKey {
+            key,
+            address: key.get_eth_address().expect("Local keys have an address"),
+            is_pending: &is_pending,
+        };
+        resolve_ready(accounts(rpc_url).sign_transaction(txn_params, remote_key))
+            .expect("Valid signed txn")
+    }
+
+    #[test]
+    fn test_remote_key_sets_eip155_v() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let key = SigningKey::Local([1; 32]);
+        let is_pending = Cell::new(false);
+        let remote_key = RemoteEthKey {
+            key: &key,
+            address: key.get_eth_address().expect("Local keys have an address"),
+            is_pending: &is_pending,
+        };
+        let msg_hash = [7; 32];
+
+        let recovery_id = remote_key
+            .sign_message(&msg_hash)
+            .expect("Valid signature")
+            .v;
+        assert!(recovery_id <= 1);
+        // Moonbase Alpha's chain ID is 1287
+        assert_eq!(
+            remote_key
+                .sign(&msg_hash, Some(1287))
+                .expect("Valid signature")
+                .v,
+            35 + 1287 * 2 + recovery_id
+        );
+        assert_eq!(
+            remote_key.sign(&msg_hash, None).expect("Valid signature").v,
+            27 + recovery_id
+        );
+    }
+
+    #[test]
+    fn test_remote_key_signs_txns_like_local_key() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let rpc_url = chain_info_registry::MOONBASEALPHA_INFO.rpc_url;
+        let secret_key = [1; 32];
+        let key = SigningKey::Local(secret_key);
+        for supports_eip1559 in [false, true] {
+            let txn_params = create_offline_txn_params(1287, supports_eip1559);
+            let local_txn = secret_key
+                .sign_txn(rpc_url, txn_params.clone())
+                .expect("Valid signed txn");
+            let remote_txn = sign_with_remote_key(rpc_url, &key, txn_params);
+            assert_eq!(remote_txn.raw_transaction, local_txn.raw_transaction);
+            assert_eq!(remote_txn.transaction_hash, local_txn.transaction_hash);
+        }
+
+        // EIP-155: the same legacy txn signed for another chain can't be replayed on this one
+        let moonbeam_txn =
+            sign_with_remote_key(rpc_url, &key, create_offline_txn_params(1284, false));
+        let moonbase_txn =
+            sign_with_remote_key(rpc_url, &key, create_offline_txn_params(1287, false));
+        assert_ne!(moonbeam_txn.raw_transaction, moonbase_txn.raw_transaction);
+        assert_ne!(moonbeam_txn.transaction_hash, moonbase_txn.transaction_hash);
+    }
+
     #[test]
     fn test_send_eth_create_txn() {
         // Generated: https://moonbase.moonscan.io/tx/0x44b9890af58b0fce5d2b90dbc4b15cac78331d89b2ddf7185b5634097f94c6d4
@@ -380,6 +902,7 @@ mod eth_utils_common_tests {
             1_000_000_000_000_000,
             &kap_privkey,
             chain_info.evm_chain_id.expect("EVM chain ID"),
+            chain_info.supports_eip1559,
             nonce,
         )
         .expect("Valid signed txn");
@@ -0,0 +1,82 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::{String, ToString};
+use pink_web3::{
+    contract::{Contract, Options},
+    transports::PinkHttp,
+    types::{SignedTransaction, H256},
+};
+
+use privadex_chain_metadata::common::{EthAddress, Nonce};
+
+use super::common;
+
+pub struct DepositForwarderFactoryContract {
+    contract: Contract<PinkHttp>,
+    rpc_url: String,
+}
+
+impl DepositForwarderFactoryContract {
+    pub fn new(rpc_url: &str, contract_address: EthAddress) -> common::Result<Self> {
+        let contract = Contract::from_json(
+            common::eth(rpc_url),
+            contract_address,
+            include_bytes!("./eth_abi/deposit_forwarder_factory_abi.json"),
+        )
+        .map_err(|_| common::EthError::InvalidABI)?;
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            contract,
+        })
+    }
+
+    /// Deploys the forwarder for this salt (if it is not already deployed) and transfers its
+    /// full balance of token to the recipient. token = 0x0 sweeps the native token.
+    /// Only the factory owner (the escrow) may call this
+    pub fn sweep(
+        &self,
+        salt: [u8; 32],
+        token: EthAddress,
+        recipient: EthAddress,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "sweep";
+        let params = (H256::from(salt), token, recipient);
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+}
+
+impl common::ContractWrapper for DepositForwarderFactoryContract {
+    fn get_rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+}
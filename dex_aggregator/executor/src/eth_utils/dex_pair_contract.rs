@@ -0,0 +1,166 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use ink::prelude::string::{String, ToString};
+use pink_web3::{
+    contract::{Contract, Options},
+    transports::{resolve_ready, PinkHttp},
+    types::U256,
+};
+
+use privadex_chain_metadata::common::{Amount, EthAddress};
+
+use super::common;
+
+// A Uniswap V2-style pair, which is also the ERC20 of its LP tokens
+pub struct DEXPairContract {
+    contract: Contract<PinkHttp>,
+    rpc_url: String,
+}
+
+impl DEXPairContract {
+    pub fn new(rpc_url: &str, contract_address: EthAddress) -> common::Result<Self> {
+        let contract = Contract::from_json(
+            common::eth(rpc_url),
+            contract_address,
+            include_bytes!("./eth_abi/dexpair_abi.json"),
+        )
+        .map_err(|_| common::EthError::InvalidABI)?;
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            contract,
+        })
+    }
+
+    pub fn factory(&self) -> common::Result<EthAddress> {
+        let x = resolve_ready(
+            self.contract
+                .query("factory", (), None, Options::default(), None),
+        );
+        x.map_err(|_| common::EthError::ContractCallFailed)
+    }
+
+    pub fn token0(&self) -> common::Result<EthAddress> {
+        let x = resolve_ready(
+            self.contract
+                .query("token0", (), None, Options::default(), None),
+        );
+        x.map_err(|_| common::EthError::ContractCallFailed)
+    }
+
+    pub fn token1(&self) -> common::Result<EthAddress> {
+        let x = resolve_ready(
+            self.contract
+                .query("token1", (), None, Options::default(), None),
+        );
+        x.map_err(|_| common::EthError::ContractCallFailed)
+    }
+
+    // (reserve0, reserve1), in the order of token0 and token1
+    pub fn get_reserves(&self) -> common::Result<(Amount, Amount)> {
+        let x: Result<(U256, U256, U256), _> =
+            resolve_ready(
+                self.contract
+                    .query("getReserves", (), None, Options::default(), None),
+            );
+        let (reserve0, reserve1, _) = x.map_err(|_| common::EthError::ContractCallFailed)?;
+        Ok((
+            common::u256_to_u128(reserve0)?,
+            common::u256_to_u128(reserve1)?,
+        ))
+    }
+
+    // Reserves ordered as (reserve of token, reserve of the other token)
+    pub fn get_reserves_for(&self, token: EthAddress) -> common::Result<(Amount, Amount)> {
+        let (reserve0, reserve1) = self.get_reserves()?;
+        if token == self.token0()? {
+            Ok((reserve0, reserve1))
+        } else if token == self.token1()? {
+            Ok((reserve1, reserve0))
+        } else {
+            Err(common::EthError::ParseFailed)
+        }
+    }
+
+    pub fn total_supply(&self) -> common::Result<Amount> {
+        let x =
+            resolve_ready(
+                self.contract
+                    .query("totalSupply", (), None, Options::default(), None),
+            );
+        let amount_u256: U256 = x.map_err(|_| common::EthError::ContractCallFailed)?;
+        common::u256_to_u128(amount_u256)
+    }
+}
+
+impl common::ContractWrapper for DEXPairContract {
+    fn get_rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+}
+
+#[cfg(test)]
+mod dexpair_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::{get_dexes_from_chain_id, registry::chain::chain_info_registry};
+
+    use super::*;
+    use crate::eth_utils::dex_router_contract::DEXRouterContract;
+
+    // StellaSwap's WGLMR/xcDOT pair
+    fn get_moonbeam_contract() -> DEXPairContract {
+        DEXPairContract::new(
+            &chain_info_registry::MOONBEAM_INFO.rpc_url,
+            EthAddress {
+                0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af"),
+            },
+        )
+        .expect("Invalid ABI")
+    }
+
+    #[test]
+    fn test_moonbeam_dexpair_tokens_and_reserves() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let pair = get_moonbeam_contract();
+        let token0 = pair.token0().expect("Request failed");
+        let token1 = pair.token1().expect("Request failed");
+        assert_ne!(token0, token1);
+
+        let (reserve0, reserve1) = pair.get_reserves().expect("Request failed");
+        assert!(reserve0 > 0 && reserve1 > 0);
+        assert_eq!(
+            pair.get_reserves_for(token1).expect("Request failed"),
+            (reserve1, reserve0)
+        );
+        assert!(pair.total_supply().expect("Request failed") > 0);
+    }
+
+    #[test]
+    fn test_moonbeam_dexpair_factory() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let chain_info = chain_info_registry::MOONBEAM_INFO;
+        let dex = get_dexes_from_chain_id(&chain_info.chain_id)[0];
+        let router_factory = DEXRouterContract::new(&chain_info.rpc_url, dex.eth_dex_router)
+            .expect("Invalid ABI")
+            .factory()
+            .expect("Request failed");
+        assert_eq!(
+            get_moonbeam_contract().factory().expect("Request failed"),
+            router_factory
+        );
+    }
+}
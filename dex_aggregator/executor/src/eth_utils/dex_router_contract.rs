@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use ink::prelude::{
     string::{String, ToString},
     vec::Vec,
 };
@@ -26,7 +26,7 @@ use pink_web3::{
     types::{SignedTransaction, U256},
 };
 
-use privadex_chain_metadata::common::{Amount, EthAddress, MillisSinceEpoch, Nonce, SecretKey};
+use privadex_chain_metadata::common::{Amount, EthAddress, Nonce};
 
 use super::common;
 
@@ -89,14 +89,33 @@ impl DEXRouterContract {
         common::u256_to_u128(amount_u256)
     }
 
+    // Amount out of every hop along path, so the last element is the swap's amount out
+    pub fn get_amounts_out(
+        &self,
+        amount_in: Amount,
+        path: Vec<EthAddress>,
+    ) -> common::Result<Vec<Amount>> {
+        let x: Result<Vec<U256>, _> = resolve_ready(self.contract.query(
+            "getAmountsOut",
+            (U256::from(amount_in), path),
+            None,
+            Options::default(),
+            None,
+        ));
+        let amounts_u256 = x.map_err(|_| common::EthError::ContractCallFailed)?;
+        amounts_u256.into_iter().map(common::u256_to_u128).collect()
+    }
+
     pub fn swap_exact_tokens_for_tokens(
         &self,
         amount_in: Amount,
         amount_out_min: Amount,
         path: Vec<EthAddress>,
         to: EthAddress,
-        deadline: MillisSinceEpoch,
-        key: &SecretKey,
+        // Compared against block.timestamp, so it is in seconds
+        deadline_secs: u64,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "swapExactTokensForTokens";
@@ -105,7 +124,7 @@ impl DEXRouterContract {
             U256::from(amount_out_min),
             path.clone(),
             to,
-            U256::from(deadline),
+            U256::from(deadline_secs),
         );
         let options_seed = Options::default();
         common::create_raw_txn(
@@ -116,6 +135,7 @@ impl DEXRouterContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -126,8 +146,9 @@ impl DEXRouterContract {
         amount_out_min: Amount,
         path: Vec<EthAddress>,
         to: EthAddress,
-        deadline: MillisSinceEpoch,
-        key: &SecretKey,
+        deadline_secs: u64,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "swapExactETHForTokens";
@@ -135,7 +156,7 @@ impl DEXRouterContract {
             U256::from(amount_out_min),
             path.clone(),
             to,
-            U256::from(deadline),
+            U256::from(deadline_secs),
         );
         let options_seed = Options::with(|options| options.value = Some(U256::from(amount_in)));
         common::create_raw_txn(
@@ -146,6 +167,7 @@ impl DEXRouterContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -156,8 +178,9 @@ impl DEXRouterContract {
         amount_out_min: Amount,
         path: Vec<EthAddress>,
         to: EthAddress,
-        deadline: MillisSinceEpoch,
-        key: &SecretKey,
+        deadline_secs: u64,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "swapExactTokensForETH";
@@ -166,7 +189,87 @@ impl DEXRouterContract {
             U256::from(amount_out_min),
             path.clone(),
             to,
-            U256::from(deadline),
+            U256::from(deadline_secs),
+        );
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+
+    // Adds at most the desired amounts in the pair's current ratio, and reverts if either
+    // side would be below its min. Mints the LP tokens to to
+    pub fn add_liquidity(
+        &self,
+        token_a: EthAddress,
+        token_b: EthAddress,
+        amount_a_desired: Amount,
+        amount_b_desired: Amount,
+        amount_a_min: Amount,
+        amount_b_min: Amount,
+        to: EthAddress,
+        deadline_secs: u64,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "addLiquidity";
+        let params = (
+            token_a,
+            token_b,
+            U256::from(amount_a_desired),
+            U256::from(amount_b_desired),
+            U256::from(amount_a_min),
+            U256::from(amount_b_min),
+            to,
+            U256::from(deadline_secs),
+        );
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+
+    // Burns liquidity LP tokens (pulled from the sender with transferFrom) and sends both of
+    // the pair's tokens to to. Reverts if either side would be below its min
+    pub fn remove_liquidity(
+        &self,
+        token_a: EthAddress,
+        token_b: EthAddress,
+        liquidity: Amount,
+        amount_a_min: Amount,
+        amount_b_min: Amount,
+        to: EthAddress,
+        deadline_secs: u64,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "removeLiquidity";
+        let params = (
+            token_a,
+            token_b,
+            U256::from(liquidity),
+            U256::from(amount_a_min),
+            U256::from(amount_b_min),
+            to,
+            U256::from(deadline_secs),
         );
         let options_seed = Options::default();
         common::create_raw_txn(
@@ -177,6 +280,7 @@ impl DEXRouterContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -251,6 +355,21 @@ mod dexrouter_tests {
         assert_eq!(quote, 200);
     }
 
+    #[test]
+    fn test_moonbeam_dexrouter_get_amounts_out() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let weth = get_moonbeam_contract().weth().expect("Request failed");
+        let usdc = EthAddress {
+            0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c"),
+        };
+        let amounts = get_moonbeam_contract()
+            .get_amounts_out(1_000_000_000_000_000_000, vec![weth, usdc])
+            .expect("Request failed");
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(amounts[0], 1_000_000_000_000_000_000);
+        assert!(amounts[1] > 0);
+    }
+
     #[test]
     fn test_moonbase_dexrouter_swap_exact_tokens_for_tokens_txn() {
         // Generated https://moonbase.moonscan.io/tx/0x8758067010d4f67b2620bd77a0268426d0fbb3e91913b4c87b2fb83002819c9d
@@ -268,7 +387,7 @@ mod dexrouter_tests {
         let to = EthAddress {
             0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
         };
-        let deadline = MillisSinceEpoch::MAX;
+        let deadline_secs = u64::MAX;
         let kap_privkey = {
             let privkey_str =
                 std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set");
@@ -283,8 +402,9 @@ mod dexrouter_tests {
                 amount_out_min,
                 path,
                 to,
-                deadline,
+                deadline_secs,
                 &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Expect swap tokens for tokens");
@@ -308,7 +428,7 @@ mod dexrouter_tests {
         let to = EthAddress {
             0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
         };
-        let deadline = MillisSinceEpoch::MAX;
+        let deadline_secs = u64::MAX;
         let kap_privkey = {
             let privkey_str =
                 std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set");
@@ -323,8 +443,9 @@ mod dexrouter_tests {
                 amount_out_min,
                 path,
                 to,
-                deadline,
+                deadline_secs,
                 &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Expect swap eth for tokens");
@@ -348,7 +469,7 @@ mod dexrouter_tests {
         let to = EthAddress {
             0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
         };
-        let deadline = MillisSinceEpoch::MAX;
+        let deadline_secs = u64::MAX;
         let kap_privkey = {
             let privkey_str =
                 std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set");
@@ -363,8 +484,9 @@ mod dexrouter_tests {
                 amount_out_min,
                 path,
                 to,
-                deadline,
+                deadline_secs,
                 &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
                 nonce,
             )
             .expect("Expect swap tokens for eth");
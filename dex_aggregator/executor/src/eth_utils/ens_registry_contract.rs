@@ -0,0 +1,112 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+use pink_web3::{
+    contract::{Contract, Options},
+    signing::keccak256,
+    transports::{resolve_ready, PinkHttp},
+    types::H256,
+};
+
+use privadex_chain_metadata::common::EthAddress;
+
+use super::common;
+
+// ENS-compatible registries (e.g. ENS itself, or a fork of it deployed on Moonbeam) map the
+// namehash of a name to a resolver contract, which in turn maps it to an address
+pub struct ENSRegistryContract {
+    registry: Contract<PinkHttp>,
+    rpc_url: String,
+}
+
+impl ENSRegistryContract {
+    pub fn new(rpc_url: String, registry_address: EthAddress) -> common::Result<Self> {
+        let registry = Contract::from_json(
+            common::eth(&rpc_url),
+            registry_address,
+            include_bytes!("./eth_abi/ens_abi.json"),
+        )
+        .map_err(|_| common::EthError::InvalidABI)?;
+        Ok(Self { registry, rpc_url })
+    }
+
+    // None if the name has no resolver or its resolver has no address for it
+    pub fn resolve(&self, name: &str) -> common::Result<Option<EthAddress>> {
+        let node = H256::from(namehash(name));
+        let resolver_address: EthAddress =
+            resolve_ready(
+                self.registry
+                    .query("resolver", (node,), None, Options::default(), None),
+            )
+            .map_err(|_| common::EthError::ContractCallFailed)?;
+        if resolver_address.is_zero() {
+            return Ok(None);
+        }
+        let resolver = Contract::from_json(
+            common::eth(&self.rpc_url),
+            resolver_address,
+            include_bytes!("./eth_abi/ens_abi.json"),
+        )
+        .map_err(|_| common::EthError::InvalidABI)?;
+        let address: EthAddress =
+            resolve_ready(resolver.query("addr", (node,), None, Options::default(), None))
+                .map_err(|_| common::EthError::ContractCallFailed)?;
+        if address.is_zero() {
+            Ok(None)
+        } else {
+            Ok(Some(address))
+        }
+    }
+}
+
+// EIP-137 namehash. Expects an already-normalized (lowercase ASCII) name
+pub fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(&node);
+        preimage[32..].copy_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(&preimage);
+    }
+    node
+}
+
+#[cfg(test)]
+mod ens_registry_tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        // Test vectors from EIP-137
+        assert_eq!(namehash(""), [0u8; 32]);
+        assert_eq!(
+            namehash("eth"),
+            hex!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            hex!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+    }
+}
@@ -16,7 +16,10 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::string::{String, ToString};
+use ink::prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use pink_web3::{
     contract::{Contract, Options},
     signing::keccak256,
@@ -24,7 +27,7 @@ use pink_web3::{
     types::{Log, SignedTransaction, U256},
 };
 
-use privadex_chain_metadata::common::{Amount, EthAddress, EthTxnHash, Nonce, SecretKey};
+use privadex_chain_metadata::common::{Amount, EthAddress, EthTxnHash, Nonce};
 
 use super::common;
 
@@ -101,11 +104,25 @@ impl ERC20Contract {
         common::u256_to_u128(amount_u256)
     }
 
+    pub fn allowance(&self, owner: EthAddress, spender: EthAddress) -> common::Result<Amount> {
+        let x = resolve_ready(self.contract.query(
+            "allowance",
+            (owner, spender),
+            None,
+            Options::default(),
+            None,
+        ));
+        let amount_u256: U256 = x.map_err(|_| common::EthError::ContractCallFailed)?;
+        // An infinite approval is U256::MAX, which does not fit in an Amount
+        Ok(common::u256_to_u128(amount_u256).unwrap_or(Amount::MAX))
+    }
+
     pub fn transfer(
         &self,
         to: EthAddress,
         amount: Amount,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "transfer";
@@ -119,6 +136,36 @@ impl ERC20Contract {
             params,
             options_seed,
             key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+
+    // For a transfer signed without gas estimation (see common::create_fixed_gas_raw_txn)
+    pub fn encode_transfer(&self, to: EthAddress, amount: Amount) -> common::Result<Vec<u8>> {
+        common::encode_call(&self.contract, "transfer", 0, (to, U256::from(amount)))
+    }
+
+    pub fn approve(
+        &self,
+        spender: EthAddress,
+        amount: Amount,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "approve";
+        let params = (spender, U256::from(amount));
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -211,7 +258,13 @@ mod erc20_tests {
         };
         let nonce = 0;
         let _signed_txn = get_moonbase_alpha_token_contract()
-            .transfer(to, amount, &kap_privkey, nonce)
+            .transfer(
+                to,
+                amount,
+                &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
+                nonce,
+            )
             .expect("Signed ERC20 transfer txn");
 
         // common::print_and_send_txn(&chain_info_registry::MOONBASEALPHA_INFO.rpc_url, signed_txn);
@@ -16,9 +16,16 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod aggregator_api;
 pub mod astar_xcm_precompile_contract;
 pub mod common;
+pub mod deposit_forwarder_factory_contract;
+pub mod dex_pair_contract;
 pub mod dex_router_contract;
+pub mod ens_registry_contract;
 pub mod erc20_contract;
 pub mod parse_txn_helper;
+pub mod threshold_round_txn;
 pub mod weth_contract;
+pub mod wormhole_guardian_api;
+pub mod wormhole_token_bridge_contract;
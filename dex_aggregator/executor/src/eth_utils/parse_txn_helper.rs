@@ -17,11 +17,21 @@
  */
 
 #[allow(unused_imports)]
-use pink_web3::types::{Bytes, Transaction, TransactionId, TransactionReceipt, U256};
+use ink::prelude::{format, vec::Vec};
+#[allow(unused_imports)]
+use pink_web3::types::{
+    BlockId, BlockNumber, Bytes, Log, Transaction, TransactionId, TransactionReceipt, U256,
+};
 #[allow(unused_imports)]
 use privadex_chain_metadata::common::{Amount, EthAddress, EthTxnHash};
+#[allow(unused_imports)]
+use privadex_common::utils::{general_utils::slice_to_hex_string, http_request::http_post_wrapper};
 
-use super::{common, erc20_contract::ERC20Contract};
+#[allow(unused_imports)]
+use super::{
+    common, erc20_contract::ERC20Contract,
+    wormhole_token_bridge_contract::WormholeTokenBridgeContract,
+};
 
 /// Parse information out of transfer transactions
 #[cfg(not(feature = "mock-txn-send"))]
@@ -49,7 +59,7 @@ pub fn parse_transfer_from_eth_send_txn(
     rpc_url: &str,
     eth_send_txn: EthTxnHash,
 ) -> common::Result<common::EthTransfer> {
-    ink_env::debug_println!("[Mock Eth parse_transfer_from_eth_send_txn]");
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_eth_send_txn]");
     Ok(common::EthTransfer {
         is_txn_success: true,
         from: EthAddress::zero(),
@@ -59,28 +69,37 @@ pub fn parse_transfer_from_eth_send_txn(
     })
 }
 
+/// Looks for transfers of token to recipient anywhere in the txn, so a deposit made through a DEX
+/// router or a multicall contract parses like a direct transfer. Their amounts are added up, and
+/// from is the txn's sender rather than whichever contract moved the tokens
 #[cfg(not(feature = "mock-txn-send"))]
 pub fn parse_transfer_from_erc20_txn(
     rpc_url: &str,
     erc20_txn_hash: EthTxnHash,
+    token: &EthAddress,
+    recipient: &EthAddress,
 ) -> common::Result<common::ERC20Transfer> {
     let receipt = get_txn_receipt(rpc_url, erc20_txn_hash)?;
-    if receipt.logs.len() != 1 {
-        return Err(common::EthError::ParseFailed);
-    }
     let is_txn_success = receipt.status == Some(1.into());
     let gas_fee_native = get_gas_fee_native(&receipt)?;
-    let transfer_log =
-        ERC20Contract::parse_transfer_log(&receipt.logs[0], is_txn_success, gas_fee_native)?;
-    // We expect that transfer_log.from == receipt.from
-    Ok(transfer_log)
+    let mut transfer = find_transfer_to_recipient(
+        &receipt.logs,
+        token,
+        recipient,
+        is_txn_success,
+        gas_fee_native,
+    )?;
+    transfer.from = receipt.from;
+    Ok(transfer)
 }
 #[cfg(feature = "mock-txn-send")]
 pub fn parse_transfer_from_erc20_txn(
     rpc_url: &str,
     erc20_txn_hash: EthTxnHash,
+    token: &EthAddress,
+    recipient: &EthAddress,
 ) -> common::Result<common::ERC20Transfer> {
-    ink_env::debug_println!("[Mock Eth parse_transfer_from_erc20_txn]");
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_erc20_txn]");
     Ok(common::ERC20Transfer {
         is_txn_success: true,
         token: EthAddress::zero(),
@@ -129,7 +148,169 @@ pub fn parse_transfer_from_dex_swap_txn(
     rpc_url: &str,
     dex_swap_txn_hash: EthTxnHash,
 ) -> common::Result<common::ERC20Transfer> {
-    ink_env::debug_println!("[Mock Eth parse_transfer_from_dex_swap_txn]");
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_dex_swap_txn]");
+    Ok(common::ERC20Transfer {
+        is_txn_success: true,
+        token: EthAddress::zero(),
+        from: EthAddress::zero(),
+        to: EthAddress::zero(),
+        amount: 1_000_000_000,
+        gas_fee_native: 2_000_000_000,
+    })
+}
+
+/// The dest token the aggregator swap sent to recipient. The aggregator may route through
+/// several pools and pay out in more than one transfer, so they are added up
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn parse_transfer_from_aggregator_swap_txn(
+    rpc_url: &str,
+    aggregator_swap_txn_hash: EthTxnHash,
+    dest_token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    parse_transfer_to_recipient_txn(rpc_url, aggregator_swap_txn_hash, dest_token, recipient)
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn parse_transfer_from_aggregator_swap_txn(
+    rpc_url: &str,
+    aggregator_swap_txn_hash: EthTxnHash,
+    dest_token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_aggregator_swap_txn]");
+    Ok(common::ERC20Transfer {
+        is_txn_success: true,
+        token: *dest_token,
+        from: EthAddress::zero(),
+        to: *recipient,
+        amount: 1_000_000_000,
+        gas_fee_native: 2_000_000_000,
+    })
+}
+
+/// The LP tokens the pair minted to recipient for an addLiquidity
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn parse_transfer_from_add_liquidity_txn(
+    rpc_url: &str,
+    add_liquidity_txn_hash: EthTxnHash,
+    lp_token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    parse_transfer_to_recipient_txn(rpc_url, add_liquidity_txn_hash, lp_token, recipient)
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn parse_transfer_from_add_liquidity_txn(
+    rpc_url: &str,
+    add_liquidity_txn_hash: EthTxnHash,
+    lp_token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_add_liquidity_txn]");
+    Ok(common::ERC20Transfer {
+        is_txn_success: true,
+        token: *lp_token,
+        from: EthAddress::zero(),
+        to: *recipient,
+        amount: 1_000_000_000,
+        gas_fee_native: 2_000_000_000,
+    })
+}
+
+/// One of the pair's tokens that a removeLiquidity sent to recipient. Call it once per token
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn parse_transfer_from_remove_liquidity_txn(
+    rpc_url: &str,
+    remove_liquidity_txn_hash: EthTxnHash,
+    token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    parse_transfer_to_recipient_txn(rpc_url, remove_liquidity_txn_hash, token, recipient)
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn parse_transfer_from_remove_liquidity_txn(
+    rpc_url: &str,
+    remove_liquidity_txn_hash: EthTxnHash,
+    token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_remove_liquidity_txn]");
+    Ok(common::ERC20Transfer {
+        is_txn_success: true,
+        token: *token,
+        from: EthAddress::zero(),
+        to: *recipient,
+        amount: 1_000_000_000,
+        gas_fee_native: 2_000_000_000,
+    })
+}
+
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn parse_wormhole_deposit_txn(
+    rpc_url: &str,
+    deposit_txn_hash: EthTxnHash,
+    token_bridge: EthAddress,
+) -> common::Result<common::WormholeDeposit> {
+    let receipt = get_txn_receipt(rpc_url, deposit_txn_hash)?;
+    let is_txn_success = receipt.status == Some(1.into());
+    let gas_fee_native = get_gas_fee_native(&receipt)?;
+    // A failed txn has no logs, so there is no sequence to parse
+    let sequence = if is_txn_success {
+        WormholeTokenBridgeContract::parse_sequence_from_logs(&receipt.logs, token_bridge)?
+    } else {
+        0
+    };
+    Ok(common::WormholeDeposit {
+        is_txn_success,
+        sequence,
+        gas_fee_native,
+    })
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn parse_wormhole_deposit_txn(
+    rpc_url: &str,
+    deposit_txn_hash: EthTxnHash,
+    token_bridge: EthAddress,
+) -> common::Result<common::WormholeDeposit> {
+    ink::env::debug_println!("[Mock Eth parse_wormhole_deposit_txn]");
+    Ok(common::WormholeDeposit {
+        is_txn_success: true,
+        sequence: 1,
+        gas_fee_native: 2_000_000_000,
+    })
+}
+
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn parse_transfer_from_wormhole_claim_txn(
+    rpc_url: &str,
+    claim_txn_hash: EthTxnHash,
+) -> common::Result<common::ERC20Transfer> {
+    // completeTransfer either mints the wrapped token or releases the locked token. Either
+    // way the ERC20 Transfer to the recipient is the only Transfer log
+    let receipt = get_txn_receipt(rpc_url, claim_txn_hash)?;
+    let is_txn_success = receipt.status == Some(1.into());
+    let gas_fee_native = get_gas_fee_native(&receipt)?;
+    for log in receipt.logs.iter() {
+        if let Ok(transfer_log) =
+            ERC20Contract::parse_transfer_log(log, is_txn_success, gas_fee_native)
+        {
+            return Ok(transfer_log);
+        }
+    }
+    Ok(common::ERC20Transfer {
+        is_txn_success,
+        token: EthAddress::zero(),
+        from: EthAddress::zero(),
+        to: EthAddress::zero(),
+        amount: 0,
+        gas_fee_native,
+    })
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn parse_transfer_from_wormhole_claim_txn(
+    rpc_url: &str,
+    claim_txn_hash: EthTxnHash,
+) -> common::Result<common::ERC20Transfer> {
+    ink::env::debug_println!("[Mock Eth parse_transfer_from_wormhole_claim_txn]");
     Ok(common::ERC20Transfer {
         is_txn_success: true,
         token: EthAddress::zero(),
@@ -152,7 +333,7 @@ pub fn get_txn_summary(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<co
 }
 #[cfg(feature = "mock-txn-send")]
 pub fn get_txn_summary(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<common::TxnSummary> {
-    ink_env::debug_println!("[Mock Eth get_txn_summary]");
+    ink::env::debug_println!("[Mock Eth get_txn_summary]");
     // let is_txn_success = unsafe {
     //     static mut x: u32 = 0;
     //     if x < 1 {
@@ -167,6 +348,114 @@ pub fn get_txn_summary(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<co
     })
 }
 
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn get_txn_inclusion(
+    rpc_url: &str,
+    txn_hash: EthTxnHash,
+) -> common::Result<common::TxnInclusion> {
+    let receipt = get_txn_receipt(rpc_url, txn_hash)?;
+    let block_num = receipt
+        .block_number
+        .ok_or(common::EthError::TransactionNotFound)?;
+    // Some providers load balance across nodes, so the receipt may come from a node that has
+    // not seen the reorg yet. Comparing against the block at that height catches that too
+    let canonical_block = common::eth(rpc_url)
+        .block(BlockId::Number(BlockNumber::Number(block_num)))
+        .resolve()
+        .map_err(|_| common::EthError::BlockNumberRequestFailed)?
+        .ok_or(common::EthError::BlockNumberRequestFailed)?;
+    Ok(common::TxnInclusion {
+        block_num: block_num.low_u32(),
+        is_canonical: receipt.block_hash.is_some() && canonical_block.hash == receipt.block_hash,
+    })
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn get_txn_inclusion(
+    rpc_url: &str,
+    txn_hash: EthTxnHash,
+) -> common::Result<common::TxnInclusion> {
+    ink::env::debug_println!("[Mock Eth get_txn_inclusion]");
+    Ok(common::TxnInclusion {
+        block_num: 0,
+        is_canonical: true,
+    })
+}
+
+/// The RPC's eth_getTransactionReceipt response as is, for when we want to keep the receipt
+/// rather than parse it (e.g. a failure bundle)
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn get_raw_txn_receipt(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<Vec<u8>> {
+    let data = format!(
+        r#"{{"id":1,"jsonrpc":"2.0","method":"eth_getTransactionReceipt","params":["{}"]}}"#,
+        slice_to_hex_string(txn_hash.as_bytes())
+    )
+    .into_bytes();
+    http_post_wrapper(rpc_url, data).map_err(|_| common::EthError::TransactionNotFound)
+}
+#[cfg(feature = "mock-txn-send")]
+pub fn get_raw_txn_receipt(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<Vec<u8>> {
+    ink::env::debug_println!("[Mock Eth get_raw_txn_receipt]");
+    Err(common::EthError::TransactionNotFound)
+}
+
+#[cfg(not(feature = "mock-txn-send"))]
+fn parse_transfer_to_recipient_txn(
+    rpc_url: &str,
+    txn_hash: EthTxnHash,
+    token: &EthAddress,
+    recipient: &EthAddress,
+) -> common::Result<common::ERC20Transfer> {
+    let receipt = get_txn_receipt(rpc_url, txn_hash)?;
+    let is_txn_success = receipt.status == Some(1.into());
+    let gas_fee_native = get_gas_fee_native(&receipt)?;
+    match find_transfer_to_recipient(
+        &receipt.logs,
+        token,
+        recipient,
+        is_txn_success,
+        gas_fee_native,
+    ) {
+        Ok(mut transfer) => {
+            transfer.from = receipt.from;
+            Ok(transfer)
+        }
+        // Like a failed DEX swap, a failed txn has no logs to parse
+        Err(_) if !is_txn_success => Ok(common::ERC20Transfer {
+            is_txn_success,
+            token: *token,
+            from: receipt.from,
+            to: *recipient,
+            amount: 0,
+            gas_fee_native,
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+// Logs that are not ERC20 Transfers (e.g. a router's Swap or an Approval) are skipped
+fn find_transfer_to_recipient(
+    logs: &[Log],
+    token: &EthAddress,
+    recipient: &EthAddress,
+    is_txn_success: bool,
+    gas_fee_native: Amount,
+) -> common::Result<common::ERC20Transfer> {
+    let mut transfers = logs
+        .iter()
+        .filter_map(|log| {
+            ERC20Contract::parse_transfer_log(log, is_txn_success, gas_fee_native).ok()
+        })
+        .filter(|transfer| transfer.token == *token && transfer.to == *recipient);
+    let mut found_transfer = transfers.next().ok_or(common::EthError::ParseFailed)?;
+    for transfer in transfers {
+        found_transfer.amount = found_transfer
+            .amount
+            .checked_add(transfer.amount)
+            .ok_or(common::EthError::ParseFailed)?;
+    }
+    Ok(found_transfer)
+}
+
 fn get_gas_fee_native(receipt: &TransactionReceipt) -> common::Result<Amount> {
     let gas_price_u256 = receipt
         .effective_gas_price
@@ -196,6 +485,8 @@ fn get_txn(rpc_url: &str, txn_hash: EthTxnHash) -> common::Result<Transaction> {
 #[cfg(test)]
 mod parse_txn_tests {
     use hex_literal::hex;
+    use ink::prelude::vec;
+    use pink_web3::{signing::keccak256, types::H256};
     use privadex_chain_metadata::{common::EthAddress, registry::chain::chain_info_registry};
 
     use super::*;
@@ -235,8 +526,17 @@ mod parse_txn_tests {
             0: hex!("ccedfd63a7f3e2c98e33ea1eebb4bf76ade3b607c8800e5cbbe6557a01549d61"),
         };
         let rpc_url = chain_info_registry::MOONBEAM_INFO.rpc_url;
-        let erc20_transfer =
-            parse_transfer_from_erc20_txn(&rpc_url, txn_hash).expect("Parse failed");
+        let erc20_transfer = parse_transfer_from_erc20_txn(
+            &rpc_url,
+            txn_hash,
+            &EthAddress {
+                0: hex!("ffffffff1fcacbd218edc0eba20fc2308c778080"),
+            },
+            &EthAddress {
+                0: hex!("e065662bf49f036756f5170edcd5cfca0a56f9a2"),
+            },
+        )
+        .expect("Parse failed");
         assert_eq!(
             erc20_transfer.token,
             EthAddress {
@@ -266,8 +566,13 @@ mod parse_txn_tests {
             0: hex!("d9ff564a3b27e41a9c59eabbec5f5564c3bf1c0bba9e54c595c3e916082ff3a8"),
         };
         let rpc_url = chain_info_registry::MOONBEAM_INFO.rpc_url;
-        let err = parse_transfer_from_erc20_txn(&rpc_url, txn_hash)
-            .expect_err("Transaction is not a transfer");
+        let err = parse_transfer_from_erc20_txn(
+            &rpc_url,
+            txn_hash,
+            &EthAddress::zero(),
+            &EthAddress::zero(),
+        )
+        .expect_err("Transaction is not a transfer");
         assert_eq!(err, common::EthError::ParseFailed);
     }
 
@@ -278,11 +583,67 @@ mod parse_txn_tests {
             0: hex!("a9ff564a3b27e41a9c59eabbec5f5564c3bf1c0bba9e54c595c3e916082ff3a8"),
         };
         let rpc_url = chain_info_registry::MOONBEAM_INFO.rpc_url;
-        let err = parse_transfer_from_erc20_txn(&rpc_url, txn_hash)
-            .expect_err("Transaction is not a transfer");
+        let err = parse_transfer_from_erc20_txn(
+            &rpc_url,
+            txn_hash,
+            &EthAddress::zero(),
+            &EthAddress::zero(),
+        )
+        .expect_err("Transaction is not a transfer");
         assert_eq!(err, common::EthError::TransactionNotFound);
     }
 
+    #[test]
+    fn test_find_transfer_to_recipient() {
+        let token = EthAddress {
+            0: hex!("ffffffff1fcacbd218edc0eba20fc2308c778080"),
+        };
+        let router = EthAddress {
+            0: hex!("70085a09d30d6f8c4ecf6ee10120d1847383bb57"),
+        };
+        let escrow = EthAddress {
+            0: hex!("05a81d8564a3ea298660e34e03e5eff9a29d7a2a"),
+        };
+        let transfer_log = |token: EthAddress, to: EthAddress, amount: u128| Log {
+            address: token,
+            topics: vec![
+                keccak256("Transfer(address,address,uint256)".as_bytes()).into(),
+                H256::from(router),
+                H256::from(to),
+            ],
+            data: Bytes(H256::from_low_u64_be(amount as u64).as_bytes().to_vec()),
+            ..Default::default()
+        };
+        // A router deposit: the user's tokens go to the router, which forwards them to the escrow
+        // in two parts, next to a Transfer of some other token and a non-Transfer log
+        let logs = vec![
+            transfer_log(token, router, 3_000),
+            Log {
+                address: router,
+                topics: vec![keccak256("Deposit(address,uint256)".as_bytes()).into()],
+                ..Default::default()
+            },
+            transfer_log(router, escrow, 5_000),
+            transfer_log(token, escrow, 1_000),
+            transfer_log(token, escrow, 2_000),
+        ];
+        let transfer = find_transfer_to_recipient(&logs, &token, &escrow, true, 10)
+            .expect("Found transfer to escrow");
+        assert_eq!(transfer.token, token);
+        assert_eq!(transfer.to, escrow);
+        assert_eq!(transfer.amount, 3_000);
+        assert_eq!(transfer.gas_fee_native, 10);
+
+        let other_recipient = EthAddress {
+            0: hex!("e065662bf49f036756f5170edcd5cfca0a56f9a2"),
+        };
+        assert_eq!(
+            find_transfer_to_recipient(&logs, &token, &other_recipient, true, 10)
+                .expect_err("No transfer to recipient"),
+            common::EthError::ParseFailed
+        );
+    }
+
     #[test]
     fn test_parse_dex_swap_transfer() {
         pink_extension_runtime::mock_ext::mock_all_ext();
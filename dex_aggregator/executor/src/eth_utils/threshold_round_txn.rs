@@ -0,0 +1,258 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use pink_web3::types::{Bytes, TransactionParameters, U256, U64};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, EthAddress, EvmChainId, Nonce};
+
+use super::{
+    aggregator_api::decode_swap_calldata,
+    common::{self, EthError, Result, EIP1559_TXN_TYPE},
+};
+use crate::concurrency_coordinator::threshold_signing_coordinator::THRESHOLD_ROUND_MILLIS;
+
+// A pinned txn may use at most this many times the gas limit and gas price that we would
+const MAX_PINNED_GAS_RATIO: u128 = 2;
+// Parties set DEX deadlines from their own clocks, which are up to about a round apart
+const MAX_DEADLINE_DRIFT_SECS: u128 = 2 * THRESHOLD_ROUND_MILLIS as u128 / 1000;
+// Calldata words below this (in 2001) can't be deadlines, so they must match exactly
+const MIN_DEADLINE_SECS: u128 = 1_000_000_000;
+
+/// The txn that the parties of a threshold round sign. Every field is filled in, since
+/// pink_web3 would otherwise fill in the missing ones (e.g. the gas price) differently for
+/// each party
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+pub struct ThresholdRoundTxn {
+    pub to: EthAddress,
+    pub value: Amount,
+    pub data: Vec<u8>,
+    pub gas: u128,
+    // The max fee per gas for EIP-1559 txns
+    pub gas_price: Amount,
+    // Some if and only if this is an EIP-1559 txn
+    pub max_priority_fee_per_gas: Option<Amount>,
+    pub chain_id: EvmChainId,
+    pub nonce: Nonce,
+}
+
+impl ThresholdRoundTxn {
+    pub fn from_txn_params(rpc_url: &str, txn_params: &TransactionParameters) -> Result<Self> {
+        let is_eip1559 = txn_params.transaction_type == Some(U64::from(EIP1559_TXN_TYPE));
+        let opt_gas_price = if is_eip1559 {
+            txn_params.max_fee_per_gas
+        } else {
+            txn_params.gas_price
+        };
+        let gas_price = match opt_gas_price {
+            Some(gas_price) => common::u256_to_u128(gas_price)?,
+            None => common::get_gas_price(rpc_url)?,
+        };
+        let max_priority_fee_per_gas = match txn_params.max_priority_fee_per_gas {
+            _ if !is_eip1559 => None,
+            // Same default as pink_web3
+            None => Some(gas_price),
+            Some(max_priority_fee) => Some(common::u256_to_u128(max_priority_fee)?),
+        };
+        let chain_id = match txn_params.chain_id {
+            Some(chain_id) => chain_id,
+            None => common::chain_id(rpc_url)?,
+        };
+        let nonce = txn_params.nonce.ok_or(EthError::UnspecifiedNonce)?;
+        if nonce > Nonce::MAX.into() {
+            return Err(EthError::AmountTooHigh);
+        }
+        Ok(Self {
+            to: txn_params.to.ok_or(EthError::InvalidArgument)?,
+            value: common::u256_to_u128(txn_params.value)?,
+            data: txn_params.data.0.clone(),
+            gas: common::u256_to_u128(txn_params.gas)?,
+            gas_price,
+            max_priority_fee_per_gas,
+            chain_id,
+            nonce: nonce.low_u32(),
+        })
+    }
+
+    pub fn to_txn_params(&self) -> TransactionParameters {
+        let mut tx = TransactionParameters::default();
+        tx.to = Some(self.to);
+        tx.value = U256::from(self.value);
+        tx.data = Bytes(self.data.clone());
+        tx.gas = U256::from(self.gas);
+        tx.chain_id = Some(self.chain_id);
+        tx.nonce = Some(U256::from(self.nonce));
+        match self.max_priority_fee_per_gas {
+            Some(max_priority_fee) => {
+                tx.transaction_type = Some(U64::from(EIP1559_TXN_TYPE));
+                tx.max_fee_per_gas = Some(U256::from(self.gas_price));
+                tx.max_priority_fee_per_gas = Some(U256::from(max_priority_fee));
+            }
+            None => tx.gas_price = Some(U256::from(self.gas_price)),
+        }
+        tx
+    }
+
+    /// True if we can sign this (pinned) txn in place of own_txn, the one we built ourselves.
+    /// It must move the same funds to the same place. Only the gas (within bounds) and the DEX
+    /// deadline may differ, or for aggregator swaps, the route that the aggregator picked
+    pub fn is_compatible_with(&self, own_txn: &Self) -> bool {
+        let is_gas_bounded =
+            |pinned: u128, own: u128| pinned <= own.saturating_mul(MAX_PINNED_GAS_RATIO);
+        self.to == own_txn.to
+            && self.value == own_txn.value
+            && self.chain_id == own_txn.chain_id
+            && self.nonce == own_txn.nonce
+            && is_gas_bounded(self.gas, own_txn.gas)
+            && is_gas_bounded(self.gas_price, own_txn.gas_price)
+            && match (
+                self.max_priority_fee_per_gas,
+                own_txn.max_priority_fee_per_gas,
+            ) {
+                (Some(pinned), Some(own)) => is_gas_bounded(pinned, own),
+                (None, None) => true,
+                _ => false,
+            }
+            && (is_same_calldata_but_deadline(&self.data, &own_txn.data)
+                || is_same_aggregator_swap(&self.data, &own_txn.data))
+    }
+}
+
+// Calldata is a selector followed by 32-byte words. The words may only differ where both
+// look like deadlines set a little apart
+fn is_same_calldata_but_deadline(pinned: &[u8], own: &[u8]) -> bool {
+    if pinned.len() != own.len() || pinned.len() < 4 || pinned[..4] != own[..4] {
+        return pinned == own;
+    }
+    pinned[4..]
+        .chunks(32)
+        .zip(own[4..].chunks(32))
+        .all(|(pinned_word, own_word)| {
+            pinned_word == own_word
+                || match (word_to_u128(pinned_word), word_to_u128(own_word)) {
+                    (Some(pinned_secs), Some(own_secs)) => {
+                        pinned_secs >= MIN_DEADLINE_SECS
+                            && own_secs >= MIN_DEADLINE_SECS
+                            && pinned_secs.abs_diff(own_secs) <= MAX_DEADLINE_DRIFT_SECS
+                    }
+                    _ => false,
+                }
+        })
+}
+
+fn word_to_u128(word: &[u8]) -> Option<u128> {
+    if word.len() != 32 || word[..16].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(u128::from_be_bytes(word[16..].try_into().ok()?))
+}
+
+// The aggregator API may route the same swap differently from one call to the next. The
+// exchange enforces the decoded terms whatever the route, so those are what must agree
+fn is_same_aggregator_swap(pinned: &[u8], own: &[u8]) -> bool {
+    match (decode_swap_calldata(pinned), decode_swap_calldata(own)) {
+        (Ok(pinned_terms), Ok(own_terms)) => {
+            pinned_terms.src_token == own_terms.src_token
+                && pinned_terms.dest_token == own_terms.dest_token
+                && pinned_terms.dest_receiver == own_terms.dest_receiver
+                && pinned_terms.amount_in == own_terms.amount_in
+                && pinned_terms.min_amount_out >= own_terms.min_amount_out
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod threshold_round_txn_tests {
+    use super::*;
+
+    const DEADLINE_SECS: u128 = 1_680_307_200;
+    const AMOUNT_OUT_MIN: u128 = 900_000_000_000_000_000;
+
+    fn encode_word(val: u128) -> Vec<u8> {
+        let mut word = vec![0; 16];
+        word.extend_from_slice(&val.to_be_bytes());
+        word
+    }
+
+    fn swap_txn(amount_out_min: u128, deadline_secs: u128) -> ThresholdRoundTxn {
+        let mut data = vec![0x38, 0xed, 0x17, 0x39];
+        data.extend(encode_word(1_000_000));
+        data.extend(encode_word(amount_out_min));
+        data.extend(encode_word(deadline_secs));
+        ThresholdRoundTxn {
+            to: EthAddress::from([0x22; 20]),
+            value: 0,
+            data,
+            gas: 200_000,
+            gas_price: 2_000_000_000,
+            max_priority_fee_per_gas: Some(1_000_000_000),
+            chain_id: 592,
+            nonce: 7,
+        }
+    }
+
+    #[test]
+    fn test_round_txn_roundtrip() {
+        let round_txn = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS);
+        let encoded = round_txn.encode();
+        let decoded = ThresholdRoundTxn::decode(&mut encoded.as_slice()).expect("decode failed");
+        assert_eq!(decoded, round_txn);
+        let txn_params = round_txn.to_txn_params();
+        assert_eq!(
+            txn_params.transaction_type,
+            Some(U64::from(EIP1559_TXN_TYPE))
+        );
+        // Every field is set, so nothing is left for pink_web3 to fetch
+        assert_eq!(
+            ThresholdRoundTxn::from_txn_params("unused", &txn_params),
+            Ok(round_txn)
+        );
+    }
+
+    #[test]
+    fn test_pinned_deadline_and_gas_drift() {
+        let own_txn = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS);
+        let mut pinned_txn = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS - 240);
+        pinned_txn.gas_price = 3_000_000_000;
+        assert!(pinned_txn.is_compatible_with(&own_txn));
+
+        pinned_txn.gas_price = 5_000_000_000;
+        assert!(!pinned_txn.is_compatible_with(&own_txn));
+    }
+
+    #[test]
+    fn test_pinned_txn_must_move_the_same_funds() {
+        let own_txn = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS);
+        // A deadline a day off
+        assert!(!swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS + 86_400).is_compatible_with(&own_txn));
+        // A lower minimum out
+        assert!(
+            !swap_txn(AMOUNT_OUT_MIN - 10_u128.pow(15), DEADLINE_SECS).is_compatible_with(&own_txn)
+        );
+
+        let mut other_recipient = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS);
+        other_recipient.to = EthAddress::from([0x33; 20]);
+        assert!(!other_recipient.is_compatible_with(&own_txn));
+
+        let mut legacy_txn = swap_txn(AMOUNT_OUT_MIN, DEADLINE_SECS);
+        legacy_txn.max_priority_fee_per_gas = None;
+        assert!(!legacy_txn.is_compatible_with(&own_txn));
+    }
+}
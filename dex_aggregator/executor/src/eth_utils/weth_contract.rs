@@ -16,14 +16,17 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::string::{String, ToString};
+use ink::prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use pink_web3::{
     contract::{Contract, Options},
     transports::PinkHttp,
     types::{SignedTransaction, U256},
 };
 
-use privadex_chain_metadata::common::{Amount, EthAddress, Nonce, SecretKey};
+use privadex_chain_metadata::common::{Amount, EthAddress, Nonce};
 
 use super::common;
 
@@ -46,11 +49,22 @@ impl WethContract {
         })
     }
 
+    // For wraps and unwraps signed without gas estimation (see common::create_fixed_gas_raw_txn).
+    // A wrap's amount is the txn's value
+    pub fn encode_deposit(&self) -> common::Result<Vec<u8>> {
+        common::encode_call(&self.contract, "deposit", 0, ())
+    }
+
+    pub fn encode_withdraw(&self, amount: Amount) -> common::Result<Vec<u8>> {
+        common::encode_call(&self.contract, "withdraw", 0, (U256::from(amount),))
+    }
+
     /// "Wrap": Deposit native token into contract (and receive wrapped native token)
     pub fn deposit(
         &self,
         amount: Amount,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "deposit";
@@ -66,6 +80,7 @@ impl WethContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -74,7 +89,8 @@ impl WethContract {
     pub fn withdraw(
         &self,
         amount: Amount,
-        key: &SecretKey,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
         nonce: Nonce,
     ) -> common::Result<SignedTransaction> {
         let func = "withdraw";
@@ -88,6 +104,7 @@ impl WethContract {
             params,
             options_seed,
             key,
+            supports_eip1559,
             nonce,
         )
     }
@@ -134,7 +151,12 @@ mod weth_tests {
         };
         let nonce = 0;
         let _signed_txn = get_moonbase_alpha_weth_contract()
-            .deposit(amount, &kap_privkey, nonce)
+            .deposit(
+                amount,
+                &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
+                nonce,
+            )
             .expect("WETH deposit txn");
 
         // common::print_and_send_txn(&chain_info_registry::MOONBASEALPHA_INFO.rpc_url, signed_txn);
@@ -154,7 +176,12 @@ mod weth_tests {
         };
         let nonce = 0;
         let _signed_txn = get_moonbase_alpha_weth_contract()
-            .withdraw(amount, &kap_privkey, nonce)
+            .withdraw(
+                amount,
+                &kap_privkey,
+                chain_info_registry::MOONBASEALPHA_INFO.supports_eip1559,
+                nonce,
+            )
             .expect("WETH deposit txn");
 
         // common::print_and_send_txn(&chain_info_registry::MOONBASEALPHA_INFO.rpc_url, signed_txn);
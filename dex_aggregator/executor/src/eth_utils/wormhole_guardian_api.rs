@@ -0,0 +1,94 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+#[allow(unused_imports)]
+use ink::prelude::{format, string::String, vec::Vec};
+use serde::Deserialize;
+
+use privadex_chain_metadata::{bridge::WormholeChainId, common::EthAddress};
+#[allow(unused_imports)]
+use privadex_common::utils::http_request::http_get_wrapper;
+
+#[allow(unused_imports)]
+use super::{common, wormhole_token_bridge_contract::WormholeTokenBridgeContract};
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct SignedVaaResponse<'a> {
+    #[serde(rename = "vaaBytes")]
+    vaa_bytes: &'a str,
+}
+
+pub struct WormholeGuardianApi {
+    pub guardian_rpc_url: String,
+}
+
+impl WormholeGuardianApi {
+    /// Returns the guardian-signed VAA for the message. This fails (with a 404) until a
+    /// quorum of guardians has signed it, which takes ~15 minutes from Ethereum (finality)
+    /// and a few seconds from Moonbeam
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn get_signed_vaa(
+        &self,
+        emitter_chain: WormholeChainId,
+        emitter_token_bridge: EthAddress,
+        sequence: u64,
+    ) -> common::Result<Vec<u8>> {
+        let url = format!(
+            "{}/v1/signed_vaa/{}/{}/{}",
+            self.guardian_rpc_url,
+            emitter_chain,
+            hex::encode(WormholeTokenBridgeContract::to_wormhole_address(
+                emitter_token_bridge
+            )),
+            sequence
+        );
+        let resp_body =
+            http_get_wrapper(&url).map_err(|_| common::EthError::TransactionNotFound)?;
+        let (resp, _): (SignedVaaResponse, usize) =
+            serde_json_core::from_slice(&resp_body).map_err(|_| common::EthError::ParseFailed)?;
+        base64::decode(resp.vaa_bytes).map_err(|_| common::EthError::ParseFailed)
+    }
+
+    #[cfg(feature = "mock-txn-send")]
+    pub fn get_signed_vaa(
+        &self,
+        _emitter_chain: WormholeChainId,
+        _emitter_token_bridge: EthAddress,
+        _sequence: u64,
+    ) -> common::Result<Vec<u8>> {
+        ink::env::debug_println!("[Mock Wormhole get_signed_vaa]");
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod wormhole_guardian_api_tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_signed_vaa_response() {
+        let resp_body = r#"{"vaaBytes":"AQAAAAMNAA=="}"#.as_bytes();
+        let (resp, _): (SignedVaaResponse, usize) =
+            serde_json_core::from_slice(resp_body).expect("Valid response");
+        assert_eq!(
+            base64::decode(resp.vaa_bytes).unwrap(),
+            vec![1, 0, 0, 0, 3, 13, 0]
+        );
+    }
+}
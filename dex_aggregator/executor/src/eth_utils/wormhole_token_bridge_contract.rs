@@ -0,0 +1,198 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use pink_web3::{
+    contract::{Contract, Options},
+    signing::keccak256,
+    transports::PinkHttp,
+    types::{Bytes, Log, SignedTransaction, H256, U256},
+};
+
+use privadex_chain_metadata::{
+    bridge::WormholeChainId,
+    common::{Amount, EthAddress, EthTxnHash, Nonce},
+};
+
+use super::common;
+
+pub struct WormholeTokenBridgeContract {
+    contract: Contract<PinkHttp>,
+    rpc_url: String,
+}
+
+impl WormholeTokenBridgeContract {
+    pub fn new(rpc_url: &str, contract_address: EthAddress) -> common::Result<Self> {
+        let contract = Contract::from_json(
+            common::eth(rpc_url),
+            contract_address,
+            include_bytes!("./eth_abi/wormhole_token_bridge_abi.json"),
+        )
+        .map_err(|_| common::EthError::InvalidABI)?;
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            contract,
+        })
+    }
+
+    /// Deposit: locks (or burns, if it is a Wormhole-wrapped token) the token in the token
+    /// bridge and publishes a message for the guardians to sign.
+    /// The token must already be approved for spending by the token bridge
+    pub fn transfer_tokens(
+        &self,
+        token: EthAddress,
+        amount: Amount,
+        recipient_chain: WormholeChainId,
+        recipient: EthAddress,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "transferTokens";
+        let params = (
+            token,
+            U256::from(amount),
+            recipient_chain,
+            H256::from(Self::to_wormhole_address(recipient)),
+            U256::zero(), // arbiterFee: we claim ourselves so there is no relayer to pay
+            nonce,        // Wormhole batching nonce, which we don't rely on
+        );
+        // The core bridge message fee is currently 0 on all chains, so we send no value
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+
+    /// Claim: submits the guardian-signed VAA, which releases (or mints) the token to the recipient
+    pub fn complete_transfer(
+        &self,
+        signed_vaa: Vec<u8>,
+        key: &impl common::TxnSigner,
+        supports_eip1559: bool,
+        nonce: Nonce,
+    ) -> common::Result<SignedTransaction> {
+        let func = "completeTransfer";
+        let params = (Bytes(signed_vaa),);
+        let options_seed = Options::default();
+        common::create_raw_txn(
+            &self.rpc_url,
+            &self.contract,
+            func,
+            0,
+            params,
+            options_seed,
+            key,
+            supports_eip1559,
+            nonce,
+        )
+    }
+
+    /// The core bridge emits LogMessagePublished for every message. The token bridge
+    /// is the sender and the sequence is the first (non-indexed) word of the log data
+    pub fn parse_sequence_from_logs(logs: &[Log], token_bridge: EthAddress) -> common::Result<u64> {
+        let topic = EthTxnHash {
+            0: keccak256("LogMessagePublished(address,uint64,uint32,bytes,uint8)".as_bytes()),
+        };
+        let sender_topic = H256::from(Self::to_wormhole_address(token_bridge));
+        logs.iter()
+            .filter(|log| log.topics.len() == 2 && log.topics[0] == topic)
+            .filter(|log| log.topics[1] == sender_topic)
+            .next()
+            .map_or(Err(common::EthError::ParseFailed), |log| {
+                if log.data.0.len() < 32 {
+                    return Err(common::EthError::ParseFailed);
+                }
+                let sequence_u256 = U256::from_big_endian(&log.data.0[0..32]);
+                if sequence_u256 > U256::from(u64::MAX) {
+                    Err(common::EthError::AmountTooHigh)
+                } else {
+                    Ok(sequence_u256.low_u64())
+                }
+            })
+    }
+
+    /// Wormhole addresses are 32 bytes, so EVM addresses are left-padded with zeros
+    pub fn to_wormhole_address(addr: EthAddress) -> [u8; 32] {
+        let mut wormhole_addr = [0u8; 32];
+        wormhole_addr[12..].copy_from_slice(&addr.0);
+        wormhole_addr
+    }
+}
+
+impl common::ContractWrapper for WormholeTokenBridgeContract {
+    fn get_rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+}
+
+#[cfg(test)]
+mod wormhole_token_bridge_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::registry::bridge::wormhole_bridge_registry;
+
+    use super::*;
+
+    #[test]
+    fn test_to_wormhole_address() {
+        assert_eq!(
+            WormholeTokenBridgeContract::to_wormhole_address(
+                wormhole_bridge_registry::ETHEREUM_TOKEN_BRIDGE
+            ),
+            hex!("0000000000000000000000003ee18b2214aff97000d974cf647e7c347e8fa585")
+        );
+    }
+
+    #[test]
+    fn test_parse_sequence_from_logs() {
+        let token_bridge = wormhole_bridge_registry::MOONBEAM_TOKEN_BRIDGE;
+        let log = Log {
+            topics: vec![
+                keccak256("LogMessagePublished(address,uint64,uint32,bytes,uint8)".as_bytes())
+                    .into(),
+                WormholeTokenBridgeContract::to_wormhole_address(token_bridge).into(),
+            ],
+            data: Bytes(
+                hex!("0000000000000000000000000000000000000000000000000000000000001a2b").to_vec(),
+            ),
+            ..Default::default()
+        };
+        assert_eq!(
+            WormholeTokenBridgeContract::parse_sequence_from_logs(&[log.clone()], token_bridge),
+            Ok(0x1a2b)
+        );
+        assert_eq!(
+            WormholeTokenBridgeContract::parse_sequence_from_logs(
+                &[log],
+                wormhole_bridge_registry::ETHEREUM_TOKEN_BRIDGE
+            ),
+            Err(common::EthError::ParseFailed)
+        );
+    }
+}
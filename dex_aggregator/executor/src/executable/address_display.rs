@@ -0,0 +1,115 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use ink::prelude::string::String;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{UniversalAddress, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+use privadex_common::{
+    utils::{general_utils::slice_to_hex_string, ss58_utils::encode_ss58},
+    uuid::Uuid,
+};
+use privadex_execution_plan::execution_plan::{ExecutionStep, ExecutionStepEnum};
+
+/// A step's addresses as an explorer for their chain shows them
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StepAddresses {
+    pub step_uuid: Uuid,
+    pub src_addr: String,
+    pub dest_addr: String,
+}
+
+impl From<&ExecutionStep> for StepAddresses {
+    fn from(step: &ExecutionStep) -> Self {
+        let common = step.get_common();
+        Self {
+            step_uuid: step.get_uuid().clone(),
+            src_addr: get_display_address(&step.get_src_chain(), &common.src_addr),
+            dest_addr: get_display_address(&get_step_dest_chain(step), &common.dest_addr),
+        }
+    }
+}
+
+// Substrate accounts in SS58 with the chain's prefix, and Eth addresses as 0x-prefixed hex.
+// A substrate account on a chain without an SS58 prefix (shouldn't happen) falls back to hex
+pub fn get_display_address(chain: &UniversalChainId, address: &UniversalAddress) -> String {
+    match address {
+        UniversalAddress::Ethereum(addr) => slice_to_hex_string(&addr.0),
+        UniversalAddress::Substrate(pubkey) => match get_chain_info_from_chain_id(chain)
+            .and_then(|chain_info| chain_info.get_ss58_prefix())
+        {
+            Some(prefix) => encode_ss58(&pubkey.0, u16::from(prefix)),
+            None => slice_to_hex_string(&pubkey.0),
+        },
+    }
+}
+
+// Bridges deliver to dest_addr on the other chain, every other step stays on its src chain
+fn get_step_dest_chain(step: &ExecutionStep) -> UniversalChainId {
+    match &step.inner {
+        ExecutionStepEnum::XCMTransfer(step) => step.dest_token.chain,
+        ExecutionStepEnum::WormholeTransfer(step) => step.dest_token.chain,
+        _ => step.get_src_chain(),
+    }
+}
+
+#[cfg(test)]
+mod address_display_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::{
+        common::{EthAddress, SubstratePublicKey},
+        registry::chain::universal_chain_id_registry::{ASTAR, ETHEREUM, POLKADOT},
+    };
+
+    use super::*;
+
+    const ALICE: UniversalAddress = UniversalAddress::Substrate(SubstratePublicKey {
+        0: hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"),
+    });
+
+    #[test]
+    fn test_substrate_address_uses_chain_prefix() {
+        assert_eq!(
+            get_display_address(&POLKADOT, &ALICE),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            get_display_address(&ASTAR, &ALICE),
+            "ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8"
+        );
+        // No SS58 prefix
+        assert_eq!(
+            get_display_address(&ETHEREUM, &ALICE),
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+    }
+
+    #[test]
+    fn test_eth_address_is_hex() {
+        let addr = UniversalAddress::Ethereum(EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        });
+        assert_eq!(
+            get_display_address(&ASTAR, &addr),
+            "0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{
+        Amount, ChainTokenId, EthAddress, MillisSinceEpoch, UniversalAddress, UniversalChainId,
+        UniversalTokenId,
+    },
+    get_chain_info_from_chain_id,
+};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{CommonExecutionMeta, EthApproveStep, EthStepStatus};
+
+use crate::{eth_utils::erc20_contract::ERC20Contract, key_container::KeyContainer};
+
+use super::{
+    execute_step_meta::ExecuteStepMeta,
+    plan_analytics::TokenAmount,
+    proof_of_reserves::get_chain_escrow,
+    traits::{Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus},
+};
+
+pub const MILLIS_PER_DAY: MillisSinceEpoch = 86_400_000;
+
+/// The escrow's standing ERC20 approvals to DEX routers, with when a swap last went through
+/// each. Fed by closed ExecutionPlans, and worked off by the admin-triggered
+/// revoke_idle_allowances, which zeroes out the approvals that went unused for too long
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AllowanceCache {
+    allowances: Vec<RouterAllowance>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RouterAllowance {
+    pub token: UniversalTokenId,
+    pub router: EthAddress,
+    pub last_used_millis: MillisSinceEpoch,
+    pub num_attempts: u32,
+    pub revocation: RevocationStatus,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RevocationStatus {
+    // The approval may still be standing
+    NotRevoked,
+    InProgress(EthApproveStep),
+    Revoked,
+}
+
+impl AllowanceCache {
+    // Swaps of the native token (swapExactETHForTokens) don't need an approval
+    pub fn record_dex_swaps(
+        &mut self,
+        dex_swaps: &[(UniversalChainId, EthAddress, TokenAmount)],
+        used_millis: MillisSinceEpoch,
+    ) {
+        for (_, router, amount_in) in dex_swaps.iter() {
+            if amount_in.token.id == ChainTokenId::Native {
+                continue;
+            }
+            match self
+                .allowances
+                .iter_mut()
+                .find(|allowance| allowance.token == amount_in.token && allowance.router == *router)
+            {
+                Some(allowance) => {
+                    allowance.last_used_millis = allowance.last_used_millis.max(used_millis);
+                    // The swap went through, so the router has been approved again by hand
+                    if allowance.revocation == RevocationStatus::Revoked {
+                        allowance.revocation = RevocationStatus::NotRevoked;
+                    }
+                }
+                None => self.allowances.push(RouterAllowance {
+                    token: amount_in.token.clone(),
+                    router: *router,
+                    last_used_millis: used_millis,
+                    num_attempts: 0,
+                    revocation: RevocationStatus::NotRevoked,
+                }),
+            }
+        }
+    }
+
+    pub fn get_allowances(&self) -> Vec<RouterAllowance> {
+        self.allowances.clone()
+    }
+
+    /// Starts or advances the revocation of every approval that went unused for over
+    /// max_idle_millis, except to the (chain, router) pairs in exempt_routers. Returns the
+    /// allowances whose revocation status changed
+    pub fn revoke_idle_step_forward(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        exempt_routers: &[(UniversalChainId, EthAddress)],
+        max_idle_millis: MillisSinceEpoch,
+    ) -> ExecutableResult<Vec<RouterAllowance>> {
+        let escrow_addrs: Vec<UniversalAddress> = keys
+            .0
+            .iter()
+            .map(|address_key_pair| address_key_pair.address.clone())
+            .collect();
+        let now_millis = execute_step_meta.cur_timestamp();
+        let mut changed_allowances = Vec::new();
+        for allowance in self.allowances.iter_mut() {
+            let should_revoke = is_idle(allowance, exempt_routers, max_idle_millis, now_millis);
+            if allowance_step_forward(
+                allowance,
+                execute_step_meta,
+                keys,
+                &escrow_addrs,
+                should_revoke,
+            )? {
+                changed_allowances.push(allowance.clone());
+            }
+        }
+        Ok(changed_allowances)
+    }
+}
+
+fn is_idle(
+    allowance: &RouterAllowance,
+    exempt_routers: &[(UniversalChainId, EthAddress)],
+    max_idle_millis: MillisSinceEpoch,
+    now_millis: MillisSinceEpoch,
+) -> bool {
+    !exempt_routers.contains(&(allowance.token.chain, allowance.router))
+        && allowance.last_used_millis.saturating_add(max_idle_millis) < now_millis
+}
+
+// A revocation that is already in flight is seen through even if the router is no longer
+// idle, since its txn may land anyway
+fn allowance_step_forward(
+    allowance: &mut RouterAllowance,
+    execute_step_meta: &ExecuteStepMeta,
+    keys: &KeyContainer,
+    escrow_addrs: &[UniversalAddress],
+    should_revoke: bool,
+) -> ExecutableResult<bool> {
+    match &mut allowance.revocation {
+        RevocationStatus::NotRevoked => {
+            if !should_revoke {
+                return Ok(false);
+            }
+            let escrow_addr = match get_chain_escrow(&allowance.token.chain, escrow_addrs) {
+                Some(UniversalAddress::Ethereum(escrow_addr)) => escrow_addr,
+                _ => return Err(ExecutableError::UnexpectedNonEthAddress),
+            };
+            // Nothing to send if it is already zero, e.g. it was revoked by hand
+            if get_allowance(execute_step_meta, allowance, escrow_addr)? == 0 {
+                allowance.revocation = RevocationStatus::Revoked;
+                return Ok(true);
+            }
+            allowance.num_attempts += 1;
+            allowance.revocation =
+                RevocationStatus::InProgress(create_revocation_step(allowance, escrow_addr)?);
+            let _ = allowance_step_forward(
+                allowance,
+                execute_step_meta,
+                keys,
+                escrow_addrs,
+                should_revoke,
+            )?;
+            Ok(true)
+        }
+        RevocationStatus::InProgress(revocation_step) => {
+            let step_res = revocation_step.execute_step_forward(execute_step_meta, keys)?;
+            match revocation_step.get_status() {
+                ExecutableSimpleStatus::Succeeded => {
+                    allowance.revocation = RevocationStatus::Revoked;
+                    Ok(true)
+                }
+                // Retried with a fresh step on the next run, if the router is still idle
+                ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
+                    allowance.revocation = RevocationStatus::NotRevoked;
+                    Ok(true)
+                }
+                _ => Ok(step_res.did_status_change),
+            }
+        }
+        RevocationStatus::Revoked => Ok(false),
+    }
+}
+
+fn get_token_eth_addr(token: &UniversalTokenId) -> ExecutableResult<EthAddress> {
+    match &token.id {
+        ChainTokenId::Native => Err(ExecutableError::UnexpectedNonEthAddress),
+        ChainTokenId::ERC20(erc20_token) => Ok(erc20_token.addr),
+        ChainTokenId::XC20(xc20_token) => Ok(xc20_token.get_eth_address()),
+    }
+}
+
+fn get_allowance(
+    execute_step_meta: &ExecuteStepMeta,
+    allowance: &RouterAllowance,
+    escrow_addr: EthAddress,
+) -> ExecutableResult<Amount> {
+    let chain_info = get_chain_info_from_chain_id(&allowance.token.chain)
+        .ok_or(ExecutableError::FailedToFindChainInfo)?;
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    ERC20Contract::new(&rpc_url, get_token_eth_addr(&allowance.token)?)
+        .map_err(|_| ExecutableError::FailedToLoadWethContract)?
+        .allowance(escrow_addr, allowance.router)
+        .map_err(|_| ExecutableError::RpcRequestFailed)
+}
+
+fn create_revocation_step(
+    allowance: &RouterAllowance,
+    escrow_addr: EthAddress,
+) -> ExecutableResult<EthApproveStep> {
+    let chain_info = get_chain_info_from_chain_id(&allowance.token.chain)
+        .ok_or(ExecutableError::FailedToFindChainInfo)?;
+    let uuid = Uuid::new(sp_core_hashing::blake2_128(
+        &(&allowance.token, &allowance.router, allowance.num_attempts).encode(),
+    ));
+    Ok(EthApproveStep {
+        uuid,
+        token: allowance.token.clone(),
+        spender: allowance.router,
+        amount: 0,
+        common: CommonExecutionMeta {
+            src_addr: UniversalAddress::Ethereum(escrow_addr),
+            dest_addr: UniversalAddress::Ethereum(escrow_addr),
+            gas_fee_native: chain_info.avg_gas_fee_in_native_token,
+            // The operator pays for it, not a plan
+            gas_fee_usd: 0,
+        },
+        status: EthStepStatus::NotStarted,
+    })
+}
+
+#[cfg(test)]
+mod allowance_hygiene_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        dex::dex_registry::{BEAMSWAP, STELLASWAP},
+        token::universal_token_id_registry::{DOT_MOONBEAM, GLMR_NATIVE},
+    };
+
+    use super::*;
+
+    fn dex_swap(
+        router: EthAddress,
+        token: UniversalTokenId,
+    ) -> (UniversalChainId, EthAddress, TokenAmount) {
+        (
+            MOONBEAM,
+            router,
+            TokenAmount {
+                token,
+                amount: 1_000,
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_dex_swaps() {
+        let mut cache = AllowanceCache::default();
+        cache.record_dex_swaps(
+            &[
+                dex_swap(STELLASWAP.eth_dex_router, DOT_MOONBEAM),
+                dex_swap(BEAMSWAP.eth_dex_router, GLMR_NATIVE),
+            ],
+            2_000,
+        );
+        // Plans don't necessarily close in order
+        cache.record_dex_swaps(&[dex_swap(STELLASWAP.eth_dex_router, DOT_MOONBEAM)], 1_000);
+        let allowances = cache.get_allowances();
+        assert_eq!(allowances.len(), 1);
+        assert_eq!(allowances[0].router, STELLASWAP.eth_dex_router);
+        assert_eq!(allowances[0].last_used_millis, 2_000);
+
+        cache.allowances[0].revocation = RevocationStatus::Revoked;
+        cache.record_dex_swaps(&[dex_swap(STELLASWAP.eth_dex_router, DOT_MOONBEAM)], 3_000);
+        assert_eq!(
+            cache.get_allowances()[0].revocation,
+            RevocationStatus::NotRevoked
+        );
+        assert_eq!(cache.get_allowances()[0].last_used_millis, 3_000);
+    }
+
+    #[test]
+    fn test_is_idle() {
+        let allowance = RouterAllowance {
+            token: DOT_MOONBEAM,
+            router: STELLASWAP.eth_dex_router,
+            last_used_millis: MILLIS_PER_DAY,
+            num_attempts: 0,
+            revocation: RevocationStatus::NotRevoked,
+        };
+        let max_idle_millis = 30 * MILLIS_PER_DAY;
+        assert!(!is_idle(
+            &allowance,
+            &[],
+            max_idle_millis,
+            31 * MILLIS_PER_DAY
+        ));
+        assert!(is_idle(
+            &allowance,
+            &[],
+            max_idle_millis,
+            31 * MILLIS_PER_DAY + 1
+        ));
+        assert!(!is_idle(
+            &allowance,
+            &[(MOONBEAM, STELLASWAP.eth_dex_router)],
+            max_idle_millis,
+            31 * MILLIS_PER_DAY + 1
+        ));
+        // An exception for the router on another chain does not count
+        assert!(is_idle(
+            &allowance,
+            &[(ASTAR, STELLASWAP.eth_dex_router)],
+            max_idle_millis,
+            31 * MILLIS_PER_DAY + 1
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Rounding policy: wherever an amount gets divided, what we send on is floored, and whatever the
+// floor drops stays in the escrow as dust. So we never send more than the escrow holds. Dust only
+// comes from the protocol fee, at most 1 unit (of the delivered token) per plan:
+// - A step's amount in is only fixed for a path's first step. Every later step takes the previous
+//   step's realized amount out whole, so hops don't round
+// - rescale_deposit and tranche splits give their rounding leftovers to the last path or tranche
+// - An integrator markup is floored, and the rest of the path's amount out goes to the user
+// The quote floors at every hop as well, which only makes it (slightly) conservative
+use privadex_chain_metadata::common::Amount;
+use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use super::{
+    executable_plan::PROTOCOL_FEE_BPS,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ProtocolFeeSplit {
+    // What the postend step delivers
+    pub amount_after_fee: Amount,
+    // PROTOCOL_FEE_BPS of the amount, floored
+    pub fee: Amount,
+    // Left in the escrow on top of the fee. amount_after_fee + fee + dust is the amount
+    pub dust: Amount,
+}
+
+pub fn split_protocol_fee(amount_no_fee: Amount) -> ProtocolFeeSplit {
+    let amount_after_fee = mul_ratio_u128(
+        amount_no_fee,
+        Amount::from(10_000 - PROTOCOL_FEE_BPS),
+        10_000,
+    );
+    let fee = mul_ratio_u128(amount_no_fee, Amount::from(PROTOCOL_FEE_BPS), 10_000);
+    ProtocolFeeSplit {
+        amount_after_fee,
+        fee,
+        dust: amount_no_fee - amount_after_fee - fee,
+    }
+}
+
+// A sponsored plan's fee is waived, so the user gets all of it and there is no dust
+pub fn split_plan_protocol_fee(
+    exec_plan: &ExecutionPlan,
+    amount_no_fee: Amount,
+) -> ProtocolFeeSplit {
+    if exec_plan.sponsorship_campaign_id.is_some() {
+        ProtocolFeeSplit {
+            amount_after_fee: amount_no_fee,
+            fee: 0,
+            dust: 0,
+        }
+    } else {
+        split_protocol_fee(amount_no_fee)
+    }
+}
+
+// Dust the plan left in the escrow, in the token it delivers. 0 until the postend amount is set.
+// Only the succeeded paths' amounts out reach the postend step (see execute_refund_step_forward)
+pub fn get_plan_dust(exec_plan: &ExecutionPlan) -> Amount {
+    if exec_plan
+        .postend_escrow_to_user_transfer
+        .get_amount_in()
+        .is_none()
+    {
+        return 0;
+    }
+    let succeeded_amount_out = exec_plan
+        .paths
+        .iter()
+        .filter(|path| path.get_status() == ExecutableSimpleStatus::Succeeded)
+        .fold(0, |amount_out, path| {
+            amount_out + path.amount_out.unwrap_or(0)
+        });
+    split_plan_protocol_fee(exec_plan, succeeded_amount_out).dust
+}
+
+#[cfg(test)]
+mod amount_rounding_tests {
+    use ink::prelude::{string::String, vec, vec::Vec};
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::{EthStepStatus, ExecutionPath},
+        test_utilities::execution_plan_factory::{self, eth_send, eth_wrap, path, ESCROW, USER},
+    };
+
+    use super::*;
+
+    fn succeeded_path(amount_out: Amount) -> ExecutionPath {
+        ExecutionPath {
+            amount_out: Some(amount_out),
+            ..path(vec![eth_wrap(
+                2,
+                Some(amount_out),
+                EthStepStatus::Confirmed(EthTxnHash::zero()),
+            )])
+        }
+    }
+
+    fn plan(paths: Vec<ExecutionPath>, postend_amount: Option<Amount>) -> ExecutionPlan {
+        ExecutionPlan {
+            postend_escrow_to_user_transfer: eth_send(
+                0xa2,
+                ESCROW,
+                USER,
+                postend_amount,
+                EthStepStatus::NotStarted,
+            ),
+            ..execution_plan_factory::plan(paths)
+        }
+    }
+
+    #[test]
+    fn test_split_protocol_fee_conserves_value() {
+        for amount in [
+            0,
+            1,
+            1_999,
+            10_000,
+            123_456_789,
+            999_999_999_999_999_999,
+            Amount::MAX / 10_000,
+        ] {
+            let split = split_protocol_fee(amount);
+            assert_eq!(split.amount_after_fee + split.fee + split.dust, amount);
+            assert!(split.dust <= 1);
+        }
+        assert_eq!(
+            split_protocol_fee(10_000),
+            ProtocolFeeSplit {
+                amount_after_fee: 9_995,
+                fee: 5,
+                dust: 0,
+            }
+        );
+        // 1_999 * 0.9995 = 1_998.0005 and 1_999 * 0.0005 = 0.9995
+        assert_eq!(
+            split_protocol_fee(1_999),
+            ProtocolFeeSplit {
+                amount_after_fee: 1_998,
+                fee: 0,
+                dust: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_plan_dust() {
+        let paths = vec![succeeded_path(1_999), succeeded_path(10_000)];
+        assert_eq!(get_plan_dust(&plan(paths.clone(), None)), 0);
+
+        // 11_999 = 11_993 to the user + 5 fee + 1 dust
+        let settled_plan = plan(paths, Some(11_993));
+        assert_eq!(get_plan_dust(&settled_plan), 1);
+        let split = split_protocol_fee(11_999);
+        assert_eq!(
+            settled_plan.postend_escrow_to_user_transfer.get_amount_in(),
+            Some(split.amount_after_fee)
+        );
+        assert_eq!(split.fee, 5);
+    }
+
+    #[test]
+    fn test_sponsored_plan_has_no_fee_or_dust() {
+        let paths = vec![succeeded_path(1_999), succeeded_path(10_000)];
+        let mut sponsored_plan = plan(paths, Some(11_999));
+        sponsored_plan.sponsorship_campaign_id = Some(String::from("spring"));
+        assert_eq!(
+            split_plan_protocol_fee(&sponsored_plan, 11_999),
+            ProtocolFeeSplit {
+                amount_after_fee: 11_999,
+                fee: 0,
+                dust: 0,
+            }
+        );
+        assert_eq!(get_plan_dust(&sponsored_plan), 0);
+    }
+}
@@ -0,0 +1,416 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+// Canaries are small swaps that the escrow runs on its own funds on a schedule (see
+// run_canaries), so a broken route, bridge or executor path shows up before users hit it,
+// e.g. right after an upgrade
+use ink::prelude::{string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{
+    Amount, BlockNum, EthAddress, EthTxnHash, MillisSinceEpoch, UniversalTokenId,
+};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{EthStepStatus, ExecutionPlan, ExecutionStepEnum};
+
+use super::{
+    plan_analytics::PlanAnalytics,
+    shadow_quotes::get_diff_bps,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+use crate::quote_engine::{self, plan, quote, QuoteContext, QuoteRequest};
+
+pub const MAX_CANARIES: usize = 10;
+// Every canary spends fees, so they can't run more often than this
+pub const MIN_CANARY_INTERVAL_MILLIS: MillisSinceEpoch = 60 * 60 * 1000;
+// Only the latest results are kept
+pub const MAX_CANARY_RESULTS: usize = 200;
+
+/// A route the operator wants checked end to end. The escrow swaps amount_in of src_token
+/// into dest_token and delivers it to itself
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Canary {
+    pub name: String,
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub amount_in: Amount,
+    pub interval_millis: MillisSinceEpoch,
+    // The run fails if it delivers more than this many bps short of its quote
+    pub max_slippage_bps: u16,
+    // The most src token the canary may spend over all its runs. The admin raises it to top
+    // the canary up
+    pub budget: Amount,
+}
+
+impl Canary {
+    pub fn is_valid(&self) -> bool {
+        !self.name.is_empty()
+            && self.src_token != self.dest_token
+            && self.amount_in > 0
+            && self.amount_in <= self.budget
+            && self.interval_millis >= MIN_CANARY_INTERVAL_MILLIS
+            && self.max_slippage_bps <= 10_000
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CanaryResult {
+    pub name: String,
+    // None if the run couldn't be planned, e.g. because the route has no path anymore
+    pub exec_plan_uuid: Option<Uuid>,
+    pub started_millis: MillisSinceEpoch,
+    pub closed_millis: MillisSinceEpoch,
+    pub passed: bool,
+    pub quoted_amount_out: Amount,
+    // Before our fee, like the quote. None unless the plan succeeded
+    pub realized_amount_out: Option<Amount>,
+    // Negative if the plan delivered less than quoted (see ShadowQuoteResult)
+    pub realized_vs_quote_bps: Option<i32>,
+}
+
+impl CanaryResult {
+    // None for plans without a quote, which can't have been started by run_canaries
+    pub fn new(canary: &Canary, plan: &PlanAnalytics) -> Option<Self> {
+        let quoted_amount_out = plan.quote.as_ref()?.amount_out;
+        let realized_amount_out = plan
+            .amount_out
+            .as_ref()
+            .map(|amount_out| amount_out.amount + plan.protocol_fee.amount);
+        let realized_vs_quote_bps = realized_amount_out
+            .map(|realized_amount_out| get_diff_bps(quoted_amount_out, realized_amount_out));
+        Some(Self {
+            name: canary.name.clone(),
+            exec_plan_uuid: Some(plan.exec_plan_uuid.clone()),
+            started_millis: plan.started_millis,
+            closed_millis: plan.closed_millis,
+            passed: plan.is_success
+                && realized_vs_quote_bps.map_or(false, |diff_bps| {
+                    diff_bps >= -(canary.max_slippage_bps as i32)
+                }),
+            quoted_amount_out,
+            realized_amount_out,
+            realized_vs_quote_bps,
+        })
+    }
+
+    pub fn unplanned(canary: &Canary, now_millis: MillisSinceEpoch) -> Self {
+        Self {
+            name: canary.name.clone(),
+            exec_plan_uuid: None,
+            started_millis: now_millis,
+            closed_millis: now_millis,
+            passed: false,
+            quoted_amount_out: 0,
+            realized_amount_out: None,
+            realized_vs_quote_bps: None,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CanaryState {
+    pub name: String,
+    pub spent: Amount,
+    pub last_started_millis: MillisSinceEpoch,
+    // The run that hasn't closed yet. A canary never has two runs in flight
+    pub pending_exec_plan_uuid: Option<Uuid>,
+}
+
+/// What run_canaries keeps between runs. Updated under its own lock
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CanaryLedger {
+    pub states: Vec<CanaryState>,
+    // Oldest first
+    pub results: Vec<CanaryResult>,
+}
+
+impl CanaryLedger {
+    pub fn get_state(&self, name: &str) -> Option<&CanaryState> {
+        self.states.iter().find(|state| state.name == name)
+    }
+
+    // Not already in flight, last started at least interval_millis ago, and with enough budget
+    // left for another run
+    pub fn is_due(&self, canary: &Canary, now_millis: MillisSinceEpoch) -> bool {
+        match self.get_state(&canary.name) {
+            None => canary.amount_in <= canary.budget,
+            Some(state) => {
+                state.pending_exec_plan_uuid.is_none()
+                    && now_millis.saturating_sub(state.last_started_millis)
+                        >= canary.interval_millis
+                    && state.spent.saturating_add(canary.amount_in) <= canary.budget
+            }
+        }
+    }
+
+    pub fn get_pending_runs(&self) -> Vec<(String, Uuid)> {
+        self.states
+            .iter()
+            .filter_map(|state| {
+                state
+                    .pending_exec_plan_uuid
+                    .clone()
+                    .map(|exec_plan_uuid| (state.name.clone(), exec_plan_uuid))
+            })
+            .collect()
+    }
+
+    // The amount in counts against the budget as soon as the run starts, whatever comes of it
+    pub fn record_started(
+        &mut self,
+        canary: &Canary,
+        exec_plan_uuid: Uuid,
+        now_millis: MillisSinceEpoch,
+    ) {
+        match self
+            .states
+            .iter_mut()
+            .find(|state| state.name == canary.name)
+        {
+            Some(state) => {
+                state.spent = state.spent.saturating_add(canary.amount_in);
+                state.last_started_millis = now_millis;
+                state.pending_exec_plan_uuid = Some(exec_plan_uuid);
+            }
+            None => self.states.push(CanaryState {
+                name: canary.name.clone(),
+                spent: canary.amount_in,
+                last_started_millis: now_millis,
+                pending_exec_plan_uuid: Some(exec_plan_uuid),
+            }),
+        }
+    }
+
+    // Nothing is spent, but the canary still waits interval_millis before it is tried again
+    pub fn record_unplanned(&mut self, canary: &Canary, now_millis: MillisSinceEpoch) {
+        match self
+            .states
+            .iter_mut()
+            .find(|state| state.name == canary.name)
+        {
+            Some(state) => state.last_started_millis = now_millis,
+            None => self.states.push(CanaryState {
+                name: canary.name.clone(),
+                spent: 0,
+                last_started_millis: now_millis,
+                pending_exec_plan_uuid: None,
+            }),
+        }
+        self.record_closed(
+            &canary.name,
+            Some(CanaryResult::unplanned(canary, now_millis)),
+        );
+    }
+
+    // A canary removed while its run was in flight has nothing to judge the run by, in which
+    // case there is no result
+    pub fn record_closed(&mut self, name: &str, result: Option<CanaryResult>) {
+        if let Some(state) = self.states.iter_mut().find(|state| state.name == name) {
+            state.pending_exec_plan_uuid = None;
+        }
+        if let Some(result) = result {
+            self.results.push(result);
+            let num_dropped = self.results.len().saturating_sub(MAX_CANARY_RESULTS);
+            self.results.drain(..num_dropped);
+        }
+    }
+
+    // Removing a canary forgets what it spent, but not its results
+    pub fn retain_canaries(&mut self, canaries: &[Canary]) {
+        self.states.retain(|state| {
+            state.pending_exec_plan_uuid.is_some()
+                || canaries.iter().any(|canary| canary.name == state.name)
+        });
+    }
+}
+
+// Full SOR on the context's graph, like any swap. The escrow is both the src and the dest
+pub fn create_canary_plan(
+    context: &QuoteContext,
+    canary: &Canary,
+    escrow_eth_addr: EthAddress,
+) -> quote_engine::Result<ExecutionPlan> {
+    let request = QuoteRequest {
+        src_token_id: canary.src_token.clone(),
+        dest_token_id: canary.dest_token.clone(),
+        amount_in: canary.amount_in,
+        src_addr: escrow_eth_addr,
+        dest_addr: escrow_eth_addr,
+    };
+    plan(context, quote(context, &request)?, None)
+}
+
+// The canary's funds are already in the escrow, so its plan starts with the prestart step
+// confirmed at cur_block and no user txn to wait on. Only EVM src tokens are supported
+pub fn set_canary_prestart(exec_plan: &mut ExecutionPlan, cur_block: BlockNum) -> Option<()> {
+    match &mut exec_plan.prestart_user_to_escrow_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => {
+            step.status = EthStepStatus::Confirmed(EthTxnHash::zero());
+        }
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            step.status = EthStepStatus::Confirmed(EthTxnHash::zero());
+        }
+        _ => return None,
+    }
+    exec_plan.prestart_confirmed_block = Some(cur_block);
+    Some(())
+}
+
+// A run is over once its plan is. A failed plan counts as soon as it starts refunding, since
+// that only moves the funds back within the escrow
+pub fn is_canary_run_closed(exec_plan: &ExecutionPlan) -> bool {
+    exec_plan
+        .refund_escrow_to_user_transfer
+        .get_amount_in()
+        .is_some()
+        || matches!(
+            exec_plan.get_status(),
+            ExecutableSimpleStatus::Succeeded
+                | ExecutableSimpleStatus::Failed
+                | ExecutableSimpleStatus::Dropped
+                | ExecutableSimpleStatus::Refunded
+        )
+}
+
+#[cfg(test)]
+mod canaries_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::token::universal_token_id_registry::{
+        DOT_NATIVE, GLMR_NATIVE,
+    };
+    use privadex_execution_plan::execution_plan::PlanQuote;
+
+    use super::*;
+    use crate::executable::plan_analytics::TokenAmount;
+
+    fn canary(budget: Amount) -> Canary {
+        Canary {
+            name: "glmr-to-dot".into(),
+            src_token: GLMR_NATIVE,
+            dest_token: DOT_NATIVE,
+            amount_in: 1_000,
+            interval_millis: MIN_CANARY_INTERVAL_MILLIS,
+            max_slippage_bps: 100,
+            budget,
+        }
+    }
+
+    fn plan_analytics(amount_out: Option<Amount>) -> PlanAnalytics {
+        PlanAnalytics {
+            exec_plan_uuid: Uuid::new([1; 16]),
+            src_chain: GLMR_NATIVE.chain,
+            started_millis: 0,
+            closed_millis: 60_000,
+            is_success: amount_out.is_some(),
+            amount_in: TokenAmount {
+                token: GLMR_NATIVE,
+                amount: 1_000,
+            },
+            protocol_fee: TokenAmount {
+                token: DOT_NATIVE,
+                amount: if amount_out.is_some() { 10 } else { 0 },
+            },
+            dex_swaps: vec![],
+            amount_out: amount_out.map(|amount| TokenAmount {
+                token: DOT_NATIVE,
+                amount,
+            }),
+            quote: Some(PlanQuote {
+                amount_out: 1_000,
+                amount_in_usd: 0,
+                amount_out_usd: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_canary_validity() {
+        assert!(canary(1_000).is_valid());
+        assert!(!canary(999).is_valid());
+        let mut same_token = canary(1_000);
+        same_token.dest_token = GLMR_NATIVE;
+        assert!(!same_token.is_valid());
+        let mut too_frequent = canary(1_000);
+        too_frequent.interval_millis = MIN_CANARY_INTERVAL_MILLIS - 1;
+        assert!(!too_frequent.is_valid());
+    }
+
+    #[test]
+    fn test_canary_schedule_and_budget() {
+        let canary = canary(2_500);
+        let mut ledger = CanaryLedger::default();
+        assert!(ledger.is_due(&canary, 0));
+        ledger.record_started(&canary, Uuid::new([1; 16]), 0);
+        assert_eq!(
+            ledger.get_pending_runs(),
+            vec![(canary.name.clone(), Uuid::new([1; 16]))]
+        );
+        // In flight
+        assert!(!ledger.is_due(&canary, MIN_CANARY_INTERVAL_MILLIS));
+
+        ledger.record_closed(&canary.name, None);
+        assert!(ledger.get_pending_runs().is_empty());
+        assert!(!ledger.is_due(&canary, MIN_CANARY_INTERVAL_MILLIS - 1));
+        assert!(ledger.is_due(&canary, MIN_CANARY_INTERVAL_MILLIS));
+
+        ledger.record_started(&canary, Uuid::new([2; 16]), MIN_CANARY_INTERVAL_MILLIS);
+        ledger.record_closed(&canary.name, None);
+        // 2_000 of the 2_500 budget spent, which doesn't cover another run
+        assert_eq!(ledger.get_state(&canary.name).unwrap().spent, 2_000);
+        assert!(!ledger.is_due(&canary, 10 * MIN_CANARY_INTERVAL_MILLIS));
+
+        // Removed canaries are forgotten once they have nothing in flight
+        ledger.retain_canaries(&[]);
+        assert!(ledger.states.is_empty());
+    }
+
+    #[test]
+    fn test_canary_result() {
+        let canary = canary(1_000);
+        // 1% short of the quote (before our fee) is still within max_slippage_bps
+        let result = CanaryResult::new(&canary, &plan_analytics(Some(980))).unwrap();
+        assert_eq!(result.realized_amount_out, Some(990));
+        assert_eq!(result.realized_vs_quote_bps, Some(-100));
+        assert!(result.passed);
+
+        let result = CanaryResult::new(&canary, &plan_analytics(Some(970))).unwrap();
+        assert_eq!(result.realized_vs_quote_bps, Some(-200));
+        assert!(!result.passed);
+
+        let result = CanaryResult::new(&canary, &plan_analytics(None)).unwrap();
+        assert_eq!(result.realized_vs_quote_bps, None);
+        assert!(!result.passed);
+
+        // A run that couldn't be planned fails without spending the budget
+        let mut ledger = CanaryLedger::default();
+        ledger.record_unplanned(&canary, 0);
+        assert_eq!(ledger.get_state(&canary.name).unwrap().spent, 0);
+        assert!(!ledger.results[0].passed);
+        assert_eq!(ledger.results[0].exec_plan_uuid, None);
+        assert!(!ledger.is_due(&canary, MIN_CANARY_INTERVAL_MILLIS - 1));
+
+        let mut ledger = CanaryLedger::default();
+        for _ in 0..MAX_CANARY_RESULTS + 1 {
+            ledger.record_closed(&canary.name, Some(result.clone()));
+        }
+        assert_eq!(ledger.results.len(), MAX_CANARY_RESULTS);
+    }
+}
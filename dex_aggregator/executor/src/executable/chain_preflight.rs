@@ -0,0 +1,307 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    chain_info::{ChainInfo, DepositForwarderFactory},
+    common::{
+        Amount, BlockNum, ChainTokenId, EthAddress, MillisSinceEpoch, UniversalAddress,
+        UniversalChainId, UniversalTokenId,
+    },
+    get_dexes_from_chain_id,
+    registry::dex::DexId,
+};
+
+use crate::eth_utils::{
+    astar_xcm_precompile_contract::ASTAR_XCM_PRECOMPILE_ADDRESS, common::get_code,
+    dex_router_contract::DEXRouterContract, erc20_contract::ERC20Contract,
+};
+use crate::substrate_utils::indexer_utils::subsquid_utils::SubstrateSubsquidUtils;
+
+use super::{execute_step_meta::ExecuteStepMeta, proof_of_reserves::get_chain_escrow};
+
+// The escrow should be able to pay for at least this many transactions at the chain's
+// average gas fee before we route anything through it
+pub const PREFLIGHT_MIN_GAS_TXNS: Amount = 20;
+// Steps look up their extrinsics and events in the indexer, which must keep up with the chain
+pub const PREFLIGHT_MAX_INDEXER_LAG_BLOCKS: BlockNum = 20;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PreflightCheck {
+    ChainHead,
+    // The account has a balance, i.e. it exists, and its nonce can be read. None if none of
+    // the escrow keys has the chain's address type
+    EscrowAccount(Option<UniversalAddress>),
+    EscrowGasBalance(UniversalAddress),
+    DexRouter(DexId, EthAddress),
+    WrappedNativeToken(EthAddress),
+    XcmPrecompile(EthAddress),
+    DepositForwarderFactory(EthAddress),
+    Indexer,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PreflightOutcome {
+    Passed,
+    Failed(String /* reason */),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PreflightCheckResult {
+    pub check: PreflightCheck,
+    pub outcome: PreflightOutcome,
+}
+
+/// The onboarding checklist for a chain. A new chain starts with an empty escrow that has never
+/// sent a transaction there, and its first plans fail in confusing ways if anything on this
+/// list is off
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChainPreflightReport {
+    pub chain: UniversalChainId,
+    pub as_of_millis: MillisSinceEpoch,
+    pub checks: Vec<PreflightCheckResult>,
+}
+
+impl ChainPreflightReport {
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|result| result.outcome == PreflightOutcome::Passed)
+    }
+}
+
+/// insurance_buffer is in the chain's native token. The escrow must hold at least that much
+/// (and never less than PREFLIGHT_MIN_GAS_TXNS average transactions' worth of gas)
+pub fn get_min_escrow_gas_balance(chain_info: &ChainInfo, insurance_buffer: Amount) -> Amount {
+    chain_info
+        .avg_gas_fee_in_native_token
+        .saturating_mul(PREFLIGHT_MIN_GAS_TXNS)
+        .max(insurance_buffer)
+}
+
+pub fn check_escrow_gas_balance(balance: Amount, min_balance: Amount) -> PreflightOutcome {
+    if balance >= min_balance {
+        PreflightOutcome::Passed
+    } else {
+        PreflightOutcome::Failed(format!("balance {} < minimum {}", balance, min_balance))
+    }
+}
+
+pub fn check_indexer_lag(chain_head: BlockNum, indexed_height: BlockNum) -> PreflightOutcome {
+    let lag = chain_head.saturating_sub(indexed_height);
+    if lag <= PREFLIGHT_MAX_INDEXER_LAG_BLOCKS {
+        PreflightOutcome::Passed
+    } else {
+        PreflightOutcome::Failed(format!(
+            "indexed up to block {}, {} blocks behind the chain",
+            indexed_height, lag
+        ))
+    }
+}
+
+/// Runs every check that applies to the chain. Only reads, so it is safe to run any time.
+/// deposit_forwarder_factory is the one new plans on the chain use, i.e. the admin's if set
+pub fn run_chain_preflight(
+    execute_step_meta: &ExecuteStepMeta,
+    chain_info: &ChainInfo,
+    escrow_addrs: &[UniversalAddress],
+    insurance_buffer: Amount,
+    deposit_forwarder_factory: Option<DepositForwarderFactory>,
+) -> Vec<PreflightCheckResult> {
+    let chain_id = chain_info.chain_id;
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    let mut results = Vec::new();
+    let mut push = |check: PreflightCheck, outcome: PreflightOutcome| {
+        results.push(PreflightCheckResult { check, outcome });
+    };
+
+    let chain_head = execute_step_meta.get_cur_block(&chain_id).ok();
+    push(
+        PreflightCheck::ChainHead,
+        match chain_head {
+            Some(_) => PreflightOutcome::Passed,
+            None => PreflightOutcome::Failed("RPC endpoint did not return a block".into()),
+        },
+    );
+
+    match get_chain_escrow(&chain_id, escrow_addrs) {
+        Some(escrow_addr) => {
+            let native = UniversalTokenId {
+                chain: chain_id,
+                id: ChainTokenId::Native,
+            };
+            let balance = execute_step_meta.get_balance(&native, &escrow_addr);
+            let nonce = execute_step_meta.get_system_nonce(&chain_id, &escrow_addr);
+            push(
+                PreflightCheck::EscrowAccount(Some(escrow_addr.clone())),
+                match (&balance, nonce) {
+                    (Ok(0), Ok(_)) => PreflightOutcome::Failed(
+                        "account has no balance, so it does not exist yet".into(),
+                    ),
+                    (Ok(_), Ok(_)) => PreflightOutcome::Passed,
+                    _ => PreflightOutcome::Failed("failed to read balance or nonce".into()),
+                },
+            );
+            push(
+                PreflightCheck::EscrowGasBalance(escrow_addr),
+                match balance {
+                    Ok(balance) => check_escrow_gas_balance(
+                        balance,
+                        get_min_escrow_gas_balance(chain_info, insurance_buffer),
+                    ),
+                    Err(_) => PreflightOutcome::Failed("failed to read balance".into()),
+                },
+            );
+        }
+        None => push(
+            PreflightCheck::EscrowAccount(None),
+            PreflightOutcome::Failed("no escrow key has this chain's address type".into()),
+        ),
+    }
+
+    for dex in get_dexes_from_chain_id(&chain_id) {
+        let factory = DEXRouterContract::new(&rpc_url, dex.eth_dex_router)
+            .and_then(|router| router.factory());
+        push(
+            PreflightCheck::DexRouter(dex.id, dex.eth_dex_router),
+            match factory {
+                Ok(factory) if factory != EthAddress::zero() => PreflightOutcome::Passed,
+                Ok(_) => PreflightOutcome::Failed("factory() returned the zero address".into()),
+                Err(_) => PreflightOutcome::Failed("factory() call failed".into()),
+            },
+        );
+    }
+
+    if let Some(weth_addr) = chain_info.weth_addr {
+        let decimals = ERC20Contract::new(&rpc_url, weth_addr).and_then(|weth| weth.decimals());
+        push(
+            PreflightCheck::WrappedNativeToken(weth_addr),
+            match decimals {
+                Ok(_) => PreflightOutcome::Passed,
+                Err(_) => PreflightOutcome::Failed("decimals() call failed".into()),
+            },
+        );
+    }
+
+    if chain_info.has_xcm_precompile {
+        push(
+            PreflightCheck::XcmPrecompile(ASTAR_XCM_PRECOMPILE_ADDRESS),
+            check_has_code(&rpc_url, ASTAR_XCM_PRECOMPILE_ADDRESS),
+        );
+    }
+
+    if let Some(factory) = deposit_forwarder_factory {
+        push(
+            PreflightCheck::DepositForwarderFactory(factory.addr),
+            check_has_code(&rpc_url, factory.addr),
+        );
+    }
+
+    if let Some(archive_url) = chain_info.get_subsquid_graphql_archive_url() {
+        let indexed_height = SubstrateSubsquidUtils {
+            subsquid_graphql_archive_url: archive_url.into(),
+        }
+        .get_indexed_height();
+        push(
+            PreflightCheck::Indexer,
+            match (chain_head, indexed_height) {
+                (Some(chain_head), Ok(indexed_height)) => {
+                    check_indexer_lag(chain_head, indexed_height)
+                }
+                (None, _) => PreflightOutcome::Failed("chain head is unknown".into()),
+                (_, Err(_)) => PreflightOutcome::Failed("squidStatus query failed".into()),
+            },
+        );
+    }
+
+    results
+}
+
+fn check_has_code(rpc_url: &str, addr: EthAddress) -> PreflightOutcome {
+    match get_code(rpc_url, addr) {
+        Ok(code) if !code.is_empty() => PreflightOutcome::Passed,
+        Ok(_) => PreflightOutcome::Failed("no code at address".into()),
+        Err(_) => PreflightOutcome::Failed("eth_getCode call failed".into()),
+    }
+}
+
+#[cfg(test)]
+mod chain_preflight_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::chain_info_registry;
+
+    use super::*;
+
+    #[test]
+    fn test_min_escrow_gas_balance() {
+        let chain_info = &chain_info_registry::MOONBEAM_INFO;
+        let min_gas = chain_info.avg_gas_fee_in_native_token * PREFLIGHT_MIN_GAS_TXNS;
+        assert_eq!(get_min_escrow_gas_balance(chain_info, 0), min_gas);
+        assert_eq!(
+            get_min_escrow_gas_balance(chain_info, min_gas + 1),
+            min_gas + 1
+        );
+        assert_eq!(
+            check_escrow_gas_balance(min_gas, min_gas),
+            PreflightOutcome::Passed
+        );
+        assert!(matches!(
+            check_escrow_gas_balance(min_gas - 1, min_gas),
+            PreflightOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_indexer_lag() {
+        assert_eq!(check_indexer_lag(1_000, 1_000), PreflightOutcome::Passed);
+        // An indexer ahead of our (finalized) head is fine
+        assert_eq!(check_indexer_lag(1_000, 1_005), PreflightOutcome::Passed);
+        assert_eq!(
+            check_indexer_lag(1_000, 1_000 - PREFLIGHT_MAX_INDEXER_LAG_BLOCKS),
+            PreflightOutcome::Passed
+        );
+        assert!(matches!(
+            check_indexer_lag(1_000, 900),
+            PreflightOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_report_passed() {
+        let mut report = ChainPreflightReport {
+            chain: chain_info_registry::MOONBEAM_INFO.chain_id,
+            as_of_millis: 0,
+            checks: vec![PreflightCheckResult {
+                check: PreflightCheck::ChainHead,
+                outcome: PreflightOutcome::Passed,
+            }],
+        };
+        assert!(report.passed());
+        report.checks.push(PreflightCheckResult {
+            check: PreflightCheck::Indexer,
+            outcome: PreflightOutcome::Failed("behind".into()),
+        });
+        assert!(!report.passed());
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{MillisSinceEpoch, UniversalChainId},
+    registry::chain::universal_chain_id_registry::{ASTAR, MOONBASE_ALPHA, MOONBEAM},
+};
+
+// The chains that DEX swap steps run on (see get_dexes_from_chain_id), since swap deadlines are
+// the only thing we hand to a chain in its own clock
+pub const SKEW_SAMPLED_CHAINS: [UniversalChainId; 3] = [ASTAR, MOONBEAM, MOONBASE_ALPHA];
+// After this a sample says little about the chain's clock, so we go back to the contract's
+pub const MAX_SKEW_SAMPLE_AGE_MILLIS: MillisSinceEpoch = 3_600_000;
+// A larger gap is far more likely a stalled chain or a broken RPC endpoint than a skewed clock
+pub const MAX_CLOCK_SKEW_MILLIS: i64 = 600_000;
+
+/// How far a chain's clock (per its latest block timestamp) is ahead of the contract's. Negative
+/// if it is behind
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChainClockSkew {
+    pub chain_id: UniversalChainId,
+    pub skew_millis: i64,
+    pub sampled_millis: MillisSinceEpoch,
+}
+
+// The latest block can be up to a block time old, so a block timestamp that far behind is just
+// the block's age rather than skew
+pub fn compute_skew_millis(
+    block_timestamp_millis: MillisSinceEpoch,
+    now_millis: MillisSinceEpoch,
+    avg_block_time_millis: MillisSinceEpoch,
+) -> i64 {
+    let raw_skew_millis = i128::from(block_timestamp_millis) - i128::from(now_millis);
+    let block_age_millis = i128::from(avg_block_time_millis);
+    let skew_millis = if raw_skew_millis >= 0 {
+        raw_skew_millis
+    } else if raw_skew_millis >= -block_age_millis {
+        0
+    } else {
+        raw_skew_millis + block_age_millis
+    };
+    skew_millis.clamp(
+        -i128::from(MAX_CLOCK_SKEW_MILLIS),
+        i128::from(MAX_CLOCK_SKEW_MILLIS),
+    ) as i64
+}
+
+// The chain's current time per its latest sample. Falls back to the contract's clock if there
+// is no recent one
+pub fn get_chain_timestamp(
+    clock_skews: &[ChainClockSkew],
+    chain_id: &UniversalChainId,
+    now_millis: MillisSinceEpoch,
+) -> MillisSinceEpoch {
+    let skew_millis = clock_skews
+        .iter()
+        .find(|clock_skew| {
+            clock_skew.chain_id == *chain_id
+                && now_millis.saturating_sub(clock_skew.sampled_millis)
+                    <= MAX_SKEW_SAMPLE_AGE_MILLIS
+        })
+        .map(|clock_skew| clock_skew.skew_millis)
+        .unwrap_or(0);
+    if skew_millis >= 0 {
+        now_millis.saturating_add(skew_millis.unsigned_abs())
+    } else {
+        now_millis.saturating_sub(skew_millis.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod clock_skew_tests {
+    use ink::prelude::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_compute_skew_millis() {
+        // Chain ahead
+        assert_eq!(compute_skew_millis(1_005_000, 1_000_000, 12_000), 5_000);
+        // Behind by no more than a block, so it is just the block's age
+        assert_eq!(compute_skew_millis(990_000, 1_000_000, 12_000), 0);
+        assert_eq!(compute_skew_millis(988_000, 1_000_000, 12_000), 0);
+        // Chain behind
+        assert_eq!(compute_skew_millis(978_000, 1_000_000, 12_000), -10_000);
+        // Clamped
+        assert_eq!(
+            compute_skew_millis(10_000_000, 1_000_000, 12_000),
+            MAX_CLOCK_SKEW_MILLIS
+        );
+        assert_eq!(
+            compute_skew_millis(0, 10_000_000, 12_000),
+            -MAX_CLOCK_SKEW_MILLIS
+        );
+    }
+
+    #[test]
+    fn test_get_chain_timestamp() {
+        let clock_skews = vec![
+            ChainClockSkew {
+                chain_id: MOONBEAM,
+                skew_millis: -30_000,
+                sampled_millis: 1_000_000,
+            },
+            ChainClockSkew {
+                chain_id: ASTAR,
+                skew_millis: 20_000,
+                sampled_millis: 1_000_000,
+            },
+        ];
+        assert_eq!(
+            get_chain_timestamp(&clock_skews, &MOONBEAM, 1_500_000),
+            1_470_000
+        );
+        assert_eq!(
+            get_chain_timestamp(&clock_skews, &ASTAR, 1_500_000),
+            1_520_000
+        );
+        // No sample
+        assert_eq!(
+            get_chain_timestamp(&clock_skews, &MOONBASE_ALPHA, 1_500_000),
+            1_500_000
+        );
+        // Stale sample
+        assert_eq!(
+            get_chain_timestamp(
+                &clock_skews,
+                &MOONBEAM,
+                1_000_001 + MAX_SKEW_SAMPLE_AGE_MILLIS
+            ),
+            1_000_001 + MAX_SKEW_SAMPLE_AGE_MILLIS
+        );
+    }
+}
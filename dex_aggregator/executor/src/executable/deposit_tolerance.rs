@@ -0,0 +1,168 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use privadex_chain_metadata::common::Amount;
+use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_execution_plan::{
+    execution_plan::{
+        EthStepStatus, ExecutionPlan, ExecutionStepEnum, PlanReviewStatus, RemarkDepositStatus,
+    },
+    graph_solution_to_execution_plan::converter::rescale_deposit,
+};
+
+// Users sometimes deposit a little more or less than they were quoted, e.g. a wallet that rounds
+// the amount. Up to this far off either way, the plan is rescaled to the deposit and carries on
+pub const DEPOSIT_TOLERANCE_BPS: u32 = 50;
+
+pub fn is_within_deposit_tolerance(quoted_amount: Amount, deposited_amount: Amount) -> bool {
+    let tolerance = mul_ratio_u128(quoted_amount, DEPOSIT_TOLERANCE_BPS as u128, 10_000);
+    quoted_amount.abs_diff(deposited_amount) <= tolerance
+}
+
+// Takes a prestart deposit that failed only because of its amount. The plan is rescaled to what
+// was deposited and the prestart step confirmed. Past the tolerance the plan is also parked for
+// review, and since it is rescaled all the same, a refund returns exactly what was deposited.
+// Returns false (and leaves the plan as is) if the plan cannot be rescaled
+pub fn accept_mismatched_deposit(exec_plan: &mut ExecutionPlan, deposited_amount: Amount) -> bool {
+    let quoted_amount = match exec_plan.prestart_user_to_escrow_transfer.get_amount_in() {
+        Some(quoted_amount) => quoted_amount,
+        None => return false,
+    };
+    let is_confirmable = match &exec_plan.prestart_user_to_escrow_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => matches!(step.status, EthStepStatus::Failed(_)),
+        ExecutionStepEnum::ERC20Transfer(step) => matches!(step.status, EthStepStatus::Failed(_)),
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => {
+            matches!(step.status, RemarkDepositStatus::Failed(_))
+        }
+        _ => false,
+    };
+    if !is_confirmable || rescale_deposit(exec_plan, deposited_amount).is_err() {
+        return false;
+    }
+    match &mut exec_plan.prestart_user_to_escrow_transfer.inner {
+        ExecutionStepEnum::EthSend(step) => {
+            if let EthStepStatus::Failed(txn_hash) = step.status {
+                step.status = EthStepStatus::Confirmed(txn_hash);
+            }
+        }
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            if let EthStepStatus::Failed(txn_hash) = step.status {
+                step.status = EthStepStatus::Confirmed(txn_hash);
+            }
+        }
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => {
+            if let RemarkDepositStatus::Failed(extrinsic_id) = &step.status {
+                step.status = RemarkDepositStatus::Confirmed(extrinsic_id.clone());
+            }
+        }
+        _ => {}
+    }
+    if !is_within_deposit_tolerance(quoted_amount, deposited_amount) {
+        exec_plan.review_status = PlanReviewStatus::DepositMismatch { deposited_amount };
+    }
+    true
+}
+
+#[cfg(test)]
+mod deposit_tolerance_tests {
+    use ink::prelude::{vec, vec::Vec};
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::{ExecutionStep, PlanQuote},
+        test_utilities::execution_plan_factory::{self, eth_wrap, path, ESCROW, USER},
+    };
+
+    use super::*;
+
+    fn prestart(amount: Option<Amount>, status: EthStepStatus) -> ExecutionStep {
+        execution_plan_factory::eth_send(0xa1, USER, ESCROW, amount, status)
+    }
+
+    // 10_000 split 6_000/4_000 across two paths, whose deposit failed on its amount
+    fn plan() -> ExecutionPlan {
+        let wrap_path =
+            |amount: Amount| path(vec![eth_wrap(2, Some(amount), EthStepStatus::NotStarted)]);
+        ExecutionPlan {
+            prestart_user_to_escrow_transfer: prestart(
+                Some(10_000),
+                EthStepStatus::Failed(EthTxnHash::zero()),
+            ),
+            quote: Some(PlanQuote {
+                amount_out: 1_000,
+                amount_in_usd: 1_000,
+                amount_out_usd: 1_000,
+            }),
+            prestart_confirmed_block: None,
+            ..execution_plan_factory::plan(vec![wrap_path(6_000), wrap_path(4_000)])
+        }
+    }
+
+    fn path_amounts_in(exec_plan: &ExecutionPlan) -> Vec<Amount> {
+        exec_plan
+            .paths
+            .iter()
+            .map(|path| path.steps[0].get_amount_in().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_is_within_deposit_tolerance() {
+        assert!(is_within_deposit_tolerance(10_000, 10_000));
+        assert!(is_within_deposit_tolerance(10_000, 10_050));
+        assert!(is_within_deposit_tolerance(10_000, 9_950));
+        assert!(!is_within_deposit_tolerance(10_000, 10_051));
+        assert!(!is_within_deposit_tolerance(10_000, 9_949));
+    }
+
+    #[test]
+    fn test_accept_deposit_within_tolerance() {
+        let mut exec_plan = plan();
+        assert!(accept_mismatched_deposit(&mut exec_plan, 9_973));
+        assert_eq!(
+            exec_plan.prestart_user_to_escrow_transfer,
+            prestart(Some(9_973), EthStepStatus::Confirmed(EthTxnHash::zero()))
+        );
+        // 6_000 * 0.9973 rounds down, and the last path makes up the difference
+        assert_eq!(path_amounts_in(&exec_plan), vec![5_983, 3_990]);
+        assert_eq!(exec_plan.quote.as_ref().unwrap().amount_out, 997);
+        assert_eq!(exec_plan.review_status, PlanReviewStatus::NotNeeded);
+    }
+
+    #[test]
+    fn test_accept_deposit_beyond_tolerance() {
+        let mut exec_plan = plan();
+        assert!(accept_mismatched_deposit(&mut exec_plan, 12_000));
+        assert_eq!(path_amounts_in(&exec_plan), vec![7_200, 4_800]);
+        assert_eq!(
+            exec_plan.review_status,
+            PlanReviewStatus::DepositMismatch {
+                deposited_amount: 12_000
+            }
+        );
+        assert!(exec_plan.review_status.is_pending());
+    }
+
+    #[test]
+    fn test_accept_deposit_needs_failed_prestart() {
+        let mut exec_plan = plan();
+        exec_plan.prestart_user_to_escrow_transfer = prestart(Some(10_000), EthStepStatus::Dropped);
+        let original_plan = exec_plan.clone();
+        assert!(!accept_mismatched_deposit(&mut exec_plan, 9_990));
+        assert_eq!(exec_plan, original_plan);
+    }
+}
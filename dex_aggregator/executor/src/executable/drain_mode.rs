@@ -0,0 +1,75 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use super::{
+    plan_events::get_all_steps,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+// While the workers drain, a plan only steps forward if it has a txn out there to follow up on.
+// A presigned txn counts too since its nonce is already taken, and holding it back would block
+// every later txn from the escrow on that chain
+pub fn has_step_in_flight(exec_plan: &ExecutionPlan) -> bool {
+    get_all_steps(exec_plan).into_iter().any(|step| {
+        let status = step.get_status();
+        status == ExecutableSimpleStatus::InProgress
+            || (status == ExecutableSimpleStatus::NotStarted && step.presigned_txn.is_some())
+    })
+}
+
+#[cfg(test)]
+mod drain_mode_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::{EthStepStatus, ExecutionStep, PresignedTxn},
+        test_utilities::execution_plan_factory::{eth_wrap, path, plan, submitted},
+    };
+
+    use super::*;
+
+    fn plan_with_path_step(path_step: ExecutionStep) -> ExecutionPlan {
+        plan(vec![path(vec![path_step])])
+    }
+
+    #[test]
+    fn test_has_step_in_flight() {
+        // Between steps, so it can pause here
+        assert!(!has_step_in_flight(&plan_with_path_step(eth_wrap(
+            2,
+            Some(1_000),
+            EthStepStatus::NotStarted
+        ))));
+
+        assert!(has_step_in_flight(&plan_with_path_step(eth_wrap(
+            2,
+            Some(1_000),
+            submitted(EthTxnHash::zero())
+        ))));
+
+        let mut presigned_step = eth_wrap(2, Some(1_000), EthStepStatus::NotStarted);
+        presigned_step.presigned_txn = Some(PresignedTxn {
+            nonce: 3,
+            txn_hash: EthTxnHash::zero(),
+            raw_txn: vec![0xf8],
+        });
+        assert!(has_step_in_flight(&plan_with_path_step(presigned_step)));
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{
+    Amount, ChainTokenId, EthAddress, UniversalAddress, UniversalChainId, UniversalTokenId,
+};
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use super::proof_of_reserves::ProofOfReserves;
+use crate::quote_engine::{plan, quote, QuoteContext, QuoteEngineError, QuoteRequest};
+
+/// What an escrow account holds of a token beyond what it owes users (and, for the native
+/// token, beyond the chain's insurance buffer, which also pays the escrow's gas)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SpareHolding {
+    pub token: UniversalTokenId,
+    pub holder: UniversalAddress,
+    pub amount: Amount,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ConsolidationProposal {
+    pub holding: SpareHolding,
+    pub amount_in_usd: Amount,
+    // In the target token, net of the estimated gas and bridge fees
+    pub amount_out: Amount,
+    pub exec_plan: ExecutionPlan,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ConsolidationSkipReason {
+    AlreadyTargetToken,
+    // The graph has no USD price for the token
+    Unpriced,
+    BelowMinValue,
+    // The estimated gas and bridge fees would eat up the whole balance
+    FeesExceedValue,
+    NoRoute,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ConsolidationReport {
+    pub proposals: Vec<ConsolidationProposal>,
+    pub skipped: Vec<(SpareHolding, ConsolidationSkipReason)>,
+}
+
+pub fn get_spare_holdings(
+    proof: &ProofOfReserves,
+    insurance_buffers: &[(UniversalChainId, Amount)],
+) -> Vec<SpareHolding> {
+    let mut holdings = Vec::new();
+    for chain_reserves in proof.chains.iter() {
+        let insurance_buffer = insurance_buffers
+            .iter()
+            .find(|(chain, _)| *chain == chain_reserves.chain)
+            .map_or(0, |(_, buffer)| *buffer);
+        for reserve in chain_reserves.tokens.iter() {
+            let reserved = if reserve.token.id == ChainTokenId::Native {
+                reserve.liabilities.saturating_add(insurance_buffer)
+            } else {
+                reserve.liabilities
+            };
+            let amount = reserve.escrow_balance.saturating_sub(reserved);
+            if amount > 0 {
+                holdings.push(SpareHolding {
+                    token: reserve.token.clone(),
+                    holder: reserve.holder.clone(),
+                    amount,
+                });
+            }
+        }
+    }
+    holdings
+}
+
+/// Routes each spare holding into target_token with the same SOR as a user's swap. Holdings
+/// worth less than min_value_usd (in the units of a quote's amount_in_usd) are skipped, so no
+/// gas is spent moving worthless dust. The escrow is both the plans' user and recipient
+pub fn plan_consolidation(
+    context: &QuoteContext,
+    holdings: Vec<SpareHolding>,
+    target_token: &UniversalTokenId,
+    escrow_eth_addr: EthAddress,
+    min_value_usd: Amount,
+) -> ConsolidationReport {
+    let mut report = ConsolidationReport {
+        proposals: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for holding in holdings.into_iter() {
+        match propose(
+            context,
+            &holding,
+            target_token,
+            escrow_eth_addr,
+            min_value_usd,
+        ) {
+            Ok((amount_in_usd, amount_out, exec_plan)) => {
+                report.proposals.push(ConsolidationProposal {
+                    holding,
+                    amount_in_usd,
+                    amount_out,
+                    exec_plan,
+                })
+            }
+            Err(reason) => report.skipped.push((holding, reason)),
+        }
+    }
+    report
+}
+
+fn propose(
+    context: &QuoteContext,
+    holding: &SpareHolding,
+    target_token: &UniversalTokenId,
+    escrow_eth_addr: EthAddress,
+    min_value_usd: Amount,
+) -> Result<(Amount, Amount, ExecutionPlan), ConsolidationSkipReason> {
+    if holding.token == *target_token {
+        return Err(ConsolidationSkipReason::AlreadyTargetToken);
+    }
+    let value_usd = context
+        .get_amount_usd(&holding.token, holding.amount)
+        .map_err(|_| ConsolidationSkipReason::Unpriced)?;
+    if value_usd < min_value_usd {
+        return Err(ConsolidationSkipReason::BelowMinValue);
+    }
+    let request = QuoteRequest {
+        src_token_id: holding.token.clone(),
+        dest_token_id: target_token.clone(),
+        amount_in: holding.amount,
+        src_addr: escrow_eth_addr,
+        dest_addr: escrow_eth_addr,
+    };
+    let quote = quote(context, &request).map_err(|e| match e {
+        QuoteEngineError::AmountInBelowMinimumUsd => ConsolidationSkipReason::BelowMinValue,
+        QuoteEngineError::AmountInDoesNotCoverFees => ConsolidationSkipReason::FeesExceedValue,
+        _ => ConsolidationSkipReason::NoRoute,
+    })?;
+    let (amount_in_usd, amount_out) = (quote.amount_in_usd, quote.amount_out);
+    let exec_plan = plan(context, quote, None).map_err(|_| ConsolidationSkipReason::NoRoute)?;
+    Ok((amount_in_usd, amount_out, exec_plan))
+}
+
+#[cfg(test)]
+mod dust_consolidation_tests {
+    use hex_literal::hex;
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        token::universal_token_id_registry::{DOT_MOONBEAM, GLMR_NATIVE},
+    };
+    use privadex_routing::graph::graph::Graph;
+
+    use super::*;
+    use crate::executable::proof_of_reserves::{ChainReserves, TokenReserve};
+
+    const ESCROW: EthAddress = EthAddress {
+        0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+    };
+
+    fn reserve(
+        token: UniversalTokenId,
+        escrow_balance: Amount,
+        liabilities: Amount,
+    ) -> TokenReserve {
+        TokenReserve {
+            token,
+            holder: UniversalAddress::Ethereum(ESCROW),
+            escrow_balance,
+            liabilities,
+        }
+    }
+
+    #[test]
+    fn test_spare_holdings_leave_liabilities_and_buffer() {
+        let proof = ProofOfReserves {
+            as_of_millis: 0,
+            num_in_flight_plans: 1,
+            chains: vec![ChainReserves {
+                chain: MOONBEAM,
+                block_number: 100,
+                tokens: vec![
+                    reserve(GLMR_NATIVE, 1_000, 200),
+                    reserve(DOT_MOONBEAM, 50, 50),
+                ],
+            }],
+        };
+        assert_eq!(
+            get_spare_holdings(&proof, &[(MOONBEAM, 300), (ASTAR, 10_000)]),
+            vec![SpareHolding {
+                token: GLMR_NATIVE,
+                holder: UniversalAddress::Ethereum(ESCROW),
+                amount: 500,
+            }]
+        );
+        // A buffer above the spare balance leaves nothing to consolidate
+        assert!(get_spare_holdings(&proof, &[(MOONBEAM, 900)]).is_empty());
+    }
+
+    #[test]
+    fn test_plan_consolidation_skips_without_routing() {
+        let context = QuoteContext::from_graph(Graph::new(), 0);
+        let holding = |token| SpareHolding {
+            token,
+            holder: UniversalAddress::Ethereum(ESCROW),
+            amount: 1_000,
+        };
+        let report = plan_consolidation(
+            &context,
+            vec![holding(GLMR_NATIVE), holding(DOT_MOONBEAM)],
+            &DOT_MOONBEAM,
+            ESCROW,
+            0,
+        );
+        assert!(report.proposals.is_empty());
+        assert_eq!(
+            report.skipped,
+            vec![
+                (holding(GLMR_NATIVE), ConsolidationSkipReason::Unpriced),
+                (
+                    holding(DOT_MOONBEAM),
+                    ConsolidationSkipReason::AlreadyTargetToken
+                ),
+            ]
+        );
+    }
+}
@@ -101,6 +101,7 @@ impl Executable for ExecutionPath {
             if step_to_process.get_status() == ExecutableSimpleStatus::Succeeded
                 && idx < self.steps.len() - 1
             {
+                self.record_step_amount_out(idx, amount_out);
                 // Propagate amount_out from one step to amount_in in the next
                 let next_step = &mut self.steps[idx + 1];
                 next_step.set_amount_in(amount_out);
@@ -121,6 +122,7 @@ impl Executable for ExecutionPath {
                     if next_step.get_status() == ExecutableSimpleStatus::Succeeded
                         && idx + 1 < self.steps.len() - 1
                     {
+                        self.record_step_amount_out(idx + 1, amount_out2);
                         let next_next_step = &mut self.steps[idx + 2];
                         next_next_step.set_amount_in(amount_out2);
                         Ok(StepForwardResult {
@@ -129,6 +131,7 @@ impl Executable for ExecutionPath {
                         })
                     } else {
                         // We finished the last step in the path
+                        self.record_step_amount_out(idx + 1, amount_out2);
                         self.amount_out = Some(amount_out2);
                         Ok(StepForwardResult {
                             did_status_change: true,
@@ -143,6 +146,7 @@ impl Executable for ExecutionPath {
                 }
             } else {
                 // We finished the last step in the path
+                self.record_step_amount_out(idx, amount_out);
                 self.amount_out = Some(amount_out);
                 Ok(StepForwardResult {
                     did_status_change: true,
@@ -168,8 +172,8 @@ impl Executable for ExecutionPath {
 mod executable_path_tests {
     use core::str::FromStr;
     use hex_literal::hex;
-    use ink_env::debug_println;
-    use ink_prelude::{vec, vec::Vec};
+    use ink::env::debug_println;
+    use ink::prelude::{vec, vec::Vec};
     use privadex_chain_metadata::{
         common::{
             ChainTokenId, ERC20Token, EthAddress, SecretKeyContainer, SubstratePublicKey,
@@ -182,14 +186,14 @@ mod executable_path_tests {
             token::universal_token_id_registry,
         },
     };
-    use privadex_common::uuid::Uuid;
+    use privadex_common::{signature_scheme::SignatureScheme, uuid::Uuid};
     use privadex_execution_plan::execution_plan::{
         CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, ERC20TransferStep,
         EthDexSwapStep, EthSendStep, EthStepStatus, EthUnwrapStep, EthWrapStep, ExecutionStep,
         ExecutionStepEnum, XCMTransferStep,
     };
 
-    use crate::key_container::AddressKeyPair;
+    use crate::key_container::{AddressKeyPair, SigningKey};
 
     use super::super::traits::ExecutableError;
     use super::*;
@@ -214,14 +218,13 @@ mod executable_path_tests {
                 .expect("ETH_PRIVATE_KEY to_hex failed")
                 .0
         };
-        KeyContainer {
-            0: vec![AddressKeyPair {
-                address: UniversalAddress::Ethereum(EthAddress {
-                    0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
-                }),
-                key: kap_privkey,
-            }],
-        }
+        KeyContainer::new(vec![AddressKeyPair {
+            address: UniversalAddress::Ethereum(EthAddress {
+                0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+            }),
+            key: SigningKey::Local(kap_privkey),
+            sig_scheme: SignatureScheme::Ethereum,
+        }])
     }
 
     #[test]
@@ -269,6 +272,7 @@ mod executable_path_tests {
                 })),
             ],
             amount_out: None,
+            step_amounts_out: Vec::new(),
         };
 
         assert_eq!(exec_path.get_status(), ExecutableSimpleStatus::NotStarted);
@@ -16,14 +16,34 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use privadex_chain_metadata::common::Amount;
-use privadex_common::utils::general_utils::mul_ratio_u128;
-use privadex_execution_plan::execution_plan::{ExecutionPath, ExecutionPlan};
+use privadex_chain_metadata::{common::Amount, get_chain_info_from_chain_id};
+use privadex_execution_plan::{
+    execution_plan::{
+        EthStepStatus, ExecutionPath, ExecutionPlan, ExecutionPolicy, ExecutionStepEnum,
+        PathExecutionMode, StepMilestone,
+    },
+    graph_solution_to_execution_plan::converter::{
+        set_remove_liquidity_amounts_out, switch_deposit_form,
+    },
+};
 
-use crate::key_container::KeyContainer;
+use crate::{eth_utils, key_container::KeyContainer};
 
 use super::{
+    amount_rounding::split_plan_protocol_fee,
+    deposit_tolerance::accept_mismatched_deposit,
+    executable_step_helpers::{
+        executable_eth_steps::{
+            find_mismatched_deposit, find_other_form_deposit, get_confirmed_transfer_txn_hash,
+            reverify_confirmed_transfer,
+        },
+        executable_remark_deposit::{
+            find_mismatched_remark_deposit, get_confirmed_remark_deposit_id,
+            reverify_confirmed_remark_deposit,
+        },
+    },
     execute_step_meta::ExecuteStepMeta,
+    presign::release_presigned_txns,
     traits::{
         Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus, StepForwardResult,
     },
@@ -34,12 +54,16 @@ impl Executable for ExecutionPlan {
         if self.prestart_user_to_escrow_transfer.get_status() == ExecutableSimpleStatus::NotStarted
         {
             ExecutableSimpleStatus::NotStarted
+        } else if is_refunding(self) {
+            get_refund_status(self)
         } else if self.postend_escrow_to_user_transfer.get_status()
             == ExecutableSimpleStatus::Succeeded
         {
             ExecutableSimpleStatus::Succeeded
         } else if self.prestart_user_to_escrow_transfer.get_status()
             == ExecutableSimpleStatus::Dropped
+            || get_deposit_sweep_status(self) == ExecutableSimpleStatus::Dropped
+            || get_remove_liquidity_status(self) == ExecutableSimpleStatus::Dropped
             || self.postend_escrow_to_user_transfer.get_status() == ExecutableSimpleStatus::Dropped
             || self
                 .paths
@@ -49,6 +73,8 @@ impl Executable for ExecutionPlan {
             ExecutableSimpleStatus::Dropped
         } else if self.prestart_user_to_escrow_transfer.get_status()
             == ExecutableSimpleStatus::Failed
+            || get_deposit_sweep_status(self) == ExecutableSimpleStatus::Failed
+            || get_remove_liquidity_status(self) == ExecutableSimpleStatus::Failed
             || self.postend_escrow_to_user_transfer.get_status() == ExecutableSimpleStatus::Failed
             || self
                 .paths
@@ -56,6 +82,11 @@ impl Executable for ExecutionPlan {
                 .any(|path| path.get_status() == ExecutableSimpleStatus::Failed)
         {
             ExecutableSimpleStatus::Failed
+        } else if self.prestart_user_to_escrow_transfer.get_status()
+            == ExecutableSimpleStatus::Succeeded
+            && self.prestart_confirmed_block.is_none()
+        {
+            ExecutableSimpleStatus::PrestartConfirming
         } else {
             ExecutableSimpleStatus::InProgress
         }
@@ -71,7 +102,17 @@ impl Executable for ExecutionPlan {
                 }) + self
                     .postend_escrow_to_user_transfer
                     .get_total_fee_usd()
-                    .unwrap_or(0),
+                    .unwrap_or(0)
+                    + self
+                        .deposit_sweep
+                        .as_ref()
+                        .and_then(|step| step.get_total_fee_usd())
+                        .unwrap_or(0)
+                    + self
+                        .remove_liquidity
+                        .as_ref()
+                        .and_then(|step| step.get_total_fee_usd())
+                        .unwrap_or(0),
             )
         } else {
             None
@@ -88,6 +129,7 @@ impl Executable for ExecutionPlan {
         if status == ExecutableSimpleStatus::Dropped
             || status == ExecutableSimpleStatus::Failed
             || status == ExecutableSimpleStatus::Succeeded
+            || status == ExecutableSimpleStatus::Refunded
         {
             return Err(ExecutableError::CalledStepForwardOnFinishedPlan);
         }
@@ -95,26 +137,97 @@ impl Executable for ExecutionPlan {
             match self.prestart_user_to_escrow_transfer.get_status() {
                 ExecutableSimpleStatus::NotStarted => Err(ExecutableError::PrestartStepNotStarted),
                 // The Failed check above captures this below state
-                ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
-                    Err(ExecutableError::UnknownBadState)
-                }
+                ExecutableSimpleStatus::Failed
+                | ExecutableSimpleStatus::Dropped
+                | ExecutableSimpleStatus::PrestartConfirming
+                | ExecutableSimpleStatus::Refunded => Err(ExecutableError::UnknownBadState),
                 ExecutableSimpleStatus::InProgress => {
                     let prestart_step_result = self
                         .prestart_user_to_escrow_transfer
                         .execute_step_forward(execute_step_meta, keys)?;
+                    let did_switch_deposit_form =
+                        switch_prestart_deposit_form(self, execute_step_meta)?;
+                    let did_accept_deposit =
+                        accept_prestart_deposit_amount(self, execute_step_meta)?;
+                    // The deposit never landed, so the presigned txns will never be sent
+                    let did_release_presigned =
+                        match self.prestart_user_to_escrow_transfer.get_status() {
+                            ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
+                                release_presigned_txns(self, execute_step_meta)
+                            }
+                            _ => false,
+                        };
                     Ok((
-                        prestart_step_result.did_status_change,
+                        prestart_step_result.did_status_change
+                            | did_switch_deposit_form
+                            | did_accept_deposit
+                            | did_release_presigned,
                         self.prestart_user_to_escrow_transfer.get_status()
                             == ExecutableSimpleStatus::Succeeded,
                     ))
                 }
                 ExecutableSimpleStatus::Succeeded => Ok((false, true)),
             }?;
+        let should_process_paths = should_process_paths
+            && (self.prestart_confirmed_block.is_some() || {
+                did_plan_status_change =
+                    did_plan_status_change | confirm_prestart_deposit(self, execute_step_meta)?;
+                self.prestart_confirmed_block.is_some()
+            });
+        // Funds only reach the escrow once they are swept out of the deposit address
+        let should_process_paths = should_process_paths
+            && match self.deposit_sweep.as_mut() {
+                Some(deposit_sweep)
+                    if deposit_sweep.get_status() != ExecutableSimpleStatus::Succeeded =>
+                {
+                    let sweep_res = deposit_sweep.execute_step_forward(execute_step_meta, keys)?;
+                    did_plan_status_change = did_plan_status_change | sweep_res.did_status_change;
+                    deposit_sweep.get_status() == ExecutableSimpleStatus::Succeeded
+                }
+                _ => true,
+            };
+        // An LP source's paths start from the pair's tokens, which the escrow only holds once
+        // the LP tokens are redeemed
+        let should_process_paths = should_process_paths
+            && match self.remove_liquidity.as_mut() {
+                Some(remove_liquidity)
+                    if remove_liquidity.get_status() != ExecutableSimpleStatus::Succeeded =>
+                {
+                    let remove_res =
+                        remove_liquidity.execute_step_forward(execute_step_meta, keys)?;
+                    did_plan_status_change = did_plan_status_change | remove_res.did_status_change;
+                    if remove_liquidity.get_status() == ExecutableSimpleStatus::Succeeded {
+                        set_remove_liquidity_amounts_out(self)
+                            .map_err(|_| ExecutableError::InvalidRemoveLiquidity)?;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                _ => true,
+            };
+        if should_process_paths && !is_refunding(self) {
+            did_plan_status_change =
+                did_plan_status_change | enforce_now_or_never_policy(self, execute_step_meta)?;
+        }
         if !should_process_paths {
             Ok(StepForwardResult {
                 did_status_change: did_plan_status_change,
                 amount_out: None,
             })
+        } else if is_refunding(self)
+            || (!have_all_exec_paths_succeeded(self)
+                && execute_step_meta.cur_timestamp() > self.deadline_millis)
+        {
+            let did_release_presigned = release_presigned_txns(self, execute_step_meta);
+            let did_refund_status_change =
+                execute_refund_step_forward(self, execute_step_meta, keys)?;
+            Ok(StepForwardResult {
+                did_status_change: did_plan_status_change
+                    | did_release_presigned
+                    | did_refund_status_change,
+                amount_out: None,
+            })
         } else if !have_all_exec_paths_succeeded(self) {
             for exec_path in self.paths.iter_mut() {
                 if exec_path.get_status() == ExecutableSimpleStatus::NotStarted
@@ -132,14 +245,25 @@ impl Executable for ExecutionPlan {
                     // Stop processing other paths and exit early if any have failed
                     break;
                 }
+                if self.path_execution_mode == PathExecutionMode::Sequential
+                    && exec_path.get_status() != ExecutableSimpleStatus::Succeeded
+                {
+                    // The next tranche only starts once this one has delivered to the escrow
+                    break;
+                }
             }
             Ok(StepForwardResult {
                 did_status_change: did_plan_status_change,
                 amount_out: None,
             })
+        } else if is_postend_held(self, execute_step_meta) {
+            Ok(StepForwardResult {
+                did_status_change: did_plan_status_change,
+                amount_out: None,
+            })
         } else {
             let total_amount = sum_exec_paths_amounts_out(&self.paths);
-            let amount_in_after_fee = calc_amount_after_simple_fee(total_amount);
+            let amount_in_after_fee = calc_amount_after_simple_fee(self, total_amount);
             self.postend_escrow_to_user_transfer
                 .set_amount_in(amount_in_after_fee);
             let postend_res = self
@@ -155,6 +279,183 @@ impl Executable for ExecutionPlan {
     }
 }
 
+// The prestart step confirms as soon as the user's txn has a receipt, but that block can still be
+// reorged out. So the escrow only acts on the deposit once it is the src chain's
+// prestart_confirmation_depth deep, its block is still canonical, and the transfer checks out
+// again. Returns true if the plan changed
+fn confirm_prestart_deposit(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<bool> {
+    let prestart = &mut exec_plan.prestart_user_to_escrow_transfer;
+    if execute_step_meta.is_paper_trading() {
+        // There is no user txn to wait on
+        exec_plan.prestart_confirmed_block = Some(
+            prestart
+                .timing
+                .confirmed
+                .as_ref()
+                .and_then(|milestone| milestone.block_num)
+                .unwrap_or_default(),
+        );
+        return Ok(true);
+    }
+    let chain_info = get_chain_info_from_chain_id(&prestart.get_src_chain())
+        .ok_or(ExecutableError::FailedToFindChainInfo)?;
+    if let Some(extrinsic_id) = get_confirmed_remark_deposit_id(prestart) {
+        let cur_block = execute_step_meta.get_cur_block(&prestart.get_src_chain())?;
+        if cur_block.saturating_sub(extrinsic_id.block_num) + 1
+            < chain_info.prestart_confirmation_depth
+        {
+            return Ok(false);
+        }
+        return match reverify_confirmed_remark_deposit(prestart, execute_step_meta)? {
+            Some(ExecutableSimpleStatus::Succeeded) => {
+                exec_plan.prestart_confirmed_block = Some(extrinsic_id.block_num);
+                Ok(true)
+            }
+            Some(_) => Ok(true),
+            // The remark may have moved blocks, in which case its id changed
+            None if get_confirmed_remark_deposit_id(prestart).as_ref() != Some(&extrinsic_id) => {
+                Ok(true)
+            }
+            None if execute_step_meta.cur_timestamp() > exec_plan.deadline_millis => {
+                prestart.drop();
+                Ok(true)
+            }
+            None => Ok(false),
+        };
+    }
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    let txn_hash =
+        get_confirmed_transfer_txn_hash(prestart).ok_or(ExecutableError::UnexpectedStepStatus)?;
+    let inclusion = match eth_utils::parse_txn_helper::get_txn_inclusion(&rpc_url, txn_hash) {
+        Ok(inclusion) if inclusion.is_canonical => inclusion,
+        // Reorged out (or the node has not caught up). The txn may well land again, so we keep
+        // waiting until the plan's deadline
+        _ if execute_step_meta.cur_timestamp() > exec_plan.deadline_millis => {
+            prestart.drop();
+            return Ok(true);
+        }
+        _ => return Ok(false),
+    };
+    let cur_block =
+        eth_utils::common::block_number(&rpc_url).map_err(|_| ExecutableError::RpcRequestFailed)?;
+    // A txn in the latest block is 1 deep
+    if cur_block.saturating_sub(inclusion.block_num) + 1 < chain_info.prestart_confirmation_depth {
+        return Ok(false);
+    }
+    match reverify_confirmed_transfer(prestart, &rpc_url)? {
+        Some(ExecutableSimpleStatus::Succeeded) => {
+            exec_plan.prestart_confirmed_block = Some(inclusion.block_num);
+            Ok(true)
+        }
+        // The plan is now Failed
+        Some(_) => Ok(true),
+        None => Ok(false),
+    }
+}
+
+// A user who was quoted the native token may deposit its wrapped form instead (or vice versa).
+// As long as the amount matches, we switch the plan over to the form they deposited rather than
+// failing it. The deposit is then reverified like any other in confirm_prestart_deposit.
+// Returns true if the plan changed
+fn switch_prestart_deposit_form(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<bool> {
+    if execute_step_meta.is_paper_trading() {
+        return Ok(false);
+    }
+    let src_chain = exec_plan.prestart_user_to_escrow_transfer.get_src_chain();
+    let chain_info =
+        get_chain_info_from_chain_id(&src_chain).ok_or(ExecutableError::FailedToFindChainInfo)?;
+    let weth_addr = match chain_info.weth_addr {
+        Some(weth_addr) => weth_addr,
+        None => return Ok(false),
+    };
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    let txn_hash = match find_other_form_deposit(
+        &exec_plan.prestart_user_to_escrow_transfer,
+        &weth_addr,
+        &rpc_url,
+    ) {
+        Some(txn_hash) => txn_hash,
+        None => return Ok(false),
+    };
+    // The switch rewrites the paths' first steps, so their presigned txns no longer apply
+    let _ = release_presigned_txns(exec_plan, execute_step_meta);
+    let worst_case_duration_millis = exec_plan.get_worst_case_duration_millis();
+    if switch_deposit_form(exec_plan).is_err() {
+        return Ok(false);
+    }
+    exec_plan.deadline_millis += exec_plan
+        .get_worst_case_duration_millis()
+        .saturating_sub(worst_case_duration_millis);
+
+    let prestart = &mut exec_plan.prestart_user_to_escrow_transfer;
+    match &mut prestart.inner {
+        ExecutionStepEnum::EthSend(step) => step.status = EthStepStatus::Confirmed(txn_hash),
+        ExecutionStepEnum::ERC20Transfer(step) => step.status = EthStepStatus::Confirmed(txn_hash),
+        _ => return Err(ExecutableError::UnexpectedStepStatus),
+    }
+    // A failed deposit already has its confirmed milestone
+    if prestart.timing.confirmed.is_none() {
+        prestart.timing.confirmed = Some(StepMilestone {
+            block_num: execute_step_meta.get_cur_block(&src_chain).ok(),
+            timestamp_millis: execute_step_meta.cur_timestamp(),
+        });
+    }
+    Ok(true)
+}
+
+// A user who deposits more or less than they were quoted fails the prestart step. Instead of
+// failing the whole plan, we rescale it to the amount they deposited (see deposit_tolerance).
+// Returns true if the plan changed
+fn accept_prestart_deposit_amount(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<bool> {
+    if execute_step_meta.is_paper_trading()
+        || exec_plan.prestart_user_to_escrow_transfer.get_status() != ExecutableSimpleStatus::Failed
+    {
+        return Ok(false);
+    }
+    let prestart = &exec_plan.prestart_user_to_escrow_transfer;
+    let opt_deposited_amount = match &prestart.inner {
+        ExecutionStepEnum::SubstrateRemarkDeposit(_) => {
+            find_mismatched_remark_deposit(prestart, execute_step_meta)
+        }
+        _ => {
+            let chain_info = get_chain_info_from_chain_id(&prestart.get_src_chain())
+                .ok_or(ExecutableError::FailedToFindChainInfo)?;
+            find_mismatched_deposit(prestart, &execute_step_meta.get_rpc_url(chain_info))
+        }
+    };
+    let deposited_amount = match opt_deposited_amount {
+        Some(deposited_amount) => deposited_amount,
+        None => return Ok(false),
+    };
+    // The paths' amounts change, so their presigned txns no longer apply
+    let _ = release_presigned_txns(exec_plan, execute_step_meta);
+    Ok(accept_mismatched_deposit(exec_plan, deposited_amount))
+}
+
+// No deposit sweep behaves like an already-completed one
+fn get_deposit_sweep_status(exec_plan: &ExecutionPlan) -> ExecutableSimpleStatus {
+    exec_plan
+        .deposit_sweep
+        .as_ref()
+        .map_or(ExecutableSimpleStatus::Succeeded, |step| step.get_status())
+}
+
+fn get_remove_liquidity_status(exec_plan: &ExecutionPlan) -> ExecutableSimpleStatus {
+    exec_plan
+        .remove_liquidity
+        .as_ref()
+        .map_or(ExecutableSimpleStatus::Succeeded, |step| step.get_status())
+}
+
 fn have_all_exec_paths_succeeded(exec_plan: &ExecutionPlan) -> bool {
     exec_plan
         .paths
@@ -162,6 +463,206 @@ fn have_all_exec_paths_succeeded(exec_plan: &ExecutionPlan) -> bool {
         .all(|path| path.get_status() == ExecutableSimpleStatus::Succeeded)
 }
 
+fn is_any_exec_path_in_progress(exec_plan: &ExecutionPlan) -> bool {
+    exec_plan
+        .paths
+        .iter()
+        .any(|path| path.get_status() == ExecutableSimpleStatus::InProgress)
+}
+
+// The first hop is whatever starts as soon as the deposit reaches the escrow, i.e. the first
+// step of every path (or only of the first tranche if the paths run sequentially)
+fn have_first_hops_succeeded(exec_plan: &ExecutionPlan) -> bool {
+    let num_first_paths = match exec_plan.path_execution_mode {
+        PathExecutionMode::Concurrent => exec_plan.paths.len(),
+        PathExecutionMode::Sequential => 1,
+    };
+    exec_plan.paths.iter().take(num_first_paths).all(|path| {
+        path.steps.first().map_or(true, |step| {
+            step.get_status() == ExecutableSimpleStatus::Succeeded
+        })
+    })
+}
+
+// Starts the now-or-never countdown once the deposit has landed, and pulls the deadline in
+// (so that the refund flow kicks in right away) if the first hop misses it.
+// Returns whether the plan changed
+fn enforce_now_or_never_policy(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<bool> {
+    let (max_blocks, first_hop_deadline_block) = match exec_plan.execution_policy {
+        ExecutionPolicy::Default => return Ok(false),
+        ExecutionPolicy::NowOrNever {
+            max_blocks,
+            first_hop_deadline_block,
+        } => (max_blocks, first_hop_deadline_block),
+    };
+    if have_first_hops_succeeded(exec_plan) {
+        return Ok(false);
+    }
+    let cur_block = execute_step_meta
+        .get_cur_block(&exec_plan.prestart_user_to_escrow_transfer.get_src_chain())?;
+    match first_hop_deadline_block {
+        None => {
+            exec_plan.execution_policy = ExecutionPolicy::NowOrNever {
+                max_blocks,
+                first_hop_deadline_block: Some(cur_block.saturating_add(max_blocks)),
+            };
+            Ok(true)
+        }
+        Some(deadline_block) if cur_block > deadline_block => {
+            exec_plan.deadline_millis = execute_step_meta.cur_timestamp().saturating_sub(1);
+            Ok(true)
+        }
+        Some(_) => Ok(false),
+    }
+}
+
+// The refund amount is only set once the plan has missed its deadline
+fn is_refunding(exec_plan: &ExecutionPlan) -> bool {
+    exec_plan
+        .refund_escrow_to_user_transfer
+        .get_amount_in()
+        .is_some()
+}
+
+fn get_refund_status(exec_plan: &ExecutionPlan) -> ExecutableSimpleStatus {
+    // The postend amount is only set once the started paths have finished
+    if is_any_exec_path_in_progress(exec_plan) {
+        return ExecutableSimpleStatus::InProgress;
+    }
+    // A zero refund or an unset postend amount means there was nothing to send back
+    let refund_status = if exec_plan.refund_escrow_to_user_transfer.get_amount_in() == Some(0) {
+        ExecutableSimpleStatus::Succeeded
+    } else {
+        exec_plan.refund_escrow_to_user_transfer.get_status()
+    };
+    let postend_status = if exec_plan
+        .postend_escrow_to_user_transfer
+        .get_amount_in()
+        .is_none()
+    {
+        ExecutableSimpleStatus::Succeeded
+    } else {
+        exec_plan.postend_escrow_to_user_transfer.get_status()
+    };
+    if refund_status == ExecutableSimpleStatus::Dropped
+        || postend_status == ExecutableSimpleStatus::Dropped
+    {
+        ExecutableSimpleStatus::Dropped
+    } else if refund_status == ExecutableSimpleStatus::Failed
+        || postend_status == ExecutableSimpleStatus::Failed
+    {
+        ExecutableSimpleStatus::Failed
+    } else if refund_status == ExecutableSimpleStatus::Succeeded
+        && postend_status == ExecutableSimpleStatus::Succeeded
+    {
+        ExecutableSimpleStatus::Refunded
+    } else {
+        ExecutableSimpleStatus::InProgress
+    }
+}
+
+// Once the deadline passes no more paths start. Paths that never started still have their
+// funds in the escrow in the src token, so those are refunded. Paths that have started are no
+// longer in the src token, so they run to completion, and whatever the completed paths delivered
+// goes out through the postend step as usual. Started paths that fail are left to the stranded
+// funds ledger, as are an LP source's paths that never started, since the escrow holds the
+// pair's tokens rather than the LP tokens the refund sends back
+fn execute_refund_step_forward(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+    keys: &KeyContainer,
+) -> ExecutableResult<bool> {
+    let mut did_status_change = false;
+    if !is_refunding(exec_plan) {
+        let refund_amount = if exec_plan.remove_liquidity.is_some() {
+            0
+        } else {
+            exec_plan
+                .paths
+                .iter()
+                .filter(|path| path.get_status() == ExecutableSimpleStatus::NotStarted)
+                .fold(0, |amount, path| {
+                    amount
+                        + path
+                            .steps
+                            .first()
+                            .and_then(|step| step.get_amount_in())
+                            .unwrap_or(0)
+                })
+        };
+        exec_plan
+            .refund_escrow_to_user_transfer
+            .set_amount_in(refund_amount);
+        did_status_change = true;
+    }
+    for exec_path in exec_plan.paths.iter_mut() {
+        if exec_path.get_status() == ExecutableSimpleStatus::InProgress {
+            let path_res = exec_path.execute_step_forward(execute_step_meta, keys)?;
+            did_status_change = did_status_change | path_res.did_status_change;
+        }
+    }
+    if !is_any_exec_path_in_progress(exec_plan)
+        && exec_plan
+            .postend_escrow_to_user_transfer
+            .get_amount_in()
+            .is_none()
+    {
+        let succeeded_amount_out = exec_plan
+            .paths
+            .iter()
+            .filter(|path| path.get_status() == ExecutableSimpleStatus::Succeeded)
+            .fold(0, |amount_out, path| {
+                amount_out + path.amount_out.unwrap_or(0)
+            });
+        if succeeded_amount_out > 0 {
+            let amount_in_after_fee = calc_amount_after_simple_fee(exec_plan, succeeded_amount_out);
+            exec_plan
+                .postend_escrow_to_user_transfer
+                .set_amount_in(amount_in_after_fee);
+            did_status_change = true;
+        }
+    }
+
+    // Both transfers are sent from the escrow, so we send them one after the other
+    let refund_status = exec_plan.refund_escrow_to_user_transfer.get_status();
+    if exec_plan.refund_escrow_to_user_transfer.get_amount_in() != Some(0)
+        && (refund_status == ExecutableSimpleStatus::NotStarted
+            || refund_status == ExecutableSimpleStatus::InProgress)
+    {
+        let refund_res = exec_plan
+            .refund_escrow_to_user_transfer
+            .execute_step_forward(execute_step_meta, keys)?;
+        did_status_change = did_status_change | refund_res.did_status_change;
+    } else if exec_plan
+        .postend_escrow_to_user_transfer
+        .get_amount_in()
+        .is_some()
+    {
+        let postend_status = exec_plan.postend_escrow_to_user_transfer.get_status();
+        if (postend_status == ExecutableSimpleStatus::NotStarted
+            || postend_status == ExecutableSimpleStatus::InProgress)
+            && !is_postend_held(exec_plan, execute_step_meta)
+        {
+            let postend_res = exec_plan
+                .postend_escrow_to_user_transfer
+                .execute_step_forward(execute_step_meta, keys)?;
+            did_status_change = did_status_change | postend_res.did_status_change;
+        }
+    }
+    Ok(did_status_change)
+}
+
+// Once broadcast, the postend transfer has to run its course. So a hold only keeps it from
+// starting
+fn is_postend_held(exec_plan: &ExecutionPlan, execute_step_meta: &ExecuteStepMeta) -> bool {
+    execute_step_meta.is_delivery_held()
+        && exec_plan.postend_escrow_to_user_transfer.get_status()
+            == ExecutableSimpleStatus::NotStarted
+}
+
 fn sum_exec_paths_amounts_out(exec_paths: &[ExecutionPath]) -> Amount {
     exec_paths.iter().fold(0, |amount_out, exec_path| {
         // All the amount outs should be non-null!
@@ -169,12 +670,14 @@ fn sum_exec_paths_amounts_out(exec_paths: &[ExecutionPath]) -> Amount {
     })
 }
 
+// Simple 0.05% fee. Also reported in the quote's fee breakdown
+pub const PROTOCOL_FEE_BPS: u16 = 5;
+
 // TODO_lowpriority: Can make this fee as sophisticated as possible (e.g. depend on the
 // complexity of the execution plan, etc.). Simple % fee for now.
-fn calc_amount_after_simple_fee(amount_no_fee: Amount) -> Amount {
-    // Simple 0.05% fee
+fn calc_amount_after_simple_fee(exec_plan: &ExecutionPlan, amount_no_fee: Amount) -> Amount {
     // TODO: This needs to account for gas fees before true go-live
-    mul_ratio_u128(amount_no_fee, 9_995, 10_000)
+    split_plan_protocol_fee(exec_plan, amount_no_fee).amount_after_fee
 }
 
 // Prerequisites for these tests: You need to have sufficient funds in your account!
@@ -187,12 +690,13 @@ fn calc_amount_after_simple_fee(amount_no_fee: Amount) -> Amount {
 mod executable_plan_tests {
     use core::str::FromStr;
     use hex_literal::hex;
-    use ink_env::debug_println;
-    use ink_prelude::{vec, vec::Vec};
+    use ink::env::debug_println;
+    use ink::prelude::{vec, vec::Vec};
     use privadex_chain_metadata::{
         common::{
-            BlockNum, ChainTokenId, ERC20Token, EthAddress, EthTxnHash, SecretKeyContainer,
-            SubstratePublicKey, UniversalAddress, UniversalChainId, UniversalTokenId,
+            BlockNum, ChainTokenId, ERC20Token, EthAddress, EthTxnHash, MillisSinceEpoch,
+            SecretKeyContainer, SubstratePublicKey, UniversalAddress, UniversalChainId,
+            UniversalTokenId,
         },
         registry::{
             bridge::xcm_bridge_registry::XCM_BRIDGES,
@@ -201,14 +705,14 @@ mod executable_plan_tests {
             token::universal_token_id_registry,
         },
     };
-    use privadex_common::uuid::Uuid;
+    use privadex_common::{signature_scheme::SignatureScheme, uuid::Uuid};
     use privadex_execution_plan::execution_plan::{
         CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, ERC20TransferStep,
         EthDexSwapStep, EthPendingTxnId, EthSendStep, EthStepStatus, EthUnwrapStep, EthWrapStep,
-        ExecutionPath, ExecutionStep, ExecutionStepEnum, XCMTransferStep,
+        ExecutionPath, ExecutionStep, ExecutionStepEnum, PlanReviewStatus, XCMTransferStep,
     };
 
-    use crate::key_container::AddressKeyPair;
+    use crate::key_container::{AddressKeyPair, SigningKey};
 
     use super::super::traits::ExecutableError;
     use super::*;
@@ -233,21 +737,16 @@ mod executable_plan_tests {
                 .expect("ETH_PRIVATE_KEY to_hex failed")
                 .0
         };
-        KeyContainer {
-            0: vec![AddressKeyPair {
-                address: UniversalAddress::Ethereum(EthAddress {
-                    0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
-                }),
-                key: kap_privkey,
-            }],
-        }
+        KeyContainer::new(vec![AddressKeyPair {
+            address: UniversalAddress::Ethereum(EthAddress {
+                0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+            }),
+            key: SigningKey::Local(kap_privkey),
+            sig_scheme: SignatureScheme::Ethereum,
+        }])
     }
 
-    #[test]
-    fn simple_plan() {
-        pink_extension_runtime::mock_ext::mock_all_ext();
-
-        let (addr, execute_step_meta, keys) = dummy_state();
+    fn dummy_plan(addr: &UniversalAddress, deadline_millis: MillisSinceEpoch) -> ExecutionPlan {
         let exec_path1 = ExecutionPath {
             steps: vec![
                 ExecutionStep::new(ExecutionStepEnum::EthWrap(EthWrapStep {
@@ -288,6 +787,7 @@ mod executable_plan_tests {
                 })),
             ],
             amount_out: None,
+            step_amounts_out: Vec::new(),
         };
         let exec_path2 = ExecutionPath {
             steps: vec![
@@ -317,8 +817,9 @@ mod executable_plan_tests {
                 })),
             ],
             amount_out: None,
+            step_amounts_out: Vec::new(),
         };
-        let mut exec_plan = ExecutionPlan {
+        ExecutionPlan {
             uuid: Uuid::new([0u8; 16]),
             paths: vec![exec_path1, exec_path2],
             prestart_user_to_escrow_transfer: ExecutionStep::new(ExecutionStepEnum::EthSend(
@@ -338,6 +839,7 @@ mod executable_plan_tests {
                     }),
                 },
             )),
+            deposit_sweep: None,
             postend_escrow_to_user_transfer: ExecutionStep::new(ExecutionStepEnum::EthSend(
                 EthSendStep {
                     uuid: Uuid::new([0u8; 16]),
@@ -352,7 +854,43 @@ mod executable_plan_tests {
                     status: EthStepStatus::NotStarted,
                 },
             )),
-        };
+            refund_escrow_to_user_transfer: ExecutionStep::new(ExecutionStepEnum::EthSend(
+                EthSendStep {
+                    uuid: Uuid::new([0u8; 16]),
+                    chain: universal_chain_id_registry::MOONBEAM,
+                    amount: None,
+                    common: CommonExecutionMeta {
+                        src_addr: addr.clone(),
+                        dest_addr: addr.clone(),
+                        gas_fee_native: 1_000_000_000,
+                        gas_fee_usd: 2_000_0000_000,
+                    },
+                    status: EthStepStatus::NotStarted,
+                },
+            )),
+            path_execution_mode: PathExecutionMode::Concurrent,
+            execution_policy: ExecutionPolicy::Default,
+            deadline_millis,
+            last_progress_millis: 0,
+            paper_trade: false,
+            dest_name: None,
+            metadata: None,
+            quote: None,
+            prestart_confirmed_block: None,
+            review_status: PlanReviewStatus::NotNeeded,
+            route_hash: None,
+            quoted_terms: None,
+            remove_liquidity: None,
+            sponsorship_campaign_id: None,
+        }
+    }
+
+    #[test]
+    fn simple_plan() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
 
         // Prestart step is in progress
         assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::InProgress);
@@ -360,6 +898,7 @@ mod executable_plan_tests {
 
         while exec_plan.get_status() == ExecutableSimpleStatus::NotStarted
             || exec_plan.get_status() == ExecutableSimpleStatus::InProgress
+            || exec_plan.get_status() == ExecutableSimpleStatus::PrestartConfirming
         {
             if !have_all_exec_paths_succeeded(&exec_plan) {
                 assert_eq!(exec_plan.get_total_fee_usd(), None);
@@ -378,4 +917,211 @@ mod executable_plan_tests {
         assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Succeeded);
         assert!(exec_plan.get_total_fee_usd().is_some());
     }
+
+    #[test]
+    fn sequential_plan_runs_one_tranche_at_a_time() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
+        exec_plan.path_execution_mode = PathExecutionMode::Sequential;
+
+        while exec_plan.get_status() == ExecutableSimpleStatus::InProgress
+            || exec_plan.get_status() == ExecutableSimpleStatus::PrestartConfirming
+        {
+            exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+            // The second tranche only starts once the first has delivered to the escrow
+            if exec_plan.paths[1].get_status() != ExecutableSimpleStatus::NotStarted {
+                assert_eq!(
+                    exec_plan.paths[0].get_status(),
+                    ExecutableSimpleStatus::Succeeded
+                );
+            }
+        }
+
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Succeeded);
+        assert!(have_all_exec_paths_succeeded(&exec_plan));
+        // Both tranches are delivered together at the end
+        let total_amount_out = sum_exec_paths_amounts_out(&exec_plan.paths);
+        assert_eq!(
+            exec_plan.postend_escrow_to_user_transfer.get_amount_in(),
+            Some(calc_amount_after_simple_fee(&exec_plan, total_amount_out))
+        );
+    }
+
+    #[test]
+    fn held_delivery_waits_for_approval() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, mut execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
+        execute_step_meta.set_hold_delivery(true);
+
+        while !have_all_exec_paths_succeeded(&exec_plan) {
+            exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+        }
+        let res = exec_plan
+            .execute_step_forward(&execute_step_meta, &keys)
+            .expect("Step should succeed");
+        assert!(!res.did_status_change);
+        assert_eq!(
+            exec_plan.postend_escrow_to_user_transfer.get_status(),
+            ExecutableSimpleStatus::NotStarted
+        );
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::InProgress);
+
+        execute_step_meta.set_hold_delivery(false);
+        while exec_plan.get_status() == ExecutableSimpleStatus::InProgress {
+            exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+        }
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Succeeded);
+    }
+
+    #[test]
+    fn prestart_deposit_is_confirmed_before_paths_start() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
+        if let ExecutionStepEnum::EthSend(step) =
+            &mut exec_plan.prestart_user_to_escrow_transfer.inner
+        {
+            step.status = EthStepStatus::Confirmed(EthTxnHash::zero());
+        }
+        // The deposit has landed but nobody has checked how deep it is yet
+        assert_eq!(
+            exec_plan.get_status(),
+            ExecutableSimpleStatus::PrestartConfirming
+        );
+
+        let res = exec_plan
+            .execute_step_forward(&execute_step_meta, &keys)
+            .expect("Step should succeed");
+        assert!(res.did_status_change);
+        // The mocked receipt is in block 0, which is deep enough on any chain
+        assert_eq!(exec_plan.prestart_confirmed_block, Some(0));
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::InProgress);
+        assert!(exec_plan
+            .paths
+            .iter()
+            .any(|path| path.get_status() != ExecutableSimpleStatus::NotStarted));
+    }
+
+    #[test]
+    fn plan_past_deadline_is_refunded() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        // Deadline is already in the past so none of the paths should start
+        let mut exec_plan = dummy_plan(&addr, 0);
+
+        while exec_plan.get_status() == ExecutableSimpleStatus::NotStarted
+            || exec_plan.get_status() == ExecutableSimpleStatus::InProgress
+        {
+            let res = exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+            debug_println!("Step forward result: {:?}", res);
+            debug_println!("State: {:?}, {}\n", exec_plan.get_status(), exec_plan);
+        }
+
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Refunded);
+        assert!(exec_plan
+            .paths
+            .iter()
+            .all(|path| path.get_status() == ExecutableSimpleStatus::NotStarted));
+        assert_eq!(
+            exec_plan.refund_escrow_to_user_transfer.get_amount_in(),
+            Some(2_000_000_000)
+        );
+        assert_eq!(
+            exec_plan.postend_escrow_to_user_transfer.get_amount_in(),
+            None
+        );
+        assert_eq!(exec_plan.get_total_fee_usd(), None);
+        assert_eq!(
+            exec_plan.execute_step_forward(&execute_step_meta, &keys),
+            Err(ExecutableError::CalledStepForwardOnFinishedPlan)
+        );
+    }
+
+    #[test]
+    fn started_path_past_deadline_runs_to_completion() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
+        // The second path only starts once the first has delivered
+        exec_plan.path_execution_mode = PathExecutionMode::Sequential;
+        while exec_plan.paths[0].get_status() == ExecutableSimpleStatus::NotStarted {
+            exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+        }
+        assert_eq!(
+            exec_plan.paths[0].get_status(),
+            ExecutableSimpleStatus::InProgress
+        );
+        exec_plan.deadline_millis = 0;
+
+        while exec_plan.get_status() == ExecutableSimpleStatus::InProgress {
+            exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+        }
+
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Refunded);
+        assert_eq!(
+            exec_plan.paths[0].get_status(),
+            ExecutableSimpleStatus::Succeeded
+        );
+        assert_eq!(
+            exec_plan.paths[1].get_status(),
+            ExecutableSimpleStatus::NotStarted
+        );
+        assert_eq!(
+            exec_plan.refund_escrow_to_user_transfer.get_amount_in(),
+            Some(1_000_000_000)
+        );
+        let amount_out = exec_plan.paths[0].amount_out.expect("Path succeeded");
+        assert_eq!(
+            exec_plan.postend_escrow_to_user_transfer.get_amount_in(),
+            Some(calc_amount_after_simple_fee(&exec_plan, amount_out))
+        );
+    }
+
+    #[test]
+    fn now_or_never_plan_past_first_hop_deadline_is_refunded() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+
+        let (addr, execute_step_meta, keys) = dummy_state();
+        let mut exec_plan = dummy_plan(&addr, MillisSinceEpoch::MAX);
+        // The first hop window has long closed
+        exec_plan.execution_policy = ExecutionPolicy::NowOrNever {
+            max_blocks: 0,
+            first_hop_deadline_block: Some(0),
+        };
+
+        while exec_plan.get_status() == ExecutableSimpleStatus::NotStarted
+            || exec_plan.get_status() == ExecutableSimpleStatus::InProgress
+        {
+            let res = exec_plan
+                .execute_step_forward(&execute_step_meta, &keys)
+                .expect("Step should succeed");
+            debug_println!("Step forward result: {:?}", res);
+        }
+
+        assert_eq!(exec_plan.get_status(), ExecutableSimpleStatus::Refunded);
+        assert!(!have_first_hops_succeeded(&exec_plan));
+        assert_eq!(
+            exec_plan.refund_escrow_to_user_transfer.get_amount_in(),
+            Some(2_000_000_000)
+        );
+    }
 }
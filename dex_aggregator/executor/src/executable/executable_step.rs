@@ -18,18 +18,20 @@
 
 use privadex_chain_metadata::common::Amount;
 use privadex_common::utils::general_utils::mul_ratio_u128;
-use privadex_execution_plan::execution_plan::{ExecutionStep, ExecutionStepEnum};
+use privadex_execution_plan::execution_plan::{ExecutionStep, ExecutionStepEnum, StepMilestone};
 
 use crate::key_container::KeyContainer;
 
 use super::{
     execute_step_meta::ExecuteStepMeta,
+    presign::broadcast_presigned_txn,
     traits::{Executable, ExecutableResult, ExecutableSimpleStatus, StepForwardResult},
 };
 
 // After this many blocks, we assume the txn is dropped
 // 12 seconds per block * 64 block ~ 768 seconds
 // This is also used for Era, which requires this to be a power of 2!
+// Only the default, the admin can change it with set_executor_config
 pub const TXN_NUM_BLOCKS_ALIVE: u32 = 64;
 
 impl Executable for ExecutionStep {
@@ -41,6 +43,14 @@ impl Executable for ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.get_status(),
             ExecutionStepEnum::EthDexSwap(step) => step.get_status(),
             ExecutionStepEnum::XCMTransfer(step) => step.get_status(),
+            ExecutionStepEnum::WormholeTransfer(step) => step.get_status(),
+            ExecutionStepEnum::EthDepositSweep(step) => step.get_status(),
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => step.get_status(),
+            ExecutionStepEnum::EthContractCall(step) => step.get_status(),
+            ExecutionStepEnum::EthFeeSkim(step) => step.get_status(),
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.get_status(),
+            ExecutionStepEnum::EthZapIn(step) => step.get_status(),
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.get_status(),
         }
     }
 
@@ -52,6 +62,14 @@ impl Executable for ExecutionStep {
             ExecutionStepEnum::EthUnwrap(step) => step.get_total_fee_usd(),
             ExecutionStepEnum::EthDexSwap(step) => step.get_total_fee_usd(),
             ExecutionStepEnum::XCMTransfer(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::WormholeTransfer(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthDepositSweep(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::SubstrateRemarkDeposit(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthContractCall(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthFeeSkim(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthAggregatorSwap(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthZapIn(step) => step.get_total_fee_usd(),
+            ExecutionStepEnum::EthRemoveLiquidity(step) => step.get_total_fee_usd(),
         }
     }
 
@@ -60,41 +78,114 @@ impl Executable for ExecutionStep {
         execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
     ) -> ExecutableResult<StepForwardResult> {
+        let old_status = self.get_status();
+        // Presigned txns still go out since their nonces are already taken
+        if execute_step_meta.is_draining()
+            && old_status == ExecutableSimpleStatus::NotStarted
+            && self.presigned_txn.is_none()
+        {
+            return Ok(StepForwardResult {
+                did_status_change: false,
+                amount_out: None,
+            });
+        }
         let step_forward_res = {
             if self.get_amount_in().unwrap_or(0) > 0 {
-                match &mut self.inner {
-                    ExecutionStepEnum::EthSend(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                    ExecutionStepEnum::ERC20Transfer(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                    ExecutionStepEnum::EthWrap(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                    ExecutionStepEnum::EthUnwrap(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                    ExecutionStepEnum::EthDexSwap(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                    ExecutionStepEnum::XCMTransfer(step) => {
-                        step.execute_step_forward(execute_step_meta, keys)
-                    }
-                }?
+                match broadcast_presigned_txn(self, execute_step_meta)? {
+                    Some(presigned_res) => presigned_res,
+                    None => match &mut self.inner {
+                        ExecutionStepEnum::EthSend(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::ERC20Transfer(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthWrap(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthUnwrap(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthDexSwap(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::XCMTransfer(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::WormholeTransfer(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthDepositSweep(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::SubstrateRemarkDeposit(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthContractCall(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthFeeSkim(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthAggregatorSwap(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthZapIn(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                        ExecutionStepEnum::EthRemoveLiquidity(step) => {
+                            step.execute_step_forward(execute_step_meta, keys)
+                        }
+                    }?,
+                }
             } else {
                 self.drop(); // Change the status to Dropped
+                             // The step's nonce is dropped below along with the step
+                self.presigned_txn = None;
                 StepForwardResult {
                     did_status_change: true,
                     amount_out: None,
                 }
             }
         };
+        if step_forward_res.did_status_change {
+            record_step_timing(self, &old_status, execute_step_meta);
+        }
         let _ = terminate_exec_step_if_dropped_or_finalized(self, execute_step_meta)?;
         Ok(step_forward_res)
     }
 }
 
+fn record_step_timing(
+    exec_step: &mut ExecutionStep,
+    old_status: &ExecutableSimpleStatus,
+    execute_step_meta: &ExecuteStepMeta,
+) {
+    let new_status = exec_step.get_status();
+    let is_submitted = *old_status == ExecutableSimpleStatus::NotStarted
+        && new_status != ExecutableSimpleStatus::NotStarted
+        && new_status != ExecutableSimpleStatus::Dropped;
+    let is_confirmed = new_status == ExecutableSimpleStatus::Succeeded
+        || new_status == ExecutableSimpleStatus::Failed;
+    if !is_submitted && !is_confirmed {
+        return;
+    }
+    // Timing is informational, so a failed block lookup should not fail the step
+    let milestone = StepMilestone {
+        block_num: execute_step_meta
+            .get_cur_block(&exec_step.get_src_chain())
+            .ok(),
+        timestamp_millis: execute_step_meta.cur_timestamp(),
+    };
+    // A step can go from NotStarted straight to confirmed (e.g. when paper trading)
+    if is_submitted {
+        exec_step.timing.submitted = Some(milestone.clone());
+    }
+    if is_confirmed {
+        exec_step.timing.confirmed = Some(milestone);
+    }
+}
+
 fn terminate_exec_step_if_dropped_or_finalized(
     exec_step: &ExecutionStep,
     execute_step_meta: &ExecuteStepMeta,
@@ -106,7 +197,10 @@ fn terminate_exec_step_if_dropped_or_finalized(
         ExecutableSimpleStatus::Dropped => {
             execute_step_meta.drop_execstep(exec_step.get_uuid(), exec_step.get_src_chain())
         }
-        ExecutableSimpleStatus::NotStarted | ExecutableSimpleStatus::InProgress => Ok(()),
+        ExecutableSimpleStatus::NotStarted
+        | ExecutableSimpleStatus::InProgress
+        | ExecutableSimpleStatus::Refunded
+        | ExecutableSimpleStatus::PrestartConfirming => Ok(()),
     }
 }
 
@@ -123,7 +217,7 @@ pub fn get_updated_gas_fee_usd(
 #[cfg(test)]
 mod float_tests {
     use super::*;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     fn get_updated_gas_fee_usd_float(
         updated_gas_fee_native: Amount,
@@ -161,8 +255,8 @@ mod float_tests {
 mod executable_step_tests {
     use core::str::FromStr;
     use hex_literal::hex;
-    use ink_env::debug_println;
-    use ink_prelude::{vec, vec::Vec};
+    use ink::env::debug_println;
+    use ink::prelude::{vec, vec::Vec};
     use privadex_chain_metadata::{
         common::{
             ChainTokenId, ERC20Token, EthAddress, SecretKeyContainer, SubstratePublicKey,
@@ -175,13 +269,13 @@ mod executable_step_tests {
             token::universal_token_id_registry,
         },
     };
-    use privadex_common::uuid::Uuid;
+    use privadex_common::{signature_scheme::SignatureScheme, uuid::Uuid};
     use privadex_execution_plan::execution_plan::{
         CommonExecutionMeta, CrossChainStepStatus, DexRouterFunction, ERC20TransferStep,
         EthDexSwapStep, EthSendStep, EthStepStatus, EthUnwrapStep, EthWrapStep, XCMTransferStep,
     };
 
-    use crate::key_container::AddressKeyPair;
+    use crate::key_container::{AddressKeyPair, SigningKey};
 
     use super::super::traits::ExecutableError;
     use super::*;
@@ -206,14 +300,13 @@ mod executable_step_tests {
                 .expect("ETH_PRIVATE_KEY to_hex failed")
                 .0
         };
-        KeyContainer {
-            0: vec![AddressKeyPair {
-                address: UniversalAddress::Ethereum(EthAddress {
-                    0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
-                }),
-                key: kap_privkey,
-            }],
-        }
+        KeyContainer::new(vec![AddressKeyPair {
+            address: UniversalAddress::Ethereum(EthAddress {
+                0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+            }),
+            key: SigningKey::Local(kap_privkey),
+            sig_scheme: SignatureScheme::Ethereum,
+        }])
     }
 
     fn execute_eth_step(mut exec_step: ExecutionStep) {
@@ -403,6 +496,7 @@ mod executable_step_tests {
                 .dest_multilocation_template
                 .get_full_dest_multilocation(dest_addr.clone())
                 .expect("Wallet template should generate MultiLocation"),
+            dest_weight_limit: bridge.dest_weight_limit,
             amount_in: Some(1_000_000_000),
             bridge_fee_native: 100_000_000,
             bridge_fee_usd: 1_000_000_000_000_000,
@@ -17,40 +17,39 @@
  */
 
 use duplicate::duplicate_item;
-use ink_prelude::vec::Vec;
+use ink::prelude::{vec, vec::Vec};
 
 use pink_web3::types::SignedTransaction;
 use privadex_chain_metadata::{
     chain_info::ChainInfo,
     common::{
-        Amount, BlockNum, ChainTokenId, EthAddress, EthTxnHash, Nonce, UniversalAddress,
-        UniversalChainId,
+        Amount, BlockNum, ChainTokenId, ERC20Token, EthAddress, EthTxnHash, Nonce,
+        UniversalAddress, UniversalChainId, UniversalTokenId,
     },
-    get_chain_info_from_chain_id,
+    get_aggregator_from_aggregator_id, get_chain_info_from_chain_id,
 };
 use privadex_common::uuid::Uuid;
 use privadex_execution_plan::execution_plan::{
-    DexRouterFunction, ERC20TransferStep, EthDexSwapStep, EthPendingTxnId, EthSendStep,
-    EthStepStatus, EthUnwrapStep, EthWrapStep,
+    CommonExecutionMeta, DexRouterFunction, ERC20TransferStep, EthAggregatorSwapStep,
+    EthApproveStep, EthContractCallStep, EthDepositSweepStep, EthDexSwapStep, EthFeeSkimStep,
+    EthPendingTxnId, EthRemoveLiquidityStep, EthSendStep, EthStepStatus, EthUnwrapStep,
+    EthWrapStep, EthZapInStep, ExecutionStep, ExecutionStepEnum, AGGREGATOR_SWAP_SLIPPAGE_BPS,
 };
 
 use crate::{
     eth_utils,
     executable::{
-        executable_step::{get_updated_gas_fee_usd, TXN_NUM_BLOCKS_ALIVE},
+        executable_step::get_updated_gas_fee_usd,
         execute_step_meta::ExecuteStepMeta,
         traits::{
             Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus,
             StepForwardResult,
         },
+        zap_in, zap_out,
     },
     key_container::KeyContainer,
 };
 
-// The DEX swap deadline is this many millis in the future i.e. the txn will fail
-// if it is included in a block after 8 minutes
-const DEX_SWAP_LIFE_MILLIS: u64 = 480_000;
-
 #[duplicate_item(
 	exec_step;
 	[EthSendStep];
@@ -58,6 +57,13 @@ const DEX_SWAP_LIFE_MILLIS: u64 = 480_000;
     [EthUnwrapStep];
     [EthWrapStep];
     [EthDexSwapStep];
+    [EthDepositSweepStep];
+    [EthContractCallStep];
+    [EthFeeSkimStep];
+    [EthApproveStep];
+    [EthAggregatorSwapStep];
+    [EthZapInStep];
+    [EthRemoveLiquidityStep];
 )]
 impl Executable for exec_step {
     fn get_status(&self) -> ExecutableSimpleStatus {
@@ -90,13 +96,34 @@ impl Executable for exec_step {
                 txn_hash,
                 end_block_num,
             }) => {
-                let res = self.execute_step_forward_if_inprogress(txn_hash, end_block_num)?;
+                let res = if execute_step_meta.is_paper_trading() {
+                    Some(self.get_paper_completed_step_result(
+                        execute_step_meta,
+                        txn_hash,
+                        self.common.gas_fee_native,
+                    )?)
+                } else {
+                    self.execute_step_forward_if_inprogress(
+                        execute_step_meta,
+                        txn_hash,
+                        end_block_num,
+                    )?
+                };
+                let res = match res {
+                    Some(completed_step_result) => {
+                        self.on_txn_completed(execute_step_meta, completed_step_result)?
+                    }
+                    None => None,
+                };
                 if let Some(completed_step_result) = res {
                     Ok((
                         Some(completed_step_result.new_status),
                         Some(completed_step_result.actual_gas_fee_native),
                         Some(completed_step_result.amount_out),
                     ))
+                } else if self.status == EthStepStatus::NotStarted {
+                    // on_txn_completed moved the step on to its next txn
+                    Ok((Some(EthStepStatus::NotStarted), None, None))
                 } else {
                     Ok((None, None, None))
                 }
@@ -140,33 +167,39 @@ trait EthExecutableHelper {
     ) -> ExecutableResult<EthStepStatus /* new status */> {
         let chain_info = get_chain_info_from_chain_id(&self.get_chain())
             .ok_or(ExecutableError::FailedToFindChainInfo)?;
-        let cur_block = eth_utils::common::block_number(chain_info.rpc_url)
-            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let src_addr = if let UniversalAddress::Ethereum(src_addr) = self.src_addr() {
+            Ok(src_addr.clone())
+        } else {
+            Err(ExecutableError::UnexpectedNonEthAddress)
+        }?;
+        let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+        // The system nonce shares the round trip with the block number, so we fetch it even
+        // though a nonce pool may not need it
+        let (cur_block, system_nonce) =
+            eth_utils::common::block_number_and_system_nonce(&rpc_url, src_addr)
+                .map_err(|_| ExecutableError::RpcRequestFailed)?;
 
         // Using NonceManager to get the nonce in a concurrent-safe way
-        let nonce = {
-            let system_nonce = {
-                if let UniversalAddress::Ethereum(src_addr) = self.src_addr() {
-                    eth_utils::common::get_next_system_nonce(chain_info.rpc_url, src_addr.clone())
-                        .map_err(|_| ExecutableError::RpcRequestFailed)
-                } else {
-                    Err(ExecutableError::UnexpectedNonEthAddress)
-                }
-            }?;
-            execute_step_meta.get_nonce(
-                self.get_exec_step_uuid(),
-                self.get_chain(),
-                cur_block,
-                system_nonce,
-            )
-        }?;
-        let signed_txn = self.create_raw_txn(execute_step_meta, keys, chain_info, nonce)?;
+        let nonce = execute_step_meta.get_pooled_nonce(
+            self.get_exec_step_uuid(),
+            self.get_chain(),
+            self.src_addr(),
+            cur_block,
+            || Ok(system_nonce),
+        )?;
+        let signed_txn =
+            self.create_raw_txn(execute_step_meta, keys, chain_info, &rpc_url, nonce)?;
 
-        let txn_hash = self.send_raw_txn(chain_info.rpc_url, signed_txn)?;
+        let txn_hash = execute_step_meta.send_eth_txn(
+            self.get_exec_step_uuid(),
+            self.get_chain(),
+            &rpc_url,
+            signed_txn,
+        )?;
 
         Ok(EthStepStatus::Submitted(EthPendingTxnId {
             txn_hash,
-            end_block_num: cur_block + TXN_NUM_BLOCKS_ALIVE,
+            end_block_num: cur_block + execute_step_meta.get_executor_config().txn_num_blocks_alive,
         }))
     }
 
@@ -175,12 +208,14 @@ trait EthExecutableHelper {
     // Err(_) if we encountered an error
     fn execute_step_forward_if_inprogress(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         txn_hash: EthTxnHash,
         end_block_num: BlockNum,
     ) -> ExecutableResult<Option<CompletedStepResult>> {
         let chain_info = get_chain_info_from_chain_id(&self.get_chain())
             .ok_or(ExecutableError::FailedToFindChainInfo)?;
-        let cur_block = eth_utils::common::block_number(chain_info.rpc_url)
+        let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+        let cur_block = eth_utils::common::block_number(&rpc_url)
             .map_err(|_| ExecutableError::RpcRequestFailed)?;
 
         if cur_block > end_block_num {
@@ -190,26 +225,37 @@ trait EthExecutableHelper {
                 amount_out: 0,
             }))
         } else {
-            Ok(self.get_completed_step_result(chain_info.rpc_url, txn_hash))
+            Ok(self.get_completed_step_result(&rpc_url, txn_hash))
         }
     }
 
+    // Nothing was broadcast in paper trading mode, so we confirm right away with our gas
+    // estimate and what the txn would have produced at the current chain state
+    fn get_paper_completed_step_result(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        txn_hash: EthTxnHash,
+        estimated_gas_fee_native: Amount,
+    ) -> ExecutableResult<CompletedStepResult> {
+        let chain_info = get_chain_info_from_chain_id(&self.get_chain())
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        Ok(CompletedStepResult {
+            new_status: EthStepStatus::Confirmed(txn_hash),
+            actual_gas_fee_native: estimated_gas_fee_native,
+            amount_out: self.get_paper_amount_out(&execute_step_meta.get_rpc_url(chain_info))?,
+        })
+    }
+
     fn create_raw_txn(
         &self,
         execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction>;
 
-    fn send_raw_txn(
-        &self,
-        rpc_url: &str,
-        raw_txn: SignedTransaction,
-    ) -> ExecutableResult<EthTxnHash> {
-        eth_utils::common::send_raw_transaction(rpc_url, raw_txn)
-            .map_err(|_| ExecutableError::RpcRequestFailed)
-    }
+    fn get_paper_amount_out(&self, rpc_url: &str) -> ExecutableResult<Amount>;
 
     fn get_completed_step_result(
         &self,
@@ -222,6 +268,16 @@ trait EthExecutableHelper {
     fn get_chain(&self) -> UniversalChainId;
 
     fn get_exec_step_uuid(&self) -> &Uuid;
+
+    // Steps that take more than one txn (i.e. EthContractCallStep's approval) handle the earlier
+    // txns here. They return None after setting status back to NotStarted for the next txn
+    fn on_txn_completed(
+        &mut self,
+        _execute_step_meta: &ExecuteStepMeta,
+        completed_step_result: CompletedStepResult,
+    ) -> ExecutableResult<Option<CompletedStepResult>> {
+        Ok(Some(completed_step_result))
+    }
 }
 
 impl EthExecutableHelper for EthSendStep {
@@ -230,6 +286,7 @@ impl EthExecutableHelper for EthSendStep {
         _execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction> {
         let to_addr = {
@@ -248,14 +305,19 @@ impl EthExecutableHelper for EthSendStep {
             .ok_or(ExecutableError::UnexpectedNullEvmChainId)?;
 
         eth_utils::common::create_send_eth_raw_txn(
-            chain_info.rpc_url,
+            rpc_url,
             to_addr,
             amount,
             key,
             evm_chain_id,
+            chain_info.supports_eip1559,
             nonce,
         )
-        .map_err(|_| ExecutableError::FailedToCreateTxn)
+        .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
     }
 
     fn get_completed_step_result(
@@ -290,6 +352,7 @@ impl EthExecutableHelper for ERC20TransferStep {
         _execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction> {
         let to_addr = {
@@ -312,12 +375,15 @@ impl EthExecutableHelper for ERC20TransferStep {
             }
         }?;
 
-        let erc20_contract =
-            eth_utils::erc20_contract::ERC20Contract::new(chain_info.rpc_url, token_eth_addr)
-                .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+        let erc20_contract = eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_eth_addr)
+            .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
         erc20_contract
-            .transfer(to_addr, amount, key, nonce)
-            .map_err(|_| ExecutableError::FailedToCreateTxn)
+            .transfer(to_addr, amount, key, chain_info.supports_eip1559, nonce)
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
     }
 
     fn get_completed_step_result(
@@ -336,6 +402,7 @@ impl EthExecutableHelper for ERC20TransferStep {
             rpc_url,
             txn_hash,
             &token_addr,
+            helpers::get_eth_dest_addr(&self.common)?,
             self.amount
                 .expect("Should have checked for erroneously null amount in create_raw_txn"),
         )
@@ -360,6 +427,7 @@ impl EthExecutableHelper for EthWrapStep {
         _execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction> {
         let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
@@ -368,15 +436,19 @@ impl EthExecutableHelper for EthWrapStep {
             .ok_or(ExecutableError::SecretNotFound)?;
 
         let weth_contract = eth_utils::weth_contract::WethContract::new(
-            chain_info.rpc_url,
+            rpc_url,
             chain_info
                 .weth_addr
                 .ok_or(ExecutableError::FailedToLoadWethContract)?,
         )
         .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
         weth_contract
-            .deposit(amount, key, nonce)
-            .map_err(|_| ExecutableError::FailedToCreateTxn)
+            .deposit(amount, key, chain_info.supports_eip1559, nonce)
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
     }
 
     fn get_completed_step_result(
@@ -411,6 +483,7 @@ impl EthExecutableHelper for EthUnwrapStep {
         _execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction> {
         let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
@@ -419,15 +492,19 @@ impl EthExecutableHelper for EthUnwrapStep {
             .ok_or(ExecutableError::SecretNotFound)?;
 
         let weth_contract = eth_utils::weth_contract::WethContract::new(
-            chain_info.rpc_url,
+            rpc_url,
             chain_info
                 .weth_addr
                 .ok_or(ExecutableError::FailedToLoadWethContract)?,
         )
         .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
         weth_contract
-            .withdraw(amount, key, nonce)
-            .map_err(|_| ExecutableError::FailedToCreateTxn)
+            .withdraw(amount, key, chain_info.supports_eip1559, nonce)
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
     }
 
     fn get_completed_step_result(
@@ -462,6 +539,7 @@ impl EthExecutableHelper for EthDexSwapStep {
         execute_step_meta: &ExecuteStepMeta,
         keys: &KeyContainer,
         chain_info: &ChainInfo,
+        rpc_url: &str,
         nonce: Nonce,
     ) -> ExecutableResult<SignedTransaction> {
         let amount_in = self
@@ -471,18 +549,7 @@ impl EthExecutableHelper for EthDexSwapStep {
         // but doing so means we need to handle failed transactions if the limit
         // price is exceeded. For simplicity, we exclude this feature in the MVP.
         let amount_out_min = 0;
-        let path = {
-            let swap_path: Result<Vec<EthAddress>, ExecutableError> = self
-                .token_path
-                .iter()
-                .map(|universal_token_id| match &universal_token_id.id {
-                    ChainTokenId::Native => Err(ExecutableError::UnexpectedNonEthAddress),
-                    ChainTokenId::ERC20(erc20_token) => Ok(erc20_token.addr),
-                    ChainTokenId::XC20(xc20_token) => Ok(xc20_token.get_eth_address()),
-                })
-                .collect();
-            swap_path?
-        };
+        let path = helpers::get_swap_path(&self.token_path)?;
         let to_addr = {
             if let UniversalAddress::Ethereum(eth_addr) = self.common.dest_addr.clone() {
                 Ok(eth_addr)
@@ -490,22 +557,18 @@ impl EthExecutableHelper for EthDexSwapStep {
                 Err(ExecutableError::UnexpectedNonEthAddress)
             }
         }?;
-        let deadline = {
-            if execute_step_meta.cur_timestamp() > u64::MAX - DEX_SWAP_LIFE_MILLIS {
-                u64::MAX
-            } else {
-                execute_step_meta.cur_timestamp() + DEX_SWAP_LIFE_MILLIS
-            }
-        };
+        // In the chain's clock, since that is what the router checks it against
+        let deadline_secs = execute_step_meta
+            .get_chain_timestamp(&chain_info.chain_id)
+            .saturating_add(execute_step_meta.get_executor_config().dex_swap_life_millis)
+            / 1000;
         let key = keys
             .get_key(self.src_addr())
             .ok_or(ExecutableError::SecretNotFound)?;
 
-        let dex_router_contract = eth_utils::dex_router_contract::DEXRouterContract::new(
-            chain_info.rpc_url,
-            self.dex_router_addr,
-        )
-        .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+        let dex_router_contract =
+            eth_utils::dex_router_contract::DEXRouterContract::new(rpc_url, self.dex_router_addr)
+                .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
         let router_func = match self.dex_router_func {
             DexRouterFunction::SwapExactETHForTokens => {
                 eth_utils::dex_router_contract::DEXRouterContract::swap_exact_eth_for_tokens
@@ -523,11 +586,28 @@ impl EthExecutableHelper for EthDexSwapStep {
             amount_out_min,
             path,
             to_addr,
-            deadline,
+            deadline_secs,
             key,
+            chain_info.supports_eip1559,
             nonce,
         )
-        .map_err(|_| ExecutableError::FailedToCreateTxn)
+        .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, rpc_url: &str) -> ExecutableResult<Amount> {
+        let amount_in = self
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let dex_router_contract =
+            eth_utils::dex_router_contract::DEXRouterContract::new(rpc_url, self.dex_router_addr)
+                .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+        let amounts_out = dex_router_contract
+            .get_amounts_out(amount_in, helpers::get_swap_path(&self.token_path)?)
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        amounts_out
+            .last()
+            .copied()
+            .ok_or(ExecutableError::RpcRequestFailed)
     }
 
     fn get_completed_step_result(
@@ -569,77 +649,1204 @@ impl EthExecutableHelper for EthDexSwapStep {
     }
 }
 
-mod helpers {
-    use super::*;
+impl EthExecutableHelper for EthDepositSweepStep {
+    fn create_raw_txn(
+        &self,
+        _execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let escrow_addr = {
+            if let UniversalAddress::Ethereum(eth_addr) = self.common.dest_addr.clone() {
+                Ok(eth_addr)
+            } else {
+                Err(ExecutableError::UnexpectedNonEthAddress)
+            }
+        }?;
+        let _ = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
 
-    // For ETH send, ERC20 transfer, we know that amount_out SHOULD be the same as amount_in but
-    // we check anyway. This is important! For the prestart step, a user could otherwise cheat the
-    // system by passing in a different value of amount_in (or different token ID) and sending a txn
-    // of lower value (or of different token)
-    pub(super) fn verified_get_completed_step_result_for_eth_transfer(
+        // The factory treats the zero address as the native token
+        let token_eth_addr = match &self.token.id {
+            ChainTokenId::Native => EthAddress::zero(),
+            ChainTokenId::ERC20(erc20_token) => erc20_token.addr,
+            ChainTokenId::XC20(xc20_token) => xc20_token.get_eth_address(),
+        };
+
+        let factory_contract =
+            eth_utils::deposit_forwarder_factory_contract::DepositForwarderFactoryContract::new(
+                rpc_url,
+                self.factory_addr,
+            )
+            .map_err(|_| ExecutableError::FailedToLoadDepositForwarderFactoryContract)?;
+        factory_contract
+            .sweep(
+                self.salt,
+                token_eth_addr,
+                escrow_addr,
+                key,
+                chain_info.supports_eip1559,
+                nonce,
+            )
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
+    }
+
+    // The prestart step already verified the deposited amount, and the forwarder
+    // cannot hold less than that since only the escrow can sweep it
+    fn get_completed_step_result(
+        &self,
         rpc_url: &str,
-        eth_send_txn: EthTxnHash,
-        expected_amount: Amount,
+        txn_hash: EthTxnHash,
     ) -> Option<CompletedStepResult> {
-        if let Ok(eth_transfer) =
-            eth_utils::parse_txn_helper::parse_transfer_from_eth_send_txn(rpc_url, eth_send_txn)
-        {
-            if is_eth_transfer_invalid(&eth_transfer, expected_amount) {
-                ink_env::debug_println!("Unexpected! Amount received from Eth transfer ({}) does not match expected amount ({})",
-                    eth_transfer.amount, expected_amount);
-                // Treat this like a fail
-                Some(CompletedStepResult {
-                    new_status: EthStepStatus::Failed(eth_send_txn),
-                    actual_gas_fee_native: eth_transfer.gas_fee_native,
-                    amount_out: 0,
-                })
-            } else {
-                if eth_transfer.is_txn_success {
-                    Some(CompletedStepResult {
-                        new_status: EthStepStatus::Confirmed(eth_send_txn),
-                        actual_gas_fee_native: eth_transfer.gas_fee_native,
-                        amount_out: expected_amount,
-                    })
-                } else {
-                    Some(CompletedStepResult {
-                        new_status: EthStepStatus::Failed(eth_send_txn),
-                        actual_gas_fee_native: eth_transfer.gas_fee_native,
-                        amount_out: 0,
-                    })
-                }
+        helpers::get_completed_step_result_for_known_amount(
+            rpc_url,
+            txn_hash,
+            self.amount
+                .expect("Should have checked for erroneously null amount in create_raw_txn"),
+        )
+    }
+
+    // The deposit address (common.src_addr) has no key. The escrow owns the factory
+    // and signs the sweep
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.dest_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.token.chain
+    }
+
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+}
+
+impl EthExecutableHelper for EthContractCallStep {
+    fn create_raw_txn(
+        &self,
+        _execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+        let token_eth_addr = match &self.token.id {
+            ChainTokenId::Native => None,
+            ChainTokenId::ERC20(erc20_token) => Some(erc20_token.addr),
+            ChainTokenId::XC20(xc20_token) => Some(xc20_token.get_eth_address()),
+        };
+
+        match token_eth_addr {
+            // Approve exactly amount so the target can't pull any other escrow funds
+            Some(token_eth_addr) if self.is_approving() => {
+                let erc20_contract =
+                    eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_eth_addr)
+                        .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+                erc20_contract
+                    .approve(
+                        self.call.target_addr,
+                        amount,
+                        key,
+                        chain_info.supports_eip1559,
+                        nonce,
+                    )
+                    .map_err(ExecutableError::from_create_txn_error)
             }
+            _ => {
+                let calldata = self
+                    .call
+                    .get_calldata(amount)
+                    .ok_or(ExecutableError::InvalidContractCall)?;
+                let value = if token_eth_addr.is_none() { amount } else { 0 };
+                eth_utils::common::create_contract_call_raw_txn(
+                    rpc_url,
+                    self.call.target_addr,
+                    calldata,
+                    value,
+                    key,
+                    chain_info.supports_eip1559,
+                    nonce,
+                )
+                .map_err(ExecutableError::from_create_txn_error)
+            }
+        }
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        self.amount.ok_or(ExecutableError::UnexpectedNullAmount)
+    }
+
+    // We can't tell how the target used the tokens, only that the call went through with
+    // exactly amount approved (or sent as value)
+    fn get_completed_step_result(
+        &self,
+        rpc_url: &str,
+        txn_hash: EthTxnHash,
+    ) -> Option<CompletedStepResult> {
+        helpers::get_completed_step_result_for_known_amount(
+            rpc_url,
+            txn_hash,
+            self.amount
+                .expect("Should have checked for erroneously null amount in create_raw_txn"),
+        )
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.token.chain
+    }
+
+    // The approval gets a nonce of its own
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        if self.is_approving() {
+            &self.approval_uuid
         } else {
-            None
+            &self.uuid
         }
     }
 
-    #[cfg(not(feature = "mock-txn-send"))]
-    fn is_eth_transfer_invalid(
-        eth_transfer: &eth_utils::common::EthTransfer,
-        expected_amount: Amount,
-    ) -> bool {
-        eth_transfer.amount != expected_amount
+    fn on_txn_completed(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        completed_step_result: CompletedStepResult,
+    ) -> ExecutableResult<Option<CompletedStepResult>> {
+        if !self.is_approving() {
+            return Ok(Some(completed_step_result));
+        }
+        // The approval's nonce is spent either way. If it failed or dropped, so does the step
+        execute_step_meta.finalize_execstep(&self.approval_uuid, self.token.chain)?;
+        if let EthStepStatus::Confirmed(txn_hash) = completed_step_result.new_status {
+            self.approval_txn_hash = Some(txn_hash);
+            self.status = EthStepStatus::NotStarted;
+            Ok(None)
+        } else {
+            Ok(Some(completed_step_result))
+        }
     }
+}
 
-    #[cfg(feature = "mock-txn-send")]
-    fn is_eth_transfer_invalid(
-        eth_transfer: &eth_utils::common::EthTransfer,
-        expected_amount: Amount,
-    ) -> bool {
-        false
+impl EthExecutableHelper for EthAggregatorSwapStep {
+    fn create_raw_txn(
+        &self,
+        _execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let amount_in = self
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+
+        match helpers::get_erc20_addr(&self.src_token) {
+            // Approve exactly amount_in so the exchange can't pull any other escrow funds
+            Some(token_eth_addr) if self.is_approving() => {
+                let erc20_contract =
+                    eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_eth_addr)
+                        .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+                erc20_contract
+                    .approve(
+                        self.exchange_addr,
+                        amount_in,
+                        key,
+                        chain_info.supports_eip1559,
+                        nonce,
+                    )
+                    .map_err(ExecutableError::from_create_txn_error)
+            }
+            _ => {
+                let swap_txn = helpers::get_checked_aggregator_swap_txn(self, rpc_url)?;
+                eth_utils::common::create_contract_call_raw_txn(
+                    rpc_url,
+                    swap_txn.to,
+                    swap_txn.calldata,
+                    swap_txn.value,
+                    key,
+                    chain_info.supports_eip1559,
+                    nonce,
+                )
+                .map_err(ExecutableError::from_create_txn_error)
+            }
+        }
     }
 
-    pub(super) fn verified_get_completed_step_result_for_erc20_transfer(
+    fn get_paper_amount_out(&self, rpc_url: &str) -> ExecutableResult<Amount> {
+        let amount_in = self
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let aggregator_api = helpers::get_aggregator_api(self);
+        let gas_price = eth_utils::common::get_gas_price(rpc_url)
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        aggregator_api
+            .get_quote(&self.src_token, &self.dest_token, amount_in, gas_price)
+            .map_err(|_| ExecutableError::FailedToGetAggregatorSwap)
+    }
+
+    fn get_completed_step_result(
+        &self,
         rpc_url: &str,
-        erc20_txn_hash: EthTxnHash,
-        expected_token: &EthAddress,
-        expected_amount: Amount,
+        txn_hash: EthTxnHash,
     ) -> Option<CompletedStepResult> {
-        if let Ok(erc20_transfer) =
-            eth_utils::parse_txn_helper::parse_transfer_from_erc20_txn(rpc_url, erc20_txn_hash)
-        {
+        if self.is_approving() {
+            return helpers::get_completed_step_result_for_known_amount(rpc_url, txn_hash, 0);
+        }
+        let erc20_transfer = eth_utils::parse_txn_helper::parse_transfer_from_aggregator_swap_txn(
+            rpc_url,
+            txn_hash,
+            &helpers::get_erc20_addr(&self.dest_token)?,
+            helpers::get_eth_dest_addr(&self.common)?,
+        )
+        .ok()?;
+        if erc20_transfer.is_txn_success {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Confirmed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: erc20_transfer.amount,
+            })
+        } else {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Failed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: 0,
+            })
+        }
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.src_token.chain
+    }
+
+    // The approval gets a nonce of its own
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        if self.is_approving() {
+            &self.approval_uuid
+        } else {
+            &self.uuid
+        }
+    }
+
+    fn on_txn_completed(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        completed_step_result: CompletedStepResult,
+    ) -> ExecutableResult<Option<CompletedStepResult>> {
+        if !self.is_approving() {
+            return Ok(Some(completed_step_result));
+        }
+        // The approval's nonce is spent either way. If it failed or dropped, so does the step
+        execute_step_meta.finalize_execstep(&self.approval_uuid, self.src_token.chain)?;
+        if let EthStepStatus::Confirmed(txn_hash) = completed_step_result.new_status {
+            self.approval_txn_hash = Some(txn_hash);
+            self.status = EthStepStatus::NotStarted;
+            Ok(None)
+        } else {
+            Ok(Some(completed_step_result))
+        }
+    }
+}
+
+impl EthExecutableHelper for EthZapInStep {
+    fn create_raw_txn(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let token_addr =
+            helpers::get_erc20_addr(&self.token).ok_or(ExecutableError::InvalidZapIn)?;
+        let pair_token_addr =
+            helpers::get_erc20_addr(&self.pair_token).ok_or(ExecutableError::InvalidZapIn)?;
+        let escrow_addr = helpers::get_eth_src_addr(&self.common)
+            .ok_or(ExecutableError::UnexpectedNonEthAddress)?;
+        // In the chain's clock, since that is what the router checks it against
+        let deadline_secs = execute_step_meta
+            .get_chain_timestamp(&chain_info.chain_id)
+            .saturating_add(execute_step_meta.get_executor_config().dex_swap_life_millis)
+            / 1000;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+        let dex_router_contract =
+            eth_utils::dex_router_contract::DEXRouterContract::new(rpc_url, self.dex_router_addr)
+                .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+
+        if self.is_swapping() {
+            // The swap pays out into the escrow, which then adds it as liquidity. Like
+            // EthDexSwapStep, there is no limit price
+            let amount_in = self
+                .get_swap_amount_in()
+                .ok_or(ExecutableError::UnexpectedNullAmount)?;
+            return dex_router_contract
+                .swap_exact_tokens_for_tokens(
+                    amount_in,
+                    0,
+                    vec![token_addr, pair_token_addr],
+                    *escrow_addr,
+                    deadline_secs,
+                    key,
+                    chain_info.supports_eip1559,
+                    nonce,
+                )
+                .map_err(ExecutableError::from_create_txn_error);
+        }
+
+        let amount = self
+            .get_liquidity_amount()
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let pair_token_amount = self
+            .pair_token_amount
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let to_addr = helpers::get_eth_dest_addr(&self.common)
+            .ok_or(ExecutableError::UnexpectedNonEthAddress)?;
+        let (reserve, pair_token_reserve) = helpers::get_zap_in_reserves(self, rpc_url)?;
+        let (amount_min, pair_token_amount_min) = zap_in::get_add_liquidity_min_amounts(
+            amount,
+            pair_token_amount,
+            reserve,
+            pair_token_reserve,
+        );
+        dex_router_contract
+            .add_liquidity(
+                token_addr,
+                pair_token_addr,
+                amount,
+                pair_token_amount,
+                amount_min,
+                pair_token_amount_min,
+                *to_addr,
+                deadline_secs,
+                key,
+                chain_info.supports_eip1559,
+                nonce,
+            )
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    fn get_paper_amount_out(&self, rpc_url: &str) -> ExecutableResult<Amount> {
+        let token_addr =
+            helpers::get_erc20_addr(&self.token).ok_or(ExecutableError::InvalidZapIn)?;
+        let pair_token_addr =
+            helpers::get_erc20_addr(&self.pair_token).ok_or(ExecutableError::InvalidZapIn)?;
+        let dex_router_contract =
+            eth_utils::dex_router_contract::DEXRouterContract::new(rpc_url, self.dex_router_addr)
+                .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+        if self.is_swapping() {
+            let amount_in = self
+                .get_swap_amount_in()
+                .ok_or(ExecutableError::UnexpectedNullAmount)?;
+            let amounts_out = dex_router_contract
+                .get_amounts_out(amount_in, vec![token_addr, pair_token_addr])
+                .map_err(|_| ExecutableError::RpcRequestFailed)?;
+            return amounts_out
+                .last()
+                .copied()
+                .ok_or(ExecutableError::RpcRequestFailed);
+        }
+        // Nothing was swapped in paper trading mode, so the reserves have not moved yet
+        let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let pair_token_amount = self
+            .pair_token_amount
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let (reserve, pair_token_reserve) = helpers::get_zap_in_reserves(self, rpc_url)?;
+        let total_supply = helpers::get_dex_pair_contract(self, rpc_url)?
+            .total_supply()
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        zap_in::estimate_zap_in_liquidity(
+            amount,
+            pair_token_amount,
+            reserve,
+            pair_token_reserve,
+            total_supply,
+        )
+        .ok_or(ExecutableError::InvalidZapIn)
+    }
+
+    // The swap's amount out is the pair token it paid the escrow, and addLiquidity's is the LP
+    // tokens it minted to the user
+    fn get_completed_step_result(
+        &self,
+        rpc_url: &str,
+        txn_hash: EthTxnHash,
+    ) -> Option<CompletedStepResult> {
+        let erc20_transfer = if self.is_swapping() {
+            eth_utils::parse_txn_helper::parse_transfer_from_dex_swap_txn(rpc_url, txn_hash)
+        } else {
+            eth_utils::parse_txn_helper::parse_transfer_from_add_liquidity_txn(
+                rpc_url,
+                txn_hash,
+                &helpers::get_erc20_addr(&self.lp_token)?,
+                helpers::get_eth_dest_addr(&self.common)?,
+            )
+        }
+        .ok()?;
+        if erc20_transfer.is_txn_success {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Confirmed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: erc20_transfer.amount,
+            })
+        } else {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Failed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: 0,
+            })
+        }
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.token.chain
+    }
+
+    // The swap gets a nonce of its own
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        if self.is_swapping() {
+            &self.swap_uuid
+        } else {
+            &self.uuid
+        }
+    }
+
+    fn on_txn_completed(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        completed_step_result: CompletedStepResult,
+    ) -> ExecutableResult<Option<CompletedStepResult>> {
+        if !self.is_swapping() {
+            return Ok(Some(completed_step_result));
+        }
+        // The swap's nonce is spent either way. If it failed or dropped, so does the step
+        execute_step_meta.finalize_execstep(&self.swap_uuid, self.token.chain)?;
+        if let EthStepStatus::Confirmed(txn_hash) = completed_step_result.new_status {
+            self.swap_txn_hash = Some(txn_hash);
+            self.pair_token_amount = Some(completed_step_result.amount_out);
+            self.status = EthStepStatus::NotStarted;
+            Ok(None)
+        } else {
+            Ok(Some(completed_step_result))
+        }
+    }
+}
+
+impl EthExecutableHelper for EthRemoveLiquidityStep {
+    fn create_raw_txn(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+
+        if self.is_approving() {
+            // Approve exactly amount so the router can't pull any other escrow funds
+            let lp_token_addr = helpers::get_erc20_addr(&self.lp_token)
+                .ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+            let erc20_contract =
+                eth_utils::erc20_contract::ERC20Contract::new(rpc_url, lp_token_addr)
+                    .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+            return erc20_contract
+                .approve(
+                    self.dex_router_addr,
+                    amount,
+                    key,
+                    chain_info.supports_eip1559,
+                    nonce,
+                )
+                .map_err(ExecutableError::from_create_txn_error);
+        }
+
+        let token_a_addr = helpers::get_erc20_addr(&self.token_a)
+            .ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+        let token_b_addr = helpers::get_erc20_addr(&self.token_b)
+            .ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+        let to_addr = helpers::get_eth_dest_addr(&self.common)
+            .ok_or(ExecutableError::UnexpectedNonEthAddress)?;
+        // In the chain's clock, since that is what the router checks it against
+        let deadline_secs = execute_step_meta
+            .get_chain_timestamp(&chain_info.chain_id)
+            .saturating_add(execute_step_meta.get_executor_config().dex_swap_life_millis)
+            / 1000;
+        let (reserve_a, reserve_b, total_supply) =
+            helpers::get_remove_liquidity_reserves(self, rpc_url)?;
+        let (amount_a_min, amount_b_min) =
+            zap_out::get_remove_liquidity_min_amounts(amount, reserve_a, reserve_b, total_supply)
+                .ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+        eth_utils::dex_router_contract::DEXRouterContract::new(rpc_url, self.dex_router_addr)
+            .map_err(|_| ExecutableError::FailedToLoadWethContract)?
+            .remove_liquidity(
+                token_a_addr,
+                token_b_addr,
+                amount,
+                amount_a_min,
+                amount_b_min,
+                *to_addr,
+                deadline_secs,
+                key,
+                chain_info.supports_eip1559,
+                nonce,
+            )
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    // token_a's share of the reserves. Nothing was burned in paper trading mode, so the
+    // reserves have not moved yet
+    fn get_paper_amount_out(&self, rpc_url: &str) -> ExecutableResult<Amount> {
+        if self.is_approving() {
+            return Ok(0);
+        }
+        Ok(helpers::get_paper_remove_liquidity_amounts(self, rpc_url)?.0)
+    }
+
+    // removeLiquidity's amount out is the token_a it paid the escrow. on_txn_completed looks up
+    // token_b's
+    fn get_completed_step_result(
+        &self,
+        rpc_url: &str,
+        txn_hash: EthTxnHash,
+    ) -> Option<CompletedStepResult> {
+        if self.is_approving() {
+            return helpers::get_completed_step_result_for_known_amount(rpc_url, txn_hash, 0);
+        }
+        let erc20_transfer = eth_utils::parse_txn_helper::parse_transfer_from_remove_liquidity_txn(
+            rpc_url,
+            txn_hash,
+            &helpers::get_erc20_addr(&self.token_a)?,
+            helpers::get_eth_dest_addr(&self.common)?,
+        )
+        .ok()?;
+        if erc20_transfer.is_txn_success {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Confirmed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: erc20_transfer.amount,
+            })
+        } else {
+            Some(CompletedStepResult {
+                new_status: EthStepStatus::Failed(txn_hash),
+                actual_gas_fee_native: erc20_transfer.gas_fee_native,
+                amount_out: 0,
+            })
+        }
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.lp_token.chain
+    }
+
+    // The approval gets a nonce of its own
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        if self.is_approving() {
+            &self.approval_uuid
+        } else {
+            &self.uuid
+        }
+    }
+
+    fn on_txn_completed(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        completed_step_result: CompletedStepResult,
+    ) -> ExecutableResult<Option<CompletedStepResult>> {
+        if !self.is_approving() {
+            if let EthStepStatus::Confirmed(txn_hash) = completed_step_result.new_status {
+                let chain_info = get_chain_info_from_chain_id(&self.lp_token.chain)
+                    .ok_or(ExecutableError::FailedToFindChainInfo)?;
+                let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+                // An error leaves the step Submitted, so this is retried on the next call
+                let token_b_amount_out = if execute_step_meta.is_paper_trading() {
+                    helpers::get_paper_remove_liquidity_amounts(self, &rpc_url)?.1
+                } else {
+                    let token_b_addr = helpers::get_erc20_addr(&self.token_b)
+                        .ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+                    let to_addr = helpers::get_eth_dest_addr(&self.common)
+                        .ok_or(ExecutableError::UnexpectedNonEthAddress)?;
+                    eth_utils::parse_txn_helper::parse_transfer_from_remove_liquidity_txn(
+                        &rpc_url,
+                        txn_hash,
+                        &token_b_addr,
+                        to_addr,
+                    )
+                    .map_err(|_| ExecutableError::RpcRequestFailed)?
+                    .amount
+                };
+                self.token_a_amount_out = Some(completed_step_result.amount_out);
+                self.token_b_amount_out = Some(token_b_amount_out);
+            }
+            return Ok(Some(completed_step_result));
+        }
+        // The approval's nonce is spent either way. If it failed or dropped, so does the step
+        execute_step_meta.finalize_execstep(&self.approval_uuid, self.lp_token.chain)?;
+        if let EthStepStatus::Confirmed(txn_hash) = completed_step_result.new_status {
+            self.approval_txn_hash = Some(txn_hash);
+            self.status = EthStepStatus::NotStarted;
+            Ok(None)
+        } else {
+            Ok(Some(completed_step_result))
+        }
+    }
+}
+
+impl EthExecutableHelper for EthFeeSkimStep {
+    fn create_raw_txn(
+        &self,
+        _execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let to_addr = {
+            if let UniversalAddress::Ethereum(eth_addr) = self.common.dest_addr.clone() {
+                Ok(eth_addr)
+            } else {
+                Err(ExecutableError::UnexpectedNonEthAddress)
+            }
+        }?;
+        let markup_amount = self
+            .get_markup_amount()
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+
+        match helpers::get_erc20_addr(&self.token) {
+            None => {
+                let evm_chain_id = chain_info
+                    .evm_chain_id
+                    .ok_or(ExecutableError::UnexpectedNullEvmChainId)?;
+                eth_utils::common::create_send_eth_raw_txn(
+                    rpc_url,
+                    to_addr,
+                    markup_amount,
+                    key,
+                    evm_chain_id,
+                    chain_info.supports_eip1559,
+                    nonce,
+                )
+                .map_err(ExecutableError::from_create_txn_error)
+            }
+            Some(token_eth_addr) => {
+                let erc20_contract =
+                    eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_eth_addr)
+                        .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+                erc20_contract
+                    .transfer(
+                        to_addr,
+                        markup_amount,
+                        key,
+                        chain_info.supports_eip1559,
+                        nonce,
+                    )
+                    .map_err(ExecutableError::from_create_txn_error)
+            }
+        }
+    }
+
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        let amount = self.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        Ok(amount - self.get_markup_amount().unwrap_or(0))
+    }
+
+    // The txn only moves the markup. What is left in the escrow is the path's amount out
+    fn get_completed_step_result(
+        &self,
+        rpc_url: &str,
+        txn_hash: EthTxnHash,
+    ) -> Option<CompletedStepResult> {
+        let amount = self
+            .amount
+            .expect("Should have checked for erroneously null amount in create_raw_txn");
+        let markup_amount = self.get_markup_amount().unwrap_or(0);
+        let res = match helpers::get_erc20_addr(&self.token) {
+            None => helpers::verified_get_completed_step_result_for_eth_transfer(
+                rpc_url,
+                txn_hash,
+                markup_amount,
+            ),
+            Some(token_eth_addr) => helpers::verified_get_completed_step_result_for_erc20_transfer(
+                rpc_url,
+                txn_hash,
+                &token_eth_addr,
+                helpers::get_eth_dest_addr(&self.common)?,
+                markup_amount,
+            ),
+        }?;
+        Some(match res.new_status {
+            EthStepStatus::Confirmed(_) => CompletedStepResult {
+                amount_out: amount - markup_amount,
+                ..res
+            },
+            _ => res,
+        })
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.token.chain
+    }
+
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+}
+
+impl EthExecutableHelper for EthApproveStep {
+    fn create_raw_txn(
+        &self,
+        _execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        nonce: Nonce,
+    ) -> ExecutableResult<SignedTransaction> {
+        let token_eth_addr =
+            helpers::get_erc20_addr(&self.token).ok_or(ExecutableError::UnexpectedNonEthAddress)?;
+        let key = keys
+            .get_key(self.src_addr())
+            .ok_or(ExecutableError::SecretNotFound)?;
+        let erc20_contract = eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_eth_addr)
+            .map_err(|_| ExecutableError::FailedToLoadWethContract)?;
+        erc20_contract
+            .approve(
+                self.spender,
+                self.amount,
+                key,
+                chain_info.supports_eip1559,
+                nonce,
+            )
+            .map_err(ExecutableError::from_create_txn_error)
+    }
+
+    // Nothing moves
+    fn get_paper_amount_out(&self, _rpc_url: &str) -> ExecutableResult<Amount> {
+        Ok(0)
+    }
+
+    fn get_completed_step_result(
+        &self,
+        rpc_url: &str,
+        txn_hash: EthTxnHash,
+    ) -> Option<CompletedStepResult> {
+        helpers::get_completed_step_result_for_known_amount(rpc_url, txn_hash, 0)
+    }
+
+    fn src_addr(&self) -> &UniversalAddress {
+        &self.common.src_addr
+    }
+
+    fn get_chain(&self) -> UniversalChainId {
+        self.token.chain
+    }
+
+    fn get_exec_step_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+}
+
+pub fn get_confirmed_transfer_txn_hash(exec_step: &ExecutionStep) -> Option<EthTxnHash> {
+    match &exec_step.inner {
+        ExecutionStepEnum::EthSend(EthSendStep {
+            status: EthStepStatus::Confirmed(txn_hash),
+            ..
+        })
+        | ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+            status: EthStepStatus::Confirmed(txn_hash),
+            ..
+        }) => Some(*txn_hash),
+        _ => None,
+    }
+}
+
+/// Looks up a Confirmed EthSend/ERC20Transfer's txn again and re-runs the checks it confirmed
+/// with. The prestart deposit goes through this once it is deep enough, since it may have first
+/// confirmed in a block that was later reorged out. The step is marked Failed if it no longer
+/// checks out. Ok(None) if the txn could not be looked up
+pub fn reverify_confirmed_transfer(
+    exec_step: &mut ExecutionStep,
+    rpc_url: &str,
+) -> ExecutableResult<Option<ExecutableSimpleStatus>> {
+    let txn_hash =
+        get_confirmed_transfer_txn_hash(exec_step).ok_or(ExecutableError::UnexpectedStepStatus)?;
+    let opt_new_status = match &exec_step.inner {
+        ExecutionStepEnum::EthSend(step) => step.get_completed_step_result(rpc_url, txn_hash),
+        ExecutionStepEnum::ERC20Transfer(step) => step.get_completed_step_result(rpc_url, txn_hash),
+        _ => None,
+    }
+    .map(|completed_step_result| completed_step_result.new_status);
+    Ok(opt_new_status.map(|new_status| {
+        match &mut exec_step.inner {
+            ExecutionStepEnum::EthSend(step) => step.status = new_status,
+            ExecutionStepEnum::ERC20Transfer(step) => step.status = new_status,
+            _ => {}
+        }
+        exec_step.get_status()
+    }))
+}
+
+/// Looks for a prestart deposit made in the other form of the quoted token i.e. the wrapped
+/// token when the step expects the native one, or vice versa. A WETH transfer fails an EthSend
+/// step (it carries no value), and a native send never parses as an ERC20 transfer. Returns the
+/// txn hash if the txn successfully moved the expected amount of the other form to the escrow
+pub fn find_other_form_deposit(
+    exec_step: &ExecutionStep,
+    weth_addr: &EthAddress,
+    rpc_url: &str,
+) -> Option<EthTxnHash> {
+    match &exec_step.inner {
+        ExecutionStepEnum::EthSend(EthSendStep {
+            amount: Some(amount),
+            common,
+            status: EthStepStatus::Failed(txn_hash),
+            ..
+        }) => {
+            let erc20_transfer = eth_utils::parse_txn_helper::parse_transfer_from_erc20_txn(
+                rpc_url,
+                *txn_hash,
+                weth_addr,
+                helpers::get_eth_dest_addr(common)?,
+            )
+            .ok()?;
+            if erc20_transfer.is_txn_success && erc20_transfer.amount == *amount {
+                Some(*txn_hash)
+            } else {
+                None
+            }
+        }
+        ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+            token,
+            amount: Some(amount),
+            common,
+            status: EthStepStatus::Submitted(EthPendingTxnId { txn_hash, .. }),
+            ..
+        }) if token.id == ChainTokenId::ERC20(ERC20Token { addr: *weth_addr }) => {
+            let eth_transfer =
+                eth_utils::parse_txn_helper::parse_transfer_from_eth_send_txn(rpc_url, *txn_hash)
+                    .ok()?;
+            if eth_transfer.is_txn_success
+                && eth_transfer.amount == *amount
+                && common.dest_addr == UniversalAddress::Ethereum(eth_transfer.to)
+            {
+                Some(*txn_hash)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Looks for a failed prestart deposit that did move the quoted token to the escrow, just not the
+/// quoted amount of it. Returns the amount it moved
+pub fn find_mismatched_deposit(exec_step: &ExecutionStep, rpc_url: &str) -> Option<Amount> {
+    match &exec_step.inner {
+        ExecutionStepEnum::EthSend(EthSendStep {
+            amount: Some(amount),
+            common,
+            status: EthStepStatus::Failed(txn_hash),
+            ..
+        }) => {
+            let eth_transfer =
+                eth_utils::parse_txn_helper::parse_transfer_from_eth_send_txn(rpc_url, *txn_hash)
+                    .ok()?;
+            if eth_transfer.is_txn_success
+                && eth_transfer.amount > 0
+                && eth_transfer.amount != *amount
+                && common.dest_addr == UniversalAddress::Ethereum(eth_transfer.to)
+            {
+                Some(eth_transfer.amount)
+            } else {
+                None
+            }
+        }
+        ExecutionStepEnum::ERC20Transfer(ERC20TransferStep {
+            token,
+            amount: Some(amount),
+            common,
+            status: EthStepStatus::Failed(txn_hash),
+            ..
+        }) => {
+            let erc20_transfer = eth_utils::parse_txn_helper::parse_transfer_from_erc20_txn(
+                rpc_url,
+                *txn_hash,
+                &helpers::get_erc20_addr(token)?,
+                helpers::get_eth_dest_addr(common)?,
+            )
+            .ok()?;
+            if erc20_transfer.is_txn_success
+                && erc20_transfer.amount > 0
+                && erc20_transfer.amount != *amount
+            {
+                Some(erc20_transfer.amount)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+mod helpers {
+    use super::*;
+
+    pub(super) fn get_swap_path(
+        token_path: &[UniversalTokenId],
+    ) -> ExecutableResult<Vec<EthAddress>> {
+        token_path
+            .iter()
+            .map(|universal_token_id| match &universal_token_id.id {
+                ChainTokenId::Native => Err(ExecutableError::UnexpectedNonEthAddress),
+                ChainTokenId::ERC20(erc20_token) => Ok(erc20_token.addr),
+                ChainTokenId::XC20(xc20_token) => Ok(xc20_token.get_eth_address()),
+            })
+            .collect()
+    }
+
+    // None for the native token
+    pub(super) fn get_erc20_addr(token: &UniversalTokenId) -> Option<EthAddress> {
+        match &token.id {
+            ChainTokenId::Native => None,
+            ChainTokenId::ERC20(erc20_token) => Some(erc20_token.addr),
+            ChainTokenId::XC20(xc20_token) => Some(xc20_token.get_eth_address()),
+        }
+    }
+
+    pub(super) fn get_aggregator_api(
+        step: &EthAggregatorSwapStep,
+    ) -> eth_utils::aggregator_api::AggregatorApi {
+        eth_utils::aggregator_api::AggregatorApi::new(get_aggregator_from_aggregator_id(
+            &step.aggregator_id,
+        ))
+    }
+
+    // The API builds the swap calldata, so we don't take it on trust: the txn must go to the
+    // exchange the plan was made with (the only spender we approved), send value only for a
+    // native src token, and its calldata must swap exactly amount_in of the step's tokens, pay
+    // out to the escrow, and revert if it pays out less than the plan's slippage allows
+    pub(super) fn get_checked_aggregator_swap_txn(
+        step: &EthAggregatorSwapStep,
+        rpc_url: &str,
+    ) -> ExecutableResult<eth_utils::aggregator_api::AggregatorSwapTxn> {
+        let amount_in = step
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let amount_out_min = step
+            .get_amount_out_min()
+            .ok_or(ExecutableError::InvalidAggregatorSwap)?;
+        // The payout is only parsed from the dest token's Transfer logs
+        if get_erc20_addr(&step.dest_token).is_none() {
+            return Err(ExecutableError::InvalidAggregatorSwap);
+        }
+        let escrow_addr = if let UniversalAddress::Ethereum(eth_addr) = &step.common.dest_addr {
+            Ok(*eth_addr)
+        } else {
+            Err(ExecutableError::UnexpectedNonEthAddress)
+        }?;
+        let gas_price = eth_utils::common::get_gas_price(rpc_url)
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let aggregator_api = get_aggregator_api(step);
+        let swap_txn = aggregator_api
+            .get_swap_txn(
+                &step.src_token,
+                &step.dest_token,
+                amount_in,
+                gas_price,
+                AGGREGATOR_SWAP_SLIPPAGE_BPS,
+                escrow_addr,
+            )
+            .map_err(|_| ExecutableError::FailedToGetAggregatorSwap)?;
+        let expected_value = if step.needs_approval() { 0 } else { amount_in };
+        let terms = &swap_txn.terms;
+        if swap_txn.to != step.exchange_addr
+            || swap_txn.value != expected_value
+            || terms.src_token != aggregator_api.get_token_addr(&step.src_token)
+            || terms.dest_token != aggregator_api.get_token_addr(&step.dest_token)
+            || terms.dest_receiver != escrow_addr
+            || terms.amount_in != amount_in
+            || terms.min_amount_out < amount_out_min
+        {
+            return Err(ExecutableError::InvalidAggregatorSwap);
+        }
+        Ok(swap_txn)
+    }
+
+    pub(super) fn get_eth_dest_addr(common: &CommonExecutionMeta) -> Option<&EthAddress> {
+        match &common.dest_addr {
+            UniversalAddress::Ethereum(eth_addr) => Some(eth_addr),
+            _ => None,
+        }
+    }
+
+    pub(super) fn get_eth_src_addr(common: &CommonExecutionMeta) -> Option<&EthAddress> {
+        match &common.src_addr {
+            UniversalAddress::Ethereum(eth_addr) => Some(eth_addr),
+            _ => None,
+        }
+    }
+
+    pub(super) fn get_dex_pair_contract(
+        step: &EthZapInStep,
+        rpc_url: &str,
+    ) -> ExecutableResult<eth_utils::dex_pair_contract::DEXPairContract> {
+        let lp_token_addr = get_erc20_addr(&step.lp_token).ok_or(ExecutableError::InvalidZapIn)?;
+        eth_utils::dex_pair_contract::DEXPairContract::new(rpc_url, lp_token_addr)
+            .map_err(|_| ExecutableError::InvalidZapIn)
+    }
+
+    // (reserve of token, reserve of pair_token)
+    pub(super) fn get_zap_in_reserves(
+        step: &EthZapInStep,
+        rpc_url: &str,
+    ) -> ExecutableResult<(Amount, Amount)> {
+        let token_addr = get_erc20_addr(&step.token).ok_or(ExecutableError::InvalidZapIn)?;
+        get_dex_pair_contract(step, rpc_url)?
+            .get_reserves_for(token_addr)
+            .map_err(|_| ExecutableError::RpcRequestFailed)
+    }
+
+    // (reserve of token_a, reserve of token_b, LP token total supply)
+    pub(super) fn get_remove_liquidity_reserves(
+        step: &EthRemoveLiquidityStep,
+        rpc_url: &str,
+    ) -> ExecutableResult<(Amount, Amount, Amount)> {
+        let lp_token_addr =
+            get_erc20_addr(&step.lp_token).ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+        let token_a_addr =
+            get_erc20_addr(&step.token_a).ok_or(ExecutableError::InvalidRemoveLiquidity)?;
+        let pair = eth_utils::dex_pair_contract::DEXPairContract::new(rpc_url, lp_token_addr)
+            .map_err(|_| ExecutableError::InvalidRemoveLiquidity)?;
+        let (reserve_a, reserve_b) = pair
+            .get_reserves_for(token_a_addr)
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let total_supply = pair
+            .total_supply()
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        Ok((reserve_a, reserve_b, total_supply))
+    }
+
+    // What burning the step's LP tokens pays out of each token at the current reserves
+    pub(super) fn get_paper_remove_liquidity_amounts(
+        step: &EthRemoveLiquidityStep,
+        rpc_url: &str,
+    ) -> ExecutableResult<(Amount, Amount)> {
+        let amount = step.amount.ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let (reserve_a, reserve_b, total_supply) = get_remove_liquidity_reserves(step, rpc_url)?;
+        zap_out::get_remove_liquidity_amounts(amount, reserve_a, reserve_b, total_supply)
+            .ok_or(ExecutableError::InvalidRemoveLiquidity)
+    }
+
+    // For ETH send, ERC20 transfer, we know that amount_out SHOULD be the same as amount_in but
+    // we check anyway. This is important! For the prestart step, a user could otherwise cheat the
+    // system by passing in a different value of amount_in (or different token ID) and sending a txn
+    // of lower value (or of different token)
+    pub(super) fn verified_get_completed_step_result_for_eth_transfer(
+        rpc_url: &str,
+        eth_send_txn: EthTxnHash,
+        expected_amount: Amount,
+    ) -> Option<CompletedStepResult> {
+        if let Ok(eth_transfer) =
+            eth_utils::parse_txn_helper::parse_transfer_from_eth_send_txn(rpc_url, eth_send_txn)
+        {
+            if is_eth_transfer_invalid(&eth_transfer, expected_amount) {
+                ink::env::debug_println!("Unexpected! Amount received from Eth transfer ({}) does not match expected amount ({})",
+                    eth_transfer.amount, expected_amount);
+                // Treat this like a fail
+                Some(CompletedStepResult {
+                    new_status: EthStepStatus::Failed(eth_send_txn),
+                    actual_gas_fee_native: eth_transfer.gas_fee_native,
+                    amount_out: 0,
+                })
+            } else {
+                if eth_transfer.is_txn_success {
+                    Some(CompletedStepResult {
+                        new_status: EthStepStatus::Confirmed(eth_send_txn),
+                        actual_gas_fee_native: eth_transfer.gas_fee_native,
+                        amount_out: expected_amount,
+                    })
+                } else {
+                    Some(CompletedStepResult {
+                        new_status: EthStepStatus::Failed(eth_send_txn),
+                        actual_gas_fee_native: eth_transfer.gas_fee_native,
+                        amount_out: 0,
+                    })
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(feature = "mock-txn-send"))]
+    fn is_eth_transfer_invalid(
+        eth_transfer: &eth_utils::common::EthTransfer,
+        expected_amount: Amount,
+    ) -> bool {
+        eth_transfer.amount != expected_amount
+    }
+
+    #[cfg(feature = "mock-txn-send")]
+    fn is_eth_transfer_invalid(
+        eth_transfer: &eth_utils::common::EthTransfer,
+        expected_amount: Amount,
+    ) -> bool {
+        false
+    }
+
+    pub(super) fn verified_get_completed_step_result_for_erc20_transfer(
+        rpc_url: &str,
+        erc20_txn_hash: EthTxnHash,
+        expected_token: &EthAddress,
+        expected_recipient: &EthAddress,
+        expected_amount: Amount,
+    ) -> Option<CompletedStepResult> {
+        // Only transfers to the recipient count, however the txn got the tokens there
+        if let Ok(erc20_transfer) = eth_utils::parse_txn_helper::parse_transfer_from_erc20_txn(
+            rpc_url,
+            erc20_txn_hash,
+            expected_token,
+            expected_recipient,
+        ) {
             if is_erc20_transfer_invalid(&erc20_transfer, expected_token, expected_amount) {
-                ink_env::debug_println!("Unexpected! Amount/token received from Eth transfer ({} {:?}) does not match expected amount ({} {:?})",
+                ink::env::debug_println!("Unexpected! Amount/token received from Eth transfer ({} {:?}) does not match expected amount ({} {:?})",
                     erc20_transfer.amount, erc20_transfer.token, expected_amount, expected_token);
                 // Treat this like a fail
                 Some(CompletedStepResult {
@@ -0,0 +1,242 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::ToString;
+
+use privadex_chain_metadata::{
+    common::{Amount, BlockNum},
+    get_chain_info_from_chain_id,
+};
+use privadex_execution_plan::execution_plan::{
+    ExecutionStep, ExecutionStepEnum, RemarkDepositStatus, SubstrateFinalizedExtrinsicId,
+    SubstrateRemarkDepositStep,
+};
+
+use crate::{
+    executable::{
+        execute_step_meta::ExecuteStepMeta,
+        remark_deposit::get_remark_hash,
+        traits::{
+            Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus,
+            StepForwardResult,
+        },
+    },
+    key_container::KeyContainer,
+    substrate_utils::indexer_utils::subsquid_utils::{
+        SubstrateRemarkDepositResult, SubstrateSubsquidUtils,
+    },
+};
+
+impl Executable for SubstrateRemarkDepositStep {
+    fn get_status(&self) -> ExecutableSimpleStatus {
+        (&self.status).into()
+    }
+
+    fn get_total_fee_usd(&self) -> Option<Amount> {
+        if self.get_status() == ExecutableSimpleStatus::Succeeded {
+            Some(self.common.gas_fee_usd)
+        } else {
+            None
+        }
+    }
+
+    // We never send anything here. The user's batch lands on its own and we just look for it
+    fn execute_step_forward(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        _keys: &KeyContainer,
+    ) -> ExecutableResult<StepForwardResult> {
+        let pending_remark_id = match &self.status {
+            RemarkDepositStatus::NotStarted => return Err(ExecutableError::PrestartStepNotStarted),
+            RemarkDepositStatus::Dropped
+            | RemarkDepositStatus::Failed(_)
+            | RemarkDepositStatus::Confirmed(_) => {
+                return Err(ExecutableError::CalledStepForwardOnFinishedStep)
+            }
+            RemarkDepositStatus::Pending(pending_remark_id) => pending_remark_id.clone(),
+        };
+        let cur_block = execute_step_meta.get_cur_block(&self.chain)?;
+        let max_block = cur_block.min(pending_remark_id.end_block_num);
+        let (new_status, amount_out) = match self.lookup_deposit(
+            execute_step_meta,
+            pending_remark_id.start_block_num,
+            max_block,
+        ) {
+            Ok(deposit) => self.get_completed_status(&deposit),
+            Err(_) if cur_block > pending_remark_id.end_block_num => {
+                (RemarkDepositStatus::Dropped, 0)
+            }
+            Err(_) => {
+                return Ok(StepForwardResult {
+                    did_status_change: false,
+                    amount_out: None,
+                })
+            }
+        };
+        self.status = new_status;
+        Ok(StepForwardResult {
+            did_status_change: true,
+            amount_out: Some(amount_out),
+        })
+    }
+}
+
+trait RemarkDepositExecutableHelper {
+    fn lookup_deposit(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        min_block: BlockNum,
+        max_block: BlockNum,
+    ) -> ExecutableResult<SubstrateRemarkDepositResult>;
+
+    fn get_completed_status(
+        &self,
+        deposit: &SubstrateRemarkDepositResult,
+    ) -> (RemarkDepositStatus, Amount);
+}
+
+impl RemarkDepositExecutableHelper for SubstrateRemarkDepositStep {
+    fn lookup_deposit(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        min_block: BlockNum,
+        max_block: BlockNum,
+    ) -> ExecutableResult<SubstrateRemarkDepositResult> {
+        let chain_info = get_chain_info_from_chain_id(&self.chain)
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let subsquid_utils = SubstrateSubsquidUtils {
+            subsquid_graphql_archive_url: chain_info
+                .get_subsquid_graphql_archive_url()
+                .ok_or(ExecutableError::UnsupportedChain)?
+                .to_string(),
+        };
+        subsquid_utils
+            .lookup_remark_deposit(
+                min_block,
+                max_block,
+                &get_remark_hash(&self.remark),
+                &self.common.dest_addr,
+                self.amount,
+            )
+            .map_err(|_| ExecutableError::SubstrateIndexerLookupFailed)
+    }
+
+    // Like an Eth prestart, the user could send less than the amount_in they asked a quote for.
+    // We treat that as a fail
+    fn get_completed_status(
+        &self,
+        deposit: &SubstrateRemarkDepositResult,
+    ) -> (RemarkDepositStatus, Amount) {
+        let extrinsic_id = SubstrateFinalizedExtrinsicId {
+            block_num: deposit.block_num,
+            extrinsic_index: deposit.extrinsic_index,
+        };
+        match self.amount {
+            Some(amount) if amount == deposit.amount_in => {
+                (RemarkDepositStatus::Confirmed(extrinsic_id), amount)
+            }
+            _ => {
+                ink::env::debug_println!(
+                    "Unexpected! Amount received from remark deposit ({}) does not match expected amount ({:?})",
+                    deposit.amount_in,
+                    self.amount
+                );
+                (RemarkDepositStatus::Failed(extrinsic_id), 0)
+            }
+        }
+    }
+}
+
+pub fn get_confirmed_remark_deposit_id(
+    exec_step: &ExecutionStep,
+) -> Option<SubstrateFinalizedExtrinsicId> {
+    match &exec_step.inner {
+        ExecutionStepEnum::SubstrateRemarkDeposit(SubstrateRemarkDepositStep {
+            status: RemarkDepositStatus::Confirmed(extrinsic_id),
+            ..
+        }) => Some(extrinsic_id.clone()),
+        _ => None,
+    }
+}
+
+/// The remark deposit counterpart of find_mismatched_deposit: the tagged batch credited the
+/// escrow, just not with the quoted amount. Returns the amount it credited
+pub fn find_mismatched_remark_deposit(
+    exec_step: &ExecutionStep,
+    execute_step_meta: &ExecuteStepMeta,
+) -> Option<Amount> {
+    let (step, extrinsic_id) = match &exec_step.inner {
+        ExecutionStepEnum::SubstrateRemarkDeposit(
+            step @ SubstrateRemarkDepositStep {
+                status: RemarkDepositStatus::Failed(extrinsic_id),
+                ..
+            },
+        ) => (step, extrinsic_id),
+        _ => return None,
+    };
+    let deposit = step
+        .lookup_deposit(
+            execute_step_meta,
+            extrinsic_id.block_num,
+            extrinsic_id.block_num,
+        )
+        .ok()?;
+    if deposit.extrinsic_index == extrinsic_id.extrinsic_index
+        && deposit.amount_in > 0
+        && Some(deposit.amount_in) != step.amount
+    {
+        Some(deposit.amount_in)
+    } else {
+        None
+    }
+}
+
+/// The remark deposit counterpart of reverify_confirmed_transfer. The indexer can only tell us
+/// which extrinsic remarked, so a deposit that was reorged into a different block just moves
+/// there (and has to get deep enough again). Ok(None) if it is not settled yet
+pub fn reverify_confirmed_remark_deposit(
+    exec_step: &mut ExecutionStep,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<Option<ExecutableSimpleStatus>> {
+    let extrinsic_id =
+        get_confirmed_remark_deposit_id(exec_step).ok_or(ExecutableError::UnexpectedStepStatus)?;
+    let step = match &mut exec_step.inner {
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => step,
+        _ => return Err(ExecutableError::UnexpectedStepStatus),
+    };
+    let cur_block = execute_step_meta.get_cur_block(&step.chain)?;
+    let deposit = match step.lookup_deposit(
+        execute_step_meta,
+        extrinsic_id
+            .block_num
+            .saturating_sub(execute_step_meta.get_executor_config().txn_num_blocks_alive),
+        cur_block,
+    ) {
+        Ok(deposit) => deposit,
+        Err(_) => return Ok(None),
+    };
+    let (new_status, _) = step.get_completed_status(&deposit);
+    let is_same_extrinsic = deposit.block_num == extrinsic_id.block_num
+        && deposit.extrinsic_index == extrinsic_id.extrinsic_index;
+    step.status = new_status;
+    if is_same_extrinsic || step.get_status() == ExecutableSimpleStatus::Failed {
+        Ok(Some(step.get_status()))
+    } else {
+        Ok(None)
+    }
+}
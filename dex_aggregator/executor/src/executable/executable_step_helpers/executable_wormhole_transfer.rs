@@ -0,0 +1,453 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::{String, ToString};
+
+use privadex_chain_metadata::{
+    bridge::WORMHOLE_MAX_DECIMALS,
+    chain_info::ChainInfo,
+    common::{Amount, BlockNum, ChainTokenId, EthAddress, Nonce, UniversalAddress},
+    get_chain_info_from_chain_id,
+    registry::{bridge::wormhole_bridge_registry, token::token_decimals_registry},
+};
+use privadex_execution_plan::execution_plan::{
+    EthPendingTxnId, MultiPhaseBridgeStepStatus, WormholeMessageId, WormholeTransferStep,
+};
+
+use crate::{
+    eth_utils::{
+        self, wormhole_guardian_api::WormholeGuardianApi,
+        wormhole_token_bridge_contract::WormholeTokenBridgeContract,
+    },
+    executable::{
+        executable_step::get_updated_gas_fee_usd,
+        execute_step_meta::ExecuteStepMeta,
+        traits::{
+            Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus,
+            StepForwardResult,
+        },
+    },
+    key_container::KeyContainer,
+};
+
+impl Executable for WormholeTransferStep {
+    fn get_status(&self) -> ExecutableSimpleStatus {
+        (&self.status).into()
+    }
+
+    fn get_total_fee_usd(&self) -> Option<Amount> {
+        if self.get_status() == ExecutableSimpleStatus::Succeeded {
+            Some(self.common.gas_fee_usd + self.bridge_fee_usd)
+        } else {
+            None
+        }
+    }
+
+    fn execute_step_forward(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+    ) -> ExecutableResult<StepForwardResult> {
+        let optional_intermediate_result = match &self.status {
+            MultiPhaseBridgeStepStatus::Dropped
+            | MultiPhaseBridgeStepStatus::Failed(_)
+            | MultiPhaseBridgeStepStatus::Confirmed(_, _) => {
+                Err(ExecutableError::CalledStepForwardOnFinishedStep)
+            }
+            MultiPhaseBridgeStepStatus::NotStarted => self
+                .execute_step_forward_if_notstarted(execute_step_meta, keys)
+                .map(|res| Some(res)),
+            MultiPhaseBridgeStepStatus::DepositSubmitted(pending_txn_id) => {
+                self.execute_step_forward_if_deposit_submitted(execute_step_meta, pending_txn_id)
+            }
+            MultiPhaseBridgeStepStatus::AwaitingRelay(message_id) => {
+                self.execute_step_forward_if_awaiting_relay(execute_step_meta, keys, message_id)
+            }
+            MultiPhaseBridgeStepStatus::ClaimSubmitted(message_id, pending_txn_id) => self
+                .execute_step_forward_if_claim_submitted(
+                    execute_step_meta,
+                    message_id,
+                    pending_txn_id,
+                ),
+        }?;
+
+        if let Some(intermediate_step_res) = optional_intermediate_result {
+            self.status = intermediate_step_res.new_status;
+            if let Some(updated_gas_fee_native) = intermediate_step_res.updated_gas_fee_native {
+                self.common.gas_fee_usd = get_updated_gas_fee_usd(
+                    updated_gas_fee_native,
+                    self.common.gas_fee_native,
+                    self.common.gas_fee_usd,
+                );
+                self.common.gas_fee_native = updated_gas_fee_native;
+            }
+            if let Some(updated_bridge_fee_native) = intermediate_step_res.updated_bridge_fee_native
+            {
+                self.bridge_fee_usd = get_updated_gas_fee_usd(
+                    updated_bridge_fee_native,
+                    self.bridge_fee_native,
+                    self.bridge_fee_usd,
+                );
+                self.bridge_fee_native = updated_bridge_fee_native;
+            }
+            Ok(StepForwardResult {
+                did_status_change: true,
+                amount_out: intermediate_step_res.amount_out,
+            })
+        } else {
+            Ok(StepForwardResult {
+                did_status_change: false,
+                amount_out: None,
+            })
+        }
+    }
+}
+
+struct IntermediateStepResult {
+    pub new_status: MultiPhaseBridgeStepStatus,
+    // Gas fee of the deposit txn on the source chain
+    pub updated_gas_fee_native: Option<Amount>,
+    // Gas fee of the claim txn on the destination chain
+    pub updated_bridge_fee_native: Option<Amount>,
+    // amount_out is null until Confirmed, and 0 if Failed or Dropped
+    pub amount_out: Option<Amount>,
+}
+
+trait WormholeTransferExecutableHelper {
+    fn execute_step_forward_if_notstarted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+    ) -> ExecutableResult<IntermediateStepResult>;
+
+    fn execute_step_forward_if_deposit_submitted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        pending_txn_id: &EthPendingTxnId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>>;
+
+    fn execute_step_forward_if_awaiting_relay(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        message_id: &WormholeMessageId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>>;
+
+    fn execute_step_forward_if_claim_submitted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        message_id: &WormholeMessageId,
+        pending_txn_id: &EthPendingTxnId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>>;
+}
+
+impl WormholeTransferExecutableHelper for WormholeTransferStep {
+    fn execute_step_forward_if_notstarted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+    ) -> ExecutableResult<IntermediateStepResult> {
+        let amount = self
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let (src_chain_info, src_rpc_url, src_cur_block) =
+            helpers::get_chain_utils(execute_step_meta, &self.src_token.chain)?;
+        let (src_addr, key) = helpers::get_eth_addr_and_key(&self.common.src_addr, keys)?;
+        let recipient = helpers::get_eth_addr(&self.common.dest_addr)?;
+        let token_eth_addr = match &self.src_token.id {
+            ChainTokenId::Native => Err(ExecutableError::UnexpectedNonEthAddress),
+            ChainTokenId::ERC20(erc20_token) => Ok(erc20_token.addr),
+            ChainTokenId::XC20(xc20_token) => Ok(xc20_token.get_eth_address()),
+        }?;
+
+        let nonce = helpers::get_nonce(
+            execute_step_meta,
+            self,
+            src_chain_info,
+            &src_rpc_url,
+            src_addr,
+            src_cur_block,
+        )?;
+        let signed_txn = WormholeTokenBridgeContract::new(&src_rpc_url, self.src_token_bridge_addr)
+            .map_err(|_| ExecutableError::FailedToLoadWormholeContract)?
+            .transfer_tokens(
+                token_eth_addr,
+                amount,
+                self.dest_wormhole_chain_id,
+                recipient,
+                key,
+                src_chain_info.supports_eip1559,
+                nonce,
+            )
+            .map_err(ExecutableError::from_create_txn_error)?;
+        let txn_hash = execute_step_meta.send_eth_txn(
+            &self.uuid,
+            self.src_token.chain,
+            &src_rpc_url,
+            signed_txn,
+        )?;
+
+        Ok(IntermediateStepResult {
+            new_status: MultiPhaseBridgeStepStatus::DepositSubmitted(EthPendingTxnId {
+                txn_hash,
+                end_block_num: src_cur_block
+                    + execute_step_meta.get_executor_config().txn_num_blocks_alive,
+            }),
+            updated_gas_fee_native: None,
+            updated_bridge_fee_native: None,
+            amount_out: None,
+        })
+    }
+
+    fn execute_step_forward_if_deposit_submitted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        pending_txn_id: &EthPendingTxnId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>> {
+        if execute_step_meta.is_paper_trading() {
+            // Nothing was broadcast, so the guardians will never sign a VAA for us to build
+            // the claim with. We stop at the deposit and assume the full amount arrives
+            let amount = self
+                .amount_in
+                .ok_or(ExecutableError::UnexpectedNullAmount)?;
+            return Ok(Some(IntermediateStepResult {
+                new_status: MultiPhaseBridgeStepStatus::Confirmed(
+                    WormholeMessageId {
+                        deposit_txn_hash: pending_txn_id.txn_hash,
+                        sequence: 0,
+                    },
+                    pending_txn_id.txn_hash,
+                ),
+                updated_gas_fee_native: None,
+                updated_bridge_fee_native: None,
+                amount_out: Some(token_decimals_registry::normalize_amount_with_max_decimals(
+                    amount,
+                    &self.src_token,
+                    &self.dest_token,
+                    WORMHOLE_MAX_DECIMALS,
+                )),
+            }));
+        }
+        let (_, src_rpc_url, src_cur_block) =
+            helpers::get_chain_utils(execute_step_meta, &self.src_token.chain)?;
+
+        if src_cur_block > pending_txn_id.end_block_num {
+            Ok(Some(IntermediateStepResult {
+                new_status: MultiPhaseBridgeStepStatus::Dropped,
+                updated_gas_fee_native: Some(0),
+                updated_bridge_fee_native: Some(0),
+                amount_out: Some(0),
+            }))
+        } else if let Ok(deposit) = eth_utils::parse_txn_helper::parse_wormhole_deposit_txn(
+            &src_rpc_url,
+            pending_txn_id.txn_hash,
+            self.src_token_bridge_addr,
+        ) {
+            if deposit.is_txn_success {
+                // The source chain nonce is used up, but the step is far from finished
+                // so we release it here rather than when the step terminates
+                let _ = execute_step_meta.finalize_execstep(&self.uuid, self.src_token.chain)?;
+                Ok(Some(IntermediateStepResult {
+                    new_status: MultiPhaseBridgeStepStatus::AwaitingRelay(WormholeMessageId {
+                        deposit_txn_hash: pending_txn_id.txn_hash,
+                        sequence: deposit.sequence,
+                    }),
+                    updated_gas_fee_native: Some(deposit.gas_fee_native),
+                    updated_bridge_fee_native: None,
+                    amount_out: None,
+                }))
+            } else {
+                Ok(Some(IntermediateStepResult {
+                    new_status: MultiPhaseBridgeStepStatus::Failed(pending_txn_id.txn_hash),
+                    updated_gas_fee_native: Some(deposit.gas_fee_native),
+                    updated_bridge_fee_native: Some(0),
+                    amount_out: Some(0),
+                }))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn execute_step_forward_if_awaiting_relay(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+        message_id: &WormholeMessageId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>> {
+        let guardian_api = WormholeGuardianApi {
+            guardian_rpc_url: wormhole_bridge_registry::GUARDIAN_RPC_URL.to_string(),
+        };
+        let signed_vaa = match guardian_api.get_signed_vaa(
+            self.src_wormhole_chain_id,
+            self.src_token_bridge_addr,
+            message_id.sequence,
+        ) {
+            Ok(signed_vaa) => signed_vaa,
+            // Not enough guardians have signed yet
+            Err(_) => return Ok(None),
+        };
+
+        let (dest_chain_info, dest_rpc_url, dest_cur_block) =
+            helpers::get_chain_utils(execute_step_meta, &self.dest_token.chain)?;
+        // We send the claim from the escrow account that receives the funds
+        let (claimer_addr, key) = helpers::get_eth_addr_and_key(&self.common.dest_addr, keys)?;
+        let nonce = helpers::get_nonce(
+            execute_step_meta,
+            self,
+            dest_chain_info,
+            &dest_rpc_url,
+            claimer_addr,
+            dest_cur_block,
+        )?;
+        let signed_txn =
+            WormholeTokenBridgeContract::new(&dest_rpc_url, self.dest_token_bridge_addr)
+                .map_err(|_| ExecutableError::FailedToLoadWormholeContract)?
+                .complete_transfer(signed_vaa, key, dest_chain_info.supports_eip1559, nonce)
+                .map_err(ExecutableError::from_create_txn_error)?;
+        let txn_hash = execute_step_meta.send_eth_txn(
+            &self.uuid,
+            self.dest_token.chain,
+            &dest_rpc_url,
+            signed_txn,
+        )?;
+
+        Ok(Some(IntermediateStepResult {
+            new_status: MultiPhaseBridgeStepStatus::ClaimSubmitted(
+                message_id.clone(),
+                EthPendingTxnId {
+                    txn_hash,
+                    end_block_num: dest_cur_block
+                        + execute_step_meta.get_executor_config().txn_num_blocks_alive,
+                },
+            ),
+            updated_gas_fee_native: None,
+            updated_bridge_fee_native: None,
+            amount_out: None,
+        }))
+    }
+
+    fn execute_step_forward_if_claim_submitted(
+        &self,
+        execute_step_meta: &ExecuteStepMeta,
+        message_id: &WormholeMessageId,
+        pending_txn_id: &EthPendingTxnId,
+    ) -> ExecutableResult<Option<IntermediateStepResult>> {
+        let (_, dest_rpc_url, dest_cur_block) =
+            helpers::get_chain_utils(execute_step_meta, &self.dest_token.chain)?;
+
+        if dest_cur_block > pending_txn_id.end_block_num {
+            // The deposit is already locked in the bridge, so we never drop the step.
+            // We just release the nonce and claim again
+            let _ = execute_step_meta.drop_execstep(&self.uuid, self.dest_token.chain)?;
+            Ok(Some(IntermediateStepResult {
+                new_status: MultiPhaseBridgeStepStatus::AwaitingRelay(message_id.clone()),
+                updated_gas_fee_native: None,
+                updated_bridge_fee_native: None,
+                amount_out: None,
+            }))
+        } else if let Ok(transfer) =
+            eth_utils::parse_txn_helper::parse_transfer_from_wormhole_claim_txn(
+                &dest_rpc_url,
+                pending_txn_id.txn_hash,
+            )
+        {
+            let _ = execute_step_meta.finalize_execstep(&self.uuid, self.dest_token.chain)?;
+            if transfer.is_txn_success {
+                Ok(Some(IntermediateStepResult {
+                    new_status: MultiPhaseBridgeStepStatus::Confirmed(
+                        message_id.clone(),
+                        pending_txn_id.txn_hash,
+                    ),
+                    updated_gas_fee_native: None,
+                    updated_bridge_fee_native: Some(transfer.gas_fee_native),
+                    amount_out: Some(transfer.amount),
+                }))
+            } else {
+                // The VAA can still be redeemed manually, but that is outside the scope of
+                // automatic execution
+                Ok(Some(IntermediateStepResult {
+                    new_status: MultiPhaseBridgeStepStatus::Failed(pending_txn_id.txn_hash),
+                    updated_gas_fee_native: None,
+                    updated_bridge_fee_native: Some(transfer.gas_fee_native),
+                    amount_out: Some(0),
+                }))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+mod helpers {
+    use privadex_chain_metadata::common::UniversalChainId;
+
+    use crate::key_container::SigningKey;
+
+    use super::*;
+
+    pub(super) fn get_chain_utils(
+        execute_step_meta: &ExecuteStepMeta,
+        chain_id: &UniversalChainId,
+    ) -> ExecutableResult<(&'static ChainInfo, String /* rpc_url */, BlockNum)> {
+        let chain_info = get_chain_info_from_chain_id(&chain_id)
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+        let cur_block = eth_utils::common::block_number(&rpc_url)
+            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        Ok((chain_info, rpc_url, cur_block))
+    }
+
+    pub(super) fn get_eth_addr(addr: &UniversalAddress) -> ExecutableResult<EthAddress> {
+        if let UniversalAddress::Ethereum(eth_addr) = addr {
+            Ok(eth_addr.clone())
+        } else {
+            Err(ExecutableError::UnexpectedNonEthAddress)
+        }
+    }
+
+    pub(super) fn get_eth_addr_and_key<'a>(
+        addr: &UniversalAddress,
+        keys: &'a KeyContainer,
+    ) -> ExecutableResult<(EthAddress, &'a SigningKey)> {
+        let eth_addr = get_eth_addr(addr)?;
+        let key = keys.get_key(addr).ok_or(ExecutableError::SecretNotFound)?;
+        Ok((eth_addr, key))
+    }
+
+    // Using NonceManager to get the nonce in a concurrent-safe way
+    pub(super) fn get_nonce(
+        execute_step_meta: &ExecuteStepMeta,
+        step: &WormholeTransferStep,
+        chain_info: &ChainInfo,
+        rpc_url: &str,
+        addr: EthAddress,
+        cur_block: BlockNum,
+    ) -> ExecutableResult<Nonce> {
+        execute_step_meta.get_pooled_nonce(
+            &step.uuid,
+            chain_info.chain_id,
+            &UniversalAddress::Ethereum(addr),
+            cur_block,
+            || {
+                eth_utils::common::get_next_system_nonce(rpc_url, addr)
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            },
+        )
+    }
+}
@@ -16,15 +16,17 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{string::ToString, vec::Vec};
-use sp_runtime::{generic::Era, AccountId32};
+use ink::prelude::{string::ToString, vec::Vec};
+use sp_runtime::generic::Era;
 
 use privadex_chain_metadata::{
-    common::{Amount, BlockNum, Nonce, SecretKey, UniversalAddress},
+    chain_info::{ChainInfo, XcmTransferMethod},
+    common::{Amount, BlockNum, ChainTokenId, Nonce, UniversalAddress},
     get_chain_info_from_chain_id,
-    registry::chain::universal_chain_id_registry,
+    registry::token::token_decimals_registry,
+    xcm_location::negotiate_xcm_version,
 };
-use privadex_common::{signature_scheme::SignatureScheme, utils::ss58_utils::Ss58Codec};
+use privadex_common::signature_scheme::SignatureScheme;
 use privadex_execution_plan::execution_plan::{
     CrossChainStepStatus, EthPendingTxnId, FinalizedTxnId, PendingTxnId, SubstrateEventId,
     SubstrateFinalizedExtrinsicId, SubstratePendingEventId, SubstratePendingExtrinsicId,
@@ -34,7 +36,7 @@ use privadex_execution_plan::execution_plan::{
 use crate::{
     eth_utils,
     executable::{
-        executable_step::{get_updated_gas_fee_usd, TXN_NUM_BLOCKS_ALIVE},
+        executable_step::get_updated_gas_fee_usd,
         execute_step_meta::ExecuteStepMeta,
         traits::{
             Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus,
@@ -42,14 +44,13 @@ use crate::{
         },
     },
     extrinsic_call_factory::{
-        moonbase_alpha_xtokens_transfer_multiasset, moonbeam_xtokens_transfer_multiasset,
-        polkadot_xcm_limited_reserve_transfer_assets,
+        xcm_pallet_limited_reserve_transfer_assets, xtokens_transfer_multiasset,
     },
-    key_container::KeyContainer,
+    key_container::{KeyContainer, SigningKey},
     substrate_utils::{
         extrinsic_sig_config::ExtrinsicSigConfig,
-        indexer_utils::subsquid_utils::SubstrateSubsquidUtils,
-        node_rpc_utils::SubstrateNodeRpcUtils,
+        indexer_utils::subsquid_utils::{SubstrateExtrinsicFeeResult, SubstrateSubsquidUtils},
+        node_rpc_utils::{ExtrinsicContext, SubstrateNodeRpcUtils, TxPayment},
     },
 };
 
@@ -74,18 +75,25 @@ impl Executable for XCMTransferStep {
         let optional_intermediate_result = match &self.status {
             CrossChainStepStatus::Dropped
             | CrossChainStepStatus::Failed(_)
+            | CrossChainStepStatus::RemoteFailed(_, _, _)
             | CrossChainStepStatus::Confirmed(_, _) => {
                 Err(ExecutableError::CalledStepForwardOnFinishedStep)
             }
             CrossChainStepStatus::NotStarted => self
                 .execute_step_forward_if_notstarted(execute_step_meta, keys)
                 .map(|res| Some(res)),
-            CrossChainStepStatus::Submitted(pending_txn_id, pending_event_id) => {
-                self.execute_step_forward_if_submitted(pending_txn_id, pending_event_id)
-            }
-            CrossChainStepStatus::LocalConfirmed(txn_id, pending_event_id) => {
-                self.execute_step_forward_if_local_confirmed(txn_id, pending_event_id)
-            }
+            CrossChainStepStatus::Submitted(pending_txn_id, pending_event_id) => self
+                .execute_step_forward_if_submitted(
+                    execute_step_meta,
+                    pending_txn_id,
+                    pending_event_id,
+                ),
+            CrossChainStepStatus::LocalConfirmed(txn_id, pending_event_id) => self
+                .execute_step_forward_if_local_confirmed(
+                    execute_step_meta,
+                    txn_id,
+                    pending_event_id,
+                ),
         }?;
 
         if let Some(intermediate_step_res) = optional_intermediate_result {
@@ -98,6 +106,15 @@ impl Executable for XCMTransferStep {
                 );
                 self.common.gas_fee_native = updated_gas_fee_native;
             }
+            if let Some(updated_bridge_fee_native) = intermediate_step_res.updated_bridge_fee_native
+            {
+                self.bridge_fee_usd = get_updated_gas_fee_usd(
+                    updated_bridge_fee_native,
+                    self.bridge_fee_native,
+                    self.bridge_fee_usd,
+                );
+                self.bridge_fee_native = updated_bridge_fee_native;
+            }
             Ok(StepForwardResult {
                 did_status_change: true,
                 amount_out: intermediate_step_res.amount_out,
@@ -113,11 +130,12 @@ impl Executable for XCMTransferStep {
 
 struct IntermediateStepResult {
     pub new_status: CrossChainStepStatus,
-    // For the MVP we do not parse fees from Substrate extrinsics and update them in our state,
-    // so we keep all our estimates for bridge fees and for extrinsic-based gas fees.
-    // We only update the gas fee if we sent it via an Ethereum transaction (e.g. Astar XCM precompile)
+    // We keep our estimates for bridge fees. The gas fee is updated from the Ethereum transaction
+    // (e.g. Astar XCM precompile) or the extrinsic's fee events once it is confirmed
     pub updated_gas_fee_native: Option<Amount>,
-    // amount_out is null if LocalConfirmed, 0 if Failed or Dropped, and a real value if Confirmed
+    // Only set once Confirmed, from the amount that arrived (see get_realized_bridge_fee)
+    pub updated_bridge_fee_native: Option<Amount>,
+    // amount_out is null if LocalConfirmed, 0 if (Remote)Failed or Dropped, and a real value if Confirmed
     pub amount_out: Option<Amount>,
 }
 
@@ -130,44 +148,59 @@ trait XCMTransferExecutableHelper {
 
     fn execute_step_forward_if_notstarted_astar_precompile(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
+        src_chain_info: &ChainInfo,
         src_chain_rpc_url: &str,
         src_cur_block: BlockNum,
         dest_cur_block: BlockNum,
         nonce: Nonce,
         amount: Amount,
-        key: &SecretKey,
+        key: &SigningKey,
     ) -> ExecutableResult<IntermediateStepResult>;
 
     fn execute_step_forward_if_notstarted_substrate_extrinsic(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         src_subutils: SubstrateNodeRpcUtils,
         src_cur_block: BlockNum,
         dest_cur_block: BlockNum,
         encoded_call_data: Vec<u8>,
         nonce: Nonce,
-        key: &SecretKey,
+        signer_addr: &UniversalAddress,
+        key: &SigningKey,
+        sig_scheme: SignatureScheme,
     ) -> ExecutableResult<IntermediateStepResult>;
 
     fn execute_step_forward_if_submitted(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &PendingTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>>;
 
+    fn get_paper_confirmed_step_result(
+        &self,
+        pending_txn_id: &PendingTxnId,
+        pending_event_id: &SubstratePendingEventId,
+    ) -> ExecutableResult<IntermediateStepResult>;
+
     fn execute_step_forward_if_submitted_eth_helper(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &EthPendingTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>>;
 
     fn execute_step_forward_if_submitted_substrate_helper(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &SubstratePendingExtrinsicId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>>;
 
     fn execute_step_forward_if_local_confirmed(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         txn_id: &FinalizedTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>>;
@@ -180,100 +213,127 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
         keys: &KeyContainer,
     ) -> ExecutableResult<IntermediateStepResult> {
         let (src_chain_info, src_subutils, src_cur_block, _) =
-            helpers::get_chain_utils(&self.src_token.chain)?;
-        let (_, _, dest_cur_block, _) = helpers::get_chain_utils(&self.dest_token.chain)?;
+            helpers::get_chain_utils(execute_step_meta, &self.src_token.chain)?;
+        let (dest_chain_info, _, dest_cur_block, _) =
+            helpers::get_chain_utils(execute_step_meta, &self.dest_token.chain)?;
+        // If the escrow only acts for src_addr through a proxy, the proxy's delegate signs and
+        // its nonce is the one used
+        let proxy =
+            execute_step_meta.get_substrate_proxy(&self.src_token.chain, &self.common.src_addr);
+        let signer_addr =
+            proxy.map_or_else(|| self.common.src_addr.clone(), |proxy| proxy.get_signer());
 
         // Using NonceManager to get the nonce in a concurrent-safe way
-        let nonce = {
-            let system_nonce = {
-                match self.common.src_addr {
-                    UniversalAddress::Ethereum(eth_addr) => {
-                        eth_utils::common::get_next_system_nonce(
-                            src_chain_info.rpc_url,
-                            eth_addr.clone(),
-                        )
+        let nonce = execute_step_meta.get_pooled_nonce(
+            &self.uuid,
+            self.src_token.chain,
+            &signer_addr,
+            src_cur_block,
+            || match &signer_addr {
+                UniversalAddress::Ethereum(eth_addr) => eth_utils::common::get_next_system_nonce(
+                    &src_subutils.rpc_url,
+                    eth_addr.clone(),
+                )
+                .map_err(|_| ExecutableError::RpcRequestFailed),
+                UniversalAddress::Substrate(_) => {
+                    let ss58_address = keys
+                        .get_chain_account(&self.src_token.chain, &signer_addr)
+                        .ok_or(ExecutableError::SecretNotFound)?
+                        .ss58_address
+                        .ok_or(ExecutableError::Ss58AddressFormatNotFound)?;
+                    src_subutils
+                        .get_next_system_nonce(&ss58_address)
                         .map_err(|_| ExecutableError::RpcRequestFailed)
-                    }
-                    UniversalAddress::Substrate(substrate_addr) => {
-                        let ss58_prefix = src_chain_info
-                            .get_ss58_prefix()
-                            .ok_or(ExecutableError::Ss58AddressFormatNotFound)?;
-                        let ss58_address = AccountId32::new(substrate_addr.0)
-                            .to_ss58check_with_version(ss58_prefix);
-                        src_subutils
-                            .get_next_system_nonce(&ss58_address)
-                            .map_err(|_| ExecutableError::RpcRequestFailed)
-                    }
                 }
-            }?;
-            execute_step_meta.get_nonce(
-                &self.uuid,
-                self.src_token.chain,
-                src_cur_block,
-                system_nonce,
-            )
-        }?;
+            },
+        )?;
         let amount = self
             .amount_in
             .ok_or(ExecutableError::UnexpectedNullAmount)?;
         let key = keys
-            .get_key(&self.common.src_addr)
+            .get_key(&signer_addr)
             .ok_or(ExecutableError::SecretNotFound)?;
 
-        // Special handling for Astar's precompile
-        if self.src_token.chain == universal_chain_id_registry::ASTAR {
-            return self.execute_step_forward_if_notstarted_astar_precompile(
-                src_chain_info.rpc_url,
-                src_cur_block,
-                dest_cur_block,
-                nonce,
-                amount,
-                key,
-            );
-        }
-
-        // General handling for Substrate extrinsics
+        let transfer_method = src_chain_info
+            .get_xcm_transfer_method()
+            .ok_or(ExecutableError::UnsupportedChain)?;
         let asset = xcm::prelude::MultiAsset {
             id: xcm::prelude::AssetId::Concrete(self.token_asset_multilocation.clone()),
             fun: xcm::prelude::Fungible(amount),
         };
-        let encoded_call_data = match &self.src_token.chain {
-            &universal_chain_id_registry::MOONBEAM => {
-                moonbeam_xtokens_transfer_multiasset(asset, self.full_dest_multilocation.clone())
-                    .map_err(|_| ExecutableError::FailedToCreateTxn)
+        let xcm_version = negotiate_xcm_version(src_chain_info, dest_chain_info)
+            .map_err(|_| ExecutableError::UnsupportedChain)?;
+        // Falls back to the lane's configured limit if there is no calibration (yet)
+        let dest_weight_limit = execute_step_meta
+            .pull_xcm_weight_calibration_from_s3()
+            .map_or(self.dest_weight_limit, |calibration| {
+                calibration.get_weight_limit(
+                    &self.src_token,
+                    &self.dest_token,
+                    self.dest_weight_limit,
+                )
+            });
+        let encoded_call_data = match transfer_method {
+            // The XCM precompile (v1) does not take a weight limit
+            XcmTransferMethod::EvmPrecompile => {
+                return self.execute_step_forward_if_notstarted_astar_precompile(
+                    execute_step_meta,
+                    src_chain_info,
+                    &src_subutils.rpc_url,
+                    src_cur_block,
+                    dest_cur_block,
+                    nonce,
+                    amount,
+                    key,
+                );
             }
-            &universal_chain_id_registry::POLKADOT => polkadot_xcm_limited_reserve_transfer_assets(
+            XcmTransferMethod::XTokens(pallet_index) => xtokens_transfer_multiasset(
+                pallet_index,
+                xcm_version,
                 asset,
                 self.full_dest_multilocation.clone(),
-            )
-            .map_err(|_| ExecutableError::FailedToCreateTxn),
-            &universal_chain_id_registry::MOONBASE_ALPHA => {
-                moonbase_alpha_xtokens_transfer_multiasset(
+                dest_weight_limit,
+            ),
+            XcmTransferMethod::XcmPallet(pallet_index) => {
+                xcm_pallet_limited_reserve_transfer_assets(
+                    pallet_index,
+                    xcm_version,
                     asset,
                     self.full_dest_multilocation.clone(),
+                    dest_weight_limit,
                 )
-                .map_err(|_| ExecutableError::FailedToCreateTxn)
             }
-            _ => Err(ExecutableError::UnsupportedChain),
-        }?;
+        }
+        .map_err(|_| ExecutableError::FailedToCreateTxn)?;
+        let encoded_call_data = match proxy {
+            Some(proxy) => proxy.wrap_call(&encoded_call_data),
+            None => encoded_call_data,
+        };
         self.execute_step_forward_if_notstarted_substrate_extrinsic(
+            execute_step_meta,
             src_subutils,
             src_cur_block,
             dest_cur_block,
             encoded_call_data,
             nonce,
+            &signer_addr,
             key,
+            // Substrate escrow accounts can be Sr25519 or Ed25519
+            keys.get_sig_scheme(&signer_addr)
+                .unwrap_or(SignatureScheme::Sr25519),
         )
     }
 
     fn execute_step_forward_if_notstarted_astar_precompile(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
+        src_chain_info: &ChainInfo,
         src_chain_rpc_url: &str,
         src_cur_block: BlockNum,
         dest_cur_block: BlockNum,
         nonce: Nonce,
         amount: Amount,
-        key: &SecretKey,
+        key: &SigningKey,
     ) -> ExecutableResult<IntermediateStepResult> {
         let astar_xcm_precompile =
             eth_utils::astar_xcm_precompile_contract::AstarXcmContract::new(src_chain_rpc_url)
@@ -285,61 +345,78 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                 self.dest_token.chain,
                 self.common.dest_addr.clone(),
                 key,
+                src_chain_info.supports_eip1559,
                 nonce,
             )
-            .map_err(|_| ExecutableError::FailedToCreateTxn)?;
+            .map_err(ExecutableError::from_create_txn_error)?;
 
-        let txn_hash = eth_utils::common::send_raw_transaction(src_chain_rpc_url, signed_txn)
-            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let txn_hash = execute_step_meta.send_eth_txn(
+            &self.uuid,
+            self.src_token.chain,
+            src_chain_rpc_url,
+            signed_txn,
+        )?;
 
         Ok(IntermediateStepResult {
             new_status: CrossChainStepStatus::Submitted(
                 PendingTxnId::Ethereum(EthPendingTxnId {
                     txn_hash,
-                    end_block_num: src_cur_block + TXN_NUM_BLOCKS_ALIVE,
+                    end_block_num: src_cur_block
+                        + execute_step_meta.get_executor_config().txn_num_blocks_alive,
                 }),
                 SubstratePendingEventId {
                     start_block_num: dest_cur_block,
+                    message_hash: None,
                 },
             ),
             updated_gas_fee_native: None,
+            updated_bridge_fee_native: None,
             amount_out: None,
         })
     }
 
     fn execute_step_forward_if_notstarted_substrate_extrinsic(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         src_subutils: SubstrateNodeRpcUtils,
         src_cur_block: BlockNum,
         dest_cur_block: BlockNum,
         encoded_call_data: Vec<u8>,
         nonce: Nonce,
-        key: &SecretKey,
+        signer_addr: &UniversalAddress,
+        key: &SigningKey,
+        sig_scheme: SignatureScheme,
     ) -> ExecutableResult<IntermediateStepResult> {
-        let runtime_version = src_subutils
-            .get_runtime_version()
-            .map_err(|_| ExecutableError::RpcRequestFailed)?;
-        let genesis_hash = src_subutils
-            .get_genesis_hash()
+        let ExtrinsicContext {
+            runtime_version,
+            genesis_hash,
+            finalized_head_hash,
+        } = src_subutils
+            .get_extrinsic_context()
             .map_err(|_| ExecutableError::RpcRequestFailed)?;
         let era = Era::Immortal;
         // TODO: Using a mortal error causes bad extrinsic signatures (at least on Moonbeam).
         // Need to investigate late on how to resolve that
         // let era = Era::mortal(TXN_NUM_BLOCKS_ALIVE.into(), src_cur_block.into());
         let finalized_head = if era != Era::Immortal {
-            src_subutils
-                .get_finalized_head_hash()
-                .map_err(|_| ExecutableError::RpcRequestFailed)?
+            finalized_head_hash
         } else {
             genesis_hash.clone()
         };
+        let src_chain_info = get_chain_info_from_chain_id(&self.src_token.chain)
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let tx_payment = TxPayment::from_chain_info(
+            src_chain_info,
+            0, // tip
+            execute_step_meta.get_fee_asset(&self.src_token.chain),
+        );
 
-        let tx_raw = match self.common.src_addr {
+        let tx_raw = match signer_addr {
             UniversalAddress::Ethereum(eth_addr) => {
                 let sigconfig = ExtrinsicSigConfig::<[u8; 20]> {
                     sig_scheme: SignatureScheme::Ethereum,
                     signer: eth_addr.0,
-                    privkey: key.to_vec(),
+                    key: key.clone(),
                 };
                 src_subutils.create_extrinsic::<[u8; 20]>(
                     sigconfig,
@@ -349,14 +426,14 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                     genesis_hash,
                     finalized_head, // checkpoint block hash
                     era,
-                    0, // tip
+                    tx_payment,
                 )
             }
             UniversalAddress::Substrate(substrate_addr) => {
                 let sigconfig = ExtrinsicSigConfig::<[u8; 32]> {
-                    sig_scheme: SignatureScheme::Sr25519,
+                    sig_scheme,
                     signer: substrate_addr.0,
-                    privkey: key.to_vec(),
+                    key: key.clone(),
                 };
                 src_subutils.create_extrinsic::<[u8; 32]>(
                     sigconfig,
@@ -366,52 +443,69 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                     genesis_hash,
                     finalized_head, // checkpoint block hash
                     era,
-                    0, // tip
+                    tx_payment,
                 )
             }
-        };
+        }
+        .map_err(|_| ExecutableError::FailedToCreateTxn)?;
 
-        ink_env::debug_println!(
+        ink::env::debug_println!(
             "Tx: {:?}",
             privadex_common::utils::general_utils::slice_to_hex_string(&tx_raw)
         );
 
-        let res = src_subutils.send_extrinsic(&tx_raw);
+        let res = execute_step_meta.send_extrinsic(
+            &self.uuid,
+            self.src_token.chain,
+            &src_subutils,
+            &tx_raw,
+        );
 
-        ink_env::debug_println!("XCM transfer send_extrinsic: {:?}", res);
+        ink::env::debug_println!("XCM transfer send_extrinsic: {:?}", res);
 
-        let extrinsic_hash = res.map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let extrinsic_hash = res?;
 
         Ok(IntermediateStepResult {
             new_status: CrossChainStepStatus::Submitted(
                 PendingTxnId::Substrate(SubstratePendingExtrinsicId {
                     start_block_num: src_cur_block,
                     // synced with transaction mortality
-                    end_block_num: src_cur_block + TXN_NUM_BLOCKS_ALIVE,
+                    end_block_num: src_cur_block
+                        + execute_step_meta.get_executor_config().txn_num_blocks_alive,
                     extrinsic_hash,
                 }),
                 SubstratePendingEventId {
                     start_block_num: dest_cur_block,
+                    message_hash: None,
                 },
             ),
             updated_gas_fee_native: None,
+            updated_bridge_fee_native: None,
             amount_out: None,
         })
     }
 
     fn execute_step_forward_if_submitted(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &PendingTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>> {
+        if execute_step_meta.is_paper_trading() {
+            return self
+                .get_paper_confirmed_step_result(pending_txn_id, pending_event_id)
+                .map(|res| Some(res));
+        }
         let intermediate_step_result = match pending_txn_id {
             PendingTxnId::Ethereum(eth_pending_txn_id) => self
                 .execute_step_forward_if_submitted_eth_helper(
+                    execute_step_meta,
                     &eth_pending_txn_id,
                     pending_event_id,
                 ),
             PendingTxnId::Substrate(substrate_pending_extrinsic_id) => self
                 .execute_step_forward_if_submitted_substrate_helper(
+                    execute_step_meta,
                     &substrate_pending_extrinsic_id,
                     pending_event_id,
                 ),
@@ -421,10 +515,15 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
             Some(IntermediateStepResult {
                 new_status: CrossChainStepStatus::LocalConfirmed(txn_id, pending_event_id),
                 updated_gas_fee_native,
+                updated_bridge_fee_native: None,
                 amount_out: _,
             }) => {
-                if let Ok(Some(confirmed_step_result)) =
-                    self.execute_step_forward_if_local_confirmed(txn_id, pending_event_id)
+                if let Ok(Some(confirmed_step_result)) = self
+                    .execute_step_forward_if_local_confirmed(
+                        execute_step_meta,
+                        txn_id,
+                        pending_event_id,
+                    )
                 {
                     Ok(Some(IntermediateStepResult {
                         new_status: confirmed_step_result.new_status,
@@ -432,6 +531,7 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                         // step since Confirmed never sets gas fee (as it looks up
                         // the remote chain whereas gas fees are on the local chain)
                         updated_gas_fee_native: *updated_gas_fee_native,
+                        updated_bridge_fee_native: confirmed_step_result.updated_bridge_fee_native,
                         amount_out: confirmed_step_result.amount_out,
                     }))
                 } else {
@@ -444,22 +544,64 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
         }
     }
 
+    // Nothing was broadcast in paper trading mode, so there are no local or remote events to
+    // look up. We confirm right away and assume the full amount arrives (ignoring the bridge
+    // fee, which is in the dest chain's native token) in the dest token's decimals
+    fn get_paper_confirmed_step_result(
+        &self,
+        pending_txn_id: &PendingTxnId,
+        pending_event_id: &SubstratePendingEventId,
+    ) -> ExecutableResult<IntermediateStepResult> {
+        let amount = self
+            .amount_in
+            .ok_or(ExecutableError::UnexpectedNullAmount)?;
+        let finalized_txn_id = match pending_txn_id {
+            PendingTxnId::Ethereum(eth_pending_txn_id) => {
+                FinalizedTxnId::Ethereum(eth_pending_txn_id.txn_hash)
+            }
+            PendingTxnId::Substrate(substrate_pending_extrinsic_id) => {
+                FinalizedTxnId::Substrate(SubstrateFinalizedExtrinsicId {
+                    block_num: substrate_pending_extrinsic_id.start_block_num,
+                    extrinsic_index: 0,
+                })
+            }
+        };
+        Ok(IntermediateStepResult {
+            new_status: CrossChainStepStatus::Confirmed(
+                finalized_txn_id,
+                SubstrateEventId {
+                    block_num: pending_event_id.start_block_num,
+                    event_index: 0,
+                },
+            ),
+            updated_gas_fee_native: None,
+            updated_bridge_fee_native: None,
+            amount_out: Some(token_decimals_registry::normalize_amount(
+                amount,
+                &self.src_token,
+                &self.dest_token,
+            )),
+        })
+    }
+
     fn execute_step_forward_if_submitted_eth_helper(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &EthPendingTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>> {
-        let (src_chain_info, _, src_cur_block, _) =
-            helpers::get_chain_utils(&self.src_token.chain)?;
+        let (_, src_subutils, src_cur_block, _) =
+            helpers::get_chain_utils(execute_step_meta, &self.src_token.chain)?;
 
         if src_cur_block > pending_txn_id.end_block_num {
             Ok(Some(IntermediateStepResult {
                 new_status: CrossChainStepStatus::Dropped,
                 updated_gas_fee_native: Some(0),
+                updated_bridge_fee_native: None,
                 amount_out: Some(0),
             }))
         } else if let Ok(txn_summary) = eth_utils::parse_txn_helper::get_txn_summary(
-            src_chain_info.rpc_url,
+            &src_subutils.rpc_url,
             pending_txn_id.txn_hash,
         ) {
             let finalized_txn_id = FinalizedTxnId::Ethereum(pending_txn_id.txn_hash);
@@ -470,12 +612,14 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                         pending_event_id.clone(),
                     ),
                     updated_gas_fee_native: Some(txn_summary.gas_fee_native),
+                    updated_bridge_fee_native: None,
                     amount_out: None,
                 }))
             } else {
                 Ok(Some(IntermediateStepResult {
                     new_status: CrossChainStepStatus::Failed(finalized_txn_id),
                     updated_gas_fee_native: Some(txn_summary.gas_fee_native),
+                    updated_bridge_fee_native: None,
                     amount_out: Some(0),
                 }))
             }
@@ -486,15 +630,17 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
 
     fn execute_step_forward_if_submitted_substrate_helper(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         pending_txn_id: &SubstratePendingExtrinsicId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>> {
         let (_, _, src_cur_block, src_subsquid_utils) =
-            helpers::get_chain_utils(&self.src_token.chain)?;
+            helpers::get_chain_utils(execute_step_meta, &self.src_token.chain)?;
         if src_cur_block > pending_txn_id.end_block_num {
             Ok(Some(IntermediateStepResult {
                 new_status: CrossChainStepStatus::Dropped,
                 updated_gas_fee_native: Some(0),
+                updated_bridge_fee_native: None,
                 amount_out: Some(0),
             }))
         } else if let Ok(extrinsic_summary) = src_subsquid_utils.lookup_extrinsic_by_hash(
@@ -506,19 +652,41 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                 block_num: extrinsic_summary.block_num,
                 extrinsic_index: extrinsic_summary.extrinsic_index,
             });
+            let updated_gas_fee_native = src_subsquid_utils
+                .lookup_extrinsic_fee(
+                    extrinsic_summary.block_num,
+                    extrinsic_summary.extrinsic_index,
+                )
+                .ok()
+                .and_then(|fee| {
+                    helpers::get_updated_gas_fee_native(&fee, self.common.gas_fee_native)
+                });
             if extrinsic_summary.is_extrinsic_success {
+                // Not every source chain/direction emits the hash, in which case we fall back
+                // to matching the remote events by the transfer's fields
+                let message_hash = src_subsquid_utils
+                    .lookup_xcm_message_hash(
+                        extrinsic_summary.block_num,
+                        extrinsic_summary.extrinsic_index,
+                    )
+                    .ok();
                 Ok(Some(IntermediateStepResult {
                     new_status: CrossChainStepStatus::LocalConfirmed(
                         finalized_txn_id,
-                        pending_event_id.clone(),
+                        SubstratePendingEventId {
+                            start_block_num: pending_event_id.start_block_num,
+                            message_hash,
+                        },
                     ),
-                    updated_gas_fee_native: None, // we do not update fees for parsed extrinsics
+                    updated_gas_fee_native,
+                    updated_bridge_fee_native: None,
                     amount_out: None,
                 }))
             } else {
                 Ok(Some(IntermediateStepResult {
                     new_status: CrossChainStepStatus::Failed(finalized_txn_id),
-                    updated_gas_fee_native: None,
+                    updated_gas_fee_native,
+                    updated_bridge_fee_native: None,
                     amount_out: Some(0),
                 }))
             }
@@ -529,6 +697,7 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
 
     fn execute_step_forward_if_local_confirmed(
         &self,
+        execute_step_meta: &ExecuteStepMeta,
         txn_id: &FinalizedTxnId,
         pending_event_id: &SubstratePendingEventId,
     ) -> ExecutableResult<Option<IntermediateStepResult>> {
@@ -536,7 +705,7 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
             .amount_in
             .ok_or(ExecutableError::UnexpectedNullAmount)?;
         let (_, _, dest_cur_block, dest_subsquid_utils) =
-            helpers::get_chain_utils(&self.dest_token.chain)?;
+            helpers::get_chain_utils(execute_step_meta, &self.dest_token.chain)?;
 
         if let Ok(xcm_transfer_event_summary) = dest_subsquid_utils.lookup_xcm_event_transfer(
             pending_event_id.start_block_num,
@@ -545,7 +714,36 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
             self.dest_token.clone(),
             amount,
             self.common.dest_addr.clone(),
+            pending_event_id.message_hash.as_ref(),
         ) {
+            if let Some(weight_used) = xcm_transfer_event_summary.weight_used {
+                helpers::record_weight_used(
+                    execute_step_meta,
+                    &self.src_token,
+                    &self.dest_token,
+                    weight_used,
+                );
+            }
+            let realized_bridge_fee = helpers::get_realized_bridge_fee(
+                &self.src_token,
+                &self.dest_token,
+                amount,
+                xcm_transfer_event_summary.amount_out,
+            );
+            helpers::record_bridge_fee(
+                execute_step_meta,
+                &self.src_token,
+                &self.dest_token,
+                realized_bridge_fee,
+            );
+            // bridge_fee_native is in the dest chain's native token, so we can only replace it
+            // when that is what we transferred. Otherwise the estimate stays, but the
+            // calibration still corrects future quotes
+            let updated_bridge_fee_native = if self.dest_token.id == ChainTokenId::Native {
+                Some(realized_bridge_fee)
+            } else {
+                None
+            };
             Ok(Some(IntermediateStepResult {
                 new_status: CrossChainStepStatus::Confirmed(
                     txn_id.clone(),
@@ -555,8 +753,40 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
                     },
                 ),
                 updated_gas_fee_native: None,
+                updated_bridge_fee_native,
                 amount_out: Some(xcm_transfer_event_summary.amount_out),
             }))
+        } else if let Some(message_hash) = &pending_event_id.message_hash {
+            // A failure event only identifies its message by hash, so without the hash we can't
+            // tell our failure from anyone else's. The step then stays pending until the stuck
+            // plan scan flags it for a manual override
+            if let Ok(xcm_failure_event_summary) = dest_subsquid_utils.lookup_xcm_failure_event(
+                pending_event_id.start_block_num,
+                dest_cur_block,
+                self.src_token.clone(),
+                self.dest_token.clone(),
+                message_hash,
+            ) {
+                ink::env::debug_println!(
+                    "XCM transfer failed on the remote chain: {:?}",
+                    xcm_failure_event_summary.failure_kind
+                );
+                Ok(Some(IntermediateStepResult {
+                    new_status: CrossChainStepStatus::RemoteFailed(
+                        txn_id.clone(),
+                        SubstrateEventId {
+                            block_num: xcm_failure_event_summary.block_num,
+                            event_index: xcm_failure_event_summary.event_index,
+                        },
+                        xcm_failure_event_summary.failure_kind,
+                    ),
+                    updated_gas_fee_native: None,
+                    updated_bridge_fee_native: None,
+                    amount_out: Some(0),
+                }))
+            } else {
+                Ok(None)
+            }
         } else {
             Ok(None)
         }
@@ -564,11 +794,15 @@ impl XCMTransferExecutableHelper for XCMTransferStep {
 }
 
 mod helpers {
-    use privadex_chain_metadata::{chain_info::ChainInfo, common::UniversalChainId};
+    use privadex_chain_metadata::{
+        chain_info::ChainInfo,
+        common::{UniversalChainId, UniversalTokenId},
+    };
 
     use super::*;
 
     pub(super) fn get_chain_utils(
+        execute_step_meta: &ExecuteStepMeta,
         chain_id: &UniversalChainId,
     ) -> ExecutableResult<(
         &ChainInfo,
@@ -579,14 +813,75 @@ mod helpers {
         let chain_info = get_chain_info_from_chain_id(&chain_id)
             .ok_or(ExecutableError::FailedToFindChainInfo)?;
         let subutils = SubstrateNodeRpcUtils {
-            rpc_url: chain_info.rpc_url.to_string(),
+            rpc_url: execute_step_meta.get_rpc_url(chain_info),
         };
-        let cur_block = subutils
-            .get_finalized_block_number()
-            .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let cur_block = execute_step_meta.get_cur_block(chain_id)?;
         let subsquid_utils = SubstrateSubsquidUtils {
-            subsquid_graphql_archive_url: chain_info.subsquid_graphql_archive_url.to_string(),
+            subsquid_graphql_archive_url: chain_info
+                .get_subsquid_graphql_archive_url()
+                .ok_or(ExecutableError::UnsupportedChain)?
+                .to_string(),
         };
         Ok((chain_info, subutils, cur_block, subsquid_utils))
     }
+
+    // A treasury-only fee is a lower bound, so it only replaces an estimate that was too low
+    pub(super) fn get_updated_gas_fee_native(
+        fee: &SubstrateExtrinsicFeeResult,
+        estimated_gas_fee_native: Amount,
+    ) -> Option<Amount> {
+        if fee.is_exact || fee.fee_native > estimated_gas_fee_native {
+            Some(fee.fee_native)
+        } else {
+            None
+        }
+    }
+
+    // What the destination chain kept (in dest token units). The dest events report what
+    // reached the beneficiary, i.e. amount_in less the fee
+    pub(super) fn get_realized_bridge_fee(
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        amount_in: Amount,
+        amount_out: Amount,
+    ) -> Amount {
+        token_decimals_registry::normalize_amount(amount_in, src_token, dest_token)
+            .saturating_sub(amount_out)
+    }
+
+    pub(super) fn record_bridge_fee(
+        execute_step_meta: &ExecuteStepMeta,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        bridge_fee: Amount,
+    ) {
+        // Saving over a calibration we failed to read would drop its samples
+        let mut calibration = match execute_step_meta.pull_xcm_bridge_fee_calibration_from_s3() {
+            Ok(calibration) => calibration,
+            Err(err) => {
+                ink::env::debug_println!("Failed to pull XCM bridge fee calibration: {:?}", err);
+                return;
+            }
+        };
+        calibration.record_bridge_fee(src_token, dest_token, bridge_fee);
+        if let Err(err) = execute_step_meta.save_xcm_bridge_fee_calibration_to_s3(&calibration) {
+            ink::env::debug_println!("Failed to save XCM bridge fee calibration: {:?}", err);
+        }
+    }
+
+    // Calibration is best-effort, so a storage failure should not hold up the step
+    pub(super) fn record_weight_used(
+        execute_step_meta: &ExecuteStepMeta,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        weight_used: u64,
+    ) {
+        let mut calibration = execute_step_meta
+            .pull_xcm_weight_calibration_from_s3()
+            .unwrap_or_default();
+        calibration.record_weight_used(src_token, dest_token, weight_used);
+        if let Err(err) = execute_step_meta.save_xcm_weight_calibration_to_s3(&calibration) {
+            ink::env::debug_println!("Failed to save XCM weight calibration: {:?}", err);
+        }
+    }
 }
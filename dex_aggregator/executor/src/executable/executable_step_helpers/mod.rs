@@ -17,4 +17,6 @@
  */
 
 pub mod executable_eth_steps;
+pub mod executable_remark_deposit;
+pub mod executable_wormhole_transfer;
 pub mod executable_xcm_transfer;
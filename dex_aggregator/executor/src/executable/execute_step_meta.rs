@@ -16,30 +16,106 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{
+use core::cell::RefCell;
+use ink::prelude::{
+    format,
     string::{String, ToString},
     vec,
     vec::Vec,
 };
+use pink_web3::types::SignedTransaction;
 use scale::{Decode, Encode};
 
 use privadex_chain_metadata::{
-    common::{BlockNum, EthTxnHash, MillisSinceEpoch, Nonce, UniversalChainId},
+    chain_info::ChainInfo,
+    common::{
+        Amount, AssetId, BlockNum, ChainTokenId, EthAddress, EthTxnHash, MillisSinceEpoch, Nonce,
+        SubstrateExtrinsicHash, UniversalAddress, UniversalChainId, UniversalTokenId,
+        NATIVE_TOKEN_DECIMALS,
+    },
     get_chain_info_from_chain_id,
     registry::chain::universal_chain_id_registry,
 };
-use privadex_common::{utils::s3_api::S3Api, uuid::Uuid};
-use privadex_execution_plan::execution_plan::ExecutionPlan;
+use privadex_common::{
+    utils::{
+        general_utils::slice_to_hex_string,
+        s3_api::{Error as S3Error, S3Api},
+        ss58_utils::encode_ss58,
+    },
+    uuid::Uuid,
+};
+use privadex_execution_plan::{
+    deposit_address::DepositRemark,
+    execution_plan::ExecutionPlan,
+    schema_check::SchemaCheckError,
+    stored_plan::{decode_stored_plan, encode_stored_plan},
+};
+use privadex_routing::{
+    graph_snapshot::{GraphSnapshot, GRAPH_SNAPSHOT_VERSION},
+    liquidity_summary::LiquiditySummary,
+};
 
-use super::traits::{ExecutableError, ExecutableResult};
+use super::{
+    allowance_hygiene::AllowanceCache,
+    canaries::CanaryLedger,
+    clock_skew::{get_chain_timestamp, ChainClockSkew},
+    extrinsic_batch::{submit_queued_extrinsics, ExtrinsicBatchWindow},
+    failure_bundle::{get_failure_bundle_object_key, FailureBundle},
+    nonce_pool::{NoncePools, MAX_NONCE_POOL_SIZE},
+    paper_trade::{get_paper_txns_object_key, PaperTradeLog, PaperTxn, PaperTxnKind},
+    payment_request::{get_payment_request_object_key, InvoiceId, PaymentRequest},
+    plan_analytics::{DailyStats, DaysSinceEpoch, PlanAnalytics},
+    plan_events::{
+        get_plan_event_log_object_key, get_plan_events_object_key, to_ndjson, BroadcastLog,
+        BroadcastTxn, PlanEvent, PlanEventKind, TxnChainKind,
+    },
+    relayer_rewards::{get_relayer_payout_object_key, RelayerPayout},
+    remark_deposit::{get_remark_deposit_object_key, get_remark_hash, RemarkDepositRequest},
+    route_blacklist::RouteBlacklist,
+    shadow_quotes::{ArchiveSlot, ArchivedPairCache, ShadowQuoteStats},
+    step_timing::StepDurationSamples,
+    stranded_funds::StrandedFundsLedger,
+    traits::{ExecutableError, ExecutableResult},
+    xcm_bridge_fee_calibration::XcmBridgeFeeCalibration,
+    xcm_weight_calibration::XcmWeightCalibration,
+};
 use crate::{
+    block_number_provider::{BlockNumberProvider, FinalityPolicy},
     concurrency_coordinator::{
-        execution_plan_assigner::ExecutionPlanAssigner, nonce_manager::NonceManager,
+        execution_plan_assigner::ExecutionPlanAssigner,
+        job_lock::{JobLock, LockedJob},
+        nonce_manager::{DeploymentEpoch, NonceManager},
         prestart_step_uniqueness_enforcer::PrestartStepUniquenessEnforcer,
     },
+    destination_proof::{get_destination_proof_object_key, DestinationProof},
+    eth_utils,
+    executor_config::ExecutorConfig,
+    quote_engine::SnapshotId,
+    rpc_endpoints::{get_rpc_url, RpcEndpoint},
+    storage_credentials::StorageCredentials,
+    substrate_proxy::{find_substrate_proxy, SubstrateProxy},
     substrate_utils::node_rpc_utils::SubstrateNodeRpcUtils,
 };
 
+const ANALYTICS_BUCKET_NAME: &str = "analytics";
+// Plans that don't decode are copied here as they are, e.g. "dead-letter/0x0101..."
+const DEAD_LETTER_OBJECT_KEY_PREFIX: &str = "dead-letter";
+const XCM_WEIGHT_CALIBRATION_OBJECT_KEY: &str = "xcm-weight-calibration";
+const XCM_BRIDGE_FEE_CALIBRATION_OBJECT_KEY: &str = "xcm-bridge-fee-calibration";
+const STRANDED_FUNDS_LEDGER_OBJECT_KEY: &str = "stranded-funds-ledger";
+const PENDING_PLAN_ANALYTICS_OBJECT_KEY: &str = "pending-plan-analytics";
+const PENDING_SHADOW_QUOTES_OBJECT_KEY: &str = "pending-shadow-quotes";
+const CANARY_LEDGER_OBJECT_KEY: &str = "canary-ledger";
+const PAIR_CACHE_OBJECT_KEY: &str = "pair-cache";
+const LIQUIDITY_SUMMARY_OBJECT_KEY: &str = "liquidity-summary";
+const PAUSED_CHAINS_OBJECT_KEY: &str = "paused-chains";
+const STEP_DURATIONS_OBJECT_KEY: &str = "step-durations";
+const ROUTE_BLACKLIST_OBJECT_KEY: &str = "route-blacklist";
+const ALLOWANCE_CACHE_OBJECT_KEY: &str = "allowance-cache";
+const CHAIN_CLOCK_SKEWS_OBJECT_KEY: &str = "chain-clock-skews";
+// The plan events are its audit trail, so one S3 error should not leave a gap in them
+const PLAN_EVENTS_SAVE_ATTEMPTS: u32 = 3;
+
 /// Necessary metadata to execute a step
 /// Initially I was going to make this a trait/template but it becomes
 /// really messy so I just created an enum
@@ -50,59 +126,140 @@ pub enum ExecuteStepMeta {
 
 pub struct DummyExecuteStepMeta {
     cur_timestamp: MillisSinceEpoch,
+    paper_trade_log: Option<PaperTradeLog>,
+    broadcast_log: BroadcastLog,
+    rpc_endpoints: Vec<RpcEndpoint>,
+    fee_assets: Vec<(UniversalChainId, AssetId)>,
+    substrate_proxies: Vec<SubstrateProxy>,
+    best_block_chains: Vec<UniversalChainId>,
+    cur_blocks: Vec<(UniversalChainId, BlockNum)>,
+    hold_delivery: bool,
+    draining: bool,
+    executor_config: ExecutorConfig,
 }
 
 pub struct LiveExecuteStepMeta {
     cur_timestamp: MillisSinceEpoch,
-    s3_api: S3Api,
+    // Separate so that each can be given credentials scoped to its bucket
+    plan_s3_api: S3Api,
+    analytics_s3_api: S3Api,
     exec_plan_assigner: ExecutionPlanAssigner,
     prestart_step_uniqueness_enforcer: PrestartStepUniquenessEnforcer,
+    // Serializes the read-modify-writes of the shared S3 objects (see LockedJob)
+    job_lock: JobLock,
     chain_nonce_managers: Vec<(UniversalChainId, NonceManager)>,
+    // Escrow whose nonce state the NonceManagers use. None is the shared escrow
+    escrow_name: Option<String>,
+    // 0 disables nonce pools (see get_pooled_nonce)
+    nonce_pool_size: u8,
+    nonce_pools: NoncePools,
+    // Only set while stepping forward a paper-trading ExecutionPlan
+    paper_trade_log: Option<PaperTradeLog>,
+    // Every txn and extrinsic sent since the caller last took them (see take_broadcast_txns)
+    broadcast_log: BroadcastLog,
+    // Only set while stepping forward several ExecutionPlans at once (see send_extrinsic)
+    extrinsic_batch_window: Option<ExtrinsicBatchWindow>,
+    // Override the registry's rpc_url for their chains (see get_rpc_url)
+    rpc_endpoints: Vec<RpcEndpoint>,
+    // Extrinsics on these chains pay their fees in the asset instead of the native token
+    fee_assets: Vec<(UniversalChainId, AssetId)>,
+    // Escrow accounts that are only acted for through a proxy on these chains
+    substrate_proxies: Vec<SubstrateProxy>,
+    // Chains where the current ExecutionPlan uses FinalityPolicy::BestBlock (see
+    // get_cur_block). Every other chain uses Finalized
+    best_block_chains: Vec<UniversalChainId>,
+    // Block numbers looked up ahead of time (see prefetch_cur_blocks). get_cur_block reads
+    // these instead of the chain
+    cur_blocks: Vec<(UniversalChainId, BlockNum)>,
+    // Set while the current ExecutionPlan's delivery is waiting on an approver's co-sign
+    hold_delivery: bool,
+    // Set while the workers drain (see set_drain_mode). Steps that have not started stay put
+    draining: bool,
+    executor_config: ExecutorConfig,
+    // The ETag of each ExecutionPlan as we last pulled or saved it, so that a save only
+    // overwrites the version we read (see save_exec_plan_to_s3)
+    exec_plan_etags: RefCell<Vec<(Uuid, String)>>,
+    // The ExecutionPlans pulled or saved so far in this call, so that reading a plan again (e.g.
+    // in a helper) neither goes back to S3 nor misses our own save
+    exec_plan_cache: RefCell<Vec<ExecutionPlan>>,
 }
 
 impl ExecuteStepMeta {
     pub fn dummy(cur_timestamp: MillisSinceEpoch) -> Self {
-        Self::NoCloudStorage(DummyExecuteStepMeta { cur_timestamp })
+        Self::NoCloudStorage(DummyExecuteStepMeta {
+            cur_timestamp,
+            paper_trade_log: None,
+            broadcast_log: BroadcastLog::default(),
+            rpc_endpoints: Vec::new(),
+            fee_assets: Vec::new(),
+            substrate_proxies: Vec::new(),
+            best_block_chains: Vec::new(),
+            cur_blocks: Vec::new(),
+            hold_delivery: false,
+            draining: false,
+            executor_config: ExecutorConfig::default(),
+        })
     }
 
     // Deliberately named this way so that the user knows (and I remember) these are
     // the supported chains
     pub fn new_for_astar_moonbeam_polkadot(
         cur_timestamp: MillisSinceEpoch,
-        s3_access_key: String,
-        s3_secret_key: String,
-        dynamodb_access_key: String,
-        dynamodb_secret_key: String,
+        storage_credentials: StorageCredentials,
+        deployment_epoch: DeploymentEpoch,
     ) -> Self {
-        let s3_api = S3Api::new(s3_access_key, s3_secret_key);
+        let StorageCredentials {
+            plan_store,
+            claim_table,
+            nonce_table,
+            analytics,
+        } = storage_credentials;
+        let plan_s3_api = S3Api::new(plan_store.access_key, plan_store.secret_key);
+        let analytics_s3_api = S3Api::new(analytics.access_key, analytics.secret_key);
         let exec_plan_assigner = ExecutionPlanAssigner::new(
-            dynamodb_access_key.clone(),
-            dynamodb_secret_key.clone(),
+            claim_table.access_key.clone(),
+            claim_table.secret_key.clone(),
             cur_timestamp,
         );
         let prestart_step_uniqueness_enforcer = PrestartStepUniquenessEnforcer::new(
-            dynamodb_access_key.clone(),
-            dynamodb_secret_key.clone(),
+            claim_table.access_key.clone(),
+            claim_table.secret_key.clone(),
+            cur_timestamp,
+        );
+        let job_lock = JobLock::new(
+            claim_table.access_key,
+            claim_table.secret_key,
             cur_timestamp,
         );
         let chain_nonce_managers = {
             let astar_nonce_manager = NonceManager::new(
-                dynamodb_access_key.clone(),
-                dynamodb_secret_key.clone(),
+                nonce_table.access_key.clone(),
+                nonce_table.secret_key.clone(),
                 "astar",
                 cur_timestamp,
+                deployment_epoch,
             );
             let moonbeam_nonce_manager = NonceManager::new(
-                dynamodb_access_key.clone(),
-                dynamodb_secret_key.clone(),
+                nonce_table.access_key.clone(),
+                nonce_table.secret_key.clone(),
                 "moonbeam",
                 cur_timestamp,
+                deployment_epoch,
             );
             let polkadot_nonce_manager = NonceManager::new(
-                dynamodb_access_key.clone(),
-                dynamodb_secret_key.clone(),
+                nonce_table.access_key.clone(),
+                nonce_table.secret_key.clone(),
                 "polkadot",
                 cur_timestamp,
+                deployment_epoch,
+            );
+            // Only used to claim Wormhole transfers into Ethereum
+            let ethereum_nonce_manager = NonceManager::new(
+                nonce_table.access_key.clone(),
+                nonce_table.secret_key.clone(),
+                "ethereum",
+                cur_timestamp,
+                deployment_epoch,
             );
             vec![
                 (universal_chain_id_registry::ASTAR, astar_nonce_manager),
@@ -114,14 +271,36 @@ impl ExecuteStepMeta {
                     universal_chain_id_registry::POLKADOT,
                     polkadot_nonce_manager,
                 ),
+                (
+                    universal_chain_id_registry::ETHEREUM,
+                    ethereum_nonce_manager,
+                ),
             ]
         };
         Self::WithCloudStorage(LiveExecuteStepMeta {
             cur_timestamp,
-            s3_api,
+            plan_s3_api,
+            analytics_s3_api,
             exec_plan_assigner,
             prestart_step_uniqueness_enforcer,
+            job_lock,
             chain_nonce_managers,
+            escrow_name: None,
+            nonce_pool_size: 0,
+            nonce_pools: NoncePools::default(),
+            paper_trade_log: None,
+            broadcast_log: BroadcastLog::default(),
+            extrinsic_batch_window: None,
+            rpc_endpoints: Vec::new(),
+            fee_assets: Vec::new(),
+            substrate_proxies: Vec::new(),
+            best_block_chains: Vec::new(),
+            cur_blocks: Vec::new(),
+            hold_delivery: false,
+            draining: false,
+            executor_config: ExecutorConfig::default(),
+            exec_plan_etags: RefCell::new(Vec::new()),
+            exec_plan_cache: RefCell::new(Vec::new()),
         })
     }
 
@@ -132,46 +311,1136 @@ impl ExecuteStepMeta {
         }
     }
 
+    // In paper trading mode, txns are signed but recorded (see send_eth_txn and send_extrinsic)
+    // instead of broadcast, and we leave the NonceManagers alone since no nonce is consumed
+    pub fn set_paper_trade(&mut self, enabled: bool) {
+        let paper_trade_log = if enabled {
+            Some(PaperTradeLog::default())
+        } else {
+            None
+        };
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.paper_trade_log = paper_trade_log,
+            Self::WithCloudStorage(live) => live.paper_trade_log = paper_trade_log,
+        }
+    }
+
+    pub fn set_rpc_endpoints(&mut self, rpc_endpoints: Vec<RpcEndpoint>) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.rpc_endpoints = rpc_endpoints,
+            Self::WithCloudStorage(live) => live.rpc_endpoints = rpc_endpoints,
+        }
+    }
+
+    // Steps must get their RPC URL from here rather than from chain_info.rpc_url, so that the
+    // endpoints the admin configured take effect
+    pub fn get_rpc_url(&self, chain_info: &ChainInfo) -> String {
+        let rpc_endpoints = match self {
+            Self::NoCloudStorage(dummy) => &dummy.rpc_endpoints,
+            Self::WithCloudStorage(live) => &live.rpc_endpoints,
+        };
+        get_rpc_url(rpc_endpoints, chain_info)
+    }
+
+    pub fn set_fee_assets(&mut self, fee_assets: Vec<(UniversalChainId, AssetId)>) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.fee_assets = fee_assets,
+            Self::WithCloudStorage(live) => live.fee_assets = fee_assets,
+        }
+    }
+
+    pub fn get_fee_asset(&self, chain_id: &UniversalChainId) -> Option<AssetId> {
+        let fee_assets = match self {
+            Self::NoCloudStorage(dummy) => &dummy.fee_assets,
+            Self::WithCloudStorage(live) => &live.fee_assets,
+        };
+        fee_assets
+            .iter()
+            .find(|(fee_asset_chain_id, _)| fee_asset_chain_id == chain_id)
+            .map(|(_, asset_id)| *asset_id)
+    }
+
+    pub fn set_substrate_proxies(&mut self, substrate_proxies: Vec<SubstrateProxy>) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.substrate_proxies = substrate_proxies,
+            Self::WithCloudStorage(live) => live.substrate_proxies = substrate_proxies,
+        }
+    }
+
+    // Extrinsics sending from src_addr on chain_id must be signed by the proxy's delegate and
+    // wrapped in proxy.proxy (see SubstrateProxy)
+    pub fn get_substrate_proxy(
+        &self,
+        chain_id: &UniversalChainId,
+        src_addr: &UniversalAddress,
+    ) -> Option<&SubstrateProxy> {
+        let substrate_proxies = match self {
+            Self::NoCloudStorage(dummy) => &dummy.substrate_proxies,
+            Self::WithCloudStorage(live) => &live.substrate_proxies,
+        };
+        find_substrate_proxy(substrate_proxies, chain_id, src_addr)
+    }
+
+    pub fn set_best_block_chains(&mut self, best_block_chains: Vec<UniversalChainId>) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.best_block_chains = best_block_chains,
+            Self::WithCloudStorage(live) => live.best_block_chains = best_block_chains,
+        }
+    }
+
+    pub fn get_finality_policy(&self, chain_id: &UniversalChainId) -> FinalityPolicy {
+        let best_block_chains = match self {
+            Self::NoCloudStorage(dummy) => &dummy.best_block_chains,
+            Self::WithCloudStorage(live) => &live.best_block_chains,
+        };
+        if best_block_chains.contains(chain_id) {
+            FinalityPolicy::BestBlock
+        } else {
+            FinalityPolicy::Finalized
+        }
+    }
+
+    // While held, the plan goes as far as it can but does not start the postend escrow to user
+    // transfer
+    pub fn set_hold_delivery(&mut self, hold_delivery: bool) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.hold_delivery = hold_delivery,
+            Self::WithCloudStorage(live) => live.hold_delivery = hold_delivery,
+        }
+    }
+
+    pub fn is_delivery_held(&self) -> bool {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.hold_delivery,
+            Self::WithCloudStorage(live) => live.hold_delivery,
+        }
+    }
+
+    // While draining, steps that have not started stay put so the plan pauses once its in-flight
+    // txns land
+    pub fn set_draining(&mut self, draining: bool) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.draining = draining,
+            Self::WithCloudStorage(live) => live.draining = draining,
+        }
+    }
+
+    pub fn is_draining(&self) -> bool {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.draining,
+            Self::WithCloudStorage(live) => live.draining,
+        }
+    }
+
+    pub fn set_executor_config(&mut self, executor_config: ExecutorConfig) {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.executor_config = executor_config,
+            Self::WithCloudStorage(live) => live.executor_config = executor_config,
+        }
+    }
+
+    pub fn get_executor_config(&self) -> &ExecutorConfig {
+        match self {
+            Self::NoCloudStorage(dummy) => &dummy.executor_config,
+            Self::WithCloudStorage(live) => &live.executor_config,
+        }
+    }
+
+    pub fn get_cur_block(&self, chain_id: &UniversalChainId) -> ExecutableResult<BlockNum> {
+        let cur_blocks = match self {
+            Self::NoCloudStorage(dummy) => &dummy.cur_blocks,
+            Self::WithCloudStorage(live) => &live.cur_blocks,
+        };
+        if let Some((_, cur_block)) = cur_blocks
+            .iter()
+            .find(|(cur_block_chain_id, _)| cur_block_chain_id == chain_id)
+        {
+            return Ok(*cur_block);
+        }
+        let chain_info =
+            get_chain_info_from_chain_id(chain_id).ok_or(ExecutableError::FailedToFindChainInfo)?;
+        BlockNumberProvider::from_chain_info_and_rpc_url(chain_info, self.get_rpc_url(chain_info))
+            .get_block_number(self.get_finality_policy(chain_id))
+            .map_err(|_| ExecutableError::RpcRequestFailed)
+    }
+
+    // Looks up the chains' block numbers concurrently, so that an invocation that needs several
+    // of them (e.g. creating a plan) doesn't wait on each chain in turn. Later get_cur_block
+    // calls for these chains return the prefetched number, so only use this where a block
+    // number read at the start of the invocation is recent enough. Chains whose lookup failed
+    // are left to get_cur_block to retry
+    pub fn prefetch_cur_blocks(&mut self, chain_ids: &[UniversalChainId]) {
+        let mut lookup_chain_ids = Vec::new();
+        let mut providers = Vec::new();
+        for chain_id in chain_ids {
+            if let Some(chain_info) = get_chain_info_from_chain_id(chain_id) {
+                lookup_chain_ids.push(chain_id.clone());
+                providers.push((
+                    BlockNumberProvider::from_chain_info_and_rpc_url(
+                        chain_info,
+                        self.get_rpc_url(chain_info),
+                    ),
+                    self.get_finality_policy(chain_id),
+                ));
+            }
+        }
+        let prefetched: Vec<(UniversalChainId, BlockNum)> = lookup_chain_ids
+            .into_iter()
+            .zip(BlockNumberProvider::get_block_numbers(&providers))
+            .filter_map(|(chain_id, cur_block)| Some((chain_id, cur_block.ok()?)))
+            .collect();
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.cur_blocks = prefetched,
+            Self::WithCloudStorage(live) => live.cur_blocks = prefetched,
+        }
+    }
+
+    // Reads the chain's own clock, unlike cur_timestamp
+    pub fn get_latest_block_timestamp(
+        &self,
+        chain_id: &UniversalChainId,
+    ) -> ExecutableResult<MillisSinceEpoch> {
+        let chain_info =
+            get_chain_info_from_chain_id(chain_id).ok_or(ExecutableError::FailedToFindChainInfo)?;
+        eth_utils::common::latest_block_timestamp_millis(&self.get_rpc_url(chain_info))
+            .map_err(|_| ExecutableError::RpcRequestFailed)
+    }
+
+    // cur_timestamp adjusted by the chain's latest sampled clock skew (see
+    // sample_chain_clock_skews). Use it for anything the chain compares against block.timestamp
+    pub fn get_chain_timestamp(&self, chain_id: &UniversalChainId) -> MillisSinceEpoch {
+        let clock_skews = self.pull_chain_clock_skews_from_s3().unwrap_or_default();
+        get_chain_timestamp(&clock_skews, chain_id, self.cur_timestamp())
+    }
+
+    // Always reads the chain, even when paper trading
+    pub fn get_balance(
+        &self,
+        token: &UniversalTokenId,
+        holder: &UniversalAddress,
+    ) -> ExecutableResult<Amount> {
+        let chain_info = get_chain_info_from_chain_id(&token.chain)
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let rpc_url = self.get_rpc_url(chain_info);
+        match (holder, &token.id) {
+            (UniversalAddress::Ethereum(eth_addr), ChainTokenId::Native) => {
+                eth_utils::common::get_native_balance(&rpc_url, eth_addr.clone())
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            (UniversalAddress::Ethereum(eth_addr), ChainTokenId::ERC20(erc20)) => {
+                eth_utils::erc20_contract::ERC20Contract::new(&rpc_url, erc20.addr.clone())
+                    .and_then(|contract| contract.balance_of(eth_addr.clone()))
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            (UniversalAddress::Ethereum(eth_addr), ChainTokenId::XC20(xc20)) => {
+                eth_utils::erc20_contract::ERC20Contract::new(&rpc_url, xc20.get_eth_address())
+                    .and_then(|contract| contract.balance_of(eth_addr.clone()))
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            (UniversalAddress::Substrate(pubkey), ChainTokenId::Native) => {
+                SubstrateNodeRpcUtils { rpc_url }
+                    .get_free_balance(&pubkey.0)
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            // The substrate escrow only ever holds native tokens
+            (UniversalAddress::Substrate(_), _) => Err(ExecutableError::UnsupportedChain),
+        }
+    }
+
+    // The holder's next nonce on the chain, ignoring the NonceManager
+    pub fn get_system_nonce(
+        &self,
+        chain_id: &UniversalChainId,
+        holder: &UniversalAddress,
+    ) -> ExecutableResult<Nonce> {
+        let chain_info =
+            get_chain_info_from_chain_id(chain_id).ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let rpc_url = self.get_rpc_url(chain_info);
+        match holder {
+            UniversalAddress::Ethereum(eth_addr) => {
+                eth_utils::common::get_next_system_nonce(&rpc_url, eth_addr.clone())
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            UniversalAddress::Substrate(pubkey) => {
+                let ss58_prefix = chain_info
+                    .get_ss58_prefix()
+                    .ok_or(ExecutableError::Ss58AddressFormatNotFound)?;
+                SubstrateNodeRpcUtils { rpc_url }
+                    .get_next_system_nonce(&encode_ss58(&pubkey.0, u16::from(ss58_prefix)))
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+        }
+    }
+
+    pub fn get_token_decimals(&self, token: &UniversalTokenId) -> ExecutableResult<u8> {
+        let chain_info = get_chain_info_from_chain_id(&token.chain)
+            .ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let rpc_url = self.get_rpc_url(chain_info);
+        match &token.id {
+            ChainTokenId::Native if chain_info.evm_chain_id.is_some() => {
+                Ok(NATIVE_TOKEN_DECIMALS as u8)
+            }
+            ChainTokenId::Native => SubstrateNodeRpcUtils { rpc_url }
+                .get_native_token_decimals()
+                .map_err(|_| ExecutableError::RpcRequestFailed),
+            ChainTokenId::ERC20(erc20) => {
+                eth_utils::erc20_contract::ERC20Contract::new(&rpc_url, erc20.addr.clone())
+                    .and_then(|contract| contract.decimals())
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            ChainTokenId::XC20(xc20) => {
+                eth_utils::erc20_contract::ERC20Contract::new(&rpc_url, xc20.get_eth_address())
+                    .and_then(|contract| contract.decimals())
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+        }
+    }
+
+    pub fn is_paper_trading(&self) -> bool {
+        self.paper_trade_log().is_some()
+    }
+
+    fn paper_trade_log(&self) -> Option<&PaperTradeLog> {
+        match self {
+            Self::NoCloudStorage(dummy) => dummy.paper_trade_log.as_ref(),
+            Self::WithCloudStorage(live) => live.paper_trade_log.as_ref(),
+        }
+    }
+
+    fn broadcast_log(&self) -> &BroadcastLog {
+        match self {
+            Self::NoCloudStorage(dummy) => &dummy.broadcast_log,
+            Self::WithCloudStorage(live) => &live.broadcast_log,
+        }
+    }
+
+    // The txns sent since the last call, for the plan's TxnBroadcast events. Paper txns are
+    // never sent, so they are not among them
+    pub fn take_broadcast_txns(&self) -> Vec<BroadcastTxn> {
+        self.broadcast_log().take()
+    }
+
+    // A txn that fails to send may still have reached the node, so it is logged either way
+    fn record_broadcast(
+        &self,
+        exec_step_uuid: &Uuid,
+        chain_kind: TxnChainKind,
+        txn_hash: [u8; 32],
+        raw_txn: Vec<u8>,
+    ) {
+        self.broadcast_log().record(BroadcastTxn {
+            exec_step_uuid: exec_step_uuid.clone(),
+            chain_kind,
+            txn_hash,
+            raw_txn,
+        });
+    }
+
+    pub fn send_eth_txn(
+        &self,
+        exec_step_uuid: &Uuid,
+        chain: UniversalChainId,
+        rpc_url: &str,
+        signed_txn: SignedTransaction,
+    ) -> ExecutableResult<EthTxnHash> {
+        match self.paper_trade_log() {
+            None => {
+                self.record_broadcast(
+                    exec_step_uuid,
+                    TxnChainKind::Ethereum,
+                    signed_txn.transaction_hash.0,
+                    signed_txn.raw_transaction.0.clone(),
+                );
+                eth_utils::common::send_raw_transaction(rpc_url, signed_txn)
+                    .map_err(|_| ExecutableError::RpcRequestFailed)
+            }
+            Some(log) => {
+                let txn_hash = signed_txn.transaction_hash;
+                log.record(PaperTxn {
+                    exec_step_uuid: exec_step_uuid.clone(),
+                    chain,
+                    timestamp_millis: self.cur_timestamp(),
+                    kind: PaperTxnKind::Eth {
+                        txn_hash,
+                        raw_txn: signed_txn.raw_transaction.0,
+                    },
+                });
+                Ok(txn_hash)
+            }
+        }
+    }
+
+    // Sends a txn that was signed before the plan started (see presign)
+    pub fn send_presigned_eth_txn(
+        &self,
+        exec_step_uuid: &Uuid,
+        rpc_url: &str,
+        raw_txn: Vec<u8>,
+        txn_hash: EthTxnHash,
+    ) -> ExecutableResult<EthTxnHash> {
+        self.record_broadcast(
+            exec_step_uuid,
+            TxnChainKind::Ethereum,
+            txn_hash.0,
+            raw_txn.clone(),
+        );
+        eth_utils::common::send_raw_transaction_bytes(rpc_url, raw_txn, txn_hash)
+            .map_err(|_| ExecutableError::RpcRequestFailed)
+    }
+
+    // Extrinsics signed while an extrinsic batch window is open are queued, and only submitted
+    // in submit_extrinsic_batch
+    pub fn open_extrinsic_batch_window(&mut self) {
+        if let Self::WithCloudStorage(live) = self {
+            live.extrinsic_batch_window = Some(ExtrinsicBatchWindow::default());
+        }
+    }
+
+    pub fn is_batching_extrinsics(&self) -> bool {
+        self.extrinsic_batch_window().is_some()
+    }
+
+    fn extrinsic_batch_window(&self) -> Option<&ExtrinsicBatchWindow> {
+        match self {
+            Self::NoCloudStorage(_) => None,
+            Self::WithCloudStorage(live) => live.extrinsic_batch_window.as_ref(),
+        }
+    }
+
+    // Returns the steps whose extrinsics may not have been submitted (see
+    // submit_queued_extrinsics)
+    pub fn submit_extrinsic_batch(&self) -> Vec<Uuid> {
+        match self.extrinsic_batch_window() {
+            Some(window) => submit_queued_extrinsics(window.take()),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn send_extrinsic(
+        &self,
+        exec_step_uuid: &Uuid,
+        chain: UniversalChainId,
+        subutils: &SubstrateNodeRpcUtils,
+        raw_extrinsic: &[u8],
+    ) -> ExecutableResult<SubstrateExtrinsicHash> {
+        match self.paper_trade_log() {
+            None => {
+                // Queued extrinsics are logged as they are queued. The batch that sends them
+                // goes out in the same call
+                self.record_broadcast(
+                    exec_step_uuid,
+                    TxnChainKind::Substrate,
+                    sp_core_hashing::blake2_256(raw_extrinsic),
+                    raw_extrinsic.to_vec(),
+                );
+                match self.extrinsic_batch_window() {
+                    Some(window) => {
+                        Ok(window.queue(exec_step_uuid, chain, &subutils.rpc_url, raw_extrinsic))
+                    }
+                    None => subutils
+                        .send_extrinsic(raw_extrinsic)
+                        .map_err(|_| ExecutableError::RpcRequestFailed),
+                }
+            }
+            Some(log) => {
+                // Same hash that author_submitExtrinsic would have returned
+                let extrinsic_hash =
+                    SubstrateExtrinsicHash::from(sp_core_hashing::blake2_256(raw_extrinsic));
+                log.record(PaperTxn {
+                    exec_step_uuid: exec_step_uuid.clone(),
+                    chain,
+                    timestamp_millis: self.cur_timestamp(),
+                    kind: PaperTxnKind::SubstrateExtrinsic {
+                        extrinsic_hash,
+                        raw_extrinsic: raw_extrinsic.to_vec(),
+                    },
+                });
+                Ok(extrinsic_hash)
+            }
+        }
+    }
+
+    pub fn pull_paper_txns_from_s3(
+        &self,
+        exec_plan_uuid: &Uuid,
+    ) -> ExecutableResult<Vec<PaperTxn>> {
+        // The object does not exist until the plan's first txn
+        self.pull_analytics_object_or_default(get_paper_txns_object_key(exec_plan_uuid))
+    }
+
+    // Appends the txns recorded since the last call to the plan's paper txns
+    pub fn save_paper_txns_to_s3(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<()> {
+        let new_txns = match self.paper_trade_log() {
+            Some(log) => log.take(),
+            None => return Ok(()),
+        };
+        if new_txns.is_empty() {
+            return Ok(());
+        }
+        // If the read fails, saving would overwrite the txns already recorded
+        let mut txns = self.pull_paper_txns_from_s3(exec_plan_uuid)?;
+        txns.extend(new_txns);
+        self.save_analytics_object(get_paper_txns_object_key(exec_plan_uuid), &txns.encode())
+    }
+
+    // Only overwrites the version of the plan that we pulled (if we pulled it), else fails with
+    // VersionConflict. The caller should then pull the plan again and merge in its changes
+    // (see plan_merge). Plans we never pulled, i.e. new ones, are saved unconditionally
     pub fn save_exec_plan_to_s3(&self, exec_plan: &ExecutionPlan) -> ExecutableResult<()> {
         match self {
             Self::NoCloudStorage(_) => Ok(()),
             Self::WithCloudStorage(live) => {
                 let object_key = exec_plan.uuid.to_hex_string();
                 let bucket_name = "execution-plan".to_string();
-                live.s3_api
-                    .put_object_raw(
+                let prev_etag = live.get_exec_plan_etag(&exec_plan.uuid);
+                let put_res = live.plan_s3_api.put_object_raw_if_match(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    object_key,
+                    bucket_name,
+                    "us-east-1".to_string(),
+                    &encode_stored_plan(exec_plan),
+                    prev_etag.as_deref(),
+                );
+                match put_res {
+                    Ok((_, etag)) => {
+                        live.set_exec_plan_etag(&exec_plan.uuid, etag);
+                        live.set_cached_exec_plan(exec_plan.clone());
+                        Ok(())
+                    }
+                    Err(e) => {
+                        // We no longer know what S3 holds, so the next pull must go there
+                        live.evict_cached_exec_plan(&exec_plan.uuid);
+                        Err(match e {
+                            S3Error::PreconditionFailed => ExecutableError::VersionConflict,
+                            _ => ExecutableError::FailedToSaveToS3,
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pull_exec_plan_from_s3(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<ExecutionPlan> {
+        match self {
+            Self::NoCloudStorage(_) => Err(ExecutableError::FailedToPullFromS3),
+            Self::WithCloudStorage(live) => {
+                if let Some(exec_plan) = live.get_cached_exec_plan(exec_plan_uuid) {
+                    return Ok(exec_plan);
+                }
+                let object_key = exec_plan_uuid.to_hex_string();
+                let bucket_name = "execution-plan".to_string();
+                let (exec_plan_bytes, etag) = live
+                    .plan_s3_api
+                    .get_object_raw_with_etag(
                         live.cur_timestamp,
                         "storj".to_string(),
                         object_key,
                         bucket_name,
                         "us-east-1".to_string(),
-                        &exec_plan.encode(),
                     )
-                    .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(()))
+                    .map_err(|_| ExecutableError::FailedToPullFromS3)?;
+                live.set_exec_plan_etag(exec_plan_uuid, etag);
+                // Migrates plans saved in the V0 layout. Strict, since a plan written by a
+                // newer schema can decode from a prefix of its bytes and would then be saved back
+                // without the rest
+                let exec_plan = decode_stored_plan(&exec_plan_bytes).map_err(|e| match e {
+                    SchemaCheckError::UnknownVersion(_) => {
+                        ExecutableError::UnknownStoredPlanVersion
+                    }
+                    _ => ExecutableError::FailedToDeserializeFromS3,
+                })?;
+                live.set_cached_exec_plan(exec_plan.clone());
+                Ok(exec_plan)
             }
         }
     }
 
-    pub fn pull_exec_plan_from_s3(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<ExecutionPlan> {
+    // Copies the plan's bytes into the execution-plan bucket's dead-letter prefix. The original
+    // object is left in place
+    pub fn quarantine_exec_plan_in_s3(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<()> {
         match self {
-            Self::NoCloudStorage(_) => Err(ExecutableError::FailedToPullFromS3),
+            Self::NoCloudStorage(_) => Ok(()),
             Self::WithCloudStorage(live) => {
-                let object_key = exec_plan_uuid.to_hex_string();
                 let bucket_name = "execution-plan".to_string();
                 let exec_plan_bytes = live
-                    .s3_api
+                    .plan_s3_api
                     .get_object_raw(
                         live.cur_timestamp,
                         "storj".to_string(),
-                        object_key,
+                        exec_plan_uuid.to_hex_string(),
+                        bucket_name.clone(),
+                        "us-east-1".to_string(),
+                    )
+                    .map_err(|_| ExecutableError::FailedToPullFromS3)?;
+                live.plan_s3_api
+                    .put_object_raw(
+                        live.cur_timestamp,
+                        "storj".to_string(),
+                        format!(
+                            "{}/{}",
+                            DEAD_LETTER_OBJECT_KEY_PREFIX,
+                            exec_plan_uuid.to_hex_string()
+                        ),
                         bucket_name,
                         "us-east-1".to_string(),
+                        &exec_plan_bytes,
+                    )
+                    .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(()))
+            }
+        }
+    }
+
+    // Lives in the analytics bucket alongside other stats derived from executed steps.
+    // Concurrent workers may overwrite each other's samples, which only costs us a few
+    // calibration points
+    pub fn pull_xcm_weight_calibration_from_s3(&self) -> ExecutableResult<XcmWeightCalibration> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(XcmWeightCalibration::default()),
+            Self::WithCloudStorage(live) => {
+                let calibration_bytes = live
+                    .analytics_s3_api
+                    .get_object_raw(
+                        live.cur_timestamp,
+                        "storj".to_string(),
+                        XCM_WEIGHT_CALIBRATION_OBJECT_KEY.to_string(),
+                        ANALYTICS_BUCKET_NAME.to_string(),
+                        "us-east-1".to_string(),
                     )
                     .map_err(|_| ExecutableError::FailedToPullFromS3)?;
-                ExecutionPlan::decode(&mut exec_plan_bytes.as_slice()).map_or_else(
-                    |_| Err(ExecutableError::FailedToDeserializeFromS3),
-                    |exec_plan| Ok(exec_plan),
+                XcmWeightCalibration::decode(&mut calibration_bytes.as_slice())
+                    .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+            }
+        }
+    }
+
+    pub fn save_xcm_weight_calibration_to_s3(
+        &self,
+        calibration: &XcmWeightCalibration,
+    ) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => live
+                .analytics_s3_api
+                .put_object_raw(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    XCM_WEIGHT_CALIBRATION_OBJECT_KEY.to_string(),
+                    ANALYTICS_BUCKET_NAME.to_string(),
+                    "us-east-1".to_string(),
+                    &calibration.encode(),
+                )
+                .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(())),
+        }
+    }
+
+    // Same caveats as the weight calibration. Every quote reads it, so only a missing object
+    // reads as uncalibrated
+    pub fn pull_xcm_bridge_fee_calibration_from_s3(
+        &self,
+    ) -> ExecutableResult<XcmBridgeFeeCalibration> {
+        self.pull_analytics_object_or_default(XCM_BRIDGE_FEE_CALIBRATION_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_xcm_bridge_fee_calibration_to_s3(
+        &self,
+        calibration: &XcmBridgeFeeCalibration,
+    ) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => live
+                .analytics_s3_api
+                .put_object_raw(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    XCM_BRIDGE_FEE_CALIBRATION_OBJECT_KEY.to_string(),
+                    ANALYTICS_BUCKET_NAME.to_string(),
+                    "us-east-1".to_string(),
+                    &calibration.encode(),
                 )
+                .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(())),
+        }
+    }
+
+    // Unlike the calibration, losing an update here loses track of funds, so callers must
+    // hold claim_stranded_funds_ledger while they read-modify-write it
+    pub fn pull_stranded_funds_ledger_from_s3(&self) -> ExecutableResult<StrandedFundsLedger> {
+        self.pull_analytics_object_or_default(STRANDED_FUNDS_LEDGER_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_stranded_funds_ledger_to_s3(
+        &self,
+        ledger: &StrandedFundsLedger,
+    ) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => live
+                .analytics_s3_api
+                .put_object_raw(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    STRANDED_FUNDS_LEDGER_OBJECT_KEY.to_string(),
+                    ANALYTICS_BUCKET_NAME.to_string(),
+                    "us-east-1".to_string(),
+                    &ledger.encode(),
+                )
+                .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(())),
+        }
+    }
+
+    // Closed plans that have not been rolled into DailyStats yet
+    pub fn pull_pending_plan_analytics_from_s3(&self) -> ExecutableResult<Vec<PlanAnalytics>> {
+        self.pull_analytics_object_or_default(PENDING_PLAN_ANALYTICS_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_pending_plan_analytics_to_s3(
+        &self,
+        plans: &[PlanAnalytics],
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            PENDING_PLAN_ANALYTICS_OBJECT_KEY.to_string(),
+            &plans.encode(),
+        )
+    }
+
+    // Unlike the pending queue, kept after aggregation so get_execution_price can look it up
+    pub fn pull_plan_analytics_record_from_s3(
+        &self,
+        exec_plan_uuid: &Uuid,
+    ) -> ExecutableResult<PlanAnalytics> {
+        let bytes = self.pull_analytics_object(get_plan_analytics_object_key(exec_plan_uuid))?;
+        PlanAnalytics::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_plan_analytics_record_to_s3(
+        &self,
+        plan_analytics: &PlanAnalytics,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_plan_analytics_object_key(&plan_analytics.exec_plan_uuid),
+            &plan_analytics.encode(),
+        )
+    }
+
+    pub fn pull_daily_stats_from_s3(&self, day: DaysSinceEpoch) -> ExecutableResult<DailyStats> {
+        let bytes = self.pull_analytics_object(get_daily_stats_object_key(day))?;
+        DailyStats::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_daily_stats_to_s3(&self, daily_stats: &DailyStats) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_daily_stats_object_key(daily_stats.day),
+            &daily_stats.encode(),
+        )
+    }
+
+    // Closed plans that have not been shadow quoted yet. Updated under the plan analytics lock
+    pub fn pull_pending_shadow_quotes_from_s3(&self) -> ExecutableResult<Vec<PlanAnalytics>> {
+        self.pull_analytics_object_or_default(PENDING_SHADOW_QUOTES_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_pending_shadow_quotes_to_s3(
+        &self,
+        plans: &[PlanAnalytics],
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            PENDING_SHADOW_QUOTES_OBJECT_KEY.to_string(),
+            &plans.encode(),
+        )
+    }
+
+    pub fn pull_shadow_quote_stats_from_s3(
+        &self,
+        day: DaysSinceEpoch,
+    ) -> ExecutableResult<ShadowQuoteStats> {
+        let bytes = self.pull_analytics_object(get_shadow_quote_stats_object_key(day))?;
+        ShadowQuoteStats::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_shadow_quote_stats_to_s3(&self, stats: &ShadowQuoteStats) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_shadow_quote_stats_object_key(stats.day),
+            &stats.encode(),
+        )
+    }
+
+    // Updated under the plan analytics lock
+    pub fn pull_step_durations_from_s3(&self) -> ExecutableResult<StepDurationSamples> {
+        self.pull_analytics_object_or_default(STEP_DURATIONS_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_step_durations_to_s3(
+        &self,
+        step_durations: &StepDurationSamples,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            STEP_DURATIONS_OBJECT_KEY.to_string(),
+            &step_durations.encode(),
+        )
+    }
+
+    // Updated under the plan analytics lock
+    pub fn pull_route_blacklist_from_s3(&self) -> ExecutableResult<RouteBlacklist> {
+        self.pull_analytics_object_or_default(ROUTE_BLACKLIST_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_route_blacklist_to_s3(
+        &self,
+        route_blacklist: &RouteBlacklist,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            ROUTE_BLACKLIST_OBJECT_KEY.to_string(),
+            &route_blacklist.encode(),
+        )
+    }
+
+    // Also updated under the plan analytics lock
+    pub fn pull_allowance_cache_from_s3(&self) -> ExecutableResult<AllowanceCache> {
+        self.pull_analytics_object_or_default(ALLOWANCE_CACHE_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_allowance_cache_to_s3(
+        &self,
+        allowance_cache: &AllowanceCache,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            ALLOWANCE_CACHE_OBJECT_KEY.to_string(),
+            &allowance_cache.encode(),
+        )
+    }
+
+    // Updated under the canary ledger lock
+    pub fn pull_canary_ledger_from_s3(&self) -> ExecutableResult<CanaryLedger> {
+        self.pull_analytics_object_or_default(CANARY_LEDGER_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_canary_ledger_to_s3(&self, ledger: &CanaryLedger) -> ExecutableResult<()> {
+        self.save_analytics_object(CANARY_LEDGER_OBJECT_KEY.to_string(), &ledger.encode())
+    }
+
+    pub fn claim_canary_ledger(&self) -> bool /* didClaimSuccessfully */ {
+        self.acquire_job_lock(LockedJob::CanaryLedger)
+    }
+
+    pub fn release_canary_ledger(&self) -> ExecutableResult<()> {
+        self.release_job_lock(LockedJob::CanaryLedger)
+    }
+
+    pub fn claim_plan_analytics(&self) -> bool /* didClaimSuccessfully */ {
+        self.acquire_job_lock(LockedJob::PlanAnalytics)
+    }
+
+    pub fn release_plan_analytics(&self) -> ExecutableResult<()> {
+        self.release_job_lock(LockedJob::PlanAnalytics)
+    }
+
+    // Each batch of events is its own object since S3 has no append
+    pub fn save_plan_events_to_s3(&self, events: &[PlanEvent]) -> ExecutableResult<()> {
+        let mut res = Ok(());
+        for _ in 0..PLAN_EVENTS_SAVE_ATTEMPTS {
+            res = self.try_save_plan_events_to_s3(events);
+            if res.is_ok() {
+                break;
+            }
+        }
+        res
+    }
+
+    fn try_save_plan_events_to_s3(&self, events: &[PlanEvent]) -> ExecutableResult<()> {
+        match events.first() {
+            None => Ok(()),
+            Some(first_event) => {
+                let ndjson = to_ndjson(events);
+                self.save_analytics_object(
+                    get_plan_events_object_key(
+                        &first_event.exec_plan_uuid,
+                        first_event.timestamp_millis,
+                    ),
+                    ndjson.as_bytes(),
+                )?;
+                self.append_to_plan_event_log(first_event, &ndjson)
+            }
+        }
+    }
+
+    // Returns the bundle's object key
+    pub fn save_failure_bundle_to_s3(&self, bundle: &FailureBundle) -> ExecutableResult<String> {
+        let object_key =
+            get_failure_bundle_object_key(&bundle.exec_plan.uuid, bundle.created_millis);
+        self.save_analytics_object(object_key.clone(), &bundle.encode())?;
+        Ok(object_key)
+    }
+
+    pub fn pull_plan_event_log_from_s3(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<String> {
+        let bytes = self.pull_analytics_object(get_plan_event_log_object_key(exec_plan_uuid))?;
+        String::from_utf8(bytes).map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    // Only the worker that claimed the plan writes its events (the stuck plan scan aside), so
+    // the read-modify-write is safe. If the pull fails we skip the batch rather than start the
+    // log over, since only a Created batch can start it
+    fn append_to_plan_event_log(
+        &self,
+        first_event: &PlanEvent,
+        ndjson: &str,
+    ) -> ExecutableResult<()> {
+        let object_key = get_plan_event_log_object_key(&first_event.exec_plan_uuid);
+        let mut event_log = match first_event.kind {
+            PlanEventKind::Created { .. } => Vec::new(),
+            _ => self.pull_analytics_object(object_key.clone())?,
+        };
+        // An earlier attempt may have saved it and still reported a failure
+        if !event_log.ends_with(ndjson.as_bytes()) {
+            event_log.extend_from_slice(ndjson.as_bytes());
+        }
+        self.save_analytics_object(object_key, &event_log)
+    }
+
+    pub fn pull_pair_cache_from_s3(&self) -> ExecutableResult<GraphSnapshot> {
+        let bytes = self.pull_analytics_object(PAIR_CACHE_OBJECT_KEY.to_string())?;
+        GraphSnapshot::from_bytes(&bytes).map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_pair_cache_to_s3(&self, pair_cache: &GraphSnapshot) -> ExecutableResult<()> {
+        self.save_analytics_object(PAIR_CACHE_OBJECT_KEY.to_string(), &pair_cache.to_bytes())
+    }
+
+    // Same version check as the archives, since a pinned quote may outlive a version bump
+    pub fn pull_pinned_snapshot_from_s3(
+        &self,
+        snapshot_id: &SnapshotId,
+    ) -> ExecutableResult<GraphSnapshot> {
+        let bytes = self.pull_analytics_object(get_pinned_snapshot_object_key(snapshot_id))?;
+        GraphSnapshot::from_bytes(&bytes).map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_pinned_snapshot_to_s3(
+        &self,
+        snapshot_id: &SnapshotId,
+        snapshot: &GraphSnapshot,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_pinned_snapshot_object_key(snapshot_id),
+            &snapshot.to_bytes(),
+        )
+    }
+
+    // Archives from before a GRAPH_SNAPSHOT_VERSION bump can't be turned into a graph anymore
+    pub fn pull_pair_cache_archive_from_s3(
+        &self,
+        slot: ArchiveSlot,
+    ) -> ExecutableResult<ArchivedPairCache> {
+        let bytes = self.pull_analytics_object(get_pair_cache_archive_object_key(slot))?;
+        ArchivedPairCache::decode(&mut bytes.as_slice())
+            .ok()
+            .filter(|archive| archive.pair_cache.version == GRAPH_SNAPSHOT_VERSION)
+            .ok_or(ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_pair_cache_archive_to_s3(
+        &self,
+        slot: ArchiveSlot,
+        archive: &ArchivedPairCache,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(get_pair_cache_archive_object_key(slot), &archive.encode())
+    }
+
+    pub fn pull_liquidity_summary_from_s3(&self) -> ExecutableResult<LiquiditySummary> {
+        let bytes = self.pull_analytics_object(LIQUIDITY_SUMMARY_OBJECT_KEY.to_string())?;
+        LiquiditySummary::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_liquidity_summary_to_s3(&self, summary: &LiquiditySummary) -> ExecutableResult<()> {
+        self.save_analytics_object(LIQUIDITY_SUMMARY_OBJECT_KEY.to_string(), &summary.encode())
+    }
+
+    // Chains that reconcile_reserves found short. Nothing saved yet means none are paused
+    // No chain is paused until reconcile_reserves first saves the set
+    pub fn pull_paused_chains_from_s3(&self) -> ExecutableResult<Vec<UniversalChainId>> {
+        self.pull_analytics_object_or_default(PAUSED_CHAINS_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_paused_chains_to_s3(
+        &self,
+        paused_chains: &[UniversalChainId],
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            PAUSED_CHAINS_OBJECT_KEY.to_string(),
+            &paused_chains.encode(),
+        )
+    }
+
+    pub fn pull_chain_clock_skews_from_s3(&self) -> ExecutableResult<Vec<ChainClockSkew>> {
+        self.pull_analytics_object_or_default(CHAIN_CLOCK_SKEWS_OBJECT_KEY.to_string())
+    }
+
+    pub fn save_chain_clock_skews_to_s3(
+        &self,
+        clock_skews: &[ChainClockSkew],
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            CHAIN_CLOCK_SKEWS_OBJECT_KEY.to_string(),
+            &clock_skews.encode(),
+        )
+    }
+
+    pub fn pull_remark_deposit_request_from_s3(
+        &self,
+        remark: &DepositRemark,
+    ) -> ExecutableResult<RemarkDepositRequest> {
+        let bytes = self.pull_analytics_object(get_remark_deposit_object_key(remark))?;
+        RemarkDepositRequest::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_remark_deposit_request_to_s3(
+        &self,
+        request: &RemarkDepositRequest,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_remark_deposit_object_key(&request.remark),
+            &request.encode(),
+        )
+    }
+
+    pub fn pull_payment_request_from_s3(
+        &self,
+        invoice_id: &InvoiceId,
+    ) -> ExecutableResult<PaymentRequest> {
+        let bytes = self.pull_analytics_object(get_payment_request_object_key(invoice_id))?;
+        PaymentRequest::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_payment_request_to_s3(&self, request: &PaymentRequest) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_payment_request_object_key(&request.invoice_id),
+            &request.encode(),
+        )
+    }
+
+    pub fn pull_relayer_payout_from_s3(
+        &self,
+        worker: &[u8; 32],
+    ) -> ExecutableResult<RelayerPayout> {
+        let bytes = self.pull_analytics_object(get_relayer_payout_object_key(worker))?;
+        RelayerPayout::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_relayer_payout_to_s3(&self, payout: &RelayerPayout) -> ExecutableResult<()> {
+        self.save_analytics_object(
+            get_relayer_payout_object_key(&payout.worker),
+            &payout.encode(),
+        )
+    }
+
+    pub fn pull_destination_proof_from_s3(
+        &self,
+        dest_addr: &EthAddress,
+    ) -> ExecutableResult<DestinationProof> {
+        let bytes = self.pull_analytics_object(get_destination_proof_object_key(dest_addr))?;
+        DestinationProof::decode(&mut bytes.as_slice())
+            .map_err(|_| ExecutableError::FailedToDeserializeFromS3)
+    }
+
+    pub fn save_destination_proof_to_s3(
+        &self,
+        dest_addr: &EthAddress,
+        proof: &DestinationProof,
+    ) -> ExecutableResult<()> {
+        self.save_analytics_object(get_destination_proof_object_key(dest_addr), &proof.encode())
+    }
+
+    fn pull_analytics_object(&self, object_key: String) -> ExecutableResult<Vec<u8>> {
+        match self {
+            Self::NoCloudStorage(_) => Err(ExecutableError::FailedToPullFromS3),
+            Self::WithCloudStorage(live) => live
+                .analytics_s3_api
+                .get_object_raw(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    object_key,
+                    ANALYTICS_BUCKET_NAME.to_string(),
+                    "us-east-1".to_string(),
+                )
+                .map_err(|e| match e {
+                    S3Error::NotFound => ExecutableError::S3ObjectNotFound,
+                    _ => ExecutableError::FailedToPullFromS3,
+                }),
+        }
+    }
+
+    // For the objects that only exist once they are first saved, e.g. a ledger: a missing
+    // object reads as empty, but any other error is returned. Callers save what they read back,
+    // so reading a transient error as empty would overwrite the object with an empty one
+    fn pull_analytics_object_or_default<T: Decode + Default>(
+        &self,
+        object_key: String,
+    ) -> ExecutableResult<T> {
+        match self.pull_analytics_object(object_key) {
+            Ok(bytes) => T::decode(&mut bytes.as_slice())
+                .map_err(|_| ExecutableError::FailedToDeserializeFromS3),
+            Err(ExecutableError::S3ObjectNotFound) => Ok(T::default()),
+            // Nothing is ever saved either
+            Err(_) if matches!(self, Self::NoCloudStorage(_)) => Ok(T::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save_analytics_object(&self, object_key: String, bytes: &[u8]) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => live
+                .analytics_s3_api
+                .put_object_raw(
+                    live.cur_timestamp,
+                    "storj".to_string(),
+                    object_key,
+                    ANALYTICS_BUCKET_NAME.to_string(),
+                    "us-east-1".to_string(),
+                    bytes,
+                )
+                .map_or_else(|_| Err(ExecutableError::FailedToSaveToS3), |_| Ok(())),
+        }
+    }
+
+    pub fn claim_stranded_funds_ledger(&self) -> bool /* didClaimSuccessfully */ {
+        self.acquire_job_lock(LockedJob::StrandedFundsLedger)
+    }
+
+    pub fn release_stranded_funds_ledger(&self) -> ExecutableResult<()> {
+        self.release_job_lock(LockedJob::StrandedFundsLedger)
+    }
+
+    fn acquire_job_lock(&self, job: LockedJob) -> bool /* didClaimSuccessfully */ {
+        match self {
+            Self::NoCloudStorage(_) => true,
+            Self::WithCloudStorage(live) => live.job_lock.attempt_acquire(job) == Ok(true),
+        }
+    }
+
+    fn release_job_lock(&self, job: LockedJob) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => live
+                .job_lock
+                .release(job)
+                .map_err(|_| ExecutableError::FailedToUpdateDynamoDb),
+        }
+    }
+
+    // Claims made after this get the worker's affinity (see ExecutionPlanAssigner), and the
+    // claims and nonce reservations are signed with the worker's identity key
+    pub fn set_worker(&mut self, worker: [u8; 32]) {
+        if let Self::WithCloudStorage(live) = self {
+            live.exec_plan_assigner.set_worker(worker);
+            for (_, nonce_man) in live.chain_nonce_managers.iter_mut() {
+                nonce_man.set_worker(worker);
             }
         }
     }
@@ -192,12 +1461,17 @@ impl ExecuteStepMeta {
         }
     }
 
-    pub fn unclaim_exec_plan(&self, exec_plan_uuid: &Uuid) -> ExecutableResult<()> {
+    // Workers listing the due plans skip this one until next_poll_after_millis
+    pub fn unclaim_exec_plan(
+        &self,
+        exec_plan_uuid: &Uuid,
+        next_poll_after_millis: MillisSinceEpoch,
+    ) -> ExecutableResult<()> {
         match self {
             Self::NoCloudStorage(_) => Ok(()),
             Self::WithCloudStorage(live) => live
                 .exec_plan_assigner
-                .unallocate_exec_plan(exec_plan_uuid)
+                .unallocate_exec_plan(exec_plan_uuid, next_poll_after_millis)
                 .map_err(|_| ExecutableError::FailedToUpdateDynamoDb),
         }
     }
@@ -230,6 +1504,9 @@ impl ExecuteStepMeta {
         cur_block: BlockNum,
         system_nonce: Nonce,
     ) -> ExecutableResult<Nonce> {
+        if self.is_paper_trading() {
+            return Ok(system_nonce);
+        }
         match self {
             Self::NoCloudStorage(_) => Ok(system_nonce),
             Self::WithCloudStorage(live) => {
@@ -246,6 +1523,9 @@ impl ExecuteStepMeta {
         exec_step_uuid: &Uuid,
         src_chain: UniversalChainId,
     ) -> ExecutableResult<()> {
+        if self.is_paper_trading() {
+            return Ok(());
+        }
         match self {
             Self::NoCloudStorage(_) => Ok(()),
             Self::WithCloudStorage(live) => {
@@ -253,7 +1533,7 @@ impl ExecuteStepMeta {
                 // We could have passed in cur_block but it makes the interface needlessly complex,
                 // so we just compute it again here. Note: that may mean that we store +-1 in our
                 // database, which is fine
-                let cur_block = get_cur_block(&src_chain)?;
+                let cur_block = self.get_cur_block(&src_chain)?;
                 nonce_man
                     .finalize_execstep(exec_step_uuid, cur_block)
                     .map_err(|_| ExecutableError::FailedToUpdateDynamoDb)
@@ -266,6 +1546,9 @@ impl ExecuteStepMeta {
         exec_step_uuid: &Uuid,
         src_chain: UniversalChainId,
     ) -> ExecutableResult<()> {
+        if self.is_paper_trading() {
+            return Ok(());
+        }
         match self {
             Self::NoCloudStorage(_) => Ok(()),
             Self::WithCloudStorage(live) => {
@@ -277,6 +1560,115 @@ impl ExecuteStepMeta {
         }
     }
 
+    // Plans of a named escrow take their nonces from that escrow's nonce state. The pools reserved
+    // for the previous escrow are released first, since they belong to its nonce state
+    pub fn set_escrow_name(&mut self, escrow_name: Option<&str>) {
+        let is_same_escrow = match self {
+            Self::NoCloudStorage(_) => return,
+            Self::WithCloudStorage(live) => live.escrow_name.as_deref() == escrow_name,
+        };
+        if is_same_escrow {
+            return;
+        }
+        let _ = self.release_nonce_pools();
+        if let Self::WithCloudStorage(live) = self {
+            for (_, nonce_man) in live.chain_nonce_managers.iter_mut() {
+                nonce_man.set_escrow_name(escrow_name);
+            }
+            live.escrow_name = escrow_name.map(|escrow_name| escrow_name.to_string());
+        }
+    }
+
+    pub fn set_nonce_pool_size(&mut self, nonce_pool_size: u8) {
+        if let Self::WithCloudStorage(live) = self {
+            live.nonce_pool_size = nonce_pool_size.min(MAX_NONCE_POOL_SIZE);
+        }
+    }
+
+    // Same as get_nonce when nonce pools are disabled. Otherwise the nonce comes from a block
+    // reserved for the (chain, signer), and the system nonce is only fetched to reserve a
+    // block. Call release_nonce_pools once the invocation is done with its steps
+    pub fn get_pooled_nonce(
+        &self,
+        exec_step_uuid: &Uuid,
+        src_chain: UniversalChainId,
+        signer: &UniversalAddress,
+        cur_block: BlockNum,
+        get_system_nonce: impl FnOnce() -> ExecutableResult<Nonce>,
+    ) -> ExecutableResult<Nonce> {
+        let live = match self {
+            Self::WithCloudStorage(live)
+                if live.nonce_pool_size > 0 && live.paper_trade_log.is_none() =>
+            {
+                live
+            }
+            _ => return self.get_nonce(exec_step_uuid, src_chain, cur_block, get_system_nonce()?),
+        };
+        let nonce_man = Self::get_nonce_manager(live, src_chain)?;
+        let (pool_id, nonce) = match live.nonce_pools.take_nonce(src_chain, signer) {
+            Some(pooled_nonce) => pooled_nonce,
+            None => {
+                let system_nonce = get_system_nonce()?;
+                // A step reserves at most one block, so its UUID doubles as the block's
+                let pool_id = exec_step_uuid.clone();
+                let pool_size = Nonce::from(live.nonce_pool_size);
+                match nonce_man.reserve_nonce_block(&pool_id, cur_block, system_nonce, pool_size) {
+                    Ok(first_nonce) => {
+                        live.nonce_pools.add_pool(
+                            src_chain,
+                            signer.clone(),
+                            pool_id,
+                            first_nonce,
+                            pool_size,
+                        );
+                        live.nonce_pools
+                            .take_nonce(src_chain, signer)
+                            .ok_or(ExecutableError::FailedToGetNonce)?
+                    }
+                    // e.g. there are dropped nonces, which must be reused before new ones
+                    Err(_) => {
+                        return nonce_man
+                            .get_nonce(exec_step_uuid, cur_block, system_nonce)
+                            .map_err(|_| ExecutableError::FailedToGetNonce)
+                    }
+                }
+            }
+        };
+        if nonce_man
+            .assign_pooled_nonce(exec_step_uuid, &pool_id, nonce, cur_block)
+            .is_ok()
+        {
+            return Ok(nonce);
+        }
+        // Most likely the step was assigned a nonce in an earlier invocation
+        live.nonce_pools.return_nonce(&pool_id, nonce);
+        nonce_man
+            .get_existing_assignment(exec_step_uuid)
+            .map_err(|_| ExecutableError::FailedToGetNonce)
+    }
+
+    // Nonces that were reserved but not handed out become dropped nonces, which the next steps
+    // on the chain reuse. Exhausted blocks must be released too
+    pub fn release_nonce_pools(&self) -> ExecutableResult<()> {
+        match self {
+            Self::NoCloudStorage(_) => Ok(()),
+            Self::WithCloudStorage(live) => {
+                let mut res = Ok(());
+                for pool in live.nonce_pools.take_all() {
+                    // Keep going so that one failure does not hold back the other blocks
+                    let release_res =
+                        Self::get_nonce_manager(live, pool.chain).and_then(|nonce_man| {
+                            nonce_man
+                                .release_nonce_block(&pool.pool_id, &pool.unused_nonces)
+                                .map_err(|_| ExecutableError::FailedToUpdateDynamoDb)
+                        });
+                    res = res.and(release_res);
+                }
+                res
+            }
+        }
+    }
+
     fn get_nonce_manager(
         live: &LiveExecuteStepMeta,
         chain_id: UniversalChainId,
@@ -306,24 +1698,102 @@ impl ExecuteStepMeta {
             }
         }
     }
+
+    // Remarks go in the same set as the prestart txn hashes. We register the hash that
+    // System.Remarked reports, which can't collide with a txn hash
+    pub fn register_prestart_remark(&self, remark: &DepositRemark) -> bool /* is prestartRemarkNew */
+    {
+        self.register_prestart_txn_hash(&EthTxnHash::from(get_remark_hash(remark)))
+    }
+}
+
+impl LiveExecuteStepMeta {
+    fn get_exec_plan_etag(&self, exec_plan_uuid: &Uuid) -> Option<String> {
+        self.exec_plan_etags
+            .borrow()
+            .iter()
+            .find(|(uuid, _)| uuid == exec_plan_uuid)
+            .map(|(_, etag)| etag.clone())
+    }
+
+    // A missing ETag (e.g. the gateway didn't send one) also forgets the old one, which would
+    // fail every later save
+    fn set_exec_plan_etag(&self, exec_plan_uuid: &Uuid, etag: Option<String>) {
+        let mut etags = self.exec_plan_etags.borrow_mut();
+        etags.retain(|(uuid, _)| uuid != exec_plan_uuid);
+        if let Some(etag) = etag {
+            etags.push((exec_plan_uuid.clone(), etag));
+        }
+    }
+
+    fn get_cached_exec_plan(&self, exec_plan_uuid: &Uuid) -> Option<ExecutionPlan> {
+        self.exec_plan_cache
+            .borrow()
+            .iter()
+            .find(|exec_plan| &exec_plan.uuid == exec_plan_uuid)
+            .cloned()
+    }
+
+    fn set_cached_exec_plan(&self, exec_plan: ExecutionPlan) {
+        self.evict_cached_exec_plan(&exec_plan.uuid);
+        self.exec_plan_cache.borrow_mut().push(exec_plan);
+    }
+
+    fn evict_cached_exec_plan(&self, exec_plan_uuid: &Uuid) {
+        self.exec_plan_cache
+            .borrow_mut()
+            .retain(|exec_plan| &exec_plan.uuid != exec_plan_uuid);
+    }
+}
+
+fn get_daily_stats_object_key(day: DaysSinceEpoch) -> String {
+    format!("daily-stats-{}", day)
+}
+
+fn get_plan_analytics_object_key(exec_plan_uuid: &Uuid) -> String {
+    format!("plan-analytics-{}", exec_plan_uuid.to_hex_string())
+}
+
+fn get_shadow_quote_stats_object_key(day: DaysSinceEpoch) -> String {
+    format!("shadow-quote-stats-{}", day)
+}
+
+fn get_pair_cache_archive_object_key(slot: ArchiveSlot) -> String {
+    format!("pair-cache-archive-{}", slot)
 }
 
-fn get_cur_block(chain_id: &UniversalChainId) -> ExecutableResult<BlockNum> {
-    // We assume all ChainIds support Substrate-like extrinsics. Fine for the near future
-    let chain_info =
-        get_chain_info_from_chain_id(&chain_id).ok_or(ExecutableError::FailedToFindChainInfo)?;
-    let subutils = SubstrateNodeRpcUtils {
-        rpc_url: chain_info.rpc_url.to_string(),
-    };
-    subutils
-        .get_finalized_block_number()
-        .map_err(|_| ExecutableError::RpcRequestFailed)
+fn get_pinned_snapshot_object_key(snapshot_id: &SnapshotId) -> String {
+    format!("pinned-snapshot-{}", slice_to_hex_string(snapshot_id))
 }
 
 #[cfg(test)]
 mod execute_step_meta_tests {
     use super::*;
 
+    #[cfg(any(feature = "s3-live-test", feature = "dynamodb-live-test"))]
+    fn shared_storage_credentials(
+        dynamodb_access_key: String,
+        dynamodb_secret_key: String,
+        s3_access_key: String,
+        s3_secret_key: String,
+    ) -> StorageCredentials {
+        use crate::storage_credentials::StorageCredential;
+        let dynamodb = StorageCredential {
+            access_key: dynamodb_access_key,
+            secret_key: dynamodb_secret_key,
+        };
+        let s3 = StorageCredential {
+            access_key: s3_access_key,
+            secret_key: s3_secret_key,
+        };
+        StorageCredentials {
+            plan_store: s3.clone(),
+            claim_table: dynamodb.clone(),
+            nonce_table: dynamodb,
+            analytics: s3,
+        }
+    }
+
     fn now_millis() -> MillisSinceEpoch {
         use std::time::SystemTime;
         SystemTime::now()
@@ -334,6 +1804,73 @@ mod execute_step_meta_tests {
             .unwrap()
     }
 
+    #[test]
+    fn test_paper_trade_records_eth_txn_instead_of_sending() {
+        use pink_web3::types::{Bytes, H256};
+
+        let mut meta = ExecuteStepMeta::dummy(now_millis());
+        meta.set_paper_trade(true);
+        let step_uuid = Uuid::new([1; 16]);
+        let signed_txn = SignedTransaction {
+            message_hash: H256::zero(),
+            v: 0,
+            r: H256::zero(),
+            s: H256::zero(),
+            raw_transaction: Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+            transaction_hash: H256::repeat_byte(7),
+        };
+        // Nothing listens here, so this fails if we actually broadcast
+        let txn_hash = meta
+            .send_eth_txn(
+                &step_uuid,
+                universal_chain_id_registry::MOONBEAM,
+                "http://127.0.0.1:1",
+                signed_txn,
+            )
+            .expect("Paper txn should not be sent");
+        assert_eq!(txn_hash, H256::repeat_byte(7));
+        assert_eq!(
+            meta.get_nonce(&step_uuid, universal_chain_id_registry::MOONBEAM, 100, 5),
+            Ok(5)
+        );
+
+        let txns = meta.paper_trade_log().expect("Paper trading").take();
+        assert_eq!(txns.len(), 1);
+        assert_eq!(txns[0].exec_step_uuid, step_uuid);
+        assert_eq!(
+            txns[0].kind,
+            PaperTxnKind::Eth {
+                txn_hash,
+                raw_txn: vec![0xde, 0xad, 0xbe, 0xef],
+            }
+        );
+    }
+
+    #[test]
+    fn test_paper_trade_extrinsic_hash_matches_node() {
+        let mut meta = ExecuteStepMeta::dummy(now_millis());
+        meta.set_paper_trade(true);
+        let subutils = SubstrateNodeRpcUtils {
+            rpc_url: "http://127.0.0.1:1".to_string(),
+        };
+        let raw_extrinsic = vec![0x28, 0x04, 0x01, 0x02];
+        let extrinsic_hash = meta
+            .send_extrinsic(
+                &Uuid::new([2; 16]),
+                universal_chain_id_registry::POLKADOT,
+                &subutils,
+                &raw_extrinsic,
+            )
+            .expect("Paper extrinsic should not be sent");
+        assert_eq!(
+            extrinsic_hash,
+            SubstrateExtrinsicHash::from(sp_core_hashing::blake2_256(&raw_extrinsic))
+        );
+
+        meta.set_paper_trade(false);
+        assert!(!meta.is_paper_trading());
+    }
+
     #[cfg(feature = "s3-live-test")]
     #[test]
     fn test_pull_exec_plan_from_s3() {
@@ -345,17 +1882,15 @@ mod execute_step_meta_tests {
             std::env::var("S3_SECRET_KEY").expect("Env var S3_SECRET_KEY is not set");
         let meta = ExecuteStepMeta::new_for_astar_moonbeam_polkadot(
             now_millis(),
-            s3_access_key,
-            s3_secret_key,
-            String::new(),
-            String::new(),
+            shared_storage_credentials(String::new(), String::new(), s3_access_key, s3_secret_key),
+            0,
         );
         let uuid = Uuid::from_str("6b9177a7f4aab43378be787cff1a25f1").unwrap();
-        ink_env::debug_println!("Uuid = {:?}", uuid);
+        ink::env::debug_println!("Uuid = {:?}", uuid);
         let exec_plan = meta
             .pull_exec_plan_from_s3(&uuid)
             .expect("Failed to find exec plan");
-        ink_env::debug_println!("Pulled execution plan: {:?}", exec_plan);
+        ink::env::debug_println!("Pulled execution plan: {:?}", exec_plan);
     }
 
     #[cfg(feature = "dynamodb-live-test")]
@@ -369,14 +1904,17 @@ mod execute_step_meta_tests {
             std::env::var("DYNAMODB_SECRET_KEY").expect("Env var DYNAMODB_SECRET_KEY is not set");
         let meta = ExecuteStepMeta::new_for_astar_moonbeam_polkadot(
             now_millis(),
-            String::new(),
-            String::new(),
-            dynamodb_access_key,
-            dynamodb_secret_key,
+            shared_storage_credentials(
+                dynamodb_access_key,
+                dynamodb_secret_key,
+                String::new(),
+                String::new(),
+            ),
+            0,
         );
         let uuid = Uuid::from_str("c7b008e74cc65d08d2f8814030c862bc").unwrap();
-        ink_env::debug_println!("Uuid = {:?}", uuid);
+        ink::env::debug_println!("Uuid = {:?}", uuid);
         let removed_exec_plan = meta.remove_completed_exec_plan(&uuid);
-        ink_env::debug_println!("Removed execution plan: {:?}", removed_exec_plan);
+        ink::env::debug_println!("Removed execution plan: {:?}", removed_exec_plan);
     }
 }
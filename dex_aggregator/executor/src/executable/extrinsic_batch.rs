@@ -0,0 +1,208 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use core::cell::RefCell;
+use ink::prelude::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use privadex_chain_metadata::{
+    chain_info::ChainInfo,
+    common::{SubstrateExtrinsicHash, UniversalChainId},
+};
+use privadex_common::{utils::general_utils::slice_to_hex_string, uuid::Uuid};
+
+use crate::extrinsic_call_factory::utility_batch_all;
+use crate::json_rpc_batch::JsonRpcCall;
+
+// Bounds the work (and RPC calls) of one execution_plans_step_forward invocation
+pub const MAX_BATCH_EXEC_PLANS: usize = 8;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QueuedExtrinsic {
+    pub exec_step_uuid: Uuid,
+    pub chain: UniversalChainId,
+    pub rpc_url: String,
+    pub raw_extrinsic: Vec<u8>,
+}
+
+/// Substrate extrinsics signed while several plans step forward in one invocation (see
+/// execution_plans_step_forward). Instead of each step submitting its own, they wait here
+/// until every plan has stepped forward, and then each chain's go out in one JSON-RPC batch.
+/// Steps of the same escrow draw consecutive nonces from the same nonce pool, and the batch
+/// keeps the order they were signed in, so the node never sees a nonce gap
+#[derive(Default)]
+pub struct ExtrinsicBatchWindow {
+    queued: RefCell<Vec<QueuedExtrinsic>>,
+}
+
+impl ExtrinsicBatchWindow {
+    // Same hash that author_submitExtrinsic would have returned
+    pub fn queue(
+        &self,
+        exec_step_uuid: &Uuid,
+        chain: UniversalChainId,
+        rpc_url: &str,
+        raw_extrinsic: &[u8],
+    ) -> SubstrateExtrinsicHash {
+        self.queued.borrow_mut().push(QueuedExtrinsic {
+            exec_step_uuid: exec_step_uuid.clone(),
+            chain,
+            rpc_url: rpc_url.to_string(),
+            raw_extrinsic: raw_extrinsic.to_vec(),
+        });
+        SubstrateExtrinsicHash::from(sp_core_hashing::blake2_256(raw_extrinsic))
+    }
+
+    // Drains the extrinsics queued so far
+    pub fn take(&self) -> Vec<QueuedExtrinsic> {
+        self.queued.take()
+    }
+}
+
+// Groups by RPC URL (i.e. chain), keeping the queue's order within each group
+pub fn group_by_rpc_url(queued: Vec<QueuedExtrinsic>) -> Vec<(String, Vec<QueuedExtrinsic>)> {
+    let mut groups: Vec<(String, Vec<QueuedExtrinsic>)> = Vec::new();
+    for extrinsic in queued.into_iter() {
+        match groups
+            .iter_mut()
+            .find(|(rpc_url, _)| *rpc_url == extrinsic.rpc_url)
+        {
+            Some((_, group)) => group.push(extrinsic),
+            None => groups.push((extrinsic.rpc_url.clone(), ink::prelude::vec![extrinsic])),
+        }
+    }
+    groups
+}
+
+pub fn get_submit_extrinsic_call(raw_extrinsic: &[u8]) -> JsonRpcCall<'static> {
+    JsonRpcCall::new(
+        "author_submitExtrinsic",
+        &format!(r#"["{}"]"#, slice_to_hex_string(raw_extrinsic)),
+    )
+}
+
+/// Returns the steps whose extrinsics may not have reached the node, i.e. those whose chain's
+/// batch request failed. We don't parse the responses one by one: an extrinsic the node
+/// rejected is never found, so its step is dropped once its window passes, like any extrinsic
+/// lost before inclusion
+#[cfg(not(feature = "mock-txn-send"))]
+pub fn submit_queued_extrinsics(queued: Vec<QueuedExtrinsic>) -> Vec<Uuid> {
+    let mut failed_steps = Vec::new();
+    for (rpc_url, group) in group_by_rpc_url(queued).into_iter() {
+        let calls: Vec<JsonRpcCall> = group
+            .iter()
+            .map(|extrinsic| get_submit_extrinsic_call(&extrinsic.raw_extrinsic))
+            .collect();
+        if crate::json_rpc_batch::send_batch(&rpc_url, &calls).is_err() {
+            failed_steps.extend(group.into_iter().map(|extrinsic| extrinsic.exec_step_uuid));
+        }
+    }
+    failed_steps
+}
+
+#[cfg(feature = "mock-txn-send")]
+pub fn submit_queued_extrinsics(queued: Vec<QueuedExtrinsic>) -> Vec<Uuid> {
+    ink::env::debug_println!(
+        "[Mock Substrate submit_queued_extrinsics] {} extrinsics",
+        queued.len()
+    );
+    Vec::new()
+}
+
+/// Several calls from the same origin as one call, i.e. one extrinsic and one nonce. None if
+/// there are several calls but the chain has no utility pallet
+pub fn merge_calls(chain_info: &ChainInfo, calls: &[Vec<u8>]) -> Option<Vec<u8>> {
+    match calls {
+        [call] => Some(call.clone()),
+        _ => Some(utility_batch_all(chain_info.utility_pallet_index?, calls)),
+    }
+}
+
+#[cfg(test)]
+mod extrinsic_batch_tests {
+    use hex_literal::hex;
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::{
+        chain_info_registry::{ETHEREUM_INFO, POLKADOT_INFO},
+        universal_chain_id_registry::{ASTAR, POLKADOT},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_batch_window_groups_in_order() {
+        let window = ExtrinsicBatchWindow::default();
+        let raw_extrinsics = [vec![1u8], vec![2u8], vec![3u8]];
+        let hash = window.queue(
+            &Uuid::new([1u8; 16]),
+            POLKADOT,
+            "polkadot",
+            &raw_extrinsics[0],
+        );
+        assert_eq!(
+            hash,
+            SubstrateExtrinsicHash::from(sp_core_hashing::blake2_256(&raw_extrinsics[0]))
+        );
+        window.queue(&Uuid::new([2u8; 16]), ASTAR, "astar", &raw_extrinsics[1]);
+        window.queue(
+            &Uuid::new([3u8; 16]),
+            POLKADOT,
+            "polkadot",
+            &raw_extrinsics[2],
+        );
+
+        let groups = group_by_rpc_url(window.take());
+        assert!(window.take().is_empty());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "polkadot");
+        assert_eq!(
+            groups[0]
+                .1
+                .iter()
+                .map(|extrinsic| extrinsic.raw_extrinsic.clone())
+                .collect::<Vec<_>>(),
+            vec![raw_extrinsics[0].clone(), raw_extrinsics[2].clone()]
+        );
+        assert_eq!(groups[1].0, "astar");
+        assert_eq!(groups[1].1[0].exec_step_uuid, Uuid::new([2u8; 16]));
+    }
+
+    #[test]
+    fn test_submit_extrinsic_call() {
+        let call = get_submit_extrinsic_call(&hex!("0a0b"));
+        assert_eq!(call.method, "author_submitExtrinsic");
+        assert_eq!(call.params, r#"["0x0a0b"]"#);
+    }
+
+    #[test]
+    fn test_merge_calls() {
+        let calls = [hex!("0001").to_vec(), hex!("0002").to_vec()];
+        assert_eq!(
+            merge_calls(&ETHEREUM_INFO, &calls[..1]),
+            Some(calls[0].clone())
+        );
+        assert_eq!(
+            merge_calls(&POLKADOT_INFO, &calls),
+            Some(utility_batch_all(0x1a, &calls))
+        );
+        assert_eq!(merge_calls(&ETHEREUM_INFO, &calls), None);
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{EthTxnHash, MillisSinceEpoch, SubstrateExtrinsicHash, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{
+    CrossChainStepStatus, EthStepStatus, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+    FinalizedTxnId, MultiPhaseBridgeStepStatus, PendingTxnId, RemarkDepositStatus,
+    SubstrateFinalizedExtrinsicId,
+};
+
+use super::{
+    address_display::StepAddresses, execute_step_meta::ExecuteStepMeta, plan_events::get_all_steps,
+};
+use crate::{
+    concurrency_coordinator::nonce_manager::DeploymentEpoch, eth_utils::parse_txn_helper,
+    substrate_utils::indexer_utils::subsquid_utils::SubstrateSubsquidUtils,
+};
+
+const FAILURE_BUNDLE_OBJECT_KEY_PREFIX: &str = "failure-bundles";
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BundledTxnId {
+    Ethereum(EthTxnHash),
+    // Submitted, but not yet found by the indexer
+    SubstratePending(SubstrateExtrinsicHash),
+    Substrate(SubstrateFinalizedExtrinsicId),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BundledTxn {
+    pub step_uuid: Uuid,
+    pub chain: UniversalChainId,
+    pub txn_id: BundledTxnId,
+    // The RPC's raw eth_getTransactionReceipt response for Eth txns, and the indexer's raw JSON
+    // (call, fee and events) for finalized extrinsics. None if the lookup failed, or for pending
+    // extrinsics since there is nothing to look up by yet
+    pub raw_lookup: Option<Vec<u8>>,
+}
+
+// The deployment's config as it affects a plan. RPC URLs are left out since they can contain
+// API keys, so we only note which chains have a custom endpoint
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BundledConfig {
+    pub storage_version: u16,
+    pub deployment_epoch: DeploymentEpoch,
+    pub nonce_pool_size: u8,
+    pub bypass_screening: bool,
+    pub best_block_chains: Vec<UniversalChainId>,
+    pub custom_rpc_chains: Vec<UniversalChainId>,
+    pub paused_chains: Vec<UniversalChainId>,
+}
+
+/// Everything we know about a plan in one object, so that a failed swap can be looked into
+/// without access to the deployment (see export_failure_bundle)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FailureBundle {
+    pub exec_plan: ExecutionPlan,
+    // NDJSON, in the same format as the plan event stream. Empty for plans that started before
+    // we kept a log per plan
+    pub event_log: String,
+    pub txns: Vec<BundledTxn>,
+    // Every step's addresses in their chain's format, to look them up on explorers
+    pub step_addresses: Vec<StepAddresses>,
+    pub config: BundledConfig,
+    pub created_millis: MillisSinceEpoch,
+}
+
+pub fn get_failure_bundle_object_key(
+    exec_plan_uuid: &Uuid,
+    timestamp_millis: MillisSinceEpoch,
+) -> String {
+    format!(
+        "{}/{}-{}.scale",
+        FAILURE_BUNDLE_OBJECT_KEY_PREFIX,
+        exec_plan_uuid.to_hex_string(),
+        timestamp_millis
+    )
+}
+
+pub fn get_step_addresses(exec_plan: &ExecutionPlan) -> Vec<StepAddresses> {
+    get_all_steps(exec_plan)
+        .into_iter()
+        .map(StepAddresses::from)
+        .collect()
+}
+
+// Looks up every txn the plan's steps sent (or were sent). This makes an RPC or indexer call
+// per txn, so it is only meant for one-off exports
+pub fn get_bundled_txns(
+    exec_plan: &ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> Vec<BundledTxn> {
+    get_all_steps(exec_plan)
+        .into_iter()
+        .flat_map(|step| {
+            get_step_txn_ids(step)
+                .into_iter()
+                .map(|(chain, txn_id)| BundledTxn {
+                    step_uuid: step.get_uuid().clone(),
+                    raw_lookup: lookup_raw_txn(&chain, &txn_id, execute_step_meta),
+                    chain,
+                    txn_id,
+                })
+        })
+        .collect()
+}
+
+fn lookup_raw_txn(
+    chain: &UniversalChainId,
+    txn_id: &BundledTxnId,
+    execute_step_meta: &ExecuteStepMeta,
+) -> Option<Vec<u8>> {
+    let chain_info = get_chain_info_from_chain_id(chain)?;
+    match txn_id {
+        BundledTxnId::Ethereum(txn_hash) => parse_txn_helper::get_raw_txn_receipt(
+            &execute_step_meta.get_rpc_url(chain_info),
+            *txn_hash,
+        )
+        .ok(),
+        BundledTxnId::Substrate(extrinsic_id) => SubstrateSubsquidUtils {
+            subsquid_graphql_archive_url: chain_info
+                .get_subsquid_graphql_archive_url()?
+                .to_string(),
+        }
+        .lookup_raw_extrinsic(extrinsic_id.block_num, extrinsic_id.extrinsic_index)
+        .ok(),
+        BundledTxnId::SubstratePending(_) => None,
+    }
+}
+
+// Every txn that the step's status refers to, with the chain it is on. Wormhole claims are on
+// the destination chain, everything else is on the step's src chain
+pub(super) fn get_step_txn_ids(step: &ExecutionStep) -> Vec<(UniversalChainId, BundledTxnId)> {
+    let src_chain = step.get_src_chain();
+    let eth_status = match &step.inner {
+        ExecutionStepEnum::EthSend(step) => Some(&step.status),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(&step.status),
+        ExecutionStepEnum::EthWrap(step) => Some(&step.status),
+        ExecutionStepEnum::EthUnwrap(step) => Some(&step.status),
+        ExecutionStepEnum::EthDexSwap(step) => Some(&step.status),
+        ExecutionStepEnum::EthDepositSweep(step) => Some(&step.status),
+        ExecutionStepEnum::EthContractCall(step) => Some(&step.status),
+        ExecutionStepEnum::EthFeeSkim(step) => Some(&step.status),
+        ExecutionStepEnum::EthAggregatorSwap(step) => Some(&step.status),
+        ExecutionStepEnum::EthZapIn(step) => Some(&step.status),
+        ExecutionStepEnum::EthRemoveLiquidity(step) => Some(&step.status),
+        ExecutionStepEnum::XCMTransfer(_)
+        | ExecutionStepEnum::WormholeTransfer(_)
+        | ExecutionStepEnum::SubstrateRemarkDeposit(_) => None,
+    };
+    let mut txn_ids = Vec::new();
+    match eth_status {
+        Some(EthStepStatus::Submitted(pending_txn_id)) => {
+            txn_ids.push((src_chain, BundledTxnId::Ethereum(pending_txn_id.txn_hash)))
+        }
+        Some(EthStepStatus::Failed(txn_hash) | EthStepStatus::Confirmed(txn_hash)) => {
+            txn_ids.push((src_chain, BundledTxnId::Ethereum(*txn_hash)))
+        }
+        _ => {}
+    }
+    match &step.inner {
+        ExecutionStepEnum::EthContractCall(step) => {
+            if let Some(approval_txn_hash) = step.approval_txn_hash {
+                txn_ids.push((src_chain, BundledTxnId::Ethereum(approval_txn_hash)))
+            }
+        }
+        ExecutionStepEnum::EthAggregatorSwap(step) => {
+            if let Some(approval_txn_hash) = step.approval_txn_hash {
+                txn_ids.push((src_chain, BundledTxnId::Ethereum(approval_txn_hash)))
+            }
+        }
+        ExecutionStepEnum::EthZapIn(step) => {
+            if let Some(swap_txn_hash) = step.swap_txn_hash {
+                txn_ids.push((src_chain, BundledTxnId::Ethereum(swap_txn_hash)))
+            }
+        }
+        ExecutionStepEnum::EthRemoveLiquidity(step) => {
+            if let Some(approval_txn_hash) = step.approval_txn_hash {
+                txn_ids.push((src_chain, BundledTxnId::Ethereum(approval_txn_hash)))
+            }
+        }
+        ExecutionStepEnum::XCMTransfer(step) => {
+            let txn_id = match &step.status {
+                CrossChainStepStatus::Submitted(PendingTxnId::Ethereum(pending_txn_id), _) => {
+                    Some(BundledTxnId::Ethereum(pending_txn_id.txn_hash))
+                }
+                CrossChainStepStatus::Submitted(PendingTxnId::Substrate(pending_txn_id), _) => {
+                    Some(BundledTxnId::SubstratePending(
+                        pending_txn_id.extrinsic_hash.clone(),
+                    ))
+                }
+                CrossChainStepStatus::Failed(finalized_txn_id)
+                | CrossChainStepStatus::LocalConfirmed(finalized_txn_id, _)
+                | CrossChainStepStatus::Confirmed(finalized_txn_id, _)
+                | CrossChainStepStatus::RemoteFailed(finalized_txn_id, _, _) => {
+                    Some(match finalized_txn_id {
+                        FinalizedTxnId::Ethereum(txn_hash) => BundledTxnId::Ethereum(*txn_hash),
+                        FinalizedTxnId::Substrate(extrinsic_id) => {
+                            BundledTxnId::Substrate(extrinsic_id.clone())
+                        }
+                    })
+                }
+                CrossChainStepStatus::NotStarted | CrossChainStepStatus::Dropped => None,
+            };
+            txn_ids.extend(txn_id.map(|txn_id| (src_chain, txn_id)));
+        }
+        ExecutionStepEnum::WormholeTransfer(step) => {
+            let dest_chain = step.dest_token.chain;
+            match &step.status {
+                MultiPhaseBridgeStepStatus::DepositSubmitted(pending_txn_id) => {
+                    txn_ids.push((src_chain, BundledTxnId::Ethereum(pending_txn_id.txn_hash)))
+                }
+                MultiPhaseBridgeStepStatus::Failed(txn_hash) => {
+                    txn_ids.push((src_chain, BundledTxnId::Ethereum(*txn_hash)))
+                }
+                MultiPhaseBridgeStepStatus::AwaitingRelay(message_id) => txn_ids.push((
+                    src_chain,
+                    BundledTxnId::Ethereum(message_id.deposit_txn_hash),
+                )),
+                MultiPhaseBridgeStepStatus::ClaimSubmitted(message_id, pending_txn_id) => {
+                    txn_ids.push((
+                        src_chain,
+                        BundledTxnId::Ethereum(message_id.deposit_txn_hash),
+                    ));
+                    txn_ids.push((dest_chain, BundledTxnId::Ethereum(pending_txn_id.txn_hash)));
+                }
+                MultiPhaseBridgeStepStatus::Confirmed(message_id, claim_txn_hash) => {
+                    txn_ids.push((
+                        src_chain,
+                        BundledTxnId::Ethereum(message_id.deposit_txn_hash),
+                    ));
+                    txn_ids.push((dest_chain, BundledTxnId::Ethereum(*claim_txn_hash)));
+                }
+                MultiPhaseBridgeStepStatus::NotStarted | MultiPhaseBridgeStepStatus::Dropped => {}
+            }
+        }
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => match &step.status {
+            RemarkDepositStatus::Failed(extrinsic_id)
+            | RemarkDepositStatus::Confirmed(extrinsic_id) => {
+                txn_ids.push((src_chain, BundledTxnId::Substrate(extrinsic_id.clone())))
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    txn_ids
+}
+
+#[cfg(test)]
+mod failure_bundle_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::universal_chain_id_registry::{ASTAR, MOONBEAM};
+    use privadex_execution_plan::{
+        execution_plan::SubstrateRemarkDepositStep,
+        test_utilities::execution_plan_factory::{self, common, submitted, ESCROW},
+    };
+
+    use super::*;
+
+    fn eth_send(status: EthStepStatus) -> ExecutionStep {
+        execution_plan_factory::eth_send(1, ESCROW, ESCROW, Some(1_000), status)
+    }
+
+    #[test]
+    fn test_step_txn_ids_include_pending_txns() {
+        assert_eq!(
+            get_step_txn_ids(&eth_send(EthStepStatus::NotStarted)),
+            vec![]
+        );
+        assert_eq!(
+            get_step_txn_ids(&eth_send(submitted(EthTxnHash::from([0xb1; 32])))),
+            vec![(
+                MOONBEAM,
+                BundledTxnId::Ethereum(EthTxnHash::from([0xb1; 32]))
+            )]
+        );
+        assert_eq!(
+            get_step_txn_ids(&eth_send(EthStepStatus::Failed(EthTxnHash::from(
+                [0xb2; 32]
+            )))),
+            vec![(
+                MOONBEAM,
+                BundledTxnId::Ethereum(EthTxnHash::from([0xb2; 32]))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_step_txn_ids_for_remark_deposit() {
+        let extrinsic_id = SubstrateFinalizedExtrinsicId {
+            block_num: 1_000,
+            extrinsic_index: 3,
+        };
+        let step = ExecutionStep::new(ExecutionStepEnum::SubstrateRemarkDeposit(
+            SubstrateRemarkDepositStep {
+                uuid: Uuid::new([2u8; 16]),
+                chain: ASTAR,
+                remark: [0xab; 16],
+                amount: Some(1_000),
+                common: common(ESCROW, ESCROW),
+                status: RemarkDepositStatus::Confirmed(extrinsic_id.clone()),
+            },
+        ));
+        assert_eq!(
+            get_step_txn_ids(&step),
+            vec![(ASTAR, BundledTxnId::Substrate(extrinsic_id))]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use privadex_chain_metadata::common::Amount;
+use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_execution_plan::execution_plan::{ExecutionPlan, PlanReviewStatus};
+
+use super::{
+    plan_events::get_all_steps,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+// Gas the escrow has actually paid for so far. Succeeded and failed txns both burn gas, while
+// dropped ones never landed. The prestart transfer is paid for by the user so it does not count
+pub fn get_realized_gas_fee_usd(exec_plan: &ExecutionPlan) -> Amount {
+    get_all_steps(exec_plan)
+        .into_iter()
+        .skip(1)
+        .filter(|step| {
+            let status = step.get_status();
+            status == ExecutableSimpleStatus::Succeeded || status == ExecutableSimpleStatus::Failed
+        })
+        .map(|step| step.get_common().gas_fee_usd)
+        .fold(0, Amount::saturating_add)
+}
+
+// The ceiling is relative to the plan's quoted input value, so plans without a quote have none
+pub fn get_gas_ceiling_usd(exec_plan: &ExecutionPlan, gas_ceiling_bps: u32) -> Option<Amount> {
+    exec_plan
+        .quote
+        .as_ref()
+        .map(|quote| mul_ratio_u128(quote.amount_in_usd, gas_ceiling_bps as u128, 10_000))
+}
+
+// Flags an open plan for review once its realized gas passes the ceiling. A plan the admin
+// already resolved is never flagged again. Returns true if the plan was just flagged
+pub fn flag_if_over_gas_ceiling(exec_plan: &mut ExecutionPlan, gas_ceiling_bps: u32) -> bool {
+    if exec_plan.review_status != PlanReviewStatus::NotNeeded {
+        return false;
+    }
+    match exec_plan.get_status() {
+        ExecutableSimpleStatus::Succeeded
+        | ExecutableSimpleStatus::Failed
+        | ExecutableSimpleStatus::Dropped
+        | ExecutableSimpleStatus::Refunded => return false,
+        _ => {}
+    }
+    let gas_ceiling_usd = match get_gas_ceiling_usd(exec_plan, gas_ceiling_bps) {
+        Some(gas_ceiling_usd) => gas_ceiling_usd,
+        None => return false,
+    };
+    let realized_gas_fee_usd = get_realized_gas_fee_usd(exec_plan);
+    if realized_gas_fee_usd <= gas_ceiling_usd {
+        return false;
+    }
+    exec_plan.review_status = PlanReviewStatus::NeedsReview {
+        realized_gas_fee_usd,
+    };
+    true
+}
+
+#[cfg(test)]
+mod gas_ceiling_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::{EthStepStatus, ExecutionStep, PlanQuote},
+        test_utilities::execution_plan_factory::{self, path, plan},
+    };
+
+    use super::*;
+
+    fn with_gas_fee_usd(mut step: ExecutionStep, gas_fee_usd: Amount) -> ExecutionStep {
+        step.get_common_mut().gas_fee_usd = gas_fee_usd;
+        step
+    }
+
+    fn eth_wrap(gas_fee_usd: Amount, status: EthStepStatus) -> ExecutionStep {
+        with_gas_fee_usd(
+            execution_plan_factory::eth_wrap(2, Some(1_000), status),
+            gas_fee_usd,
+        )
+    }
+
+    // $100 plan whose prestart landed, with the given path steps
+    fn plan_with_path_steps(path_steps: Vec<ExecutionStep>) -> ExecutionPlan {
+        let mut exec_plan = ExecutionPlan {
+            quote: Some(PlanQuote {
+                amount_out: 1_000,
+                amount_in_usd: 1_000,
+                amount_out_usd: 1_000,
+            }),
+            ..plan(vec![path(path_steps)])
+        };
+        exec_plan
+            .prestart_user_to_escrow_transfer
+            .get_common_mut()
+            .gas_fee_usd = 500;
+        exec_plan
+    }
+
+    #[test]
+    fn test_realized_gas_fee_usd() {
+        let exec_plan = plan_with_path_steps(vec![
+            eth_wrap(10, EthStepStatus::Confirmed(EthTxnHash::zero())),
+            eth_wrap(20, EthStepStatus::Failed(EthTxnHash::zero())),
+            eth_wrap(40, EthStepStatus::Dropped),
+            eth_wrap(80, EthStepStatus::NotStarted),
+        ]);
+        // Only the confirmed and failed path steps, not the user-paid prestart
+        assert_eq!(get_realized_gas_fee_usd(&exec_plan), 30);
+    }
+
+    #[test]
+    fn test_flag_if_over_gas_ceiling() {
+        let mut exec_plan = plan_with_path_steps(vec![
+            eth_wrap(30, EthStepStatus::Failed(EthTxnHash::zero())),
+            eth_wrap(30, EthStepStatus::Dropped),
+        ]);
+        // 5% of 1_000 is 50
+        assert!(!flag_if_over_gas_ceiling(&mut exec_plan, 500));
+        assert_eq!(exec_plan.review_status, PlanReviewStatus::NotNeeded);
+        assert!(flag_if_over_gas_ceiling(&mut exec_plan, 200));
+        assert_eq!(
+            exec_plan.review_status,
+            PlanReviewStatus::NeedsReview {
+                realized_gas_fee_usd: 30
+            }
+        );
+
+        exec_plan.review_status = PlanReviewStatus::Resolved;
+        assert!(!flag_if_over_gas_ceiling(&mut exec_plan, 0));
+    }
+
+    #[test]
+    fn test_no_ceiling_without_quote() {
+        let mut exec_plan = plan_with_path_steps(vec![eth_wrap(
+            1_000_000,
+            EthStepStatus::Failed(EthTxnHash::zero()),
+        )]);
+        exec_plan.quote = None;
+        assert!(!flag_if_over_gas_ceiling(&mut exec_plan, 0));
+    }
+}
@@ -0,0 +1,401 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+
+use privadex_chain_metadata::common::{Amount, BlockNum, EthTxnHash, MillisSinceEpoch, Nonce};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{
+    CrossChainStepStatus, EthStepStatus, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+    FinalizedTxnId, MultiPhaseBridgeStepStatus, RemarkDepositStatus, StepMilestone,
+    SubstrateEventId, SubstrateFinalizedExtrinsicId, XcmFailureKind,
+};
+
+use super::traits::{Executable, ExecutableSimpleStatus};
+
+// Kept short, and free of anything that needs escaping in the event log's NDJSON
+pub const MAX_OVERRIDE_JUSTIFICATION_LEN: usize = 256;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ManualOverrideError {
+    StepNotFound,
+    // Only a step in flight can be overridden. Finished steps stay as the executor saw them
+    StepNotInProgress,
+    // Either the step's status can't be moved to the requested one from where it is, or
+    // txn_id is the wrong kind of id for it
+    UnsupportedOverride,
+}
+
+// An operator's off-chain evidence for the step: an Ethereum txn hash (hex, with or without 0x),
+// or a Substrate extrinsic or event as "block_num-index"
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ManualTxnId {
+    Ethereum(EthTxnHash),
+    Substrate { block_num: BlockNum, index: Nonce },
+}
+
+impl ManualTxnId {
+    pub fn parse(txn_id: &str) -> Option<Self> {
+        if let Some((block_num, index)) = txn_id.split_once('-') {
+            return Some(Self::Substrate {
+                block_num: block_num.parse().ok()?,
+                index: index.parse().ok()?,
+            });
+        }
+        let hex_str = txn_id.strip_prefix("0x").unwrap_or(txn_id);
+        let raw_hash: [u8; 32] = hex::decode(hex_str).ok()?.try_into().ok()?;
+        Some(Self::Ethereum(EthTxnHash { 0: raw_hash }))
+    }
+
+    fn eth_txn_hash(&self) -> Result<EthTxnHash, ManualOverrideError> {
+        match self {
+            Self::Ethereum(txn_hash) => Ok(*txn_hash),
+            Self::Substrate { .. } => Err(ManualOverrideError::UnsupportedOverride),
+        }
+    }
+
+    fn substrate_extrinsic_id(&self) -> Result<SubstrateFinalizedExtrinsicId, ManualOverrideError> {
+        match self {
+            Self::Substrate { block_num, index } => Ok(SubstrateFinalizedExtrinsicId {
+                block_num: *block_num,
+                extrinsic_index: *index,
+            }),
+            Self::Ethereum(_) => Err(ManualOverrideError::UnsupportedOverride),
+        }
+    }
+
+    fn substrate_event_id(&self) -> Result<SubstrateEventId, ManualOverrideError> {
+        self.substrate_extrinsic_id()
+            .map(|extrinsic_id| SubstrateEventId {
+                block_num: extrinsic_id.block_num,
+                event_index: extrinsic_id.extrinsic_index,
+            })
+    }
+
+    fn finalized_txn_id(&self) -> FinalizedTxnId {
+        match self {
+            Self::Ethereum(txn_hash) => FinalizedTxnId::Ethereum(*txn_hash),
+            Self::Substrate { block_num, index } => {
+                FinalizedTxnId::Substrate(SubstrateFinalizedExtrinsicId {
+                    block_num: *block_num,
+                    extrinsic_index: *index,
+                })
+            }
+        }
+    }
+}
+
+pub fn is_valid_justification(justification: &str) -> bool {
+    !justification.trim().is_empty()
+        && justification.len() <= MAX_OVERRIDE_JUSTIFICATION_LEN
+        && justification
+            .chars()
+            .all(|c| (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\')
+}
+
+// What the step's status becomes when it is forced to succeed (confirm = true) or fail.
+// Cross-chain steps have two sides: an XCM transfer is only forced once its local txn
+// confirmed (txn_id is then the remote event), and a Wormhole transfer only once its deposit
+// did (txn_id is then the claim txn). Either can be failed earlier on
+fn override_step_status(
+    step: &mut ExecutionStep,
+    confirm: bool,
+    txn_id: &ManualTxnId,
+) -> Result<(), ManualOverrideError> {
+    let eth_status = |status: &mut EthStepStatus| -> Result<(), ManualOverrideError> {
+        let txn_hash = txn_id.eth_txn_hash()?;
+        *status = if confirm {
+            EthStepStatus::Confirmed(txn_hash)
+        } else {
+            EthStepStatus::Failed(txn_hash)
+        };
+        Ok(())
+    };
+    match &mut step.inner {
+        ExecutionStepEnum::EthSend(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::ERC20Transfer(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthWrap(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthUnwrap(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthDexSwap(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthDepositSweep(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthFeeSkim(step) => eth_status(&mut step.status),
+        // The txn in flight is the approval, not the call
+        ExecutionStepEnum::EthContractCall(step) if confirm && step.is_approving() => {
+            Err(ManualOverrideError::UnsupportedOverride)
+        }
+        ExecutionStepEnum::EthContractCall(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::EthAggregatorSwap(step) if confirm && step.is_approving() => {
+            Err(ManualOverrideError::UnsupportedOverride)
+        }
+        ExecutionStepEnum::EthAggregatorSwap(step) => eth_status(&mut step.status),
+        // Confirming the swap would also need what it paid out, to add as liquidity
+        ExecutionStepEnum::EthZapIn(step) if confirm && step.is_swapping() => {
+            Err(ManualOverrideError::UnsupportedOverride)
+        }
+        ExecutionStepEnum::EthZapIn(step) => eth_status(&mut step.status),
+        // Confirming either txn would also need what removeLiquidity paid out of both tokens
+        ExecutionStepEnum::EthRemoveLiquidity(_) if confirm => {
+            Err(ManualOverrideError::UnsupportedOverride)
+        }
+        ExecutionStepEnum::EthRemoveLiquidity(step) => eth_status(&mut step.status),
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => {
+            let extrinsic_id = txn_id.substrate_extrinsic_id()?;
+            step.status = if confirm {
+                RemarkDepositStatus::Confirmed(extrinsic_id)
+            } else {
+                RemarkDepositStatus::Failed(extrinsic_id)
+            };
+            Ok(())
+        }
+        ExecutionStepEnum::XCMTransfer(step) => {
+            step.status = match (&step.status, confirm) {
+                (CrossChainStepStatus::LocalConfirmed(local_txn_id, _), true) => {
+                    CrossChainStepStatus::Confirmed(
+                        local_txn_id.clone(),
+                        txn_id.substrate_event_id()?,
+                    )
+                }
+                (CrossChainStepStatus::LocalConfirmed(local_txn_id, _), false) => {
+                    CrossChainStepStatus::RemoteFailed(
+                        local_txn_id.clone(),
+                        txn_id.substrate_event_id()?,
+                        XcmFailureKind::Other,
+                    )
+                }
+                (CrossChainStepStatus::Submitted(..), false) => {
+                    CrossChainStepStatus::Failed(txn_id.finalized_txn_id())
+                }
+                _ => return Err(ManualOverrideError::UnsupportedOverride),
+            };
+            Ok(())
+        }
+        ExecutionStepEnum::WormholeTransfer(step) => {
+            let txn_hash = txn_id.eth_txn_hash()?;
+            step.status = match (&step.status, confirm) {
+                (
+                    MultiPhaseBridgeStepStatus::AwaitingRelay(message_id)
+                    | MultiPhaseBridgeStepStatus::ClaimSubmitted(message_id, _),
+                    true,
+                ) => MultiPhaseBridgeStepStatus::Confirmed(message_id.clone(), txn_hash),
+                (_, false) => MultiPhaseBridgeStepStatus::Failed(txn_hash),
+                _ => return Err(ManualOverrideError::UnsupportedOverride),
+            };
+            Ok(())
+        }
+    }
+}
+
+fn get_all_steps_mut(exec_plan: &mut ExecutionPlan) -> Vec<&mut ExecutionStep> {
+    let mut steps = Vec::new();
+    steps.push(&mut exec_plan.prestart_user_to_escrow_transfer);
+    if let Some(deposit_sweep) = exec_plan.deposit_sweep.as_mut() {
+        steps.push(deposit_sweep);
+    }
+    if let Some(remove_liquidity) = exec_plan.remove_liquidity.as_mut() {
+        steps.push(remove_liquidity);
+    }
+    for path in exec_plan.paths.iter_mut() {
+        steps.extend(path.steps.iter_mut());
+    }
+    steps.push(&mut exec_plan.postend_escrow_to_user_transfer);
+    steps.push(&mut exec_plan.refund_escrow_to_user_transfer);
+    steps
+}
+
+// Forces an in-flight step to succeed or fail on the operator's say-so, for when the automated
+// checks can't tell (e.g. the indexer is down). A path step forced to succeed also hands
+// amount_out to the next step, like ExecutionPath::execute_step_forward does. Returns the step's
+// status before and after
+pub fn force_step_status(
+    exec_plan: &mut ExecutionPlan,
+    step_uuid: &Uuid,
+    confirm: bool,
+    txn_id: &ManualTxnId,
+    amount_out: Amount,
+    cur_timestamp: MillisSinceEpoch,
+) -> Result<(ExecutableSimpleStatus, ExecutableSimpleStatus), ManualOverrideError> {
+    let step = get_all_steps_mut(exec_plan)
+        .into_iter()
+        .find(|step| step.get_uuid() == step_uuid)
+        .ok_or(ManualOverrideError::StepNotFound)?;
+    let from = step.get_status();
+    if from != ExecutableSimpleStatus::InProgress {
+        return Err(ManualOverrideError::StepNotInProgress);
+    }
+    override_step_status(step, confirm, txn_id)?;
+    step.timing.confirmed = Some(StepMilestone {
+        block_num: None,
+        timestamp_millis: cur_timestamp,
+    });
+    let to = step.get_status();
+
+    if to == ExecutableSimpleStatus::Succeeded {
+        for path in exec_plan.paths.iter_mut() {
+            if let Some(idx) = path
+                .steps
+                .iter()
+                .position(|step| step.get_uuid() == step_uuid)
+            {
+                path.record_step_amount_out(idx, amount_out);
+                match path.steps.get_mut(idx + 1) {
+                    Some(next_step) => next_step.set_amount_in(amount_out),
+                    None => path.amount_out = Some(amount_out),
+                }
+            }
+        }
+    }
+    Ok((from, to))
+}
+
+#[cfg(test)]
+mod manual_override_tests {
+    use ink::prelude::vec;
+    use privadex_execution_plan::test_utilities::execution_plan_factory::{
+        self, eth_wrap, path, submitted,
+    };
+
+    use super::*;
+
+    const HASH: EthTxnHash = EthTxnHash { 0: [0xaa; 32] };
+
+    // Deposit landed, and the path's first step is in flight
+    fn plan() -> ExecutionPlan {
+        execution_plan_factory::plan(vec![path(vec![
+            eth_wrap(2, Some(1_000), submitted(HASH)),
+            eth_wrap(3, None, EthStepStatus::NotStarted),
+        ])])
+    }
+
+    #[test]
+    fn test_parse_manual_txn_id() {
+        let hash_str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        assert_eq!(
+            ManualTxnId::parse(hash_str),
+            Some(ManualTxnId::Ethereum(HASH))
+        );
+        assert_eq!(
+            ManualTxnId::parse(&("0x".to_string() + hash_str)),
+            Some(ManualTxnId::Ethereum(HASH))
+        );
+        assert_eq!(
+            ManualTxnId::parse("1234-5"),
+            Some(ManualTxnId::Substrate {
+                block_num: 1234,
+                index: 5
+            })
+        );
+        assert_eq!(ManualTxnId::parse("aaaa"), None);
+        assert_eq!(ManualTxnId::parse("1234-"), None);
+    }
+
+    #[test]
+    fn test_is_valid_justification() {
+        assert!(is_valid_justification("Indexer down, seen on Moonscan"));
+        assert!(!is_valid_justification("  "));
+        assert!(!is_valid_justification("Said \"ok\""));
+        assert!(!is_valid_justification("line\nbreak"));
+        assert!(!is_valid_justification(
+            &"a".repeat(MAX_OVERRIDE_JUSTIFICATION_LEN + 1)
+        ));
+    }
+
+    #[test]
+    fn test_force_step_confirmed() {
+        let mut exec_plan = plan();
+        let step_uuid = Uuid::new([2u8; 16]);
+        assert_eq!(
+            force_step_status(
+                &mut exec_plan,
+                &step_uuid,
+                true,
+                &ManualTxnId::Ethereum(HASH),
+                990,
+                7
+            ),
+            Ok((
+                ExecutableSimpleStatus::InProgress,
+                ExecutableSimpleStatus::Succeeded
+            ))
+        );
+        let path = &exec_plan.paths[0];
+        assert_eq!(path.step_amounts_out, vec![990]);
+        assert_eq!(path.steps[1].get_amount_in(), Some(990));
+        assert_eq!(
+            path.steps[0]
+                .timing
+                .confirmed
+                .as_ref()
+                .unwrap()
+                .timestamp_millis,
+            7
+        );
+
+        // Already finished
+        assert_eq!(
+            force_step_status(
+                &mut exec_plan,
+                &step_uuid,
+                false,
+                &ManualTxnId::Ethereum(HASH),
+                0,
+                8
+            ),
+            Err(ManualOverrideError::StepNotInProgress)
+        );
+        assert_eq!(
+            force_step_status(
+                &mut exec_plan,
+                &Uuid::new([9u8; 16]),
+                true,
+                &ManualTxnId::Ethereum(HASH),
+                0,
+                8
+            ),
+            Err(ManualOverrideError::StepNotFound)
+        );
+    }
+
+    #[test]
+    fn test_force_step_failed_needs_matching_txn_id() {
+        let mut exec_plan = plan();
+        let step_uuid = Uuid::new([2u8; 16]);
+        let substrate_txn_id = ManualTxnId::Substrate {
+            block_num: 1,
+            index: 2,
+        };
+        assert_eq!(
+            force_step_status(&mut exec_plan, &step_uuid, false, &substrate_txn_id, 0, 7),
+            Err(ManualOverrideError::UnsupportedOverride)
+        );
+        assert_eq!(
+            force_step_status(
+                &mut exec_plan,
+                &step_uuid,
+                false,
+                &ManualTxnId::Ethereum(HASH),
+                0,
+                7
+            ),
+            Ok((
+                ExecutableSimpleStatus::InProgress,
+                ExecutableSimpleStatus::Failed
+            ))
+        );
+        assert!(exec_plan.paths[0].step_amounts_out.is_empty());
+    }
+}
@@ -16,9 +16,47 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod address_display;
+pub mod allowance_hygiene;
+pub mod amount_rounding;
+pub mod canaries;
+pub mod chain_preflight;
+pub mod clock_skew;
+pub mod deposit_tolerance;
+pub mod drain_mode;
+pub mod dust_consolidation;
 pub mod executable_path;
 pub mod executable_plan;
 pub mod executable_step;
 pub mod executable_step_helpers;
 pub mod execute_step_meta;
+pub mod extrinsic_batch;
+pub mod failure_bundle;
+pub mod gas_ceiling;
+pub mod manual_override;
+pub mod nonce_pool;
+pub mod paper_trade;
+pub mod payment_request;
+pub mod plan_analytics;
+pub mod plan_events;
+pub mod plan_merge;
+pub mod plan_public_view;
+pub mod plan_replay;
+pub mod poll_schedule;
+pub mod presign;
+pub mod proof_of_reserves;
+pub mod registry_check;
+pub mod relayer_rewards;
+pub mod remark_deposit;
+pub mod route_blacklist;
+pub mod router_allowlist;
+pub mod shadow_quotes;
+pub mod sponsorship;
+pub mod step_timing;
+pub mod stranded_funds;
+pub mod stuck_plans;
 pub mod traits;
+pub mod xcm_bridge_fee_calibration;
+pub mod xcm_weight_calibration;
+pub mod zap_in;
+pub mod zap_out;
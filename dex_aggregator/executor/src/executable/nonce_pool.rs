@@ -0,0 +1,151 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use core::cell::RefCell;
+use ink::prelude::vec::Vec;
+
+use privadex_chain_metadata::common::{Nonce, UniversalAddress, UniversalChainId};
+use privadex_common::uuid::Uuid;
+
+// Caps how many nonces a worker can hold back from the other workers (and how many end up
+// as dropped nonces if most of them go unused)
+pub const MAX_NONCE_POOL_SIZE: u8 = 16;
+
+// A block of nonces reserved through the NonceManager for one (chain, signer)
+pub struct NoncePool {
+    pub chain: UniversalChainId,
+    pub signer: UniversalAddress,
+    pub pool_id: Uuid,
+    pub unused_nonces: Vec<Nonce>,
+}
+
+// Nonce blocks reserved during one invocation. Steps that become ready together (e.g.
+// parallel paths) take their nonces from here instead of fetching the system nonce and going
+// through NonceManager::get_nonce one by one. Every pool must be released at the end of the
+// invocation, exhausted or not, so that a cold start can happen again
+// Steps only get a shared reference to the ExecuteStepMeta, hence the RefCell
+#[derive(Default)]
+pub struct NoncePools {
+    pools: RefCell<Vec<NoncePool>>,
+}
+
+impl NoncePools {
+    pub fn add_pool(
+        &self,
+        chain: UniversalChainId,
+        signer: UniversalAddress,
+        pool_id: Uuid,
+        first_nonce: Nonce,
+        pool_size: Nonce,
+    ) {
+        self.pools.borrow_mut().push(NoncePool {
+            chain,
+            signer,
+            pool_id,
+            unused_nonces: (first_nonce..first_nonce + pool_size).collect(),
+        });
+    }
+
+    // Lowest unused nonce for the (chain, signer), along with the pool it came from
+    pub fn take_nonce(
+        &self,
+        chain: UniversalChainId,
+        signer: &UniversalAddress,
+    ) -> Option<(Uuid, Nonce)> {
+        self.pools
+            .borrow_mut()
+            .iter_mut()
+            .find(|pool| {
+                pool.chain == chain && &pool.signer == signer && !pool.unused_nonces.is_empty()
+            })
+            .map(|pool| (pool.pool_id.clone(), pool.unused_nonces.remove(0)))
+    }
+
+    // For a nonce that was taken but could not be assigned to the step
+    pub fn return_nonce(&self, pool_id: &Uuid, nonce: Nonce) {
+        if let Some(pool) = self
+            .pools
+            .borrow_mut()
+            .iter_mut()
+            .find(|pool| &pool.pool_id == pool_id)
+        {
+            pool.unused_nonces.insert(0, nonce);
+        }
+    }
+
+    // Drains the pools so that the caller can release them
+    pub fn take_all(&self) -> Vec<NoncePool> {
+        self.pools.take()
+    }
+}
+
+#[cfg(test)]
+mod nonce_pool_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::EthAddress, registry::chain::universal_chain_id_registry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_take_and_return_nonces() {
+        let signer = UniversalAddress::Ethereum(EthAddress::repeat_byte(1));
+        let other_signer = UniversalAddress::Ethereum(EthAddress::repeat_byte(2));
+        let pool_id = Uuid::new([1; 16]);
+        let pools = NoncePools::default();
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::MOONBEAM, &signer),
+            None
+        );
+
+        pools.add_pool(
+            universal_chain_id_registry::MOONBEAM,
+            signer.clone(),
+            pool_id.clone(),
+            10,
+            3,
+        );
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::ASTAR, &signer),
+            None
+        );
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::MOONBEAM, &other_signer),
+            None
+        );
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::MOONBEAM, &signer),
+            Some((pool_id.clone(), 10))
+        );
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::MOONBEAM, &signer),
+            Some((pool_id.clone(), 11))
+        );
+        pools.return_nonce(&pool_id, 11);
+        assert_eq!(
+            pools.take_nonce(universal_chain_id_registry::MOONBEAM, &signer),
+            Some((pool_id.clone(), 11))
+        );
+
+        let released_pools = pools.take_all();
+        assert_eq!(released_pools.len(), 1);
+        assert_eq!(released_pools[0].unused_nonces, vec![12]);
+        assert!(pools.take_all().is_empty());
+    }
+}
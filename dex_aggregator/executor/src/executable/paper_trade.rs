@@ -0,0 +1,78 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use core::cell::RefCell;
+use ink::prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{
+    EthTxnHash, MillisSinceEpoch, SubstrateExtrinsicHash, UniversalChainId,
+};
+use privadex_common::uuid::Uuid;
+
+const PAPER_TXNS_OBJECT_KEY_PREFIX: &str = "paper-txns";
+
+/// A txn that a paper-trading ExecutionPlan signed but never broadcast. Paper trading runs
+/// every step against live chain state, so this is exactly what the plan would have sent
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PaperTxn {
+    pub exec_step_uuid: Uuid,
+    pub chain: UniversalChainId,
+    pub timestamp_millis: MillisSinceEpoch,
+    pub kind: PaperTxnKind,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PaperTxnKind {
+    Eth {
+        txn_hash: EthTxnHash,
+        raw_txn: Vec<u8>,
+    },
+    SubstrateExtrinsic {
+        extrinsic_hash: SubstrateExtrinsicHash,
+        raw_extrinsic: Vec<u8>,
+    },
+}
+
+// Steps only get a shared reference to the ExecuteStepMeta, hence the RefCell
+#[derive(Default)]
+pub struct PaperTradeLog {
+    txns: RefCell<Vec<PaperTxn>>,
+}
+
+impl PaperTradeLog {
+    pub fn record(&self, txn: PaperTxn) {
+        self.txns.borrow_mut().push(txn);
+    }
+
+    // Drains the txns recorded so far
+    pub fn take(&self) -> Vec<PaperTxn> {
+        self.txns.take()
+    }
+}
+
+// One object per plan. It is small (a handful of txns) so we just rewrite it on every step
+pub fn get_paper_txns_object_key(exec_plan_uuid: &Uuid) -> String {
+    format!(
+        "{}-{}",
+        PAPER_TXNS_OBJECT_KEY_PREFIX,
+        exec_plan_uuid.to_hex_string()
+    )
+}
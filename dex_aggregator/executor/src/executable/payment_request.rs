@@ -0,0 +1,214 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, MillisSinceEpoch};
+use privadex_common::{utils::general_utils::slice_to_hex_string, uuid::Uuid};
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use super::traits::{Executable, ExecutableSimpleStatus};
+
+const PAYMENT_REQUEST_OBJECT_KEY_PREFIX: &str = "payment-request";
+
+pub type InvoiceId = [u8; 16];
+
+// A merchant's invoice for exactly exact_amount_out of dest_token, delivered to dest_eth_addr.
+// Kept in S3 under the invoice ID
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PaymentRequest {
+    pub invoice_id: InvoiceId,
+    pub dest_network_name: String,
+    pub dest_eth_addr: String,
+    pub dest_token: String,
+    pub exact_amount_out: Amount,
+    pub expiry_millis: MillisSinceEpoch,
+    // The latest swap started to pay it. A swap that failed or was refunded doesn't count, and
+    // the invoice can be paid again until it expires
+    pub exec_plan_uuid: Option<Uuid>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum InvoiceStatus {
+    Open,
+    Expired,
+    // A swap paying it is in flight
+    Pending(Uuid),
+    Paid {
+        exec_plan_uuid: Uuid,
+        amount_out: Amount,
+    },
+    // The swap completed but delivered less than exact_amount_out, e.g. the price moved between
+    // quote_for_invoice and the swap. The merchant keeps what was delivered
+    Underpaid {
+        exec_plan_uuid: Uuid,
+        amount_out: Amount,
+    },
+}
+
+impl InvoiceStatus {
+    // exec_plan is the one request.exec_plan_uuid refers to
+    pub fn new(
+        request: &PaymentRequest,
+        exec_plan: Option<&ExecutionPlan>,
+        now_millis: MillisSinceEpoch,
+    ) -> Self {
+        let payment = exec_plan.map(|exec_plan| {
+            (
+                exec_plan.uuid.clone(),
+                exec_plan.get_status(),
+                exec_plan.postend_escrow_to_user_transfer.get_amount_in(),
+            )
+        });
+        get_invoice_status(request, payment, now_millis)
+    }
+
+    // Whether a new swap may be started to pay it
+    pub fn is_payable(&self) -> bool {
+        *self == Self::Open
+    }
+}
+
+fn get_invoice_status(
+    request: &PaymentRequest,
+    payment: Option<(Uuid, ExecutableSimpleStatus, Option<Amount>)>,
+    now_millis: MillisSinceEpoch,
+) -> InvoiceStatus {
+    match payment {
+        Some((exec_plan_uuid, ExecutableSimpleStatus::Succeeded, amount_out)) => {
+            let amount_out = amount_out.unwrap_or(0);
+            if amount_out >= request.exact_amount_out {
+                InvoiceStatus::Paid {
+                    exec_plan_uuid,
+                    amount_out,
+                }
+            } else {
+                InvoiceStatus::Underpaid {
+                    exec_plan_uuid,
+                    amount_out,
+                }
+            }
+        }
+        Some((
+            exec_plan_uuid,
+            ExecutableSimpleStatus::NotStarted
+            | ExecutableSimpleStatus::InProgress
+            | ExecutableSimpleStatus::PrestartConfirming,
+            _,
+        )) => InvoiceStatus::Pending(exec_plan_uuid),
+        // The payer got their funds back (or never sent them)
+        Some((_, ExecutableSimpleStatus::Failed, _))
+        | Some((_, ExecutableSimpleStatus::Dropped, _))
+        | Some((_, ExecutableSimpleStatus::Refunded, _))
+        | None => {
+            if now_millis > request.expiry_millis {
+                InvoiceStatus::Expired
+            } else {
+                InvoiceStatus::Open
+            }
+        }
+    }
+}
+
+pub fn get_payment_request_object_key(invoice_id: &InvoiceId) -> String {
+    format!(
+        "{}-{}",
+        PAYMENT_REQUEST_OBJECT_KEY_PREFIX,
+        &slice_to_hex_string(invoice_id)[2..]
+    )
+}
+
+#[cfg(test)]
+mod payment_request_tests {
+    use ink::prelude::string::ToString;
+
+    use super::*;
+
+    fn request() -> PaymentRequest {
+        PaymentRequest {
+            invoice_id: [0xab; 16],
+            dest_network_name: "moonbeam".to_string(),
+            dest_eth_addr: "05a81d8564a3eA298660e34e03E5Eff9a29d7a2A".to_string(),
+            dest_token: "native".to_string(),
+            exact_amount_out: 1_000,
+            expiry_millis: 5_000,
+            exec_plan_uuid: None,
+        }
+    }
+
+    #[test]
+    fn test_invoice_status() {
+        let uuid = Uuid::new([1u8; 16]);
+        assert_eq!(
+            get_invoice_status(&request(), None, 5_000),
+            InvoiceStatus::Open
+        );
+        assert_eq!(
+            get_invoice_status(&request(), None, 5_001),
+            InvoiceStatus::Expired
+        );
+        assert_eq!(
+            get_invoice_status(
+                &request(),
+                Some((uuid.clone(), ExecutableSimpleStatus::InProgress, None)),
+                6_000
+            ),
+            InvoiceStatus::Pending(uuid.clone())
+        );
+        assert_eq!(
+            get_invoice_status(
+                &request(),
+                Some((uuid.clone(), ExecutableSimpleStatus::Succeeded, Some(1_001))),
+                6_000
+            ),
+            InvoiceStatus::Paid {
+                exec_plan_uuid: uuid.clone(),
+                amount_out: 1_001
+            }
+        );
+        assert_eq!(
+            get_invoice_status(
+                &request(),
+                Some((uuid.clone(), ExecutableSimpleStatus::Succeeded, Some(999))),
+                6_000
+            ),
+            InvoiceStatus::Underpaid {
+                exec_plan_uuid: uuid.clone(),
+                amount_out: 999
+            }
+        );
+        // A refunded payment reopens the invoice until it expires
+        let refunded = Some((uuid, ExecutableSimpleStatus::Refunded, None));
+        assert!(get_invoice_status(&request(), refunded.clone(), 4_000).is_payable());
+        assert_eq!(
+            get_invoice_status(&request(), refunded, 6_000),
+            InvoiceStatus::Expired
+        );
+    }
+
+    #[test]
+    fn test_object_key() {
+        assert_eq!(
+            get_payment_request_object_key(&[0xab; 16]),
+            "payment-request-abababababababababababababababab"
+        );
+    }
+}
@@ -0,0 +1,704 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{
+        Amount, ChainTokenId, EthAddress, MillisSinceEpoch, UniversalChainId, UniversalTokenId,
+    },
+    get_dexes_from_chain_id,
+    registry::dex::DexId,
+};
+use privadex_common::{fixed_point::DecimalFixedPoint, uuid::Uuid};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStepEnum, PlanQuote};
+
+use crate::fx_rate::FxRate;
+
+use super::traits::{Executable, ExecutableSimpleStatus};
+
+const MILLIS_PER_DAY: MillisSinceEpoch = 86_400_000;
+
+pub type DaysSinceEpoch = u32;
+
+// ExecutionPrice::realized_price is in # dest tokens per src token x 10^PRICE_EXPONENT
+pub const PRICE_EXPONENT: u32 = 18;
+
+pub fn get_day(millis: MillisSinceEpoch) -> DaysSinceEpoch {
+    (millis / MILLIS_PER_DAY) as DaysSinceEpoch
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TokenAmount {
+    pub token: UniversalTokenId,
+    pub amount: Amount,
+}
+
+/// Summary of a closed ExecutionPlan. These are queued up as plans close and rolled into
+/// DailyStats by the aggregation job
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PlanAnalytics {
+    pub exec_plan_uuid: Uuid,
+    pub src_chain: UniversalChainId,
+    pub started_millis: MillisSinceEpoch,
+    pub closed_millis: MillisSinceEpoch,
+    pub is_success: bool,
+    pub amount_in: TokenAmount,
+    // Kept by the escrow, in the dest token
+    pub protocol_fee: TokenAmount,
+    // (chain, router) and amount_in of every DEX swap that went through
+    pub dex_swaps: Vec<(UniversalChainId, EthAddress, TokenAmount)>,
+    // What the user received in the dest token. None unless the plan succeeded
+    pub amount_out: Option<TokenAmount>,
+    pub quote: Option<PlanQuote>,
+}
+
+impl PlanAnalytics {
+    pub fn from_closed_exec_plan(
+        exec_plan: &ExecutionPlan,
+        closed_millis: MillisSinceEpoch,
+    ) -> Option<Self> {
+        let amount_in = TokenAmount {
+            token: get_transfer_token(&exec_plan.prestart_user_to_escrow_transfer.inner)?,
+            amount: exec_plan.prestart_user_to_escrow_transfer.get_amount_in()?,
+        };
+        // The postend step gets the paths' output minus our fee (also when refunding)
+        let protocol_fee = TokenAmount {
+            token: get_transfer_token(&exec_plan.postend_escrow_to_user_transfer.inner)?,
+            amount: if exec_plan.postend_escrow_to_user_transfer.get_status()
+                == ExecutableSimpleStatus::Succeeded
+            {
+                let paths_amount_out = exec_plan
+                    .paths
+                    .iter()
+                    .filter(|path| path.get_status() == ExecutableSimpleStatus::Succeeded)
+                    .fold(0, |amount_out, path| {
+                        amount_out + path.amount_out.unwrap_or(0)
+                    });
+                paths_amount_out.saturating_sub(
+                    exec_plan
+                        .postend_escrow_to_user_transfer
+                        .get_amount_in()
+                        .unwrap_or(0),
+                )
+            } else {
+                0
+            },
+        };
+        let dex_swaps = exec_plan
+            .paths
+            .iter()
+            .flat_map(|path| path.steps.iter())
+            .filter(|step| step.get_status() == ExecutableSimpleStatus::Succeeded)
+            .filter_map(|step| match &step.inner {
+                ExecutionStepEnum::EthDexSwap(swap_step) => {
+                    let token = swap_step.token_path.first()?.clone();
+                    Some((
+                        token.chain,
+                        swap_step.dex_router_addr,
+                        TokenAmount {
+                            token,
+                            amount: swap_step.amount_in?,
+                        },
+                    ))
+                }
+                _ => None,
+            })
+            .collect();
+        let amount_out = if exec_plan.postend_escrow_to_user_transfer.get_status()
+            == ExecutableSimpleStatus::Succeeded
+        {
+            Some(TokenAmount {
+                token: protocol_fee.token.clone(),
+                amount: exec_plan.postend_escrow_to_user_transfer.get_amount_in()?,
+            })
+        } else {
+            None
+        };
+        Some(Self {
+            exec_plan_uuid: exec_plan.uuid.clone(),
+            src_chain: amount_in.token.chain,
+            // The deadline is set to the plan's creation time plus its worst-case duration
+            started_millis: exec_plan
+                .deadline_millis
+                .saturating_sub(exec_plan.get_worst_case_duration_millis()),
+            closed_millis,
+            is_success: exec_plan.get_status() == ExecutableSimpleStatus::Succeeded,
+            amount_in,
+            protocol_fee,
+            dex_swaps,
+            amount_out,
+            quote: exec_plan.quote.clone(),
+        })
+    }
+}
+
+/// Returned by get_execution_price. Lets integrators settle a swap without their own price
+/// history
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ExecutionPrice {
+    pub exec_plan_uuid: Uuid,
+    pub closed_millis: MillisSinceEpoch,
+    pub amount_in: TokenAmount,
+    pub amount_out: TokenAmount,
+    // amount_out / amount_in in whole tokens (i.e. normalized for decimals)
+    pub realized_price: Amount,
+    // $ x 10^USD_AMOUNT_EXPONENT per whole token, at the prices the plan was quoted with.
+    // None for plans started before quotes were recorded
+    pub src_token_usd_price: Option<Amount>,
+    pub dest_token_usd_price: Option<Amount>,
+    // The same prices in the reference currency, converted at the current FX rate (not the
+    // rate when the plan closed). None if no reference currency is set
+    pub fx_rate: Option<FxRate>,
+    pub src_token_reference_price: Option<Amount>,
+    pub dest_token_reference_price: Option<Amount>,
+}
+
+impl ExecutionPrice {
+    /// None unless the plan succeeded
+    pub fn new(plan: &PlanAnalytics, src_decimals: u8, dest_decimals: u8) -> Option<Self> {
+        let amount_out = plan.amount_out.clone()?;
+        if plan.amount_in.amount == 0 {
+            return None;
+        }
+        let realized_price = DecimalFixedPoint::u128_mul_div(
+            amount_out.amount,
+            &DecimalFixedPoint {
+                coef: 1,
+                exp: (PRICE_EXPONENT as i8) + (src_decimals as i8),
+            },
+            &DecimalFixedPoint {
+                coef: plan.amount_in.amount,
+                exp: dest_decimals as i8,
+            },
+        );
+        let (src_token_usd_price, dest_token_usd_price) = match &plan.quote {
+            Some(quote) => (
+                get_usd_price(quote.amount_in_usd, plan.amount_in.amount, src_decimals),
+                get_usd_price(quote.amount_out_usd, quote.amount_out, dest_decimals),
+            ),
+            None => (None, None),
+        };
+        Some(Self {
+            exec_plan_uuid: plan.exec_plan_uuid.clone(),
+            closed_millis: plan.closed_millis,
+            amount_in: plan.amount_in.clone(),
+            amount_out,
+            realized_price,
+            src_token_usd_price,
+            dest_token_usd_price,
+            fx_rate: None,
+            src_token_reference_price: None,
+            dest_token_reference_price: None,
+        })
+    }
+
+    pub fn with_fx_rate(self, fx_rate: FxRate) -> Self {
+        Self {
+            src_token_reference_price: self.src_token_usd_price.map(|p| fx_rate.from_usd(p)),
+            dest_token_reference_price: self.dest_token_usd_price.map(|p| fx_rate.from_usd(p)),
+            fx_rate: Some(fx_rate),
+            ..self
+        }
+    }
+}
+
+fn get_usd_price(amount_usd: Amount, amount: Amount, decimals: u8) -> Option<Amount> {
+    if amount == 0 {
+        None
+    } else {
+        Some(DecimalFixedPoint::u128_mul_div(
+            amount_usd,
+            &DecimalFixedPoint {
+                coef: 1,
+                exp: decimals as i8,
+            },
+            &DecimalFixedPoint {
+                coef: amount,
+                exp: 0,
+            },
+        ))
+    }
+}
+
+pub fn get_transfer_token(step: &ExecutionStepEnum) -> Option<UniversalTokenId> {
+    match step {
+        ExecutionStepEnum::EthSend(step) => Some(UniversalTokenId {
+            chain: step.chain,
+            id: ChainTokenId::Native,
+        }),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(step.token.clone()),
+        ExecutionStepEnum::EthContractCall(step) => Some(step.token.clone()),
+        ExecutionStepEnum::EthZapIn(step) => Some(step.token.clone()),
+        ExecutionStepEnum::SubstrateRemarkDeposit(step) => Some(UniversalTokenId {
+            chain: step.chain,
+            id: ChainTokenId::Native,
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StatsCounters {
+    pub num_swaps: u32,
+    pub num_succeeded: u32,
+    // Over succeeded swaps only
+    pub total_completion_millis: MillisSinceEpoch,
+    // We don't have a price feed at aggregation time, so amounts are kept per token
+    pub volume: Vec<TokenAmount>,
+    pub fee_revenue: Vec<TokenAmount>,
+}
+
+impl StatsCounters {
+    pub fn success_rate_bps(&self) -> u16 {
+        if self.num_swaps == 0 {
+            0
+        } else {
+            (u64::from(self.num_succeeded) * 10_000 / u64::from(self.num_swaps)) as u16
+        }
+    }
+
+    pub fn avg_completion_millis(&self) -> Option<MillisSinceEpoch> {
+        if self.num_succeeded == 0 {
+            None
+        } else {
+            Some(self.total_completion_millis / MillisSinceEpoch::from(self.num_succeeded))
+        }
+    }
+
+    fn add_swap(
+        &mut self,
+        is_success: bool,
+        completion_millis: MillisSinceEpoch,
+        volume: &TokenAmount,
+        fee_revenue: Option<&TokenAmount>,
+    ) {
+        self.num_swaps += 1;
+        if is_success {
+            self.num_succeeded += 1;
+            self.total_completion_millis += completion_millis;
+        }
+        add_token_amount(&mut self.volume, volume);
+        if let Some(fee_revenue) = fee_revenue {
+            add_token_amount(&mut self.fee_revenue, fee_revenue);
+        }
+    }
+
+    fn merge(&mut self, other: &StatsCounters) {
+        self.num_swaps += other.num_swaps;
+        self.num_succeeded += other.num_succeeded;
+        self.total_completion_millis += other.total_completion_millis;
+        for token_amount in other.volume.iter() {
+            add_token_amount(&mut self.volume, token_amount);
+        }
+        for token_amount in other.fee_revenue.iter() {
+            add_token_amount(&mut self.fee_revenue, token_amount);
+        }
+    }
+}
+
+fn add_token_amount(token_amounts: &mut Vec<TokenAmount>, to_add: &TokenAmount) {
+    if to_add.amount == 0 {
+        return;
+    }
+    if let Some(token_amount) = token_amounts
+        .iter_mut()
+        .find(|token_amount| token_amount.token == to_add.token)
+    {
+        token_amount.amount = token_amount.amount.saturating_add(to_add.amount);
+    } else {
+        token_amounts.push(to_add.clone());
+    }
+}
+
+/// Per-chain (by src chain) and per-DEX aggregates of the plans that closed on a given day
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DailyStats {
+    pub day: DaysSinceEpoch,
+    pub per_chain: Vec<(UniversalChainId, StatsCounters)>,
+    pub per_dex: Vec<(UniversalChainId, EthAddress, StatsCounters)>,
+}
+
+impl DailyStats {
+    pub fn new(day: DaysSinceEpoch) -> Self {
+        Self {
+            day,
+            per_chain: Vec::new(),
+            per_dex: Vec::new(),
+        }
+    }
+
+    pub fn add_plan(&mut self, plan: &PlanAnalytics) {
+        let completion_millis = plan.closed_millis.saturating_sub(plan.started_millis);
+        let chain_counters = match self
+            .per_chain
+            .iter()
+            .position(|(chain, _)| *chain == plan.src_chain)
+        {
+            Some(i) => &mut self.per_chain[i].1,
+            None => {
+                self.per_chain
+                    .push((plan.src_chain, StatsCounters::default()));
+                &mut self.per_chain.last_mut().expect("Just pushed").1
+            }
+        };
+        chain_counters.add_swap(
+            plan.is_success,
+            completion_millis,
+            &plan.amount_in,
+            Some(&plan.protocol_fee),
+        );
+        for (chain, router, amount_in) in plan.dex_swaps.iter() {
+            let dex_counters =
+                match self.per_dex.iter().position(|(dex_chain, dex_router, _)| {
+                    dex_chain == chain && dex_router == router
+                }) {
+                    Some(i) => &mut self.per_dex[i].2,
+                    None => {
+                        self.per_dex
+                            .push((*chain, *router, StatsCounters::default()));
+                        &mut self.per_dex.last_mut().expect("Just pushed").2
+                    }
+                };
+            dex_counters.add_swap(plan.is_success, completion_millis, amount_in, None);
+        }
+    }
+}
+
+/// Returned by get_stats. Totals are over all chains for the requested days
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StatsReport {
+    pub num_swaps: u32,
+    pub num_succeeded: u32,
+    pub success_rate_bps: u16,
+    pub avg_completion_millis: Option<MillisSinceEpoch>,
+    pub volume: Vec<TokenAmount>,
+    pub fee_revenue: Vec<TokenAmount>,
+    pub daily_stats: Vec<DailyStats>,
+}
+
+impl From<Vec<DailyStats>> for StatsReport {
+    fn from(daily_stats: Vec<DailyStats>) -> Self {
+        let mut totals = StatsCounters::default();
+        for (_, chain_counters) in daily_stats.iter().flat_map(|day| day.per_chain.iter()) {
+            totals.merge(chain_counters);
+        }
+        Self {
+            num_swaps: totals.num_swaps,
+            num_succeeded: totals.num_succeeded,
+            success_rate_bps: totals.success_rate_bps(),
+            avg_completion_millis: totals.avg_completion_millis(),
+            volume: totals.volume,
+            fee_revenue: totals.fee_revenue,
+            daily_stats,
+        }
+    }
+}
+
+/// Volume routed through one DEX router over the days of a DexVolumeReport
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DexVolume {
+    pub chain: UniversalChainId,
+    pub dex_router: EthAddress,
+    // None if the router is no longer in the DEX registry
+    pub dex_id: Option<DexId>,
+    // Swap steps, so a plan that splits across the DEX or trades on it twice counts more than once
+    pub num_swaps: u32,
+    // The swap steps' amount in, per token
+    pub volume: Vec<TokenAmount>,
+}
+
+/// Returned by get_dex_volume_report, for claiming volume-based fee rebates from DEXes. Only
+/// swap steps that went through count, also when the plan as a whole failed
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DexVolumeReport {
+    pub from_day: DaysSinceEpoch,
+    pub to_day: DaysSinceEpoch,
+    pub per_dex: Vec<DexVolume>,
+}
+
+impl DexVolumeReport {
+    // Swaps count towards the day their plan closed. Days outside from_day..=to_day are skipped
+    pub fn new(
+        from_day: DaysSinceEpoch,
+        to_day: DaysSinceEpoch,
+        daily_stats: &[DailyStats],
+    ) -> Self {
+        let mut per_dex: Vec<(UniversalChainId, EthAddress, StatsCounters)> = Vec::new();
+        for (chain, router, dex_counters) in daily_stats
+            .iter()
+            .filter(|stats| stats.day >= from_day && stats.day <= to_day)
+            .flat_map(|stats| stats.per_dex.iter())
+        {
+            match per_dex
+                .iter_mut()
+                .find(|(dex_chain, dex_router, _)| dex_chain == chain && dex_router == router)
+            {
+                Some((_, _, counters)) => counters.merge(dex_counters),
+                None => per_dex.push((*chain, *router, dex_counters.clone())),
+            }
+        }
+        Self {
+            from_day,
+            to_day,
+            per_dex: per_dex
+                .into_iter()
+                .map(|(chain, dex_router, counters)| DexVolume {
+                    chain,
+                    dex_router,
+                    dex_id: get_dexes_from_chain_id(&chain)
+                        .into_iter()
+                        .find(|dex| dex.eth_dex_router == dex_router)
+                        .map(|dex| dex.id),
+                    num_swaps: counters.num_swaps,
+                    volume: counters.volume,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Rolls the queued PlanAnalytics into the given days' stats, creating days as needed.
+/// Returns the days that changed
+pub fn aggregate_plan_analytics(
+    daily_stats: &mut Vec<DailyStats>,
+    plans: &[PlanAnalytics],
+) -> Vec<DaysSinceEpoch> {
+    let mut changed_days = vec![];
+    for plan in plans.iter() {
+        let day = get_day(plan.closed_millis);
+        match daily_stats.iter_mut().find(|stats| stats.day == day) {
+            Some(stats) => stats.add_plan(plan),
+            None => {
+                let mut stats = DailyStats::new(day);
+                stats.add_plan(plan);
+                daily_stats.push(stats);
+            }
+        }
+        if !changed_days.contains(&day) {
+            changed_days.push(day);
+        }
+    }
+    changed_days
+}
+
+#[cfg(test)]
+mod plan_analytics_tests {
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        dex::dex_registry,
+        token::universal_token_id_registry::{ASTR_NATIVE, DOT_NATIVE, GLMR_NATIVE},
+    };
+
+    use super::*;
+    use crate::fx_rate::ReferenceCurrency;
+
+    fn plan_analytics(
+        src_token: UniversalTokenId,
+        closed_millis: MillisSinceEpoch,
+        is_success: bool,
+        dex_swaps: Vec<(UniversalChainId, EthAddress, TokenAmount)>,
+    ) -> PlanAnalytics {
+        PlanAnalytics {
+            exec_plan_uuid: Uuid::new([0u8; 16]),
+            src_chain: src_token.chain,
+            started_millis: closed_millis - 60_000,
+            closed_millis,
+            is_success,
+            amount_in: TokenAmount {
+                token: src_token,
+                amount: 1_000,
+            },
+            protocol_fee: TokenAmount {
+                token: DOT_NATIVE,
+                amount: if is_success { 5 } else { 0 },
+            },
+            dex_swaps,
+            amount_out: None,
+            quote: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_by_day_chain_and_dex() {
+        let router = EthAddress::zero();
+        let glmr_swap = (
+            MOONBEAM,
+            router,
+            TokenAmount {
+                token: GLMR_NATIVE,
+                amount: 1_000,
+            },
+        );
+        let plans = vec![
+            plan_analytics(
+                GLMR_NATIVE,
+                MILLIS_PER_DAY + 1,
+                true,
+                vec![glmr_swap.clone()],
+            ),
+            plan_analytics(GLMR_NATIVE, MILLIS_PER_DAY + 2, false, vec![glmr_swap]),
+            plan_analytics(ASTR_NATIVE, MILLIS_PER_DAY + 3, true, vec![]),
+            plan_analytics(ASTR_NATIVE, 2 * MILLIS_PER_DAY, true, vec![]),
+        ];
+        let mut daily_stats = vec![];
+        assert_eq!(
+            aggregate_plan_analytics(&mut daily_stats, &plans),
+            vec![1, 2]
+        );
+
+        let day1 = &daily_stats[0];
+        assert_eq!(day1.per_chain.len(), 2);
+        let (chain, moonbeam_counters) = &day1.per_chain[0];
+        assert_eq!(*chain, MOONBEAM);
+        assert_eq!(moonbeam_counters.num_swaps, 2);
+        assert_eq!(moonbeam_counters.success_rate_bps(), 5_000);
+        assert_eq!(moonbeam_counters.avg_completion_millis(), Some(60_000));
+        assert_eq!(
+            moonbeam_counters.volume,
+            vec![TokenAmount {
+                token: GLMR_NATIVE,
+                amount: 2_000
+            }]
+        );
+        assert_eq!(day1.per_chain[1].0, ASTAR);
+        assert_eq!(day1.per_dex.len(), 1);
+        assert_eq!(day1.per_dex[0].2.num_swaps, 2);
+
+        let report = StatsReport::from(daily_stats);
+        assert_eq!(report.num_swaps, 4);
+        assert_eq!(report.num_succeeded, 3);
+        assert_eq!(report.success_rate_bps, 7_500);
+        assert_eq!(
+            report.fee_revenue,
+            vec![TokenAmount {
+                token: DOT_NATIVE,
+                amount: 15
+            }]
+        );
+    }
+
+    #[test]
+    fn test_execution_price() {
+        // 2 DOT (10 decimals) in for 15 GLMR (18 decimals) out
+        let mut plan = plan_analytics(DOT_NATIVE, MILLIS_PER_DAY, true, vec![]);
+        plan.amount_in.amount = 20_000_000_000;
+        assert_eq!(ExecutionPrice::new(&plan, 10, 18), None);
+
+        plan.amount_out = Some(TokenAmount {
+            token: GLMR_NATIVE,
+            amount: 15 * u128::pow(10, 18),
+        });
+        let price = ExecutionPrice::new(&plan, 10, 18).expect("Plan succeeded");
+        assert_eq!(price.realized_price, 75 * u128::pow(10, 17)); // 7.5 GLMR/DOT
+        assert_eq!(price.src_token_usd_price, None);
+
+        // Quoted 16 GLMR out, with DOT at $5 and GLMR at $0.625
+        plan.quote = Some(PlanQuote {
+            amount_out: 16 * u128::pow(10, 18),
+            amount_in_usd: 10 * u128::pow(10, 18),
+            amount_out_usd: 10 * u128::pow(10, 18),
+        });
+        let price = ExecutionPrice::new(&plan, 10, 18).expect("Plan succeeded");
+        assert_eq!(price.src_token_usd_price, Some(5 * u128::pow(10, 18)));
+        assert_eq!(price.dest_token_usd_price, Some(625 * u128::pow(10, 15)));
+        assert_eq!(price.src_token_reference_price, None);
+
+        // At 0.5 units of the reference currency per $1
+        let price = price.with_fx_rate(FxRate {
+            currency: ReferenceCurrency::Eur,
+            units_per_usd: 5 * u128::pow(10, 17),
+        });
+        assert_eq!(
+            price.src_token_reference_price,
+            Some(25 * u128::pow(10, 17))
+        );
+        assert_eq!(
+            price.dest_token_reference_price,
+            Some(3125 * u128::pow(10, 14))
+        );
+    }
+
+    #[test]
+    fn test_dex_volume_report() {
+        let stellaswap_router = dex_registry::STELLASWAP.eth_dex_router;
+        let glmr_swap = |router| {
+            (
+                MOONBEAM,
+                router,
+                TokenAmount {
+                    token: GLMR_NATIVE,
+                    amount: 1_000,
+                },
+            )
+        };
+        let plans = vec![
+            plan_analytics(
+                GLMR_NATIVE,
+                MILLIS_PER_DAY,
+                true,
+                vec![glmr_swap(stellaswap_router)],
+            ),
+            plan_analytics(
+                GLMR_NATIVE,
+                2 * MILLIS_PER_DAY,
+                false,
+                vec![glmr_swap(stellaswap_router), glmr_swap(EthAddress::zero())],
+            ),
+            plan_analytics(
+                GLMR_NATIVE,
+                3 * MILLIS_PER_DAY,
+                true,
+                vec![glmr_swap(stellaswap_router)],
+            ),
+        ];
+        let mut daily_stats = vec![];
+        aggregate_plan_analytics(&mut daily_stats, &plans);
+
+        let report = DexVolumeReport::new(1, 2, &daily_stats);
+        assert_eq!(report.per_dex.len(), 2);
+        assert_eq!(report.per_dex[0].dex_id, Some(DexId::Stellaswap));
+        assert_eq!(report.per_dex[0].num_swaps, 2);
+        assert_eq!(
+            report.per_dex[0].volume,
+            vec![TokenAmount {
+                token: GLMR_NATIVE,
+                amount: 2_000
+            }]
+        );
+        assert_eq!(report.per_dex[1].dex_id, None);
+        assert_eq!(report.per_dex[1].num_swaps, 1);
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let report = StatsReport::from(vec![]);
+        assert_eq!(report.num_swaps, 0);
+        assert_eq!(report.success_rate_bps, 0);
+        assert_eq!(report.avg_completion_millis, None);
+    }
+}
@@ -0,0 +1,618 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use core::cell::RefCell;
+use ink::prelude::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::Deserialize;
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::{
+    utils::general_utils::{hex_string_to_vec, slice_to_hex_string},
+    uuid::Uuid,
+};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStep, ExecutionStepEnum};
+
+use super::{
+    address_display::StepAddresses,
+    plan_analytics::{get_day, DaysSinceEpoch},
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+const PLAN_EVENTS_OBJECT_KEY_PREFIX: &str = "plan-events";
+const PLAN_EVENT_LOG_OBJECT_KEY_PREFIX: &str = "plan-event-log";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PlanEventKind {
+    // The user's address on the src chain and the destination, as their chains' explorers show
+    // them (see address_display). Empty when read back from logs written before they were added
+    Created {
+        src_addr: String,
+        dest_addr: String,
+    },
+    StepTransition {
+        step_uuid: Uuid,
+        from: ExecutableSimpleStatus,
+        to: ExecutableSimpleStatus,
+    },
+    Completed(ExecutableSimpleStatus),
+    // Flagged by the stuck plan watchdog. Does not change any status, and may repeat on every
+    // scan until the plan makes progress
+    Stuck {
+        stalled_millis: MillisSinceEpoch,
+        escalated: bool,
+    },
+    // An admin forced a step's status (see manual_override), giving their reason
+    ManualOverride {
+        step_uuid: Uuid,
+        from: ExecutableSimpleStatus,
+        to: ExecutableSimpleStatus,
+        txn_id: String,
+        justification: String,
+    },
+    // The stored plan no longer decodes, so it was copied to the dead-letter prefix and taken
+    // off the plan list. Always the plan's last event
+    Quarantined,
+    // A signed txn or extrinsic exactly as the step sent it, so that an auditor can decode it
+    // and check it against the plan. chain_kind says how txn_hash was computed from raw_txn
+    TxnBroadcast {
+        step_uuid: Uuid,
+        chain_kind: TxnChainKind,
+        txn_hash: [u8; 32],
+        raw_txn: Vec<u8>,
+    },
+}
+
+/// The kind of chain a broadcast txn was sent to
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TxnChainKind {
+    // txn_hash is the keccak256 of the signed txn
+    Ethereum,
+    // txn_hash is the blake2_256 of the extrinsic
+    Substrate,
+}
+
+/// Lifecycle event of an ExecutionPlan. These are only ever appended to the event stream
+/// (one S3 object per batch) so that external pipelines can index them without touching
+/// DynamoDB
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlanEvent {
+    pub exec_plan_uuid: Uuid,
+    pub timestamp_millis: MillisSinceEpoch,
+    pub kind: PlanEventKind,
+}
+
+impl PlanEvent {
+    // One JSON object per line (NDJSON), which is what Athena/Spark expect
+    pub fn to_json(&self) -> String {
+        let (event, details) = match &self.kind {
+            PlanEventKind::Created {
+                src_addr,
+                dest_addr,
+            } => (
+                "created",
+                format!(",\"src_addr\":\"{}\",\"dest_addr\":\"{}\"", src_addr, dest_addr),
+            ),
+            PlanEventKind::StepTransition {
+                step_uuid,
+                from,
+                to,
+            } => (
+                "step_transition",
+                format!(
+                    ",\"step_uuid\":\"{}\",\"from\":\"{}\",\"to\":\"{}\"",
+                    step_uuid.to_hex_string(),
+                    get_status_name(from),
+                    get_status_name(to)
+                ),
+            ),
+            PlanEventKind::Completed(status) => (
+                "completed",
+                format!(",\"status\":\"{}\"", get_status_name(status)),
+            ),
+            PlanEventKind::Stuck {
+                stalled_millis,
+                escalated,
+            } => (
+                "stuck",
+                format!(
+                    ",\"stalled_millis\":{},\"escalated\":{}",
+                    stalled_millis, escalated
+                ),
+            ),
+            // manual_override only lets through text that needs no escaping
+            PlanEventKind::ManualOverride {
+                step_uuid,
+                from,
+                to,
+                txn_id,
+                justification,
+            } => (
+                "manual_override",
+                format!(
+                    ",\"step_uuid\":\"{}\",\"from\":\"{}\",\"to\":\"{}\",\"txn_id\":\"{}\",\"justification\":\"{}\"",
+                    step_uuid.to_hex_string(),
+                    get_status_name(from),
+                    get_status_name(to),
+                    txn_id,
+                    justification
+                ),
+            ),
+            PlanEventKind::Quarantined => ("quarantined", String::new()),
+            PlanEventKind::TxnBroadcast {
+                step_uuid,
+                chain_kind,
+                txn_hash,
+                raw_txn,
+            } => (
+                "txn_broadcast",
+                format!(
+                    ",\"step_uuid\":\"{}\",\"chain_kind\":\"{}\",\"txn_hash\":\"{}\",\"raw_txn\":\"{}\"",
+                    step_uuid.to_hex_string(),
+                    get_chain_kind_name(chain_kind),
+                    slice_to_hex_string(txn_hash),
+                    slice_to_hex_string(raw_txn)
+                ),
+            ),
+        };
+        format!(
+            "{{\"exec_plan_uuid\":\"{}\",\"timestamp_millis\":{},\"event\":\"{}\"{}}}",
+            self.exec_plan_uuid.to_hex_string(),
+            self.timestamp_millis,
+            event,
+            details
+        )
+    }
+}
+
+// Field names match PlanEvent::to_json. The event-specific fields are absent for other events
+#[derive(Deserialize)]
+struct PlanEventJson<'a> {
+    exec_plan_uuid: &'a str,
+    timestamp_millis: MillisSinceEpoch,
+    event: &'a str,
+    #[serde(borrow)]
+    src_addr: Option<&'a str>,
+    #[serde(borrow)]
+    dest_addr: Option<&'a str>,
+    #[serde(borrow)]
+    step_uuid: Option<&'a str>,
+    #[serde(borrow)]
+    from: Option<&'a str>,
+    #[serde(borrow)]
+    to: Option<&'a str>,
+    #[serde(borrow)]
+    status: Option<&'a str>,
+    stalled_millis: Option<MillisSinceEpoch>,
+    escalated: Option<bool>,
+    #[serde(borrow)]
+    txn_id: Option<&'a str>,
+    #[serde(borrow)]
+    justification: Option<&'a str>,
+    #[serde(borrow)]
+    chain_kind: Option<&'a str>,
+    #[serde(borrow)]
+    txn_hash: Option<&'a str>,
+    #[serde(borrow)]
+    raw_txn: Option<&'a str>,
+}
+
+impl PlanEvent {
+    // Inverse of to_json, for tools that read the event stream back (e.g. plan_replay)
+    pub fn from_json(line: &str) -> Option<Self> {
+        let (raw, _): (PlanEventJson, usize) = serde_json_core::from_str(line).ok()?;
+        let kind = match raw.event {
+            "created" => PlanEventKind::Created {
+                src_addr: raw.src_addr.unwrap_or_default().to_string(),
+                dest_addr: raw.dest_addr.unwrap_or_default().to_string(),
+            },
+            "step_transition" => PlanEventKind::StepTransition {
+                step_uuid: parse_uuid(raw.step_uuid?)?,
+                from: parse_status_name(raw.from?)?,
+                to: parse_status_name(raw.to?)?,
+            },
+            "completed" => PlanEventKind::Completed(parse_status_name(raw.status?)?),
+            "stuck" => PlanEventKind::Stuck {
+                stalled_millis: raw.stalled_millis?,
+                escalated: raw.escalated?,
+            },
+            "manual_override" => PlanEventKind::ManualOverride {
+                step_uuid: parse_uuid(raw.step_uuid?)?,
+                from: parse_status_name(raw.from?)?,
+                to: parse_status_name(raw.to?)?,
+                txn_id: raw.txn_id?.to_string(),
+                justification: raw.justification?.to_string(),
+            },
+            "quarantined" => PlanEventKind::Quarantined,
+            "txn_broadcast" => PlanEventKind::TxnBroadcast {
+                step_uuid: parse_uuid(raw.step_uuid?)?,
+                chain_kind: parse_chain_kind_name(raw.chain_kind?)?,
+                txn_hash: hex_string_to_vec(raw.txn_hash?).ok()?.try_into().ok()?,
+                raw_txn: hex_string_to_vec(raw.raw_txn?).ok()?,
+            },
+            _ => return None,
+        };
+        Some(Self {
+            exec_plan_uuid: parse_uuid(raw.exec_plan_uuid)?,
+            timestamp_millis: raw.timestamp_millis,
+            kind,
+        })
+    }
+}
+
+impl PlanEventKind {
+    pub fn created(exec_plan: &ExecutionPlan) -> Self {
+        Self::Created {
+            src_addr: StepAddresses::from(&exec_plan.prestart_user_to_escrow_transfer).src_addr,
+            dest_addr: StepAddresses::from(&exec_plan.postend_escrow_to_user_transfer).dest_addr,
+        }
+    }
+}
+
+/// A txn or extrinsic that a step sent, kept until its plan's events are saved
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BroadcastTxn {
+    pub exec_step_uuid: Uuid,
+    pub chain_kind: TxnChainKind,
+    pub txn_hash: [u8; 32],
+    pub raw_txn: Vec<u8>,
+}
+
+// Filled in from send_eth_txn and send_extrinsic, which only have &ExecuteStepMeta
+#[derive(Default)]
+pub struct BroadcastLog {
+    txns: RefCell<Vec<BroadcastTxn>>,
+}
+
+impl BroadcastLog {
+    pub fn record(&self, txn: BroadcastTxn) {
+        self.txns.borrow_mut().push(txn);
+    }
+
+    // Drains the txns recorded so far
+    pub fn take(&self) -> Vec<BroadcastTxn> {
+        self.txns.take()
+    }
+}
+
+// An approval (or a zap's swap) is logged under the step it is for, even though it has a UUID
+// of its own. Txns of steps outside the plan (e.g. an allowance revocation) are left out
+pub fn get_txn_broadcast_events(
+    exec_plan: &ExecutionPlan,
+    broadcast_txns: Vec<BroadcastTxn>,
+    timestamp_millis: MillisSinceEpoch,
+) -> Vec<PlanEvent> {
+    let steps = get_all_steps(exec_plan);
+    broadcast_txns
+        .into_iter()
+        .filter_map(|txn| {
+            let step = steps.iter().find(|step| {
+                *step.get_uuid() == txn.exec_step_uuid
+                    || get_extra_txn_uuid(step) == Some(&txn.exec_step_uuid)
+            })?;
+            Some(PlanEvent {
+                exec_plan_uuid: exec_plan.uuid.clone(),
+                timestamp_millis,
+                kind: PlanEventKind::TxnBroadcast {
+                    step_uuid: step.get_uuid().clone(),
+                    chain_kind: txn.chain_kind,
+                    txn_hash: txn.txn_hash,
+                    raw_txn: txn.raw_txn,
+                },
+            })
+        })
+        .collect()
+}
+
+// The UUID a step's earlier txn takes its nonce under, for the steps that send more than one
+fn get_extra_txn_uuid(step: &ExecutionStep) -> Option<&Uuid> {
+    match &step.inner {
+        ExecutionStepEnum::EthContractCall(step) => Some(&step.approval_uuid),
+        ExecutionStepEnum::EthAggregatorSwap(step) => Some(&step.approval_uuid),
+        ExecutionStepEnum::EthZapIn(step) => Some(&step.swap_uuid),
+        ExecutionStepEnum::EthRemoveLiquidity(step) => Some(&step.approval_uuid),
+        _ => None,
+    }
+}
+
+pub fn to_ndjson(events: &[PlanEvent]) -> String {
+    events
+        .iter()
+        .map(|event| format!("{}\n", event.to_json()))
+        .collect()
+}
+
+// Every step of the plan, in roughly the order they execute
+pub(super) fn get_all_steps(exec_plan: &ExecutionPlan) -> Vec<&ExecutionStep> {
+    let mut steps = Vec::new();
+    steps.push(&exec_plan.prestart_user_to_escrow_transfer);
+    if let Some(deposit_sweep) = &exec_plan.deposit_sweep {
+        steps.push(deposit_sweep);
+    }
+    if let Some(remove_liquidity) = &exec_plan.remove_liquidity {
+        steps.push(remove_liquidity);
+    }
+    for path in exec_plan.paths.iter() {
+        steps.extend(path.steps.iter());
+    }
+    steps.push(&exec_plan.postend_escrow_to_user_transfer);
+    steps.push(&exec_plan.refund_escrow_to_user_transfer);
+    steps
+}
+
+/// Snapshot of every step's status, used to diff a plan before and after it steps forward
+pub fn get_step_statuses(exec_plan: &ExecutionPlan) -> Vec<(Uuid, ExecutableSimpleStatus)> {
+    get_all_steps(exec_plan)
+        .into_iter()
+        .map(|step| (step.get_uuid().clone(), step.get_status()))
+        .collect()
+}
+
+pub fn get_step_transition_events(
+    exec_plan_uuid: &Uuid,
+    old_step_statuses: &[(Uuid, ExecutableSimpleStatus)],
+    new_step_statuses: &[(Uuid, ExecutableSimpleStatus)],
+    timestamp_millis: MillisSinceEpoch,
+) -> Vec<PlanEvent> {
+    new_step_statuses
+        .iter()
+        .filter_map(|(step_uuid, new_status)| {
+            let old_status = old_step_statuses
+                .iter()
+                .find(|(uuid, _)| uuid == step_uuid)
+                .map_or(ExecutableSimpleStatus::NotStarted, |(_, status)| {
+                    status.clone()
+                });
+            if old_status == *new_status {
+                None
+            } else {
+                Some(PlanEvent {
+                    exec_plan_uuid: exec_plan_uuid.clone(),
+                    timestamp_millis,
+                    kind: PlanEventKind::StepTransition {
+                        step_uuid: step_uuid.clone(),
+                        from: old_status,
+                        to: new_status.clone(),
+                    },
+                })
+            }
+        })
+        .collect()
+}
+
+/// Hive-style date partition (plan-events/dt=YYYY-MM-DD/...) so queries can prune by day.
+/// The timestamp comes first in the file name so objects list in order within a partition
+pub fn get_plan_events_object_key(
+    exec_plan_uuid: &Uuid,
+    timestamp_millis: MillisSinceEpoch,
+) -> String {
+    let (year, month, day) = get_civil_date(get_day(timestamp_millis));
+    format!(
+        "{}/dt={:04}-{:02}-{:02}/{}-{}.ndjson",
+        PLAN_EVENTS_OBJECT_KEY_PREFIX,
+        year,
+        month,
+        day,
+        timestamp_millis,
+        exec_plan_uuid.to_hex_string()
+    )
+}
+
+// S3 can't list the stream's objects by plan, so each plan's events are also appended to an
+// object of its own (same NDJSON format)
+pub fn get_plan_event_log_object_key(exec_plan_uuid: &Uuid) -> String {
+    format!(
+        "{}/{}.ndjson",
+        PLAN_EVENT_LOG_OBJECT_KEY_PREFIX,
+        exec_plan_uuid.to_hex_string()
+    )
+}
+
+pub(super) fn get_status_name(status: &ExecutableSimpleStatus) -> &'static str {
+    match status {
+        ExecutableSimpleStatus::NotStarted => "not_started",
+        ExecutableSimpleStatus::InProgress => "in_progress",
+        ExecutableSimpleStatus::Failed => "failed",
+        ExecutableSimpleStatus::Dropped => "dropped",
+        ExecutableSimpleStatus::Succeeded => "succeeded",
+        ExecutableSimpleStatus::Refunded => "refunded",
+        ExecutableSimpleStatus::PrestartConfirming => "prestart_confirming",
+    }
+}
+
+fn parse_status_name(name: &str) -> Option<ExecutableSimpleStatus> {
+    match name {
+        "not_started" => Some(ExecutableSimpleStatus::NotStarted),
+        "in_progress" => Some(ExecutableSimpleStatus::InProgress),
+        "failed" => Some(ExecutableSimpleStatus::Failed),
+        "dropped" => Some(ExecutableSimpleStatus::Dropped),
+        "succeeded" => Some(ExecutableSimpleStatus::Succeeded),
+        "refunded" => Some(ExecutableSimpleStatus::Refunded),
+        "prestart_confirming" => Some(ExecutableSimpleStatus::PrestartConfirming),
+        _ => None,
+    }
+}
+
+fn get_chain_kind_name(chain_kind: &TxnChainKind) -> &'static str {
+    match chain_kind {
+        TxnChainKind::Ethereum => "ethereum",
+        TxnChainKind::Substrate => "substrate",
+    }
+}
+
+fn parse_chain_kind_name(name: &str) -> Option<TxnChainKind> {
+    match name {
+        "ethereum" => Some(TxnChainKind::Ethereum),
+        "substrate" => Some(TxnChainKind::Substrate),
+        _ => None,
+    }
+}
+
+fn parse_uuid(hex_str: &str) -> Option<Uuid> {
+    let raw_uuid: [u8; 16] = hex_string_to_vec(hex_str).ok()?.try_into().ok()?;
+    Some(Uuid::new(raw_uuid))
+}
+
+// Days since 1970-01-01 to (year, month, day) in the proleptic Gregorian calendar
+// (http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+fn get_civil_date(days: DaysSinceEpoch) -> (u32, u32, u32) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod plan_events_tests {
+    use ink::prelude::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(get_civil_date(0), (1970, 1, 1));
+        assert_eq!(get_civil_date(11_016), (2000, 2, 29));
+        assert_eq!(get_civil_date(19_447), (2023, 3, 31));
+    }
+
+    #[test]
+    fn test_plan_events_object_key() {
+        let uuid = Uuid::new([0xab; 16]);
+        assert_eq!(
+            get_plan_events_object_key(&uuid, 1_680_307_200_000),
+            "plan-events/dt=2023-04-01/1680307200000-0xabababababababababababababababab.ndjson"
+        );
+    }
+
+    #[test]
+    fn test_step_transition_events() {
+        let plan_uuid = Uuid::new([1; 16]);
+        let (step1, step2, step3) = (Uuid::new([2; 16]), Uuid::new([3; 16]), Uuid::new([4; 16]));
+        let old_statuses = vec![
+            (step1.clone(), ExecutableSimpleStatus::InProgress),
+            (step2.clone(), ExecutableSimpleStatus::NotStarted),
+            (step3.clone(), ExecutableSimpleStatus::NotStarted),
+        ];
+        let new_statuses = vec![
+            (step1.clone(), ExecutableSimpleStatus::Succeeded),
+            (step2.clone(), ExecutableSimpleStatus::InProgress),
+            (step3, ExecutableSimpleStatus::NotStarted),
+        ];
+        let events = get_step_transition_events(&plan_uuid, &old_statuses, &new_statuses, 5);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].kind,
+            PlanEventKind::StepTransition {
+                step_uuid: step1,
+                from: ExecutableSimpleStatus::InProgress,
+                to: ExecutableSimpleStatus::Succeeded,
+            }
+        );
+        assert_eq!(
+            to_ndjson(&events[1..]),
+            "{\"exec_plan_uuid\":\"0x01010101010101010101010101010101\",\"timestamp_millis\":5,\
+            \"event\":\"step_transition\",\"step_uuid\":\"0x03030303030303030303030303030303\",\
+            \"from\":\"not_started\",\"to\":\"in_progress\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_event_json_roundtrip() {
+        let events = vec![
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 5,
+                kind: PlanEventKind::Created {
+                    src_addr: "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string(),
+                    dest_addr: "0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a".to_string(),
+                },
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 6,
+                kind: PlanEventKind::StepTransition {
+                    step_uuid: Uuid::new([2; 16]),
+                    from: ExecutableSimpleStatus::InProgress,
+                    to: ExecutableSimpleStatus::Dropped,
+                },
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 7,
+                kind: PlanEventKind::Completed(ExecutableSimpleStatus::Refunded),
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 8,
+                kind: PlanEventKind::Stuck {
+                    stalled_millis: 700_000,
+                    escalated: true,
+                },
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 9,
+                kind: PlanEventKind::ManualOverride {
+                    step_uuid: Uuid::new([3; 16]),
+                    from: ExecutableSimpleStatus::InProgress,
+                    to: ExecutableSimpleStatus::Succeeded,
+                    txn_id: "1234-5".to_string(),
+                    justification: "Indexer down, delivery seen on Subscan".to_string(),
+                },
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 10,
+                kind: PlanEventKind::Quarantined,
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 11,
+                kind: PlanEventKind::TxnBroadcast {
+                    step_uuid: Uuid::new([4; 16]),
+                    chain_kind: TxnChainKind::Ethereum,
+                    txn_hash: [7; 32],
+                    raw_txn: vec![0xf8, 0x6b, 0x01, 0x02],
+                },
+            },
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1; 16]),
+                timestamp_millis: 12,
+                kind: PlanEventKind::TxnBroadcast {
+                    step_uuid: Uuid::new([5; 16]),
+                    chain_kind: TxnChainKind::Substrate,
+                    txn_hash: [8; 32],
+                    raw_txn: vec![0x45, 0x02, 0x84],
+                },
+            },
+        ];
+        for event in events {
+            assert_eq!(PlanEvent::from_json(&event.to_json()), Some(event));
+        }
+        assert_eq!(PlanEvent::from_json("{\"event\":\"created\"}"), None);
+    }
+}
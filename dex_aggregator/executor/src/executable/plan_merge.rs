@@ -0,0 +1,255 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use privadex_execution_plan::execution_plan::{
+    ExecutionPath, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+};
+
+use super::traits::{Executable, ExecutableSimpleStatus};
+
+// Merges our copy of an ExecutionPlan into theirs, i.e. the copy someone else saved to S3 after
+// we pulled ours (see ExecutableError::VersionConflict). Steps only move forward, so each path
+// and each standalone step is taken from whichever copy got further with it. Everything else
+// (deadline, review status, ...) comes from theirs, since those are only changed out of band
+pub fn merge_exec_plans(ours: &ExecutionPlan, theirs: ExecutionPlan) -> ExecutionPlan {
+    let mut merged = theirs;
+    merge_step(
+        &mut merged.prestart_user_to_escrow_transfer,
+        &ours.prestart_user_to_escrow_transfer,
+    );
+    if let (Some(merged_sweep), Some(our_sweep)) = (&mut merged.deposit_sweep, &ours.deposit_sweep)
+    {
+        merge_step(merged_sweep, our_sweep);
+    }
+    // No path runs until an LP source's LP tokens are redeemed, which also rescales the paths.
+    // So if we got further with that, theirs are all unstarted and ours are the ones to keep
+    let is_our_remove_liquidity_further =
+        match (&mut merged.remove_liquidity, &ours.remove_liquidity) {
+            (Some(merged_step), Some(our_step)) => {
+                let is_further = get_step_progress(our_step) > get_step_progress(merged_step);
+                merge_step(merged_step, our_step);
+                is_further
+            }
+            _ => false,
+        };
+    for (merged_path, our_path) in merged.paths.iter_mut().zip(ours.paths.iter()) {
+        // Paths are never reordered, but don't trust an index match alone
+        if merged_path.steps.first().map(|step| step.get_uuid())
+            != our_path.steps.first().map(|step| step.get_uuid())
+        {
+            continue;
+        }
+        if is_our_remove_liquidity_further
+            || get_path_progress(our_path) > get_path_progress(merged_path)
+        {
+            *merged_path = our_path.clone();
+        }
+    }
+    merge_step(
+        &mut merged.postend_escrow_to_user_transfer,
+        &ours.postend_escrow_to_user_transfer,
+    );
+    merge_step(
+        &mut merged.refund_escrow_to_user_transfer,
+        &ours.refund_escrow_to_user_transfer,
+    );
+    merged.last_progress_millis = merged.last_progress_millis.max(ours.last_progress_millis);
+    merged.prestart_confirmed_block = merged
+        .prestart_confirmed_block
+        .or(ours.prestart_confirmed_block);
+    merged
+}
+
+fn merge_step(merged: &mut ExecutionStep, ours: &ExecutionStep) {
+    if merged.get_uuid() == ours.get_uuid() && get_step_progress(ours) > get_step_progress(merged) {
+        *merged = ours.clone();
+    }
+}
+
+// A zap-in's swap and addLiquidity share its status, which goes back to NotStarted once the swap
+// is confirmed. So a confirmed swap comes first. Likewise for an LP source's approval
+fn get_step_progress(step: &ExecutionStep) -> (bool, u32) {
+    let is_swapped = match &step.inner {
+        ExecutionStepEnum::EthZapIn(zap_in) => !zap_in.is_swapping(),
+        ExecutionStepEnum::EthRemoveLiquidity(remove_liquidity) => !remove_liquidity.is_approving(),
+        _ => false,
+    };
+    (is_swapped, get_progress(&step.get_status()))
+}
+
+fn get_path_progress(path: &ExecutionPath) -> u32 {
+    path.steps
+        .iter()
+        .map(|step| get_progress(&step.get_status()))
+        .sum()
+}
+
+// A dropped step gets retried, so it is only just ahead of one that has not started
+fn get_progress(status: &ExecutableSimpleStatus) -> u32 {
+    match status {
+        ExecutableSimpleStatus::NotStarted => 0,
+        ExecutableSimpleStatus::Dropped => 1,
+        ExecutableSimpleStatus::InProgress | ExecutableSimpleStatus::PrestartConfirming => 2,
+        ExecutableSimpleStatus::Failed
+        | ExecutableSimpleStatus::Succeeded
+        | ExecutableSimpleStatus::Refunded => 3,
+    }
+}
+
+#[cfg(test)]
+mod plan_merge_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::{ChainTokenId, ERC20Token, EthAddress, EthTxnHash, UniversalTokenId},
+        registry::chain::universal_chain_id_registry::MOONBEAM,
+    };
+    use privadex_common::uuid::Uuid;
+    use privadex_execution_plan::{
+        execution_plan::{EthPendingTxnId, EthStepStatus, EthZapInStep},
+        test_utilities::execution_plan_factory::{self, common, path, ESCROW},
+    };
+
+    use super::*;
+
+    const HASH: EthTxnHash = EthTxnHash { 0: [0xaa; 32] };
+    const SUBMITTED: EthStepStatus = EthStepStatus::Submitted(EthPendingTxnId {
+        txn_hash: HASH,
+        end_block_num: 100,
+    });
+
+    fn eth_send(uuid_byte: u8, status: EthStepStatus) -> ExecutionStep {
+        execution_plan_factory::eth_send(uuid_byte, ESCROW, ESCROW, Some(1_000), status)
+    }
+
+    fn erc20(addr_byte: u8) -> UniversalTokenId {
+        UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress { 0: [addr_byte; 20] },
+            }),
+        }
+    }
+
+    fn zap_in(swap_txn_hash: Option<EthTxnHash>, status: EthStepStatus) -> ExecutionStep {
+        ExecutionStep::new(ExecutionStepEnum::EthZapIn(EthZapInStep {
+            uuid: Uuid::new([5u8; 16]),
+            dex_router_addr: EthAddress { 0: [0x44; 20] },
+            token: erc20(0x11),
+            pair_token: erc20(0x22),
+            lp_token: erc20(0x33),
+            amount: Some(1_000),
+            common: common(ESCROW, ESCROW),
+            swap_uuid: Uuid::new([7u8; 16]),
+            swap_txn_hash,
+            pair_token_amount: swap_txn_hash.map(|_| 490),
+            status,
+        }))
+    }
+
+    fn plan(path_statuses: [EthStepStatus; 2], postend_status: EthStepStatus) -> ExecutionPlan {
+        let [first_status, second_status] = path_statuses;
+        ExecutionPlan {
+            postend_escrow_to_user_transfer: eth_send(5, postend_status),
+            deadline_millis: 10_000,
+            ..execution_plan_factory::plan(vec![path(vec![
+                eth_send(2, first_status),
+                eth_send(3, second_status),
+            ])])
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_our_progress() {
+        let mut ours = plan(
+            [EthStepStatus::Confirmed(HASH), SUBMITTED],
+            EthStepStatus::NotStarted,
+        );
+        ours.last_progress_millis = 500;
+        // Someone else only touched the deadline
+        let mut theirs = plan(
+            [EthStepStatus::NotStarted, EthStepStatus::NotStarted],
+            EthStepStatus::NotStarted,
+        );
+        theirs.deadline_millis = 20_000;
+
+        let merged = merge_exec_plans(&ours, theirs);
+        assert_eq!(merged.paths, ours.paths);
+        assert_eq!(merged.deadline_millis, 20_000);
+        assert_eq!(merged.last_progress_millis, 500);
+    }
+
+    #[test]
+    fn test_merge_keeps_their_progress() {
+        let ours = plan(
+            [SUBMITTED, EthStepStatus::NotStarted],
+            EthStepStatus::NotStarted,
+        );
+        let theirs = plan(
+            [
+                EthStepStatus::Confirmed(HASH),
+                EthStepStatus::Confirmed(HASH),
+            ],
+            SUBMITTED,
+        );
+
+        let merged = merge_exec_plans(&ours, theirs.clone());
+        assert_eq!(merged, theirs);
+    }
+
+    #[test]
+    fn test_merge_takes_each_part_from_the_further_copy() {
+        let ours = plan(
+            [EthStepStatus::Confirmed(HASH), SUBMITTED],
+            EthStepStatus::NotStarted,
+        );
+        let theirs = plan(
+            [EthStepStatus::NotStarted, EthStepStatus::NotStarted],
+            SUBMITTED,
+        );
+
+        let merged = merge_exec_plans(&ours, theirs.clone());
+        assert_eq!(merged.paths, ours.paths);
+        assert_eq!(
+            merged.postend_escrow_to_user_transfer,
+            theirs.postend_escrow_to_user_transfer
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_confirmed_zap_in_swap() {
+        // The swap's confirmation set the status back to NotStarted for addLiquidity
+        let mut ours = plan(
+            [EthStepStatus::NotStarted, EthStepStatus::NotStarted],
+            EthStepStatus::NotStarted,
+        );
+        ours.postend_escrow_to_user_transfer = zap_in(Some(HASH), EthStepStatus::NotStarted);
+        let mut theirs = ours.clone();
+        theirs.postend_escrow_to_user_transfer = zap_in(None, SUBMITTED);
+
+        let merged = merge_exec_plans(&ours, theirs.clone());
+        assert_eq!(
+            merged.postend_escrow_to_user_transfer,
+            ours.postend_escrow_to_user_transfer
+        );
+        let merged = merge_exec_plans(&theirs, ours.clone());
+        assert_eq!(
+            merged.postend_escrow_to_user_transfer,
+            ours.postend_escrow_to_user_transfer
+        );
+    }
+}
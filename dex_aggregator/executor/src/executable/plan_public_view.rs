@@ -0,0 +1,241 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use privadex_chain_metadata::common::{UniversalAddress, UniversalChainId};
+use privadex_common::{
+    signature_scheme::SignatureScheme, utils::general_utils::slice_to_hex_string, uuid::Uuid,
+};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStep};
+
+use super::{
+    address_display::StepAddresses,
+    failure_bundle::{get_step_txn_ids, BundledTxnId},
+    plan_events::{get_all_steps, get_status_name},
+    step_timing::StepKind,
+    traits::Executable,
+};
+use crate::destination_proof::DestinationProof;
+
+// Redacted addresses keep this many characters at each end, e.g. 0x05a8...7a2a
+const REDACTED_ADDRESS_PREFIX_LEN: usize = 6;
+const REDACTED_ADDRESS_SUFFIX_LEN: usize = 4;
+
+/// What get_plan_public_view returns, as JSON for swap trackers on third-party sites. It has
+/// each step's status, chain and txns, which are public on-chain anyway. Addresses are
+/// shortened unless the plan's user proved they own it, so that a leaked plan UUID doesn't
+/// link the user's accounts together
+pub fn get_plan_public_view(exec_plan: &ExecutionPlan, reveal_addresses: bool) -> String {
+    let steps: Vec<String> = get_all_steps(exec_plan)
+        .into_iter()
+        .map(|step| get_step_json(step, reveal_addresses))
+        .collect();
+    let route_hash = match &exec_plan.route_hash {
+        Some(route_hash) => format!("\"{}\"", slice_to_hex_string(route_hash)),
+        None => "null".to_string(),
+    };
+    format!(
+        "{{\"exec_plan_uuid\":\"{}\",\"status\":\"{}\",\"deadline_millis\":{},\
+        \"route_hash\":{},\"addresses_redacted\":{},\"steps\":[{}]}}",
+        exec_plan.uuid.to_hex_string(),
+        get_status_name(&exec_plan.get_status()),
+        exec_plan.deadline_millis,
+        route_hash,
+        !reveal_addresses,
+        steps.join(",")
+    )
+}
+
+// What the plan's user signs to see the full addresses. It names the plan, so a proof can't be
+// replayed for another plan
+pub fn get_plan_owner_challenge(exec_plan_uuid: &Uuid) -> String {
+    format!(
+        "PrivaDEX: I started swap {} and want to see its addresses",
+        exec_plan_uuid.to_hex_string()
+    )
+}
+
+// The owner is whoever sent the deposit. An SS58 user (remark deposits) proves with its
+// Sr25519 key, and an EVM user with any proof that recovers to its address
+pub fn is_plan_owner(exec_plan: &ExecutionPlan, proof: &DestinationProof) -> bool {
+    let challenge = get_plan_owner_challenge(&exec_plan.uuid).into_bytes();
+    let user_addr = &exec_plan
+        .prestart_user_to_escrow_transfer
+        .get_common()
+        .src_addr;
+    match (user_addr, proof) {
+        (UniversalAddress::Substrate(owner), DestinationProof::Sr25519 { pubkey, signature }) => {
+            owner.0 == *pubkey
+                && SignatureScheme::Sr25519.verify_unprefixed_msg(pubkey, &challenge, signature)
+        }
+        (UniversalAddress::Substrate(_), DestinationProof::Ethereum(_)) => false,
+        (UniversalAddress::Ethereum(owner), _) => proof
+            .recover_signer(&challenge)
+            .map_or(false, |signer| signer == *owner),
+    }
+}
+
+fn get_step_json(step: &ExecutionStep, reveal_addresses: bool) -> String {
+    let addresses = StepAddresses::from(step);
+    let (src_addr, dest_addr) = if reveal_addresses {
+        (addresses.src_addr, addresses.dest_addr)
+    } else {
+        (
+            redact_address(&addresses.src_addr),
+            redact_address(&addresses.dest_addr),
+        )
+    };
+    let txns: Vec<String> = get_step_txn_ids(step)
+        .into_iter()
+        .map(|(chain, txn_id)| get_txn_json(&chain, &txn_id))
+        .collect();
+    format!(
+        "{{\"step_uuid\":\"{}\",\"kind\":\"{}\",\"chain\":\"{}\",\"status\":\"{}\",\
+        \"src_addr\":\"{}\",\"dest_addr\":\"{}\",\"txns\":[{}]}}",
+        step.get_uuid().to_hex_string(),
+        get_step_kind_name(&(&step.inner).into()),
+        step.get_src_chain(),
+        get_status_name(&step.get_status()),
+        src_addr,
+        dest_addr,
+        txns.join(",")
+    )
+}
+
+// Finalized extrinsics as <block>-<index>, the way Subscan links them. A pending extrinsic
+// only has its hash until the indexer finds it
+fn get_txn_json(chain: &UniversalChainId, txn_id: &BundledTxnId) -> String {
+    let txn = match txn_id {
+        BundledTxnId::Ethereum(txn_hash) => slice_to_hex_string(txn_hash.as_bytes()),
+        BundledTxnId::SubstratePending(extrinsic_hash) => {
+            slice_to_hex_string(extrinsic_hash.as_bytes())
+        }
+        BundledTxnId::Substrate(extrinsic_id) => {
+            format!(
+                "{}-{}",
+                extrinsic_id.block_num, extrinsic_id.extrinsic_index
+            )
+        }
+    };
+    format!("{{\"chain\":\"{}\",\"txn\":\"{}\"}}", chain, txn)
+}
+
+fn redact_address(address: &str) -> String {
+    if address.len() <= REDACTED_ADDRESS_PREFIX_LEN + REDACTED_ADDRESS_SUFFIX_LEN {
+        return address.to_string();
+    }
+    format!(
+        "{}...{}",
+        &address[..REDACTED_ADDRESS_PREFIX_LEN],
+        &address[address.len() - REDACTED_ADDRESS_SUFFIX_LEN..]
+    )
+}
+
+fn get_step_kind_name(kind: &StepKind) -> &'static str {
+    match kind {
+        StepKind::EthSend => "eth_send",
+        StepKind::ERC20Transfer => "erc20_transfer",
+        StepKind::EthWrap => "eth_wrap",
+        StepKind::EthUnwrap => "eth_unwrap",
+        StepKind::EthDexSwap => "eth_dex_swap",
+        StepKind::XCMTransfer => "xcm_transfer",
+        StepKind::WormholeTransfer => "wormhole_transfer",
+        StepKind::EthDepositSweep => "eth_deposit_sweep",
+        StepKind::SubstrateRemarkDeposit => "substrate_remark_deposit",
+        StepKind::EthContractCall => "eth_contract_call",
+        StepKind::EthFeeSkim => "eth_fee_skim",
+        StepKind::EthAggregatorSwap => "eth_aggregator_swap",
+        StepKind::EthZapIn => "eth_zap_in",
+        StepKind::EthRemoveLiquidity => "eth_remove_liquidity",
+    }
+}
+
+#[cfg(test)]
+mod plan_public_view_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::EthStepStatus,
+        test_utilities::execution_plan_factory::{eth_send, path, plan, ESCROW},
+    };
+
+    use super::*;
+
+    fn exec_plan() -> ExecutionPlan {
+        ExecutionPlan {
+            prestart_user_to_escrow_transfer: eth_send(
+                0xa1,
+                ESCROW,
+                ESCROW,
+                Some(1_000),
+                EthStepStatus::Confirmed(EthTxnHash::repeat_byte(0xab)),
+            ),
+            deadline_millis: 60_000,
+            ..plan(vec![path(Vec::new())])
+        }
+    }
+
+    #[test]
+    fn test_public_view_redacts_addresses() {
+        let exec_plan = exec_plan();
+        let view = get_plan_public_view(&exec_plan, false);
+        assert!(view.starts_with(&format!(
+            "{{\"exec_plan_uuid\":\"{}\",\"status\":\"in_progress\",\"deadline_millis\":60000,\
+            \"route_hash\":null,\"addresses_redacted\":true,\"steps\":[",
+            exec_plan.uuid.to_hex_string()
+        )));
+        assert!(view.contains(
+            "\"src_addr\":\"0x05a8...7a2a\",\"dest_addr\":\"0x05a8...7a2a\",\"txns\":[{\"chain\":\
+            \"Polkadot_Para_2004\",\"txn\":\"0xabababababababababababababababababababababababababababababababab\"}]"
+        ));
+        assert!(!view.contains("05a81d8564a3ea298660e34e03e5eff9a29d7a2a"));
+        assert!(get_plan_public_view(&exec_plan, true)
+            .contains("\"src_addr\":\"0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a\""));
+    }
+
+    #[test]
+    fn test_redact_address() {
+        assert_eq!(
+            redact_address("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            "15oF4u...6Sp5"
+        );
+        assert_eq!(redact_address("0x1234"), "0x1234");
+    }
+
+    #[test]
+    fn test_is_plan_owner() {
+        let exec_plan = exec_plan();
+        // Not a valid signature, so no signer to compare against the user
+        assert!(!is_plan_owner(
+            &exec_plan,
+            &DestinationProof::Ethereum(vec![0u8; 65])
+        ));
+        assert!(!is_plan_owner(
+            &exec_plan,
+            &DestinationProof::Sr25519 {
+                pubkey: [0u8; 32],
+                signature: vec![0u8; 64],
+            }
+        ));
+    }
+}
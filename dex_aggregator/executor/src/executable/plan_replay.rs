@@ -0,0 +1,796 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use pink_web3::signing::keccak256;
+use serde::Deserialize;
+
+use privadex_chain_metadata::common::EthTxnHash;
+use privadex_common::{utils::general_utils::hex_string_to_vec, uuid::Uuid};
+use privadex_execution_plan::execution_plan::{
+    CrossChainStepStatus, EthStepStatus, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+    FinalizedTxnId, MultiPhaseBridgeStepStatus,
+};
+
+use super::{
+    plan_events::{get_all_steps, PlanEvent, PlanEventKind, TxnChainKind},
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+/// What the recorded chain data (eth_getTransactionReceipt) says about a txn
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecordedTxnOutcome {
+    Succeeded,
+    Reverted,
+    NotIncluded,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ReplayFixtures {
+    pub eth_txns: Vec<(EthTxnHash, RecordedTxnOutcome)>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReplayError {
+    CreatedEventNotFirst,
+    EventAfterCompletion,
+    EventsOutOfOrder,
+    WrongExecPlanUuid(Uuid),
+    UnknownStep(Uuid),
+    // The transition's from status is not what the previous events left the step in
+    TransitionFromMismatch {
+        step_uuid: Uuid,
+        replayed: ExecutableSimpleStatus,
+        recorded: ExecutableSimpleStatus,
+    },
+    IllegalTransition {
+        step_uuid: Uuid,
+        from: ExecutableSimpleStatus,
+        to: ExecutableSimpleStatus,
+    },
+    FinalStatusMismatch {
+        step_uuid: Uuid,
+        replayed: ExecutableSimpleStatus,
+        archived: ExecutableSimpleStatus,
+    },
+    CompletedStatusMismatch {
+        recorded: ExecutableSimpleStatus,
+        archived: ExecutableSimpleStatus,
+    },
+    MissingCompletedEvent,
+    MissingFixture(EthTxnHash),
+    // The chain data moves the step somewhere other than where the executor recorded it going
+    TransitionDivergence {
+        step_uuid: Uuid,
+        derived: ExecutableSimpleStatus,
+        recorded: ExecutableSimpleStatus,
+    },
+    // The broadcast's raw bytes don't hash to its txn hash, so they are not what was sent
+    BroadcastHashMismatch(Uuid),
+    ChainDataMismatch {
+        step_uuid: Uuid,
+        txn_hash: EthTxnHash,
+        expected: RecordedTxnOutcome,
+        recorded: RecordedTxnOutcome,
+    },
+}
+pub type ReplayResult<T> = core::result::Result<T, ReplayError>;
+
+// Mirrors the result of eth_getTransactionReceipt: status is "0x1", "0x0" or null if the txn
+// was never included
+#[derive(Deserialize)]
+struct ReplayFixturesJson<'a> {
+    #[serde(borrow)]
+    eth_receipts: Vec<EthReceiptJson<'a>>,
+}
+
+#[derive(Deserialize)]
+struct EthReceiptJson<'a> {
+    txn_hash: &'a str,
+    #[serde(borrow)]
+    status: Option<&'a str>,
+}
+
+impl ReplayFixtures {
+    /// Parses {"eth_receipts": [{"txn_hash": "0x...", "status": "0x1"}, ...]}
+    pub fn from_json(json: &[u8]) -> Option<Self> {
+        let (raw, _): (ReplayFixturesJson, usize) = serde_json_core::from_slice(json).ok()?;
+        let mut eth_txns = Vec::new();
+        for receipt in raw.eth_receipts {
+            let txn_hash: [u8; 32] = hex_string_to_vec(receipt.txn_hash).ok()?.try_into().ok()?;
+            let outcome = match receipt.status {
+                Some("0x1") => RecordedTxnOutcome::Succeeded,
+                Some("0x0") => RecordedTxnOutcome::Reverted,
+                None => RecordedTxnOutcome::NotIncluded,
+                Some(_) => return None,
+            };
+            eth_txns.push((EthTxnHash::from(txn_hash), outcome));
+        }
+        Some(Self { eth_txns })
+    }
+
+    fn get_outcome(&self, txn_hash: &EthTxnHash) -> Option<RecordedTxnOutcome> {
+        self.eth_txns
+            .iter()
+            .find(|(hash, _)| hash == txn_hash)
+            .map(|(_, outcome)| *outcome)
+    }
+}
+
+/// Re-derives every step's transitions from the txns the plan's event log says were sent and
+/// the recorded chain data, and checks that the executor recorded the same ones and ended up
+/// where the archived plan is, i.e. that it only moved a step forward when the chain said so.
+/// Only Ethereum steps are derived from chain data for now (Substrate extrinsics and remote
+/// XCM events are not recorded in the fixtures), and the other steps' transitions are only
+/// checked to be legal. Each broadcast txn in the log must hash to the txn hash it was logged
+/// with. Returns the number of step transitions replayed
+pub fn replay_execution_plan(
+    archived_plan: &ExecutionPlan,
+    events: &[PlanEvent],
+    fixtures: &ReplayFixtures,
+) -> ReplayResult<usize> {
+    let steps = get_all_steps(archived_plan);
+    // start_swap creates the plan with the user's txn already submitted
+    let prestart_uuid = archived_plan.prestart_user_to_escrow_transfer.get_uuid();
+    let mut replayed_statuses: Vec<(Uuid, ExecutableSimpleStatus)> = steps
+        .iter()
+        .map(|step| {
+            let initial_status = if step.get_uuid() == prestart_uuid {
+                ExecutableSimpleStatus::InProgress
+            } else {
+                ExecutableSimpleStatus::NotStarted
+            };
+            (step.get_uuid().clone(), initial_status)
+        })
+        .collect();
+
+    let mut num_transitions = 0;
+    let mut last_timestamp = 0;
+    let mut completed_status = None;
+    // The chain data may well not back these up, which is why they were forced
+    let mut overridden_steps: Vec<Uuid> = Vec::new();
+    let mut sent_txns: Vec<(Uuid, EthTxnHash)> = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        if event.exec_plan_uuid != archived_plan.uuid {
+            return Err(ReplayError::WrongExecPlanUuid(event.exec_plan_uuid.clone()));
+        }
+        if event.timestamp_millis < last_timestamp {
+            return Err(ReplayError::EventsOutOfOrder);
+        }
+        last_timestamp = event.timestamp_millis;
+        if completed_status.is_some() {
+            return Err(ReplayError::EventAfterCompletion);
+        }
+        match &event.kind {
+            PlanEventKind::Created { .. } => {
+                if index != 0 {
+                    return Err(ReplayError::CreatedEventNotFirst);
+                }
+            }
+            PlanEventKind::StepTransition {
+                step_uuid,
+                from,
+                to,
+            } => {
+                let (_, replayed_status) = replayed_statuses
+                    .iter_mut()
+                    .find(|(uuid, _)| uuid == step_uuid)
+                    .ok_or(ReplayError::UnknownStep(step_uuid.clone()))?;
+                if replayed_status != from {
+                    return Err(ReplayError::TransitionFromMismatch {
+                        step_uuid: step_uuid.clone(),
+                        replayed: replayed_status.clone(),
+                        recorded: from.clone(),
+                    });
+                }
+                // The user sends the prestart txn, so it is not in the log. Its final status is
+                // still checked against the chain data below
+                let derived = if *step_uuid == *prestart_uuid {
+                    None
+                } else {
+                    let step = steps
+                        .iter()
+                        .find(|step| step.get_uuid() == step_uuid)
+                        .expect("Replayed statuses are built from the steps");
+                    let step_sent_txns: Vec<EthTxnHash> = sent_txns
+                        .iter()
+                        .filter(|(uuid, _)| uuid == step_uuid)
+                        .map(|(_, txn_hash)| txn_hash.clone())
+                        .collect();
+                    derive_eth_transition(step, to, &step_sent_txns, fixtures)?
+                };
+                // A step only goes back to NotStarted between its txns, which the chain data
+                // has to vouch for
+                if is_closed_status(from)
+                    || (*to == ExecutableSimpleStatus::NotStarted && derived.is_none())
+                {
+                    return Err(ReplayError::IllegalTransition {
+                        step_uuid: step_uuid.clone(),
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+                if let Some(derived) = derived {
+                    if derived != *to {
+                        return Err(ReplayError::TransitionDivergence {
+                            step_uuid: step_uuid.clone(),
+                            derived,
+                            recorded: to.clone(),
+                        });
+                    }
+                }
+                *replayed_status = to.clone();
+                num_transitions += 1;
+            }
+            PlanEventKind::Completed(status) => {
+                completed_status = Some(status.clone());
+            }
+            // Informational only
+            PlanEventKind::Stuck { .. } | PlanEventKind::Quarantined => {}
+            PlanEventKind::TxnBroadcast {
+                step_uuid,
+                chain_kind,
+                txn_hash,
+                raw_txn,
+            } => {
+                if !replayed_statuses.iter().any(|(uuid, _)| uuid == step_uuid) {
+                    return Err(ReplayError::UnknownStep(step_uuid.clone()));
+                }
+                if !is_raw_txn_of(raw_txn, txn_hash, chain_kind) {
+                    return Err(ReplayError::BroadcastHashMismatch(step_uuid.clone()));
+                }
+                if *chain_kind == TxnChainKind::Ethereum {
+                    sent_txns.push((step_uuid.clone(), EthTxnHash::from(*txn_hash)));
+                }
+            }
+            PlanEventKind::ManualOverride {
+                step_uuid,
+                from,
+                to,
+                ..
+            } => {
+                let (_, replayed_status) = replayed_statuses
+                    .iter_mut()
+                    .find(|(uuid, _)| uuid == step_uuid)
+                    .ok_or(ReplayError::UnknownStep(step_uuid.clone()))?;
+                if replayed_status != from {
+                    return Err(ReplayError::TransitionFromMismatch {
+                        step_uuid: step_uuid.clone(),
+                        replayed: replayed_status.clone(),
+                        recorded: from.clone(),
+                    });
+                }
+                *replayed_status = to.clone();
+                overridden_steps.push(step_uuid.clone());
+                num_transitions += 1;
+            }
+        }
+    }
+
+    for (step, (step_uuid, replayed_status)) in steps.iter().zip(replayed_statuses.iter()) {
+        let archived_status = step.get_status();
+        if *replayed_status != archived_status {
+            return Err(ReplayError::FinalStatusMismatch {
+                step_uuid: step_uuid.clone(),
+                replayed: replayed_status.clone(),
+                archived: archived_status,
+            });
+        }
+        if !overridden_steps.contains(step_uuid) {
+            check_chain_data(step, fixtures)?;
+        }
+    }
+
+    let archived_plan_status = archived_plan.get_status();
+    match completed_status {
+        Some(recorded) if recorded != archived_plan_status => {
+            Err(ReplayError::CompletedStatusMismatch {
+                recorded,
+                archived: archived_plan_status,
+            })
+        }
+        None if is_closed_status(&archived_plan_status) => Err(ReplayError::MissingCompletedEvent),
+        _ => Ok(num_transitions),
+    }
+}
+
+fn is_raw_txn_of(raw_txn: &[u8], txn_hash: &[u8; 32], chain_kind: &TxnChainKind) -> bool {
+    match chain_kind {
+        TxnChainKind::Ethereum => keccak256(raw_txn) == *txn_hash,
+        TxnChainKind::Substrate => sp_core_hashing::blake2_256(raw_txn) == *txn_hash,
+    }
+}
+
+fn is_closed_status(status: &ExecutableSimpleStatus) -> bool {
+    *status != ExecutableSimpleStatus::NotStarted
+        && *status != ExecutableSimpleStatus::InProgress
+        && *status != ExecutableSimpleStatus::PrestartConfirming
+}
+
+// Where the chain data moves an Ethereum step to, given the txns sent for it so far. Sending a
+// txn starts it. Once the txn lands (or is dropped) the step settles, unless more txns follow
+// (e.g. the swap after its approval), in which case it goes back to NotStarted for the next
+// one. None for the other steps
+fn derive_eth_transition(
+    step: &ExecutionStep,
+    to: &ExecutableSimpleStatus,
+    sent_txns: &[EthTxnHash],
+    fixtures: &ReplayFixtures,
+) -> ReplayResult<Option<ExecutableSimpleStatus>> {
+    let num_txns = match get_num_eth_txns(step) {
+        Some(num_txns) => num_txns,
+        None => return Ok(None),
+    };
+    let last_txn_hash = match sent_txns.last() {
+        Some(txn_hash) => txn_hash,
+        None => return Ok(Some(ExecutableSimpleStatus::NotStarted)),
+    };
+    // The receipt may only come in a later pass
+    if *to == ExecutableSimpleStatus::InProgress {
+        return Ok(Some(ExecutableSimpleStatus::InProgress));
+    }
+    let outcome = fixtures
+        .get_outcome(last_txn_hash)
+        .ok_or(ReplayError::MissingFixture(last_txn_hash.clone()))?;
+    Ok(Some(match outcome {
+        RecordedTxnOutcome::Reverted => ExecutableSimpleStatus::Failed,
+        RecordedTxnOutcome::NotIncluded => ExecutableSimpleStatus::Dropped,
+        RecordedTxnOutcome::Succeeded if sent_txns.len() < num_txns => {
+            ExecutableSimpleStatus::NotStarted
+        }
+        RecordedTxnOutcome::Succeeded => ExecutableSimpleStatus::Succeeded,
+    }))
+}
+
+// The number of txns an Ethereum step sends, which is more than one if it needed an approval
+// (or a swap before zapping in). None for the steps that are not (only) Ethereum txns
+fn get_num_eth_txns(step: &ExecutionStep) -> Option<usize> {
+    let has_extra_txn = match &step.inner {
+        ExecutionStepEnum::EthSend(_)
+        | ExecutionStepEnum::ERC20Transfer(_)
+        | ExecutionStepEnum::EthWrap(_)
+        | ExecutionStepEnum::EthUnwrap(_)
+        | ExecutionStepEnum::EthDexSwap(_)
+        | ExecutionStepEnum::EthDepositSweep(_)
+        | ExecutionStepEnum::EthFeeSkim(_) => false,
+        ExecutionStepEnum::EthContractCall(step) => step.approval_txn_hash.is_some(),
+        ExecutionStepEnum::EthAggregatorSwap(step) => step.approval_txn_hash.is_some(),
+        ExecutionStepEnum::EthZapIn(step) => step.swap_txn_hash.is_some(),
+        ExecutionStepEnum::EthRemoveLiquidity(step) => step.approval_txn_hash.is_some(),
+        ExecutionStepEnum::XCMTransfer(_)
+        | ExecutionStepEnum::WormholeTransfer(_)
+        | ExecutionStepEnum::SubstrateRemarkDeposit(_) => return None,
+    };
+    Some(if has_extra_txn { 2 } else { 1 })
+}
+
+fn check_chain_data(step: &ExecutionStep, fixtures: &ReplayFixtures) -> ReplayResult<()> {
+    for (txn_hash, expected) in get_expected_eth_outcomes(step) {
+        let recorded = fixtures
+            .get_outcome(&txn_hash)
+            .ok_or(ReplayError::MissingFixture(txn_hash))?;
+        if recorded != expected {
+            return Err(ReplayError::ChainDataMismatch {
+                step_uuid: step.get_uuid().clone(),
+                txn_hash,
+                expected,
+                recorded,
+            });
+        }
+    }
+    Ok(())
+}
+
+// The outcome the chain must have reported for each Ethereum txn that the step's status
+// commits to. Pending txns commit to nothing yet
+fn get_expected_eth_outcomes(step: &ExecutionStep) -> Vec<(EthTxnHash, RecordedTxnOutcome)> {
+    let eth_status = match &step.inner {
+        ExecutionStepEnum::EthSend(step) => Some(&step.status),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(&step.status),
+        ExecutionStepEnum::EthWrap(step) => Some(&step.status),
+        ExecutionStepEnum::EthUnwrap(step) => Some(&step.status),
+        ExecutionStepEnum::EthDexSwap(step) => Some(&step.status),
+        ExecutionStepEnum::EthDepositSweep(step) => Some(&step.status),
+        ExecutionStepEnum::EthContractCall(step) => Some(&step.status),
+        ExecutionStepEnum::EthFeeSkim(step) => Some(&step.status),
+        ExecutionStepEnum::EthAggregatorSwap(step) => Some(&step.status),
+        ExecutionStepEnum::EthZapIn(step) => Some(&step.status),
+        ExecutionStepEnum::EthRemoveLiquidity(step) => Some(&step.status),
+        ExecutionStepEnum::XCMTransfer(_)
+        | ExecutionStepEnum::WormholeTransfer(_)
+        | ExecutionStepEnum::SubstrateRemarkDeposit(_) => None,
+    };
+    let mut outcomes = Vec::new();
+    match eth_status {
+        Some(EthStepStatus::Confirmed(txn_hash)) => {
+            outcomes.push((txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+        }
+        Some(EthStepStatus::Failed(txn_hash)) => {
+            outcomes.push((txn_hash.clone(), RecordedTxnOutcome::Reverted))
+        }
+        _ => {}
+    }
+    match &step.inner {
+        ExecutionStepEnum::EthContractCall(step) => {
+            if let Some(approval_txn_hash) = &step.approval_txn_hash {
+                outcomes.push((approval_txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+            }
+        }
+        ExecutionStepEnum::EthAggregatorSwap(step) => {
+            if let Some(approval_txn_hash) = &step.approval_txn_hash {
+                outcomes.push((approval_txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+            }
+        }
+        ExecutionStepEnum::EthZapIn(step) => {
+            if let Some(swap_txn_hash) = &step.swap_txn_hash {
+                outcomes.push((swap_txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+            }
+        }
+        ExecutionStepEnum::EthRemoveLiquidity(step) => {
+            if let Some(approval_txn_hash) = &step.approval_txn_hash {
+                outcomes.push((approval_txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+            }
+        }
+        ExecutionStepEnum::XCMTransfer(step) => match &step.status {
+            CrossChainStepStatus::Failed(FinalizedTxnId::Ethereum(txn_hash)) => {
+                outcomes.push((txn_hash.clone(), RecordedTxnOutcome::Reverted))
+            }
+            CrossChainStepStatus::LocalConfirmed(FinalizedTxnId::Ethereum(txn_hash), _)
+            | CrossChainStepStatus::Confirmed(FinalizedTxnId::Ethereum(txn_hash), _)
+            | CrossChainStepStatus::RemoteFailed(FinalizedTxnId::Ethereum(txn_hash), _, _) => {
+                outcomes.push((txn_hash.clone(), RecordedTxnOutcome::Succeeded))
+            }
+            _ => {}
+        },
+        ExecutionStepEnum::WormholeTransfer(step) => match &step.status {
+            MultiPhaseBridgeStepStatus::Failed(txn_hash) => {
+                outcomes.push((txn_hash.clone(), RecordedTxnOutcome::Reverted))
+            }
+            MultiPhaseBridgeStepStatus::AwaitingRelay(message_id)
+            | MultiPhaseBridgeStepStatus::ClaimSubmitted(message_id, _) => outcomes.push((
+                message_id.deposit_txn_hash.clone(),
+                RecordedTxnOutcome::Succeeded,
+            )),
+            MultiPhaseBridgeStepStatus::Confirmed(message_id, claim_txn_hash) => {
+                outcomes.push((
+                    message_id.deposit_txn_hash.clone(),
+                    RecordedTxnOutcome::Succeeded,
+                ));
+                outcomes.push((claim_txn_hash.clone(), RecordedTxnOutcome::Succeeded));
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod plan_replay_tests {
+    use ink::prelude::{format, string::String, vec};
+    use privadex_chain_metadata::{
+        common::{EthAddress, MillisSinceEpoch},
+        registry::token::universal_token_id_registry::GLMR_NATIVE,
+    };
+    use privadex_common::utils::general_utils::slice_to_hex_string;
+    use privadex_execution_plan::{
+        execution_plan::{EthRemoveLiquidityStep, ExecutionPath},
+        test_utilities::execution_plan_factory::{
+            common, eth_send, eth_wrap, path, plan, ESCROW, USER,
+        },
+    };
+
+    use super::*;
+
+    // The signed bytes of the txn a step sent, i.e. what its TxnBroadcast event holds
+    fn raw_txn(txn_byte: u8) -> Vec<u8> {
+        vec![0xf8, 0x6b, txn_byte]
+    }
+
+    fn txn_hash(txn_byte: u8) -> EthTxnHash {
+        EthTxnHash::from(keccak256(&raw_txn(txn_byte)))
+    }
+
+    // prestart (0x02..), wrap (0x03..) and postend (0x04..) all confirmed. The user sent the
+    // prestart txn, so unlike the others it was not broadcast by the executor
+    fn succeeded_plan() -> ExecutionPlan {
+        ExecutionPlan {
+            paths: vec![ExecutionPath {
+                amount_out: Some(1_000),
+                ..path(vec![eth_wrap(
+                    3,
+                    Some(1_000),
+                    EthStepStatus::Confirmed(txn_hash(3)),
+                )])
+            }],
+            prestart_user_to_escrow_transfer: eth_send(
+                2,
+                USER,
+                ESCROW,
+                Some(2_000),
+                EthStepStatus::Confirmed(EthTxnHash::from([0xb2; 32])),
+            ),
+            postend_escrow_to_user_transfer: eth_send(
+                4,
+                ESCROW,
+                USER,
+                None,
+                EthStepStatus::Confirmed(txn_hash(4)),
+            ),
+            refund_escrow_to_user_transfer: eth_send(
+                5,
+                ESCROW,
+                USER,
+                None,
+                EthStepStatus::NotStarted,
+            ),
+            ..plan(Vec::new())
+        }
+    }
+
+    fn transition(
+        timestamp_millis: MillisSinceEpoch,
+        uuid_byte: u8,
+        from: ExecutableSimpleStatus,
+        to: ExecutableSimpleStatus,
+    ) -> PlanEvent {
+        PlanEvent {
+            exec_plan_uuid: Uuid::new([1u8; 16]),
+            timestamp_millis,
+            kind: PlanEventKind::StepTransition {
+                step_uuid: Uuid::new([uuid_byte; 16]),
+                from,
+                to,
+            },
+        }
+    }
+
+    fn broadcast(timestamp_millis: MillisSinceEpoch, uuid_byte: u8, txn_byte: u8) -> PlanEvent {
+        PlanEvent {
+            exec_plan_uuid: Uuid::new([1u8; 16]),
+            timestamp_millis,
+            kind: PlanEventKind::TxnBroadcast {
+                step_uuid: Uuid::new([uuid_byte; 16]),
+                chain_kind: TxnChainKind::Ethereum,
+                txn_hash: txn_hash(txn_byte).0,
+                raw_txn: raw_txn(txn_byte),
+            },
+        }
+    }
+
+    fn succeeded_plan_events() -> Vec<PlanEvent> {
+        use ExecutableSimpleStatus::*;
+        vec![
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1u8; 16]),
+                timestamp_millis: 1,
+                kind: PlanEventKind::Created {
+                    src_addr: String::new(),
+                    dest_addr: String::new(),
+                },
+            },
+            transition(2, 2, InProgress, Succeeded),
+            broadcast(2, 3, 3),
+            transition(2, 3, NotStarted, InProgress),
+            transition(3, 3, InProgress, Succeeded),
+            broadcast(3, 4, 4),
+            transition(3, 4, NotStarted, InProgress),
+            transition(4, 4, InProgress, Succeeded),
+            PlanEvent {
+                exec_plan_uuid: Uuid::new([1u8; 16]),
+                timestamp_millis: 4,
+                kind: PlanEventKind::Completed(Succeeded),
+            },
+        ]
+    }
+
+    fn succeeded_plan_fixtures() -> ReplayFixtures {
+        let json = format!(
+            r#"{{"eth_receipts": [
+                {{"txn_hash": "0xb2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2", "status": "0x1"}},
+                {{"txn_hash": "{}", "status": "0x1"}},
+                {{"txn_hash": "{}", "status": "0x1"}},
+                {{"txn_hash": "0xb5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5", "status": null}}
+            ]}}"#,
+            slice_to_hex_string(txn_hash(3).as_bytes()),
+            slice_to_hex_string(txn_hash(4).as_bytes()),
+        );
+        ReplayFixtures::from_json(json.as_bytes()).expect("Valid fixtures")
+    }
+
+    #[test]
+    fn test_consistent_history_replays() {
+        let fixtures = succeeded_plan_fixtures();
+        assert_eq!(fixtures.eth_txns.len(), 4);
+        assert_eq!(
+            fixtures.eth_txns[3],
+            (
+                EthTxnHash::from([0xb5; 32]),
+                RecordedTxnOutcome::NotIncluded
+            )
+        );
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &succeeded_plan_events(), &fixtures),
+            Ok(5)
+        );
+    }
+
+    #[test]
+    fn test_skipped_transition_is_detected() {
+        let mut events = succeeded_plan_events();
+        // Drop the wrap step's NotStarted -> InProgress transition
+        events.remove(3);
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &events, &succeeded_plan_fixtures()),
+            Err(ReplayError::TransitionFromMismatch {
+                step_uuid: Uuid::new([3u8; 16]),
+                replayed: ExecutableSimpleStatus::NotStarted,
+                recorded: ExecutableSimpleStatus::InProgress,
+            })
+        );
+    }
+
+    #[test]
+    fn test_broadcast_txns_are_checked_against_their_hash() {
+        let raw_txn = vec![0xf8, 0x6b, 0x01, 0x02];
+        let broadcast = |chain_kind: TxnChainKind, txn_hash: [u8; 32]| PlanEvent {
+            exec_plan_uuid: Uuid::new([1u8; 16]),
+            timestamp_millis: 3,
+            kind: PlanEventKind::TxnBroadcast {
+                step_uuid: Uuid::new([4u8; 16]),
+                chain_kind,
+                txn_hash,
+                raw_txn: raw_txn.clone(),
+            },
+        };
+        let keccak_hash = keccak256(&raw_txn);
+        let blake2_hash = sp_core_hashing::blake2_256(&raw_txn);
+        let fixtures = succeeded_plan_fixtures();
+        for (chain_kind, txn_hash) in [
+            (TxnChainKind::Ethereum, keccak_hash),
+            (TxnChainKind::Substrate, blake2_hash),
+        ] {
+            let mut events = succeeded_plan_events();
+            events.insert(4, broadcast(chain_kind, txn_hash));
+            assert_eq!(
+                replay_execution_plan(&succeeded_plan(), &events, &fixtures),
+                Ok(5)
+            );
+        }
+
+        // The hash has to be the one the chain kind uses
+        for (chain_kind, txn_hash) in [
+            (TxnChainKind::Ethereum, blake2_hash),
+            (TxnChainKind::Substrate, keccak_hash),
+            (TxnChainKind::Ethereum, [0xb4; 32]),
+        ] {
+            let mut events = succeeded_plan_events();
+            events.insert(4, broadcast(chain_kind, txn_hash));
+            assert_eq!(
+                replay_execution_plan(&succeeded_plan(), &events, &fixtures),
+                Err(ReplayError::BroadcastHashMismatch(Uuid::new([4u8; 16])))
+            );
+        }
+    }
+
+    #[test]
+    fn test_chain_data_mismatch_is_detected() {
+        let mut fixtures = succeeded_plan_fixtures();
+        fixtures.eth_txns[0].1 = RecordedTxnOutcome::Reverted;
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &succeeded_plan_events(), &fixtures),
+            Err(ReplayError::ChainDataMismatch {
+                step_uuid: Uuid::new([2u8; 16]),
+                txn_hash: EthTxnHash::from([0xb2; 32]),
+                expected: RecordedTxnOutcome::Succeeded,
+                recorded: RecordedTxnOutcome::Reverted,
+            })
+        );
+    }
+
+    #[test]
+    fn test_transitions_are_derived_from_chain_data() {
+        // The wrap reverted, so the executor should have failed it
+        let mut fixtures = succeeded_plan_fixtures();
+        fixtures.eth_txns[1].1 = RecordedTxnOutcome::Reverted;
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &succeeded_plan_events(), &fixtures),
+            Err(ReplayError::TransitionDivergence {
+                step_uuid: Uuid::new([3u8; 16]),
+                derived: ExecutableSimpleStatus::Failed,
+                recorded: ExecutableSimpleStatus::Succeeded,
+            })
+        );
+        fixtures.eth_txns[1].1 = RecordedTxnOutcome::NotIncluded;
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &succeeded_plan_events(), &fixtures),
+            Err(ReplayError::TransitionDivergence {
+                step_uuid: Uuid::new([3u8; 16]),
+                derived: ExecutableSimpleStatus::Dropped,
+                recorded: ExecutableSimpleStatus::Succeeded,
+            })
+        );
+
+        // Nothing was sent for the wrap, so it can't have started
+        let mut events = succeeded_plan_events();
+        events.remove(2);
+        assert_eq!(
+            replay_execution_plan(&succeeded_plan(), &events, &succeeded_plan_fixtures()),
+            Err(ReplayError::TransitionDivergence {
+                step_uuid: Uuid::new([3u8; 16]),
+                derived: ExecutableSimpleStatus::NotStarted,
+                recorded: ExecutableSimpleStatus::InProgress,
+            })
+        );
+    }
+
+    #[test]
+    fn test_step_goes_back_to_not_started_between_its_txns() {
+        use ExecutableSimpleStatus::*;
+        // Redeeming the LP tokens (0x06..) takes an approval (0x07..) first
+        let mut plan = succeeded_plan();
+        plan.remove_liquidity = Some(ExecutionStep::new(ExecutionStepEnum::EthRemoveLiquidity(
+            EthRemoveLiquidityStep {
+                uuid: Uuid::new([6u8; 16]),
+                dex_router_addr: EthAddress { 0: [0xaa; 20] },
+                lp_token: GLMR_NATIVE,
+                token_a: GLMR_NATIVE,
+                token_b: GLMR_NATIVE,
+                num_token_a_paths: 1,
+                amount: Some(500),
+                common: common(ESCROW, ESCROW),
+                approval_uuid: Uuid::new([7u8; 16]),
+                approval_txn_hash: Some(txn_hash(7)),
+                token_a_amount_out: Some(1_000),
+                token_b_amount_out: Some(1_000),
+                status: EthStepStatus::Confirmed(txn_hash(6)),
+            },
+        )));
+        let mut fixtures = succeeded_plan_fixtures();
+        fixtures
+            .eth_txns
+            .push((txn_hash(7), RecordedTxnOutcome::Succeeded));
+        fixtures
+            .eth_txns
+            .push((txn_hash(6), RecordedTxnOutcome::Succeeded));
+        let mut events = succeeded_plan_events();
+        events.splice(
+            2..2,
+            [
+                broadcast(2, 6, 7),
+                transition(2, 6, NotStarted, InProgress),
+                transition(2, 6, InProgress, NotStarted),
+                broadcast(2, 6, 6),
+                transition(2, 6, NotStarted, InProgress),
+                transition(2, 6, InProgress, Succeeded),
+            ],
+        );
+        assert_eq!(replay_execution_plan(&plan, &events, &fixtures), Ok(9));
+
+        // Only the approval had landed
+        events.drain(4..7);
+        assert_eq!(
+            replay_execution_plan(&plan, &events, &fixtures),
+            Err(ReplayError::TransitionDivergence {
+                step_uuid: Uuid::new([6u8; 16]),
+                derived: NotStarted,
+                recorded: Succeeded,
+            })
+        );
+    }
+}
@@ -0,0 +1,245 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use privadex_chain_metadata::{
+    common::{MillisSinceEpoch, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+use privadex_execution_plan::execution_plan::{
+    ExecutionPath, ExecutionPlan, ExecutionStep, ExecutionStepEnum, PathExecutionMode,
+};
+
+use super::{
+    plan_events::get_all_steps,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+// Roughly how many blocks a step in flight needs before another step forward can make
+// progress on it. An Eth step waits on its txn to finalize, an XCM transfer also waits on the
+// message to land on the dest chain, and a Wormhole transfer waits on the guardians' VAA
+const ETH_STEP_POLL_BLOCKS: u64 = 2;
+const XCM_TRANSFER_POLL_BLOCKS: u64 = 4;
+const WORMHOLE_TRANSFER_POLL_BLOCKS: u64 = 10;
+// Polling early only wastes an invocation, but polling late delays the whole plan. So we cap
+// the wait on slow chains
+const MAX_POLL_INTERVAL_MILLIS: MillisSinceEpoch = 60_000;
+
+fn get_avg_block_time_millis(chain: &UniversalChainId) -> MillisSinceEpoch {
+    get_chain_info_from_chain_id(chain).map_or(0, |chain_info| chain_info.avg_block_time_millis)
+}
+
+// How long after submission a step is expected to confirm
+fn get_confirmation_latency_millis(step: &ExecutionStep) -> MillisSinceEpoch {
+    match &step.inner {
+        ExecutionStepEnum::XCMTransfer(step) => {
+            XCM_TRANSFER_POLL_BLOCKS
+                * get_avg_block_time_millis(&step.src_token.chain)
+                    .max(get_avg_block_time_millis(&step.dest_token.chain))
+        }
+        ExecutionStepEnum::WormholeTransfer(step) => {
+            WORMHOLE_TRANSFER_POLL_BLOCKS * get_avg_block_time_millis(&step.src_token.chain)
+        }
+        _ => ETH_STEP_POLL_BLOCKS * get_avg_block_time_millis(&step.get_src_chain()),
+    }
+}
+
+pub fn get_poll_interval_millis(step: &ExecutionStep) -> MillisSinceEpoch {
+    get_confirmation_latency_millis(step).min(MAX_POLL_INTERVAL_MILLIS)
+}
+
+// One step forward submits the step, then it is polled until it confirms. That usually takes
+// one poll, but more on chains slow enough that the poll interval is capped
+fn get_step_forward_calls(step: &ExecutionStep) -> u32 {
+    match step.get_status() {
+        ExecutableSimpleStatus::NotStarted => {}
+        ExecutableSimpleStatus::InProgress => return 1,
+        _ => return 0,
+    }
+    let poll_interval_millis = get_poll_interval_millis(step).max(1);
+    let polls =
+        (get_confirmation_latency_millis(step) + poll_interval_millis - 1) / poll_interval_millis;
+    1 + polls.max(1) as u32
+}
+
+fn get_path_step_forward_calls(path: &ExecutionPath) -> u32 {
+    path.steps.iter().map(get_step_forward_calls).sum()
+}
+
+/// Roughly how many more execution_plan_step_forward calls it takes to drive the plan to
+/// completion, if the worker keeps to the polling schedule. Until a refund starts, this assumes
+/// the plan succeeds. Concurrent paths step forward in the same calls
+pub fn get_estimated_step_forward_calls(exec_plan: &ExecutionPlan) -> u32 {
+    let refund = &exec_plan.refund_escrow_to_user_transfer;
+    if refund.get_status() != ExecutableSimpleStatus::NotStarted {
+        return get_step_forward_calls(refund);
+    }
+    match exec_plan.get_status() {
+        ExecutableSimpleStatus::NotStarted | ExecutableSimpleStatus::InProgress => {
+            get_step_forward_calls(&exec_plan.prestart_user_to_escrow_transfer)
+                + get_estimated_step_forward_calls_after_prestart(exec_plan)
+        }
+        // The deposit still has to get deeper before the plan starts
+        ExecutableSimpleStatus::PrestartConfirming => {
+            1 + get_estimated_step_forward_calls_after_prestart(exec_plan)
+        }
+        _ => 0,
+    }
+}
+
+fn get_estimated_step_forward_calls_after_prestart(exec_plan: &ExecutionPlan) -> u32 {
+    let path_calls = exec_plan.paths.iter().map(get_path_step_forward_calls);
+    let paths_calls = match exec_plan.path_execution_mode {
+        PathExecutionMode::Concurrent => path_calls.max().unwrap_or(0),
+        PathExecutionMode::Sequential => path_calls.sum(),
+    };
+    exec_plan
+        .deposit_sweep
+        .as_ref()
+        .map_or(0, get_step_forward_calls)
+        + exec_plan
+            .remove_liquidity
+            .as_ref()
+            .map_or(0, get_step_forward_calls)
+        + paths_calls
+        + get_step_forward_calls(&exec_plan.postend_escrow_to_user_transfer)
+}
+
+// A plan is next worth stepping forward once the soonest of its steps in flight could have
+// progressed. With none in flight, the next step is ready to start so the plan is due right away
+pub fn get_next_poll_after_millis(
+    exec_plan: &ExecutionPlan,
+    cur_timestamp: MillisSinceEpoch,
+) -> MillisSinceEpoch {
+    // Never due until the admin resolves the review, which makes it due again
+    if exec_plan.review_status.is_pending() {
+        return MillisSinceEpoch::MAX;
+    }
+    // Waiting on the prestart deposit to get deeper is like waiting on its txn again
+    if exec_plan.get_status() == ExecutableSimpleStatus::PrestartConfirming {
+        return cur_timestamp
+            + get_poll_interval_millis(&exec_plan.prestart_user_to_escrow_transfer);
+    }
+    get_all_steps(exec_plan)
+        .into_iter()
+        .filter(|step| step.get_status() == ExecutableSimpleStatus::InProgress)
+        .map(get_poll_interval_millis)
+        .min()
+        .map_or(cur_timestamp, |interval_millis| {
+            cur_timestamp + interval_millis
+        })
+}
+
+#[cfg(test)]
+mod poll_schedule_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::EthTxnHash,
+        registry::chain::universal_chain_id_registry::{ARBITRUM, MOONBEAM},
+    };
+    use privadex_execution_plan::{
+        execution_plan::{EthStepStatus, PathExecutionMode, PlanReviewStatus},
+        test_utilities::execution_plan_factory::{self, path, plan, ESCROW},
+    };
+
+    use super::*;
+
+    fn eth_send(chain: UniversalChainId, status: EthStepStatus) -> ExecutionStep {
+        let mut step = execution_plan_factory::eth_send(0, ESCROW, ESCROW, Some(1_000), status);
+        if let ExecutionStepEnum::EthSend(send_step) = &mut step.inner {
+            send_step.chain = chain;
+        }
+        step
+    }
+
+    fn submitted() -> EthStepStatus {
+        execution_plan_factory::submitted(EthTxnHash::zero())
+    }
+
+    fn plan_with_path_step(path_step: ExecutionStep) -> ExecutionPlan {
+        ExecutionPlan {
+            last_progress_millis: 1_000_000,
+            ..plan(vec![path(vec![path_step])])
+        }
+    }
+
+    #[test]
+    fn test_eth_step_poll_interval() {
+        assert_eq!(
+            get_poll_interval_millis(&eth_send(MOONBEAM, submitted())),
+            24_000
+        );
+        assert_eq!(
+            get_poll_interval_millis(&eth_send(ARBITRUM, submitted())),
+            500
+        );
+    }
+
+    #[test]
+    fn test_next_poll_after_millis() {
+        let in_flight_plan = plan_with_path_step(eth_send(MOONBEAM, submitted()));
+        assert_eq!(
+            get_next_poll_after_millis(&in_flight_plan, 2_000_000),
+            2_024_000
+        );
+
+        // The path step is ready to start, so there is no reason to wait
+        let ready_plan = plan_with_path_step(eth_send(MOONBEAM, EthStepStatus::NotStarted));
+        assert_eq!(
+            get_next_poll_after_millis(&ready_plan, 2_000_000),
+            2_000_000
+        );
+
+        let mut flagged_plan = ready_plan;
+        flagged_plan.review_status = PlanReviewStatus::NeedsReview {
+            realized_gas_fee_usd: 0,
+        };
+        assert_eq!(
+            get_next_poll_after_millis(&flagged_plan, 2_000_000),
+            MillisSinceEpoch::MAX
+        );
+    }
+
+    #[test]
+    fn test_estimated_step_forward_calls() {
+        // Submit and confirm each of the path step and the postend transfer
+        let ready_plan = plan_with_path_step(eth_send(MOONBEAM, EthStepStatus::NotStarted));
+        assert_eq!(get_estimated_step_forward_calls(&ready_plan), 4);
+
+        let in_flight_plan = plan_with_path_step(eth_send(MOONBEAM, submitted()));
+        assert_eq!(get_estimated_step_forward_calls(&in_flight_plan), 3);
+
+        let mut concurrent_plan = ready_plan.clone();
+        concurrent_plan.paths.push(concurrent_plan.paths[0].clone());
+        assert_eq!(get_estimated_step_forward_calls(&concurrent_plan), 4);
+        let mut sequential_plan = concurrent_plan;
+        sequential_plan.path_execution_mode = PathExecutionMode::Sequential;
+        assert_eq!(get_estimated_step_forward_calls(&sequential_plan), 6);
+
+        let mut refunding_plan = ready_plan;
+        refunding_plan.refund_escrow_to_user_transfer = eth_send(MOONBEAM, submitted());
+        assert_eq!(get_estimated_step_forward_calls(&refunding_plan), 1);
+
+        let mut done_plan = plan_with_path_step(eth_send(
+            MOONBEAM,
+            EthStepStatus::Confirmed(EthTxnHash::zero()),
+        ));
+        done_plan.postend_escrow_to_user_transfer =
+            eth_send(MOONBEAM, EthStepStatus::Confirmed(EthTxnHash::zero()));
+        assert_eq!(get_estimated_step_forward_calls(&done_plan), 0);
+    }
+}
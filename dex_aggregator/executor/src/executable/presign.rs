@@ -0,0 +1,377 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+// Short EVM-only plans can have their static txns signed when the plan is created, so that
+// stepping forward only has to broadcast them. A txn is static if nothing in it depends on how
+// the steps before it went, i.e. a transfer, wrap or unwrap whose amount is already known (in
+// practice the first step of each path). DEX swaps are never static since their deadline is
+// set when they are sent. Every other step is signed when it runs, as usual
+use ink::prelude::vec::Vec;
+use pink_web3::types::SignedTransaction;
+
+use privadex_chain_metadata::{
+    chain_info::ChainInfo,
+    common::{ChainTokenId, EthAddress, Nonce, UniversalAddress},
+    get_chain_info_from_chain_id,
+};
+use privadex_execution_plan::execution_plan::{
+    EthPendingTxnId, EthStepStatus, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+    PathExecutionMode, PresignedTxn,
+};
+
+use crate::{eth_utils, key_container::KeyContainer};
+
+use super::{
+    execute_step_meta::ExecuteStepMeta,
+    traits::{
+        Executable, ExecutableError, ExecutableResult, ExecutableSimpleStatus, StepForwardResult,
+    },
+};
+
+// Upper bound on the admin's presign_max_steps, since every presigned txn holds a nonce until
+// the plan gets to it
+pub const MAX_PRESIGN_ROUTE_STEPS: u8 = 8;
+
+// The escrow doesn't hold the deposit yet when these are signed, so their gas can't be estimated.
+// The limits are well above what the txns use (ETH_SEND_GAS_LIMIT is pink_web3's default)
+const ETH_SEND_GAS_LIMIT: u64 = 100_000;
+const ERC20_TRANSFER_GAS_LIMIT: u64 = 200_000;
+const WRAP_GAS_LIMIT: u64 = 200_000;
+// Leaves room for the gas price to rise before the txn is broadcast
+const PRESIGN_GAS_PRICE_MULTIPLIER: u64 = 2;
+
+// The plan's deposit is already in flight, and its route has at most max_route_steps steps (the
+// postend transfer included), all of them on EVM chains. Its paths must run concurrently: a later
+// tranche of a Sequential plan may not start for a long time, and its presigned nonce would hold
+// up every other txn from the escrow on that chain until then
+pub fn is_presignable_route(exec_plan: &ExecutionPlan, max_route_steps: u8) -> bool {
+    if exec_plan.paper_trade
+        || exec_plan.path_execution_mode != PathExecutionMode::Concurrent
+        || exec_plan.deposit_sweep.is_some()
+        || exec_plan.remove_liquidity.is_some()
+        || exec_plan.prestart_user_to_escrow_transfer.get_status()
+            != ExecutableSimpleStatus::InProgress
+    {
+        return false;
+    }
+    let route_steps: Vec<&ExecutionStep> = exec_plan
+        .paths
+        .iter()
+        .flat_map(|path| path.steps.iter())
+        .chain(core::iter::once(&exec_plan.postend_escrow_to_user_transfer))
+        .collect();
+    route_steps.len() <= max_route_steps as usize
+        && route_steps.iter().all(|step| {
+            // An aggregator swap's calldata comes from its API at execution time, and a zap-in's
+            // addLiquidity needs what its swap paid out, so neither can be signed ahead
+            matches!(
+                step.inner,
+                ExecutionStepEnum::EthSend(_)
+                    | ExecutionStepEnum::ERC20Transfer(_)
+                    | ExecutionStepEnum::EthWrap(_)
+                    | ExecutionStepEnum::EthUnwrap(_)
+                    | ExecutionStepEnum::EthDexSwap(_)
+                    | ExecutionStepEnum::EthContractCall(_)
+                    | ExecutionStepEnum::EthFeeSkim(_)
+            )
+        })
+}
+
+// Assigns each static step its nonce in the NonceManager and signs its txn. Returns the number
+// of txns signed
+pub fn presign_static_txns(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+    keys: &KeyContainer,
+) -> ExecutableResult<u32> {
+    let mut num_presigned = 0;
+    for exec_step in exec_plan
+        .paths
+        .iter_mut()
+        .flat_map(|path| path.steps.iter_mut())
+        .chain(core::iter::once(
+            &mut exec_plan.postend_escrow_to_user_transfer,
+        ))
+        .filter(|exec_step| is_static_step(exec_step))
+    {
+        let chain = exec_step.get_src_chain();
+        let chain_info =
+            get_chain_info_from_chain_id(&chain).ok_or(ExecutableError::FailedToFindChainInfo)?;
+        let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+        let src_addr = get_eth_addr(&exec_step.get_common().src_addr)?;
+        let (cur_block, system_nonce) =
+            eth_utils::common::block_number_and_system_nonce(&rpc_url, src_addr)
+                .map_err(|_| ExecutableError::RpcRequestFailed)?;
+        let nonce =
+            execute_step_meta.get_nonce(exec_step.get_uuid(), chain, cur_block, system_nonce)?;
+        let signed_txn = match sign_static_txn(exec_step, chain_info, &rpc_url, keys, nonce) {
+            Ok(signed_txn) => signed_txn,
+            Err(e) => {
+                // Hand the nonce back, since the txn won't be sent with it
+                let _ = execute_step_meta.drop_execstep(exec_step.get_uuid(), chain);
+                return Err(e);
+            }
+        };
+        exec_step.presigned_txn = Some(PresignedTxn {
+            nonce,
+            txn_hash: signed_txn.transaction_hash,
+            raw_txn: signed_txn.raw_transaction.0,
+        });
+        num_presigned += 1;
+    }
+    Ok(num_presigned)
+}
+
+// Ok(None) if the step has no presigned txn, in which case it builds and signs its own. If the
+// broadcast fails, the step is left to sign a fresh txn with the same nonce next time
+pub fn broadcast_presigned_txn(
+    exec_step: &mut ExecutionStep,
+    execute_step_meta: &ExecuteStepMeta,
+) -> ExecutableResult<Option<StepForwardResult>> {
+    if exec_step.get_status() != ExecutableSimpleStatus::NotStarted {
+        return Ok(None);
+    }
+    let presigned_txn = match &exec_step.presigned_txn {
+        Some(presigned_txn) => presigned_txn.clone(),
+        None => return Ok(None),
+    };
+    let chain = exec_step.get_src_chain();
+    let chain_info =
+        get_chain_info_from_chain_id(&chain).ok_or(ExecutableError::FailedToFindChainInfo)?;
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    let cur_block = execute_step_meta.get_cur_block(&chain)?;
+    // Paper-trading plans are never presigned, so this always goes out
+    let send_res = execute_step_meta.send_presigned_eth_txn(
+        exec_step.get_uuid(),
+        &rpc_url,
+        presigned_txn.raw_txn,
+        presigned_txn.txn_hash,
+    );
+    exec_step.presigned_txn = None;
+    let txn_hash = send_res?;
+    let new_status = EthStepStatus::Submitted(EthPendingTxnId {
+        txn_hash,
+        end_block_num: cur_block + execute_step_meta.get_executor_config().txn_num_blocks_alive,
+    });
+    match &mut exec_step.inner {
+        ExecutionStepEnum::EthSend(step) => step.status = new_status,
+        ExecutionStepEnum::ERC20Transfer(step) => step.status = new_status,
+        ExecutionStepEnum::EthWrap(step) => step.status = new_status,
+        ExecutionStepEnum::EthUnwrap(step) => step.status = new_status,
+        _ => return Err(ExecutableError::UnexpectedStepStatus),
+    }
+    Ok(Some(StepForwardResult {
+        did_status_change: true,
+        amount_out: None,
+    }))
+}
+
+// For presigned txns that will never be sent, e.g. because the plan is refunding. Their nonces
+// go back to the NonceManager as dropped nonces, so that the escrow's later txns on the chain
+// aren't stuck behind them. A txn whose nonce could not be dropped keeps its blob, so the next
+// call retries it
+pub fn release_presigned_txns(
+    exec_plan: &mut ExecutionPlan,
+    execute_step_meta: &ExecuteStepMeta,
+) -> bool /* didReleaseAny */ {
+    let mut did_release_any = false;
+    for exec_step in exec_plan
+        .paths
+        .iter_mut()
+        .flat_map(|path| path.steps.iter_mut())
+        .chain(core::iter::once(
+            &mut exec_plan.postend_escrow_to_user_transfer,
+        ))
+        .filter(|exec_step| exec_step.presigned_txn.is_some())
+    {
+        if execute_step_meta
+            .drop_execstep(exec_step.get_uuid(), exec_step.get_src_chain())
+            .is_ok()
+        {
+            exec_step.presigned_txn = None;
+            did_release_any = true;
+        }
+    }
+    did_release_any
+}
+
+fn is_static_step(exec_step: &ExecutionStep) -> bool {
+    exec_step.presigned_txn.is_none()
+        && exec_step.get_status() == ExecutableSimpleStatus::NotStarted
+        && exec_step.get_amount_in().unwrap_or(0) > 0
+        && matches!(
+            exec_step.inner,
+            ExecutionStepEnum::EthSend(_)
+                | ExecutionStepEnum::ERC20Transfer(_)
+                | ExecutionStepEnum::EthWrap(_)
+                | ExecutionStepEnum::EthUnwrap(_)
+        )
+}
+
+fn sign_static_txn(
+    exec_step: &ExecutionStep,
+    chain_info: &ChainInfo,
+    rpc_url: &str,
+    keys: &KeyContainer,
+    nonce: Nonce,
+) -> ExecutableResult<SignedTransaction> {
+    let common = exec_step.get_common();
+    let key = keys
+        .get_key(&common.src_addr)
+        .ok_or(ExecutableError::SecretNotFound)?;
+    let evm_chain_id = chain_info
+        .evm_chain_id
+        .ok_or(ExecutableError::UnexpectedNullEvmChainId)?;
+    let amount = exec_step
+        .get_amount_in()
+        .ok_or(ExecutableError::UnexpectedNullAmount)?;
+    let load_weth_contract = || {
+        chain_info
+            .weth_addr
+            .ok_or(ExecutableError::FailedToLoadWethContract)
+            .and_then(|weth_addr| {
+                eth_utils::weth_contract::WethContract::new(rpc_url, weth_addr)
+                    .map(|weth_contract| (weth_addr, weth_contract))
+                    .map_err(|_| ExecutableError::FailedToLoadWethContract)
+            })
+    };
+    let (to, value, calldata, gas_limit) = match &exec_step.inner {
+        ExecutionStepEnum::EthSend(_) => (
+            get_eth_addr(&common.dest_addr)?,
+            amount,
+            Vec::new(),
+            ETH_SEND_GAS_LIMIT,
+        ),
+        ExecutionStepEnum::ERC20Transfer(step) => {
+            let token_addr = match &step.token.id {
+                ChainTokenId::ERC20(erc20_token) => erc20_token.addr,
+                ChainTokenId::XC20(xc20_token) => xc20_token.get_eth_address(),
+                ChainTokenId::Native => return Err(ExecutableError::UnexpectedNonEthAddress),
+            };
+            let dest_addr = get_eth_addr(&common.dest_addr)?;
+            let calldata = eth_utils::erc20_contract::ERC20Contract::new(rpc_url, token_addr)
+                .and_then(|erc20_contract| erc20_contract.encode_transfer(dest_addr, amount))
+                .map_err(|_| ExecutableError::FailedToCreateTxn)?;
+            (token_addr, 0, calldata, ERC20_TRANSFER_GAS_LIMIT)
+        }
+        ExecutionStepEnum::EthWrap(_) => {
+            let (weth_addr, weth_contract) = load_weth_contract()?;
+            let calldata = weth_contract
+                .encode_deposit()
+                .map_err(|_| ExecutableError::FailedToCreateTxn)?;
+            (weth_addr, amount, calldata, WRAP_GAS_LIMIT)
+        }
+        ExecutionStepEnum::EthUnwrap(_) => {
+            let (weth_addr, weth_contract) = load_weth_contract()?;
+            let calldata = weth_contract
+                .encode_withdraw(amount)
+                .map_err(|_| ExecutableError::FailedToCreateTxn)?;
+            (weth_addr, 0, calldata, WRAP_GAS_LIMIT)
+        }
+        _ => return Err(ExecutableError::FailedToCreateTxn),
+    };
+    eth_utils::common::create_fixed_gas_raw_txn(
+        rpc_url,
+        to,
+        value,
+        calldata,
+        gas_limit,
+        PRESIGN_GAS_PRICE_MULTIPLIER,
+        key,
+        evm_chain_id,
+        chain_info.supports_eip1559,
+        nonce,
+    )
+    .map_err(ExecutableError::from_create_txn_error)
+}
+
+fn get_eth_addr(addr: &UniversalAddress) -> ExecutableResult<EthAddress> {
+    match addr {
+        UniversalAddress::Ethereum(eth_addr) => Ok(*eth_addr),
+        _ => Err(ExecutableError::UnexpectedNonEthAddress),
+    }
+}
+
+#[cfg(test)]
+mod presign_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::common::{Amount, EthTxnHash};
+    use privadex_execution_plan::test_utilities::execution_plan_factory::{
+        self, path, submitted, ESCROW, USER,
+    };
+
+    use super::*;
+
+    fn eth_send(amount: Option<Amount>, status: EthStepStatus) -> ExecutionStep {
+        execution_plan_factory::eth_send(0, ESCROW, ESCROW, amount, status)
+    }
+
+    fn eth_wrap() -> ExecutionStep {
+        execution_plan_factory::eth_wrap(2, Some(1_000), EthStepStatus::NotStarted)
+    }
+
+    // Plan whose user deposit was submitted but hasn't landed yet
+    fn plan(path_steps: Vec<ExecutionStep>) -> ExecutionPlan {
+        ExecutionPlan {
+            prestart_user_to_escrow_transfer: execution_plan_factory::eth_send(
+                0xa1,
+                USER,
+                ESCROW,
+                Some(1_000),
+                submitted(EthTxnHash::zero()),
+            ),
+            prestart_confirmed_block: None,
+            ..execution_plan_factory::plan(vec![path(path_steps)])
+        }
+    }
+
+    #[test]
+    fn test_is_presignable_route() {
+        let exec_plan = plan(vec![eth_wrap()]);
+        assert!(is_presignable_route(&exec_plan, 2));
+        // The postend transfer counts too
+        assert!(!is_presignable_route(&exec_plan, 1));
+
+        let mut paper_plan = plan(vec![eth_wrap()]);
+        paper_plan.paper_trade = true;
+        assert!(!is_presignable_route(&paper_plan, 2));
+
+        let mut sequential_plan = plan(vec![eth_wrap()]);
+        sequential_plan.path_execution_mode = PathExecutionMode::Sequential;
+        assert!(!is_presignable_route(&sequential_plan, 2));
+
+        let mut landed_plan = plan(vec![eth_wrap()]);
+        landed_plan.prestart_user_to_escrow_transfer =
+            eth_send(Some(1_000), EthStepStatus::Confirmed(EthTxnHash::zero()));
+        assert!(!is_presignable_route(&landed_plan, 2));
+    }
+
+    #[test]
+    fn test_is_static_step() {
+        assert!(is_static_step(&eth_wrap()));
+        // The postend amount is only known once the paths are done
+        assert!(!is_static_step(&eth_send(None, EthStepStatus::NotStarted)));
+
+        let mut presigned_wrap = eth_wrap();
+        presigned_wrap.presigned_txn = Some(PresignedTxn {
+            nonce: 0,
+            txn_hash: EthTxnHash::zero(),
+            raw_txn: Vec::new(),
+        });
+        assert!(!is_static_step(&presigned_wrap));
+    }
+}
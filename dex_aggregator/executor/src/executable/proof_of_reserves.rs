@@ -0,0 +1,513 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String, vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{
+        Amount, BlockNum, ChainTokenId, MillisSinceEpoch, UniversalAddress, UniversalChainId,
+        UniversalTokenId,
+    },
+    get_chain_info_from_chain_id,
+};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStep};
+
+use super::{
+    execute_step_meta::ExecuteStepMeta,
+    plan_events::get_all_steps,
+    stranded_funds::{get_src_token_and_holder, StrandedBalance, StrandedFundsHolder},
+    traits::{Executable, ExecutableResult, ExecutableSimpleStatus},
+};
+
+/// Funds the escrow holds on behalf of users: the token, the escrow account holding it, and
+/// the amount
+pub type Liability = (UniversalTokenId, UniversalAddress, Amount);
+
+/// What the escrow holds next to what it owes users, per chain, so that anyone can check that
+/// the escrow is not running fractional. Each chain's block number is where the balances
+/// were read, so they can be re-checked against the chain
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ProofOfReserves {
+    pub as_of_millis: MillisSinceEpoch,
+    pub num_in_flight_plans: u32,
+    pub chains: Vec<ChainReserves>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChainReserves {
+    pub chain: UniversalChainId,
+    pub block_number: BlockNum,
+    pub tokens: Vec<TokenReserve>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TokenReserve {
+    pub token: UniversalTokenId,
+    pub holder: UniversalAddress,
+    pub escrow_balance: Amount,
+    pub liabilities: Amount,
+}
+
+impl TokenReserve {
+    pub fn is_fully_reserved(&self) -> bool {
+        self.escrow_balance >= self.liabilities
+    }
+}
+
+/// A token whose escrow balance is below what the escrow owes users plus (for the native
+/// token) the chain's insurance buffer
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReserveShortfall {
+    pub token: UniversalTokenId,
+    pub holder: UniversalAddress,
+    pub escrow_balance: Amount,
+    pub required: Amount,
+}
+
+impl ReserveShortfall {
+    // Amounts are strings since they can exceed what a JSON number holds exactly
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"token\":\"{}\",\"escrow_balance\":\"{}\",\"required\":\"{}\"}}",
+            self.token, self.escrow_balance, self.required
+        )
+    }
+}
+
+impl ProofOfReserves {
+    /// Lists the native token of every (chain, escrow) pair plus every token with
+    /// liabilities. Balances and block numbers are filled in by fetch_balances
+    pub fn new(
+        as_of_millis: MillisSinceEpoch,
+        num_in_flight_plans: u32,
+        chain_escrows: &[(UniversalChainId, UniversalAddress)],
+        liabilities: Vec<Liability>,
+    ) -> Self {
+        let mut proof = Self {
+            as_of_millis,
+            num_in_flight_plans,
+            chains: Vec::new(),
+        };
+        for (chain, escrow_addr) in chain_escrows.iter() {
+            let native = UniversalTokenId {
+                chain: *chain,
+                id: ChainTokenId::Native,
+            };
+            proof.add_liability((native, escrow_addr.clone(), 0));
+        }
+        for liability in liabilities.into_iter() {
+            proof.add_liability(liability);
+        }
+        proof
+    }
+
+    fn add_liability(&mut self, (token, holder, amount): Liability) {
+        let chain_reserves = match self
+            .chains
+            .iter_mut()
+            .position(|chain_reserves| chain_reserves.chain == token.chain)
+        {
+            Some(i) => &mut self.chains[i],
+            None => {
+                self.chains.push(ChainReserves {
+                    chain: token.chain,
+                    block_number: 0,
+                    tokens: Vec::new(),
+                });
+                self.chains.last_mut().expect("Just pushed")
+            }
+        };
+        match chain_reserves
+            .tokens
+            .iter_mut()
+            .find(|reserve| reserve.token == token && reserve.holder == holder)
+        {
+            Some(reserve) => reserve.liabilities += amount,
+            None => chain_reserves.tokens.push(TokenReserve {
+                token,
+                holder,
+                escrow_balance: 0,
+                liabilities: amount,
+            }),
+        }
+    }
+
+    // Lists token (held by holder) even if nothing is owed in it, so its balance is fetched too
+    pub fn track_token(&mut self, token: UniversalTokenId, holder: UniversalAddress) {
+        self.add_liability((token, holder, 0));
+    }
+
+    // Drops the chains where none of holders has a reserve
+    pub fn retain_holders(&mut self, holders: &[UniversalAddress]) {
+        for chain_reserves in self.chains.iter_mut() {
+            chain_reserves
+                .tokens
+                .retain(|reserve| holders.contains(&reserve.holder));
+        }
+        self.chains
+            .retain(|chain_reserves| !chain_reserves.tokens.is_empty());
+    }
+
+    // The block number is read first, so the balances are at least that fresh
+    pub fn fetch_balances(&mut self, execute_step_meta: &ExecuteStepMeta) -> ExecutableResult<()> {
+        for chain_reserves in self.chains.iter_mut() {
+            chain_reserves.block_number = execute_step_meta.get_cur_block(&chain_reserves.chain)?;
+            for reserve in chain_reserves.tokens.iter_mut() {
+                reserve.escrow_balance =
+                    execute_step_meta.get_balance(&reserve.token, &reserve.holder)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// insurance_buffers are in each chain's native token. The escrow pays gas in it, so that
+    /// is where a buffer against bad fills and gas spikes is kept
+    pub fn find_shortfalls(
+        &self,
+        insurance_buffers: &[(UniversalChainId, Amount)],
+    ) -> Vec<ReserveShortfall> {
+        let mut shortfalls = Vec::new();
+        for chain_reserves in self.chains.iter() {
+            let insurance_buffer = insurance_buffers
+                .iter()
+                .find(|(chain, _)| *chain == chain_reserves.chain)
+                .map_or(0, |(_, buffer)| *buffer);
+            for reserve in chain_reserves.tokens.iter() {
+                let required = if reserve.token.id == ChainTokenId::Native {
+                    reserve.liabilities.saturating_add(insurance_buffer)
+                } else {
+                    reserve.liabilities
+                };
+                if reserve.escrow_balance < required {
+                    shortfalls.push(ReserveShortfall {
+                        token: reserve.token.clone(),
+                        holder: reserve.holder.clone(),
+                        escrow_balance: reserve.escrow_balance,
+                        required,
+                    });
+                }
+            }
+        }
+        shortfalls
+    }
+
+    pub fn is_fully_reserved(&self) -> bool {
+        self.chains.iter().all(|chain_reserves| {
+            chain_reserves
+                .tokens
+                .iter()
+                .all(|reserve| reserve.is_fully_reserved())
+        })
+    }
+}
+
+/// What the escrow owes the user of an in-flight ExecutionPlan, wherever the funds currently
+/// are. Funds that are still with the user or in a deposit forwarder are not owed by the
+/// escrow yet. A cross-chain transfer in flight is attributed to its source chain, so that
+/// chain can look short until the transfer lands
+pub fn get_plan_liabilities(exec_plan: &ExecutionPlan) -> Vec<Liability> {
+    if exec_plan.get_status() != ExecutableSimpleStatus::InProgress
+        || exec_plan.prestart_user_to_escrow_transfer.get_status()
+            != ExecutableSimpleStatus::Succeeded
+        || exec_plan.deposit_sweep.as_ref().map_or(false, |sweep| {
+            sweep.get_status() != ExecutableSimpleStatus::Succeeded
+        })
+    {
+        return vec![];
+    }
+    let mut liabilities = Vec::new();
+    let mut push_liability = |step: &ExecutionStep, amount: Amount| {
+        if let Some((token, StrandedFundsHolder::Escrow(holder))) = get_src_token_and_holder(step) {
+            if amount > 0 {
+                liabilities.push((token, holder, amount));
+            }
+        }
+    };
+
+    // No path runs until the escrow has redeemed an LP source's LP tokens
+    if let Some(remove_liquidity) = &exec_plan.remove_liquidity {
+        if remove_liquidity.get_status() != ExecutableSimpleStatus::Succeeded {
+            push_liability(
+                remove_liquidity,
+                remove_liquidity.get_amount_in().unwrap_or(0),
+            );
+            return liabilities;
+        }
+    }
+
+    let is_refunding = exec_plan
+        .refund_escrow_to_user_transfer
+        .get_amount_in()
+        .is_some();
+    if is_refunding
+        && exec_plan.refund_escrow_to_user_transfer.get_status()
+            != ExecutableSimpleStatus::Succeeded
+    {
+        push_liability(
+            &exec_plan.refund_escrow_to_user_transfer,
+            exec_plan
+                .refund_escrow_to_user_transfer
+                .get_amount_in()
+                .unwrap_or(0),
+        );
+    }
+
+    let mut succeeded_amount_out = 0;
+    for path in exec_plan.paths.iter() {
+        if path.get_status() == ExecutableSimpleStatus::Succeeded {
+            succeeded_amount_out += path.amount_out.unwrap_or(0);
+            continue;
+        }
+        let first_unfinished_step = path
+            .steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| step.get_status() != ExecutableSimpleStatus::Succeeded);
+        if let Some((i, step)) = first_unfinished_step {
+            // The refund covers the paths that never started, unless they start from an LP
+            // source's redeemed tokens
+            if is_refunding
+                && exec_plan.remove_liquidity.is_none()
+                && i == 0
+                && step.get_status() == ExecutableSimpleStatus::NotStarted
+            {
+                continue;
+            }
+            push_liability(step, step.get_amount_in().unwrap_or(0));
+        }
+    }
+    if exec_plan.postend_escrow_to_user_transfer.get_status() != ExecutableSimpleStatus::Succeeded {
+        push_liability(
+            &exec_plan.postend_escrow_to_user_transfer,
+            succeeded_amount_out,
+        );
+    }
+    liabilities
+}
+
+/// Unrecovered stranded funds in the escrow are owed to users. The ones without a recipient
+/// are kept by us, but we count them anyway to be conservative
+pub fn get_stranded_liabilities(unrecovered_balances: &[StrandedBalance]) -> Vec<Liability> {
+    unrecovered_balances
+        .iter()
+        .filter_map(|balance| match &balance.holder {
+            StrandedFundsHolder::Escrow(holder) => {
+                Some((balance.token.clone(), holder.clone(), balance.amount))
+            }
+            StrandedFundsHolder::DepositForwarder(_) => None,
+        })
+        .collect()
+}
+
+pub fn get_shortfall_chains(shortfalls: &[ReserveShortfall]) -> Vec<UniversalChainId> {
+    let mut chains: Vec<UniversalChainId> = Vec::new();
+    for shortfall in shortfalls.iter() {
+        if !chains.contains(&shortfall.token.chain) {
+            chains.push(shortfall.token.chain);
+        }
+    }
+    chains
+}
+
+/// Every chain where the escrow would hold the plan's funds at some point
+pub fn get_plan_chains(exec_plan: &ExecutionPlan) -> Vec<UniversalChainId> {
+    let mut chains: Vec<UniversalChainId> = Vec::new();
+    for step in get_all_steps(exec_plan) {
+        let chain = step.get_src_chain();
+        if !chains.contains(&chain) {
+            chains.push(chain);
+        }
+    }
+    chains
+}
+
+pub fn to_alert_webhook_body(shortfalls: &[ReserveShortfall]) -> String {
+    let shortfalls: Vec<String> = shortfalls
+        .iter()
+        .map(|shortfall| shortfall.to_json())
+        .collect();
+    format!("{{\"reserve_shortfalls\":[{}]}}", shortfalls.join(","))
+}
+
+/// The escrow account that holds funds on chain_id: the Eth escrow on EVM chains and the
+/// substrate escrow elsewhere
+pub fn get_chain_escrow(
+    chain_id: &UniversalChainId,
+    escrow_addrs: &[UniversalAddress],
+) -> Option<UniversalAddress> {
+    let is_evm_chain = get_chain_info_from_chain_id(chain_id)?
+        .evm_chain_id
+        .is_some();
+    escrow_addrs
+        .iter()
+        .find(|addr| matches!(addr, UniversalAddress::Ethereum(_)) == is_evm_chain)
+        .cloned()
+}
+
+#[cfg(test)]
+mod proof_of_reserves_tests {
+    use privadex_chain_metadata::{
+        common::{ERC20Token, EthAddress, EthTxnHash},
+        registry::{
+            chain::{chain_info_registry, universal_chain_id_registry::MOONBEAM},
+            token::universal_token_id_registry::GLMR_NATIVE,
+        },
+    };
+    use privadex_execution_plan::{
+        execution_plan::{EthStepStatus, ExecutionPath},
+        test_utilities::execution_plan_factory::{
+            eth_send, eth_unwrap, eth_wrap, path, plan, submitted, ESCROW, USER,
+        },
+    };
+
+    use super::*;
+
+    fn prestart(status: EthStepStatus) -> ExecutionStep {
+        eth_send(0xa1, USER, ESCROW, Some(2_000), status)
+    }
+
+    fn wrap_unwrap_path(unwrap_status: EthStepStatus, amount_out: Option<Amount>) -> ExecutionPath {
+        ExecutionPath {
+            amount_out,
+            ..path(vec![
+                eth_wrap(0, Some(1_000), EthStepStatus::Confirmed(EthTxnHash::zero())),
+                eth_unwrap(0, Some(1_000), unwrap_status),
+            ])
+        }
+    }
+
+    fn in_flight_plan(paths: Vec<ExecutionPath>) -> ExecutionPlan {
+        ExecutionPlan {
+            prestart_user_to_escrow_transfer: prestart(
+                EthStepStatus::Confirmed(EthTxnHash::zero()),
+            ),
+            ..plan(paths)
+        }
+    }
+
+    #[test]
+    fn test_plan_liabilities() {
+        let weth = UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: chain_info_registry::MOONBEAM_INFO.weth_addr.unwrap(),
+            }),
+        };
+        let exec_plan = in_flight_plan(vec![
+            wrap_unwrap_path(submitted(EthTxnHash::zero()), None),
+            wrap_unwrap_path(EthStepStatus::Confirmed(EthTxnHash::zero()), Some(990)),
+        ]);
+        assert_eq!(
+            get_plan_liabilities(&exec_plan),
+            vec![(weth, ESCROW, 1_000), (GLMR_NATIVE, ESCROW, 990)]
+        );
+    }
+
+    #[test]
+    fn test_no_liabilities_before_deposit() {
+        let mut exec_plan = in_flight_plan(vec![wrap_unwrap_path(EthStepStatus::NotStarted, None)]);
+        exec_plan.prestart_user_to_escrow_transfer = prestart(submitted(EthTxnHash::zero()));
+        assert!(get_plan_liabilities(&exec_plan).is_empty());
+    }
+
+    #[test]
+    fn test_proof_of_reserves_sums_liabilities() {
+        let proof = ProofOfReserves::new(
+            1_000,
+            2,
+            &[(MOONBEAM, ESCROW)],
+            vec![(GLMR_NATIVE, ESCROW, 990), (GLMR_NATIVE, ESCROW, 10)],
+        );
+        assert_eq!(proof.chains.len(), 1);
+        assert_eq!(
+            proof.chains[0].tokens,
+            vec![TokenReserve {
+                token: GLMR_NATIVE,
+                holder: ESCROW,
+                escrow_balance: 0,
+                liabilities: 1_000,
+            }]
+        );
+        assert!(!proof.is_fully_reserved());
+    }
+
+    #[test]
+    fn test_retain_holders() {
+        let other_escrow = UniversalAddress::Ethereum(EthAddress { 0: [0x22; 20] });
+        let mut proof = ProofOfReserves::new(
+            1_000,
+            2,
+            &[(MOONBEAM, ESCROW), (MOONBEAM, other_escrow.clone())],
+            vec![
+                (GLMR_NATIVE, ESCROW, 990),
+                (GLMR_NATIVE, other_escrow.clone(), 10),
+            ],
+        );
+        proof.retain_holders(&[other_escrow.clone()]);
+        assert_eq!(
+            proof.chains[0].tokens,
+            vec![TokenReserve {
+                token: GLMR_NATIVE,
+                holder: other_escrow,
+                escrow_balance: 0,
+                liabilities: 10,
+            }]
+        );
+        proof.retain_holders(&[]);
+        assert!(proof.chains.is_empty());
+    }
+
+    #[test]
+    fn test_find_shortfalls_with_insurance_buffer() {
+        let mut proof = ProofOfReserves::new(
+            1_000,
+            1,
+            &[(MOONBEAM, ESCROW)],
+            vec![(GLMR_NATIVE, ESCROW, 990)],
+        );
+        proof.chains[0].tokens[0].escrow_balance = 1_000;
+        assert!(proof.is_fully_reserved());
+        assert!(proof.find_shortfalls(&[]).is_empty());
+        assert!(proof.find_shortfalls(&[(MOONBEAM, 10)]).is_empty());
+
+        let shortfalls = proof.find_shortfalls(&[(MOONBEAM, 11)]);
+        assert_eq!(
+            shortfalls,
+            vec![ReserveShortfall {
+                token: GLMR_NATIVE,
+                holder: ESCROW,
+                escrow_balance: 1_000,
+                required: 1_001,
+            }]
+        );
+        assert_eq!(get_shortfall_chains(&shortfalls), vec![MOONBEAM]);
+        assert_eq!(
+            to_alert_webhook_body(&shortfalls),
+            format!(
+                "{{\"reserve_shortfalls\":[{{\"token\":\"{}\",\"escrow_balance\":\"1000\",\
+                \"required\":\"1001\"}}]}}",
+                GLMR_NATIVE
+            )
+        );
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    chain_info::ChainInfo,
+    common::{EthAddress, EvmChainId, MillisSinceEpoch, UniversalChainId},
+    get_dexes_from_chain_id,
+    registry::dex::DexId,
+};
+
+use crate::eth_utils::common::{chain_id, get_code};
+use crate::substrate_utils::node_rpc_utils::SubstrateNodeRpcUtils;
+
+use super::execute_step_meta::ExecuteStepMeta;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RegistryCheck {
+    // ChainInfo.evm_chain_id vs. eth_chainId. EVM txns we sign for the wrong chain ID are
+    // rejected
+    EvmChainId,
+    // The ChainInfo's ss58 prefix vs. system_properties' ss58Format. Addresses we encode with
+    // the wrong prefix point at other accounts
+    Ss58Prefix,
+    // ChainInfo.weth_addr has code
+    WrappedNativeToken(EthAddress),
+    // Dex.eth_dex_router has code
+    DexRouter(DexId, EthAddress),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RegistryCheckOutcome {
+    Matches,
+    Mismatch { registry: String, live: String },
+    // The live value couldn't be read, so we can't tell
+    Unknown(String /* reason */),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RegistryCheckResult {
+    pub check: RegistryCheck,
+    pub outcome: RegistryCheckOutcome,
+}
+
+/// A chain's hard-coded registry values checked against the chain itself. Unlike the preflight
+/// checklist, this is about the registry being right, not the escrow being ready
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RegistryReport {
+    pub chain: UniversalChainId,
+    pub as_of_millis: MillisSinceEpoch,
+    pub checks: Vec<RegistryCheckResult>,
+}
+
+impl RegistryReport {
+    pub fn has_mismatch(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|result| matches!(result.outcome, RegistryCheckOutcome::Mismatch { .. }))
+    }
+}
+
+pub fn compare_evm_chain_id(
+    registry: EvmChainId,
+    live: Option<EvmChainId>,
+) -> RegistryCheckOutcome {
+    match live {
+        Some(live) if live == registry => RegistryCheckOutcome::Matches,
+        Some(live) => RegistryCheckOutcome::Mismatch {
+            registry: format!("{}", registry),
+            live: format!("{}", live),
+        },
+        None => RegistryCheckOutcome::Unknown("eth_chainId call failed".into()),
+    }
+}
+
+pub fn compare_ss58_prefix(registry: u16, live: Option<u16>) -> RegistryCheckOutcome {
+    match live {
+        Some(live) if live == registry => RegistryCheckOutcome::Matches,
+        Some(live) => RegistryCheckOutcome::Mismatch {
+            registry: format!("{}", registry),
+            live: format!("{}", live),
+        },
+        None => RegistryCheckOutcome::Unknown("system_properties call failed".into()),
+    }
+}
+
+// live is the address's code, None if eth_getCode failed
+pub fn check_code_presence(live: Option<&[u8]>) -> RegistryCheckOutcome {
+    match live {
+        Some(code) if !code.is_empty() => RegistryCheckOutcome::Matches,
+        Some(_) => RegistryCheckOutcome::Mismatch {
+            registry: "contract".into(),
+            live: "no code".into(),
+        },
+        None => RegistryCheckOutcome::Unknown("eth_getCode call failed".into()),
+    }
+}
+
+/// Only reads, so it is safe to run any time
+pub fn verify_chain_registry(
+    execute_step_meta: &ExecuteStepMeta,
+    chain_info: &ChainInfo,
+) -> Vec<RegistryCheckResult> {
+    let rpc_url = execute_step_meta.get_rpc_url(chain_info);
+    let mut results = Vec::new();
+    let mut push = |check: RegistryCheck, outcome: RegistryCheckOutcome| {
+        results.push(RegistryCheckResult { check, outcome });
+    };
+
+    if let Some(evm_chain_id) = chain_info.evm_chain_id {
+        push(
+            RegistryCheck::EvmChainId,
+            compare_evm_chain_id(evm_chain_id, chain_id(&rpc_url).ok()),
+        );
+    }
+
+    if let Some(ss58_prefix) = chain_info.get_ss58_prefix() {
+        let live = SubstrateNodeRpcUtils {
+            rpc_url: rpc_url.clone(),
+        }
+        .get_ss58_prefix()
+        .ok();
+        push(
+            RegistryCheck::Ss58Prefix,
+            compare_ss58_prefix(u16::from(ss58_prefix), live),
+        );
+    }
+
+    if let Some(weth_addr) = chain_info.weth_addr {
+        push(
+            RegistryCheck::WrappedNativeToken(weth_addr),
+            check_code_presence(get_code(&rpc_url, weth_addr).ok().as_deref()),
+        );
+    }
+
+    for dex in get_dexes_from_chain_id(&chain_info.chain_id) {
+        push(
+            RegistryCheck::DexRouter(dex.id, dex.eth_dex_router),
+            check_code_presence(get_code(&rpc_url, dex.eth_dex_router).ok().as_deref()),
+        );
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod registry_check_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::chain_info_registry;
+
+    use super::*;
+
+    #[test]
+    fn test_compare_registry_values() {
+        assert_eq!(
+            compare_evm_chain_id(1284, Some(1284)),
+            RegistryCheckOutcome::Matches
+        );
+        assert_eq!(
+            compare_evm_chain_id(1284, Some(1287)),
+            RegistryCheckOutcome::Mismatch {
+                registry: "1284".into(),
+                live: "1287".into(),
+            }
+        );
+        assert!(matches!(
+            compare_evm_chain_id(1284, None),
+            RegistryCheckOutcome::Unknown(_)
+        ));
+        assert_eq!(
+            compare_ss58_prefix(0, Some(0)),
+            RegistryCheckOutcome::Matches
+        );
+        assert!(matches!(
+            compare_ss58_prefix(0, Some(2)),
+            RegistryCheckOutcome::Mismatch { .. }
+        ));
+        assert_eq!(
+            check_code_presence(Some(&[0x60, 0x80])),
+            RegistryCheckOutcome::Matches
+        );
+        assert!(matches!(
+            check_code_presence(Some(&[])),
+            RegistryCheckOutcome::Mismatch { .. }
+        ));
+        assert!(matches!(
+            check_code_presence(None),
+            RegistryCheckOutcome::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_report_has_mismatch() {
+        let mut report = RegistryReport {
+            chain: chain_info_registry::MOONBEAM_INFO.chain_id,
+            as_of_millis: 0,
+            checks: vec![RegistryCheckResult {
+                check: RegistryCheck::EvmChainId,
+                outcome: RegistryCheckOutcome::Matches,
+            }],
+        };
+        assert!(!report.has_mismatch());
+        // A check we couldn't run isn't drift
+        report.checks.push(RegistryCheckResult {
+            check: RegistryCheck::Ss58Prefix,
+            outcome: RegistryCheckOutcome::Unknown("timeout".into()),
+        });
+        assert!(!report.has_mismatch());
+        report.checks.push(RegistryCheckResult {
+            check: RegistryCheck::EvmChainId,
+            outcome: compare_evm_chain_id(1284, Some(1)),
+        });
+        assert!(report.has_mismatch());
+    }
+}
@@ -0,0 +1,309 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{Amount, EthAddress, UniversalAddress, UniversalTokenId, USD_AMOUNT_EXPONENT},
+    get_chain_info_from_chain_id,
+    registry::token::{stablecoin_registry, token_decimals_registry},
+};
+use privadex_common::{
+    utils::general_utils::{mul_ratio_u128, slice_to_hex_string},
+    uuid::Uuid,
+};
+use privadex_execution_plan::execution_plan::{
+    CommonExecutionMeta, ERC20TransferStep, EthStepStatus, ExecutionStep, ExecutionStepEnum,
+};
+
+use crate::{
+    concurrency_coordinator::relayer_registry::RelayerContribution, key_container::KeyContainer,
+};
+
+use super::{
+    execute_step_meta::ExecuteStepMeta,
+    plan_analytics::TokenAmount,
+    traits::{Executable, ExecutableResult, ExecutableSimpleStatus},
+};
+
+const RELAYER_PAYOUT_OBJECT_KEY_PREFIX: &str = "relayer-payout";
+
+/// How third-party workers are rewarded for stepping plans forward. Rewards accrue in USD and
+/// are paid out in payout_token (a stablecoin, so we can treat 1 token as $1), out of the
+/// protocol fees that plans delivering payout_token leave in the escrow
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RelayerRewardRate {
+    pub payout_token: UniversalTokenId,
+    // $ x 10^18 per step whose status changed while the worker stepped the plan forward
+    pub usd_per_step: Amount,
+    // Share of the escrow gas the worker's txns spent that is paid back on top
+    pub gas_rebate_bps: u32,
+}
+
+impl RelayerRewardRate {
+    pub fn is_valid(&self) -> bool {
+        stablecoin_registry::get_stablecoins(self.payout_token.chain)
+            .any(|token| token == self.payout_token)
+            && token_decimals_registry::get_decimals(&self.payout_token).is_some()
+            && self.gas_rebate_bps <= 10_000
+    }
+
+    // $ x 10^18 the worker has earned in total, including what it already claimed
+    pub fn get_earned_usd(&self, contribution: &RelayerContribution) -> Amount {
+        self.usd_per_step
+            .saturating_mul(contribution.num_steps_advanced as Amount)
+            .saturating_add(mul_ratio_u128(
+                contribution.gas_fee_usd,
+                self.gas_rebate_bps as u128,
+                10_000,
+            ))
+    }
+
+    pub fn get_claimable_usd(&self, contribution: &RelayerContribution) -> Amount {
+        self.get_earned_usd(contribution)
+            .saturating_sub(contribution.claimed_usd)
+    }
+
+    pub fn usd_to_payout_amount(&self, amount_usd: Amount) -> Option<Amount> {
+        let decimals = token_decimals_registry::get_decimals(&self.payout_token)?;
+        Some(token_decimals_registry::convert_decimals(
+            amount_usd,
+            USD_AMOUNT_EXPONENT as u8,
+            decimals,
+        ))
+    }
+
+    pub fn payout_amount_to_usd(&self, amount: Amount) -> Option<Amount> {
+        let decimals = token_decimals_registry::get_decimals(&self.payout_token)?;
+        Some(token_decimals_registry::convert_decimals(
+            amount,
+            decimals,
+            USD_AMOUNT_EXPONENT as u8,
+        ))
+    }
+
+    // What a closed plan adds to the fee pool. Fees in any other token stay with the protocol
+    pub fn get_fee_pool_contribution(&self, protocol_fee: &TokenAmount) -> Option<Amount> {
+        if protocol_fee.token == self.payout_token && protocol_fee.amount > 0 {
+            Some(protocol_fee.amount)
+        } else {
+            None
+        }
+    }
+}
+
+/// A claim_relayer_rewards payout from the escrow to the worker's recipient address. Kept in S3
+/// per worker until it settles, so that a worker only has one payout in flight
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RelayerPayout {
+    pub worker: [u8; 32],
+    pub amount_usd: Amount,
+    pub num_attempts: u32,
+    pub step: ExecutionStep,
+}
+
+impl RelayerPayout {
+    pub fn new(
+        worker: [u8; 32],
+        escrow_addr: EthAddress,
+        recipient: EthAddress,
+        token: UniversalTokenId,
+        amount: Amount,
+        amount_usd: Amount,
+    ) -> Option<Self> {
+        Some(Self {
+            worker,
+            amount_usd,
+            num_attempts: 1,
+            step: create_payout_step(
+                &worker,
+                0,
+                UniversalAddress::Ethereum(escrow_addr),
+                UniversalAddress::Ethereum(recipient),
+                token,
+                amount,
+            )?,
+        })
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.step.get_status() == ExecutableSimpleStatus::Succeeded
+    }
+
+    /// Starts or advances the transfer. A failed or dropped transfer is retried with a fresh
+    /// step, since the claim was already taken out of the fee pool. Returns whether the payout
+    /// changed
+    pub fn step_forward(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+    ) -> ExecutableResult<bool> {
+        let step_res = self.step.execute_step_forward(execute_step_meta, keys)?;
+        match self.step.get_status() {
+            ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
+                let (token, amount) = match &self.step.inner {
+                    ExecutionStepEnum::ERC20Transfer(transfer_step) => (
+                        transfer_step.token.clone(),
+                        transfer_step.amount.unwrap_or(0),
+                    ),
+                    _ => return Ok(step_res.did_status_change),
+                };
+                let common = self.step.get_common().clone();
+                if let Some(retry_step) = create_payout_step(
+                    &self.worker,
+                    self.num_attempts,
+                    common.src_addr,
+                    common.dest_addr,
+                    token,
+                    amount,
+                ) {
+                    self.num_attempts += 1;
+                    self.step = retry_step;
+                }
+                Ok(true)
+            }
+            _ => Ok(step_res.did_status_change),
+        }
+    }
+}
+
+fn create_payout_step(
+    worker: &[u8; 32],
+    num_attempts: u32,
+    src_addr: UniversalAddress,
+    dest_addr: UniversalAddress,
+    token: UniversalTokenId,
+    amount: Amount,
+) -> Option<ExecutionStep> {
+    let uuid = Uuid::new(sp_core_hashing::blake2_128(
+        &(worker, &dest_addr, &token, amount, num_attempts).encode(),
+    ));
+    let chain_info = get_chain_info_from_chain_id(&token.chain)?;
+    Some(ExecutionStep::new(ExecutionStepEnum::ERC20Transfer(
+        ERC20TransferStep {
+            uuid,
+            token,
+            amount: Some(amount),
+            common: CommonExecutionMeta {
+                src_addr,
+                dest_addr,
+                gas_fee_native: chain_info.avg_gas_fee_in_native_token,
+                // Paid by the protocol, not by a user
+                gas_fee_usd: 0,
+            },
+            status: EthStepStatus::NotStarted,
+        },
+    )))
+}
+
+pub fn get_relayer_payout_object_key(worker: &[u8; 32]) -> String {
+    format!(
+        "{}-{}",
+        RELAYER_PAYOUT_OBJECT_KEY_PREFIX,
+        &slice_to_hex_string(worker)[2..]
+    )
+}
+
+#[cfg(test)]
+mod relayer_rewards_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::registry::token::universal_token_id_registry::{
+        USDC_WH_MOONBEAM, USDT_MOONBEAM,
+    };
+
+    use super::*;
+
+    fn rate() -> RelayerRewardRate {
+        RelayerRewardRate {
+            payout_token: USDC_WH_MOONBEAM,
+            // $0.05
+            usd_per_step: 50_000_000_000_000_000,
+            gas_rebate_bps: 12_000,
+        }
+    }
+
+    #[test]
+    fn test_claimable_usd() {
+        let contribution = RelayerContribution {
+            num_steps_advanced: 10,
+            gas_fee_usd: 2_000_000_000_000_000_000,
+            claimed_usd: 1_000_000_000_000_000_000,
+        };
+        // 10 * $0.05 + 120% of $2 - $1
+        assert_eq!(
+            rate().get_claimable_usd(&contribution),
+            1_900_000_000_000_000_000
+        );
+        assert_eq!(
+            rate().usd_to_payout_amount(1_900_000_000_000_000_000),
+            Some(1_900_000)
+        );
+        assert_eq!(
+            rate().payout_amount_to_usd(1_900_000),
+            Some(1_900_000_000_000_000_000)
+        );
+
+        // Rebates above 100% are rejected by set_relayer_reward_rate
+        assert!(!rate().is_valid());
+        let valid_rate = RelayerRewardRate {
+            gas_rebate_bps: 10_000,
+            ..rate()
+        };
+        assert!(valid_rate.is_valid());
+        assert!(valid_rate
+            .get_fee_pool_contribution(&TokenAmount {
+                token: USDT_MOONBEAM,
+                amount: 1_000,
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn test_new_payout() {
+        let escrow = EthAddress {
+            0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+        };
+        let recipient = EthAddress {
+            0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
+        };
+        let payout = RelayerPayout::new(
+            [3u8; 32],
+            escrow,
+            recipient,
+            USDC_WH_MOONBEAM,
+            1_900_000,
+            1_900_000_000_000_000_000,
+        )
+        .expect("Moonbeam is registered");
+        assert_eq!(payout.num_attempts, 1);
+        assert!(!payout.is_settled());
+        match &payout.step.inner {
+            ExecutionStepEnum::ERC20Transfer(transfer_step) => {
+                assert_eq!(transfer_step.amount, Some(1_900_000));
+                assert_eq!(
+                    transfer_step.common.dest_addr,
+                    UniversalAddress::Ethereum(recipient)
+                );
+            }
+            _ => panic!("Expected an ERC20 transfer"),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, BlockNum, UniversalAddress};
+use privadex_common::utils::general_utils::slice_to_hex_string;
+use privadex_execution_plan::deposit_address::DepositRemark;
+
+const REMARK_DEPOSIT_OBJECT_KEY_PREFIX: &str = "remark-deposit";
+
+// How long after get_deposit_instructions we keep looking for the user's batch. An exchange
+// can't add the remark, so funds on one first need to be withdrawn to the user's own account,
+// and exchange withdrawals can sit in a queue for a while. Hence this is much longer than
+// TXN_NUM_BLOCKS_ALIVE (~2 hours at 12 second blocks)
+pub const REMARK_DEPOSIT_NUM_BLOCKS_ALIVE: BlockNum = 600;
+
+/// How to deposit with a remark instead of an EVM txn. From one account, send amount_in of the
+/// src chain's native token to deposit_account and call system.remarkWithEvent(remark) in the
+/// same utility.batchAll, at the latest in valid_until_block. Then start the swap with
+/// start_remark_deposit_swap(remark)
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DepositInstructions {
+    // SS58 on chains whose EVM accounts are backed by a hashed account (e.g. Astar), else hex
+    pub deposit_account: String,
+    // Remarked as text, i.e. these hex characters and not the bytes they decode to
+    pub remark: String,
+    pub amount_in: Amount,
+    pub valid_until_block: BlockNum,
+}
+
+// What get_deposit_instructions was called with, so that the swap can be started from just the
+// remark. Kept in S3 under the remark
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RemarkDepositRequest {
+    pub remark: DepositRemark,
+    pub src_network_name: String,
+    pub dest_network_name: String,
+    pub src_eth_addr: String,
+    pub dest_eth_addr: String,
+    pub src_token: String,
+    pub dest_token: String,
+    pub amount_in_str: String,
+    pub deposit_account: UniversalAddress,
+    pub start_block_num: BlockNum,
+    pub end_block_num: BlockNum,
+}
+
+// Hex without the 0x, so that exchanges that only take alphanumeric memos accept it
+pub fn get_remark_text(remark: &DepositRemark) -> String {
+    slice_to_hex_string(remark)[2..].into()
+}
+
+// What System.Remarked reports for the remark
+pub fn get_remark_hash(remark: &DepositRemark) -> [u8; 32] {
+    sp_core_hashing::blake2_256(get_remark_text(remark).as_bytes())
+}
+
+pub fn get_remark_deposit_object_key(remark: &DepositRemark) -> String {
+    format!(
+        "{}-{}",
+        REMARK_DEPOSIT_OBJECT_KEY_PREFIX,
+        get_remark_text(remark)
+    )
+}
+
+#[cfg(test)]
+mod remark_deposit_tests {
+    use super::*;
+
+    #[test]
+    fn test_remark_hash_is_over_the_text() {
+        let remark: DepositRemark = [0xab; 16];
+        assert_eq!(get_remark_text(&remark), "abababababababababababababababab");
+        assert_eq!(
+            get_remark_hash(&remark),
+            sp_core_hashing::blake2_256(b"abababababababababababababababab")
+        );
+        assert_ne!(
+            get_remark_hash(&remark),
+            sp_core_hashing::blake2_256(&remark)
+        );
+    }
+}
@@ -0,0 +1,282 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{EthAddress, MillisSinceEpoch, UniversalTokenId};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStepEnum};
+use privadex_routing::graph::{
+    edge::{Edge, SwapEdge},
+    traits::QuoteGetter,
+};
+
+use super::traits::{Executable, ExecutableSimpleStatus};
+
+// A segment is blacklisted once it fails this many steps within FAILURE_WINDOW_MILLIS
+pub const MAX_FAILURES_IN_WINDOW: usize = 3;
+pub const FAILURE_WINDOW_MILLIS: MillisSinceEpoch = 6 * 60 * 60 * 1000;
+// After which the segment is routed through again, with a clean slate
+pub const BLACKLIST_COOLDOWN_MILLIS: MillisSinceEpoch = 12 * 60 * 60 * 1000;
+
+/// The part of a route that a failed step is blamed on
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RouteSegment {
+    // The DEX's pool between the two tokens, in either direction (token_a < token_b)
+    DexPool {
+        dex_router: EthAddress,
+        token_a: UniversalTokenId,
+        token_b: UniversalTokenId,
+    },
+    // XCM or Wormhole
+    BridgeLane {
+        src_token: UniversalTokenId,
+        dest_token: UniversalTokenId,
+    },
+}
+
+impl RouteSegment {
+    pub fn dex_pool(
+        dex_router: EthAddress,
+        token1: &UniversalTokenId,
+        token2: &UniversalTokenId,
+    ) -> Self {
+        let (token_a, token_b) = if token1 <= token2 {
+            (token1.clone(), token2.clone())
+        } else {
+            (token2.clone(), token1.clone())
+        };
+        Self::DexPool {
+            dex_router,
+            token_a,
+            token_b,
+        }
+    }
+
+    pub fn matches_edge(&self, edge: &Edge) -> bool {
+        match (self, edge) {
+            (Self::DexPool { .. }, Edge::Swap(SwapEdge::CPMM(cpmm_edge))) => {
+                *self
+                    == Self::dex_pool(
+                        cpmm_edge.dex.eth_dex_router,
+                        &cpmm_edge.src_token,
+                        &cpmm_edge.dest_token,
+                    )
+            }
+            (
+                Self::BridgeLane {
+                    src_token,
+                    dest_token,
+                },
+                Edge::Bridge(bridge_edge),
+            ) => bridge_edge.get_src_dest_token() == (src_token, dest_token),
+            _ => false,
+        }
+    }
+}
+
+// A multi-pool swap step fails as a whole, so all of its pools are blamed
+fn get_step_segments(step: &ExecutionStepEnum) -> Vec<RouteSegment> {
+    match step {
+        ExecutionStepEnum::EthDexSwap(swap_step) => swap_step
+            .token_path
+            .windows(2)
+            .map(|pair| RouteSegment::dex_pool(swap_step.dex_router_addr, &pair[0], &pair[1]))
+            .collect(),
+        ExecutionStepEnum::XCMTransfer(xcm_step) => vec![RouteSegment::BridgeLane {
+            src_token: xcm_step.src_token.clone(),
+            dest_token: xcm_step.dest_token.clone(),
+        }],
+        ExecutionStepEnum::WormholeTransfer(wormhole_step) => vec![RouteSegment::BridgeLane {
+            src_token: wormhole_step.src_token.clone(),
+            dest_token: wormhole_step.dest_token.clone(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Recent step failures per route segment, fed by closed ExecutionPlans. Segments that keep
+/// failing are left out of the graph that quotes and plans are routed on until their cooldown
+/// ends
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RouteBlacklist {
+    segments: Vec<SegmentFailures>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct SegmentFailures {
+    segment: RouteSegment,
+    // Within FAILURE_WINDOW_MILLIS, oldest first
+    recent_failure_millis: Vec<MillisSinceEpoch>,
+    blacklisted_until: Option<MillisSinceEpoch>,
+}
+
+impl RouteBlacklist {
+    // Returns whether any step failed, i.e. whether the blacklist needs to be saved
+    pub fn record_closed_exec_plan(
+        &mut self,
+        exec_plan: &ExecutionPlan,
+        now_millis: MillisSinceEpoch,
+    ) -> bool /* didChange */ {
+        let failed_segments: Vec<RouteSegment> = exec_plan
+            .paths
+            .iter()
+            .flat_map(|path| path.steps.iter())
+            .filter(|step| step.get_status() == ExecutableSimpleStatus::Failed)
+            .flat_map(|step| get_step_segments(&step.inner))
+            .collect();
+        for segment in failed_segments.iter() {
+            self.record_failure(segment, now_millis);
+        }
+        !failed_segments.is_empty()
+    }
+
+    pub fn record_failure(&mut self, segment: &RouteSegment, now_millis: MillisSinceEpoch) {
+        self.evict_expired(now_millis);
+        let entry = match self
+            .segments
+            .iter()
+            .position(|entry| &entry.segment == segment)
+        {
+            Some(index) => &mut self.segments[index],
+            None => {
+                self.segments.push(SegmentFailures {
+                    segment: segment.clone(),
+                    recent_failure_millis: Vec::new(),
+                    blacklisted_until: None,
+                });
+                self.segments.last_mut().expect("Just pushed")
+            }
+        };
+        entry.recent_failure_millis.push(now_millis);
+        if entry.blacklisted_until.is_none()
+            && entry.recent_failure_millis.len() >= MAX_FAILURES_IN_WINDOW
+        {
+            entry.blacklisted_until = Some(now_millis + BLACKLIST_COOLDOWN_MILLIS);
+        }
+    }
+
+    // With the time each one is blacklisted until
+    pub fn get_blacklisted_segments(
+        &self,
+        now_millis: MillisSinceEpoch,
+    ) -> Vec<(RouteSegment, MillisSinceEpoch)> {
+        self.segments
+            .iter()
+            .filter_map(|entry| match entry.blacklisted_until {
+                Some(until_millis) if until_millis > now_millis => {
+                    Some((entry.segment.clone(), until_millis))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Drops failures that fell out of the window and blacklistings whose cooldown ended. An
+    // entry is removed once it has neither, so the object only grows with failing segments
+    fn evict_expired(&mut self, now_millis: MillisSinceEpoch) {
+        for entry in self.segments.iter_mut() {
+            if entry
+                .blacklisted_until
+                .map_or(false, |until_millis| until_millis <= now_millis)
+            {
+                entry.blacklisted_until = None;
+                entry.recent_failure_millis.clear();
+            }
+            entry
+                .recent_failure_millis
+                .retain(|millis| millis + FAILURE_WINDOW_MILLIS > now_millis);
+        }
+        self.segments.retain(|entry| {
+            entry.blacklisted_until.is_some() || !entry.recent_failure_millis.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod route_blacklist_tests {
+    use privadex_chain_metadata::registry::{
+        dex::dex_registry::STELLASWAP,
+        token::universal_token_id_registry::{DOT_MOONBEAM, DOT_NATIVE, GLMR_NATIVE},
+    };
+
+    use super::*;
+
+    fn stellaswap_pool() -> RouteSegment {
+        RouteSegment::dex_pool(STELLASWAP.eth_dex_router, &GLMR_NATIVE, &DOT_MOONBEAM)
+    }
+
+    #[test]
+    fn test_dex_pool_is_undirected() {
+        assert_eq!(
+            stellaswap_pool(),
+            RouteSegment::dex_pool(STELLASWAP.eth_dex_router, &DOT_MOONBEAM, &GLMR_NATIVE)
+        );
+        assert_ne!(
+            stellaswap_pool(),
+            RouteSegment::dex_pool(EthAddress::zero(), &GLMR_NATIVE, &DOT_MOONBEAM)
+        );
+    }
+
+    #[test]
+    fn test_blacklisted_after_repeated_failures() {
+        let mut blacklist = RouteBlacklist::default();
+        let lane = RouteSegment::BridgeLane {
+            src_token: DOT_NATIVE,
+            dest_token: DOT_MOONBEAM,
+        };
+        for i in 0..MAX_FAILURES_IN_WINDOW as MillisSinceEpoch {
+            assert!(blacklist.get_blacklisted_segments(i).is_empty());
+            blacklist.record_failure(&stellaswap_pool(), i);
+        }
+        blacklist.record_failure(&lane, 10);
+        let until_millis =
+            MAX_FAILURES_IN_WINDOW as MillisSinceEpoch - 1 + BLACKLIST_COOLDOWN_MILLIS;
+        assert_eq!(
+            blacklist.get_blacklisted_segments(10),
+            vec![(stellaswap_pool(), until_millis)]
+        );
+        // The cooldown ends with a clean slate
+        assert!(blacklist.get_blacklisted_segments(until_millis).is_empty());
+        blacklist.record_failure(&stellaswap_pool(), until_millis);
+        assert!(blacklist.get_blacklisted_segments(until_millis).is_empty());
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_forgotten() {
+        let mut blacklist = RouteBlacklist::default();
+        blacklist.record_failure(&stellaswap_pool(), 0);
+        blacklist.record_failure(&stellaswap_pool(), 1);
+        blacklist.record_failure(&stellaswap_pool(), FAILURE_WINDOW_MILLIS + 1);
+        assert!(blacklist
+            .get_blacklisted_segments(FAILURE_WINDOW_MILLIS + 1)
+            .is_empty());
+        blacklist.record_failure(
+            &RouteSegment::BridgeLane {
+                src_token: DOT_MOONBEAM,
+                dest_token: DOT_NATIVE,
+            },
+            3 * FAILURE_WINDOW_MILLIS,
+        );
+        // Only the lane is left
+        assert_eq!(blacklist.segments.len(), 1);
+    }
+}
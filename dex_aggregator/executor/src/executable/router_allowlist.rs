@@ -0,0 +1,239 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{EthAddress, UniversalChainId},
+    get_dexes_from_chain_id,
+};
+use privadex_execution_plan::execution_plan::{ExecutionPlan, ExecutionStepEnum};
+
+// Each new plan reads the whole list
+pub const MAX_ROUTER_ALLOWLIST_LEN: usize = 32;
+
+/// A DEX router that isn't in the compiled-in DEX registry but that the admin allowed plans to
+/// swap through, e.g. a DEX's new router deployed before a release adds it
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AllowedRouter {
+    pub chain_id: UniversalChainId,
+    pub router: EthAddress,
+    // What the router's factory() must return
+    pub factory: EthAddress,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RouterCheckError {
+    // Neither the DEX registry nor the router allowlist has the router
+    UnknownRouter(UniversalChainId, EthAddress),
+    FactoryCallFailed(UniversalChainId, EthAddress),
+    // The router is ours but points at another factory, i.e. other pools
+    FactoryMismatch(UniversalChainId, EthAddress),
+}
+
+/// The registry's DEXes take precedence over the allowlist
+pub fn get_expected_factory(
+    chain_id: UniversalChainId,
+    router: &EthAddress,
+    allowlist: &[AllowedRouter],
+) -> Option<EthAddress> {
+    get_dexes_from_chain_id(&chain_id)
+        .iter()
+        .find(|dex| &dex.eth_dex_router == router)
+        .map(|dex| dex.eth_dex_factory)
+        .or_else(|| {
+            allowlist
+                .iter()
+                .find(|allowed| allowed.chain_id == chain_id && &allowed.router == router)
+                .map(|allowed| allowed.factory)
+        })
+}
+
+// The router a pair's LP tokens are added through, i.e. the one whose factory made the pair.
+// Like get_expected_factory, the registry's DEXes take precedence over the allowlist
+pub fn get_router_for_factory(
+    chain_id: UniversalChainId,
+    factory: &EthAddress,
+    allowlist: &[AllowedRouter],
+) -> Option<EthAddress> {
+    get_dexes_from_chain_id(&chain_id)
+        .iter()
+        .find(|dex| &dex.eth_dex_factory == factory)
+        .map(|dex| dex.eth_dex_router)
+        .or_else(|| {
+            allowlist
+                .iter()
+                .find(|allowed| allowed.chain_id == chain_id && &allowed.factory == factory)
+                .map(|allowed| allowed.router)
+        })
+}
+
+// Every router the plan swaps through (including a zap-in's and an LP source's), once each
+pub fn get_plan_dex_routers(exec_plan: &ExecutionPlan) -> Vec<(UniversalChainId, EthAddress)> {
+    let mut routers = Vec::new();
+    for step in exec_plan
+        .paths
+        .iter()
+        .flat_map(|path| path.steps.iter())
+        .chain(core::iter::once(&exec_plan.postend_escrow_to_user_transfer))
+        .chain(exec_plan.remove_liquidity.iter())
+    {
+        let router = match &step.inner {
+            ExecutionStepEnum::EthDexSwap(swap) => (swap.token_path[0].chain, swap.dex_router_addr),
+            ExecutionStepEnum::EthZapIn(zap_in) => (zap_in.token.chain, zap_in.dex_router_addr),
+            ExecutionStepEnum::EthRemoveLiquidity(remove_liquidity) => (
+                remove_liquidity.lp_token.chain,
+                remove_liquidity.dex_router_addr,
+            ),
+            _ => continue,
+        };
+        if !routers.contains(&router) {
+            routers.push(router);
+        }
+    }
+    routers
+}
+
+/// get_factory is the router's on-chain factory() (an eth_call), so the routers are all
+/// checked against the registry or the allowlist before any of them is called
+pub fn check_dex_routers<F>(
+    routers: &[(UniversalChainId, EthAddress)],
+    allowlist: &[AllowedRouter],
+    get_factory: F,
+) -> Result<(), RouterCheckError>
+where
+    F: Fn(UniversalChainId, EthAddress) -> Option<EthAddress>,
+{
+    let mut expected_factories = Vec::new();
+    for &(chain_id, router) in routers.iter() {
+        let factory = get_expected_factory(chain_id, &router, allowlist)
+            .ok_or(RouterCheckError::UnknownRouter(chain_id, router))?;
+        expected_factories.push((chain_id, router, factory));
+    }
+    for (chain_id, router, expected_factory) in expected_factories.into_iter() {
+        let factory = get_factory(chain_id, router)
+            .ok_or(RouterCheckError::FactoryCallFailed(chain_id, router))?;
+        if factory != expected_factory {
+            return Err(RouterCheckError::FactoryMismatch(chain_id, router));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod router_allowlist_tests {
+    use hex_literal::hex;
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        dex::dex_registry::{BEAMSWAP, STELLASWAP},
+    };
+
+    use super::*;
+
+    const NEW_ROUTER: EthAddress = EthAddress {
+        0: hex!("1111111111111111111111111111111111111111"),
+    };
+    const NEW_FACTORY: EthAddress = EthAddress {
+        0: hex!("2222222222222222222222222222222222222222"),
+    };
+
+    fn allowlist() -> Vec<AllowedRouter> {
+        vec![AllowedRouter {
+            chain_id: MOONBEAM,
+            router: NEW_ROUTER,
+            factory: NEW_FACTORY,
+        }]
+    }
+
+    #[test]
+    fn test_get_expected_factory() {
+        assert_eq!(
+            get_expected_factory(MOONBEAM, &STELLASWAP.eth_dex_router, &[]),
+            Some(STELLASWAP.eth_dex_factory)
+        );
+        // Registry DEXes are per chain
+        assert_eq!(
+            get_expected_factory(ASTAR, &STELLASWAP.eth_dex_router, &[]),
+            None
+        );
+        assert_eq!(get_expected_factory(MOONBEAM, &NEW_ROUTER, &[]), None);
+        assert_eq!(
+            get_expected_factory(MOONBEAM, &NEW_ROUTER, &allowlist()),
+            Some(NEW_FACTORY)
+        );
+        assert_eq!(get_expected_factory(ASTAR, &NEW_ROUTER, &allowlist()), None);
+    }
+
+    #[test]
+    fn test_get_router_for_factory() {
+        assert_eq!(
+            get_router_for_factory(MOONBEAM, &STELLASWAP.eth_dex_factory, &[]),
+            Some(STELLASWAP.eth_dex_router)
+        );
+        assert_eq!(
+            get_router_for_factory(ASTAR, &STELLASWAP.eth_dex_factory, &[]),
+            None
+        );
+        assert_eq!(
+            get_router_for_factory(MOONBEAM, &NEW_FACTORY, &allowlist()),
+            Some(NEW_ROUTER)
+        );
+        assert_eq!(get_router_for_factory(MOONBEAM, &NEW_FACTORY, &[]), None);
+    }
+
+    #[test]
+    fn test_check_dex_routers() {
+        let routers = vec![
+            (MOONBEAM, STELLASWAP.eth_dex_router),
+            (MOONBEAM, NEW_ROUTER),
+        ];
+        let honest_factory =
+            |chain_id, router| get_expected_factory(chain_id, &router, &allowlist());
+        assert_eq!(
+            check_dex_routers(&routers, &allowlist(), honest_factory),
+            Ok(())
+        );
+        // Unknown routers fail before any factory() call
+        assert_eq!(
+            check_dex_routers(&routers, &[], |_, _| panic!("factory() called")),
+            Err(RouterCheckError::UnknownRouter(MOONBEAM, NEW_ROUTER))
+        );
+        assert_eq!(
+            check_dex_routers(&routers, &allowlist(), |_, router| {
+                if router == NEW_ROUTER {
+                    None
+                } else {
+                    Some(STELLASWAP.eth_dex_factory)
+                }
+            }),
+            Err(RouterCheckError::FactoryCallFailed(MOONBEAM, NEW_ROUTER))
+        );
+        assert_eq!(
+            check_dex_routers(&[(MOONBEAM, BEAMSWAP.eth_dex_router)], &[], |_, _| Some(
+                STELLASWAP.eth_dex_factory
+            )),
+            Err(RouterCheckError::FactoryMismatch(
+                MOONBEAM,
+                BEAMSWAP.eth_dex_router
+            ))
+        );
+    }
+}
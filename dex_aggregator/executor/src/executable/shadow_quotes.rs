@@ -0,0 +1,379 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, EthAddress, MillisSinceEpoch};
+use privadex_common::{utils::general_utils::mul_ratio_u128, uuid::Uuid};
+use privadex_routing::graph_snapshot::GraphSnapshot;
+
+use super::plan_analytics::{get_day, DaysSinceEpoch, PlanAnalytics};
+use crate::quote_engine::{quote, QuoteContext, QuoteRequest};
+
+// refresh_pair_cache keeps the last pair cache of every hour, so closed plans can be quoted
+// again on (roughly) the data that was live when they started
+pub const PAIR_CACHE_ARCHIVE_INTERVAL_MILLIS: MillisSinceEpoch = 60 * 60 * 1000;
+// A plan with no archived pair cache this close to its start is counted but not compared
+pub const MAX_SNAPSHOT_AGE_MILLIS: MillisSinceEpoch = 30 * 60 * 1000;
+// Each shadow quote builds a graph and runs the SOR, so a run only gets through a few plans
+pub const MAX_SHADOW_QUOTES_PER_RUN: usize = 5;
+// Plans closed while the job is not running pile up, and only the latest ones are kept
+pub const MAX_PENDING_SHADOW_QUOTES: usize = 200;
+// A shadow quote that beats the live quote by more than this counts as the SOR underperforming
+pub const UNDERPERFORMANCE_THRESHOLD_BPS: i32 = 50;
+// Per day, the plans whose live quote fell the furthest short of their shadow quote
+pub const MAX_WORST_PLANS: usize = 5;
+
+pub type ArchiveSlot = u32;
+
+pub fn get_archive_slot(millis: MillisSinceEpoch) -> ArchiveSlot {
+    (millis / PAIR_CACHE_ARCHIVE_INTERVAL_MILLIS) as ArchiveSlot
+}
+
+// The archive of the plan's own slot may have been taken after the plan started, so the slot
+// before is a candidate too
+pub fn get_candidate_archive_slots(started_millis: MillisSinceEpoch) -> [ArchiveSlot; 2] {
+    let slot = get_archive_slot(started_millis);
+    [slot, slot.saturating_sub(1)]
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct ArchivedPairCache {
+    pub fetched_millis: MillisSinceEpoch,
+    pub pair_cache: GraphSnapshot,
+}
+
+// Closest to started_millis, and within MAX_SNAPSHOT_AGE_MILLIS of it
+pub fn pick_archive<'a>(
+    archives: impl Iterator<Item = &'a ArchivedPairCache>,
+    started_millis: MillisSinceEpoch,
+) -> Option<&'a ArchivedPairCache> {
+    archives
+        .filter(|archive| {
+            archive.fetched_millis.abs_diff(started_millis) <= MAX_SNAPSHOT_AGE_MILLIS
+        })
+        .min_by_key(|archive| archive.fetched_millis.abs_diff(started_millis))
+}
+
+// What the SOR quotes for the plan's swap on the context's graph. Like the live quote it is net
+// of the estimated gas and bridge fees. None if the graph has no route
+pub fn get_shadow_amount_out(context: &QuoteContext, plan: &PlanAnalytics) -> Option<Amount> {
+    let request = QuoteRequest {
+        src_token_id: plan.amount_in.token.clone(),
+        dest_token_id: plan.protocol_fee.token.clone(),
+        amount_in: plan.amount_in.amount,
+        src_addr: EthAddress::zero(),
+        dest_addr: EthAddress::zero(),
+    };
+    quote(context, &request).ok().map(|quote| quote.amount_out)
+}
+
+// Relative to base, e.g. 25 means other is 0.25% more than base. Saturates at the i32 bounds
+pub fn get_diff_bps(base: Amount, other: Amount) -> i32 {
+    if base == 0 {
+        return if other == 0 { 0 } else { i32::MAX };
+    }
+    let diff = base.abs_diff(other);
+    let diff_bps = if diff / base >= (i32::MAX / 10_000) as Amount {
+        i32::MAX
+    } else {
+        mul_ratio_u128(diff, 10_000, base).min(i32::MAX as Amount) as i32
+    };
+    if other >= base {
+        diff_bps
+    } else {
+        -diff_bps
+    }
+}
+
+/// A closed plan's live quote next to its shadow quote and what it actually delivered
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ShadowQuoteResult {
+    pub exec_plan_uuid: Uuid,
+    pub closed_millis: MillisSinceEpoch,
+    // How far the archived pair cache was from the plan's start. None if there was none close
+    // enough
+    pub snapshot_age_millis: Option<MillisSinceEpoch>,
+    pub quoted_amount_out: Amount,
+    pub shadow_amount_out: Option<Amount>,
+    // Before our fee, like the quote. None unless the plan succeeded
+    pub realized_amount_out: Option<Amount>,
+}
+
+impl ShadowQuoteResult {
+    // None for plans without a quote, which have nothing to compare against
+    pub fn new(
+        plan: &PlanAnalytics,
+        archive_fetched_millis: Option<MillisSinceEpoch>,
+        shadow_amount_out: Option<Amount>,
+    ) -> Option<Self> {
+        let quoted_amount_out = plan.quote.as_ref()?.amount_out;
+        Some(Self {
+            exec_plan_uuid: plan.exec_plan_uuid.clone(),
+            closed_millis: plan.closed_millis,
+            snapshot_age_millis: archive_fetched_millis
+                .map(|fetched_millis| fetched_millis.abs_diff(plan.started_millis)),
+            quoted_amount_out,
+            shadow_amount_out,
+            realized_amount_out: plan
+                .amount_out
+                .as_ref()
+                .map(|amount_out| amount_out.amount + plan.protocol_fee.amount),
+        })
+    }
+
+    // Positive if the shadow quote beat the live one, i.e. the route the plan took was worse
+    // than what the SOR finds on the archived data
+    pub fn shadow_vs_quote_bps(&self) -> Option<i32> {
+        self.shadow_amount_out
+            .map(|shadow_amount_out| get_diff_bps(self.quoted_amount_out, shadow_amount_out))
+    }
+
+    // Negative if the plan delivered less than quoted, e.g. because the quote's data was stale
+    pub fn realized_vs_quote_bps(&self) -> Option<i32> {
+        self.realized_amount_out
+            .map(|realized_amount_out| get_diff_bps(self.quoted_amount_out, realized_amount_out))
+    }
+}
+
+/// Shadow quoting discrepancies of the plans that closed on a day
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ShadowQuoteStats {
+    pub day: DaysSinceEpoch,
+    pub num_plans: u32,
+    // No archived pair cache within MAX_SNAPSHOT_AGE_MILLIS of the plan's start
+    pub num_no_snapshot: u32,
+    // The SOR found no route on the archived pair cache
+    pub num_no_shadow_route: u32,
+    // The rest were compared
+    pub num_compared: u32,
+    pub sum_snapshot_age_millis: MillisSinceEpoch,
+    pub sum_shadow_vs_quote_bps: i64,
+    // Shadow quote beat the live quote by more than UNDERPERFORMANCE_THRESHOLD_BPS
+    pub num_underperformed: u32,
+    pub num_realized: u32,
+    pub sum_realized_vs_quote_bps: i64,
+    // (exec plan, shadow_vs_quote_bps), worst first
+    pub worst_plans: Vec<(Uuid, i32)>,
+}
+
+impl ShadowQuoteStats {
+    pub fn new(day: DaysSinceEpoch) -> Self {
+        Self {
+            day,
+            num_plans: 0,
+            num_no_snapshot: 0,
+            num_no_shadow_route: 0,
+            num_compared: 0,
+            sum_snapshot_age_millis: 0,
+            sum_shadow_vs_quote_bps: 0,
+            num_underperformed: 0,
+            num_realized: 0,
+            sum_realized_vs_quote_bps: 0,
+            worst_plans: Vec::new(),
+        }
+    }
+
+    pub fn add_result(&mut self, result: &ShadowQuoteResult) {
+        self.num_plans += 1;
+        if let Some(realized_vs_quote_bps) = result.realized_vs_quote_bps() {
+            self.num_realized += 1;
+            self.sum_realized_vs_quote_bps += realized_vs_quote_bps as i64;
+        }
+        let snapshot_age_millis = match result.snapshot_age_millis {
+            Some(snapshot_age_millis) => snapshot_age_millis,
+            None => {
+                self.num_no_snapshot += 1;
+                return;
+            }
+        };
+        let shadow_vs_quote_bps = match result.shadow_vs_quote_bps() {
+            Some(shadow_vs_quote_bps) => shadow_vs_quote_bps,
+            None => {
+                self.num_no_shadow_route += 1;
+                return;
+            }
+        };
+        self.num_compared += 1;
+        self.sum_snapshot_age_millis += snapshot_age_millis;
+        self.sum_shadow_vs_quote_bps += shadow_vs_quote_bps as i64;
+        if shadow_vs_quote_bps > UNDERPERFORMANCE_THRESHOLD_BPS {
+            self.num_underperformed += 1;
+            self.worst_plans
+                .push((result.exec_plan_uuid.clone(), shadow_vs_quote_bps));
+            self.worst_plans.sort_by(|a, b| b.1.cmp(&a.1));
+            self.worst_plans.truncate(MAX_WORST_PLANS);
+        }
+    }
+
+    pub fn avg_shadow_vs_quote_bps(&self) -> Option<i32> {
+        (self.num_compared > 0)
+            .then(|| (self.sum_shadow_vs_quote_bps / self.num_compared as i64) as i32)
+    }
+
+    pub fn avg_realized_vs_quote_bps(&self) -> Option<i32> {
+        (self.num_realized > 0)
+            .then(|| (self.sum_realized_vs_quote_bps / self.num_realized as i64) as i32)
+    }
+}
+
+pub fn aggregate_shadow_quote_results(
+    daily_stats: &mut Vec<ShadowQuoteStats>,
+    results: &[ShadowQuoteResult],
+) -> Vec<DaysSinceEpoch> {
+    let mut changed_days = vec![];
+    for result in results.iter() {
+        let day = get_day(result.closed_millis);
+        match daily_stats.iter_mut().find(|stats| stats.day == day) {
+            Some(stats) => stats.add_result(result),
+            None => {
+                let mut stats = ShadowQuoteStats::new(day);
+                stats.add_result(result);
+                daily_stats.push(stats);
+            }
+        }
+        if !changed_days.contains(&day) {
+            changed_days.push(day);
+        }
+    }
+    changed_days
+}
+
+#[cfg(test)]
+mod shadow_quotes_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry::{
+        DOT_NATIVE, GLMR_NATIVE,
+    };
+    use privadex_execution_plan::execution_plan::PlanQuote;
+    use privadex_routing::graph_snapshot::GRAPH_SNAPSHOT_VERSION;
+
+    use super::*;
+    use crate::executable::plan_analytics::TokenAmount;
+
+    const MILLIS_PER_DAY: MillisSinceEpoch = 86_400_000;
+
+    fn plan_analytics(
+        uuid_byte: u8,
+        quoted_amount_out: Option<Amount>,
+        amount_out: Option<Amount>,
+    ) -> PlanAnalytics {
+        PlanAnalytics {
+            exec_plan_uuid: Uuid::new([uuid_byte; 16]),
+            src_chain: GLMR_NATIVE.chain,
+            started_millis: MILLIS_PER_DAY - 60_000,
+            closed_millis: MILLIS_PER_DAY,
+            is_success: amount_out.is_some(),
+            amount_in: TokenAmount {
+                token: GLMR_NATIVE,
+                amount: 1_000,
+            },
+            protocol_fee: TokenAmount {
+                token: DOT_NATIVE,
+                amount: if amount_out.is_some() { 10 } else { 0 },
+            },
+            dex_swaps: vec![],
+            amount_out: amount_out.map(|amount| TokenAmount {
+                token: DOT_NATIVE,
+                amount,
+            }),
+            quote: quoted_amount_out.map(|amount_out| PlanQuote {
+                amount_out,
+                amount_in_usd: 0,
+                amount_out_usd: 0,
+            }),
+        }
+    }
+
+    fn archive(fetched_millis: MillisSinceEpoch) -> ArchivedPairCache {
+        ArchivedPairCache {
+            fetched_millis,
+            pair_cache: GraphSnapshot {
+                version: GRAPH_SNAPSHOT_VERSION,
+                chain_ids: vec![],
+                dex_responses: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_diff_bps() {
+        assert_eq!(get_diff_bps(10_000, 10_025), 25);
+        assert_eq!(get_diff_bps(10_000, 9_900), -100);
+        assert_eq!(get_diff_bps(10_000, 10_000), 0);
+        assert_eq!(get_diff_bps(0, 1), i32::MAX);
+        assert_eq!(get_diff_bps(1, Amount::MAX), i32::MAX);
+    }
+
+    #[test]
+    fn test_pick_archive() {
+        let started_millis = 10 * PAIR_CACHE_ARCHIVE_INTERVAL_MILLIS + 5 * 60 * 1000;
+        assert_eq!(get_candidate_archive_slots(started_millis), [10, 9]);
+        let archives = vec![
+            archive(started_millis - 20 * 60 * 1000),
+            archive(started_millis + 10 * 60 * 1000),
+            archive(started_millis - MAX_SNAPSHOT_AGE_MILLIS - 1),
+        ];
+        assert_eq!(
+            pick_archive(archives.iter(), started_millis).map(|archive| archive.fetched_millis),
+            Some(started_millis + 10 * 60 * 1000)
+        );
+        assert!(pick_archive(archives[2..].iter(), started_millis).is_none());
+    }
+
+    #[test]
+    fn test_aggregate_shadow_quote_results() {
+        let results: Vec<ShadowQuoteResult> = vec![
+            // The live route fell 2% short of the shadow quote, and delivered as quoted
+            ShadowQuoteResult::new(
+                &plan_analytics(1, Some(980), Some(970)),
+                Some(0),
+                Some(1_000),
+            ),
+            // Shadow quote within the threshold, and delivered 1% less than quoted
+            ShadowQuoteResult::new(
+                &plan_analytics(2, Some(1_000), Some(980)),
+                Some(0),
+                Some(1_001),
+            ),
+            ShadowQuoteResult::new(&plan_analytics(3, Some(1_000), None), None, None),
+            ShadowQuoteResult::new(&plan_analytics(4, Some(1_000), None), Some(0), None),
+        ]
+        .into_iter()
+        .map(|result| result.expect("Plan has a quote"))
+        .collect();
+        assert!(ShadowQuoteResult::new(&plan_analytics(5, None, None), Some(0), None).is_none());
+
+        let mut daily_stats = vec![];
+        assert_eq!(
+            aggregate_shadow_quote_results(&mut daily_stats, &results),
+            vec![1]
+        );
+        let stats = &daily_stats[0];
+        assert_eq!(stats.num_plans, 4);
+        assert_eq!(stats.num_no_snapshot, 1);
+        assert_eq!(stats.num_no_shadow_route, 1);
+        assert_eq!(stats.num_compared, 2);
+        assert_eq!(stats.num_underperformed, 1);
+        assert_eq!(stats.worst_plans, vec![(Uuid::new([1u8; 16]), 204)]);
+        assert_eq!(stats.avg_shadow_vs_quote_bps(), Some(107));
+        assert_eq!(stats.num_realized, 2);
+        assert_eq!(stats.avg_realized_vs_quote_bps(), Some(-50));
+    }
+}
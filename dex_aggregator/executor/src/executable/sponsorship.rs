@@ -0,0 +1,228 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Sponsorship campaigns let marketing pay for users' swaps: a sponsored plan's protocol fee is
+// waived, and what the campaign covers (the waived fee plus the escrow's gas for the plan) is
+// drawn down from its USD budget in the coordination store when the plan starts
+use ink::prelude::{string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, UniversalChainId, UniversalTokenId};
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+use privadex_routing::graph::fee_breakdown::FeeBreakdown;
+
+use super::{plan_analytics::get_transfer_token, proof_of_reserves::get_plan_chains};
+
+pub const MAX_SPONSORSHIP_CAMPAIGNS: usize = 10;
+// The ID is part of the campaign's coordination store key
+pub const MAX_CAMPAIGN_ID_LEN: usize = 32;
+
+/// A swap is eligible if every chain its plan touches is in eligible_chains, and its src and
+/// dest tokens are both in eligible_tokens. Empty eligible_tokens allows any token
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SponsorshipCampaign {
+    pub campaign_id: String,
+    // $ x 10^18, over all the campaign's plans
+    pub budget_usd: Amount,
+    pub eligible_chains: Vec<UniversalChainId>,
+    pub eligible_tokens: Vec<UniversalTokenId>,
+}
+
+impl SponsorshipCampaign {
+    pub fn is_valid(&self) -> bool {
+        is_valid_campaign_id(&self.campaign_id)
+            && self.budget_usd > 0
+            && !self.eligible_chains.is_empty()
+    }
+
+    pub fn is_eligible(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        chains: &[UniversalChainId],
+    ) -> bool {
+        let is_token_eligible = |token: &UniversalTokenId| {
+            self.eligible_tokens.is_empty() || self.eligible_tokens.contains(token)
+        };
+        is_token_eligible(src_token)
+            && is_token_eligible(dest_token)
+            && chains
+                .iter()
+                .all(|chain| self.eligible_chains.contains(chain))
+    }
+}
+
+pub fn is_valid_campaign_id(campaign_id: &str) -> bool {
+    !campaign_id.is_empty()
+        && campaign_id.len() <= MAX_CAMPAIGN_ID_LEN
+        && campaign_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// What the campaign pays for a plan quoted with fee_breakdown: our fee and the gas of every hop.
+// Bridge and DEX fees aren't ours to waive
+pub fn get_sponsored_usd(fee_breakdown: &FeeBreakdown) -> Amount {
+    fee_breakdown
+        .hops_per_path
+        .iter()
+        .flatten()
+        .fold(fee_breakdown.protocol_fee_usd, |sponsored_usd, hop_fee| {
+            sponsored_usd + hop_fee.gas_fee_usd
+        })
+}
+
+pub fn waive_protocol_fee(fee_breakdown: &mut FeeBreakdown) {
+    fee_breakdown.total_fee_usd -= fee_breakdown.protocol_fee_usd;
+    fee_breakdown.protocol_fee_in_dest_token = 0;
+    fee_breakdown.protocol_fee_usd = 0;
+}
+
+// Every chain a quote's hops touch, for checking eligibility before there is a plan
+pub fn get_fee_breakdown_chains(fee_breakdown: &FeeBreakdown) -> Vec<UniversalChainId> {
+    let mut chains: Vec<UniversalChainId> = Vec::new();
+    for hop_fee in fee_breakdown.hops_per_path.iter().flatten() {
+        for chain in [hop_fee.src_token.chain, hop_fee.dest_token.chain] {
+            if !chains.contains(&chain) {
+                chains.push(chain);
+            }
+        }
+    }
+    chains
+}
+
+// Marks the plan as sponsored by campaign and waives the fee in its quoted terms. Returns what the
+// campaign pays for it (see get_sponsored_usd), or None if the plan isn't eligible or has no
+// quoted terms to go by
+pub fn set_plan_sponsorship(
+    exec_plan: &mut ExecutionPlan,
+    campaign: &SponsorshipCampaign,
+) -> Option<Amount> {
+    let src_token = get_transfer_token(&exec_plan.prestart_user_to_escrow_transfer.inner)?;
+    let dest_token = get_transfer_token(&exec_plan.postend_escrow_to_user_transfer.inner)?;
+    if !campaign.is_eligible(&src_token, &dest_token, &get_plan_chains(exec_plan)) {
+        return None;
+    }
+    let quoted_terms = exec_plan.quoted_terms.as_mut()?;
+    let sponsored_usd = get_sponsored_usd(&quoted_terms.fee_breakdown);
+    waive_protocol_fee(&mut quoted_terms.fee_breakdown);
+    exec_plan.sponsorship_campaign_id = Some(campaign.campaign_id.clone());
+    Some(sponsored_usd)
+}
+
+#[cfg(test)]
+mod sponsorship_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        token::universal_token_id_registry::{GLMR_NATIVE, USDT_ASTAR, USDT_MOONBEAM},
+    };
+    use privadex_routing::graph::fee_breakdown::HopFee;
+
+    use super::*;
+
+    fn campaign(eligible_tokens: Vec<UniversalTokenId>) -> SponsorshipCampaign {
+        SponsorshipCampaign {
+            campaign_id: String::from("spring-2023"),
+            budget_usd: 1_000,
+            eligible_chains: vec![MOONBEAM],
+            eligible_tokens,
+        }
+    }
+
+    fn hop_fee(
+        src_token: UniversalTokenId,
+        dest_token: UniversalTokenId,
+        gas_fee_usd: Amount,
+    ) -> HopFee {
+        HopFee {
+            src_token,
+            dest_token,
+            lp_fee_in_src_token: 3,
+            lp_fee_usd: 3,
+            gas_fee_native: 1,
+            gas_fee_usd,
+            bridge_fee_native: 0,
+            bridge_fee_usd: 0,
+        }
+    }
+
+    #[test]
+    fn test_campaign_validity() {
+        assert!(campaign(Vec::new()).is_valid());
+        for campaign_id in ["", "spring 2023", "spring/2023", "a".repeat(33).as_str()] {
+            let invalid_campaign = SponsorshipCampaign {
+                campaign_id: String::from(campaign_id),
+                ..campaign(Vec::new())
+            };
+            assert!(!invalid_campaign.is_valid());
+        }
+        assert!(!SponsorshipCampaign {
+            budget_usd: 0,
+            ..campaign(Vec::new())
+        }
+        .is_valid());
+        assert!(!SponsorshipCampaign {
+            eligible_chains: Vec::new(),
+            ..campaign(Vec::new())
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_campaign_eligibility() {
+        let any_token = campaign(Vec::new());
+        assert!(any_token.is_eligible(&GLMR_NATIVE, &USDT_MOONBEAM, &[MOONBEAM]));
+        // Every chain along the way must be eligible, not just the ends
+        assert!(!any_token.is_eligible(&GLMR_NATIVE, &USDT_MOONBEAM, &[MOONBEAM, ASTAR]));
+
+        let usdt_only = campaign(vec![USDT_MOONBEAM]);
+        assert!(usdt_only.is_eligible(&USDT_MOONBEAM, &USDT_MOONBEAM, &[MOONBEAM]));
+        assert!(!usdt_only.is_eligible(&GLMR_NATIVE, &USDT_MOONBEAM, &[MOONBEAM]));
+    }
+
+    #[test]
+    fn test_sponsored_usd_and_waived_fee() {
+        let mut fee_breakdown = FeeBreakdown {
+            hops_per_path: vec![
+                vec![hop_fee(GLMR_NATIVE, USDT_MOONBEAM, 20)],
+                vec![
+                    hop_fee(GLMR_NATIVE, USDT_MOONBEAM, 10),
+                    hop_fee(USDT_MOONBEAM, USDT_ASTAR, 5),
+                ],
+            ],
+            protocol_fee_in_dest_token: 7,
+            protocol_fee_usd: 8,
+            integrator_fee_in_dest_token: 0,
+            integrator_fee_usd: 0,
+            total_fee_usd: 52,
+        };
+        // 8 fee + 20 + 10 + 5 gas. The LP fees are left to the user
+        assert_eq!(get_sponsored_usd(&fee_breakdown), 43);
+        assert_eq!(
+            get_fee_breakdown_chains(&fee_breakdown),
+            vec![MOONBEAM, ASTAR]
+        );
+
+        waive_protocol_fee(&mut fee_breakdown);
+        assert_eq!(fee_breakdown.protocol_fee_in_dest_token, 0);
+        assert_eq!(fee_breakdown.protocol_fee_usd, 0);
+        assert_eq!(fee_breakdown.total_fee_usd, 44);
+    }
+}
@@ -0,0 +1,481 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, MillisSinceEpoch, UniversalChainId};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{
+    ExecutionPath, ExecutionPlan, ExecutionStep, ExecutionStepEnum, PathExecutionMode, RouteHash,
+    StepTiming,
+};
+
+use super::{
+    address_display::StepAddresses,
+    amount_rounding::get_plan_dust,
+    plan_events::get_all_steps,
+    poll_schedule::get_estimated_step_forward_calls,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+// Per (step kind, chain), only the most recent durations are kept
+pub const MAX_STEP_DURATION_SAMPLES: usize = 100;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StepKind {
+    EthSend,
+    ERC20Transfer,
+    EthWrap,
+    EthUnwrap,
+    EthDexSwap,
+    XCMTransfer,
+    WormholeTransfer,
+    EthDepositSweep,
+    SubstrateRemarkDeposit,
+    EthContractCall,
+    EthFeeSkim,
+    EthAggregatorSwap,
+    EthZapIn,
+    EthRemoveLiquidity,
+}
+
+impl From<&ExecutionStepEnum> for StepKind {
+    fn from(step: &ExecutionStepEnum) -> Self {
+        match step {
+            ExecutionStepEnum::EthSend(_) => Self::EthSend,
+            ExecutionStepEnum::ERC20Transfer(_) => Self::ERC20Transfer,
+            ExecutionStepEnum::EthWrap(_) => Self::EthWrap,
+            ExecutionStepEnum::EthUnwrap(_) => Self::EthUnwrap,
+            ExecutionStepEnum::EthDexSwap(_) => Self::EthDexSwap,
+            ExecutionStepEnum::XCMTransfer(_) => Self::XCMTransfer,
+            ExecutionStepEnum::WormholeTransfer(_) => Self::WormholeTransfer,
+            ExecutionStepEnum::EthDepositSweep(_) => Self::EthDepositSweep,
+            ExecutionStepEnum::SubstrateRemarkDeposit(_) => Self::SubstrateRemarkDeposit,
+            ExecutionStepEnum::EthContractCall(_) => Self::EthContractCall,
+            ExecutionStepEnum::EthFeeSkim(_) => Self::EthFeeSkim,
+            ExecutionStepEnum::EthAggregatorSwap(_) => Self::EthAggregatorSwap,
+            ExecutionStepEnum::EthZapIn(_) => Self::EthZapIn,
+            ExecutionStepEnum::EthRemoveLiquidity(_) => Self::EthRemoveLiquidity,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StepStatus {
+    pub step_uuid: Uuid,
+    pub kind: StepKind,
+    pub chain: UniversalChainId,
+    pub status: ExecutableSimpleStatus,
+    pub timing: StepTiming,
+    // As the chain's explorer shows them, e.g. SS58 with the chain's prefix
+    pub src_addr: String,
+    pub dest_addr: String,
+}
+
+impl From<&ExecutionStep> for StepStatus {
+    fn from(step: &ExecutionStep) -> Self {
+        let addresses = StepAddresses::from(step);
+        Self {
+            step_uuid: step.get_uuid().clone(),
+            kind: (&step.inner).into(),
+            chain: step.get_src_chain(),
+            status: step.get_status(),
+            timing: step.timing.clone(),
+            src_addr: addresses.src_addr,
+            dest_addr: addresses.dest_addr,
+        }
+    }
+}
+
+/// Returned by get_swap_status. Lighter than the ExecutionPlan, and has what a UI needs for
+/// a progress bar
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SwapStatus {
+    pub exec_plan_uuid: Uuid,
+    pub status: ExecutableSimpleStatus,
+    pub deadline_millis: MillisSinceEpoch,
+    // In roughly the order they execute
+    pub steps: Vec<StepStatus>,
+    // One per path, in the same order as the plan's
+    pub paths: Vec<PathAmounts>,
+    // How many more execution_plan_step_forward calls the plan should take, to budget for
+    pub estimated_step_forward_calls: u32,
+    // What rounding left in the escrow, in the delivered token (see amount_rounding)
+    pub dust: Amount,
+    // Same as the quote's, if the plan takes the route that was quoted
+    pub route_hash: Option<RouteHash>,
+}
+
+/// Value flow along a path: its amount in, then the realized amount out of each step that
+/// succeeded so far. Consecutive amounts show where slippage and fees were taken
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PathAmounts {
+    pub amount_in: Option<Amount>,
+    pub step_amounts_out: Vec<Amount>,
+}
+
+impl From<&ExecutionPath> for PathAmounts {
+    fn from(path: &ExecutionPath) -> Self {
+        Self {
+            amount_in: path.steps.first().and_then(|step| step.get_amount_in()),
+            step_amounts_out: path.step_amounts_out.clone(),
+        }
+    }
+}
+
+impl From<&ExecutionPlan> for SwapStatus {
+    fn from(exec_plan: &ExecutionPlan) -> Self {
+        Self {
+            exec_plan_uuid: exec_plan.uuid.clone(),
+            status: exec_plan.get_status(),
+            deadline_millis: exec_plan.deadline_millis,
+            steps: get_all_steps(exec_plan)
+                .into_iter()
+                .map(StepStatus::from)
+                .collect(),
+            paths: exec_plan.paths.iter().map(PathAmounts::from).collect(),
+            estimated_step_forward_calls: get_estimated_step_forward_calls(exec_plan),
+            dust: get_plan_dust(exec_plan),
+            route_hash: exec_plan.route_hash,
+        }
+    }
+}
+
+// Submitted to confirmed
+fn get_step_duration_millis(step: &ExecutionStep) -> Option<MillisSinceEpoch> {
+    let submitted = step.timing.submitted.as_ref()?;
+    let confirmed = step.timing.confirmed.as_ref()?;
+    Some(
+        confirmed
+            .timestamp_millis
+            .saturating_sub(submitted.timestamp_millis),
+    )
+}
+
+/// Measured durations of the steps that succeeded, per (step kind, src chain). Fed by closed
+/// plans
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StepDurationSamples {
+    // Oldest first
+    samples: Vec<(StepKind, UniversalChainId, Vec<MillisSinceEpoch>)>,
+}
+
+impl StepDurationSamples {
+    pub fn add_plan(&mut self, exec_plan: &ExecutionPlan) {
+        for step in get_all_steps(exec_plan) {
+            if step.get_status() != ExecutableSimpleStatus::Succeeded {
+                continue;
+            }
+            if let Some(duration_millis) = get_step_duration_millis(step) {
+                self.add_sample((&step.inner).into(), step.get_src_chain(), duration_millis);
+            }
+        }
+    }
+
+    pub fn add_sample(
+        &mut self,
+        kind: StepKind,
+        chain: UniversalChainId,
+        duration_millis: MillisSinceEpoch,
+    ) {
+        let durations = match self
+            .samples
+            .iter()
+            .position(|(sample_kind, sample_chain, _)| {
+                *sample_kind == kind && *sample_chain == chain
+            }) {
+            Some(i) => &mut self.samples[i].2,
+            None => {
+                self.samples.push((kind, chain, Vec::new()));
+                &mut self.samples.last_mut().expect("Just pushed").2
+            }
+        };
+        durations.push(duration_millis);
+        if durations.len() > MAX_STEP_DURATION_SAMPLES {
+            durations.remove(0);
+        }
+    }
+
+    // Nearest-rank percentile. None if there are no samples yet
+    pub fn get_percentile(
+        &self,
+        kind: StepKind,
+        chain: &UniversalChainId,
+        percentile: u8,
+    ) -> Option<MillisSinceEpoch> {
+        let mut durations = self.get_samples(kind, chain).to_vec();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+        let rank = (durations.len() * usize::from(percentile.min(100)) + 99) / 100;
+        Some(durations[rank.max(1) - 1])
+    }
+
+    pub fn get_samples(&self, kind: StepKind, chain: &UniversalChainId) -> &[MillisSinceEpoch] {
+        self.samples
+            .iter()
+            .find(|(sample_kind, sample_chain, _)| *sample_kind == kind && sample_chain == chain)
+            .map_or(&[], |(_, _, durations)| durations.as_slice())
+    }
+}
+
+/// Returned by estimate_completion. Both are the time left from as_of_millis, and 0 once the
+/// plan has closed
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CompletionEstimate {
+    pub as_of_millis: MillisSinceEpoch,
+    pub p50_remaining_millis: MillisSinceEpoch,
+    pub p90_remaining_millis: MillisSinceEpoch,
+}
+
+impl CompletionEstimate {
+    pub fn new(
+        exec_plan: &ExecutionPlan,
+        step_durations: &StepDurationSamples,
+        now_millis: MillisSinceEpoch,
+    ) -> Self {
+        Self {
+            as_of_millis: now_millis,
+            p50_remaining_millis: get_plan_remaining_millis(
+                exec_plan,
+                step_durations,
+                50,
+                now_millis,
+            ),
+            p90_remaining_millis: get_plan_remaining_millis(
+                exec_plan,
+                step_durations,
+                90,
+                now_millis,
+            ),
+        }
+    }
+}
+
+// Adding up each step's percentile overstates the plan's percentile, since steps are rarely all
+// slow at once. That is fine for a progress bar. The refund step is left out since it only runs
+// if the plan misses its deadline
+fn get_plan_remaining_millis(
+    exec_plan: &ExecutionPlan,
+    step_durations: &StepDurationSamples,
+    percentile: u8,
+    now_millis: MillisSinceEpoch,
+) -> MillisSinceEpoch {
+    let status = exec_plan.get_status();
+    if status != ExecutableSimpleStatus::NotStarted
+        && status != ExecutableSimpleStatus::InProgress
+        && status != ExecutableSimpleStatus::PrestartConfirming
+    {
+        return 0;
+    }
+    let get_step_millis = |step: &ExecutionStep| {
+        get_step_remaining_millis(step, step_durations, percentile, now_millis)
+    };
+    let path_millis = exec_plan.paths.iter().map(|path| {
+        path.steps
+            .iter()
+            .map(get_step_millis)
+            .sum::<MillisSinceEpoch>()
+    });
+    let paths_millis = match exec_plan.path_execution_mode {
+        PathExecutionMode::Concurrent => path_millis.max().unwrap_or(0),
+        PathExecutionMode::Sequential => path_millis.sum(),
+    };
+    get_step_millis(&exec_plan.prestart_user_to_escrow_transfer)
+        + exec_plan.deposit_sweep.as_ref().map_or(0, get_step_millis)
+        + exec_plan
+            .remove_liquidity
+            .as_ref()
+            .map_or(0, get_step_millis)
+        + paths_millis
+        + get_step_millis(&exec_plan.postend_escrow_to_user_transfer)
+}
+
+// Steps of a kind we have no samples for (on that chain) fall back to the worst case
+fn get_step_remaining_millis(
+    step: &ExecutionStep,
+    step_durations: &StepDurationSamples,
+    percentile: u8,
+    now_millis: MillisSinceEpoch,
+) -> MillisSinceEpoch {
+    let get_duration_millis = || {
+        step_durations
+            .get_percentile((&step.inner).into(), &step.get_src_chain(), percentile)
+            .unwrap_or_else(|| step.get_worst_case_duration_millis())
+    };
+    match step.get_status() {
+        ExecutableSimpleStatus::NotStarted => get_duration_millis(),
+        ExecutableSimpleStatus::InProgress => {
+            let elapsed_millis = step.timing.submitted.as_ref().map_or(0, |submitted| {
+                now_millis.saturating_sub(submitted.timestamp_millis)
+            });
+            get_duration_millis().saturating_sub(elapsed_millis)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod step_timing_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::EthTxnHash,
+        registry::chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+    };
+    use privadex_execution_plan::{
+        execution_plan::{EthPendingTxnId, EthStepStatus, StepMilestone},
+        test_utilities::execution_plan_factory::{eth_send, path, plan, ESCROW},
+    };
+
+    use super::*;
+
+    fn confirmed_eth_send(
+        submitted_millis: MillisSinceEpoch,
+        confirmed_millis: MillisSinceEpoch,
+    ) -> ExecutionStep {
+        let mut step = eth_send(
+            0,
+            ESCROW,
+            ESCROW,
+            Some(1_000),
+            EthStepStatus::Confirmed(EthTxnHash::zero()),
+        );
+        step.timing = StepTiming {
+            submitted: Some(StepMilestone {
+                block_num: Some(100),
+                timestamp_millis: submitted_millis,
+            }),
+            confirmed: Some(StepMilestone {
+                block_num: Some(102),
+                timestamp_millis: confirmed_millis,
+            }),
+        };
+        step
+    }
+
+    #[test]
+    fn test_swap_status_and_durations() {
+        let exec_plan = ExecutionPlan {
+            paths: vec![ExecutionPath {
+                amount_out: Some(1_000),
+                step_amounts_out: vec![1_000],
+                ..path(vec![confirmed_eth_send(1_000, 13_000)])
+            }],
+            prestart_user_to_escrow_transfer: confirmed_eth_send(0, 0),
+            postend_escrow_to_user_transfer: confirmed_eth_send(13_000, 37_000),
+            last_progress_millis: 37_000,
+            ..plan(Vec::new())
+        };
+        let swap_status = SwapStatus::from(&exec_plan);
+        assert_eq!(swap_status.steps.len(), 4);
+        assert_eq!(swap_status.steps[1].kind, StepKind::EthSend);
+        assert_eq!(
+            swap_status.steps[3].status,
+            ExecutableSimpleStatus::NotStarted
+        );
+        assert_eq!(swap_status.steps[3].timing, StepTiming::default());
+        assert_eq!(
+            swap_status.paths,
+            vec![PathAmounts {
+                amount_in: Some(1_000),
+                step_amounts_out: vec![1_000],
+            }]
+        );
+
+        let mut samples = StepDurationSamples::default();
+        samples.add_plan(&exec_plan);
+        // The refund step never ran, so it has no sample
+        assert_eq!(
+            samples.get_samples(StepKind::EthSend, &MOONBEAM),
+            &[0, 12_000, 24_000]
+        );
+        assert!(samples.get_samples(StepKind::EthSend, &ASTAR).is_empty());
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut samples = StepDurationSamples::default();
+        assert_eq!(samples.get_percentile(StepKind::EthSend, &ASTAR, 50), None);
+        for duration_millis in (1..=10).rev() {
+            samples.add_sample(StepKind::EthSend, ASTAR, duration_millis * 1_000);
+        }
+        assert_eq!(
+            samples.get_percentile(StepKind::EthSend, &ASTAR, 50),
+            Some(5_000)
+        );
+        assert_eq!(
+            samples.get_percentile(StepKind::EthSend, &ASTAR, 90),
+            Some(9_000)
+        );
+        assert_eq!(
+            samples.get_percentile(StepKind::EthSend, &ASTAR, 0),
+            Some(1_000)
+        );
+    }
+
+    #[test]
+    fn test_completion_estimate() {
+        let mut samples = StepDurationSamples::default();
+        for duration_millis in [10_000, 20_000] {
+            samples.add_sample(StepKind::EthSend, MOONBEAM, duration_millis);
+        }
+        let mut in_flight_step = confirmed_eth_send(100_000, 0);
+        if let ExecutionStepEnum::EthSend(step) = &mut in_flight_step.inner {
+            step.status = EthStepStatus::Submitted(EthPendingTxnId {
+                txn_hash: EthTxnHash::zero(),
+                end_block_num: 200,
+            });
+        }
+        in_flight_step.timing.confirmed = None;
+        let mut postend_step = in_flight_step.clone();
+        if let ExecutionStepEnum::EthSend(step) = &mut postend_step.inner {
+            step.status = EthStepStatus::NotStarted;
+        }
+        postend_step.timing = StepTiming::default();
+        let exec_plan = ExecutionPlan {
+            prestart_user_to_escrow_transfer: confirmed_eth_send(0, 0),
+            postend_escrow_to_user_transfer: postend_step.clone(),
+            refund_escrow_to_user_transfer: postend_step,
+            last_progress_millis: 100_000,
+            ..plan(vec![path(vec![in_flight_step])])
+        };
+        // 4s into the path step, then the postend step
+        let estimate = CompletionEstimate::new(&exec_plan, &samples, 104_000);
+        assert_eq!(estimate.p50_remaining_millis, 6_000 + 10_000);
+        assert_eq!(estimate.p90_remaining_millis, 16_000 + 20_000);
+    }
+
+    #[test]
+    fn test_only_recent_samples_are_kept() {
+        let mut samples = StepDurationSamples::default();
+        for duration_millis in 0..(MAX_STEP_DURATION_SAMPLES as MillisSinceEpoch + 1) {
+            samples.add_sample(StepKind::XCMTransfer, ASTAR, duration_millis);
+        }
+        let durations = samples.get_samples(StepKind::XCMTransfer, &ASTAR);
+        assert_eq!(durations.len(), MAX_STEP_DURATION_SAMPLES);
+        assert_eq!(durations[0], 1);
+    }
+}
@@ -0,0 +1,578 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{Amount, ChainTokenId, ERC20Token, UniversalAddress, UniversalTokenId},
+    get_chain_info_from_chain_id,
+};
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::{
+    CommonExecutionMeta, CrossChainStepStatus, ERC20TransferStep, EthDepositSweepStep, EthSendStep,
+    EthStepStatus, ExecutionPlan, ExecutionStep, ExecutionStepEnum,
+};
+
+use crate::key_container::KeyContainer;
+
+use super::{
+    execute_step_meta::ExecuteStepMeta,
+    traits::{Executable, ExecutableResult, ExecutableSimpleStatus},
+};
+
+/// Residual balances left behind by ExecutionPlans that closed without succeeding, e.g. the
+/// intermediate token of a path whose swap failed. Recorded when the plan closes and worked
+/// off by the admin-triggered sweep_stranded_funds
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StrandedFundsLedger {
+    balances: Vec<StrandedBalance>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StrandedBalance {
+    pub exec_plan_uuid: Uuid,
+    pub token: UniversalTokenId,
+    pub amount: Amount,
+    pub holder: StrandedFundsHolder,
+    // The user's address on token.chain, if the plan has one (i.e. the src or dest chain).
+    // Otherwise the funds are simply kept in the escrow
+    pub recipient: Option<UniversalAddress>,
+    pub num_attempts: u32,
+    pub recovery: RecoveryStatus,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StrandedFundsHolder {
+    Escrow(UniversalAddress),
+    // The plan's deposit sweep never went through. We retry it before refunding
+    DepositForwarder(EthDepositSweepStep),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RecoveryStatus {
+    Pending,
+    InProgress(ExecutionStep),
+    Refunded(ExecutionStep),
+    // Stays in the main escrow (no user address on that chain)
+    Consolidated,
+}
+
+impl StrandedFundsLedger {
+    pub fn record_closed_exec_plan(&mut self, exec_plan: &ExecutionPlan) {
+        self.balances.extend(get_stranded_balances(exec_plan));
+    }
+
+    pub fn get_unrecovered_balances(&self) -> Vec<StrandedBalance> {
+        self.balances
+            .iter()
+            .filter(|balance| {
+                balance.recovery == RecoveryStatus::Pending
+                    || matches!(balance.recovery, RecoveryStatus::InProgress(_))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Starts or advances the recovery of every unrecovered balance. Returns the balances
+    /// whose recovery status changed i.e. what was moved (or is now being moved)
+    pub fn sweep_step_forward(
+        &mut self,
+        execute_step_meta: &ExecuteStepMeta,
+        keys: &KeyContainer,
+    ) -> ExecutableResult<Vec<StrandedBalance>> {
+        let mut changed_balances = Vec::new();
+        for balance in self.balances.iter_mut() {
+            if balance_step_forward(balance, execute_step_meta, keys)? {
+                changed_balances.push(balance.clone());
+            }
+        }
+        // Fully recovered balances are only kept around for the report
+        self.balances.retain(|balance| {
+            balance.recovery == RecoveryStatus::Pending
+                || matches!(balance.recovery, RecoveryStatus::InProgress(_))
+        });
+        Ok(changed_balances)
+    }
+}
+
+fn balance_step_forward(
+    balance: &mut StrandedBalance,
+    execute_step_meta: &ExecuteStepMeta,
+    keys: &KeyContainer,
+) -> ExecutableResult<bool> {
+    match &mut balance.recovery {
+        RecoveryStatus::Pending => {
+            if let Some(recovery_step) = create_recovery_step(balance) {
+                balance.num_attempts += 1;
+                balance.recovery = RecoveryStatus::InProgress(recovery_step);
+                let _ = balance_step_forward(balance, execute_step_meta, keys)?;
+            } else {
+                balance.recovery = RecoveryStatus::Consolidated;
+            }
+            Ok(true)
+        }
+        RecoveryStatus::InProgress(recovery_step) => {
+            let step_res = recovery_step.execute_step_forward(execute_step_meta, keys)?;
+            match recovery_step.get_status() {
+                ExecutableSimpleStatus::Succeeded => {
+                    if let ExecutionStepEnum::EthDepositSweep(sweep_step) = &recovery_step.inner {
+                        // Now in the escrow, so the next sweep refunds (or keeps) it
+                        balance.holder =
+                            StrandedFundsHolder::Escrow(sweep_step.common.dest_addr.clone());
+                        balance.recovery = RecoveryStatus::Pending;
+                    } else {
+                        balance.recovery = RecoveryStatus::Refunded(recovery_step.clone());
+                    }
+                    Ok(true)
+                }
+                // Retried with a fresh step on the next sweep
+                ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
+                    balance.recovery = RecoveryStatus::Pending;
+                    Ok(true)
+                }
+                _ => Ok(step_res.did_status_change),
+            }
+        }
+        RecoveryStatus::Refunded(_) | RecoveryStatus::Consolidated => Ok(false),
+    }
+}
+
+// None if the funds should just stay where they are
+fn create_recovery_step(balance: &StrandedBalance) -> Option<ExecutionStep> {
+    let uuid = Uuid::new(sp_core_hashing::blake2_128(
+        &(
+            &balance.exec_plan_uuid,
+            &balance.token,
+            balance.num_attempts,
+        )
+            .encode(),
+    ));
+    let chain_info = get_chain_info_from_chain_id(&balance.token.chain)?;
+    match &balance.holder {
+        StrandedFundsHolder::DepositForwarder(sweep_step) => Some(ExecutionStep::new(
+            ExecutionStepEnum::EthDepositSweep(EthDepositSweepStep {
+                uuid,
+                status: EthStepStatus::NotStarted,
+                ..sweep_step.clone()
+            }),
+        )),
+        StrandedFundsHolder::Escrow(escrow_addr) => {
+            // We can only send from the escrow's EVM account
+            if !matches!(escrow_addr, UniversalAddress::Ethereum(_)) {
+                return None;
+            }
+            let common = CommonExecutionMeta {
+                src_addr: escrow_addr.clone(),
+                dest_addr: balance.recipient.clone()?,
+                gas_fee_native: chain_info.avg_gas_fee_in_native_token,
+                // We don't have a price feed handy, and this is not charged to anyone
+                gas_fee_usd: 0,
+            };
+            let amount = Some(balance.amount);
+            let status = EthStepStatus::NotStarted;
+            if balance.token.id == ChainTokenId::Native {
+                Some(ExecutionStep::new(ExecutionStepEnum::EthSend(
+                    EthSendStep {
+                        uuid,
+                        chain: balance.token.chain,
+                        amount,
+                        common,
+                        status,
+                    },
+                )))
+            } else {
+                Some(ExecutionStep::new(ExecutionStepEnum::ERC20Transfer(
+                    ERC20TransferStep {
+                        uuid,
+                        token: balance.token.clone(),
+                        amount,
+                        common,
+                        status,
+                    },
+                )))
+            }
+        }
+    }
+}
+
+/// Attributes the funds of a closed (non-succeeded) ExecutionPlan to where they are stuck.
+/// XCM transfers that failed on the remote chain (the assets are trapped there) are left for
+/// manual recovery
+pub fn get_stranded_balances(exec_plan: &ExecutionPlan) -> Vec<StrandedBalance> {
+    let mut balances = Vec::new();
+    let src_user_addr = get_transfer_common(&exec_plan.refund_escrow_to_user_transfer)
+        .map(|common| common.dest_addr.clone());
+    let dest_user_addr = get_transfer_common(&exec_plan.postend_escrow_to_user_transfer)
+        .map(|common| common.dest_addr.clone());
+    let src_chain = exec_plan.refund_escrow_to_user_transfer.get_src_chain();
+    let dest_chain = exec_plan.postend_escrow_to_user_transfer.get_src_chain();
+    let mut push_balance =
+        |token: UniversalTokenId, amount: Amount, holder: StrandedFundsHolder| {
+            if amount == 0 {
+                return;
+            }
+            let recipient = if token.chain == src_chain {
+                src_user_addr.clone()
+            } else if token.chain == dest_chain {
+                dest_user_addr.clone()
+            } else {
+                None
+            };
+            balances.push(StrandedBalance {
+                exec_plan_uuid: exec_plan.uuid.clone(),
+                token,
+                amount,
+                holder,
+                recipient,
+                num_attempts: 0,
+                recovery: RecoveryStatus::Pending,
+            });
+        };
+
+    if let Some(ExecutionStep {
+        inner: ExecutionStepEnum::EthDepositSweep(sweep_step),
+        ..
+    }) = &exec_plan.deposit_sweep
+    {
+        let sweep_status: ExecutableSimpleStatus = (&sweep_step.status).into();
+        if sweep_status == ExecutableSimpleStatus::Failed
+            || sweep_status == ExecutableSimpleStatus::Dropped
+        {
+            // Nothing else ran, so this is everything the user sent
+            push_balance(
+                sweep_step.token.clone(),
+                sweep_step.amount.unwrap_or(0),
+                StrandedFundsHolder::DepositForwarder(sweep_step.clone()),
+            );
+            return balances;
+        }
+    }
+    if let Some(remove_liquidity) = &exec_plan.remove_liquidity {
+        if is_step_closed_unsuccessfully(remove_liquidity) {
+            // The LP tokens never left the escrow, and no path ran
+            if let Some((token, holder)) = get_src_token_and_holder(remove_liquidity) {
+                push_balance(token, remove_liquidity.get_amount_in().unwrap_or(0), holder);
+            }
+            return balances;
+        }
+    }
+
+    let is_refunding = exec_plan
+        .refund_escrow_to_user_transfer
+        .get_amount_in()
+        .is_some();
+    if is_step_closed_unsuccessfully(&exec_plan.refund_escrow_to_user_transfer) {
+        if let Some((token, holder)) =
+            get_src_token_and_holder(&exec_plan.refund_escrow_to_user_transfer)
+        {
+            push_balance(
+                token,
+                exec_plan
+                    .refund_escrow_to_user_transfer
+                    .get_amount_in()
+                    .unwrap_or(0),
+                holder,
+            );
+        }
+    }
+
+    let mut succeeded_amount_out = 0;
+    for path in exec_plan.paths.iter() {
+        if path.get_status() == ExecutableSimpleStatus::Succeeded {
+            succeeded_amount_out += path.amount_out.unwrap_or(0);
+            continue;
+        }
+        let first_unfinished_step = path
+            .steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| step.get_status() != ExecutableSimpleStatus::Succeeded);
+        if let Some((i, step)) = first_unfinished_step {
+            let is_stuck = match step.get_status() {
+                // The refund step covers paths that never started, unless they start from an
+                // LP source's redeemed tokens
+                ExecutableSimpleStatus::NotStarted => {
+                    i > 0 || !is_refunding || exec_plan.remove_liquidity.is_some()
+                }
+                ExecutableSimpleStatus::Failed | ExecutableSimpleStatus::Dropped => {
+                    !is_xcm_remote_failed(step)
+                }
+                _ => false,
+            };
+            if is_stuck {
+                if let Some((token, holder)) = get_src_token_and_holder(step) {
+                    push_balance(token, step.get_amount_in().unwrap_or(0), holder);
+                }
+            }
+        }
+    }
+    if exec_plan.postend_escrow_to_user_transfer.get_status() != ExecutableSimpleStatus::Succeeded {
+        if let Some((token, holder)) =
+            get_src_token_and_holder(&exec_plan.postend_escrow_to_user_transfer)
+        {
+            push_balance(token, succeeded_amount_out, holder);
+        }
+    }
+    balances
+}
+
+fn is_step_closed_unsuccessfully(step: &ExecutionStep) -> bool {
+    step.get_status() == ExecutableSimpleStatus::Failed
+        || step.get_status() == ExecutableSimpleStatus::Dropped
+}
+
+fn is_xcm_remote_failed(step: &ExecutionStep) -> bool {
+    matches!(
+        &step.inner,
+        ExecutionStepEnum::XCMTransfer(xcm_step)
+            if matches!(xcm_step.status, CrossChainStepStatus::RemoteFailed(_, _, _))
+    )
+}
+
+fn get_transfer_common(step: &ExecutionStep) -> Option<&CommonExecutionMeta> {
+    match &step.inner {
+        ExecutionStepEnum::EthSend(step) => Some(&step.common),
+        ExecutionStepEnum::ERC20Transfer(step) => Some(&step.common),
+        // A zap-in's dest is the user, who gets whatever it stranded as-is
+        ExecutionStepEnum::EthZapIn(step) => Some(&step.common),
+        // A swap-and-call's target is a contract, so its stranded funds are left for manual
+        // recovery rather than sent there
+        _ => None,
+    }
+}
+
+// The token a step consumes and the account that holds it before the step runs
+pub fn get_src_token_and_holder(
+    step: &ExecutionStep,
+) -> Option<(UniversalTokenId, StrandedFundsHolder)> {
+    let native = |chain| UniversalTokenId {
+        chain,
+        id: ChainTokenId::Native,
+    };
+    let (token, common) = match &step.inner {
+        ExecutionStepEnum::EthSend(step) => (native(step.chain), &step.common),
+        ExecutionStepEnum::ERC20Transfer(step) => (step.token.clone(), &step.common),
+        ExecutionStepEnum::EthWrap(step) => (native(step.chain), &step.common),
+        ExecutionStepEnum::EthUnwrap(step) => {
+            let weth_addr = get_chain_info_from_chain_id(&step.chain)?.weth_addr?;
+            (
+                UniversalTokenId {
+                    chain: step.chain,
+                    id: ChainTokenId::ERC20(ERC20Token { addr: weth_addr }),
+                },
+                &step.common,
+            )
+        }
+        ExecutionStepEnum::EthDexSwap(step) => (step.token_path.first()?.clone(), &step.common),
+        ExecutionStepEnum::XCMTransfer(step) => (step.src_token.clone(), &step.common),
+        ExecutionStepEnum::WormholeTransfer(step) => (step.src_token.clone(), &step.common),
+        ExecutionStepEnum::EthContractCall(step) => (step.token.clone(), &step.common),
+        ExecutionStepEnum::EthFeeSkim(step) => (step.token.clone(), &step.common),
+        ExecutionStepEnum::EthAggregatorSwap(step) => (step.src_token.clone(), &step.common),
+        ExecutionStepEnum::EthZapIn(step) => (step.token.clone(), &step.common),
+        ExecutionStepEnum::EthRemoveLiquidity(step) => (step.lp_token.clone(), &step.common),
+        ExecutionStepEnum::EthDepositSweep(step) => {
+            return Some((
+                step.token.clone(),
+                StrandedFundsHolder::DepositForwarder(step.clone()),
+            ))
+        }
+        // The funds are still the user's until the deposit lands
+        ExecutionStepEnum::SubstrateRemarkDeposit(_) => return None,
+    };
+    Some((token, StrandedFundsHolder::Escrow(common.src_addr.clone())))
+}
+
+#[cfg(test)]
+mod stranded_funds_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::{EthAddress, EthTxnHash},
+        registry::{
+            chain::{chain_info_registry, universal_chain_id_registry::MOONBEAM},
+            token::universal_token_id_registry::GLMR_NATIVE,
+        },
+    };
+    use privadex_execution_plan::{
+        execution_plan::{EthRemoveLiquidityStep, ExecutionPath},
+        test_utilities::execution_plan_factory::{
+            common, eth_send, eth_unwrap, eth_wrap, path, plan, ESCROW, USER,
+        },
+    };
+
+    use super::*;
+
+    fn refund(amount: Option<Amount>, status: EthStepStatus) -> ExecutionStep {
+        eth_send(0xa3, ESCROW, USER, amount, status)
+    }
+
+    fn wrap_unwrap_path(
+        wrap_status: EthStepStatus,
+        unwrap_amount: Option<Amount>,
+        unwrap_status: EthStepStatus,
+    ) -> ExecutionPath {
+        path(vec![
+            eth_wrap(0, Some(1_000), wrap_status),
+            eth_unwrap(0, unwrap_amount, unwrap_status),
+        ])
+    }
+
+    fn closed_plan(paths: Vec<ExecutionPath>) -> ExecutionPlan {
+        ExecutionPlan {
+            prestart_user_to_escrow_transfer: eth_send(
+                0xa1,
+                USER,
+                ESCROW,
+                Some(2_000),
+                EthStepStatus::Confirmed(EthTxnHash::zero()),
+            ),
+            ..plan(paths)
+        }
+    }
+
+    #[test]
+    fn test_failed_and_unstarted_paths_are_stranded() {
+        let weth = UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: chain_info_registry::MOONBEAM_INFO.weth_addr.unwrap(),
+            }),
+        };
+        let exec_plan = closed_plan(vec![
+            wrap_unwrap_path(
+                EthStepStatus::Confirmed(EthTxnHash::zero()),
+                Some(1_000),
+                EthStepStatus::Failed(EthTxnHash::zero()),
+            ),
+            wrap_unwrap_path(EthStepStatus::NotStarted, None, EthStepStatus::NotStarted),
+        ]);
+        let balances = get_stranded_balances(&exec_plan);
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].token, weth);
+        assert_eq!(balances[1].token, GLMR_NATIVE);
+        for balance in balances.iter() {
+            assert_eq!(balance.amount, 1_000);
+            assert_eq!(balance.holder, StrandedFundsHolder::Escrow(ESCROW));
+            assert_eq!(balance.recipient, Some(USER));
+            assert_eq!(balance.recovery, RecoveryStatus::Pending);
+        }
+    }
+
+    #[test]
+    fn test_refunded_paths_are_not_stranded() {
+        let mut exec_plan = closed_plan(vec![wrap_unwrap_path(
+            EthStepStatus::NotStarted,
+            None,
+            EthStepStatus::NotStarted,
+        )]);
+        exec_plan.refund_escrow_to_user_transfer =
+            refund(Some(1_000), EthStepStatus::Confirmed(EthTxnHash::zero()));
+        assert!(get_stranded_balances(&exec_plan).is_empty());
+
+        // Unless the refund itself failed
+        exec_plan.refund_escrow_to_user_transfer =
+            refund(Some(1_000), EthStepStatus::Failed(EthTxnHash::zero()));
+        let balances = get_stranded_balances(&exec_plan);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].token, GLMR_NATIVE);
+        assert_eq!(balances[0].amount, 1_000);
+    }
+
+    #[test]
+    fn test_failed_deposit_sweep_is_stranded_in_forwarder() {
+        let mut exec_plan = closed_plan(vec![wrap_unwrap_path(
+            EthStepStatus::NotStarted,
+            None,
+            EthStepStatus::NotStarted,
+        )]);
+        let sweep_step = EthDepositSweepStep {
+            uuid: Uuid::new([2u8; 16]),
+            token: GLMR_NATIVE,
+            factory_addr: EthAddress::zero(),
+            salt: [3u8; 32],
+            amount: Some(2_000),
+            common: common(USER, ESCROW),
+            status: EthStepStatus::Failed(EthTxnHash::zero()),
+        };
+        exec_plan.deposit_sweep = Some(ExecutionStep::new(ExecutionStepEnum::EthDepositSweep(
+            sweep_step.clone(),
+        )));
+        let balances = get_stranded_balances(&exec_plan);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].amount, 2_000);
+        assert_eq!(
+            balances[0].holder,
+            StrandedFundsHolder::DepositForwarder(sweep_step)
+        );
+    }
+
+    #[test]
+    fn test_lp_source_paths_are_not_refunded() {
+        let lp_token = UniversalTokenId {
+            chain: MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress { 0: [0xcc; 20] },
+            }),
+        };
+        let mut remove_liquidity_step = EthRemoveLiquidityStep {
+            uuid: Uuid::new([2u8; 16]),
+            dex_router_addr: EthAddress { 0: [0xaa; 20] },
+            lp_token: lp_token.clone(),
+            token_a: GLMR_NATIVE,
+            token_b: GLMR_NATIVE,
+            num_token_a_paths: 1,
+            amount: Some(500),
+            common: common(ESCROW, ESCROW),
+            approval_uuid: Uuid::new([3u8; 16]),
+            approval_txn_hash: Some(EthTxnHash::zero()),
+            token_a_amount_out: Some(1_000),
+            token_b_amount_out: Some(1_000),
+            status: EthStepStatus::Confirmed(EthTxnHash::zero()),
+        };
+        let mut exec_plan = closed_plan(vec![wrap_unwrap_path(
+            EthStepStatus::NotStarted,
+            None,
+            EthStepStatus::NotStarted,
+        )]);
+        exec_plan.remove_liquidity = Some(ExecutionStep::new(
+            ExecutionStepEnum::EthRemoveLiquidity(remove_liquidity_step.clone()),
+        ));
+        // The refund is in the LP token, so it sends nothing back for the redeemed tokens
+        exec_plan.refund_escrow_to_user_transfer = refund(Some(0), EthStepStatus::NotStarted);
+        let balances = get_stranded_balances(&exec_plan);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].token, GLMR_NATIVE);
+        assert_eq!(balances[0].amount, 1_000);
+
+        // The LP tokens themselves are stranded if they were never redeemed
+        remove_liquidity_step.status = EthStepStatus::Failed(EthTxnHash::zero());
+        exec_plan.remove_liquidity = Some(ExecutionStep::new(
+            ExecutionStepEnum::EthRemoveLiquidity(remove_liquidity_step),
+        ));
+        let balances = get_stranded_balances(&exec_plan);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].token, lp_token);
+        assert_eq!(balances[0].amount, 500);
+        assert_eq!(balances[0].holder, StrandedFundsHolder::Escrow(ESCROW));
+    }
+}
@@ -0,0 +1,195 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::uuid::Uuid;
+use privadex_execution_plan::execution_plan::ExecutionPlan;
+
+use super::{
+    plan_events::get_all_steps,
+    traits::{Executable, ExecutableSimpleStatus},
+};
+
+/// An in-progress ExecutionPlan that has gone longer than its SLA without any step changing
+/// status, e.g. because the workers keep failing on it or a bridge never delivered
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StuckPlan {
+    pub exec_plan_uuid: Uuid,
+    // Steps that were in flight when the plan stalled. Empty if it is stuck between steps
+    pub in_progress_step_uuids: Vec<Uuid>,
+    pub stalled_millis: MillisSinceEpoch,
+    pub sla_millis: MillisSinceEpoch,
+    // Whether the scan moved the plan's deadline up so that it refunds on the next step forward
+    pub escalated: bool,
+}
+
+impl StuckPlan {
+    pub fn to_json(&self) -> String {
+        let step_uuids: Vec<String> = self
+            .in_progress_step_uuids
+            .iter()
+            .map(|uuid| format!("\"{}\"", uuid.to_hex_string()))
+            .collect();
+        format!(
+            "{{\"exec_plan_uuid\":\"{}\",\"in_progress_step_uuids\":[{}],\"stalled_millis\":{},\
+            \"sla_millis\":{},\"escalated\":{}}}",
+            self.exec_plan_uuid.to_hex_string(),
+            step_uuids.join(","),
+            self.stalled_millis,
+            self.sla_millis,
+            self.escalated
+        )
+    }
+}
+
+// Body of the stuck plan webhook
+pub fn to_webhook_body(stuck_plans: &[StuckPlan]) -> String {
+    let plans: Vec<String> = stuck_plans.iter().map(|plan| plan.to_json()).collect();
+    format!("{{\"stuck_plans\":[{}]}}", plans.join(","))
+}
+
+// The SLA is the longest worst case duration among the steps in flight. With none in flight
+// the plan is only waiting on a worker to start its next step, which is bounded like an Eth
+// step (and the prestart step always is one)
+pub fn get_sla_millis(exec_plan: &ExecutionPlan) -> MillisSinceEpoch {
+    get_all_steps(exec_plan)
+        .into_iter()
+        .filter(|step| step.get_status() == ExecutableSimpleStatus::InProgress)
+        .map(|step| step.get_worst_case_duration_millis())
+        .max()
+        .unwrap_or_else(|| {
+            exec_plan
+                .prestart_user_to_escrow_transfer
+                .get_worst_case_duration_millis()
+        })
+}
+
+pub fn find_stuck_plan(
+    exec_plan: &ExecutionPlan,
+    cur_timestamp: MillisSinceEpoch,
+) -> Option<StuckPlan> {
+    let status = exec_plan.get_status();
+    if status != ExecutableSimpleStatus::InProgress
+        && status != ExecutableSimpleStatus::PrestartConfirming
+    {
+        return None;
+    }
+    // Plans waiting on a review are parked on purpose
+    if exec_plan.review_status.is_pending() {
+        return None;
+    }
+    let stalled_millis = cur_timestamp.saturating_sub(exec_plan.last_progress_millis);
+    let sla_millis = get_sla_millis(exec_plan);
+    if stalled_millis <= sla_millis {
+        return None;
+    }
+    Some(StuckPlan {
+        exec_plan_uuid: exec_plan.uuid.clone(),
+        in_progress_step_uuids: get_all_steps(exec_plan)
+            .into_iter()
+            .filter(|step| step.get_status() == ExecutableSimpleStatus::InProgress)
+            .map(|step| step.get_uuid().clone())
+            .collect(),
+        stalled_millis,
+        sla_millis,
+        escalated: false,
+    })
+}
+
+// Escalating moves the deadline up to now, so the next step forward stops starting paths and
+// refunds the ones that never started. The started ones run to completion, and whatever a failed
+// one leaves midway ends up in the stranded funds ledger once the plan closes. Returns false if the plan is already past its deadline
+pub fn escalate_stuck_plan(exec_plan: &mut ExecutionPlan, cur_timestamp: MillisSinceEpoch) -> bool {
+    if exec_plan.deadline_millis <= cur_timestamp {
+        return false;
+    }
+    exec_plan.deadline_millis = cur_timestamp;
+    true
+}
+
+#[cfg(test)]
+mod stuck_plans_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::common::EthTxnHash;
+    use privadex_execution_plan::{
+        execution_plan::EthStepStatus,
+        test_utilities::execution_plan_factory::{eth_wrap, path, plan, submitted},
+    };
+
+    use super::*;
+
+    fn plan_with_wrap_step(wrap_status: EthStepStatus) -> ExecutionPlan {
+        ExecutionPlan {
+            last_progress_millis: 1_000_000,
+            ..plan(vec![path(vec![eth_wrap(2, Some(1_000), wrap_status)])])
+        }
+    }
+
+    #[test]
+    fn test_find_stuck_plan() {
+        let exec_plan = plan_with_wrap_step(submitted(EthTxnHash::zero()));
+        let sla_millis = get_sla_millis(&exec_plan);
+        assert_eq!(find_stuck_plan(&exec_plan, 1_000_000 + sla_millis), None);
+
+        let stuck_plan = find_stuck_plan(&exec_plan, 1_000_001 + sla_millis).expect("Stuck");
+        assert_eq!(stuck_plan.exec_plan_uuid, Uuid::new([1u8; 16]));
+        assert_eq!(
+            stuck_plan.in_progress_step_uuids,
+            vec![Uuid::new([2u8; 16])]
+        );
+        assert_eq!(stuck_plan.stalled_millis, sla_millis + 1);
+        assert!(!stuck_plan.escalated);
+    }
+
+    #[test]
+    fn test_closed_plan_is_never_stuck() {
+        let exec_plan = plan_with_wrap_step(EthStepStatus::Failed(EthTxnHash::zero()));
+        assert_eq!(find_stuck_plan(&exec_plan, MillisSinceEpoch::MAX), None);
+    }
+
+    #[test]
+    fn test_escalate_stuck_plan() {
+        let mut exec_plan = plan_with_wrap_step(submitted(EthTxnHash::zero()));
+        assert!(escalate_stuck_plan(&mut exec_plan, 5_000_000));
+        assert_eq!(exec_plan.deadline_millis, 5_000_000);
+        // Already past its deadline, so there is nothing left to escalate
+        assert!(!escalate_stuck_plan(&mut exec_plan, 6_000_000));
+        assert_eq!(exec_plan.deadline_millis, 5_000_000);
+    }
+
+    #[test]
+    fn test_webhook_body() {
+        let stuck_plan = StuckPlan {
+            exec_plan_uuid: Uuid::new([0xab; 16]),
+            in_progress_step_uuids: vec![Uuid::new([0xcd; 16])],
+            stalled_millis: 200_000,
+            sla_millis: 180_000,
+            escalated: true,
+        };
+        assert_eq!(
+            to_webhook_body(&[stuck_plan]),
+            "{\"stuck_plans\":[{\"exec_plan_uuid\":\"0xabababababababababababababababab\",\
+            \"in_progress_step_uuids\":[\"0xcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd\"],\
+            \"stalled_millis\":200000,\"sla_millis\":180000,\"escalated\":true}]}"
+        );
+    }
+}
@@ -19,12 +19,14 @@
 use scale::{Decode, Encode};
 
 use privadex_chain_metadata::common::Amount;
-use privadex_execution_plan::execution_plan::{CrossChainStepStatus, EthStepStatus};
+use privadex_execution_plan::execution_plan::{
+    CrossChainStepStatus, EthStepStatus, MultiPhaseBridgeStepStatus, RemarkDepositStatus,
+};
 
 use super::execute_step_meta::ExecuteStepMeta;
-use crate::key_container::KeyContainer;
+use crate::{eth_utils::common::EthError, key_container::KeyContainer};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Decode, Encode, Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum ExecutableSimpleStatus {
     NotStarted,
@@ -32,6 +34,11 @@ pub enum ExecutableSimpleStatus {
     Failed,
     Dropped,
     Succeeded,
+    // Only for ExecutionPlan: it missed its deadline and the leftover funds were returned
+    Refunded,
+    // Only for ExecutionPlan: the prestart deposit landed but is not yet deep enough on the src
+    // chain for the escrow to act on it
+    PrestartConfirming,
 }
 
 #[derive(Decode, Encode, Debug, PartialEq, Eq, Clone)]
@@ -44,25 +51,51 @@ pub enum ExecutableError {
     FailedToCreateTxn,
     FailedToDeserializeFromS3,
     FailedToFindChainInfo,
+    FailedToGetAggregatorSwap,
     FailedToGetNonce,
     FailedToLoadAstarPrecompileContract,
+    FailedToLoadDepositForwarderFactoryContract,
     FailedToLoadWethContract,
+    FailedToLoadWormholeContract,
     FailedToPullFromS3,
     FailedToSaveToS3,
     FailedToUpdateDynamoDb,
+    InvalidAggregatorSwap,
+    InvalidContractCall,
+    InvalidRemoveLiquidity,
+    InvalidZapIn,
     PrestartStepNotStarted,
     RpcRequestFailed,
+    // The object is not in S3. Unlike FailedToPullFromS3, it is safe to treat as empty
+    S3ObjectNotFound,
     SecretNotFound,
     Ss58AddressFormatNotFound,
     SubstrateIndexerLookupFailed,
+    // The step's txn is waiting on other threshold parties to sign it, so the step stays
+    // NotStarted (see ThresholdSigner)
+    ThresholdSignaturePending,
     UnexpectedNonEthAddress,
     UnexpectedNullAmount,
     UnexpectedNullEvmChainId,
     UnexpectedStepStatus,
     UnsupportedChain,
+    // The ExecutionPlan in S3 was saved by a newer build, in a layout this one doesn't know
+    UnknownStoredPlanVersion,
+    // The ExecutionPlan changed in S3 since we pulled it
+    VersionConflict,
 }
 pub type ExecutableResult<T> = core::result::Result<T, ExecutableError>;
 
+impl ExecutableError {
+    // For a txn that could not be built or signed
+    pub fn from_create_txn_error(e: EthError) -> Self {
+        match e {
+            EthError::ThresholdSignaturePending => Self::ThresholdSignaturePending,
+            _ => Self::FailedToCreateTxn,
+        }
+    }
+}
+
 // Implement for ExecutionPlan, ExecutionPath, ExecutionStep
 pub trait Executable {
     fn get_status(&self) -> ExecutableSimpleStatus;
@@ -103,9 +136,36 @@ impl From<&CrossChainStepStatus> for ExecutableSimpleStatus {
             CrossChainStepStatus::NotStarted => Self::NotStarted,
             CrossChainStepStatus::Dropped => Self::Dropped,
             CrossChainStepStatus::Failed(_) => Self::Failed,
+            CrossChainStepStatus::RemoteFailed(_, _, _) => Self::Failed,
             CrossChainStepStatus::Submitted(_, _) => Self::InProgress,
             CrossChainStepStatus::LocalConfirmed(_, _) => Self::InProgress,
             CrossChainStepStatus::Confirmed(_, _) => Self::Succeeded,
         }
     }
 }
+
+impl From<&MultiPhaseBridgeStepStatus> for ExecutableSimpleStatus {
+    fn from(status: &MultiPhaseBridgeStepStatus) -> Self {
+        match status {
+            MultiPhaseBridgeStepStatus::NotStarted => Self::NotStarted,
+            MultiPhaseBridgeStepStatus::Dropped => Self::Dropped,
+            MultiPhaseBridgeStepStatus::Failed(_) => Self::Failed,
+            MultiPhaseBridgeStepStatus::DepositSubmitted(_) => Self::InProgress,
+            MultiPhaseBridgeStepStatus::AwaitingRelay(_) => Self::InProgress,
+            MultiPhaseBridgeStepStatus::ClaimSubmitted(_, _) => Self::InProgress,
+            MultiPhaseBridgeStepStatus::Confirmed(_, _) => Self::Succeeded,
+        }
+    }
+}
+
+impl From<&RemarkDepositStatus> for ExecutableSimpleStatus {
+    fn from(status: &RemarkDepositStatus) -> Self {
+        match status {
+            RemarkDepositStatus::NotStarted => Self::NotStarted,
+            RemarkDepositStatus::Pending(_) => Self::InProgress,
+            RemarkDepositStatus::Dropped => Self::Dropped,
+            RemarkDepositStatus::Failed(_) => Self::Failed,
+            RemarkDepositStatus::Confirmed(_) => Self::Succeeded,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{Amount, UniversalTokenId};
+
+// How many recent confirmed transfers we remember per lane
+const MAX_SAMPLES_PER_LANE: usize = 10;
+
+/// Bridge fee actually paid by recent confirmed XCM transfers, per (src_token, dest_token)
+/// lane, in dest token units. The realized fee is the amount we sent minus the amount the
+/// beneficiary received. The bridge registry's estimate is a hard-coded average per dest
+/// chain, so quotes use the lane's recent average instead once we have observed transfers
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct XcmBridgeFeeCalibration {
+    lanes: Vec<LaneBridgeFeeSamples>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct LaneBridgeFeeSamples {
+    src_token: UniversalTokenId,
+    dest_token: UniversalTokenId,
+    // Oldest first
+    recent_bridge_fees: Vec<Amount>,
+}
+
+impl XcmBridgeFeeCalibration {
+    pub fn record_bridge_fee(
+        &mut self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        bridge_fee: Amount,
+    ) {
+        if let Some(lane) = self
+            .lanes
+            .iter_mut()
+            .find(|lane| &lane.src_token == src_token && &lane.dest_token == dest_token)
+        {
+            if lane.recent_bridge_fees.len() >= MAX_SAMPLES_PER_LANE {
+                lane.recent_bridge_fees.remove(0);
+            }
+            lane.recent_bridge_fees.push(bridge_fee);
+        } else {
+            self.lanes.push(LaneBridgeFeeSamples {
+                src_token: src_token.clone(),
+                dest_token: dest_token.clone(),
+                recent_bridge_fees: vec![bridge_fee],
+            });
+        }
+    }
+
+    /// None if we have not observed any transfers on the lane yet
+    pub fn get_bridge_fee(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+    ) -> Option<Amount> {
+        let lane = self
+            .lanes
+            .iter()
+            .find(|lane| &lane.src_token == src_token && &lane.dest_token == dest_token)?;
+        if lane.recent_bridge_fees.is_empty() {
+            return None;
+        }
+        Some(
+            lane.recent_bridge_fees.iter().sum::<Amount>()
+                / lane.recent_bridge_fees.len() as Amount,
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod xcm_bridge_fee_calibration_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry::{
+        DOT_MOONBEAM, DOT_NATIVE, GLMR_ASTAR, GLMR_NATIVE,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_uncalibrated_lane() {
+        let mut calibration = XcmBridgeFeeCalibration::default();
+        calibration.record_bridge_fee(&DOT_NATIVE, &DOT_MOONBEAM, 20_000_000);
+        assert_eq!(calibration.get_bridge_fee(&GLMR_NATIVE, &GLMR_ASTAR), None);
+        // Lanes are directional
+        assert_eq!(calibration.get_bridge_fee(&DOT_MOONBEAM, &DOT_NATIVE), None);
+    }
+
+    #[test]
+    fn test_calibrated_fee_is_recent_average() {
+        let mut calibration = XcmBridgeFeeCalibration::default();
+        calibration.record_bridge_fee(&DOT_NATIVE, &DOT_MOONBEAM, 20_000_000);
+        calibration.record_bridge_fee(&DOT_NATIVE, &DOT_MOONBEAM, 30_000_000);
+        calibration.record_bridge_fee(&DOT_NATIVE, &DOT_MOONBEAM, 40_000_000);
+        assert_eq!(
+            calibration.get_bridge_fee(&DOT_NATIVE, &DOT_MOONBEAM),
+            Some(30_000_000)
+        );
+    }
+
+    #[test]
+    fn test_old_samples_are_evicted() {
+        let mut calibration = XcmBridgeFeeCalibration::default();
+        calibration.record_bridge_fee(&GLMR_NATIVE, &GLMR_ASTAR, 1_000_000_000_000_000_000);
+        for _ in 0..MAX_SAMPLES_PER_LANE {
+            calibration.record_bridge_fee(&GLMR_NATIVE, &GLMR_ASTAR, 5_000_000_000_000_000);
+        }
+        assert_eq!(
+            calibration.get_bridge_fee(&GLMR_NATIVE, &GLMR_ASTAR),
+            Some(5_000_000_000_000_000)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::UniversalTokenId;
+
+// How many recent successful transfers we remember per lane
+const MAX_SAMPLES_PER_LANE: usize = 10;
+// We buy 1.5x the heaviest recently observed weight, since execution weight varies a bit
+// between runtime upgrades (and whether the beneficiary account already exists)
+const WEIGHT_MARGIN_NUMERATOR: u64 = 3;
+const WEIGHT_MARGIN_DENOMINATOR: u64 = 2;
+
+/// Weight actually consumed on the destination chain by recent successful XCM transfers,
+/// per (src_token, dest_token) lane. The bridge registry's dest_weight_limit is a static
+/// upper bound; buying far more weight than needed can make the fee exceed small transfer
+/// amounts, so we lower the limit to what the lane has recently needed (plus a margin)
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct XcmWeightCalibration {
+    lanes: Vec<LaneWeightSamples>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct LaneWeightSamples {
+    src_token: UniversalTokenId,
+    dest_token: UniversalTokenId,
+    // Oldest first
+    recent_weights_used: Vec<u64>,
+}
+
+impl XcmWeightCalibration {
+    pub fn record_weight_used(
+        &mut self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        weight_used: u64,
+    ) {
+        if let Some(lane) = self
+            .lanes
+            .iter_mut()
+            .find(|lane| &lane.src_token == src_token && &lane.dest_token == dest_token)
+        {
+            if lane.recent_weights_used.len() >= MAX_SAMPLES_PER_LANE {
+                lane.recent_weights_used.remove(0);
+            }
+            lane.recent_weights_used.push(weight_used);
+        } else {
+            self.lanes.push(LaneWeightSamples {
+                src_token: src_token.clone(),
+                dest_token: dest_token.clone(),
+                recent_weights_used: vec![weight_used],
+            });
+        }
+    }
+
+    /// Never exceeds the lane's configured limit, and falls back to it if we have
+    /// not observed any transfers on the lane yet
+    pub fn get_weight_limit(
+        &self,
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+        configured_weight_limit: u64,
+    ) -> u64 {
+        self.lanes
+            .iter()
+            .find(|lane| &lane.src_token == src_token && &lane.dest_token == dest_token)
+            .and_then(|lane| lane.recent_weights_used.iter().max())
+            .map_or(configured_weight_limit, |max_weight_used| {
+                let calibrated = max_weight_used.saturating_mul(WEIGHT_MARGIN_NUMERATOR)
+                    / WEIGHT_MARGIN_DENOMINATOR;
+                calibrated.min(configured_weight_limit)
+            })
+    }
+}
+
+#[cfg(test)]
+mod xcm_weight_calibration_tests {
+    use privadex_chain_metadata::{
+        bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        registry::token::universal_token_id_registry::{
+            DOT_MOONBEAM, DOT_NATIVE, GLMR_ASTAR, GLMR_NATIVE,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_uncalibrated_lane_uses_configured_limit() {
+        let mut calibration = XcmWeightCalibration::default();
+        calibration.record_weight_used(&DOT_NATIVE, &DOT_MOONBEAM, 1_000_000_000);
+        assert_eq!(
+            calibration.get_weight_limit(&GLMR_NATIVE, &GLMR_ASTAR, DEFAULT_XCM_DEST_WEIGHT_LIMIT),
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_calibrated_limit_uses_max_recent_weight() {
+        let mut calibration = XcmWeightCalibration::default();
+        calibration.record_weight_used(&GLMR_NATIVE, &GLMR_ASTAR, 800_000_000);
+        calibration.record_weight_used(&GLMR_NATIVE, &GLMR_ASTAR, 1_000_000_000);
+        calibration.record_weight_used(&GLMR_NATIVE, &GLMR_ASTAR, 900_000_000);
+        assert_eq!(
+            calibration.get_weight_limit(&GLMR_NATIVE, &GLMR_ASTAR, DEFAULT_XCM_DEST_WEIGHT_LIMIT),
+            1_500_000_000
+        );
+        // Capped at the configured limit
+        assert_eq!(
+            calibration.get_weight_limit(&GLMR_NATIVE, &GLMR_ASTAR, 1_200_000_000),
+            1_200_000_000
+        );
+    }
+
+    #[test]
+    fn test_old_samples_are_evicted() {
+        let mut calibration = XcmWeightCalibration::default();
+        calibration.record_weight_used(&GLMR_NATIVE, &GLMR_ASTAR, 4_000_000_000);
+        for _ in 0..MAX_SAMPLES_PER_LANE {
+            calibration.record_weight_used(&GLMR_NATIVE, &GLMR_ASTAR, 1_000_000_000);
+        }
+        assert_eq!(
+            calibration.get_weight_limit(&GLMR_NATIVE, &GLMR_ASTAR, DEFAULT_XCM_DEST_WEIGHT_LIMIT),
+            1_500_000_000
+        );
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use privadex_chain_metadata::common::Amount;
+use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_execution_plan::execution_plan::ZAP_IN_SLIPPAGE_BPS;
+
+// What a Uniswap V2-style router's addLiquidity actually adds of the desired amounts: as much
+// as it can in the pair's current ratio. The rest stays with the sender
+pub fn get_add_liquidity_amounts(
+    amount_a_desired: Amount,
+    amount_b_desired: Amount,
+    reserve_a: Amount,
+    reserve_b: Amount,
+) -> (Amount, Amount) {
+    // The first liquidity sets the ratio
+    if reserve_a == 0 || reserve_b == 0 {
+        return (amount_a_desired, amount_b_desired);
+    }
+    let amount_b_optimal = mul_ratio_u128(amount_a_desired, reserve_b, reserve_a);
+    if amount_b_optimal <= amount_b_desired {
+        (amount_a_desired, amount_b_optimal)
+    } else {
+        (
+            mul_ratio_u128(amount_b_desired, reserve_a, reserve_b),
+            amount_b_desired,
+        )
+    }
+}
+
+// addLiquidity's amountAMin and amountBMin: what the reserves call for at signing, less
+// ZAP_IN_SLIPPAGE_BPS
+pub fn get_add_liquidity_min_amounts(
+    amount_a_desired: Amount,
+    amount_b_desired: Amount,
+    reserve_a: Amount,
+    reserve_b: Amount,
+) -> (Amount, Amount) {
+    let (amount_a, amount_b) =
+        get_add_liquidity_amounts(amount_a_desired, amount_b_desired, reserve_a, reserve_b);
+    let keep_bps = (10_000 - ZAP_IN_SLIPPAGE_BPS) as u128;
+    (
+        mul_ratio_u128(amount_a, keep_bps, 10_000),
+        mul_ratio_u128(amount_b, keep_bps, 10_000),
+    )
+}
+
+// The LP tokens the pair mints for adding amount_a and amount_b (already in the pair's ratio)
+// to an existing pair. None for an empty pair, where the mint depends on the pair's minimum
+// liquidity
+pub fn get_liquidity_minted(
+    amount_a: Amount,
+    amount_b: Amount,
+    reserve_a: Amount,
+    reserve_b: Amount,
+    total_supply: Amount,
+) -> Option<Amount> {
+    if reserve_a == 0 || reserve_b == 0 || total_supply == 0 {
+        return None;
+    }
+    Some(
+        mul_ratio_u128(amount_a, total_supply, reserve_a).min(mul_ratio_u128(
+            amount_b,
+            total_supply,
+            reserve_b,
+        )),
+    )
+}
+
+// The LP tokens a zap-in of amount gets, given what the pair pays out for half of it (e.g. from
+// the router's getAmountsOut). The swap moves the reserves before the liquidity is added
+pub fn estimate_zap_in_liquidity(
+    amount: Amount,
+    swap_amount_out: Amount,
+    reserve_in: Amount,
+    reserve_out: Amount,
+    total_supply: Amount,
+) -> Option<Amount> {
+    let swap_amount_in = amount / 2;
+    let reserve_in = reserve_in.checked_add(swap_amount_in)?;
+    let reserve_out = reserve_out.checked_sub(swap_amount_out)?;
+    let (amount_a, amount_b) = get_add_liquidity_amounts(
+        amount - swap_amount_in,
+        swap_amount_out,
+        reserve_in,
+        reserve_out,
+    );
+    get_liquidity_minted(amount_a, amount_b, reserve_in, reserve_out, total_supply)
+}
+
+#[cfg(test)]
+mod zap_in_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_add_liquidity_amounts() {
+        // 1:2 pair, so 100 of a pairs with 200 of b
+        assert_eq!(
+            get_add_liquidity_amounts(100, 300, 1_000, 2_000),
+            (100, 200)
+        );
+        assert_eq!(get_add_liquidity_amounts(100, 150, 1_000, 2_000), (75, 150));
+        assert_eq!(get_add_liquidity_amounts(100, 150, 0, 0), (100, 150));
+    }
+
+    #[test]
+    fn test_get_add_liquidity_min_amounts() {
+        assert_eq!(
+            get_add_liquidity_min_amounts(10_000, 30_000, 1_000, 2_000),
+            (9_900, 19_800)
+        );
+    }
+
+    #[test]
+    fn test_get_liquidity_minted() {
+        assert_eq!(get_liquidity_minted(100, 200, 1_000, 2_000, 500), Some(50));
+        // Off-ratio amounts only get credit for the smaller side
+        assert_eq!(get_liquidity_minted(100, 100, 1_000, 2_000, 500), Some(25));
+        assert_eq!(get_liquidity_minted(100, 200, 0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_estimate_zap_in_liquidity() {
+        // 1:1 pair with no fee: swapping 100 into 1_000/1_000 pays out 1_000 * 100 / 1_100
+        let swap_amount_out = 90;
+        let liquidity = estimate_zap_in_liquidity(200, swap_amount_out, 1_000, 1_000, 1_000)
+            .expect("Existing pair");
+        // After the swap the reserves are 1_100/910, so the other 100 of a pairs with 82 of b
+        assert_eq!(liquidity, 100 * 1_000 / 1_100);
+        assert_eq!(
+            estimate_zap_in_liquidity(200, 2_000, 1_000, 1_000, 1_000),
+            None
+        );
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+use privadex_chain_metadata::common::Amount;
+use privadex_common::utils::general_utils::mul_ratio_u128;
+use privadex_execution_plan::execution_plan::REMOVE_LIQUIDITY_SLIPPAGE_BPS;
+
+// What a Uniswap V2-style pair pays out of each reserve for burning liquidity LP tokens: the
+// LP tokens' share of the reserves. None for an empty pair or more LP tokens than exist
+pub fn get_remove_liquidity_amounts(
+    liquidity: Amount,
+    reserve_a: Amount,
+    reserve_b: Amount,
+    total_supply: Amount,
+) -> Option<(Amount, Amount)> {
+    if total_supply == 0 || liquidity > total_supply {
+        return None;
+    }
+    Some((
+        mul_ratio_u128(liquidity, reserve_a, total_supply),
+        mul_ratio_u128(liquidity, reserve_b, total_supply),
+    ))
+}
+
+// removeLiquidity's amountAMin and amountBMin: what the reserves pay out at signing, less
+// REMOVE_LIQUIDITY_SLIPPAGE_BPS
+pub fn get_remove_liquidity_min_amounts(
+    liquidity: Amount,
+    reserve_a: Amount,
+    reserve_b: Amount,
+    total_supply: Amount,
+) -> Option<(Amount, Amount)> {
+    let (amount_a, amount_b) =
+        get_remove_liquidity_amounts(liquidity, reserve_a, reserve_b, total_supply)?;
+    let keep_bps = (10_000 - REMOVE_LIQUIDITY_SLIPPAGE_BPS) as u128;
+    Some((
+        mul_ratio_u128(amount_a, keep_bps, 10_000),
+        mul_ratio_u128(amount_b, keep_bps, 10_000),
+    ))
+}
+
+#[cfg(test)]
+mod zap_out_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_remove_liquidity_amounts() {
+        // A tenth of the LP tokens gets a tenth of each reserve
+        assert_eq!(
+            get_remove_liquidity_amounts(50, 1_000, 2_000, 500),
+            Some((100, 200))
+        );
+        assert_eq!(get_remove_liquidity_amounts(50, 0, 0, 0), None);
+        assert_eq!(get_remove_liquidity_amounts(600, 1_000, 2_000, 500), None);
+    }
+
+    #[test]
+    fn test_get_remove_liquidity_min_amounts() {
+        assert_eq!(
+            get_remove_liquidity_min_amounts(500, 10_000, 30_000, 1_000),
+            Some((4_950, 14_850))
+        );
+    }
+}
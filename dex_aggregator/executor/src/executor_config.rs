@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::{BlockNum, MillisSinceEpoch};
+
+use crate::executable::executable_step::TXN_NUM_BLOCKS_ALIVE;
+
+// The DEX swap deadline is this many millis in the future i.e. the txn will fail
+// if it is included in a block after 8 minutes
+pub const DEX_SWAP_LIFE_MILLIS: MillisSinceEpoch = 480_000;
+
+const MIN_TXN_NUM_BLOCKS_ALIVE: BlockNum = 16;
+const MAX_TXN_NUM_BLOCKS_ALIVE: BlockNum = 1024;
+const MIN_DEX_SWAP_LIFE_MILLIS: MillisSinceEpoch = 60_000;
+const MAX_DEX_SWAP_LIFE_MILLIS: MillisSinceEpoch = 3_600_000;
+
+#[derive(Debug, PartialEq)]
+pub enum ExecutorConfigError {
+    DexSwapLifeOutOfRange,
+    TxnNumBlocksAliveNotPowerOfTwo,
+    TxnNumBlocksAliveOutOfRange,
+}
+
+/// Step execution limits that can be tuned without a redeploy (see set_executor_config). The
+/// defaults are the limits the executor always used
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ExecutorConfig {
+    // After this many blocks, we assume a submitted txn is dropped. Must stay a power of 2 so
+    // that it can double as a mortal Era's period
+    pub txn_num_blocks_alive: BlockNum,
+    pub dex_swap_life_millis: MillisSinceEpoch,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            txn_num_blocks_alive: TXN_NUM_BLOCKS_ALIVE,
+            dex_swap_life_millis: DEX_SWAP_LIFE_MILLIS,
+        }
+    }
+}
+
+impl ExecutorConfig {
+    pub fn validate(&self) -> Result<(), ExecutorConfigError> {
+        if self.txn_num_blocks_alive < MIN_TXN_NUM_BLOCKS_ALIVE
+            || self.txn_num_blocks_alive > MAX_TXN_NUM_BLOCKS_ALIVE
+        {
+            return Err(ExecutorConfigError::TxnNumBlocksAliveOutOfRange);
+        }
+        if !self.txn_num_blocks_alive.is_power_of_two() {
+            return Err(ExecutorConfigError::TxnNumBlocksAliveNotPowerOfTwo);
+        }
+        if self.dex_swap_life_millis < MIN_DEX_SWAP_LIFE_MILLIS
+            || self.dex_swap_life_millis > MAX_DEX_SWAP_LIFE_MILLIS
+        {
+            return Err(ExecutorConfigError::DexSwapLifeOutOfRange);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod executor_config_tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(ExecutorConfig::default().validate(), Ok(()));
+        assert_eq!(
+            ExecutorConfig {
+                txn_num_blocks_alive: 128,
+                dex_swap_life_millis: 300_000,
+            }
+            .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            ExecutorConfig {
+                txn_num_blocks_alive: 8,
+                ..ExecutorConfig::default()
+            }
+            .validate(),
+            Err(ExecutorConfigError::TxnNumBlocksAliveOutOfRange)
+        );
+        assert_eq!(
+            ExecutorConfig {
+                txn_num_blocks_alive: 100,
+                ..ExecutorConfig::default()
+            }
+            .validate(),
+            Err(ExecutorConfigError::TxnNumBlocksAliveNotPowerOfTwo)
+        );
+        assert_eq!(
+            ExecutorConfig {
+                dex_swap_life_millis: 10_000,
+                ..ExecutorConfig::default()
+            }
+            .validate(),
+            Err(ExecutorConfigError::DexSwapLifeOutOfRange)
+        );
+    }
+}
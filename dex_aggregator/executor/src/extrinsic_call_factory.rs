@@ -16,15 +16,23 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 use scale::{Decode, Encode};
 
-use privadex_chain_metadata::bridge::split_into_dest_and_beneficiary;
+use privadex_chain_metadata::{
+    bridge::split_into_dest_and_beneficiary,
+    common::SubstratePublicKey,
+    xcm_location::{
+        to_versioned_multiasset, to_versioned_multiassets, to_versioned_multilocation,
+        VersionedMultiAsset, VersionedMultiAssets, VersionedMultiLocation, XcmVersion,
+    },
+};
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum ExtrinsicCallFactoryError {
     FailedToSplitFullDestMultiLocation,
+    UnsupportedXcmVersion,
 }
 type Result<T> = core::result::Result<T, ExtrinsicCallFactoryError>;
 
@@ -40,66 +48,54 @@ pub struct UnsignedExtrinsic<Call> {
 // from a raw u64 to WeightLimit in late 2022 in an upgrade.
 // I need a way to monitor these breaking changes and update the encoding accordingly.
 
-pub fn moonbeam_xtokens_transfer_multiasset(
-    asset: xcm::prelude::MultiAsset,
-    full_dest: xcm::prelude::MultiLocation,
-) -> Result<Vec<u8>> {
-    #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    struct XTokensTransferMultiassetCall {
-        asset: xcm::prelude::VersionedMultiAsset,
-        dest: xcm::prelude::VersionedMultiLocation,
-        dest_weight_limit: xcm::prelude::WeightLimit,
-    }
-
-    let raw_call_data = UnsignedExtrinsic {
-        pallet_id: 0x6a,
-        call_id: 0x01,
-        call: XTokensTransferMultiassetCall {
-            asset: xcm::prelude::VersionedMultiAsset::from(asset),
-            dest: xcm::prelude::VersionedMultiLocation::from(full_dest),
-            dest_weight_limit: xcm::prelude::WeightLimit::Limited(10_000_000_000u64),
-        },
-    };
-
-    Ok(raw_call_data.encode())
-}
-
-pub fn moonbase_alpha_xtokens_transfer_multiasset(
+// The pallet index comes from the source chain's ChainInfo (e.g. 0x6a on Moonbeam and 0x1e on
+// Moonbase Alpha), and the XCM version is negotiated between the source and destination chains.
+// dest_weight_limit is the weight bought on the destination chain. Too little weight and the
+// message is rejected by the destination's Barrier, too much and the fee can exceed the
+// transferred amount (TooExpensive)
+pub fn xtokens_transfer_multiasset(
+    pallet_id: u8,
+    xcm_version: XcmVersion,
     asset: xcm::prelude::MultiAsset,
     full_dest: xcm::prelude::MultiLocation,
+    dest_weight_limit: u64,
 ) -> Result<Vec<u8>> {
     #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     struct XTokensTransferMultiassetCall {
-        asset: xcm::prelude::VersionedMultiAsset,
-        dest: xcm::prelude::VersionedMultiLocation,
+        asset: VersionedMultiAsset,
+        dest: VersionedMultiLocation,
         dest_weight_limit: xcm::prelude::WeightLimit,
     }
 
     let raw_call_data = UnsignedExtrinsic {
-        pallet_id: 0x1e,
+        pallet_id,
         call_id: 0x01,
         call: XTokensTransferMultiassetCall {
-            asset: xcm::prelude::VersionedMultiAsset::from(asset),
-            dest: xcm::prelude::VersionedMultiLocation::from(full_dest),
-            dest_weight_limit: xcm::prelude::WeightLimit::Limited(10_000_000_000u64),
+            asset: to_versioned_multiasset(asset, xcm_version)
+                .map_err(|_| ExtrinsicCallFactoryError::UnsupportedXcmVersion)?,
+            dest: to_versioned_multilocation(full_dest, xcm_version)
+                .map_err(|_| ExtrinsicCallFactoryError::UnsupportedXcmVersion)?,
+            dest_weight_limit: xcm::prelude::WeightLimit::Limited(dest_weight_limit),
         },
     };
 
     Ok(raw_call_data.encode())
 }
 
-pub fn polkadot_xcm_limited_reserve_transfer_assets(
+pub fn xcm_pallet_limited_reserve_transfer_assets(
+    pallet_id: u8,
+    xcm_version: XcmVersion,
     asset: xcm::prelude::MultiAsset,
     full_dest: xcm::prelude::MultiLocation,
+    dest_weight_limit: u64,
 ) -> Result<Vec<u8>> {
     #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     struct XcmLimitedReserveTransferAssets {
-        dest: xcm::prelude::VersionedMultiLocation,
-        beneficiary: xcm::prelude::VersionedMultiLocation,
-        assets: xcm::prelude::VersionedMultiAssets,
+        dest: VersionedMultiLocation,
+        beneficiary: VersionedMultiLocation,
+        assets: VersionedMultiAssets,
         fee_asset_item: u32,
         weight_limit: xcm::prelude::WeightLimit,
     }
@@ -108,16 +104,19 @@ pub fn polkadot_xcm_limited_reserve_transfer_assets(
         .map_err(|_| ExtrinsicCallFactoryError::FailedToSplitFullDestMultiLocation)?;
 
     let assets =
-        xcm::prelude::VersionedMultiAssets::from(xcm::prelude::MultiAssets::from(vec![asset]));
+        to_versioned_multiassets(xcm::prelude::MultiAssets::from(vec![asset]), xcm_version)
+            .map_err(|_| ExtrinsicCallFactoryError::UnsupportedXcmVersion)?;
     let fee_asset_item = 0u32;
-    let weight_limit = xcm::prelude::WeightLimit::Limited(10_000_000_000u64);
+    let weight_limit = xcm::prelude::WeightLimit::Limited(dest_weight_limit);
 
     let raw_call_data = UnsignedExtrinsic {
-        pallet_id: 0x63,
+        pallet_id,
         call_id: 0x08,
         call: XcmLimitedReserveTransferAssets {
-            dest: xcm::prelude::VersionedMultiLocation::from(dest),
-            beneficiary: xcm::prelude::VersionedMultiLocation::from(beneficiary),
+            dest: to_versioned_multilocation(dest, xcm_version)
+                .map_err(|_| ExtrinsicCallFactoryError::UnsupportedXcmVersion)?,
+            beneficiary: to_versioned_multilocation(beneficiary, xcm_version)
+                .map_err(|_| ExtrinsicCallFactoryError::UnsupportedXcmVersion)?,
             assets,
             fee_asset_item,
             weight_limit,
@@ -126,6 +125,34 @@ pub fn polkadot_xcm_limited_reserve_transfer_assets(
     Ok(raw_call_data.encode())
 }
 
+// utility.batch_all: the calls are dispatched in order from the same origin, and if one fails
+// the whole extrinsic reverts. The calls are already encoded (e.g. by the functions above), and
+// a Vec<Call> encodes as its compact length followed by the calls back to back
+pub fn utility_batch_all(pallet_id: u8, calls: &[Vec<u8>]) -> Vec<u8> {
+    let mut raw_call_data = vec![pallet_id, 0x02];
+    scale::Compact(calls.len() as u32).encode_to(&mut raw_call_data);
+    for call in calls.iter() {
+        raw_call_data.extend_from_slice(call);
+    }
+    raw_call_data
+}
+
+// proxy.proxy: the signer dispatches call as real, which must have added the signer as its
+// proxy. force_proxy_type picks which of the proxy relationships to use, None lets the chain
+// pick any that allows call. real is a MultiAddress::Id, i.e. 0x00 then the 32-byte key
+pub fn proxy_proxy(
+    pallet_id: u8,
+    real: &SubstratePublicKey,
+    force_proxy_type: Option<u8>,
+    call: &[u8],
+) -> Vec<u8> {
+    let mut raw_call_data = vec![pallet_id, 0x00, 0x00];
+    raw_call_data.extend_from_slice(&real.0);
+    force_proxy_type.encode_to(&mut raw_call_data);
+    raw_call_data.extend_from_slice(call);
+    raw_call_data
+}
+
 #[cfg(test)]
 mod extrinsic_call_factory_tests {
     use hex_literal::hex;
@@ -134,8 +161,12 @@ mod extrinsic_call_factory_tests {
     };
 
     use privadex_chain_metadata::{
+        bridge::DEFAULT_XCM_DEST_WEIGHT_LIMIT,
         common::{EthAddress, SubstratePublicKey, UniversalAddress},
-        registry::bridge::xcm_bridge_registry::XCM_BRIDGES,
+        registry::{
+            bridge::xcm_bridge_registry::XCM_BRIDGES,
+            chain::chain_info_registry::{MOONBASEALPHA_INFO, MOONBEAM_INFO, POLKADOT_INFO},
+        },
     };
     #[allow(unused_imports)]
     use privadex_common::utils::general_utils::slice_to_hex_string;
@@ -165,12 +196,67 @@ mod extrinsic_call_factory_tests {
             ),
         };
 
-        let extrinsic_data = moonbase_alpha_xtokens_transfer_multiasset(alpha_dev_asset, full_dest)
-            .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xtokens_transfer_multiasset(
+            MOONBASEALPHA_INFO
+                .xtokens_pallet_index
+                .expect("xTokens pallet"),
+            XcmVersion::V1,
+            alpha_dev_asset.clone(),
+            full_dest.clone(),
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2Fwss.api.moonbase.moonbeam.network#/extrinsics/decode/0x1e01010000010403000f0080c6a47e8d0301010200e10d030005a81d8564a3ea298660e34e03e5eff9a29d7a2a0102286bee
         let expected_extrinsic_data = hex!("1e01010000010403000f0080c6a47e8d0301010200e10d030005a81d8564a3ea298660e34e03e5eff9a29d7a2a010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
+
+        // Neither the asset nor the account names a network, so V3 only changes the version bytes
+        let extrinsic_data = xtokens_transfer_multiasset(
+            MOONBASEALPHA_INFO
+                .xtokens_pallet_index
+                .expect("xTokens pallet"),
+            XcmVersion::V3,
+            alpha_dev_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        let expected_extrinsic_data = hex!("1e01030000010403000f0080c6a47e8d0303010200e10d030005a81d8564a3ea298660e34e03e5eff9a29d7a2a010700e40b5402").to_vec();
+        assert_eq!(extrinsic_data, expected_extrinsic_data);
+    }
+
+    #[test]
+    fn test_utility_batch_all() {
+        let polkadot_utility = POLKADOT_INFO.utility_pallet_index.expect("Utility pallet");
+        // Any encoded calls, they are copied as-is
+        let calls = [hex!("00000401").to_vec(), hex!("0000080203").to_vec()];
+        assert_eq!(
+            utility_batch_all(polkadot_utility, &calls),
+            hex!("1a0208000004010000080203").to_vec()
+        );
+        assert_eq!(
+            utility_batch_all(polkadot_utility, &[]),
+            hex!("1a0200").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_proxy_proxy() {
+        let polkadot_proxy = POLKADOT_INFO.proxy_pallet_index.expect("Proxy pallet");
+        let real = SubstratePublicKey {
+            0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
+        };
+        let call = hex!("0000080203").to_vec();
+        assert_eq!(
+            proxy_proxy(polkadot_proxy, &real, None, &call),
+            hex!("1d00005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be000000080203").to_vec()
+        );
+        // ProxyType::Any is 0x00 on Polkadot
+        assert_eq!(
+            proxy_proxy(polkadot_proxy, &real, Some(0x00), &call),
+            hex!("1d00005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be01000000080203").to_vec()
+        );
     }
 
     #[test]
@@ -190,9 +276,15 @@ mod extrinsic_call_factory_tests {
             .get_full_dest_multilocation(dest)
             .expect("Valid dest MultiLocation");
 
-        let extrinsic_data = moonbeam_xtokens_transfer_multiasset(astr_moonbeam_asset, full_dest)
-            .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xtokens_transfer_multiasset(
+            MOONBEAM_INFO.xtokens_pallet_index.expect("xTokens pallet"),
+            XcmVersion::V1,
+            astr_moonbeam_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F1rpc.io%2Fglmr#/extrinsics/decode/0x6a010100010100591f0013000014bbf08ac60201010200591f01005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be0102286bee
         let expected_extrinsic_data = hex!("6a010100010100591f0013000014bbf08ac60201010200591f01005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
@@ -215,9 +307,15 @@ mod extrinsic_call_factory_tests {
             .get_full_dest_multilocation(dest)
             .expect("Valid dest MultiLocation");
 
-        let extrinsic_data = moonbeam_xtokens_transfer_multiasset(dot_moonbeam_asset, full_dest)
-            .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xtokens_transfer_multiasset(
+            MOONBEAM_INFO.xtokens_pallet_index.expect("xTokens pallet"),
+            XcmVersion::V1,
+            dot_moonbeam_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F1rpc.io%2Fglmr#/extrinsics/decode/0x6a0101000100000700902f5009010101010060b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a0102286bee
         let expected_extrinsic_data = hex!("6a0101000100000700902f5009010101010060b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
@@ -240,9 +338,15 @@ mod extrinsic_call_factory_tests {
             .get_full_dest_multilocation(dest)
             .expect("Valid dest MultiLocation");
 
-        let extrinsic_data = moonbeam_xtokens_transfer_multiasset(glmr_native_asset, full_dest)
-            .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xtokens_transfer_multiasset(
+            MOONBEAM_INFO.xtokens_pallet_index.expect("xTokens pallet"),
+            XcmVersion::V1,
+            glmr_native_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F1rpc.io%2Fglmr#/extrinsics/decode/0x6a0101000001040a001300008a5d7845630101010200591f01005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be0102286bee
         let expected_extrinsic_data = hex!("6a0101000001040a001300008a5d7845630101010200591f01005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
@@ -265,10 +369,15 @@ mod extrinsic_call_factory_tests {
             .get_full_dest_multilocation(dest)
             .expect("Valid dest MultiLocation");
 
-        let extrinsic_data =
-            polkadot_xcm_limited_reserve_transfer_assets(dot_native_asset, full_dest)
-                .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xcm_pallet_limited_reserve_transfer_assets(
+            POLKADOT_INFO.xcm_pallet_index.expect("XCM pallet"),
+            XcmVersion::V1,
+            dot_native_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F1rpc.io%2Fdot#/extrinsics/decode/0x630801000100591f01000101005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be01040000000002286bee000000000102286bee
         let expected_extrinsic_data = hex!("630801000100591f01000101005134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be01040000000002286bee00000000010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
@@ -290,10 +399,15 @@ mod extrinsic_call_factory_tests {
             .get_full_dest_multilocation(dest)
             .expect("Valid dest MultiLocation");
 
-        let extrinsic_data =
-            polkadot_xcm_limited_reserve_transfer_assets(dot_native_asset, full_dest)
-                .expect("Valid extrinsic");
-        // ink_env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
+        let extrinsic_data = xcm_pallet_limited_reserve_transfer_assets(
+            POLKADOT_INFO.xcm_pallet_index.expect("XCM pallet"),
+            XcmVersion::V1,
+            dot_native_asset,
+            full_dest,
+            DEFAULT_XCM_DEST_WEIGHT_LIMIT,
+        )
+        .expect("Valid extrinsic");
+        // ink::env::debug_println!("Data: {:?}", slice_to_hex_string(&extrinsic_data));
         // https://polkadot.js.org/apps/?rpc=wss%3A%2F%2F1rpc.io%2Fdot#/extrinsics/decode/0x630801000100511f010001030005a81d8564a3ea298660e34e03e5eff9a29d7a2a01040000000002286bee000000000102286bee
         let expected_extrinsic_data = hex!("630801000100511f010001030005a81d8564a3ea298660e34e03e5eff9a29d7a2a01040000000002286bee00000000010700e40b5402").to_vec();
         assert_eq!(extrinsic_data, expected_extrinsic_data);
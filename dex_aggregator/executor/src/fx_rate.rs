@@ -0,0 +1,204 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+#[allow(unused_imports)]
+use ink::prelude::{format, string::String};
+use scale::{Decode, Encode};
+use serde::Deserialize;
+
+use privadex_chain_metadata::common::{Amount, USD_AMOUNT_EXPONENT};
+use privadex_common::fixed_point::DecimalFixedPoint;
+#[allow(unused_imports)]
+use privadex_common::utils::http_request::http_get_wrapper;
+use privadex_routing::graph::fee_breakdown::FeeBreakdown;
+
+#[derive(Debug, PartialEq)]
+pub enum FxRateError {
+    ParseFailed,
+    RequestFailed,
+}
+pub type Result<T> = core::result::Result<T, FxRateError>;
+
+/// Currency that quotes and analytics are also reported in. Everything is priced in USD
+/// internally (the subgraphs' derivedUSD), so this is only a conversion at the end
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ReferenceCurrency {
+    Usd,
+    Eur,
+    Btc,
+    Dot,
+}
+
+impl ReferenceCurrency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Usd => "usd",
+            Self::Eur => "eur",
+            Self::Btc => "btc",
+            Self::Dot => "dot",
+        }
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReferenceCurrencyConfig {
+    pub currency: ReferenceCurrency,
+    // See FxRateApi. Not called for Usd
+    pub fx_url: String,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FxRate {
+    pub currency: ReferenceCurrency,
+    // Units of the currency per $1, x 10^USD_AMOUNT_EXPONENT
+    pub units_per_usd: Amount,
+}
+
+impl FxRate {
+    pub fn usd() -> Self {
+        Self {
+            currency: ReferenceCurrency::Usd,
+            units_per_usd: u128::pow(10, USD_AMOUNT_EXPONENT),
+        }
+    }
+
+    // Both amounts are x 10^USD_AMOUNT_EXPONENT
+    pub fn from_usd(&self, amount_usd: Amount) -> Amount {
+        DecimalFixedPoint::u128_mul_div(
+            amount_usd,
+            &DecimalFixedPoint {
+                coef: self.units_per_usd,
+                exp: 0,
+            },
+            &DecimalFixedPoint {
+                coef: 1,
+                exp: USD_AMOUNT_EXPONENT as i8,
+            },
+        )
+    }
+}
+
+/// A quote's USD amounts in the reference currency, x 10^USD_AMOUNT_EXPONENT
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReferenceAmounts {
+    pub fx_rate: FxRate,
+    pub amount_in: Amount,
+    pub amount_out: Amount,
+    pub protocol_fee: Amount,
+    pub total_fee: Amount,
+}
+
+impl ReferenceAmounts {
+    pub fn new(
+        fx_rate: FxRate,
+        amount_in_usd: Amount,
+        amount_out_usd: Amount,
+        fee_breakdown: &FeeBreakdown,
+    ) -> Self {
+        Self {
+            amount_in: fx_rate.from_usd(amount_in_usd),
+            amount_out: fx_rate.from_usd(amount_out_usd),
+            protocol_fee: fx_rate.from_usd(fee_breakdown.protocol_fee_usd),
+            total_fee: fx_rate.from_usd(fee_breakdown.total_fee_usd),
+            fx_rate,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct FxRateResponse<'a> {
+    units_per_usd: &'a str,
+}
+
+/// Client for an FX conversion source (e.g. a thin proxy in front of an FX or crypto price
+/// feed). GET {fx_url}?currency=eur responds with {"units_per_usd":"0.9213"}, i.e. the price
+/// of $1 in the currency as a decimal string
+pub struct FxRateApi {
+    pub fx_url: String,
+}
+
+impl FxRateApi {
+    pub fn get_fx_rate(&self, currency: ReferenceCurrency) -> Result<FxRate> {
+        if currency == ReferenceCurrency::Usd {
+            return Ok(FxRate::usd());
+        }
+        let resp_body = http_get_wrapper(&get_fx_rate_url(&self.fx_url, currency))
+            .map_err(|_| FxRateError::RequestFailed)?;
+        parse_fx_rate_response(currency, &resp_body)
+    }
+}
+
+fn get_fx_rate_url(fx_url: &str, currency: ReferenceCurrency) -> String {
+    format!("{}?currency={}", fx_url, currency.code())
+}
+
+fn parse_fx_rate_response(currency: ReferenceCurrency, resp_body: &[u8]) -> Result<FxRate> {
+    let (resp, _): (FxRateResponse, usize) =
+        serde_json_core::from_slice(resp_body).map_err(|_| FxRateError::ParseFailed)?;
+    let units_per_usd =
+        DecimalFixedPoint::from_str_and_exp(resp.units_per_usd, USD_AMOUNT_EXPONENT as u8).coef;
+    // Also catches rates too small to represent
+    if units_per_usd == 0 {
+        return Err(FxRateError::ParseFailed);
+    }
+    Ok(FxRate {
+        currency,
+        units_per_usd,
+    })
+}
+
+#[cfg(test)]
+mod fx_rate_tests {
+    use super::*;
+
+    #[test]
+    fn test_fx_rate_url() {
+        assert_eq!(
+            get_fx_rate_url("https://fx.example/rate", ReferenceCurrency::Btc),
+            "https://fx.example/rate?currency=btc"
+        );
+    }
+
+    #[test]
+    fn test_parse_fx_rate_response() {
+        let fx_rate = parse_fx_rate_response(
+            ReferenceCurrency::Eur,
+            r#"{"units_per_usd":"0.92"}"#.as_bytes(),
+        )
+        .expect("Valid response");
+        assert_eq!(fx_rate.units_per_usd, 92 * u128::pow(10, 16));
+        // $25 is 23 EUR
+        assert_eq!(
+            fx_rate.from_usd(25 * u128::pow(10, 18)),
+            23 * u128::pow(10, 18)
+        );
+        assert_eq!(
+            parse_fx_rate_response(
+                ReferenceCurrency::Eur,
+                r#"{"units_per_usd":"0"}"#.as_bytes()
+            ),
+            Err(FxRateError::ParseFailed)
+        );
+        assert_eq!(FxRate::usd().from_usd(12345), 12345);
+    }
+}
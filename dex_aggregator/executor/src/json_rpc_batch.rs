@@ -0,0 +1,181 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::Deserialize;
+
+use privadex_common::utils::http_request::{http_post_batch_wrapper, http_post_wrapper};
+
+#[derive(Debug, PartialEq)]
+pub enum JsonRpcBatchError {
+    InvalidBody,
+    RequestFailed,
+    UnexpectedResponseOrder,
+}
+pub type Result<T> = core::result::Result<T, JsonRpcBatchError>;
+
+// params must be a JSON array, e.g. r#"["0x05a8...", "latest"]"#
+pub struct JsonRpcCall<'a> {
+    pub method: &'a str,
+    pub params: String,
+}
+
+impl<'a> JsonRpcCall<'a> {
+    pub fn new(method: &'a str, params: &str) -> Self {
+        Self {
+            method,
+            params: params.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JsonRpcResponse<T> {
+    pub id: u32,
+    pub result: T,
+}
+
+// The batch response is typed by position, e.g. (JsonRpcResponse<&str>, JsonRpcResponse<u32>)
+// for a batch of two calls
+pub trait JsonRpcBatchResponse {
+    fn ids(&self) -> Vec<u32>;
+}
+
+macro_rules! impl_json_rpc_batch_response {
+    ($($name:ident: $index:tt),+) => {
+        impl<$($name),+> JsonRpcBatchResponse for ($(JsonRpcResponse<$name>,)+) {
+            fn ids(&self) -> Vec<u32> {
+                ink::prelude::vec![$(self.$index.id),+]
+            }
+        }
+    };
+}
+
+impl_json_rpc_batch_response!(A: 0, B: 1);
+impl_json_rpc_batch_response!(A: 0, B: 1, C: 2);
+impl_json_rpc_batch_response!(A: 0, B: 1, C: 2, D: 3);
+
+// Sends all the calls in one HTTP request and returns the raw response, which the caller
+// parses with parse_batch_response (it borrows from the body)
+pub fn send_batch(rpc_url: &str, calls: &[JsonRpcCall]) -> Result<Vec<u8>> {
+    http_post_wrapper(rpc_url, get_batch_request_body(calls).into_bytes())
+        .map_err(|_| JsonRpcBatchError::RequestFailed)
+}
+
+// Unlike send_batch, each call goes to its own rpc_url in its own HTTP request (e.g. the same
+// call to several chains). The requests go out concurrently, and each call's raw response is
+// returned in order
+pub fn send_concurrently(calls: &[(&str /* rpc_url */, JsonRpcCall)]) -> Vec<Result<Vec<u8>>> {
+    http_post_batch_wrapper(
+        calls
+            .iter()
+            .map(|(rpc_url, call)| (rpc_url.to_string(), get_request_body(0, call).into_bytes()))
+            .collect(),
+    )
+    .into_iter()
+    .map(|response| response.map_err(|_| JsonRpcBatchError::RequestFailed))
+    .collect()
+}
+
+// The spec lets nodes respond in any order. The ones we use respond in request order, but we
+// check the ids rather than silently mix up the results
+pub fn parse_batch_response<'a, T>(resp_body: &'a [u8]) -> Result<T>
+where
+    T: Deserialize<'a> + JsonRpcBatchResponse,
+{
+    let (responses, _): (T, usize) =
+        serde_json_core::from_slice(resp_body).map_err(|_| JsonRpcBatchError::InvalidBody)?;
+    let is_in_order = responses
+        .ids()
+        .iter()
+        .enumerate()
+        .all(|(index, id)| *id as usize == index);
+    if is_in_order {
+        Ok(responses)
+    } else {
+        Err(JsonRpcBatchError::UnexpectedResponseOrder)
+    }
+}
+
+// Each call's id is its index in the batch
+fn get_batch_request_body(calls: &[JsonRpcCall]) -> String {
+    let requests: Vec<String> = calls
+        .iter()
+        .enumerate()
+        .map(|(id, call)| get_request_body(id, call))
+        .collect();
+    format!("[{}]", requests.join(","))
+}
+
+fn get_request_body(id: usize, call: &JsonRpcCall) -> String {
+    format!(
+        r#"{{"id":{},"jsonrpc":"2.0","method":"{}","params":{}}}"#,
+        id, call.method, call.params
+    )
+}
+
+#[cfg(test)]
+mod json_rpc_batch_tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_request_body() {
+        let calls = [
+            JsonRpcCall::new("eth_blockNumber", "[]"),
+            JsonRpcCall::new(
+                "eth_getTransactionCount",
+                r#"["0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a","latest"]"#,
+            ),
+        ];
+        assert_eq!(
+            get_batch_request_body(&calls),
+            r#"[{"id":0,"jsonrpc":"2.0","method":"eth_blockNumber","params":[]},{"id":1,"jsonrpc":"2.0","method":"eth_getTransactionCount","params":["0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a","latest"]}]"#
+        );
+    }
+
+    #[test]
+    fn test_parse_batch_response() {
+        let resp_body = r#"[{"jsonrpc":"2.0","id":0,"result":"0x3b1a2c"},{"jsonrpc":"2.0","id":1,"result":42}]"#;
+        let (block_number, nonce): (JsonRpcResponse<&str>, JsonRpcResponse<u32>) =
+            parse_batch_response(resp_body.as_bytes()).expect("Valid batch response");
+        assert_eq!(block_number.result, "0x3b1a2c");
+        assert_eq!(nonce.result, 42);
+
+        let out_of_order_body = r#"[{"jsonrpc":"2.0","id":1,"result":"0x2a"},{"jsonrpc":"2.0","id":0,"result":"0x3b1a2c"}]"#;
+        assert_eq!(
+            parse_batch_response::<(JsonRpcResponse<&str>, JsonRpcResponse<&str>)>(
+                out_of_order_body.as_bytes()
+            )
+            .map(|_| ()),
+            Err(JsonRpcBatchError::UnexpectedResponseOrder)
+        );
+        // A failed call has an error instead of a result
+        let error_body = r#"[{"jsonrpc":"2.0","id":0,"result":"0x3b1a2c"},{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"oops"}}]"#;
+        assert_eq!(
+            parse_batch_response::<(JsonRpcResponse<&str>, JsonRpcResponse<&str>)>(
+                error_body.as_bytes()
+            )
+            .map(|_| ()),
+            Err(JsonRpcBatchError::InvalidBody)
+        );
+    }
+}
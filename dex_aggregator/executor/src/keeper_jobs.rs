@@ -0,0 +1,132 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Machine-readable polling jobs for external automation networks. A Gelato checker or
+// Chainlink Automation checkUpkeep can poll get_keeper_jobs, and when can_exec is set, submit
+// each due job's call_data to the contract as is. So keepers can drive execution without
+// knowing anything about ExecutionPlans
+use ink::prelude::{string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::common::MillisSinceEpoch;
+use privadex_common::uuid::Uuid;
+
+pub const STEP_FORWARD_SELECTOR: [u8; 4] = ink::selector_bytes!("execution_plan_step_forward");
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct KeeperJob {
+    pub exec_plan_uuid: Uuid,
+    // 0 if the plan is due regardless of time (its poll schedule was never recorded)
+    pub next_poll_after_millis: MillisSinceEpoch,
+    pub is_due: bool,
+    // SCALE-encoded execution_plan_step_forward message (selector followed by its argument)
+    pub call_data: Vec<u8>,
+}
+
+#[derive(Encode, Decode, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct KeeperJobs {
+    // Whether any job is due now i.e. Gelato's canExec / Chainlink's upkeepNeeded
+    pub can_exec: bool,
+    // Due jobs first, then the rest by when they become due
+    pub jobs: Vec<KeeperJob>,
+}
+
+impl KeeperJobs {
+    // poll_schedule is every active plan with its next_poll_after_millis, if one was recorded
+    pub fn new(
+        poll_schedule: &[(Uuid, Option<MillisSinceEpoch>)],
+        now_millis: MillisSinceEpoch,
+        max_jobs: usize,
+    ) -> Self {
+        let mut jobs: Vec<KeeperJob> = poll_schedule
+            .iter()
+            .map(|(exec_plan_uuid, next_poll_after_millis)| {
+                let next_poll_after_millis = next_poll_after_millis.unwrap_or(0);
+                KeeperJob {
+                    exec_plan_uuid: exec_plan_uuid.clone(),
+                    next_poll_after_millis,
+                    is_due: next_poll_after_millis <= now_millis,
+                    call_data: get_step_forward_call_data(exec_plan_uuid),
+                }
+            })
+            .collect();
+        jobs.sort_by_key(|job| job.next_poll_after_millis);
+        jobs.truncate(max_jobs);
+        Self {
+            can_exec: jobs.iter().any(|job| job.is_due),
+            jobs,
+        }
+    }
+}
+
+pub fn get_step_forward_call_data(exec_plan_uuid: &Uuid) -> Vec<u8> {
+    let mut call_data = STEP_FORWARD_SELECTOR.to_vec();
+    // The message takes the UUID without the 0x prefix
+    String::from(&exec_plan_uuid.to_hex_string()[2..]).encode_to(&mut call_data);
+    call_data
+}
+
+#[cfg(test)]
+mod keeper_jobs_tests {
+    use ink::prelude::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_keeper_jobs() {
+        let poll_schedule = vec![
+            (Uuid::new([1u8; 16]), Some(5_000)),
+            (Uuid::new([2u8; 16]), None),
+            (Uuid::new([3u8; 16]), Some(1_000)),
+        ];
+        let keeper_jobs = KeeperJobs::new(&poll_schedule, 2_000, 10);
+        assert!(keeper_jobs.can_exec);
+        assert_eq!(
+            keeper_jobs
+                .jobs
+                .iter()
+                .map(|job| (job.exec_plan_uuid.clone(), job.is_due))
+                .collect::<Vec<_>>(),
+            vec![
+                (Uuid::new([2u8; 16]), true),
+                (Uuid::new([3u8; 16]), true),
+                (Uuid::new([1u8; 16]), false),
+            ]
+        );
+
+        // Capped at max_jobs, keeping the due ones
+        let keeper_jobs = KeeperJobs::new(&poll_schedule, 2_000, 1);
+        assert_eq!(keeper_jobs.jobs.len(), 1);
+        assert!(keeper_jobs.jobs[0].is_due);
+
+        let keeper_jobs = KeeperJobs::new(&poll_schedule[..1], 2_000, 10);
+        assert!(!keeper_jobs.can_exec);
+    }
+
+    #[test]
+    fn test_step_forward_call_data() {
+        let call_data = get_step_forward_call_data(&Uuid::new([1u8; 16]));
+        assert_eq!(call_data[..4], STEP_FORWARD_SELECTOR);
+        assert_eq!(
+            String::decode(&mut &call_data[4..]).expect("Valid call data"),
+            "01010101010101010101010101010101"
+        );
+    }
+}
@@ -16,68 +16,339 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::vec::Vec;
-use privadex_chain_metadata::common::{SecretKey, UniversalAddress};
+use core::cell::RefCell;
+use ink::prelude::{string::String, vec::Vec};
+use pink_extension::chain_extension::signing;
+use privadex_chain_metadata::{
+    chain_info::AddressType,
+    common::{EthAddress, MillisSinceEpoch, Nonce, SecretKey, UniversalAddress, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+use privadex_common::{
+    signature_scheme::SignatureScheme,
+    utils::{
+        remote_signer_api::RemoteSignerApi, ss58_utils::encode_ss58,
+        threshold_aggregator_api::ThresholdAggregatorApi,
+    },
+};
+
+use crate::concurrency_coordinator::threshold_signing_coordinator::ThresholdSigningCoordinator;
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub struct KeyContainer(pub Vec<AddressKeyPair>);
+pub struct KeyContainer {
+    pairs: Vec<AddressKeyPair>,
+    // Filled in lazily by get_chain_account, so each (chain, address) is derived at most once
+    // per KeyContainer
+    chain_accounts: RefCell<Vec<ChainAccount>>,
+}
 
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub struct AddressKeyPair {
     pub address: UniversalAddress,
-    pub key: SecretKey,
+    pub key: SigningKey,
+    // Substrate accounts can be Sr25519 or Ed25519, and the address alone doesn't tell us which
+    pub sig_scheme: SignatureScheme,
+}
+
+/// A key's address as seen by one chain
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ChainAccount {
+    pub chain_id: UniversalChainId,
+    pub address: UniversalAddress,
+    // The address in the chain's SS58 format (what its RPC methods take). None for Ethereum
+    // addresses and for chains without an SS58 prefix
+    pub ss58_address: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ChainKey<'a> {
+    pub account: ChainAccount,
+    pub key: &'a SigningKey,
+    pub sig_scheme: SignatureScheme,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SigningKey {
+    Local(SecretKey),
+    // The key never leaves the remote signing service (e.g. an HSM)
+    Remote(RemoteSigner),
+    // ECDSA only for now. The escrow key is split among independent workers (e.g. 2-of-3)
+    Threshold(ThresholdSigner),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RemoteSigner {
+    pub api: RemoteSignerApi,
+    // Tells the signing service which of its keys to use
+    pub address: UniversalAddress,
+    // Signing requests are authenticated with the time the KeyContainer was created, so
+    // a KeyContainer should not outlive the contract call that created it
+    pub timestamp_millis: MillisSinceEpoch,
+}
+
+/// One party of a threshold escrow. Each call adds this party's partial signature to the
+/// coordination store and only yields a signature once enough parties have signed the same
+/// message hash, so steps signed this way take several invocations (by different workers) to
+/// get submitted. The step stays NotStarted and keeps its nonce assignment in the meantime.
+/// Parties rebuild the txn every time with a fresh gas price and DEX deadline, so the first
+/// party of a round pins its txn and the others sign that one (see ThresholdRoundTxn)
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ThresholdSigner {
+    // The escrow address that the combined key controls
+    pub address: EthAddress,
+    pub key_share: SecretKey,
+    pub party_index: u8,
+    pub threshold: u8,
+    pub aggregator_api: ThresholdAggregatorApi,
+    pub dynamodb_access_key: String,
+    pub dynamodb_secret_key: String,
+    pub timestamp_millis: MillisSinceEpoch,
+}
+
+// Do not print the key share or DynamoDB credentials
+impl core::fmt::Debug for ThresholdSigner {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "ThresholdSigner(party {} of {}-of-n, {:?})",
+            self.party_index, self.threshold, self.aggregator_api
+        )
+    }
+}
+
+impl SigningKey {
+    /// Signs msg the way sig_scheme expects i.e. Ethereum hashes msg with Keccak-256 first
+    pub fn sign(&self, sig_scheme: SignatureScheme, msg: &[u8]) -> Option<Vec<u8>> {
+        match (self, sig_scheme) {
+            (_, SignatureScheme::Ethereum) => self
+                .sign_prehashed_ecdsa(&sp_core_hashing::keccak_256(msg))
+                .map(|signature| signature.to_vec()),
+            (Self::Local(secret_key), _) => Some(sig_scheme.sign(msg, secret_key)),
+            (Self::Remote(remote_signer), _) => remote_signer.sign(sig_scheme, msg),
+            (Self::Threshold(_), _) => None,
+        }
+    }
+
+    /// Returns r || s || v with v in {0, 1}
+    pub fn sign_prehashed_ecdsa(&self, msg_hash: &[u8; 32]) -> Option<[u8; 65]> {
+        match self {
+            Self::Local(secret_key) => Some(signing::ecdsa_sign_prehashed(secret_key, *msg_hash)),
+            Self::Remote(remote_signer) => remote_signer
+                .sign(SignatureScheme::Ethereum, msg_hash)?
+                .try_into()
+                .ok(),
+            Self::Threshold(threshold_signer) => {
+                threshold_signer.sign_prehashed_ecdsa(msg_hash).ok()
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ThresholdSigningError {
+    // Not enough parties have signed this message hash yet. Not a failure: the other parties
+    // add theirs when they get to the step
+    Pending,
+    SigningFailed,
+}
+
+impl ThresholdSigner {
+    pub fn sign_prehashed_ecdsa(
+        &self,
+        msg_hash: &[u8; 32],
+    ) -> Result<[u8; 65], ThresholdSigningError> {
+        let partial_sig = signing::ecdsa_sign_prehashed(&self.key_share, *msg_hash);
+        let partials = self
+            .coordinator()
+            .add_partial_signature(msg_hash, self.party_index, &partial_sig)
+            .map_err(|_| ThresholdSigningError::SigningFailed)?;
+        if partials.len() < self.threshold as usize {
+            ink::env::debug_println!(
+                "Threshold signature pending: {}/{} partials",
+                partials.len(),
+                self.threshold
+            );
+            return Err(ThresholdSigningError::Pending);
+        }
+        self.aggregator_api
+            .aggregate_ecdsa(msg_hash, &partials)
+            .map_err(|_| ThresholdSigningError::SigningFailed)
+    }
+
+    // Returns the txn pinned for the current round, which another party may have pinned
+    pub fn pin_round_txn(&self, chain_id: u64, nonce: Nonce, round_txn: &[u8]) -> Option<Vec<u8>> {
+        self.coordinator()
+            .pin_round_txn(&self.address, chain_id, nonce, round_txn)
+            .ok()
+    }
+
+    fn coordinator(&self) -> ThresholdSigningCoordinator {
+        ThresholdSigningCoordinator::new(
+            self.dynamodb_access_key.clone(),
+            self.dynamodb_secret_key.clone(),
+            self.timestamp_millis,
+        )
+    }
+}
+
+impl RemoteSigner {
+    fn sign(&self, sig_scheme: SignatureScheme, payload: &[u8]) -> Option<Vec<u8>> {
+        let key: &[u8] = match &self.address {
+            UniversalAddress::Ethereum(eth_addr) => &eth_addr.0,
+            UniversalAddress::Substrate(substrate_pubkey) => &substrate_pubkey.0,
+        };
+        self.api
+            .sign(self.timestamp_millis, key, sig_scheme, payload)
+            .ok()
+    }
 }
 
 impl KeyContainer {
-    pub fn get_key(&self, address: &UniversalAddress) -> Option<&SecretKey> {
-        for pair in self.0.iter() {
+    pub fn new(pairs: Vec<AddressKeyPair>) -> Self {
+        Self {
+            pairs,
+            chain_accounts: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn get_addresses(&self) -> Vec<UniversalAddress> {
+        self.pairs.iter().map(|pair| pair.address.clone()).collect()
+    }
+
+    pub fn get_key(&self, address: &UniversalAddress) -> Option<&SigningKey> {
+        for pair in self.pairs.iter() {
             if pair.address == *address {
                 return Some(&pair.key);
             }
         }
         None
     }
+
+    pub fn get_sig_scheme(&self, address: &UniversalAddress) -> Option<SignatureScheme> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.address == *address)
+            .map(|pair| pair.sig_scheme)
+    }
+
+    /// The first key of the given address type, along with its address on chain_id
+    pub fn get_key_for_chain(
+        &self,
+        chain_id: &UniversalChainId,
+        address_type: AddressType,
+    ) -> Option<ChainKey<'_>> {
+        let pair = self.pairs.iter().find(|pair| {
+            let pair_address_type = match pair.address {
+                UniversalAddress::Ethereum(_) => AddressType::Ethereum,
+                UniversalAddress::Substrate(_) => AddressType::SS58,
+            };
+            pair_address_type == address_type
+        })?;
+        Some(ChainKey {
+            account: self.get_chain_account(chain_id, &pair.address)?,
+            key: &pair.key,
+            sig_scheme: pair.sig_scheme,
+        })
+    }
+
+    /// The key that holds funds on chain_id: the Ethereum key on EVM chains and the Substrate
+    /// key elsewhere (the same rule as proof_of_reserves::get_chain_escrow)
+    pub fn get_escrow_key_for_chain(&self, chain_id: &UniversalChainId) -> Option<ChainKey<'_>> {
+        let address_type = match get_chain_info_from_chain_id(chain_id)?.evm_chain_id {
+            Some(_) => AddressType::Ethereum,
+            None => AddressType::SS58,
+        };
+        self.get_key_for_chain(chain_id, address_type)
+    }
+
+    /// None if we don't hold the key for address or if chain_id is unknown
+    pub fn get_chain_account(
+        &self,
+        chain_id: &UniversalChainId,
+        address: &UniversalAddress,
+    ) -> Option<ChainAccount> {
+        if let Some(account) = self
+            .chain_accounts
+            .borrow()
+            .iter()
+            .find(|account| account.chain_id == *chain_id && account.address == *address)
+        {
+            return Some(account.clone());
+        }
+        if !self.pairs.iter().any(|pair| pair.address == *address) {
+            return None;
+        }
+        let chain_info = get_chain_info_from_chain_id(chain_id)?;
+        let ss58_address = match (address, chain_info.get_ss58_prefix()) {
+            (UniversalAddress::Substrate(substrate_pubkey), Some(ss58_prefix)) => {
+                Some(encode_ss58(&substrate_pubkey.0, u16::from(ss58_prefix)))
+            }
+            _ => None,
+        };
+        let account = ChainAccount {
+            chain_id: *chain_id,
+            address: address.clone(),
+            ss58_address,
+        };
+        self.chain_accounts.borrow_mut().push(account.clone());
+        Some(account)
+    }
 }
 
 #[cfg(test)]
 mod key_container_tests {
     use hex_literal::hex;
 
-    use privadex_chain_metadata::common::{EthAddress, SubstratePublicKey};
+    use privadex_chain_metadata::{
+        common::SubstratePublicKey,
+        registry::chain::universal_chain_id_registry::{ASTAR, ETHEREUM, MOONBEAM, POLKADOT},
+    };
 
     use super::*;
 
     fn create_dummy_keycontainer() -> KeyContainer {
-        KeyContainer {
-            0: vec![
-                AddressKeyPair {
-                    address: UniversalAddress::Ethereum(EthAddress {
-                        0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
-                    }),
-                    key: hex!("ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00"),
-                },
-                AddressKeyPair {
-                    address: UniversalAddress::Substrate(SubstratePublicKey {
-                        0: hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
-                    }),
-                    key: hex!("11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee"),
-                },
-                AddressKeyPair {
-                    address: UniversalAddress::Substrate(SubstratePublicKey {
-                        0: hex!("ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
-                    }),
-                    key: hex!("22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd"),
-                },
-                AddressKeyPair {
-                    address: UniversalAddress::Ethereum(EthAddress {
-                        0: hex!("ff02030405060708090a0b0c0d0e0f1011121314"),
-                    }),
-                    key: hex!("cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33"),
-                },
-            ],
-        }
+        KeyContainer::new(vec![
+            AddressKeyPair {
+                address: UniversalAddress::Ethereum(EthAddress {
+                    0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
+                }),
+                key: SigningKey::Local(hex!(
+                    "ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00"
+                )),
+                sig_scheme: SignatureScheme::Ethereum,
+            },
+            AddressKeyPair {
+                address: UniversalAddress::Substrate(SubstratePublicKey {
+                    0: hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+                }),
+                key: SigningKey::Local(hex!(
+                    "11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee"
+                )),
+                sig_scheme: SignatureScheme::Sr25519,
+            },
+            AddressKeyPair {
+                address: UniversalAddress::Substrate(SubstratePublicKey {
+                    0: hex!("ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+                }),
+                key: SigningKey::Local(hex!(
+                    "22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd"
+                )),
+                sig_scheme: SignatureScheme::Ed25519,
+            },
+            AddressKeyPair {
+                address: UniversalAddress::Ethereum(EthAddress {
+                    0: hex!("ff02030405060708090a0b0c0d0e0f1011121314"),
+                }),
+                key: SigningKey::Local(hex!(
+                    "cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33"
+                )),
+                sig_scheme: SignatureScheme::Ethereum,
+            },
+        ])
     }
 
     #[test]
@@ -89,7 +360,9 @@ mod key_container_tests {
                     0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
                 }))
                 .expect("Key exists"),
-            &hex!("ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00")
+            &SigningKey::Local(hex!(
+                "ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00"
+            ))
         );
         assert_eq!(
             key_container
@@ -97,7 +370,9 @@ mod key_container_tests {
                     0: hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
                 }))
                 .expect("Key exists"),
-            &hex!("11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee")
+            &SigningKey::Local(hex!(
+                "11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee11ee"
+            ))
         );
         assert_eq!(
             key_container
@@ -105,7 +380,9 @@ mod key_container_tests {
                     0: hex!("ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
                 }))
                 .expect("Key exists"),
-            &hex!("22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd")
+            &SigningKey::Local(hex!(
+                "22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd22dd"
+            ))
         );
         assert_eq!(
             key_container
@@ -113,7 +390,9 @@ mod key_container_tests {
                     0: hex!("ff02030405060708090a0b0c0d0e0f1011121314"),
                 }))
                 .expect("Key exists"),
-            &hex!("cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33")
+            &SigningKey::Local(hex!(
+                "cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33cc33"
+            ))
         );
     }
 
@@ -131,4 +410,124 @@ mod key_container_tests {
             }))
             .is_none());
     }
+
+    #[test]
+    fn test_get_sig_scheme() {
+        let key_container = create_dummy_keycontainer();
+        assert_eq!(
+            key_container.get_sig_scheme(&UniversalAddress::Substrate(SubstratePublicKey {
+                0: hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+            })),
+            Some(SignatureScheme::Sr25519)
+        );
+        assert_eq!(
+            key_container.get_sig_scheme(&UniversalAddress::Substrate(SubstratePublicKey {
+                0: hex!("ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+            })),
+            Some(SignatureScheme::Ed25519)
+        );
+        assert_eq!(
+            key_container.get_sig_scheme(&UniversalAddress::Ethereum(EthAddress {
+                0: hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"),
+            })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_key_for_chain() {
+        let key_container = create_dummy_keycontainer();
+        let substrate_pubkey = SubstratePublicKey {
+            0: hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+        };
+        let astar_key = key_container
+            .get_key_for_chain(&ASTAR, AddressType::SS58)
+            .expect("Key exists");
+        assert_eq!(
+            astar_key.account.address,
+            UniversalAddress::Substrate(substrate_pubkey.clone())
+        );
+        assert_eq!(
+            astar_key.account.ss58_address,
+            Some(encode_ss58(&substrate_pubkey.0, 5))
+        );
+        assert_eq!(astar_key.sig_scheme, SignatureScheme::Sr25519);
+
+        let moonbeam_key = key_container
+            .get_key_for_chain(&MOONBEAM, AddressType::Ethereum)
+            .expect("Key exists");
+        assert_eq!(
+            moonbeam_key.account.address,
+            UniversalAddress::Ethereum(EthAddress {
+                0: hex!("0102030405060708090a0b0c0d0e0f1011121314"),
+            })
+        );
+        assert_eq!(moonbeam_key.account.ss58_address, None);
+        assert_eq!(
+            moonbeam_key.key,
+            &SigningKey::Local(hex!(
+                "ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_escrow_key_for_chain() {
+        let key_container = create_dummy_keycontainer();
+        assert_eq!(
+            key_container
+                .get_escrow_key_for_chain(&MOONBEAM)
+                .expect("Key exists")
+                .sig_scheme,
+            SignatureScheme::Ethereum
+        );
+        assert_eq!(
+            key_container
+                .get_escrow_key_for_chain(&ETHEREUM)
+                .expect("Key exists")
+                .sig_scheme,
+            SignatureScheme::Ethereum
+        );
+        let polkadot_key = key_container
+            .get_escrow_key_for_chain(&POLKADOT)
+            .expect("Key exists");
+        assert_eq!(polkadot_key.sig_scheme, SignatureScheme::Sr25519);
+        assert_eq!(
+            polkadot_key.account.ss58_address,
+            Some(encode_ss58(
+                &hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_chain_account_is_cached() {
+        let key_container = create_dummy_keycontainer();
+        let address = UniversalAddress::Substrate(SubstratePublicKey {
+            0: hex!("ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+        });
+        let account = key_container
+            .get_chain_account(&ASTAR, &address)
+            .expect("Key exists");
+        assert_eq!(
+            key_container.get_chain_account(&ASTAR, &address),
+            Some(account)
+        );
+        assert!(key_container
+            .get_chain_account(&POLKADOT, &address)
+            .is_some());
+        assert_eq!(key_container.chain_accounts.borrow().len(), 2);
+
+        // We don't derive accounts for keys we don't hold
+        assert!(key_container
+            .get_chain_account(
+                &ASTAR,
+                &UniversalAddress::Substrate(SubstratePublicKey {
+                    0: hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"),
+                })
+            )
+            .is_none());
+        assert_eq!(key_container.chain_accounts.borrow().len(), 2);
+    }
 }
@@ -19,113 +19,699 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
+pub mod block_number_provider;
 pub mod concurrency_coordinator;
+pub mod destination_guard;
+pub mod destination_proof;
+pub mod escrow_key_sets;
 pub mod eth_utils;
 pub mod executable;
+pub mod executor_config;
 pub mod extrinsic_call_factory;
+pub mod fx_rate;
+pub mod json_rpc_batch;
+pub mod keeper_jobs;
 pub mod key_container;
+pub mod name_resolver;
+pub mod quote_engine;
+pub mod rpc_endpoints;
+pub mod screening_api;
+pub mod storage_credentials;
+pub mod substrate_proxy;
 pub mod substrate_utils;
+pub mod token_list;
 
 #[pink_extension::contract(env=PinkEnvironment)]
 mod privadex_phat {
-    use ink_env::debug_println;
-    use ink_prelude::{
+    use ink::env::debug_println;
+    use ink::prelude::{
         string::{String, ToString},
         vec,
         vec::Vec,
     };
-    use ink_storage::traits::SpreadAllocate;
+    use ink::storage::{Lazy, Mapping};
     use pink_extension::PinkEnvironment;
     use scale::{Decode, Encode};
     use sp_core::Pair;
 
     use privadex_chain_metadata::{
+        chain_info::{AssetTxPaymentKind, ChainInfo, DepositForwarderFactory},
         common::{
-            Amount, BlockNum, EthAddress, EthTxnHash, MillisSinceEpoch, SecretKey,
+            Amount, AssetId, BlockNum, EthAddress, EthTxnHash, MillisSinceEpoch, SecretKey,
             SubstratePublicKey, UniversalAddress, UniversalChainId, UniversalTokenId,
         },
         get_chain_info_from_chain_id,
-        registry::chain::universal_chain_id_registry,
+        registry::{
+            chain::universal_chain_id_registry,
+            dex::pool_blocklist_overlay::{BlockedPool, PoolBlocklistOverlay},
+            token::{
+                token_list_overlay::{ListedToken, TokenListOverlay},
+                xc20_registry_overlay::{DiscoveredXC20, XC20RegistryOverlay},
+            },
+        },
     };
     use privadex_common::{
-        utils::general_utils::{hex_string_to_vec, slice_to_hex_string},
+        signature_scheme::SignatureScheme,
+        utils::{
+            general_utils::{hex_string_to_vec, slice_to_hex_string},
+            http_request::http_post_wrapper,
+            remote_signer_api::RemoteSignerApi,
+            ss58_utils::{decode_ss58, encode_ss58, h160_to_mapped_ss58},
+            threshold_aggregator_api::ThresholdAggregatorApi,
+        },
         uuid::Uuid,
     };
-    use privadex_execution_plan::execution_plan::{
-        EthPendingTxnId, EthStepStatus, ExecutionPlan, ExecutionStepEnum,
+    use privadex_execution_plan::{
+        deposit_address::{get_deposit_remark, DepositRemark},
+        execution_plan::{
+            CommonExecutionMeta, ContractCall, EthPendingTxnId, EthStepStatus, ExecutionPlan,
+            ExecutionPolicy, ExecutionStep, ExecutionStepEnum, PlanReviewStatus,
+            RemarkDepositStatus, ResolvedDestinationName, SubstratePendingRemarkId,
+            SubstrateRemarkDepositStep, MAX_PLAN_METADATA_LEN,
+        },
+        graph_solution_to_execution_plan::converter::{
+            set_contract_call_delivery, set_plan_escrow,
+        },
+    };
+    use privadex_routing::{
+        graph::fee_breakdown::FeeBreakdown, graph_snapshot::GraphSnapshot,
+        liquidity_summary::LiquiditySummary, routing_config::RoutingConfig,
     };
-    use privadex_routing::{graph::graph::GraphSolution, graph_builder, smart_order_router};
 
-    use crate::concurrency_coordinator::execution_plan_assigner::ExecutionPlanAssigner;
+    use crate::block_number_provider::{
+        get_finality_policy, BestBlockTier, BlockNumberProvider, FinalityPolicy,
+    };
+    use crate::concurrency_coordinator::{
+        address_alias_registry::AddressAliasRegistry,
+        delivery_approval_registry::{DeliveryApprovalRegistry, DeliveryApprovalRegistryError},
+        execution_plan_assigner::{DueExecPlan, ExecPlanClaim, ExecutionPlanAssigner},
+        nonce_manager::{DeploymentEpoch, NonceManager, NonceManagerError},
+        plan_tag_index::PlanTagIndex,
+        relayer_registry::{RelayerContribution, RelayerRegistry},
+        sponsorship_registry::{SponsorshipRegistry, SponsorshipRegistryError},
+        threshold_signing_coordinator::MAX_THRESHOLD_PARTIES,
+        worker_identity::WorkerIdentity,
+    };
+    use crate::destination_guard::{check_destination_address, DestinationGuardError};
+    use crate::destination_proof::{get_destination_challenge, DestinationProof};
+    use crate::escrow_key_sets::{
+        add_escrow_key_set, find_escrow_key_set, find_plan_escrow_key_set, EscrowAccount,
+        EscrowKeySet, EscrowKeySetError, MAX_ESCROW_KEY_SETS,
+    };
+    use crate::eth_utils::{common::get_code, dex_router_contract::DEXRouterContract};
     use crate::executable::{
-        executable_step::TXN_NUM_BLOCKS_ALIVE,
+        allowance_hygiene::{RouterAllowance, MILLIS_PER_DAY},
+        canaries::{
+            create_canary_plan, is_canary_run_closed, set_canary_prestart, Canary, CanaryResult,
+            MAX_CANARIES,
+        },
+        chain_preflight::{run_chain_preflight, ChainPreflightReport},
+        clock_skew::{compute_skew_millis, ChainClockSkew, SKEW_SAMPLED_CHAINS},
+        drain_mode::has_step_in_flight,
+        dust_consolidation::{get_spare_holdings, plan_consolidation, ConsolidationReport},
         execute_step_meta::ExecuteStepMeta,
+        extrinsic_batch::MAX_BATCH_EXEC_PLANS,
+        failure_bundle::{get_bundled_txns, get_step_addresses, BundledConfig, FailureBundle},
+        gas_ceiling::{flag_if_over_gas_ceiling, get_realized_gas_fee_usd},
+        manual_override::{
+            force_step_status, is_valid_justification, ManualOverrideError, ManualTxnId,
+        },
+        nonce_pool::MAX_NONCE_POOL_SIZE,
+        paper_trade::PaperTxn,
+        payment_request::{InvoiceId, InvoiceStatus, PaymentRequest},
+        plan_analytics::{
+            aggregate_plan_analytics, get_day, DailyStats, DaysSinceEpoch, DexVolumeReport,
+            ExecutionPrice, PlanAnalytics, StatsReport,
+        },
+        plan_events::{
+            get_step_statuses, get_step_transition_events, get_txn_broadcast_events, PlanEvent,
+            PlanEventKind,
+        },
+        plan_merge::merge_exec_plans,
+        plan_public_view::{get_plan_owner_challenge, get_plan_public_view, is_plan_owner},
+        poll_schedule::get_next_poll_after_millis,
+        presign::{is_presignable_route, presign_static_txns, MAX_PRESIGN_ROUTE_STEPS},
+        proof_of_reserves::{
+            get_chain_escrow, get_plan_chains, get_plan_liabilities, get_shortfall_chains,
+            get_stranded_liabilities, to_alert_webhook_body, ProofOfReserves, ReserveShortfall,
+        },
+        registry_check::{verify_chain_registry, RegistryReport},
+        relayer_rewards::{RelayerPayout, RelayerRewardRate},
+        remark_deposit::{
+            get_remark_text, DepositInstructions, RemarkDepositRequest,
+            REMARK_DEPOSIT_NUM_BLOCKS_ALIVE,
+        },
+        route_blacklist::{RouteBlacklist, RouteSegment},
+        router_allowlist::{
+            check_dex_routers, get_plan_dex_routers, AllowedRouter, RouterCheckError,
+            MAX_ROUTER_ALLOWLIST_LEN,
+        },
+        shadow_quotes::{
+            aggregate_shadow_quote_results, get_archive_slot, get_candidate_archive_slots,
+            get_shadow_amount_out, pick_archive, ArchiveSlot, ArchivedPairCache, ShadowQuoteResult,
+            ShadowQuoteStats, MAX_PENDING_SHADOW_QUOTES, MAX_SHADOW_QUOTES_PER_RUN,
+        },
+        sponsorship::{
+            get_fee_breakdown_chains, get_sponsored_usd, set_plan_sponsorship, waive_protocol_fee,
+            SponsorshipCampaign, MAX_SPONSORSHIP_CAMPAIGNS,
+        },
+        step_timing::{CompletionEstimate, SwapStatus},
+        stranded_funds::StrandedBalance,
+        stuck_plans::{escalate_stuck_plan, find_stuck_plan, to_webhook_body, StuckPlan},
         traits::{Executable, ExecutableError, ExecutableSimpleStatus},
     };
-    use crate::key_container::{AddressKeyPair, KeyContainer};
-    use crate::substrate_utils::node_rpc_utils::SubstrateNodeRpcUtils;
+    use crate::executor_config::ExecutorConfig;
+    use crate::fx_rate::{FxRate, FxRateApi, ReferenceAmounts, ReferenceCurrencyConfig};
+    use crate::keeper_jobs::KeeperJobs;
+    use crate::key_container::{
+        AddressKeyPair, KeyContainer, RemoteSigner, SigningKey, ThresholdSigner,
+    };
+    use crate::name_resolver::{normalize_name, resolve_name, NameResolver};
+    use crate::quote_engine::{
+        get_snapshot_id, set_integrator_markup, set_wrapped_delivery, set_zap_in_delivery,
+        validate_integrator_markup, QuoteContext, QuoteEngine, QuoteEngineError, QuoteResponse,
+        SnapshotId, VenueQuote, QUOTE_CHAIN_IDS,
+    };
+    use crate::rpc_endpoints::{get_rpc_url, RpcEndpoint, RpcEndpointError};
+    use crate::screening_api::ScreeningApi;
+    use crate::storage_credentials::{
+        resolve_storage_credential, set_storage_credential, StorageCredential, StorageCredentials,
+        StorageFunction,
+    };
+    use crate::substrate_proxy::{SubstrateProxy, SubstrateProxyError};
+    use crate::substrate_utils::{
+        asset_registry_utils::SubstrateAssetRegistryUtils, node_rpc_utils::SubstrateNodeRpcUtils,
+    };
+    use crate::token_list::{check_token_list, fetch_token_list, TokenListError};
 
     type Result<T> = core::result::Result<T, Error>;
     type HexStrNo0x = String;
 
+    // Each day is a separate S3 object, so this bounds the number of requests in get_stats
+    const MAX_STATS_NUM_DAYS: u32 = 31;
+
+    // Keeps the allowlist cheap to read in every start_swap
+    const MAX_DESTINATION_ALLOWLIST_LEN: usize = 64;
+    // The integrator accounts are read in every start_swap too
+    const MAX_INTEGRATOR_ACCOUNTS: usize = 64;
+
+    // Every graph build filters on it, so keep it short
+    const MAX_POOL_BLOCKLIST_LEN: usize = 128;
+
+    // Every quote reads the discovered XC20s. Moonbeam and Astar each have ~100 today
+    const MAX_DISCOVERED_XC20S_PER_CHAIN: usize = 512;
+
+    // blake2_256 of an integrator's API key. We never store the raw key
+    type ApiKeyHash = [u8; 32];
+
+    // Bump this when a release needs migrate_storage to run after upgrade. New fields must not
+    // change the encoding of the root struct (put them in a Lazy or Mapping), or else the
+    // upgraded code can't decode the existing storage
+    const STORAGE_VERSION: u16 = 3;
+
     #[ink(storage)]
-    #[derive(SpreadAllocate)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub struct PrivaDex {
         admin: AccountId,
         escrow_eth_private_key: Option<SecretKey>,
         escrow_substrate_private_key: Option<SecretKey>,
+        // Sr25519 unless set otherwise in init_secret_keys
+        escrow_substrate_sig_scheme: SignatureScheme,
         dynamodb_access_key: Option<String>,
         dynamodb_secret_key: Option<String>,
         s3_access_key: Option<String>,
         s3_secret_key: Option<String>,
+        // Integrators with an entry here can only start swaps that deliver to one of its
+        // addresses. API keys without an entry are unrestricted
+        destination_allowlists: Mapping<ApiKeyHash, Vec<EthAddress>>,
+        // If set, start_swap refuses to create a plan unless this endpoint clears the src and
+        // dest addresses (see ScreeningApi)
+        screening_url: Option<String>,
+        // Lets self-hosted operators turn screening off without losing the configured URL
+        bypass_screening: bool,
+        // If set, escrow keys live in an HSM behind this signing service and the
+        // escrow_*_private_key fields stay empty
+        remote_signer_url: Option<String>,
+        remote_signer_auth_secret: Option<String>,
+        remote_signer_eth_address: Option<[u8; 20]>,
+        remote_signer_substrate_pubkey: Option<[u8; 32]>,
+        // If set, the escrow Eth key is split among independent workers and this worker only
+        // holds one share of it (see ThresholdSigner)
+        threshold_aggregator_url: Option<String>,
+        threshold_key_share: Option<SecretKey>,
+        threshold_party_index: u8,
+        threshold: u8,
+        threshold_eth_address: Option<[u8; 20]>,
+        // Empty on instances from before upgrades were supported (storage version 1)
+        storage_version: Lazy<u16>,
+        // If set, scan_stuck_plans posts the plans it flags here
+        stuck_plan_webhook_url: Lazy<Option<String>>,
+        // Tried in order to resolve a destination name passed to start_swap. Empty (the
+        // default) means start_swap only accepts addresses
+        name_resolvers: Lazy<Vec<NameResolver>>,
+        // Number of nonces a worker reserves at a time per (chain, signer). Empty or 0 (the
+        // default) means every step gets its nonce from the NonceManager on its own
+        nonce_pool_size: Lazy<u8>,
+        // Per-chain overrides of the registry's rpc_url. The API keys in here are never returned
+        // by a message
+        rpc_endpoints: Lazy<Vec<RpcEndpoint>>,
+        // Chains (with pallet-asset-tx-payment) whose extrinsic fees the escrow pays in a
+        // pallet-assets asset instead of the native token
+        fee_assets: Lazy<Vec<(UniversalChainId, AssetId)>>,
+        // Per chain, how much native token the escrow must hold on top of what it owes users.
+        // reconcile_reserves pauses new swaps on a chain that falls below it
+        insurance_buffers: Lazy<Vec<(UniversalChainId, Amount)>>,
+        // If set, reconcile_reserves posts the shortfalls it finds here
+        reserve_alert_webhook_url: Lazy<Option<String>>,
+        // Chains where small plans step forward on the best block instead of the finalized
+        // one. Empty (the default) means every plan waits for finality
+        best_block_tiers: Lazy<Vec<BestBlockTier>>,
+        // Namespaces the NonceManagers' DynamoDB keys. Empty on instances deployed before
+        // epochs, which keep using the un-namespaced keys
+        deployment_epoch: Lazy<DeploymentEpoch>,
+        // If set, swaps are only started to destinations that proved control of their key with
+        // prove_destination. Empty (the default) means any destination is accepted
+        require_destination_proof: Lazy<bool>,
+        // Contracts that start_swap_and_call may deliver to. The escrow makes the call, so
+        // this can't be open to any contract
+        contract_call_targets: Lazy<Vec<(UniversalChainId, EthAddress)>>,
+        // If set, a plan stops stepping forward once the escrow has spent more than this many
+        // bps of its quoted input value on gas, until the admin resolves it
+        gas_ceiling_bps: Lazy<Option<u32>>,
+        // If set, plans quoted above this ($ x 10^18), and plans with no quote, only deliver
+        // once the admin or a delivery approver calls approve_delivery on them
+        delivery_approval_threshold_usd: Lazy<Option<Amount>>,
+        delivery_approvers: Lazy<Vec<AccountId>>,
+        // If set, workers accrue rewards for the steps they advance, claimable with
+        // claim_relayer_rewards. Empty (the default) means nothing is tracked
+        relayer_reward_rate: Lazy<Option<RelayerRewardRate>>,
+        // If set, quote and get_execution_price also report their USD amounts in this currency
+        reference_currency: Lazy<Option<ReferenceCurrencyConfig>>,
+        // Plans whose route is at most this many EVM steps get their static txns signed in
+        // start_swap. Empty or 0 (the default) means every txn is signed when its step runs
+        presign_max_steps: Lazy<u8>,
+        // Empty (the default) means ExecutorConfig::default() and RoutingConfig::default()
+        executor_config: Lazy<ExecutorConfig>,
+        routing_config: Lazy<RoutingConfig>,
+        // DEX pools that quotes and new plans never route through. Empty (the default) blocks none
+        pool_blocklist: Lazy<PoolBlocklistOverlay>,
+        // DEX routers whose approvals revoke_idle_allowances keeps however long they go unused.
+        // Empty (the default) exempts none
+        allowance_exempt_routers: Lazy<Vec<(UniversalChainId, EthAddress)>>,
+        // DEX routers new plans may swap through on top of the DEX registry's. Empty (the
+        // default) allows only the registry's
+        router_allowlist: Lazy<Vec<AllowedRouter>>,
+        // Integrators' own escrow accounts (see escrow_key_sets). Plans started without an
+        // escrow name use the shared escrow above
+        escrow_key_sets: Lazy<Vec<EscrowKeySet>>,
+        // The escrow names each API key may pass to start_swap. API keys without an entry can
+        // only use the shared escrow
+        api_key_escrows: Mapping<ApiKeyHash, Vec<String>>,
+        // Functions with their own storage credential pair. The others use the shared DynamoDB
+        // and S3 pairs above
+        storage_credentials: Lazy<Vec<(StorageFunction, StorageCredential)>>,
+        // Escrow accounts that are only acted for through a proxy, e.g. a cold account whose
+        // proxy is the escrow's Substrate key. Empty (the default) signs as the escrow directly
+        substrate_proxies: Lazy<Vec<SubstrateProxy>>,
+        // Routes that run_canaries checks with small swaps of the shared escrow's own funds.
+        // Empty (the default) runs none
+        canaries: Lazy<Vec<Canary>>,
+        // Campaigns that pay for eligible swaps (see start_sponsored_swap). Their budgets'
+        // drawdown is kept in DynamoDB
+        sponsorship_campaigns: Lazy<Vec<SponsorshipCampaign>>,
+        // Per chain, the factory whose forwarders give each plan its own deposit address (see
+        // set_deposit_forwarder_factory). Overrides the registry's, which has none by default
+        deposit_forwarder_factories: Lazy<Vec<(UniversalChainId, DepositForwarderFactory)>>,
+        // Accounts that always start swaps under an API key (see set_integrator_account), so an
+        // integrator can't leave its key out to get around its destination allowlist. A key
+        // bound here can only be passed by its accounts
+        integrator_accounts: Lazy<Vec<(AccountId, ApiKeyHash)>>,
+        // The imported token list (see import_token_list). Kept here rather than in S3 since
+        // every quote reads it. Empty (the default) adds nothing to the registry
+        token_list: Lazy<TokenListOverlay>,
+        // The XC20s found in each chain's asset registry (see discover_xc20_tokens). Quotes
+        // treat them like token list entries, so the list's symbols take priority
+        xc20_registry: Lazy<XC20RegistryOverlay>,
     }
 
     #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         AlreadyInitialized,
+        ApiKeyNotBoundToCaller,
+        ContractCallTargetHasNoCode,
+        ContractCallTargetNotAllowed,
         DbRequestFailed,
+        DeliveryAlreadyApproved,
+        DeliveryApprovalFailed,
+        DepositForwarderFactoryHasNoCode,
+        DestinationAllowlistTooLong,
+        DestinationIsPrecompile,
+        DestinationIsTokenContract,
+        DestinationIsZeroAddress,
+        DestinationNameNotFound,
+        DestinationNotAllowed,
+        DestinationNotProven,
+        DestTokenNotNative,
+        DexRouterFactoryMismatch,
+        DexRouterNotAllowed,
+        EscrowKeySetAlreadyExists,
+        EscrowNotAllowed,
+        EscrowNotFound,
+        EscrowNotSupportedOnChain,
         ExecutionPlanClaimedByAnotherWorker,
+        ExecutionPlanFromNewerBuild,
+        ExecutionPlanQuarantined,
+        FailedToAlertOperator,
         FailedToCreateExecutionPlan,
         FailedToCreateGraph,
+        FailedToGetTokenDecimals,
+        FailedToPullAllowanceCache,
+        FailedToPullBridgeFeeCalibration,
+        FailedToPullCanaryLedger,
+        FailedToPullChainClockSkews,
         FailedToPullExecutionPlan,
+        FailedToPullPaperTxns,
+        FailedToPullPausedChains,
+        FailedToPullPlanAnalytics,
+        FailedToPullRouteBlacklist,
+        FailedToPullStepDurations,
+        FailedToPullStrandedFundsLedger,
+        FailedToSaveAllowanceCache,
+        FailedToSaveCanaryLedger,
+        FailedToSaveChainClockSkews,
+        FailedToSaveDestinationProof,
+        FailedToSaveFailureBundle,
         FailedToSaveExecutionPlan,
+        FailedToSavePairCache,
+        FailedToSavePausedChains,
+        FailedToSavePaymentRequest,
+        FailedToSavePlanAnalytics,
+        // The plan stepped forward, but its events could not be saved, so its audit trail has a
+        // gap from this pass on
+        FailedToSavePlanEvents,
+        FailedToSaveRelayerPayout,
+        FailedToSaveRemarkDeposit,
+        FailedToSaveRouteBlacklist,
+        FailedToSaveStrandedFundsLedger,
+        NameResolutionFailed,
         NoPathFound,
         NoPermissions,
+        NoRelayerRewards,
+        NoStablecoinOnNetwork,
+        NoWrappedNativeToken,
+        NonceMigrationFailed,
+        NonceReservationsInFlight,
+        PlanAnalyticsClaimedByAnotherWorker,
+        PlanDoesNotNeedReview,
+        PlanMetadataTooLong,
+        PlanNeedsReview,
+        PlanNotSettled,
+        PlanTagLookupFailed,
+        PoolBlocklistTooLong,
         PrestartTxnIsAlreadyUsed,
+        QuoteBelowInvoiceAmount,
+        RelayerClaimFailed,
+        RelayerRewardsDisabled,
+        NotEligibleForSponsorship,
+        SponsorshipBudgetExhausted,
+        SponsorshipCampaignNotFound,
+        RemarkDepositNotFound,
+        RemarkDepositsUnsupported,
+        RouterAllowlistTooLong,
         InvalidAddress,
+        InvalidCanary,
+        InvalidSponsorshipCampaign,
+        InvalidContractCall,
+        InvalidDeploymentEpoch,
+        InvalidDestinationName,
+        InvalidDestinationProof,
+        InvalidOwnerProof,
+        InvalidEscrowName,
+        Ss58PrefixMismatch,
         InvalidNumber,
+        InvalidOverrideJustification,
+        InvalidPayoutToken,
+        InvalidPlanMetadata,
         InvalidExecutionPlanUuid,
+        InvalidExecutorConfig,
+        InvalidRoutingConfig,
+        InvalidRpcEndpoint,
+        InvalidSubstrateProxy,
         InvalidUserToEscrowTxn,
         InvalidHexAddrString,
+        InvalidIntegratorMarkup,
+        InvalidThresholdConfig,
+        // The pair to zap into doesn't hold the dest token, or isn't from a DEX we know of
+        InvalidZapIn,
+        // The LP tokens to zap out of aren't from a DEX we know of, or one of the pair's tokens
+        // is the dest token
+        InvalidLpSource,
+        InvalidTokenList,
+        InvalidTxnId,
+        InvoiceExpired,
+        InvoiceNotFound,
+        InvoiceNotPayable,
+        LiquiditySummaryNotFound,
+        AmountInBelowMinimumUsd,
+        AmountInAboveMaximumUsd,
+        AmountInDoesNotCoverFees,
+        AmountOutNotReachable,
+        AddressFailedScreening,
+        BlockedPool,
+        ChainPaused,
+        ScreeningRequestFailed,
         InvalidTokenString,
         RpcRequestFailed,
+        SnapshotNotFound,
         StepForwardFailed(ExecutableError),
+        StepNotFound,
+        StepNotInProgress,
+        StrandedFundsLedgerClaimedByAnotherWorker,
+        CanaryLedgerClaimedByAnotherWorker,
+        CanaryNotFound,
+        TokenListRequestFailed,
+        TokenListTooLong,
+        TooManyCanaries,
+        TooManySponsorshipCampaigns,
+        TooManyEscrowKeySets,
+        TooManyExecutionPlans,
+        TooManyIntegratorAccounts,
+        TooManyTranches,
         UninitializedEscrow,
+        UnknownToken,
+        UnknownTokenSymbol,
+        UnsupportedFeeAsset,
         UnsupportedNetwork,
+        UnsupportedSignatureScheme,
+        UnsupportedStepOverride,
+        UnsupportedStorageVersion,
+        UpgradeFailed,
+        WorkersDraining,
+        TooManyDiscoveredXC20s,
+        XC20DiscoveryFailed,
+        TestnetQuotesUnsupported,
+    }
+
+    impl From<QuoteEngineError> for Error {
+        fn from(error: QuoteEngineError) -> Self {
+            match error {
+                QuoteEngineError::AmountInAboveMaximumUsd => Self::AmountInAboveMaximumUsd,
+                QuoteEngineError::AmountInBelowMinimumUsd => Self::AmountInBelowMinimumUsd,
+                QuoteEngineError::AmountInDoesNotCoverFees => Self::AmountInDoesNotCoverFees,
+                QuoteEngineError::AmountOutNotReachable => Self::AmountOutNotReachable,
+                QuoteEngineError::BlockedPool => Self::BlockedPool,
+                QuoteEngineError::DestTokenNotNative => Self::DestTokenNotNative,
+                QuoteEngineError::FailedToCreateExecutionPlan => Self::FailedToCreateExecutionPlan,
+                QuoteEngineError::FailedToCreateGraph => Self::FailedToCreateGraph,
+                QuoteEngineError::InvalidHexAddrString => Self::InvalidHexAddrString,
+                QuoteEngineError::InvalidIntegratorMarkup => Self::InvalidIntegratorMarkup,
+                QuoteEngineError::InvalidLpSource => Self::InvalidLpSource,
+                QuoteEngineError::InvalidNumber => Self::InvalidNumber,
+                QuoteEngineError::InvalidTokenString => Self::InvalidTokenString,
+                QuoteEngineError::InvalidZapIn => Self::InvalidZapIn,
+                QuoteEngineError::NoPathFound => Self::NoPathFound,
+                QuoteEngineError::NoStablecoinOnNetwork => Self::NoStablecoinOnNetwork,
+                QuoteEngineError::NoWrappedNativeToken => Self::NoWrappedNativeToken,
+                QuoteEngineError::RpcRequestFailed => Self::RpcRequestFailed,
+                QuoteEngineError::TestnetQuotesUnsupported => Self::TestnetQuotesUnsupported,
+                QuoteEngineError::TooManyTranches => Self::TooManyTranches,
+                QuoteEngineError::UnknownToken => Self::UnknownToken,
+                QuoteEngineError::UnknownTokenSymbol => Self::UnknownTokenSymbol,
+                QuoteEngineError::UnsupportedNetwork => Self::UnsupportedNetwork,
+            }
+        }
+    }
+
+    impl From<EscrowKeySetError> for Error {
+        fn from(error: EscrowKeySetError) -> Self {
+            match error {
+                EscrowKeySetError::AlreadyExists => Self::EscrowKeySetAlreadyExists,
+                EscrowKeySetError::InvalidName => Self::InvalidEscrowName,
+                EscrowKeySetError::TooMany => Self::TooManyEscrowKeySets,
+            }
+        }
+    }
+
+    impl From<NonceManagerError> for Error {
+        fn from(error: NonceManagerError) -> Self {
+            match error {
+                NonceManagerError::ReservationsInFlight => Self::NonceReservationsInFlight,
+                NonceManagerError::SameDeploymentEpoch => Self::InvalidDeploymentEpoch,
+                NonceManagerError::ConditionalCheckFailed
+                | NonceManagerError::UnexpectedDeserializationError
+                | NonceManagerError::UnlikelyAllNonceGettersFailed
+                | NonceManagerError::UnverifiedReservation
+                | NonceManagerError::UpdateFailed => Self::NonceMigrationFailed,
+            }
+        }
+    }
+
+    impl From<DestinationGuardError> for Error {
+        fn from(error: DestinationGuardError) -> Self {
+            match error {
+                DestinationGuardError::Precompile => Self::DestinationIsPrecompile,
+                DestinationGuardError::TokenContract => Self::DestinationIsTokenContract,
+                DestinationGuardError::ZeroAddress => Self::DestinationIsZeroAddress,
+            }
+        }
+    }
+
+    impl From<RpcEndpointError> for Error {
+        fn from(error: RpcEndpointError) -> Self {
+            match error {
+                RpcEndpointError::InvalidApiKey
+                | RpcEndpointError::InvalidUrl
+                | RpcEndpointError::MissingApiKey => Self::InvalidRpcEndpoint,
+                RpcEndpointError::UnsupportedNetwork => Self::UnsupportedNetwork,
+            }
+        }
+    }
+
+    impl From<SubstrateProxyError> for Error {
+        fn from(error: SubstrateProxyError) -> Self {
+            match error {
+                SubstrateProxyError::SameRealAndDelegate => Self::InvalidSubstrateProxy,
+                SubstrateProxyError::UnsupportedNetwork => Self::UnsupportedNetwork,
+            }
+        }
+    }
+
+    impl From<TokenListError> for Error {
+        fn from(error: TokenListError) -> Self {
+            match error {
+                TokenListError::InvalidToken | TokenListError::ParseFailed => {
+                    Self::InvalidTokenList
+                }
+                TokenListError::RequestFailed | TokenListError::UnsupportedUrl => {
+                    Self::TokenListRequestFailed
+                }
+                TokenListError::TooManyTokens => Self::TokenListTooLong,
+            }
+        }
+    }
+
+    impl From<ManualOverrideError> for Error {
+        fn from(error: ManualOverrideError) -> Self {
+            match error {
+                ManualOverrideError::StepNotFound => Self::StepNotFound,
+                ManualOverrideError::StepNotInProgress => Self::StepNotInProgress,
+                ManualOverrideError::UnsupportedOverride => Self::UnsupportedStepOverride,
+            }
+        }
     }
 
     impl PrivaDex {
         #[ink(constructor)]
         pub fn new() -> Self {
-            let admin = Self::env().caller();
-            ink_lang::utils::initialize_contract(|this: &mut Self| {
-                this.admin = admin;
-                this.escrow_eth_private_key = None;
-                this.escrow_substrate_private_key = None;
-                this.dynamodb_access_key = None;
-                this.dynamodb_secret_key = None;
-                this.s3_access_key = None;
-                this.s3_secret_key = None;
-            })
+            let mut contract = Self {
+                admin: Self::env().caller(),
+                escrow_eth_private_key: None,
+                escrow_substrate_private_key: None,
+                escrow_substrate_sig_scheme: SignatureScheme::Sr25519,
+                dynamodb_access_key: None,
+                dynamodb_secret_key: None,
+                s3_access_key: None,
+                s3_secret_key: None,
+                destination_allowlists: Mapping::default(),
+                screening_url: None,
+                bypass_screening: false,
+                remote_signer_url: None,
+                remote_signer_auth_secret: None,
+                remote_signer_eth_address: None,
+                remote_signer_substrate_pubkey: None,
+                threshold_aggregator_url: None,
+                threshold_key_share: None,
+                threshold_party_index: 0,
+                threshold: 0,
+                threshold_eth_address: None,
+                storage_version: Lazy::new(),
+                stuck_plan_webhook_url: Lazy::new(),
+                name_resolvers: Lazy::new(),
+                nonce_pool_size: Lazy::new(),
+                rpc_endpoints: Lazy::new(),
+                fee_assets: Lazy::new(),
+                insurance_buffers: Lazy::new(),
+                reserve_alert_webhook_url: Lazy::new(),
+                best_block_tiers: Lazy::new(),
+                deployment_epoch: Lazy::new(),
+                require_destination_proof: Lazy::new(),
+                contract_call_targets: Lazy::new(),
+                gas_ceiling_bps: Lazy::new(),
+                delivery_approval_threshold_usd: Lazy::new(),
+                delivery_approvers: Lazy::new(),
+                relayer_reward_rate: Lazy::new(),
+                reference_currency: Lazy::new(),
+                presign_max_steps: Lazy::new(),
+                executor_config: Lazy::new(),
+                routing_config: Lazy::new(),
+                pool_blocklist: Lazy::new(),
+                allowance_exempt_routers: Lazy::new(),
+                router_allowlist: Lazy::new(),
+                escrow_key_sets: Lazy::new(),
+                api_key_escrows: Mapping::default(),
+                storage_credentials: Lazy::new(),
+                substrate_proxies: Lazy::new(),
+                canaries: Lazy::new(),
+                sponsorship_campaigns: Lazy::new(),
+                deposit_forwarder_factories: Lazy::new(),
+                integrator_accounts: Lazy::new(),
+                token_list: Lazy::new(),
+                xc20_registry: Lazy::new(),
+            };
+            contract.storage_version.set(&STORAGE_VERSION);
+            // Upgrades keep the epoch, so only a redeployment gets a new one
+            contract
+                .deployment_epoch
+                .set(&Self::env().block_timestamp());
+            contract
+        }
+
+        /// Redeploys a contract that was built against ink! 3, whose storage this build cannot
+        /// read. Carries over the non-secret config; the caller becomes admin and re-sends the
+        /// keys through the init_* messages as on a fresh deployment
+        #[ink(constructor)]
+        pub fn migrate(
+            screening_url: Option<String>,
+            bypass_screening: bool,
+            // (api_key, dest_eth_addrs) as passed to set_destination_allowlist
+            destination_allowlists: Vec<(String, Vec<HexStrNo0x>)>,
+        ) -> Result<Self> {
+            let mut contract = Self::new();
+            contract.set_screening_config(screening_url, bypass_screening)?;
+            for (api_key, dest_eth_addrs) in destination_allowlists {
+                contract.set_destination_allowlist(api_key, dest_eth_addrs)?;
+            }
+            Ok(contract)
         }
 
         #[ink(message)]
         pub fn init_secret_keys(
             &mut self,
-            escrow_eth_private_key: HexStrNo0x, // hex string WITHOUT 0x e.g. abcdef...
-            escrow_substrate_private_key: HexStrNo0x,
+            // hex string WITHOUT 0x e.g. abcdef... Pass None if using init_remote_signer
+            escrow_eth_private_key: Option<HexStrNo0x>,
+            escrow_substrate_private_key: Option<HexStrNo0x>,
+            escrow_substrate_sig_scheme: SignatureScheme, // Sr25519 or Ed25519
             dynamodb_access_key: String,
             dynamodb_secret_key: String,
             s3_secret_key: String,
@@ -134,14 +720,21 @@ mod privadex_phat {
             if Self::env().caller() != self.admin {
                 return Err(Error::NoPermissions);
             }
-            if self.escrow_eth_private_key.is_some() {
+            if self.dynamodb_access_key.is_some() {
                 return Err(Error::AlreadyInitialized);
             }
-            let eth_secret: SecretKey = io_helper::hex_str_to_u8_32(&escrow_eth_private_key)?;
-            let substrate_secret: SecretKey =
-                io_helper::hex_str_to_u8_32(&escrow_substrate_private_key)?;
-            self.escrow_eth_private_key = Some(eth_secret);
-            self.escrow_substrate_private_key = Some(substrate_secret);
+            self.escrow_eth_private_key = match escrow_eth_private_key {
+                Some(privkey) => Some(io_helper::hex_str_to_u8_32(&privkey)?),
+                None => None,
+            };
+            self.escrow_substrate_private_key = match escrow_substrate_private_key {
+                Some(privkey) => Some(io_helper::hex_str_to_u8_32(&privkey)?),
+                None => None,
+            };
+            if escrow_substrate_sig_scheme == SignatureScheme::Ethereum {
+                return Err(Error::UnsupportedSignatureScheme);
+            }
+            self.escrow_substrate_sig_scheme = escrow_substrate_sig_scheme;
             self.dynamodb_access_key = Some(dynamodb_access_key);
             self.dynamodb_secret_key = Some(dynamodb_secret_key);
             self.s3_access_key = Some(s3_access_key);
@@ -149,15 +742,209 @@ mod privadex_phat {
             Ok(())
         }
 
+        // Escrow accounts whose keys are held by an external signing service (e.g. an HSM).
+        // The substrate key uses the sig scheme passed to init_secret_keys
+        #[ink(message)]
+        pub fn init_remote_signer(
+            &mut self,
+            signer_url: String,
+            auth_secret: String,
+            escrow_eth_address: HexStrNo0x,
+            escrow_substrate_pubkey: HexStrNo0x,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if self.remote_signer_url.is_some()
+                || self.threshold_aggregator_url.is_some()
+                || self.escrow_eth_private_key.is_some()
+                || self.escrow_substrate_private_key.is_some()
+            {
+                return Err(Error::AlreadyInitialized);
+            }
+            let eth_address = io_helper::hex_str_to_eth_addr(&escrow_eth_address)?;
+            let substrate_pubkey =
+                io_helper::str_to_substrate_pubkey(&escrow_substrate_pubkey, None)?;
+            self.remote_signer_url = Some(signer_url);
+            self.remote_signer_auth_secret = Some(auth_secret);
+            self.remote_signer_eth_address = Some(eth_address.0);
+            self.remote_signer_substrate_pubkey = Some(substrate_pubkey);
+            Ok(())
+        }
+
+        // Makes this worker one party of a threshold escrow (e.g. 2-of-3 among independent Phat
+        // workers). Only the Eth escrow is supported for now, so the substrate escrow still
+        // needs a private key from init_secret_keys
+        #[ink(message)]
+        pub fn init_threshold_signer(
+            &mut self,
+            aggregator_url: String,
+            key_share: HexStrNo0x,
+            party_index: u8,
+            threshold: u8,
+            escrow_eth_address: HexStrNo0x,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if self.threshold_aggregator_url.is_some()
+                || self.remote_signer_url.is_some()
+                || self.escrow_eth_private_key.is_some()
+            {
+                return Err(Error::AlreadyInitialized);
+            }
+            if threshold == 0
+                || threshold > MAX_THRESHOLD_PARTIES
+                || party_index >= MAX_THRESHOLD_PARTIES
+            {
+                return Err(Error::InvalidThresholdConfig);
+            }
+            let eth_address = io_helper::hex_str_to_eth_addr(&escrow_eth_address)?;
+            self.threshold_key_share = Some(io_helper::hex_str_to_u8_32(&key_share)?);
+            self.threshold_aggregator_url = Some(aggregator_url);
+            self.threshold_party_index = party_index;
+            self.threshold = threshold;
+            self.threshold_eth_address = Some(eth_address.0);
+            Ok(())
+        }
+
+        /// Admin only. Gives each function in credentials (function, access_key, secret_key)
+        /// its own storage credential pair, so that each pair can be scoped to what its function
+        /// touches. Functions left out keep using the shared pairs from init_secret_keys
+        #[ink(message)]
+        pub fn init_storage_credentials(
+            &mut self,
+            credentials: Vec<(StorageFunction, String, String)>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut scoped = self.storage_credentials.get().unwrap_or_default();
+            if !scoped.is_empty() {
+                return Err(Error::AlreadyInitialized);
+            }
+            for (function, access_key, secret_key) in credentials {
+                set_storage_credential(
+                    &mut scoped,
+                    function,
+                    StorageCredential {
+                        access_key,
+                        secret_key,
+                    },
+                );
+            }
+            self.storage_credentials.set(&scoped);
+            Ok(())
+        }
+
+        /// Admin only. Replaces function's storage credential pair, or gives it one if it used
+        /// the shared pair. Takes effect on the next message, after which the old pair can be
+        /// revoked. The other functions' pairs are left alone
+        #[ink(message)]
+        pub fn rotate_storage_credentials(
+            &mut self,
+            function: StorageFunction,
+            access_key: String,
+            secret_key: String,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut scoped = self.storage_credentials.get().unwrap_or_default();
+            set_storage_credential(
+                &mut scoped,
+                function,
+                StorageCredential {
+                    access_key,
+                    secret_key,
+                },
+            );
+            self.storage_credentials.set(&scoped);
+            Ok(())
+        }
+
+        /// The functions that have their own storage credential pair. The keys themselves are
+        /// never returned
+        #[ink(message)]
+        pub fn get_scoped_storage_functions(&self) -> Vec<StorageFunction> {
+            self.storage_credentials
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(function, _)| function)
+                .collect()
+        }
+
         #[ink(message)]
         pub fn get_admin(&self) -> AccountId {
             self.admin
         }
 
+        /// Admin only. Swaps in the code at code_hash but keeps this contract's storage, so the
+        /// escrow keys and config carry over. Call migrate_storage on the new code afterwards
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)
+        }
+
+        /// Admin only. Brings the storage up to this code's STORAGE_VERSION and returns it.
+        /// Does nothing if it is already up to date
+        #[ink(message)]
+        pub fn migrate_storage(&mut self) -> Result<u16> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut version = self.get_storage_version();
+            if version > STORAGE_VERSION {
+                return Err(Error::UnsupportedStorageVersion);
+            }
+            while version < STORAGE_VERSION {
+                self.migrate_storage_from(version)?;
+                version += 1;
+                self.storage_version.set(&version);
+            }
+            Ok(version)
+        }
+
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u16 {
+            self.storage_version.get().unwrap_or(1)
+        }
+
+        // Each arm moves the storage from version to version + 1
+        fn migrate_storage_from(&mut self, version: u16) -> Result<()> {
+            match version {
+                // Version 2 only adds storage_version, which migrate_storage sets
+                1 => Ok(()),
+                // Version 3 keeps the escrow's SignatureScheme where version 2 kept an Ed25519
+                // flag. The flag's false and true decode as the scheme's first two variants
+                2 => {
+                    self.escrow_substrate_sig_scheme = match self.escrow_substrate_sig_scheme {
+                        SignatureScheme::Ethereum => SignatureScheme::Sr25519,
+                        SignatureScheme::Sr25519 => SignatureScheme::Ed25519,
+                        SignatureScheme::Ed25519 => return Err(Error::UnsupportedStorageVersion),
+                    };
+                    Ok(())
+                }
+                _ => Err(Error::UnsupportedStorageVersion),
+            }
+        }
+
         #[ink(message)]
         pub fn get_escrow_eth_account_address(&self) -> Result<String> {
             // We only support paths that start on Moonbeam or Astar for now, so we simply return
             // the Eth address instead of doing a match statement on network_name
+            if let Some(remote_address) = self
+                .remote_signer_eth_address
+                .or(self.threshold_eth_address)
+            {
+                return Ok(slice_to_hex_string(&remote_address));
+            }
             let privkey = self
                 .escrow_eth_private_key
                 .ok_or(Error::UninitializedEscrow)?;
@@ -178,6 +965,99 @@ mod privadex_phat {
                 .map_err(|_| Error::FailedToPullExecutionPlan)
         }
 
+        /// Admin only. Saves the plan together with its event log, the raw receipts (or indexer
+        /// records) of every txn it sent, and the config it ran under to S3, and returns the
+        /// object's key. Meant for looking into failed swaps, so it does not have to be settled
+        #[ink(message)]
+        pub fn export_failure_bundle(&self, exec_plan_uuid_str: HexStrNo0x) -> Result<String> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let exec_plan = self.get_exec_plan(exec_plan_uuid_str)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let bundle = FailureBundle {
+                event_log: execute_step_meta
+                    .pull_plan_event_log_from_s3(&exec_plan.uuid)
+                    .unwrap_or_default(),
+                txns: get_bundled_txns(&exec_plan, &execute_step_meta),
+                step_addresses: get_step_addresses(&exec_plan),
+                config: BundledConfig {
+                    storage_version: self.storage_version.get().unwrap_or_default(),
+                    deployment_epoch: self.get_deployment_epoch(),
+                    nonce_pool_size: self.get_nonce_pool_size(),
+                    bypass_screening: self.bypass_screening,
+                    best_block_chains: self.get_best_block_chains(&exec_plan),
+                    custom_rpc_chains: self
+                        .rpc_endpoints
+                        .get()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|rpc_endpoint| rpc_endpoint.chain_id)
+                        .collect(),
+                    paused_chains: execute_step_meta
+                        .pull_paused_chains_from_s3()
+                        .unwrap_or_default(),
+                },
+                created_millis: self.now_millis(),
+                exec_plan,
+            };
+            execute_step_meta
+                .save_failure_bundle_to_s3(&bundle)
+                .map_err(|_| Error::FailedToSaveFailureBundle)
+        }
+
+        /// Status and timing of each step, for progress bars
+        #[ink(message)]
+        pub fn get_swap_status(&self, exec_plan_uuid_str: HexStrNo0x) -> Result<SwapStatus> {
+            self.get_exec_plan(exec_plan_uuid_str)
+                .map(|exec_plan| SwapStatus::from(&exec_plan))
+        }
+
+        /// Anyone can call this. The plan's statuses, chains and txns as JSON, for swap trackers
+        /// on other sites. Addresses are shortened unless owner_proof is the plan's user signing
+        /// get_plan_owner_challenge
+        #[ink(message)]
+        pub fn get_plan_public_view(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+            owner_proof: Option<DestinationProof>,
+        ) -> Result<String> {
+            let exec_plan = self.get_exec_plan(exec_plan_uuid_str)?;
+            let reveal_addresses = match &owner_proof {
+                Some(proof) if !is_plan_owner(&exec_plan, proof) => {
+                    return Err(Error::InvalidOwnerProof)
+                }
+                Some(_) => true,
+                None => false,
+            };
+            Ok(get_plan_public_view(&exec_plan, reveal_addresses))
+        }
+
+        #[ink(message)]
+        pub fn get_plan_owner_challenge(&self, exec_plan_uuid_str: HexStrNo0x) -> Result<String> {
+            let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+            Ok(get_plan_owner_challenge(&Uuid::new(exec_plan_uuid_raw)))
+        }
+
+        /// p50/p90 of the time left for an in-flight plan, from the measured durations of its
+        /// remaining step kinds on their chains
+        #[ink(message)]
+        pub fn estimate_completion(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+        ) -> Result<CompletionEstimate> {
+            let exec_plan = self.get_exec_plan(exec_plan_uuid_str)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let step_durations = execute_step_meta
+                .pull_step_durations_from_s3()
+                .map_err(|_| Error::FailedToPullStepDurations)?;
+            Ok(CompletionEstimate::new(
+                &exec_plan,
+                &step_durations,
+                self.now_millis(),
+            ))
+        }
+
         #[ink(message)]
         pub fn execution_plan_step_forward(
             &self,
@@ -187,289 +1067,5026 @@ mod privadex_phat {
                 let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
                 Uuid::new(exec_plan_uuid_raw)
             };
-            let execute_step_meta = self.create_execute_step_meta()?;
+            let mut execute_step_meta = self.create_execute_step_meta()?;
+            let keys = self.create_key_container()?;
+            let worker: [u8; 32] = *Self::env().caller().as_ref();
+            execute_step_meta.set_worker(worker);
+            self.step_forward_exec_plan(&mut execute_step_meta, &keys, exec_plan_uuid)
+        }
+
+        /// Same as execution_plan_step_forward for up to 8 plans in one invocation, with each
+        /// plan's result in order. The Substrate extrinsics they sign are submitted together at
+        /// the end, one JSON-RPC batch per chain. With nonce pools on (see
+        /// set_nonce_pool_size), steps of the same escrow take consecutive nonces from one pool
+        #[ink(message)]
+        pub fn execution_plans_step_forward(
+            &self,
+            exec_plan_uuid_strs: Vec<HexStrNo0x>,
+        ) -> Result<Vec<Result<Option<Amount>>>> {
+            if exec_plan_uuid_strs.len() > MAX_BATCH_EXEC_PLANS {
+                return Err(Error::TooManyExecutionPlans);
+            }
+            let mut exec_plan_uuids = Vec::new();
+            for exec_plan_uuid_str in exec_plan_uuid_strs.iter() {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(exec_plan_uuid_str)?;
+                exec_plan_uuids.push(Uuid::new(exec_plan_uuid_raw));
+            }
+            let mut execute_step_meta = self.create_execute_step_meta()?;
             let keys = self.create_key_container()?;
+            let worker: [u8; 32] = *Self::env().caller().as_ref();
+            execute_step_meta.set_worker(worker);
+            execute_step_meta.open_extrinsic_batch_window();
+            let mut results = Vec::new();
+            for exec_plan_uuid in exec_plan_uuids.into_iter() {
+                results.push(self.step_forward_exec_plan(
+                    &mut execute_step_meta,
+                    &keys,
+                    exec_plan_uuid,
+                ));
+            }
+            // The plans are saved by now with their extrinsics as submitted. If a chain's batch
+            // fails, its steps are dropped once their window passes and then retried
+            let unsubmitted_steps = execute_step_meta.submit_extrinsic_batch();
+            if !unsubmitted_steps.is_empty() {
+                debug_println!("Failed to submit extrinsics: {:?}", unsubmitted_steps);
+            }
+            let _ = execute_step_meta.release_nonce_pools();
+            Ok(results)
+        }
 
+        fn step_forward_exec_plan(
+            &self,
+            execute_step_meta: &mut ExecuteStepMeta,
+            keys: &KeyContainer,
+            exec_plan_uuid: Uuid,
+        ) -> Result<Option<Amount>> {
             let is_claim_successful = execute_step_meta.claim_exec_plan(&exec_plan_uuid);
             if !is_claim_successful {
                 return Err(Error::ExecutionPlanClaimedByAnotherWorker);
             }
-            let mut exec_plan = execute_step_meta
-                .pull_exec_plan_from_s3(&exec_plan_uuid)
-                .map_err(|_| Error::FailedToPullExecutionPlan)?;
+            let mut exec_plan = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                Ok(exec_plan) => exec_plan,
+                // Not corrupt, so it is left for the workers on the newer build. It stays claimed
+                // until the claim expires, so the workers on this one don't keep pulling it
+                Err(ExecutableError::UnknownStoredPlanVersion) => {
+                    return Err(Error::ExecutionPlanFromNewerBuild);
+                }
+                // It matches none of the layouts, legacy ones included, so retrying would never
+                // get anywhere
+                Err(ExecutableError::FailedToDeserializeFromS3) => {
+                    self.quarantine_exec_plan(execute_step_meta, &exec_plan_uuid)?;
+                    return Err(Error::ExecutionPlanQuarantined);
+                }
+                Err(_) => return Err(Error::FailedToPullExecutionPlan),
+            };
+            if exec_plan.review_status.is_pending() {
+                // Stays parked until the admin calls resolve_plan_review
+                let _ = execute_step_meta.unclaim_exec_plan(
+                    &exec_plan_uuid,
+                    get_next_poll_after_millis(&exec_plan, execute_step_meta.cur_timestamp()),
+                );
+                return Err(Error::PlanNeedsReview);
+            }
+            // Failing to read the flag should not hold up every plan
+            if self.is_draining().unwrap_or(false) {
+                if !has_step_in_flight(&exec_plan) {
+                    let _ = execute_step_meta.unclaim_exec_plan(
+                        &exec_plan_uuid,
+                        get_next_poll_after_millis(&exec_plan, execute_step_meta.cur_timestamp()),
+                    );
+                    return Err(Error::WorkersDraining);
+                }
+                execute_step_meta.set_draining(true);
+            }
+            execute_step_meta.set_paper_trade(exec_plan.paper_trade);
+            execute_step_meta.set_nonce_pool_size(self.nonce_pool_size.get().unwrap_or_default());
+            // Plans started with a named escrow sign with its keys and take nonces of their own
+            let escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+            let escrow_key_set = find_plan_escrow_key_set(&escrow_key_sets, &exec_plan);
+            execute_step_meta.set_escrow_name(escrow_key_set.map(|set| set.name.as_str()));
+            let escrow_keys = escrow_key_set.map(EscrowKeySet::to_key_container);
+            let keys = escrow_keys.as_ref().unwrap_or(keys);
+            execute_step_meta.set_best_block_chains(self.get_best_block_chains(&exec_plan));
+            execute_step_meta.set_hold_delivery(self.is_delivery_held(&exec_plan));
+            let old_step_statuses = get_step_statuses(&exec_plan);
+            let old_gas_fee_usd = get_realized_gas_fee_usd(&exec_plan);
+            let broadcast_events;
             let step_forward_res = {
                 let result_wrapped_step_forward_res =
-                    exec_plan.execute_step_forward(&execute_step_meta, &keys);
+                    exec_plan.execute_step_forward(execute_step_meta, keys);
+                // Taken even if the step forward failed, since it may have failed after sending
+                broadcast_events = get_txn_broadcast_events(
+                    &exec_plan,
+                    execute_step_meta.take_broadcast_txns(),
+                    execute_step_meta.cur_timestamp(),
+                );
+                // Unused nonces must be handed back even if the step forward failed. A batch
+                // hands them back once all its plans have stepped forward
+                if !execute_step_meta.is_batching_extrinsics() {
+                    let _ = execute_step_meta.release_nonce_pools();
+                }
                 if let Err(executable_err) = result_wrapped_step_forward_res {
-                    if executable_err == ExecutableError::CalledStepForwardOnFinishedPlan {
+                    let is_closed = executable_err
+                        == ExecutableError::CalledStepForwardOnFinishedPlan
+                        && Self::record_closed_exec_plan(execute_step_meta, &exec_plan).is_ok();
+                    let mut events = broadcast_events;
+                    if is_closed {
+                        // Closed by a manual override, or by an earlier step forward that failed
+                        // to record it. Either way, this is the pass that takes it off
+                        events.push(PlanEvent {
+                            exec_plan_uuid: exec_plan.uuid.clone(),
+                            timestamp_millis: execute_step_meta.cur_timestamp(),
+                            kind: PlanEventKind::Completed(exec_plan.get_status()),
+                        });
+                    }
+                    let did_save_events = execute_step_meta.save_plan_events_to_s3(&events).is_ok();
+                    if is_closed {
+                        self.fund_relayer_fee_pool(execute_step_meta, &exec_plan);
+                        self.credit_back_sponsorship(&exec_plan);
                         let _ = execute_step_meta.remove_completed_exec_plan(&exec_plan_uuid);
                         debug_println!("Removed completed exec plan!");
                     } else {
                         // Unclaim adds the data back so we avoid doing so when we remove it. Sort of
-                        // hacky, can revisit later
-                        let _ = execute_step_meta.unclaim_exec_plan(&exec_plan_uuid);
+                        // hacky, can revisit later. The failure may well be transient, so the
+                        // plan stays due
+                        let _ = execute_step_meta
+                            .unclaim_exec_plan(&exec_plan_uuid, execute_step_meta.cur_timestamp());
+                    }
+                    // The next pass retries the step forward, but nothing brings back the events
+                    if !did_save_events {
+                        return Err(Error::FailedToSavePlanEvents);
                     }
                     return Err(Error::StepForwardFailed(executable_err));
                 }
                 result_wrapped_step_forward_res.expect("Result must be okay now")
             };
+            // Like the event stream, this is only for inspection and must not block execution
+            let _ = execute_step_meta.save_paper_txns_to_s3(&exec_plan_uuid);
 
+            let did_flag_for_review = self
+                .gas_ceiling_bps
+                .get()
+                .flatten()
+                .map_or(false, |gas_ceiling_bps| {
+                    flag_if_over_gas_ceiling(&mut exec_plan, gas_ceiling_bps)
+                });
             if step_forward_res.did_status_change {
+                exec_plan.last_progress_millis = execute_step_meta.cur_timestamp();
+            }
+            if step_forward_res.did_status_change || did_flag_for_review {
                 // Discard result because there is nothing we can/need to do if it fails
-                let _ = execute_step_meta.save_exec_plan_to_s3(&exec_plan);
+                let _ = Self::save_exec_plan_merging(execute_step_meta, &mut exec_plan);
             }
             let new_status = exec_plan.get_status();
-            if new_status == ExecutableSimpleStatus::Succeeded
-                || new_status == ExecutableSimpleStatus::Failed
-                || new_status == ExecutableSimpleStatus::Dropped
-            {
+            // The txns come first since they are what moved their steps forward
+            let mut events = broadcast_events;
+            if step_forward_res.did_status_change {
+                let transition_events = get_step_transition_events(
+                    &exec_plan.uuid,
+                    &old_step_statuses,
+                    &get_step_statuses(&exec_plan),
+                    execute_step_meta.cur_timestamp(),
+                );
+                self.record_relayer_contribution(
+                    &exec_plan,
+                    transition_events.len() as u32,
+                    old_gas_fee_usd,
+                );
+                events.extend(transition_events);
+            }
+            // If we fail to record the plan, we keep it around to retry
+            let is_closed = Self::is_closed_status(&new_status)
+                && Self::record_closed_exec_plan(execute_step_meta, &exec_plan).is_ok();
+            // Completed is recorded once, by the pass that takes the plan off the plan list
+            if is_closed {
+                events.push(PlanEvent {
+                    exec_plan_uuid: exec_plan.uuid.clone(),
+                    timestamp_millis: execute_step_meta.cur_timestamp(),
+                    kind: PlanEventKind::Completed(new_status),
+                });
+            }
+            // The events must never block execution, but the caller has to hear of a gap in them
+            let did_save_events = execute_step_meta.save_plan_events_to_s3(&events).is_ok();
+            if is_closed {
+                self.fund_relayer_fee_pool(execute_step_meta, &exec_plan);
+                self.credit_back_sponsorship(&exec_plan);
                 // Discard result because there is nothing we can/need to do if it fails
                 let _ = execute_step_meta.remove_completed_exec_plan(&exec_plan_uuid);
             } else {
                 // TODO_lowpriority: implement this as a RAII guard for cleanliness
                 // Unclaim adds the data back so we avoid doing so when we remove it. Sort of
                 // hacky, can revisit later
-                let _ = execute_step_meta.unclaim_exec_plan(&exec_plan_uuid);
+                let _ = execute_step_meta.unclaim_exec_plan(
+                    &exec_plan_uuid,
+                    get_next_poll_after_millis(&exec_plan, execute_step_meta.cur_timestamp()),
+                );
             }
 
+            if !did_save_events {
+                return Err(Error::FailedToSavePlanEvents);
+            }
             Ok(step_forward_res.amount_out)
         }
 
-        fn create_execute_step_meta(&self) -> Result<ExecuteStepMeta> {
-            Ok(ExecuteStepMeta::new_for_astar_moonbeam_polkadot(
-                self.now_millis(),
-                self.s3_access_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-                self.s3_secret_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-                self.dynamodb_access_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-                self.dynamodb_secret_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-            ))
+        // Copies the plan to the dead-letter prefix and alerts the operator on the stuck plan
+        // webhook, then takes it off the plan list so that workers and the scans stop picking it
+        // up. The user's funds are stuck until the operator recovers the plan, so it is only
+        // taken off once the alert is delivered. Until then (or if the copy fails) it stays
+        // claimed, and is retried once the claim expires
+        fn quarantine_exec_plan(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan_uuid: &Uuid,
+        ) -> Result<()> {
+            execute_step_meta
+                .quarantine_exec_plan_in_s3(exec_plan_uuid)
+                .map_err(|_| Error::FailedToPullExecutionPlan)?;
+            debug_println!("Quarantined undecodable plan {:?}", exec_plan_uuid);
+            let _ = execute_step_meta.save_plan_events_to_s3(&[PlanEvent {
+                exec_plan_uuid: exec_plan_uuid.clone(),
+                timestamp_millis: execute_step_meta.cur_timestamp(),
+                kind: PlanEventKind::Quarantined,
+            }]);
+            let webhook_url = self
+                .stuck_plan_webhook_url
+                .get()
+                .flatten()
+                .ok_or(Error::FailedToAlertOperator)?;
+            http_post_wrapper(
+                &webhook_url,
+                format!(
+                    "{{\"quarantined_plan\":\"{}\"}}",
+                    exec_plan_uuid.to_hex_string()
+                )
+                .into_bytes(),
+            )
+            .map_err(|_| Error::FailedToAlertOperator)?;
+            let _ = execute_step_meta.remove_completed_exec_plan(exec_plan_uuid);
+            Ok(())
         }
 
-        fn create_key_container(&self) -> Result<KeyContainer> {
-            let eth_secret_key = self
-                .escrow_eth_private_key
-                .ok_or(Error::UninitializedEscrow)?
-                .clone();
-            let substrate_secret_key = self
-                .escrow_substrate_private_key
-                .ok_or(Error::UninitializedEscrow)?
-                .clone();
-
-            let eth_address =
-                Self::get_eth_address_from_pair(&sp_core::ecdsa::Pair::from_seed(&eth_secret_key))?;
-            let substrate_pubkey = SubstratePublicKey {
-                0: sp_core::sr25519::Pair::from_seed(&substrate_secret_key)
-                    .public()
-                    .0,
-            };
+        /// Admin only. Refunds (or consolidates into the escrow) the leftover balances of
+        /// plans that closed without succeeding, and returns what it moved. Recovery txns take
+        /// a while to confirm, so call this repeatedly until the ledger is empty
+        #[ink(message)]
+        pub fn sweep_stranded_funds(&self) -> Result<Vec<StrandedBalance>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let keys = self.create_key_container()?;
+            if !execute_step_meta.claim_stranded_funds_ledger() {
+                return Err(Error::StrandedFundsLedgerClaimedByAnotherWorker);
+            }
+            let sweep_res = execute_step_meta
+                .pull_stranded_funds_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullStrandedFundsLedger)
+                .and_then(|mut ledger| {
+                    // Save even if a step fails midway since earlier balances may have moved
+                    let sweep_res = ledger.sweep_step_forward(&execute_step_meta, &keys);
+                    execute_step_meta
+                        .save_stranded_funds_ledger_to_s3(&ledger)
+                        .map_err(|_| Error::FailedToSaveStrandedFundsLedger)
+                        .and(sweep_res.map_err(Error::StepForwardFailed))
+                });
+            let _ = execute_step_meta.release_stranded_funds_ledger();
+            sweep_res
+        }
 
-            Ok(KeyContainer {
-                0: vec![
-                    AddressKeyPair {
-                        address: UniversalAddress::Ethereum(eth_address),
-                        key: eth_secret_key,
-                    },
-                    AddressKeyPair {
-                        address: UniversalAddress::Substrate(substrate_pubkey),
-                        key: substrate_secret_key,
-                    },
-                ],
-            })
+        #[ink(message)]
+        pub fn get_stranded_funds(&self) -> Result<Vec<StrandedBalance>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            Ok(execute_step_meta
+                .pull_stranded_funds_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullStrandedFundsLedger)?
+                .get_unrecovered_balances())
         }
 
+        /// Admin only. Revokes (approves zero for) the escrow's standing approvals to DEX
+        /// routers that no swap went through in the last max_idle_days, except to exempt routers.
+        /// Returns the allowances whose revocation moved on. Revocation txns take a while to
+        /// confirm, so run this periodically
         #[ink(message)]
-        pub fn start_swap(
-            &self,
-            user_to_escrow_transfer_eth_txn: HexStrNo0x,
-            src_network_name: String,
-            dest_network_name: String,
-            src_eth_addr: HexStrNo0x,
-            dest_eth_addr: HexStrNo0x,
+        pub fn revoke_idle_allowances(&self, max_idle_days: u32) -> Result<Vec<RouterAllowance>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if max_idle_days == 0 {
+                return Err(Error::InvalidNumber);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let keys = self.create_key_container()?;
+            // Closing plans record their swaps in the cache under this lock
+            if !execute_step_meta.claim_plan_analytics() {
+                return Err(Error::PlanAnalyticsClaimedByAnotherWorker);
+            }
+            let revoke_res = execute_step_meta
+                .pull_allowance_cache_from_s3()
+                .map_err(|_| Error::FailedToPullAllowanceCache)
+                .and_then(|mut allowance_cache| {
+                    // Save even if a revocation fails midway since earlier ones may have been sent
+                    let revoke_res = allowance_cache.revoke_idle_step_forward(
+                        &execute_step_meta,
+                        &keys,
+                        &self.allowance_exempt_routers.get().unwrap_or_default(),
+                        MillisSinceEpoch::from(max_idle_days) * MILLIS_PER_DAY,
+                    );
+                    execute_step_meta
+                        .save_allowance_cache_to_s3(&allowance_cache)
+                        .map_err(|_| Error::FailedToSaveAllowanceCache)
+                        .and(revoke_res.map_err(Error::StepForwardFailed))
+                });
+            let _ = execute_step_meta.release_plan_analytics();
+            revoke_res
+        }
+
+        /// The escrow's ERC20 approvals to DEX routers that swaps have used, with when each was
+        /// last used and whether it has been revoked
+        #[ink(message)]
+        pub fn get_allowance_cache(&self) -> Result<Vec<RouterAllowance>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            Ok(execute_step_meta
+                .pull_allowance_cache_from_s3()
+                .map_err(|_| Error::FailedToPullAllowanceCache)?
+                .get_allowances())
+        }
+
+        /// Admin only. Exempts (or stops exempting) router_eth_addr on network_name from
+        /// revoke_idle_allowances, e.g. for a router that is rarely routed through but should stay
+        /// approved
+        #[ink(message)]
+        pub fn set_allowance_exempt_router(
+            &mut self,
+            network_name: String,
+            router_eth_addr: HexStrNo0x,
+            exempt: bool,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let router_addr = io_helper::hex_str_to_eth_addr(&router_eth_addr)?;
+            let mut exempt_routers = self.allowance_exempt_routers.get().unwrap_or_default();
+            exempt_routers.retain(|router| *router != (chain_id, router_addr));
+            if exempt {
+                exempt_routers.push((chain_id, router_addr));
+            }
+            self.allowance_exempt_routers.set(&exempt_routers);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_allowance_exempt_routers(&self) -> Vec<(UniversalChainId, EthAddress)> {
+            self.allowance_exempt_routers.get().unwrap_or_default()
+        }
+
+        /// Admin only. Lets new plans swap through a DEX router that isn't in the DEX registry,
+        /// as long as its factory() returns factory_eth_addr. None removes the router. Routers
+        /// in the registry are always allowed, against the registry's factory
+        #[ink(message)]
+        pub fn set_allowed_router(
+            &mut self,
+            network_name: String,
+            router_eth_addr: HexStrNo0x,
+            factory_eth_addr: Option<HexStrNo0x>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let router = io_helper::hex_str_to_eth_addr(&router_eth_addr)?;
+            let mut router_allowlist = self.router_allowlist.get().unwrap_or_default();
+            router_allowlist
+                .retain(|allowed| !(allowed.chain_id == chain_id && allowed.router == router));
+            if let Some(factory_eth_addr) = factory_eth_addr {
+                if router_allowlist.len() >= MAX_ROUTER_ALLOWLIST_LEN {
+                    return Err(Error::RouterAllowlistTooLong);
+                }
+                router_allowlist.push(AllowedRouter {
+                    chain_id,
+                    router,
+                    factory: io_helper::hex_str_to_eth_addr(&factory_eth_addr)?,
+                });
+            }
+            self.router_allowlist.set(&router_allowlist);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_router_allowlist(&self) -> Vec<AllowedRouter> {
+            self.router_allowlist.get().unwrap_or_default()
+        }
+
+        /// Rolls the analytics of recently closed plans into the daily per-chain and per-DEX
+        /// stats. Meant to be run periodically by the scheduler
+        #[ink(message)]
+        pub fn aggregate_stats(&self) -> Result<Vec<DaysSinceEpoch>> /* updated days */ {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            if !execute_step_meta.claim_plan_analytics() {
+                return Err(Error::PlanAnalyticsClaimedByAnotherWorker);
+            }
+            let aggregate_res = Self::aggregate_pending_plan_analytics(&execute_step_meta);
+            let _ = execute_step_meta.release_plan_analytics();
+            aggregate_res
+        }
+
+        /// Quotes recently closed plans again on the pair cache archived closest to their start,
+        /// and rolls how far their live quote and realized output were off into the daily shadow
+        /// quote stats. Meant to be run periodically by the scheduler. Returns the number of
+        /// plans processed
+        #[ink(message)]
+        pub fn run_shadow_quotes(&self) -> Result<u32> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            if !execute_step_meta.claim_plan_analytics() {
+                return Err(Error::PlanAnalyticsClaimedByAnotherWorker);
+            }
+            let shadow_quote_res = self.shadow_quote_pending_plans(&execute_step_meta);
+            let _ = execute_step_meta.release_plan_analytics();
+            shadow_quote_res
+        }
+
+        /// Shadow quote stats of the plans that closed on the num_days days ending today
+        #[ink(message)]
+        pub fn get_shadow_quote_stats(&self, num_days: u32) -> Result<Vec<ShadowQuoteStats>> {
+            if num_days == 0 || num_days > MAX_STATS_NUM_DAYS {
+                return Err(Error::InvalidNumber);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let today = get_day(self.now_millis());
+            Ok((today.saturating_sub(num_days - 1)..=today)
+                .filter_map(|day| execute_step_meta.pull_shadow_quote_stats_from_s3(day).ok())
+                .collect())
+        }
+
+        /// Admin only. Adds or replaces the canary called name. Every interval_millis,
+        /// run_canaries has the shared escrow swap amount_in of src_token into dest_token and
+        /// deliver it to itself, until the canary has spent budget of src_token over all its
+        /// runs. A run fails if it delivers more than max_slippage_bps short of its quote. Fund
+        /// the escrow with the budget on top of what it holds otherwise. Only EVM src tokens are
+        /// supported
+        #[ink(message)]
+        pub fn set_canary(
+            &mut self,
+            name: String,
+            src_network_name: String,
             src_token: String,
+            dest_network_name: String,
             dest_token: String,
-            amount_in_str: String, // String because JavaScript numbers are maxed at 2^53
-        ) -> Result<Uuid> {
-            let user_to_escrow_txn =
-                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
-            let mut exec_plan = self.compute_execution_plan(
-                src_network_name.clone(),
-                dest_network_name,
-                src_eth_addr,
-                dest_eth_addr,
-                src_token,
-                dest_token,
-                amount_in_str,
+            amount_in: Amount,
+            interval_millis: MillisSinceEpoch,
+            max_slippage_bps: u16,
+            budget: Amount,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let quote_engine = self.quote_engine()?;
+            let canary = Canary {
+                name,
+                src_token: quote_engine.parse_token_id(&src_network_name, &src_token)?,
+                dest_token: quote_engine.parse_token_id(&dest_network_name, &dest_token)?,
+                amount_in,
+                interval_millis,
+                max_slippage_bps,
+                budget,
+            };
+            if !canary.is_valid() {
+                return Err(Error::InvalidCanary);
+            }
+            let mut canaries = self.canaries.get().unwrap_or_default();
+            canaries.retain(|other| other.name != canary.name);
+            if canaries.len() >= MAX_CANARIES {
+                return Err(Error::TooManyCanaries);
+            }
+            canaries.push(canary);
+            self.canaries.set(&canaries);
+            Ok(())
+        }
+
+        /// Admin only. A run that is already in flight still finishes, but gets no result
+        #[ink(message)]
+        pub fn remove_canary(&mut self, name: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut canaries = self.canaries.get().unwrap_or_default();
+            let num_canaries = canaries.len();
+            canaries.retain(|canary| canary.name != name);
+            if canaries.len() == num_canaries {
+                return Err(Error::CanaryNotFound);
+            }
+            self.canaries.set(&canaries);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_canaries(&self) -> Vec<Canary> {
+            self.canaries.get().unwrap_or_default()
+        }
+
+        /// Records the result of every canary run whose plan has closed, then starts a run of
+        /// every canary that is due (see set_canary). A run is a plan like any other, which the
+        /// workers step forward, except that its funds are already in the escrow. Meant to be
+        /// run periodically by the scheduler. Returns the results recorded in this call,
+        /// including the runs that couldn't be planned
+        #[ink(message)]
+        pub fn run_canaries(&self) -> Result<Vec<CanaryResult>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            if !execute_step_meta.claim_canary_ledger() {
+                return Err(Error::CanaryLedgerClaimedByAnotherWorker);
+            }
+            let run_res = self.run_due_canaries(&execute_step_meta);
+            let _ = execute_step_meta.release_canary_ledger();
+            run_res
+        }
+
+        /// The latest canary results, oldest first. Pass a name to only get that canary's
+        #[ink(message)]
+        pub fn get_canary_results(&self, name: Option<String>) -> Result<Vec<CanaryResult>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            Ok(execute_step_meta
+                .pull_canary_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullCanaryLedger)?
+                .results
+                .into_iter()
+                .filter(|result| name.as_ref().map_or(true, |name| &result.name == name))
+                .collect())
+        }
+
+        /// Stats of the plans that closed on the num_days days ending today
+        #[ink(message)]
+        pub fn get_stats(&self, num_days: u32) -> Result<StatsReport> {
+            if num_days == 0 || num_days > MAX_STATS_NUM_DAYS {
+                return Err(Error::InvalidNumber);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let today = get_day(self.now_millis());
+            let daily_stats: Vec<DailyStats> = (today.saturating_sub(num_days - 1)..=today)
+                .filter_map(|day| execute_step_meta.pull_daily_stats_from_s3(day).ok())
+                .collect();
+            Ok(StatsReport::from(daily_stats))
+        }
+
+        /// Volume routed through each DEX by the plans that closed from from_day to to_day
+        /// (inclusive, in days since the epoch), for claiming volume-based fee rebates
+        #[ink(message)]
+        pub fn get_dex_volume_report(
+            &self,
+            from_day: DaysSinceEpoch,
+            to_day: DaysSinceEpoch,
+        ) -> Result<DexVolumeReport> {
+            if from_day > to_day || to_day - from_day >= MAX_STATS_NUM_DAYS {
+                return Err(Error::InvalidNumber);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let daily_stats: Vec<DailyStats> = (from_day..=to_day)
+                .filter_map(|day| execute_step_meta.pull_daily_stats_from_s3(day).ok())
+                .collect();
+            Ok(DexVolumeReport::new(from_day, to_day, &daily_stats))
+        }
+
+        /// Realized price and USD prices of a closed plan, from its analytics record, plus the
+        /// prices in the reference currency if one is set. Plans that were refunded or failed
+        /// have no price (PlanNotSettled)
+        #[ink(message)]
+        pub fn get_execution_price(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+        ) -> Result<ExecutionPrice> {
+            let exec_plan_uuid = {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+                Uuid::new(exec_plan_uuid_raw)
+            };
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let plan_analytics = execute_step_meta
+                .pull_plan_analytics_record_from_s3(&exec_plan_uuid)
+                .map_err(|_| Error::FailedToPullPlanAnalytics)?;
+            let dest_token = match &plan_analytics.amount_out {
+                Some(amount_out) => &amount_out.token,
+                None => return Err(Error::PlanNotSettled),
+            };
+            let src_decimals = execute_step_meta
+                .get_token_decimals(&plan_analytics.amount_in.token)
+                .map_err(|_| Error::FailedToGetTokenDecimals)?;
+            let dest_decimals = execute_step_meta
+                .get_token_decimals(dest_token)
+                .map_err(|_| Error::FailedToGetTokenDecimals)?;
+            let price = ExecutionPrice::new(&plan_analytics, src_decimals, dest_decimals)
+                .ok_or(Error::PlanNotSettled)?;
+            Ok(match self.get_reference_fx_rate() {
+                Some(fx_rate) => price.with_fx_rate(fx_rate),
+                None => price,
+            })
+        }
+
+        /// Per chain, the escrow's balances next to what it owes users: the funds of in-flight
+        /// plans and the stranded funds not yet recovered. Lets anyone check that the escrow is
+        /// fully reserved. The balances are read live, at the block number reported per chain
+        #[ink(message)]
+        pub fn get_proof_of_reserves(&self) -> Result<ProofOfReserves> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            self.compute_proof_of_reserves(&execute_step_meta, None, &[])
+        }
+
+        /// Same as get_proof_of_reserves for one escrow's accounts only. Pass None for the
+        /// shared escrow
+        #[ink(message)]
+        pub fn get_escrow_proof_of_reserves(
+            &self,
+            escrow_name: Option<String>,
+        ) -> Result<ProofOfReserves> {
+            let escrow_addrs = match escrow_name {
+                Some(escrow_name) => {
+                    let escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+                    find_escrow_key_set(&escrow_key_sets, &escrow_name)
+                        .ok_or(Error::EscrowNotFound)?
+                        .get_addresses()
+                }
+                None => self.get_shared_escrow_addrs()?,
+            };
+            let execute_step_meta = self.create_execute_step_meta()?;
+            self.compute_proof_of_reserves(&execute_step_meta, Some(&escrow_addrs), &[])
+        }
+
+        /// Pauses new swaps on every chain where the escrow holds less than it owes users plus
+        /// that chain's insurance buffer, and unpauses the chains that recovered. Posts the
+        /// shortfalls to the reserve alert webhook (if set). Meant to be run periodically by
+        /// the scheduler
+        #[ink(message)]
+        pub fn reconcile_reserves(&self) -> Result<Vec<ReserveShortfall>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let proof = self.compute_proof_of_reserves(&execute_step_meta, None, &[])?;
+            let shortfalls =
+                proof.find_shortfalls(&self.insurance_buffers.get().unwrap_or_default());
+            execute_step_meta
+                .save_paused_chains_to_s3(&get_shortfall_chains(&shortfalls))
+                .map_err(|_| Error::FailedToSavePausedChains)?;
+            if let Some(webhook_url) = self.reserve_alert_webhook_url.get().flatten() {
+                if !shortfalls.is_empty() {
+                    // Best-effort. The chains are paused either way
+                    let _ = http_post_wrapper(
+                        &webhook_url,
+                        to_alert_webhook_body(&shortfalls).into_bytes(),
+                    );
+                }
+            }
+            Ok(shortfalls)
+        }
+
+        /// Admin only. Proposes how to consolidate the shared escrow's spare balances (what it
+        /// holds beyond what it owes users and, for native tokens, the insurance buffer) into
+        /// target_token, and builds each one's ExecutionPlan with the same SOR as start_swap.
+        /// Balances worth less than min_value_usd (in the units of a quote's amount_in_usd), or
+        /// whose fees would eat them up, are skipped. tokens lists the (network_name, token)
+        /// pairs to check on top of the native tokens and the tokens owed to users. The plans
+        /// are only proposed, not started
+        #[ink(message)]
+        pub fn plan_consolidation(
+            &self,
+            target_network_name: String,
+            target_token: String,
+            min_value_usd: Amount,
+            tokens: Vec<(String, String)>,
+        ) -> Result<ConsolidationReport> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let quote_engine = self.quote_engine()?;
+            let target_token_id =
+                quote_engine.parse_token_id(&target_network_name, &target_token)?;
+            let tracked_tokens = tokens
+                .iter()
+                .map(|(network_name, token)| quote_engine.parse_token_id(network_name, token))
+                .collect::<core::result::Result<Vec<_>, _>>()?;
+            let escrow_addrs = self.get_shared_escrow_addrs()?;
+            let escrow_eth_addr = escrow_addrs
+                .iter()
+                .find_map(|addr| match addr {
+                    UniversalAddress::Ethereum(eth_addr) => Some(*eth_addr),
+                    UniversalAddress::Substrate(_) => None,
+                })
+                .ok_or(Error::UninitializedEscrow)?;
+
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let proof = self.compute_proof_of_reserves(
+                &execute_step_meta,
+                Some(&escrow_addrs),
+                &tracked_tokens,
             )?;
-            match &mut exec_plan.prestart_user_to_escrow_transfer.inner {
-                ExecutionStepEnum::EthSend(step) => {
-                    let cur_block =
-                        Self::get_cur_block(&io_helper::chain_name_to_id(&src_network_name)?)?;
-                    step.status = EthStepStatus::Submitted(EthPendingTxnId {
-                        txn_hash: user_to_escrow_txn.clone(),
-                        end_block_num: cur_block + TXN_NUM_BLOCKS_ALIVE,
-                    });
+            let holdings =
+                get_spare_holdings(&proof, &self.insurance_buffers.get().unwrap_or_default());
+            let context = quote_engine.build_context()?;
+            Ok(plan_consolidation(
+                &context,
+                holdings,
+                &target_token_id,
+                escrow_eth_addr,
+                min_value_usd,
+            ))
+        }
+
+        #[ink(message)]
+        pub fn get_paused_chains(&self) -> Result<Vec<UniversalChainId>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            execute_step_meta
+                .pull_paused_chains_from_s3()
+                .map_err(|_| Error::FailedToPullPausedChains)
+        }
+
+        /// Admin only. Resumes swaps on a chain that reconcile_reserves paused, e.g. after
+        /// topping up the escrow. The next reconciliation pauses it again if it is still short
+        #[ink(message)]
+        pub fn unpause_chain(&self, network_name: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let mut paused_chains = execute_step_meta
+                .pull_paused_chains_from_s3()
+                .map_err(|_| Error::FailedToPullPausedChains)?;
+            paused_chains.retain(|paused_chain_id| *paused_chain_id != chain_id);
+            execute_step_meta
+                .save_paused_chains_to_s3(&paused_chains)
+                .map_err(|_| Error::FailedToSavePausedChains)
+        }
+
+        /// Compares each DEX chain's latest block timestamp with the contract's clock. DEX swap
+        /// deadlines are then set in the chain's clock. A chain that cannot be read keeps its
+        /// previous sample, which is ignored once it is an hour old. Meant to be run
+        /// periodically by the scheduler
+        #[ink(message)]
+        pub fn sample_chain_clock_skews(&self) -> Result<Vec<ChainClockSkew>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let prev_clock_skews = execute_step_meta
+                .pull_chain_clock_skews_from_s3()
+                .map_err(|_| Error::FailedToPullChainClockSkews)?;
+            let clock_skews: Vec<ChainClockSkew> = SKEW_SAMPLED_CHAINS
+                .iter()
+                .filter_map(|chain_id| {
+                    let chain_info = get_chain_info_from_chain_id(chain_id)?;
+                    match execute_step_meta.get_latest_block_timestamp(chain_id) {
+                        Ok(block_timestamp_millis) => Some(ChainClockSkew {
+                            chain_id: *chain_id,
+                            skew_millis: compute_skew_millis(
+                                block_timestamp_millis,
+                                execute_step_meta.cur_timestamp(),
+                                chain_info.avg_block_time_millis,
+                            ),
+                            sampled_millis: execute_step_meta.cur_timestamp(),
+                        }),
+                        Err(_) => prev_clock_skews
+                            .iter()
+                            .find(|clock_skew| clock_skew.chain_id == *chain_id)
+                            .cloned(),
+                    }
+                })
+                .collect();
+            execute_step_meta
+                .save_chain_clock_skews_to_s3(&clock_skews)
+                .map_err(|_| Error::FailedToSaveChainClockSkews)?;
+            Ok(clock_skews)
+        }
+
+        #[ink(message)]
+        pub fn get_chain_clock_skews(&self) -> Result<Vec<ChainClockSkew>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            execute_step_meta
+                .pull_chain_clock_skews_from_s3()
+                .map_err(|_| Error::FailedToPullChainClockSkews)
+        }
+
+        /// Pools and bridge lanes that quotes and new plans currently route around, because they
+        /// kept failing steps, with the time (in millis) each one is blacklisted until
+        #[ink(message)]
+        pub fn get_route_blacklist(&self) -> Result<Vec<(RouteSegment, MillisSinceEpoch)>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            Ok(execute_step_meta
+                .pull_route_blacklist_from_s3()
+                .map_err(|_| Error::FailedToPullRouteBlacklist)?
+                .get_blacklisted_segments(self.now_millis()))
+        }
+
+        /// Admin only. Routes through every blacklisted segment again and forgets their recent
+        /// failures, e.g. after a broken pool or lane has been fixed
+        #[ink(message)]
+        pub fn clear_route_blacklist(&self) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let execute_step_meta = self.create_execute_step_meta()?;
+            if !execute_step_meta.claim_plan_analytics() {
+                return Err(Error::PlanAnalyticsClaimedByAnotherWorker);
+            }
+            let save_res = execute_step_meta
+                .save_route_blacklist_to_s3(&RouteBlacklist::default())
+                .map_err(|_| Error::FailedToSaveRouteBlacklist);
+            let _ = execute_step_meta.release_plan_analytics();
+            save_res
+        }
+
+        /// The onboarding checklist for a chain: the escrow account exists and holds enough gas,
+        /// the DEX routers, wrapped native token and precompiles respond to calls, and the
+        /// indexer keeps up with the chain. Admin only if enable is set, which also unpauses the
+        /// chain (like unpause_chain) if every check passed
+        #[ink(message)]
+        pub fn preflight_chain(
+            &self,
+            network_name: String,
+            enable: bool,
+        ) -> Result<ChainPreflightReport> {
+            if enable && Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let escrow_addrs = self.create_key_container()?.get_addresses();
+            let insurance_buffer = self
+                .insurance_buffers
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .find(|(buffer_chain_id, _)| *buffer_chain_id == chain_id)
+                .map_or(0, |(_, buffer)| buffer);
+            let report = ChainPreflightReport {
+                chain: chain_id,
+                as_of_millis: execute_step_meta.cur_timestamp(),
+                checks: run_chain_preflight(
+                    &execute_step_meta,
+                    chain_info,
+                    &escrow_addrs,
+                    insurance_buffer,
+                    self.get_deposit_forwarder_factory(chain_info),
+                ),
+            };
+
+            if enable && report.passed() {
+                let mut paused_chains = execute_step_meta
+                    .pull_paused_chains_from_s3()
+                    .map_err(|_| Error::FailedToPullPausedChains)?;
+                paused_chains.retain(|paused_chain_id| *paused_chain_id != chain_id);
+                execute_step_meta
+                    .save_paused_chains_to_s3(&paused_chains)
+                    .map_err(|_| Error::FailedToSavePausedChains)?;
+            }
+            Ok(report)
+        }
+
+        /// Checks the chain's hard-coded registry values against the chain: the EVM chain ID
+        /// (eth_chainId), the ss58 prefix (system_properties), and that the wrapped native token
+        /// and DEX routers have code. A mismatch means the registry drifted and plans on the
+        /// chain may strand funds, so pause it until a release fixes the registry
+        #[ink(message)]
+        pub fn verify_registry(&self, network_name: String) -> Result<RegistryReport> {
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            Ok(RegistryReport {
+                chain: chain_id,
+                as_of_millis: execute_step_meta.cur_timestamp(),
+                checks: verify_chain_registry(&execute_step_meta, chain_info),
+            })
+        }
+
+        /// Admin only. The buffer is in the chain's native token. Pass None to remove it, in
+        /// which case the escrow only has to cover what it owes users
+        #[ink(message)]
+        pub fn set_insurance_buffer(
+            &mut self,
+            network_name: String,
+            insurance_buffer: Option<Amount>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let mut insurance_buffers = self.insurance_buffers.get().unwrap_or_default();
+            insurance_buffers.retain(|(buffer_chain_id, _)| *buffer_chain_id != chain_id);
+            if let Some(insurance_buffer) = insurance_buffer {
+                insurance_buffers.push((chain_id, insurance_buffer));
+            }
+            self.insurance_buffers.set(&insurance_buffers);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_insurance_buffers(&self) -> Vec<(UniversalChainId, Amount)> {
+            self.insurance_buffers.get().unwrap_or_default()
+        }
+
+        /// Admin only. Pass webhook_url = None to stop posting reserve shortfalls
+        #[ink(message)]
+        pub fn set_reserve_alert_webhook(&mut self, webhook_url: Option<String>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.reserve_alert_webhook_url.set(&webhook_url);
+            Ok(())
+        }
+
+        fn get_shared_escrow_addrs(&self) -> Result<Vec<UniversalAddress>> {
+            Ok(self.create_key_container()?.get_addresses())
+        }
+
+        // Liabilities are per holder, so every escrow is checked against what it owes by itself.
+        // If holders is set, only those accounts' reserves are kept (and their balances fetched).
+        // tracked_tokens are listed for the shared escrow even if nothing is owed in them
+        fn compute_proof_of_reserves(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+            holders: Option<&[UniversalAddress]>,
+            tracked_tokens: &[UniversalTokenId],
+        ) -> Result<ProofOfReserves> {
+            let mut escrows = vec![self.get_shared_escrow_addrs()?];
+            for escrow_key_set in self.escrow_key_sets.get().unwrap_or_default().iter() {
+                escrows.push(escrow_key_set.get_addresses());
+            }
+            let chain_escrows: Vec<(UniversalChainId, UniversalAddress)> = QUOTE_CHAIN_IDS
+                .iter()
+                .flat_map(|chain_id| {
+                    escrows.iter().filter_map(move |escrow_addrs| {
+                        Some((*chain_id, get_chain_escrow(chain_id, escrow_addrs)?))
+                    })
+                })
+                .collect();
+
+            let mut liabilities = Vec::new();
+            let mut num_in_flight_plans = 0;
+            for exec_plan_uuid in self.get_all_execplan_ids()? {
+                // Also skips the lock UUIDs older builds put in the plan set, which have no plan
+                let exec_plan = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                    Ok(exec_plan) => exec_plan,
+                    Err(_) => continue,
+                };
+                let plan_liabilities = get_plan_liabilities(&exec_plan);
+                if !plan_liabilities.is_empty() {
+                    num_in_flight_plans += 1;
+                    liabilities.extend(plan_liabilities);
                 }
-                ExecutionStepEnum::ERC20Transfer(step) => {
-                    let cur_block =
-                        Self::get_cur_block(&io_helper::chain_name_to_id(&src_network_name)?)?;
-                    step.status = EthStepStatus::Submitted(EthPendingTxnId {
-                        txn_hash: user_to_escrow_txn.clone(),
-                        end_block_num: cur_block + TXN_NUM_BLOCKS_ALIVE,
-                    });
+            }
+            // A ledger we can't read would understate the liabilities
+            let stranded_balances = execute_step_meta
+                .pull_stranded_funds_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullStrandedFundsLedger)?
+                .get_unrecovered_balances();
+            liabilities.extend(get_stranded_liabilities(&stranded_balances));
+
+            let mut proof = ProofOfReserves::new(
+                execute_step_meta.cur_timestamp(),
+                num_in_flight_plans,
+                &chain_escrows,
+                liabilities,
+            );
+            for token in tracked_tokens.iter() {
+                if let Some(escrow_addr) = get_chain_escrow(&token.chain, &escrows[0]) {
+                    proof.track_token(token.clone(), escrow_addr);
                 }
-                _ => return Err(Error::InvalidUserToEscrowTxn),
+            }
+            if let Some(holders) = holders {
+                proof.retain_holders(holders);
+            }
+            proof
+                .fetch_balances(execute_step_meta)
+                .map_err(|_| Error::RpcRequestFailed)?;
+            Ok(proof)
+        }
+
+        /// Flags the pending plans that went longer than the worst case duration of their
+        /// in-flight steps without making progress. Anyone can run it, but only the admin's scans
+        /// report them to the stuck plan webhook (if set) and the plan event stream, so it is
+        /// meant to be run periodically by the scheduler as admin. Admin only if escalate is set,
+        /// which also moves the stuck plans into the refund flow
+        #[ink(message)]
+        pub fn scan_stuck_plans(&self, escalate: bool) -> Result<Vec<StuckPlan>> {
+            let is_admin = Self::env().caller() == self.admin;
+            if escalate && !is_admin {
+                return Err(Error::NoPermissions);
             }
             let execute_step_meta = self.create_execute_step_meta()?;
-            if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
-                return Err(Error::PrestartTxnIsAlreadyUsed);
+            let mut stuck_plans = Vec::new();
+            for exec_plan_uuid in self.get_all_execplan_ids()? {
+                // Also skips the lock UUIDs older builds put in the plan set, which have no plan
+                let exec_plan = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                    Ok(exec_plan) => exec_plan,
+                    Err(_) => continue,
+                };
+                if let Some(mut stuck_plan) =
+                    find_stuck_plan(&exec_plan, execute_step_meta.cur_timestamp())
+                {
+                    if escalate {
+                        stuck_plan.escalated =
+                            Self::escalate_stuck_exec_plan(&execute_step_meta, &exec_plan_uuid);
+                    }
+                    if is_admin {
+                        let _ = execute_step_meta.save_plan_events_to_s3(&[PlanEvent {
+                            exec_plan_uuid: exec_plan_uuid.clone(),
+                            timestamp_millis: execute_step_meta.cur_timestamp(),
+                            kind: PlanEventKind::Stuck {
+                                stalled_millis: stuck_plan.stalled_millis,
+                                escalated: stuck_plan.escalated,
+                            },
+                        }]);
+                    }
+                    stuck_plans.push(stuck_plan);
+                }
             }
-            let _ = execute_step_meta.save_exec_plan_to_s3(&exec_plan);
-            let _ = execute_step_meta.register_exec_plan(&exec_plan.uuid);
+            if let Some(webhook_url) = self.stuck_plan_webhook_url.get().flatten() {
+                if is_admin && !stuck_plans.is_empty() {
+                    // Best-effort like the event stream. The caller gets the stuck plans either way
+                    let _ =
+                        http_post_wrapper(&webhook_url, to_webhook_body(&stuck_plans).into_bytes());
+                }
+            }
+            Ok(stuck_plans)
+        }
+
+        /// Admin only. While draining, workers only step forward the plans that have a txn in
+        /// flight, and only until it lands, so every plan ends up paused between steps. Meant
+        /// for maintenance windows: wait for get_draining_plans to come back empty, then stop
+        /// the workers. Deadlines keep running, so keep the window short
+        #[ink(message)]
+        pub fn set_drain_mode(&self, draining: bool) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.create_exec_plan_assigner()?
+                .set_draining(draining)
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        #[ink(message)]
+        pub fn is_draining(&self) -> Result<bool> {
+            self.create_exec_plan_assigner()?
+                .is_draining()
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        /// The active plans that still have a txn in flight, i.e. the ones the workers must
+        /// keep stepping forward before the drain is done
+        #[ink(message)]
+        pub fn get_draining_plans(&self) -> Result<Vec<Uuid>> {
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let mut draining_plans = Vec::new();
+            for exec_plan_uuid in self.get_all_execplan_ids()? {
+                // Also skips the lock UUIDs older builds put in the plan set, which have no plan
+                let exec_plan = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                    Ok(exec_plan) => exec_plan,
+                    Err(_) => continue,
+                };
+                if has_step_in_flight(&exec_plan) {
+                    draining_plans.push(exec_plan_uuid);
+                }
+            }
+            Ok(draining_plans)
+        }
+
+        /// Admin only. The plans that are claimed right now, with who claimed them and when.
+        /// A Stale lease means the claimer did not unclaim the plan within a minute (e.g. it
+        /// crashed mid step), and the next claim will take the plan over. Claims by the admin
+        /// (e.g. resolve_plan_review) have no claimer. A claim that is not verified was not
+        /// signed by its claimer, i.e. it was written to DynamoDB without going through the
+        /// contract, and is taken over by the next claim too
+        #[ink(message)]
+        pub fn list_claims(&self) -> Result<Vec<ExecPlanClaim>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.create_exec_plan_assigner()?
+                .get_execplan_claims()
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        /// The sr25519 public key that worker's claims and nonce reservations are signed with,
+        /// to check them outside the contract. The key is derived in the contract, so it can't
+        /// be used by anyone else. The zero account is the contract's own identity, used for
+        /// writes that are not made by a worker (see the concurrency coordinator README)
+        #[ink(message)]
+        pub fn get_worker_identity(&self, worker: AccountId) -> Vec<u8> {
+            WorkerIdentity::derive(*worker.as_ref()).public_key()
+        }
+
+        /// Admin only. A plan whose realized gas passes gas_ceiling_bps of its quoted input
+        /// value stops stepping forward until resolve_plan_review is called on it. Plans without
+        /// a quote are never flagged. Pass None to disable
+        #[ink(message)]
+        pub fn set_gas_ceiling_bps(&mut self, gas_ceiling_bps: Option<u32>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.gas_ceiling_bps.set(&gas_ceiling_bps);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_gas_ceiling_bps(&self) -> Option<u32> {
+            self.gas_ceiling_bps.get().flatten()
+        }
+
+        /// Admin only. Lets a plan flagged by the gas ceiling, or parked over a deposit that was
+        /// too far off its quote, step forward again. The ceiling no longer applies to it
+        /// afterwards. If refund is set, its deadline also moves up to now so that it refunds
+        /// instead of continuing
+        #[ink(message)]
+        pub fn resolve_plan_review(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+            refund: bool,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let exec_plan_uuid = {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+                Uuid::new(exec_plan_uuid_raw)
+            };
+            let execute_step_meta = self.create_execute_step_meta()?;
+            // We claim the plan so that we don't race a worker that is stepping it forward
+            if !execute_step_meta.claim_exec_plan(&exec_plan_uuid) {
+                return Err(Error::ExecutionPlanClaimedByAnotherWorker);
+            }
+            let resolve_res = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                Ok(mut exec_plan) => match exec_plan.review_status {
+                    PlanReviewStatus::NeedsReview { .. }
+                    | PlanReviewStatus::DepositMismatch { .. } => {
+                        exec_plan.review_status = PlanReviewStatus::Resolved;
+                        if refund {
+                            escalate_stuck_plan(&mut exec_plan, execute_step_meta.cur_timestamp());
+                        }
+                        execute_step_meta
+                            .save_exec_plan_to_s3(&exec_plan)
+                            .map_err(|_| Error::FailedToSaveExecutionPlan)
+                    }
+                    _ => Err(Error::PlanDoesNotNeedReview),
+                },
+                Err(_) => Err(Error::FailedToPullExecutionPlan),
+            };
+            // Due right away, whether it continues or refunds
+            let _ = execute_step_meta
+                .unclaim_exec_plan(&exec_plan_uuid, execute_step_meta.cur_timestamp());
+            resolve_res
+        }
+
+        /// Admin only. Marks a step in flight as succeeded, for when there is off-chain evidence
+        /// that it did but its automated confirmation is broken (e.g. an indexer outage).
+        /// txn_id is the step's Ethereum txn hash, or its Substrate extrinsic as
+        /// "block_num-index" (for an XCM transfer whose local txn confirmed, the remote event).
+        /// amount_out is what the step delivered, and is handed to the next step of its path.
+        /// The justification is required and is recorded in the plan's event log: at most 256
+        /// printable ASCII chars, without quotes or backslashes
+        #[ink(message)]
+        pub fn force_mark_step_confirmed(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+            step_uuid_str: HexStrNo0x,
+            txn_id: String,
+            amount_out: Amount,
+            justification: String,
+        ) -> Result<()> {
+            self.force_step_status(
+                exec_plan_uuid_str,
+                step_uuid_str,
+                true,
+                txn_id,
+                amount_out,
+                justification,
+            )
+        }
+
+        /// Admin only. Like force_mark_step_confirmed, but marks the step as failed. For an XCM
+        /// transfer whose local txn confirmed, txn_id is the remote event it failed with
+        #[ink(message)]
+        pub fn force_fail_step(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+            step_uuid_str: HexStrNo0x,
+            txn_id: String,
+            justification: String,
+        ) -> Result<()> {
+            self.force_step_status(
+                exec_plan_uuid_str,
+                step_uuid_str,
+                false,
+                txn_id,
+                0,
+                justification,
+            )
+        }
+
+        fn force_step_status(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+            step_uuid_str: HexStrNo0x,
+            confirm: bool,
+            txn_id: String,
+            amount_out: Amount,
+            justification: String,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if !is_valid_justification(&justification) {
+                return Err(Error::InvalidOverrideJustification);
+            }
+            let manual_txn_id = ManualTxnId::parse(&txn_id).ok_or(Error::InvalidTxnId)?;
+            let exec_plan_uuid = Uuid::new(io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?);
+            let step_uuid = Uuid::new(io_helper::hex_str_to_u8_16(&step_uuid_str)?);
+            let execute_step_meta = self.create_execute_step_meta()?;
+            // We claim the plan so that we don't race a worker that is stepping it forward
+            if !execute_step_meta.claim_exec_plan(&exec_plan_uuid) {
+                return Err(Error::ExecutionPlanClaimedByAnotherWorker);
+            }
+            let override_res = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                Ok(mut exec_plan) => {
+                    force_step_status(
+                        &mut exec_plan,
+                        &step_uuid,
+                        confirm,
+                        &manual_txn_id,
+                        amount_out,
+                        execute_step_meta.cur_timestamp(),
+                    )
+                    .map_err(Error::from)
+                    .and_then(|(from, to)| {
+                        // The justification is only in the event, so we write it before the plan
+                        // and leave the plan untouched if we can't. If saving the plan then fails,
+                        // the event outlives an override that didn't happen, and retrying records
+                        // it again
+                        execute_step_meta
+                            .save_plan_events_to_s3(&[PlanEvent {
+                                exec_plan_uuid: exec_plan.uuid.clone(),
+                                timestamp_millis: execute_step_meta.cur_timestamp(),
+                                kind: PlanEventKind::ManualOverride {
+                                    step_uuid: step_uuid.clone(),
+                                    from,
+                                    to,
+                                    txn_id,
+                                    justification,
+                                },
+                            }])
+                            .map_err(|_| Error::FailedToSavePlanEvents)?;
+                        // If this closes the plan, the worker that takes it off records Completed
+                        execute_step_meta
+                            .save_exec_plan_to_s3(&exec_plan)
+                            .map_err(|_| Error::FailedToSaveExecutionPlan)
+                    })
+                }
+                Err(_) => Err(Error::FailedToPullExecutionPlan),
+            };
+            // Due right away, so that a worker picks up from the forced status
+            let _ = execute_step_meta
+                .unclaim_exec_plan(&exec_plan_uuid, execute_step_meta.cur_timestamp());
+            override_res
+        }
+
+        /// Admin only. Plans quoted above threshold_usd ($ x 10^18), and plans with no quote,
+        /// hold their final escrow to user transfer until approve_delivery is called on them.
+        /// Smaller plans deliver as usual. Pass None to stop holding deliveries
+        #[ink(message)]
+        pub fn set_delivery_approval_threshold_usd(
+            &mut self,
+            threshold_usd: Option<Amount>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.delivery_approval_threshold_usd.set(&threshold_usd);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_delivery_approval_threshold_usd(&self) -> Option<Amount> {
+            self.delivery_approval_threshold_usd.get().flatten()
+        }
+
+        /// Admin only. Operators that may call approve_delivery besides the admin
+        #[ink(message)]
+        pub fn set_delivery_approvers(&mut self, approvers: Vec<AccountId>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.delivery_approvers.set(&approvers);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_delivery_approvers(&self) -> Vec<AccountId> {
+            self.delivery_approvers.get().unwrap_or_default()
+        }
+
+        /// Admin or delivery approver only. Co-signs the final escrow to user transfer of a
+        /// plan held by the delivery approval threshold, so that the next step forward
+        /// broadcasts it. Can be called before the plan gets there
+        #[ink(message)]
+        pub fn approve_delivery(&self, exec_plan_uuid_str: HexStrNo0x) -> Result<()> {
+            let caller = Self::env().caller();
+            if caller != self.admin && !self.get_delivery_approvers().contains(&caller) {
+                return Err(Error::NoPermissions);
+            }
+            let exec_plan_uuid = {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+                Uuid::new(exec_plan_uuid_raw)
+            };
+            self.create_delivery_approval_registry()?
+                .approve(&exec_plan_uuid, caller.as_ref())
+                .map_err(|err| match err {
+                    DeliveryApprovalRegistryError::AlreadyApproved => {
+                        Error::DeliveryAlreadyApproved
+                    }
+                    _ => Error::DeliveryApprovalFailed,
+                })
+        }
+
+        /// Returns who approved the plan's delivery, if anyone did
+        #[ink(message)]
+        pub fn get_delivery_approver(
+            &self,
+            exec_plan_uuid_str: HexStrNo0x,
+        ) -> Result<Option<AccountId>> {
+            let exec_plan_uuid = {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+                Uuid::new(exec_plan_uuid_raw)
+            };
+            let approver = self
+                .create_delivery_approval_registry()?
+                .get_approver(&exec_plan_uuid)
+                .map_err(|_| Error::DbRequestFailed)?;
+            Ok(approver.map(AccountId::from))
+        }
+
+        /// Admin only. Adds or replaces the campaign with this ID (letters, digits, '-' and '_',
+        /// at most 32). Swaps started with start_sponsored_swap pay no protocol fee, and the
+        /// campaign covers that fee and the escrow's gas for them, up to budget_usd ($ x 10^18)
+        /// over all its plans. Only swaps that stay on eligible_network_names and go between
+        /// eligible_tokens ((network_name, token) pairs, or any token if empty) are eligible.
+        /// Lowering the budget below what the campaign has spent only stops new plans
+        #[ink(message)]
+        pub fn set_sponsorship_campaign(
+            &mut self,
+            campaign_id: String,
+            budget_usd: Amount,
+            eligible_network_names: Vec<String>,
+            eligible_tokens: Vec<(String /* network_name */, String /* token */)>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let quote_engine = self.quote_engine()?;
+            let campaign = SponsorshipCampaign {
+                campaign_id,
+                budget_usd,
+                eligible_chains: eligible_network_names
+                    .iter()
+                    .map(|network_name| io_helper::chain_name_to_id(network_name))
+                    .collect::<Result<Vec<UniversalChainId>>>()?,
+                eligible_tokens: eligible_tokens
+                    .iter()
+                    .map(|(network_name, token)| {
+                        quote_engine
+                            .parse_token_id(network_name, token)
+                            .map_err(Error::from)
+                    })
+                    .collect::<Result<Vec<UniversalTokenId>>>()?,
+            };
+            if !campaign.is_valid() {
+                return Err(Error::InvalidSponsorshipCampaign);
+            }
+            let mut campaigns = self.sponsorship_campaigns.get().unwrap_or_default();
+            campaigns.retain(|other| other.campaign_id != campaign.campaign_id);
+            if campaigns.len() >= MAX_SPONSORSHIP_CAMPAIGNS {
+                return Err(Error::TooManySponsorshipCampaigns);
+            }
+            campaigns.push(campaign);
+            self.sponsorship_campaigns.set(&campaigns);
+            Ok(())
+        }
+
+        /// Admin only. Plans the campaign already paid for keep their waived fee
+        #[ink(message)]
+        pub fn remove_sponsorship_campaign(&mut self, campaign_id: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut campaigns = self.sponsorship_campaigns.get().unwrap_or_default();
+            let num_campaigns = campaigns.len();
+            campaigns.retain(|campaign| campaign.campaign_id != campaign_id);
+            if campaigns.len() == num_campaigns {
+                return Err(Error::SponsorshipCampaignNotFound);
+            }
+            self.sponsorship_campaigns.set(&campaigns);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_sponsorship_campaigns(&self) -> Vec<SponsorshipCampaign> {
+            self.sponsorship_campaigns.get().unwrap_or_default()
+        }
+
+        /// How much of its budget the campaign has used so far ($ x 10^18)
+        #[ink(message)]
+        pub fn get_sponsorship_spent_usd(&self, campaign_id: String) -> Result<Amount> {
+            self.get_sponsorship_campaign(&campaign_id)?;
+            self.create_sponsorship_registry()?
+                .get_spent_usd(&campaign_id)
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        /// Admin only. Lets anyone run a worker: callers of execution_plan_step_forward accrue
+        /// rate.usd_per_step per step they advance plus rate.gas_rebate_bps of the escrow gas
+        /// their txns spent, claimable with claim_relayer_rewards. Claims are paid out of the
+        /// protocol fees of plans that deliver rate.payout_token, which must be a registered
+        /// stablecoin. The rate applies to past contributions too. Pass None to stop tracking
+        /// contributions and accepting claims
+        #[ink(message)]
+        pub fn set_relayer_reward_rate(&mut self, rate: Option<RelayerRewardRate>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if let Some(rate) = &rate {
+                if !rate.is_valid() {
+                    return Err(Error::InvalidPayoutToken);
+                }
+            }
+            self.relayer_reward_rate.set(&rate);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_relayer_reward_rate(&self) -> Option<RelayerRewardRate> {
+            self.relayer_reward_rate.get().flatten()
+        }
+
+        /// Returns what the worker has contributed so far and the rewards it can claim ($ x
+        /// 10^18), before capping them at the fee pool
+        #[ink(message)]
+        pub fn get_relayer_rewards(
+            &self,
+            worker: AccountId,
+        ) -> Result<(RelayerContribution, Amount /* claimable_usd */)> {
+            let rate = self
+                .get_relayer_reward_rate()
+                .ok_or(Error::RelayerRewardsDisabled)?;
+            let contribution = self
+                .create_relayer_registry()?
+                .get_contribution(worker.as_ref())
+                .map_err(|_| Error::DbRequestFailed)?;
+            let claimable_usd = rate.get_claimable_usd(&contribution);
+            Ok((contribution, claimable_usd))
+        }
+
+        /// Returns the protocol fees available for relayer rewards, in the payout token
+        #[ink(message)]
+        pub fn get_relayer_fee_pool(&self) -> Result<Amount> {
+            let rate = self
+                .get_relayer_reward_rate()
+                .ok_or(Error::RelayerRewardsDisabled)?;
+            self.create_relayer_registry()?
+                .get_fee_pool(&rate.payout_token)
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        /// Pays the caller's unclaimed rewards, up to what the fee pool holds, from the escrow
+        /// to recipient_eth_addr on the payout token's chain. A worker has one payout in flight
+        /// at a time: until it settles, this only steps it forward (recipient_eth_addr is
+        /// ignored), so call it again to follow the payout through
+        #[ink(message)]
+        pub fn claim_relayer_rewards(&self, recipient_eth_addr: String) -> Result<RelayerPayout> {
+            let rate = self
+                .get_relayer_reward_rate()
+                .ok_or(Error::RelayerRewardsDisabled)?;
+            let worker: [u8; 32] = *Self::env().caller().as_ref();
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let keys = self.create_key_container()?;
+
+            // We can't tell a missing payout from a failed pull, so a failed pull lets a new
+            // claim overwrite an unsettled payout. That only ever costs the worker
+            if let Ok(mut payout) = execute_step_meta.pull_relayer_payout_from_s3(&worker) {
+                if !payout.is_settled() {
+                    let step_res = payout.step_forward(&execute_step_meta, &keys);
+                    execute_step_meta
+                        .save_relayer_payout_to_s3(&payout)
+                        .map_err(|_| Error::FailedToSaveRelayerPayout)?;
+                    step_res.map_err(Error::StepForwardFailed)?;
+                    return Ok(payout);
+                }
+            }
+
+            let recipient = io_helper::hex_str_to_eth_addr(&recipient_eth_addr)?;
+            let escrow_addr = match keys
+                .get_escrow_key_for_chain(&rate.payout_token.chain)
+                .map(|escrow_key| escrow_key.account.address)
+            {
+                Some(UniversalAddress::Ethereum(escrow_addr)) => escrow_addr,
+                _ => return Err(Error::UninitializedEscrow),
+            };
+            let relayer_registry = self.create_relayer_registry()?;
+            let contribution = relayer_registry
+                .get_contribution(&worker)
+                .map_err(|_| Error::DbRequestFailed)?;
+            let fee_pool_usd = relayer_registry
+                .get_fee_pool(&rate.payout_token)
+                .map_err(|_| Error::DbRequestFailed)
+                .and_then(|fee_pool| {
+                    rate.payout_amount_to_usd(fee_pool)
+                        .ok_or(Error::FailedToGetTokenDecimals)
+                })?;
+            let amount = rate
+                .usd_to_payout_amount(rate.get_claimable_usd(&contribution).min(fee_pool_usd))
+                .ok_or(Error::FailedToGetTokenDecimals)?;
+            if amount == 0 {
+                return Err(Error::NoRelayerRewards);
+            }
+            // What the worker actually gets after rounding down to the token's decimals
+            let amount_usd = rate
+                .payout_amount_to_usd(amount)
+                .ok_or(Error::FailedToGetTokenDecimals)?;
+            let mut payout = RelayerPayout::new(
+                worker,
+                escrow_addr,
+                recipient,
+                rate.payout_token.clone(),
+                amount,
+                amount_usd,
+            )
+            .ok_or(Error::UnsupportedNetwork)?;
+
+            relayer_registry
+                .debit_fee_pool(&rate.payout_token, amount)
+                .map_err(|_| Error::RelayerClaimFailed)?;
+            if relayer_registry
+                .record_claim(
+                    &worker,
+                    contribution.claimed_usd,
+                    contribution.claimed_usd.saturating_add(amount_usd),
+                )
+                .is_err()
+            {
+                // E.g. a concurrent claim by the same worker won, so we hand back our debit
+                let _ = relayer_registry.credit_fee_pool(&rate.payout_token, amount);
+                return Err(Error::RelayerClaimFailed);
+            }
+            // Only broadcast once the payout is on record, so that it can't be paid twice
+            execute_step_meta
+                .save_relayer_payout_to_s3(&payout)
+                .map_err(|_| Error::FailedToSaveRelayerPayout)?;
+            let step_res = payout.step_forward(&execute_step_meta, &keys);
+            // The next claim steps it forward from the saved copy if this save fails, and the
+            // step's nonce is reserved under its UUID, so it can't be sent twice
+            let _ = execute_step_meta.save_relayer_payout_to_s3(&payout);
+            step_res.map_err(Error::StepForwardFailed)?;
+            Ok(payout)
+        }
+
+        /// Admin only. Lets steps that are ready at the same time on a chain share a block of
+        /// nonce_pool_size reserved nonces instead of fetching one at a time. Capped at
+        /// MAX_NONCE_POOL_SIZE. Pass 0 to disable
+        #[ink(message)]
+        pub fn set_nonce_pool_size(&mut self, nonce_pool_size: u8) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.nonce_pool_size
+                .set(&nonce_pool_size.min(MAX_NONCE_POOL_SIZE));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_nonce_pool_size(&self) -> u8 {
+            self.nonce_pool_size.get().unwrap_or_default()
+        }
+
+        /// Admin only. start_swap signs the transfers, wraps and unwraps that start a plan's
+        /// paths when the plan's route is at most presign_max_steps EVM steps, so that workers
+        /// only have to broadcast them. Capped at MAX_PRESIGN_ROUTE_STEPS. Pass 0 to disable
+        #[ink(message)]
+        pub fn set_presign_max_steps(&mut self, presign_max_steps: u8) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.presign_max_steps
+                .set(&presign_max_steps.min(MAX_PRESIGN_ROUTE_STEPS));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_presign_max_steps(&self) -> u8 {
+            self.presign_max_steps.get().unwrap_or_default()
+        }
+
+        /// Admin only. How long submitted txns and DEX swaps stay valid. Takes effect on the
+        /// next message, including for the steps of plans already in flight
+        #[ink(message)]
+        pub fn set_executor_config(&mut self, executor_config: ExecutorConfig) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            executor_config
+                .validate()
+                .map_err(|_| Error::InvalidExecutorConfig)?;
+            self.executor_config.set(&executor_config);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_executor_config(&self) -> ExecutorConfig {
+            self.executor_config.get().unwrap_or_default()
+        }
+
+        /// Admin only. Limits the routes quotes and new plans consider, and which DEX pairs make
+        /// it into the graph. Plans already created keep their route
+        #[ink(message)]
+        pub fn set_routing_config(&mut self, routing_config: RoutingConfig) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            routing_config
+                .validate()
+                .map_err(|_| Error::InvalidRoutingConfig)?;
+            self.routing_config.set(&routing_config);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_routing_config(&self) -> RoutingConfig {
+            self.routing_config.get().unwrap_or_default()
+        }
+
+        /// Admin only. Replaces the pool blocklist: quotes and new plans never swap through
+        /// these pools, whatever their tokens. Plans already created keep their route
+        #[ink(message)]
+        pub fn set_pool_blocklist(&mut self, pools: Vec<BlockedPool>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if pools.len() > MAX_POOL_BLOCKLIST_LEN {
+                return Err(Error::PoolBlocklistTooLong);
+            }
+            let mut pool_blocklist = PoolBlocklistOverlay::new();
+            pool_blocklist.replace(pools);
+            self.pool_blocklist.set(&pool_blocklist);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_pool_blocklist(&self) -> PoolBlocklistOverlay {
+            self.pool_blocklist.get().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_deployment_epoch(&self) -> DeploymentEpoch {
+            self.deployment_epoch.get().unwrap_or_default()
+        }
+
+        /// Admin only. Moves network_name's nonce state from prior_epoch (see
+        /// get_deployment_epoch on the prior deployment) to this deployment's epoch, and stops
+        /// the prior deployment from handing out any more nonces on it. Pass adopt = true if
+        /// this deployment takes over the prior deployment's in-flight plans. Otherwise the
+        /// prior reservations are purged, which fails with NonceReservationsInFlight while any
+        /// of them can still land on chain. Returns the number of reservations moved or purged
+        #[ink(message)]
+        pub fn migrate_nonce_reservations(
+            &self,
+            network_name: String,
+            prior_epoch: DeploymentEpoch,
+            adopt: bool,
+        ) -> Result<u32> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let network_name = network_name.to_lowercase();
+            // Ethereum has a NonceManager too (to claim Wormhole transfers), though no swap
+            // starts there
+            let chain_id = match network_name.as_str() {
+                "ethereum" => universal_chain_id_registry::ETHEREUM,
+                _ => io_helper::chain_name_to_id(&network_name)?,
+            };
+            let escrow = self
+                .create_key_container()?
+                .get_escrow_key_for_chain(&chain_id)
+                .ok_or(Error::UninitializedEscrow)?
+                .account
+                .address;
+            let cur_block = self.get_cur_block(&chain_id)?;
+            let system_nonce = self
+                .create_execute_step_meta()?
+                .get_system_nonce(&chain_id, &escrow)
+                .map_err(|_| Error::RpcRequestFailed)?;
+            let nonce_table = self.get_storage_credential(StorageFunction::NonceTable)?;
+            let nonce_manager = NonceManager::new(
+                nonce_table.access_key,
+                nonce_table.secret_key,
+                &network_name,
+                self.now_millis(),
+                self.get_deployment_epoch(),
+            );
+            Ok(nonce_manager.migrate_reservations(prior_epoch, adopt, cur_block, system_nonce)?)
+        }
+
+        /// Admin only. Sends network_name's RPC requests to url_template instead of the
+        /// registry's endpoint. "{api_key}" in url_template is replaced with api_key.
+        /// Pass url_template = None to go back to the registry's endpoint. Takes effect on the
+        /// next message, so keys can be rotated without redeploying
+        #[ink(message)]
+        pub fn set_rpc_endpoint(
+            &mut self,
+            network_name: String,
+            url_template: Option<String>,
+            api_key: Option<String>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            // Also encrypts any keys that were stored before keys were encrypted
+            let mut rpc_endpoints: Vec<RpcEndpoint> = self
+                .rpc_endpoints
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|endpoint| endpoint.chain_id != chain_id)
+                .map(RpcEndpoint::with_encrypted_api_key)
+                .collect();
+            if let Some(url_template) = url_template {
+                rpc_endpoints.push(RpcEndpoint::new(chain_id, url_template, api_key)?);
+            }
+            self.rpc_endpoints.set(&rpc_endpoints);
+            Ok(())
+        }
+
+        /// Admin only. Makes the escrow pay network_name's extrinsic fees in the pallet-assets
+        /// asset asset_id (which it must hold), so it needs less of the native token. Only chains
+        /// with pallet-asset-tx-payment support this. Pass asset_id = None to pay in the native
+        /// token again
+        #[ink(message)]
+        pub fn set_fee_asset(
+            &mut self,
+            network_name: String,
+            asset_id: Option<AssetId>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            let mut fee_assets = self.fee_assets.get().unwrap_or_default();
+            fee_assets.retain(|(fee_asset_chain_id, _)| *fee_asset_chain_id != chain_id);
+            if let Some(asset_id) = asset_id {
+                let is_supported = match chain_info.asset_tx_payment {
+                    Some(AssetTxPaymentKind::U32AssetIds) => u32::try_from(asset_id).is_ok(),
+                    Some(AssetTxPaymentKind::U128AssetIds) => true,
+                    None => false,
+                };
+                if !is_supported {
+                    return Err(Error::UnsupportedFeeAsset);
+                }
+                fee_assets.push((chain_id, asset_id));
+            }
+            self.fee_assets.set(&fee_assets);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_fee_assets(&self) -> Vec<(UniversalChainId, AssetId)> {
+            self.fee_assets.get().unwrap_or_default()
+        }
+
+        /// Admin only. New plans depositing on network_name have the user deposit into their own
+        /// forwarder address of factory_eth_addr, which a deposit sweep step then drains into the
+        /// shared escrow. The factory must be deployed with the escrow as its owner, and
+        /// forwarder_init_code_hash must be the keccak256 of the forwarder's init code that it
+        /// deploys with CREATE2. Pass factory = None to have users deposit into the escrow again
+        #[ink(message)]
+        pub fn set_deposit_forwarder_factory(
+            &mut self,
+            network_name: String,
+            // (factory_eth_addr, forwarder_init_code_hash)
+            factory: Option<(HexStrNo0x, HexStrNo0x)>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            let mut factories = self.deposit_forwarder_factories.get().unwrap_or_default();
+            factories.retain(|(factory_chain_id, _)| *factory_chain_id != chain_id);
+            if let Some((factory_eth_addr, forwarder_init_code_hash)) = factory {
+                let factory = DepositForwarderFactory {
+                    addr: io_helper::hex_str_to_eth_addr(&factory_eth_addr)?,
+                    forwarder_init_code_hash: io_helper::hex_str_to_u8_32(
+                        forwarder_init_code_hash
+                            .strip_prefix("0x")
+                            .unwrap_or(&forwarder_init_code_hash),
+                    )?,
+                };
+                let rpc_url =
+                    get_rpc_url(&self.rpc_endpoints.get().unwrap_or_default(), chain_info);
+                let code = get_code(&rpc_url, factory.addr).map_err(|_| Error::RpcRequestFailed)?;
+                if code.is_empty() {
+                    return Err(Error::DepositForwarderFactoryHasNoCode);
+                }
+                factories.push((chain_id, factory));
+            }
+            self.deposit_forwarder_factories.set(&factories);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_deposit_forwarder_factories(
+            &self,
+        ) -> Vec<(UniversalChainId, DepositForwarderFactory)> {
+            self.deposit_forwarder_factories.get().unwrap_or_default()
+        }
+
+        // The admin's factory for the chain, else the registry's
+        fn get_deposit_forwarder_factory(
+            &self,
+            chain_info: &ChainInfo,
+        ) -> Option<DepositForwarderFactory> {
+            self.deposit_forwarder_factories
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .find(|(chain_id, _)| *chain_id == chain_info.chain_id)
+                .map(|(_, factory)| factory)
+                .or(chain_info.deposit_forwarder_factory)
+        }
+
+        /// Admin only. On network_name, extrinsics sending from real_account (hex pubkey or
+        /// SS58) are signed by delegate_account and wrapped in proxy.proxy, so real_account's key
+        /// can stay offline. delegate_account must be one of the escrow's Substrate keys and
+        /// real_account must have added it as a proxy (of force_proxy_type, the chain's ProxyType
+        /// index, if set). Pass delegate_account = None to sign as real_account again
+        #[ink(message)]
+        pub fn set_substrate_proxy(
+            &mut self,
+            network_name: String,
+            real_account: String,
+            delegate_account: Option<String>,
+            force_proxy_type: Option<u8>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let prefix = io_helper::chain_name_to_ss58_prefix(&network_name)?;
+            let real = SubstratePublicKey {
+                0: io_helper::str_to_substrate_pubkey(&real_account, Some(prefix))?,
+            };
+            let mut substrate_proxies = self.substrate_proxies.get().unwrap_or_default();
+            substrate_proxies.retain(|proxy| proxy.chain_id != chain_id || proxy.real != real);
+            if let Some(delegate_account) = delegate_account {
+                let delegate = SubstratePublicKey {
+                    0: io_helper::str_to_substrate_pubkey(&delegate_account, Some(prefix))?,
+                };
+                substrate_proxies.push(SubstrateProxy::new(
+                    chain_id,
+                    real,
+                    delegate,
+                    force_proxy_type,
+                )?);
+            }
+            self.substrate_proxies.set(&substrate_proxies);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_substrate_proxies(&self) -> Vec<SubstrateProxy> {
+            self.substrate_proxies.get().unwrap_or_default()
+        }
+
+        /// Admin only. Plans quoted at most max_plan_usd ($ x 10^18) step forward on
+        /// network_name's best block rather than waiting for finality. Larger plans, and plans
+        /// with no quote, still wait. Pass None to wait for finality on every plan again
+        #[ink(message)]
+        pub fn set_best_block_tier(
+            &mut self,
+            network_name: String,
+            max_plan_usd: Option<Amount>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let mut best_block_tiers = self.best_block_tiers.get().unwrap_or_default();
+            best_block_tiers.retain(|tier| tier.chain_id != chain_id);
+            if let Some(max_plan_usd) = max_plan_usd {
+                best_block_tiers.push(BestBlockTier {
+                    chain_id,
+                    max_plan_usd,
+                });
+            }
+            self.best_block_tiers.set(&best_block_tiers);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_best_block_tiers(&self) -> Vec<BestBlockTier> {
+            self.best_block_tiers.get().unwrap_or_default()
+        }
+
+        /// Admin only. Returns (chain_id, url_template, has_api_key) for each override. The API
+        /// keys themselves are never returned
+        #[ink(message)]
+        pub fn get_rpc_endpoints(&self) -> Result<Vec<(UniversalChainId, String, bool)>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            Ok(self
+                .rpc_endpoints
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|endpoint| {
+                    let has_api_key = endpoint.has_api_key();
+                    (endpoint.chain_id, endpoint.url_template, has_api_key)
+                })
+                .collect())
+        }
+
+        /// Admin only. Pass webhook_url = None to stop posting stuck and quarantined plans
+        #[ink(message)]
+        pub fn set_stuck_plan_webhook(&mut self, webhook_url: Option<String>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.stuck_plan_webhook_url.set(&webhook_url);
+            Ok(())
+        }
+
+        // We claim the plan so that we don't race a worker that is stepping it forward
+        fn escalate_stuck_exec_plan(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan_uuid: &Uuid,
+        ) -> bool /* didEscalate */ {
+            if !execute_step_meta.claim_exec_plan(exec_plan_uuid) {
+                return false;
+            }
+            let did_escalate = match execute_step_meta.pull_exec_plan_from_s3(exec_plan_uuid) {
+                Ok(mut exec_plan) => {
+                    escalate_stuck_plan(&mut exec_plan, execute_step_meta.cur_timestamp())
+                        && execute_step_meta.save_exec_plan_to_s3(&exec_plan).is_ok()
+                }
+                Err(_) => false,
+            };
+            // Due right away so that the refund starts on the next step forward
+            let _ = execute_step_meta
+                .unclaim_exec_plan(exec_plan_uuid, execute_step_meta.cur_timestamp());
+            did_escalate
+        }
+
+        fn is_closed_status(status: &ExecutableSimpleStatus) -> bool {
+            *status == ExecutableSimpleStatus::Succeeded
+                || *status == ExecutableSimpleStatus::Failed
+                || *status == ExecutableSimpleStatus::Dropped
+                || *status == ExecutableSimpleStatus::Refunded
+        }
+
+        fn aggregate_pending_plan_analytics(
+            execute_step_meta: &ExecuteStepMeta,
+        ) -> Result<Vec<DaysSinceEpoch>> {
+            let pending_plans = execute_step_meta
+                .pull_pending_plan_analytics_from_s3()
+                .map_err(|_| Error::FailedToPullPlanAnalytics)?;
+            let mut daily_stats: Vec<DailyStats> = Vec::new();
+            for plan in pending_plans.iter() {
+                let day = get_day(plan.closed_millis);
+                if daily_stats.iter().all(|stats| stats.day != day) {
+                    // The day's first plan creates its stats. Any other error would reset them
+                    daily_stats.push(match execute_step_meta.pull_daily_stats_from_s3(day) {
+                        Ok(stats) => stats,
+                        Err(ExecutableError::S3ObjectNotFound) => DailyStats::new(day),
+                        Err(_) => return Err(Error::FailedToPullPlanAnalytics),
+                    });
+                }
+            }
+            let updated_days = aggregate_plan_analytics(&mut daily_stats, &pending_plans);
+            for stats in daily_stats.iter() {
+                execute_step_meta
+                    .save_daily_stats_to_s3(stats)
+                    .map_err(|_| Error::FailedToSavePlanAnalytics)?;
+            }
+            execute_step_meta
+                .save_pending_plan_analytics_to_s3(&[])
+                .map_err(|_| Error::FailedToSavePlanAnalytics)?;
+            Ok(updated_days)
+        }
+
+        fn run_due_canaries(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+        ) -> Result<Vec<CanaryResult>> {
+            let canaries = self.canaries.get().unwrap_or_default();
+            let mut ledger = execute_step_meta
+                .pull_canary_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullCanaryLedger)?;
+            let now_millis = self.now_millis();
+            let mut results: Vec<CanaryResult> = Vec::new();
+            for (name, exec_plan_uuid) in ledger.get_pending_runs() {
+                // Tried again next time if S3 is down
+                let exec_plan = match execute_step_meta.pull_exec_plan_from_s3(&exec_plan_uuid) {
+                    Ok(exec_plan) => exec_plan,
+                    Err(_) => continue,
+                };
+                if !is_canary_run_closed(&exec_plan) {
+                    continue;
+                }
+                let result =
+                    canaries
+                        .iter()
+                        .find(|canary| canary.name == name)
+                        .and_then(|canary| {
+                            PlanAnalytics::from_closed_exec_plan(&exec_plan, now_millis)
+                                .and_then(|plan| CanaryResult::new(canary, &plan))
+                        });
+                results.extend(result.clone());
+                ledger.record_closed(&name, result);
+            }
+            ledger.retain_canaries(&canaries);
+
+            // A run started now would sit there until the drain is over
+            if !self.is_draining().unwrap_or(false) {
+                let escrow_eth_addr = self
+                    .get_shared_escrow_addrs()?
+                    .iter()
+                    .find_map(|addr| match addr {
+                        UniversalAddress::Ethereum(eth_addr) => Some(*eth_addr),
+                        UniversalAddress::Substrate(_) => None,
+                    })
+                    .ok_or(Error::UninitializedEscrow)?;
+                for canary in canaries.iter() {
+                    if !ledger.is_due(canary, now_millis) {
+                        continue;
+                    }
+                    match self.start_canary_run(execute_step_meta, canary, escrow_eth_addr) {
+                        Ok(exec_plan_uuid) => {
+                            ledger.record_started(canary, exec_plan_uuid, now_millis)
+                        }
+                        // Pausing a chain is the operator's call, not a broken route. Neither is
+                        // an S3 hiccup, so the run is tried again next time
+                        Err(Error::ChainPaused) | Err(Error::FailedToPullPausedChains) => {}
+                        Err(_) => {
+                            ledger.record_unplanned(canary, now_millis);
+                            results.extend(ledger.results.last().cloned());
+                        }
+                    }
+                }
+            }
+            execute_step_meta
+                .save_canary_ledger_to_s3(&ledger)
+                .map_err(|_| Error::FailedToSaveCanaryLedger)?;
+            Ok(results)
+        }
+
+        // Checked like a user's swap, except that the escrow both funds and receives the run
+        fn start_canary_run(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+            canary: &Canary,
+            escrow_eth_addr: EthAddress,
+        ) -> Result<Uuid> {
+            let context = self.quote_engine()?.build_context()?;
+            let mut exec_plan = create_canary_plan(&context, canary, escrow_eth_addr)?;
+            let paused_chains = execute_step_meta
+                .pull_paused_chains_from_s3()
+                .map_err(|_| Error::FailedToPullPausedChains)?;
+            if get_plan_chains(&exec_plan)
+                .iter()
+                .any(|chain_id| paused_chains.contains(chain_id))
+            {
+                return Err(Error::ChainPaused);
+            }
+            self.check_plan_dex_routers(&exec_plan, execute_step_meta)?;
+            let cur_block = self.get_cur_block(&canary.src_token.chain)?;
+            set_canary_prestart(&mut exec_plan, cur_block).ok_or(Error::InvalidCanary)?;
+            Self::register_new_exec_plan(execute_step_meta, &mut exec_plan);
+            Ok(exec_plan.uuid)
+        }
+
+        fn shadow_quote_pending_plans(&self, execute_step_meta: &ExecuteStepMeta) -> Result<u32> {
+            let mut pending_plans = execute_step_meta
+                .pull_pending_shadow_quotes_from_s3()
+                .map_err(|_| Error::FailedToPullPlanAnalytics)?;
+            let num_plans = pending_plans.len().min(MAX_SHADOW_QUOTES_PER_RUN);
+            let plans: Vec<PlanAnalytics> = pending_plans.drain(..num_plans).collect();
+
+            // Plans that started close together share archives, so each one is only pulled and
+            // turned into a graph once
+            let mut archives: Vec<(ArchiveSlot, Option<ArchivedPairCache>)> = Vec::new();
+            let mut contexts: Vec<(MillisSinceEpoch, Option<QuoteContext>)> = Vec::new();
+            let mut results: Vec<ShadowQuoteResult> = Vec::new();
+            for plan in plans.iter() {
+                let candidate_slots = get_candidate_archive_slots(plan.started_millis);
+                for slot in candidate_slots.iter() {
+                    if archives
+                        .iter()
+                        .all(|(archive_slot, _)| archive_slot != slot)
+                    {
+                        archives.push((
+                            *slot,
+                            execute_step_meta
+                                .pull_pair_cache_archive_from_s3(*slot)
+                                .ok(),
+                        ));
+                    }
+                }
+                let archive = pick_archive(
+                    archives
+                        .iter()
+                        .filter(|(slot, _)| candidate_slots.contains(slot))
+                        .filter_map(|(_, archive)| archive.as_ref()),
+                    plan.started_millis,
+                );
+                let shadow_amount_out = match archive {
+                    Some(archive) => {
+                        if contexts
+                            .iter()
+                            .all(|(fetched_millis, _)| *fetched_millis != archive.fetched_millis)
+                        {
+                            let context = archive.pair_cache.to_graph().ok().map(|graph| {
+                                QuoteContext::from_graph(graph, self.now_millis())
+                                    .with_routing_config(self.get_routing_config())
+                            });
+                            contexts.push((archive.fetched_millis, context));
+                        }
+                        contexts
+                            .iter()
+                            .find(|(fetched_millis, _)| *fetched_millis == archive.fetched_millis)
+                            .and_then(|(_, context)| context.as_ref())
+                            .and_then(|context| get_shadow_amount_out(context, plan))
+                    }
+                    None => None,
+                };
+                if let Some(result) = ShadowQuoteResult::new(
+                    plan,
+                    archive.map(|archive| archive.fetched_millis),
+                    shadow_amount_out,
+                ) {
+                    results.push(result);
+                }
+            }
+
+            let mut daily_stats: Vec<ShadowQuoteStats> = Vec::new();
+            for result in results.iter() {
+                let day = get_day(result.closed_millis);
+                if daily_stats.iter().all(|stats| stats.day != day) {
+                    // The day's first result creates its stats. Any other error would reset them
+                    let stats = match execute_step_meta.pull_shadow_quote_stats_from_s3(day) {
+                        Ok(stats) => stats,
+                        Err(ExecutableError::S3ObjectNotFound) => ShadowQuoteStats::new(day),
+                        Err(_) => return Err(Error::FailedToPullPlanAnalytics),
+                    };
+                    daily_stats.push(stats);
+                }
+            }
+            let _ = aggregate_shadow_quote_results(&mut daily_stats, &results);
+            for stats in daily_stats.iter() {
+                execute_step_meta
+                    .save_shadow_quote_stats_to_s3(stats)
+                    .map_err(|_| Error::FailedToSavePlanAnalytics)?;
+            }
+            execute_step_meta
+                .save_pending_shadow_quotes_to_s3(&pending_plans)
+                .map_err(|_| Error::FailedToSavePlanAnalytics)?;
+            Ok(num_plans as u32)
+        }
+
+        fn record_closed_exec_plan(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &ExecutionPlan,
+        ) -> Result<()> {
+            // A paper plan never moved funds, and we keep it out of the stats
+            if exec_plan.paper_trade {
+                return Ok(());
+            }
+            let _ = Self::record_stranded_funds(execute_step_meta, exec_plan)?;
+            // Stats are best-effort, so we don't hold up closing the plan for them
+            let _ = Self::record_plan_analytics(execute_step_meta, exec_plan);
+            Ok(())
+        }
+
+        fn record_plan_analytics(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &ExecutionPlan,
+        ) -> Result<()> {
+            let plan_analytics =
+                PlanAnalytics::from_closed_exec_plan(exec_plan, execute_step_meta.cur_timestamp())
+                    .ok_or(Error::FailedToSavePlanAnalytics)?;
+            // Its own object, so it does not need the lock
+            execute_step_meta
+                .save_plan_analytics_record_to_s3(&plan_analytics)
+                .map_err(|_| Error::FailedToSavePlanAnalytics)?;
+            if !execute_step_meta.claim_plan_analytics() {
+                return Err(Error::PlanAnalyticsClaimedByAnotherWorker);
+            }
+            let record_res =
+                Self::record_pending_plan_analytics(execute_step_meta, exec_plan, plan_analytics);
+            let _ = execute_step_meta.release_plan_analytics();
+            record_res
+        }
+
+        // Under the plan analytics lock. Every object is read before any is saved, so that one
+        // that can't be read is never saved back as empty
+        fn record_pending_plan_analytics(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &ExecutionPlan,
+            plan_analytics: PlanAnalytics,
+        ) -> Result<()> {
+            let mut pending_plans = execute_step_meta
+                .pull_pending_plan_analytics_from_s3()
+                .map_err(|_| Error::FailedToPullPlanAnalytics)?;
+            // Only plans with a quote have something to compare the shadow quote against
+            let mut pending_shadow_quotes = execute_step_meta
+                .pull_pending_shadow_quotes_from_s3()
+                .map_err(|_| Error::FailedToPullPlanAnalytics)?;
+            if plan_analytics.quote.is_some() {
+                pending_shadow_quotes.push(plan_analytics.clone());
+                let num_dropped = pending_shadow_quotes
+                    .len()
+                    .saturating_sub(MAX_PENDING_SHADOW_QUOTES);
+                pending_shadow_quotes.drain(..num_dropped);
+            }
+            // Swaps that went through count as uses of their router's approval
+            let mut allowance_cache = execute_step_meta
+                .pull_allowance_cache_from_s3()
+                .map_err(|_| Error::FailedToPullAllowanceCache)?;
+            allowance_cache
+                .record_dex_swaps(&plan_analytics.dex_swaps, execute_step_meta.cur_timestamp());
+            pending_plans.push(plan_analytics);
+            let mut step_durations = execute_step_meta
+                .pull_step_durations_from_s3()
+                .map_err(|_| Error::FailedToPullStepDurations)?;
+            step_durations.add_plan(exec_plan);
+            // Failed steps count against their pools and bridge lanes (see route_blacklist)
+            let mut route_blacklist = execute_step_meta
+                .pull_route_blacklist_from_s3()
+                .map_err(|_| Error::FailedToPullRouteBlacklist)?;
+            let did_record_failures = route_blacklist
+                .record_closed_exec_plan(exec_plan, execute_step_meta.cur_timestamp());
+            execute_step_meta
+                .save_pending_plan_analytics_to_s3(&pending_plans)
+                .and(execute_step_meta.save_pending_shadow_quotes_to_s3(&pending_shadow_quotes))
+                .and(execute_step_meta.save_step_durations_to_s3(&step_durations))
+                .and(execute_step_meta.save_allowance_cache_to_s3(&allowance_cache))
+                .and(if did_record_failures {
+                    execute_step_meta.save_route_blacklist_to_s3(&route_blacklist)
+                } else {
+                    Ok(())
+                })
+                .map_err(|_| Error::FailedToSavePlanAnalytics)
+        }
+
+        fn record_stranded_funds(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &ExecutionPlan,
+        ) -> Result<()> {
+            if exec_plan.get_status() == ExecutableSimpleStatus::Succeeded {
+                return Ok(());
+            }
+            if !execute_step_meta.claim_stranded_funds_ledger() {
+                return Err(Error::StrandedFundsLedgerClaimedByAnotherWorker);
+            }
+            let save_res = execute_step_meta
+                .pull_stranded_funds_ledger_from_s3()
+                .map_err(|_| Error::FailedToPullStrandedFundsLedger)
+                .and_then(|mut ledger| {
+                    ledger.record_closed_exec_plan(exec_plan);
+                    execute_step_meta
+                        .save_stranded_funds_ledger_to_s3(&ledger)
+                        .map_err(|_| Error::FailedToSaveStrandedFundsLedger)
+                });
+            let _ = execute_step_meta.release_stranded_funds_ledger();
+            save_res
+        }
+
+        // Credits the caller with the steps it just advanced and the gas their txns spent.
+        // Best-effort, so that the coordination store never holds up a plan
+        fn record_relayer_contribution(
+            &self,
+            exec_plan: &ExecutionPlan,
+            num_steps_advanced: u32,
+            old_gas_fee_usd: Amount,
+        ) {
+            // Paper plans only simulate their txns
+            if exec_plan.paper_trade || self.get_relayer_reward_rate().is_none() {
+                return;
+            }
+            let gas_fee_usd = get_realized_gas_fee_usd(exec_plan).saturating_sub(old_gas_fee_usd);
+            if let Ok(relayer_registry) = self.create_relayer_registry() {
+                let _ = relayer_registry.record_contribution(
+                    Self::env().caller().as_ref(),
+                    num_steps_advanced,
+                    gas_fee_usd,
+                );
+            }
+        }
+
+        // Sets aside the protocol fee of a closed plan for relayer rewards, if it is in the
+        // payout token. Best-effort like record_relayer_contribution
+        fn fund_relayer_fee_pool(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &ExecutionPlan,
+        ) {
+            if exec_plan.paper_trade {
+                return;
+            }
+            let rate = match self.get_relayer_reward_rate() {
+                Some(rate) => rate,
+                None => return,
+            };
+            let amount = match PlanAnalytics::from_closed_exec_plan(
+                exec_plan,
+                execute_step_meta.cur_timestamp(),
+            )
+            .and_then(|plan_analytics| rate.get_fee_pool_contribution(&plan_analytics.protocol_fee))
+            {
+                Some(amount) => amount,
+                None => return,
+            };
+            if let Ok(relayer_registry) = self.create_relayer_registry() {
+                let _ = relayer_registry.fund_fee_pool(&rate.payout_token, &exec_plan.uuid, amount);
+            }
+        }
+
+        // A sponsored plan that closed without going through hands its drawdown back to the
+        // campaign, less the gas it used. Best-effort like the fee pool
+        fn credit_back_sponsorship(&self, exec_plan: &ExecutionPlan) {
+            if exec_plan.get_status() == ExecutableSimpleStatus::Succeeded {
+                return;
+            }
+            let campaign_id = match &exec_plan.sponsorship_campaign_id {
+                Some(campaign_id) => campaign_id,
+                None => return,
+            };
+            if let Ok(sponsorship_registry) = self.create_sponsorship_registry() {
+                let _ = sponsorship_registry.credit_back(
+                    campaign_id,
+                    &exec_plan.uuid,
+                    get_realized_gas_fee_usd(exec_plan),
+                );
+            }
+        }
+
+        fn create_execute_step_meta(&self) -> Result<ExecuteStepMeta> {
+            let mut execute_step_meta = ExecuteStepMeta::new_for_astar_moonbeam_polkadot(
+                self.now_millis(),
+                self.get_storage_credentials()?,
+                self.get_deployment_epoch(),
+            );
+            execute_step_meta.set_rpc_endpoints(self.rpc_endpoints.get().unwrap_or_default());
+            execute_step_meta.set_fee_assets(self.fee_assets.get().unwrap_or_default());
+            execute_step_meta
+                .set_substrate_proxies(self.substrate_proxies.get().unwrap_or_default());
+            execute_step_meta.set_executor_config(self.get_executor_config());
+            Ok(execute_step_meta)
+        }
+
+        fn get_storage_credentials(&self) -> Result<StorageCredentials> {
+            Ok(StorageCredentials {
+                plan_store: self.get_storage_credential(StorageFunction::PlanStore)?,
+                claim_table: self.get_storage_credential(StorageFunction::ClaimTable)?,
+                nonce_table: self.get_storage_credential(StorageFunction::NonceTable)?,
+                analytics: self.get_storage_credential(StorageFunction::Analytics)?,
+            })
+        }
+
+        fn get_storage_credential(&self, function: StorageFunction) -> Result<StorageCredential> {
+            let shared_pair = |access_key: &Option<String>, secret_key: &Option<String>| {
+                access_key
+                    .clone()
+                    .zip(secret_key.clone())
+                    .map(|(access_key, secret_key)| StorageCredential {
+                        access_key,
+                        secret_key,
+                    })
+            };
+            resolve_storage_credential(
+                &self.storage_credentials.get().unwrap_or_default(),
+                function,
+                shared_pair(&self.dynamodb_access_key, &self.dynamodb_secret_key),
+                shared_pair(&self.s3_access_key, &self.s3_secret_key),
+            )
+            .ok_or(Error::UninitializedEscrow)
+        }
+
+        fn get_best_block_chains(&self, exec_plan: &ExecutionPlan) -> Vec<UniversalChainId> {
+            let best_block_tiers = self.best_block_tiers.get().unwrap_or_default();
+            let plan_usd = exec_plan.quote.as_ref().map(|quote| quote.amount_in_usd);
+            best_block_tiers
+                .iter()
+                .map(|tier| tier.chain_id)
+                .filter(|chain_id| {
+                    get_finality_policy(&best_block_tiers, chain_id, plan_usd)
+                        == FinalityPolicy::BestBlock
+                })
+                .collect()
+        }
+
+        fn requires_delivery_approval(&self, exec_plan: &ExecutionPlan) -> bool {
+            if exec_plan.paper_trade {
+                return false;
+            }
+            match self.delivery_approval_threshold_usd.get().flatten() {
+                Some(threshold_usd) => exec_plan
+                    .quote
+                    .as_ref()
+                    .map_or(true, |quote| quote.amount_in_usd > threshold_usd),
+                None => false,
+            }
+        }
+
+        // If we can't tell whether the delivery was approved, we hold it and check again on
+        // the next step forward
+        fn is_delivery_held(&self, exec_plan: &ExecutionPlan) -> bool {
+            if !self.requires_delivery_approval(exec_plan)
+                || exec_plan.postend_escrow_to_user_transfer.get_status()
+                    != ExecutableSimpleStatus::NotStarted
+            {
+                return false;
+            }
+            self.create_delivery_approval_registry()
+                .ok()
+                .and_then(|registry| registry.get_approver(&exec_plan.uuid).ok())
+                .map_or(true, |approver| approver.is_none())
+        }
+
+        fn create_key_container(&self) -> Result<KeyContainer> {
+            if let Some(signer_url) = &self.remote_signer_url {
+                return self.create_remote_key_container(signer_url);
+            }
+            let (eth_address, eth_key) = match &self.threshold_aggregator_url {
+                Some(aggregator_url) => self.create_threshold_eth_key(aggregator_url)?,
+                None => {
+                    let eth_secret_key = self
+                        .escrow_eth_private_key
+                        .ok_or(Error::UninitializedEscrow)?
+                        .clone();
+                    (
+                        Self::get_eth_address_from_pair(&sp_core::ecdsa::Pair::from_seed(
+                            &eth_secret_key,
+                        ))?,
+                        SigningKey::Local(eth_secret_key),
+                    )
+                }
+            };
+            let substrate_secret_key = self
+                .escrow_substrate_private_key
+                .ok_or(Error::UninitializedEscrow)?
+                .clone();
+
+            let substrate_pubkey = SubstratePublicKey {
+                0: match self.escrow_substrate_sig_scheme {
+                    SignatureScheme::Ed25519 => {
+                        sp_core::ed25519::Pair::from_seed(&substrate_secret_key)
+                            .public()
+                            .0
+                    }
+                    _ => {
+                        sp_core::sr25519::Pair::from_seed(&substrate_secret_key)
+                            .public()
+                            .0
+                    }
+                },
+            };
+
+            Ok(KeyContainer::new(vec![
+                AddressKeyPair {
+                    address: UniversalAddress::Ethereum(eth_address),
+                    key: eth_key,
+                    sig_scheme: SignatureScheme::Ethereum,
+                },
+                AddressKeyPair {
+                    address: UniversalAddress::Substrate(substrate_pubkey),
+                    key: SigningKey::Local(substrate_secret_key),
+                    sig_scheme: self.escrow_substrate_sig_scheme,
+                },
+            ]))
+        }
+
+        fn create_threshold_eth_key(
+            &self,
+            aggregator_url: &str,
+        ) -> Result<(EthAddress, SigningKey)> {
+            let address = EthAddress {
+                0: self
+                    .threshold_eth_address
+                    .ok_or(Error::UninitializedEscrow)?,
+            };
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            let threshold_signer = ThresholdSigner {
+                address,
+                key_share: self.threshold_key_share.ok_or(Error::UninitializedEscrow)?,
+                party_index: self.threshold_party_index,
+                threshold: self.threshold,
+                aggregator_api: ThresholdAggregatorApi::new(aggregator_url.to_string()),
+                // Partial signatures are collected in the same store we use to coordinate
+                // nonces and plan assignments
+                dynamodb_access_key: claim_table.access_key,
+                dynamodb_secret_key: claim_table.secret_key,
+                timestamp_millis: self.now_millis(),
+            };
+            Ok((address, SigningKey::Threshold(threshold_signer)))
+        }
+
+        fn create_remote_key_container(&self, signer_url: &str) -> Result<KeyContainer> {
+            let api = RemoteSignerApi::new(
+                signer_url.to_string(),
+                self.remote_signer_auth_secret
+                    .clone()
+                    .ok_or(Error::UninitializedEscrow)?,
+            );
+            let eth_address = UniversalAddress::Ethereum(EthAddress {
+                0: self
+                    .remote_signer_eth_address
+                    .ok_or(Error::UninitializedEscrow)?,
+            });
+            let substrate_address = UniversalAddress::Substrate(SubstratePublicKey {
+                0: self
+                    .remote_signer_substrate_pubkey
+                    .ok_or(Error::UninitializedEscrow)?,
+            });
+            let timestamp_millis = self.now_millis();
+
+            Ok(KeyContainer::new(vec![
+                AddressKeyPair {
+                    address: eth_address.clone(),
+                    key: SigningKey::Remote(RemoteSigner {
+                        api: api.clone(),
+                        address: eth_address,
+                        timestamp_millis,
+                    }),
+                    sig_scheme: SignatureScheme::Ethereum,
+                },
+                AddressKeyPair {
+                    address: substrate_address.clone(),
+                    key: SigningKey::Remote(RemoteSigner {
+                        api,
+                        address: substrate_address,
+                        timestamp_millis,
+                    }),
+                    sig_scheme: self.escrow_substrate_sig_scheme,
+                },
+            ]))
+        }
+
+        #[ink(message)]
+        pub fn start_swap(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String, // String because JavaScript numbers are maxed at 2^53
+            // If set, the swap is executed in sequential tranches of at most this amount so
+            // that only one tranche is in flight at a time (slower but bounds funds at risk)
+            max_amount_at_risk_str: Option<String>,
+            // If set, the plan is refunded unless its first hop is confirmed within this many
+            // src chain blocks of the deposit landing, instead of retrying until the deadline
+            now_or_never_max_blocks: Option<BlockNum>,
+            // If dest_token is native, deliver its wrapped version (e.g. WGLMR) instead
+            deliver_wrapped: bool,
+            // Skips the check that dest_eth_addr isn't the zero address, a token contract or a
+            // precompile (see destination_guard). Only for callers that really mean it
+            allow_unsafe_destination: bool,
+            api_key: Option<String>,
+            // Opaque bytes (e.g. the integrator's order ID) stored with the plan. The plan can
+            // then be looked up by them with find_plan_by_tag
+            metadata: Option<HexStrNo0x>,
+            // Name of the escrow key set the plan's funds go through (see add_escrow_key_set),
+            // which api_key must be allowed to use. None for the shared escrow. The user's
+            // deposit must then go to that escrow's address (see get_escrow_accounts)
+            escrow: Option<String>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                max_amount_at_risk_str,
+                now_or_never_max_blocks,
+                deliver_wrapped,
+                false,
+                allow_unsafe_destination,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                None,
+                escrow,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Same as start_swap, but the src token is moved to the dest token as-is, e.g. DOT on
+        /// Polkadot to xcDOT on Moonbeam. The route is only bridges (and wraps or unwraps), so
+        /// there is no price exposure, and tokens that aren't the same asset fail with NoPathFound
+        #[ink(message)]
+        pub fn transfer(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            // See start_swap
+            allow_unsafe_destination: bool,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                None,
+                None,
+                false,
+                true,
+                allow_unsafe_destination,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Where and how to deposit on a Substrate chain whose users can't easily send an EVM
+        /// txn (e.g. withdrawing from an exchange). The deposit goes to the shared escrow and is
+        /// told apart from others by a fresh remark, so the swap can be started afterwards with
+        /// just the remark (see start_remark_deposit_swap)
+        #[ink(message)]
+        pub fn get_deposit_instructions(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+        ) -> Result<DepositInstructions> {
+            let chain_id = io_helper::chain_name_to_id(&src_network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            if !chain_info.accepts_remark_deposits {
+                return Err(Error::RemarkDepositsUnsupported);
+            }
+            // Checked again when the swap starts, but we should not hand out a deposit account
+            // for a swap that can't start
+            let (resolved_dest_eth_addr, _) = self.resolve_destination(dest_eth_addr.clone())?;
+            self.check_destination_proven(&resolved_dest_eth_addr)?;
+            let exec_plan = self.compute_execution_plan(
+                src_network_name.clone(),
+                dest_network_name.clone(),
+                src_eth_addr.clone(),
+                resolved_dest_eth_addr,
+                src_token.clone(),
+                dest_token.clone(),
+                amount_in_str.clone(),
+                None,
+            )?;
+            let amount_in = match &exec_plan.prestart_user_to_escrow_transfer.inner {
+                ExecutionStepEnum::EthSend(step) => step.amount.ok_or(Error::InvalidNumber)?,
+                // Only native tokens can be sent alongside a remark
+                _ => return Err(Error::RemarkDepositsUnsupported),
+            };
+            let deposit_account = chain_info
+                .get_evm_backing_account(&Self::get_plan_escrow_eth_addr(&exec_plan)?)
+                .ok_or(Error::RemarkDepositsUnsupported)?;
+            let entropy = (pink_extension::ext().getrandom(32), self.now_millis()).encode();
+            let remark = get_deposit_remark(
+                &io_helper::hex_str_to_eth_addr(&src_eth_addr)?,
+                amount_in,
+                &entropy,
+            );
+            let cur_block = self.get_cur_block(&chain_id)?;
+            let request = RemarkDepositRequest {
+                remark,
+                src_network_name: src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                deposit_account: deposit_account.clone(),
+                start_block_num: cur_block,
+                end_block_num: cur_block + REMARK_DEPOSIT_NUM_BLOCKS_ALIVE,
+            };
+            self.create_execute_step_meta()?
+                .save_remark_deposit_request_to_s3(&request)
+                .map_err(|_| Error::FailedToSaveRemarkDeposit)?;
+            let deposit_account = match &deposit_account {
+                UniversalAddress::Substrate(pubkey) => encode_ss58(
+                    &pubkey.0,
+                    io_helper::chain_name_to_ss58_prefix(&src_network_name)?,
+                ),
+                UniversalAddress::Ethereum(addr) => io_helper::eth_addr_to_hex_str(addr),
+            };
+            Ok(DepositInstructions {
+                deposit_account,
+                remark: get_remark_text(&remark),
+                amount_in,
+                valid_until_block: request.end_block_num,
+            })
+        }
+
+        /// Starts the swap that get_deposit_instructions handed out remark (hex encoded) for.
+        /// This can be called before the deposit lands: the plan waits for it until the
+        /// instructions' valid_until_block
+        #[ink(message)]
+        pub fn start_remark_deposit_swap(
+            &self,
+            remark: HexStrNo0x,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let remark: DepositRemark = io_helper::hex_str_to_u8_16(&remark)?;
+            let request = self
+                .create_execute_step_meta()?
+                .pull_remark_deposit_request_from_s3(&remark)
+                .map_err(|_| Error::RemarkDepositNotFound)?;
+            let pending_remark_id = SubstratePendingRemarkId {
+                start_block_num: request.start_block_num,
+                end_block_num: request.end_block_num,
+            };
+            let deposit_account = request.deposit_account;
+            self.start_swap_with_prestart(
+                request.src_network_name,
+                request.dest_network_name,
+                request.src_eth_addr,
+                request.dest_eth_addr,
+                request.src_token,
+                request.dest_token,
+                request.amount_in_str,
+                max_amount_at_risk_str,
+                now_or_never_max_blocks,
+                false,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    Self::set_prestart_remark_deposit(
+                        exec_plan,
+                        remark,
+                        deposit_account,
+                        pending_remark_id,
+                    )?;
+                    if !execute_step_meta.register_prestart_remark(&remark) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Creates an invoice for exactly exact_amount_out_str of dest_token, delivered to
+        /// dest_eth_addr on dest_network_name, and returns its ID (hex encoded). It can be paid
+        /// from any token and chain until expiry_millis: see quote_for_invoice and
+        /// start_swap_for_invoice
+        #[ink(message)]
+        pub fn create_payment_request(
+            &self,
+            dest_network_name: String,
+            dest_eth_addr: HexStrNo0x,
+            dest_token: String,
+            exact_amount_out_str: String,
+            expiry_millis: MillisSinceEpoch,
+        ) -> Result<HexStrNo0x> {
+            let exact_amount_out: Amount = exact_amount_out_str
+                .parse()
+                .map_err(|_| Error::InvalidNumber)?;
+            if exact_amount_out == 0 {
+                return Err(Error::InvalidNumber);
+            }
+            if expiry_millis <= self.now_millis() {
+                return Err(Error::InvoiceExpired);
+            }
+            self.quote_engine()?
+                .parse_token_id(&dest_network_name, &dest_token)?;
+            // Checked again when each payment starts, but an invoice nobody can pay is useless
+            let (resolved_dest_eth_addr, _) = self.resolve_destination(dest_eth_addr.clone())?;
+            self.check_destination_proven(&resolved_dest_eth_addr)?;
+            let entropy = (pink_extension::ext().getrandom(32), self.now_millis()).encode();
+            let request = PaymentRequest {
+                invoice_id: sp_core_hashing::blake2_128(&entropy),
+                dest_network_name,
+                dest_eth_addr,
+                dest_token,
+                exact_amount_out,
+                expiry_millis,
+                exec_plan_uuid: None,
+            };
+            self.create_execute_step_meta()?
+                .save_payment_request_to_s3(&request)
+                .map_err(|_| Error::FailedToSavePaymentRequest)?;
+            Ok(slice_to_hex_string(&request.invoice_id)[2..].into())
+        }
+
+        /// How much src_token it takes to pay the invoice, i.e. to deliver at least its
+        /// exact_amount_out net of fees. Pass the returned amount in (or a little more, in case
+        /// the price moves) to start_swap_for_invoice
+        #[ink(message)]
+        pub fn quote_for_invoice(
+            &self,
+            invoice_id: HexStrNo0x,
+            src_network_name: String,
+            src_token: String,
+        ) -> Result<(
+            Amount,           /* amount in of src token */
+            Amount,           /* quote in the invoice's token */
+            Amount,           /* src token USD */
+            Amount,           /* dest token USD */
+            MillisSinceEpoch, /* worst-case completion deadline */
+            FeeBreakdown,
+        )> {
+            let request = self.get_payable_payment_request(&invoice_id)?;
+            self.quote_engine()?
+                .quote_exact_out(
+                    &src_network_name,
+                    &request.dest_network_name,
+                    &src_token,
+                    &request.dest_token,
+                    request.exact_amount_out,
+                )
+                .map_err(Error::from)
+        }
+
+        /// Same as start_swap, but pays the invoice: the destination, dest token and network
+        /// are the invoice's. Fails if amount_in_str no longer quotes at least the invoice's
+        /// exact_amount_out. An invoice whose payment failed or was refunded can be paid again
+        #[ink(message)]
+        pub fn start_swap_for_invoice(
+            &self,
+            invoice_id: HexStrNo0x,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            src_token: String,
+            amount_in_str: String,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            let mut request = self.get_payable_payment_request(&invoice_id)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                request.dest_network_name.clone(),
+                src_eth_addr,
+                request.dest_eth_addr.clone(),
+                src_token,
+                request.dest_token.clone(),
+                amount_in_str,
+                None,
+                None,
+                false,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    let amount_out = exec_plan.quote.as_ref().map_or(0, |quote| quote.amount_out);
+                    if amount_out < request.exact_amount_out {
+                        return Err(Error::QuoteBelowInvoiceAmount);
+                    }
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    request.exec_plan_uuid = Some(exec_plan.uuid.clone());
+                    execute_step_meta
+                        .save_payment_request_to_s3(&request)
+                        .map_err(|_| Error::FailedToSavePaymentRequest)
+                },
+            )
+        }
+
+        /// Whether the invoice is open, expired, being paid or paid, along with the swap that
+        /// paid it
+        #[ink(message)]
+        pub fn get_invoice_status(&self, invoice_id: HexStrNo0x) -> Result<InvoiceStatus> {
+            let invoice_id: InvoiceId = io_helper::hex_str_to_u8_16(&invoice_id)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let request = execute_step_meta
+                .pull_payment_request_from_s3(&invoice_id)
+                .map_err(|_| Error::InvoiceNotFound)?;
+            self.get_invoice_status_inner(&execute_step_meta, &request)
+        }
+
+        fn get_invoice_status_inner(
+            &self,
+            execute_step_meta: &ExecuteStepMeta,
+            request: &PaymentRequest,
+        ) -> Result<InvoiceStatus> {
+            let exec_plan = request
+                .exec_plan_uuid
+                .as_ref()
+                .map(|exec_plan_uuid| execute_step_meta.pull_exec_plan_from_s3(exec_plan_uuid))
+                .transpose()
+                .map_err(|_| Error::FailedToPullExecutionPlan)?;
+            Ok(InvoiceStatus::new(
+                request,
+                exec_plan.as_ref(),
+                self.now_millis(),
+            ))
+        }
+
+        fn get_payable_payment_request(&self, invoice_id: &str) -> Result<PaymentRequest> {
+            let invoice_id: InvoiceId = io_helper::hex_str_to_u8_16(invoice_id)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            let request = execute_step_meta
+                .pull_payment_request_from_s3(&invoice_id)
+                .map_err(|_| Error::InvoiceNotFound)?;
+            match self.get_invoice_status_inner(&execute_step_meta, &request)? {
+                InvoiceStatus::Open => Ok(request),
+                InvoiceStatus::Expired => Err(Error::InvoiceExpired),
+                _ => Err(Error::InvoiceNotPayable),
+            }
+        }
+
+        /// Same as start_swap, but instead of sending dest_token to a destination, the escrow
+        /// calls target_eth_addr (see set_contract_call_target) with calldata_template (hex
+        /// encoded). The template is the full ABI-encoded call with the amount argument set to
+        /// CONTRACT_CALL_AMOUNT_PLACEHOLDER, which the executor fills in with the amount received.
+        /// ERC20s are approved to the target for exactly that amount before the call, and native
+        /// tokens are sent as the call's value
+        #[ink(message)]
+        pub fn start_swap_and_call(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            target_eth_addr: HexStrNo0x,
+            calldata_template: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+            deliver_wrapped: bool,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            let contract_call = ContractCall {
+                target_addr: io_helper::hex_str_to_eth_addr(&target_eth_addr)?,
+                calldata_template: hex_string_to_vec(&("0x".to_string() + &calldata_template))
+                    .map_err(|_| Error::InvalidContractCall)?,
+            };
+            if contract_call.get_amount_offset().is_none() {
+                return Err(Error::InvalidContractCall);
+            }
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                target_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                max_amount_at_risk_str,
+                now_or_never_max_blocks,
+                deliver_wrapped,
+                false,
+                false,
+                api_key,
+                metadata,
+                Some(contract_call),
+                None,
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Same as start_swap, but the dest token is delivered as liquidity (a zap-in) in the
+        /// Uniswap V2-style pair at pair_eth_addr, quoted with quote_into_lp. The escrow swaps
+        /// half of what the route delivered into the pair's other token and adds both through
+        /// the router of the pair's DEX, which mints the LP tokens to dest_eth_addr. The pair
+        /// must hold the dest token (wrapped, if it is native), and its factory must be a
+        /// registry DEX's or on the router allowlist
+        #[ink(message)]
+        pub fn start_swap_into_lp(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            pair_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            let pair_addr = io_helper::hex_str_to_eth_addr(&pair_eth_addr)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                max_amount_at_risk_str,
+                now_or_never_max_blocks,
+                false,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                None,
+                Some(pair_addr),
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Same as start_swap, but the deposit is amount_in_str LP tokens of the Uniswap V2-style
+        /// pair at pair_eth_addr on src_network_name (a zap-out), quoted with quote_from_lp. The
+        /// escrow redeems them through the router of the pair's DEX, and each of the pair's two
+        /// tokens is routed to dest_token on paths of its own. The pair's factory must be a
+        /// registry DEX's or on the router allowlist, and neither of its tokens can be dest_token.
+        /// If the swap fails after the redemption, the pair's tokens are recorded as stranded
+        /// funds rather than refunded as LP tokens
+        #[ink(message)]
+        pub fn start_swap_from_lp(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            pair_eth_addr: HexStrNo0x,
+            dest_token: String,
+            amount_in_str: String,
+            now_or_never_max_blocks: Option<BlockNum>,
+            deliver_wrapped: bool,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            let pair_addr = io_helper::hex_str_to_eth_addr(&pair_eth_addr)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                pair_eth_addr,
+                dest_token,
+                amount_in_str,
+                None,
+                now_or_never_max_blocks,
+                deliver_wrapped,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                Some(pair_addr),
+                None,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        /// Same as start_swap, but campaign_id pays for the swap (see set_sponsorship_campaign):
+        /// the user gets the paths' whole output, with no protocol fee. Quote it with
+        /// quote_sponsored. Fails with NotEligibleForSponsorship or SponsorshipBudgetExhausted
+        /// before the plan starts, so the deposit can still be used with start_swap
+        #[ink(message)]
+        pub fn start_sponsored_swap(
+            &self,
+            campaign_id: String,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            now_or_never_max_blocks: Option<BlockNum>,
+            deliver_wrapped: bool,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let campaign = self.get_sponsorship_campaign(&campaign_id)?;
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                None,
+                now_or_never_max_blocks,
+                deliver_wrapped,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                None,
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    let sponsored_usd = set_plan_sponsorship(exec_plan, &campaign)
+                        .ok_or(Error::NotEligibleForSponsorship)?;
+                    let sponsorship_registry = self.create_sponsorship_registry()?;
+                    sponsorship_registry
+                        .draw_down(
+                            &campaign.campaign_id,
+                            &exec_plan.uuid,
+                            sponsored_usd,
+                            campaign.budget_usd,
+                        )
+                        .map_err(|e| match e {
+                            SponsorshipRegistryError::BudgetExhausted
+                            | SponsorshipRegistryError::ConditionalCheckFailed => {
+                                Error::SponsorshipBudgetExhausted
+                            }
+                            _ => Error::DbRequestFailed,
+                        })?;
+                    let prestart_res = self
+                        .set_prestart_txn_submitted(
+                            exec_plan,
+                            execute_step_meta,
+                            user_to_escrow_txn.clone(),
+                            &src_network_name,
+                        )
+                        .and_then(|_| {
+                            if execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                                Ok(())
+                            } else {
+                                Err(Error::PrestartTxnIsAlreadyUsed)
+                            }
+                        });
+                    // The plan won't start, so it shouldn't count against the budget
+                    if prestart_res.is_err() {
+                        let _ = sponsorship_registry.credit_back(
+                            &campaign.campaign_id,
+                            &exec_plan.uuid,
+                            0,
+                        );
+                    }
+                    prestart_res
+                },
+            )
+        }
+
+        /// Same as start_swap, but for an integrator's white-label swap quoted with
+        /// quote_with_markup. Each path ends by sending markup_bps of what it delivered to
+        /// markup_recipient_eth_addr, and the user gets the rest minus the protocol fee
+        #[ink(message)]
+        pub fn start_swap_with_markup(
+            &self,
+            user_to_escrow_transfer_eth_txn: HexStrNo0x,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+            deliver_wrapped: bool,
+            markup_bps: u16,
+            markup_recipient_eth_addr: HexStrNo0x,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+        ) -> Result<Uuid> {
+            let user_to_escrow_txn =
+                io_helper::hex_str_to_eth_txn_hash(&user_to_escrow_transfer_eth_txn)?;
+            let markup_recipient = io_helper::hex_str_to_eth_addr(&markup_recipient_eth_addr)?;
+            validate_integrator_markup(markup_bps, &markup_recipient)?;
+            self.start_swap_with_prestart(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                max_amount_at_risk_str,
+                now_or_never_max_blocks,
+                deliver_wrapped,
+                false,
+                false,
+                api_key,
+                metadata,
+                None,
+                Some((markup_bps, markup_recipient)),
+                None,
+                None,
+                None,
+                |exec_plan, execute_step_meta| {
+                    self.set_prestart_txn_submitted(
+                        exec_plan,
+                        execute_step_meta,
+                        user_to_escrow_txn.clone(),
+                        &src_network_name,
+                    )?;
+                    if !execute_step_meta.register_prestart_txn_hash(&user_to_escrow_txn) {
+                        return Err(Error::PrestartTxnIsAlreadyUsed);
+                    }
+                    Ok(())
+                },
+            )
+        }
+
+        // set_prestart sets the prestart step's status (the deposit the plan waits for) and makes
+        // sure that no other plan can claim the same deposit. If contract_call is set, it
+        // replaces the transfer to dest_eth_addr (its target). markup is (markup_bps, recipient).
+        // If zap_in_pair is set, dest_eth_addr gets that pair's LP tokens instead. If lp_source_pair
+        // is set, the deposit is amount_in_str of that pair's LP tokens (src_token and
+        // max_amount_at_risk_str are ignored)
+        fn start_swap_with_prestart<F>(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+            deliver_wrapped: bool,
+            transfer_only: bool,
+            allow_unsafe_destination: bool,
+            api_key: Option<String>,
+            metadata: Option<HexStrNo0x>,
+            contract_call: Option<ContractCall>,
+            markup: Option<(u16, EthAddress)>,
+            zap_in_pair: Option<EthAddress>,
+            lp_source_pair: Option<EthAddress>,
+            escrow: Option<String>,
+            set_prestart: F,
+        ) -> Result<Uuid>
+        where
+            F: FnOnce(&mut ExecutionPlan, &ExecuteStepMeta) -> Result<()>,
+        {
+            self.check_api_key_caller(api_key.as_deref())?;
+            let escrow_key_set = match &escrow {
+                Some(escrow) => Some(self.get_allowed_escrow_key_set(api_key.as_deref(), escrow)?),
+                None => None,
+            };
+            let metadata = metadata
+                .map(|metadata| io_helper::hex_str_to_plan_metadata(&metadata))
+                .transpose()?;
+            let (dest_eth_addr, dest_name) = match &contract_call {
+                Some(contract_call) => {
+                    self.check_contract_call_target(&dest_network_name, contract_call)?;
+                    (dest_eth_addr, None)
+                }
+                None => self.resolve_destination(dest_eth_addr)?,
+            };
+            if let Some(api_key) = &api_key {
+                self.check_destination_allowed(api_key, &dest_eth_addr)?;
+            }
+            // A contract can't sign the challenge, which is why call targets are allowlisted
+            if contract_call.is_none() {
+                self.check_destination_proven(&dest_eth_addr)?;
+            }
+            self.screen_addresses(&src_eth_addr, &dest_eth_addr)?;
+            // Call targets are contracts by design (and allowlisted)
+            let guarded_dest_addr = match &contract_call {
+                None if !allow_unsafe_destination => {
+                    Some(io_helper::hex_str_to_eth_addr(&dest_eth_addr)?)
+                }
+                _ => None,
+            };
+            let mut exec_plan = match lp_source_pair {
+                Some(pair_addr) => {
+                    let mut quote_engine = self.quote_engine()?;
+                    if let Some((markup_bps, _)) = markup {
+                        quote_engine = quote_engine.with_markup_bps(markup_bps);
+                    }
+                    quote_engine.compute_lp_source_execution_plan(
+                        &src_network_name,
+                        &dest_network_name,
+                        &src_eth_addr,
+                        &dest_eth_addr,
+                        pair_addr,
+                        &dest_token,
+                        &amount_in_str,
+                        &self.get_router_allowlist(),
+                    )?
+                }
+                None => self.compute_execution_plan_with_risk_limit(
+                    src_network_name,
+                    dest_network_name,
+                    src_eth_addr,
+                    dest_eth_addr,
+                    src_token,
+                    dest_token,
+                    amount_in_str,
+                    max_amount_at_risk_str,
+                    transfer_only,
+                    markup.map(|(markup_bps, _)| markup_bps),
+                )?,
+            };
+            if let Some(dest_addr) = guarded_dest_addr {
+                let dest_chain = exec_plan.postend_escrow_to_user_transfer.get_src_chain();
+                let dest_chain_info =
+                    get_chain_info_from_chain_id(&dest_chain).ok_or(Error::UnsupportedNetwork)?;
+                check_destination_address(dest_chain_info, &dest_addr)?;
+            }
+            // Before the contract call, so that the target is called with the wrapped token
+            if deliver_wrapped {
+                set_wrapped_delivery(&mut exec_plan)?;
+            }
+            if let Some(contract_call) = contract_call {
+                let transfer_duration = exec_plan
+                    .postend_escrow_to_user_transfer
+                    .get_worst_case_duration_millis();
+                set_contract_call_delivery(&mut exec_plan, contract_call)
+                    .map_err(|_| Error::InvalidContractCall)?;
+                // The call may need an approval first, which the deadline has to leave time for
+                exec_plan.deadline_millis += exec_plan
+                    .postend_escrow_to_user_transfer
+                    .get_worst_case_duration_millis()
+                    - transfer_duration;
+            }
+            if let Some(pair_addr) = zap_in_pair {
+                // The pair holds the wrapped token
+                if matches!(
+                    exec_plan.postend_escrow_to_user_transfer.inner,
+                    ExecutionStepEnum::EthSend(_)
+                ) {
+                    set_wrapped_delivery(&mut exec_plan)?;
+                }
+                let token = match &exec_plan.postend_escrow_to_user_transfer.inner {
+                    ExecutionStepEnum::ERC20Transfer(step) => step.token.clone(),
+                    _ => return Err(Error::InvalidZapIn),
+                };
+                let target = self.quote_engine()?.get_zap_in_target(
+                    &token,
+                    pair_addr,
+                    &self.get_router_allowlist(),
+                )?;
+                set_zap_in_delivery(&mut exec_plan, &target)?;
+            }
+            if let Some((markup_bps, recipient)) = markup {
+                set_integrator_markup(&mut exec_plan, markup_bps, recipient)?;
+            }
+            exec_plan.execution_policy =
+                ExecutionPolicy::from_now_or_never_max_blocks(now_or_never_max_blocks);
+            exec_plan.dest_name = dest_name;
+            exec_plan.metadata = metadata;
+            // Wrapped delivery, the contract call, the zap-in and the markup may have pushed back
+            // the deadline
+            if let Some(quoted_terms) = &mut exec_plan.quoted_terms {
+                quoted_terms.expiry_millis = exec_plan.deadline_millis;
+            }
+            let mut execute_step_meta = self.create_execute_step_meta()?;
+            // Last, since every step added above sends from or to the shared escrow too
+            if let Some(escrow_key_set) = &escrow_key_set {
+                set_plan_escrow(
+                    &mut exec_plan,
+                    escrow_key_set.eth_address,
+                    escrow_key_set.substrate_pubkey,
+                )
+                .map_err(|_| Error::EscrowNotSupportedOnChain)?;
+                execute_step_meta.set_escrow_name(Some(&escrow_key_set.name));
+            }
+            // Fails closed: if we can't tell, the plan's chains may be paused
+            let paused_chains = execute_step_meta
+                .pull_paused_chains_from_s3()
+                .map_err(|_| Error::FailedToPullPausedChains)?;
+            if get_plan_chains(&exec_plan)
+                .iter()
+                .any(|chain_id| paused_chains.contains(chain_id))
+            {
+                return Err(Error::ChainPaused);
+            }
+            self.check_plan_dex_routers(&exec_plan, &execute_step_meta)?;
+            // One concurrent round for every chain the plan touches, rather than a lookup per
+            // chain whenever creating the plan needs one (e.g. the prestart step's expiry window)
+            execute_step_meta.prefetch_cur_blocks(&get_plan_chains(&exec_plan));
+            set_prestart(&mut exec_plan, &execute_step_meta)?;
+            self.presign_plan(&mut exec_plan, &execute_step_meta, escrow_key_set.as_ref());
+            Self::register_new_exec_plan(&execute_step_meta, &mut exec_plan);
+            if let Some(metadata) = &exec_plan.metadata {
+                // The plan has started at this point, so a failure here only means it can't be
+                // found by its tag
+                let tag_hash = Self::hash_plan_tag(api_key.as_deref(), metadata);
+                let _ = self
+                    .create_plan_tag_index()
+                    .map(|plan_tag_index| plan_tag_index.set_tag(&tag_hash, &exec_plan.uuid));
+            }
+            Ok(exec_plan.uuid)
+        }
+
+        /// The plan most recently started with this metadata (hex encoded) and api_key. Tags
+        /// are scoped per api_key, so pass the same one given to start_swap
+        #[ink(message)]
+        pub fn find_plan_by_tag(
+            &self,
+            metadata: HexStrNo0x,
+            api_key: Option<String>,
+        ) -> Result<Option<Uuid>> {
+            let metadata = io_helper::hex_str_to_plan_metadata(&metadata)?;
+            let tag_hash = Self::hash_plan_tag(api_key.as_deref(), &metadata);
+            self.create_plan_tag_index()?
+                .get_tag(&tag_hash)
+                .map_err(|_| Error::PlanTagLookupFailed)
+        }
+
+        /// Admin only. Same as start_swap but without the user's deposit: the plan is paper
+        /// traded, i.e. every step is signed against live chain state but never broadcast.
+        /// Step it forward as usual and inspect what it would have sent with get_paper_txns
+        #[ink(message)]
+        pub fn start_paper_swap(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            now_or_never_max_blocks: Option<BlockNum>,
+        ) -> Result<Uuid> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let (dest_eth_addr, dest_name) = self.resolve_destination(dest_eth_addr)?;
+            let mut exec_plan = self.compute_execution_plan_with_risk_limit(
+                src_network_name.clone(),
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+                max_amount_at_risk_str,
+                false,
+                None,
+            )?;
+            exec_plan.execution_policy =
+                ExecutionPolicy::from_now_or_never_max_blocks(now_or_never_max_blocks);
+            exec_plan.dest_name = dest_name;
+            exec_plan.paper_trade = true;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            // There is no user txn. Paper trading confirms the prestart step without looking it up
+            self.set_prestart_txn_submitted(
+                &mut exec_plan,
+                &execute_step_meta,
+                EthTxnHash::zero(),
+                &src_network_name,
+            )?;
+            Self::register_new_exec_plan(&execute_step_meta, &mut exec_plan);
             Ok(exec_plan.uuid)
         }
 
-        fn get_cur_block(chain_id: &UniversalChainId) -> Result<BlockNum> {
-            // We assume all ChainIds support Substrate-like extrinsics. Fine for the near future
-            let chain_info =
-                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
-            let subutils = SubstrateNodeRpcUtils {
-                rpc_url: chain_info.rpc_url.to_string(),
-            };
-            subutils
-                .get_finalized_block_number()
-                .map_err(|_| Error::RpcRequestFailed)
+        /// Txns that a paper-trading plan (see start_paper_swap) signed so far, in order
+        #[ink(message)]
+        pub fn get_paper_txns(&self, exec_plan_uuid_str: HexStrNo0x) -> Result<Vec<PaperTxn>> {
+            let exec_plan_uuid = {
+                let exec_plan_uuid_raw = io_helper::hex_str_to_u8_16(&exec_plan_uuid_str)?;
+                Uuid::new(exec_plan_uuid_raw)
+            };
+            let execute_step_meta = self.create_execute_step_meta()?;
+            execute_step_meta
+                .pull_paper_txns_from_s3(&exec_plan_uuid)
+                .map_err(|_| Error::FailedToPullPaperTxns)
+        }
+
+        // A swap step sends the escrow's tokens to its router, so the router must be one we
+        // know of, and its pools those of the factory we expect
+        fn check_plan_dex_routers(
+            &self,
+            exec_plan: &ExecutionPlan,
+            execute_step_meta: &ExecuteStepMeta,
+        ) -> Result<()> {
+            check_dex_routers(
+                &get_plan_dex_routers(exec_plan),
+                &self.get_router_allowlist(),
+                |chain_id, router| {
+                    let chain_info = get_chain_info_from_chain_id(&chain_id)?;
+                    DEXRouterContract::new(&execute_step_meta.get_rpc_url(chain_info), router)
+                        .and_then(|router| router.factory())
+                        .ok()
+                },
+            )
+            .map_err(|err| match err {
+                RouterCheckError::UnknownRouter(..) => Error::DexRouterNotAllowed,
+                RouterCheckError::FactoryCallFailed(..) => Error::RpcRequestFailed,
+                RouterCheckError::FactoryMismatch(..) => Error::DexRouterFactoryMismatch,
+            })
+        }
+
+        // Best-effort: any txn that isn't presigned is signed when its step runs, as usual
+        fn presign_plan(
+            &self,
+            exec_plan: &mut ExecutionPlan,
+            execute_step_meta: &ExecuteStepMeta,
+            escrow_key_set: Option<&EscrowKeySet>,
+        ) {
+            let presign_max_steps = self.get_presign_max_steps();
+            if presign_max_steps == 0 || !is_presignable_route(exec_plan, presign_max_steps) {
+                return;
+            }
+            let keys = match escrow_key_set {
+                Some(escrow_key_set) => Ok(escrow_key_set.to_key_container()),
+                None => self.create_key_container(),
+            };
+            if let Ok(keys) = keys {
+                let _ = presign_static_txns(exec_plan, execute_step_meta, &keys);
+            }
+        }
+
+        fn set_prestart_txn_submitted(
+            &self,
+            exec_plan: &mut ExecutionPlan,
+            execute_step_meta: &ExecuteStepMeta,
+            user_to_escrow_txn: EthTxnHash,
+            src_network_name: &str,
+        ) -> Result<()> {
+            let src_chain_id = io_helper::chain_name_to_id(src_network_name)?;
+            match &mut exec_plan.prestart_user_to_escrow_transfer.inner {
+                ExecutionStepEnum::EthSend(step) => {
+                    let cur_block = execute_step_meta
+                        .get_cur_block(&src_chain_id)
+                        .map_err(|_| Error::RpcRequestFailed)?;
+                    step.status = EthStepStatus::Submitted(EthPendingTxnId {
+                        txn_hash: user_to_escrow_txn,
+                        end_block_num: cur_block + self.get_executor_config().txn_num_blocks_alive,
+                    });
+                }
+                ExecutionStepEnum::ERC20Transfer(step) => {
+                    let cur_block = execute_step_meta
+                        .get_cur_block(&src_chain_id)
+                        .map_err(|_| Error::RpcRequestFailed)?;
+                    step.status = EthStepStatus::Submitted(EthPendingTxnId {
+                        txn_hash: user_to_escrow_txn,
+                        end_block_num: cur_block + self.get_executor_config().txn_num_blocks_alive,
+                    });
+                }
+                _ => return Err(Error::InvalidUserToEscrowTxn),
+            }
+            Ok(())
+        }
+
+        // The deposit lands straight in the escrow's EVM backing account, so there is nothing
+        // to sweep
+        fn set_prestart_remark_deposit(
+            exec_plan: &mut ExecutionPlan,
+            remark: DepositRemark,
+            deposit_account: UniversalAddress,
+            pending_remark_id: SubstratePendingRemarkId,
+        ) -> Result<()> {
+            let remark_deposit_step = match &exec_plan.prestart_user_to_escrow_transfer.inner {
+                ExecutionStepEnum::EthSend(step) => SubstrateRemarkDepositStep {
+                    uuid: step.uuid.clone(),
+                    chain: step.chain,
+                    remark,
+                    amount: step.amount,
+                    common: CommonExecutionMeta {
+                        dest_addr: deposit_account,
+                        ..step.common.clone()
+                    },
+                    status: RemarkDepositStatus::Pending(pending_remark_id),
+                },
+                _ => return Err(Error::RemarkDepositsUnsupported),
+            };
+            exec_plan.prestart_user_to_escrow_transfer = ExecutionStep::new(
+                ExecutionStepEnum::SubstrateRemarkDeposit(remark_deposit_step),
+            );
+            exec_plan.deposit_sweep = None;
+            Ok(())
+        }
+
+        // The deposit sweep (if any) moves funds from the plan's deposit address into the escrow,
+        // else the prestart step sends them there directly
+        fn get_plan_escrow_eth_addr(exec_plan: &ExecutionPlan) -> Result<EthAddress> {
+            let escrow_addr = match &exec_plan.deposit_sweep {
+                Some(deposit_sweep) => match &deposit_sweep.inner {
+                    ExecutionStepEnum::EthDepositSweep(step) => &step.common.dest_addr,
+                    _ => return Err(Error::FailedToCreateExecutionPlan),
+                },
+                None => match &exec_plan.prestart_user_to_escrow_transfer.inner {
+                    ExecutionStepEnum::EthSend(step) => &step.common.dest_addr,
+                    _ => return Err(Error::FailedToCreateExecutionPlan),
+                },
+            };
+            match escrow_addr {
+                UniversalAddress::Ethereum(addr) => Ok(*addr),
+                UniversalAddress::Substrate(_) => Err(Error::FailedToCreateExecutionPlan),
+            }
+        }
+
+        // Someone else (e.g. an admin override) may have saved the plan since we pulled it. Rather
+        // than overwrite their changes, pull their version, merge ours in and try once more
+        fn save_exec_plan_merging(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &mut ExecutionPlan,
+        ) -> Result<(), ExecutableError> {
+            match execute_step_meta.save_exec_plan_to_s3(exec_plan) {
+                Err(ExecutableError::VersionConflict) => {
+                    let theirs = execute_step_meta.pull_exec_plan_from_s3(&exec_plan.uuid)?;
+                    *exec_plan = merge_exec_plans(exec_plan, theirs);
+                    execute_step_meta.save_exec_plan_to_s3(exec_plan)
+                }
+                res => res,
+            }
+        }
+
+        fn register_new_exec_plan(
+            execute_step_meta: &ExecuteStepMeta,
+            exec_plan: &mut ExecutionPlan,
+        ) {
+            exec_plan.last_progress_millis = execute_step_meta.cur_timestamp();
+            let _ = execute_step_meta.save_exec_plan_to_s3(exec_plan);
+            let _ = execute_step_meta.register_exec_plan(&exec_plan.uuid);
+            let _ = execute_step_meta.save_plan_events_to_s3(&[PlanEvent {
+                exec_plan_uuid: exec_plan.uuid.clone(),
+                timestamp_millis: execute_step_meta.cur_timestamp(),
+                kind: PlanEventKind::created(exec_plan),
+            }]);
+        }
+
+        /// Admin only. Restricts the swaps started with api_key to the given destination addresses
+        #[ink(message)]
+        pub fn set_destination_allowlist(
+            &mut self,
+            api_key: String,
+            dest_eth_addrs: Vec<HexStrNo0x>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if dest_eth_addrs.len() > MAX_DESTINATION_ALLOWLIST_LEN {
+                return Err(Error::DestinationAllowlistTooLong);
+            }
+            let allowlist = dest_eth_addrs
+                .iter()
+                .map(|addr| io_helper::hex_str_to_eth_addr(addr))
+                .collect::<Result<Vec<EthAddress>>>()?;
+            self.destination_allowlists
+                .insert(Self::hash_api_key(&api_key), &allowlist);
+            Ok(())
+        }
+
+        /// Admin only. Swaps started by account are then always checked against api_key's
+        /// destination allowlist and escrows, and fail with ApiKeyNotBoundToCaller if they pass
+        /// no key or another one. Once a key has an account, other callers can't pass it. Bind
+        /// every account an integrator with a destination allowlist calls from, since swaps
+        /// without a key are unrestricted. Pass api_key = None to unbind account
+        #[ink(message)]
+        pub fn set_integrator_account(
+            &mut self,
+            account: AccountId,
+            api_key: Option<String>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut integrator_accounts = self.integrator_accounts.get().unwrap_or_default();
+            integrator_accounts.retain(|(integrator_account, _)| *integrator_account != account);
+            if let Some(api_key) = api_key {
+                if integrator_accounts.len() >= MAX_INTEGRATOR_ACCOUNTS {
+                    return Err(Error::TooManyIntegratorAccounts);
+                }
+                integrator_accounts.push((account, Self::hash_api_key(&api_key)));
+            }
+            self.integrator_accounts.set(&integrator_accounts);
+            Ok(())
+        }
+
+        /// Admin only
+        #[ink(message)]
+        pub fn get_integrator_accounts(&self) -> Result<Vec<(AccountId, ApiKeyHash)>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            Ok(self.integrator_accounts.get().unwrap_or_default())
+        }
+
+        /// Admin only. Lifts the destination restriction on api_key
+        #[ink(message)]
+        pub fn remove_destination_allowlist(&mut self, api_key: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.destination_allowlists
+                .remove(Self::hash_api_key(&api_key));
+            Ok(())
+        }
+
+        /// Admin only
+        #[ink(message)]
+        pub fn get_destination_allowlist(
+            &self,
+            api_key: String,
+        ) -> Result<Option<Vec<EthAddress>>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            Ok(self
+                .destination_allowlists
+                .get(Self::hash_api_key(&api_key)))
+        }
+
+        /// Admin only. Adds an escrow account pair that plans can be started with instead of the
+        /// shared escrow (see start_swap), e.g. to keep an integrator's funds segregated. The
+        /// keys are hex strings WITHOUT 0x. At most 8 escrow key sets
+        #[ink(message)]
+        pub fn add_escrow_key_set(
+            &mut self,
+            name: String,
+            eth_private_key: HexStrNo0x,
+            substrate_private_key: HexStrNo0x,
+            substrate_sig_scheme: SignatureScheme, // Sr25519 or Ed25519
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let eth_private_key = io_helper::hex_str_to_u8_32(&eth_private_key)?;
+            let substrate_private_key = io_helper::hex_str_to_u8_32(&substrate_private_key)?;
+            let eth_address = Self::get_eth_address_from_pair(&sp_core::ecdsa::Pair::from_seed(
+                &eth_private_key,
+            ))?;
+            let substrate_pubkey = match substrate_sig_scheme {
+                SignatureScheme::Sr25519 => {
+                    sp_core::sr25519::Pair::from_seed(&substrate_private_key)
+                        .public()
+                        .0
+                }
+                SignatureScheme::Ed25519 => {
+                    sp_core::ed25519::Pair::from_seed(&substrate_private_key)
+                        .public()
+                        .0
+                }
+                SignatureScheme::Ethereum => return Err(Error::UnsupportedSignatureScheme),
+            };
+            let escrow_key_set = EscrowKeySet::new(
+                name,
+                eth_address,
+                eth_private_key,
+                SubstratePublicKey {
+                    0: substrate_pubkey,
+                },
+                substrate_private_key,
+                substrate_sig_scheme,
+            )?;
+            let mut escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+            add_escrow_key_set(&mut escrow_key_sets, escrow_key_set)?;
+            self.escrow_key_sets.set(&escrow_key_sets);
+            Ok(())
+        }
+
+        /// Admin only. Only remove an escrow key set once its plans have closed and its funds
+        /// have been moved out, since its plans can't be stepped forward without its keys
+        #[ink(message)]
+        pub fn remove_escrow_key_set(&mut self, name: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let mut escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+            let num_escrow_key_sets = escrow_key_sets.len();
+            escrow_key_sets.retain(|escrow_key_set| escrow_key_set.name != name);
+            if escrow_key_sets.len() == num_escrow_key_sets {
+                return Err(Error::EscrowNotFound);
+            }
+            self.escrow_key_sets.set(&escrow_key_sets);
+            Ok(())
+        }
+
+        /// The addresses of every escrow key set, which is where users send the deposits of
+        /// plans started with its name
+        #[ink(message)]
+        pub fn get_escrow_accounts(&self) -> Vec<EscrowAccount> {
+            self.escrow_key_sets
+                .get()
+                .unwrap_or_default()
+                .iter()
+                .map(EscrowKeySet::get_account)
+                .collect()
+        }
+
+        /// Admin only. Lets the swaps started with api_key use the given escrow key sets
+        #[ink(message)]
+        pub fn set_api_key_escrows(
+            &mut self,
+            api_key: String,
+            escrow_names: Vec<String>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            if escrow_names.len() > MAX_ESCROW_KEY_SETS {
+                return Err(Error::TooManyEscrowKeySets);
+            }
+            let escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+            if escrow_names
+                .iter()
+                .any(|name| find_escrow_key_set(&escrow_key_sets, name).is_none())
+            {
+                return Err(Error::EscrowNotFound);
+            }
+            self.api_key_escrows
+                .insert(Self::hash_api_key(&api_key), &escrow_names);
+            Ok(())
+        }
+
+        /// Admin only. api_key can then only use the shared escrow
+        #[ink(message)]
+        pub fn remove_api_key_escrows(&mut self, api_key: String) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.api_key_escrows.remove(Self::hash_api_key(&api_key));
+            Ok(())
+        }
+
+        fn get_allowed_escrow_key_set(
+            &self,
+            api_key: Option<&str>,
+            escrow: &str,
+        ) -> Result<EscrowKeySet> {
+            let allowed_escrows = api_key
+                .and_then(|api_key| self.api_key_escrows.get(Self::hash_api_key(api_key)))
+                .unwrap_or_default();
+            if !allowed_escrows.iter().any(|name| name == escrow) {
+                return Err(Error::EscrowNotAllowed);
+            }
+            let escrow_key_sets = self.escrow_key_sets.get().unwrap_or_default();
+            find_escrow_key_set(&escrow_key_sets, escrow)
+                .cloned()
+                .ok_or(Error::EscrowNotFound)
+        }
+
+        fn check_api_key_caller(&self, api_key: Option<&str>) -> Result<()> {
+            let integrator_accounts = self.integrator_accounts.get().unwrap_or_default();
+            let caller = Self::env().caller();
+            let api_key_hash = api_key.map(Self::hash_api_key);
+            let caller_api_key_hash = integrator_accounts
+                .iter()
+                .find(|(account, _)| *account == caller)
+                .map(|(_, api_key_hash)| *api_key_hash);
+            let is_bound_elsewhere = api_key_hash.map_or(false, |api_key_hash| {
+                integrator_accounts
+                    .iter()
+                    .any(|(_, bound_api_key_hash)| *bound_api_key_hash == api_key_hash)
+            });
+            match caller_api_key_hash {
+                Some(caller_api_key_hash) if api_key_hash != Some(caller_api_key_hash) => {
+                    Err(Error::ApiKeyNotBoundToCaller)
+                }
+                None if is_bound_elsewhere => Err(Error::ApiKeyNotBoundToCaller),
+                _ => Ok(()),
+            }
+        }
+
+        fn check_destination_allowed(&self, api_key: &str, dest_eth_addr: &str) -> Result<()> {
+            match self.destination_allowlists.get(Self::hash_api_key(api_key)) {
+                None => Ok(()),
+                Some(allowlist) => {
+                    let dest_addr = io_helper::hex_str_to_eth_addr(dest_eth_addr)?;
+                    if allowlist.contains(&dest_addr) {
+                        Ok(())
+                    } else {
+                        Err(Error::DestinationNotAllowed)
+                    }
+                }
+            }
+        }
+
+        /// Admin only. If set, start_swap (and get_deposit_instructions) reject a destination
+        /// until it has signed get_destination_challenge and the signature was passed to
+        /// prove_destination. A mistyped destination would otherwise receive the swap for good
+        #[ink(message)]
+        pub fn set_require_destination_proof(&mut self, require: bool) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.require_destination_proof.set(&require);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_require_destination_proof(&self) -> bool {
+            self.require_destination_proof.get().unwrap_or_default()
+        }
+
+        /// The text that dest_eth_addr signs for prove_destination: with personal_sign from an
+        /// Eth wallet, or with the Sr25519 key whose pubkey starts with dest_eth_addr
+        #[ink(message)]
+        pub fn get_destination_challenge(&self, dest_eth_addr: HexStrNo0x) -> Result<String> {
+            Ok(get_destination_challenge(&io_helper::hex_str_to_eth_addr(
+                &dest_eth_addr,
+            )?))
+        }
+
+        /// Checks proof against get_destination_challenge and saves it, so every later swap to
+        /// dest_eth_addr is accepted. Only needs to be called once per destination
+        #[ink(message)]
+        pub fn prove_destination(
+            &self,
+            dest_eth_addr: HexStrNo0x,
+            proof: DestinationProof,
+        ) -> Result<()> {
+            let dest_addr = io_helper::hex_str_to_eth_addr(&dest_eth_addr)?;
+            proof
+                .verify(&dest_addr)
+                .map_err(|_| Error::InvalidDestinationProof)?;
+            self.create_execute_step_meta()?
+                .save_destination_proof_to_s3(&dest_addr, &proof)
+                .map_err(|_| Error::FailedToSaveDestinationProof)
+        }
+
+        // The saved proof is verified again since it comes back from S3
+        fn check_destination_proven(&self, dest_eth_addr: &str) -> Result<()> {
+            if !self.require_destination_proof.get().unwrap_or_default() {
+                return Ok(());
+            }
+            let dest_addr = io_helper::hex_str_to_eth_addr(dest_eth_addr)?;
+            match self
+                .create_execute_step_meta()?
+                .pull_destination_proof_from_s3(&dest_addr)
+            {
+                Ok(proof) if proof.verify(&dest_addr).is_ok() => Ok(()),
+                _ => Err(Error::DestinationNotProven),
+            }
+        }
+
+        /// Admin only. Allows (or disallows) start_swap_and_call to deliver to target_eth_addr on
+        /// network_name. Only allow contracts that can't move more than what they are called with
+        /// out of the escrow, e.g. not a DEX router or token bridge that the escrow has approved
+        #[ink(message)]
+        pub fn set_contract_call_target(
+            &mut self,
+            network_name: String,
+            target_eth_addr: HexStrNo0x,
+            allowed: bool,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let target_addr = io_helper::hex_str_to_eth_addr(&target_eth_addr)?;
+            let mut contract_call_targets = self.contract_call_targets.get().unwrap_or_default();
+            contract_call_targets.retain(|target| *target != (chain_id, target_addr));
+            if allowed {
+                let chain_info =
+                    get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+                let rpc_url =
+                    get_rpc_url(&self.rpc_endpoints.get().unwrap_or_default(), chain_info);
+                let code = get_code(&rpc_url, target_addr).map_err(|_| Error::RpcRequestFailed)?;
+                if code.is_empty() {
+                    return Err(Error::ContractCallTargetHasNoCode);
+                }
+                contract_call_targets.push((chain_id, target_addr));
+            }
+            self.contract_call_targets.set(&contract_call_targets);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_contract_call_targets(&self) -> Vec<(UniversalChainId, EthAddress)> {
+            self.contract_call_targets.get().unwrap_or_default()
+        }
+
+        fn check_contract_call_target(
+            &self,
+            dest_network_name: &str,
+            contract_call: &ContractCall,
+        ) -> Result<()> {
+            let chain_id = io_helper::chain_name_to_id(dest_network_name)?;
+            if self
+                .contract_call_targets
+                .get()
+                .unwrap_or_default()
+                .contains(&(chain_id, contract_call.target_addr))
+            {
+                Ok(())
+            } else {
+                Err(Error::ContractCallTargetNotAllowed)
+            }
+        }
+
+        /// Admin only. Resolvers that start_swap tries, in order, when it is given a
+        /// destination name instead of an address. Pass an empty list to only accept addresses
+        #[ink(message)]
+        pub fn set_name_resolvers(&mut self, name_resolvers: Vec<NameResolver>) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.name_resolvers.set(&name_resolvers);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_name_resolvers(&self) -> Vec<NameResolver> {
+            self.name_resolvers.get().unwrap_or_default()
+        }
+
+        /// Admin only. Points alias at dest_eth_addr in the address book (the AliasRegistry
+        /// resolver), overwriting any previous address
+        #[ink(message)]
+        pub fn set_address_alias(&self, alias: String, dest_eth_addr: HexStrNo0x) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let alias = normalize_name(&alias).ok_or(Error::InvalidDestinationName)?;
+            let address = io_helper::hex_str_to_eth_addr(&dest_eth_addr)?;
+            self.create_address_alias_registry()?
+                .set_alias(&alias, &address)
+                .map_err(|_| Error::DbRequestFailed)
+        }
+
+        /// Lets the frontend show the user where a name resolves to before they deposit
+        #[ink(message)]
+        pub fn resolve_destination_name(&self, name: String) -> Result<EthAddress> {
+            let name = normalize_name(&name).ok_or(Error::InvalidDestinationName)?;
+            self.resolve_normalized_name(&name)
+        }
+
+        /// Re-encodes a substrate address (hex pubkey or SS58 for any chain) for network_name,
+        /// e.g. to show a user's Polkadot address in its Astar form
+        #[ink(message)]
+        pub fn get_ss58_address(&self, network_name: String, address: String) -> Result<String> {
+            let prefix = io_helper::chain_name_to_ss58_prefix(&network_name)?;
+            let pubkey = io_helper::str_to_substrate_pubkey(&address, None)?;
+            Ok(encode_ss58(&pubkey, prefix))
+        }
+
+        /// Checks that an SS58 address is encoded for network_name and returns its pubkey
+        #[ink(message)]
+        pub fn validate_ss58_address(
+            &self,
+            network_name: String,
+            address: String,
+        ) -> Result<HexStrNo0x> {
+            let prefix = io_helper::chain_name_to_ss58_prefix(&network_name)?;
+            let pubkey = io_helper::str_to_substrate_pubkey(&address, Some(prefix))?;
+            Ok(slice_to_hex_string(&pubkey)[2..].to_string())
+        }
+
+        /// The substrate account that an Eth address maps to on chains like Astar, where
+        /// native tokens sent to it can be used from the EVM side
+        #[ink(message)]
+        pub fn get_evm_mapped_address(
+            &self,
+            network_name: String,
+            eth_addr: HexStrNo0x,
+        ) -> Result<String> {
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            if !chain_info.has_hashed_evm_account_mapping {
+                return Err(Error::UnsupportedNetwork);
+            }
+            let prefix = io_helper::chain_name_to_ss58_prefix(&network_name)?;
+            let address = io_helper::hex_str_to_eth_addr(&eth_addr)?;
+            Ok(h160_to_mapped_ss58(&address.0, prefix))
+        }
+
+        // Addresses are passed through as is. Anything else is treated as a name
+        fn resolve_destination(
+            &self,
+            dest: HexStrNo0x,
+        ) -> Result<(HexStrNo0x, Option<ResolvedDestinationName>)> {
+            if io_helper::hex_str_to_eth_addr(&dest).is_ok() {
+                return Ok((dest, None));
+            }
+            // Destinations are always Eth addresses, so an SS58 address is a mistake rather than
+            // a name we failed to find
+            if decode_ss58(&dest).is_ok() {
+                return Err(Error::InvalidAddress);
+            }
+            let name = normalize_name(&dest).ok_or(Error::InvalidDestinationName)?;
+            let resolved_address = self.resolve_normalized_name(&name)?;
+            Ok((
+                io_helper::eth_addr_to_hex_str(&resolved_address),
+                Some(ResolvedDestinationName {
+                    name,
+                    resolved_address,
+                }),
+            ))
+        }
+
+        fn resolve_normalized_name(&self, name: &str) -> Result<EthAddress> {
+            // Only the AliasRegistry resolver needs DynamoDB
+            let alias_registry = self.create_address_alias_registry().ok();
+            resolve_name(
+                &self.name_resolvers.get().unwrap_or_default(),
+                name,
+                alias_registry.as_ref(),
+                &self.rpc_endpoints.get().unwrap_or_default(),
+            )
+            .map_err(|_| Error::NameResolutionFailed)?
+            .ok_or(Error::DestinationNameNotFound)
+        }
+
+        fn create_plan_tag_index(&self) -> Result<PlanTagIndex> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(PlanTagIndex::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
+        }
+
+        fn create_delivery_approval_registry(&self) -> Result<DeliveryApprovalRegistry> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(DeliveryApprovalRegistry::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
+        }
+
+        fn create_relayer_registry(&self) -> Result<RelayerRegistry> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(RelayerRegistry::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
+        }
+
+        fn create_sponsorship_registry(&self) -> Result<SponsorshipRegistry> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(SponsorshipRegistry::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
+        }
+
+        fn get_sponsorship_campaign(&self, campaign_id: &str) -> Result<SponsorshipCampaign> {
+            self.sponsorship_campaigns
+                .get()
+                .unwrap_or_default()
+                .into_iter()
+                .find(|campaign| campaign.campaign_id == campaign_id)
+                .ok_or(Error::SponsorshipCampaignNotFound)
+        }
+
+        fn create_address_alias_registry(&self) -> Result<AddressAliasRegistry> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(AddressAliasRegistry::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
+        }
+
+        /// Admin only. Fetches and checks the tokenlist JSON (Uniswap's format) at
+        /// token_list_url, which must be HTTPS. Only a query can make the request, so this
+        /// returns the list for the admin to submit with set_token_list
+        #[ink(message)]
+        pub fn import_token_list(&self, token_list_url: String) -> Result<TokenListOverlay> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            Ok(fetch_token_list(&token_list_url)?)
+        }
+
+        /// Admin only. Replaces the token list overlay. Quotes then accept the list's symbols on
+        /// top of the built-in ones, and resolve_token knows its tokens
+        #[ink(message)]
+        pub fn set_token_list(&mut self, token_list: TokenListOverlay) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            check_token_list(&token_list)?;
+            self.token_list.set(&token_list);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_token_list(&self) -> TokenListOverlay {
+            self.token_list.get().unwrap_or_default()
+        }
+
+        /// Admin only. Reads the XC20s (asset ID, symbol, decimals and MultiLocation) from the
+        /// chain's asset registry: assetManager on Moonbeam, xcAssetConfig on Astar. Only a
+        /// query can make the requests, so this returns them for the admin to submit with
+        /// set_discovered_xc20_tokens
+        #[ink(message)]
+        pub fn discover_xc20_tokens(&self, network_name: String) -> Result<Vec<DiscoveredXC20>> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            if chain_info.xc20_registry.is_none() {
+                return Err(Error::UnsupportedNetwork);
+            }
+            let asset_registry_utils = SubstrateAssetRegistryUtils {
+                node_rpc_utils: SubstrateNodeRpcUtils::from_chain_info(
+                    chain_info,
+                    &self.rpc_endpoints.get().unwrap_or_default(),
+                ),
+            };
+            asset_registry_utils
+                .discover_xc20_tokens(chain_info)
+                .map_err(|_| Error::XC20DiscoveryFailed)
+        }
+
+        /// Admin only. Replaces the chain's discovered XC20s. Quotes then accept their symbols
+        /// and resolve_token knows them, unless the registry or the token list already does
+        #[ink(message)]
+        pub fn set_discovered_xc20_tokens(
+            &mut self,
+            network_name: String,
+            tokens: Vec<DiscoveredXC20>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let chain_id = io_helper::chain_name_to_id(&network_name)?;
+            if tokens.len() > MAX_DISCOVERED_XC20S_PER_CHAIN {
+                return Err(Error::TooManyDiscoveredXC20s);
+            }
+            let mut xc20_registry = self.get_xc20_registry();
+            xc20_registry.replace_chain(chain_id, tokens);
+            self.xc20_registry.set(&xc20_registry);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_xc20_registry(&self) -> XC20RegistryOverlay {
+            self.xc20_registry.get().unwrap_or_default()
+        }
+
+        /// The token (a symbol or address, like quote's token arguments) with its symbol, name,
+        /// decimals and logo from the registry, the imported token list and the discovered XC20s
+        #[ink(message)]
+        pub fn resolve_token(&self, network_name: String, token: String) -> Result<ListedToken> {
+            Ok(self.quote_engine()?.resolve_token(&network_name, &token)?)
+        }
+
+        /// Admin only. Pass screening_url = None to disable pre-trade screening
+        #[ink(message)]
+        pub fn set_screening_config(
+            &mut self,
+            screening_url: Option<String>,
+            bypass_screening: bool,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.screening_url = screening_url;
+            self.bypass_screening = bypass_screening;
+            Ok(())
+        }
+
+        // Fails closed: if the screening provider is unreachable, we do not create the plan
+        fn screen_addresses(&self, src_eth_addr: &str, dest_eth_addr: &str) -> Result<()> {
+            let screening_url = match &self.screening_url {
+                Some(url) if !self.bypass_screening => url.clone(),
+                _ => return Ok(()),
+            };
+            let addresses = [
+                io_helper::hex_str_to_eth_addr(src_eth_addr)?,
+                io_helper::hex_str_to_eth_addr(dest_eth_addr)?,
+            ];
+            let is_allowed = ScreeningApi { screening_url }
+                .are_addresses_allowed(&addresses)
+                .map_err(|_| Error::ScreeningRequestFailed)?;
+            if is_allowed {
+                Ok(())
+            } else {
+                Err(Error::AddressFailedScreening)
+            }
+        }
+
+        /// Admin only. Pass None to report in USD only
+        #[ink(message)]
+        pub fn set_reference_currency(
+            &mut self,
+            config: Option<ReferenceCurrencyConfig>,
+        ) -> Result<()> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            self.reference_currency.set(&config);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_reference_currency(&self) -> Option<ReferenceCurrencyConfig> {
+            self.reference_currency.get().flatten()
+        }
+
+        // Best-effort: a quote shouldn't fail because the FX source is down, it just
+        // comes back in USD only
+        fn get_reference_fx_rate(&self) -> Option<FxRate> {
+            let config = self.get_reference_currency()?;
+            FxRateApi {
+                fx_url: config.fx_url,
+            }
+            .get_fx_rate(config.currency)
+            .ok()
+        }
+
+        fn hash_api_key(api_key: &str) -> ApiKeyHash {
+            sp_core_hashing::blake2_256(api_key.as_bytes())
+        }
+
+        fn hash_plan_tag(api_key: Option<&str>, metadata: &[u8]) -> [u8; 16] {
+            sp_core_hashing::blake2_128(&(api_key.map(Self::hash_api_key), metadata).encode())
+        }
+
+        fn get_cur_block(&self, chain_id: &UniversalChainId) -> Result<BlockNum> {
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            BlockNumberProvider::from_chain_info(
+                chain_info,
+                &self.rpc_endpoints.get().unwrap_or_default(),
+            )
+            .get_finalized_block_number()
+            .map_err(|_| Error::RpcRequestFailed)
+        }
+
+        #[ink(message)]
+        pub fn compute_execution_plan(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            // From get_latest_snapshot_id (and passed to quote_on_snapshot too), so the plan
+            // routes on the same liquidity as the quote. None routes on the live subgraphs
+            snapshot_id: Option<SnapshotId>,
+        ) -> Result<ExecutionPlan> {
+            self.quote_engine_on_snapshot(snapshot_id)?
+                .compute_execution_plan(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_eth_addr,
+                    &dest_eth_addr,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                )
+                .map_err(Error::from)
+        }
+
+        fn compute_execution_plan_with_risk_limit(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_eth_addr: HexStrNo0x,
+            dest_eth_addr: HexStrNo0x,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            max_amount_at_risk_str: Option<String>,
+            transfer_only: bool,
+            markup_bps: Option<u16>,
+        ) -> Result<ExecutionPlan> {
+            let mut quote_engine = if transfer_only {
+                self.quote_engine()?.with_transfer_only()
+            } else {
+                self.quote_engine()?
+            };
+            if let Some(markup_bps) = markup_bps {
+                quote_engine = quote_engine.with_markup_bps(markup_bps);
+            }
+            quote_engine
+                .compute_execution_plan_with_risk_limit(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_eth_addr,
+                    &dest_eth_addr,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    max_amount_at_risk_str.as_deref(),
+                )
+                .map_err(Error::from)
+        }
+
+        #[ink(message)]
+        pub fn quote(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            // See start_swap
+            deliver_wrapped: bool,
+        ) -> Result<QuoteResponse> {
+            let quote_engine = self.quote_engine()?;
+            let subgraph_quote = quote_engine.quote(
+                &src_network_name,
+                &dest_network_name,
+                &src_token,
+                &dest_token,
+                &amount_in_str,
+                deliver_wrapped,
+            );
+            // A DEX whose subgraph is down is left out of the graph, which can also leave
+            // the pair without a path
+            let pair_cache = match subgraph_quote {
+                Err(QuoteEngineError::FailedToCreateGraph | QuoteEngineError::NoPathFound) => self
+                    .create_execute_step_meta()
+                    .ok()
+                    .and_then(|execute_step_meta| execute_step_meta.pull_pair_cache_from_s3().ok()),
+                _ => None,
+            };
+            match pair_cache {
+                Some(pair_cache) => quote_engine.quote_from_pair_cache(
+                    &pair_cache,
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    deliver_wrapped,
+                ),
+                None => subgraph_quote,
+            }
+            .map(|response| self.with_reference_amounts(response))
+            .map_err(Error::from)
+        }
+
+        fn with_reference_amounts(&self, response: QuoteResponse) -> QuoteResponse {
+            let reference_amounts = self.get_reference_fx_rate().map(|fx_rate| {
+                ReferenceAmounts::new(
+                    fx_rate,
+                    response.amount_in_usd,
+                    response.amount_out_usd,
+                    &response.fee_breakdown,
+                )
+            });
+            QuoteResponse {
+                reference_amounts,
+                ..response
+            }
+        }
+
+        /// Quote for transfer, i.e. moving the src token to the dest token as-is. The graph only
+        /// has the bridges, so this is cheaper than quote and never falls back to the pair cache
+        #[ink(message)]
+        pub fn quote_transfer(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+        ) -> Result<QuoteResponse> {
+            self.quote_engine()?
+                .with_transfer_only()
+                .quote(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    false,
+                )
+                .map(|response| self.with_reference_amounts(response))
+                .map_err(Error::from)
+        }
+
+        /// Same as quote, but with an integrator's markup of markup_bps (at most
+        /// MAX_INTEGRATOR_MARKUP_BPS) on top of the protocol fee, for white-label integrations.
+        /// The markup is reported in the fee breakdown, and start_swap_with_markup sends it to
+        /// markup_recipient_eth_addr
+        #[ink(message)]
+        pub fn quote_with_markup(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            deliver_wrapped: bool,
+            markup_bps: u16,
+            markup_recipient_eth_addr: HexStrNo0x,
+        ) -> Result<QuoteResponse> {
+            self.quote_engine()?
+                .quote_with_markup(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    deliver_wrapped,
+                    markup_bps,
+                    &markup_recipient_eth_addr,
+                )
+                .map(|response| self.with_reference_amounts(response))
+                .map_err(Error::from)
+        }
+
+        /// Same as quote, but for start_swap_into_lp: the dest token is delivered as liquidity in
+        /// the pair at pair_eth_addr. Also returns the LP tokens that gets at the pair's current
+        /// reserves. Unlike quote, this doesn't fall back to the pair cache
+        #[ink(message)]
+        pub fn quote_into_lp(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            pair_eth_addr: HexStrNo0x,
+        ) -> Result<(Amount /* LP tokens */, QuoteResponse)> {
+            self.quote_engine()?
+                .quote_zap_in(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    &pair_eth_addr,
+                    &self.get_router_allowlist(),
+                )
+                .map(|(lp_amount_out, response)| {
+                    (lp_amount_out, self.with_reference_amounts(response))
+                })
+                .map_err(Error::from)
+        }
+
+        /// Same as quote, but for start_swap_from_lp: the src amount is amount_in_str LP tokens of
+        /// the pair at pair_eth_addr on src_network_name. src token USD is what they redeem for
+        /// at the pair's current reserves. Unlike quote, this doesn't fall back to the pair cache
+        #[ink(message)]
+        pub fn quote_from_lp(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            pair_eth_addr: HexStrNo0x,
+            dest_token: String,
+            amount_in_str: String,
+            // See start_swap
+            deliver_wrapped: bool,
+        ) -> Result<QuoteResponse> {
+            self.quote_engine()?
+                .quote_from_lp(
+                    &src_network_name,
+                    &dest_network_name,
+                    &pair_eth_addr,
+                    &dest_token,
+                    &amount_in_str,
+                    deliver_wrapped,
+                    &self.get_router_allowlist(),
+                )
+                .map(|response| self.with_reference_amounts(response))
+                .map_err(Error::from)
         }
 
+        /// Same as quote, but for a swap that campaign_id pays for (see start_sponsored_swap).
+        /// The fee breakdown has no protocol fee, and sponsored_usd is what the campaign would
+        /// pay. Fails if the swap isn't eligible or the campaign has less than that left
         #[ink(message)]
-        pub fn compute_execution_plan(
+        pub fn quote_sponsored(
             &self,
+            campaign_id: String,
             src_network_name: String,
             dest_network_name: String,
-            src_eth_addr: HexStrNo0x,
-            dest_eth_addr: HexStrNo0x,
             src_token: String,
             dest_token: String,
             amount_in_str: String,
-        ) -> Result<ExecutionPlan> {
-            let (graph_solution, _, _, _) = self.compute_graph_solution_with_quote(
-                src_network_name,
-                dest_network_name,
-                src_eth_addr,
-                dest_eth_addr,
-                src_token,
-                dest_token,
-                amount_in_str,
+            // See start_swap
+            deliver_wrapped: bool,
+        ) -> Result<(QuoteResponse, Amount /* sponsored_usd */)> {
+            let campaign = self.get_sponsorship_campaign(&campaign_id)?;
+            let quote_engine = self.quote_engine()?;
+            let mut response = quote_engine.quote(
+                &src_network_name,
+                &dest_network_name,
+                &src_token,
+                &dest_token,
+                &amount_in_str,
+                deliver_wrapped,
             )?;
-            let exec_plan = ExecutionPlan::try_from(graph_solution)
-                .map_err(|_| Error::FailedToCreateExecutionPlan)?;
-            Ok(exec_plan)
+            if !campaign.is_eligible(
+                &quote_engine.parse_token_id(&src_network_name, &src_token)?,
+                &quote_engine.parse_token_id(&dest_network_name, &dest_token)?,
+                &get_fee_breakdown_chains(&response.fee_breakdown),
+            ) {
+                return Err(Error::NotEligibleForSponsorship);
+            }
+            let sponsored_usd = get_sponsored_usd(&response.fee_breakdown);
+            let spent_usd = self
+                .create_sponsorship_registry()?
+                .get_spent_usd(&campaign.campaign_id)
+                .map_err(|_| Error::DbRequestFailed)?;
+            if spent_usd.saturating_add(sponsored_usd) > campaign.budget_usd {
+                return Err(Error::SponsorshipBudgetExhausted);
+            }
+            waive_protocol_fee(&mut response.fee_breakdown);
+            Ok((self.with_reference_amounts(response), sponsored_usd))
         }
 
+        /// Quotes delivery into each stablecoin on dest_network_name and returns the one with
+        /// the highest USD amount out, net of fees, along with its quote. start_swap picks the
+        /// same way when passed dest_token = "best-stable". Unlike quote, this doesn't fall back
+        /// to the pair cache
         #[ink(message)]
-        pub fn quote(
+        pub fn quote_best_stable(
             &self,
             src_network_name: String,
             dest_network_name: String,
             src_token: String,
-            dest_token: String,
             amount_in_str: String,
-        ) -> Result<(Amount, Amount, Amount)> {
-            let (_, quote, src_usd, dest_usd) = self.compute_graph_solution_with_quote(
-                src_network_name,
-                dest_network_name,
-                "0000000000000000000000000000000000000000".to_string(), // dummy value, gets discarded for the quote
-                "0000000000000000000000000000000000000000".to_string(), // dummy value, gets discarded for the quote
-                src_token,
-                dest_token,
-                amount_in_str,
-            )?;
-            Ok((quote, src_usd, dest_usd))
+        ) -> Result<(
+            UniversalTokenId, /* chosen stablecoin */
+            Amount,           /* quote in the chosen stablecoin */
+            Amount,           /* src token USD */
+            Amount,           /* dest token USD */
+            MillisSinceEpoch, /* worst-case completion deadline */
+            FeeBreakdown,
+        )> {
+            self.quote_engine()?
+                .quote_best_stable(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &amount_in_str,
+                )
+                .map_err(Error::from)
         }
 
-        pub fn compute_graph_solution_with_quote(
+        /// The aggregated route's quote next to the best quote through each DEX alone (e.g.
+        /// "you save 1.2% vs. Stellaswap alone"). Both are priced on the same graph. DEXes that
+        /// can't fill the pair on their own (e.g. when it needs a swap on both chains) are left
+        /// out. Unlike quote, this doesn't fall back to the pair cache
+        #[ink(message)]
+        pub fn get_venue_quotes(
             &self,
             src_network_name: String,
             dest_network_name: String,
-            src_eth_addr: HexStrNo0x,
-            dest_eth_addr: HexStrNo0x,
             src_token: String,
             dest_token: String,
             amount_in_str: String,
         ) -> Result<(
-            GraphSolution,
-            Amount, /* quote in dest token */
-            Amount, /* src token USD */
-            Amount, /* dest token USD */
+            Amount, /* aggregated quote in dest token */
+            Vec<VenueQuote>,
         )> {
-            let amount_in: Amount = amount_in_str.parse().map_err(|_| Error::InvalidNumber)?;
-            let src_token_id = UniversalTokenId {
-                chain: io_helper::chain_name_to_id(&src_network_name)?,
-                id: io_helper::token_str_to_id(&src_token)?,
-            };
-            let dest_token_id = UniversalTokenId {
-                chain: io_helper::chain_name_to_id(&dest_network_name)?,
-                id: io_helper::token_str_to_id(&dest_token)?,
-            };
-            let src_addr = io_helper::hex_str_to_eth_addr(&src_eth_addr)?;
-            let dest_addr = io_helper::hex_str_to_eth_addr(&dest_eth_addr)?;
+            self.quote_engine()?
+                .get_venue_quotes(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                )
+                .map_err(Error::from)
+        }
 
-            let chain_ids: Vec<UniversalChainId> = vec![
-                universal_chain_id_registry::ASTAR,
-                universal_chain_id_registry::MOONBEAM,
-                universal_chain_id_registry::POLKADOT,
-            ];
-            let graph = graph_builder::create_graph_from_chain_ids(&chain_ids).unwrap();
-            debug_println!("Vertex count: {}", graph.simple_graph.vertex_count());
-            debug_println!("Edge count: {}", graph.simple_graph.edge_count());
-
-            let sor_config = smart_order_router::single_path_sor::SORConfig::default();
-            let sor = smart_order_router::single_path_sor::SinglePathSOR::new(
-                &graph,
-                src_addr,
-                dest_addr,
-                src_token_id.clone(),
-                dest_token_id.clone(),
-                sor_config,
-            );
-            let graph_solution = sor
-                .compute_graph_solution(amount_in)
-                .map_err(|_| Error::NoPathFound)?;
-            let src_usd_amount = graph
-                .get_token(&src_token_id)
-                .expect("Token is in graph since we found a path")
-                .derived_usd
-                .add_exp(6)
-                .mul_u128(amount_in);
-            let quote = graph_solution.get_quote_with_estimated_txn_fees();
-            let dest_usd_amount = graph
-                .get_token(&dest_token_id)
-                .expect("Token is in graph since we found a path")
-                .derived_usd
-                .add_exp(6)
-                .mul_u128(quote);
-            Ok((graph_solution, quote, src_usd_amount, dest_usd_amount))
-        }
-
-        #[ink(message)]
-        pub fn get_execplan_ids(&self) -> Result<Vec<Uuid>> {
-            let execute_step_meta = ExecutionPlanAssigner::new(
-                self.dynamodb_access_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-                self.dynamodb_secret_key
-                    .clone()
-                    .ok_or(Error::UninitializedEscrow)?,
-                self.now_millis(),
+        /// Whether any route could take src_token to dest_token, so a UI can grey out the pairs
+        /// that can't be swapped. Answered from the pair cache (see refresh_pair_cache) without
+        /// running the SOR, so a true doesn't guarantee that quote finds a route for every
+        /// amount. A false is reliable up to pools added since the last refresh
+        #[ink(message)]
+        pub fn supports_route(
+            &self,
+            src_network_name: String,
+            src_token: String,
+            dest_network_name: String,
+            dest_token: String,
+        ) -> Result<bool> {
+            let pair_cache = self
+                .create_execute_step_meta()?
+                .pull_pair_cache_from_s3()
+                .map_err(|_| Error::SnapshotNotFound)?;
+            self.quote_engine()?
+                .supports_route(
+                    &pair_cache,
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                )
+                .map_err(Error::from)
+        }
+
+        /// The largest amount of src_token (in its smallest unit) that the current liquidity
+        /// absorbs with at most max_price_impact_bps of price impact, e.g. to cap an input
+        /// slider. Price impact is measured against a $100 quote, before gas and bridge fees.
+        /// Capped at the largest swap quote accepts
+        #[ink(message)]
+        pub fn get_max_input(
+            &self,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            max_price_impact_bps: u32,
+        ) -> Result<Amount> {
+            self.quote_engine()?
+                .get_max_input(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    max_price_impact_bps,
+                )
+                .map_err(Error::from)
+        }
+
+        /// Admin only. Caches the DEXes' pairs (with their reserves and prices) in S3 for quote
+        /// to fall back on when the subgraphs are down, and summarizes them for
+        /// get_liquidity_summary. Run it periodically from the scheduler. Returns the number of
+        /// DEXes cached
+        #[ink(message)]
+        pub fn refresh_pair_cache(&self) -> Result<u32> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NoPermissions);
+            }
+            let pair_cache =
+                GraphSnapshot::fetch(&QUOTE_CHAIN_IDS).map_err(|_| Error::FailedToCreateGraph)?;
+            let execute_step_meta = self.create_execute_step_meta()?;
+            execute_step_meta
+                .save_pair_cache_to_s3(&pair_cache)
+                .map_err(|_| Error::FailedToSavePairCache)?;
+            // Unlike the pair cache, pinned snapshots are never overwritten (see
+            // get_latest_snapshot_id)
+            execute_step_meta
+                .save_pinned_snapshot_to_s3(&get_snapshot_id(&pair_cache), &pair_cache)
+                .map_err(|_| Error::FailedToSavePairCache)?;
+            // For run_shadow_quotes. Overwrites the archive of an earlier refresh in the same slot
+            let now_millis = self.now_millis();
+            let _ = execute_step_meta.save_pair_cache_archive_to_s3(
+                get_archive_slot(now_millis),
+                &ArchivedPairCache {
+                    fetched_millis: now_millis,
+                    pair_cache: pair_cache.clone(),
+                },
             );
-            Ok(execute_step_meta.get_execplan_ids().unwrap_or_default())
+            // The summary is only for introspection, so it must not fail the refresh
+            let prev_summary = execute_step_meta.pull_liquidity_summary_from_s3().ok();
+            if let Ok(summary) = LiquiditySummary::from_snapshot(
+                &pair_cache,
+                self.now_millis(),
+                prev_summary.as_ref(),
+            ) {
+                let _ = execute_step_meta.save_liquidity_summary_to_s3(&summary);
+            }
+            Ok(pair_cache.dex_responses.len() as u32)
+        }
+
+        /// ID of the snapshot the last refresh_pair_cache saved. Pass it to quote_on_snapshot and
+        /// then compute_execution_plan, so that the plan routes on the pools the user was quoted
+        #[ink(message)]
+        pub fn get_latest_snapshot_id(&self) -> Result<SnapshotId> {
+            self.create_execute_step_meta()?
+                .pull_pair_cache_from_s3()
+                .map(|pair_cache| get_snapshot_id(&pair_cache))
+                .map_err(|_| Error::SnapshotNotFound)
+        }
+
+        /// Same as quote, but routes on the pinned snapshot's pools and prices instead of the
+        /// live subgraphs (and never falls back to on-chain quotes)
+        #[ink(message)]
+        pub fn quote_on_snapshot(
+            &self,
+            snapshot_id: SnapshotId,
+            src_network_name: String,
+            dest_network_name: String,
+            src_token: String,
+            dest_token: String,
+            amount_in_str: String,
+            deliver_wrapped: bool,
+        ) -> Result<QuoteResponse> {
+            self.quote_engine_on_snapshot(Some(snapshot_id))?
+                .quote(
+                    &src_network_name,
+                    &dest_network_name,
+                    &src_token,
+                    &dest_token,
+                    &amount_in_str,
+                    deliver_wrapped,
+                )
+                .map(|response| self.with_reference_amounts(response))
+                .map_err(Error::from)
+        }
+
+        fn quote_engine_on_snapshot(&self, snapshot_id: Option<SnapshotId>) -> Result<QuoteEngine> {
+            let quote_engine = self.quote_engine()?;
+            match snapshot_id {
+                Some(snapshot_id) => {
+                    let snapshot = self
+                        .create_execute_step_meta()?
+                        .pull_pinned_snapshot_from_s3(&snapshot_id)
+                        .map_err(|_| Error::SnapshotNotFound)?;
+                    Ok(quote_engine.with_pinned_snapshot(snapshot))
+                }
+                None => Ok(quote_engine),
+            }
+        }
+
+        /// Per-chain token and pool counts and TVL ($ x 10^18) of the graph built by the last
+        /// refresh_pair_cache, and when each DEX's subgraph last responded
+        #[ink(message)]
+        pub fn get_liquidity_summary(&self) -> Result<LiquiditySummary> {
+            self.create_execute_step_meta()?
+                .pull_liquidity_summary_from_s3()
+                .map_err(|_| Error::LiquiditySummaryNotFound)
+        }
+
+        // Quoting needs none of the contract's storage, so a query-only deployment can use
+        // QuoteEngine directly. Without S3 keys there is no route blacklist or bridge fee
+        // calibration to apply. With them, failing to read either fails the quote rather than
+        // routing through blacklisted segments
+        fn quote_engine(&self) -> Result<QuoteEngine> {
+            let (blacklisted_segments, bridge_fee_calibration) =
+                match self.create_execute_step_meta() {
+                    Ok(execute_step_meta) => (
+                        execute_step_meta
+                            .pull_route_blacklist_from_s3()
+                            .map_err(|_| Error::FailedToPullRouteBlacklist)?
+                            .get_blacklisted_segments(self.now_millis())
+                            .into_iter()
+                            .map(|(segment, _)| segment)
+                            .collect(),
+                        execute_step_meta
+                            .pull_xcm_bridge_fee_calibration_from_s3()
+                            .map_err(|_| Error::FailedToPullBridgeFeeCalibration)?,
+                    ),
+                    Err(_) => Default::default(),
+                };
+            let mut token_list = self.get_token_list();
+            token_list.extend(self.get_xc20_registry().to_listed_tokens());
+            Ok(QuoteEngine::new(self.now_millis())
+                .with_token_list(token_list)
+                .with_rpc_endpoints(self.rpc_endpoints.get().unwrap_or_default())
+                .with_blacklisted_segments(blacklisted_segments)
+                .with_pool_blocklist(self.get_pool_blocklist())
+                .with_bridge_fee_calibration(bridge_fee_calibration)
+                .with_routing_config(self.get_routing_config())
+                .with_deposit_forwarder_factories(self.get_deposit_forwarder_factories()))
+        }
+
+        /// The active plans that are due to be stepped forward. A plan whose steps are in
+        /// flight is skipped until they could have confirmed (based on the step type and the
+        /// chain's block time), so workers don't waste invocations polling it too early.
+        /// For a while after a plan is due, only the worker that last stepped it forward can
+        /// claim it. That worker is returned as the plan's affinity
+        #[ink(message)]
+        pub fn get_execplan_ids(&self) -> Result<Vec<DueExecPlan>> {
+            Ok(self
+                .create_exec_plan_assigner()?
+                .get_due_execplans()
+                .unwrap_or_default())
+        }
+
+        /// Polling jobs for external automation networks (e.g. Gelato or Chainlink Automation).
+        /// Like get_execplan_ids, but also lists the plans that are not due yet with when they
+        /// will be, due ones first. Each job's call_data is the execution_plan_step_forward
+        /// message for its plan, ready to submit. Returns at most max_jobs jobs
+        #[ink(message)]
+        pub fn get_keeper_jobs(&self, max_jobs: u32) -> Result<KeeperJobs> {
+            let poll_schedule = self
+                .create_exec_plan_assigner()?
+                .get_execplan_poll_schedule()
+                .map_err(|_| Error::DbRequestFailed)?;
+            Ok(KeeperJobs::new(
+                &poll_schedule,
+                self.now_millis(),
+                max_jobs as usize,
+            ))
+        }
+
+        // Includes the plans that are not due yet
+        fn get_all_execplan_ids(&self) -> Result<Vec<Uuid>> {
+            Ok(self
+                .create_exec_plan_assigner()?
+                .get_execplan_ids()
+                .unwrap_or_default())
+        }
+
+        fn create_exec_plan_assigner(&self) -> Result<ExecutionPlanAssigner> {
+            let claim_table = self.get_storage_credential(StorageFunction::ClaimTable)?;
+            Ok(ExecutionPlanAssigner::new(
+                claim_table.access_key,
+                claim_table.secret_key,
+                self.now_millis(),
+            ))
         }
 
         fn get_eth_address_from_pair(pair: &sp_core::ecdsa::Pair) -> Result<EthAddress> {
@@ -478,7 +6095,7 @@ mod privadex_phat {
 
         fn get_eth_address_from_pubkey(pubkey: &[u8; 33]) -> Result<EthAddress> {
             let mut address = EthAddress::zero();
-            if ink_env::ecdsa_to_eth_address(pubkey, &mut address.0).is_err() {
+            if ink::env::ecdsa_to_eth_address(pubkey, &mut address.0).is_err() {
                 Err(Error::InvalidAddress)
             } else {
                 Ok(address)
@@ -504,49 +6121,45 @@ mod privadex_phat {
     }
 
     mod io_helper {
-        use privadex_chain_metadata::{
-            common::{AssetId, ChainTokenId, ERC20Token, UniversalChainId, XC20Token},
-            registry::chain::universal_chain_id_registry,
-        };
-
         use super::*;
+        use crate::quote_engine;
 
+        // The parsers shared with QuoteEngine live in quote_engine, these just convert the error
         pub fn chain_name_to_id(chain_name: &str) -> Result<UniversalChainId> {
-            match chain_name.to_lowercase().as_str() {
-                "astar" => Ok(universal_chain_id_registry::ASTAR),
-                "moonbeam" => Ok(universal_chain_id_registry::MOONBEAM),
-                "polkadot" => Ok(universal_chain_id_registry::POLKADOT),
-                _ => Err(Error::UnsupportedNetwork),
-            }
-        }
-
-        pub fn token_str_to_id(token_str: &str) -> Result<ChainTokenId> {
-            let lowercase_token_str = token_str.to_lowercase();
-            let token_str = lowercase_token_str.as_str();
-            if "native" == token_str {
-                Ok(ChainTokenId::Native)
-            } else if "xc20,id=" == &token_str[..8] {
-                let asset_id: AssetId = token_str[8..]
-                    .parse()
-                    .map_err(|_| Error::InvalidTokenString)?;
-                Ok(ChainTokenId::XC20(XC20Token::from_asset_id(asset_id)))
-            } else if "xc20,addr=" == &token_str[..10] {
-                let eth_addr = hex_str_to_eth_addr(&token_str[12..])?;
-                Ok(ChainTokenId::XC20(XC20Token::from_eth_address(eth_addr)))
-            } else if "erc20,addr=" == &token_str[..11] {
-                let eth_addr = hex_str_to_eth_addr(&token_str[13..])?;
-                Ok(ChainTokenId::ERC20(ERC20Token { addr: eth_addr }))
-            } else {
-                Err(Error::InvalidTokenString)
-            }
+            quote_engine::chain_name_to_id(chain_name).map_err(Error::from)
         }
 
         pub fn hex_str_to_eth_addr(hex_str: &str) -> Result<EthAddress> {
-            let raw_addr: [u8; 20] = hex_string_to_vec(&("0x".to_string() + hex_str))
-                .map_err(|_| Error::InvalidHexAddrString)?
-                .try_into()
-                .map_err(|_| Error::InvalidHexAddrString)?;
-            Ok(EthAddress { 0: raw_addr })
+            quote_engine::hex_str_to_eth_addr(hex_str).map_err(Error::from)
+        }
+
+        pub fn chain_name_to_ss58_prefix(chain_name: &str) -> Result<u16> {
+            let chain_id = chain_name_to_id(chain_name)?;
+            let chain_info =
+                get_chain_info_from_chain_id(&chain_id).ok_or(Error::UnsupportedNetwork)?;
+            chain_info
+                .get_ss58_prefix()
+                .map(u16::from)
+                .ok_or(Error::UnsupportedNetwork)
+        }
+
+        // Accepts a hex pubkey or an SS58 address. If expected_prefix is set, an SS58 address
+        // encoded for another chain is rejected
+        pub fn str_to_substrate_pubkey(s: &str, expected_prefix: Option<u16>) -> Result<[u8; 32]> {
+            if let Ok(pubkey) = hex_str_to_u8_32(s.strip_prefix("0x").unwrap_or(s)) {
+                return Ok(pubkey);
+            }
+            let (pubkey, prefix) = decode_ss58(s).map_err(|_| Error::InvalidAddress)?;
+            match expected_prefix {
+                Some(expected_prefix) if expected_prefix != prefix => {
+                    Err(Error::Ss58PrefixMismatch)
+                }
+                _ => Ok(pubkey),
+            }
+        }
+
+        pub fn eth_addr_to_hex_str(addr: &EthAddress) -> HexStrNo0x {
+            slice_to_hex_string(&addr.0)[2..].to_string()
         }
 
         pub fn hex_str_to_u8_32(hex_str: &str) -> Result<[u8; 32]> {
@@ -565,6 +6178,16 @@ mod privadex_phat {
             Ok(raw_hash)
         }
 
+        pub fn hex_str_to_plan_metadata(hex_str: &str) -> Result<Vec<u8>> {
+            let metadata = hex_string_to_vec(&("0x".to_string() + hex_str))
+                .map_err(|_| Error::InvalidPlanMetadata)?;
+            if metadata.len() > MAX_PLAN_METADATA_LEN {
+                Err(Error::PlanMetadataTooLong)
+            } else {
+                Ok(metadata)
+            }
+        }
+
         pub fn hex_str_to_eth_txn_hash(hex_str: &str) -> Result<EthTxnHash> {
             Ok(EthTxnHash {
                 0: hex_str_to_u8_32(hex_str)?,
@@ -572,20 +6195,293 @@ mod privadex_phat {
         }
     }
 
+    #[cfg(test)]
+    mod upgrade_tests {
+        use ink::storage::traits::{Storable, StorageKey};
+
+        use super::*;
+
+        const ADMIN: [u8; 32] = [1; 32];
+        const ESCROW_KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+        const API_KEY: &str = "integrator";
+        const DEST_ADDR: &str = "42B7D766824422F499F84703eC4E2abb273171cF";
+        // The root struct of a version 1 instance, as create_v1_contract sets it up (the
+        // destination allowlist is a Mapping, so it isn't in here). Never regenerate this.
+        // Version 1 is the first release with upgrade. The ink 3 instances from before it can't
+        // be upgraded at all, so nothing here covers them (see the README's "Upgrading a
+        // deployment")
+        const STORAGE_V1_GOLDEN: &str = include_str!("./golden/storage_v1.hex");
+
+        fn create_v1_contract() -> PrivaDex {
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from(ADMIN));
+            let mut contract = PrivaDex::new();
+            contract
+                .init_secret_keys(
+                    Some(ESCROW_KEY.to_string()),
+                    Some(ESCROW_KEY.to_string()),
+                    SignatureScheme::Ed25519,
+                    "dynamodb_access".to_string(),
+                    "dynamodb_secret".to_string(),
+                    "s3_secret".to_string(),
+                    "s3_access".to_string(),
+                )
+                .expect("Valid init");
+            contract
+                .set_destination_allowlist(API_KEY.to_string(), vec![DEST_ADDR.to_string()])
+                .expect("Valid allowlist");
+            contract
+                .set_screening_config(Some("https://screening.example".to_string()), true)
+                .expect("Admin can set screening");
+            // Version 1 storage has nothing at the storage_version key
+            ink::env::clear_contract_storage(&contract.storage_version.key());
+            // ..nor a deployment epoch
+            ink::env::clear_contract_storage(&contract.deployment_epoch.key());
+            contract
+        }
+
+        // What the new code sees after set_code_hash: the old root struct decoded as the new one
+        fn swap_code(contract: &PrivaDex) -> PrivaDex {
+            let mut encoded = Vec::new();
+            Storable::encode(contract, &mut encoded);
+            <PrivaDex as Storable>::decode(&mut encoded.as_slice()).expect("Decodable storage")
+        }
+
+        // What the new code sees after set_code_hash on a version 1 instance
+        fn decode_v1_storage(encoded: &[u8]) -> PrivaDex {
+            let mut input = encoded;
+            let contract = <PrivaDex as Storable>::decode(&mut input).expect("Decodable storage");
+            assert!(
+                input.is_empty(),
+                "The root struct must use up all of the bytes"
+            );
+            contract
+        }
+
+        fn get_v1_golden() -> Vec<u8> {
+            hex::decode(STORAGE_V1_GOLDEN.split_whitespace().collect::<String>())
+                .expect("Golden files must be hex")
+        }
+
+        #[ink::test]
+        fn test_upgrade_from_v1_preserves_storage() {
+            let contract = create_v1_contract();
+            let mut upgraded = decode_v1_storage(&get_v1_golden());
+            assert_eq!(upgraded.get_storage_version(), 1);
+            assert_eq!(upgraded.migrate_storage(), Ok(STORAGE_VERSION));
+            assert_eq!(upgraded.get_storage_version(), STORAGE_VERSION);
+
+            assert_eq!(upgraded.admin, AccountId::from(ADMIN));
+            assert_eq!(
+                upgraded.escrow_eth_private_key,
+                contract.escrow_eth_private_key
+            );
+            assert_eq!(
+                upgraded.escrow_substrate_private_key,
+                contract.escrow_substrate_private_key
+            );
+            assert_eq!(
+                upgraded.escrow_substrate_sig_scheme,
+                SignatureScheme::Ed25519
+            );
+            assert_eq!(
+                upgraded.dynamodb_access_key.as_deref(),
+                Some("dynamodb_access")
+            );
+            assert_eq!(
+                upgraded.dynamodb_secret_key.as_deref(),
+                Some("dynamodb_secret")
+            );
+            assert_eq!(upgraded.s3_access_key.as_deref(), Some("s3_access"));
+            assert_eq!(upgraded.s3_secret_key.as_deref(), Some("s3_secret"));
+            assert_eq!(
+                upgraded.screening_url.as_deref(),
+                Some("https://screening.example")
+            );
+            assert!(upgraded.bypass_screening);
+            assert_eq!(
+                upgraded.get_destination_allowlist(API_KEY.to_string()),
+                Ok(Some(vec![
+                    io_helper::hex_str_to_eth_addr(DEST_ADDR).unwrap()
+                ]))
+            );
+            assert_eq!(upgraded.threshold, contract.threshold);
+            assert_eq!(upgraded.remote_signer_url, contract.remote_signer_url);
+            // So its NonceManagers keep using the keys from before deployment epochs
+            assert_eq!(upgraded.get_deployment_epoch(), 0);
+        }
+
+        #[ink::test]
+        fn test_upgrade_from_v1_keeps_sr25519_escrow() {
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from(ADMIN));
+            let mut encoded = get_v1_golden();
+            // The Ed25519 flag, after the admin and the two Option<SecretKey>s
+            let flag_index = 32 + 33 + 33;
+            assert_eq!(encoded[flag_index], 1);
+            encoded[flag_index] = 0;
+
+            let mut upgraded = decode_v1_storage(&encoded);
+            assert_eq!(upgraded.migrate_storage(), Ok(STORAGE_VERSION));
+            assert_eq!(
+                upgraded.escrow_substrate_sig_scheme,
+                SignatureScheme::Sr25519
+            );
+        }
+
+        #[ink::test]
+        fn test_redeployment_gets_new_epoch() {
+            ink::env::test::set_block_timestamp::<PinkEnvironment>(1_690_000_000_000);
+            let contract = PrivaDex::new();
+            assert_eq!(contract.get_deployment_epoch(), 1_690_000_000_000);
+            assert_eq!(
+                swap_code(&contract).get_deployment_epoch(),
+                1_690_000_000_000
+            );
+
+            ink::env::test::set_block_timestamp::<PinkEnvironment>(1_700_000_000_000);
+            assert_eq!(PrivaDex::new().get_deployment_epoch(), 1_700_000_000_000);
+        }
+
+        #[ink::test]
+        fn test_migrate_storage_is_idempotent() {
+            let mut contract = PrivaDex::new();
+            assert_eq!(contract.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(contract.migrate_storage(), Ok(STORAGE_VERSION));
+            assert_eq!(contract.migrate_storage(), Ok(STORAGE_VERSION));
+        }
+
+        #[ink::test]
+        fn test_migrate_storage_rejects_newer_version() {
+            let mut contract = PrivaDex::new();
+            contract.storage_version.set(&(STORAGE_VERSION + 1));
+            assert_eq!(
+                contract.migrate_storage(),
+                Err(Error::UnsupportedStorageVersion)
+            );
+        }
+
+        #[ink::test]
+        fn test_upgrade_is_admin_only() {
+            let mut contract = create_v1_contract();
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from([2; 32]));
+            assert_eq!(
+                contract.upgrade(Hash::from([0; 32])),
+                Err(Error::NoPermissions)
+            );
+            assert_eq!(contract.migrate_storage(), Err(Error::NoPermissions));
+            assert_eq!(
+                contract.migrate_nonce_reservations("astar".to_string(), 0, true),
+                Err(Error::NoPermissions)
+            );
+        }
+
+        #[ink::test]
+        fn test_api_key_bound_to_caller() {
+            let mut contract = create_v1_contract();
+            let integrator = AccountId::from([3; 32]);
+            let user = AccountId::from([4; 32]);
+            // Unbound keys can be passed (or left out) by anyone
+            ink::env::test::set_caller::<PinkEnvironment>(user);
+            assert_eq!(contract.check_api_key_caller(None), Ok(()));
+            assert_eq!(contract.check_api_key_caller(Some(API_KEY)), Ok(()));
+
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from(ADMIN));
+            contract
+                .set_integrator_account(integrator, Some(API_KEY.to_string()))
+                .expect("Admin can bind an account");
+
+            // The integrator can't leave its key out or pass another one
+            ink::env::test::set_caller::<PinkEnvironment>(integrator);
+            assert_eq!(contract.check_api_key_caller(Some(API_KEY)), Ok(()));
+            assert_eq!(
+                contract.check_api_key_caller(None),
+                Err(Error::ApiKeyNotBoundToCaller)
+            );
+            assert_eq!(
+                contract.check_api_key_caller(Some("other")),
+                Err(Error::ApiKeyNotBoundToCaller)
+            );
+            assert_eq!(
+                contract.set_integrator_account(integrator, None),
+                Err(Error::NoPermissions)
+            );
+            // ..and nobody else can pass it
+            ink::env::test::set_caller::<PinkEnvironment>(user);
+            assert_eq!(
+                contract.check_api_key_caller(Some(API_KEY)),
+                Err(Error::ApiKeyNotBoundToCaller)
+            );
+            assert_eq!(contract.check_api_key_caller(None), Ok(()));
+
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from(ADMIN));
+            contract
+                .set_integrator_account(integrator, None)
+                .expect("Admin can unbind an account");
+            ink::env::test::set_caller::<PinkEnvironment>(user);
+            assert_eq!(contract.check_api_key_caller(Some(API_KEY)), Ok(()));
+        }
+
+        #[ink::test]
+        fn test_storage_credentials_split_after_upgrade() {
+            let mut upgraded = swap_code(&create_v1_contract());
+            // Deployments from before the split keep using the shared pairs
+            assert!(upgraded.get_scoped_storage_functions().is_empty());
+            assert_eq!(
+                upgraded
+                    .get_storage_credential(StorageFunction::NonceTable)
+                    .map(|credential| credential.access_key),
+                Ok("dynamodb_access".to_string())
+            );
+
+            upgraded
+                .init_storage_credentials(vec![(
+                    StorageFunction::Analytics,
+                    "analytics_access".to_string(),
+                    "analytics_secret".to_string(),
+                )])
+                .expect("Admin can init storage credentials");
+            assert_eq!(
+                upgraded.init_storage_credentials(Vec::new()),
+                Err(Error::AlreadyInitialized)
+            );
+            upgraded
+                .rotate_storage_credentials(
+                    StorageFunction::NonceTable,
+                    "nonce_access".to_string(),
+                    "nonce_secret".to_string(),
+                )
+                .expect("Admin can rotate storage credentials");
+
+            let credentials = upgraded.get_storage_credentials().unwrap();
+            assert_eq!(credentials.analytics.access_key, "analytics_access");
+            assert_eq!(credentials.nonce_table.access_key, "nonce_access");
+            assert_eq!(credentials.claim_table.access_key, "dynamodb_access");
+            assert_eq!(credentials.plan_store.access_key, "s3_access");
+
+            ink::env::test::set_caller::<PinkEnvironment>(AccountId::from([2; 32]));
+            assert_eq!(
+                upgraded.rotate_storage_credentials(
+                    StorageFunction::Analytics,
+                    "stolen_access".to_string(),
+                    "stolen_secret".to_string(),
+                ),
+                Err(Error::NoPermissions)
+            );
+        }
+    }
+
     #[cfg(all(feature = "dynamodb-live-test", feature = "s3-live-test"))]
     #[cfg(test)]
     mod phat_tests {
         use core::str::FromStr;
         use hex_literal::hex;
-        use ink_env::debug_println;
-        use ink_lang as ink;
-        use openbrush::traits::mock::{Addressable, SharedCallStack};
+        use ink::env::debug_println;
 
         use privadex_chain_metadata::common::{
             ChainTokenId, ERC20Token, SecretKeyContainer, XC20Token,
         };
 
         use super::*;
+        use crate::quote_engine;
 
         fn get_nonull_env_var(name: &str) -> Option<String> {
             if let Ok(val) = std::env::var(name) {
@@ -593,11 +6489,11 @@ mod privadex_phat {
                     return Some(val);
                 }
             }
-            ink_env::debug_println!("Env var {name} is not set");
+            ink::env::debug_println!("Env var {name} is not set");
             None
         }
 
-        fn get_phat_contract() -> Addressable<PrivaDex> {
+        fn get_phat_contract() -> PrivaDex {
             let escrow_eth_private_key = SecretKeyContainer::from_str(
                 &std::env::var("ETH_PRIVATE_KEY").expect("Env var ETH_PRIVATE_KEY is not set"),
             )
@@ -614,23 +6510,22 @@ mod privadex_phat {
             let s3_access_key = get_nonull_env_var("S3_ACCESS_KEY").unwrap();
             let s3_secret_key = get_nonull_env_var("S3_SECRET_KEY").unwrap();
 
-            let escrow_eth_pubkey = ink_env::AccountId::try_from(
+            let escrow_eth_pubkey = ink::primitives::AccountId::try_from(
                 sp_core::sr25519::Pair::from_seed(&escrow_eth_private_key)
                     .public()
                     .0,
             )
             .expect("Valid account");
 
-            let stack = SharedCallStack::new(escrow_eth_pubkey.clone());
-            // Sets the stack's caller before constructor is called (which sets admin)
-            stack.push(&escrow_eth_pubkey);
-            let contract = Addressable::create_native(1, PrivaDex::new(), stack.clone());
+            // Sets the caller before constructor is called (which sets admin)
+            ink::env::test::set_caller::<PinkEnvironment>(escrow_eth_pubkey);
+            let mut contract = PrivaDex::new();
 
             let _ = contract
-                .call_mut()
                 .init_secret_keys(
-                    slice_to_hex_string(&escrow_eth_private_key)[2..].to_string(),
-                    slice_to_hex_string(&escrow_substrate_private_key)[2..].to_string(),
+                    Some(slice_to_hex_string(&escrow_eth_private_key)[2..].to_string()),
+                    Some(slice_to_hex_string(&escrow_substrate_private_key)[2..].to_string()),
+                    SignatureScheme::Sr25519,
                     dynamodb_access_key,
                     dynamodb_secret_key,
                     s3_secret_key,
@@ -645,7 +6540,7 @@ mod privadex_phat {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
             let contract = get_phat_contract();
-            let admin = contract.call().get_admin();
+            let admin = contract.get_admin();
             debug_println!("Admin: {:?}", slice_to_hex_string(admin.as_ref()));
         }
 
@@ -654,7 +6549,7 @@ mod privadex_phat {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
             let contract = get_phat_contract();
-            let addr = contract.call().get_escrow_eth_account_address();
+            let addr = contract.get_escrow_eth_account_address();
             debug_println!("Escrow Eth account: {:?}", addr);
         }
 
@@ -662,13 +6557,15 @@ mod privadex_phat {
         fn test_token_parse() {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
-            let x =
-                io_helper::token_str_to_id("erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c")
-                    .expect("Valid ERC20 addr");
-            let y =
-                io_helper::token_str_to_id("xC20,addr=0xFfFFfFff1FcaCBd218EDc0EbA20Fc2308C778080")
-                    .expect("Valid XC20 addr");
-            let z = io_helper::token_str_to_id("Xc20,id=42259045809535163221576417993425387648")
+            let x = quote_engine::token_str_to_id(
+                "erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c",
+            )
+            .expect("Valid ERC20 addr");
+            let y = quote_engine::token_str_to_id(
+                "xC20,addr=0xFfFFfFff1FcaCBd218EDc0EbA20Fc2308C778080",
+            )
+            .expect("Valid XC20 addr");
+            let z = quote_engine::token_str_to_id("Xc20,id=42259045809535163221576417993425387648")
                 .expect("Valid XC20 id");
             assert_eq!(
                 x,
@@ -687,12 +6584,60 @@ mod privadex_phat {
             assert_eq!(z, y);
         }
 
+        #[ink::test]
+        fn test_ss58_addresses() {
+            let contract = PrivaDex::new();
+            let alice_polkadot = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string();
+            let alice_hex =
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d".to_string();
+            let alice_astar = "ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8".to_string();
+
+            assert_eq!(
+                contract.get_ss58_address("astar".to_string(), alice_polkadot.clone()),
+                Ok(alice_astar.clone())
+            );
+            assert_eq!(
+                contract.get_ss58_address("astar".to_string(), alice_hex.clone()),
+                Ok(alice_astar.clone())
+            );
+            assert_eq!(
+                contract.validate_ss58_address("astar".to_string(), alice_astar),
+                Ok(alice_hex)
+            );
+            assert_eq!(
+                contract.validate_ss58_address("astar".to_string(), alice_polkadot.clone()),
+                Err(Error::Ss58PrefixMismatch)
+            );
+            assert_eq!(
+                contract.validate_ss58_address("astar".to_string(), "not-an-address".to_string()),
+                Err(Error::InvalidAddress)
+            );
+            assert_eq!(
+                contract.get_evm_mapped_address(
+                    "astar".to_string(),
+                    "05a81d8564a3ea298660e34e03e5eff9a29d7a2a".to_string()
+                ),
+                Ok("XmmrKUnJjEsupddUPCKQuBkUqEFiCm6jJPZaaYK5T25f9w7".to_string())
+            );
+            assert_eq!(
+                contract.get_evm_mapped_address(
+                    "moonbeam".to_string(),
+                    "05a81d8564a3ea298660e34e03e5eff9a29d7a2a".to_string()
+                ),
+                Err(Error::UnsupportedNetwork)
+            );
+            assert_eq!(
+                contract.resolve_destination(alice_polkadot),
+                Err(Error::InvalidAddress)
+            );
+        }
+
         #[ink::test]
         fn test_compute_exec_plan() {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
             let contract = get_phat_contract();
-            let exec_plan = contract.call().compute_execution_plan(
+            let exec_plan = contract.compute_execution_plan(
                 "astar".to_string(),
                 "moonbeam".to_string(),
                 "90204F4683D20367ae8044CfE23aC63e87C996CE".to_string(),
@@ -700,6 +6645,7 @@ mod privadex_phat {
                 "native".to_string(),
                 "erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c".to_string(), // USDC_wormhole
                 "100000000000000000000".to_string(),
+                None,
             );
             debug_println!("Execution plan: {:?}", exec_plan);
         }
@@ -709,12 +6655,13 @@ mod privadex_phat {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
             let contract = get_phat_contract();
-            let quote = contract.call().quote(
+            let quote = contract.quote(
                 "astar".to_string(),
                 "moonbeam".to_string(),
                 "native".to_string(),
                 "erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c".to_string(), // USDC_wormhole
                 "100000000000000000000".to_string(),
+                false,
             );
             debug_println!("Quote: {:?}", quote);
         }
@@ -725,7 +6672,6 @@ mod privadex_phat {
 
             let contract = get_phat_contract();
             let exec_plan_uuid = contract
-                .call()
                 .start_swap(
                     "d471de9980d69157cbdefbbb659b63c9edcc4855fc65d0898191aad5b160a80a".to_string(),
                     "astar".to_string(),
@@ -735,6 +6681,13 @@ mod privadex_phat {
                     "native".to_string(),
                     "erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c".to_string(), // USDC_wormhole
                     "100000000000000000000".to_string(),
+                    None,
+                    None,
+                    false,
+                    false,
+                    None,
+                    Some("6f726465722d31".to_string()), // "order-1"
+                    None,
                 )
                 .expect("Should save execution plan into S3");
             debug_println!("Saved execution plan in S3 with UUID {:?}", exec_plan_uuid);
@@ -745,7 +6698,7 @@ mod privadex_phat {
             pink_extension_runtime::mock_ext::mock_all_ext();
 
             let contract = get_phat_contract();
-            let exec_plan_ids = contract.call().get_execplan_ids();
+            let exec_plan_ids = contract.get_execplan_ids();
             debug_println!("Execution plans: {:?}", exec_plan_ids);
         }
     }
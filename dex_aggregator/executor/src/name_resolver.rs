@@ -0,0 +1,142 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::string::String;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{EthAddress, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+
+use crate::concurrency_coordinator::address_alias_registry::AddressAliasRegistry;
+use crate::eth_utils::ens_registry_contract::ENSRegistryContract;
+use crate::rpc_endpoints::{get_rpc_url, RpcEndpoint};
+
+// ENS allows much longer names, but nobody types those into a swap form
+const MAX_NAME_LEN: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub enum NameResolverError {
+    AliasRegistryUnavailable,
+    RequestFailed,
+    UnsupportedNetwork,
+}
+pub type Result<T> = core::result::Result<T, NameResolverError>;
+
+/// A source that start_swap can resolve a destination name with. The configured resolvers
+/// are tried in order and the first one that knows the name wins
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NameResolver {
+    // Aliases that the admin registered with set_address_alias (see AddressAliasRegistry)
+    AliasRegistry,
+    // ENS-compatible registry contract, queried with eth_call on the given chain
+    Ens {
+        chain_id: UniversalChainId,
+        registry_address: EthAddress,
+    },
+}
+
+impl NameResolver {
+    fn resolve(
+        &self,
+        name: &str,
+        alias_registry: Option<&AddressAliasRegistry>,
+        rpc_endpoints: &[RpcEndpoint],
+    ) -> Result<Option<EthAddress>> {
+        match self {
+            Self::AliasRegistry => alias_registry
+                .ok_or(NameResolverError::AliasRegistryUnavailable)?
+                .get_alias(name)
+                .map_err(|_| NameResolverError::RequestFailed),
+            Self::Ens {
+                chain_id,
+                registry_address,
+            } => {
+                let chain_info = get_chain_info_from_chain_id(chain_id)
+                    .ok_or(NameResolverError::UnsupportedNetwork)?;
+                ENSRegistryContract::new(get_rpc_url(rpc_endpoints, chain_info), *registry_address)
+                    .and_then(|registry| registry.resolve(name))
+                    .map_err(|_| NameResolverError::RequestFailed)
+            }
+        }
+    }
+}
+
+// Fails closed: if a resolver errors out we don't fall through to the next one, which could
+// map the same name to a different address. name must already be normalized
+pub fn resolve_name(
+    resolvers: &[NameResolver],
+    name: &str,
+    alias_registry: Option<&AddressAliasRegistry>,
+    rpc_endpoints: &[RpcEndpoint],
+) -> Result<Option<EthAddress>> {
+    for resolver in resolvers {
+        if let Some(address) = resolver.resolve(name, alias_registry, rpc_endpoints)? {
+            return Ok(Some(address));
+        }
+    }
+    Ok(None)
+}
+
+// Lowercases the name. None if it is too long, has empty labels, or has characters other
+// than [a-z0-9._-] (we don't support internationalized names)
+pub fn normalize_name(name: &str) -> Option<String> {
+    let name = name.to_ascii_lowercase();
+    let is_valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name.bytes().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'.' || c == b'-' || c == b'_'
+        })
+        && name.split('.').all(|label| !label.is_empty());
+    if is_valid {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod name_resolver_tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Alice.ETH"), Some("alice.eth".into()));
+        assert_eq!(
+            normalize_name("kraken_deposit-1"),
+            Some("kraken_deposit-1".into())
+        );
+        assert_eq!(normalize_name(""), None);
+        assert_eq!(normalize_name("alice..eth"), None);
+        assert_eq!(normalize_name(".eth"), None);
+        // Would otherwise break out of the DynamoDB request's JSON string
+        assert_eq!(normalize_name("alice\", \"id"), None);
+        assert_eq!(normalize_name(&"a".repeat(MAX_NAME_LEN + 1)), None);
+    }
+
+    #[test]
+    fn test_resolve_name_without_resolvers() {
+        assert_eq!(resolve_name(&[], "alice.eth", None, &[]), Ok(None));
+        assert_eq!(
+            resolve_name(&[NameResolver::AliasRegistry], "alice", None, &[]),
+            Err(NameResolverError::AliasRegistryUnavailable)
+        );
+    }
+}
@@ -0,0 +1,2503 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Everything quote and compute_execution_plan need, without any of the contract's storage.
+// It never touches the escrow keys or DynamoDB, so it also works in a query-only deployment
+// that was never given secrets
+use ink::env::debug_println;
+use ink::prelude::{string::ToString, vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    chain_info::{ChainInfo, DepositForwarderFactory},
+    common::{
+        Amount, AssetId, ChainTokenId, ERC20Token, EthAddress, MillisSinceEpoch, UniversalChainId,
+        UniversalTokenId, XC20Token,
+    },
+    get_aggregators_from_chain_id, get_chain_info_from_chain_id,
+    registry::{
+        chain::universal_chain_id_registry,
+        dex::{pool_blocklist_overlay::PoolBlocklistOverlay, DexId},
+        token::{
+            stablecoin_registry, token_decimals_registry,
+            token_list_overlay::{ListedToken, TokenListOverlay},
+            token_symbol_registry,
+        },
+    },
+};
+use privadex_common::utils::general_utils::{hex_string_to_vec, mul_ratio_u128};
+use privadex_execution_plan::{
+    execution_plan::{
+        ExecutionPlan, PathExecutionMode, PlanQuote, QuotedTerms, RouteHash,
+        MAX_INTEGRATOR_MARKUP_BPS,
+    },
+    graph_solution_to_execution_plan::{
+        common::GraphToExecConversionError, converter as graph_solution_converter,
+        route_hash::get_route_hash,
+    },
+};
+use privadex_routing::{
+    graph::{
+        edge::{AggregatorSwapEdge, Edge, SwapEdge},
+        fee_breakdown::FeeBreakdown,
+        graph::{Graph, GraphPath, GraphSolution, SplitGraphPath},
+        traits::QuoteGetter,
+    },
+    graph_builder,
+    graph_snapshot::GraphSnapshot,
+    routing_config::RoutingConfig,
+    smart_order_router::{
+        self, route_exists::route_exists, router_candidate_paths::find_router_candidate_paths,
+        venue_quotes::find_venue_quotes,
+    },
+    PublicError,
+};
+
+use crate::eth_utils::{
+    self, aggregator_api::AggregatorApi, dex_pair_contract::DEXPairContract,
+    dex_router_contract::DEXRouterContract,
+};
+use crate::executable::{
+    deposit_tolerance::DEPOSIT_TOLERANCE_BPS,
+    executable_plan::PROTOCOL_FEE_BPS,
+    route_blacklist::RouteSegment,
+    router_allowlist::{get_router_for_factory, AllowedRouter},
+    xcm_bridge_fee_calibration::XcmBridgeFeeCalibration,
+    zap_in, zap_out,
+};
+use crate::fx_rate::ReferenceAmounts;
+use crate::rpc_endpoints::{get_rpc_url, RpcEndpoint};
+
+// Upper bound on the number of tranches in a staged swap. Every tranche pays its own
+// gas and bridge fees and the ExecutionPlan must stay small enough to store cheaply
+pub const MAX_NUM_TRANCHES: usize = 10;
+
+// Sanity bounds on a swap's input value, in $ x 10^6 (same units as the quote's USD amounts).
+// Since a token's derived USD price is per smallest unit, these also catch amounts that
+// were passed with the wrong number of decimals (e.g. 100 wei of an 18-decimals token)
+pub const MIN_AMOUNT_IN_USD: Amount = 1_000_000; // $1
+pub const MAX_AMOUNT_IN_USD: Amount = 1_000_000_000_000; // $1M
+
+// A direct-pool quote that loses more than this share of the input's USD value (DEX fee,
+// price impact and gas) is considered poor, and we run the full SOR instead since a
+// multi-hop or cross-chain route may do better
+const MAX_DIRECT_QUOTE_VALUE_LOSS_BPS: Amount = 100;
+
+// Passed as the dest token to deliver whichever stablecoin on the dest chain nets the most USD
+pub const BEST_STABLE_SELECTOR: &str = "best-stable";
+
+// An exact-output quote's amount in may overshoot the smallest one that nets the amount out by
+// at most this much. Every step of the search runs the SOR once, so this bounds the search too
+const EXACT_OUT_TOLERANCE_BPS: Amount = 5;
+const MAX_EXACT_OUT_NUM_QUOTES: usize = 40;
+
+// get_max_input measures price impact against the quote of an amount in worth this much, in
+// $ x 10^6. Its answer may fall short of the largest amount within the bound by at most
+// MAX_INPUT_TOLERANCE_BPS
+const MAX_INPUT_REFERENCE_USD: Amount = 100_000_000; // $100
+const MAX_INPUT_TOLERANCE_BPS: Amount = 50;
+const MAX_INPUT_NUM_QUOTES: usize = 40;
+
+// Converts USD amounts back into a token's amount through the USD value of this much of it. It
+// only needs to be large enough that its USD value doesn't round to 0
+const PRICE_PROBE_AMOUNT: Amount = 1_000_000_000_000_000_000;
+
+// The chains whose DEXes and bridges the full graph (and the pair cache) is built from
+#[cfg(not(feature = "testnet"))]
+pub const QUOTE_CHAIN_IDS: [UniversalChainId; 3] = [
+    universal_chain_id_registry::ASTAR,
+    universal_chain_id_registry::MOONBEAM,
+    universal_chain_id_registry::POLKADOT,
+];
+// Moonbase Alpha has the only testnet DEX, and no XCM bridges to the other Moonbase chains are
+// registered yet
+#[cfg(feature = "testnet")]
+pub const QUOTE_CHAIN_IDS: [UniversalChainId; 1] = [universal_chain_id_registry::MOONBASE_ALPHA];
+
+// Names a pair cache snapshot by its content, so two refreshes that fetched the same pools get
+// the same ID. A quote and a plan pinned to the same ID route on exactly the same liquidity
+pub type SnapshotId = [u8; 16];
+
+pub fn get_snapshot_id(snapshot: &GraphSnapshot) -> SnapshotId {
+    sp_core_hashing::blake2_128(&snapshot.to_bytes())
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum QuoteEngineError {
+    AmountInAboveMaximumUsd,
+    AmountInBelowMinimumUsd,
+    AmountInDoesNotCoverFees,
+    AmountOutNotReachable,
+    BlockedPool,
+    DestTokenNotNative,
+    FailedToCreateExecutionPlan,
+    FailedToCreateGraph,
+    InvalidHexAddrString,
+    InvalidIntegratorMarkup,
+    // Not a pair with a registry DEX's or allowlisted router's factory, one of its tokens is the
+    // dest token, or the LP tokens redeem for nothing
+    InvalidLpSource,
+    InvalidNumber,
+    InvalidTokenString,
+    // Not a pair of the dest token, or no registry DEX or allowlisted router has its factory
+    InvalidZapIn,
+    NoPathFound,
+    NoStablecoinOnNetwork,
+    NoWrappedNativeToken,
+    RpcRequestFailed,
+    // The testnet build has no graph to quote on yet (see the README's Testnets section)
+    TestnetQuotesUnsupported,
+    TooManyTranches,
+    // Neither a registry token nor on the imported token list, so its decimals are unknown
+    UnknownToken,
+    UnknownTokenSymbol,
+    UnsupportedNetwork,
+}
+pub type Result<T> = core::result::Result<T, QuoteEngineError>;
+
+// Tells the frontend how much to trust a quote
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum QuoteSource {
+    Subgraph,
+    // The subgraphs were down, so the amount out is from the DEX router's getAmountsOut over a
+    // cached pair list. USD amounts and fees use the cached prices
+    OnChainFallback,
+}
+
+/// What the quote messages return. Integrators decode it by field name from the contract
+/// metadata, so new fields are added at the end rather than changing the return type
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QuoteResponse {
+    // In the dest token
+    pub amount_out: Amount,
+    pub amount_in_usd: Amount,
+    pub amount_out_usd: Amount,
+    // Worst-case completion deadline
+    pub deadline_millis: MillisSinceEpoch,
+    pub fee_breakdown: FeeBreakdown,
+    // get_swap_status returns the plan's, so the frontend can tell if it was routed as quoted
+    pub route_hash: RouteHash,
+    pub quote_source: QuoteSource,
+    // The USD amounts in the reference currency, if the admin set one (see fx_rate)
+    pub reference_amounts: Option<ReferenceAmounts>,
+}
+
+// The graph a quote is priced against: its pools, bridges and every token's derived USD price.
+// Building it costs a subgraph request per DEX, so a caller that quotes several pairs or
+// amounts (or quotes and then plans) should build it once and reuse it across
+// quote(context, request) and plan(context, quote)
+pub struct QuoteContext {
+    graph: Graph,
+    // Used for the ExecutionPlan deadlines
+    now_millis: MillisSinceEpoch,
+    // Limits the routes the SOR considers on the graph
+    routing_config: RoutingConfig,
+    // Left out of the graph, and plan refuses quotes that still swap through one of them
+    pool_blocklist: PoolBlocklistOverlay,
+    // Override the registry's factory of the chain, so plan gives deposits on it their own
+    // address (see set_deposit_forwarder)
+    deposit_forwarder_factories: Vec<(UniversalChainId, DepositForwarderFactory)>,
+}
+
+impl QuoteContext {
+    // Prices come from whatever graph is passed in, e.g. a direct-pair graph or a pair cache
+    pub fn from_graph(graph: Graph, now_millis: MillisSinceEpoch) -> Self {
+        Self {
+            graph,
+            now_millis,
+            routing_config: RoutingConfig::default(),
+            pool_blocklist: PoolBlocklistOverlay::new(),
+            deposit_forwarder_factories: Vec::new(),
+        }
+    }
+
+    pub fn with_routing_config(mut self, routing_config: RoutingConfig) -> Self {
+        self.routing_config = routing_config;
+        self
+    }
+
+    pub fn with_deposit_forwarder_factories(
+        mut self,
+        deposit_forwarder_factories: Vec<(UniversalChainId, DepositForwarderFactory)>,
+    ) -> Self {
+        self.deposit_forwarder_factories = deposit_forwarder_factories;
+        self
+    }
+
+    pub fn with_pool_blocklist(mut self, pool_blocklist: PoolBlocklistOverlay) -> Self {
+        let num_excluded = graph_builder::exclude_blocked_pools(&mut self.graph, &pool_blocklist);
+        if num_excluded > 0 {
+            debug_println!("Excluded {} edges of blocked pools", num_excluded);
+        }
+        self.pool_blocklist = pool_blocklist;
+        self
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn get_amount_usd(&self, token_id: &UniversalTokenId, amount: Amount) -> Result<Amount> {
+        get_amount_usd(&self.graph, token_id, amount)
+    }
+
+    // If the swap has not completed by this deadline, the user gets refunded instead
+    fn get_deadline_millis(
+        &self,
+        graph_solution: GraphSolution,
+        deliver_wrapped: bool,
+    ) -> Result<MillisSinceEpoch> {
+        self.get_deadline_millis_with_markup(graph_solution, deliver_wrapped, 0)
+    }
+
+    // A markup adds a fee skim step to every path. Its recipient doesn't affect the deadline
+    fn get_deadline_millis_with_markup(
+        &self,
+        graph_solution: GraphSolution,
+        deliver_wrapped: bool,
+        markup_bps: u16,
+    ) -> Result<MillisSinceEpoch> {
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution)
+            .map_err(|_| QuoteEngineError::FailedToCreateExecutionPlan)?;
+        if deliver_wrapped {
+            set_wrapped_delivery(&mut exec_plan)?;
+        }
+        if markup_bps > 0 {
+            set_integrator_markup(&mut exec_plan, markup_bps, EthAddress::zero())?;
+        }
+        Ok(self.now_millis + exec_plan.get_worst_case_duration_millis())
+    }
+
+    fn get_zap_in_deadline_millis(
+        &self,
+        graph_solution: GraphSolution,
+        deliver_wrapped: bool,
+        target: &ZapInTarget,
+    ) -> Result<MillisSinceEpoch> {
+        let mut exec_plan = ExecutionPlan::try_from(graph_solution)
+            .map_err(|_| QuoteEngineError::FailedToCreateExecutionPlan)?;
+        if deliver_wrapped {
+            set_wrapped_delivery(&mut exec_plan)?;
+        }
+        set_zap_in_delivery(&mut exec_plan, target)?;
+        Ok(self.now_millis + exec_plan.get_worst_case_duration_millis())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QuoteRequest {
+    pub src_token_id: UniversalTokenId,
+    pub dest_token_id: UniversalTokenId,
+    pub amount_in: Amount,
+    // Only end up in the plan's steps, so a quote-only caller can pass EthAddress::zero()
+    pub src_addr: EthAddress,
+    pub dest_addr: EthAddress,
+}
+
+#[derive(Debug, Clone)]
+pub struct Quote {
+    pub graph_solution: GraphSolution,
+    // In dest token, net of the estimated gas and bridge fees
+    pub amount_out: Amount,
+    pub amount_in_usd: Amount,
+    pub amount_out_usd: Amount,
+    pub fee_breakdown: FeeBreakdown,
+}
+
+// What routing through a single DEX would have returned, for comparison with the aggregated quote
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct VenueQuote {
+    pub dex_id: DexId,
+    // In dest token, net of the estimated gas and bridge fees (like Quote::amount_out)
+    pub amount_out: Amount,
+    // How much more the aggregated quote returns, relative to amount_out
+    pub savings_bps: u32,
+}
+
+// For a native dest token, delivers its wrapped version (e.g. WGLMR) instead. The deadline is
+// pushed back if that adds a wrap step
+pub fn set_wrapped_delivery(exec_plan: &mut ExecutionPlan) -> Result<()> {
+    let worst_case_duration_millis = exec_plan.get_worst_case_duration_millis();
+    graph_solution_converter::set_wrapped_delivery(exec_plan).map_err(|e| match e {
+        GraphToExecConversionError::DestTokenNotNative => QuoteEngineError::DestTokenNotNative,
+        GraphToExecConversionError::NoWrappedNativeToken => QuoteEngineError::NoWrappedNativeToken,
+        // Swapping the wrapped token for itself
+        GraphToExecConversionError::WrappedDeliveryOfWrappedToken => QuoteEngineError::NoPathFound,
+        _ => QuoteEngineError::FailedToCreateExecutionPlan,
+    })?;
+    exec_plan.deadline_millis += exec_plan
+        .get_worst_case_duration_millis()
+        .saturating_sub(worst_case_duration_millis);
+    Ok(())
+}
+
+// Sends markup_bps of what each path delivers to recipient (see quote_with_markup). Call it after
+// set_wrapped_delivery. The deadline is pushed back for the fee skim steps
+pub fn set_integrator_markup(
+    exec_plan: &mut ExecutionPlan,
+    markup_bps: u16,
+    recipient: EthAddress,
+) -> Result<()> {
+    let worst_case_duration_millis = exec_plan.get_worst_case_duration_millis();
+    graph_solution_converter::set_integrator_markup(exec_plan, markup_bps, recipient).map_err(
+        |e| match e {
+            GraphToExecConversionError::InvalidIntegratorMarkup => {
+                QuoteEngineError::InvalidIntegratorMarkup
+            }
+            _ => QuoteEngineError::FailedToCreateExecutionPlan,
+        },
+    )?;
+    exec_plan.deadline_millis += exec_plan
+        .get_worst_case_duration_millis()
+        .saturating_sub(worst_case_duration_millis);
+    Ok(())
+}
+
+// The pair a zap-in adds the dest token to, and the router it is added through (see
+// QuoteEngine::get_zap_in_target)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ZapInTarget {
+    pub dex_router_addr: EthAddress,
+    // The dest token as the pair holds it, i.e. wrapped if it is native
+    pub token: UniversalTokenId,
+    pub pair_token: UniversalTokenId,
+    pub lp_token: UniversalTokenId,
+}
+
+// Delivers the dest token as liquidity in target's pair instead (see quote_zap_in). Call it after
+// set_wrapped_delivery and before set_integrator_markup. The deadline is pushed back for the
+// zap-in's swap
+pub fn set_zap_in_delivery(exec_plan: &mut ExecutionPlan, target: &ZapInTarget) -> Result<()> {
+    let worst_case_duration_millis = exec_plan.get_worst_case_duration_millis();
+    graph_solution_converter::set_zap_in_delivery(
+        exec_plan,
+        target.dex_router_addr,
+        target.pair_token.clone(),
+        target.lp_token.clone(),
+    )
+    .map_err(|e| match e {
+        GraphToExecConversionError::InvalidZapIn => QuoteEngineError::InvalidZapIn,
+        _ => QuoteEngineError::FailedToCreateExecutionPlan,
+    })?;
+    exec_plan.deadline_millis += exec_plan
+        .get_worst_case_duration_millis()
+        .saturating_sub(worst_case_duration_millis);
+    Ok(())
+}
+
+// The pair whose LP tokens a zap-out redeems, the router it redeems them through (see
+// QuoteEngine::get_lp_source), and what the LP tokens redeem for at the pair's current reserves
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LpSource {
+    pub dex_router_addr: EthAddress,
+    pub lp_token: UniversalTokenId,
+    pub token_a: UniversalTokenId,
+    pub token_b: UniversalTokenId,
+    pub token_a_amount: Amount,
+    pub token_b_amount: Amount,
+}
+
+// Merges token_b_plan into exec_plan behind a removeLiquidity of source's pair (see
+// compute_lp_source_execution_plan). Call it before any of the other set_* functions. The
+// deadline is pushed back for the removal, and the merged paths' extra duration if they run
+// sequentially
+pub fn set_lp_source(
+    exec_plan: &mut ExecutionPlan,
+    token_b_plan: ExecutionPlan,
+    source: &LpSource,
+    lp_amount: Amount,
+) -> Result<()> {
+    let worst_case_duration_millis = exec_plan
+        .get_worst_case_duration_millis()
+        .max(token_b_plan.get_worst_case_duration_millis());
+    let deadline_millis = exec_plan.deadline_millis.max(token_b_plan.deadline_millis);
+    graph_solution_converter::set_lp_source(
+        exec_plan,
+        token_b_plan,
+        source.dex_router_addr,
+        source.lp_token.clone(),
+        lp_amount,
+    )
+    .map_err(|e| match e {
+        GraphToExecConversionError::InvalidLpSource => QuoteEngineError::InvalidLpSource,
+        _ => QuoteEngineError::FailedToCreateExecutionPlan,
+    })?;
+    exec_plan.deadline_millis = deadline_millis
+        + exec_plan
+            .get_worst_case_duration_millis()
+            .saturating_sub(worst_case_duration_millis);
+    Ok(())
+}
+
+pub fn validate_integrator_markup(markup_bps: u16, recipient: &EthAddress) -> Result<()> {
+    if markup_bps == 0 || markup_bps > MAX_INTEGRATOR_MARKUP_BPS || *recipient == EthAddress::zero()
+    {
+        Err(QuoteEngineError::InvalidIntegratorMarkup)
+    } else {
+        Ok(())
+    }
+}
+
+// The full graph over QUOTE_CHAIN_IDS
+pub fn build_context(now_millis: MillisSinceEpoch) -> Result<QuoteContext> {
+    build_context_with_config(now_millis, RoutingConfig::default())
+}
+
+pub fn build_context_with_config(
+    now_millis: MillisSinceEpoch,
+    routing_config: RoutingConfig,
+) -> Result<QuoteContext> {
+    check_quotes_supported()?;
+    let graph =
+        graph_builder::create_graph_from_chain_ids_with_config(&QUOTE_CHAIN_IDS, &routing_config)
+            .map_err(|_| QuoteEngineError::FailedToCreateGraph)?;
+    debug_println!("Vertex count: {}", graph.simple_graph.vertex_count());
+    debug_println!("Edge count: {}", graph.simple_graph.edge_count());
+    Ok(QuoteContext::from_graph(graph, now_millis).with_routing_config(routing_config))
+}
+
+// Only the bridges, wraps and unwraps over QUOTE_CHAIN_IDS (see quote_transfer). It skips the DEX
+// pairs, so it is much cheaper to build than the full graph
+pub fn build_transfer_context(now_millis: MillisSinceEpoch) -> Result<QuoteContext> {
+    check_quotes_supported()?;
+    let graph = graph_builder::create_transfer_graph(&QUOTE_CHAIN_IDS)
+        .map_err(|_| QuoteEngineError::FailedToCreateGraph)?;
+    Ok(QuoteContext::from_graph(graph, now_millis))
+}
+
+// The Moonbase Alpha DEX has no indexer, so on the testnet build the graph would have no pools
+// or USD prices. Fail with a dedicated error rather than FailedToCreateGraph, which the quote
+// messages treat as the subgraphs being down
+fn check_quotes_supported() -> Result<()> {
+    if cfg!(feature = "testnet") {
+        Err(QuoteEngineError::TestnetQuotesUnsupported)
+    } else {
+        Ok(())
+    }
+}
+
+// Runs the SOR on the context's graph. No network requests, so it is cheap to call repeatedly
+pub fn quote(context: &QuoteContext, request: &QuoteRequest) -> Result<Quote> {
+    quote_with_sor_config(
+        context,
+        request,
+        smart_order_router::single_path_sor::SORConfig::new(&context.routing_config),
+    )
+}
+
+// Like quote, but the src token is moved to the dest token as-is, i.e. the route is only bridges,
+// wraps and unwraps. Tokens that aren't the same asset get NoPathFound
+pub fn quote_transfer(context: &QuoteContext, request: &QuoteRequest) -> Result<Quote> {
+    quote_with_sor_config(
+        context,
+        request,
+        smart_order_router::single_path_sor::SORConfig::new(&context.routing_config)
+            .with_transfer_only(),
+    )
+}
+
+fn quote_with_sor_config(
+    context: &QuoteContext,
+    request: &QuoteRequest,
+    sor_config: smart_order_router::single_path_sor::SORConfig,
+) -> Result<Quote> {
+    let sor = smart_order_router::single_path_sor::SinglePathSOR::new(
+        &context.graph,
+        request.src_addr,
+        request.dest_addr,
+        request.src_token_id.clone(),
+        request.dest_token_id.clone(),
+        sor_config,
+    );
+    // Checked before the search, which is by far the most expensive part of a quote
+    let amount_in_usd = context.get_amount_usd(&request.src_token_id, request.amount_in)?;
+    validate_amount_in_usd(amount_in_usd)?;
+    let graph_solution = sor
+        .compute_graph_solution(request.amount_in)
+        .map_err(|e| match e {
+            PublicError::AmountInDoesNotCoverFees | PublicError::ZeroAmountIn => {
+                QuoteEngineError::AmountInDoesNotCoverFees
+            }
+            _ => QuoteEngineError::NoPathFound,
+        })?;
+    let amount_out = graph_solution.get_quote_with_estimated_txn_fees();
+    validate_quote_covers_fees(amount_out)?;
+    let amount_out_usd = context.get_amount_usd(&request.dest_token_id, amount_out)?;
+    let fee_breakdown = graph_solution.get_fee_breakdown(&context.graph, PROTOCOL_FEE_BPS);
+    Ok(Quote {
+        graph_solution,
+        amount_out,
+        amount_in_usd,
+        amount_out_usd,
+        fee_breakdown,
+    })
+}
+
+// Quotes the request into each of dest_token_ids and keeps the one with the highest USD amount
+// out, since the candidates need not share decimals. Returns the request with the dest token
+// that won. Candidates the request can't be quoted into are skipped
+pub fn quote_best_dest_token(
+    context: &QuoteContext,
+    request: &QuoteRequest,
+    dest_token_ids: &[UniversalTokenId],
+) -> Result<(QuoteRequest, Quote)> {
+    let mut best_quote: Option<(QuoteRequest, Quote)> = None;
+    let mut first_err = None;
+    for dest_token_id in dest_token_ids.iter() {
+        let candidate_request = QuoteRequest {
+            dest_token_id: dest_token_id.clone(),
+            ..request.clone()
+        };
+        match quote(context, &candidate_request) {
+            Ok(candidate_quote) => {
+                let is_better = best_quote.as_ref().map_or(true, |(_, best_quote)| {
+                    candidate_quote.amount_out_usd > best_quote.amount_out_usd
+                });
+                if is_better {
+                    best_quote = Some((candidate_request, candidate_quote));
+                }
+            }
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    best_quote.ok_or(first_err.unwrap_or(QuoteEngineError::NoPathFound))
+}
+
+// Exact-output quote: the smallest amount in (to within EXACT_OUT_TOLERANCE_BPS) that nets at
+// least amount_out of the dest token after fees. request.amount_in is ignored. Returns the
+// request with the amount in that was found, so it can be planned like any other quote
+pub fn quote_exact_out(
+    context: &QuoteContext,
+    request: &QuoteRequest,
+    amount_out: Amount,
+) -> Result<(QuoteRequest, Quote)> {
+    // Start the search at the amount in worth as much as amount_out
+    let amount_out_usd = context.get_amount_usd(&request.dest_token_id, amount_out)?;
+    let probe_usd = context.get_amount_usd(&request.src_token_id, PRICE_PROBE_AMOUNT)?;
+    if probe_usd == 0 {
+        return Err(QuoteEngineError::NoPathFound);
+    }
+    let estimated_amount_in = mul_ratio_u128(amount_out_usd, PRICE_PROBE_AMOUNT, probe_usd);
+    find_exact_out_amount_in(amount_out, estimated_amount_in, |amount_in| {
+        let candidate_request = QuoteRequest {
+            amount_in,
+            ..request.clone()
+        };
+        match quote(context, &candidate_request) {
+            Ok(candidate_quote) => Ok(Some((
+                candidate_quote.amount_out,
+                (candidate_request, candidate_quote),
+            ))),
+            // Too little to quote, so we need more in
+            Err(QuoteEngineError::AmountInBelowMinimumUsd)
+            | Err(QuoteEngineError::AmountInDoesNotCoverFees) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+    .map(|(_, best)| best)
+}
+
+// Doubles the amount in from estimated_amount_in until it nets amount_out, then binary searches
+// down. The gas and bridge fees don't scale with the amount in, so the quote isn't linear in
+// it, but it is increasing. get_quote returns the amount out along with the quote, or None if
+// the amount in is too small to quote
+fn find_exact_out_amount_in<T>(
+    amount_out: Amount,
+    estimated_amount_in: Amount,
+    mut get_quote: impl FnMut(Amount) -> Result<Option<(Amount, T)>>,
+) -> Result<(Amount, T)> {
+    let mut num_quotes = 0;
+    // Highest amount in known to fall short
+    let mut low = 0;
+    let mut high = estimated_amount_in.max(1);
+    let mut high_quote = loop {
+        if num_quotes == MAX_EXACT_OUT_NUM_QUOTES {
+            return Err(QuoteEngineError::AmountOutNotReachable);
+        }
+        num_quotes += 1;
+        match get_quote(high)? {
+            Some((quote_amount_out, quote)) if quote_amount_out >= amount_out => break quote,
+            _ => {
+                low = high;
+                high = high
+                    .checked_mul(2)
+                    .ok_or(QuoteEngineError::AmountOutNotReachable)?;
+            }
+        }
+    };
+    while high - low > mul_ratio_u128(high, EXACT_OUT_TOLERANCE_BPS, 10_000).max(1)
+        && num_quotes < MAX_EXACT_OUT_NUM_QUOTES
+    {
+        let mid = low + (high - low) / 2;
+        num_quotes += 1;
+        match get_quote(mid)? {
+            Some((quote_amount_out, quote)) if quote_amount_out >= amount_out => {
+                high = mid;
+                high_quote = quote;
+            }
+            _ => low = mid,
+        }
+    }
+    Ok((high, high_quote))
+}
+
+// The largest amount in that the route's liquidity absorbs with at most max_price_impact_bps of
+// price impact, e.g. to cap a UI's input slider. Price impact is measured against the quote of
+// an amount in worth MAX_INPUT_REFERENCE_USD, before the estimated gas and bridge fees (which
+// don't scale with the amount in). Capped at the amount in worth MAX_AMOUNT_IN_USD, since
+// quote refuses anything larger. request.amount_in is ignored
+pub fn get_max_input(
+    context: &QuoteContext,
+    request: &QuoteRequest,
+    max_price_impact_bps: u32,
+) -> Result<Amount> {
+    if max_price_impact_bps >= 10_000 {
+        return Err(QuoteEngineError::InvalidNumber);
+    }
+    let probe_usd = context.get_amount_usd(&request.src_token_id, PRICE_PROBE_AMOUNT)?;
+    if probe_usd == 0 {
+        return Err(QuoteEngineError::NoPathFound);
+    }
+    let get_gross_amount_out = |amount_in: Amount| -> Result<Amount> {
+        let candidate_request = QuoteRequest {
+            amount_in,
+            ..request.clone()
+        };
+        Ok(quote(context, &candidate_request)?
+            .graph_solution
+            .get_quote())
+    };
+    let reference_amount_in =
+        mul_ratio_u128(MAX_INPUT_REFERENCE_USD, PRICE_PROBE_AMOUNT, probe_usd).max(1);
+    let reference_amount_out = get_gross_amount_out(reference_amount_in)?;
+    let max_amount_in = mul_ratio_u128(MAX_AMOUNT_IN_USD, PRICE_PROBE_AMOUNT, probe_usd);
+    find_max_amount_in(reference_amount_in, max_amount_in, |amount_in| {
+        let amount_out_at_reference_price =
+            mul_ratio_u128(amount_in, reference_amount_out, reference_amount_in);
+        let min_amount_out = mul_ratio_u128(
+            amount_out_at_reference_price,
+            10_000 - max_price_impact_bps as Amount,
+            10_000,
+        );
+        Ok(get_gross_amount_out(amount_in)? >= min_amount_out)
+    })
+}
+
+// Doubles the amount in from min_amount_in (which is taken to be within the bound) until it
+// isn't within the bound or reaches max_amount_in, then binary searches up. Price impact only
+// grows with the amount in
+fn find_max_amount_in(
+    min_amount_in: Amount,
+    max_amount_in: Amount,
+    mut is_within_bound: impl FnMut(Amount) -> Result<bool>,
+) -> Result<Amount> {
+    let mut num_quotes = 0;
+    // Highest amount in known to be within the bound
+    let mut low = min_amount_in.min(max_amount_in);
+    let mut high = loop {
+        if low == max_amount_in || num_quotes == MAX_INPUT_NUM_QUOTES {
+            return Ok(low);
+        }
+        let candidate = low.saturating_mul(2).min(max_amount_in);
+        num_quotes += 1;
+        if is_within_bound(candidate)? {
+            low = candidate;
+        } else {
+            break candidate;
+        }
+    };
+    while high - low > mul_ratio_u128(low, MAX_INPUT_TOLERANCE_BPS, 10_000).max(1)
+        && num_quotes < MAX_INPUT_NUM_QUOTES
+    {
+        let mid = low + (high - low) / 2;
+        num_quotes += 1;
+        if is_within_bound(mid)? {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+// Best first. Priced on the same graph as quote, so the comparison needs no extra requests. Venues
+// whose fees eat the whole amount are left out
+pub fn venue_quotes(
+    context: &QuoteContext,
+    request: &QuoteRequest,
+    quote: &Quote,
+) -> Result<Vec<VenueQuote>> {
+    Ok(find_venue_quotes(
+        &context.graph,
+        &request.src_token_id,
+        &request.dest_token_id,
+        request.amount_in,
+    )
+    .map_err(|_| QuoteEngineError::NoPathFound)?
+    .into_iter()
+    .filter(|(_, amount_out)| *amount_out > 0)
+    .map(|(dex_id, amount_out)| VenueQuote {
+        dex_id,
+        amount_out,
+        savings_bps: get_savings_bps(quote.amount_out, amount_out),
+    })
+    .collect())
+}
+
+// Turns a quote into an ExecutionPlan, split into sequential tranches of at most
+// max_amount_at_risk if one is given
+pub fn plan(
+    context: &QuoteContext,
+    quote: Quote,
+    max_amount_at_risk: Option<Amount>,
+) -> Result<ExecutionPlan> {
+    graph_solution_converter::check_no_blocked_pools(
+        &quote.graph_solution,
+        &context.pool_blocklist,
+    )
+    .map_err(|_| QuoteEngineError::BlockedPool)?;
+    // For the quoted terms, since the graph solution is moved into the plan below
+    let route_hash = get_route_hash(&quote.graph_solution);
+    let amount_in = quote.graph_solution.amount_in;
+    let graph_solution = match max_amount_at_risk {
+        Some(max_amount_at_risk) => quote
+            .graph_solution
+            .split_into_tranches(max_amount_at_risk, MAX_NUM_TRANCHES)
+            .map_err(|e| match e {
+                PublicError::TooManyTranches => QuoteEngineError::TooManyTranches,
+                _ => QuoteEngineError::InvalidNumber,
+            })?,
+        None => quote.graph_solution,
+    };
+    let deposit_salt = graph_solution_converter::get_graph_solution_deposit_salt(&graph_solution)
+        .map_err(|_| QuoteEngineError::FailedToCreateExecutionPlan)?;
+    let mut exec_plan = ExecutionPlan::try_from(graph_solution)
+        .map_err(|_| QuoteEngineError::FailedToCreateExecutionPlan)?;
+    if max_amount_at_risk.is_some() {
+        exec_plan.path_execution_mode = PathExecutionMode::Sequential;
+    }
+    let deposit_chain = exec_plan.prestart_user_to_escrow_transfer.get_src_chain();
+    if let Some((_, factory)) = context
+        .deposit_forwarder_factories
+        .iter()
+        .find(|(chain, _)| *chain == deposit_chain)
+    {
+        graph_solution_converter::set_deposit_forwarder(&mut exec_plan, factory, deposit_salt)
+            .map_err(|_| QuoteEngineError::FailedToCreateExecutionPlan)?;
+    }
+    // Set after path_execution_mode since sequential tranches take longer
+    exec_plan.deadline_millis = context.now_millis + exec_plan.get_worst_case_duration_millis();
+    exec_plan.quote = Some(PlanQuote {
+        amount_out: quote.amount_out,
+        amount_in_usd: quote.amount_in_usd,
+        amount_out_usd: quote.amount_out_usd,
+    });
+    // The caller updates expiry_millis if it adds steps that push back the deadline
+    exec_plan.quoted_terms = Some(QuotedTerms {
+        quoted_at_millis: context.now_millis,
+        amount_in,
+        amount_out: quote.amount_out,
+        amount_in_usd: quote.amount_in_usd,
+        amount_out_usd: quote.amount_out_usd,
+        fee_breakdown: quote.fee_breakdown,
+        deposit_tolerance_bps: DEPOSIT_TOLERANCE_BPS,
+        expiry_millis: exec_plan.deadline_millis,
+        route_hash,
+    });
+    Ok(exec_plan)
+}
+
+pub struct QuoteEngine {
+    // Used for the ExecutionPlan deadlines. The caller passes it in since only a contract has
+    // the block timestamp
+    now_millis: MillisSinceEpoch,
+    // Symbols of the operator's imported token list (see token_list) are also accepted
+    token_list: TokenListOverlay,
+    // Only used for the on-chain fallback quotes
+    rpc_endpoints: Vec<RpcEndpoint>,
+    // Left out of every graph we route on (see route_blacklist)
+    blacklisted_segments: Vec<RouteSegment>,
+    // Operator-blocked pools, also left out of every graph (see QuoteContext)
+    pool_blocklist: PoolBlocklistOverlay,
+    // Bridge fees recent XCM transfers actually paid, which replace the registry's estimates
+    bridge_fee_calibration: XcmBridgeFeeCalibration,
+    // Quotes and plans only move the src token to the dest token as-is (see quote_transfer)
+    transfer_only: bool,
+    // Only changes the fee breakdown that plans record in their quoted terms. The markup itself
+    // is added to the plan with set_integrator_markup
+    markup_bps: Option<u16>,
+    routing_config: RoutingConfig,
+    // Routes on this snapshot's pools instead of the live subgraphs (see with_pinned_snapshot)
+    pinned_snapshot: Option<GraphSnapshot>,
+    deposit_forwarder_factories: Vec<(UniversalChainId, DepositForwarderFactory)>,
+}
+
+impl QuoteEngine {
+    pub fn new(now_millis: MillisSinceEpoch) -> Self {
+        Self {
+            now_millis,
+            token_list: TokenListOverlay::new(),
+            rpc_endpoints: Vec::new(),
+            blacklisted_segments: Vec::new(),
+            pool_blocklist: PoolBlocklistOverlay::new(),
+            bridge_fee_calibration: XcmBridgeFeeCalibration::default(),
+            transfer_only: false,
+            markup_bps: None,
+            routing_config: RoutingConfig::default(),
+            pinned_snapshot: None,
+            deposit_forwarder_factories: Vec::new(),
+        }
+    }
+
+    pub fn with_token_list(mut self, token_list: TokenListOverlay) -> Self {
+        self.token_list = token_list;
+        self
+    }
+
+    pub fn with_rpc_endpoints(mut self, rpc_endpoints: Vec<RpcEndpoint>) -> Self {
+        self.rpc_endpoints = rpc_endpoints;
+        self
+    }
+
+    pub fn with_blacklisted_segments(mut self, blacklisted_segments: Vec<RouteSegment>) -> Self {
+        self.blacklisted_segments = blacklisted_segments;
+        self
+    }
+
+    pub fn with_pool_blocklist(mut self, pool_blocklist: PoolBlocklistOverlay) -> Self {
+        self.pool_blocklist = pool_blocklist;
+        self
+    }
+
+    pub fn with_bridge_fee_calibration(
+        mut self,
+        bridge_fee_calibration: XcmBridgeFeeCalibration,
+    ) -> Self {
+        self.bridge_fee_calibration = bridge_fee_calibration;
+        self
+    }
+
+    pub fn with_transfer_only(mut self) -> Self {
+        self.transfer_only = true;
+        self
+    }
+
+    pub fn with_markup_bps(mut self, markup_bps: u16) -> Self {
+        self.markup_bps = Some(markup_bps);
+        self
+    }
+
+    pub fn with_routing_config(mut self, routing_config: RoutingConfig) -> Self {
+        self.routing_config = routing_config;
+        self
+    }
+
+    // Quotes and plans are then deterministic for a given snapshot: the graph is built from
+    // its stored subgraph responses, and the direct pair shortcut (a live request) is skipped.
+    // Transfer-only quotes don't route on pools, so they ignore it
+    pub fn with_pinned_snapshot(mut self, snapshot: GraphSnapshot) -> Self {
+        self.pinned_snapshot = Some(snapshot);
+        self
+    }
+
+    pub fn with_deposit_forwarder_factories(
+        mut self,
+        deposit_forwarder_factories: Vec<(UniversalChainId, DepositForwarderFactory)>,
+    ) -> Self {
+        self.deposit_forwarder_factories = deposit_forwarder_factories;
+        self
+    }
+
+    pub fn build_context(&self) -> Result<QuoteContext> {
+        if let (Some(snapshot), false) = (&self.pinned_snapshot, self.transfer_only) {
+            let graph = snapshot
+                .to_graph()
+                .map_err(|_| QuoteEngineError::FailedToCreateGraph)?;
+            return Ok(self.create_context(graph));
+        }
+        let mut context = if self.transfer_only {
+            build_transfer_context(self.now_millis)?
+                .with_routing_config(self.routing_config.clone())
+        } else {
+            build_context_with_config(self.now_millis, self.routing_config.clone())?
+        };
+        self.exclude_blacklisted_segments(&mut context.graph);
+        self.apply_bridge_fee_calibration(&mut context.graph);
+        Ok(context
+            .with_pool_blocklist(self.pool_blocklist.clone())
+            .with_deposit_forwarder_factories(self.deposit_forwarder_factories.clone()))
+    }
+
+    fn create_context(&self, mut graph: Graph) -> QuoteContext {
+        self.exclude_blacklisted_segments(&mut graph);
+        self.apply_bridge_fee_calibration(&mut graph);
+        QuoteContext::from_graph(graph, self.now_millis)
+            .with_routing_config(self.routing_config.clone())
+            .with_pool_blocklist(self.pool_blocklist.clone())
+            .with_deposit_forwarder_factories(self.deposit_forwarder_factories.clone())
+    }
+
+    fn exclude_blacklisted_segments(&self, graph: &mut Graph) {
+        if self.blacklisted_segments.is_empty() {
+            return;
+        }
+        let num_excluded = graph.retain_edges(|edge| {
+            !self
+                .blacklisted_segments
+                .iter()
+                .any(|segment| segment.matches_edge(edge))
+        });
+        debug_println!("Excluded {} blacklisted edges", num_excluded);
+    }
+
+    fn apply_bridge_fee_calibration(&self, graph: &mut Graph) {
+        if self.bridge_fee_calibration.is_empty() {
+            return;
+        }
+        let num_updated = graph_builder::apply_xcm_bridge_fees(graph, |src_token, dest_token| {
+            self.bridge_fee_calibration
+                .get_bridge_fee(src_token, dest_token)
+        });
+        debug_println!("Calibrated the bridge fees of {} XCM edges", num_updated);
+    }
+
+    // Also accepts the token list's symbols, like every other token argument
+    pub fn parse_token_id(&self, network_name: &str, token: &str) -> Result<UniversalTokenId> {
+        network_and_token_str_to_id_with_token_list(network_name, token, &self.token_list)
+    }
+
+    // The token with its symbol, name, decimals and logo. The registry's symbol and decimals
+    // win over the token list's, which fills in the rest (and covers tokens the registry lacks)
+    pub fn resolve_token(&self, network_name: &str, token: &str) -> Result<ListedToken> {
+        let token_id = self.parse_token_id(network_name, token)?;
+        let listed_token = self.token_list.get(&token_id);
+        let symbol = token_symbol_registry::token_to_symbol(&token_id)
+            .map(|symbol| symbol.to_string())
+            .or_else(|| listed_token.map(|listed_token| listed_token.symbol.clone()));
+        let decimals = token_decimals_registry::get_decimals(&token_id)
+            .or_else(|| listed_token.map(|listed_token| listed_token.decimals));
+        match (symbol, decimals) {
+            (Some(symbol), Some(decimals)) => Ok(ListedToken {
+                name: listed_token
+                    .map_or_else(|| symbol.clone(), |listed_token| listed_token.name.clone()),
+                logo_uri: listed_token.and_then(|listed_token| listed_token.logo_uri.clone()),
+                token: token_id,
+                symbol,
+                decimals,
+            }),
+            _ => Err(QuoteEngineError::UnknownToken),
+        }
+    }
+
+    pub fn parse_quote_request(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_eth_addr: &str,
+        dest_eth_addr: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+    ) -> Result<QuoteRequest> {
+        let (amount_in, src_token_id, dest_token_id) = self.parse_quote_args(
+            src_network_name,
+            dest_network_name,
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        Ok(QuoteRequest {
+            src_token_id,
+            dest_token_id,
+            amount_in,
+            src_addr: hex_str_to_eth_addr(src_eth_addr)?,
+            dest_addr: hex_str_to_eth_addr(dest_eth_addr)?,
+        })
+    }
+
+    pub fn compute_execution_plan(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_eth_addr: &str,
+        dest_eth_addr: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+    ) -> Result<ExecutionPlan> {
+        self.compute_execution_plan_with_risk_limit(
+            src_network_name,
+            dest_network_name,
+            src_eth_addr,
+            dest_eth_addr,
+            src_token,
+            dest_token,
+            amount_in_str,
+            None,
+        )
+    }
+
+    pub fn compute_execution_plan_with_risk_limit(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_eth_addr: &str,
+        dest_eth_addr: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+        max_amount_at_risk_str: Option<&str>,
+    ) -> Result<ExecutionPlan> {
+        let max_amount_at_risk = max_amount_at_risk_str
+            .map(|amount_str| {
+                amount_str
+                    .parse::<Amount>()
+                    .map_err(|_| QuoteEngineError::InvalidNumber)
+            })
+            .transpose()?;
+        let (_, context, mut quote) = self.compute_quote_with_selector(
+            src_network_name,
+            dest_network_name,
+            src_eth_addr,
+            dest_eth_addr,
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        if let Some(markup_bps) = self.markup_bps {
+            quote.fee_breakdown = quote.graph_solution.get_fee_breakdown_with_markup(
+                context.graph(),
+                PROTOCOL_FEE_BPS,
+                markup_bps,
+            );
+        }
+        plan(&context, quote, max_amount_at_risk)
+    }
+
+    pub fn quote(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+        deliver_wrapped: bool,
+    ) -> Result<QuoteResponse> {
+        let request = self.parse_quote_request(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        let (context, quote) = self.compute_quote(&request)?;
+        let route_hash = get_route_hash(&quote.graph_solution);
+        let deadline_millis = context.get_deadline_millis(quote.graph_solution, deliver_wrapped)?;
+        Ok(QuoteResponse {
+            amount_out: quote.amount_out,
+            amount_in_usd: quote.amount_in_usd,
+            amount_out_usd: quote.amount_out_usd,
+            deadline_millis,
+            fee_breakdown: quote.fee_breakdown,
+            route_hash,
+            quote_source: QuoteSource::Subgraph,
+            reference_amounts: None,
+        })
+    }
+
+    // Same as quote, but the fee breakdown includes the integrator's markup and the deadline
+    // leaves time for its fee skim steps. amount_out is before both fees, as in quote
+    pub fn quote_with_markup(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+        deliver_wrapped: bool,
+        markup_bps: u16,
+        markup_recipient_eth_addr: &str,
+    ) -> Result<QuoteResponse> {
+        validate_integrator_markup(markup_bps, &hex_str_to_eth_addr(markup_recipient_eth_addr)?)?;
+        let request = self.parse_quote_request(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        let (context, quote) = self.compute_quote(&request)?;
+        let fee_breakdown = quote.graph_solution.get_fee_breakdown_with_markup(
+            context.graph(),
+            PROTOCOL_FEE_BPS,
+            markup_bps,
+        );
+        let route_hash = get_route_hash(&quote.graph_solution);
+        let deadline_millis = context.get_deadline_millis_with_markup(
+            quote.graph_solution,
+            deliver_wrapped,
+            markup_bps,
+        )?;
+        Ok(QuoteResponse {
+            amount_out: quote.amount_out,
+            amount_in_usd: quote.amount_in_usd,
+            amount_out_usd: quote.amount_out_usd,
+            deadline_millis,
+            fee_breakdown,
+            route_hash,
+            quote_source: QuoteSource::Subgraph,
+            reference_amounts: None,
+        })
+    }
+
+    // Same as quote, but the dest token is delivered as liquidity in the pair at pair_eth_addr (see
+    // get_zap_in_target). Also returns the LP tokens that gets at the pair's current reserves
+    pub fn quote_zap_in(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+        pair_eth_addr: &str,
+        router_allowlist: &[AllowedRouter],
+    ) -> Result<(Amount /* LP tokens */, QuoteResponse)> {
+        let request = self.parse_quote_request(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        let target = self.get_zap_in_target(
+            &request.dest_token_id,
+            hex_str_to_eth_addr(pair_eth_addr)?,
+            router_allowlist,
+        )?;
+        let (context, quote) = self.compute_quote(&request)?;
+        let route_hash = get_route_hash(&quote.graph_solution);
+        let deliver_wrapped = request.dest_token_id.id == ChainTokenId::Native;
+        let deadline_millis =
+            context.get_zap_in_deadline_millis(quote.graph_solution, deliver_wrapped, &target)?;
+        let lp_amount_out = self.estimate_zap_in_liquidity(&target, quote.amount_out)?;
+        Ok((
+            lp_amount_out,
+            QuoteResponse {
+                amount_out: quote.amount_out,
+                amount_in_usd: quote.amount_in_usd,
+                amount_out_usd: quote.amount_out_usd,
+                deadline_millis,
+                fee_breakdown: quote.fee_breakdown,
+                route_hash,
+                quote_source: QuoteSource::Subgraph,
+                reference_amounts: None,
+            },
+        ))
+    }
+
+    // The pair must hold the dest token (wrapped, if it is native), and its factory must be a
+    // registry DEX's or an allowlisted router's. That router is the one the zap-in goes through
+    pub fn get_zap_in_target(
+        &self,
+        dest_token_id: &UniversalTokenId,
+        pair_addr: EthAddress,
+        router_allowlist: &[AllowedRouter],
+    ) -> Result<ZapInTarget> {
+        let chain_id = dest_token_id.chain;
+        let chain_info =
+            get_chain_info_from_chain_id(&chain_id).ok_or(QuoteEngineError::UnsupportedNetwork)?;
+        let (token, token_addr) = match get_dex_token_address(dest_token_id) {
+            Some(token_addr) => (dest_token_id.clone(), token_addr),
+            None => {
+                let weth_addr = chain_info
+                    .weth_addr
+                    .ok_or(QuoteEngineError::NoWrappedNativeToken)?;
+                (erc20_token_id(chain_id, weth_addr), weth_addr)
+            }
+        };
+        let pair = DEXPairContract::new(&get_rpc_url(&self.rpc_endpoints, chain_info), pair_addr)
+            .map_err(|_| QuoteEngineError::InvalidZapIn)?;
+        let (token0, token1, factory) = match (pair.token0(), pair.token1(), pair.factory()) {
+            (Ok(token0), Ok(token1), Ok(factory)) => (token0, token1, factory),
+            _ => return Err(QuoteEngineError::InvalidZapIn),
+        };
+        let pair_token_addr = if token0 == token_addr {
+            token1
+        } else if token1 == token_addr {
+            token0
+        } else {
+            return Err(QuoteEngineError::InvalidZapIn);
+        };
+        let dex_router_addr = get_router_for_factory(chain_id, &factory, router_allowlist)
+            .ok_or(QuoteEngineError::InvalidZapIn)?;
+        Ok(ZapInTarget {
+            dex_router_addr,
+            token,
+            pair_token: erc20_token_id(chain_id, pair_token_addr),
+            lp_token: erc20_token_id(chain_id, pair_addr),
+        })
+    }
+
+    // The pair's factory must be a registry DEX's or an allowlisted router's, like a zap-in's.
+    // token_a is the pair's token0, and the amounts are what lp_amount redeems for right now
+    pub fn get_lp_source(
+        &self,
+        chain_id: UniversalChainId,
+        pair_addr: EthAddress,
+        lp_amount: Amount,
+        router_allowlist: &[AllowedRouter],
+    ) -> Result<LpSource> {
+        let chain_info =
+            get_chain_info_from_chain_id(&chain_id).ok_or(QuoteEngineError::UnsupportedNetwork)?;
+        let pair = DEXPairContract::new(&get_rpc_url(&self.rpc_endpoints, chain_info), pair_addr)
+            .map_err(|_| QuoteEngineError::InvalidLpSource)?;
+        let (token0, token1, factory) = match (pair.token0(), pair.token1(), pair.factory()) {
+            (Ok(token0), Ok(token1), Ok(factory)) => (token0, token1, factory),
+            _ => return Err(QuoteEngineError::InvalidLpSource),
+        };
+        let dex_router_addr = get_router_for_factory(chain_id, &factory, router_allowlist)
+            .ok_or(QuoteEngineError::InvalidLpSource)?;
+        let (reserve0, reserve1) = pair
+            .get_reserves()
+            .map_err(|_| QuoteEngineError::RpcRequestFailed)?;
+        let total_supply = pair
+            .total_supply()
+            .map_err(|_| QuoteEngineError::RpcRequestFailed)?;
+        let (token_a_amount, token_b_amount) =
+            zap_out::get_remove_liquidity_amounts(lp_amount, reserve0, reserve1, total_supply)
+                .ok_or(QuoteEngineError::InvalidLpSource)?;
+        // Each token gets paths of its own, which can't route nothing
+        if token_a_amount == 0 || token_b_amount == 0 {
+            return Err(QuoteEngineError::InvalidLpSource);
+        }
+        Ok(LpSource {
+            dex_router_addr,
+            lp_token: erc20_token_id(chain_id, pair_addr),
+            token_a: erc20_token_id(chain_id, token0),
+            token_b: erc20_token_id(chain_id, token1),
+            token_a_amount,
+            token_b_amount,
+        })
+    }
+
+    // Plans a zap-out: the user deposits lp_amount_str LP tokens of the pair at pair_addr on
+    // src_network_name, and the escrow redeems them for the pair's two tokens. Each is then
+    // routed to dest_token on paths of its own, from what the LP tokens redeem for at the
+    // current reserves. Neither of the pair's tokens can be the dest token, since that half
+    // would have nothing to route. There are no tranches, so there is no risk limit either
+    pub fn compute_lp_source_execution_plan(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_eth_addr: &str,
+        dest_eth_addr: &str,
+        pair_addr: EthAddress,
+        dest_token: &str,
+        lp_amount_str: &str,
+        router_allowlist: &[AllowedRouter],
+    ) -> Result<ExecutionPlan> {
+        let lp_amount = parse_amount_in(lp_amount_str)?;
+        let source = self.get_lp_source(
+            chain_name_to_id(src_network_name)?,
+            pair_addr,
+            lp_amount,
+            router_allowlist,
+        )?;
+        let dest_token_id = self.parse_token_id(dest_network_name, dest_token)?;
+        if source.token_a == dest_token_id || source.token_b == dest_token_id {
+            return Err(QuoteEngineError::InvalidLpSource);
+        }
+        let src_addr = hex_str_to_eth_addr(src_eth_addr)?;
+        let dest_addr = hex_str_to_eth_addr(dest_eth_addr)?;
+        // Both halves are priced against the same graph
+        let context = self.build_context()?;
+        let quote_half = |src_token_id: &UniversalTokenId, amount_in: Amount| -> Result<Quote> {
+            let mut half_quote = quote(
+                &context,
+                &QuoteRequest {
+                    src_token_id: src_token_id.clone(),
+                    dest_token_id: dest_token_id.clone(),
+                    amount_in,
+                    src_addr,
+                    dest_addr,
+                },
+            )?;
+            if let Some(markup_bps) = self.markup_bps {
+                half_quote.fee_breakdown = half_quote.graph_solution.get_fee_breakdown_with_markup(
+                    context.graph(),
+                    PROTOCOL_FEE_BPS,
+                    markup_bps,
+                );
+            }
+            Ok(half_quote)
+        };
+        let quote_a = quote_half(&source.token_a, source.token_a_amount)?;
+        let quote_b = quote_half(&source.token_b, source.token_b_amount)?;
+
+        // The quoted terms cover both halves, with the LP tokens as the amount in
+        let mut graph_solution = quote_a.graph_solution.clone();
+        graph_solution
+            .paths
+            .extend(quote_b.graph_solution.paths.iter().cloned());
+        let route_hash = get_route_hash(&graph_solution);
+        let plan_quote = PlanQuote {
+            amount_out: quote_a.amount_out + quote_b.amount_out,
+            amount_in_usd: quote_a.amount_in_usd + quote_b.amount_in_usd,
+            amount_out_usd: quote_a.amount_out_usd + quote_b.amount_out_usd,
+        };
+        let fee_breakdown = merge_fee_breakdowns(&quote_a.fee_breakdown, &quote_b.fee_breakdown);
+
+        let mut exec_plan = plan(&context, quote_a, None)?;
+        let token_b_plan = plan(&context, quote_b, None)?;
+        set_lp_source(&mut exec_plan, token_b_plan, &source, lp_amount)?;
+        exec_plan.quoted_terms = Some(QuotedTerms {
+            quoted_at_millis: context.now_millis,
+            amount_in: lp_amount,
+            amount_out: plan_quote.amount_out,
+            amount_in_usd: plan_quote.amount_in_usd,
+            amount_out_usd: plan_quote.amount_out_usd,
+            fee_breakdown,
+            deposit_tolerance_bps: DEPOSIT_TOLERANCE_BPS,
+            expiry_millis: exec_plan.deadline_millis,
+            route_hash,
+        });
+        exec_plan.quote = Some(plan_quote);
+        Ok(exec_plan)
+    }
+
+    // Same as quote, but for compute_lp_source_execution_plan. amount_in_usd is what the LP
+    // tokens redeem for
+    pub fn quote_from_lp(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        pair_eth_addr: &str,
+        dest_token: &str,
+        lp_amount_str: &str,
+        deliver_wrapped: bool,
+        router_allowlist: &[AllowedRouter],
+    ) -> Result<QuoteResponse> {
+        let mut exec_plan = self.compute_lp_source_execution_plan(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            hex_str_to_eth_addr(pair_eth_addr)?,
+            dest_token,
+            lp_amount_str,
+            router_allowlist,
+        )?;
+        if deliver_wrapped {
+            set_wrapped_delivery(&mut exec_plan)?;
+        }
+        let quoted_terms = exec_plan
+            .quoted_terms
+            .ok_or(QuoteEngineError::FailedToCreateExecutionPlan)?;
+        Ok(QuoteResponse {
+            amount_out: quoted_terms.amount_out,
+            amount_in_usd: quoted_terms.amount_in_usd,
+            amount_out_usd: quoted_terms.amount_out_usd,
+            deadline_millis: exec_plan.deadline_millis,
+            fee_breakdown: quoted_terms.fee_breakdown,
+            route_hash: quoted_terms.route_hash,
+            quote_source: QuoteSource::Subgraph,
+            reference_amounts: None,
+        })
+    }
+
+    // Same as quote with dest_token = BEST_STABLE_SELECTOR, but also returns the stablecoin that
+    // was picked. Stablecoins are never native, so there is nothing to deliver wrapped
+    pub fn quote_best_stable(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        amount_in_str: &str,
+    ) -> Result<(
+        UniversalTokenId, /* chosen stablecoin */
+        Amount,           /* quote in the chosen stablecoin */
+        Amount,           /* src token USD */
+        Amount,           /* dest token USD */
+        MillisSinceEpoch, /* worst-case completion deadline */
+        FeeBreakdown,
+    )> {
+        let (request, context, quote) = self.compute_quote_with_selector(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            src_token,
+            BEST_STABLE_SELECTOR,
+            amount_in_str,
+        )?;
+        let deadline_millis = context.get_deadline_millis(quote.graph_solution, false)?;
+        Ok((
+            request.dest_token_id,
+            quote.amount_out,
+            quote.amount_in_usd,
+            quote.amount_out_usd,
+            deadline_millis,
+            quote.fee_breakdown,
+        ))
+    }
+
+    // Quotes the amount of src_token that delivers at least amount_out of dest_token, net of
+    // fees. Always uses the full graph, since the search runs many quotes on the same context
+    pub fn quote_exact_out(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_out: Amount,
+    ) -> Result<(
+        Amount,           /* amount in of src token */
+        Amount,           /* quote in dest token (at least amount_out) */
+        Amount,           /* src token USD */
+        Amount,           /* dest token USD */
+        MillisSinceEpoch, /* worst-case completion deadline */
+        FeeBreakdown,
+    )> {
+        if amount_out == 0 {
+            return Err(QuoteEngineError::InvalidNumber);
+        }
+        let request = QuoteRequest {
+            src_token_id: network_and_token_str_to_id_with_token_list(
+                src_network_name,
+                src_token,
+                &self.token_list,
+            )?,
+            dest_token_id: network_and_token_str_to_id_with_token_list(
+                dest_network_name,
+                dest_token,
+                &self.token_list,
+            )?,
+            amount_in: 0,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        };
+        let context = self.build_context()?;
+        let (request, quote) = quote_exact_out(&context, &request, amount_out)?;
+        let deadline_millis = context.get_deadline_millis(quote.graph_solution, false)?;
+        Ok((
+            request.amount_in,
+            quote.amount_out,
+            quote.amount_in_usd,
+            quote.amount_out_usd,
+            deadline_millis,
+            quote.fee_breakdown,
+        ))
+    }
+
+    // Always uses the full graph: the direct pair graph would leave out every DEX's multi-hop
+    // routes
+    pub fn get_venue_quotes(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+    ) -> Result<(
+        Amount, /* aggregated quote in dest token */
+        Vec<VenueQuote>,
+    )> {
+        let request = self.parse_quote_request(
+            src_network_name,
+            dest_network_name,
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            "0000000000000000000000000000000000000000", // dummy value, gets discarded for the quote
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        let context = self.build_context()?;
+        let aggregated_quote = quote(&context, &request)?;
+        let venue_quotes = venue_quotes(&context, &request, &aggregated_quote)?;
+        Ok((aggregated_quote.amount_out, venue_quotes))
+    }
+
+    // Always uses the full graph, since the largest input may well need a multi-hop route
+    pub fn get_max_input(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        max_price_impact_bps: u32,
+    ) -> Result<Amount> {
+        let request = QuoteRequest {
+            src_token_id: network_and_token_str_to_id_with_token_list(
+                src_network_name,
+                src_token,
+                &self.token_list,
+            )?,
+            dest_token_id: network_and_token_str_to_id_with_token_list(
+                dest_network_name,
+                dest_token,
+                &self.token_list,
+            )?,
+            amount_in: 0,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        };
+        let context = self.build_context()?;
+        get_max_input(&context, &request, max_price_impact_bps)
+    }
+
+    // Half of amount is swapped through the pair itself, so the router's getAmountsOut and the
+    // pair's reserves are all it takes
+    fn estimate_zap_in_liquidity(&self, target: &ZapInTarget, amount: Amount) -> Result<Amount> {
+        let chain_info = get_chain_info_from_chain_id(&target.token.chain)
+            .ok_or(QuoteEngineError::UnsupportedNetwork)?;
+        let rpc_url = get_rpc_url(&self.rpc_endpoints, chain_info);
+        let (token_addr, pair_token_addr, lp_token_addr) = match (
+            get_dex_token_address(&target.token),
+            get_dex_token_address(&target.pair_token),
+            get_dex_token_address(&target.lp_token),
+        ) {
+            (Some(token_addr), Some(pair_token_addr), Some(lp_token_addr)) => {
+                (token_addr, pair_token_addr, lp_token_addr)
+            }
+            _ => return Err(QuoteEngineError::InvalidZapIn),
+        };
+        let pair = DEXPairContract::new(&rpc_url, lp_token_addr)
+            .map_err(|_| QuoteEngineError::InvalidZapIn)?;
+        let (reserve_in, reserve_out) = pair
+            .get_reserves_for(token_addr)
+            .map_err(|_| QuoteEngineError::RpcRequestFailed)?;
+        let total_supply = pair
+            .total_supply()
+            .map_err(|_| QuoteEngineError::RpcRequestFailed)?;
+        let swap_amount_out = DEXRouterContract::new(&rpc_url, target.dex_router_addr)
+            .map_err(|_| QuoteEngineError::InvalidZapIn)?
+            .get_amounts_out(amount / 2, vec![token_addr, pair_token_addr])
+            .map_err(|_| QuoteEngineError::RpcRequestFailed)?
+            .last()
+            .copied()
+            .ok_or(QuoteEngineError::RpcRequestFailed)?;
+        // An empty pair has no price to zap in at
+        zap_in::estimate_zap_in_liquidity(
+            amount,
+            swap_amount_out,
+            reserve_in,
+            reserve_out,
+            total_supply,
+        )
+        .ok_or(QuoteEngineError::InvalidZapIn)
+    }
+
+    // Tries the direct pool first and only builds the full graph if that fails (a transfer has
+    // no pool to try). Returns the context the quote was priced against, since plan needs its
+    // timestamp
+    fn compute_quote(&self, request: &QuoteRequest) -> Result<(QuoteContext, Quote)> {
+        if self.transfer_only {
+            let context = self.build_context()?;
+            let transfer_quote = quote_transfer(&context, request)?;
+            return Ok((context, transfer_quote));
+        }
+        if self.pinned_snapshot.is_some() {
+            let context = self.build_context()?;
+            let full_quote = quote(&context, request)?;
+            return Ok((context, full_quote));
+        }
+        let (mut context, dex_quote) = match self.compute_direct_pair_quote(request) {
+            Some(direct_quote) => direct_quote,
+            None => {
+                let context = self.build_context()?;
+                let full_quote = quote(&context, request)?;
+                (context, full_quote)
+            }
+        };
+        // The aggregator edges are routed on alongside the pools, so a split between them and
+        // the DEXes is possible too. We only switch if that beats the DEX-only quote
+        if self.add_aggregator_edges(&mut context, request) > 0 {
+            if let Ok(aggregated_quote) = quote(&context, request) {
+                if aggregated_quote.amount_out > dex_quote.amount_out {
+                    return Ok((context, aggregated_quote));
+                }
+            }
+        }
+        Ok((context, dex_quote))
+    }
+
+    // Aggregators quote a single amount in, so their edges are fetched for each request rather
+    // than being part of the graph. Only same-chain swaps into an ERC20 get one, since the swap's
+    // payout is read off the dest token's Transfer logs. An aggregator whose request fails is
+    // just left out. Returns the number of edges added
+    fn add_aggregator_edges(&self, context: &mut QuoteContext, request: &QuoteRequest) -> usize {
+        let (src_token, dest_token) = (&request.src_token_id, &request.dest_token_id);
+        if src_token.chain != dest_token.chain || dest_token.id == ChainTokenId::Native {
+            return 0;
+        }
+        let aggregators = get_aggregators_from_chain_id(&src_token.chain);
+        let chain_info = match get_chain_info_from_chain_id(&src_token.chain) {
+            Some(chain_info) if !aggregators.is_empty() => chain_info,
+            _ => return 0,
+        };
+        // An ERC20 src token is approved in a txn of its own
+        let gas_fee_native = if src_token.id == ChainTokenId::Native {
+            chain_info.avg_gas_fee_in_native_token
+        } else {
+            2 * chain_info.avg_gas_fee_in_native_token
+        };
+        let gas_fee_usd = match get_native_amount_usd(context, chain_info, gas_fee_native) {
+            Some(gas_fee_usd) => gas_fee_usd,
+            None => return 0,
+        };
+        let gas_price =
+            match eth_utils::common::get_gas_price(&get_rpc_url(&self.rpc_endpoints, chain_info)) {
+                Ok(gas_price) => gas_price,
+                Err(_) => return 0,
+            };
+
+        let mut num_added = 0;
+        for aggregator in aggregators {
+            let quoted_amount_out = match AggregatorApi::new(aggregator).get_quote(
+                src_token,
+                dest_token,
+                request.amount_in,
+                gas_price,
+            ) {
+                Ok(amount_out) if amount_out > 0 => amount_out,
+                res => {
+                    debug_println!("No {} quote: {:?}", aggregator.id, res);
+                    continue;
+                }
+            };
+            let quoted_amount_out_usd = match context.get_amount_usd(dest_token, quoted_amount_out)
+            {
+                Ok(amount_usd) if amount_usd > 0 => amount_usd,
+                _ => continue,
+            };
+            let edge = AggregatorSwapEdge {
+                src_token: src_token.clone(),
+                dest_token: dest_token.clone(),
+                quoted_amount_in: request.amount_in,
+                quoted_amount_out,
+                estimated_gas_fee_in_dest_token: mul_ratio_u128(
+                    quoted_amount_out,
+                    gas_fee_usd,
+                    quoted_amount_out_usd,
+                ),
+                estimated_gas_fee_usd: gas_fee_usd,
+                aggregator,
+            };
+            if context
+                .graph
+                .add_edge(Edge::Swap(SwapEdge::Aggregator(edge)))
+                .is_ok()
+            {
+                num_added += 1;
+            }
+        }
+        num_added
+    }
+
+    // Like compute_quote, but dest_token may also be BEST_STABLE_SELECTOR. Every stablecoin on
+    // the dest chain is then quoted on the full graph (one direct pair graph per stablecoin
+    // would cost more requests than it saves). Returns the request with the dest token it
+    // was quoted into
+    fn compute_quote_with_selector(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_eth_addr: &str,
+        dest_eth_addr: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+    ) -> Result<(QuoteRequest, QuoteContext, Quote)> {
+        if !dest_token.trim().eq_ignore_ascii_case(BEST_STABLE_SELECTOR) {
+            let request = self.parse_quote_request(
+                src_network_name,
+                dest_network_name,
+                src_eth_addr,
+                dest_eth_addr,
+                src_token,
+                dest_token,
+                amount_in_str,
+            )?;
+            let (context, quote) = self.compute_quote(&request)?;
+            return Ok((request, context, quote));
+        }
+        let stablecoins: Vec<UniversalTokenId> =
+            stablecoin_registry::get_stablecoins(chain_name_to_id(dest_network_name)?).collect();
+        let request = QuoteRequest {
+            src_token_id: network_and_token_str_to_id_with_token_list(
+                src_network_name,
+                src_token,
+                &self.token_list,
+            )?,
+            dest_token_id: stablecoins
+                .first()
+                .cloned()
+                .ok_or(QuoteEngineError::NoStablecoinOnNetwork)?,
+            amount_in: parse_amount_in(amount_in_str)?,
+            src_addr: hex_str_to_eth_addr(src_eth_addr)?,
+            dest_addr: hex_str_to_eth_addr(dest_eth_addr)?,
+        };
+        let context = self.build_context()?;
+        let (request, quote) = quote_best_dest_token(&context, &request, &stablecoins)?;
+        Ok((request, context, quote))
+    }
+
+    // Degraded quote for when the DEX subgraphs are down. The candidate routes come from the
+    // cached pair list (pair_cache), and each one is re-quoted with its DEX router's
+    // getAmountsOut since the cached reserves are stale. Routers don't bridge, so only
+    // same-chain swaps can be quoted this way
+    pub fn quote_from_pair_cache(
+        &self,
+        pair_cache: &GraphSnapshot,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+        deliver_wrapped: bool,
+    ) -> Result<QuoteResponse> {
+        let (amount_in, src_token_id, dest_token_id) = self.parse_quote_args(
+            src_network_name,
+            dest_network_name,
+            src_token,
+            dest_token,
+            amount_in_str,
+        )?;
+        let chain_info = get_chain_info_from_chain_id(&src_token_id.chain)
+            .ok_or(QuoteEngineError::UnsupportedNetwork)?;
+        let rpc_url = get_rpc_url(&self.rpc_endpoints, chain_info);
+        let context = self.create_context(
+            pair_cache
+                .to_graph()
+                .map_err(|_| QuoteEngineError::FailedToCreateGraph)?,
+        );
+        // Checked before the routers are asked for their amounts out
+        let src_usd_amount = context.get_amount_usd(&src_token_id, amount_in)?;
+        validate_amount_in_usd(src_usd_amount)?;
+        let (quote, path) =
+            find_router_candidate_paths(context.graph(), &src_token_id, &dest_token_id, amount_in)
+                .map_err(|_| QuoteEngineError::NoPathFound)?
+                .into_iter()
+                .filter_map(|path| {
+                    let amount_out = get_router_amount_out(&rpc_url, &path, amount_in)?;
+                    Some((
+                        amount_out.saturating_sub(path.get_estimated_txn_fees_in_dest_token()),
+                        path,
+                    ))
+                })
+                .max_by_key(|(quote, _)| *quote)
+                .ok_or(QuoteEngineError::NoPathFound)?;
+        validate_quote_covers_fees(quote)?;
+        let dest_usd_amount = context.get_amount_usd(&dest_token_id, quote)?;
+        let graph_solution = GraphSolution {
+            paths: vec![SplitGraphPath {
+                path,
+                fraction_amount_in: amount_in,
+                fraction_bps: 10_000,
+            }],
+            amount_in,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        };
+        // The LP and gas fees are the cached estimates
+        let fee_breakdown = graph_solution.get_fee_breakdown(context.graph(), PROTOCOL_FEE_BPS);
+        let route_hash = get_route_hash(&graph_solution);
+        let deadline_millis = context.get_deadline_millis(graph_solution, deliver_wrapped)?;
+        Ok(QuoteResponse {
+            amount_out: quote,
+            amount_in_usd: src_usd_amount,
+            amount_out_usd: dest_usd_amount,
+            deadline_millis,
+            fee_breakdown,
+            route_hash,
+            quote_source: QuoteSource::OnChainFallback,
+            reference_amounts: None,
+        })
+    }
+
+    // Checked on the pair cache with the same blacklisted segments and blocked pools as a quote,
+    // but without pricing anything. A true can still end in NoPathFound or
+    // AmountInDoesNotCoverFees
+    pub fn supports_route(
+        &self,
+        pair_cache: &GraphSnapshot,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+    ) -> Result<bool> {
+        let src_token_id = network_and_token_str_to_id_with_token_list(
+            src_network_name,
+            src_token,
+            &self.token_list,
+        )?;
+        let dest_token_id = network_and_token_str_to_id_with_token_list(
+            dest_network_name,
+            dest_token,
+            &self.token_list,
+        )?;
+        let context = self.create_context(
+            pair_cache
+                .to_graph()
+                .map_err(|_| QuoteEngineError::FailedToCreateGraph)?,
+        );
+        Ok(route_exists(
+            context.graph(),
+            &src_token_id,
+            &dest_token_id,
+            &context.routing_config,
+        ))
+    }
+
+    fn parse_quote_args(
+        &self,
+        src_network_name: &str,
+        dest_network_name: &str,
+        src_token: &str,
+        dest_token: &str,
+        amount_in_str: &str,
+    ) -> Result<(Amount, UniversalTokenId, UniversalTokenId)> {
+        let amount_in = parse_amount_in(amount_in_str)?;
+        let src_token_id = network_and_token_str_to_id_with_token_list(
+            src_network_name,
+            src_token,
+            &self.token_list,
+        )?;
+        let dest_token_id = network_and_token_str_to_id_with_token_list(
+            dest_network_name,
+            dest_token,
+            &self.token_list,
+        )?;
+        Ok((amount_in, src_token_id, dest_token_id))
+    }
+
+    // Fast path for same-chain pairs with a direct pool: we only fetch those pools instead of
+    // every DEX's pairs. Returns None if there is no direct pool or its quote is poor, in which
+    // case the caller falls back to the full graph
+    fn compute_direct_pair_quote(&self, request: &QuoteRequest) -> Option<(QuoteContext, Quote)> {
+        let graph =
+            graph_builder::create_direct_pair_graph(&request.src_token_id, &request.dest_token_id)
+                .ok()?;
+        let context = self.create_context(graph);
+        let direct_quote = quote(&context, request).ok()?;
+        let min_dest_usd_amount = direct_quote
+            .amount_in_usd
+            .saturating_mul(10_000 - MAX_DIRECT_QUOTE_VALUE_LOSS_BPS)
+            / 10_000;
+        if direct_quote.amount_out_usd < min_dest_usd_amount {
+            debug_println!(
+                "Direct pool quote is poor ({} USD for {} USD), using the full graph",
+                direct_quote.amount_out_usd,
+                direct_quote.amount_in_usd
+            );
+            return None;
+        }
+        Some((context, direct_quote))
+    }
+}
+
+// Amount out of the path's pools (the candidate paths are on a single DEX). A Wrap or Unwrap
+// around them is 1:1, so it doesn't change the amount
+fn get_router_amount_out(rpc_url: &str, path: &GraphPath, amount_in: Amount) -> Option<Amount> {
+    let mut dex_router_addr = None;
+    let mut token_path: Vec<EthAddress> = Vec::new();
+    for edge in path.0.iter() {
+        if let Edge::Swap(SwapEdge::CPMM(cpmm_edge)) = edge {
+            if token_path.is_empty() {
+                token_path.push(get_dex_token_address(&cpmm_edge.src_token)?);
+            }
+            token_path.push(get_dex_token_address(&cpmm_edge.dest_token)?);
+            dex_router_addr = Some(cpmm_edge.dex.eth_dex_router);
+        }
+    }
+    let amounts_out = DEXRouterContract::new(rpc_url, dex_router_addr?)
+        .ok()?
+        .get_amounts_out(amount_in, token_path)
+        .map_err(|e| debug_println!("getAmountsOut failed: {:?}", e))
+        .ok()?;
+    amounts_out.last().copied()
+}
+
+// A direct pair graph may only have the wrapped native token, which is priced the same
+fn get_native_amount_usd(
+    context: &QuoteContext,
+    chain_info: &ChainInfo,
+    amount: Amount,
+) -> Option<Amount> {
+    let native_token = UniversalTokenId {
+        chain: chain_info.chain_id,
+        id: ChainTokenId::Native,
+    };
+    context
+        .get_amount_usd(&native_token, amount)
+        .ok()
+        .or_else(|| {
+            let wrapped_token = UniversalTokenId {
+                chain: chain_info.chain_id,
+                id: ChainTokenId::ERC20(ERC20Token {
+                    addr: chain_info.weth_addr?,
+                }),
+            };
+            context.get_amount_usd(&wrapped_token, amount).ok()
+        })
+}
+
+fn get_dex_token_address(token_id: &UniversalTokenId) -> Option<EthAddress> {
+    match &token_id.id {
+        ChainTokenId::Native => None,
+        ChainTokenId::ERC20(erc20) => Some(erc20.addr),
+        ChainTokenId::XC20(xc20) => Some(xc20.get_eth_address()),
+    }
+}
+
+fn erc20_token_id(chain_id: UniversalChainId, addr: EthAddress) -> UniversalTokenId {
+    UniversalTokenId {
+        chain: chain_id,
+        id: ChainTokenId::ERC20(ERC20Token { addr }),
+    }
+}
+
+fn get_amount_usd(graph: &Graph, token_id: &UniversalTokenId, amount: Amount) -> Result<Amount> {
+    Ok(graph
+        .get_token(token_id)
+        .ok_or(QuoteEngineError::NoPathFound)?
+        .derived_usd
+        .add_exp(6)
+        .mul_u128(amount))
+}
+
+// 0 if the venue is at least as good, e.g. when the aggregated route is that venue's route
+fn get_savings_bps(aggregated_amount_out: Amount, venue_amount_out: Amount) -> u32 {
+    let savings = aggregated_amount_out.saturating_sub(venue_amount_out);
+    // mul_ratio_u128 truncates a result that doesn't fit in u128
+    if savings / venue_amount_out >= (u32::MAX / 10_000) as Amount {
+        return u32::MAX;
+    }
+    mul_ratio_u128(savings, 10_000, venue_amount_out).min(u32::MAX as Amount) as u32
+}
+
+// For a quote whose paths were quoted separately, e.g. the two halves of a zap-out
+fn merge_fee_breakdowns(a: &FeeBreakdown, b: &FeeBreakdown) -> FeeBreakdown {
+    FeeBreakdown {
+        hops_per_path: a
+            .hops_per_path
+            .iter()
+            .chain(b.hops_per_path.iter())
+            .cloned()
+            .collect(),
+        protocol_fee_in_dest_token: a.protocol_fee_in_dest_token + b.protocol_fee_in_dest_token,
+        protocol_fee_usd: a.protocol_fee_usd + b.protocol_fee_usd,
+        integrator_fee_in_dest_token: a.integrator_fee_in_dest_token
+            + b.integrator_fee_in_dest_token,
+        integrator_fee_usd: a.integrator_fee_usd + b.integrator_fee_usd,
+        total_fee_usd: a.total_fee_usd + b.total_fee_usd,
+    }
+}
+
+fn parse_amount_in(amount_in_str: &str) -> Result<Amount> {
+    let amount_in: Amount = amount_in_str
+        .parse()
+        .map_err(|_| QuoteEngineError::InvalidNumber)?;
+    if amount_in == 0 {
+        return Err(QuoteEngineError::AmountInBelowMinimumUsd);
+    }
+    Ok(amount_in)
+}
+
+pub fn chain_name_to_id(chain_name: &str) -> Result<UniversalChainId> {
+    match chain_name.to_lowercase().as_str() {
+        "astar" => Ok(universal_chain_id_registry::ASTAR),
+        "moonbeam" => Ok(universal_chain_id_registry::MOONBEAM),
+        "polkadot" => Ok(universal_chain_id_registry::POLKADOT),
+        #[cfg(feature = "testnet")]
+        "moonbase-alpha" => Ok(universal_chain_id_registry::MOONBASE_ALPHA),
+        _ => Err(QuoteEngineError::UnsupportedNetwork),
+    }
+}
+
+// Accepts everything token_str_to_id does, plus a token symbol either on its own
+// (looked up on network_name) or as "<symbol>@<network>" e.g. "USDC@moonbeam", in
+// which case the network must match network_name
+pub fn network_and_token_str_to_id(
+    network_name: &str,
+    token_str: &str,
+) -> Result<UniversalTokenId> {
+    network_and_token_str_to_id_with_token_list(network_name, token_str, &TokenListOverlay::new())
+}
+
+// Same as network_and_token_str_to_id, but symbols can also come from an imported token list.
+// The hand-maintained symbols take priority
+pub fn network_and_token_str_to_id_with_token_list(
+    network_name: &str,
+    token_str: &str,
+    token_list: &TokenListOverlay,
+) -> Result<UniversalTokenId> {
+    let chain = chain_name_to_id(network_name)?;
+    let id = if let Some((symbol, symbol_network_name)) = token_str.trim().split_once('@') {
+        if chain_name_to_id(symbol_network_name.trim())? != chain {
+            return Err(QuoteEngineError::InvalidTokenString);
+        }
+        symbol_to_token_id(chain, symbol.trim(), token_list)?
+    } else {
+        token_str_to_id(token_str)
+            .or_else(|_| symbol_to_token_id(chain, token_str.trim(), token_list))?
+    };
+    Ok(UniversalTokenId { chain, id })
+}
+
+fn symbol_to_token_id(
+    chain: UniversalChainId,
+    symbol: &str,
+    token_list: &TokenListOverlay,
+) -> Result<ChainTokenId> {
+    token_symbol_registry::symbol_to_token(chain, symbol)
+        .or_else(|| token_list.symbol_to_token(chain, symbol))
+        .map(|token| token.id)
+        .ok_or(QuoteEngineError::UnknownTokenSymbol)
+}
+
+// Case-insensitive "native", "xc20,id=<asset id>", "xc20,addr=<address>" or
+// "erc20,addr=<address>", where the address may or may not have a 0x prefix
+pub fn token_str_to_id(token_str: &str) -> Result<ChainTokenId> {
+    let lowercase_token_str = token_str.trim().to_lowercase();
+    let token_str = lowercase_token_str.as_str();
+    if "native" == token_str {
+        Ok(ChainTokenId::Native)
+    } else if let Some(asset_id_str) = token_str.strip_prefix("xc20,id=") {
+        let asset_id: AssetId = asset_id_str
+            .parse()
+            .map_err(|_| QuoteEngineError::InvalidTokenString)?;
+        Ok(ChainTokenId::XC20(XC20Token::from_asset_id(asset_id)))
+    } else if let Some(addr_str) = token_str.strip_prefix("xc20,addr=") {
+        let eth_addr = hex_str_to_eth_addr(addr_str)?;
+        Ok(ChainTokenId::XC20(XC20Token::from_eth_address(eth_addr)))
+    } else if let Some(addr_str) = token_str.strip_prefix("erc20,addr=") {
+        let eth_addr = hex_str_to_eth_addr(addr_str)?;
+        Ok(ChainTokenId::ERC20(ERC20Token { addr: eth_addr }))
+    } else {
+        Err(QuoteEngineError::InvalidTokenString)
+    }
+}
+
+// Tolerates a 0x prefix even though we expect HexStrNo0x
+pub fn hex_str_to_eth_addr(hex_str: &str) -> Result<EthAddress> {
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    let raw_addr: [u8; 20] = hex_string_to_vec(&("0x".to_string() + hex_str))
+        .map_err(|_| QuoteEngineError::InvalidHexAddrString)?
+        .try_into()
+        .map_err(|_| QuoteEngineError::InvalidHexAddrString)?;
+    Ok(EthAddress { 0: raw_addr })
+}
+
+// Rejects dust and implausibly large swaps before they are routed
+pub fn validate_amount_in_usd(src_usd_amount: Amount) -> Result<()> {
+    if src_usd_amount < MIN_AMOUNT_IN_USD {
+        Err(QuoteEngineError::AmountInBelowMinimumUsd)
+    } else if src_usd_amount > MAX_AMOUNT_IN_USD {
+        Err(QuoteEngineError::AmountInAboveMaximumUsd)
+    } else {
+        Ok(())
+    }
+}
+
+// The estimated gas and bridge fees can only be known once the swap is routed
+pub fn validate_quote_covers_fees(quote_with_fees: Amount) -> Result<()> {
+    if quote_with_fees == 0 {
+        Err(QuoteEngineError::AmountInDoesNotCoverFees)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod quote_engine_tests {
+    use hex_literal::hex;
+    use privadex_chain_metadata::{
+        common::{ChainTokenId, ERC20Token},
+        registry::token::universal_token_id_registry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_token_str_with_and_without_0x() {
+        let expected = ChainTokenId::ERC20(ERC20Token {
+            addr: EthAddress {
+                0: hex!("931715FEE2d06333043d11F658C8CE934aC61D0c"),
+            },
+        });
+        for token_str in [
+            "erc20,addr=0x931715FEE2d06333043d11F658C8CE934aC61D0c",
+            "ERC20,addr=931715FEE2d06333043d11F658C8CE934aC61D0c",
+            " erc20,addr=0X931715fee2d06333043d11f658c8ce934ac61d0c ",
+        ] {
+            assert_eq!(token_str_to_id(token_str), Ok(expected.clone()));
+        }
+    }
+
+    #[test]
+    fn test_short_and_malformed_token_strs() {
+        for token_str in [
+            "",
+            "x",
+            "xc20",
+            "xc20,id=",
+            "xc20,id=-1",
+            "xc20,addr=",
+            "xc20,addr=0x",
+            "erc20,addr=0x1234",
+            "erc20,addr=0xzz1715FEE2d06333043d11F658C8CE934aC61D0c",
+            "ércé",
+        ] {
+            assert!(token_str_to_id(token_str).is_err());
+        }
+        assert_eq!(
+            token_str_to_id("xc20"),
+            Err(QuoteEngineError::InvalidTokenString)
+        );
+        assert_eq!(
+            token_str_to_id("erc20,addr=0x1234"),
+            Err(QuoteEngineError::InvalidHexAddrString)
+        );
+    }
+
+    #[test]
+    fn test_validate_integrator_markup() {
+        let recipient = EthAddress {
+            0: hex!("00000000000000000000000000000000000000cc"),
+        };
+        assert_eq!(validate_integrator_markup(30, &recipient), Ok(()));
+        assert_eq!(
+            validate_integrator_markup(MAX_INTEGRATOR_MARKUP_BPS, &recipient),
+            Ok(())
+        );
+        for (markup_bps, recipient) in [
+            (0, recipient),
+            (MAX_INTEGRATOR_MARKUP_BPS + 1, recipient),
+            (30, EthAddress::zero()),
+        ] {
+            assert_eq!(
+                validate_integrator_markup(markup_bps, &recipient),
+                Err(QuoteEngineError::InvalidIntegratorMarkup)
+            );
+        }
+    }
+
+    #[cfg(feature = "testnet")]
+    #[test]
+    fn test_testnet_chains() {
+        assert_eq!(
+            chain_name_to_id("moonbase-alpha"),
+            Ok(universal_chain_id_registry::MOONBASE_ALPHA)
+        );
+        assert_eq!(
+            chain_name_to_id("moonbase-beta"),
+            Err(QuoteEngineError::UnsupportedNetwork)
+        );
+        assert_eq!(
+            QUOTE_CHAIN_IDS,
+            [universal_chain_id_registry::MOONBASE_ALPHA]
+        );
+        assert!(matches!(
+            build_context(0),
+            Err(QuoteEngineError::TestnetQuotesUnsupported)
+        ));
+        assert!(matches!(
+            build_transfer_context(0),
+            Err(QuoteEngineError::TestnetQuotesUnsupported)
+        ));
+    }
+
+    #[test]
+    fn test_token_symbol_lookup() {
+        assert_eq!(
+            network_and_token_str_to_id("moonbeam", "USDC@moonbeam"),
+            Ok(universal_token_id_registry::USDC_WH_MOONBEAM)
+        );
+        assert_eq!(
+            network_and_token_str_to_id("moonbeam", "xcdot"),
+            Ok(universal_token_id_registry::DOT_MOONBEAM)
+        );
+        assert_eq!(
+            network_and_token_str_to_id("astar", "DOT"),
+            Ok(universal_token_id_registry::DOT_ASTAR)
+        );
+        assert_eq!(
+            network_and_token_str_to_id("astar", "native"),
+            Ok(universal_token_id_registry::ASTR_NATIVE)
+        );
+        // The symbol's network must match the requested network
+        assert_eq!(
+            network_and_token_str_to_id("astar", "USDT@moonbeam"),
+            Err(QuoteEngineError::InvalidTokenString)
+        );
+        assert_eq!(
+            network_and_token_str_to_id("astar", "FOO@astar"),
+            Err(QuoteEngineError::UnknownTokenSymbol)
+        );
+    }
+
+    #[test]
+    fn test_token_list_symbol_lookup() {
+        let mut token_list = TokenListOverlay::new();
+        token_list.replace(
+            "Test list".to_string(),
+            Default::default(),
+            vec![
+                ListedToken {
+                    token: universal_token_id_registry::GLMR_NATIVE,
+                    symbol: "FOO".to_string(),
+                    name: "Foo".to_string(),
+                    decimals: 18,
+                    logo_uri: None,
+                },
+                ListedToken {
+                    token: universal_token_id_registry::DOT_MOONBEAM,
+                    symbol: "USDC".to_string(),
+                    name: "Not USDC".to_string(),
+                    decimals: 10,
+                    logo_uri: None,
+                },
+            ],
+        );
+        assert_eq!(
+            network_and_token_str_to_id_with_token_list("moonbeam", "foo", &token_list),
+            Ok(universal_token_id_registry::GLMR_NATIVE)
+        );
+        // The hand-maintained symbols win over the token list
+        assert_eq!(
+            network_and_token_str_to_id_with_token_list("moonbeam", "USDC", &token_list),
+            Ok(universal_token_id_registry::USDC_WH_MOONBEAM)
+        );
+        assert_eq!(
+            network_and_token_str_to_id_with_token_list("astar", "FOO", &token_list),
+            Err(QuoteEngineError::UnknownTokenSymbol)
+        );
+    }
+
+    #[test]
+    fn test_resolve_token() {
+        let unregistered_token = UniversalTokenId {
+            chain: universal_chain_id_registry::MOONBEAM,
+            id: ChainTokenId::ERC20(ERC20Token {
+                addr: EthAddress {
+                    0: hex!("1111111111111111111111111111111111111111"),
+                },
+            }),
+        };
+        let mut token_list = TokenListOverlay::new();
+        token_list.replace(
+            "Test list".to_string(),
+            Default::default(),
+            vec![
+                ListedToken {
+                    token: unregistered_token.clone(),
+                    symbol: "FOO".to_string(),
+                    name: "Foo".to_string(),
+                    decimals: 8,
+                    logo_uri: Some("https://example.com/foo.png".to_string()),
+                },
+                ListedToken {
+                    token: universal_token_id_registry::USDC_WH_MOONBEAM,
+                    symbol: "USDCwh".to_string(),
+                    name: "USD Coin (Wormhole)".to_string(),
+                    decimals: 18,
+                    logo_uri: None,
+                },
+            ],
+        );
+        let quote_engine = QuoteEngine::new(0).with_token_list(token_list);
+
+        // Only on the token list, by symbol or by address
+        let foo = quote_engine.resolve_token("moonbeam", "foo").unwrap();
+        assert_eq!(foo.token, unregistered_token);
+        assert_eq!(foo.decimals, 8);
+        assert_eq!(
+            quote_engine.resolve_token("moonbeam", "0x1111111111111111111111111111111111111111"),
+            Ok(foo)
+        );
+
+        // The registry's symbol and decimals win, the list adds the name
+        let usdc = quote_engine.resolve_token("moonbeam", "USDC").unwrap();
+        assert_eq!(usdc.symbol, "USDC");
+        assert_eq!(usdc.decimals, 6);
+        assert_eq!(usdc.name, "USD Coin (Wormhole)");
+
+        // On neither
+        assert_eq!(
+            quote_engine.resolve_token("moonbeam", "0x2222222222222222222222222222222222222222"),
+            Err(QuoteEngineError::UnknownToken)
+        );
+    }
+
+    #[test]
+    fn test_validate_amount_in() {
+        assert_eq!(validate_amount_in_usd(MIN_AMOUNT_IN_USD), Ok(()));
+        assert_eq!(validate_amount_in_usd(MAX_AMOUNT_IN_USD), Ok(()));
+        assert_eq!(
+            validate_amount_in_usd(MIN_AMOUNT_IN_USD - 1),
+            Err(QuoteEngineError::AmountInBelowMinimumUsd)
+        );
+        assert_eq!(
+            validate_amount_in_usd(MAX_AMOUNT_IN_USD + 1),
+            Err(QuoteEngineError::AmountInAboveMaximumUsd)
+        );
+        assert_eq!(validate_quote_covers_fees(1), Ok(()));
+        assert_eq!(
+            validate_quote_covers_fees(0),
+            Err(QuoteEngineError::AmountInDoesNotCoverFees)
+        );
+    }
+
+    #[test]
+    fn test_savings_bps() {
+        assert_eq!(get_savings_bps(1_000, 1_000), 0);
+        assert_eq!(get_savings_bps(1_000, 1_100), 0);
+        assert_eq!(get_savings_bps(1_025, 1_000), 250);
+        assert_eq!(get_savings_bps(Amount::MAX, 1), u32::MAX);
+    }
+
+    #[test]
+    fn test_merge_fee_breakdowns() {
+        let fee_breakdown = |fee: Amount| FeeBreakdown {
+            hops_per_path: vec![Vec::new()],
+            protocol_fee_in_dest_token: fee,
+            protocol_fee_usd: 2 * fee,
+            integrator_fee_in_dest_token: 0,
+            integrator_fee_usd: 0,
+            total_fee_usd: 3 * fee,
+        };
+        let merged = merge_fee_breakdowns(&fee_breakdown(10), &fee_breakdown(5));
+        assert_eq!(merged.hops_per_path.len(), 2);
+        assert_eq!(merged.protocol_fee_in_dest_token, 15);
+        assert_eq!(merged.protocol_fee_usd, 30);
+        assert_eq!(merged.total_fee_usd, 45);
+    }
+
+    #[test]
+    fn test_exact_out_search() {
+        // 2 out per 1 in, less a fixed fee of 100. Below 10 in is too little to quote
+        let get_quote = |amount_in: Amount| -> Result<Option<(Amount, Amount)>> {
+            Ok((amount_in >= 10).then(|| ((2 * amount_in).saturating_sub(100), amount_in)))
+        };
+        // The smallest amount in that nets 1_000 is 550
+        for estimated_amount_in in [0, 1, 500, 550, 10_000] {
+            let (amount_in, quoted_amount_in) =
+                find_exact_out_amount_in(1_000, estimated_amount_in, get_quote).unwrap();
+            assert_eq!(amount_in, quoted_amount_in);
+            assert!(amount_in >= 550);
+            assert!(amount_in <= 550 + mul_ratio_u128(550, EXACT_OUT_TOLERANCE_BPS, 10_000) + 1);
+        }
+        assert_eq!(
+            find_exact_out_amount_in(1_000, 100, |_| Ok(Some((0, ())))),
+            Err(QuoteEngineError::AmountOutNotReachable)
+        );
+        assert_eq!(
+            find_exact_out_amount_in(1_000, 100, |_| Err::<Option<(Amount, ())>, _>(
+                QuoteEngineError::AmountInAboveMaximumUsd
+            )),
+            Err(QuoteEngineError::AmountInAboveMaximumUsd)
+        );
+    }
+
+    #[test]
+    fn test_max_input_search() {
+        // Within the bound up to 7_000 in
+        for (min_amount_in, max_amount_in) in [(1, 1_000_000), (100, 1_000_000), (6_999, 7_001)] {
+            let max_input = find_max_amount_in(min_amount_in, max_amount_in, |amount_in| {
+                Ok(amount_in <= 7_000)
+            })
+            .unwrap();
+            assert!(max_input <= 7_000);
+            assert!(
+                max_input >= 7_000 - mul_ratio_u128(7_000, MAX_INPUT_TOLERANCE_BPS, 10_000) - 1
+            );
+        }
+        // Capped at max_amount_in
+        assert_eq!(find_max_amount_in(100, 5_000, |_| Ok(true)), Ok(5_000));
+        assert_eq!(find_max_amount_in(100, 50, |_| Ok(true)), Ok(50));
+        // min_amount_in is within the bound even if nothing above it is
+        assert_eq!(find_max_amount_in(100, 5_000, |_| Ok(false)), Ok(100));
+        assert_eq!(
+            find_max_amount_in(100, 5_000, |_| Err(QuoteEngineError::NoPathFound)),
+            Err(QuoteEngineError::NoPathFound)
+        );
+    }
+
+    #[test]
+    fn test_pinned_snapshot() {
+        let snapshot = GraphSnapshot {
+            version: privadex_routing::graph_snapshot::GRAPH_SNAPSHOT_VERSION,
+            chain_ids: vec![universal_chain_id_registry::ASTAR],
+            dex_responses: Vec::new(),
+        };
+        let mut other_snapshot = snapshot.clone();
+        other_snapshot
+            .chain_ids
+            .push(universal_chain_id_registry::MOONBEAM);
+        assert_eq!(
+            get_snapshot_id(&snapshot),
+            get_snapshot_id(&snapshot.clone())
+        );
+        assert_ne!(get_snapshot_id(&snapshot), get_snapshot_id(&other_snapshot));
+
+        // Without DEX responses the graph only has ASTR's wrap and unwrap, and building it
+        // makes no requests
+        let context = QuoteEngine::new(0)
+            .with_pinned_snapshot(snapshot)
+            .build_context()
+            .unwrap();
+        assert_eq!(context.graph().edge_count(), 2);
+    }
+
+    // Poor man's fuzzing: random concatenations of fragments that look like token strings
+    #[test]
+    fn test_token_str_parser_never_panics() {
+        let fragments = [
+            "", "x", "c", "2", "0", ",", "=", "@", "id", "addr", "erc20", "xc20", "0x", "0X",
+            "native", "moonbeam", "usdc", "é", "ff", "123", " ",
+        ];
+        let mut rng_state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..20_000 {
+            let mut token_str = String::new();
+            // xorshift64
+            rng_state ^= rng_state << 13;
+            rng_state ^= rng_state >> 7;
+            rng_state ^= rng_state << 17;
+            let num_fragments = (rng_state % 8) as usize;
+            for i in 0..num_fragments {
+                let idx = ((rng_state >> (8 * i)) % fragments.len() as u64) as usize;
+                token_str.push_str(fragments[idx]);
+            }
+            let _ = token_str_to_id(&token_str);
+            let _ = network_and_token_str_to_id("moonbeam", &token_str);
+        }
+    }
+}
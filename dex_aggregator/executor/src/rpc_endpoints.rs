@@ -0,0 +1,297 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    chain_info::ChainInfo, common::UniversalChainId, get_chain_info_from_chain_id,
+};
+
+// Most paid providers take the API key in the URL (e.g. OnFinality's ?apikey=). The admin
+// sets the key separately from the URL so that the configured endpoints can be listed
+// without revealing it
+pub const API_KEY_PLACEHOLDER: &str = "{api_key}";
+const MAX_RPC_URL_LEN: usize = 256;
+// Stored keys start with this byte, followed by the nonce and the ciphertext. Keys set before
+// they were encrypted are stored as plaintext, which is printable ASCII so never starts with it
+const ENCRYPTED_API_KEY_VERSION: u8 = 1;
+const API_KEY_NONCE_LEN: u8 = 16;
+const API_KEY_ENCRYPTION_SALT: &[u8] = b"privadex-rpc-endpoint-api-key";
+
+#[derive(Debug, PartialEq)]
+pub enum RpcEndpointError {
+    InvalidApiKey,
+    InvalidUrl,
+    MissingApiKey,
+    UnsupportedNetwork,
+}
+pub type Result<T> = core::result::Result<T, RpcEndpointError>;
+
+/// Admin-supplied RPC endpoint that replaces the registry's rpc_url for a chain, e.g. a
+/// private endpoint that (unlike the public ones) accepts author_submitExtrinsic
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RpcEndpoint {
+    pub chain_id: UniversalChainId,
+    // May contain API_KEY_PLACEHOLDER, which is replaced with api_key
+    pub url_template: String,
+    api_key: Option<EncryptedApiKey>,
+}
+
+// Encrypted with a key derived from the contract's own key, so neither a copy of contract
+// storage nor the worker's state reveals the API key. Same SCALE encoding as the String that
+// it replaced
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct EncryptedApiKey(Vec<u8>);
+
+impl EncryptedApiKey {
+    fn encrypt(api_key: &str) -> Self {
+        let nonce = pink_extension::ext().getrandom(API_KEY_NONCE_LEN);
+        let mut bytes = vec![ENCRYPTED_API_KEY_VERSION];
+        bytes.extend(&nonce);
+        bytes.extend(apply_keystream(&nonce, api_key.as_bytes()));
+        Self(bytes)
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.0.first() == Some(&ENCRYPTED_API_KEY_VERSION)
+    }
+
+    fn decrypt(&self) -> Option<String> {
+        if !self.is_encrypted() {
+            return String::from_utf8(self.0.clone()).ok();
+        }
+        let nonce_and_ciphertext = &self.0[1..];
+        if nonce_and_ciphertext.len() < API_KEY_NONCE_LEN as usize {
+            return None;
+        }
+        let (nonce, ciphertext) = nonce_and_ciphertext.split_at(API_KEY_NONCE_LEN as usize);
+        String::from_utf8(apply_keystream(nonce, ciphertext)).ok()
+    }
+}
+
+// XORs data with the blocks blake2_256(key, nonce, block index). Only the admin can set keys,
+// so we need confidentiality but not a MAC
+fn apply_keystream(nonce: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = sp_core_hashing::blake2_256(
+        &pink_extension::ext().derive_sr25519_key(API_KEY_ENCRYPTION_SALT.into()),
+    );
+    data.chunks(32)
+        .enumerate()
+        .flat_map(|(i, chunk)| {
+            let block = sp_core_hashing::blake2_256(&(key, nonce, i as u32).encode());
+            chunk
+                .iter()
+                .zip(block)
+                .map(|(byte, key_byte)| byte ^ key_byte)
+                .collect::<Vec<u8>>()
+        })
+        .collect()
+}
+
+impl RpcEndpoint {
+    pub fn new(
+        chain_id: UniversalChainId,
+        url_template: String,
+        api_key: Option<String>,
+    ) -> Result<Self> {
+        if get_chain_info_from_chain_id(&chain_id).is_none() {
+            return Err(RpcEndpointError::UnsupportedNetwork);
+        }
+        if !url_template.starts_with("https://") || url_template.len() > MAX_RPC_URL_LEN {
+            return Err(RpcEndpointError::InvalidUrl);
+        }
+        if url_template.contains(API_KEY_PLACEHOLDER) && api_key.is_none() {
+            return Err(RpcEndpointError::MissingApiKey);
+        }
+        if let Some(api_key) = &api_key {
+            if api_key.is_empty() || !api_key.bytes().all(|c| c.is_ascii_graphic()) {
+                return Err(RpcEndpointError::InvalidApiKey);
+            }
+        }
+        Ok(Self {
+            chain_id,
+            url_template,
+            api_key: api_key.as_deref().map(EncryptedApiKey::encrypt),
+        })
+    }
+
+    // Encrypts a key that was stored before keys were encrypted
+    pub fn with_encrypted_api_key(self) -> Self {
+        match &self.api_key {
+            Some(api_key) if !api_key.is_encrypted() => Self {
+                api_key: api_key.decrypt().as_deref().map(EncryptedApiKey::encrypt),
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    // None if the stored key doesn't decrypt
+    fn get_url(&self) -> Option<String> {
+        match &self.api_key {
+            Some(api_key) => Some(
+                self.url_template
+                    .replace(API_KEY_PLACEHOLDER, &api_key.decrypt()?),
+            ),
+            None => Some(self.url_template.clone()),
+        }
+    }
+}
+
+// The configured endpoint for the chain if there is one, and the registry's otherwise (also
+// if the configured endpoint's key is unreadable, rather than sending a URL without it).
+// Every RPC request should get its URL from here (or from ExecuteStepMeta::get_rpc_url)
+pub fn get_rpc_url(rpc_endpoints: &[RpcEndpoint], chain_info: &ChainInfo) -> String {
+    rpc_endpoints
+        .iter()
+        .find(|endpoint| endpoint.chain_id == chain_info.chain_id)
+        .and_then(|endpoint| endpoint.get_url())
+        .unwrap_or_else(|| chain_info.rpc_url.to_string())
+}
+
+#[cfg(test)]
+mod rpc_endpoints_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::{
+        chain_info_registry, universal_chain_id_registry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_endpoint_overrides_registry() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let endpoint = RpcEndpoint::new(
+            universal_chain_id_registry::MOONBEAM,
+            "https://moonbeam.api.onfinality.io/rpc?apikey={api_key}".into(),
+            Some("secret".into()),
+        )
+        .expect("Valid endpoint");
+        let rpc_endpoints = vec![endpoint];
+        assert_eq!(
+            get_rpc_url(&rpc_endpoints, &chain_info_registry::MOONBEAM_INFO),
+            "https://moonbeam.api.onfinality.io/rpc?apikey=secret"
+        );
+        assert_eq!(
+            get_rpc_url(&rpc_endpoints, &chain_info_registry::ASTAR_INFO),
+            chain_info_registry::ASTAR_INFO.rpc_url
+        );
+    }
+
+    #[test]
+    fn test_api_key_is_stored_encrypted() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let endpoint = RpcEndpoint::new(
+            universal_chain_id_registry::MOONBEAM,
+            "https://moonbeam.api.onfinality.io/rpc?apikey={api_key}".into(),
+            Some("secret".into()),
+        )
+        .expect("Valid endpoint");
+        let encoded = endpoint.encode();
+        assert!(!encoded.windows(6).any(|window| window == b"secret"));
+        // The nonce makes each encryption different
+        let other_endpoint = RpcEndpoint::new(
+            universal_chain_id_registry::MOONBEAM,
+            "https://moonbeam.api.onfinality.io/rpc?apikey={api_key}".into(),
+            Some("secret".into()),
+        )
+        .expect("Valid endpoint");
+        assert_ne!(endpoint, other_endpoint);
+
+        let decoded = RpcEndpoint::decode(&mut encoded.as_slice()).expect("Valid encoding");
+        assert_eq!(
+            get_rpc_url(&[decoded], &chain_info_registry::MOONBEAM_INFO),
+            "https://moonbeam.api.onfinality.io/rpc?apikey=secret"
+        );
+    }
+
+    #[test]
+    fn test_legacy_plaintext_api_key() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        // How endpoints were stored before keys were encrypted
+        let legacy_encoded = (
+            universal_chain_id_registry::MOONBEAM,
+            String::from("https://moonbeam.api.onfinality.io/rpc?apikey={api_key}"),
+            Some(String::from("secret")),
+        )
+            .encode();
+        let legacy =
+            RpcEndpoint::decode(&mut legacy_encoded.as_slice()).expect("Same SCALE layout");
+        assert_eq!(
+            get_rpc_url(&[legacy.clone()], &chain_info_registry::MOONBEAM_INFO),
+            "https://moonbeam.api.onfinality.io/rpc?apikey=secret"
+        );
+
+        let migrated = legacy.with_encrypted_api_key();
+        assert!(!migrated
+            .encode()
+            .windows(6)
+            .any(|window| window == b"secret"));
+        assert_eq!(
+            get_rpc_url(&[migrated], &chain_info_registry::MOONBEAM_INFO),
+            "https://moonbeam.api.onfinality.io/rpc?apikey=secret"
+        );
+    }
+
+    #[test]
+    fn test_invalid_endpoints() {
+        assert_eq!(
+            RpcEndpoint::new(
+                universal_chain_id_registry::MOONBEAM,
+                "http://localhost:9933".into(),
+                None
+            ),
+            Err(RpcEndpointError::InvalidUrl)
+        );
+        assert_eq!(
+            RpcEndpoint::new(
+                universal_chain_id_registry::MOONBEAM,
+                "https://moonbeam.api.onfinality.io/rpc?apikey={api_key}".into(),
+                None
+            ),
+            Err(RpcEndpointError::MissingApiKey)
+        );
+        assert_eq!(
+            RpcEndpoint::new(
+                universal_chain_id_registry::MOONBEAM,
+                "https://moonbeam.api.onfinality.io/rpc?apikey={api_key}".into(),
+                Some("\u{1}secret".into())
+            ),
+            Err(RpcEndpointError::InvalidApiKey)
+        );
+        assert_eq!(
+            RpcEndpoint::new(
+                UniversalChainId::EVM(56),
+                "https://bsc.example".into(),
+                None
+            ),
+            Err(RpcEndpointError::UnsupportedNetwork)
+        );
+    }
+}
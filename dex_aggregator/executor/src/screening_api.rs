@@ -0,0 +1,103 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+#[allow(unused_imports)]
+use ink::prelude::{format, string::String, vec::Vec};
+use serde::Deserialize;
+
+use privadex_chain_metadata::common::EthAddress;
+#[allow(unused_imports)]
+use privadex_common::utils::{general_utils::slice_to_hex_string, http_request::http_post_wrapper};
+
+#[derive(Debug, PartialEq)]
+pub enum ScreeningError {
+    ParseFailed,
+    RequestFailed,
+}
+pub type Result<T> = core::result::Result<T, ScreeningError>;
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct ScreeningResponse {
+    allowed: bool,
+}
+
+/// Client for an external address screening (e.g. OFAC sanctions) provider. The endpoint
+/// takes {"addresses": ["0x...", ...]} and responds with {"allowed": bool}, where allowed
+/// is false if any of the addresses is flagged
+pub struct ScreeningApi {
+    pub screening_url: String,
+}
+
+impl ScreeningApi {
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn are_addresses_allowed(&self, addresses: &[EthAddress]) -> Result<bool> {
+        let resp_body = http_post_wrapper(
+            &self.screening_url,
+            get_screening_request_body(addresses).into_bytes(),
+        )
+        .map_err(|_| ScreeningError::RequestFailed)?;
+        let (resp, _): (ScreeningResponse, usize) =
+            serde_json_core::from_slice(&resp_body).map_err(|_| ScreeningError::ParseFailed)?;
+        Ok(resp.allowed)
+    }
+
+    #[cfg(feature = "mock-txn-send")]
+    pub fn are_addresses_allowed(&self, _addresses: &[EthAddress]) -> Result<bool> {
+        ink::env::debug_println!("[Mock ScreeningApi are_addresses_allowed]");
+        Ok(true)
+    }
+}
+
+#[allow(dead_code)]
+fn get_screening_request_body(addresses: &[EthAddress]) -> String {
+    let quoted_addresses: Vec<String> = addresses
+        .iter()
+        .map(|addr| format!("\"{}\"", slice_to_hex_string(&addr.0)))
+        .collect();
+    format!("{{\"addresses\":[{}]}}", quoted_addresses.join(","))
+}
+
+#[cfg(test)]
+mod screening_api_tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_screening_request_body() {
+        let addresses = [
+            EthAddress {
+                0: hex!("05a81d8564a3ea298660e34e03e5eff9a29d7a2a"),
+            },
+            EthAddress {
+                0: hex!("42b7d766824422f499f84703ec4e2abb273171cf"),
+            },
+        ];
+        assert_eq!(
+            get_screening_request_body(&addresses),
+            r#"{"addresses":["0x05a81d8564a3ea298660e34e03e5eff9a29d7a2a","0x42b7d766824422f499f84703ec4e2abb273171cf"]}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_screening_response() {
+        let (resp, _): (ScreeningResponse, usize) =
+            serde_json_core::from_slice(r#"{"allowed":false}"#.as_bytes()).expect("Valid response");
+        assert!(!resp.allowed);
+    }
+}
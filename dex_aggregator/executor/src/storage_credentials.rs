@@ -0,0 +1,138 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+/// What a storage credential pair is used for. Each can be given its own pair (e.g. an IAM
+/// user whose policy only allows that table's items or that bucket), so that a leaked pair
+/// exposes one function rather than all of them
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StorageFunction {
+    // S3 execution-plan bucket (read and write)
+    PlanStore,
+    // DynamoDB plan claims, plus the other coordination items (prestart txns, tags, delivery
+    // approvals, relayers, aliases and threshold signatures)
+    ClaimTable,
+    // DynamoDB NonceManager items
+    NonceTable,
+    // S3 analytics bucket
+    Analytics,
+}
+
+impl StorageFunction {
+    fn is_s3(&self) -> bool {
+        matches!(self, Self::PlanStore | Self::Analytics)
+    }
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug, scale_info::TypeInfo))]
+pub struct StorageCredential {
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// The pair used for each StorageFunction, as handed to ExecuteStepMeta
+#[derive(Clone)]
+pub struct StorageCredentials {
+    pub plan_store: StorageCredential,
+    pub claim_table: StorageCredential,
+    pub nonce_table: StorageCredential,
+    pub analytics: StorageCredential,
+}
+
+// A function without its own pair falls back to the shared DynamoDB or S3 pair from
+// init_secret_keys, so deployments from before the split keep working unchanged
+pub fn resolve_storage_credential(
+    scoped: &[(StorageFunction, StorageCredential)],
+    function: StorageFunction,
+    shared_dynamodb: Option<StorageCredential>,
+    shared_s3: Option<StorageCredential>,
+) -> Option<StorageCredential> {
+    match scoped.iter().find(|(f, _)| *f == function) {
+        Some((_, credential)) => Some(credential.clone()),
+        None if function.is_s3() => shared_s3,
+        None => shared_dynamodb,
+    }
+}
+
+// Replaces function's pair if it already has one
+pub fn set_storage_credential(
+    scoped: &mut Vec<(StorageFunction, StorageCredential)>,
+    function: StorageFunction,
+    credential: StorageCredential,
+) {
+    scoped.retain(|(f, _)| *f != function);
+    scoped.push((function, credential));
+}
+
+#[cfg(test)]
+mod storage_credentials_tests {
+    use super::*;
+    use ink::prelude::{string::ToString, vec};
+
+    fn credential(access_key: &str) -> StorageCredential {
+        StorageCredential {
+            access_key: access_key.to_string(),
+            secret_key: "secret".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_shared_pair() {
+        let scoped = vec![(StorageFunction::NonceTable, credential("nonce"))];
+        let resolve = |function| {
+            resolve_storage_credential(
+                &scoped,
+                function,
+                Some(credential("dynamodb")),
+                Some(credential("s3")),
+            )
+            .map(|c| c.access_key)
+        };
+        assert_eq!(
+            resolve(StorageFunction::NonceTable),
+            Some("nonce".to_string())
+        );
+        assert_eq!(
+            resolve(StorageFunction::ClaimTable),
+            Some("dynamodb".to_string())
+        );
+        assert_eq!(resolve(StorageFunction::PlanStore), Some("s3".to_string()));
+        assert_eq!(resolve(StorageFunction::Analytics), Some("s3".to_string()));
+        assert_eq!(
+            resolve_storage_credential(&scoped, StorageFunction::Analytics, None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_set_replaces_existing_pair() {
+        let mut scoped = Vec::new();
+        set_storage_credential(&mut scoped, StorageFunction::Analytics, credential("old"));
+        set_storage_credential(&mut scoped, StorageFunction::PlanStore, credential("plans"));
+        set_storage_credential(&mut scoped, StorageFunction::Analytics, credential("new"));
+        assert_eq!(scoped.len(), 2);
+        assert_eq!(
+            resolve_storage_credential(&scoped, StorageFunction::Analytics, None, None),
+            Some(credential("new"))
+        );
+    }
+}
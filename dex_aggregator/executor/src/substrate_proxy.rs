@@ -0,0 +1,157 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{SubstratePublicKey, UniversalAddress, UniversalChainId},
+    get_chain_info_from_chain_id,
+};
+
+use crate::extrinsic_call_factory::proxy_proxy;
+
+#[derive(Debug, PartialEq)]
+pub enum SubstrateProxyError {
+    SameRealAndDelegate,
+    UnsupportedNetwork,
+}
+pub type Result<T> = core::result::Result<T, SubstrateProxyError>;
+
+/// Admin-supplied proxy relationship on one chain. real is the escrow account that holds the
+/// funds (and appears in ExecutionPlans), but its key stays offline. The escrow's Substrate key
+/// (delegate) was added as a proxy of real with a restricted proxy type, so it can only
+/// dispatch the calls the proxy type's filter allows. A leaked delegate key then can't e.g.
+/// transfer real's balances or remove the proxy
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstrateProxy {
+    pub chain_id: UniversalChainId,
+    pub real: SubstratePublicKey,
+    pub delegate: SubstratePublicKey,
+    // The chain's ProxyType index. None lets the chain pick any proxy relationship between the
+    // two that allows the call
+    pub force_proxy_type: Option<u8>,
+    pallet_index: u8,
+}
+
+impl SubstrateProxy {
+    pub fn new(
+        chain_id: UniversalChainId,
+        real: SubstratePublicKey,
+        delegate: SubstratePublicKey,
+        force_proxy_type: Option<u8>,
+    ) -> Result<Self> {
+        let pallet_index = get_chain_info_from_chain_id(&chain_id)
+            .and_then(|chain_info| chain_info.proxy_pallet_index)
+            .ok_or(SubstrateProxyError::UnsupportedNetwork)?;
+        if real == delegate {
+            return Err(SubstrateProxyError::SameRealAndDelegate);
+        }
+        Ok(Self {
+            chain_id,
+            real,
+            delegate,
+            force_proxy_type,
+            pallet_index,
+        })
+    }
+
+    // The account that signs (and pays the fees of) the extrinsic
+    pub fn get_signer(&self) -> UniversalAddress {
+        UniversalAddress::Substrate(self.delegate.clone())
+    }
+
+    pub fn wrap_call(&self, call: &[u8]) -> Vec<u8> {
+        proxy_proxy(self.pallet_index, &self.real, self.force_proxy_type, call)
+    }
+}
+
+// The proxy through which the escrow acts for src_addr on chain_id, if there is one
+pub fn find_substrate_proxy<'a>(
+    substrate_proxies: &'a [SubstrateProxy],
+    chain_id: &UniversalChainId,
+    src_addr: &UniversalAddress,
+) -> Option<&'a SubstrateProxy> {
+    substrate_proxies.iter().find(|proxy| {
+        proxy.chain_id == *chain_id
+            && matches!(src_addr, UniversalAddress::Substrate(pubkey) if *pubkey == proxy.real)
+    })
+}
+
+#[cfg(test)]
+mod substrate_proxy_tests {
+    use hex_literal::hex;
+    use ink::prelude::vec;
+    use privadex_chain_metadata::registry::chain::universal_chain_id_registry;
+
+    use super::*;
+
+    const REAL: SubstratePublicKey = SubstratePublicKey {
+        0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
+    };
+    const DELEGATE: SubstratePublicKey = SubstratePublicKey {
+        0: hex!("60b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a"),
+    };
+
+    #[test]
+    fn test_find_substrate_proxy() {
+        let proxy =
+            SubstrateProxy::new(universal_chain_id_registry::POLKADOT, REAL, DELEGATE, None)
+                .expect("Valid proxy");
+        let substrate_proxies = vec![proxy.clone()];
+        assert_eq!(
+            find_substrate_proxy(
+                &substrate_proxies,
+                &universal_chain_id_registry::POLKADOT,
+                &UniversalAddress::Substrate(REAL)
+            ),
+            Some(&proxy)
+        );
+        assert_eq!(proxy.get_signer(), UniversalAddress::Substrate(DELEGATE));
+        // Only on the chain it was configured for, and only for real
+        assert_eq!(
+            find_substrate_proxy(
+                &substrate_proxies,
+                &universal_chain_id_registry::ASTAR,
+                &UniversalAddress::Substrate(REAL)
+            ),
+            None
+        );
+        assert_eq!(
+            find_substrate_proxy(
+                &substrate_proxies,
+                &universal_chain_id_registry::POLKADOT,
+                &UniversalAddress::Substrate(DELEGATE)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_proxies() {
+        assert_eq!(
+            SubstrateProxy::new(universal_chain_id_registry::MOONBEAM, REAL, DELEGATE, None),
+            Err(SubstrateProxyError::UnsupportedNetwork)
+        );
+        assert_eq!(
+            SubstrateProxy::new(universal_chain_id_registry::POLKADOT, REAL, REAL, None),
+            Err(SubstrateProxyError::SameRealAndDelegate)
+        );
+    }
+}
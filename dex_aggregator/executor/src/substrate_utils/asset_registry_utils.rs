@@ -0,0 +1,263 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::String, vec::Vec};
+use scale::Decode;
+
+use privadex_chain_metadata::{
+    chain_info::{ChainInfo, XC20RegistryKind},
+    common::{ChainTokenId, UniversalTokenId, XC20Token},
+    registry::token::xc20_registry_overlay::{DiscoveredXC20, XC20RegistryOverlay},
+};
+
+use super::{
+    common::{Result, SubstrateError},
+    node_rpc_utils::SubstrateNodeRpcUtils,
+};
+
+// Asset IDs are u128 on both Moonbeam and Astar
+type AssetId = u128;
+
+const ASSET_ID_ENCODED_LEN: usize = 16;
+const KEYS_PAGE_SIZE: u32 = 256;
+
+// pallet_assets::AssetMetadata (we only need symbol and decimals)
+#[derive(Decode, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+struct AssetMetadata {
+    deposit: u128,
+    name: Vec<u8>,
+    symbol: Vec<u8>,
+    decimals: u8,
+    is_frozen: bool,
+}
+
+// Moonbeam's assetManager stores an enum with a single variant
+#[derive(Decode, Debug, PartialEq, Eq)]
+enum MoonbeamAssetType {
+    Xcm(xcm::v1::MultiLocation),
+}
+
+// Builds the XC20 <-> MultiLocation <-> ERC20 precompile mapping by walking the chain's
+// asset-to-location map and looking up each asset's metadata. Assets without a location
+// are local assets (not XC20s), so we never see them
+pub struct SubstrateAssetRegistryUtils {
+    pub node_rpc_utils: SubstrateNodeRpcUtils,
+}
+
+impl SubstrateAssetRegistryUtils {
+    pub fn discover_xc20_tokens(&self, chain_info: &ChainInfo) -> Result<Vec<DiscoveredXC20>> {
+        let registry_kind = chain_info
+            .xc20_registry
+            .ok_or(SubstrateError::InvalidXcmLookup)?;
+        let location_prefix = match registry_kind {
+            XC20RegistryKind::MoonbeamAssetManager => storage_prefix("AssetManager", "AssetIdType"),
+            XC20RegistryKind::AstarXcAssetConfig => {
+                storage_prefix("XcAssetConfig", "AssetIdToLocation")
+            }
+        };
+
+        let mut discovered = Vec::new();
+        let mut start_key: Option<Vec<u8>> = None;
+        loop {
+            let keys = self.node_rpc_utils.get_storage_keys_paged(
+                &location_prefix,
+                KEYS_PAGE_SIZE,
+                start_key.as_deref(),
+            )?;
+            for key in keys.iter() {
+                // Both maps are keyed by a *Concat hasher, so the raw asset ID is the suffix
+                let asset_id = asset_id_from_storage_key(key)?;
+                let location = match self.node_rpc_utils.get_storage(key)? {
+                    Some(raw_location) => decode_location(registry_kind, &raw_location)?,
+                    None => continue,
+                };
+                let metadata = match self
+                    .node_rpc_utils
+                    .get_storage(&asset_metadata_storage_key(asset_id))?
+                {
+                    Some(raw_metadata) => AssetMetadata::decode(&mut raw_metadata.as_slice())
+                        .map_err(|_| SubstrateError::InvalidBody)?,
+                    None => continue,
+                };
+                discovered.push(DiscoveredXC20 {
+                    token: UniversalTokenId {
+                        chain: chain_info.chain_id,
+                        id: ChainTokenId::XC20(XC20Token::from_asset_id(asset_id)),
+                    },
+                    symbol: String::from_utf8_lossy(&metadata.symbol).into_owned(),
+                    decimals: metadata.decimals,
+                    token_asset_multilocation: location,
+                });
+            }
+            if keys.len() < KEYS_PAGE_SIZE as usize {
+                break;
+            }
+            start_key = keys.last().cloned();
+        }
+        Ok(discovered)
+    }
+
+    // Returns the number of XC20s now cached for this chain
+    pub fn refresh_overlay(
+        &self,
+        chain_info: &ChainInfo,
+        overlay: &mut XC20RegistryOverlay,
+    ) -> Result<usize> {
+        let discovered = self.discover_xc20_tokens(chain_info)?;
+        let num_discovered = discovered.len();
+        overlay.replace_chain(chain_info.chain_id, discovered);
+        Ok(num_discovered)
+    }
+}
+
+fn storage_prefix(module: &str, method: &str) -> Vec<u8> {
+    let mut prefix = Vec::new();
+    prefix.extend(sp_core_hashing::twox_128(module.as_bytes()));
+    prefix.extend(sp_core_hashing::twox_128(method.as_bytes()));
+    prefix
+}
+
+// assets.metadata is keyed by Blake2_128Concat on both Moonbeam and Astar
+fn asset_metadata_storage_key(asset_id: AssetId) -> Vec<u8> {
+    let encoded_asset_id = asset_id.to_le_bytes();
+    let mut key = storage_prefix("Assets", "Metadata");
+    key.extend(sp_core_hashing::blake2_128(&encoded_asset_id));
+    key.extend(encoded_asset_id);
+    key
+}
+
+fn asset_id_from_storage_key(key: &[u8]) -> Result<AssetId> {
+    if key.len() < ASSET_ID_ENCODED_LEN {
+        return Err(SubstrateError::InvalidBody);
+    }
+    AssetId::decode(&mut &key[key.len() - ASSET_ID_ENCODED_LEN..])
+        .map_err(|_| SubstrateError::InvalidBody)
+}
+
+fn decode_location(
+    registry_kind: XC20RegistryKind,
+    raw_location: &[u8],
+) -> Result<xcm::latest::MultiLocation> {
+    let mut input = raw_location;
+    match registry_kind {
+        XC20RegistryKind::MoonbeamAssetManager => {
+            let MoonbeamAssetType::Xcm(location) =
+                MoonbeamAssetType::decode(&mut input).map_err(|_| SubstrateError::InvalidBody)?;
+            Ok(location)
+        }
+        XC20RegistryKind::AstarXcAssetConfig => {
+            let versioned_location = xcm::VersionedMultiLocation::decode(&mut input)
+                .map_err(|_| SubstrateError::InvalidBody)?;
+            xcm::latest::MultiLocation::try_from(versioned_location)
+                .map_err(|_| SubstrateError::InvalidXcmLookup)
+        }
+    }
+}
+
+#[cfg(test)]
+mod asset_registry_utils_tests {
+    use ink::prelude::{string::ToString, vec};
+    use scale::Encode;
+    use xcm::latest::{Junction, Junctions, MultiLocation};
+
+    use privadex_chain_metadata::registry::{
+        chain::chain_info_registry::{ASTAR_INFO, MOONBEAM_INFO},
+        token::universal_token_id_registry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_asset_id_round_trips_through_metadata_key() {
+        let asset_id: AssetId = 42_259_045_809_535_163_221_576_417_993_425_387_648;
+        let key = asset_metadata_storage_key(asset_id);
+        // twox128 + twox128 + blake2_128 + raw asset ID
+        assert_eq!(key.len(), 16 + 16 + 16 + ASSET_ID_ENCODED_LEN);
+        assert_eq!(key[..32], storage_prefix("Assets", "Metadata"));
+        assert_eq!(asset_id_from_storage_key(&key), Ok(asset_id));
+    }
+
+    #[test]
+    fn test_decode_locations() {
+        let dot_location = MultiLocation {
+            parents: 1,
+            interior: Junctions::Here,
+        };
+        // AssetType::Xcm is variant 0
+        let moonbeam_raw = {
+            let mut raw = vec![0u8];
+            raw.extend(dot_location.encode());
+            raw
+        };
+        assert_eq!(
+            decode_location(XC20RegistryKind::MoonbeamAssetManager, &moonbeam_raw),
+            Ok(dot_location.clone())
+        );
+
+        let glmr_location = MultiLocation {
+            parents: 1,
+            interior: Junctions::X2(Junction::Parachain(2004), Junction::PalletInstance(10)),
+        };
+        let astar_raw = xcm::VersionedMultiLocation::from(glmr_location.clone()).encode();
+        assert_eq!(
+            decode_location(XC20RegistryKind::AstarXcAssetConfig, &astar_raw),
+            Ok(glmr_location)
+        );
+    }
+
+    #[test]
+    fn test_decode_asset_metadata() {
+        let raw = (0u128, b"xcDOT".to_vec(), b"xcDOT".to_vec(), 10u8, false).encode();
+        let metadata = AssetMetadata::decode(&mut raw.as_slice()).expect("Valid metadata");
+        assert_eq!(metadata.symbol, b"xcDOT".to_vec());
+        assert_eq!(metadata.decimals, 10);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_discover_moonbeam_xc20s() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let utils = SubstrateAssetRegistryUtils {
+            node_rpc_utils: SubstrateNodeRpcUtils::from_chain_info(&MOONBEAM_INFO, &[]),
+        };
+        let mut overlay = XC20RegistryOverlay::new();
+        let num_discovered = utils
+            .refresh_overlay(&MOONBEAM_INFO, &mut overlay)
+            .expect("Discovery should succeed");
+        assert!(num_discovered > 0);
+        assert!(overlay
+            .get(&universal_token_id_registry::DOT_MOONBEAM)
+            .is_some());
+    }
+
+    #[test]
+    #[ignore]
+    fn test_discover_astar_xc20s() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let utils = SubstrateAssetRegistryUtils {
+            node_rpc_utils: SubstrateNodeRpcUtils::from_chain_info(&ASTAR_INFO, &[]),
+        };
+        let discovered = utils
+            .discover_xc20_tokens(&ASTAR_INFO)
+            .expect("Discovery should succeed");
+        assert!(discovered
+            .iter()
+            .any(|xc20| xc20.token == universal_token_id_registry::DOT_ASTAR));
+    }
+}
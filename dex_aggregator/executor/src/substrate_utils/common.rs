@@ -26,6 +26,7 @@ pub enum SubstrateError {
     InvalidXcmLookup,
     NotFound,
     RequestFailed,
+    SignExtrinsicFailed,
     UnknownEvent,
 }
 pub type Result<T> = core::result::Result<T, SubstrateError>;
@@ -16,18 +16,19 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::vec::Vec;
-use pink_extension::chain_extension::{signing, SigType};
+use ink::prelude::vec::Vec;
 use scale::Encode;
-use sp_core::sr25519;
+use sp_core::{ed25519, sr25519};
 use sp_runtime::{MultiAddress, MultiSignature};
 
 use privadex_common::signature_scheme::SignatureScheme;
 
+use crate::key_container::SigningKey;
+
 pub struct ExtrinsicSigConfig<AccountId> {
     pub sig_scheme: SignatureScheme,
     pub signer: AccountId,
-    pub privkey: Vec<u8>,
+    pub key: SigningKey,
 }
 
 impl<AccountId> ExtrinsicSigConfig<AccountId>
@@ -39,11 +40,14 @@ where
     pub fn get_encoded_signer(&self) -> Vec<u8> {
         match self.sig_scheme {
             SignatureScheme::Ethereum => self.signer.encode(),
-            SignatureScheme::Sr25519 => MultiAddress::<AccountId, u32>::Id(self.signer).encode(),
+            SignatureScheme::Sr25519 | SignatureScheme::Ed25519 => {
+                MultiAddress::<AccountId, u32>::Id(self.signer).encode()
+            }
         }
     }
 
-    pub fn get_encoded_signature(&self, encoded_data: Vec<u8>) -> Vec<u8> {
+    /// Returns None if the (remote) signer fails to sign
+    pub fn get_encoded_signature(&self, encoded_data: Vec<u8>) -> Option<Vec<u8>> {
         let payload = if encoded_data.len() > 256 {
             sp_core_hashing::blake2_256(&encoded_data).to_vec()
         } else {
@@ -52,17 +56,24 @@ where
 
         match self.sig_scheme {
             // Use Keccak-256 hasher instead of Blake2-256 (which is the ECDSA default)
-            SignatureScheme::Ethereum => {
-                signing::ecdsa_sign_prehashed(&self.privkey, sp_core_hashing::keccak_256(&payload))
-                    .to_vec()
-            }
+            SignatureScheme::Ethereum => self
+                .key
+                .sign_prehashed_ecdsa(&sp_core_hashing::keccak_256(&payload))
+                .map(|signature| signature.to_vec()),
             SignatureScheme::Sr25519 => {
-                let signature = signing::sign(&payload, &self.privkey, SigType::Sr25519);
-                MultiSignature::from(
-                    sr25519::Signature::try_from(signature.as_slice())
-                        .expect("Expected 64-byte raw signature"),
+                let signature = self.key.sign(SignatureScheme::Sr25519, &payload)?;
+                Some(
+                    MultiSignature::from(sr25519::Signature::try_from(signature.as_slice()).ok()?)
+                        .encode(),
+                )
+            }
+            // MultiSignature lets Ed25519 accounts sign on the same chains as Sr25519 accounts
+            SignatureScheme::Ed25519 => {
+                let signature = self.key.sign(SignatureScheme::Ed25519, &payload)?;
+                Some(
+                    MultiSignature::from(ed25519::Signature::try_from(signature.as_slice()).ok()?)
+                        .encode(),
                 )
-                .encode()
             }
         }
     }
@@ -71,7 +82,8 @@ where
 #[cfg(test)]
 mod tests {
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
+    use pink_extension::chain_extension::{signing, SigType};
 
     use privadex_common::utils::general_utils::slice_to_hex_string;
 
@@ -108,4 +120,20 @@ mod tests {
         let verified = SignatureScheme::Sr25519.verify(&pubkey, msg.as_bytes(), &signature);
         assert_eq!(verified, true);
     }
+
+    #[test]
+    fn verify_ed25519_msg_signature() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let secret_key = hex!("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a"); // Alice
+        let pubkey = signing::get_public_key(&secret_key, SigType::Ed25519);
+        let msg = "test message";
+        let signature = SignatureScheme::Ed25519.sign(msg.as_bytes(), &secret_key);
+        assert_eq!(signature.len(), 64);
+        let verified = SignatureScheme::Ed25519.verify(&pubkey, msg.as_bytes(), &signature);
+        assert_eq!(verified, true);
+        assert_eq!(
+            SignatureScheme::Sr25519.verify(&pubkey, msg.as_bytes(), &signature),
+            false
+        );
+    }
 }
@@ -17,7 +17,7 @@
  */
 
 use core::{fmt, str::FromStr};
-use ink_prelude::{
+use ink::prelude::{
     format,
     string::{String, ToString},
     vec::Vec,
@@ -26,7 +26,7 @@ use serde::{de, Deserialize, Deserializer};
 
 use privadex_chain_metadata::common::{
     Amount, AssetId, BlockNum, EthAddress, Nonce, SubstrateExtrinsicHash, SubstratePublicKey,
-    UniversalAddress,
+    UniversalAddress, XcmMessageHash,
 };
 use privadex_common::utils::{
     general_utils::{hex_string_to_vec, slice_to_hex_string},
@@ -36,6 +36,10 @@ use privadex_common::utils::{
 use super::super::common::{Result, SubstrateError};
 use super::xcm_transfer_lookup;
 
+// Remarks of a deposit's hash that we look at. Anyone who sees the remark can remark it too, so
+// a griefer would need to remark it this many times (before the user does) to hide the deposit
+pub const MAX_REMARK_CANDIDATES: u32 = 20;
+
 pub fn extrinsic_hash_lookup_call(
     query_url: &str,
     min_block: BlockNum,
@@ -43,7 +47,7 @@ pub fn extrinsic_hash_lookup_call(
     extrinsic_hash: &SubstrateExtrinsicHash,
 ) -> Result<Vec<Extrinsic>> {
     let query = get_extrinsic_hash_lookup_query(min_block, max_block, extrinsic_hash);
-    ink_env::debug_println!("Query: {}", query);
+    ink::env::debug_println!("Query: {}", query);
     let raw_bytes = graphql_query(query_url, &query)?;
 
     let (decoded, _): (DataWrapper<ExtrinsicVec>, usize) =
@@ -58,7 +62,21 @@ pub fn xcm_transfer_event_lookup_call(
     xcm_lookup: &xcm_transfer_lookup::XCMTransferLookup,
 ) -> Result<Vec<Block>> {
     let query = get_xcm_transfer_event_lookup_query(min_block, max_block, xcm_lookup);
-    // ink_env::debug_println!("Query: {}", query);
+    // ink::env::debug_println!("Query: {}", query);
+    let raw_bytes = graphql_query(query_url, &query)?;
+
+    let (decoded, _): (DataWrapper<BlocksVec>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.blocks)
+}
+
+pub fn xcm_failure_event_lookup_call(
+    query_url: &str,
+    min_block: BlockNum,
+    max_block: BlockNum,
+    msg_pass_direction: &xcm_transfer_lookup::MessagePassingDirection,
+) -> Result<Vec<Block>> {
+    let query = get_xcm_failure_event_lookup_query(min_block, max_block, msg_pass_direction);
     let raw_bytes = graphql_query(query_url, &query)?;
 
     let (decoded, _): (DataWrapper<BlocksVec>, usize) =
@@ -66,6 +84,78 @@ pub fn xcm_transfer_event_lookup_call(
     Ok(decoded.data.blocks)
 }
 
+pub fn xcm_message_sent_lookup_call(
+    query_url: &str,
+    block_num: BlockNum,
+    extrinsic_index: Nonce,
+) -> Result<Vec<Event>> {
+    let query = get_xcm_message_sent_lookup_query(block_num, extrinsic_index);
+    let raw_bytes = graphql_query(query_url, &query)?;
+
+    let (decoded, _): (DataWrapper<EventsVec>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.events)
+}
+
+pub fn remark_event_lookup_call(
+    query_url: &str,
+    min_block: BlockNum,
+    max_block: BlockNum,
+    remark_hash: &[u8; 32],
+) -> Result<Vec<RemarkedEvent>> {
+    let query = get_remark_event_lookup_query(min_block, max_block, remark_hash);
+    let raw_bytes = graphql_query(query_url, &query)?;
+
+    let (decoded, _): (DataWrapper<RemarkedEventsVec>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.events)
+}
+
+pub fn balances_transfer_lookup_call(
+    query_url: &str,
+    block_num: BlockNum,
+    extrinsic_index: Nonce,
+) -> Result<Vec<Event>> {
+    let query = get_balances_transfer_lookup_query(block_num, extrinsic_index);
+    let raw_bytes = graphql_query(query_url, &query)?;
+
+    let (decoded, _): (DataWrapper<EventsVec>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.events)
+}
+
+pub fn extrinsic_fee_lookup_call(
+    query_url: &str,
+    block_num: BlockNum,
+    extrinsic_index: Nonce,
+) -> Result<Vec<Event>> {
+    let query = get_extrinsic_fee_lookup_query(block_num, extrinsic_index);
+    let raw_bytes = graphql_query(query_url, &query)?;
+
+    let (decoded, _): (DataWrapper<EventsVec>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.events)
+}
+
+// Returns the response body as is, e.g. to keep as a record of the extrinsic
+pub fn extrinsic_raw_lookup_call(
+    query_url: &str,
+    block_num: BlockNum,
+    extrinsic_index: Nonce,
+) -> Result<Vec<u8>> {
+    let query = get_extrinsic_raw_lookup_query(block_num, extrinsic_index);
+    graphql_query(query_url, &query)
+}
+
+// Highest block the indexer has processed
+pub fn squid_status_call(query_url: &str) -> Result<BlockNum> {
+    let raw_bytes = graphql_query(query_url, "squidStatus { height }")?;
+
+    let (decoded, _): (DataWrapper<SquidStatusWrapper>, usize) =
+        serde_json_core::from_slice(&raw_bytes).or(Err(SubstrateError::InvalidBody))?;
+    Ok(decoded.data.squidStatus.height)
+}
+
 fn get_extrinsic_hash_lookup_query(
     min_block: BlockNum,
     max_block: BlockNum,
@@ -91,6 +181,32 @@ fn get_extrinsic_hash_lookup_query(
     .to_string()
 }
 
+fn get_extrinsic_raw_lookup_query(block_num: BlockNum, extrinsic_index: Nonce) -> String {
+    format!(
+        "\
+            extrinsics(limit: 1, \
+                where: {{ block: {{ height_eq: {} }}, indexInBlock_eq: {} }}) \
+            {{ \
+                hash \
+                success \
+                error \
+                fee \
+                call {{ \
+                    name \
+                    args \
+                }} \
+                events {{ \
+                    name \
+                    indexInBlock \
+                    args \
+                }} \
+            }} \
+            ",
+        block_num, extrinsic_index,
+    )
+    .to_string()
+}
+
 fn get_xcm_transfer_event_lookup_query(
     min_block: BlockNum,
     max_block: BlockNum,
@@ -135,6 +251,135 @@ fn get_xcm_transfer_event_lookup_query(
     .to_string()
 }
 
+// XCMP reports failures in a separate event, but UMP and DMP report both successes and
+// failures in the same event (with the error in its Outcome), so we filter those client-side
+fn get_xcm_failure_event_lookup_query(
+    min_block: BlockNum,
+    max_block: BlockNum,
+    msg_pass_direction: &xcm_transfer_lookup::MessagePassingDirection,
+) -> String {
+    let event_name = match msg_pass_direction {
+        xcm_transfer_lookup::MessagePassingDirection::Xcmp => "XcmpQueue.Fail".to_string(),
+        _ => msg_pass_direction.event_success_name(),
+    };
+    let extrinsic_call_name = match msg_pass_direction {
+        xcm_transfer_lookup::MessagePassingDirection::Ump => "\\\"ParaInherent.enter\\\"",
+        _ => "\\\"ParachainSystem.set_validation_data\\\"",
+    };
+    format!(
+        "\
+            blocks(limit: {}, where: {{ height_gte: {}, height_lte: {} }}) {{ \
+                height \
+                events(limit:50, where: {{ \
+                    extrinsic: {{ call: {{ name_eq: {} }} }}, \
+                    name_eq: \\\"{}\\\" \
+                }}) {{ \
+                    name \
+                    indexInBlock \
+                    args \
+                }} \
+            }} \
+            ",
+        max_block - min_block + 1,
+        min_block,
+        max_block,
+        extrinsic_call_name,
+        event_name,
+    )
+    .to_string()
+}
+
+fn get_xcm_message_sent_lookup_query(block_num: BlockNum, extrinsic_index: Nonce) -> String {
+    format!(
+        "\
+            events(limit: 1, \
+                where: {{ block: {{ height_eq: {} }}, \
+                            extrinsic: {{ indexInBlock_eq: {} }}, \
+                            name_in: [ \\\"XcmpQueue.XcmpMessageSent\\\" \\\"ParachainSystem.UpwardMessageSent\\\" ] }}) \
+            {{ \
+                name \
+                indexInBlock \
+                args \
+            }} \
+            ",
+        block_num, extrinsic_index,
+    )
+    .to_string()
+}
+
+// System.remarkWithEvent only reports the blake2_256 hash of the remark, so we match on that.
+// Anyone can remark the same hash, so this returns every remark in the range (in block order) up
+// to MAX_REMARK_CANDIDATES
+fn get_remark_event_lookup_query(
+    min_block: BlockNum,
+    max_block: BlockNum,
+    remark_hash: &[u8; 32],
+) -> String {
+    // The filter is a JSON string inside a GraphQL string inside the JSON request body, hence
+    // the extra escaping
+    let args_filter = format!(
+        r#"\"{{\\\"hash\\\": \\\"{}\\\"}}\""#,
+        &slice_to_hex_string(remark_hash)
+    );
+    format!(
+        "\
+            events(limit: {}, orderBy: block_height_ASC, \
+                where: {{ block: {{ AND: {{ height_gte: {}, height_lte: {} }} }}, \
+                            name_eq: \\\"System.Remarked\\\", \
+                            args_jsonContains: {} }}) \
+            {{ \
+                block {{ \
+                    height \
+                }} \
+                extrinsic {{ \
+                    indexInBlock \
+                }} \
+                args \
+            }} \
+            ",
+        MAX_REMARK_CANDIDATES, min_block, max_block, args_filter,
+    )
+    .to_string()
+}
+
+fn get_balances_transfer_lookup_query(block_num: BlockNum, extrinsic_index: Nonce) -> String {
+    format!(
+        "\
+            events(limit: 50, \
+                where: {{ block: {{ height_eq: {} }}, \
+                            extrinsic: {{ indexInBlock_eq: {} }}, \
+                            name_eq: \\\"Balances.Transfer\\\" }}) \
+            {{ \
+                name \
+                indexInBlock \
+                args \
+            }} \
+            ",
+        block_num, extrinsic_index,
+    )
+    .to_string()
+}
+
+// Fees are charged in the extrinsic's own phase, so its fee events are linked to it like the
+// rest of its events
+fn get_extrinsic_fee_lookup_query(block_num: BlockNum, extrinsic_index: Nonce) -> String {
+    format!(
+        "\
+            events(limit: 10, \
+                where: {{ block: {{ height_eq: {} }}, \
+                            extrinsic: {{ indexInBlock_eq: {} }}, \
+                            name_in: [ \\\"TransactionPayment.TransactionFeePaid\\\" \\\"Treasury.Deposit\\\" ] }}) \
+            {{ \
+                name \
+                indexInBlock \
+                args \
+            }} \
+            ",
+        block_num, extrinsic_index,
+    )
+    .to_string()
+}
+
 // The below works but is slow (takes ~5 seconds to execute on Moonbeam). Via some experimentation
 // I found that the where clause in blocks is the bottleneck (I assume field indexing issues).
 // Thus we adjust the query
@@ -184,7 +429,18 @@ struct DataWrapper<T> {
 }
 
 #[derive(Deserialize, Debug)]
-#[serde(bound(deserialize = "ink_prelude::vec::Vec<Extrinsic>: Deserialize<'de>"))]
+#[allow(non_snake_case)]
+struct SquidStatusWrapper {
+    pub squidStatus: SquidStatus,
+}
+
+#[derive(Deserialize, Debug)]
+struct SquidStatus {
+    pub height: BlockNum,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<Extrinsic>: Deserialize<'de>"))]
 struct ExtrinsicVec {
     pub extrinsics: Vec<Extrinsic>,
 }
@@ -203,13 +459,45 @@ pub struct ExtrinsicBlock {
 }
 
 #[derive(Deserialize, Debug)]
-#[serde(bound(deserialize = "ink_prelude::vec::Vec<Block>: Deserialize<'de>"))]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<RemarkedEvent>: Deserialize<'de>"))]
+struct RemarkedEventsVec {
+    pub events: Vec<RemarkedEvent>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemarkedEvent {
+    pub block: ExtrinsicBlock,
+    pub extrinsic: RemarkedEventExtrinsic,
+    pub args: RemarkedArgs,
+}
+
+// We already know the hash
+#[derive(Deserialize, Debug)]
+pub struct RemarkedArgs {
+    #[serde(deserialize_with = "hex_str_to_universal_address")]
+    pub sender: UniversalAddress,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+pub struct RemarkedEventExtrinsic {
+    pub indexInBlock: Nonce,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<Block>: Deserialize<'de>"))]
 struct BlocksVec {
     pub blocks: Vec<Block>,
 }
 
 #[derive(Deserialize, Debug)]
-#[serde(bound(deserialize = "ink_prelude::vec::Vec<Event>: Deserialize<'de>"))]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<Event>: Deserialize<'de>"))]
+struct EventsVec {
+    pub events: Vec<Event>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<Event>: Deserialize<'de>"))]
 pub struct Block {
     pub height: BlockNum,
     pub events: Vec<Event>,
@@ -231,22 +519,38 @@ impl<'de> Deserialize<'de> for Event {
     where
         D: Deserializer<'de>,
     {
-        // These args are completely ignored but I can't figure out a cleaner way to
-        // deserialize; you need to parse to the end and you can't serialize into an
-        // arbitrary bytearray (and I'd like to avoid using a HashMap)
+        // The message-passing args are mostly ignored (except for the XCMP weight) but I can't
+        // figure out a cleaner way to deserialize; you need to parse to the end and you can't
+        // serialize into an arbitrary bytearray (and I'd like to avoid using a HashMap)
         #[derive(Deserialize, Debug)]
         #[allow(non_snake_case)]
-        #[allow(dead_code)]
         struct XcmpArgs<'a> {
             pub messageHash: &'a str,
             pub weight: RefTimeContainer<'a>,
         }
         #[derive(Deserialize, Debug)]
         #[allow(non_snake_case)]
+        struct RawXcmMessageSentArgs<'a> {
+            pub messageHash: Option<&'a str>,
+        }
+        #[derive(Deserialize, Debug)]
+        #[allow(non_snake_case)]
         #[allow(dead_code)]
+        struct RawXcmpFailArgs<'a> {
+            pub messageHash: &'a str,
+            pub error: XcmErrorContainer<'a>,
+            pub weight: RefTimeContainer<'a>,
+        }
+        #[derive(Deserialize, Debug)]
+        #[allow(non_snake_case)]
         struct RefTimeContainer<'a> {
             pub refTime: &'a str,
         }
+        #[derive(Deserialize, Debug)]
+        #[allow(non_snake_case)]
+        struct XcmErrorContainer<'a> {
+            pub __kind: &'a str,
+        }
 
         #[derive(Deserialize, Debug)]
         #[allow(non_snake_case)]
@@ -255,12 +559,73 @@ impl<'de> Deserialize<'de> for Event {
             pub messageId: &'a str,
             pub outcome: OutcomeContainer<'a>,
         }
-        #[derive(Deserialize, Debug)]
-        #[allow(non_snake_case)]
-        #[allow(dead_code)]
+
+        // The Outcome is one of
+        // {__kind: "Complete", value: weight},
+        // {__kind: "Incomplete", value: [weight, {__kind: error}]}, or
+        // {__kind: "Error", value: {__kind: error}}
+        // and we only keep the error
+        #[derive(Debug)]
         struct OutcomeContainer<'a> {
-            pub __kind: &'a str,
-            pub value: &'a str,
+            pub error: Option<&'a str>,
+        }
+        impl<'de> Deserialize<'de> for OutcomeContainer<'de> {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct OutcomeVisitor;
+
+                impl<'de> de::Visitor<'de> for OutcomeVisitor {
+                    type Value = OutcomeContainer<'de>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("struct Outcome {__kind: _, value: _}")
+                    }
+
+                    // Like Event, this relies on __kind being ordered before value
+                    fn visit_map<V>(
+                        self,
+                        mut map: V,
+                    ) -> core::result::Result<OutcomeContainer<'de>, V::Error>
+                    where
+                        V: de::MapAccess<'de>,
+                    {
+                        let mut kind: Option<&'de str> = None;
+                        let mut error = None;
+                        while let Some(key) = map.next_key::<&'de str>()? {
+                            match key {
+                                "__kind" => kind = Some(map.next_value()?),
+                                "value" => {
+                                    error = match kind {
+                                        Some("Incomplete") => {
+                                            let (_, err): (de::IgnoredAny, XcmErrorContainer) =
+                                                map.next_value()?;
+                                            Some(err.__kind)
+                                        }
+                                        Some("Error") => {
+                                            let err: XcmErrorContainer = map.next_value()?;
+                                            Some(err.__kind)
+                                        }
+                                        Some(_) => {
+                                            let _: de::IgnoredAny = map.next_value()?;
+                                            None
+                                        }
+                                        None => return Err(de::Error::missing_field("__kind")),
+                                    };
+                                }
+                                _ => {
+                                    let _: de::IgnoredAny = map.next_value()?;
+                                }
+                            }
+                        }
+                        kind.ok_or_else(|| de::Error::missing_field("__kind"))?;
+                        Ok(OutcomeContainer { error })
+                    }
+                }
+
+                deserializer.deserialize_map(OutcomeVisitor)
+            }
         }
 
         #[derive(Debug)]
@@ -375,17 +740,64 @@ impl<'de> Deserialize<'de> for Event {
                                     let val: BalancesUpdateArgs = map.next_value()?;
                                     Some(Args::BalancesUpdateArgs(val))
                                 }
+                                Some(EventType::BalancesTransfer) => {
+                                    let val: BalancesTransferArgs = map.next_value()?;
+                                    Some(Args::BalancesTransfer(val))
+                                }
+                                Some(EventType::TransactionFeePaid) => {
+                                    let val: TransactionFeePaidArgs = map.next_value()?;
+                                    Some(Args::TransactionFeePaid(val))
+                                }
+                                Some(EventType::TreasuryDeposit) => {
+                                    let val: TreasuryDepositArgs = map.next_value()?;
+                                    Some(Args::TreasuryDeposit(val))
+                                }
                                 Some(EventType::Xcmp) => {
-                                    let _val: XcmpArgs = map.next_value()?;
-                                    Some(Args::Ignored)
+                                    let val: XcmpArgs = map.next_value()?;
+                                    Some(Args::XcmpSuccess(XcmpSuccessArgs {
+                                        message_hash: hex_str_to_message_hash::<V::Error>(
+                                            val.messageHash,
+                                        )?,
+                                        weight_used: val.weight.refTime.parse().map_err(|_| {
+                                            de::Error::custom("String to weight failed")
+                                        })?,
+                                    }))
+                                }
+                                Some(EventType::XcmpFail) => {
+                                    let val: RawXcmpFailArgs = map.next_value()?;
+                                    Some(Args::XcmpFail(XcmpFailArgs {
+                                        message_hash: hex_str_to_message_hash::<V::Error>(
+                                            val.messageHash,
+                                        )?,
+                                        error: val.error.__kind.to_string(),
+                                    }))
+                                }
+                                Some(EventType::XcmMessageSent) => {
+                                    let val: RawXcmMessageSentArgs = map.next_value()?;
+                                    Some(Args::XcmMessageSent(XcmMessageSentArgs {
+                                        message_hash: val
+                                            .messageHash
+                                            .map(hex_str_to_message_hash::<V::Error>)
+                                            .transpose()?,
+                                    }))
                                 }
                                 Some(EventType::Ump) => {
-                                    let _val: UmpArgs = map.next_value()?;
-                                    Some(Args::Ignored)
+                                    let val: UmpArgs = map.next_value()?;
+                                    Some(Args::XcmOutcome(XcmOutcomeArgs {
+                                        message_hash: hex_str_to_message_hash::<V::Error>(
+                                            val.messageId,
+                                        )?,
+                                        error: val.outcome.error.map(|err| err.to_string()),
+                                    }))
                                 }
                                 Some(EventType::Dmp) => {
-                                    let _val: DmpQueueArgs = map.next_value()?;
-                                    Some(Args::Ignored)
+                                    let val: DmpQueueArgs = map.next_value()?;
+                                    Some(Args::XcmOutcome(XcmOutcomeArgs {
+                                        message_hash: hex_str_to_message_hash::<V::Error>(
+                                            val.messageId,
+                                        )?,
+                                        error: val.outcome.error.map(|err| err.to_string()),
+                                    }))
                                 }
                                 None => {
                                     return Err(de::Error::missing_field("name"));
@@ -416,7 +828,12 @@ pub enum EventType {
     AssetsIssued,
     BalancesDeposit,
     BalancesWithdraw,
+    BalancesTransfer,
+    TransactionFeePaid,
+    TreasuryDeposit,
     Xcmp,
+    XcmpFail,
+    XcmMessageSent,
     Ump,
     Dmp,
 }
@@ -439,7 +856,14 @@ impl FromStr for EventType {
             "Assets.Issued" => Ok(Self::AssetsIssued),
             "Balances.Deposit" => Ok(Self::BalancesDeposit),
             "Balances.Withdraw" => Ok(Self::BalancesWithdraw),
+            "Balances.Transfer" => Ok(Self::BalancesTransfer),
+            "TransactionPayment.TransactionFeePaid" => Ok(Self::TransactionFeePaid),
+            "Treasury.Deposit" => Ok(Self::TreasuryDeposit),
             "XcmpQueue.Success" => Ok(Self::Xcmp),
+            "XcmpQueue.Fail" => Ok(Self::XcmpFail),
+            "XcmpQueue.XcmpMessageSent" | "ParachainSystem.UpwardMessageSent" => {
+                Ok(Self::XcmMessageSent)
+            }
             "Ump.ExecutedUpward" => Ok(Self::Ump),
             "DmpQueue.ExecutedDownward" => Ok(Self::Dmp),
             _ => Err(SubstrateError::UnknownEvent),
@@ -447,11 +871,20 @@ impl FromStr for EventType {
     }
 }
 
-#[derive(Deserialize, Debug)]
+// Built by hand in the Event deserializer (based on the event name)
+#[derive(Debug)]
 pub enum Args {
     AssetsIssued(AssetsIssuedArgs),
     BalancesUpdateArgs(BalancesUpdateArgs),
-    Ignored,
+    BalancesTransfer(BalancesTransferArgs),
+    TransactionFeePaid(TransactionFeePaidArgs),
+    TreasuryDeposit(TreasuryDepositArgs),
+    XcmpSuccess(XcmpSuccessArgs),
+    XcmpFail(XcmpFailArgs),
+    // Emitted on the source chain when an XCMP or UMP message is queued
+    XcmMessageSent(XcmMessageSentArgs),
+    // Ump.ExecutedUpward and DmpQueue.ExecutedDownward
+    XcmOutcome(XcmOutcomeArgs),
 }
 
 #[derive(Deserialize, Debug)]
@@ -474,6 +907,61 @@ pub struct BalancesUpdateArgs {
     pub who: UniversalAddress,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct BalancesTransferArgs {
+    #[serde(deserialize_with = "hex_str_to_universal_address")]
+    pub from: UniversalAddress,
+    #[serde(deserialize_with = "hex_str_to_universal_address")]
+    pub to: UniversalAddress,
+    #[serde(deserialize_with = "quoted_str_to_amount")]
+    pub amount: Amount,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+pub struct TransactionFeePaidArgs {
+    #[serde(deserialize_with = "hex_str_to_universal_address")]
+    pub who: UniversalAddress,
+    // Includes the tip
+    #[serde(deserialize_with = "quoted_str_to_amount")]
+    pub actualFee: Amount,
+    #[serde(deserialize_with = "quoted_str_to_amount")]
+    pub tip: Amount,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TreasuryDepositArgs {
+    #[serde(deserialize_with = "quoted_str_to_amount")]
+    pub value: Amount,
+}
+
+#[derive(Debug)]
+pub struct XcmpSuccessArgs {
+    pub message_hash: XcmMessageHash,
+    // refTime actually consumed by the message on this chain
+    pub weight_used: u64,
+}
+
+#[derive(Debug)]
+pub struct XcmpFailArgs {
+    pub message_hash: XcmMessageHash,
+    // XcmError variant name, e.g. "Barrier" or "TooExpensive"
+    pub error: String,
+}
+
+#[derive(Debug)]
+pub struct XcmMessageSentArgs {
+    // Older runtimes do not report the hash of upward messages
+    pub message_hash: Option<XcmMessageHash>,
+}
+
+#[derive(Debug)]
+pub struct XcmOutcomeArgs {
+    pub message_hash: XcmMessageHash,
+    // None if the message executed completely
+    pub error: Option<String>,
+}
+
 fn quoted_str_to_asset_id<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> core::result::Result<AssetId, D::Error> {
@@ -513,6 +1001,16 @@ fn hex_str_to_universal_address<'de, D: Deserializer<'de>>(
     }
 }
 
+fn hex_str_to_message_hash<E: de::Error>(string: &str) -> core::result::Result<XcmMessageHash, E> {
+    let hash_vec =
+        hex_string_to_vec(string).map_err(|_| de::Error::custom("Hex string to vec failed"))?;
+    if hash_vec.len() == 32 {
+        Ok(XcmMessageHash::from_slice(&hash_vec))
+    } else {
+        Err(de::Error::custom("Message hash is not 32 bytes"))
+    }
+}
+
 fn graphql_query<'a, 'b>(query_url: &'a str, nested_data: &'b str) -> Result<Vec<u8>> {
     let data = format!(r#"{{"query": "{{ {} }}" }}"#, nested_data).into_bytes();
     http_post_wrapper(query_url, data).map_err(|_| SubstrateError::RequestFailed)
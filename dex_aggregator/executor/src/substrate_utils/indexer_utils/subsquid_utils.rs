@@ -16,22 +16,21 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::string::String;
+use ink::prelude::{string::String, vec::Vec};
 
 use privadex_chain_metadata::{
     common::{
         Amount, BlockNum, ChainTokenId, Nonce, SubstrateExtrinsicHash, UniversalAddress,
-        UniversalTokenId,
+        UniversalTokenId, XcmMessageHash,
     },
     get_chain_info_from_chain_id, get_sovereign_account,
 };
 
+use privadex_execution_plan::execution_plan::XcmFailureKind;
+
 use super::super::common::{Result, SubstrateError};
 use super::{graphql_helper, xcm_transfer_lookup};
 
-// Querying gas fees from extrinsics is tricky (requires parsing events),
-// so we likely won't bother updating our initial gas estimates
-
 /// Interface for querying Substrate extrinsics and events from a Subsquid indexer
 pub struct SubstrateSubsquidUtils {
     pub subsquid_graphql_archive_url: String,
@@ -45,12 +44,42 @@ pub struct SubstrateFinalizedExtrinsicResult {
     pub extrinsic_index: Nonce,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstrateExtrinsicFeeResult {
+    pub fee_native: Amount,
+    // False if the runtime predates TransactionPayment.TransactionFeePaid. Then fee_native is
+    // only what went to the treasury (the rest is burned or paid to the block author), i.e. a
+    // lower bound on the fee
+    pub is_exact: bool,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub struct SubstrateXCMTransferEventResult {
     pub block_num: BlockNum,
     pub event_index: Nonce,
     pub amount_out: Amount,
+    // Weight the message consumed on the destination chain. Only XCMP events report it
+    pub weight_used: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstrateXCMFailureEventResult {
+    pub block_num: BlockNum,
+    pub event_index: Nonce,
+    pub failure_kind: XcmFailureKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubstrateRemarkDepositResult {
+    pub block_num: BlockNum,
+    pub extrinsic_index: Nonce,
+    // Sum of the extrinsic's balances transfers from the remark's sender to the deposit account.
+    // Never 0, since a remark without one is not a deposit
+    pub amount_in: Amount,
 }
 
 impl SubstrateSubsquidUtils {
@@ -85,7 +114,7 @@ impl SubstrateSubsquidUtils {
         max_block: BlockNum,
         extrinsic_hash: &SubstrateExtrinsicHash,
     ) -> Result<SubstrateFinalizedExtrinsicResult> {
-        ink_env::debug_println!("[Mock Substrate lookup_extrinsic_by_hash]");
+        ink::env::debug_println!("[Mock Substrate lookup_extrinsic_by_hash]");
         // unsafe {
         //     static mut x: u32 = 0;
         //     if x < 2 {
@@ -109,6 +138,7 @@ impl SubstrateSubsquidUtils {
         dest_token: UniversalTokenId,
         amount: Amount,
         dest_addr: UniversalAddress,
+        message_hash: Option<&XcmMessageHash>,
     ) -> Result<SubstrateXCMTransferEventResult> {
         let xcm_lookup = xcm_transfer_lookup::XCMTransferLookup::from_tokens_amount_addr(
             src_token, dest_token, amount, dest_addr,
@@ -119,6 +149,13 @@ impl SubstrateSubsquidUtils {
             max_block,
             &xcm_lookup,
         )?;
+        if let Some(message_hash) = message_hash {
+            return Self::process_xcm_event_transfer_by_message_hash(
+                &xcm_lookup,
+                &all_blocks,
+                message_hash,
+            );
+        }
         match xcm_lookup.token_pallet {
             xcm_transfer_lookup::TokenPallet::Asset => {
                 Self::process_xcm_event_transfer_asset(&xcm_lookup, &all_blocks)
@@ -137,8 +174,9 @@ impl SubstrateSubsquidUtils {
         dest_token: UniversalTokenId,
         amount: Amount,
         dest_addr: UniversalAddress,
+        message_hash: Option<&XcmMessageHash>,
     ) -> Result<SubstrateXCMTransferEventResult> {
-        ink_env::debug_println!("[Mock Substrate lookup_xcm_event_transfer]");
+        ink::env::debug_println!("[Mock Substrate lookup_xcm_event_transfer]");
         // Cheap way to allow multiple not found periods
         // unsafe {
         //     static mut x: u32 = 0;
@@ -151,14 +189,363 @@ impl SubstrateSubsquidUtils {
             block_num: max_block,
             event_index: 0,
             amount_out: amount,
+            weight_used: None,
         })
     }
 
+    // Looks up the XCM message hash emitted by a (finalized) XCMP or UMP transfer extrinsic on
+    // this (the source) chain. The destination chain reports the same hash when it executes the
+    // message. The relay chain does not emit the hash of downward messages
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn lookup_xcm_message_hash(
+        &self,
+        block_num: BlockNum,
+        extrinsic_index: Nonce,
+    ) -> Result<XcmMessageHash> {
+        let events = graphql_helper::xcm_message_sent_lookup_call(
+            &self.subsquid_graphql_archive_url,
+            block_num,
+            extrinsic_index,
+        )?;
+        events
+            .iter()
+            .find_map(|event| match &event.args {
+                graphql_helper::Args::XcmMessageSent(args) => args.message_hash,
+                _ => None,
+            })
+            .ok_or(SubstrateError::NotFound)
+    }
+    #[cfg(feature = "mock-txn-send")]
+    pub fn lookup_xcm_message_hash(
+        &self,
+        _block_num: BlockNum,
+        _extrinsic_index: Nonce,
+    ) -> Result<XcmMessageHash> {
+        ink::env::debug_println!("[Mock Substrate lookup_xcm_message_hash]");
+        Err(SubstrateError::NotFound)
+    }
+
+    // The fee the extrinsic actually paid (including the tip). Failed extrinsics pay too
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn lookup_extrinsic_fee(
+        &self,
+        block_num: BlockNum,
+        extrinsic_index: Nonce,
+    ) -> Result<SubstrateExtrinsicFeeResult> {
+        let events = graphql_helper::extrinsic_fee_lookup_call(
+            &self.subsquid_graphql_archive_url,
+            block_num,
+            extrinsic_index,
+        )?;
+        Self::process_extrinsic_fee_events(&events)
+    }
+    #[cfg(feature = "mock-txn-send")]
+    pub fn lookup_extrinsic_fee(
+        &self,
+        _block_num: BlockNum,
+        _extrinsic_index: Nonce,
+    ) -> Result<SubstrateExtrinsicFeeResult> {
+        ink::env::debug_println!("[Mock Substrate lookup_extrinsic_fee]");
+        Err(SubstrateError::NotFound)
+    }
+
+    // Lets us tell "not found" apart from "not indexed yet"
+    pub fn get_indexed_height(&self) -> Result<BlockNum> {
+        graphql_helper::squid_status_call(&self.subsquid_graphql_archive_url)
+    }
+
+    // The indexer's record of the extrinsic (its call, fee and events) as raw JSON
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn lookup_raw_extrinsic(
+        &self,
+        block_num: BlockNum,
+        extrinsic_index: Nonce,
+    ) -> Result<Vec<u8>> {
+        graphql_helper::extrinsic_raw_lookup_call(
+            &self.subsquid_graphql_archive_url,
+            block_num,
+            extrinsic_index,
+        )
+    }
+    #[cfg(feature = "mock-txn-send")]
+    pub fn lookup_raw_extrinsic(
+        &self,
+        _block_num: BlockNum,
+        _extrinsic_index: Nonce,
+    ) -> Result<Vec<u8>> {
+        ink::env::debug_println!("[Mock Substrate lookup_raw_extrinsic]");
+        Err(SubstrateError::NotFound)
+    }
+
+    // Finds the extrinsic (the user's batch) that remarked a remark with this hash, and what the
+    // remark's sender transferred to deposit_addr in it. The remark is public once sent, so a
+    // remark whose sender transferred nothing there is ignored. Of the rest, the first one that
+    // transferred expected_amount wins, else the first one
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn lookup_remark_deposit(
+        &self,
+        min_block: BlockNum,
+        max_block: BlockNum,
+        remark_hash: &[u8; 32],
+        deposit_addr: &UniversalAddress,
+        expected_amount: Option<Amount>,
+    ) -> Result<SubstrateRemarkDepositResult> {
+        let remarked_events = graphql_helper::remark_event_lookup_call(
+            &self.subsquid_graphql_archive_url,
+            min_block,
+            max_block,
+            remark_hash,
+        )?;
+        let mut deposits: Vec<SubstrateRemarkDepositResult> = Vec::new();
+        for remarked_event in remarked_events.iter() {
+            let block_num = remarked_event.block.height;
+            let extrinsic_index = remarked_event.extrinsic.indexInBlock;
+            let transfer_events = graphql_helper::balances_transfer_lookup_call(
+                &self.subsquid_graphql_archive_url,
+                block_num,
+                extrinsic_index,
+            )?;
+            let amount_in = Self::sum_remark_deposit_transfers(
+                &transfer_events,
+                &remarked_event.args.sender,
+                deposit_addr,
+            );
+            if amount_in == 0 {
+                continue;
+            }
+            let deposit = SubstrateRemarkDepositResult {
+                block_num,
+                extrinsic_index,
+                amount_in,
+            };
+            if Some(amount_in) == expected_amount {
+                return Ok(deposit);
+            }
+            deposits.push(deposit);
+        }
+        deposits.into_iter().next().ok_or(SubstrateError::NotFound)
+    }
+    #[cfg(feature = "mock-txn-send")]
+    pub fn lookup_remark_deposit(
+        &self,
+        _min_block: BlockNum,
+        _max_block: BlockNum,
+        _remark_hash: &[u8; 32],
+        _deposit_addr: &UniversalAddress,
+        _expected_amount: Option<Amount>,
+    ) -> Result<SubstrateRemarkDepositResult> {
+        ink::env::debug_println!("[Mock Substrate lookup_remark_deposit]");
+        Err(SubstrateError::NotFound)
+    }
+
+    // Only a failure of this message matches. The failure events name neither the sender nor the
+    // amount, so any other failure in the block range could be someone else's
+    #[cfg(not(feature = "mock-txn-send"))]
+    pub fn lookup_xcm_failure_event(
+        &self,
+        min_block: BlockNum,
+        max_block: BlockNum,
+        src_token: UniversalTokenId,
+        dest_token: UniversalTokenId,
+        message_hash: &XcmMessageHash,
+    ) -> Result<SubstrateXCMFailureEventResult> {
+        let msg_pass_direction =
+            xcm_transfer_lookup::MessagePassingDirection::from_tokens(&src_token, &dest_token)?;
+        let all_blocks = graphql_helper::xcm_failure_event_lookup_call(
+            &self.subsquid_graphql_archive_url,
+            min_block,
+            max_block,
+            &msg_pass_direction,
+        )?;
+        for block in all_blocks.iter() {
+            for event in block.events.iter() {
+                let failure = match &event.args {
+                    graphql_helper::Args::XcmpFail(args) => {
+                        Some((&args.message_hash, args.error.as_str()))
+                    }
+                    graphql_helper::Args::XcmOutcome(args) => args
+                        .error
+                        .as_ref()
+                        .map(|error| (&args.message_hash, error.as_str())),
+                    _ => None,
+                };
+                if let Some((failed_message_hash, error)) = failure {
+                    if message_hash == failed_message_hash {
+                        return Ok(SubstrateXCMFailureEventResult {
+                            block_num: block.height,
+                            event_index: event.index_in_block,
+                            failure_kind: XcmFailureKind::from_xcm_error_name(error),
+                        });
+                    }
+                }
+            }
+        }
+        Err(SubstrateError::NotFound)
+    }
+    #[cfg(feature = "mock-txn-send")]
+    pub fn lookup_xcm_failure_event(
+        &self,
+        _min_block: BlockNum,
+        _max_block: BlockNum,
+        _src_token: UniversalTokenId,
+        _dest_token: UniversalTokenId,
+        _message_hash: &XcmMessageHash,
+    ) -> Result<SubstrateXCMFailureEventResult> {
+        ink::env::debug_println!("[Mock Substrate lookup_xcm_failure_event]");
+        Err(SubstrateError::NotFound)
+    }
+
+    fn process_extrinsic_fee_events(
+        events: &[graphql_helper::Event],
+    ) -> Result<SubstrateExtrinsicFeeResult> {
+        let fee_paid = events.iter().find_map(|event| match &event.args {
+            graphql_helper::Args::TransactionFeePaid(args) => Some(args.actualFee),
+            _ => None,
+        });
+        if let Some(fee_native) = fee_paid {
+            return Ok(SubstrateExtrinsicFeeResult {
+                fee_native,
+                is_exact: true,
+            });
+        }
+        let treasury_deposits: Vec<Amount> = events
+            .iter()
+            .filter_map(|event| match &event.args {
+                graphql_helper::Args::TreasuryDeposit(args) => Some(args.value),
+                _ => None,
+            })
+            .collect();
+        if treasury_deposits.is_empty() {
+            Err(SubstrateError::NotFound)
+        } else {
+            Ok(SubstrateExtrinsicFeeResult {
+                fee_native: treasury_deposits.iter().sum(),
+                is_exact: false,
+            })
+        }
+    }
+
+    // Only the remark's sender can have made its deposit. Anyone else who remarked the same hash
+    // would otherwise claim the transfers of whoever shares its extrinsic
+    fn sum_remark_deposit_transfers(
+        transfer_events: &[graphql_helper::Event],
+        sender: &UniversalAddress,
+        deposit_addr: &UniversalAddress,
+    ) -> Amount {
+        transfer_events
+            .iter()
+            .filter_map(|event| match &event.args {
+                graphql_helper::Args::BalancesTransfer(args)
+                    if &args.from == sender && &args.to == deposit_addr =>
+                {
+                    Some(args.amount)
+                }
+                _ => None,
+            })
+            .sum()
+    }
+
+    // The message-passing event follows the deposit events it caused, so the first one after
+    // our (origin and amount matched) deposit is our message's. Any later one is another
+    // message's, so if ours reports no weight we don't take a later one's
+    fn get_xcmp_weight_used(events: &[graphql_helper::Event], after_index: Nonce) -> Option<u64> {
+        let msg_pass_event = events.iter().find(|event| {
+            event.index_in_block > after_index
+                && matches!(
+                    event.args,
+                    graphql_helper::Args::XcmpSuccess(_) | graphql_helper::Args::XcmOutcome(_)
+                )
+        })?;
+        match &msg_pass_event.args {
+            graphql_helper::Args::XcmpSuccess(args) => Some(args.weight_used),
+            _ => None,
+        }
+    }
+
+    // Events are returned in block order and the deposits caused by a message precede its
+    // message-passing event, so the deposits for our message are the ones between the previous
+    // message-passing event and ours. Unlike matching by amount this is unambiguous when
+    // several similar transfers land in the same block, and it tolerates the destination
+    // chain charging a different fee than we expected
+    fn process_xcm_event_transfer_by_message_hash(
+        xcm_lookup: &xcm_transfer_lookup::XCMTransferLookup,
+        all_blocks: &[graphql_helper::Block],
+        message_hash: &XcmMessageHash,
+    ) -> Result<SubstrateXCMTransferEventResult> {
+        for block in all_blocks.iter() {
+            let e = &block.events;
+            let msg_pass_idx = e.iter().position(|event| match &event.args {
+                graphql_helper::Args::XcmpSuccess(args) => &args.message_hash == message_hash,
+                graphql_helper::Args::XcmOutcome(args) => {
+                    &args.message_hash == message_hash && args.error.is_none()
+                }
+                _ => false,
+            });
+            let msg_pass_idx = match msg_pass_idx {
+                Some(idx) => idx,
+                None => continue,
+            };
+            let msg_start_idx = e[..msg_pass_idx]
+                .iter()
+                .rposition(|event| {
+                    matches!(
+                        event.args,
+                        graphql_helper::Args::XcmpSuccess(_) | graphql_helper::Args::XcmOutcome(_)
+                    )
+                })
+                .map_or(0, |idx| idx + 1);
+
+            let deposit = e[msg_start_idx..msg_pass_idx].iter().find_map(|event| {
+                match (&xcm_lookup.token_pallet, &event.args) {
+                    (
+                        xcm_transfer_lookup::TokenPallet::Asset,
+                        graphql_helper::Args::AssetsIssued(args),
+                    ) => {
+                        let is_correct_asset = match &xcm_lookup.dest_token.id {
+                            ChainTokenId::XC20(token) => token.get_asset_id() == args.assetId,
+                            _ => false,
+                        };
+                        if is_correct_asset && args.owner == xcm_lookup.dest_addr {
+                            Some((event.index_in_block, args.totalSupply))
+                        } else {
+                            None
+                        }
+                    }
+                    (
+                        xcm_transfer_lookup::TokenPallet::Balance,
+                        graphql_helper::Args::BalancesUpdateArgs(args),
+                    ) if event.name == graphql_helper::EventType::BalancesDeposit => {
+                        if args.who == xcm_lookup.dest_addr {
+                            Some((event.index_in_block, args.amount))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                }
+            });
+            // The message was executed but did not pay out to our beneficiary, which
+            // should not happen since we built the message
+            let (event_index, amount_out) = deposit.ok_or(SubstrateError::InvalidXcmLookup)?;
+            let weight_used = match &e[msg_pass_idx].args {
+                graphql_helper::Args::XcmpSuccess(args) => Some(args.weight_used),
+                _ => None,
+            };
+            return Ok(SubstrateXCMTransferEventResult {
+                block_num: block.height,
+                event_index,
+                amount_out,
+                weight_used,
+            });
+        }
+        Err(SubstrateError::NotFound)
+    }
+
     fn process_xcm_event_transfer_asset(
         xcm_lookup: &xcm_transfer_lookup::XCMTransferLookup,
         all_blocks: &[graphql_helper::Block],
     ) -> Result<SubstrateXCMTransferEventResult> {
-        ink_env::debug_println!("Blocks: {:?}", all_blocks);
+        ink::env::debug_println!("Blocks: {:?}", all_blocks);
         let msg_pass_event = graphql_helper::EventType::from(&xcm_lookup.msg_pass_direction);
 
         for block in all_blocks.iter() {
@@ -197,6 +584,7 @@ impl SubstrateSubsquidUtils {
                                 block_num: block.height,
                                 event_index: e[i].index_in_block,
                                 amount_out: args1.totalSupply,
+                                weight_used: Self::get_xcmp_weight_used(e, e[i].index_in_block),
                             });
                         }
                     }
@@ -210,7 +598,7 @@ impl SubstrateSubsquidUtils {
         xcm_lookup: &xcm_transfer_lookup::XCMTransferLookup,
         all_blocks: &[graphql_helper::Block],
     ) -> Result<SubstrateXCMTransferEventResult> {
-        ink_env::debug_println!("Blocks: {:?}", all_blocks);
+        ink::env::debug_println!("Blocks: {:?}", all_blocks);
         let dest_chain_info = get_chain_info_from_chain_id(&xcm_lookup.dest_token.chain)
             .ok_or(SubstrateError::InvalidXcmLookup)?;
         let sovereign_account = get_sovereign_account(xcm_lookup.src_token.chain, dest_chain_info)
@@ -250,6 +638,7 @@ impl SubstrateSubsquidUtils {
                                 block_num: block.height,
                                 event_index: e[i].index_in_block,
                                 amount_out: args2.amount,
+                                weight_used: Self::get_xcmp_weight_used(e, e[i].index_in_block),
                             });
                         }
                     }
@@ -279,7 +668,10 @@ mod subsquid_utils_tests {
 
     fn get_subutils(chain_info: &ChainInfo) -> SubstrateSubsquidUtils {
         SubstrateSubsquidUtils {
-            subsquid_graphql_archive_url: chain_info.subsquid_graphql_archive_url.to_string(),
+            subsquid_graphql_archive_url: chain_info
+                .get_subsquid_graphql_archive_url()
+                .expect("Subsquid archive")
+                .to_string(),
         }
     }
 
@@ -365,11 +757,39 @@ mod subsquid_utils_tests {
                 UniversalAddress::Ethereum(EthAddress {
                     0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
                 }),
+                None,
+            )
+            .expect("Expected results");
+        assert_eq!(event_result.block_num, 2_497_827);
+        assert_eq!(event_result.amount_out, 20_140_552_627_375_819);
+        assert_eq!(event_result.event_index, 661);
+    }
+
+    #[test]
+    fn test_xcmp_asset_transfer_event_lookup_by_message_hash() {
+        // Same transfer as above, but we match on the XcmpQueue.Success message hash so the
+        // amount is not used
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let message_hash = XcmMessageHash::from(hex!(
+            "a367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c"
+        ));
+        let event_result = get_subutils(&MOONBEAM_INFO)
+            .lookup_xcm_event_transfer(
+                2_497_800,
+                2_497_900,
+                universal_token_id_registry::ASTR_NATIVE,
+                universal_token_id_registry::ASTR_MOONBEAM,
+                1,
+                UniversalAddress::Ethereum(EthAddress {
+                    0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
+                }),
+                Some(&message_hash),
             )
             .expect("Expected results");
         assert_eq!(event_result.block_num, 2_497_827);
         assert_eq!(event_result.amount_out, 20_140_552_627_375_819);
         assert_eq!(event_result.event_index, 661);
+        assert_eq!(event_result.weight_used, Some(800_000_000));
     }
 
     #[test]
@@ -387,6 +807,7 @@ mod subsquid_utils_tests {
                 UniversalAddress::Substrate(SubstratePublicKey {
                     0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
                 }),
+                None,
             )
             .expect("Expected results");
         assert_eq!(event_result.block_num, 2_527_187);
@@ -409,6 +830,7 @@ mod subsquid_utils_tests {
                 UniversalAddress::Substrate(SubstratePublicKey {
                     0: hex!("60b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a"),
                 }),
+                None,
             )
             .expect("Expected results");
         assert_eq!(event_result.block_num, 13_372_856);
@@ -434,6 +856,7 @@ mod subsquid_utils_tests {
                 "
                     ),
                 }),
+                None,
             )
             .expect("Expected results");
         assert_eq!(event_result.block_num, 2_514_195);
@@ -453,6 +876,7 @@ mod subsquid_utils_tests {
             UniversalAddress::Ethereum(EthAddress {
                 0: hex!("05a81d8564a3eA298660e34e03E5Eff9a29d7a2A"),
             }),
+            None,
         );
         assert_eq!(event_result, Err(SubstrateError::NotFound));
     }
@@ -464,6 +888,7 @@ mod subsquid_utils_tests {
         let balances_withdraw_event = "{\"name\":\"Balances.Withdraw\",\"indexInBlock\":31,\"args\":{\"amount\": \"39530582548\",\"who\":\"0x60b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a\"}}";
         let balances_deposit_event = "{\"name\":\"Balances.Deposit\",\"indexInBlock\":31,\"args\":{\"amount\": \"40000000000\",\"who\":\"0x60b94741c7094ac2820cceebeb24720af9e1049d7d4cb215f5080fbf5bdcbd4a\"}}";
         let xcmp_success_event = "{\"name\":\"XcmpQueue.Success\",\"indexInBlock\":663,\"args\":{\"messageHash\":\"0xa367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c\",\"weight\":{\"refTime\":\"800000000\"}}}";
+        let xcmp_fail_event = "{\"name\":\"XcmpQueue.Fail\",\"indexInBlock\":12,\"args\":{\"messageHash\":\"0xa367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c\",\"error\":{\"__kind\":\"Barrier\"},\"weight\":{\"refTime\":\"0\"}}}";
         let ump_executed_event = "{\"name\":\"Ump.ExecutedUpward\",\"indexInBlock\":35,\"args\":[\"0x0ec6dc35ff782af7a75e486524970fac6d3f07dc49564d5998842d7caf7da006\",{\"__kind\":\"Complete\",\"value\":\"4000000000\"}]}";
         let dmp_executed_event = "{\"name\": \"DmpQueue.ExecutedDownward\",\"indexInBlock\":8,\"args\":{\"messageId\":\"0x239aedd60a367e72b3fb95c34b55e096ceefd6910ec7a11866e99c5c06885ba0\",\"outcome\":{\"__kind\":\"Complete\",\"value\":\"4000000000\"}}}";
 
@@ -472,15 +897,163 @@ mod subsquid_utils_tests {
             balances_withdraw_event,
             balances_deposit_event,
             xcmp_success_event,
+            xcmp_fail_event,
             ump_executed_event,
             dmp_executed_event,
         ]
         .into_iter()
         {
-            ink_env::debug_println!("Will decode {}...", event);
+            ink::env::debug_println!("Will decode {}...", event);
             let (decoded, _): (graphql_helper::Event, usize) =
                 serde_json_core::from_slice(event.as_bytes()).expect("deserialize failed");
-            ink_env::debug_println!("Decoded: {:?}\n", decoded);
+            ink::env::debug_println!("Decoded: {:?}\n", decoded);
         }
     }
+
+    #[test]
+    fn test_xcmp_event_args_deserialization() {
+        let xcmp_success_event = "{\"name\":\"XcmpQueue.Success\",\"indexInBlock\":663,\"args\":{\"messageHash\":\"0xa367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c\",\"weight\":{\"refTime\":\"800000000\"}}}";
+        let (decoded, _): (graphql_helper::Event, usize) =
+            serde_json_core::from_slice(xcmp_success_event.as_bytes()).expect("deserialize failed");
+        match decoded.args {
+            graphql_helper::Args::XcmpSuccess(args) => assert_eq!(args.weight_used, 800_000_000),
+            _ => panic!("Expected XcmpSuccess args"),
+        }
+
+        let xcmp_fail_event = "{\"name\":\"XcmpQueue.Fail\",\"indexInBlock\":12,\"args\":{\"messageHash\":\"0xa367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c\",\"error\":{\"__kind\":\"TooExpensive\"},\"weight\":{\"refTime\":\"0\"}}}";
+        let (decoded, _): (graphql_helper::Event, usize) =
+            serde_json_core::from_slice(xcmp_fail_event.as_bytes()).expect("deserialize failed");
+        match decoded.args {
+            graphql_helper::Args::XcmpFail(args) => assert_eq!(
+                XcmFailureKind::from_xcm_error_name(&args.error),
+                XcmFailureKind::TooExpensive
+            ),
+            _ => panic!("Expected XcmpFail args"),
+        }
+    }
+
+    #[test]
+    fn test_xcm_outcome_error_deserialization() {
+        let dmp_incomplete_event = "{\"name\": \"DmpQueue.ExecutedDownward\",\"indexInBlock\":8,\"args\":{\"messageId\":\"0x239aedd60a367e72b3fb95c34b55e096ceefd6910ec7a11866e99c5c06885ba0\",\"outcome\":{\"__kind\":\"Incomplete\",\"value\":[\"4000000000\",{\"__kind\":\"TooExpensive\"}]}}}";
+        let (decoded, _): (graphql_helper::Event, usize) =
+            serde_json_core::from_slice(dmp_incomplete_event.as_bytes())
+                .expect("deserialize failed");
+        match decoded.args {
+            graphql_helper::Args::XcmOutcome(args) => {
+                assert_eq!(
+                    args.message_hash,
+                    XcmMessageHash::from(hex!(
+                        "239aedd60a367e72b3fb95c34b55e096ceefd6910ec7a11866e99c5c06885ba0"
+                    ))
+                );
+                assert_eq!(args.error.as_deref(), Some("TooExpensive"));
+            }
+            _ => panic!("Expected XcmOutcome args"),
+        }
+
+        let ump_error_event = "{\"name\":\"Ump.ExecutedUpward\",\"indexInBlock\":35,\"args\":[\"0x0ec6dc35ff782af7a75e486524970fac6d3f07dc49564d5998842d7caf7da006\",{\"__kind\":\"Error\",\"value\":{\"__kind\":\"Barrier\"}}]}";
+        let (decoded, _): (graphql_helper::Event, usize) =
+            serde_json_core::from_slice(ump_error_event.as_bytes()).expect("deserialize failed");
+        match decoded.args {
+            graphql_helper::Args::XcmOutcome(args) => {
+                assert_eq!(args.error.as_deref(), Some("Barrier"))
+            }
+            _ => panic!("Expected XcmOutcome args"),
+        }
+
+        let ump_complete_event = "{\"name\":\"Ump.ExecutedUpward\",\"indexInBlock\":35,\"args\":[\"0x0ec6dc35ff782af7a75e486524970fac6d3f07dc49564d5998842d7caf7da006\",{\"__kind\":\"Complete\",\"value\":\"4000000000\"}]}";
+        let (decoded, _): (graphql_helper::Event, usize) =
+            serde_json_core::from_slice(ump_complete_event.as_bytes()).expect("deserialize failed");
+        match decoded.args {
+            graphql_helper::Args::XcmOutcome(args) => assert_eq!(args.error, None),
+            _ => panic!("Expected XcmOutcome args"),
+        }
+    }
+
+    #[test]
+    fn test_xcmp_weight_used() {
+        let decode = |event: &str| -> graphql_helper::Event {
+            let (decoded, _): (graphql_helper::Event, usize) =
+                serde_json_core::from_slice(event.as_bytes()).expect("deserialize failed");
+            decoded
+        };
+        let ump_complete_event = "{\"name\":\"Ump.ExecutedUpward\",\"indexInBlock\":35,\"args\":[\"0x0ec6dc35ff782af7a75e486524970fac6d3f07dc49564d5998842d7caf7da006\",{\"__kind\":\"Complete\",\"value\":\"4000000000\"}]}";
+        let xcmp_success_event = "{\"name\":\"XcmpQueue.Success\",\"indexInBlock\":663,\"args\":{\"messageHash\":\"0xa367aeaf94deea8e4c03a90edafda41a0cddc45464859021d2c51dab5399af3c\",\"weight\":{\"refTime\":\"800000000\"}}}";
+
+        let events = [decode(ump_complete_event), decode(xcmp_success_event)];
+        assert_eq!(
+            SubstrateSubsquidUtils::get_xcmp_weight_used(&events, 40),
+            Some(800_000_000)
+        );
+        // A deposit at 30 was caused by the message at 35, not by the later XCMP message
+        assert_eq!(
+            SubstrateSubsquidUtils::get_xcmp_weight_used(&events, 30),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extrinsic_fee_events() {
+        let decode = |event: &str| -> graphql_helper::Event {
+            let (decoded, _): (graphql_helper::Event, usize) =
+                serde_json_core::from_slice(event.as_bytes()).expect("deserialize failed");
+            decoded
+        };
+        let treasury_deposit_event =
+            "{\"name\":\"Treasury.Deposit\",\"indexInBlock\":9,\"args\":{\"value\":\"160000000\"}}";
+        let fee_paid_event = "{\"name\":\"TransactionPayment.TransactionFeePaid\",\"indexInBlock\":10,\"args\":{\"who\":\"0x5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be\",\"actualFee\":\"205000000\",\"tip\":\"5000000\"}}";
+
+        let events = [decode(treasury_deposit_event), decode(fee_paid_event)];
+        assert_eq!(
+            SubstrateSubsquidUtils::process_extrinsic_fee_events(&events),
+            Ok(SubstrateExtrinsicFeeResult {
+                fee_native: 205_000_000,
+                is_exact: true,
+            })
+        );
+
+        // Older runtimes only tell us the treasury's share
+        let events = [decode(treasury_deposit_event)];
+        assert_eq!(
+            SubstrateSubsquidUtils::process_extrinsic_fee_events(&events),
+            Ok(SubstrateExtrinsicFeeResult {
+                fee_native: 160_000_000,
+                is_exact: false,
+            })
+        );
+
+        assert_eq!(
+            SubstrateSubsquidUtils::process_extrinsic_fee_events(&[]),
+            Err(SubstrateError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_remark_deposit_transfers() {
+        let decode = |event: &str| -> graphql_helper::Event {
+            let (decoded, _): (graphql_helper::Event, usize) =
+                serde_json_core::from_slice(event.as_bytes()).expect("deserialize failed");
+            decoded
+        };
+        let sender = UniversalAddress::Substrate(SubstratePublicKey {
+            0: hex!("5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be"),
+        });
+        let escrow = UniversalAddress::Substrate(SubstratePublicKey {
+            0: hex!("7011b670bb662eedbd60a1c4c11b7c197ec22e7cfe87df00013ca2c494f3b01a"),
+        });
+        let sender_transfer_event = "{\"name\":\"Balances.Transfer\",\"indexInBlock\":3,\"args\":{\"from\":\"0x5134c7f0e31c2a9e19dceddb7403b2836c69cce0b0719d2f58ec0d4da35129be\",\"to\":\"0x7011b670bb662eedbd60a1c4c11b7c197ec22e7cfe87df00013ca2c494f3b01a\",\"amount\":\"1000000\"}}";
+        let other_transfer_event = "{\"name\":\"Balances.Transfer\",\"indexInBlock\":4,\"args\":{\"from\":\"0x0c2d5a5ac68a2ea1a2be5e57ea9bc9ee9c2afc4b9d0da3d4e7a84c4a4a0e0a7f\",\"to\":\"0x7011b670bb662eedbd60a1c4c11b7c197ec22e7cfe87df00013ca2c494f3b01a\",\"amount\":\"5000000\"}}";
+
+        let events = [decode(sender_transfer_event), decode(other_transfer_event)];
+        assert_eq!(
+            SubstrateSubsquidUtils::sum_remark_deposit_transfers(&events, &sender, &escrow),
+            1_000_000
+        );
+        // A front-running remark whose sender sent nothing is not a deposit
+        let events = [decode(other_transfer_event)];
+        assert_eq!(
+            SubstrateSubsquidUtils::sum_remark_deposit_transfers(&events, &sender, &escrow),
+            0
+        );
+    }
 }
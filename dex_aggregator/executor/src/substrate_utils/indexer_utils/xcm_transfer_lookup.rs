@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::string::{String, ToString};
+use ink::prelude::string::{String, ToString};
 
 use privadex_chain_metadata::common::{Amount, ChainTokenId, UniversalAddress, UniversalTokenId};
 
@@ -42,26 +42,11 @@ impl XCMTransferLookup {
         amount: Amount,
         dest_addr: UniversalAddress,
     ) -> Result<Self> {
-        if (src_token.chain.get_relay() != dest_token.chain.get_relay())
-            || (src_token.chain == dest_token.chain)
-        {
-            return Err(SubstrateError::InvalidXcmLookup);
-        }
-
         let token_pallet = match dest_token.id {
             ChainTokenId::Native => TokenPallet::Balance,
             _ => TokenPallet::Asset,
         };
-        let msg_pass_direction = match (
-            src_token.chain.get_parachain_id().is_some(),
-            dest_token.chain.get_parachain_id().is_some(),
-        ) {
-            // (is_parachain, is_parachain)
-            (true, true) => Ok(MessagePassingDirection::Xcmp),
-            (true, false) => Ok(MessagePassingDirection::Ump),
-            (false, true) => Ok(MessagePassingDirection::Dmp),
-            (false, false) => Err(SubstrateError::InvalidXcmLookup),
-        }?;
+        let msg_pass_direction = MessagePassingDirection::from_tokens(&src_token, &dest_token)?;
 
         Ok(Self {
             src_token,
@@ -99,6 +84,27 @@ pub enum MessagePassingDirection {
 }
 
 impl MessagePassingDirection {
+    pub fn from_tokens(
+        src_token: &UniversalTokenId,
+        dest_token: &UniversalTokenId,
+    ) -> Result<Self> {
+        if !src_token.chain.shares_relay_with(&dest_token.chain)
+            || (src_token.chain == dest_token.chain)
+        {
+            return Err(SubstrateError::InvalidXcmLookup);
+        }
+        match (
+            src_token.chain.get_parachain_id().is_some(),
+            dest_token.chain.get_parachain_id().is_some(),
+        ) {
+            // (is_parachain, is_parachain)
+            (true, true) => Ok(Self::Xcmp),
+            (true, false) => Ok(Self::Ump),
+            (false, true) => Ok(Self::Dmp),
+            (false, false) => Err(SubstrateError::InvalidXcmLookup),
+        }
+    }
+
     // The name of the event that is produced on the destination chain
     pub fn event_success_name(&self) -> String {
         match self {
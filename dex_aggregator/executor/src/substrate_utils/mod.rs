@@ -16,6 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+pub mod asset_registry_utils;
 pub mod common;
 pub mod extrinsic_sig_config;
 pub mod indexer_utils;
@@ -16,13 +16,13 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_env::debug_println;
-use ink_prelude::{
+use ink::env::debug_println;
+use ink::prelude::{
     format,
     string::{String, ToString},
     vec::Vec,
 };
-use scale::{Compact, Decode, Encode};
+use scale::{Compact, Decode, Encode, Output};
 use serde::Deserialize;
 use sp_runtime::{
     generic::{Block as GenericBlock, Era, Header as GenericHeader},
@@ -30,7 +30,10 @@ use sp_runtime::{
     OpaqueExtrinsic,
 };
 
-use privadex_chain_metadata::common::{BlockHash, BlockNum, Nonce, SubstrateExtrinsicHash};
+use privadex_chain_metadata::{
+    chain_info::{AssetTxPaymentKind, ChainInfo},
+    common::{Amount, AssetId, BlockHash, BlockNum, Nonce, SubstrateExtrinsicHash},
+};
 use privadex_common::utils::{
     general_utils::{hex_string_to_vec as hex_string_to_vec_delegate, slice_to_hex_string},
     http_request::http_post_wrapper,
@@ -40,6 +43,8 @@ use super::{
     common::{Result, SubstrateError},
     extrinsic_sig_config::ExtrinsicSigConfig,
 };
+use crate::json_rpc_batch::{parse_batch_response, send_batch, JsonRpcCall, JsonRpcResponse};
+use crate::rpc_endpoints::{get_rpc_url, RpcEndpoint};
 
 pub struct SubstrateNodeRpcUtils {
     pub rpc_url: String,
@@ -81,7 +86,55 @@ struct StrRefRpcResponse<'a> {
     id: u32,
 }
 
+#[derive(Deserialize, Encode, Clone, Debug, PartialEq)]
+#[serde(bound(deserialize = "ink::prelude::vec::Vec<(&'a str, u32)>: Deserialize<'de>"))]
+#[allow(non_snake_case)] // camelCase allows for the derived deserialize to work out of the box
+struct RuntimeVersionResult<'a> {
+    specName: &'a str,
+    implName: &'a str,
+    authoringVersion: u32,
+    specVersion: u32,
+    implVersion: u32,
+    #[serde(borrow)]
+    apis: Vec<(&'a str, u32)>,
+    transactionVersion: u32,
+    stateVersion: u32,
+}
+
+impl From<RuntimeVersionResult<'_>> for RuntimeVersion {
+    fn from(runtime_version_result: RuntimeVersionResult) -> Self {
+        let mut api_vec: Vec<(String, u32)> = Vec::new();
+        for (api_str, api_u32) in runtime_version_result.apis {
+            api_vec.push((api_str.to_string().parse().unwrap(), api_u32));
+        }
+
+        Self {
+            spec_name: runtime_version_result.specName.to_string().parse().unwrap(),
+            impl_name: runtime_version_result.implName.to_string().parse().unwrap(),
+            authoring_version: runtime_version_result.authoringVersion,
+            spec_version: runtime_version_result.specVersion,
+            impl_version: runtime_version_result.implVersion,
+            apis: api_vec,
+            transaction_version: runtime_version_result.transactionVersion,
+            state_version: runtime_version_result.stateVersion,
+        }
+    }
+}
+
+// What create_extrinsic needs from the node
+pub struct ExtrinsicContext {
+    pub runtime_version: RuntimeVersion,
+    pub genesis_hash: BlockHash,
+    pub finalized_head_hash: BlockHash,
+}
+
 impl SubstrateNodeRpcUtils {
+    pub fn from_chain_info(chain_info: &ChainInfo, rpc_endpoints: &[RpcEndpoint]) -> Self {
+        Self {
+            rpc_url: get_rpc_url(rpc_endpoints, chain_info),
+        }
+    }
+
     pub fn get_next_system_nonce(&self, account_id: &str) -> Result<Nonce> {
         let data = format!(
             r#"{{"id":1,"jsonrpc":"2.0","method":"system_accountNextIndex","params":["{}"]}}"#,
@@ -105,45 +158,69 @@ impl SubstrateNodeRpcUtils {
             id: u32,
         }
 
-        #[derive(Deserialize, Encode, Clone, Debug, PartialEq)]
-        #[serde(bound(deserialize = "ink_prelude::vec::Vec<(&'a str, u32)>: Deserialize<'de>"))]
-        #[allow(non_snake_case)] // camelCase allows for the derived deserialize to work out of the box
-        struct RuntimeVersionResult<'a> {
-            specName: &'a str,
-            implName: &'a str,
-            authoringVersion: u32,
-            specVersion: u32,
-            implVersion: u32,
-            #[serde(borrow)]
-            apis: Vec<(&'a str, u32)>,
-            transactionVersion: u32,
-            stateVersion: u32,
-        }
-
         let data = r#"{"id":1, "jsonrpc":"2.0", "method": "state_getRuntimeVersion"}"#
             .to_string()
             .into_bytes();
         let resp_body = self.call_rpc(data)?;
         let (runtime_version, _): (RawRuntimeVersion, usize) =
             serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
-        let runtime_version_result = runtime_version.result;
-        let mut api_vec: Vec<(String, u32)> = Vec::new();
-        for (api_str, api_u32) in runtime_version_result.apis {
-            api_vec.push((api_str.to_string().parse().unwrap(), api_u32));
+        Ok(RuntimeVersion::from(runtime_version.result))
+    }
+
+    pub fn get_native_token_decimals(&self) -> Result<u8> {
+        #[derive(Deserialize, Debug)]
+        #[allow(non_snake_case)]
+        struct SystemProperties {
+            tokenDecimals: u8,
         }
 
-        let runtime_version = RuntimeVersion {
-            spec_name: runtime_version_result.specName.to_string().parse().unwrap(),
-            impl_name: runtime_version_result.implName.to_string().parse().unwrap(),
-            authoring_version: runtime_version_result.authoringVersion,
-            spec_version: runtime_version_result.specVersion,
-            impl_version: runtime_version_result.implVersion,
-            apis: api_vec,
-            transaction_version: runtime_version_result.transactionVersion,
-            state_version: runtime_version_result.stateVersion,
-        };
+        let data = r#"{"id":1, "jsonrpc":"2.0", "method": "system_properties"}"#
+            .to_string()
+            .into_bytes();
+        let resp_body = self.call_rpc(data)?;
+        let (properties, _): (RpcResponse<SystemProperties>, usize) =
+            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+        Ok(properties.result.tokenDecimals)
+    }
+
+    pub fn get_ss58_prefix(&self) -> Result<u16> {
+        #[derive(Deserialize, Debug)]
+        #[allow(non_snake_case)]
+        struct SystemProperties {
+            ss58Format: u16,
+        }
 
-        Ok(runtime_version)
+        let data = r#"{"id":1, "jsonrpc":"2.0", "method": "system_properties"}"#
+            .to_string()
+            .into_bytes();
+        let resp_body = self.call_rpc(data)?;
+        let (properties, _): (RpcResponse<SystemProperties>, usize) =
+            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+        Ok(properties.result.ss58Format)
+    }
+
+    // Same as get_runtime_version, get_genesis_hash and get_finalized_head_hash, in one round
+    // trip
+    pub fn get_extrinsic_context(&self) -> Result<ExtrinsicContext> {
+        let calls = [
+            JsonRpcCall::new("state_getRuntimeVersion", "[]"),
+            JsonRpcCall::new("chain_getBlockHash", "[0]"),
+            JsonRpcCall::new("chain_getFinalizedHead", "[]"),
+        ];
+        let resp_body =
+            send_batch(&self.rpc_url, &calls).map_err(|_| SubstrateError::RequestFailed)?;
+        let (runtime_version, genesis_hash, finalized_head_hash): (
+            JsonRpcResponse<RuntimeVersionResult>,
+            JsonRpcResponse<&str>,
+            JsonRpcResponse<&str>,
+        ) = parse_batch_response(&resp_body).map_err(|_| SubstrateError::InvalidBody)?;
+        Ok(ExtrinsicContext {
+            runtime_version: RuntimeVersion::from(runtime_version.result),
+            genesis_hash: BlockHash::from_slice(&hex_string_to_vec(genesis_hash.result)?),
+            finalized_head_hash: BlockHash::from_slice(&hex_string_to_vec(
+                finalized_head_hash.result,
+            )?),
+        })
     }
 
     pub fn get_block_hash(&self, block_number: u32) -> Result<BlockHash> {
@@ -169,25 +246,65 @@ impl SubstrateNodeRpcUtils {
             .to_string()
             .into_bytes();
         let resp_body = self.call_rpc(data)?;
-        let (finalized_head_hash, _): (StrRefRpcResponse, usize) =
-            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+        Self::parse_block_hash_response(&resp_body)
+    }
+
+    // Two round trips, since System.Number is read at the finalized head's hash
+    pub fn get_finalized_block_number(&self) -> Result<BlockNum> {
+        let finalized_head_hash = self.get_finalized_head_hash()?;
+        self.get_block_number_at(Some(&finalized_head_hash))
+    }
 
-        let v = hex_string_to_vec(finalized_head_hash.result)?;
+    // Ahead of the finalized block by a few blocks, which may still be reorged out
+    pub fn get_best_block_number(&self) -> Result<BlockNum> {
+        self.get_block_number_at(None)
+    }
+
+    // The calls behind get_finalized_block_number and get_best_block_number as bare calls, for
+    // callers that send them alongside other chains' calls (see
+    // BlockNumberProvider::get_block_numbers)
+    pub fn finalized_head_call() -> JsonRpcCall<'static> {
+        JsonRpcCall::new("chain_getFinalizedHead", "[]")
+    }
+
+    // At the best block if block_hash is None
+    pub fn block_number_call(block_hash: Option<&BlockHash>) -> JsonRpcCall<'static> {
+        let storage_key = get_storage_key("System", "Number");
+        let params = match block_hash {
+            Some(block_hash) => format!(
+                r#"["{}","{}"]"#,
+                storage_key,
+                slice_to_hex_string(&block_hash.0)
+            ),
+            None => format!(r#"["{}"]"#, storage_key),
+        };
+        JsonRpcCall::new("state_getStorage", &params)
+    }
+
+    pub fn parse_block_hash_response(resp_body: &[u8]) -> Result<BlockHash> {
+        let (block_hash, _): (StrRefRpcResponse, usize) =
+            serde_json_core::from_slice(resp_body).or(Err(SubstrateError::InvalidBody))?;
+        let v = hex_string_to_vec(block_hash.result)?;
+        if v.len() != BlockHash::len_bytes() {
+            return Err(SubstrateError::InvalidBody);
+        }
         Ok(BlockHash::from_slice(&v))
     }
 
-    pub fn get_finalized_block_number(&self) -> Result<BlockNum> {
-        // It is critical that the module and method are upper-cased to compute the correct storage key!
-        let resp_body = self.query_storage("System", "Number")?;
-        // debug_println!("Json string response: {:?}", String::from_utf8(resp_body.clone()));
+    pub fn parse_block_number_response(resp_body: &[u8]) -> Result<BlockNum> {
         // This is messy decoding, but we can clean this up later
         let (number_encoded, _): (StrRefRpcResponse, usize) =
-            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+            serde_json_core::from_slice(resp_body).or(Err(SubstrateError::InvalidBody))?;
         let number_bytes = hex_string_to_vec(number_encoded.result)?;
-        let number = <BlockNum as scale::Decode>::decode(&mut number_bytes.as_slice())
-            .map_err(|_| SubstrateError::InvalidBody)?;
+        <BlockNum as scale::Decode>::decode(&mut number_bytes.as_slice())
+            .map_err(|_| SubstrateError::InvalidBody)
+    }
 
-        Ok(number)
+    // At the best block if block_hash is None
+    fn get_block_number_at(&self, block_hash: Option<&BlockHash>) -> Result<BlockNum> {
+        // It is critical that the module and method are upper-cased to compute the correct storage key!
+        let resp_body = self.query_storage("System", "Number", block_hash)?;
+        Self::parse_block_number_response(&resp_body)
     }
 
     #[allow(dead_code)]
@@ -243,20 +360,99 @@ impl SubstrateNodeRpcUtils {
         Ok(signed_block.result)
     }
 
-    fn query_storage(&self, module: &str, method: &str) -> Result<Vec<u8>> {
-        let storage_key = {
-            let mut vec = Vec::new();
-            vec.extend(sp_core_hashing::twox_128(module.as_bytes()));
-            vec.extend(sp_core_hashing::twox_128(method.as_bytes()));
-            slice_to_hex_string(&vec)
-        };
+    fn query_storage(
+        &self,
+        module: &str,
+        method: &str,
+        block_hash: Option<&BlockHash>,
+    ) -> Result<Vec<u8>> {
+        let storage_key = get_storage_key(module, method);
         // debug_println!("Storage key: {:?}", &storage_key);
+        let data = match block_hash {
+            Some(block_hash) => format!(
+                r#"{{"id":1,"jsonrpc":"2.0","method":"state_getStorage","params":["{}","{}"]}}"#,
+                storage_key,
+                slice_to_hex_string(&block_hash.0)
+            ),
+            None => format!(
+                r#"{{"id":1,"jsonrpc":"2.0","method":"state_getStorage","params":["{}"]}}"#,
+                storage_key
+            ),
+        }
+        .into_bytes();
+        self.call_rpc(data)
+    }
+
+    // Free native balance from System.Account. An account that was never funded (or was
+    // reaped) has no entry, which is a balance of 0
+    pub fn get_free_balance(&self, pubkey: &[u8; 32]) -> Result<Amount> {
+        // frame_system::AccountInfo followed by pallet_balances::AccountData, which starts
+        // with the free balance. Decode ignores the trailing fields
+        #[derive(Decode)]
+        #[allow(dead_code)]
+        struct AccountInfoPrefix {
+            nonce: u32,
+            consumers: u32,
+            providers: u32,
+            sufficients: u32,
+            free: u128,
+        }
+
+        let mut storage_key = Vec::new();
+        storage_key.extend(sp_core_hashing::twox_128(b"System"));
+        storage_key.extend(sp_core_hashing::twox_128(b"Account"));
+        storage_key.extend(sp_core_hashing::blake2_128(pubkey));
+        storage_key.extend(pubkey);
+        match self.get_storage(&storage_key)? {
+            Some(encoded) => AccountInfoPrefix::decode(&mut encoded.as_slice())
+                .map(|account_info| account_info.free)
+                .map_err(|_| SubstrateError::InvalidBody),
+            None => Ok(0),
+        }
+    }
+
+    // Returns None if there is no value at this key
+    pub fn get_storage(&self, storage_key: &[u8]) -> Result<Option<Vec<u8>>> {
         let data = format!(
             r#"{{"id":1,"jsonrpc":"2.0","method":"state_getStorage","params":["{}"]}}"#,
-            storage_key
+            slice_to_hex_string(storage_key)
         )
         .into_bytes();
-        self.call_rpc(data)
+        let resp_body = self.call_rpc(data)?;
+        let (value_encoded, _): (RpcResponse<Option<&str>>, usize) =
+            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+        value_encoded.result.map(hex_string_to_vec).transpose()
+    }
+
+    // Returns up to `count` storage keys starting with `prefix`, after `start_key` if provided
+    pub fn get_storage_keys_paged(
+        &self,
+        prefix: &[u8],
+        count: u32,
+        start_key: Option<&[u8]>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let data = match start_key {
+            Some(start_key) => format!(
+                r#"{{"id":1,"jsonrpc":"2.0","method":"state_getKeysPaged","params":["{}",{},"{}"]}}"#,
+                slice_to_hex_string(prefix),
+                count,
+                slice_to_hex_string(start_key)
+            ),
+            None => format!(
+                r#"{{"id":1,"jsonrpc":"2.0","method":"state_getKeysPaged","params":["{}",{}]}}"#,
+                slice_to_hex_string(prefix),
+                count
+            ),
+        }
+        .into_bytes();
+        let resp_body = self.call_rpc(data)?;
+        let (keys_encoded, _): (RpcResponse<Vec<&str>>, usize) =
+            serde_json_core::from_slice(&resp_body).or(Err(SubstrateError::InvalidBody))?;
+        keys_encoded
+            .result
+            .into_iter()
+            .map(hex_string_to_vec)
+            .collect()
     }
 
     pub fn create_extrinsic<AccountId>(
@@ -268,8 +464,8 @@ impl SubstrateNodeRpcUtils {
         genesis_hash: BlockHash,
         checkpoint_block_hash: BlockHash,
         era: Era,
-        tip: u128,
-    ) -> Vec<u8>
+        tx_payment: TxPayment,
+    ) -> Result<Vec<u8>>
     where
         AccountId: Copy + Encode,
     {
@@ -277,7 +473,7 @@ impl SubstrateNodeRpcUtils {
         let extra = Extra {
             era,
             nonce: Compact(account_nonce),
-            tip: Compact(tip),
+            tx_payment,
         };
 
         // Construct our custom additional params.
@@ -296,7 +492,9 @@ impl SubstrateNodeRpcUtils {
             encoded_inner
         };
         // Construct signature
-        let encoded_signature = sigconfig.get_encoded_signature(encoded_payload);
+        let encoded_signature = sigconfig
+            .get_encoded_signature(encoded_payload)
+            .ok_or(SubstrateError::SignExtrinsicFailed)?;
 
         debug_println!(
             "Extrinsic head (isSigned + extrinsic version): {:?}",
@@ -329,7 +527,7 @@ impl SubstrateNodeRpcUtils {
             encoded
         };
 
-        extrinsic
+        Ok(extrinsic)
     }
 
     #[cfg(not(feature = "mock-txn-send"))]
@@ -341,7 +539,7 @@ impl SubstrateNodeRpcUtils {
         )
         .into_bytes();
         let resp_body = self.call_rpc(data)?;
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     "Json string response: {:?}",
         //     String::from_utf8(resp_body.clone())
         // );
@@ -353,7 +551,7 @@ impl SubstrateNodeRpcUtils {
 
     #[cfg(feature = "mock-txn-send")]
     pub fn send_extrinsic(&self, extrinsic_hash: &[u8]) -> Result<SubstrateExtrinsicHash> {
-        ink_env::debug_println!("[Mock Substrate send_extrinsic]");
+        ink::env::debug_println!("[Mock Substrate send_extrinsic]");
         Ok(SubstrateExtrinsicHash::zero())
     }
 
@@ -376,6 +574,13 @@ impl SubstrateNodeRpcUtils {
     }
 }
 
+fn get_storage_key(module: &str, method: &str) -> String {
+    let mut vec = Vec::new();
+    vec.extend(sp_core_hashing::twox_128(module.as_bytes()));
+    vec.extend(sp_core_hashing::twox_128(method.as_bytes()));
+    slice_to_hex_string(&vec)
+}
+
 fn hex_string_to_vec(s: &str) -> Result<Vec<u8>> {
     hex_string_to_vec_delegate(s).map_err(|_| SubstrateError::InvalidHex)
 }
@@ -387,8 +592,60 @@ struct Extra {
     era: Era,
     // Nonce
     nonce: Compact<u32>,
-    // Tip for the block producer.
-    tip: Compact<u128>,
+    // Tip for the block producer (and the fee asset, if the chain supports one)
+    tx_payment: TxPayment,
+}
+
+// The fee payment signed extension, which must match the chain's runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TxPayment {
+    // ChargeTransactionPayment
+    Native {
+        tip: u128,
+    },
+    // ChargeAssetTxPayment. fee_asset = None pays in the native token
+    Asset {
+        kind: AssetTxPaymentKind,
+        tip: u128,
+        fee_asset: Option<AssetId>,
+    },
+}
+
+impl TxPayment {
+    // fee_asset is ignored on chains without pallet-asset-tx-payment
+    pub fn from_chain_info(chain_info: &ChainInfo, tip: u128, fee_asset: Option<AssetId>) -> Self {
+        match chain_info.asset_tx_payment {
+            Some(kind) => Self::Asset {
+                kind,
+                tip,
+                fee_asset,
+            },
+            None => Self::Native { tip },
+        }
+    }
+}
+
+impl Encode for TxPayment {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        match self {
+            Self::Native { tip } => Compact(*tip).encode_to(dest),
+            Self::Asset {
+                kind,
+                tip,
+                fee_asset,
+            } => {
+                Compact(*tip).encode_to(dest);
+                match kind {
+                    // The fee asset config only accepts ids that fit
+                    AssetTxPaymentKind::U32AssetIds => {
+                        fee_asset.map(|asset_id| asset_id as u32).encode_to(dest)
+                    }
+                    AssetTxPaymentKind::U128AssetIds => fee_asset.encode_to(dest),
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -398,9 +655,137 @@ mod tests {
     use privadex_chain_metadata::{chain_info::ChainInfo, registry::chain::chain_info_registry};
 
     fn utils(chain_info: &ChainInfo) -> SubstrateNodeRpcUtils {
-        SubstrateNodeRpcUtils {
-            rpc_url: chain_info.rpc_url.to_string(),
-        }
+        SubstrateNodeRpcUtils::from_chain_info(chain_info, &[])
+    }
+
+    #[test]
+    fn test_block_number_call_and_response() {
+        let best_call = SubstrateNodeRpcUtils::block_number_call(None);
+        assert_eq!(best_call.method, "state_getStorage");
+        assert_eq!(
+            best_call.params,
+            r#"["0x26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac"]"#
+        );
+        let finalized_call = SubstrateNodeRpcUtils::block_number_call(Some(&BlockHash::zero()));
+        assert!(finalized_call.params.ends_with(
+            r#"","0x0000000000000000000000000000000000000000000000000000000000000000"]"#
+        ));
+
+        // System.Number is a SCALE-encoded (little endian) u32
+        assert_eq!(
+            SubstrateNodeRpcUtils::parse_block_number_response(
+                br#"{"jsonrpc":"2.0","result":"0x10e45a01","id":0}"#
+            ),
+            Ok(22_733_840)
+        );
+        assert!(SubstrateNodeRpcUtils::parse_block_hash_response(
+            br#"{"jsonrpc":"2.0","result":"0x1234","id":0}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_tx_payment_encoding() {
+        // The fee asset is dropped on chains without pallet-asset-tx-payment
+        assert_eq!(
+            TxPayment::from_chain_info(&chain_info_registry::MOONBEAM_INFO, 0, Some(1984)).encode(),
+            vec![0x00]
+        );
+        assert_eq!(
+            TxPayment::Asset {
+                kind: AssetTxPaymentKind::U32AssetIds,
+                tip: 0,
+                fee_asset: None,
+            }
+            .encode(),
+            vec![0x00, 0x00]
+        );
+        assert_eq!(
+            TxPayment::Asset {
+                kind: AssetTxPaymentKind::U32AssetIds,
+                tip: 1,
+                fee_asset: Some(1984),
+            }
+            .encode(),
+            vec![0x04, 0x01, 0xc0, 0x07, 0x00, 0x00]
+        );
+        let mut u128_encoding = vec![0x00, 0x01, 0xc0, 0x07];
+        u128_encoding.extend([0u8; 14]);
+        assert_eq!(
+            TxPayment::Asset {
+                kind: AssetTxPaymentKind::U128AssetIds,
+                tip: 0,
+                fee_asset: Some(1984),
+            }
+            .encode(),
+            u128_encoding
+        );
+    }
+
+    #[test]
+    fn test_astar_fee_asset_extrinsic_encoding() {
+        use crate::key_container::SigningKey;
+        use privadex_common::signature_scheme::SignatureScheme;
+
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let chain_info = &chain_info_registry::ASTAR_INFO;
+        let runtime_version = RuntimeVersion {
+            spec_name: "astar".into(),
+            impl_name: "astar".into(),
+            authoring_version: 1,
+            spec_version: 57,
+            impl_version: 0,
+            apis: Vec::new(),
+            transaction_version: 2,
+            state_version: 1,
+        };
+        let signer = [0x11u8; 32];
+        let call_data = hex!("0b00deadbeef");
+        let create = |fee_asset: Option<AssetId>| {
+            utils(chain_info)
+                .create_extrinsic::<[u8; 32]>(
+                    ExtrinsicSigConfig {
+                        sig_scheme: SignatureScheme::Sr25519,
+                        signer,
+                        // Alice
+                        key: SigningKey::Local(hex!(
+                            "e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a"
+                        )),
+                    },
+                    &call_data,
+                    7, // nonce
+                    runtime_version.clone(),
+                    BlockHash::zero(),
+                    BlockHash::zero(),
+                    Era::Immortal,
+                    TxPayment::from_chain_info(chain_info, 0, fee_asset),
+                )
+                .expect("Local key signs")
+        };
+        // Compact length prefix, then "is signed" + v4, MultiAddress::Id(signer) and the
+        // MultiSignature::Sr25519 (1 + 64 bytes)
+        let extra_offset = 2 + 1 + 33 + 65;
+
+        let extrinsic = create(Some(1984));
+        let mut expected_extra = vec![0x00, 0x1c, 0x00, 0x01]; // immortal, nonce 7, no tip, Some(
+        expected_extra.extend(1984u128.to_le_bytes()); // u128 asset id)
+        assert_eq!(
+            &extrinsic[extra_offset..extra_offset + expected_extra.len()],
+            expected_extra.as_slice()
+        );
+        assert!(extrinsic.ends_with(&call_data));
+        assert_eq!(
+            Compact::<u32>::decode(&mut &extrinsic[..]).unwrap().0 as usize,
+            extrinsic.len() - 2
+        );
+
+        // Without a fee asset the extension is still ChargeAssetTxPayment, paying in ASTR
+        let extrinsic = create(None);
+        assert_eq!(
+            &extrinsic[extra_offset..extra_offset + 4],
+            &[0x00, 0x1c, 0x00, 0x00]
+        );
+        assert_eq!(extrinsic.len(), extra_offset + 4 + call_data.len());
     }
 
     #[test]
@@ -423,7 +808,7 @@ mod tests {
         let nonce = utils(&chain_info_registry::ASTAR_INFO)
             .get_next_system_nonce(address)
             .expect("Expected valid nonce");
-        // ink_env::debug_println!("nonce = {}", nonce);
+        // ink::env::debug_println!("nonce = {}", nonce);
         assert!(nonce > 1);
     }
 
@@ -453,6 +838,26 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "private-rpc-endpoint")]
+    #[test]
+    fn polkadot_native_token_decimals() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let decimals = utils(&chain_info_registry::POLKADOT_INFO)
+            .get_native_token_decimals()
+            .expect("Valid response");
+        assert_eq!(decimals, 10);
+    }
+
+    #[cfg(feature = "private-rpc-endpoint")]
+    #[test]
+    fn polkadot_ss58_prefix() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let ss58_prefix = utils(&chain_info_registry::POLKADOT_INFO)
+            .get_ss58_prefix()
+            .expect("Valid response");
+        assert_eq!(ss58_prefix, 0);
+    }
+
     #[cfg(feature = "private-rpc-endpoint")]
     #[test]
     fn polkadot_genesis() {
@@ -490,6 +895,10 @@ mod tests {
             .expect("Expected valid finalized block number");
         debug_println!("block num: {}", block_num);
         assert!(block_num > 2_475_364u32);
+        let best_block_num = utils(&chain_info_registry::MOONBEAM_INFO)
+            .get_best_block_number()
+            .expect("Expected valid best block number");
+        assert!(best_block_num >= block_num);
     }
 
     #[test]
@@ -503,6 +912,25 @@ mod tests {
         assert!(runtime_version.apis.len() > 0);
     }
 
+    #[test]
+    fn moonbeam_extrinsic_context() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let extrinsic_context = utils(&chain_info_registry::MOONBEAM_INFO)
+            .get_extrinsic_context()
+            .expect("Expected valid extrinsic context");
+        assert_eq!(extrinsic_context.runtime_version.impl_name, "moonbeam");
+        assert_eq!(
+            extrinsic_context.genesis_hash,
+            BlockHash {
+                0: hex!("fe58ea77779b7abda7da4ec526d14db9b1e9cd40a217c34892af80a9b332b76d")
+            }
+        );
+        debug_println!(
+            "finalized head hash: {}",
+            extrinsic_context.finalized_head_hash
+        );
+    }
+
     #[cfg(feature = "private-rpc-endpoint")]
     #[test]
     fn polkadot_runtime_version() {
@@ -538,9 +966,7 @@ mod send_extrinsic_tests {
     use privadex_chain_metadata::{chain_info::ChainInfo, registry::chain::chain_info_registry};
 
     fn utils(chain_info: &ChainInfo) -> SubstrateNodeRpcUtils {
-        SubstrateNodeRpcUtils {
-            rpc_url: chain_info.rpc_url.to_string(),
-        }
+        SubstrateNodeRpcUtils::from_chain_info(chain_info, &[])
     }
 
     #[test]
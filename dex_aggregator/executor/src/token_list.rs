@@ -0,0 +1,221 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{string::ToString, vec::Vec};
+use serde::Deserialize;
+
+use privadex_chain_metadata::{
+    get_chain_id_from_evm_chain_id, get_chain_info_from_chain_id,
+    registry::token::{
+        token_list_overlay::{ListedToken, TokenListOverlay, TokenListVersion},
+        universal_token_id_registry,
+    },
+};
+use privadex_common::utils::http_request::http_get_wrapper;
+
+use crate::quote_engine::hex_str_to_eth_addr;
+
+// Keeps the overlay (which every quote reads) small. Counted after dropping the tokens on
+// chains we don't support, since public lists span many chains
+pub const MAX_TOKEN_LIST_LEN: usize = 256;
+
+#[derive(Debug, PartialEq)]
+pub enum TokenListError {
+    InvalidToken,
+    ParseFailed,
+    RequestFailed,
+    TooManyTokens,
+    UnsupportedUrl,
+}
+pub type Result<T> = core::result::Result<T, TokenListError>;
+
+// Subset of https://uniswap.org/tokenlist.schema.json that we use. Tags, extensions etc.
+// are ignored
+#[derive(Deserialize, Debug)]
+struct TokenListJson<'a> {
+    name: &'a str,
+    version: TokenListVersionJson,
+    #[serde(borrow)]
+    tokens: Vec<TokenInfoJson<'a>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenListVersionJson {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+struct TokenInfoJson<'a> {
+    chainId: u64,
+    address: &'a str,
+    name: &'a str,
+    symbol: &'a str,
+    decimals: u8,
+    logoURI: Option<&'a str>,
+}
+
+// Only HTTPS, which includes S3 objects through their HTTPS endpoint
+pub fn fetch_token_list(url: &str) -> Result<TokenListOverlay> {
+    if !url.starts_with("https://") {
+        return Err(TokenListError::UnsupportedUrl);
+    }
+    let body = http_get_wrapper(url).map_err(|_| TokenListError::RequestFailed)?;
+    parse_token_list(&body)
+}
+
+pub fn parse_token_list(json: &[u8]) -> Result<TokenListOverlay> {
+    let (token_list, _): (TokenListJson, usize) =
+        serde_json_core::from_slice(json).map_err(|_| TokenListError::ParseFailed)?;
+    let mut listed_tokens = Vec::new();
+    for token_info in token_list.tokens.iter() {
+        let chain_id = match get_chain_id_from_evm_chain_id(token_info.chainId) {
+            Some(chain_id) => chain_id,
+            None => continue,
+        };
+        let addr =
+            hex_str_to_eth_addr(token_info.address).map_err(|_| TokenListError::InvalidToken)?;
+        listed_tokens.push(ListedToken {
+            // XC20 precompile addresses are recognized as such
+            token: universal_token_id_registry::chain_and_eth_addr_to_token(chain_id, addr),
+            symbol: token_info.symbol.to_string(),
+            name: token_info.name.to_string(),
+            decimals: token_info.decimals,
+            logo_uri: token_info.logoURI.map(|logo_uri| logo_uri.to_string()),
+        });
+    }
+    if listed_tokens.len() > MAX_TOKEN_LIST_LEN {
+        return Err(TokenListError::TooManyTokens);
+    }
+    let mut overlay = TokenListOverlay::new();
+    overlay.replace(
+        token_list.name.to_string(),
+        TokenListVersion {
+            major: token_list.version.major,
+            minor: token_list.version.minor,
+            patch: token_list.version.patch,
+        },
+        listed_tokens,
+    );
+    Ok(overlay)
+}
+
+// The admin submits the list that import_token_list fetched, so check it again before it is
+// stored
+pub fn check_token_list(token_list: &TokenListOverlay) -> Result<()> {
+    if token_list.len() > MAX_TOKEN_LIST_LEN {
+        return Err(TokenListError::TooManyTokens);
+    }
+    if token_list
+        .tokens()
+        .iter()
+        .any(|listed_token| get_chain_info_from_chain_id(&listed_token.token.chain).is_none())
+    {
+        return Err(TokenListError::InvalidToken);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod token_list_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry as token_reg;
+
+    use super::*;
+
+    const TOKEN_LIST_JSON: &str = r#"{
+        "name": "Moonbeam Tokens",
+        "timestamp": "2023-05-01T00:00:00.000Z",
+        "version": {"major": 2, "minor": 1, "patch": 0},
+        "keywords": ["moonbeam"],
+        "tokens": [
+            {
+                "chainId": 1284,
+                "address": "0x931715FEE2d06333043d11F658C8CE934aC61D0c",
+                "name": "USD Coin (Wormhole)",
+                "symbol": "USDC.wh",
+                "decimals": 6,
+                "logoURI": "https://example.com/usdc.png",
+                "tags": ["stablecoin"]
+            },
+            {
+                "chainId": 1284,
+                "address": "0xFfFFfFff1FcaCBd218EDc0EbA20Fc2308C778080",
+                "name": "xcDOT",
+                "symbol": "xcDOT",
+                "decimals": 10,
+                "extensions": {"bridgeInfo": {"0": {"tokenAddress": "native"}}}
+            },
+            {
+                "chainId": 56,
+                "address": "0x8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d",
+                "name": "USD Coin",
+                "symbol": "USDC",
+                "decimals": 18
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_token_list() {
+        let overlay = parse_token_list(TOKEN_LIST_JSON.as_bytes()).expect("Valid token list");
+        assert_eq!(overlay.list_name, "Moonbeam Tokens");
+        assert_eq!(
+            overlay.version,
+            TokenListVersion {
+                major: 2,
+                minor: 1,
+                patch: 0
+            }
+        );
+        // BNB Chain is not supported so its token is dropped
+        assert_eq!(overlay.len(), 2);
+        let usdc = overlay
+            .get(&token_reg::USDC_WH_MOONBEAM)
+            .expect("USDC.wh is listed");
+        assert_eq!(usdc.decimals, 6);
+        assert_eq!(
+            usdc.logo_uri.as_deref(),
+            Some("https://example.com/usdc.png")
+        );
+        // The xcDOT precompile address maps to the XC20
+        assert_eq!(
+            overlay.get(&token_reg::DOT_MOONBEAM).unwrap().logo_uri,
+            None
+        );
+        assert_eq!(check_token_list(&overlay), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_bad_token_lists() {
+        assert_eq!(
+            parse_token_list(b"{\"name\": \"Empty\"}"),
+            Err(TokenListError::ParseFailed)
+        );
+        let bad_address = TOKEN_LIST_JSON.replace("0x931715FEE2d", "0xzz1715FEE2d");
+        assert_eq!(
+            parse_token_list(bad_address.as_bytes()),
+            Err(TokenListError::InvalidToken)
+        );
+        assert_eq!(
+            fetch_token_list("http://example.com/tokens.json"),
+            Err(TokenListError::UnsupportedUrl)
+        );
+    }
+}
@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_env::debug_println;
+use ink::env::debug_println;
 use std::fs::File;
 
 use privadex_chain_metadata::common::UniversalChainId;
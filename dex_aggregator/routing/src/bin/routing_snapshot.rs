@@ -0,0 +1,103 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use std::{env, fs, process};
+
+use privadex_chain_metadata::{
+    common::UniversalChainId,
+    get_chain_id_from_network_name,
+    registry::chain::universal_chain_id_registry::{ASTAR, MOONBEAM, POLKADOT},
+};
+use privadex_routing::graph_snapshot::GraphSnapshot;
+
+// Exports and loads routing graph snapshots (see graph_snapshot::GraphSnapshot for the format).
+// Exported snapshots can seed the quote cache, or be used for offline development and tests:
+//   export <snapshot file> [network names]: fetches every DEX's subgraph on the given networks
+//     (default: astar moonbeam polkadot) and writes the snapshot
+//   validate <snapshot file>: loads the snapshot and builds its graph
+// cargo run --bin privadex_routing_snapshot --features=cli -- export graph.snapshot
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        print_usage_and_exit(&args[0]);
+    }
+    match args[1].as_str() {
+        "export" => export(&args[2], &args[3..]),
+        "validate" => validate(&args[2]),
+        _ => print_usage_and_exit(&args[0]),
+    }
+}
+
+fn export(path: &str, network_names: &[String]) {
+    pink_extension_runtime::mock_ext::mock_all_ext();
+
+    let chain_ids: Vec<UniversalChainId> = if network_names.is_empty() {
+        vec![ASTAR, MOONBEAM, POLKADOT]
+    } else {
+        network_names
+            .iter()
+            .map(|name| {
+                get_chain_id_from_network_name(name)
+                    .unwrap_or_else(|| panic!("Unknown network name: {}", name))
+            })
+            .collect()
+    };
+    let snapshot = GraphSnapshot::fetch(&chain_ids).expect("Could not fetch DEX data");
+    // Don't write a snapshot that can't be loaded
+    let graph = snapshot
+        .to_graph()
+        .expect("Fetched DEX data does not form a graph");
+    fs::write(path, snapshot.to_bytes()).expect("Could not write snapshot");
+    println!(
+        "Wrote snapshot of {} DEXes ({} vertices, {} edges) to {}",
+        snapshot.dex_responses.len(),
+        graph.simple_graph.vertex_count(),
+        graph.edge_count(),
+        path
+    );
+}
+
+fn validate(path: &str) {
+    let bytes = fs::read(path).expect("Could not read snapshot");
+    let result = GraphSnapshot::from_bytes(&bytes).and_then(|snapshot| {
+        let graph = snapshot.to_graph()?;
+        Ok((snapshot, graph))
+    });
+    match result {
+        Ok((snapshot, graph)) => println!(
+            "OK: snapshot v{} of {} DEXes on {:?} ({} vertices, {} edges)",
+            snapshot.version,
+            snapshot.dex_responses.len(),
+            snapshot.chain_ids,
+            graph.simple_graph.vertex_count(),
+            graph.edge_count()
+        ),
+        Err(err) => {
+            println!("INVALID: {}: {:?}", path, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_usage_and_exit(bin_name: &str) -> ! {
+    eprintln!(
+        "Usage: {0} export <snapshot file> [network names]\n       {0} validate <snapshot file>",
+        bin_name
+    );
+    process::exit(2);
+}
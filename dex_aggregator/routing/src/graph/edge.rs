@@ -21,12 +21,16 @@ use scale::Encode;
 use xcm::latest::MultiLocation;
 
 use privadex_chain_metadata::{
-    bridge::{WalletMultiLocationTemplate, XCMBridge},
+    bridge::{
+        WalletMultiLocationTemplate, WormholeBridge, WormholeChainId, XCMBridge,
+        WORMHOLE_MAX_DECIMALS,
+    },
     common::{
-        Amount, ChainTokenId, Dex, EthAddress, UniversalChainId, UniversalTokenId,
+        Aggregator, Amount, ChainTokenId, Dex, EthAddress, UniversalChainId, UniversalTokenId,
         USD_AMOUNT_EXPONENT,
     },
     get_chain_info_from_chain_id,
+    registry::token::token_decimals_registry,
 };
 use privadex_common::{fixed_point::DecimalFixedPoint, utils::general_utils::mul_ratio_u128};
 
@@ -55,6 +59,14 @@ impl Edge {
             false
         }
     }
+
+    // Wraps and unwraps are also swaps, but they are 1:1 and don't go through a DEX
+    pub(crate) fn is_dex_swap(&self) -> bool {
+        matches!(
+            self,
+            Self::Swap(SwapEdge::CPMM(_)) | Self::Swap(SwapEdge::Aggregator(_))
+        )
+    }
 }
 
 impl fmt::Display for Edge {
@@ -116,6 +128,7 @@ pub enum SwapEdge {
     CPMM(ConstantProductAMMSwapEdge),
     Wrap(WrapEdge),
     Unwrap(UnwrapEdge),
+    Aggregator(AggregatorSwapEdge),
     // StableswapAMMSwapEdge
     // ConcLiquidityAMMSwapEdge
 }
@@ -126,6 +139,7 @@ impl SwapEdge {
             Self::CPMM(edge) => edge.src_token.chain,
             Self::Wrap(edge) => edge.src_token.chain,
             Self::Unwrap(edge) => edge.src_token.chain,
+            Self::Aggregator(edge) => edge.src_token.chain,
         }
     }
 }
@@ -136,6 +150,7 @@ impl fmt::Display for SwapEdge {
             Self::CPMM(x) => write!(f, "CPMM_{}", x.dex.id),
             Self::Wrap(_) => write!(f, "Wrap"),
             Self::Unwrap(_) => write!(f, "Unwrap"),
+            Self::Aggregator(x) => write!(f, "Aggregator_{}", x.aggregator.id),
         }
     }
 }
@@ -146,6 +161,7 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::CPMM(cpmm_edge) => cpmm_edge.get_src_dest_token(),
             SwapEdge::Wrap(wrap_edge) => wrap_edge.get_src_dest_token(),
             SwapEdge::Unwrap(unwrap_edge) => unwrap_edge.get_src_dest_token(),
+            SwapEdge::Aggregator(aggregator_edge) => aggregator_edge.get_src_dest_token(),
         }
     }
 
@@ -154,6 +170,7 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::CPMM(cpmm_edge) => cpmm_edge.get_quote(amount_in),
             SwapEdge::Wrap(wrap_edge) => wrap_edge.get_quote(amount_in),
             SwapEdge::Unwrap(unwrap_edge) => unwrap_edge.get_quote(amount_in),
+            SwapEdge::Aggregator(aggregator_edge) => aggregator_edge.get_quote(amount_in),
         }
     }
 
@@ -164,6 +181,9 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::Unwrap(unwrap_edge) => {
                 unwrap_edge.get_quote_with_estimated_txn_fees(amount_in)
             }
+            SwapEdge::Aggregator(aggregator_edge) => {
+                aggregator_edge.get_quote_with_estimated_txn_fees(amount_in)
+            }
         }
     }
 
@@ -172,6 +192,9 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::CPMM(cpmm_edge) => cpmm_edge.get_estimated_txn_fees_in_dest_token(),
             SwapEdge::Wrap(wrap_edge) => wrap_edge.get_estimated_txn_fees_in_dest_token(),
             SwapEdge::Unwrap(unwrap_edge) => unwrap_edge.get_estimated_txn_fees_in_dest_token(),
+            SwapEdge::Aggregator(aggregator_edge) => {
+                aggregator_edge.get_estimated_txn_fees_in_dest_token()
+            }
         }
     }
 
@@ -180,6 +203,7 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::CPMM(cpmm_edge) => cpmm_edge.get_estimated_txn_fees_usd(),
             SwapEdge::Wrap(wrap_edge) => wrap_edge.get_estimated_txn_fees_usd(),
             SwapEdge::Unwrap(unwrap_edge) => unwrap_edge.get_estimated_txn_fees_usd(),
+            SwapEdge::Aggregator(aggregator_edge) => aggregator_edge.get_estimated_txn_fees_usd(),
         }
     }
 
@@ -188,6 +212,9 @@ impl QuoteGetter for SwapEdge {
             SwapEdge::CPMM(cpmm_edge) => cpmm_edge.get_dest_chain_estimated_gas_fee_usd(),
             SwapEdge::Wrap(wrap_edge) => wrap_edge.get_dest_chain_estimated_gas_fee_usd(),
             SwapEdge::Unwrap(unwrap_edge) => unwrap_edge.get_dest_chain_estimated_gas_fee_usd(),
+            SwapEdge::Aggregator(aggregator_edge) => {
+                aggregator_edge.get_dest_chain_estimated_gas_fee_usd()
+            }
         }
     }
 }
@@ -202,6 +229,8 @@ pub struct ConstantProductAMMSwapEdge {
     pub token1: ChainTokenId,
     pub reserve0: Amount,
     pub reserve1: Amount,
+    // This pool's fee, as reported by the subgraph for fee-tiered DEXes (see Dex::fee_tiers_bps)
+    pub fee_bps: u16,
     // derived value: chain_info.avg_gas_fee / dest_token.derivedEth
     pub estimated_gas_fee_in_dest_token: Amount,
     // Not used for routing but is useful downstream when executing a GraphSolution
@@ -230,7 +259,7 @@ impl QuoteGetter for ConstantProductAMMSwapEdge {
             }
         };
 
-        let after_fee_bps = Amount::from(10_000 - self.dex.fee_bps);
+        let after_fee_bps = Amount::from(10_000 - self.fee_bps);
         // Order of operations matters so we avoid int overflows!
         let denominator = denom_reserve + mul_ratio_u128(amount_in, after_fee_bps, 10_000);
         let part_numerator = mul_ratio_u128(num_reserve, after_fee_bps, 10_000);
@@ -314,22 +343,69 @@ impl QuoteGetter for UnwrapEdge {
     }
 }
 
+// A swap through another aggregator's exchange (see Aggregator). Its API quotes one amount at a
+// time, so the edge only knows the quote for the amount in it was fetched for
+#[derive(Debug, Clone, Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AggregatorSwapEdge {
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    pub quoted_amount_in: Amount,
+    pub quoted_amount_out: Amount,
+    // Includes the approval of an ERC20 src token, which is a txn of its own
+    pub estimated_gas_fee_in_dest_token: Amount,
+    // Not used for routing but is useful downstream when executing a GraphSolution
+    pub estimated_gas_fee_usd: Amount,
+
+    pub aggregator: &'static Aggregator,
+}
+
+impl QuoteGetter for AggregatorSwapEdge {
+    fn get_src_dest_token(&self) -> (&UniversalTokenId, &UniversalTokenId) {
+        (&self.src_token, &self.dest_token)
+    }
+
+    // Pro rata below the quoted amount in, which underestimates since a smaller swap has less
+    // price impact. We don't know what the aggregator would give for more, so it gives nothing
+    fn get_quote(&self, amount_in: Amount) -> Amount {
+        if amount_in > self.quoted_amount_in || self.quoted_amount_in == 0 {
+            return 0;
+        }
+        mul_ratio_u128(self.quoted_amount_out, amount_in, self.quoted_amount_in)
+    }
+
+    fn get_estimated_txn_fees_in_dest_token(&self) -> Amount {
+        self.estimated_gas_fee_in_dest_token
+    }
+
+    fn get_estimated_txn_fees_usd(&self) -> Amount {
+        self.estimated_gas_fee_usd
+    }
+
+    fn get_dest_chain_estimated_gas_fee_usd(&self) -> Amount {
+        self.estimated_gas_fee_usd
+    }
+}
+
 #[derive(Debug, Clone, Encode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum BridgeEdge {
     Xcm(XCMBridgeEdge),
+    Wormhole(WormholeBridgeEdge),
 }
 
 impl QuoteGetter for BridgeEdge {
     fn get_src_dest_token(&self) -> (&UniversalTokenId, &UniversalTokenId) {
         match self {
             BridgeEdge::Xcm(xcm_bridge_edge) => xcm_bridge_edge.get_src_dest_token(),
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => wormhole_bridge_edge.get_src_dest_token(),
         }
     }
 
     fn get_quote(&self, amount_in: Amount) -> Amount {
         match self {
             BridgeEdge::Xcm(xcm_bridge_edge) => xcm_bridge_edge.get_quote(amount_in),
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => wormhole_bridge_edge.get_quote(amount_in),
         }
     }
 
@@ -338,6 +414,9 @@ impl QuoteGetter for BridgeEdge {
             BridgeEdge::Xcm(xcm_bridge_edge) => {
                 xcm_bridge_edge.get_quote_with_estimated_txn_fees(amount_in)
             }
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => {
+                wormhole_bridge_edge.get_quote_with_estimated_txn_fees(amount_in)
+            }
         }
     }
 
@@ -346,12 +425,18 @@ impl QuoteGetter for BridgeEdge {
             BridgeEdge::Xcm(xcm_bridge_edge) => {
                 xcm_bridge_edge.get_estimated_txn_fees_in_dest_token()
             }
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => {
+                wormhole_bridge_edge.get_estimated_txn_fees_in_dest_token()
+            }
         }
     }
 
     fn get_estimated_txn_fees_usd(&self) -> Amount {
         match self {
             BridgeEdge::Xcm(xcm_bridge_edge) => xcm_bridge_edge.get_estimated_txn_fees_usd(),
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => {
+                wormhole_bridge_edge.get_estimated_txn_fees_usd()
+            }
         }
     }
 
@@ -360,6 +445,9 @@ impl QuoteGetter for BridgeEdge {
             BridgeEdge::Xcm(xcm_bridge_edge) => {
                 xcm_bridge_edge.get_dest_chain_estimated_gas_fee_usd()
             }
+            BridgeEdge::Wormhole(wormhole_bridge_edge) => {
+                wormhole_bridge_edge.get_dest_chain_estimated_gas_fee_usd()
+            }
         }
     }
 }
@@ -375,6 +463,7 @@ pub struct XCMBridgeEdge {
     pub estimated_gas_fee_usd: Amount,
     // derived value: estimated_bridge_fee_in_native_token / dest_token.derivedEth
     pub estimated_bridge_fee_in_dest_token: Amount,
+    pub estimated_bridge_fee_in_dest_chain_native_token: Amount,
     // Not used for routing but is useful downstream when executing a GraphSolution
     pub estimated_bridge_fee_usd: Amount,
 
@@ -383,6 +472,7 @@ pub struct XCMBridgeEdge {
     // XCM instruction and fee metadata needed for executor
     pub token_asset_multilocation: MultiLocation,
     pub dest_multilocation_template: WalletMultiLocationTemplate,
+    pub dest_weight_limit: u64,
 }
 
 // Can change this to a generic trait when more bridge types are added
@@ -406,7 +496,7 @@ impl XCMBridgeEdge {
         let estimated_gas_fee_usd = token_derived_usd
             .add_exp(USD_AMOUNT_EXPONENT as i8)
             .mul_u128(estimated_gas_fee_in_src_token);
-        // ink_env::debug_println!(
+        // ink::env::debug_println!(
         //     "Token fee = {}, ${}, src_derived_eth={:?}",
         //     estimated_gas_fee_in_src_token,
         //     estimated_gas_fee_usd as f64 / (Amount::pow(10, 18) as f64),
@@ -439,12 +529,31 @@ impl XCMBridgeEdge {
             estimated_gas_fee_in_src_token,
             estimated_gas_fee_usd,
             estimated_bridge_fee_in_dest_token,
+            estimated_bridge_fee_in_dest_chain_native_token: xcm_bridge
+                .estimated_bridge_fee_in_dest_chain_native_token,
             estimated_bridge_fee_usd,
             estimated_dest_chain_gas_fee_usd,
             token_asset_multilocation: xcm_bridge.token_asset_multilocation,
             dest_multilocation_template: xcm_bridge.dest_multilocation_template,
+            dest_weight_limit: xcm_bridge.dest_weight_limit,
         }
     }
+
+    // Replaces the registry's bridge fee estimate, e.g. with the fee recent transfers on this
+    // lane actually paid. The derived quantities are the same ones the edge was built with
+    pub fn set_bridge_fee_in_dest_token(
+        &mut self,
+        bridge_fee_in_dest_token: Amount,
+        dest_token_derived_eth: &DecimalFixedPoint,
+        token_derived_usd: &DecimalFixedPoint,
+    ) {
+        self.estimated_bridge_fee_in_dest_token = bridge_fee_in_dest_token;
+        self.estimated_bridge_fee_in_dest_chain_native_token =
+            dest_token_derived_eth.mul_u128(bridge_fee_in_dest_token);
+        self.estimated_bridge_fee_usd = token_derived_usd
+            .add_exp(USD_AMOUNT_EXPONENT as i8)
+            .mul_u128(bridge_fee_in_dest_token);
+    }
 }
 
 impl QuoteGetter for XCMBridgeEdge {
@@ -453,11 +562,12 @@ impl QuoteGetter for XCMBridgeEdge {
     }
 
     fn get_quote(&self, amount_in: Amount) -> Amount {
-        amount_in
+        token_decimals_registry::normalize_amount(amount_in, &self.src_token, &self.dest_token)
     }
 
     fn get_estimated_txn_fees_in_dest_token(&self) -> Amount {
-        self.estimated_gas_fee_in_src_token + self.estimated_bridge_fee_in_dest_token
+        self.get_quote(self.estimated_gas_fee_in_src_token)
+            + self.estimated_bridge_fee_in_dest_token
     }
 
     fn get_estimated_txn_fees_usd(&self) -> Amount {
@@ -469,10 +579,87 @@ impl QuoteGetter for XCMBridgeEdge {
     }
 }
 
+#[derive(Debug, Clone, Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WormholeBridgeEdge {
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    // Deposit txn on the source chain, estimated directly in the bridged token
+    pub estimated_gas_fee_in_src_token: Amount,
+    // Not used for routing but is useful downstream when executing a GraphSolution
+    pub estimated_gas_fee_usd: Amount,
+    // Claim txn on the destination chain, estimated directly in the bridged token
+    pub estimated_bridge_fee_in_dest_token: Amount,
+    pub estimated_bridge_fee_in_dest_chain_native_token: Amount,
+    // Not used for routing but is useful downstream when executing a GraphSolution
+    pub estimated_bridge_fee_usd: Amount,
+
+    // Token bridge metadata needed for executor
+    pub src_token_bridge_addr: EthAddress,
+    pub dest_token_bridge_addr: EthAddress,
+    pub src_wormhole_chain_id: WormholeChainId,
+    pub dest_wormhole_chain_id: WormholeChainId,
+}
+
+impl WormholeBridgeEdge {
+    pub fn from_bridge_and_derived_usd(
+        wormhole_bridge: WormholeBridge,
+        token_derived_usd: &DecimalFixedPoint,
+    ) -> Self {
+        let derived_usd = token_derived_usd.add_exp(USD_AMOUNT_EXPONENT as i8);
+        Self {
+            estimated_gas_fee_in_src_token: wormhole_bridge.estimated_deposit_fee_in_token,
+            estimated_gas_fee_usd: derived_usd
+                .mul_u128(wormhole_bridge.estimated_deposit_fee_in_token),
+            estimated_bridge_fee_in_dest_token: wormhole_bridge.estimated_claim_fee_in_token,
+            estimated_bridge_fee_in_dest_chain_native_token: wormhole_bridge
+                .estimated_claim_fee_in_dest_chain_native_token,
+            estimated_bridge_fee_usd: derived_usd
+                .mul_u128(wormhole_bridge.estimated_claim_fee_in_token),
+            src_token: wormhole_bridge.src_token,
+            dest_token: wormhole_bridge.dest_token,
+            src_token_bridge_addr: wormhole_bridge.src_token_bridge_addr,
+            dest_token_bridge_addr: wormhole_bridge.dest_token_bridge_addr,
+            src_wormhole_chain_id: wormhole_bridge.src_wormhole_chain_id,
+            dest_wormhole_chain_id: wormhole_bridge.dest_wormhole_chain_id,
+        }
+    }
+}
+
+impl QuoteGetter for WormholeBridgeEdge {
+    fn get_src_dest_token(&self) -> (&UniversalTokenId, &UniversalTokenId) {
+        (&self.src_token, &self.dest_token)
+    }
+
+    // The token bridge locks/mints 1:1 but truncates amounts to 8 decimals on the way
+    fn get_quote(&self, amount_in: Amount) -> Amount {
+        token_decimals_registry::normalize_amount_with_max_decimals(
+            amount_in,
+            &self.src_token,
+            &self.dest_token,
+            WORMHOLE_MAX_DECIMALS,
+        )
+    }
+
+    fn get_estimated_txn_fees_in_dest_token(&self) -> Amount {
+        self.get_quote(self.estimated_gas_fee_in_src_token)
+            + self.estimated_bridge_fee_in_dest_token
+    }
+
+    fn get_estimated_txn_fees_usd(&self) -> Amount {
+        self.estimated_gas_fee_usd + self.estimated_bridge_fee_usd
+    }
+
+    // The claim is the destination chain's transaction
+    fn get_dest_chain_estimated_gas_fee_usd(&self) -> Amount {
+        self.estimated_bridge_fee_usd
+    }
+}
+
 // Ensure that our new int implementation matches the output of our old float implementation
 #[cfg(test)]
 mod float_tests {
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     use super::*;
 
@@ -514,3 +701,31 @@ mod float_tests {
         debug_println!("{}, {}", quotei, quotef);
     }
 }
+
+#[cfg(test)]
+mod aggregator_edge_tests {
+    use privadex_chain_metadata::registry::{
+        aggregator::aggregator_registry::OPENOCEAN_MOONBEAM,
+        token::universal_token_id_registry::{GLMR_NATIVE, USDT_MOONBEAM},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_aggregator_quote_is_pro_rata_up_to_quoted_amount() {
+        let edge = AggregatorSwapEdge {
+            src_token: GLMR_NATIVE,
+            dest_token: USDT_MOONBEAM,
+            quoted_amount_in: 1_000,
+            quoted_amount_out: 300,
+            estimated_gas_fee_in_dest_token: 2,
+            estimated_gas_fee_usd: 2,
+            aggregator: &OPENOCEAN_MOONBEAM,
+        };
+        assert_eq!(edge.get_quote(1_000), 300);
+        assert_eq!(edge.get_quote(500), 150);
+        assert_eq!(edge.get_quote_with_estimated_txn_fees(500), 148);
+        assert_eq!(edge.get_quote(1_001), 0);
+        assert!(Edge::Swap(SwapEdge::Aggregator(edge)).is_dex_swap());
+    }
+}
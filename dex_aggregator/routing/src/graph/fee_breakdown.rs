@@ -0,0 +1,261 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{Amount, UniversalTokenId, USD_AMOUNT_EXPONENT},
+    get_chain_info_from_chain_id,
+};
+use privadex_common::utils::general_utils::mul_ratio_u128;
+
+use super::edge::{BridgeEdge, Edge, SwapEdge};
+use super::graph::{Graph, GraphSolution};
+use super::traits::QuoteGetter;
+
+// All USD amounts are in $ x 10^USD_AMOUNT_EXPONENT
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FeeBreakdown {
+    // One Vec<HopFee> per SplitGraphPath, in the same order
+    pub hops_per_path: Vec<Vec<HopFee>>,
+    // Taken out of the final amount by the escrow
+    pub protocol_fee_in_dest_token: Amount,
+    pub protocol_fee_usd: Amount,
+    // Markup of the integrator who quoted it (zero unless quoted with quote_with_markup). Sent
+    // to the integrator before the protocol fee is taken
+    pub integrator_fee_in_dest_token: Amount,
+    pub integrator_fee_usd: Amount,
+    pub total_fee_usd: Amount,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct HopFee {
+    pub src_token: UniversalTokenId,
+    pub dest_token: UniversalTokenId,
+    // DEX liquidity provider fee, which is taken out of the hop's input
+    pub lp_fee_in_src_token: Amount,
+    pub lp_fee_usd: Amount,
+    // Paid in the native token of the hop's source chain
+    pub gas_fee_native: Amount,
+    pub gas_fee_usd: Amount,
+    // XCM execution fee or Wormhole claim, paid in the destination chain's native token
+    pub bridge_fee_native: Amount,
+    pub bridge_fee_usd: Amount,
+}
+
+impl HopFee {
+    fn get_total_fee_usd(&self) -> Amount {
+        self.lp_fee_usd + self.gas_fee_usd + self.bridge_fee_usd
+    }
+}
+
+impl GraphSolution {
+    // The graph is needed for the tokens' USD prices (edges only store their fees in USD)
+    pub fn get_fee_breakdown(&self, graph: &Graph, protocol_fee_bps: u16) -> FeeBreakdown {
+        self.get_fee_breakdown_with_markup(graph, protocol_fee_bps, 0)
+    }
+
+    pub fn get_fee_breakdown_with_markup(
+        &self,
+        graph: &Graph,
+        protocol_fee_bps: u16,
+        markup_bps: u16,
+    ) -> FeeBreakdown {
+        let hops_per_path: Vec<Vec<HopFee>> = self
+            .paths
+            .iter()
+            .map(|split_path| {
+                let mut amount_in = split_path.fraction_amount_in;
+                split_path
+                    .path
+                    .0
+                    .iter()
+                    .map(|edge| {
+                        let hop_fee = get_hop_fee(edge, amount_in, graph);
+                        amount_in = edge.get_quote_with_estimated_txn_fees(amount_in);
+                        hop_fee
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let quote = self.get_quote_with_estimated_txn_fees();
+        let integrator_fee_in_dest_token = mul_ratio_u128(quote, Amount::from(markup_bps), 10_000);
+        // The protocol fee is taken out of what is left after the markup
+        let quote_after_markup = quote - integrator_fee_in_dest_token;
+        let protocol_fee_in_dest_token = quote_after_markup
+            - mul_ratio_u128(
+                quote_after_markup,
+                Amount::from(10_000 - protocol_fee_bps),
+                10_000,
+            );
+        let (_, dest_token) = self.get_src_dest_token();
+        let protocol_fee_usd = get_amount_usd(graph, dest_token, protocol_fee_in_dest_token);
+        let integrator_fee_usd = get_amount_usd(graph, dest_token, integrator_fee_in_dest_token);
+
+        let total_fee_usd = hops_per_path.iter().flatten().fold(
+            protocol_fee_usd + integrator_fee_usd,
+            |fees_usd, hop_fee| fees_usd + hop_fee.get_total_fee_usd(),
+        );
+        FeeBreakdown {
+            hops_per_path,
+            protocol_fee_in_dest_token,
+            protocol_fee_usd,
+            integrator_fee_in_dest_token,
+            integrator_fee_usd,
+            total_fee_usd,
+        }
+    }
+}
+
+fn get_hop_fee(edge: &Edge, amount_in: Amount, graph: &Graph) -> HopFee {
+    let (src_token, dest_token) = edge.get_src_dest_token();
+    let gas_fee_native = get_chain_info_from_chain_id(&src_token.chain)
+        .map_or(0, |chain_info| chain_info.avg_gas_fee_in_native_token);
+    let (lp_fee_in_src_token, gas_fee_usd, bridge_fee_native, bridge_fee_usd) = match edge {
+        Edge::Swap(SwapEdge::CPMM(cpmm_edge)) => (
+            mul_ratio_u128(amount_in, Amount::from(cpmm_edge.fee_bps), 10_000),
+            cpmm_edge.estimated_gas_fee_usd,
+            0,
+            0,
+        ),
+        Edge::Swap(SwapEdge::Wrap(wrap_edge)) => (0, wrap_edge.estimated_gas_fee_usd, 0, 0),
+        Edge::Swap(SwapEdge::Unwrap(unwrap_edge)) => (0, unwrap_edge.estimated_gas_fee_usd, 0, 0),
+        // The aggregator's quote is already net of its pools' fees
+        Edge::Swap(SwapEdge::Aggregator(aggregator_edge)) => {
+            (0, aggregator_edge.estimated_gas_fee_usd, 0, 0)
+        }
+        Edge::Bridge(BridgeEdge::Xcm(xcm_edge)) => (
+            0,
+            xcm_edge.estimated_gas_fee_usd,
+            xcm_edge.estimated_bridge_fee_in_dest_chain_native_token,
+            xcm_edge.estimated_bridge_fee_usd,
+        ),
+        Edge::Bridge(BridgeEdge::Wormhole(wormhole_edge)) => (
+            0,
+            wormhole_edge.estimated_gas_fee_usd,
+            wormhole_edge.estimated_bridge_fee_in_dest_chain_native_token,
+            wormhole_edge.estimated_bridge_fee_usd,
+        ),
+    };
+    HopFee {
+        src_token: src_token.clone(),
+        dest_token: dest_token.clone(),
+        lp_fee_in_src_token,
+        lp_fee_usd: get_amount_usd(graph, src_token, lp_fee_in_src_token),
+        gas_fee_native,
+        gas_fee_usd,
+        bridge_fee_native,
+        bridge_fee_usd,
+    }
+}
+
+fn get_amount_usd(graph: &Graph, token_id: &UniversalTokenId, amount: Amount) -> Amount {
+    graph.get_token(token_id).map_or(0, |token| {
+        token
+            .derived_usd
+            .add_exp(USD_AMOUNT_EXPONENT as i8)
+            .mul_u128(amount)
+    })
+}
+
+#[cfg(test)]
+mod fee_breakdown_tests {
+    use ink::prelude::vec;
+    use privadex_chain_metadata::{
+        common::EthAddress, registry::bridge::xcm_bridge_registry::XCM_BRIDGES,
+    };
+    use privadex_common::fixed_point::DecimalFixedPoint;
+
+    use super::super::edge::XCMBridgeEdge;
+    use super::super::graph::{GraphPath, SplitGraphPath, Token};
+    use super::*;
+
+    #[test]
+    fn test_xcm_bridge_fee_breakdown() {
+        let xcm_bridge = XCM_BRIDGES[0].clone();
+        let derived = DecimalFixedPoint::from_str_and_exp("10", 3);
+        let mut graph = Graph::new();
+        for token_id in [&xcm_bridge.src_token, &xcm_bridge.dest_token] {
+            graph.add_vertex(Token {
+                id: token_id.clone(),
+                derived_eth: derived.clone(),
+                derived_usd: derived.clone(),
+            });
+        }
+        let xcm_edge = XCMBridgeEdge::from_bridge_and_derived_quantities(
+            xcm_bridge.clone(),
+            &derived,
+            &derived,
+            &derived,
+        );
+        let graph_solution = GraphSolution {
+            paths: vec![SplitGraphPath {
+                path: GraphPath(vec![Edge::Bridge(BridgeEdge::Xcm(xcm_edge.clone()))]),
+                fraction_amount_in: 1_000_000_000_000,
+                fraction_bps: 10_000,
+            }],
+            amount_in: 1_000_000_000_000,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        };
+
+        let fee_breakdown = graph_solution.get_fee_breakdown(&graph, 5);
+        assert_eq!(fee_breakdown.hops_per_path.len(), 1);
+        let hop_fee = &fee_breakdown.hops_per_path[0][0];
+        assert_eq!(hop_fee.lp_fee_in_src_token, 0);
+        assert_eq!(hop_fee.gas_fee_usd, xcm_edge.estimated_gas_fee_usd);
+        assert_eq!(
+            hop_fee.bridge_fee_native,
+            xcm_bridge.estimated_bridge_fee_in_dest_chain_native_token
+        );
+        assert_eq!(hop_fee.bridge_fee_usd, xcm_edge.estimated_bridge_fee_usd);
+
+        let quote = graph_solution.get_quote_with_estimated_txn_fees();
+        assert_eq!(
+            fee_breakdown.protocol_fee_in_dest_token,
+            quote - mul_ratio_u128(quote, 9_995, 10_000)
+        );
+        assert_eq!(
+            fee_breakdown.total_fee_usd,
+            hop_fee.gas_fee_usd + hop_fee.bridge_fee_usd + fee_breakdown.protocol_fee_usd
+        );
+        assert_eq!(fee_breakdown.integrator_fee_in_dest_token, 0);
+
+        let marked_up = graph_solution.get_fee_breakdown_with_markup(&graph, 5, 30);
+        assert_eq!(
+            marked_up.integrator_fee_in_dest_token,
+            mul_ratio_u128(quote, 30, 10_000)
+        );
+        let quote_after_markup = quote - marked_up.integrator_fee_in_dest_token;
+        assert_eq!(
+            marked_up.protocol_fee_in_dest_token,
+            quote_after_markup - mul_ratio_u128(quote_after_markup, 9_995, 10_000)
+        );
+        assert_eq!(
+            marked_up.total_fee_usd,
+            hop_fee.gas_fee_usd
+                + hop_fee.bridge_fee_usd
+                + marked_up.protocol_fee_usd
+                + marked_up.integrator_fee_usd
+        );
+    }
+}
@@ -23,7 +23,9 @@ use graphlib::{Graph as SimpleGraph, VertexId};
 // This is Rust's new std HashMap implementation,
 // but this crate allows for no_std and is used in graphlib
 use hashbrown::HashMap;
-use ink_prelude::{vec, vec::Vec};
+#[cfg(feature = "dot")]
+use ink::prelude::string::ToString;
+use ink::prelude::{vec, vec::Vec};
 use scale::Encode;
 
 use privadex_chain_metadata::common::{Amount, EthAddress, UniversalTokenId};
@@ -163,6 +165,29 @@ impl Graph {
         Ok(())
     }
 
+    // Keeps the vertices (and the simple graph's edges, which may end up with no Edges between
+    // the vertex pair). Returns the number of Edges removed
+    pub fn retain_edges<F>(&mut self, mut keep: F) -> usize
+    where
+        F: FnMut(&Edge) -> bool,
+    {
+        let mut num_removed = 0;
+        for multiedge_vec in self.edges.values_mut() {
+            let len = multiedge_vec.len();
+            multiedge_vec.retain(|edge| keep(edge));
+            num_removed += len - multiedge_vec.len();
+        }
+        num_removed
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.values().flatten()
+    }
+
+    pub fn edges_mut(&mut self) -> impl Iterator<Item = &mut Edge> {
+        self.edges.values_mut().flatten()
+    }
+
     // Note this is an expensive operation, just for test purposes. If this functionality is needed
     // in prod, we should just store a variable for the count and increment it in add_edge
     pub fn edge_count(&self) -> usize {
@@ -279,6 +304,74 @@ impl GraphSolution {
             fees + split_path.path.get_dest_chain_estimated_gas_fee_usd()
         })
     }
+
+    // Splits every SplitGraphPath whose fraction_amount_in exceeds max_tranche_amount_in into
+    // several copies of the same path, each with at most max_tranche_amount_in. This lets the
+    // executor run the tranches one after another so that at most one tranche's worth of funds
+    // is in flight at a time. Note that the summed quote of the tranches is not the same as the
+    // original quote (each tranche pays its own gas fees and sees the unchanged reserves).
+    // The number of tranches is checked against max_num_tranches before any is created, since a
+    // tiny max_tranche_amount_in would otherwise allocate one path per tranche without bound
+    pub fn split_into_tranches(
+        self,
+        max_tranche_amount_in: Amount,
+        max_num_tranches: usize,
+    ) -> Result<Self> {
+        if max_tranche_amount_in == 0 {
+            return Err(PublicError::InvalidTrancheAmount);
+        }
+        let get_num_tranches = |split_path: &SplitGraphPath| -> Amount {
+            // A path with nothing in it stays a single (empty) tranche
+            let num_full_tranches = split_path.fraction_amount_in / max_tranche_amount_in;
+            let has_partial_tranche = split_path.fraction_amount_in % max_tranche_amount_in != 0;
+            (num_full_tranches + has_partial_tranche as Amount).max(1)
+        };
+        let num_tranches = self
+            .paths
+            .iter()
+            .fold(0, |num_tranches: Amount, split_path| {
+                num_tranches.saturating_add(get_num_tranches(split_path))
+            });
+        if num_tranches > max_num_tranches as Amount {
+            return Err(PublicError::TooManyTranches);
+        }
+
+        let mut paths: Vec<SplitGraphPath> = Vec::with_capacity(num_tranches as usize);
+        for split_path in self.paths.into_iter() {
+            let mut remaining_amount_in = split_path.fraction_amount_in;
+            while remaining_amount_in > max_tranche_amount_in {
+                paths.push(SplitGraphPath {
+                    path: split_path.path.clone(),
+                    fraction_amount_in: max_tranche_amount_in,
+                    fraction_bps: 0, // populated below
+                });
+                remaining_amount_in -= max_tranche_amount_in;
+            }
+            paths.push(SplitGraphPath {
+                path: split_path.path,
+                fraction_amount_in: remaining_amount_in,
+                fraction_bps: 0,
+            });
+        }
+        let mut assigned_bps: u16 = 0;
+        let num_paths = paths.len();
+        for (i, split_path) in paths.iter_mut().enumerate() {
+            split_path.fraction_bps = if i + 1 == num_paths {
+                // The last tranche takes the rounding remainder, so the tranches add up to 10_000
+                10_000u16.saturating_sub(assigned_bps)
+            } else {
+                (split_path.fraction_amount_in.saturating_mul(10_000) / self.amount_in.max(1))
+                    as u16
+            };
+            assigned_bps = assigned_bps.saturating_add(split_path.fraction_bps);
+        }
+        Ok(Self {
+            paths,
+            amount_in: self.amount_in,
+            src_addr: self.src_addr,
+            dest_addr: self.dest_addr,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -377,7 +470,7 @@ impl<lifetime> fmt::Display for struct_name {
 mod graph_tests {
     use super::super::edge::{BridgeEdge, XCMBridgeEdge};
     use super::*;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
     use privadex_chain_metadata::registry::bridge::xcm_bridge_registry;
 
     fn create_token(id: UniversalTokenId) -> Token {
@@ -431,4 +524,51 @@ mod graph_tests {
         debug_println!("Edge count: {}", graph.simple_graph.edge_count());
         assert_eq!(true, true);
     }
+
+    #[test]
+    fn test_split_into_tranches() {
+        let xcm_bridge = xcm_bridge_registry::XCM_BRIDGES[0].clone();
+        let derived = DecimalFixedPoint::from_str_and_exp("10", 3);
+        let edge = Edge::Bridge(BridgeEdge::Xcm(
+            XCMBridgeEdge::from_bridge_and_derived_quantities(
+                xcm_bridge, &derived, &derived, &derived,
+            ),
+        ));
+        let graph_solution = GraphSolution {
+            paths: vec![SplitGraphPath {
+                path: GraphPath(vec![edge]),
+                fraction_amount_in: 1_000,
+                fraction_bps: 10_000,
+            }],
+            amount_in: 1_000,
+            src_addr: EthAddress::zero(),
+            dest_addr: EthAddress::zero(),
+        };
+        assert_eq!(
+            graph_solution.clone().split_into_tranches(0, 10).err(),
+            Some(PublicError::InvalidTrancheAmount)
+        );
+        // Rejected before any tranche is created
+        assert_eq!(
+            graph_solution.clone().split_into_tranches(1, 10).err(),
+            Some(PublicError::TooManyTranches)
+        );
+        assert_eq!(
+            graph_solution.clone().split_into_tranches(300, 3).err(),
+            Some(PublicError::TooManyTranches)
+        );
+
+        let staged = graph_solution.clone().split_into_tranches(300, 4).unwrap();
+        let amounts: Vec<Amount> = staged.paths.iter().map(|p| p.fraction_amount_in).collect();
+        let bps: Vec<u16> = staged.paths.iter().map(|p| p.fraction_bps).collect();
+        assert_eq!(amounts, vec![300, 300, 300, 100]);
+        assert_eq!(bps, vec![3_000, 3_000, 3_000, 1_000]);
+        assert_eq!(staged.amount_in, 1_000);
+
+        // 333 bps each would add up to 9_999
+        let staged = graph_solution.split_into_tranches(334, 3).unwrap();
+        let bps: Vec<u16> = staged.paths.iter().map(|p| p.fraction_bps).collect();
+        assert_eq!(bps, vec![3_340, 3_340, 3_320]);
+        assert_eq!(bps.iter().sum::<u16>(), 10_000);
+    }
 }
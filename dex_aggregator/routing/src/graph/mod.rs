@@ -17,5 +17,6 @@
  */
 
 pub mod edge;
+pub mod fee_breakdown;
 pub mod graph;
 pub mod traits;
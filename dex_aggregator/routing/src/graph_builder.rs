@@ -16,32 +16,61 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
+#[cfg(feature = "pink")]
 use hashbrown::HashSet;
+use ink::prelude::vec::Vec;
+#[cfg(feature = "pink")]
+use privadex_chain_metadata::common::EthAddress;
 use privadex_chain_metadata::{
-    bridge::XCMBridge,
-    chain_info::ChainInfo,
-    common::{ChainTokenId, Dex, UniversalChainId, UniversalTokenId, USD_AMOUNT_EXPONENT},
+    bridge::{WormholeBridge, XCMBridge},
+    common::{Amount, ChainTokenId, Dex, UniversalChainId, UniversalTokenId, USD_AMOUNT_EXPONENT},
     get_chain_info_from_chain_id, get_dexes_from_chain_id,
-    registry::{bridge::xcm_bridge_registry, token::universal_token_id_registry},
+    registry::{
+        bridge::{wormhole_bridge_registry, xcm_bridge_registry},
+        dex::pool_blocklist_overlay::PoolBlocklistOverlay,
+        token::universal_token_id_registry,
+    },
 };
 use privadex_common::fixed_point::DecimalFixedPoint;
 
 use crate::graph::{
-    edge::{BridgeEdge, Edge, SwapEdge, UnwrapEdge, WrapEdge, XCMBridgeEdge},
+    edge::{
+        BridgeEdge, ConstantProductAMMSwapEdge, Edge, SwapEdge, UnwrapEdge, WormholeBridgeEdge,
+        WrapEdge, XCMBridgeEdge,
+    },
     graph::{Graph, Token},
 };
-use crate::graphql_client::get_additional_tokens_and_edges;
+#[cfg(feature = "pink")]
+use crate::graphql_client::{
+    get_additional_direct_pair_tokens_and_edges, get_additional_tokens_and_edges_for_dexes,
+    get_token_prices,
+};
+#[cfg(feature = "pink")]
+use crate::routing_config::RoutingConfig;
 use crate::{PublicError, Result};
 
 // Set low enough so that we include the ASTR/GLMR pool in ArthSwap
 // but high enough that the largest HTTP response is less than 16KB
 // (eventually we need to implement pagination of results)
-const MIN_TOKEN_PAIR_RESERVE_USD: u32 = 12_000;
+pub(crate) const MIN_TOKEN_PAIR_RESERVE_USD: u32 = 12_000;
 
-// This function *can* return an error if MIN_TOKEN_PAIR_RESERVE_USD filters out too many edges!
+// This function *can* return an error if the reserve threshold filters out too many edges!
 // I choose to return error instead of skipping adding those edges because I don't want silent
-// unexpected behavior
+// unexpected behavior.
+// A DEX whose subgraph is unreachable is the exception: we skip it and build the graph from the
+// remaining DEXes (and only fail if none of them responded)
+#[cfg(feature = "pink")]
 pub fn create_graph_from_chain_ids(chain_ids: &[UniversalChainId]) -> Result<Graph> {
+    create_graph_from_chain_ids_with_config(chain_ids, &RoutingConfig::default())
+}
+
+// Same as create_graph_from_chain_ids, but only keeps the DEX pairs with at least
+// routing_config.min_token_pair_reserve_usd of liquidity
+#[cfg(feature = "pink")]
+pub fn create_graph_from_chain_ids_with_config(
+    chain_ids: &[UniversalChainId],
+    routing_config: &RoutingConfig,
+) -> Result<Graph> {
     let mut graph = Graph::new();
 
     // Note that ORDER MATTERS in the adding of edges below.
@@ -51,60 +80,316 @@ pub fn create_graph_from_chain_ids(chain_ids: &[UniversalChainId]) -> Result<Gra
     // Then we add the XCMBridgeEdges, which create the native tokens. If two tokens are connected
     // over a bridge, we use the fact that the derived_usd and derived_eth must be equal to set the
     // native tokens' derived_eth and derived_usd
+    // Then we add the WormholeBridgeEdges, which can create the token on a chain without DEXes
+    // (e.g. Ethereum) from its counterpart on the other end of the lane
     // Finally we add the WrapEdges and UnwrapEdges
 
     // 1. Add ConstantProductAMMSwapEdges from each DEX (and connecting XC20, ERC20 vertices)
     {
-        let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+        let mut dexes: Vec<(&'static Dex, Amount)> = Vec::new();
         for chain_id in chain_ids.iter() {
             let chain_info =
                 get_chain_info_from_chain_id(chain_id).ok_or(PublicError::UnregisteredChainId)?;
-
-            let dexes = get_dexes_from_chain_id(chain_id);
-            for dex in dexes.into_iter() {
-                let _ = update_graph_with_dex(dex, chain_info, &mut token_id_set, &mut graph)?;
+            for dex in get_dexes_from_chain_id(chain_id).into_iter() {
+                dexes.push((dex, chain_info.avg_gas_fee_in_native_token));
             }
         }
+        let _ = update_graph_with_dexes(
+            &dexes,
+            routing_config.min_token_pair_reserve_usd,
+            &mut graph,
+        )?;
     }
 
-    // 2. Add XCMBridgeEdges (and connecting XC20 vertices)
-    for xcm_bridge in xcm_bridge_registry::XCM_BRIDGES.iter() {
-        let _ = update_graph_with_xcm_bridge(xcm_bridge, &mut graph)?;
+    let _ = update_graph_with_bridge_and_wrap_edges(chain_ids, &mut graph)?;
+    Ok(graph)
+}
+
+// Steps 2-4 of create_graph_from_chain_ids, i.e. everything that isn't sourced from the DEX
+// subgraphs. Must be called after the DEXes' swap edges are in the graph
+pub(crate) fn update_graph_with_bridge_and_wrap_edges(
+    chain_ids: &[UniversalChainId],
+    graph: &mut Graph,
+) -> Result<()> {
+    // 2. Add XCMBridgeEdges (and connecting XC20 vertices). We only consider bridges between
+    // the requested chains, and XCM never crosses relays (e.g. Polkadot <-> Kusama)
+    for xcm_bridge in xcm_bridge_registry::XCM_BRIDGES
+        .iter()
+        .filter(|xcm_bridge| is_xcm_bridge_within_chains(xcm_bridge, chain_ids))
+    {
+        let _ = update_graph_with_xcm_bridge(xcm_bridge, graph)?;
+    }
+
+    // 3. Add WormholeBridgeEdges between the requested chains
+    for wormhole_bridge in wormhole_bridge_registry::WORMHOLE_BRIDGES
+        .iter()
+        .filter(|wormhole_bridge| is_wormhole_bridge_within_chains(wormhole_bridge, chain_ids))
+    {
+        let _ = update_graph_with_wormhole_bridge(wormhole_bridge, graph)?;
     }
 
-    // 3. Add WrapEdge and UnwrapEdge. We expect that the wrapped native ERC20 tokens is already
+    // 4. Add WrapEdge and UnwrapEdge. We expect that the wrapped native ERC20 tokens is already
     // added to the graph, but Native tokens need not have been added (if the continue block
-    // was hit in step 2)
-    for chain_id in chain_ids.iter() {
-        let _ = update_graph_with_wrap_edges(chain_id, &mut graph)?;
+    // was hit in step 2). Chains without DEXes (only reachable over a Wormhole lane) have no
+    // priced wrapped native token, so we skip them
+    for chain_id in chain_ids
+        .iter()
+        .filter(|chain_id| !get_dexes_from_chain_id(chain_id).is_empty())
+    {
+        let _ = update_graph_with_wrap_edges(chain_id, graph)?;
     }
 
+    Ok(())
+}
+
+// Graph with only the direct pools between two tokens on the same chain (across all of that
+// chain's DEXes). It is a fast path for single-hop quotes, so anything that needs bridging,
+// wrapping or an intermediate token returns NoPathFound and the caller should fall back to
+// create_graph_from_chain_ids
+#[cfg(feature = "pink")]
+pub fn create_direct_pair_graph(
+    src_token_id: &UniversalTokenId,
+    dest_token_id: &UniversalTokenId,
+) -> Result<Graph> {
+    if src_token_id == dest_token_id {
+        return Err(PublicError::SrcTokenDestTokenAreSame);
+    }
+    if src_token_id.chain != dest_token_id.chain {
+        return Err(PublicError::NoPathFound);
+    }
+    let (src_addr, dest_addr) = match (
+        get_dex_token_address(&src_token_id.id),
+        get_dex_token_address(&dest_token_id.id),
+    ) {
+        (Some(src_addr), Some(dest_addr)) => (src_addr, dest_addr),
+        _ => return Err(PublicError::NoPathFound),
+    };
+    let chain_info = get_chain_info_from_chain_id(&src_token_id.chain)
+        .ok_or(PublicError::UnregisteredChainId)?;
+
+    let mut graph = Graph::new();
+    let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+    for dex in get_dexes_from_chain_id(&src_token_id.chain).into_iter() {
+        let (tokens, edges) = match get_additional_direct_pair_tokens_and_edges(
+            dex,
+            &src_addr,
+            &dest_addr,
+            chain_info.avg_gas_fee_in_native_token,
+            &mut token_id_set,
+        ) {
+            Ok(tokens_and_edges) => tokens_and_edges,
+            Err(e) => {
+                ink::env::debug_println!("Skipping DEX {:?} in direct pair graph: {:?}", dex.id, e);
+                continue;
+            }
+        };
+        for token in tokens.into_iter() {
+            let _ = graph.add_vertex(token);
+        }
+        for edge in edges.into_iter() {
+            let _ = graph.add_edge(Edge::Swap(SwapEdge::CPMM(edge)))?;
+        }
+    }
+    if graph.get_vertex(src_token_id).is_none() || graph.get_vertex(dest_token_id).is_none() {
+        return Err(PublicError::NoPathFound);
+    }
     Ok(graph)
 }
 
-fn update_graph_with_dex<'a>(
-    dex: &'static Dex,
-    chain_info: &'static ChainInfo,
-    token_id_set: &'a mut HashSet<UniversalTokenId>,
-    graph: &'a mut Graph,
+// Graph with only the bridges, wraps and unwraps between chain_ids, for moving a token to another
+// chain as-is (see SORConfig::transfer_only). The bridge and wrap fee estimates still need token
+// prices, but instead of every DEX's pairs we only fetch the prices of the tokens on those edges,
+// i.e. one small request per chain
+#[cfg(feature = "pink")]
+pub fn create_transfer_graph(chain_ids: &[UniversalChainId]) -> Result<Graph> {
+    let mut graph = Graph::new();
+    for chain_id in chain_ids
+        .iter()
+        .filter(|chain_id| !get_dexes_from_chain_id(chain_id).is_empty())
+    {
+        let token_addrs = get_transfer_token_addresses(chain_id, chain_ids)?;
+        // Every DEX on the chain prices the same tokens, so we only need one that responds
+        let tokens = get_dexes_from_chain_id(chain_id)
+            .into_iter()
+            .find_map(|dex| match get_token_prices(dex, &token_addrs) {
+                Ok(tokens) => Some(tokens),
+                Err(e) => {
+                    ink::env::debug_println!("Skipping DEX {:?} for token prices: {:?}", dex.id, e);
+                    None
+                }
+            })
+            .ok_or(PublicError::RequestFailed)?;
+        for token in tokens.into_iter() {
+            let _ = graph.add_vertex(token);
+        }
+    }
+    let _ = update_graph_with_bridge_and_wrap_edges(chain_ids, &mut graph)?;
+    Ok(graph)
+}
+
+// Tokens on chain_id that a bridge to another of chain_ids (or a wrap) starts or ends at
+#[cfg(feature = "pink")]
+fn get_transfer_token_addresses(
+    chain_id: &UniversalChainId,
+    chain_ids: &[UniversalChainId],
+) -> Result<Vec<EthAddress>> {
+    let chain_info =
+        get_chain_info_from_chain_id(chain_id).ok_or(PublicError::UnregisteredChainId)?;
+    let xcm_tokens = xcm_bridge_registry::XCM_BRIDGES
+        .iter()
+        .filter(|xcm_bridge| is_xcm_bridge_within_chains(xcm_bridge, chain_ids))
+        .flat_map(|xcm_bridge| [&xcm_bridge.src_token, &xcm_bridge.dest_token]);
+    let wormhole_tokens = wormhole_bridge_registry::WORMHOLE_BRIDGES
+        .iter()
+        .filter(|wormhole_bridge| is_wormhole_bridge_within_chains(wormhole_bridge, chain_ids))
+        .flat_map(|wormhole_bridge| [&wormhole_bridge.src_token, &wormhole_bridge.dest_token]);
+
+    let mut token_addrs: Vec<EthAddress> = chain_info.weth_addr.into_iter().collect();
+    for token in xcm_tokens.chain(wormhole_tokens) {
+        if token.chain != *chain_id {
+            continue;
+        }
+        if let Some(addr) = get_dex_token_address(&token.id) {
+            if !token_addrs.contains(&addr) {
+                token_addrs.push(addr);
+            }
+        }
+    }
+    Ok(token_addrs)
+}
+
+// Address the DEX subgraphs index the token by. Native tokens only trade in their wrapped form
+#[cfg(feature = "pink")]
+fn get_dex_token_address(token_id: &ChainTokenId) -> Option<EthAddress> {
+    match token_id {
+        ChainTokenId::Native => None,
+        ChainTokenId::ERC20(erc20) => Some(erc20.addr),
+        ChainTokenId::XC20(xc20) => Some(xc20.get_eth_address()),
+    }
+}
+
+fn is_xcm_bridge_within_chains(xcm_bridge: &XCMBridge, chain_ids: &[UniversalChainId]) -> bool {
+    let (src_chain, dest_chain) = (&xcm_bridge.src_token.chain, &xcm_bridge.dest_token.chain);
+    src_chain.shares_relay_with(dest_chain)
+        && chain_ids.contains(src_chain)
+        && chain_ids.contains(dest_chain)
+}
+
+fn is_wormhole_bridge_within_chains(
+    wormhole_bridge: &WormholeBridge,
+    chain_ids: &[UniversalChainId],
+) -> bool {
+    chain_ids.contains(&wormhole_bridge.src_token.chain)
+        && chain_ids.contains(&wormhole_bridge.dest_token.chain)
+}
+
+#[cfg(feature = "pink")]
+fn update_graph_with_dexes(
+    dexes: &[(&'static Dex, Amount)],
+    min_token_pair_reserve_usd: u32,
+    graph: &mut Graph,
+) -> Result<()> {
+    let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+    let dex_results = get_additional_tokens_and_edges_for_dexes(
+        dexes,
+        min_token_pair_reserve_usd,
+        &mut token_id_set,
+    );
+    update_graph_with_dex_results(dexes, dex_results, graph)
+}
+
+// dex_results[i] holds dexes[i]'s tokens and edges. DEXes that failed are skipped
+pub(crate) fn update_graph_with_dex_results(
+    dexes: &[(&'static Dex, Amount)],
+    dex_results: Vec<Result<(Vec<Token>, Vec<ConstantProductAMMSwapEdge>)>>,
+    graph: &mut Graph,
 ) -> Result<()> {
-    let (tokens, edges) = get_additional_tokens_and_edges(
-        dex,
-        MIN_TOKEN_PAIR_RESERVE_USD,
-        chain_info.avg_gas_fee_in_native_token,
-        token_id_set,
-    )?;
-    // ink_env::debug_println!("let tokens: Vec<Token> = vec!{:?};", tokens);
-    // ink_env::debug_println!("let edges: Vec<ConstantProductAMMSwapEdge> = vec!{:?};", edges);
-    for token in tokens.into_iter() {
-        let _ = graph.add_vertex(token);
-    }
-    for edge in edges.into_iter() {
-        let _ = graph.add_edge(Edge::Swap(SwapEdge::CPMM(edge)))?;
+    let mut num_dexes_added = 0;
+    for ((dex, _), dex_result) in dexes.iter().zip(dex_results.into_iter()) {
+        let (tokens, edges) = match dex_result {
+            Ok(tokens_and_edges) => tokens_and_edges,
+            Err(e) => {
+                ink::env::debug_println!("Skipping DEX {:?} in graph: {:?}", dex.id, e);
+                continue;
+            }
+        };
+        // ink::env::debug_println!("let tokens: Vec<Token> = vec!{:?};", tokens);
+        // ink::env::debug_println!("let edges: Vec<ConstantProductAMMSwapEdge> = vec!{:?};", edges);
+        for token in tokens.into_iter() {
+            let _ = graph.add_vertex(token);
+        }
+        for edge in edges.into_iter() {
+            let _ = graph.add_edge(Edge::Swap(SwapEdge::CPMM(edge)))?;
+        }
+        num_dexes_added += 1;
+    }
+    if num_dexes_added == 0 && !dexes.is_empty() {
+        return Err(PublicError::RequestFailed);
     }
     Ok(())
 }
 
+// Drops the swap edges of the operator's blocked pools, whichever way the graph was built (full,
+// direct pair or snapshot). Returns the number of edges dropped
+pub fn exclude_blocked_pools(graph: &mut Graph, pool_blocklist: &PoolBlocklistOverlay) -> usize {
+    if pool_blocklist.is_empty() {
+        return 0;
+    }
+    graph.retain_edges(|edge| match edge {
+        Edge::Swap(SwapEdge::CPMM(cpmm_edge)) => {
+            !pool_blocklist.is_blocked(cpmm_edge.dex.chain_id, &cpmm_edge.pair_address)
+        }
+        _ => true,
+    })
+}
+
+// Overrides the registry's XCM bridge fee estimates. get_bridge_fee returns the fee (in dest
+// token units) for a (src_token, dest_token) lane, or None to keep the estimate. Returns the
+// number of edges updated
+pub fn apply_xcm_bridge_fees<F>(graph: &mut Graph, get_bridge_fee: F) -> usize
+where
+    F: Fn(&UniversalTokenId, &UniversalTokenId) -> Option<Amount>,
+{
+    // The edges' prices come from their dest token vertex (see update_graph_with_xcm_bridge)
+    let updates: Vec<(UniversalTokenId, UniversalTokenId, Amount, Token)> = graph
+        .edges()
+        .filter_map(|edge| match edge {
+            Edge::Bridge(BridgeEdge::Xcm(xcm_edge)) => {
+                let bridge_fee = get_bridge_fee(&xcm_edge.src_token, &xcm_edge.dest_token)?;
+                let dest_token = graph.get_token(&xcm_edge.dest_token)?.clone();
+                Some((
+                    xcm_edge.src_token.clone(),
+                    xcm_edge.dest_token.clone(),
+                    bridge_fee,
+                    dest_token,
+                ))
+            }
+            _ => None,
+        })
+        .collect();
+    if updates.is_empty() {
+        return 0;
+    }
+    let mut num_updated = 0;
+    for edge in graph.edges_mut() {
+        if let Edge::Bridge(BridgeEdge::Xcm(xcm_edge)) = edge {
+            if let Some((_, _, bridge_fee, dest_token)) =
+                updates.iter().find(|(src_token_id, dest_token_id, _, _)| {
+                    src_token_id == &xcm_edge.src_token && dest_token_id == &xcm_edge.dest_token
+                })
+            {
+                xcm_edge.set_bridge_fee_in_dest_token(
+                    *bridge_fee,
+                    &dest_token.derived_eth,
+                    &dest_token.derived_usd,
+                );
+                num_updated += 1;
+            }
+        }
+    }
+    num_updated
+}
+
 /// Only should be called externally by tests!
 pub fn update_graph_with_xcm_bridge<'a, 'b>(
     xcm_bridge: &'a XCMBridge,
@@ -168,6 +453,45 @@ pub fn update_graph_with_xcm_bridge<'a, 'b>(
     )))
 }
 
+/// Only should be called externally by tests!
+pub fn update_graph_with_wormhole_bridge<'a, 'b>(
+    wormhole_bridge: &'a WormholeBridge,
+    graph: &'b mut Graph,
+) -> Result<()> {
+    let (present_token, missing_token_id) = {
+        match (
+            graph.get_token(&wormhole_bridge.src_token),
+            graph.get_token(&wormhole_bridge.dest_token),
+        ) {
+            (Some(src), Some(_)) => (src, None),
+            (Some(src), None) => (src, Some(wormhole_bridge.dest_token.clone())),
+            (None, Some(dest)) => (dest, Some(wormhole_bridge.src_token.clone())),
+            // Neither end is priced, so we skip adding the edge (like XCMBridgeEdges)
+            (None, None) => {
+                return Ok(());
+            }
+        }
+    };
+    let token_derived_usd = present_token.derived_usd.clone();
+    if let Some(id) = missing_token_id {
+        // Both ends of the lane are the same asset, so they share derived_usd. derived_eth
+        // is per chain and unknown for a chain without DEXes; WormholeBridgeEdge fees are
+        // estimated in the bridged token so we never use it, and just copy it over
+        let missing_token = Token {
+            id,
+            derived_eth: present_token.derived_eth.clone(),
+            derived_usd: token_derived_usd.clone(),
+        };
+        let _ = graph.add_vertex(missing_token);
+    }
+    graph.add_edge(Edge::Bridge(BridgeEdge::Wormhole(
+        WormholeBridgeEdge::from_bridge_and_derived_usd(
+            wormhole_bridge.clone(),
+            &token_derived_usd,
+        ),
+    )))
+}
+
 /// Only should be called externally by tests!
 pub fn update_graph_with_wrap_edges<'a, 'b>(
     chain_id: &'a UniversalChainId,
@@ -221,11 +545,15 @@ pub fn update_graph_with_wrap_edges<'a, 'b>(
 #[cfg(test)]
 mod graph_builder_tests {
     use super::*;
-    use ink_env::debug_println;
-    use privadex_chain_metadata::registry::chain::universal_chain_id_registry::{
-        ASTAR, MOONBEAM, POLKADOT,
+    use hex_literal::hex;
+    #[cfg(feature = "pink")]
+    use ink::env::debug_println;
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, ETHEREUM, KHALA, KUSAMA, MOONBEAM, POLKADOT},
+        dex::pool_blocklist_overlay::BlockedPool,
     };
 
+    #[cfg(feature = "pink")]
     #[test]
     fn test() {
         pink_extension_runtime::mock_ext::mock_all_ext();
@@ -236,4 +564,181 @@ mod graph_builder_tests {
         assert!(graph.simple_graph.vertex_count() > 0);
         assert!(graph.simple_graph.edge_count() > 0);
     }
+
+    #[cfg(feature = "pink")]
+    #[test]
+    fn test_direct_pair_graph_rejects_non_single_hop_pairs() {
+        // None of these cases need a subgraph request
+        assert_eq!(
+            create_direct_pair_graph(
+                &universal_token_id_registry::DOT_MOONBEAM,
+                &universal_token_id_registry::DOT_MOONBEAM
+            )
+            .err(),
+            Some(PublicError::SrcTokenDestTokenAreSame)
+        );
+        assert_eq!(
+            create_direct_pair_graph(
+                &universal_token_id_registry::DOT_MOONBEAM,
+                &universal_token_id_registry::USDT_ASTAR
+            )
+            .err(),
+            Some(PublicError::NoPathFound)
+        );
+        assert_eq!(
+            create_direct_pair_graph(
+                &universal_token_id_registry::GLMR_NATIVE,
+                &universal_token_id_registry::DOT_MOONBEAM
+            )
+            .err(),
+            Some(PublicError::NoPathFound)
+        );
+    }
+
+    #[cfg(feature = "pink")]
+    #[test]
+    fn test_transfer_token_addresses() {
+        let chain_ids = [ASTAR, MOONBEAM, POLKADOT];
+        let token_addrs = get_transfer_token_addresses(&MOONBEAM, &chain_ids).unwrap();
+        let wglmr_addr = get_chain_info_from_chain_id(&MOONBEAM)
+            .unwrap()
+            .weth_addr
+            .unwrap();
+        let xcdot_addr =
+            get_dex_token_address(&universal_token_id_registry::DOT_MOONBEAM.id).unwrap();
+        assert_eq!(token_addrs[0], wglmr_addr);
+        assert!(token_addrs.contains(&xcdot_addr));
+        assert_eq!(
+            token_addrs
+                .iter()
+                .filter(|addr| **addr == xcdot_addr)
+                .count(),
+            1
+        );
+
+        // Polkadot only has its native token, which no subgraph prices
+        assert!(get_transfer_token_addresses(&POLKADOT, &chain_ids)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_xcm_bridges_filtered_by_chain_and_relay() {
+        let polkadot_chains = vec![ASTAR, MOONBEAM, POLKADOT];
+        let kusama_chains = vec![KHALA, KUSAMA];
+        let mixed_chains = vec![MOONBEAM, KHALA, KUSAMA];
+        for xcm_bridge in xcm_bridge_registry::XCM_BRIDGES.iter() {
+            assert!(is_xcm_bridge_within_chains(xcm_bridge, &polkadot_chains));
+            assert!(!is_xcm_bridge_within_chains(xcm_bridge, &kusama_chains));
+        }
+        // Moonbeam's bridges all go to Astar or Polkadot, neither of which is requested
+        assert!(xcm_bridge_registry::XCM_BRIDGES
+            .iter()
+            .all(|xcm_bridge| !is_xcm_bridge_within_chains(xcm_bridge, &mixed_chains)));
+    }
+
+    #[test]
+    fn test_wormhole_bridge_creates_token_on_chain_without_dexes() {
+        let (eth_usdc, moonbeam_usdc) = (
+            universal_token_id_registry::USDC_ETHEREUM,
+            universal_token_id_registry::USDC_WH_MOONBEAM,
+        );
+        for wormhole_bridge in wormhole_bridge_registry::WORMHOLE_BRIDGES.iter() {
+            assert!(is_wormhole_bridge_within_chains(
+                wormhole_bridge,
+                &[MOONBEAM, ETHEREUM]
+            ));
+            assert!(!is_wormhole_bridge_within_chains(
+                wormhole_bridge,
+                &[ASTAR, MOONBEAM, POLKADOT]
+            ));
+        }
+
+        let mut graph = Graph::new();
+        // Unpriced on both ends, so nothing is added
+        let _ = update_graph_with_wormhole_bridge(
+            &wormhole_bridge_registry::WORMHOLE_BRIDGES[0],
+            &mut graph,
+        )
+        .unwrap();
+        assert!(graph.get_token(&eth_usdc).is_none());
+
+        let _ = graph.add_vertex(Token {
+            id: moonbeam_usdc.clone(),
+            derived_eth: DecimalFixedPoint::from_str_and_exp("3", 12),
+            derived_usd: DecimalFixedPoint::from_str_and_exp("0.000001", 18),
+        });
+        for wormhole_bridge in wormhole_bridge_registry::WORMHOLE_BRIDGES.iter() {
+            let _ = update_graph_with_wormhole_bridge(wormhole_bridge, &mut graph).unwrap();
+        }
+        assert_eq!(
+            graph.get_token(&eth_usdc).unwrap().derived_usd,
+            graph.get_token(&moonbeam_usdc).unwrap().derived_usd
+        );
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_exclude_blocked_pools() {
+        let mut graph = crate::test_utilities::graph_factory::small_graph();
+        let num_edges = graph.edge_count();
+        let stellaswap_pair_address = privadex_chain_metadata::common::EthAddress {
+            0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af"),
+        };
+        let mut pool_blocklist = PoolBlocklistOverlay::new();
+        // Same address on another chain
+        pool_blocklist.replace(vec![BlockedPool {
+            chain_id: ASTAR,
+            pair_address: stellaswap_pair_address,
+        }]);
+        assert_eq!(exclude_blocked_pools(&mut graph, &pool_blocklist), 0);
+
+        pool_blocklist.replace(vec![BlockedPool {
+            chain_id: MOONBEAM,
+            pair_address: stellaswap_pair_address,
+        }]);
+        // Both directions of the pool
+        assert_eq!(exclude_blocked_pools(&mut graph, &pool_blocklist), 2);
+        assert_eq!(graph.edge_count(), num_edges - 2);
+    }
+
+    #[test]
+    fn test_apply_xcm_bridge_fees() {
+        let mut graph = crate::test_utilities::graph_factory::small_graph();
+        assert_eq!(apply_xcm_bridge_fees(&mut graph, |_, _| None), 0);
+
+        let (src_token, dest_token) = graph
+            .edges()
+            .find_map(|edge| match edge {
+                Edge::Bridge(BridgeEdge::Xcm(xcm_edge)) => {
+                    Some((xcm_edge.src_token.clone(), xcm_edge.dest_token.clone()))
+                }
+                _ => None,
+            })
+            .expect("Small graph has XCM edges");
+        let bridge_fee = 1_000_000;
+        assert_eq!(
+            apply_xcm_bridge_fees(&mut graph, |src, dest| {
+                (src == &src_token && dest == &dest_token).then_some(bridge_fee)
+            }),
+            1
+        );
+        let derived_eth = graph.get_token(&dest_token).unwrap().derived_eth.clone();
+        let xcm_edge = graph
+            .edges()
+            .find_map(|edge| match edge {
+                Edge::Bridge(BridgeEdge::Xcm(xcm_edge))
+                    if xcm_edge.src_token == src_token && xcm_edge.dest_token == dest_token =>
+                {
+                    Some(xcm_edge)
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(xcm_edge.estimated_bridge_fee_in_dest_token, bridge_fee);
+        assert_eq!(
+            xcm_edge.estimated_bridge_fee_in_dest_chain_native_token,
+            derived_eth.mul_u128(bridge_fee)
+        );
+    }
 }
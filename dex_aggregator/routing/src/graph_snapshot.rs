@@ -0,0 +1,235 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Builds the same graph as graph_builder::create_graph_from_chain_ids, but from DEX subgraph
+// responses that the caller already fetched. Nothing in here makes an HTTP request, so this is
+// what a build without the pink feature uses
+use hashbrown::HashSet;
+use ink::prelude::vec::Vec;
+#[cfg(feature = "pink")]
+use privadex_chain_metadata::get_dexes_from_chain_id;
+use privadex_chain_metadata::{
+    common::{Amount, Dex, UniversalChainId, UniversalTokenId},
+    get_chain_info_from_chain_id, get_dex_from_dex_id,
+    registry::dex::DexId,
+};
+use scale::{Decode, DecodeAll, Encode};
+
+use crate::graph::graph::Graph;
+use crate::graph_builder::{
+    update_graph_with_bridge_and_wrap_edges, update_graph_with_dex_results,
+    MIN_TOKEN_PAIR_RESERVE_USD,
+};
+#[cfg(feature = "pink")]
+use crate::graphql_client::batch_get_combined_responses;
+use crate::graphql_client::{combined_query_body, get_additional_tokens_and_edges_from_response};
+use crate::{PublicError, Result};
+
+// Bump whenever GraphSnapshot's encoding or the subgraph query changes
+pub const GRAPH_SNAPSHOT_VERSION: u8 = 2;
+
+pub struct DexSnapshot<'a> {
+    pub dex: &'static Dex,
+    // Raw response to POSTing dex_snapshot_query_body(dex) to dex.graphql_url
+    pub subgraph_response: &'a [u8],
+}
+
+// The query the snapshot's subgraph responses must answer (same filters as the contract uses)
+pub fn dex_snapshot_query_body(dex: &Dex) -> Vec<u8> {
+    combined_query_body(dex, MIN_TOKEN_PAIR_RESERVE_USD)
+}
+
+// Snapshots of DEXes on chains outside of chain_ids are ignored, and a snapshot whose response
+// can't be decoded is skipped like an unreachable subgraph is in create_graph_from_chain_ids
+pub fn create_graph_from_snapshot(
+    chain_ids: &[UniversalChainId],
+    dex_snapshots: &[DexSnapshot],
+) -> Result<Graph> {
+    let mut graph = Graph::new();
+
+    let mut dexes: Vec<(&'static Dex, Amount)> = Vec::new();
+    let mut dex_results = Vec::new();
+    let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+    for chain_id in chain_ids.iter() {
+        let chain_info =
+            get_chain_info_from_chain_id(chain_id).ok_or(PublicError::UnregisteredChainId)?;
+        for snapshot in dex_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.dex.chain_id == *chain_id)
+        {
+            dexes.push((snapshot.dex, chain_info.avg_gas_fee_in_native_token));
+            dex_results.push(get_additional_tokens_and_edges_from_response(
+                snapshot.dex,
+                snapshot.subgraph_response,
+                chain_info.avg_gas_fee_in_native_token,
+                &mut token_id_set,
+            ));
+        }
+    }
+    let _ = update_graph_with_dex_results(&dexes, dex_results, &mut graph)?;
+    let _ = update_graph_with_bridge_and_wrap_edges(chain_ids, &mut graph)?;
+    Ok(graph)
+}
+
+// Snapshot file format: the SCALE encoding of this struct (version is the first byte). We store
+// the raw subgraph responses instead of the graph itself, so loading a snapshot goes through the
+// exact same graph construction as the contract
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct GraphSnapshot {
+    pub version: u8,
+    pub chain_ids: Vec<UniversalChainId>,
+    pub dex_responses: Vec<(DexId, Vec<u8>)>,
+}
+
+impl GraphSnapshot {
+    // DEXes whose subgraph fails are left out of the snapshot (the graph is then partial, same as
+    // create_graph_from_chain_ids). We only error if none of them responded
+    #[cfg(feature = "pink")]
+    pub fn fetch(chain_ids: &[UniversalChainId]) -> Result<Self> {
+        let mut dexes: Vec<&'static Dex> = Vec::new();
+        for chain_id in chain_ids.iter() {
+            let _ =
+                get_chain_info_from_chain_id(chain_id).ok_or(PublicError::UnregisteredChainId)?;
+            dexes.extend(get_dexes_from_chain_id(chain_id));
+        }
+        let responses = batch_get_combined_responses(&dexes, MIN_TOKEN_PAIR_RESERVE_USD);
+        let dex_responses: Vec<(DexId, Vec<u8>)> = dexes
+            .iter()
+            .zip(responses.into_iter())
+            .filter_map(|(dex, response)| match response {
+                Ok(raw_bytes) => Some((dex.id, raw_bytes)),
+                Err(e) => {
+                    ink::env::debug_println!("Leaving DEX {:?} out of snapshot: {:?}", dex.id, e);
+                    None
+                }
+            })
+            .collect();
+        if dex_responses.is_empty() && !dexes.is_empty() {
+            return Err(PublicError::RequestFailed);
+        }
+        Ok(Self {
+            version: GRAPH_SNAPSHOT_VERSION,
+            chain_ids: chain_ids.to_vec(),
+            dex_responses,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.first() {
+            Some(&GRAPH_SNAPSHOT_VERSION) => {}
+            Some(version) => return Err(PublicError::UnsupportedSnapshotVersion(*version)),
+            None => return Err(PublicError::InvalidBody),
+        }
+        Self::decode_all(&mut &bytes[..]).map_err(|_| PublicError::InvalidBody)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    pub fn to_graph(&self) -> Result<Graph> {
+        let dex_snapshots: Vec<DexSnapshot> = self
+            .dex_responses
+            .iter()
+            .map(|(dex_id, subgraph_response)| DexSnapshot {
+                dex: get_dex_from_dex_id(dex_id),
+                subgraph_response,
+            })
+            .collect();
+        create_graph_from_snapshot(&self.chain_ids, &dex_snapshots)
+    }
+}
+
+#[cfg(test)]
+mod graph_snapshot_tests {
+    use privadex_chain_metadata::registry::{
+        chain::universal_chain_id_registry::{ASTAR, MOONBEAM},
+        dex::dex_registry::{ARTHSWAP, STELLASWAP},
+    };
+
+    use super::*;
+
+    const ARTHSWAP_RESPONSE: &str = "{\"data\":{\
+        \"pairs\":[\
+            {\"id\":\"0xccefddff4808f3e1e0340e19e43f1e9fd088b3f2\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\
+                \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                \"token1\":{\"decimals\":18,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+            }\
+        ], \
+        \"bundleById\":{\"ethPrice\":\"0.0396186463623557942761935\"}\
+    }}";
+
+    #[test]
+    fn test_create_graph_from_snapshot() {
+        let dex_snapshots = [
+            DexSnapshot {
+                dex: &ARTHSWAP,
+                subgraph_response: ARTHSWAP_RESPONSE.as_bytes(),
+            },
+            // Not on a requested chain, so its (invalid) response is never decoded
+            DexSnapshot {
+                dex: &STELLASWAP,
+                subgraph_response: "not json".as_bytes(),
+            },
+        ];
+        let graph = create_graph_from_snapshot(&[ASTAR], &dex_snapshots).unwrap();
+        // Both pair tokens plus native ASTR from the wrap edges
+        assert_eq!(graph.simple_graph.vertex_count(), 3);
+        // Both directions of the pair plus wrap and unwrap
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_graph_snapshot_roundtrip() {
+        let snapshot = GraphSnapshot {
+            version: GRAPH_SNAPSHOT_VERSION,
+            chain_ids: vec![ASTAR],
+            dex_responses: vec![(DexId::Arthswap, ARTHSWAP_RESPONSE.as_bytes().to_vec())],
+        };
+        let decoded = GraphSnapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+        assert_eq!(decoded, snapshot);
+        assert_eq!(decoded.to_graph().unwrap().edge_count(), 4);
+
+        let mut bytes = snapshot.to_bytes();
+        bytes[0] = GRAPH_SNAPSHOT_VERSION + 1;
+        assert_eq!(
+            GraphSnapshot::from_bytes(&bytes),
+            Err(PublicError::UnsupportedSnapshotVersion(
+                GRAPH_SNAPSHOT_VERSION + 1
+            ))
+        );
+        let mut bytes = snapshot.to_bytes();
+        bytes.push(0);
+        assert_eq!(
+            GraphSnapshot::from_bytes(&bytes),
+            Err(PublicError::InvalidBody)
+        );
+    }
+
+    #[test]
+    fn test_create_graph_from_snapshot_all_responses_invalid() {
+        let dex_snapshots = [DexSnapshot {
+            dex: &STELLASWAP,
+            subgraph_response: "{\"errors\":[]}".as_bytes(),
+        }];
+        assert_eq!(
+            create_graph_from_snapshot(&[MOONBEAM], &dex_snapshots).err(),
+            Some(PublicError::RequestFailed)
+        );
+    }
+}
@@ -17,7 +17,9 @@
  */
 
 // Can create and move this to a price feed crate, but this is a good place until then
-use ink_prelude::{vec, vec::Vec};
+#[cfg(feature = "pink")]
+use ink::prelude::string::String;
+use ink::prelude::{vec, vec::Vec};
 use privadex_chain_metadata::{
     common::{
         Amount, Dex, EthAddress, UniversalTokenId, NATIVE_TOKEN_DECIMALS, USD_AMOUNT_EXPONENT,
@@ -31,32 +33,272 @@ use crate::{PublicError, Result};
 
 use hashbrown::HashSet;
 
-#[allow(dead_code)]
-pub fn get_tokens_and_edges(
+// Targeted lookup of the pools between two tokens on a single DEX. This skips every other pair,
+// so it is much cheaper than get_additional_tokens_and_edges_for_dexes when we only care about a
+// direct swap
+#[cfg(feature = "pink")]
+pub fn get_additional_direct_pair_tokens_and_edges<'a>(
     dex: &'static Dex,
-    min_token_pair_reserve_usd: u32,
+    token_a: &EthAddress,
+    token_b: &EthAddress,
     avg_gas_fee_in_native_token: Amount,
+    token_id_set: &'a mut HashSet<UniversalTokenId>, // Tokens already in this set won't be added
 ) -> Result<(Vec<Token>, Vec<ConstantProductAMMSwapEdge>)> {
-    let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
-    get_additional_tokens_and_edges(
+    let combined_raw = graphql_low_level_interface::direct_pair_call(
+        dex.graphql_url,
+        token_a,
+        token_b,
+        dex.has_fee_tiers(),
+    )?;
+    Ok(tokens_and_edges_from_response(
         dex,
-        min_token_pair_reserve_usd,
+        &combined_raw,
         avg_gas_fee_in_native_token,
-        &mut token_id_set,
+        token_id_set,
+    ))
+}
+
+// Max number of subgraph requests (retries included) we send to a single endpoint while
+// building one graph. DEXes sharing an endpoint share this budget. A request here is one DEX's
+// pairs, all of its pages included
+#[cfg(feature = "pink")]
+const MAX_REQUESTS_PER_ENDPOINT: usize = 2;
+
+// A big DEX's pairs don't fit in one Phat HTTP response (or serde_json_core) as is, so we page
+// through them, highest reserves first, and stop at MAX_PAIRS_PER_DEX. When we have to cut
+// something, it is the smallest pools, which routes rarely go through anyway
+#[cfg(feature = "pink")]
+const PAIRS_PAGE_SIZE: u32 = 100;
+const MAX_PAIRS_PER_DEX: u32 = 500;
+// Anything past this is dropped before decoding (see decode_combined_response)
+const MAX_RESPONSE_BYTES: usize = 256 * 1024;
+
+// Fetches every DEX's pairs before converting any of them, so one slow or broken subgraph
+// can't stall the others. The result for each DEX is in the same order as dexes, and a DEX whose
+// endpoint failed gets an Err (the caller decides whether a partial graph is acceptable).
+// Each element of dexes is (dex, avg_gas_fee_in_native_token of its chain).
+// min_token_pair_reserve_usd is in actual $ (no 'decimals' multiplicative factor) e.g. $500 -> 500
+#[cfg(feature = "pink")]
+pub fn get_additional_tokens_and_edges_for_dexes<'a>(
+    dexes: &[(&'static Dex, Amount)],
+    min_token_pair_reserve_usd: u32,
+    token_id_set: &'a mut HashSet<UniversalTokenId>, // Tokens already in this set won't be added
+) -> Vec<Result<(Vec<Token>, Vec<ConstantProductAMMSwapEdge>)>> {
+    let endpoints: Vec<(&str, bool)> = dexes
+        .iter()
+        .map(|(dex, _)| {
+            (
+                dex.graphql_url,
+                has_fee_tiers(dexes.iter().map(|(dex, _)| *dex), dex.graphql_url),
+            )
+        })
+        .collect();
+    let responses = graphql_low_level_interface::batch_combined_calls(
+        &endpoints,
+        min_token_pair_reserve_usd,
+        MAX_REQUESTS_PER_ENDPOINT,
+    );
+
+    // Convert in the original DEX order so token dedupe (and hence the graph) is deterministic
+    dexes
+        .iter()
+        .zip(responses.into_iter())
+        .map(|((dex, avg_gas_fee_in_native_token), response)| {
+            response.map(|combined_raw| {
+                tokens_and_edges_from_response(
+                    dex,
+                    &combined_raw,
+                    *avg_gas_fee_in_native_token,
+                    token_id_set,
+                )
+            })
+        })
+        .collect()
+}
+
+// Raw subgraph responses (i.e. get_additional_tokens_and_edges_from_response's input) of each
+// DEX, in the same order as dexes. A response that doesn't decode is returned as an Err here so it
+// never ends up in a graph snapshot. Unlike batch_combined_calls, this is a single request for up
+// to MAX_PAIRS_PER_DEX pairs, so a truncated response keeps just its leading pairs
+#[cfg(feature = "pink")]
+pub fn batch_get_combined_responses(
+    dexes: &[&'static Dex],
+    min_token_pair_reserve_usd: u32,
+) -> Vec<Result<Vec<u8>>> {
+    let requests: Vec<(&str, Vec<u8>)> = dexes
+        .iter()
+        .map(|dex| {
+            let query = graphql_low_level_interface::combined_query(
+                min_token_pair_reserve_usd,
+                0,
+                MAX_PAIRS_PER_DEX,
+                has_fee_tiers(dexes.iter().copied(), dex.graphql_url),
+            );
+            (
+                dex.graphql_url,
+                graphql_low_level_interface::graphql_query_body(&query),
+            )
+        })
+        .collect();
+    batch_fetch(
+        &requests,
+        MAX_REQUESTS_PER_ENDPOINT,
+        graphql_low_level_interface::graphql_query_batch,
+        |raw_bytes| {
+            let _ = graphql_low_level_interface::decode_combined_response(raw_bytes)?;
+            Ok(raw_bytes.to_vec())
+        },
     )
 }
 
-// min_token_pair_reserve_usd is in actual $ (no 'decimals' multiplicative factor)
-// e.g. $500 -> 500
-pub fn get_additional_tokens_and_edges<'a>(
+// DEXes that share an endpoint share a subgraph, so any of them will do
+#[cfg(feature = "pink")]
+fn has_fee_tiers<'a>(mut dexes: impl Iterator<Item = &'a Dex>, graphql_url: &str) -> bool {
+    dexes.any(|dex| dex.graphql_url == graphql_url && dex.has_fee_tiers())
+}
+
+// Each pass hands all of its requests (url, body) to send_batch at once, which sends them
+// concurrently (see graphql_query_batch) and returns their responses in order. Later passes
+// retry the failed ones. Only RequestFailed is retried (a malformed body won't fix itself), and an
+// endpoint is skipped once it has used up max_requests_per_endpoint
+#[cfg(feature = "pink")]
+fn batch_fetch<T, S, P>(
+    requests: &[(&str, Vec<u8>)],
+    max_requests_per_endpoint: usize,
+    mut send_batch: S,
+    parse: P,
+) -> Vec<Result<T>>
+where
+    S: FnMut(Vec<(String, Vec<u8>)>) -> Vec<Result<Vec<u8>>>,
+    P: Fn(&[u8]) -> Result<T>,
+{
+    let mut results: Vec<Result<T>> = requests
+        .iter()
+        .map(|_| Err(PublicError::RequestFailed))
+        .collect();
+    let mut rate_limiter = EndpointRateLimiter::new(max_requests_per_endpoint);
+    for _ in 0..max_requests_per_endpoint {
+        let mut pending = Vec::new();
+        for (i, (url, _)) in requests.iter().enumerate() {
+            if matches!(results[i], Err(PublicError::RequestFailed))
+                && rate_limiter.try_acquire(url)
+            {
+                pending.push(i);
+            }
+        }
+        if pending.is_empty() {
+            break;
+        }
+        let responses = send_batch(
+            pending
+                .iter()
+                .map(|i| (String::from(requests[*i].0), requests[*i].1.clone()))
+                .collect(),
+        );
+        for (i, response) in pending.into_iter().zip(responses.into_iter()) {
+            results[i] = response.and_then(|raw_bytes| parse(&raw_bytes));
+        }
+    }
+    results
+}
+
+#[cfg(feature = "pink")]
+struct EndpointRateLimiter<'a> {
+    max_requests_per_endpoint: usize,
+    num_requests: Vec<(&'a str, usize)>,
+}
+
+#[cfg(feature = "pink")]
+impl<'a> EndpointRateLimiter<'a> {
+    fn new(max_requests_per_endpoint: usize) -> Self {
+        Self {
+            max_requests_per_endpoint,
+            num_requests: vec![],
+        }
+    }
+
+    // Returns true (and counts the request) if the endpoint still has budget left
+    fn try_acquire(&mut self, url: &'a str) -> bool {
+        match self.num_requests.iter_mut().find(|(u, _)| *u == url) {
+            Some((_, count)) => {
+                if *count >= self.max_requests_per_endpoint {
+                    return false;
+                }
+                *count += 1;
+            }
+            None => {
+                if self.max_requests_per_endpoint == 0 {
+                    return false;
+                }
+                self.num_requests.push((url, 1));
+            }
+        }
+        true
+    }
+}
+
+// Prices of just the given tokens on a DEX, without any of its pairs. A token that the DEX
+// doesn't index (or that has no price) is left out
+#[cfg(feature = "pink")]
+pub fn get_token_prices(dex: &'static Dex, token_addrs: &[EthAddress]) -> Result<Vec<Token>> {
+    let prices_raw = graphql_low_level_interface::token_prices_call(dex.graphql_url, token_addrs)?;
+    let usd_per_native_token_unit = prices_raw
+        .bundleById
+        .ethPrice
+        .add_exp(-(NATIVE_TOKEN_DECIMALS as i8));
+    Ok(prices_raw
+        .tokens
+        .iter()
+        .map(|token| {
+            // Same conversion as in tokens_and_edges_from_response
+            let derived_eth = token
+                .derivedETH
+                .add_exp((NATIVE_TOKEN_DECIMALS as i8) - (token.decimals as i8));
+            Token {
+                id: universal_token_id_registry::chain_and_eth_addr_to_token(
+                    dex.chain_id,
+                    token.id,
+                ),
+                derived_usd: derived_eth.mul_small(&usd_per_native_token_unit),
+                derived_eth,
+            }
+        })
+        .collect())
+}
+
+// Request body to POST to a DEX's graphql_url. Its response is what
+// get_additional_tokens_and_edges_from_response expects, so callers that do their own HTTP
+// (i.e. builds without the pink feature) fetch exactly what the contract would have
+pub fn combined_query_body(dex: &Dex, min_token_pair_reserve_usd: u32) -> Vec<u8> {
+    graphql_low_level_interface::graphql_query_body(&graphql_low_level_interface::combined_query(
+        min_token_pair_reserve_usd,
+        0,
+        MAX_PAIRS_PER_DEX,
+        dex.has_fee_tiers(),
+    ))
+}
+
+pub fn get_additional_tokens_and_edges_from_response<'a>(
     dex: &'static Dex,
-    min_token_pair_reserve_usd: u32,
+    response: &[u8],
     avg_gas_fee_in_native_token: Amount,
     token_id_set: &'a mut HashSet<UniversalTokenId>, // Tokens already in this set won't be added
 ) -> Result<(Vec<Token>, Vec<ConstantProductAMMSwapEdge>)> {
-    let combined_raw =
-        graphql_low_level_interface::combined_call(dex.graphql_url, min_token_pair_reserve_usd)?;
+    let combined_raw = graphql_low_level_interface::decode_combined_response(response)?;
+    Ok(tokens_and_edges_from_response(
+        dex,
+        &combined_raw,
+        avg_gas_fee_in_native_token,
+        token_id_set,
+    ))
+}
 
+fn tokens_and_edges_from_response(
+    dex: &'static Dex,
+    combined_raw: &graphql_low_level_interface::CombinedResponse,
+    avg_gas_fee_in_native_token: Amount,
+    token_id_set: &mut HashSet<UniversalTokenId>,
+) -> (Vec<Token>, Vec<ConstantProductAMMSwapEdge>) {
     let usd_per_native_token_unit = combined_raw
         .bundleById
         .ethPrice
@@ -66,6 +308,15 @@ pub fn get_additional_tokens_and_edges<'a>(
     let mut cpmm_edges: Vec<ConstantProductAMMSwapEdge> = vec![];
 
     for token_pair in combined_raw.pairs.iter() {
+        // We swap through the DEX's router with just the token path, and for a fee-tiered DEX
+        // that uses the pair's pool in the router's tier (the DEX's fee_bps). Quoting a pool in
+        // another tier would price a swap that a different pool then executes, so we skip those
+        // (and pools whose fee the subgraph didn't give us)
+        let fee_bps = match (dex.has_fee_tiers(), token_pair.fee) {
+            (false, _) => dex.fee_bps,
+            (true, Some(fee)) if fee == dex.fee_bps => fee,
+            (true, _) => continue,
+        };
         let token0_id = universal_token_id_registry::chain_and_eth_addr_to_token(
             dex.chain_id,
             token_pair.token0.id,
@@ -128,6 +379,7 @@ pub fn get_additional_tokens_and_edges<'a>(
                 token1: token1_id.id.clone(),
                 reserve0,
                 reserve1,
+                fee_bps,
                 // # dest_token_units = (# native token units) / (# native token units / # dest token unit)
                 estimated_gas_fee_in_dest_token,
                 estimated_gas_fee_usd,
@@ -137,27 +389,28 @@ pub fn get_additional_tokens_and_edges<'a>(
         }
     }
 
-    Ok((tokens, cpmm_edges))
+    (tokens, cpmm_edges)
 }
 
 mod graphql_low_level_interface {
-    use ink_prelude::{format, vec::Vec};
+    use ink::prelude::{format, string::String, vec::Vec};
     use privadex_common::fixed_point::DecimalFixedPoint;
     #[allow(unused_imports)]
-    use privadex_common::utils::{
-        general_utils::{hex_string_to_vec, slice_to_hex_string},
-        http_request::http_post_wrapper,
-    };
+    use privadex_common::utils::general_utils::{hex_string_to_vec, slice_to_hex_string};
+    #[cfg(feature = "pink")]
+    use privadex_common::utils::http_request::{http_post_batch_wrapper, http_post_wrapper};
     use serde::{de, Deserialize, Deserializer};
 
-    use super::{EthAddress, PublicError, Result};
+    use super::{EthAddress, PublicError, Result, MAX_RESPONSE_BYTES};
+    #[cfg(feature = "pink")]
+    use super::{MAX_PAIRS_PER_DEX, PAIRS_PAGE_SIZE};
 
     #[derive(Deserialize, Debug)]
     pub(super) struct DataWrapper<T> {
         pub data: T,
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     #[derive(Deserialize, Debug)]
     #[allow(non_snake_case)]
     pub(super) struct EthPriceBundle {
@@ -171,14 +424,14 @@ mod graphql_low_level_interface {
         pub ethPrice: DecimalFixedPoint,
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     #[derive(Deserialize, Debug)]
-    #[serde(bound(deserialize = "ink_prelude::vec::Vec<TokenPair>: Deserialize<'de>"))]
+    #[serde(bound(deserialize = "ink::prelude::vec::Vec<TokenPair>: Deserialize<'de>"))]
     pub(super) struct TokenPairVec {
         pub pairs: Vec<TokenPair>,
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     #[derive(Deserialize, Debug)]
     #[allow(non_snake_case)]
     #[allow(dead_code)]
@@ -195,9 +448,9 @@ mod graphql_low_level_interface {
         pub reserve1: DecimalFixedPoint,
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     #[derive(Deserialize, Debug)]
-    #[serde(bound(deserialize = "ink_prelude::vec::Vec<Token>: Deserialize<'de>"))]
+    #[serde(bound(deserialize = "ink::prelude::vec::Vec<Token>: Deserialize<'de>"))]
     pub(super) struct TokenVec {
         pub tokens: Vec<Token>,
     }
@@ -223,16 +476,27 @@ mod graphql_low_level_interface {
         pub reserve0: DecimalFixedPoint,
         #[serde(deserialize_with = "str_to_reserve_fixed_point")]
         pub reserve1: DecimalFixedPoint,
+        // Only queried for DEXes with fee tiers (see Dex::fee_tiers_bps)
+        pub fee: Option<u16>,
     }
 
     #[derive(Deserialize, Debug)]
-    #[serde(bound(deserialize = "ink_prelude::vec::Vec<NestedTokenPair>: Deserialize<'de>"))]
+    #[serde(bound(deserialize = "ink::prelude::vec::Vec<NestedTokenPair>: Deserialize<'de>"))]
     #[allow(non_snake_case)]
     pub(super) struct CombinedResponse {
         pub bundleById: EthPrice,
         pub pairs: Vec<NestedTokenPair>,
     }
 
+    #[cfg(feature = "pink")]
+    #[derive(Deserialize, Debug)]
+    #[serde(bound(deserialize = "ink::prelude::vec::Vec<Token>: Deserialize<'de>"))]
+    #[allow(non_snake_case)]
+    pub(super) struct TokenPricesResponse {
+        pub bundleById: EthPrice,
+        pub tokens: Vec<Token>,
+    }
+
     // Empirically the value of RAW ethPrice ($ per token - generally 10^18 token units)
     // is 0.04 -> 5 for the chains' native tokens
     fn str_to_eth_price_fixed_point<'de, D: Deserializer<'de>>(
@@ -279,10 +543,90 @@ mod graphql_low_level_interface {
     // remove - but it helps with parsing)
     // Note: We filter out derivedETH == 0 because it causes dangerous (overflow) issues downstream
     // in calculating USD value, fees, etc.
-    pub(super) fn combined_call(query_url: &str, min_reserve_usd: u32) -> Result<CombinedResponse> {
-        let query = format!(
+    // Only the first page has to come through. Later pages hold smaller pools, so if one of them
+    // fails (or comes back short), we stop and keep the pairs we have
+    #[cfg(feature = "pink")]
+    // Each of endpoints is a DEX's (graphql_url, with_pool_fee). The first page of every DEX goes
+    // out in one batch (retried as in batch_fetch). Then each next page of all the DEXes whose
+    // last page came back full goes out in one batch. Only the first page has to come through:
+    // a failed later page just ends that DEX's pairs there
+    #[cfg(feature = "pink")]
+    pub(super) fn batch_combined_calls(
+        endpoints: &[(&str, bool)],
+        min_reserve_usd: u32,
+        max_requests_per_endpoint: usize,
+    ) -> Vec<Result<CombinedResponse>> {
+        let first_page_requests: Vec<(&str, Vec<u8>)> = endpoints
+            .iter()
+            .map(|(query_url, with_pool_fee)| {
+                let query = combined_query(min_reserve_usd, 0, PAIRS_PAGE_SIZE, *with_pool_fee);
+                (*query_url, graphql_query_body(&query))
+            })
+            .collect();
+        let mut results = super::batch_fetch(
+            &first_page_requests,
+            max_requests_per_endpoint,
+            graphql_query_batch,
+            decode_combined_response,
+        );
+        // Each DEX's (last page length, last page limit)
+        let mut last_pages: Vec<(u32, u32)> = results
+            .iter()
+            .map(|result| {
+                let num_pairs = result.as_ref().map_or(0, |combined| combined.pairs.len());
+                (num_pairs as u32, PAIRS_PAGE_SIZE)
+            })
+            .collect();
+        loop {
+            let mut next_pages = Vec::new();
+            let mut requests = Vec::new();
+            for (i, result) in results.iter().enumerate() {
+                let num_pairs = match result {
+                    Ok(combined) => combined.pairs.len() as u32,
+                    Err(_) => continue,
+                };
+                let (last_page_len, last_page_limit) = last_pages[i];
+                if last_page_len < last_page_limit || num_pairs >= MAX_PAIRS_PER_DEX {
+                    continue;
+                }
+                let limit = PAIRS_PAGE_SIZE.min(MAX_PAIRS_PER_DEX - num_pairs);
+                let (query_url, with_pool_fee) = endpoints[i];
+                let query = combined_query(min_reserve_usd, num_pairs, limit, with_pool_fee);
+                next_pages.push((i, limit));
+                requests.push((String::from(query_url), graphql_query_body(&query)));
+            }
+            if requests.is_empty() {
+                break;
+            }
+            for ((i, limit), response) in next_pages
+                .into_iter()
+                .zip(graphql_query_batch(requests).into_iter())
+            {
+                let page = response.and_then(|raw_bytes| decode_combined_response(&raw_bytes));
+                match (page, results[i].as_mut()) {
+                    (Ok(page), Ok(combined)) => {
+                        last_pages[i] = (page.pairs.len() as u32, limit);
+                        combined.pairs.extend(page.pairs);
+                    }
+                    // Keeps the pages we already have
+                    _ => last_pages[i] = (0, limit),
+                }
+            }
+        }
+        results
+    }
+
+    // bundleById goes first so that it survives a truncated response
+    pub(super) fn combined_query(
+        min_reserve_usd: u32,
+        offset: u32,
+        limit: u32,
+        with_pool_fee: bool,
+    ) -> String {
+        format!(
             "\
-            pairs(orderBy: reserveUSD_DESC, \
+            bundleById(id: \\\"1\\\") {{ ethPrice }} \
+            pairs(orderBy: reserveUSD_DESC, offset: {}, limit: {}, \
                 where: {{ AND: {{token0: {{derivedETH_gt: \\\"0\\\"}}, \
                                  token1: {{derivedETH_gt: \\\"0\\\"}}, \
                                  reserveUSD_gt: \\\"{}\\\"}} \
@@ -290,6 +634,161 @@ mod graphql_low_level_interface {
                 id \
                 reserve0 \
                 reserve1 \
+                {} \
+                token0 {{ \
+                    decimals \
+                    derivedETH \
+                    id \
+                }} \
+                token1 {{ \
+                    decimals \
+                    derivedETH \
+                    id \
+                }} \
+            }} \
+            ",
+            offset,
+            limit,
+            min_reserve_usd,
+            pool_fee_field(with_pool_fee)
+        )
+    }
+
+    // The subgraphs of DEXes without fee tiers don't have the field at all
+    fn pool_fee_field(with_pool_fee: bool) -> &'static str {
+        if with_pool_fee {
+            "fee"
+        } else {
+            ""
+        }
+    }
+
+    // Only the first MAX_RESPONSE_BYTES are decoded. If the whole thing does not decode (e.g. it
+    // was cut off), we fall back to decoding the pairs one at a time, up to the first one that
+    // fails. Pairs come highest reserves first, so that keeps the pools that matter most
+    pub(super) fn decode_combined_response(raw_bytes: &[u8]) -> Result<CombinedResponse> {
+        let raw_bytes = &raw_bytes[..raw_bytes.len().min(MAX_RESPONSE_BYTES)];
+        match serde_json_core::from_slice::<DataWrapper<CombinedResponse>>(raw_bytes) {
+            Ok((decoded, _)) => Ok(decoded.data),
+            Err(_) => decode_combined_response_partial(raw_bytes),
+        }
+    }
+
+    fn decode_combined_response_partial(raw_bytes: &[u8]) -> Result<CombinedResponse> {
+        // Without the price, none of the pairs are any use
+        let bundle_start =
+            find_json_value(raw_bytes, b"\"bundleById\"").ok_or(PublicError::InvalidBody)?;
+        let bundle_end =
+            json_object_end(raw_bytes, bundle_start).ok_or(PublicError::InvalidBody)?;
+        let (bundle_by_id, _): (EthPrice, usize) =
+            serde_json_core::from_slice(&raw_bytes[bundle_start..bundle_end])
+                .or(Err(PublicError::InvalidBody))?;
+
+        let mut pairs = Vec::new();
+        if let Some(array_start) = find_json_value(raw_bytes, b"\"pairs\"")
+            .filter(|array_start| raw_bytes[*array_start] == b'[')
+        {
+            let mut pos = array_start + 1;
+            loop {
+                while raw_bytes
+                    .get(pos)
+                    .map_or(false, |b| *b == b',' || b.is_ascii_whitespace())
+                {
+                    pos += 1;
+                }
+                let pair_end = match json_object_end(raw_bytes, pos) {
+                    Some(pair_end) => pair_end,
+                    None => break,
+                };
+                match serde_json_core::from_slice::<NestedTokenPair>(&raw_bytes[pos..pair_end]) {
+                    Ok((pair, _)) => pairs.push(pair),
+                    Err(_) => break,
+                }
+                pos = pair_end;
+            }
+        }
+        Ok(CombinedResponse {
+            bundleById: bundle_by_id,
+            pairs,
+        })
+    }
+
+    // Index where key's value starts, i.e. past the colon and any whitespace
+    fn find_json_value(raw_bytes: &[u8], key: &[u8]) -> Option<usize> {
+        let key_start = raw_bytes
+            .windows(key.len())
+            .position(|window| window == key)?;
+        let mut pos = key_start + key.len();
+        while raw_bytes.get(pos)?.is_ascii_whitespace() {
+            pos += 1;
+        }
+        if raw_bytes[pos] != b':' {
+            return None;
+        }
+        pos += 1;
+        while raw_bytes.get(pos)?.is_ascii_whitespace() {
+            pos += 1;
+        }
+        Some(pos)
+    }
+
+    // Index just past the object that starts at start. None if there is no object there or it
+    // is cut off
+    fn json_object_end(raw_bytes: &[u8], start: usize) -> Option<usize> {
+        if raw_bytes.get(start) != Some(&b'{') {
+            return None;
+        }
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut is_escaped = false;
+        for (i, b) in raw_bytes.iter().enumerate().skip(start) {
+            if in_string {
+                match *b {
+                    _ if is_escaped => is_escaped = false,
+                    b'\\' => is_escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match *b {
+                b'"' => in_string = true,
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Same response shape as batch_combined_calls, but only for the pair(s) between token_a and token_b.
+    // Both tokens must be in id_in for token0 and token1 since we don't know the pair's ordering
+    #[cfg(feature = "pink")]
+    pub(super) fn direct_pair_call(
+        query_url: &str,
+        token_a: &EthAddress,
+        token_b: &EthAddress,
+        with_pool_fee: bool,
+    ) -> Result<CombinedResponse> {
+        let token_ids = format!(
+            "\\\"{}\\\" \\\"{}\\\"",
+            slice_to_hex_string(&token_a.0),
+            slice_to_hex_string(&token_b.0)
+        );
+        let query = format!(
+            "\
+            pairs(where: {{ AND: {{token0: {{derivedETH_gt: \\\"0\\\", id_in: [{0}]}}, \
+                                 token1: {{derivedETH_gt: \\\"0\\\", id_in: [{0}]}}}} \
+                       }}) {{ \
+                id \
+                reserve0 \
+                reserve1 \
+                {1} \
                 token0 {{ \
                     decimals \
                     derivedETH \
@@ -303,15 +802,43 @@ mod graphql_low_level_interface {
             }} \
             bundleById(id: \\\"1\\\") {{ ethPrice }} \
             ",
-            min_reserve_usd
+            token_ids,
+            pool_fee_field(with_pool_fee)
         );
         let raw_bytes = graphql_query(query_url, &query)?;
-        let (decoded, _): (DataWrapper<CombinedResponse>, usize) =
+        decode_combined_response(&raw_bytes)
+    }
+
+    // Note: We filter out derivedETH == 0 for the same reason as in batch_combined_calls
+    #[cfg(feature = "pink")]
+    pub(super) fn token_prices_call(
+        query_url: &str,
+        token_addrs: &[EthAddress],
+    ) -> Result<TokenPricesResponse> {
+        let token_ids: String =
+            token_addrs
+                .iter()
+                .fold(String::new(), |cur: String, next: &EthAddress| {
+                    cur + " \\\"" + slice_to_hex_string(&next.0).as_str() + "\\\""
+                });
+        let query = format!(
+            "\
+            tokens(where: {{derivedETH_gt: \\\"0\\\", id_in: [{} ]}}) {{ \
+                decimals \
+                derivedETH \
+                id \
+            }} \
+            bundleById(id: \\\"1\\\") {{ ethPrice }} \
+            ",
+            token_ids
+        );
+        let raw_bytes = graphql_query(query_url, &query)?;
+        let (decoded, _): (DataWrapper<TokenPricesResponse>, usize) =
             serde_json_core::from_slice(&raw_bytes).or(Err(PublicError::InvalidBody))?;
         Ok(decoded.data)
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     pub(super) fn eth_price_call(query_url: &str) -> Result<DecimalFixedPoint> {
         let query = get_eth_price_query();
         let raw_bytes = graphql_query(query_url, &query)?;
@@ -320,12 +847,12 @@ mod graphql_low_level_interface {
         Ok(decoded.data.bundleById.ethPrice)
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     fn get_eth_price_query() -> String {
         "bundleById(id: \\\"1\\\") { ethPrice }".to_string()
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     pub(super) fn tokens_call(query_url: &str, token_addrs: &[EthAddress]) -> Result<Vec<Token>> {
         let query = get_tokens_query(token_addrs);
         let raw_bytes = graphql_query(query_url, &query)?;
@@ -334,7 +861,7 @@ mod graphql_low_level_interface {
         Ok(decoded.data.tokens)
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     fn get_tokens_query(token_addrs: &[EthAddress]) -> String {
         // Below is a string that looks like
         // String[\"0xffffffff1fcacbd218edc0eba20fc2308c778080\" \"0xacc15dc74880c9944775448304b263d191c6077f\"]
@@ -360,7 +887,7 @@ mod graphql_low_level_interface {
 
     // Note: We filter out derivedETH == 0 because it causes dangerous (overflow) issues downstream
     // in calculating USD value, fees, etc.
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     pub(super) fn pairs_call(query_url: &str, min_reserve_usd: u32) -> Result<Vec<TokenPair>> {
         let query = format!(
             "\
@@ -384,7 +911,7 @@ mod graphql_low_level_interface {
         Ok(decoded.data.pairs)
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "pink"))]
     pub(super) fn pairs_call_no_derived_eth_filter(
         query_url: &str,
         min_reserve_usd: u32,
@@ -407,18 +934,32 @@ mod graphql_low_level_interface {
         Ok(decoded.data.pairs)
     }
 
+    #[cfg(feature = "pink")]
     fn graphql_query<'a, 'b>(query_url: &'a str, nested_data: &'b str) -> Result<Vec<u8>> {
-        let data = format!(r#"{{"query": "{{ {} }}" }}"#, nested_data).into_bytes();
-        http_post_wrapper(query_url, data).map_err(|_| PublicError::RequestFailed)
+        http_post_wrapper(query_url, graphql_query_body(nested_data))
+            .map_err(|_| PublicError::RequestFailed)
+    }
+
+    // Sends the requests (url, body) concurrently. Each gets its own response, in order
+    #[cfg(feature = "pink")]
+    pub(super) fn graphql_query_batch(requests: Vec<(String, Vec<u8>)>) -> Vec<Result<Vec<u8>>> {
+        http_post_batch_wrapper(requests)
+            .into_iter()
+            .map(|response| response.map_err(|_| PublicError::RequestFailed))
+            .collect()
+    }
+
+    pub(super) fn graphql_query_body(nested_data: &str) -> Vec<u8> {
+        format!(r#"{{"query": "{{ {} }}" }}"#, nested_data).into_bytes()
     }
 }
 
 // Note that the below tests require a network connection to work! We deliberately do not
 // mock the HTTP responses so we can also test the GraphQL service
-#[cfg(test)]
+#[cfg(all(test, feature = "pink"))]
 mod graphql_client_tests {
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
     use privadex_chain_metadata::registry::dex::dex_registry::{ARTHSWAP, BEAMSWAP, STELLASWAP};
 
     use super::graphql_low_level_interface::*;
@@ -427,18 +968,148 @@ mod graphql_client_tests {
     #[test]
     fn test_graphql_tokens_cpmm_edges() {
         pink_extension_runtime::mock_ext::mock_all_ext();
-        let (tokens, cpmm_edges) =
-            get_tokens_and_edges(&STELLASWAP, 4_000_000, Amount::pow(10, 16)).unwrap();
+        let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+        let (tokens, cpmm_edges) = get_additional_tokens_and_edges_for_dexes(
+            &[(&STELLASWAP, Amount::pow(10, 16))],
+            4_000_000,
+            &mut token_id_set,
+        )
+        .remove(0)
+        .unwrap();
         assert!(tokens.len() > 0);
         assert!(cpmm_edges.len() >= tokens.len() / 2);
         debug_println!("Tokens: {:?}", tokens);
         debug_println!("CPMM edges: {:?}", cpmm_edges);
     }
 
+    // batch_fetch tests don't hit the network. The request body is what the mock endpoint returns
+    fn mock_requests<'a>(urls: &[&'a str]) -> Vec<(&'a str, Vec<u8>)> {
+        urls.iter()
+            .map(|url| (*url, url.as_bytes().to_vec()))
+            .collect()
+    }
+
+    fn parse_len(raw_bytes: &[u8]) -> Result<usize> {
+        Ok(raw_bytes.len())
+    }
+
+    #[test]
+    fn test_batch_fetch_retries_failed_endpoints() {
+        let mut batches = vec![];
+        let results = batch_fetch(
+            &mock_requests(&["a", "bb"]),
+            2,
+            |requests| {
+                let is_first_batch = batches.is_empty();
+                batches.push(requests.len());
+                requests
+                    .into_iter()
+                    .map(|(url, body)| {
+                        // "bb" fails on its first attempt only
+                        if url == "bb" && is_first_batch {
+                            Err(PublicError::RequestFailed)
+                        } else {
+                            Ok(body)
+                        }
+                    })
+                    .collect()
+            },
+            parse_len,
+        );
+        assert_eq!(results, vec![Ok(1), Ok(2)]);
+        // Both go out together, then only the failed one is retried
+        assert_eq!(batches, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_batch_fetch_partial_failure() {
+        let mut urls_called = vec![];
+        let results = batch_fetch(
+            &mock_requests(&["a", "down", "bad"]),
+            3,
+            |requests| {
+                requests
+                    .into_iter()
+                    .map(|(url, body)| {
+                        urls_called.push(url.clone());
+                        match url.as_str() {
+                            "down" => Err(PublicError::RequestFailed),
+                            _ => Ok(body),
+                        }
+                    })
+                    .collect()
+            },
+            |raw_bytes| match raw_bytes {
+                b"bad" => Err(PublicError::InvalidBody),
+                _ => Ok(()),
+            },
+        );
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err(PublicError::RequestFailed),
+                Err(PublicError::InvalidBody)
+            ]
+        );
+        // Only the unreachable endpoint is retried, up to its budget
+        assert_eq!(urls_called, vec!["a", "down", "bad", "down", "down"]);
+    }
+
+    #[test]
+    fn test_endpoint_rate_limiter_shared_budget() {
+        let mut num_requests = 0;
+        // Both DEXes share an endpoint, so the second failure can't be retried
+        let results = batch_fetch(
+            &mock_requests(&["shared", "shared"]),
+            2,
+            |requests| {
+                num_requests += requests.len();
+                requests
+                    .iter()
+                    .map(|_| Err(PublicError::RequestFailed))
+                    .collect()
+            },
+            parse_len,
+        );
+        assert_eq!(results.len(), 2);
+        assert_eq!(num_requests, 2);
+
+        let mut rate_limiter = EndpointRateLimiter::new(1);
+        assert!(rate_limiter.try_acquire("a"));
+        assert!(!rate_limiter.try_acquire("a"));
+        assert!(rate_limiter.try_acquire("b"));
+    }
+
+    #[test]
+    fn test_graphql_direct_pair_tokens_cpmm_edges() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let mut token_id_set: HashSet<UniversalTokenId> = HashSet::new();
+        // WGLMR <-> xcDOT
+        let (tokens, cpmm_edges) = get_additional_direct_pair_tokens_and_edges(
+            &STELLASWAP,
+            &EthAddress {
+                0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
+            },
+            &EthAddress {
+                0: hex!("ffffffff1fcacbd218edc0eba20fc2308c778080"),
+            },
+            Amount::pow(10, 16),
+            &mut token_id_set,
+        )
+        .unwrap();
+        assert_eq!(tokens.len(), 2);
+        // Each pool contributes one edge per direction
+        assert!(cpmm_edges.len() >= 2);
+        assert_eq!(cpmm_edges.len() % 2, 0);
+    }
+
     #[test]
     fn test_graphql_client_combined() {
         pink_extension_runtime::mock_ext::mock_all_ext();
-        let combined_data = combined_call(ARTHSWAP.graphql_url, 2_000_000).unwrap();
+        let combined_data = batch_combined_calls(&[(ARTHSWAP.graphql_url, false)], 2_000_000, 2)
+            .remove(0)
+            .unwrap();
         // debug_println!("Combined data: {:?}", combined_data);
         assert!(combined_data.pairs.len() > 0);
     }
@@ -628,4 +1299,107 @@ mod graphql_client_tests {
             DecimalFixedPoint::from_str_and_exp("0.039618646362355794", 8)
         );
     }
+
+    #[test]
+    fn test_decode_combined_response_truncated() {
+        let combined_data = "{\"data\":{\
+                                    \"bundleById\":{\"ethPrice\":\"0.0396186463623557942761935178\"}, \
+                                    \"pairs\": [\
+                                        {\"id\":\"0xccefddff4808f3e1e0340e19e43f1e9fd088b3f2\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                                            \"token1\":{\"decimals\":10,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+                                        },\
+                                        {\"id\":\"0xb4461721d3ad256cd59d207fefbfe05791ef8568\",\"reserve0\":\"28155035.667599855639049157\",\"reserve1\":\"28315125.29483649\",\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"},\
+                                            \"token1\":{\"decimals\":18,\"deriv".as_bytes();
+        assert!(
+            serde_json_core::from_slice::<DataWrapper<CombinedResponse>>(combined_data).is_err()
+        );
+
+        // Keeps the pairs before the cut-off one
+        let decoded = decode_combined_response(combined_data).unwrap();
+        assert_eq!(decoded.pairs.len(), 1);
+        assert_eq!(
+            decoded.pairs[0].id,
+            EthAddress {
+                0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")
+            }
+        );
+        assert_eq!(
+            decoded.bundleById.ethPrice,
+            DecimalFixedPoint::from_str_and_exp("0.039618646362355794", 8)
+        );
+
+        // The pairs are no use without the price
+        let no_price_data = "{\"data\":{\"pairs\":[]".as_bytes();
+        assert_eq!(
+            decode_combined_response(no_price_data).err(),
+            Some(PublicError::InvalidBody)
+        );
+    }
+
+    #[test]
+    fn test_decode_combined_response_pool_fee() {
+        let combined_data = "{\"data\":{\
+                                    \"bundleById\":{\"ethPrice\":\"0.0396186463623557942761935178\"}, \
+                                    \"pairs\": [\
+                                        {\"id\":\"0xccefddff4808f3e1e0340e19e43f1e9fd088b3f2\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\"fee\":100,\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                                            \"token1\":{\"decimals\":10,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+                                        }\
+                                    ]}}".as_bytes();
+        let decoded = decode_combined_response(combined_data).unwrap();
+        assert_eq!(decoded.pairs[0].fee, Some(100));
+    }
+
+    #[test]
+    fn test_fee_tiered_dex_only_quotes_router_pools() {
+        static TIERED_ARTHSWAP: Dex = Dex {
+            fee_bps: 25,
+            fee_tiers_bps: &[25, 100],
+            ..ARTHSWAP
+        };
+        // The same pair in the router's tier, in another tier and without a fee
+        let combined_data = "{\"data\":{\
+                                    \"bundleById\":{\"ethPrice\":\"0.0396186463623557942761935178\"}, \
+                                    \"pairs\": [\
+                                        {\"id\":\"0xccefddff4808f3e1e0340e19e43f1e9fd088b3f2\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\"fee\":100,\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                                            \"token1\":{\"decimals\":10,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+                                        },\
+                                        {\"id\":\"0x0000000000000000000000000000000000000025\",\"reserve0\":\"695294.644665235172725434\",\"reserve1\":\"6222319.6301748411321042674\",\"fee\":25,\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                                            \"token1\":{\"decimals\":10,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+                                        },\
+                                        {\"id\":\"0x0000000000000000000000000000000000000001\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\
+                                            \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                                            \"token1\":{\"decimals\":10,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+                                        }\
+                                    ]}}".as_bytes();
+        let decoded = decode_combined_response(combined_data).unwrap();
+        let mut token_id_set = HashSet::new();
+        let (_, edges) =
+            tokens_and_edges_from_response(&TIERED_ARTHSWAP, &decoded, 0, &mut token_id_set);
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|edge| edge.fee_bps == 25
+            && edge.pair_address
+                == EthAddress {
+                    0: hex!("0000000000000000000000000000000000000025")
+                }));
+
+        // DEXes without tiers keep every pool, at the DEX's fee
+        let (_, edges) = tokens_and_edges_from_response(&ARTHSWAP, &decoded, 0, &mut token_id_set);
+        assert_eq!(edges.len(), 6);
+        assert!(edges.iter().all(|edge| edge.fee_bps == ARTHSWAP.fee_bps));
+    }
+
+    #[test]
+    fn test_combined_query_pages() {
+        let query = combined_query(4_000, 200, 100, false);
+        assert!(query.contains("offset: 200, limit: 100"));
+        // So that the price survives a truncated response
+        assert!(query.find("bundleById") < query.find("pairs"));
+        assert!(!query.contains("fee"));
+        assert!(combined_query(4_000, 0, 100, true).contains("reserve1 fee"));
+    }
 }
@@ -21,7 +21,10 @@ extern crate alloc;
 
 pub mod graph;
 pub mod graph_builder;
+pub mod graph_snapshot;
 pub(crate) mod graphql_client;
+pub mod liquidity_summary;
+pub mod routing_config;
 pub mod smart_order_router;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -33,14 +36,22 @@ use privadex_chain_metadata::common::UniversalTokenId;
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum PublicError {
     AddEdgeFailed,
+    // Every route's estimated gas and bridge fees eat up the whole amount
+    AmountInDoesNotCoverFees,
     BridgeMissingSrcToken(UniversalTokenId),
     BridgeMissingDestToken(UniversalTokenId),
     CreateGraphFailed,
     InvalidBody,
+    InvalidRoutingConfig,
+    InvalidTrancheAmount,
     NoPathFound,
     RequestFailed,
     SrcTokenDestTokenAreSame,
+    // A tranche limit that would split the amount into more tranches than allowed
+    TooManyTranches,
     UnregisteredChainId,
+    UnsupportedSnapshotVersion(u8),
     VertexNotInGraph(UniversalTokenId),
+    ZeroAmountIn,
 }
 pub(crate) type Result<T> = core::result::Result<T, PublicError>;
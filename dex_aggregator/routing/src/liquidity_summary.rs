@@ -0,0 +1,202 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// What the last graph build ingested, per chain and per DEX. Before this, the only way to see
+// it was to debug_println the vertex and edge counts
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::{Amount, MillisSinceEpoch, UniversalChainId, UniversalTokenId, USD_AMOUNT_EXPONENT},
+    get_dexes_from_chain_id,
+    registry::dex::DexId,
+};
+
+use crate::graph::{
+    edge::{ConstantProductAMMSwapEdge, Edge, SwapEdge},
+    graph::Graph,
+};
+use crate::graph_snapshot::GraphSnapshot;
+use crate::Result;
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChainLiquidity {
+    pub chain: UniversalChainId,
+    pub num_tokens: u32,
+    pub num_pools: u32,
+    // $ x 10^USD_AMOUNT_EXPONENT, at the subgraphs' prices
+    pub tvl_usd: Amount,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DexLiquidity {
+    pub dex_id: DexId,
+    pub num_pools: u32,
+    pub tvl_usd: Amount,
+    // When its subgraph last responded to a build. None if it never has
+    pub last_refreshed_millis: Option<MillisSinceEpoch>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LiquiditySummary {
+    pub built_millis: MillisSinceEpoch,
+    pub chains: Vec<ChainLiquidity>,
+    // Every registered DEX on the chains, including the ones whose subgraph did not respond
+    pub dexes: Vec<DexLiquidity>,
+}
+
+impl LiquiditySummary {
+    // A DEX missing from the snapshot keeps its last_refreshed_millis from prev_summary
+    pub fn from_snapshot(
+        snapshot: &GraphSnapshot,
+        built_millis: MillisSinceEpoch,
+        prev_summary: Option<&Self>,
+    ) -> Result<Self> {
+        let graph = snapshot.to_graph()?;
+        let pools = get_pools(&graph);
+        let chains = snapshot
+            .chain_ids
+            .iter()
+            .map(|chain_id| {
+                let chain_pools = pools
+                    .iter()
+                    .filter(|(pool, _)| pool.dex.chain_id == *chain_id);
+                ChainLiquidity {
+                    chain: *chain_id,
+                    num_tokens: graph
+                        .vertices
+                        .keys()
+                        .filter(|token| token.chain == *chain_id)
+                        .count() as u32,
+                    num_pools: chain_pools.clone().count() as u32,
+                    tvl_usd: chain_pools.fold(0, |tvl_usd, (_, pool_tvl_usd)| {
+                        tvl_usd.saturating_add(*pool_tvl_usd)
+                    }),
+                }
+            })
+            .collect();
+        let dexes = snapshot
+            .chain_ids
+            .iter()
+            .flat_map(get_dexes_from_chain_id)
+            .map(|dex| {
+                let dex_pools = pools.iter().filter(|(pool, _)| pool.dex.id == dex.id);
+                let did_respond = snapshot
+                    .dex_responses
+                    .iter()
+                    .any(|(dex_id, _)| *dex_id == dex.id);
+                DexLiquidity {
+                    dex_id: dex.id,
+                    num_pools: dex_pools.clone().count() as u32,
+                    tvl_usd: dex_pools.fold(0, |tvl_usd, (_, pool_tvl_usd)| {
+                        tvl_usd.saturating_add(*pool_tvl_usd)
+                    }),
+                    last_refreshed_millis: if did_respond {
+                        Some(built_millis)
+                    } else {
+                        prev_summary.and_then(|prev_summary| {
+                            prev_summary
+                                .dexes
+                                .iter()
+                                .find(|prev_dex| prev_dex.dex_id == dex.id)
+                                .and_then(|prev_dex| prev_dex.last_refreshed_millis)
+                        })
+                    },
+                }
+            })
+            .collect();
+        Ok(Self {
+            built_millis,
+            chains,
+            dexes,
+        })
+    }
+}
+
+// Each pool with its TVL. A pool has an edge in each direction, so we only take the one from
+// token0 to token1
+fn get_pools(graph: &Graph) -> Vec<(&ConstantProductAMMSwapEdge, Amount)> {
+    graph
+        .edges()
+        .filter_map(|edge| match edge {
+            Edge::Swap(SwapEdge::CPMM(pool)) if pool.src_token.id == pool.token0 => {
+                let tvl_usd = get_usd(graph, &pool.src_token, pool.reserve0)
+                    .saturating_add(get_usd(graph, &pool.dest_token, pool.reserve1));
+                Some((pool, tvl_usd))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_usd(graph: &Graph, token_id: &UniversalTokenId, amount: Amount) -> Amount {
+    graph.get_token(token_id).map_or(0, |token| {
+        token
+            .derived_usd
+            .add_exp(USD_AMOUNT_EXPONENT as i8)
+            .mul_u128(amount)
+    })
+}
+
+#[cfg(test)]
+mod liquidity_summary_tests {
+    use privadex_chain_metadata::registry::chain::universal_chain_id_registry::ASTAR;
+
+    use super::*;
+    use crate::graph_snapshot::GRAPH_SNAPSHOT_VERSION;
+
+    const ARTHSWAP_RESPONSE: &str = "{\"data\":{\
+        \"pairs\":[\
+            {\"id\":\"0xccefddff4808f3e1e0340e19e43f1e9fd088b3f2\",\"reserve0\":\"6952946.44665235172725434\",\"reserve1\":\"62223196.301748411321042674\",\
+                \"token0\":{\"decimals\":18,\"derivedETH\":\"8.909583873683757648908068\",\"id\":\"0x75364d4f779d0bd0facd9a218c67f87dd9aff3b4\"},\
+                \"token1\":{\"decimals\":18,\"derivedETH\":\"1\",\"id\":\"0xaeaaf0e2c81af264101b9129c00f4440ccf0f720\"}\
+            }\
+        ], \
+        \"bundleById\":{\"ethPrice\":\"0.0396186463623557942761935\"}\
+    }}";
+
+    #[test]
+    fn test_liquidity_summary_from_snapshot() {
+        let snapshot = GraphSnapshot {
+            version: GRAPH_SNAPSHOT_VERSION,
+            chain_ids: vec![ASTAR],
+            dex_responses: vec![(DexId::Arthswap, ARTHSWAP_RESPONSE.as_bytes().to_vec())],
+        };
+        let summary = LiquiditySummary::from_snapshot(&snapshot, 1_000, None).unwrap();
+        assert_eq!(summary.chains.len(), 1);
+        // Both pair tokens plus native ASTR from the wrap edges
+        assert_eq!(summary.chains[0].num_tokens, 3);
+        // The wrap edges are not pools
+        assert_eq!(summary.chains[0].num_pools, 1);
+        // ~$4.92M between the two reserves
+        let tvl_usd = summary.chains[0].tvl_usd / Amount::pow(10, USD_AMOUNT_EXPONENT);
+        assert!(tvl_usd > 4_910_000 && tvl_usd < 4_930_000);
+        assert_eq!(
+            summary.dexes,
+            vec![DexLiquidity {
+                dex_id: DexId::Arthswap,
+                num_pools: 1,
+                tvl_usd: summary.chains[0].tvl_usd,
+                last_refreshed_millis: Some(1_000),
+            }]
+        );
+    }
+}
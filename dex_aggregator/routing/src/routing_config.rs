@@ -0,0 +1,152 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+
+use privadex_chain_metadata::{
+    common::UniversalTokenId, registry::token::universal_token_id_registry,
+};
+
+use crate::graph_builder::MIN_TOKEN_PAIR_RESERVE_USD;
+use crate::{PublicError, Result};
+
+// Path enumeration is exhaustive, so its cost grows quickly with the path length
+pub const MAX_ROUTING_PATH_LEN: u8 = 8;
+// Every pivot token costs two more (short) path searches per quote
+pub const MAX_PIVOT_TOKENS: usize = 8;
+
+/// Graph building and SOR limits that can be tuned without a redeploy. The defaults are the
+/// limits the router always used
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoutingConfig {
+    pub max_path_len: u8,
+    pub max_num_bridges: u8,
+    pub max_consecutive_swaps: u8,
+    // DEX pairs with less liquidity than this (in whole USD) are left out of the graph. It can
+    // only be raised, since lower thresholds push the subgraph responses past the 16KB limit
+    pub min_token_pair_reserve_usd: u32,
+    // Canonical intermediaries (per chain, since each is a token on one chain) that the SOR
+    // also routes through, on top of its exhaustive search. See find_pivot_paths
+    pub pivot_tokens: Vec<UniversalTokenId>,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            max_path_len: MAX_ROUTING_PATH_LEN,
+            max_num_bridges: 2,
+            max_consecutive_swaps: 4,
+            min_token_pair_reserve_usd: MIN_TOKEN_PAIR_RESERVE_USD,
+            pivot_tokens: default_pivot_tokens(),
+        }
+    }
+}
+
+// DOT on each chain that has it, and USDC where it is liquid
+pub fn default_pivot_tokens() -> Vec<UniversalTokenId> {
+    vec![
+        universal_token_id_registry::DOT_NATIVE,
+        universal_token_id_registry::DOT_MOONBEAM,
+        universal_token_id_registry::DOT_ASTAR,
+        universal_token_id_registry::USDC_WH_MOONBEAM,
+    ]
+}
+
+impl RoutingConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_path_len == 0
+            || self.max_path_len > MAX_ROUTING_PATH_LEN
+            || self.max_num_bridges > self.max_path_len
+            || self.max_consecutive_swaps == 0
+            || self.max_consecutive_swaps > self.max_path_len
+            || self.min_token_pair_reserve_usd < MIN_TOKEN_PAIR_RESERVE_USD
+            || self.pivot_tokens.len() > MAX_PIVOT_TOKENS
+            || (1..self.pivot_tokens.len())
+                .any(|i| self.pivot_tokens[..i].contains(&self.pivot_tokens[i]))
+        {
+            Err(PublicError::InvalidRoutingConfig)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod routing_config_tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(RoutingConfig::default().validate(), Ok(()));
+        let invalid_configs = [
+            RoutingConfig {
+                max_path_len: MAX_ROUTING_PATH_LEN + 1,
+                ..RoutingConfig::default()
+            },
+            RoutingConfig {
+                max_path_len: 3,
+                max_num_bridges: 4,
+                max_consecutive_swaps: 2,
+                ..RoutingConfig::default()
+            },
+            RoutingConfig {
+                max_consecutive_swaps: 0,
+                ..RoutingConfig::default()
+            },
+            RoutingConfig {
+                min_token_pair_reserve_usd: MIN_TOKEN_PAIR_RESERVE_USD - 1,
+                ..RoutingConfig::default()
+            },
+            RoutingConfig {
+                pivot_tokens: vec![universal_token_id_registry::DOT_NATIVE; MAX_PIVOT_TOKENS + 1],
+                ..RoutingConfig::default()
+            },
+            RoutingConfig {
+                pivot_tokens: vec![
+                    universal_token_id_registry::DOT_MOONBEAM,
+                    universal_token_id_registry::DOT_ASTAR,
+                    universal_token_id_registry::DOT_MOONBEAM,
+                ],
+                ..RoutingConfig::default()
+            },
+        ];
+        for config in invalid_configs.iter() {
+            assert_eq!(config.validate(), Err(PublicError::InvalidRoutingConfig));
+        }
+        // No pivots just means the exhaustive search alone
+        assert_eq!(
+            RoutingConfig {
+                pivot_tokens: Vec::new(),
+                ..RoutingConfig::default()
+            }
+            .validate(),
+            Ok(())
+        );
+        // Raising the reserve threshold only shrinks the graph
+        assert_eq!(
+            RoutingConfig {
+                min_token_pair_reserve_usd: 50_000,
+                ..RoutingConfig::default()
+            }
+            .validate(),
+            Ok(())
+        );
+    }
+}
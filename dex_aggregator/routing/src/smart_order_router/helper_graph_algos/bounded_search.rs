@@ -0,0 +1,331 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Searches that bound their own work, for graphs where find_all_paths would not fit in a Phat
+// contract's execution limits. They trade optimality for that: each returns a handful of paths,
+// which the SOR quotes like any others
+use core::ptr;
+
+use graphlib::VertexId;
+use hashbrown::{HashMap, HashSet};
+use ink::prelude::{collections::BinaryHeap, vec, vec::Vec};
+
+use privadex_chain_metadata::common::Amount;
+
+use super::find_all_paths::AllPathsFinderConfig;
+use crate::graph::{
+    edge::Edge,
+    graph::{Graph, GraphPathRef},
+    traits::QuoteGetter,
+};
+
+// A partial path out of the search's src
+#[derive(Clone)]
+struct Label<'a> {
+    src: VertexId,
+    vertex: VertexId,
+    amount: Amount,
+    // Partial paths end in different tokens, so we rank them by the USD value of their amount
+    value_usd: Amount,
+    path: Vec<(VertexId, &'a Edge)>,
+}
+
+impl<'a> Label<'a> {
+    fn root(graph: &Graph, src: VertexId, amount_in: Amount) -> Self {
+        Self {
+            src,
+            vertex: src,
+            amount: amount_in,
+            value_usd: get_value_usd(graph, &src, amount_in),
+            path: Vec::new(),
+        }
+    }
+
+    fn visits(&self, vertex: &VertexId) -> bool {
+        self.src == *vertex || self.path.iter().any(|(v, _)| v == vertex)
+    }
+
+    // None if the edge's fees eat up the whole amount
+    fn extend(&self, graph: &Graph, vertex: VertexId, edge: &'a Edge) -> Option<Self> {
+        let amount = edge.get_quote_with_estimated_txn_fees(self.amount);
+        if amount == 0 {
+            return None;
+        }
+        let mut path = self.path.clone();
+        path.push((vertex, edge));
+        Some(Self {
+            src: self.src,
+            vertex,
+            amount,
+            value_usd: get_value_usd(graph, &vertex, amount),
+            path,
+        })
+    }
+
+    fn has_same_edges(&self, other: &Label, num_edges: usize) -> bool {
+        self.path.len() >= num_edges
+            && other.path.len() >= num_edges
+            && self.path[..num_edges]
+                .iter()
+                .zip(other.path[..num_edges].iter())
+                .all(|((_, e1), (_, e2))| ptr::eq(*e1, *e2))
+    }
+
+    fn into_path(self) -> GraphPathRef<'a> {
+        GraphPathRef(self.path.into_iter().map(|(_, edge)| edge).collect())
+    }
+}
+
+fn get_value_usd(graph: &Graph, vertex: &VertexId, amount: Amount) -> Amount {
+    graph
+        .simple_graph
+        .fetch(vertex)
+        .map_or(0, |token| token.derived_usd.mul_u128(amount))
+}
+
+// The same limits as find_all_paths
+fn get_next_labels<'a>(
+    graph: &'a Graph,
+    label: &Label<'a>,
+    config: &AllPathsFinderConfig,
+) -> Vec<Label<'a>> {
+    if label.path.len() >= config.max_path_len as usize {
+        return Vec::new();
+    }
+    let num_bridges = label
+        .path
+        .iter()
+        .filter(|(_, edge)| edge.is_bridge())
+        .count();
+    let num_consecutive_swaps = label
+        .path
+        .iter()
+        .rev()
+        .take_while(|(_, edge)| edge.is_swap())
+        .count();
+    let mut next_labels = Vec::new();
+    for i in graph.simple_graph.out_neighbors(&label.vertex) {
+        if label.visits(i) {
+            continue;
+        }
+        let edges = graph
+            .get_edges(label.vertex, *i)
+            .expect("Edge exists in graph");
+        for edge in edges.iter() {
+            let is_allowed = (!edge.is_bridge() || num_bridges < config.max_num_bridges as usize)
+                && (!edge.is_swap()
+                    || num_consecutive_swaps < config.max_consecutive_swaps as usize)
+                && (config.allow_dex_swaps || !edge.is_dex_swap());
+            if is_allowed {
+                if let Some(next_label) = label.extend(graph, *i, edge) {
+                    next_labels.push(next_label);
+                }
+            }
+        }
+    }
+    next_labels
+}
+
+// Dijkstra, where a token is settled by the partial path that brings the most USD value into
+// it. Fees only ever lose value, so this is close to a shortest path search, but it is not exact:
+// the path limits and the CPMM price impact depend on the whole path, not just its last token
+fn find_best_amount_label<'a>(
+    graph: &'a Graph,
+    root: Label<'a>,
+    dest: &VertexId,
+    config: &AllPathsFinderConfig,
+    excluded_edges: &[&'a Edge],
+) -> Option<Label<'a>> {
+    let mut settled: HashSet<VertexId> = HashSet::new();
+    let mut heap: BinaryHeap<(Amount, usize)> = BinaryHeap::new();
+    heap.push((root.value_usd, 0));
+    let mut labels = vec![root];
+    while let Some((_, i)) = heap.pop() {
+        let vertex = labels[i].vertex;
+        if !settled.insert(vertex) {
+            continue;
+        }
+        if vertex == *dest {
+            return Some(labels.swap_remove(i));
+        }
+        for next_label in get_next_labels(graph, &labels[i], config) {
+            let (_, edge) = next_label.path.last().expect("Next labels have an edge");
+            if settled.contains(&next_label.vertex)
+                || excluded_edges
+                    .iter()
+                    .any(|excluded| ptr::eq(*excluded, *edge))
+            {
+                continue;
+            }
+            heap.push((next_label.value_usd, labels.len()));
+            labels.push(next_label);
+        }
+    }
+    None
+}
+
+// Yen's algorithm over find_best_amount_label: the first path is the best amount path, and each
+// next one is the best deviation from the paths we already have. Best first
+pub(crate) fn find_k_best_amount_paths<'a>(
+    graph: &'a Graph,
+    src: &VertexId,
+    dest: &VertexId,
+    amount_in: Amount,
+    config: &AllPathsFinderConfig,
+    k: u8,
+) -> Vec<GraphPathRef<'a>> {
+    let root = Label::root(graph, *src, amount_in);
+    let mut best: Vec<Label<'a>> =
+        match find_best_amount_label(graph, root.clone(), dest, config, &[]) {
+            Some(label) => vec![label],
+            None => return Vec::new(),
+        };
+    let mut candidates: Vec<Label<'a>> = Vec::new();
+    while best.len() < k as usize {
+        let prev = best.last().expect("There is a best path").clone();
+        let mut spur_root = root.clone();
+        for num_root_edges in 0..prev.path.len() {
+            // The spur path leaves prev after its first num_root_edges edges, but not the way
+            // any best path with the same root did
+            let excluded_edges: Vec<&'a Edge> = best
+                .iter()
+                .filter(|label| {
+                    label.path.len() > num_root_edges && label.has_same_edges(&prev, num_root_edges)
+                })
+                .map(|label| label.path[num_root_edges].1)
+                .collect();
+            if let Some(label) =
+                find_best_amount_label(graph, spur_root.clone(), dest, config, &excluded_edges)
+            {
+                let is_new = !best.iter().chain(candidates.iter()).any(|other| {
+                    other.path.len() == label.path.len()
+                        && other.has_same_edges(&label, label.path.len())
+                });
+                if is_new {
+                    candidates.push(label);
+                }
+            }
+            let (vertex, edge) = prev.path[num_root_edges];
+            spur_root = match spur_root.extend(graph, vertex, edge) {
+                Some(label) => label,
+                None => break,
+            };
+        }
+        // Every candidate ends in the dest token, so their amounts compare directly
+        let next = candidates
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, label)| label.amount)
+            .map(|(i, _)| i);
+        match next {
+            Some(i) => best.push(candidates.swap_remove(i)),
+            None => break,
+        }
+    }
+    best.into_iter().map(Label::into_path).collect()
+}
+
+// Breadth first, keeping only the beam_width partial paths worth the most at each depth, and
+// expanding each token at most max_expansions_per_vertex times in total
+pub(crate) fn find_beam_search_paths<'a>(
+    graph: &'a Graph,
+    src: &VertexId,
+    dest: &VertexId,
+    amount_in: Amount,
+    config: &AllPathsFinderConfig,
+    beam_width: u8,
+    max_expansions_per_vertex: u8,
+) -> Vec<GraphPathRef<'a>> {
+    let mut num_expansions: HashMap<VertexId, u8> = HashMap::new();
+    let mut frontier = vec![Label::root(graph, *src, amount_in)];
+    let mut paths: Vec<Label<'a>> = Vec::new();
+    while !frontier.is_empty() {
+        let mut next_frontier: Vec<Label<'a>> = Vec::new();
+        for label in frontier.iter() {
+            let n = num_expansions.entry(label.vertex).or_insert(0);
+            if *n >= max_expansions_per_vertex {
+                continue;
+            }
+            *n += 1;
+            for next_label in get_next_labels(graph, label, config) {
+                if next_label.vertex == *dest {
+                    paths.push(next_label);
+                } else {
+                    next_frontier.push(next_label);
+                }
+            }
+        }
+        next_frontier.sort_by(|label1, label2| label2.value_usd.cmp(&label1.value_usd));
+        next_frontier.truncate(beam_width as usize);
+        frontier = next_frontier;
+    }
+    paths.into_iter().map(Label::into_path).collect()
+}
+
+#[cfg(test)]
+mod bounded_search_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry;
+
+    use super::super::find_all_paths::find_all_paths;
+    use super::*;
+    use crate::test_utilities::graph_factory;
+
+    fn best_quote(paths: &Vec<GraphPathRef>, amount_in: Amount) -> Amount {
+        paths
+            .iter()
+            .map(|path| path.get_quote_with_estimated_txn_fees(amount_in))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_bounded_searches_small_graph() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let src = graph
+            .get_vertex(&universal_token_id_registry::GLMR_NATIVE)
+            .expect("GLMR native should be in the graph");
+        let dest = graph
+            .get_vertex(&universal_token_id_registry::DOT_NATIVE)
+            .expect("DOT native should be in the graph");
+        let amount_in = 100_000_000_000_000_000_000;
+        let config = AllPathsFinderConfig::default();
+
+        let exhaustive_quote = best_quote(&find_all_paths(&graph, src, dest, &config), amount_in);
+        assert!(exhaustive_quote > 0);
+
+        let best_amount_paths = find_k_best_amount_paths(&graph, src, dest, amount_in, &config, 1);
+        assert_eq!(best_amount_paths.len(), 1);
+        assert_eq!(best_quote(&best_amount_paths, amount_in), exhaustive_quote);
+
+        let k_best_amount_paths =
+            find_k_best_amount_paths(&graph, src, dest, amount_in, &config, 3);
+        assert!(k_best_amount_paths.len() >= 1 && k_best_amount_paths.len() <= 3);
+        assert_eq!(
+            best_quote(&k_best_amount_paths, amount_in),
+            exhaustive_quote
+        );
+
+        let beam_paths = find_beam_search_paths(&graph, src, dest, amount_in, &config, 4, 2);
+        assert_eq!(best_quote(&beam_paths, amount_in), exhaustive_quote);
+
+        // Nothing gets expanded, not even the src
+        let no_paths = find_beam_search_paths(&graph, src, dest, amount_in, &config, 4, 0);
+        assert_eq!(no_paths.len(), 0);
+    }
+}
@@ -18,12 +18,13 @@
 
 use graphlib::VertexId;
 use hashbrown::HashSet;
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 
 use crate::graph::{
     edge::Edge,
     graph::{Graph, GraphPathRef},
 };
+use crate::routing_config::RoutingConfig;
 
 // Empirically, the pair with the longest path that I have found has a path of length 7: 3 swaps + bridge + 3 swaps
 // e.g. 0x29F6e49c6E3397C3A84F715885F9F233A441165C (oUSD on Astar)
@@ -32,14 +33,23 @@ pub(crate) struct AllPathsFinderConfig {
     pub(crate) max_path_len: u8,
     pub(crate) max_num_bridges: u8,
     pub(crate) max_consecutive_swaps: u8,
+    // If false, only bridges, wraps and unwraps are considered
+    pub(crate) allow_dex_swaps: bool,
 }
 
 impl Default for AllPathsFinderConfig {
     fn default() -> Self {
+        Self::from(&RoutingConfig::default())
+    }
+}
+
+impl From<&RoutingConfig> for AllPathsFinderConfig {
+    fn from(routing_config: &RoutingConfig) -> Self {
         Self {
-            max_path_len: 8,
-            max_num_bridges: 2,
-            max_consecutive_swaps: 4,
+            max_path_len: routing_config.max_path_len,
+            max_num_bridges: routing_config.max_num_bridges,
+            max_consecutive_swaps: routing_config.max_consecutive_swaps,
+            allow_dex_swaps: true,
         }
     }
 }
@@ -108,7 +118,8 @@ pub(crate) fn find_all_paths<'a>(
                         let should_consider_path = (path.len() < config.max_path_len as usize)
                             && (!edge.is_bridge() || num_bridges < config.max_num_bridges as usize)
                             && (!edge.is_swap()
-                                || num_consecutive_swaps < config.max_consecutive_swaps as usize);
+                                || num_consecutive_swaps < config.max_consecutive_swaps as usize)
+                            && (config.allow_dex_swaps || !edge.is_dex_swap());
                         if should_consider_path {
                             stack.push(StackEntry {
                                 vertex: i.clone(),
@@ -151,7 +162,7 @@ fn get_num_latest_consecutive_swaps(path: &Vec<PathEntry>) -> usize {
 #[cfg(test)]
 mod find_all_paths_test {
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     use privadex_chain_metadata::{
         common::{
@@ -480,6 +491,7 @@ mod find_all_paths_test {
                 max_path_len: 100,
                 max_consecutive_swaps: 100,
                 max_num_bridges: 100,
+                allow_dex_swaps: true,
             };
             let all_paths = find_all_paths(&graph, src, dest, &config);
             let num_paths = all_paths.len();
@@ -493,6 +505,7 @@ mod find_all_paths_test {
                 max_path_len: 7,
                 max_consecutive_swaps: 100,
                 max_num_bridges: 100,
+                allow_dex_swaps: true,
             };
             let all_paths = find_all_paths(&graph, src, dest, &config);
             let num_paths = all_paths.len();
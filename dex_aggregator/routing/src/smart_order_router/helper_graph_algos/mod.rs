@@ -18,3 +18,7 @@
 
 mod find_all_paths;
 pub(crate) use find_all_paths::{find_all_paths, AllPathsFinderConfig};
+mod pivot_paths;
+pub(crate) use pivot_paths::find_pivot_paths;
+mod bounded_search;
+pub(crate) use bounded_search::{find_beam_search_paths, find_k_best_amount_paths};
@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Pivot tokens are deep-liquidity intermediaries (e.g. DOT or USDC). Exotic pairs, like two
+// native tokens on different chains, often have only thin direct lanes but quote well through a
+// pivot. We seed candidate paths src -> pivot -> dest from two short searches instead of
+// searching deeper, since the exhaustive search's cost grows quickly with the path length
+use graphlib::VertexId;
+use hashbrown::HashSet;
+use ink::prelude::vec::Vec;
+
+use privadex_chain_metadata::common::{Amount, UniversalTokenId};
+
+use super::find_all_paths::{find_all_paths, AllPathsFinderConfig};
+use crate::graph::{edge::Edge, graph::Graph, graph::GraphPathRef, traits::QuoteGetter};
+
+// Each leg is at most this long, so a joined path is at most MAX_ROUTING_PATH_LEN
+const MAX_PIVOT_LEG_LEN: u8 = 4;
+// We only join the best legs into each pivot (and out of it), by their quote
+const MAX_PIVOT_LEG_PATHS: usize = 4;
+
+pub(crate) fn find_pivot_paths<'a>(
+    graph: &'a Graph,
+    src: &'a VertexId,
+    dest: &'a VertexId,
+    pivot_tokens: &[UniversalTokenId],
+    config: &AllPathsFinderConfig,
+    amount_in: Amount,
+) -> Vec<GraphPathRef<'a>> {
+    let leg_config = AllPathsFinderConfig {
+        max_path_len: MAX_PIVOT_LEG_LEN,
+        max_num_bridges: config.max_num_bridges,
+        max_consecutive_swaps: config.max_consecutive_swaps,
+        allow_dex_swaps: config.allow_dex_swaps,
+    };
+    let mut pivot_paths: Vec<GraphPathRef<'a>> = Vec::new();
+    for pivot_token in pivot_tokens.iter() {
+        let pivot = match graph.get_vertex(pivot_token) {
+            Some(pivot) if pivot != src && pivot != dest => pivot,
+            _ => continue,
+        };
+        let first_legs = find_best_leg_paths(graph, src, pivot, &leg_config, amount_in);
+        // The legs out of the pivot are ranked by what the best leg into it delivers
+        let pivot_amount = match first_legs.first() {
+            Some((amount, _)) => *amount,
+            None => continue,
+        };
+        let second_legs = find_best_leg_paths(graph, pivot, dest, &leg_config, pivot_amount);
+        for (_, first_leg) in first_legs.iter() {
+            for (_, second_leg) in second_legs.iter() {
+                if let Some(path) = join_legs(first_leg, second_leg, config) {
+                    pivot_paths.push(path);
+                }
+            }
+        }
+    }
+    pivot_paths
+}
+
+// Best first. Legs that quote 0 (i.e. don't cover their fees) are dropped
+fn find_best_leg_paths<'a>(
+    graph: &'a Graph,
+    src: &'a VertexId,
+    dest: &'a VertexId,
+    leg_config: &AllPathsFinderConfig,
+    amount_in: Amount,
+) -> Vec<(Amount, GraphPathRef<'a>)> {
+    let mut legs: Vec<(Amount, GraphPathRef<'a>)> = find_all_paths(graph, src, dest, leg_config)
+        .into_iter()
+        .map(|path| (path.get_quote_with_estimated_txn_fees(amount_in), path))
+        .filter(|(quote, _)| *quote > 0)
+        .collect();
+    legs.sort_by(|(quote1, _), (quote2, _)| quote2.cmp(quote1));
+    legs.truncate(MAX_PIVOT_LEG_PATHS);
+    legs
+}
+
+// Each leg is within the limits on its own, but the joined path must not revisit a token and
+// its bridges and consecutive swaps (which can run across the pivot) must still be in the limits
+fn join_legs<'a>(
+    first_leg: &GraphPathRef<'a>,
+    second_leg: &GraphPathRef<'a>,
+    config: &AllPathsFinderConfig,
+) -> Option<GraphPathRef<'a>> {
+    let edges: Vec<&'a Edge> = first_leg
+        .0
+        .iter()
+        .chain(second_leg.0.iter())
+        .map(|edge| *edge)
+        .collect();
+
+    let mut tokens: HashSet<&UniversalTokenId> = HashSet::new();
+    let (src_token, _) = edges.first()?.get_src_dest_token();
+    let _ = tokens.insert(src_token);
+    for edge in edges.iter() {
+        let (_, dest_token) = edge.get_src_dest_token();
+        if !tokens.insert(dest_token) {
+            return None;
+        }
+    }
+
+    let num_bridges = edges.iter().filter(|edge| edge.is_bridge()).count();
+    let max_consecutive_swaps = edges
+        .iter()
+        .fold((0, 0), |(longest, current), edge| {
+            let current = if edge.is_swap() { current + 1 } else { 0 };
+            (longest.max(current), current)
+        })
+        .0;
+    if num_bridges > config.max_num_bridges as usize
+        || max_consecutive_swaps > config.max_consecutive_swaps as usize
+    {
+        return None;
+    }
+    Some(GraphPathRef(edges))
+}
+
+#[cfg(test)]
+mod pivot_paths_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry;
+
+    use super::*;
+    use crate::test_utilities::graph_factory;
+
+    #[test]
+    fn test_find_pivot_paths_small_graph() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let src = graph
+            .get_vertex(&universal_token_id_registry::GLMR_NATIVE)
+            .expect("GLMR native should be in the graph");
+        let dest = graph
+            .get_vertex(&universal_token_id_registry::DOT_NATIVE)
+            .expect("DOT native should be in the graph");
+        let amount_in = 100_000_000_000_000_000_000;
+        let config = AllPathsFinderConfig::default();
+
+        let pivot_paths = find_pivot_paths(
+            &graph,
+            src,
+            dest,
+            &[universal_token_id_registry::DOT_MOONBEAM],
+            &config,
+            amount_in,
+        );
+        assert!(pivot_paths.len() > 0);
+        for path in pivot_paths.iter() {
+            assert_eq!(
+                path.get_src_dest_token(),
+                (
+                    &universal_token_id_registry::GLMR_NATIVE,
+                    &universal_token_id_registry::DOT_NATIVE
+                )
+            );
+            assert!(path
+                .0
+                .iter()
+                .any(|edge| edge.get_src_dest_token().1
+                    == &universal_token_id_registry::DOT_MOONBEAM));
+        }
+
+        // The src and dest are not pivots, and neither is a token that isn't in the graph
+        let no_pivot_paths = find_pivot_paths(
+            &graph,
+            src,
+            dest,
+            &[
+                universal_token_id_registry::GLMR_NATIVE,
+                universal_token_id_registry::DOT_NATIVE,
+                universal_token_id_registry::USDT_ASTAR,
+            ],
+            &config,
+            amount_in,
+        );
+        assert_eq!(no_pivot_paths.len(), 0);
+    }
+}
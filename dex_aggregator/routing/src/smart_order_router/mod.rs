@@ -17,5 +17,8 @@
  */
 
 pub(crate) mod helper_graph_algos;
+pub mod route_exists;
+pub mod router_candidate_paths;
 pub mod single_path_sor;
 pub mod split_path_sor;
+pub mod venue_quotes;
@@ -0,0 +1,179 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Whether a pair could be routed at all, for UIs to grey out unsupported pairs before asking
+// for a quote. Nothing is priced: this is a reachability search under the RoutingConfig's path
+// limits, so it is a small fraction of the cost of the SOR's path enumeration
+use graphlib::VertexId;
+use hashbrown::HashSet;
+use ink::prelude::{vec, vec::Vec};
+
+use privadex_chain_metadata::common::UniversalTokenId;
+
+use super::helper_graph_algos::AllPathsFinderConfig;
+use crate::graph::graph::Graph;
+use crate::routing_config::RoutingConfig;
+
+// (vertex, path length, number of bridges, number of latest consecutive swaps)
+type SearchState = (VertexId, u8, u8, u8);
+
+// Unlike find_all_paths, the search does not require paths to be simple (it would have to
+// track every path to do so). Cycling back to a vertex can only use up the path limits, except
+// that a cycle through a bridge resets the consecutive swaps. So this errs on the side of
+// reporting a route, which the quote then refines. It also says nothing about whether the
+// amount in covers the fees along the route
+pub fn route_exists(
+    graph: &Graph,
+    src_token_id: &UniversalTokenId,
+    dest_token_id: &UniversalTokenId,
+    routing_config: &RoutingConfig,
+) -> bool {
+    if src_token_id == dest_token_id {
+        return false;
+    }
+    let (src, dest) = match (
+        graph.get_vertex(src_token_id),
+        graph.get_vertex(dest_token_id),
+    ) {
+        (Some(src), Some(dest)) => (*src, *dest),
+        _ => return false,
+    };
+    let config = AllPathsFinderConfig::from(routing_config);
+
+    let mut seen: HashSet<SearchState> = HashSet::new();
+    let mut stack: Vec<SearchState> = vec![(src, 0, 0, 0)];
+    while let Some(state) = stack.pop() {
+        let (u, path_len, num_bridges, num_consecutive_swaps) = state;
+        if u == dest {
+            return true;
+        }
+        if path_len >= config.max_path_len || !seen.insert(state) {
+            continue;
+        }
+        for v in graph.simple_graph.out_neighbors(&u) {
+            let edges = graph.get_edges(u, *v).expect("Edge exists in graph");
+            for edge in edges.iter() {
+                if edge.is_bridge() && num_bridges < config.max_num_bridges {
+                    stack.push((*v, path_len + 1, num_bridges + 1, 0));
+                } else if edge.is_swap()
+                    && num_consecutive_swaps < config.max_consecutive_swaps
+                    && (config.allow_dex_swaps || !edge.is_dex_swap())
+                {
+                    stack.push((*v, path_len + 1, num_bridges, num_consecutive_swaps + 1));
+                }
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod route_exists_tests {
+    use privadex_chain_metadata::{
+        common::{
+            ChainTokenId::ERC20, ERC20Token, EthAddress, UniversalChainId::SubstrateParachain,
+        },
+        registry::{chain::RelayChain::Polkadot, token::universal_token_id_registry},
+    };
+
+    use super::*;
+    use crate::smart_order_router::helper_graph_algos::find_all_paths;
+    use crate::test_utilities::graph_factory;
+
+    #[test]
+    fn test_route_exists_agrees_with_find_all_paths() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::medium_graph();
+        let routing_config = RoutingConfig::default();
+        let pairs = [
+            (
+                universal_token_id_registry::DOT_NATIVE,
+                universal_token_id_registry::GLMR_NATIVE,
+            ),
+            (
+                universal_token_id_registry::GLMR_NATIVE,
+                universal_token_id_registry::DOT_MOONBEAM,
+            ),
+            // No path in the medium graph
+            (
+                universal_token_id_registry::DOT_NATIVE,
+                universal_token_id_registry::ASTR_NATIVE,
+            ),
+        ];
+        for (src_token_id, dest_token_id) in pairs.iter() {
+            let src = graph.get_vertex(src_token_id).unwrap();
+            let dest = graph.get_vertex(dest_token_id).unwrap();
+            let has_path =
+                !find_all_paths(&graph, src, dest, &AllPathsFinderConfig::default()).is_empty();
+            assert_eq!(
+                route_exists(&graph, src_token_id, dest_token_id, &routing_config),
+                has_path
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_exists_respects_path_limits() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::medium_graph();
+        let src_token_id = universal_token_id_registry::DOT_NATIVE;
+        let dest_token_id = universal_token_id_registry::GLMR_NATIVE;
+        assert!(route_exists(
+            &graph,
+            &src_token_id,
+            &dest_token_id,
+            &RoutingConfig::default()
+        ));
+        // DOT on Polkadot only reaches Moonbeam through a bridge
+        let no_bridges = RoutingConfig {
+            max_num_bridges: 0,
+            ..RoutingConfig::default()
+        };
+        assert!(!route_exists(
+            &graph,
+            &src_token_id,
+            &dest_token_id,
+            &no_bridges
+        ));
+    }
+
+    #[test]
+    fn test_route_exists_same_or_unknown_token() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let routing_config = RoutingConfig::default();
+        assert!(!route_exists(
+            &graph,
+            &universal_token_id_registry::DOT_NATIVE,
+            &universal_token_id_registry::DOT_NATIVE,
+            &routing_config
+        ));
+        let unknown_token_id = UniversalTokenId {
+            chain: SubstrateParachain(Polkadot, 2004),
+            id: ERC20(ERC20Token {
+                addr: EthAddress::zero(),
+            }),
+        };
+        assert!(!route_exists(
+            &graph,
+            &universal_token_id_registry::DOT_NATIVE,
+            &unknown_token_id,
+            &routing_config
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// Paths that a single DEX router can quote with getAmountsOut. This is the fallback for when
+// the DEX subgraphs are down: the graph comes from a cached snapshot, so its reserves are stale,
+// and the caller re-quotes these candidates on-chain
+use ink::prelude::vec::Vec;
+
+use privadex_chain_metadata::common::{Amount, UniversalTokenId};
+
+use super::helper_graph_algos::{find_all_paths, AllPathsFinderConfig};
+use crate::graph::{
+    edge::{Edge, SwapEdge},
+    graph::{Graph, GraphPath, GraphPathRef},
+    traits::QuoteGetter,
+};
+use crate::{PublicError, Result};
+
+// Every candidate costs an eth_call, so we only keep the ones that quote best on the snapshot
+pub const MAX_ROUTER_CANDIDATE_PATHS: usize = 8;
+
+// Wrap + 2 pools + Unwrap
+const MAX_ROUTER_PATH_LEN: u8 = 4;
+// i.e. the direct pool or a route through one intermediate token
+const MAX_ROUTER_PATH_POOLS: usize = 2;
+
+// Best first (by their quote on the graph's reserves). Cross-chain pairs have no candidates
+pub fn find_router_candidate_paths(
+    graph: &Graph,
+    src_token_id: &UniversalTokenId,
+    dest_token_id: &UniversalTokenId,
+    amount_in: Amount,
+) -> Result<Vec<GraphPath>> {
+    if src_token_id == dest_token_id {
+        return Err(PublicError::SrcTokenDestTokenAreSame);
+    }
+    let src_vertex = graph
+        .get_vertex(src_token_id)
+        .ok_or(PublicError::VertexNotInGraph(src_token_id.clone()))?;
+    let dest_vertex = graph
+        .get_vertex(dest_token_id)
+        .ok_or(PublicError::VertexNotInGraph(dest_token_id.clone()))?;
+    let config = AllPathsFinderConfig {
+        max_path_len: MAX_ROUTER_PATH_LEN,
+        max_num_bridges: 0,
+        max_consecutive_swaps: MAX_ROUTER_PATH_LEN,
+        allow_dex_swaps: true,
+    };
+    let mut candidates: Vec<(Amount, GraphPathRef)> =
+        find_all_paths(graph, src_vertex, dest_vertex, &config)
+            .into_iter()
+            .filter(is_single_router_path)
+            .map(|path| (path.get_quote_with_estimated_txn_fees(amount_in), path))
+            .collect();
+    candidates.sort_by(|(quote1, _), (quote2, _)| quote2.cmp(quote1));
+    Ok(candidates
+        .into_iter()
+        .take(MAX_ROUTER_CANDIDATE_PATHS)
+        .map(|(_, path)| GraphPath::from(path))
+        .collect())
+}
+
+// Pools of one DEX, optionally preceded by a Wrap and followed by an Unwrap (which are 1:1, so
+// getAmountsOut over the pools' tokens gives the path's amount out)
+fn is_single_router_path(path: &GraphPathRef) -> bool {
+    let edges = &path.0;
+    let pools_start = match edges.first() {
+        Some(Edge::Swap(SwapEdge::Wrap(_))) => 1,
+        _ => 0,
+    };
+    let pools_end = match edges.last() {
+        Some(Edge::Swap(SwapEdge::Unwrap(_))) => edges.len() - 1,
+        _ => edges.len(),
+    };
+    if pools_start >= pools_end || pools_end - pools_start > MAX_ROUTER_PATH_POOLS {
+        return false;
+    }
+    let mut dex_ids = edges[pools_start..pools_end].iter().map(|edge| match edge {
+        Edge::Swap(SwapEdge::CPMM(cpmm_edge)) => Some(cpmm_edge.dex.id),
+        _ => None,
+    });
+    match dex_ids.next() {
+        Some(Some(dex_id)) => dex_ids.all(|other_dex_id| other_dex_id == Some(dex_id)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod router_candidate_paths_tests {
+    use privadex_chain_metadata::registry::token::universal_token_id_registry;
+
+    use super::*;
+    use crate::test_utilities::graph_factory;
+
+    #[test]
+    fn test_router_candidate_paths() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::medium_graph();
+        let amount_in = 100_000_000_000_000_000_000;
+
+        let candidates = find_router_candidate_paths(
+            &graph,
+            &universal_token_id_registry::GLMR_NATIVE,
+            &universal_token_id_registry::DOT_MOONBEAM,
+            amount_in,
+        )
+        .unwrap();
+        assert!(!candidates.is_empty());
+        assert!(candidates.len() <= MAX_ROUTER_CANDIDATE_PATHS);
+        let quotes: Vec<Amount> = candidates
+            .iter()
+            .map(|path| path.get_quote_with_estimated_txn_fees(amount_in))
+            .collect();
+        assert!(quotes.windows(2).all(|pair| pair[0] >= pair[1]));
+        for path in candidates.iter() {
+            assert!(path.0.iter().all(|edge| !edge.is_bridge()));
+            assert!(matches!(
+                path.0.first(),
+                Some(Edge::Swap(SwapEdge::Wrap(_)))
+            ));
+        }
+
+        // Routers don't bridge
+        assert!(find_router_candidate_paths(
+            &graph,
+            &universal_token_id_registry::GLMR_NATIVE,
+            &universal_token_id_registry::ASTR_NATIVE,
+            amount_in,
+        )
+        .unwrap()
+        .is_empty());
+    }
+}
@@ -16,25 +16,81 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_prelude::{vec, vec::Vec};
+use ink::prelude::{vec, vec::Vec};
 
 use privadex_chain_metadata::common::{Amount, EthAddress, UniversalTokenId};
 
-use super::helper_graph_algos::{find_all_paths, AllPathsFinderConfig};
+use super::helper_graph_algos::{
+    find_all_paths, find_beam_search_paths, find_k_best_amount_paths, find_pivot_paths,
+    AllPathsFinderConfig,
+};
 use crate::graph::graph::{Graph, GraphPath, GraphPathRef, GraphSolution, SplitGraphPath};
 use crate::graph::traits::QuoteGetter;
+use crate::routing_config::RoutingConfig;
 use crate::{PublicError, Result};
 
+// How the SOR finds its candidate paths. Exhaustive is optimal within the path limits, but its
+// cost grows quickly with the graph, so big graphs can trade optimality for a bounded search
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SearchAlgorithm {
+    // Every path within the limits (DFS)
+    Exhaustive,
+    // Dijkstra on the USD value that reaches each token. One path
+    Dijkstra,
+    // Yen's algorithm over Dijkstra. Up to k paths
+    YenKPaths {
+        k: u8,
+    },
+    // Keeps the beam_width partial paths worth the most at each depth, and expands each token at
+    // most max_expansions_per_vertex times
+    Beam {
+        beam_width: u8,
+        max_expansions_per_vertex: u8,
+    },
+}
+
 pub struct SORConfig {
     all_paths_finder_config: AllPathsFinderConfig,
+    pivot_tokens: Vec<UniversalTokenId>,
+    search_algorithm: SearchAlgorithm,
 }
 
 impl Default for SORConfig {
     fn default() -> Self {
+        Self::new(&RoutingConfig::default())
+    }
+}
+
+impl SORConfig {
+    pub fn new(routing_config: &RoutingConfig) -> Self {
         SORConfig {
-            all_paths_finder_config: AllPathsFinderConfig::default(),
+            all_paths_finder_config: AllPathsFinderConfig::from(routing_config),
+            pivot_tokens: routing_config.pivot_tokens.clone(),
+            search_algorithm: SearchAlgorithm::Exhaustive,
         }
     }
+
+    // Only routes over bridges, wraps and unwraps, i.e. moves the src token to another chain
+    // as-is (e.g. DOT to xcDOT on Moonbeam) without any price exposure. Tokens that aren't the
+    // same asset on both ends have no such route, and the SOR returns NoPathFound
+    pub fn transfer_only() -> Self {
+        SORConfig::default().with_transfer_only()
+    }
+
+    pub fn with_transfer_only(mut self) -> Self {
+        self.all_paths_finder_config.allow_dex_swaps = false;
+        self
+    }
+
+    pub fn with_pivot_tokens(mut self, pivot_tokens: Vec<UniversalTokenId>) -> Self {
+        self.pivot_tokens = pivot_tokens;
+        self
+    }
+
+    pub fn with_search_algorithm(mut self, search_algorithm: SearchAlgorithm) -> Self {
+        self.search_algorithm = search_algorithm;
+        self
+    }
 }
 
 pub struct SinglePathSOR<'a> {
@@ -80,10 +136,15 @@ impl<'a> SinglePathSOR<'a> {
         })
     }
 
+    // A src token that is the dest token has nothing to route, even across addresses: that is a
+    // plain transfer, which doesn't need the escrow
     fn find_optimal_path(&self, amount_in: Amount) -> Result<GraphPath> {
         if self.src_token == self.dest_token {
             return Err(PublicError::SrcTokenDestTokenAreSame);
         }
+        if amount_in == 0 {
+            return Err(PublicError::ZeroAmountIn);
+        }
         let src_vertex = self
             .graph
             .get_vertex(&self.src_token)
@@ -93,16 +154,51 @@ impl<'a> SinglePathSOR<'a> {
             .get_vertex(&self.dest_token)
             .ok_or(PublicError::VertexNotInGraph(self.dest_token.clone()))?;
 
-        let paths: Vec<GraphPathRef> = find_all_paths(
+        let config = &self.sor_config.all_paths_finder_config;
+        let mut paths: Vec<GraphPathRef> = match self.sor_config.search_algorithm {
+            SearchAlgorithm::Exhaustive => {
+                find_all_paths(&self.graph, src_vertex, dest_vertex, config)
+            }
+            SearchAlgorithm::Dijkstra => {
+                find_k_best_amount_paths(&self.graph, src_vertex, dest_vertex, amount_in, config, 1)
+            }
+            SearchAlgorithm::YenKPaths { k } => {
+                find_k_best_amount_paths(&self.graph, src_vertex, dest_vertex, amount_in, config, k)
+            }
+            SearchAlgorithm::Beam {
+                beam_width,
+                max_expansions_per_vertex,
+            } => find_beam_search_paths(
+                &self.graph,
+                src_vertex,
+                dest_vertex,
+                amount_in,
+                config,
+                beam_width,
+                max_expansions_per_vertex,
+            ),
+        };
+        // Pivot paths can repeat ones we already have, which is harmless: we only keep the best
+        paths.extend(find_pivot_paths(
             &self.graph,
             src_vertex,
             dest_vertex,
-            &self.sor_config.all_paths_finder_config,
-        );
-        let optimal_path = paths
+            &self.sor_config.pivot_tokens,
+            config,
+            amount_in,
+        ));
+        let (optimal_path, quote) = paths
             .into_iter()
-            .max_by_key(|path| path.get_quote_with_estimated_txn_fees(amount_in))
+            .map(|path| {
+                let quote = path.get_quote_with_estimated_txn_fees(amount_in);
+                (path, quote)
+            })
+            .max_by_key(|(_, quote)| *quote)
             .ok_or(PublicError::NoPathFound)?;
+        // Dust: a plan for it would only burn gas
+        if quote == 0 {
+            return Err(PublicError::AmountInDoesNotCoverFees);
+        }
 
         Ok(GraphPath::from(optimal_path))
     }
@@ -111,7 +207,7 @@ impl<'a> SinglePathSOR<'a> {
 #[cfg(test)]
 mod single_path_sor_tests {
     use hex_literal::hex;
-    use ink_env::debug_println;
+    use ink::env::debug_println;
 
     use privadex_chain_metadata::common::{
         ChainTokenId::ERC20, ERC20Token, EthAddress, UniversalChainId::SubstrateParachain,
@@ -190,6 +286,148 @@ mod single_path_sor_tests {
         assert!(graph_solution.paths[0].path.0.len() > 0);
     }
 
+    #[test]
+    fn test_sor_pivot_tokens() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let amount_in = 100_000_000_000_000_000_000;
+        let quote = |sor_config: SORConfig| {
+            SinglePathSOR::new(
+                &graph,
+                DUMMY_ADDR,
+                DUMMY_ADDR,
+                universal_token_id_registry::GLMR_NATIVE,
+                universal_token_id_registry::DOT_NATIVE,
+                sor_config,
+            )
+            .compute_graph_solution(amount_in)
+            .expect("We expect a solution")
+            .get_quote_with_estimated_txn_fees()
+        };
+        // Pivot paths only add candidates, so they never make the quote worse
+        let without_pivots = quote(SORConfig::default().with_pivot_tokens(Vec::new()));
+        let with_pivots = quote(SORConfig::default());
+        assert!(with_pivots >= without_pivots);
+
+        // The bounded searches find the only route in the small graph too
+        for search_algorithm in [
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::YenKPaths { k: 3 },
+            SearchAlgorithm::Beam {
+                beam_width: 4,
+                max_expansions_per_vertex: 2,
+            },
+        ] {
+            let sor_config = SORConfig::default()
+                .with_pivot_tokens(Vec::new())
+                .with_search_algorithm(search_algorithm);
+            assert_eq!(quote(sor_config), without_pivots);
+        }
+    }
+
+    #[test]
+    fn test_sor_edge_cases() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let sor = |src_token_id: UniversalTokenId,
+                   dest_token_id: UniversalTokenId,
+                   sor_config: SORConfig| {
+            SinglePathSOR::new(
+                &graph,
+                DUMMY_ADDR,
+                DUMMY_ADDR,
+                src_token_id,
+                dest_token_id,
+                sor_config,
+            )
+        };
+        let glmr_to_dot = sor(
+            universal_token_id_registry::GLMR_NATIVE,
+            universal_token_id_registry::DOT_NATIVE,
+            SORConfig::default(),
+        );
+        assert_eq!(
+            glmr_to_dot.compute_graph_solution(0).err(),
+            Some(PublicError::ZeroAmountIn)
+        );
+        assert_eq!(
+            glmr_to_dot.compute_graph_solution(1).err(),
+            Some(PublicError::AmountInDoesNotCoverFees)
+        );
+        assert_eq!(
+            sor(
+                universal_token_id_registry::GLMR_NATIVE,
+                universal_token_id_registry::GLMR_NATIVE,
+                SORConfig::default()
+            )
+            .compute_graph_solution(100_000_000_000_000_000_000)
+            .err(),
+            Some(PublicError::SrcTokenDestTokenAreSame)
+        );
+    }
+
+    #[test]
+    fn test_sor_transfer_only() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::small_graph();
+        let amount_in = 100_000_000_000;
+
+        let graph_solution = SinglePathSOR::new(
+            &graph,
+            DUMMY_ADDR,
+            DUMMY_ADDR,
+            universal_token_id_registry::DOT_NATIVE,
+            universal_token_id_registry::DOT_MOONBEAM,
+            SORConfig::transfer_only(),
+        )
+        .compute_graph_solution(amount_in)
+        .expect("DOT bridges to Moonbeam");
+        assert!(graph_solution.paths[0]
+            .path
+            .0
+            .iter()
+            .all(|edge| edge.is_bridge()));
+
+        // Wrapping is 1:1, so it is allowed too
+        let graph_solution = SinglePathSOR::new(
+            &graph,
+            DUMMY_ADDR,
+            DUMMY_ADDR,
+            universal_token_id_registry::GLMR_NATIVE,
+            UniversalTokenId {
+                chain: SubstrateParachain(Polkadot, 2004),
+                id: ERC20(ERC20Token {
+                    addr: EthAddress {
+                        0: hex!("acc15dc74880c9944775448304b263d191c6077f"),
+                    },
+                }),
+            },
+            SORConfig::transfer_only(),
+        )
+        .compute_graph_solution(100_000_000_000_000_000_000)
+        .expect("GLMR wraps to WGLMR");
+        assert!(graph_solution.paths[0]
+            .path
+            .0
+            .iter()
+            .all(|edge| !edge.is_dex_swap()));
+
+        // GLMR and DOT are different assets, so they need a swap
+        assert_eq!(
+            SinglePathSOR::new(
+                &graph,
+                DUMMY_ADDR,
+                DUMMY_ADDR,
+                universal_token_id_registry::GLMR_NATIVE,
+                universal_token_id_registry::DOT_NATIVE,
+                SORConfig::transfer_only(),
+            )
+            .compute_graph_solution(100_000_000_000_000_000_000)
+            .err(),
+            Some(PublicError::NoPathFound)
+        );
+    }
+
     #[test]
     fn test_sor_medium_graph() {
         pink_extension_runtime::mock_ext::mock_all_ext();
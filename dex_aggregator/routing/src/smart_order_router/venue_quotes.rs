@@ -0,0 +1,130 @@
+/*
+ * Copyright (C) 2023-present Kapil Sinha
+ * Company: PrivaDEX
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the Server Side Public License, version 1,
+ * as published by MongoDB, Inc.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * Server Side Public License for more details.
+ *
+ * You should have received a copy of the Server Side Public License
+ * along with this program. If not, see
+ * <http://www.mongodb.com/licensing/server-side-public-license>.
+ */
+
+// What the user would get by routing through each DEX alone, so the frontend can show how much
+// the aggregated route saves. Bridges, Wraps and Unwraps are allowed around the pools, but every
+// pool on the path has to belong to the same DEX
+use ink::prelude::vec::Vec;
+
+use privadex_chain_metadata::{
+    common::{Amount, UniversalTokenId},
+    registry::dex::DexId,
+};
+
+use super::helper_graph_algos::{find_all_paths, AllPathsFinderConfig};
+use crate::graph::{
+    edge::{Edge, SwapEdge},
+    graph::{Graph, GraphPathRef},
+    traits::QuoteGetter,
+};
+use crate::{PublicError, Result};
+
+// The best quote (net of estimated txn fees) per DEX, best first. DEXes without a single-venue
+// path are left out, e.g. a cross-chain pair that needs a swap on both chains has no entries
+pub fn find_venue_quotes(
+    graph: &Graph,
+    src_token_id: &UniversalTokenId,
+    dest_token_id: &UniversalTokenId,
+    amount_in: Amount,
+) -> Result<Vec<(DexId, Amount)>> {
+    if src_token_id == dest_token_id {
+        return Err(PublicError::SrcTokenDestTokenAreSame);
+    }
+    let src_vertex = graph
+        .get_vertex(src_token_id)
+        .ok_or(PublicError::VertexNotInGraph(src_token_id.clone()))?;
+    let dest_vertex = graph
+        .get_vertex(dest_token_id)
+        .ok_or(PublicError::VertexNotInGraph(dest_token_id.clone()))?;
+    let mut venue_quotes: Vec<(DexId, Amount)> = Vec::new();
+    for path in find_all_paths(
+        graph,
+        src_vertex,
+        dest_vertex,
+        &AllPathsFinderConfig::default(),
+    ) {
+        let dex_id = match get_single_venue(&path) {
+            Some(dex_id) => dex_id,
+            None => continue,
+        };
+        let quote = path.get_quote_with_estimated_txn_fees(amount_in);
+        match venue_quotes.iter_mut().find(|(id, _)| *id == dex_id) {
+            Some((_, best_quote)) => *best_quote = (*best_quote).max(quote),
+            None => venue_quotes.push((dex_id, quote)),
+        }
+    }
+    venue_quotes.sort_by(|(_, quote1), (_, quote2)| quote2.cmp(quote1));
+    Ok(venue_quotes)
+}
+
+// None if the path has no pools or its pools span several DEXes
+fn get_single_venue(path: &GraphPathRef) -> Option<DexId> {
+    let mut dex_ids = path.0.iter().filter_map(|edge| match edge {
+        Edge::Swap(SwapEdge::CPMM(cpmm_edge)) => Some(cpmm_edge.dex.id),
+        _ => None,
+    });
+    let dex_id = dex_ids.next()?;
+    if dex_ids.all(|other_dex_id| other_dex_id == dex_id) {
+        Some(dex_id)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod venue_quotes_tests {
+    use privadex_chain_metadata::{
+        common::EthAddress, registry::token::universal_token_id_registry,
+    };
+
+    use super::*;
+    use crate::smart_order_router::single_path_sor::{SORConfig, SinglePathSOR};
+    use crate::test_utilities::graph_factory;
+
+    #[test]
+    fn test_venue_quotes() {
+        pink_extension_runtime::mock_ext::mock_all_ext();
+        let graph = graph_factory::medium_graph();
+        let amount_in = 100_000_000_000_000_000_000;
+
+        let venue_quotes = find_venue_quotes(
+            &graph,
+            &universal_token_id_registry::GLMR_NATIVE,
+            &universal_token_id_registry::DOT_MOONBEAM,
+            amount_in,
+        )
+        .unwrap();
+        assert!(!venue_quotes.is_empty());
+        assert!(venue_quotes
+            .windows(2)
+            .all(|pair| pair[0].1 >= pair[1].1 && pair[0].0 != pair[1].0));
+
+        // The SOR picks from a superset of these paths, so it can't do worse than any one DEX
+        let graph_solution = SinglePathSOR::new(
+            &graph,
+            EthAddress::zero(),
+            EthAddress::zero(),
+            universal_token_id_registry::GLMR_NATIVE,
+            universal_token_id_registry::DOT_MOONBEAM,
+            SORConfig::default(),
+        )
+        .compute_graph_solution(amount_in)
+        .unwrap();
+        assert!(graph_solution.get_quote_with_estimated_txn_fees() >= venue_quotes[0].1);
+    }
+}
@@ -17,7 +17,7 @@
  */
 
 use hex_literal::hex;
-use ink_prelude::vec::Vec;
+use ink::prelude::vec::Vec;
 
 use privadex_chain_metadata::{
     common::{
@@ -109,13 +109,13 @@ pub fn full_graph() -> Graph {
     // and then some manual regexes to get them into the below form. 
 
     let arthswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, derived_eth: DecimalFixedPoint { coef: 8141836392, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 36269845667262000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, derived_eth: DecimalFixedPoint { coef: 1000000000, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4454750000000000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, derived_eth: DecimalFixedPoint { coef: 993255845, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4424706475513750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, derived_eth: DecimalFixedPoint { coef: 34457466433157, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 153499398593106145750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }) }, derived_eth: DecimalFixedPoint { coef: 22526593222, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 100350341155704500, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, derived_eth: DecimalFixedPoint { coef: 22534258406, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 100384487634128500, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, derived_eth: DecimalFixedPoint { coef: 22257725101, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 99152600893679750, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, derived_eth: DecimalFixedPoint { coef: 136012566022, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 605901978486504500, exp: -27 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }) }, derived_eth: DecimalFixedPoint { coef: 22522530057, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 100332240771420750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, derived_eth: DecimalFixedPoint { coef: 22507158132, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 100263762688527000, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }) }, derived_eth: DecimalFixedPoint { coef: 43554861, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 194026017039750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }) }, derived_eth: DecimalFixedPoint { coef: 468234460678484, exp: 1 }, derived_usd: DecimalFixedPoint { coef: 2085867463707476599000, exp: -25 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, derived_eth: DecimalFixedPoint { coef: 67882622, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 302400110354500, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, derived_eth: DecimalFixedPoint { coef: 22410454271, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 99832971163737250, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }) }, derived_eth: DecimalFixedPoint { coef: 174595173, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 777777846921750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }) }, derived_eth: DecimalFixedPoint { coef: 11082414, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 49369383766500, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }) }, derived_eth: DecimalFixedPoint { coef: 465563497022501, exp: 1 }, derived_usd: DecimalFixedPoint { coef: 2073968988360986329750, exp: -25 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }) }, derived_eth: DecimalFixedPoint { coef: 1144892264, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 5100208813054000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }) }, derived_eth: DecimalFixedPoint { coef: 19487293617, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 86811021240330750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }) }, derived_eth: DecimalFixedPoint { coef: 14453842978, exp: -3 }, derived_usd: DecimalFixedPoint { coef: 64388257006245500, exp: -29 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }) }, derived_eth: DecimalFixedPoint { coef: 2751360582, exp: -3 }, derived_usd: DecimalFixedPoint { coef: 12256623552664500, exp: -29 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }) }, derived_eth: DecimalFixedPoint { coef: 21658295655, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 96482292569111250, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(18446744073709551619)) }, derived_eth: DecimalFixedPoint { coef: 8878101497, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 39549722643760750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }) }, derived_eth: DecimalFixedPoint { coef: 6855457518227, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 30539349379321728250, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }) }, derived_eth: DecimalFixedPoint { coef: 44011601, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 196060679554750, exp: -35 } }];
-    let arthswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, estimated_gas_fee_in_dest_token: 36846724197844, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, estimated_gas_fee_in_dest_token: 302036984237429, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, estimated_gas_fee_in_dest_token: 8706385902, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 460269194369160000000000, reserve1: 460892635135, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("eee106aa8a0de519e8eb21c66a5c2275b46b3f4d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 460269194369160000000000, reserve1: 460892635135, estimated_gas_fee_in_dest_token: 13317592990803, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("eee106aa8a0de519e8eb21c66a5c2275b46b3f4d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 7532129188659100000000000, reserve1: 335533747900, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ddea1b3343c438c2e2d636d070cfb4f63d26636e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 7532129188659100000000000, reserve1: 335533747900, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ddea1b3343c438c2e2d636d070cfb4f63d26636e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 7502711950317520000000000, reserve1: 551619028280600, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("40e938688a121370092a06745704c112c5ee5791")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 7502711950317520000000000, reserve1: 551619028280600, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("40e938688a121370092a06745704c112c5ee5791")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311698687000, reserve1: 6996995296315690000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("bb1290c1829007f440c771b37718fabf309cd527")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311698687000, reserve1: 6996995296315690000000000, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("bb1290c1829007f440c771b37718fabf309cd527")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }), reserve0: 311299994558, reserve1: 311930893879520000000000, estimated_gas_fee_in_dest_token: 13319995544051, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("996d73ac8f97cf15bd476b77cb92ce47ca0e71fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }), reserve0: 311299994558, reserve1: 311930893879520000000000, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("996d73ac8f97cf15bd476b77cb92ce47ca0e71fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 245964013167, reserve1: 246565338743, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("d72a602c714ae36d990dc835ea5f96ef87657d5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 245964013167, reserve1: 246565338743, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("d72a602c714ae36d990dc835ea5f96ef87657d5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 221708994848, reserve1: 4975568131346360000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("806f746a7c4293092ac7aa604347be123322df1e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 221708994848, reserve1: 4975568131346360000000000, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("806f746a7c4293092ac7aa604347be123322df1e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }), reserve0: 3759591361051080000000000, reserve1: 85489683698874270000000000, estimated_gas_fee_in_dest_token: 6887864938887073, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("78d5c2adeb11be00033cc4edb2c2889cf945415e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }), reserve0: 3759591361051080000000000, reserve1: 85489683698874270000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("78d5c2adeb11be00033cc4edb2c2889cf945415e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 723653738, reserve1: 3367257715611650000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("61a49ba86e168cd25ca795b07b0a93236bb25127")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 723653738, reserve1: 3367257715611650000000000, estimated_gas_fee_in_dest_token: 0, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("61a49ba86e168cd25ca795b07b0a93236bb25127")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 2272222577747090000000000, reserve1: 33472816754848070000000000, estimated_gas_fee_in_dest_token: 4419393228505522, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("50497e7181eb9e8ccd70a9c44fb997742149482a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 2272222577747090000000000, reserve1: 33472816754848070000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("50497e7181eb9e8ccd70a9c44fb997742149482a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), reserve0: 89691803571, reserve1: 90423771344460000000000, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("7644bf8086d40ed430d5096305830aa97be77268")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), reserve0: 89691803571, reserve1: 90423771344460000000000, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("7644bf8086d40ed430d5096305830aa97be77268")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 4588488589888490000000000, reserve1: 801127961635130000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f041a8e6e27341f5f865a22f01fa37e065c32156")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 4588488589888490000000000, reserve1: 801127961635130000000000, estimated_gas_fee_in_dest_token: 1718260561533393, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f041a8e6e27341f5f865a22f01fa37e065c32156")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 29908511596, reserve1: 49623839364200, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f4119c3d9e65602bb34f2455644e45c98d29bb4b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 29908511596, reserve1: 49623839364200, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f4119c3d9e65602bb34f2455644e45c98d29bb4b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 41072624314640010000000000, reserve1: 455183866400880000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("de2edaa0cd4afd59d9618c31a060eab93ce45e01")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 41072624314640010000000000, reserve1: 455183866400880000000000, estimated_gas_fee_in_dest_token: 27069914551107727, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("de2edaa0cd4afd59d9618c31a060eab93ce45e01")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 96662342, reserve1: 32504940855300, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8ebeb1b508b9632f14bfb074a7ef0db9488d28d3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 96662342, reserve1: 32504940855300, estimated_gas_fee_in_dest_token: 0, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8ebeb1b508b9632f14bfb074a7ef0db9488d28d3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311935957887050000000000, reserve1: 357133065196500000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ef8b14e08c292cc552494ec428a75c8a3cd417b6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311935957887050000000000, reserve1: 357133065196500000000000, estimated_gas_fee_in_dest_token: 262033389021135, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ef8b14e08c292cc552494ec428a75c8a3cd417b6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 18409046870900000000000, reserve1: 15924234925, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("cf83a3d83c1265780d9374e8a7c838fe22bd3dc6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 18409046870900000000000, reserve1: 15924234925, estimated_gas_fee_in_dest_token: 15394646680865, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("cf83a3d83c1265780d9374e8a7c838fe22bd3dc6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 13834394524230000000000, reserve1: 310065223132700000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("3d78a6cca5c717c0e8702896892f3522d0b07010")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 13834394524230000000000, reserve1: 310065223132700000000000, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("3d78a6cca5c717c0e8702896892f3522d0b07010")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }), reserve0: 305371432494300000000000, reserve1: 21032853239570000, estimated_gas_fee_in_dest_token: 20755725, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("73eea1180c2d1772ea2118fda888a81943bac3c8")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }), reserve0: 305371432494300000000000, reserve1: 21032853239570000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("73eea1180c2d1772ea2118fda888a81943bac3c8")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }), reserve0: 193500881447420000000000, reserve1: 70329161031560000, estimated_gas_fee_in_dest_token: 109036962, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("49d1db92a8a1511a6eeb867221d801bc974a3073")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }), reserve0: 193500881447420000000000, reserve1: 70329161031560000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("49d1db92a8a1511a6eeb867221d801bc974a3073")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }), reserve0: 173362045356660000000000, reserve1: 7931726090890000000000, estimated_gas_fee_in_dest_token: 13851505436012, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ca59df939290421047876c917789afdb68d5d6f1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }), reserve0: 173362045356660000000000, reserve1: 7931726090890000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ca59df939290421047876c917789afdb68d5d6f1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(18446744073709551619)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(18446744073709551619)), reserve0: 140672757152900000000000, reserve1: 15844914274410000000000, estimated_gas_fee_in_dest_token: 33791008145308, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("4d0c348742d5f60baacfebffd2d80a3adfa3f0fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(18446744073709551619)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(18446744073709551619)), reserve0: 140672757152900000000000, reserve1: 15844914274410000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("4d0c348742d5f60baacfebffd2d80a3adfa3f0fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 16309478300000000000, reserve1: 111192101481210000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("92127ec0ebef8b30378d757bbe8dce18210b848b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 16309478300000000000, reserve1: 111192101481210000000000, estimated_gas_fee_in_dest_token: 43760755456, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("92127ec0ebef8b30378d757bbe8dce18210b848b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 4797499109, reserve1: 4800835199, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("900e71a3745cb660aae9e351ff665c081f1a1ea4")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 4797499109, reserve1: 4800835199, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("900e71a3745cb660aae9e351ff665c081f1a1ea4")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 3674461571610000000000, reserve1: 1213069710769580000000000, estimated_gas_fee_in_dest_token: 4419393228505522, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8897d79334c2d517b83e7846da4b922e68fda61b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 3674461571610000000000, reserve1: 1213069710769580000000000, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8897d79334c2d517b83e7846da4b922e68fda61b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 1732172514919710000000000, reserve1: 76235685903050000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("9c728cb130ed60eebaf84e6b260d369fa6415f5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 1732172514919710000000000, reserve1: 76235685903050000000000, estimated_gas_fee_in_dest_token: 6816384616410568, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("9c728cb130ed60eebaf84e6b260d369fa6415f5e")} }];
+    let arthswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 36846724197844, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 302036984237429, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 8706385902, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 460269194369160000000000, reserve1: 460892635135, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("eee106aa8a0de519e8eb21c66a5c2275b46b3f4d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4bf769b05e832fcdc9053fffbc78ca889acb5e1e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 460269194369160000000000, reserve1: 460892635135, fee_bps: 30, estimated_gas_fee_in_dest_token: 13317592990803, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("eee106aa8a0de519e8eb21c66a5c2275b46b3f4d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 7532129188659100000000000, reserve1: 335533747900, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ddea1b3343c438c2e2d636d070cfb4f63d26636e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 7532129188659100000000000, reserve1: 335533747900, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ddea1b3343c438c2e2d636d070cfb4f63d26636e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 7502711950317520000000000, reserve1: 551619028280600, fee_bps: 30, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("40e938688a121370092a06745704c112c5ee5791")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 7502711950317520000000000, reserve1: 551619028280600, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("40e938688a121370092a06745704c112c5ee5791")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311698687000, reserve1: 6996995296315690000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("bb1290c1829007f440c771b37718fabf309cd527")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311698687000, reserve1: 6996995296315690000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("bb1290c1829007f440c771b37718fabf309cd527")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }), reserve0: 311299994558, reserve1: 311930893879520000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13319995544051, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("996d73ac8f97cf15bd476b77cb92ce47ca0e71fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6de33698e9e9b787e09d3bd7771ef63557e148bb")} }), reserve0: 311299994558, reserve1: 311930893879520000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("996d73ac8f97cf15bd476b77cb92ce47ca0e71fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 245964013167, reserve1: 246565338743, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("d72a602c714ae36d990dc835ea5f96ef87657d5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 245964013167, reserve1: 246565338743, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("d72a602c714ae36d990dc835ea5f96ef87657d5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 221708994848, reserve1: 4975568131346360000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("806f746a7c4293092ac7aa604347be123322df1e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 221708994848, reserve1: 4975568131346360000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("806f746a7c4293092ac7aa604347be123322df1e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }), reserve0: 3759591361051080000000000, reserve1: 85489683698874270000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 6887864938887073, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("78d5c2adeb11be00033cc4edb2c2889cf945415e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c4335b1b76fa6d52877b3046eca68f6e708a27dd")} }), reserve0: 3759591361051080000000000, reserve1: 85489683698874270000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("78d5c2adeb11be00033cc4edb2c2889cf945415e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 723653738, reserve1: 3367257715611650000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("61a49ba86e168cd25ca795b07b0a93236bb25127")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ad543f18cff85c77e140e3e5e3c3392f6ba9d5ca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 723653738, reserve1: 3367257715611650000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 0, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("61a49ba86e168cd25ca795b07b0a93236bb25127")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 2272222577747090000000000, reserve1: 33472816754848070000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4419393228505522, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("50497e7181eb9e8ccd70a9c44fb997742149482a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 2272222577747090000000000, reserve1: 33472816754848070000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("50497e7181eb9e8ccd70a9c44fb997742149482a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), reserve0: 89691803571, reserve1: 90423771344460000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("7644bf8086d40ed430d5096305830aa97be77268")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), reserve0: 89691803571, reserve1: 90423771344460000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("7644bf8086d40ed430d5096305830aa97be77268")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 4588488589888490000000000, reserve1: 801127961635130000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f041a8e6e27341f5f865a22f01fa37e065c32156")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("431d5dff03120afa4bdf332c61a6e1766ef37bdb")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 4588488589888490000000000, reserve1: 801127961635130000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 1718260561533393, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f041a8e6e27341f5f865a22f01fa37e065c32156")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 29908511596, reserve1: 49623839364200, fee_bps: 30, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f4119c3d9e65602bb34f2455644e45c98d29bb4b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 29908511596, reserve1: 49623839364200, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("f4119c3d9e65602bb34f2455644e45c98d29bb4b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 41072624314640010000000000, reserve1: 455183866400880000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("de2edaa0cd4afd59d9618c31a060eab93ce45e01")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9448610696659de8f72e1831d392214ae1ca4838")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 41072624314640010000000000, reserve1: 455183866400880000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 27069914551107727, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("de2edaa0cd4afd59d9618c31a060eab93ce45e01")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 96662342, reserve1: 32504940855300, fee_bps: 30, estimated_gas_fee_in_dest_token: 22056, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8ebeb1b508b9632f14bfb074a7ef0db9488d28d3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000004")} }), token1: XC20(XC20Token::from_asset_id(340282366920938463463374607431768211455)), reserve0: 96662342, reserve1: 32504940855300, fee_bps: 30, estimated_gas_fee_in_dest_token: 0, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8ebeb1b508b9632f14bfb074a7ef0db9488d28d3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311935957887050000000000, reserve1: 357133065196500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ef8b14e08c292cc552494ec428a75c8a3cd417b6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("6df98e5fbff3041105cb986b9d44c572a43fcd22")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 311935957887050000000000, reserve1: 357133065196500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 262033389021135, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ef8b14e08c292cc552494ec428a75c8a3cd417b6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 18409046870900000000000, reserve1: 15924234925, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("cf83a3d83c1265780d9374e8a7c838fe22bd3dc6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("29f6e49c6e3397c3a84f715885f9f233a441165c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("6a2d262d56735dba19dd70682b39f6be9a931d98")} }), reserve0: 18409046870900000000000, reserve1: 15924234925, fee_bps: 30, estimated_gas_fee_in_dest_token: 15394646680865, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("cf83a3d83c1265780d9374e8a7c838fe22bd3dc6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 13834394524230000000000, reserve1: 310065223132700000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("3d78a6cca5c717c0e8702896892f3522d0b07010")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 13834394524230000000000, reserve1: 310065223132700000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("3d78a6cca5c717c0e8702896892f3522d0b07010")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }), reserve0: 305371432494300000000000, reserve1: 21032853239570000, fee_bps: 30, estimated_gas_fee_in_dest_token: 20755725, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("73eea1180c2d1772ea2118fda888a81943bac3c8")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000001")} }), reserve0: 305371432494300000000000, reserve1: 21032853239570000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("73eea1180c2d1772ea2118fda888a81943bac3c8")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }), reserve0: 193500881447420000000000, reserve1: 70329161031560000, fee_bps: 30, estimated_gas_fee_in_dest_token: 109036962, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("49d1db92a8a1511a6eeb867221d801bc974a3073")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff00000000000000010000000000000000")} }), reserve0: 193500881447420000000000, reserve1: 70329161031560000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("49d1db92a8a1511a6eeb867221d801bc974a3073")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }), reserve0: 173362045356660000000000, reserve1: 7931726090890000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13851505436012, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ca59df939290421047876c917789afdb68d5d6f1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("dd90e5e87a2081dcf0391920868ebc2ffb81a1af")} }), reserve0: 173362045356660000000000, reserve1: 7931726090890000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ca59df939290421047876c917789afdb68d5d6f1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(18446744073709551619)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(18446744073709551619)), reserve0: 140672757152900000000000, reserve1: 15844914274410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 33791008145308, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("4d0c348742d5f60baacfebffd2d80a3adfa3f0fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(18446744073709551619)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: XC20(XC20Token::from_asset_id(18446744073709551619)), reserve0: 140672757152900000000000, reserve1: 15844914274410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("4d0c348742d5f60baacfebffd2d80a3adfa3f0fe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 16309478300000000000, reserve1: 111192101481210000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("92127ec0ebef8b30378d757bbe8dce18210b848b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("7f27352d5f83db87a5a3e00f4b07cc2138d8ee52")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 16309478300000000000, reserve1: 111192101481210000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 43760755456, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("92127ec0ebef8b30378d757bbe8dce18210b848b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 4797499109, reserve1: 4800835199, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("900e71a3745cb660aae9e351ff665c081f1a1ea4")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: XC20(XC20Token::from_asset_id(4294969280)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3795c36e7d12a8c252a20c5a7b455f7c57b60283")} }), token1: XC20(XC20Token::from_asset_id(4294969280)), reserve0: 4797499109, reserve1: 4800835199, fee_bps: 30, estimated_gas_fee_in_dest_token: 13, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("900e71a3745cb660aae9e351ff665c081f1a1ea4")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 3674461571610000000000, reserve1: 1213069710769580000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4419393228505522, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8897d79334c2d517b83e7846da4b922e68fda61b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("733ebcc6df85f8266349defd0980f8ced9b45f35")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("de2578edec4669ba7f41c5d5d2386300bcea4678")} }), reserve0: 3674461571610000000000, reserve1: 1213069710769580000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 13386609498059, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("8897d79334c2d517b83e7846da4b922e68fda61b")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 1732172514919710000000000, reserve1: 76235685903050000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("9c728cb130ed60eebaf84e6b260d369fa6415f5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("5271d85ce4241b310c0b34b7c2f1f036686a6d7c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 1732172514919710000000000, reserve1: 76235685903050000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 6816384616410568, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("9c728cb130ed60eebaf84e6b260d369fa6415f5e")} }];
 
     let stellaswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, derived_eth: DecimalFixedPoint { coef: 1000000000, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 39278528000000000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, derived_eth: DecimalFixedPoint { coef: 15328371810, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 602075881333495680, exp: -27 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, derived_eth: DecimalFixedPoint { coef: 52534910399448, exp: 1 }, derived_usd: DecimalFixedPoint { coef: 2063493949102209452544, exp: -25 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, derived_eth: DecimalFixedPoint { coef: 2545920245, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 99999999628999360, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, derived_eth: DecimalFixedPoint { coef: 3886571180060, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 152658794919979751680, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, derived_eth: DecimalFixedPoint { coef: 19109649101, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 750598887283803328, exp: -27 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, derived_eth: DecimalFixedPoint { coef: 426446466, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 16750189455282048, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }) }, derived_eth: DecimalFixedPoint { coef: 8473928, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 332843418217984, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, derived_eth: DecimalFixedPoint { coef: 123800809, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4862713542729152, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }) }, derived_eth: DecimalFixedPoint { coef: 1644868898, exp: -3 }, derived_usd: DecimalFixedPoint { coef: 64608029066422144, exp: -29 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, derived_eth: DecimalFixedPoint { coef: 52157717941092, exp: 1 }, derived_usd: DecimalFixedPoint { coef: 2048678384565284472576, exp: -25 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }) }, derived_eth: DecimalFixedPoint { coef: 381706217, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 14992858332208576, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }) }, derived_eth: DecimalFixedPoint { coef: 59853557, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 2350959614524096, exp: -27 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }) }, derived_eth: DecimalFixedPoint { coef: 5238291649, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 205752385207412672, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }) }, derived_eth: DecimalFixedPoint { coef: 2493703141, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 97948988647456448, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, derived_eth: DecimalFixedPoint { coef: 2522305590, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 99072450741371520, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, derived_eth: DecimalFixedPoint { coef: 32355867377, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 1270890842731781056, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, derived_eth: DecimalFixedPoint { coef: 784773686247, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 30824755208916004416, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }) }, derived_eth: DecimalFixedPoint { coef: 42871075285, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 1683912730971980480, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, derived_eth: DecimalFixedPoint { coef: 2594418432, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 101904937025028096, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, derived_eth: DecimalFixedPoint { coef: 844593466, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 33174388102898048, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, derived_eth: DecimalFixedPoint { coef: 3848747683058, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 151173143633928778624, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, derived_eth: DecimalFixedPoint { coef: 2534404701, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 99547686011560128, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, derived_eth: DecimalFixedPoint { coef: 2519223748, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 98951400524082944, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }) }, derived_eth: DecimalFixedPoint { coef: 214483347904, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 8424590186181005312, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }) }, derived_eth: DecimalFixedPoint { coef: 21111317, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 829221455901376, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, derived_eth: DecimalFixedPoint { coef: 121527552, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4773423354003456, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }) }, derived_eth: DecimalFixedPoint { coef: 61248168, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 2405737881736704, exp: -23 } }];
-    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, estimated_gas_fee_in_dest_token: 4713, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, estimated_gas_fee_in_dest_token: 3087554413403, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, estimated_gas_fee_in_dest_token: 6279550, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 979693423549, reserve1: 417786799169920000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9bfcf685e641206115dadc0c9ab17181e1d4975c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 979693423549, reserve1: 417786799169920000000000, estimated_gas_fee_in_dest_token: 28139, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9bfcf685e641206115dadc0c9ab17181e1d4975c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 45447916160813520000000000, reserve1: 381446707612620000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b536c1f9a157b263b70a9a35705168acc0271742")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 45447916160813520000000000, reserve1: 381446707612620000000000, estimated_gas_fee_in_dest_token: 1416108326622553318, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b536c1f9a157b263b70a9a35705168acc0271742")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 2605847131760520000000000, reserve1: 322605985147240000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("7f5ac0fc127bcf1eaf54e3cd01b00300a0861a62")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 2605847131760520000000000, reserve1: 322605985147240000000000, estimated_gas_fee_in_dest_token: 96929899706875098, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("7f5ac0fc127bcf1eaf54e3cd01b00300a0861a62")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }), reserve0: 281730393489370000000000, reserve1: 171278327264500000, estimated_gas_fee_in_dest_token: 7295414251, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d95cab0ed89269390f2ad121798e6092ea395139")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }), reserve0: 281730393489370000000000, reserve1: 171278327264500000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d95cab0ed89269390f2ad121798e6092ea395139")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 143270956790770000000000, reserve1: 274687932, estimated_gas_fee_in_dest_token: 23, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4c5f99045af91d2b6d4fa0ea89fc47cf42711555")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 143270956790770000000000, reserve1: 274687932, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4c5f99045af91d2b6d4fa0ea89fc47cf42711555")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }), reserve0: 92325167783790000000000, reserve1: 244060232130910000000000, estimated_gas_fee_in_dest_token: 31437790283620138, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("420aaa13722a191765d86bc55212a54d9f8b5ceb")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }), reserve0: 92325167783790000000000, reserve1: 244060232130910000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("420aaa13722a191765d86bc55212a54d9f8b5ceb")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }), reserve0: 93890088992620000000000, reserve1: 15686634761155600, estimated_gas_fee_in_dest_token: 2004893376, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d3dfb90f7996a97f9f394e130342485e37dd28f7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }), reserve0: 93890088992620000000000, reserve1: 15686634761155600, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d3dfb90f7996a97f9f394e130342485e37dd28f7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 16508963555390000000000, reserve1: 86478765935600000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("00870b0e6994ffb142a91173a882d2f6a9a8ac4a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 16508963555390000000000, reserve1: 86478765935600000000000, estimated_gas_fee_in_dest_token: 2290823192765684, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("00870b0e6994ffb142a91173a882d2f6a9a8ac4a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33893760348150000000000, reserve1: 84605150899130000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6775ff3828a19eb5926c0c4d572183ca15aa4c08")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33893760348150000000000, reserve1: 84605150899130000000000, estimated_gas_fee_in_dest_token: 4812120497706025, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6775ff3828a19eb5926c0c4d572183ca15aa4c08")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33454057268, reserve1: 84726835696550000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("555b74dafc4ef3a5a1640041e3244460dc7610d1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33454057268, reserve1: 84726835696550000000000, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("555b74dafc4ef3a5a1640041e3244460dc7610d1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2041897299, reserve1: 26014487108, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("051fcf8986b30860a1341e0031e5622bd18d8a85")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2041897299, reserve1: 26014487108, estimated_gas_fee_in_dest_token: 370, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("051fcf8986b30860a1341e0031e5622bd18d8a85")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1702141315, reserve1: 55074258645550000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f4c10263f2a4b1f75b8a5fd5328fb61605321639")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1702141315, reserve1: 55074258645550000000000, estimated_gas_fee_in_dest_token: 370, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f4c10263f2a4b1f75b8a5fd5328fb61605321639")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 19956614944, reserve1: 65747212860000000000, estimated_gas_fee_in_dest_token: 15291032574483, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("ac2657ba28768fe5f09052f07a9b7ea867a4608f")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 19956614944, reserve1: 65747212860000000000, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("ac2657ba28768fe5f09052f07a9b7ea867a4608f")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1110807829140000000000, reserve1: 47371089993490000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2f412c140080039eaf9de01c95e84cfb1c8d98ad")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1110807829140000000000, reserve1: 47371089993490000000000, estimated_gas_fee_in_dest_token: 279909004386429, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2f412c140080039eaf9de01c95e84cfb1c8d98ad")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 17348091202420000000000, reserve1: 17273856853, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("5ced2f8dd70dc25cba10ad18c7543ad9ad5aeedd")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 17348091202420000000000, reserve1: 17273856853, estimated_gas_fee_in_dest_token: 4625314040322081, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("5ced2f8dd70dc25cba10ad18c7543ad9ad5aeedd")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 43637213857920000000000, reserve1: 51666530202300000000000, estimated_gas_fee_in_dest_token: 14208018985550617, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2afab635ea2dc4b498ef1c00e63b7a7dba9c93c6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 43637213857920000000000, reserve1: 51666530202300000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2afab635ea2dc4b498ef1c00e63b7a7dba9c93c6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 13987530735, reserve1: 9172302880000000000, estimated_gas_fee_in_dest_token: 3117897297560, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("0aa48bf937ee8f41f1a52d225ef5a6f6961e39fa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 13987530735, reserve1: 9172302880000000000, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("0aa48bf937ee8f41f1a52d225ef5a6f6961e39fa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 283855018784590000000000, reserve1: 13932285338, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("81e11a9374033d11cc7e7485a7192ae37d0795d6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 283855018784590000000000, reserve1: 13932285338, estimated_gas_fee_in_dest_token: 96929899706875098, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("81e11a9374033d11cc7e7485a7192ae37d0795d6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 32464282205010000000000, reserve1: 8435024810000000000, estimated_gas_fee_in_dest_token: 3117897297560, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("49a1cc58dcf28d0139daea9c18a3ca23108e78b3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 32464282205010000000000, reserve1: 8435024810000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("49a1cc58dcf28d0139daea9c18a3ca23108e78b3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 11981127714810000000000, reserve1: 30365026413830000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("367c36dae9ba198a4fee295c22bc98cb72f77fe1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 11981127714810000000000, reserve1: 30365026413830000000000, estimated_gas_fee_in_dest_token: 4734839702303724, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("367c36dae9ba198a4fee295c22bc98cb72f77fe1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 9323008608, reserve1: 9353562798, estimated_gas_fee_in_dest_token: 4763, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8bc3cceef43392b315ddd92ba30b435f79b66b9e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 9323008608, reserve1: 9353562798, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8bc3cceef43392b315ddd92ba30b435f79b66b9e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 101121342600000000000, reserve1: 21688844105940000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9d19edbfd29d2e01537624b25806493da0d73bbe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 101121342600000000000, reserve1: 21688844105940000000000, estimated_gas_fee_in_dest_token: 55948399338540, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9d19edbfd29d2e01537624b25806493da0d73bbe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 790858911501880000000000, reserve1: 16696073519070000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6f71389426efe6ff4e357ca6dd012b210a6a3c9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 790858911501880000000000, reserve1: 16696073519070000000000, estimated_gas_fee_in_dest_token: 568415509084535086, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6f71389426efe6ff4e357ca6dd012b210a6a3c9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 15707116003710000000000, reserve1: 129247366006010000000000, estimated_gas_fee_in_dest_token: 98743040590499181, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4efb208eeeb5a8c85af70e8fbc43d6806b422bec")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 15707116003710000000000, reserve1: 129247366006010000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4efb208eeeb5a8c85af70e8fbc43d6806b422bec")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 5156017466, reserve1: 15552864977390000000000, estimated_gas_fee_in_dest_token: 14208018985550617, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("077fc7b4455050249d7f5511ed588a665e03e6c5")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 5156017466, reserve1: 15552864977390000000000, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("077fc7b4455050249d7f5511ed588a665e03e6c5")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134083704542, reserve1: 8212381313780000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b6835036c9a2a96d3bfb8dbe1722971feb456a83")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134083704542, reserve1: 8212381313780000000000, estimated_gas_fee_in_dest_token: 195924, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b6835036c9a2a96d3bfb8dbe1722971feb456a83")} }];
+    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, fee_bps: 25, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4713, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 3087554413403, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, fee_bps: 25, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, fee_bps: 25, estimated_gas_fee_in_dest_token: 6279550, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 979693423549, reserve1: 417786799169920000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9bfcf685e641206115dadc0c9ab17181e1d4975c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 979693423549, reserve1: 417786799169920000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 28139, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9bfcf685e641206115dadc0c9ab17181e1d4975c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 45447916160813520000000000, reserve1: 381446707612620000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b536c1f9a157b263b70a9a35705168acc0271742")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("511ab53f793683763e5a8829738301368a2411e3")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 45447916160813520000000000, reserve1: 381446707612620000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 1416108326622553318, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b536c1f9a157b263b70a9a35705168acc0271742")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 2605847131760520000000000, reserve1: 322605985147240000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("7f5ac0fc127bcf1eaf54e3cd01b00300a0861a62")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 2605847131760520000000000, reserve1: 322605985147240000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 96929899706875098, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("7f5ac0fc127bcf1eaf54e3cd01b00300a0861a62")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }), reserve0: 281730393489370000000000, reserve1: 171278327264500000, fee_bps: 25, estimated_gas_fee_in_dest_token: 7295414251, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d95cab0ed89269390f2ad121798e6092ea395139")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff52c56a9257bb97f4b2b6f7b2d624ecda")} }), reserve0: 281730393489370000000000, reserve1: 171278327264500000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d95cab0ed89269390f2ad121798e6092ea395139")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 143270956790770000000000, reserve1: 274687932, fee_bps: 25, estimated_gas_fee_in_dest_token: 23, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4c5f99045af91d2b6d4fa0ea89fc47cf42711555")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 143270956790770000000000, reserve1: 274687932, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4c5f99045af91d2b6d4fa0ea89fc47cf42711555")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }), reserve0: 92325167783790000000000, reserve1: 244060232130910000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 31437790283620138, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("420aaa13722a191765d86bc55212a54d9f8b5ceb")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cbabee0658725b5b21e1512244734a5d5c6b51d6")} }), reserve0: 92325167783790000000000, reserve1: 244060232130910000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("420aaa13722a191765d86bc55212a54d9f8b5ceb")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }), reserve0: 93890088992620000000000, reserve1: 15686634761155600, fee_bps: 25, estimated_gas_fee_in_dest_token: 2004893376, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d3dfb90f7996a97f9f394e130342485e37dd28f7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff4c1cbcd97597339702436d4f18a375ab")} }), reserve0: 93890088992620000000000, reserve1: 15686634761155600, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("d3dfb90f7996a97f9f394e130342485e37dd28f7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 16508963555390000000000, reserve1: 86478765935600000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("00870b0e6994ffb142a91173a882d2f6a9a8ac4a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("9fda7ceec4c18008096c2fe2b85f05dc300f94d0")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 16508963555390000000000, reserve1: 86478765935600000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 2290823192765684, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("00870b0e6994ffb142a91173a882d2f6a9a8ac4a")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33893760348150000000000, reserve1: 84605150899130000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6775ff3828a19eb5926c0c4d572183ca15aa4c08")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("3405a1bd46b85c5c029483fbecf2f3e611026e45")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33893760348150000000000, reserve1: 84605150899130000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4812120497706025, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6775ff3828a19eb5926c0c4d572183ca15aa4c08")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33454057268, reserve1: 84726835696550000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("555b74dafc4ef3a5a1640041e3244460dc7610d1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 33454057268, reserve1: 84726835696550000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("555b74dafc4ef3a5a1640041e3244460dc7610d1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2041897299, reserve1: 26014487108, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("051fcf8986b30860a1341e0031e5622bd18d8a85")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2041897299, reserve1: 26014487108, fee_bps: 25, estimated_gas_fee_in_dest_token: 370, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("051fcf8986b30860a1341e0031e5622bd18d8a85")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1702141315, reserve1: 55074258645550000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f4c10263f2a4b1f75b8a5fd5328fb61605321639")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("27292cf0016e5df1d8b37306b2a98588acbd6fca")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1702141315, reserve1: 55074258645550000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 370, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f4c10263f2a4b1f75b8a5fd5328fb61605321639")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 19956614944, reserve1: 65747212860000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 15291032574483, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("ac2657ba28768fe5f09052f07a9b7ea867a4608f")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 19956614944, reserve1: 65747212860000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("ac2657ba28768fe5f09052f07a9b7ea867a4608f")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1110807829140000000000, reserve1: 47371089993490000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2f412c140080039eaf9de01c95e84cfb1c8d98ad")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("4792c1ecb969b036eb51330c63bd27899a13d84e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1110807829140000000000, reserve1: 47371089993490000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 279909004386429, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2f412c140080039eaf9de01c95e84cfb1c8d98ad")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 17348091202420000000000, reserve1: 17273856853, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("5ced2f8dd70dc25cba10ad18c7543ad9ad5aeedd")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 17348091202420000000000, reserve1: 17273856853, fee_bps: 25, estimated_gas_fee_in_dest_token: 4625314040322081, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("5ced2f8dd70dc25cba10ad18c7543ad9ad5aeedd")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 43637213857920000000000, reserve1: 51666530202300000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 14208018985550617, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2afab635ea2dc4b498ef1c00e63b7a7dba9c93c6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 43637213857920000000000, reserve1: 51666530202300000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("2afab635ea2dc4b498ef1c00e63b7a7dba9c93c6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 13987530735, reserve1: 9172302880000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 3117897297560, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("0aa48bf937ee8f41f1a52d225ef5a6f6961e39fa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 13987530735, reserve1: 9172302880000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("0aa48bf937ee8f41f1a52d225ef5a6f6961e39fa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 283855018784590000000000, reserve1: 13932285338, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("81e11a9374033d11cc7e7485a7192ae37d0795d6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("0e358838ce72d5e61e0018a2ffac4bec5f4c88d2")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 283855018784590000000000, reserve1: 13932285338, fee_bps: 25, estimated_gas_fee_in_dest_token: 96929899706875098, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("81e11a9374033d11cc7e7485a7192ae37d0795d6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 32464282205010000000000, reserve1: 8435024810000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 3117897297560, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("49a1cc58dcf28d0139daea9c18a3ca23108e78b3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 32464282205010000000000, reserve1: 8435024810000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("49a1cc58dcf28d0139daea9c18a3ca23108e78b3")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 11981127714810000000000, reserve1: 30365026413830000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("367c36dae9ba198a4fee295c22bc98cb72f77fe1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 11981127714810000000000, reserve1: 30365026413830000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4734839702303724, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("367c36dae9ba198a4fee295c22bc98cb72f77fe1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 9323008608, reserve1: 9353562798, fee_bps: 25, estimated_gas_fee_in_dest_token: 4763, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8bc3cceef43392b315ddd92ba30b435f79b66b9e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 9323008608, reserve1: 9353562798, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8bc3cceef43392b315ddd92ba30b435f79b66b9e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 101121342600000000000, reserve1: 21688844105940000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9d19edbfd29d2e01537624b25806493da0d73bbe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("30d2a9f5fdf90ace8c17952cbb4ee48a55d916a7")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 101121342600000000000, reserve1: 21688844105940000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 55948399338540, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("9d19edbfd29d2e01537624b25806493da0d73bbe")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 790858911501880000000000, reserve1: 16696073519070000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6f71389426efe6ff4e357ca6dd012b210a6a3c9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8ece0d14d619fe26e2c14c4a92c2f9e8634a039e")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 790858911501880000000000, reserve1: 16696073519070000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 568415509084535086, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("6f71389426efe6ff4e357ca6dd012b210a6a3c9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 15707116003710000000000, reserve1: 129247366006010000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 98743040590499181, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4efb208eeeb5a8c85af70e8fbc43d6806b422bec")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 15707116003710000000000, reserve1: 129247366006010000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("4efb208eeeb5a8c85af70e8fbc43d6806b422bec")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 5156017466, reserve1: 15552864977390000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 14208018985550617, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("077fc7b4455050249d7f5511ed588a665e03e6c5")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c19281f22a075e0f10351cd5d6ea9f0ac63d4327")} }), reserve0: 5156017466, reserve1: 15552864977390000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4757, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("077fc7b4455050249d7f5511ed588a665e03e6c5")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134083704542, reserve1: 8212381313780000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b6835036c9a2a96d3bfb8dbe1722971feb456a83")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("085416975fe14c2a731a97ec38b9bf8135231f62")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134083704542, reserve1: 8212381313780000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 195924, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("b6835036c9a2a96d3bfb8dbe1722971feb456a83")} }];
 
     let beamswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }) }, derived_eth: DecimalFixedPoint { coef: 2304431, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 91126002361989, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)) }, derived_eth: DecimalFixedPoint { coef: 2532132159, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 100130175779575221, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }) }, derived_eth: DecimalFixedPoint { coef: 1064510475950, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 42094809584570653050, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }) }, derived_eth: DecimalFixedPoint { coef: 234663393, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 9279486738717867, exp: -35 } }];
-    let beamswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, estimated_gas_fee_in_dest_token: 6333561, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 291676768546, reserve1: 1407925598, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("0557949ea1f39e08233246d15af6c24cf8b92b82")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 291676768546, reserve1: 1407925598, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("0557949ea1f39e08233246d15af6c24cf8b92b82")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }), reserve0: 420408864682620000000000, reserve1: 182434977446223460000000000, estimated_gas_fee_in_dest_token: 5207359213619327287, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("99588867e817023162f4d4829995299054a5fc57")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }), reserve0: 420408864682620000000000, reserve1: 182434977446223460000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("99588867e817023162f4d4829995299054a5fc57")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134092681348, reserve1: 339098961829790000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("b929914b89584b4081c7966ac6287636f7efd053")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134092681348, reserve1: 339098961829790000000000, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("b929914b89584b4081c7966ac6287636f7efd053")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), reserve0: 128970525942, reserve1: 129163549103330000000000, estimated_gas_fee_in_dest_token: 4779258747307422, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a0799832fb2b9f18acf44b92fbbedcfd6442dd5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), reserve0: 128970525942, reserve1: 129163549103330000000000, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a0799832fb2b9f18acf44b92fbbedcfd6442dd5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 127293359591, reserve1: 83621954110000000000, estimated_gas_fee_in_dest_token: 3089957074506, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba3071760d46040fb4dc7b627c9f68efaca3000")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 127293359591, reserve1: 83621954110000000000, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba3071760d46040fb4dc7b627c9f68efaca3000")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 251102237365700000000000, reserve1: 2069566982104090000000000, estimated_gas_fee_in_dest_token: 98903156575837222, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a049a6260921b5ee3183cfb943133d36d7fdb668")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 251102237365700000000000, reserve1: 2069566982104090000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a049a6260921b5ee3183cfb943133d36d7fdb668")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 94575620618150000000000, reserve1: 311614958340000000000, estimated_gas_fee_in_dest_token: 15747065737593, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("34a1f4ab3548a92c6b32cd778eed310fcd9a340d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 94575620618150000000000, reserve1: 311614958340000000000, estimated_gas_fee_in_dest_token: 4779258747307422, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("34a1f4ab3548a92c6b32cd778eed310fcd9a340d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)), reserve0: 34270125347210000000000, reserve1: 13534098216, estimated_gas_fee_in_dest_token: 4739, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("9e7f014604574bfe5fefdfe5015563f2a2ba2b22")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)), reserve0: 34270125347210000000000, reserve1: 13534098216, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("9e7f014604574bfe5fefdfe5015563f2a2ba2b22")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 28294894776420000000000, reserve1: 18483174291300, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("d8fbdef502770832e90a6352b275f20f38269b74")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 28294894776420000000000, reserve1: 18483174291300, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("d8fbdef502770832e90a6352b275f20f38269b74")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 10537427783, reserve1: 10573415577, estimated_gas_fee_in_dest_token: 4705, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a35b2c07cb123ea5e1b9c7530d0812e7e03ec3c1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 10537427783, reserve1: 10573415577, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a35b2c07cb123ea5e1b9c7530d0812e7e03ec3c1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 14781386500000000000, reserve1: 15734940783950000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("321e45b7134b5ed52129027f1743c8e71da0a339")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 14781386500000000000, reserve1: 15734940783950000000000, estimated_gas_fee_in_dest_token: 11272787136538, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("321e45b7134b5ed52129027f1743c8e71da0a339")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 64669357288250000000000, reserve1: 15175530814710000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("32b710dbf797c1b16498b0fcd83929bb19897529")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 64669357288250000000000, reserve1: 15175530814710000000000, estimated_gas_fee_in_dest_token: 51137077013115548, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("32b710dbf797c1b16498b0fcd83929bb19897529")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 27155950810, reserve1: 11597399819210000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba38f006afe746b9a0d465e53ab4182147ac3d7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 27155950810, reserve1: 11597399819210000000000, estimated_gas_fee_in_dest_token: 28098, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba38f006afe746b9a0d465e53ab4182147ac3d7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2519811603490000000000, reserve1: 2515239433, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("7ef9491774a81f6db7bb759fe2f645c334dcf5b1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2519811603490000000000, reserve1: 2515239433, estimated_gas_fee_in_dest_token: 4554649945481940, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("7ef9491774a81f6db7bb759fe2f645c334dcf5b1")} }];
+    let beamswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, fee_bps: 30, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, fee_bps: 30, estimated_gas_fee_in_dest_token: 6333561, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 291676768546, reserve1: 1407925598, fee_bps: 30, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("0557949ea1f39e08233246d15af6c24cf8b92b82")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("ffffffff5ac1f9a51a93f5c527385edf7fe98a52")} }), reserve0: 291676768546, reserve1: 1407925598, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("0557949ea1f39e08233246d15af6c24cf8b92b82")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }), reserve0: 420408864682620000000000, reserve1: 182434977446223460000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 5207359213619327287, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("99588867e817023162f4d4829995299054a5fc57")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("cd3b51d98478d53f4515a306be565c6eebef1d58")} }), reserve0: 420408864682620000000000, reserve1: 182434977446223460000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("99588867e817023162f4d4829995299054a5fc57")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134092681348, reserve1: 339098961829790000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("b929914b89584b4081c7966ac6287636f7efd053")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 134092681348, reserve1: 339098961829790000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("b929914b89584b4081c7966ac6287636f7efd053")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), reserve0: 128970525942, reserve1: 129163549103330000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4779258747307422, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a0799832fb2b9f18acf44b92fbbedcfd6442dd5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), reserve0: 128970525942, reserve1: 129163549103330000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a0799832fb2b9f18acf44b92fbbedcfd6442dd5e")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 127293359591, reserve1: 83621954110000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 3089957074506, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba3071760d46040fb4dc7b627c9f68efaca3000")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fa9343c3897324496a05fc75abed6bac29f8a40f")} }), reserve0: 127293359591, reserve1: 83621954110000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba3071760d46040fb4dc7b627c9f68efaca3000")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 251102237365700000000000, reserve1: 2069566982104090000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 98903156575837222, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a049a6260921b5ee3183cfb943133d36d7fdb668")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("fffffffecb45afd30a637967995394cc88c0c194")} }), reserve0: 251102237365700000000000, reserve1: 2069566982104090000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a049a6260921b5ee3183cfb943133d36d7fdb668")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 94575620618150000000000, reserve1: 311614958340000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 15747065737593, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("34a1f4ab3548a92c6b32cd778eed310fcd9a340d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("a649325aa7c5093d12d6f98eb4378deae68ce23f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("c9baa8cfdde8e328787e29b4b078abf2dadc2055")} }), reserve0: 94575620618150000000000, reserve1: 311614958340000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 4779258747307422, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("34a1f4ab3548a92c6b32cd778eed310fcd9a340d")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)), reserve0: 34270125347210000000000, reserve1: 13534098216, fee_bps: 30, estimated_gas_fee_in_dest_token: 4739, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("9e7f014604574bfe5fefdfe5015563f2a2ba2b22")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(311091173110107856861649819128533077277)), reserve0: 34270125347210000000000, reserve1: 13534098216, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("9e7f014604574bfe5fefdfe5015563f2a2ba2b22")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 28294894776420000000000, reserve1: 18483174291300, fee_bps: 30, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("d8fbdef502770832e90a6352b275f20f38269b74")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 28294894776420000000000, reserve1: 18483174291300, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("d8fbdef502770832e90a6352b275f20f38269b74")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 10537427783, reserve1: 10573415577, fee_bps: 30, estimated_gas_fee_in_dest_token: 4705, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a35b2c07cb123ea5e1b9c7530d0812e7e03ec3c1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("efaeee334f0fd1712f9a8cc375f427d9cdd40d73")} }), reserve0: 10537427783, reserve1: 10573415577, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("a35b2c07cb123ea5e1b9c7530d0812e7e03ec3c1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 14781386500000000000, reserve1: 15734940783950000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("321e45b7134b5ed52129027f1743c8e71da0a339")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("595c8481c48894771ce8fade54ac6bf59093f9e8")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 14781386500000000000, reserve1: 15734940783950000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 11272787136538, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("321e45b7134b5ed52129027f1743c8e71da0a339")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 64669357288250000000000, reserve1: 15175530814710000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("32b710dbf797c1b16498b0fcd83929bb19897529")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("65b09ef8c5a096c5fd3a80f1f7369e56eb932412")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 64669357288250000000000, reserve1: 15175530814710000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 51137077013115548, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("32b710dbf797c1b16498b0fcd83929bb19897529")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 27155950810, reserve1: 11597399819210000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba38f006afe746b9a0d465e53ab4182147ac3d7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("8f552a71efe5eefc207bf75485b356a0b3f01ec9")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 27155950810, reserve1: 11597399819210000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 28098, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("6ba38f006afe746b9a0d465e53ab4182147ac3d7")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2519811603490000000000, reserve1: 2515239433, fee_bps: 30, estimated_gas_fee_in_dest_token: 4703, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("7ef9491774a81f6db7bb759fe2f645c334dcf5b1")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("765277eebeca2e31912c9946eae1021199b39c61")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("818ec0a7fe18ff94269904fced6ae3dae6d6dc0b")} }), reserve0: 2519811603490000000000, reserve1: 2515239433, fee_bps: 30, estimated_gas_fee_in_dest_token: 4554649945481940, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("7ef9491774a81f6db7bb759fe2f645c334dcf5b1")} }];
 
     create_graph([(arthswap_tokens, arthswap_edges), (stellaswap_tokens, stellaswap_edges), (beamswap_tokens, beamswap_edges)], true)
 }
@@ -123,13 +123,13 @@ pub fn full_graph() -> Graph {
 // min_token_pair_reserves = $1,000,000
 pub fn medium_graph() -> Graph {
     let arthswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, derived_eth: DecimalFixedPoint { coef: 8141836392, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 36269845667262000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, derived_eth: DecimalFixedPoint { coef: 1000000000, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4454750000000000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, derived_eth: DecimalFixedPoint { coef: 993255845, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 4424706475513750, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, derived_eth: DecimalFixedPoint { coef: 34457466433157, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 153499398593106145750, exp: -35 } }];
-    let arthswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, estimated_gas_fee_in_dest_token: 36846724197844, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, estimated_gas_fee_in_dest_token: 302036984237429, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, estimated_gas_fee_in_dest_token: 8706385902, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }];
+    let arthswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("75364d4f779d0bd0facd9a218c67f87dd9aff3b4")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 5870256605502310000000000, reserve1: 48043117941334040000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 36846724197844, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("ccefddff4808f3e1e0340e19e43f1e9fd088b3f2")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 302036984237429, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e511ed88575c57767bafb72bfd10775413e3f2b0")} }), reserve0: 28341412356711130000000000, reserve1: 28533849044691410000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("b4461721d3ad256cd59d207fefbfe05791ef8568")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 300000000000000, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2006), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("81ecac0d6be0550a00ff064a4f9dd2400585fe9c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("aeaaf0e2c81af264101b9129c00f4440ccf0f720")} }), reserve0: 409628835610000000000, reserve1: 14099949204542500000000000, fee_bps: 30, estimated_gas_fee_in_dest_token: 8706385902, estimated_gas_fee_usd: 13364250000000, dex: &ARTHSWAP, pair_address: EthAddress{0: hex!("87988ebde7e661f44eb3a586c5e0ceab533a2d9c")} }];
 
     let stellaswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, derived_eth: DecimalFixedPoint { coef: 1000000000, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 39278528000000000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, derived_eth: DecimalFixedPoint { coef: 15328371810, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 602075881333495680, exp: -27 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, derived_eth: DecimalFixedPoint { coef: 52534910399448, exp: 1 }, derived_usd: DecimalFixedPoint { coef: 2063493949102209452544, exp: -25 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, derived_eth: DecimalFixedPoint { coef: 2545920245, exp: 3 }, derived_usd: DecimalFixedPoint { coef: 99999999628999360, exp: -23 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, derived_eth: DecimalFixedPoint { coef: 3886571180060, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 152658794919979751680, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, derived_eth: DecimalFixedPoint { coef: 19109649101, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 750598887283803328, exp: -27 } }];
-    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, estimated_gas_fee_in_dest_token: 4713, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, estimated_gas_fee_in_dest_token: 3087554413403, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, estimated_gas_fee_in_dest_token: 6279550, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }];
+    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, fee_bps: 25, estimated_gas_fee_in_dest_token: 22, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("e57ebd2d67b462e9926e04a8e33f01cd0d64346d")} }), reserve0: 3247613076383550000000000, reserve1: 6181819007, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("f8f5e8b9ee84664695b14862212d8092e16331f6")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("931715fee2d06333043d11f658c8ce934ac61d0c")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 1242857822349, reserve1: 3164216892816460000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 4713, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8ccbbcaf58f5422f6efd4034d8e8a3c9120adf79")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("ab3f0245b83feb11d15aaffefd7ad465a59817ed")} }), token1: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), reserve0: 774381426540000000000, reserve1: 3009688534797890000000000, fee_bps: 25, estimated_gas_fee_in_dest_token: 3087554413403, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("8577273fb3b72306f3a59e26ab77116f5d428daa")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, fee_bps: 25, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 1189204710119900, reserve1: 1472615087436500, fee_bps: 25, estimated_gas_fee_in_dest_token: 6279550, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("61bf1b38930e37850d459f3cb926cd197f5f88c0")} }];
 
     let beamswap_tokens: Vec<Token> = vec![];
-    let beamswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, estimated_gas_fee_in_dest_token: 6333561, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }];
+    let beamswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, fee_bps: 30, estimated_gas_fee_in_dest_token: 7838802, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("191cf2602ca2e534c5ccae7bcbf4c46a704bb949")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 674165350378100, reserve1: 834388165006800, fee_bps: 30, estimated_gas_fee_in_dest_token: 6333561, estimated_gas_fee_usd: 4745258280000000, dex: &BEAMSWAP, pair_address: EthAddress{0: hex!("79f05b32e29139c35cd219aedb5d99cedb1915ac")} }];
 
     create_graph([(arthswap_tokens, arthswap_edges), (stellaswap_tokens, stellaswap_edges), (beamswap_tokens, beamswap_edges)], true)
 }
@@ -140,7 +140,7 @@ pub fn small_graph() -> Graph {
     let arthswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![];
 
     let stellaswap_tokens: Vec<Token> = vec![Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, derived_eth: DecimalFixedPoint { coef: 1000000000, exp: -9 }, derived_usd: DecimalFixedPoint { coef: 39278528000000000, exp: -35 } }, Token { id: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, derived_eth: DecimalFixedPoint { coef: 15328371810, exp: -1 }, derived_usd: DecimalFixedPoint { coef: 602075881333495680, exp: -27 } }];
-    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }];
+    let stellaswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 7828620, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }, ConstantProductAMMSwapEdge { src_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)) }, dest_token: UniversalTokenId { chain: SubstrateParachain(Polkadot, 2004), id: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }) }, token0: ERC20(ERC20Token { addr: EthAddress{0: hex!("acc15dc74880c9944775448304b263d191c6077f")} }), token1: XC20(XC20Token::from_asset_id(42259045809535163221576417993425387648)), reserve0: 5815149304241150000000000, reserve1: 3805400660303600, fee_bps: 25, estimated_gas_fee_in_dest_token: 12000000000000000, estimated_gas_fee_usd: 4713423360000000, dex: &STELLASWAP, pair_address: EthAddress{0: hex!("a927e1e1e044ca1d9fe1854585003477331fe2af")} }];
 
     let beamswap_tokens: Vec<Token> = vec![];
     let beamswap_edges: Vec<ConstantProductAMMSwapEdge> = vec![];
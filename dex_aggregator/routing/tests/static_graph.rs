@@ -16,7 +16,7 @@
  * <http://www.mongodb.com/licensing/server-side-public-license>.
  */
 
-use ink_env::debug_println;
+use ink::env::debug_println;
 use privadex_routing::test_utilities::graph_factory;
 
 #[test]
@@ -0,0 +1,70 @@
+#!/bin/sh
+
+# Builds every crate under each feature combination we ship or test with. The no_std rows are checked
+# against wasm32-unknown-unknown (what the Phat contract compiles to), so anything that only builds
+# because libstd snuck in fails here instead of in 'cargo contract build'. These are 'cargo check's,
+# so nothing is linked.
+#
+# Run from the repo root: './utils/feature_matrix.sh' (add '--strict' to also fail on warnings, which
+# is usually how an import that is only used under one cfg shows up)
+
+if [ "$1" = "--strict" ]
+then
+    export RUSTFLAGS="-D warnings"
+fi
+
+WASM="--target wasm32-unknown-unknown"
+FAILED=""
+
+check() {
+    PACKAGE=$1
+    shift
+    echo "==> cargo check -p $PACKAGE $*"
+    if ! cargo check -q -p "$PACKAGE" "$@"
+    then
+        FAILED="$FAILED\n    $PACKAGE $*"
+    fi
+}
+
+# no_std without pink (no HTTP, e.g. routing from a graph snapshot the caller fetched)
+check privadex_common --no-default-features $WASM
+check privadex_chain_metadata --no-default-features $WASM
+check privadex_routing --no-default-features $WASM
+
+# no_std + pink (Phat contract)
+check privadex_common --no-default-features --features pink $WASM
+check privadex_chain_metadata --no-default-features --features pink $WASM
+check privadex_routing --no-default-features --features pink $WASM
+check privadex_execution_plan --no-default-features $WASM
+check privadex_executor --no-default-features $WASM
+check privadex_executor --no-default-features --features mock-txn-send $WASM
+check privadex_executor --no-default-features --features testnet $WASM
+
+# std without pink
+check privadex_common --no-default-features --features std --all-targets
+check privadex_chain_metadata --no-default-features --features std --all-targets
+check privadex_routing --no-default-features --features std --all-targets
+# graphlib's dot output writes through std::io, and only the (std) visualize example uses it
+check privadex_routing --no-default-features --features std,dot --all-targets
+
+# std (defaults), incl. tests and examples
+check privadex_common --all-targets
+check privadex_chain_metadata --all-targets
+check privadex_routing --all-targets
+check privadex_execution_plan --all-targets
+check privadex_executor --all-targets
+
+# mock-txn-send / test-utils / everything else we run tests with
+check privadex_routing --features test-utils,dot,cli --all-targets
+check privadex_execution_plan --features test-utils --all-targets
+check privadex_executor --features mock-txn-send --all-targets
+check privadex_executor --features private-rpc-endpoint --all-targets
+check privadex_executor --features testnet --all-targets
+
+if [ -n "$FAILED" ]
+then
+    printf "FEATURE MATRIX FAILED for:$FAILED\n"
+    exit 1
+else
+    exit 0
+fi